@@ -15,6 +15,7 @@ use tokio::time::timeout;
 use crate::backend::events::{AppServerEvent, EventSink};
 use crate::codex::args::parse_codex_args;
 use crate::shared::process_core::{kill_child_process_tree, tokio_command};
+use crate::shared::process_env_core::{apply_process_env, codex_process_env};
 use crate::types::WorkspaceEntry;
 
 #[cfg(target_os = "windows")]
@@ -691,9 +692,10 @@ pub(crate) fn build_codex_command_with_bin(
         command
     };
 
-    if let Some(path_env) = path_env {
+    if let Some(path_env) = path_env.as_deref() {
         command.env("PATH", path_env);
     }
+    apply_process_env(&mut command, &codex_process_env(), path_env.as_deref())?;
     Ok(command)
 }
 
@@ -755,10 +757,8 @@ pub(crate) async fn spawn_workspace_session<E: EventSink>(
     event_sink: E,
 ) -> Result<Arc<WorkspaceSession>, String> {
     let codex_bin = default_codex_bin;
-    let _ = check_codex_installation(codex_bin.clone()).await?;
-
     let mut command = build_codex_command_with_bin(
-        codex_bin,
+        codex_bin.clone(),
         codex_args.as_deref(),
         vec!["app-server".to_string()],
     )?;
@@ -770,7 +770,15 @@ pub(crate) async fn spawn_workspace_session<E: EventSink>(
     command.stdout(std::process::Stdio::piped());
     command.stderr(std::process::Stdio::piped());
 
-    let mut child = command.spawn().map_err(|e| e.to_string())?;
+    // `codex --version` only runs to explain a failed spawn, so connecting
+    // workspaces at launch does not start the CLI twice each.
+    let mut child = match command.spawn() {
+        Ok(child) => child,
+        Err(err) => {
+            check_codex_installation(codex_bin).await?;
+            return Err(err.to_string());
+        }
+    };
     let stdin = child.stdin.take().ok_or("missing stdin")?;
     let stdout = child.stdout.take().ok_or("missing stdout")?;
     let stderr = child.stderr.take().ok_or("missing stderr")?;
@@ -1,6 +1,8 @@
 use serde::Serialize;
 use serde_json::Value;
 
+use crate::types::{CodexLoginFlow, SessionSummary};
+
 #[derive(Serialize, Clone)]
 pub(crate) struct AppServerEvent {
     pub(crate) workspace_id: String,
@@ -24,8 +26,52 @@ pub(crate) struct TerminalExit {
     pub(crate) terminal_id: String,
 }
 
+#[derive(Debug, Serialize, Clone)]
+pub(crate) struct RemoteCommandOutput {
+    #[serde(rename = "workspaceId")]
+    pub(crate) workspace_id: String,
+    #[serde(rename = "runId")]
+    pub(crate) run_id: String,
+    pub(crate) stream: String,
+    pub(crate) data: String,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub(crate) struct CodexSessionOutput {
+    #[serde(rename = "sessionId")]
+    pub(crate) session_id: String,
+    pub(crate) stream: String,
+    pub(crate) data: String,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub(crate) struct CodexSessionExit {
+    #[serde(rename = "sessionId")]
+    pub(crate) session_id: String,
+    #[serde(rename = "exitCode")]
+    pub(crate) exit_code: Option<i32>,
+    /// Only for sessions the monitor started.
+    pub(crate) summary: Option<SessionSummary>,
+}
+
+/// Queue transitions for sessions held back by `max_concurrent_sessions`.
+/// `position` is 1-based and only set while `status` is `queued`.
+#[derive(Debug, Serialize, Clone)]
+pub(crate) struct CodexSessionQueue {
+    #[serde(rename = "sessionId")]
+    pub(crate) session_id: String,
+    pub(crate) status: String,
+    pub(crate) position: Option<usize>,
+    pub(crate) error: Option<String>,
+}
+
 pub(crate) trait EventSink: Clone + Send + Sync + 'static {
     fn emit_app_server_event(&self, event: AppServerEvent);
     fn emit_terminal_output(&self, event: TerminalOutput);
     fn emit_terminal_exit(&self, event: TerminalExit);
+    fn emit_remote_command_output(&self, event: RemoteCommandOutput);
+    fn emit_codex_session_output(&self, event: CodexSessionOutput);
+    fn emit_codex_session_exit(&self, event: CodexSessionExit);
+    fn emit_codex_session_queue(&self, event: CodexSessionQueue);
+    fn emit_codex_login_progress(&self, event: CodexLoginFlow);
 }
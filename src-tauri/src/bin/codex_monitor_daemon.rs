@@ -1,3 +1,5 @@
+#[path = "codex_monitor_daemon/auth.rs"]
+mod auth;
 #[allow(dead_code)]
 #[path = "../backend/mod.rs"]
 mod backend;
@@ -15,6 +17,10 @@ mod file_ops;
 mod file_policy;
 #[path = "../git_utils.rs"]
 mod git_utils;
+#[path = "codex_monitor_daemon/health.rs"]
+mod health;
+#[path = "codex_monitor_daemon/http_bridge.rs"]
+mod http_bridge;
 #[path = "codex_monitor_daemon/rpc.rs"]
 mod rpc;
 #[path = "../rules.rs"]
@@ -69,26 +75,51 @@ use std::io::Read;
 use std::net::SocketAddr;
 use std::path::PathBuf;
 use std::sync::Arc;
+use std::time::Duration;
 
 use ignore::WalkBuilder;
 use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
 use tokio::net::{TcpListener, TcpStream};
-use tokio::sync::{broadcast, mpsc, Mutex, Semaphore};
+use tokio::sync::{broadcast, mpsc, Mutex, RwLock, Semaphore};
 
 use backend::app_server::{spawn_workspace_session, WorkspaceSession};
-use backend::events::{AppServerEvent, EventSink, TerminalExit, TerminalOutput};
+use backend::events::{
+    AppServerEvent, CodexSessionExit, CodexSessionOutput, CodexSessionQueue, EventSink,
+    RemoteCommandOutput, TerminalExit, TerminalOutput,
+};
+use shared::ci_status_core::CiStatusCache;
 use shared::codex_core::CodexLoginCancelState;
-use shared::process_core::kill_child_process_tree;
+use shared::codex_login_core::CodexCliLoginSlot;
+use shared::codex_session_core::SessionRegistry;
+use shared::data_dir_lock_core::{self, DataDirLockError};
+use shared::monitor_error::MonitorError;
+use shared::process_core::{self, kill_child_process_tree};
 use shared::prompts_core::{self, CustomPromptEntry};
 use shared::{
-    agents_config_core, codex_aux_core, codex_core, files_core, git_core, git_ui_core,
-    local_usage_core, settings_core, workspaces_core, worktree_core,
+    activity_report_core, agents_config_core, ci_status_core, client_trust_core, codex_auth_core,
+    codex_aux_core, codex_core, codex_home_snapshot_core, codex_login_core, codex_session_core,
+    config_changelog_core, daemon_identity_core, editor_core, file_browser_core, files_core,
+    git_core, git_ui_core, guest_tokens_core, host_capabilities_core, local_usage_core,
+    plugins_core, redaction_core, remote_action_confirm_core, remote_command_core, scheduler_core,
+    session_archive_core, session_export_core, session_search_core, session_templates_core,
+    session_worktree_core, settings_core, settings_sync_core, tags_core, workspaces_core,
+    worktree_core,
 };
 use storage::{read_settings, read_workspaces};
 use types::{
-    AppSettings, GitCommitDiff, GitFileDiff, GitHubIssuesResponse, GitHubPullRequestComment,
-    GitHubPullRequestDiff, GitHubPullRequestsResponse, GitLogResponse, LocalUsageSnapshot,
-    WorkspaceEntry, WorkspaceInfo, WorkspaceSettings, WorktreeSetupStatus,
+    ActivityReport, ActivityReportRange, AppSettings, ClientApproval, CodexAuthStatus,
+    CodexHomeInfo, CodexHomeRestoreReport, CodexHomeSnapshotInfo, CodexLoginFlow,
+    CodexSessionDetail, CodexSessionInfo, ConfigChange, DaemonAuthSettings, EditorInfo,
+    GitCommitDiff, GitFileDiff, GitHubIssuesResponse, GitHubPullRequestComment,
+    GitHubPullRequestDiff, GitHubPullRequestsResponse, GitLogResponse, GuestToken,
+    IssuedGuestToken, LocalUsageSnapshot, Page, PageResult, PluginInfo, ProcessStats,
+    ProjectCiStatus, RedactionSettings, RedactionTestResult, RemoteActionConfirmation,
+    RemoteCommandAuditEntry, RemoteCommandResult, RemoteDirListing, RemoteFileChunk, Schedule,
+    ScheduleInput, ScheduleRun, SessionArchiveInfo, SessionArchiveReport, SessionExport,
+    SessionExportFormat, SessionPullRequest, SessionRestoreReport, SessionSearchFilters,
+    SessionSearchHit, SessionTemplate, SessionTemplateInput, SessionWorktreeMergeResult, Tag,
+    TagAssignments, TagInput, TagTarget, WorkspaceEntry, WorkspaceInfo, WorkspaceSettings,
+    WorktreeSetupStatus,
 };
 use workspace_settings::apply_workspace_settings_update;
 
@@ -126,6 +157,20 @@ enum DaemonEvent {
     TerminalOutput(TerminalOutput),
     #[allow(dead_code)]
     TerminalExit(TerminalExit),
+    RemoteCommandOutput(RemoteCommandOutput),
+    CodexSessionOutput(CodexSessionOutput),
+    CodexSessionExit(CodexSessionExit),
+    CodexSessionQueue(CodexSessionQueue),
+    CodexLoginProgress(CodexLoginFlow),
+    /// A client presented a valid token but is waiting for the owner.
+    ClientApprovalRequested(ClientApproval),
+    /// A destructive request is held until the owner confirms it.
+    RemoteActionConfirmationRequested(RemoteActionConfirmation),
+    /// Sent once by `daemon_shutdown` so clients can finish up before the
+    /// process exits `drain_ms` later.
+    ShuttingDown {
+        drain_ms: u64,
+    },
 }
 
 impl EventSink for DaemonEventSink {
@@ -140,24 +185,51 @@ impl EventSink for DaemonEventSink {
     fn emit_terminal_exit(&self, event: TerminalExit) {
         let _ = self.tx.send(DaemonEvent::TerminalExit(event));
     }
+
+    fn emit_remote_command_output(&self, event: RemoteCommandOutput) {
+        let _ = self.tx.send(DaemonEvent::RemoteCommandOutput(event));
+    }
+
+    fn emit_codex_session_output(&self, event: CodexSessionOutput) {
+        let _ = self.tx.send(DaemonEvent::CodexSessionOutput(event));
+    }
+
+    fn emit_codex_session_exit(&self, event: CodexSessionExit) {
+        let _ = self.tx.send(DaemonEvent::CodexSessionExit(event));
+    }
+
+    fn emit_codex_session_queue(&self, event: CodexSessionQueue) {
+        let _ = self.tx.send(DaemonEvent::CodexSessionQueue(event));
+    }
+
+    fn emit_codex_login_progress(&self, event: CodexLoginFlow) {
+        let _ = self.tx.send(DaemonEvent::CodexLoginProgress(event));
+    }
 }
 
 struct DaemonConfig {
     listen: SocketAddr,
     token: Option<String>,
+    /// The daemon's own state: its identity key and client approvals.
     data_dir: PathBuf,
+    /// Workspaces, settings and the other stores shared with the app.
+    app_data_dir: PathBuf,
 }
 
 struct DaemonState {
     data_dir: PathBuf,
+    app_data_dir: PathBuf,
     workspaces: Mutex<HashMap<String, WorkspaceEntry>>,
     sessions: Mutex<HashMap<String, Arc<WorkspaceSession>>>,
+    session_runtimes: Arc<SessionRegistry>,
     storage_path: PathBuf,
     settings_path: PathBuf,
-    app_settings: Mutex<AppSettings>,
+    app_settings: RwLock<AppSettings>,
     event_sink: DaemonEventSink,
     codex_login_cancels: Mutex<HashMap<String, CodexLoginCancelState>>,
+    codex_cli_login: CodexCliLoginSlot,
     daemon_binary_path: Option<String>,
+    ci_status: Mutex<CiStatusCache>,
 }
 
 #[derive(Serialize, Deserialize)]
@@ -168,23 +240,31 @@ struct WorkspaceFileResponse {
 
 impl DaemonState {
     fn load(config: &DaemonConfig, event_sink: DaemonEventSink) -> Self {
-        let storage_path = config.data_dir.join("workspaces.json");
-        let settings_path = config.data_dir.join("settings.json");
+        let storage_path = config.app_data_dir.join("workspaces.json");
+        let settings_path = config.app_data_dir.join("settings.json");
         let workspaces = read_workspaces(&storage_path).unwrap_or_default();
         let app_settings = read_settings(&settings_path).unwrap_or_default();
+        shared::process_env_core::set_codex_process_env(&app_settings.codex_env);
+        codex_home::set_codex_home_override(app_settings.codex_home_override.as_deref());
+        shared::redaction_core::set_redaction(&app_settings.redaction);
+        shared::config_changelog_core::set_config_changelog_dir(&config.app_data_dir);
         let daemon_binary_path = std::env::current_exe()
             .ok()
             .and_then(|path| path.to_str().map(str::to_string));
         Self {
             data_dir: config.data_dir.clone(),
+            app_data_dir: config.app_data_dir.clone(),
             workspaces: Mutex::new(workspaces),
             sessions: Mutex::new(HashMap::new()),
+            session_runtimes: Arc::new(SessionRegistry::default()),
             storage_path,
             settings_path,
-            app_settings: Mutex::new(app_settings),
+            app_settings: RwLock::new(app_settings),
             event_sink,
             codex_login_cancels: Mutex::new(HashMap::new()),
+            codex_cli_login: CodexCliLoginSlot::default(),
             daemon_binary_path,
+            ci_status: Mutex::new(CiStatusCache::default()),
         }
     }
 
@@ -317,7 +397,7 @@ impl DaemonState {
             branch,
             name,
             copy_agents_md,
-            &self.data_dir,
+            &self.app_data_dir,
             &self.workspaces,
             &self.sessions,
             &self.app_settings,
@@ -355,15 +435,19 @@ impl DaemonState {
         &self,
         workspace_id: String,
     ) -> Result<WorktreeSetupStatus, String> {
-        workspaces_core::worktree_setup_status_core(&self.workspaces, &workspace_id, &self.data_dir)
-            .await
+        workspaces_core::worktree_setup_status_core(
+            &self.workspaces,
+            &workspace_id,
+            &self.app_data_dir,
+        )
+        .await
     }
 
     async fn worktree_setup_mark_ran(&self, workspace_id: String) -> Result<(), String> {
         workspaces_core::worktree_setup_mark_ran_core(
             &self.workspaces,
             &workspace_id,
-            &self.data_dir,
+            &self.app_data_dir,
         )
         .await
     }
@@ -416,7 +500,7 @@ impl DaemonState {
         workspaces_core::rename_worktree_core(
             id,
             branch,
-            &self.data_dir,
+            &self.app_data_dir,
             &self.workspaces,
             &self.sessions,
             &self.app_settings,
@@ -583,9 +667,77 @@ impl DaemonState {
         settings_core::get_app_settings_core(&self.app_settings).await
     }
 
-    async fn update_app_settings(&self, settings: AppSettings) -> Result<AppSettings, String> {
+    /// Confirmations, the remote command lists, hooks, plugins, the Codex
+    /// environment and daemon auth guard the host against remote clients,
+    /// so settings written over RPC keep the host's values for them.
+    async fn update_app_settings(&self, mut settings: AppSettings) -> Result<AppSettings, String> {
+        let current = match read_settings(&self.settings_path) {
+            Ok(current) => current,
+            Err(_) => self.app_settings.read().await.clone(),
+        };
+        settings.confirm_remote_actions = current.confirm_remote_actions;
+        settings.remote_action_confirm_timeout_secs = current.remote_action_confirm_timeout_secs;
+        settings.remote_command_allowlist = current.remote_command_allowlist;
+        settings.remote_command_denylist = current.remote_command_denylist;
+        settings.hooks = current.hooks;
+        settings.enabled_plugins = current.enabled_plugins;
+        settings.codex_env = current.codex_env;
+        settings.daemon_auth = current.daemon_auth;
         settings_core::update_app_settings_core(settings, &self.app_settings, &self.settings_path)
             .await
+            .map_err(String::from)
+    }
+
+    async fn set_codex_home_override(&self, path: Option<String>) -> Result<CodexHomeInfo, String> {
+        settings_core::set_codex_home_override_core(path, &self.app_settings, &self.settings_path)
+            .await
+            .map_err(String::from)
+    }
+
+    async fn snapshot_codex_home(
+        &self,
+        label: Option<String>,
+        exclude_secrets: bool,
+    ) -> Result<CodexHomeSnapshotInfo, String> {
+        codex_home_snapshot_core::snapshot_codex_home_core(
+            self.app_data_dir.clone(),
+            label,
+            exclude_secrets,
+        )
+        .await
+    }
+
+    async fn list_codex_home_snapshots(&self) -> Result<Vec<CodexHomeSnapshotInfo>, String> {
+        codex_home_snapshot_core::list_codex_home_snapshots_core(self.app_data_dir.clone()).await
+    }
+
+    async fn restore_codex_home_snapshot(
+        &self,
+        id: String,
+    ) -> Result<CodexHomeRestoreReport, String> {
+        codex_home_snapshot_core::restore_codex_home_snapshot_core(self.app_data_dir.clone(), id)
+            .await
+    }
+
+    async fn list_config_changes(&self, page: Page) -> Result<PageResult<ConfigChange>, String> {
+        let data_dir = self.app_data_dir.clone();
+        tokio::task::spawn_blocking(move || {
+            config_changelog_core::list_config_changes_core(&data_dir, &page)
+        })
+        .await
+        .map_err(|err| err.to_string())?
+    }
+
+    async fn revert_config_change(&self, id: String) -> Result<ConfigChange, String> {
+        config_changelog_core::revert_config_change_core(self.app_data_dir.clone(), id).await
+    }
+
+    async fn test_redaction_rules(
+        &self,
+        sample: String,
+        settings: Option<RedactionSettings>,
+    ) -> Result<RedactionTestResult, String> {
+        redaction_core::test_redaction_rules_core(&sample, settings)
     }
 
     async fn set_codex_feature_flag(
@@ -680,6 +832,414 @@ impl DaemonState {
         files_core::file_write_core(&self.workspaces, scope, kind, workspace_id, content).await
     }
 
+    async fn remote_list_dir(
+        &self,
+        workspace_id: String,
+        rel_path: Option<String>,
+    ) -> Result<RemoteDirListing, String> {
+        file_browser_core::remote_list_dir_core(&self.workspaces, workspace_id, rel_path).await
+    }
+
+    async fn remote_read_file(
+        &self,
+        workspace_id: String,
+        rel_path: String,
+        offset: Option<u64>,
+        length: Option<u64>,
+    ) -> Result<RemoteFileChunk, String> {
+        file_browser_core::remote_read_file_core(
+            &self.workspaces,
+            workspace_id,
+            rel_path,
+            offset,
+            length,
+        )
+        .await
+    }
+
+    async fn search_sessions(
+        &self,
+        query: String,
+        filters: Option<SessionSearchFilters>,
+    ) -> Result<Vec<SessionSearchHit>, String> {
+        session_search_core::search_sessions_core(
+            &self.workspaces,
+            self.app_data_dir.clone(),
+            query,
+            filters,
+        )
+        .await
+    }
+
+    async fn archive_sessions(
+        &self,
+        before: Option<i64>,
+        dry_run: bool,
+    ) -> Result<SessionArchiveReport, String> {
+        session_archive_core::archive_sessions_core(
+            &self.workspaces,
+            &self.app_settings,
+            self.app_data_dir.clone(),
+            before,
+            dry_run,
+        )
+        .await
+    }
+
+    async fn list_archives(&self, page: Page) -> Result<PageResult<SessionArchiveInfo>, String> {
+        session_archive_core::list_archives_core(self.app_data_dir.clone(), page).await
+    }
+
+    async fn restore_archive(&self, name: String) -> Result<SessionRestoreReport, String> {
+        session_archive_core::restore_archive_core(
+            &self.workspaces,
+            self.app_data_dir.clone(),
+            name,
+        )
+        .await
+    }
+
+    async fn start_codex_session(
+        &self,
+        options: codex_session_core::StartSessionOptions,
+    ) -> Result<CodexSessionInfo, String> {
+        codex_session_core::start_codex_session_core(
+            &self.session_runtimes,
+            &self.app_settings,
+            &self.app_data_dir,
+            self.event_sink.clone(),
+            options,
+        )
+        .await
+    }
+
+    async fn merge_session_worktree(
+        &self,
+        session_id: String,
+    ) -> Result<SessionWorktreeMergeResult, String> {
+        session_worktree_core::merge_session_worktree_core(
+            &self.session_runtimes,
+            &self.app_data_dir,
+            session_id,
+        )
+        .await
+    }
+
+    async fn discard_session_worktree(&self, session_id: String) -> Result<(), String> {
+        session_worktree_core::discard_session_worktree_core(
+            &self.session_runtimes,
+            &self.app_data_dir,
+            session_id,
+        )
+        .await
+    }
+
+    async fn create_pr_from_session(
+        &self,
+        session_id: String,
+        title: String,
+        body: Option<String>,
+        base: Option<String>,
+    ) -> Result<SessionPullRequest, String> {
+        session_worktree_core::create_pr_from_session_core(
+            &self.session_runtimes,
+            &self.app_data_dir,
+            session_id,
+            title,
+            body,
+            base,
+        )
+        .await
+    }
+
+    async fn project_ci_status(
+        &self,
+        project_id: String,
+        branch: Option<String>,
+    ) -> Result<ProjectCiStatus, String> {
+        ci_status_core::project_ci_status_core(
+            &self.workspaces,
+            &self.ci_status,
+            project_id,
+            branch,
+        )
+        .await
+    }
+
+    async fn list_editors(&self) -> Vec<EditorInfo> {
+        editor_core::list_editors_core(&*self.app_settings.read().await)
+    }
+
+    async fn open_in_editor(&self, path: String, line: Option<u32>) -> Result<(), String> {
+        let settings = self.app_settings.read().await.clone();
+        editor_core::open_in_editor_core(&settings, path, line).await
+    }
+
+    async fn open_diff_in_editor(&self, session_id: String, file: String) -> Result<(), String> {
+        let settings = self.app_settings.read().await.clone();
+        editor_core::open_diff_in_editor_core(
+            &self.session_runtimes,
+            &self.app_data_dir,
+            &settings,
+            session_id,
+            file,
+        )
+        .await
+    }
+
+    async fn list_plugins(&self) -> Vec<PluginInfo> {
+        let enabled = self.app_settings.read().await.enabled_plugins.clone();
+        plugins_core::list_plugins_core(&self.app_data_dir, &enabled)
+    }
+
+    async fn enable_plugin(&self, id: String, enabled: bool) -> Result<Vec<PluginInfo>, String> {
+        let mut settings = self.app_settings.read().await.clone();
+        settings.enabled_plugins = plugins_core::toggle_plugin(
+            &self.app_data_dir,
+            settings.enabled_plugins,
+            &id,
+            enabled,
+        )?;
+        settings_core::update_app_settings_core(settings, &self.app_settings, &self.settings_path)
+            .await
+            .map_err(String::from)?;
+        Ok(self.list_plugins().await)
+    }
+
+    /// The scope a guest needs for `method`, including `ext.*` methods.
+    async fn required_method_scope(&self, method: &str) -> Option<&'static str> {
+        let enabled = self.app_settings.read().await.enabled_plugins.clone();
+        plugins_core::required_method_scope(&self.app_data_dir, &enabled, method)
+    }
+
+    async fn call_plugin_method(&self, method: &str, params: &Value) -> Result<Value, String> {
+        let enabled = self.app_settings.read().await.enabled_plugins.clone();
+        plugins_core::call_plugin_method_core(&self.app_data_dir, &enabled, method, params).await
+    }
+
+    async fn create_guest_token(
+        &self,
+        scopes: Vec<String>,
+        ttl_seconds: u64,
+        label: Option<String>,
+    ) -> Result<IssuedGuestToken, String> {
+        guest_tokens_core::create_guest_token_core(&self.app_data_dir, scopes, ttl_seconds, label)
+    }
+
+    async fn list_guest_tokens(&self) -> Result<Vec<GuestToken>, String> {
+        guest_tokens_core::list_guest_tokens_core(&self.app_data_dir)
+    }
+
+    async fn revoke_guest_token(&self, id: String) -> Result<(), String> {
+        guest_tokens_core::revoke_guest_token_core(&self.app_data_dir, &id)
+    }
+
+    async fn list_client_approvals(&self) -> Result<Vec<ClientApproval>, String> {
+        client_trust_core::list_client_approvals_core(&self.data_dir)
+    }
+
+    async fn decide_client_approval(
+        &self,
+        client_id: String,
+        approved: bool,
+    ) -> Result<ClientApproval, String> {
+        client_trust_core::decide_client_approval_core(&self.data_dir, &client_id, approved)
+    }
+
+    /// Read from disk, since the app may have changed the setting since the
+    /// daemon loaded it.
+    fn requires_client_approval(&self) -> bool {
+        read_settings(&self.settings_path)
+            .map(|settings| settings.require_client_approval)
+            .unwrap_or(false)
+    }
+
+    /// How long destructive requests wait for the owner, or `None` when they
+    /// run unconfirmed. Read from disk for each request, like
+    /// `requires_client_approval`.
+    fn remote_action_confirm_timeout(&self) -> Option<Duration> {
+        read_settings(&self.settings_path)
+            .ok()
+            .filter(|settings| settings.confirm_remote_actions)
+            .map(|settings| Duration::from_secs(settings.remote_action_confirm_timeout_secs))
+    }
+
+    async fn list_remote_action_confirmations(
+        &self,
+    ) -> Result<Vec<RemoteActionConfirmation>, String> {
+        remote_action_confirm_core::list_remote_action_confirmations_core(&self.data_dir)
+    }
+
+    /// Read from disk for each connection, like `requires_client_approval`.
+    fn daemon_auth_settings(&self) -> DaemonAuthSettings {
+        read_settings(&self.settings_path)
+            .map(|settings| settings.daemon_auth)
+            .unwrap_or_default()
+    }
+
+    /// Read from disk, since the app may have changed settings since the
+    /// daemon loaded them.
+    async fn settings_sync_snapshot(
+        &self,
+    ) -> Result<settings_sync_core::SettingsSyncSnapshot, String> {
+        settings_sync_core::settings_sync_snapshot_core(&self.app_data_dir)
+    }
+
+    async fn list_session_templates(&self) -> Result<Vec<SessionTemplate>, String> {
+        session_templates_core::list_session_templates_core(&self.app_data_dir)
+    }
+
+    async fn save_session_template(
+        &self,
+        template: SessionTemplateInput,
+    ) -> Result<SessionTemplate, String> {
+        session_templates_core::save_session_template_core(&self.app_data_dir, template)
+    }
+
+    async fn delete_session_template(&self, id: String) -> Result<(), String> {
+        session_templates_core::delete_session_template_core(&self.app_data_dir, &id)
+    }
+
+    async fn list_tags(&self) -> Result<Vec<Tag>, String> {
+        tags_core::list_tags_core(&self.app_data_dir)
+    }
+
+    async fn save_tag(&self, tag: TagInput) -> Result<Tag, String> {
+        tags_core::save_tag_core(&self.app_data_dir, tag)
+    }
+
+    async fn delete_tag(&self, id: String) -> Result<(), String> {
+        tags_core::delete_tag_core(&self.app_data_dir, &id)
+    }
+
+    async fn set_tags(
+        &self,
+        target: TagTarget,
+        id: String,
+        tag_ids: Vec<String>,
+    ) -> Result<Vec<String>, String> {
+        tags_core::set_tags_core(&self.app_data_dir, target, &id, tag_ids)
+    }
+
+    async fn get_tag_assignments(&self) -> Result<TagAssignments, String> {
+        tags_core::get_tag_assignments_core(&self.app_data_dir)
+    }
+
+    async fn list_schedules(&self) -> Result<Vec<Schedule>, String> {
+        scheduler_core::list_schedules_core(&self.app_data_dir)
+    }
+
+    async fn upsert_schedule(&self, schedule: ScheduleInput) -> Result<Schedule, String> {
+        scheduler_core::upsert_schedule_core(&self.app_data_dir, schedule)
+    }
+
+    async fn run_schedule_now(&self, id: String) -> Result<ScheduleRun, String> {
+        scheduler_core::run_schedule_now_core(
+            &self.session_runtimes,
+            &self.app_settings,
+            &self.app_data_dir,
+            self.event_sink.clone(),
+            id,
+        )
+        .await
+    }
+
+    async fn run_due_schedules(&self) -> Result<Vec<ScheduleRun>, String> {
+        scheduler_core::run_due_schedules_core(
+            &self.session_runtimes,
+            &self.app_settings,
+            &self.app_data_dir,
+            self.event_sink.clone(),
+        )
+        .await
+    }
+
+    async fn send_session_input(&self, session_id: String, text: String) -> Result<(), String> {
+        codex_session_core::send_session_input_core(&self.session_runtimes, session_id, text).await
+    }
+
+    async fn list_codex_sessions(
+        &self,
+        tags: Vec<String>,
+        page: Page,
+    ) -> Result<PageResult<CodexSessionInfo>, String> {
+        codex_session_core::list_codex_sessions_core(
+            &self.session_runtimes,
+            &self.workspaces,
+            &self.app_data_dir,
+            tags,
+            &page,
+        )
+        .await
+    }
+
+    async fn get_codex_session(&self, session_id: String) -> Result<CodexSessionDetail, String> {
+        codex_session_core::get_codex_session_core(&self.session_runtimes, session_id).await
+    }
+
+    async fn export_session(
+        &self,
+        session_id: String,
+        format: SessionExportFormat,
+        include_tool_output: bool,
+    ) -> Result<SessionExport, String> {
+        session_export_core::export_session_core(
+            &self.workspaces,
+            &self.app_data_dir,
+            session_id,
+            format,
+            include_tool_output,
+        )
+        .await
+    }
+
+    async fn read_session_export(
+        &self,
+        file_name: String,
+        offset: Option<u64>,
+        length: Option<u64>,
+    ) -> Result<RemoteFileChunk, String> {
+        session_export_core::read_session_export_core(
+            &self.app_data_dir,
+            &file_name,
+            offset,
+            length,
+        )
+    }
+
+    async fn get_process_stats(&self, pid: Option<u32>) -> Result<ProcessStats, String> {
+        process_core::sample_process_stats(pid.unwrap_or_else(std::process::id)).await
+    }
+
+    async fn cancel_queued_session(&self, session_id: String) -> Result<(), String> {
+        codex_session_core::cancel_queued_session_core(
+            &self.session_runtimes,
+            self.event_sink.clone(),
+            session_id,
+        )
+        .await
+    }
+
+    async fn terminate_session(
+        &self,
+        session_id: String,
+        pid: Option<u32>,
+        force: bool,
+    ) -> Result<(), String> {
+        codex_session_core::terminate_session_core(
+            &self.session_runtimes,
+            self.event_sink.clone(),
+            session_id,
+            pid,
+            force,
+        )
+        .await
+    }
+
+    async fn interrupt_session(&self, session_id: String) -> Result<(), String> {
+        codex_session_core::interrupt_session_core(&self.session_runtimes, session_id).await
+    }
+
     async fn start_thread(&self, workspace_id: String) -> Result<Value, String> {
         codex_core::start_thread_core(&self.sessions, &self.workspaces, workspace_id).await
     }
@@ -692,11 +1252,7 @@ impl DaemonState {
         codex_core::resume_thread_core(&self.sessions, workspace_id, thread_id).await
     }
 
-    async fn read_thread(
-        &self,
-        workspace_id: String,
-        thread_id: String,
-    ) -> Result<Value, String> {
+    async fn read_thread(&self, workspace_id: String, thread_id: String) -> Result<Value, String> {
         codex_core::read_thread_core(&self.sessions, workspace_id, thread_id).await
     }
 
@@ -765,8 +1321,7 @@ impl DaemonState {
         limit: Option<u32>,
         sort_key: Option<String>,
     ) -> Result<Value, String> {
-        codex_core::list_threads_core(&self.sessions, workspace_id, cursor, limit, sort_key)
-            .await
+        codex_core::list_threads_core(&self.sessions, workspace_id, cursor, limit, sort_key).await
     }
 
     async fn list_mcp_server_status(
@@ -1258,6 +1813,27 @@ impl DaemonState {
         .await
     }
 
+    async fn codex_auth_status(&self) -> Result<CodexAuthStatus, String> {
+        codex_auth_core::codex_auth_status_core(&self.app_settings).await
+    }
+
+    async fn codex_login_start(&self) -> Result<CodexLoginFlow, String> {
+        codex_login_core::codex_login_start_core(
+            &self.codex_cli_login,
+            &self.app_settings,
+            self.event_sink.clone(),
+        )
+        .await
+    }
+
+    async fn codex_login_flow_status(&self) -> Option<CodexLoginFlow> {
+        codex_login_core::codex_login_flow_status_core(&self.codex_cli_login).await
+    }
+
+    async fn codex_login_flow_cancel(&self) -> Result<CodexLoginFlow, String> {
+        codex_login_core::codex_login_flow_cancel_core(&self.codex_cli_login).await
+    }
+
     async fn codex_doctor(
         &self,
         codex_bin: Option<String>,
@@ -1278,7 +1854,7 @@ impl DaemonState {
         .await?;
         let diff = git_ui_core::collect_workspace_diff_core(&repo_root)?;
         let commit_message_prompt = {
-            let settings = self.app_settings.lock().await;
+            let settings = self.app_settings.read().await;
             settings.commit_message_prompt.clone()
         };
         codex_aux_core::generate_commit_message_core(
@@ -1337,6 +1913,43 @@ impl DaemonState {
         local_usage_core::local_usage_snapshot_core(&self.workspaces, days, workspace_path).await
     }
 
+    async fn activity_report(&self, range: ActivityReportRange) -> Result<ActivityReport, String> {
+        activity_report_core::activity_report_core(&self.workspaces, &self.app_data_dir, range)
+            .await
+    }
+
+    async fn run_remote_command(
+        &self,
+        workspace_id: String,
+        command: String,
+        cwd: Option<String>,
+        run_id: Option<String>,
+    ) -> Result<RemoteCommandResult, String> {
+        remote_command_core::run_remote_command_core(
+            &self.workspaces,
+            &self.app_settings,
+            &self.app_data_dir,
+            self.event_sink.clone(),
+            workspace_id,
+            command,
+            cwd,
+            run_id,
+        )
+        .await
+    }
+
+    async fn list_remote_command_audit(
+        &self,
+        page: Page,
+    ) -> Result<PageResult<RemoteCommandAuditEntry>, String> {
+        let data_dir = self.app_data_dir.clone();
+        tokio::task::spawn_blocking(move || {
+            remote_command_core::list_remote_command_audit_core(&data_dir, &page)
+        })
+        .await
+        .map_err(|err| err.to_string())?
+    }
+
     async fn menu_set_accelerators(&self, _updates: Vec<Value>) -> Result<(), String> {
         // Daemon has no native menu runtime; treat as no-op for remote parity.
         Ok(())
@@ -1499,12 +2112,18 @@ fn default_data_dir() -> PathBuf {
 fn usage() -> String {
     format!(
         "\
-USAGE:\n  codex-monitor-daemon [--listen <addr>] [--data-dir <path>] [--token <token> | --insecure-no-auth]\n\n\
-OPTIONS:\n  --listen <addr>          Bind address (default: {DEFAULT_LISTEN_ADDR})\n  --data-dir <path>        Data dir holding workspaces.json/settings.json\n  --token <token>          Shared token required by TCP clients\n  --insecure-no-auth       Disable TCP auth (dev only)\n  -h, --help               Show this help\n"
+USAGE:\n  codex-monitor-daemon [--listen <addr>] [--data-dir <path>] [--app-data-dir <path>] [--token <token> | --insecure-no-auth]\n  codex-monitor-daemon --healthcheck [--listen <addr>] [--data-dir <path>]\n\n\
+OPTIONS:\n  --listen <addr>          Bind address (default: {DEFAULT_LISTEN_ADDR})\n  --data-dir <path>        Data dir for the daemon's identity and client approvals\n  --app-data-dir <path>    Data dir holding workspaces.json/settings.json (default: --data-dir)\n  --token <token>          Shared token required by TCP clients\n  --insecure-no-auth       Disable TCP auth (dev only)\n  --healthcheck            Print health as JSON and exit non-zero on failure\n  -h, --help               Show this help\n"
     )
 }
 
-fn parse_args() -> Result<DaemonConfig, String> {
+#[derive(Debug, PartialEq, Eq)]
+enum DaemonMode {
+    Serve,
+    Healthcheck,
+}
+
+fn parse_args() -> Result<(DaemonConfig, DaemonMode), String> {
     let mut listen = DEFAULT_LISTEN_ADDR
         .parse::<SocketAddr>()
         .map_err(|err| err.to_string())?;
@@ -1514,6 +2133,8 @@ fn parse_args() -> Result<DaemonConfig, String> {
         .filter(|value| !value.is_empty());
     let mut insecure_no_auth = false;
     let mut data_dir: Option<PathBuf> = None;
+    let mut app_data_dir: Option<PathBuf> = None;
+    let mut mode = DaemonMode::Serve;
 
     let mut args = env::args().skip(1);
     while let Some(arg) = args.next() {
@@ -1542,34 +2163,46 @@ fn parse_args() -> Result<DaemonConfig, String> {
                 }
                 data_dir = Some(PathBuf::from(trimmed));
             }
+            "--app-data-dir" => {
+                let value = args.next().ok_or("--app-data-dir requires a value")?;
+                let trimmed = value.trim();
+                if trimmed.is_empty() {
+                    return Err("--app-data-dir requires a non-empty value".to_string());
+                }
+                app_data_dir = Some(PathBuf::from(trimmed));
+            }
             "--insecure-no-auth" => {
                 insecure_no_auth = true;
                 token = None;
             }
+            "--healthcheck" => mode = DaemonMode::Healthcheck,
             _ => return Err(format!("Unknown argument: {arg}")),
         }
     }
 
-    if token.is_none() && !insecure_no_auth {
+    if token.is_none() && !insecure_no_auth && mode == DaemonMode::Serve {
         return Err(
             "Missing --token (or set CODEX_MONITOR_DAEMON_TOKEN). Use --insecure-no-auth for local dev only."
                 .to_string(),
         );
     }
 
-    Ok(DaemonConfig {
+    let data_dir = data_dir.unwrap_or_else(default_data_dir);
+    let config = DaemonConfig {
         listen,
         token,
-        data_dir: data_dir.unwrap_or_else(default_data_dir),
-    })
+        app_data_dir: app_data_dir.unwrap_or_else(|| data_dir.clone()),
+        data_dir,
+    };
+    Ok((config, mode))
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::shared::process_core::kill_child_process_tree;
-    use crate::storage::write_workspaces;
-    use crate::types::WorkspaceKind;
+    use crate::storage::{write_settings, write_workspaces};
+    use crate::types::{RpcTimeouts, WorkspaceKind};
     use serde_json::json;
     use std::future::Future;
     use std::path::PathBuf;
@@ -1607,14 +2240,18 @@ mod tests {
         let (tx, _rx) = broadcast::channel::<DaemonEvent>(32);
         DaemonState {
             data_dir: data_dir.to_path_buf(),
+            app_data_dir: data_dir.to_path_buf(),
             workspaces: Mutex::new(HashMap::new()),
             sessions: Mutex::new(HashMap::new()),
+            session_runtimes: Arc::new(SessionRegistry::default()),
             storage_path: data_dir.join("workspaces.json"),
             settings_path: data_dir.join("settings.json"),
-            app_settings: Mutex::new(AppSettings::default()),
+            app_settings: RwLock::new(AppSettings::default()),
             event_sink: DaemonEventSink { tx },
             codex_login_cancels: Mutex::new(HashMap::new()),
+            codex_cli_login: CodexCliLoginSlot::default(),
             daemon_binary_path: Some("/tmp/codex-monitor-daemon".to_string()),
+            ci_status: Mutex::new(CiStatusCache::default()),
         }
     }
 
@@ -1709,7 +2346,544 @@ mod tests {
     }
 
     #[test]
-    fn rpc_prompts_list_reads_workspace_prompts() {
+    fn rpc_settings_update_keeps_the_host_allowlist() {
+        run_async_test(async {
+            let tmp = make_temp_dir("rpc-settings-host-only");
+            let state = test_state(&tmp);
+            let mut host = AppSettings::default();
+            host.remote_command_allowlist = vec!["cargo test".to_string()];
+            write_settings(&state.settings_path, &host).expect("write settings");
+
+            let mut remote = host.clone();
+            remote.remote_command_allowlist = vec!["sh *".to_string()];
+            remote.theme = "dark".to_string();
+            rpc::handle_rpc_request(
+                &state,
+                "update_app_settings",
+                json!({ "settings": remote }),
+                "daemon-test".to_string(),
+            )
+            .await
+            .expect("update settings");
+
+            let saved = read_settings(&state.settings_path).expect("read settings");
+            assert_eq!(saved.remote_command_allowlist, ["cargo test"]);
+            assert_eq!(saved.theme, "dark");
+            let _ = std::fs::remove_dir_all(&tmp);
+        });
+    }
+
+    #[test]
+    fn rpc_ping_echoes_payload() {
+        run_async_test(async {
+            let tmp = make_temp_dir("rpc-ping");
+            let state = test_state(&tmp);
+
+            let pong = rpc::handle_rpc_request(&state, "ping", json!({}), "test".to_string())
+                .await
+                .expect("ping");
+            assert_eq!(pong, json!({ "ok": true }));
+            let pong = rpc::handle_rpc_request(
+                &state,
+                "ping",
+                json!({ "payload": "xxxx" }),
+                "test".to_string(),
+            )
+            .await
+            .expect("ping");
+            assert_eq!(pong, json!({ "ok": true, "payload": "xxxx" }));
+            let _ = std::fs::remove_dir_all(&tmp);
+        });
+    }
+
+    #[test]
+    fn rpc_health_reports_each_check() {
+        run_async_test(async {
+            let tmp = make_temp_dir("rpc-health");
+            let state = test_state(&tmp);
+
+            let report = rpc::handle_rpc_request(&state, "health", json!({}), "test".to_string())
+                .await
+                .expect("health");
+            let names: Vec<&str> = report["checks"]
+                .as_array()
+                .expect("checks")
+                .iter()
+                .filter_map(|check| check["name"].as_str())
+                .collect();
+            assert_eq!(names, ["daemon", "runner", "dataDir", "disk", "settings"]);
+            assert_ne!(report["status"], json!("fail"));
+
+            std::fs::write(tmp.join("settings.json"), "{ not json").expect("write");
+            let report = rpc::handle_rpc_request(&state, "health", json!({}), "test".to_string())
+                .await
+                .expect("health");
+            assert_eq!(report["status"], json!("fail"));
+            let _ = std::fs::remove_dir_all(&tmp);
+        });
+    }
+
+    #[test]
+    fn healthcheck_warns_when_daemon_is_not_listening() {
+        run_async_test(async {
+            let tmp = make_temp_dir("healthcheck");
+            let listener = TcpListener::bind("127.0.0.1:0").await.expect("bind");
+            let addr = listener.local_addr().expect("local addr");
+            drop(listener);
+            let config = DaemonConfig {
+                listen: addr,
+                token: None,
+                data_dir: tmp.clone(),
+                app_data_dir: tmp.clone(),
+            };
+
+            let report = health::run_healthcheck(&config).await;
+            let report = serde_json::to_value(&report).expect("json");
+            assert_eq!(report["checks"][0]["name"], json!("daemon"));
+            assert_eq!(report["checks"][0]["status"], json!("warn"));
+            let _ = std::fs::remove_dir_all(&tmp);
+        });
+    }
+
+    #[test]
+    fn rpc_start_codex_session_streams_output_until_exit() {
+        run_async_test(async {
+            let tmp = make_temp_dir("rpc-start-session");
+            let state = test_state(&tmp);
+
+            let err = rpc::handle_rpc_request(
+                &state,
+                "start_codex_session",
+                json!({
+                    "projectPath": tmp.to_string_lossy(),
+                    "prompt": "hello",
+                    "approvalMode": "sometimes",
+                }),
+                "daemon-test".to_string(),
+            )
+            .await
+            .expect_err("unknown approval mode should reject");
+            assert!(err.contains("approval mode"));
+
+            if cfg!(unix) {
+                let script = tmp.join("fake-codex.sh");
+                std::fs::write(&script, "#!/bin/sh\necho \"args:$*\"\n").expect("write script");
+                #[cfg(unix)]
+                {
+                    use std::os::unix::fs::PermissionsExt;
+                    std::fs::set_permissions(&script, std::fs::Permissions::from_mode(0o755))
+                        .expect("chmod script");
+                }
+                state.app_settings.write().await.codex_bin =
+                    Some(script.to_string_lossy().to_string());
+                let mut events = state.event_sink.tx.subscribe();
+
+                let result = rpc::handle_rpc_request(
+                    &state,
+                    "start_codex_session",
+                    json!({ "projectPath": tmp.to_string_lossy(), "prompt": "fix tests" }),
+                    "daemon-test".to_string(),
+                )
+                .await
+                .expect("session should start");
+                let session_id = result
+                    .get("sessionId")
+                    .and_then(Value::as_str)
+                    .expect("session id")
+                    .to_string();
+                assert_eq!(
+                    result.get("status").and_then(Value::as_str),
+                    Some("running")
+                );
+
+                let mut stdout = String::new();
+                loop {
+                    let event = tokio::time::timeout(Duration::from_secs(5), events.recv())
+                        .await
+                        .expect("event before timeout")
+                        .expect("event");
+                    match event {
+                        DaemonEvent::CodexSessionOutput(output) => {
+                            assert_eq!(output.session_id, session_id);
+                            stdout.push_str(&output.data);
+                        }
+                        DaemonEvent::CodexSessionExit(exit) => {
+                            assert_eq!(exit.exit_code, Some(0));
+                            break;
+                        }
+                        _ => {}
+                    }
+                }
+                assert!(stdout.contains("exec --json --cd"));
+                assert!(stdout.trim_end().ends_with("fix tests"));
+            }
+            let _ = std::fs::remove_dir_all(&tmp);
+        });
+    }
+
+    #[test]
+    fn rpc_send_session_input_reaches_child_stdin() {
+        if !cfg!(unix) {
+            return;
+        }
+        run_async_test(async {
+            let tmp = make_temp_dir("rpc-session-input");
+            let state = test_state(&tmp);
+            let script = tmp.join("fake-codex.sh");
+            std::fs::write(&script, "#!/bin/sh\nread line\necho \"got:$line\"\n")
+                .expect("write script");
+            #[cfg(unix)]
+            {
+                use std::os::unix::fs::PermissionsExt;
+                std::fs::set_permissions(&script, std::fs::Permissions::from_mode(0o755))
+                    .expect("chmod script");
+            }
+            state.app_settings.write().await.codex_bin = Some(script.to_string_lossy().to_string());
+            let mut events = state.event_sink.tx.subscribe();
+
+            let missing = rpc::handle_rpc_request(
+                &state,
+                "send_session_input",
+                json!({ "sessionId": "missing", "text": "hi" }),
+                "daemon-test".to_string(),
+            )
+            .await
+            .expect_err("unknown session should reject");
+            assert!(missing.contains("not found"));
+
+            let started = rpc::handle_rpc_request(
+                &state,
+                "start_codex_session",
+                json!({ "projectPath": tmp.to_string_lossy(), "prompt": "go" }),
+                "daemon-test".to_string(),
+            )
+            .await
+            .expect("session should start");
+            let session_id = started
+                .get("sessionId")
+                .and_then(Value::as_str)
+                .expect("session id")
+                .to_string();
+
+            let detail = rpc::handle_rpc_request(
+                &state,
+                "get_codex_session",
+                json!({ "sessionId": session_id }),
+                "daemon-test".to_string(),
+            )
+            .await
+            .expect("session detail");
+            assert_eq!(
+                detail["session"]["sessionId"].as_str(),
+                Some(session_id.as_str())
+            );
+            assert!(detail["resourceStats"].is_array());
+
+            rpc::handle_rpc_request(
+                &state,
+                "send_session_input",
+                json!({ "sessionId": session_id, "text": "keep going" }),
+                "daemon-test".to_string(),
+            )
+            .await
+            .expect("input should be written");
+
+            let mut stdout = String::new();
+            loop {
+                let event = tokio::time::timeout(Duration::from_secs(5), events.recv())
+                    .await
+                    .expect("event before timeout")
+                    .expect("event");
+                match event {
+                    DaemonEvent::CodexSessionOutput(output) => stdout.push_str(&output.data),
+                    DaemonEvent::CodexSessionExit(_) => break,
+                    _ => {}
+                }
+            }
+            assert_eq!(stdout.trim_end(), "got:keep going");
+
+            let exited = rpc::handle_rpc_request(
+                &state,
+                "interrupt_session",
+                json!({ "sessionId": session_id }),
+                "daemon-test".to_string(),
+            )
+            .await
+            .expect_err("exited session cannot be interrupted");
+            assert!(exited.contains("exited"));
+            let _ = std::fs::remove_dir_all(&tmp);
+        });
+    }
+
+    #[test]
+    fn rpc_start_codex_session_queues_beyond_concurrency_limit() {
+        if !cfg!(unix) {
+            return;
+        }
+        run_async_test(async {
+            let tmp = make_temp_dir("rpc-session-queue");
+            let state = test_state(&tmp);
+            let script = tmp.join("fake-codex.sh");
+            std::fs::write(&script, "#!/bin/sh\nread line\n").expect("write script");
+            #[cfg(unix)]
+            {
+                use std::os::unix::fs::PermissionsExt;
+                std::fs::set_permissions(&script, std::fs::Permissions::from_mode(0o755))
+                    .expect("chmod script");
+            }
+            {
+                let mut settings = state.app_settings.write().await;
+                settings.codex_bin = Some(script.to_string_lossy().to_string());
+                settings.max_concurrent_sessions = Some(1);
+            }
+            let mut events = state.event_sink.tx.subscribe();
+            let start = |prompt: &'static str| {
+                rpc::handle_rpc_request(
+                    &state,
+                    "start_codex_session",
+                    json!({ "projectPath": tmp.to_string_lossy(), "prompt": prompt }),
+                    "daemon-test".to_string(),
+                )
+            };
+            let session_field = |value: &Value, key: &str| {
+                value
+                    .get(key)
+                    .and_then(Value::as_str)
+                    .expect("session field")
+                    .to_string()
+            };
+
+            let first = start("first").await.expect("first session");
+            assert_eq!(session_field(&first, "status"), "running");
+            let second = start("second").await.expect("second session");
+            assert_eq!(session_field(&second, "status"), "queued");
+            let third = start("third").await.expect("third session");
+            let third_id = session_field(&third, "sessionId");
+
+            rpc::handle_rpc_request(
+                &state,
+                "cancel_queued_session",
+                json!({ "sessionId": third_id }),
+                "daemon-test".to_string(),
+            )
+            .await
+            .expect("queued session should cancel");
+            rpc::handle_rpc_request(
+                &state,
+                "send_session_input",
+                json!({ "sessionId": session_field(&first, "sessionId"), "text": "done" }),
+                "daemon-test".to_string(),
+            )
+            .await
+            .expect("input should be written");
+
+            let first_id = session_field(&first, "sessionId");
+            let second_id = session_field(&second, "sessionId");
+            let mut first_started = false;
+            let mut cancelled = false;
+            loop {
+                let event = tokio::time::timeout(Duration::from_secs(5), events.recv())
+                    .await
+                    .expect("event before timeout")
+                    .expect("event");
+                if let DaemonEvent::CodexSessionQueue(queue) = event {
+                    if queue.status == "cancelled" {
+                        assert_eq!(queue.session_id, third_id);
+                        cancelled = true;
+                    }
+                    if queue.status == "started" && queue.session_id == first_id {
+                        first_started = true;
+                    } else if queue.status == "started" {
+                        assert_eq!(queue.session_id, second_id);
+                        break;
+                    }
+                }
+            }
+            assert!(first_started);
+            assert!(cancelled);
+            let running = state.session_runtimes.running.lock().await.clone();
+            let runtime = running.get(&second_id).expect("second session running");
+            assert!(runtime.info.lock().await.pid.is_some());
+            codex_session_core::interrupt_session_core(&state.session_runtimes, second_id)
+                .await
+                .expect("interrupt queued session once started");
+            let _ = std::fs::remove_dir_all(&tmp);
+        });
+    }
+
+    #[test]
+    fn rpc_terminate_session_stops_monitor_and_external_processes() {
+        if !cfg!(unix) {
+            return;
+        }
+        run_async_test(async {
+            let tmp = make_temp_dir("rpc-terminate-session");
+            let state = test_state(&tmp);
+            let script = tmp.join("fake-codex.sh");
+            std::fs::write(&script, "#!/bin/sh\nexec sleep 30\n").expect("write script");
+            #[cfg(unix)]
+            {
+                use std::os::unix::fs::PermissionsExt;
+                std::fs::set_permissions(&script, std::fs::Permissions::from_mode(0o755))
+                    .expect("chmod script");
+            }
+            state.app_settings.write().await.codex_bin = Some(script.to_string_lossy().to_string());
+            let mut events = state.event_sink.tx.subscribe();
+
+            let started = rpc::handle_rpc_request(
+                &state,
+                "start_codex_session",
+                json!({ "projectPath": tmp.to_string_lossy(), "prompt": "go" }),
+                "daemon-test".to_string(),
+            )
+            .await
+            .expect("session should start");
+            let session_id = started["sessionId"]
+                .as_str()
+                .expect("session id")
+                .to_string();
+
+            rpc::handle_rpc_request(
+                &state,
+                "terminate_session",
+                json!({ "sessionId": session_id, "force": true }),
+                "daemon-test".to_string(),
+            )
+            .await
+            .expect("session should terminate");
+            let detail = codex_session_core::get_codex_session_core(
+                &state.session_runtimes,
+                session_id.clone(),
+            )
+            .await
+            .expect("session detail");
+            assert_eq!(detail.session.status, "terminated");
+            loop {
+                let event = tokio::time::timeout(Duration::from_secs(5), events.recv())
+                    .await
+                    .expect("event before timeout")
+                    .expect("event");
+                if let DaemonEvent::CodexSessionExit(exit) = event {
+                    assert_eq!(exit.session_id, session_id);
+                    break;
+                }
+            }
+
+            let missing = rpc::handle_rpc_request(
+                &state,
+                "terminate_session",
+                json!({ "sessionId": "external" }),
+                "daemon-test".to_string(),
+            )
+            .await
+            .expect_err("unknown session without pid should reject");
+            assert!(missing.contains("not found"));
+
+            let mut external = std::process::Command::new("sleep")
+                .arg("30")
+                .spawn()
+                .expect("spawn external process");
+            let pid = external.id();
+            let reaper = std::thread::spawn(move || external.wait());
+            rpc::handle_rpc_request(
+                &state,
+                "terminate_session",
+                json!({ "sessionId": "external", "pid": pid }),
+                "daemon-test".to_string(),
+            )
+            .await
+            .expect("external process should stop");
+            assert!(!reaper
+                .join()
+                .expect("reaper thread")
+                .expect("wait external")
+                .success());
+            let _ = std::fs::remove_dir_all(&tmp);
+        });
+    }
+
+    #[test]
+    fn rpc_run_schedule_now_skips_dirty_project() {
+        run_async_test(async {
+            let tmp = make_temp_dir("rpc-schedule-dirty");
+            let state = test_state(&tmp);
+            let repo = tmp.join("repo");
+            std::fs::create_dir_all(&repo).expect("create repo dir");
+            let init = std::process::Command::new("git")
+                .arg("init")
+                .arg("-q")
+                .current_dir(&repo)
+                .status();
+            if !init.map(|status| status.success()).unwrap_or(false) {
+                let _ = std::fs::remove_dir_all(&tmp);
+                return;
+            }
+            std::fs::write(repo.join("notes.txt"), "wip").expect("write untracked file");
+
+            let template = rpc::handle_rpc_request(
+                &state,
+                "save_session_template",
+                json!({ "template": {
+                    "name": "Nightly deps",
+                    "prompt": "Update dependencies",
+                    "workingDir": repo.to_string_lossy(),
+                } }),
+                "daemon-test".to_string(),
+            )
+            .await
+            .expect("template should save");
+            let schedule = rpc::handle_rpc_request(
+                &state,
+                "upsert_schedule",
+                json!({ "schedule": {
+                    "name": "Nightly",
+                    "cron": "0 3 * * *",
+                    "templateId": template.get("id").and_then(Value::as_str).expect("template id"),
+                } }),
+                "daemon-test".to_string(),
+            )
+            .await
+            .expect("schedule should save");
+            assert!(schedule
+                .get("nextRunAtMs")
+                .and_then(Value::as_i64)
+                .is_some());
+            let schedule_id = schedule
+                .get("id")
+                .and_then(Value::as_str)
+                .expect("schedule id")
+                .to_string();
+
+            let run = rpc::handle_rpc_request(
+                &state,
+                "run_schedule_now",
+                json!({ "id": schedule_id }),
+                "daemon-test".to_string(),
+            )
+            .await
+            .expect("run should be recorded");
+            assert_eq!(run.get("status").and_then(Value::as_str), Some("skipped"));
+            assert!(state.session_runtimes.running.lock().await.is_empty());
+
+            let listed = rpc::handle_rpc_request(
+                &state,
+                "list_schedules",
+                json!({}),
+                "daemon-test".to_string(),
+            )
+            .await
+            .expect("list schedules");
+            let runs = listed[0]
+                .get("runs")
+                .and_then(Value::as_array)
+                .expect("runs");
+            assert_eq!(runs.len(), 1);
+            let _ = std::fs::remove_dir_all(&tmp);
+        });
+    }
+
+    #[test]
+    fn rpc_prompts_list_reads_workspace_prompts() {
         run_async_test(async {
             let tmp = make_temp_dir("rpc-prompts-list");
             let workspace_id = "ws-prompts";
@@ -1794,6 +2968,100 @@ mod tests {
             let _ = std::fs::remove_dir_all(&tmp);
         });
     }
+
+    #[test]
+    fn rpc_timeouts_advertises_classes() {
+        run_async_test(async {
+            let tmp = make_temp_dir("rpc-timeouts");
+            let state = test_state(&tmp);
+
+            let result = rpc::handle_rpc_request(
+                &state,
+                "rpc_timeouts",
+                json!({}),
+                "daemon-test".to_string(),
+            )
+            .await
+            .expect("rpc_timeouts should succeed");
+
+            let timeouts: RpcTimeouts = serde_json::from_value(result).expect("timeouts");
+            assert!(timeouts.fast_ms < timeouts.normal_ms);
+            assert!(timeouts.normal_ms < timeouts.long_ms);
+            assert!(timeouts.fast_methods.iter().any(|method| method == "ping"));
+            assert!(timeouts
+                .long_methods
+                .iter()
+                .any(|method| method == "get_git_diffs"));
+            let _ = std::fs::remove_dir_all(&tmp);
+        });
+    }
+
+    #[test]
+    fn rpc_run_remote_command_enforces_allowlist_and_audits() {
+        run_async_test(async {
+            let tmp = make_temp_dir("rpc-remote-command");
+            let workspace_dir = tmp.join("workspace");
+            std::fs::create_dir_all(&workspace_dir).expect("create workspace dir");
+            let state = test_state(&tmp);
+            insert_workspace(&state, "ws-cmd", &workspace_dir.to_string_lossy()).await;
+
+            let err = rpc::handle_rpc_request(
+                &state,
+                "run_remote_command",
+                json!({ "workspaceId": "ws-cmd", "command": "git status" }),
+                "daemon-test".to_string(),
+            )
+            .await
+            .expect_err("empty allowlist should reject");
+            assert!(err.contains("disabled"));
+
+            if cfg!(unix) {
+                state.app_settings.write().await.remote_command_allowlist =
+                    vec!["echo".to_string()];
+                let result = rpc::handle_rpc_request(
+                    &state,
+                    "run_remote_command",
+                    json!({ "workspaceId": "ws-cmd", "command": "echo hello", "runId": "run-1" }),
+                    "daemon-test".to_string(),
+                )
+                .await
+                .expect("allowlisted command should run");
+                assert_eq!(result.get("runId").and_then(Value::as_str), Some("run-1"));
+                assert_eq!(result.get("exitCode").and_then(Value::as_i64), Some(0));
+                assert_eq!(
+                    result.get("stdout").and_then(Value::as_str),
+                    Some("hello\n")
+                );
+            }
+
+            let audit =
+                std::fs::read_to_string(tmp.join("remote-command-audit.jsonl")).expect("audit log");
+            assert!(audit
+                .lines()
+                .next()
+                .is_some_and(|line| line.contains("\"denied\"")));
+
+            let listed = rpc::handle_rpc_request(
+                &state,
+                "list_remote_command_audit",
+                json!({ "page": { "limit": 1 } }),
+                "daemon-test".to_string(),
+            )
+            .await
+            .expect("list audit");
+            let items = listed["items"].as_array().expect("items");
+            assert_eq!(items.len(), 1);
+            let newest = if cfg!(unix) {
+                "echo hello"
+            } else {
+                "git status"
+            };
+            assert_eq!(items[0]["command"], newest);
+            assert_eq!(listed["nextCursor"].is_string(), cfg!(unix));
+            let _ = std::fs::remove_dir_all(&tmp);
+        });
+    }
+
     #[test]
     fn list_workspaces_syncs_from_storage_file() {
         run_async_test(async {
@@ -1908,11 +3176,664 @@ mod tests {
             let _ = std::fs::remove_dir_all(&tmp);
         });
     }
+
+    #[test]
+    fn guest_tokens_are_scoped_at_dispatch() {
+        run_async_test(async {
+            let tmp = make_temp_dir("guest-token-auth");
+            let state = Arc::new(test_state(&tmp));
+            let issued = rpc::handle_rpc_request(
+                &state,
+                "create_guest_token",
+                json!({ "scopes": ["read"], "ttlSeconds": 600 }),
+                "daemon-test".to_string(),
+            )
+            .await
+            .expect("guest token should be issued");
+            let token = issued["token"].as_str().expect("token").to_string();
+            let guest_id = issued["guest"]["id"].as_str().expect("id").to_string();
+
+            let listener = TcpListener::bind("127.0.0.1:0").await.expect("bind");
+            let addr = listener.local_addr().expect("local addr");
+            let config = Arc::new(DaemonConfig {
+                listen: addr,
+                token: Some("owner-token".to_string()),
+                data_dir: tmp.clone(),
+                app_data_dir: tmp.clone(),
+            });
+            let server_state = Arc::clone(&state);
+            let events = state.event_sink.tx.clone();
+            tokio::spawn(async move {
+                if let Ok((socket, _)) = listener.accept().await {
+                    transport::handle_client(socket, config, server_state, events, None).await;
+                }
+            });
+
+            let (reader, mut writer) = TcpStream::connect(addr)
+                .await
+                .expect("connect")
+                .into_split();
+            let mut lines = BufReader::new(reader).lines();
+            let mut next_id = 0u64;
+            let mut call = |method: &str, params: Value| {
+                next_id += 1;
+                format!(
+                    "{}\n",
+                    json!({ "id": next_id, "method": method, "params": params })
+                )
+            };
+
+            for (request, expect_ok, expect_error) in [
+                (call("auth", json!({ "token": token })), true, None),
+                (call("list_workspaces", json!({})), true, None),
+                (
+                    call("update_app_settings", json!({ "settings": {} })),
+                    false,
+                    Some("not allowed"),
+                ),
+            ] {
+                writer.write_all(request.as_bytes()).await.expect("write");
+                let line = lines.next_line().await.expect("read").expect("response");
+                let response: Value = serde_json::from_str(&line).expect("json");
+                assert_eq!(response.get("result").is_some(), expect_ok, "{line}");
+                if let Some(message) = expect_error {
+                    assert!(response["error"]["message"]
+                        .as_str()
+                        .is_some_and(|value| value.contains(message)));
+                }
+            }
+
+            rpc::handle_rpc_request(
+                &state,
+                "revoke_guest_token",
+                json!({ "id": guest_id }),
+                "daemon-test".to_string(),
+            )
+            .await
+            .expect("revoke");
+            let request = call("list_workspaces", json!({}));
+            writer.write_all(request.as_bytes()).await.expect("write");
+            let line = lines.next_line().await.expect("read").expect("response");
+            assert!(line.contains("expired or revoked"), "{line}");
+
+            let _ = std::fs::remove_dir_all(&tmp);
+        });
+    }
+
+    #[test]
+    fn signed_sessions_reject_unsigned_and_replayed_mutations() {
+        use crate::shared::request_signing_core::RequestSigner;
+
+        run_async_test(async {
+            let tmp = make_temp_dir("request-signing");
+            let state = Arc::new(test_state(&tmp));
+            let listener = TcpListener::bind("127.0.0.1:0").await.expect("bind");
+            let addr = listener.local_addr().expect("local addr");
+            let config = Arc::new(DaemonConfig {
+                listen: addr,
+                token: Some("owner-token".to_string()),
+                data_dir: tmp.clone(),
+                app_data_dir: tmp.clone(),
+            });
+            let events = state.event_sink.tx.clone();
+            tokio::spawn(async move {
+                if let Ok((socket, _)) = listener.accept().await {
+                    transport::handle_client(socket, config, state, events, None).await;
+                }
+            });
+
+            let (reader, mut writer) = TcpStream::connect(addr)
+                .await
+                .expect("connect")
+                .into_split();
+            let mut lines = BufReader::new(reader).lines();
+            let auth = json!({
+                "id": 1,
+                "method": "auth",
+                "params": { "token": "owner-token", "requestSigning": true },
+            });
+            writer
+                .write_all(format!("{auth}\n").as_bytes())
+                .await
+                .expect("write");
+            let line = lines.next_line().await.expect("read").expect("auth");
+            let response: Value = serde_json::from_str(&line).expect("json");
+            let nonce = response["result"]["signing"]["nonce"]
+                .as_str()
+                .expect("signing nonce");
+            let signer = RequestSigner::new("owner-token", nonce);
+
+            let params = json!({ "scopes": ["read"], "ttlSeconds": 600 });
+            let signed = format!(
+                "{}\n",
+                json!({
+                    "id": 4,
+                    "method": "create_guest_token",
+                    "params": params,
+                    "sig": signer.signature(4, "create_guest_token", &params),
+                })
+            );
+            for (request, expected_error) in [
+                (
+                    format!("{}\n", json!({ "id": 2, "method": "list_workspaces" })),
+                    None,
+                ),
+                (
+                    format!(
+                        "{}\n",
+                        json!({ "id": 3, "method": "create_guest_token", "params": params })
+                    ),
+                    Some("signature is missing"),
+                ),
+                (signed.clone(), None),
+                (signed, Some("already used")),
+            ] {
+                writer.write_all(request.as_bytes()).await.expect("write");
+                let line = lines.next_line().await.expect("read").expect("response");
+                let response: Value = serde_json::from_str(&line).expect("json");
+                match expected_error {
+                    None => assert!(response.get("result").is_some(), "{line}"),
+                    Some(message) => {
+                        assert_eq!(response["error"]["category"], json!("auth"), "{line}");
+                        assert!(line.contains(message), "{line}");
+                    }
+                }
+            }
+
+            let _ = std::fs::remove_dir_all(&tmp);
+        });
+    }
+
+    #[test]
+    fn daemon_identify_is_answered_before_auth() {
+        run_async_test(async {
+            let tmp = make_temp_dir("daemon-identify");
+            let secret = daemon_identity_core::ensure_daemon_identity(&tmp).expect("identity");
+            let state = Arc::new(test_state(&tmp));
+            let listener = TcpListener::bind("127.0.0.1:0").await.expect("bind");
+            let addr = listener.local_addr().expect("local addr");
+            let config = Arc::new(DaemonConfig {
+                listen: addr,
+                token: Some("owner-token".to_string()),
+                data_dir: tmp.clone(),
+                app_data_dir: tmp.clone(),
+            });
+            let events = state.event_sink.tx.clone();
+            tokio::spawn(async move {
+                if let Ok((socket, _)) = listener.accept().await {
+                    transport::handle_client(socket, config, state, events, None).await;
+                }
+            });
+
+            let (reader, mut writer) = TcpStream::connect(addr)
+                .await
+                .expect("connect")
+                .into_split();
+            let mut lines = BufReader::new(reader).lines();
+            let request = json!({
+                "id": 1,
+                "method": daemon_identity_core::DAEMON_IDENTIFY_METHOD,
+                "params": { "challenge": "nonce-1" },
+            });
+            writer
+                .write_all(format!("{request}\n").as_bytes())
+                .await
+                .expect("write");
+            let line = lines.next_line().await.expect("read").expect("response");
+            let response: Value = serde_json::from_str(&line).expect("json");
+            let proof = response["result"]["proof"].as_str().expect("proof");
+            assert!(daemon_identity_core::verify_daemon_identity_proof(
+                &secret, "nonce-1", proof
+            ));
+
+            let request = json!({ "id": 2, "method": "ping", "params": {} });
+            writer
+                .write_all(format!("{request}\n").as_bytes())
+                .await
+                .expect("write");
+            let line = lines.next_line().await.expect("read").expect("response");
+            let response: Value = serde_json::from_str(&line).expect("json");
+            assert_eq!(response["error"]["message"], "unauthorized", "{line}");
+            assert_eq!(response["error"]["category"], "auth", "{line}");
+
+            let _ = std::fs::remove_dir_all(&tmp);
+        });
+    }
+
+    #[test]
+    fn new_clients_wait_for_approval_before_auth_succeeds() {
+        run_async_test(async {
+            let tmp = make_temp_dir("client-approval");
+            write_settings(
+                &tmp.join("settings.json"),
+                &AppSettings {
+                    require_client_approval: true,
+                    ..AppSettings::default()
+                },
+            )
+            .expect("write settings");
+            let state = Arc::new(test_state(&tmp));
+            let mut approval_events = state.event_sink.tx.subscribe();
+            let listener = TcpListener::bind("127.0.0.1:0").await.expect("bind");
+            let addr = listener.local_addr().expect("local addr");
+            let config = Arc::new(DaemonConfig {
+                listen: addr,
+                token: Some("owner-token".to_string()),
+                data_dir: tmp.clone(),
+                app_data_dir: tmp.clone(),
+            });
+            let server_state = Arc::clone(&state);
+            let events = state.event_sink.tx.clone();
+            tokio::spawn(async move {
+                while let Ok((socket, _)) = listener.accept().await {
+                    tokio::spawn(transport::handle_client(
+                        socket,
+                        Arc::clone(&config),
+                        Arc::clone(&server_state),
+                        events.clone(),
+                        None,
+                    ));
+                }
+            });
+            let auth = |client_id: &'static str| async move {
+                let (reader, mut writer) = TcpStream::connect(addr)
+                    .await
+                    .expect("connect")
+                    .into_split();
+                let request = json!({
+                    "id": 1,
+                    "method": "auth",
+                    "params": { "token": "owner-token", "clientId": client_id, "clientName": "Phone" },
+                });
+                writer
+                    .write_all(format!("{request}\n").as_bytes())
+                    .await
+                    .expect("write");
+                let line = BufReader::new(reader)
+                    .lines()
+                    .next_line()
+                    .await
+                    .expect("read")
+                    .expect("response");
+                serde_json::from_str::<Value>(&line).expect("json")
+            };
+
+            let response = auth("phone").await;
+            assert_eq!(
+                response["error"]["message"], "client is waiting for approval on the host",
+                "{response}"
+            );
+            let Ok(DaemonEvent::ClientApprovalRequested(approval)) = approval_events.recv().await
+            else {
+                panic!("approval request should be announced");
+            };
+            assert_eq!(approval.client_id, "phone");
+            assert_eq!(approval.client_name.as_deref(), Some("Phone"));
+
+            rpc::handle_rpc_request(
+                &state,
+                "decide_client_approval",
+                json!({ "clientId": "phone", "approved": true }),
+                "daemon-test".to_string(),
+            )
+            .await
+            .expect("approve");
+            assert_eq!(auth("phone").await["result"]["ok"], true);
+
+            rpc::handle_rpc_request(
+                &state,
+                "decide_client_approval",
+                json!({ "clientId": "phone", "approved": false }),
+                "daemon-test".to_string(),
+            )
+            .await
+            .expect("deny");
+            assert_eq!(
+                auth("phone").await["error"]["message"],
+                "client was denied access"
+            );
+
+            let _ = std::fs::remove_dir_all(&tmp);
+        });
+    }
+
+    #[test]
+    fn negotiated_frames_carry_requests_and_enforce_the_limit() {
+        use crate::shared::rpc_framing_core::{
+            encode_message, framing_request, FrameReader, Framing, MIN_MAX_FRAME_BYTES,
+        };
+
+        run_async_test(async {
+            let tmp = make_temp_dir("daemon-framing");
+            let state = Arc::new(test_state(&tmp));
+            let listener = TcpListener::bind("127.0.0.1:0").await.expect("bind");
+            let addr = listener.local_addr().expect("local addr");
+            let config = Arc::new(DaemonConfig {
+                listen: addr,
+                token: Some("owner-token".to_string()),
+                data_dir: tmp.clone(),
+                app_data_dir: tmp.clone(),
+            });
+            let events = state.event_sink.tx.clone();
+            tokio::spawn(async move {
+                if let Ok((socket, _)) = listener.accept().await {
+                    transport::handle_client(socket, config, state, events, None).await;
+                }
+            });
+
+            let (reader, mut writer) = TcpStream::connect(addr)
+                .await
+                .expect("connect")
+                .into_split();
+            let mut frames = FrameReader::new(reader, MIN_MAX_FRAME_BYTES);
+            let request = framing_request(1, 1024);
+            writer
+                .write_all(&encode_message(&request, Framing::Lines))
+                .await
+                .expect("write");
+            let line = frames
+                .next_message()
+                .await
+                .expect("read")
+                .expect("response");
+            let response: Value = serde_json::from_str(&line).expect("json");
+            assert_eq!(
+                response["result"]["maxFrameBytes"],
+                json!(MIN_MAX_FRAME_BYTES),
+                "{line}"
+            );
+            frames.use_length_prefixed(MIN_MAX_FRAME_BYTES);
+
+            let request =
+                json!({ "id": 2, "method": "auth", "params": { "token": "owner-token" } })
+                    .to_string();
+            writer
+                .write_all(&encode_message(&request, Framing::LengthPrefixed))
+                .await
+                .expect("write");
+            let frame = frames
+                .next_message()
+                .await
+                .expect("read")
+                .expect("response");
+            let response: Value = serde_json::from_str(&frame).expect("json");
+            assert_eq!(response["id"], 2, "{frame}");
+            assert!(response.get("error").is_none(), "{frame}");
+
+            writer
+                .write_all(&(MIN_MAX_FRAME_BYTES as u32 + 1).to_be_bytes())
+                .await
+                .expect("write");
+            let closed = tokio::time::timeout(Duration::from_secs(5), frames.next_message())
+                .await
+                .expect("daemon should drop the connection");
+            assert!(matches!(closed, Ok(None) | Err(_)));
+
+            let _ = std::fs::remove_dir_all(&tmp);
+        });
+    }
+
+    #[test]
+    fn heartbeat_subscribers_receive_periodic_beats() {
+        run_async_test(async {
+            let tmp = make_temp_dir("daemon-heartbeat");
+            let state = Arc::new(test_state(&tmp));
+            let listener = TcpListener::bind("127.0.0.1:0").await.expect("bind");
+            let addr = listener.local_addr().expect("local addr");
+            let config = Arc::new(DaemonConfig {
+                listen: addr,
+                token: None,
+                data_dir: tmp.clone(),
+                app_data_dir: tmp.clone(),
+            });
+            let events = state.event_sink.tx.clone();
+            tokio::spawn(async move {
+                if let Ok((socket, _)) = listener.accept().await {
+                    transport::handle_client(socket, config, state, events, None).await;
+                }
+            });
+
+            let (reader, mut writer) = TcpStream::connect(addr)
+                .await
+                .expect("connect")
+                .into_split();
+            let mut lines = BufReader::new(reader).lines();
+            let request = json!({
+                "id": 1,
+                "method": "daemon_heartbeat_subscribe",
+                "params": { "intervalMs": 1, "events": false },
+            });
+            writer
+                .write_all(format!("{request}\n").as_bytes())
+                .await
+                .expect("write");
+
+            let mut beats = 0;
+            while beats < 2 {
+                let line = tokio::time::timeout(Duration::from_secs(2), lines.next_line())
+                    .await
+                    .expect("timely line")
+                    .expect("read")
+                    .expect("line");
+                let message: Value = serde_json::from_str(&line).expect("json");
+                if message["id"] == json!(1) {
+                    assert_eq!(message["result"]["intervalMs"], json!(250));
+                    continue;
+                }
+                assert_eq!(message["method"], json!("daemon-heartbeat"));
+                assert_eq!(message["params"]["pid"], json!(std::process::id()));
+                beats += 1;
+            }
+
+            let _ = std::fs::remove_dir_all(&tmp);
+        });
+    }
+
+    #[test]
+    fn connected_clients_are_told_when_the_daemon_shuts_down() {
+        run_async_test(async {
+            let tmp = make_temp_dir("daemon-shutting-down");
+            let state = Arc::new(test_state(&tmp));
+            let listener = TcpListener::bind("127.0.0.1:0").await.expect("bind");
+            let addr = listener.local_addr().expect("local addr");
+            let config = Arc::new(DaemonConfig {
+                listen: addr,
+                token: None,
+                data_dir: tmp.clone(),
+                app_data_dir: tmp.clone(),
+            });
+            let events = state.event_sink.tx.clone();
+            let shutdown_tx = events.clone();
+            tokio::spawn(async move {
+                if let Ok((socket, _)) = listener.accept().await {
+                    transport::handle_client(socket, config, state, events, None).await;
+                }
+            });
+
+            let (reader, mut writer) = TcpStream::connect(addr)
+                .await
+                .expect("connect")
+                .into_split();
+            let mut lines = BufReader::new(reader).lines();
+            // Round-trip a ping so the client is subscribed before the event.
+            writer
+                .write_all(b"{\"id\":1,\"method\":\"ping\"}\n")
+                .await
+                .expect("write");
+            lines.next_line().await.expect("read").expect("ping");
+
+            let _ = shutdown_tx.send(DaemonEvent::ShuttingDown { drain_ms: 500 });
+            let line = tokio::time::timeout(Duration::from_secs(2), lines.next_line())
+                .await
+                .expect("timely line")
+                .expect("read")
+                .expect("line");
+            let message: Value = serde_json::from_str(&line).expect("json");
+            assert_eq!(message["method"], json!("daemon-shutting-down"));
+            assert_eq!(message["params"]["drainMs"], json!(500));
+
+            let _ = std::fs::remove_dir_all(&tmp);
+        });
+    }
+
+    #[test]
+    fn websocket_clients_share_the_line_protocol() {
+        use futures_util::{SinkExt, StreamExt};
+        use tokio_tungstenite::tungstenite::Message;
+
+        run_async_test(async {
+            let tmp = make_temp_dir("websocket-transport");
+            let state = Arc::new(test_state(&tmp));
+            let listener = TcpListener::bind("127.0.0.1:0").await.expect("bind");
+            let addr = listener.local_addr().expect("local addr");
+            let config = Arc::new(DaemonConfig {
+                listen: addr,
+                token: Some("owner-token".to_string()),
+                data_dir: tmp.clone(),
+                app_data_dir: tmp.clone(),
+            });
+            let events = state.event_sink.tx.clone();
+            tokio::spawn(async move {
+                if let Ok((socket, _)) = listener.accept().await {
+                    transport::accept_client(socket, config, state, events).await;
+                }
+            });
+
+            let stream = TcpStream::connect(addr).await.expect("connect");
+            let (mut websocket, _) =
+                tokio_tungstenite::client_async(format!("ws://{addr}/"), stream)
+                    .await
+                    .expect("handshake");
+            for (id, method, params) in [
+                (1, "auth", json!({ "token": "owner-token" })),
+                (2, "ping", json!({})),
+            ] {
+                let request = json!({ "id": id, "method": method, "params": params });
+                websocket
+                    .send(Message::Text(request.to_string()))
+                    .await
+                    .expect("send");
+                let response = loop {
+                    match websocket.next().await.expect("frame").expect("message") {
+                        Message::Text(text) => break text,
+                        _ => continue,
+                    }
+                };
+                let response: Value = serde_json::from_str(&response).expect("json");
+                assert_eq!(response["id"], json!(id));
+                assert!(response.get("result").is_some(), "{response}");
+            }
+
+            let _ = std::fs::remove_dir_all(&tmp);
+        });
+    }
+
+    #[test]
+    fn http_bridge_requires_bearer_token_and_dispatches_rpc() {
+        use tokio::io::AsyncReadExt;
+
+        run_async_test(async {
+            let tmp = make_temp_dir("http-bridge");
+            let state = Arc::new(test_state(&tmp));
+            let listener = TcpListener::bind("127.0.0.1:0").await.expect("bind");
+            let addr = listener.local_addr().expect("local addr");
+            let config = Arc::new(DaemonConfig {
+                listen: addr,
+                token: Some("owner-token".to_string()),
+                data_dir: tmp.clone(),
+                app_data_dir: tmp.clone(),
+            });
+            tokio::spawn(http_bridge::serve(listener, config, state));
+
+            let send = |request: String| async move {
+                let mut stream = TcpStream::connect(addr).await.expect("connect");
+                stream.write_all(request.as_bytes()).await.expect("write");
+                let mut response = String::new();
+                stream.read_to_string(&mut response).await.expect("read");
+                response
+            };
+
+            let response = send("GET /v1/list_workspaces HTTP/1.1\r\n\r\n".to_string()).await;
+            assert!(response.starts_with("HTTP/1.1 401"), "{response}");
+
+            let response = send(
+                "GET /v1/list_workspaces HTTP/1.1\r\nAuthorization: Bearer owner-token\r\n\r\n"
+                    .to_string(),
+            )
+            .await;
+            assert!(response.starts_with("HTTP/1.1 200"), "{response}");
+            assert!(response.ends_with("{\"result\":[]}"), "{response}");
+
+            let body = "{\"workspaceId\":\"missing\"}";
+            let response = send(format!(
+                "POST /v1/list_threads HTTP/1.1\r\nAuthorization: Bearer owner-token\r\nContent-Length: {}\r\n\r\n{body}",
+                body.len()
+            ))
+            .await;
+            assert!(response.starts_with("HTTP/1.1 400"), "{response}");
+            assert!(response.contains("\"error\""), "{response}");
+
+            let response = send(
+                "GET /v1/no_such_method HTTP/1.1\r\nAuthorization: Bearer owner-token\r\n\r\n"
+                    .to_string(),
+            )
+            .await;
+            assert!(response.starts_with("HTTP/1.1 404"), "{response}");
+
+            let response = send(
+                "GET /v1/ext.deploy.status HTTP/1.1\r\nAuthorization: Bearer owner-token\r\n\r\n"
+                    .to_string(),
+            )
+            .await;
+            assert!(response.starts_with("HTTP/1.1 404"), "{response}");
+
+            // Destructive requests wait for the host like TCP ones, and a
+            // bridge caller cannot switch that off.
+            let settings = AppSettings {
+                confirm_remote_actions: true,
+                remote_action_confirm_timeout_secs: 0,
+                ..AppSettings::default()
+            };
+            std::fs::write(
+                tmp.join("settings.json"),
+                serde_json::to_string(&settings).expect("serialize settings"),
+            )
+            .expect("write settings");
+            let body = serde_json::to_string(&json!({
+                "settings": AppSettings {
+                    confirm_remote_actions: false,
+                    ..AppSettings::default()
+                }
+            }))
+            .expect("serialize settings");
+            let response = send(format!(
+                "POST /v1/update_app_settings HTTP/1.1\r\nAuthorization: Bearer owner-token\r\nContent-Length: {}\r\n\r\n{body}",
+                body.len()
+            ))
+            .await;
+            assert!(response.starts_with("HTTP/1.1 200"), "{response}");
+            assert!(
+                response.contains("\"confirmRemoteActions\":true"),
+                "{response}"
+            );
+
+            let body = "{\"sessionId\":\"s1\"}";
+            let response = send(format!(
+                "POST /v1/terminate_session HTTP/1.1\r\nAuthorization: Bearer owner-token\r\nContent-Length: {}\r\n\r\n{body}",
+                body.len()
+            ))
+            .await;
+            assert!(response.starts_with("HTTP/1.1 403"), "{response}");
+            assert!(response.contains("waiting for confirmation"), "{response}");
+
+            let _ = std::fs::remove_dir_all(&tmp);
+        });
+    }
 }
 
 fn main() {
-    let config = match parse_args() {
-        Ok(config) => config,
+    let (config, mode) = match parse_args() {
+        Ok(parsed) => parsed,
         Err(err) => {
             eprintln!("{err}\n\n{}", usage());
             std::process::exit(2);
@@ -1924,14 +3845,51 @@ fn main() {
         .build()
         .expect("failed to build tokio runtime");
 
+    if mode == DaemonMode::Healthcheck {
+        let report = runtime.block_on(health::run_healthcheck(&config));
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&report).unwrap_or_default()
+        );
+        let failed = report.status == health::HealthStatus::Fail;
+        std::process::exit(if failed { 1 } else { 0 });
+    }
+
+    // Held for the life of the process; the OS releases it if the daemon
+    // crashes, so the next start takes over.
+    let _data_dir_lock = match data_dir_lock_core::acquire_data_dir_lock(&config.data_dir) {
+        Ok(guard) => guard,
+        Err(err) => {
+            eprintln!("{}", err.message(&config.data_dir));
+            let code = match err {
+                DataDirLockError::Locked(_) => data_dir_lock_core::DATA_DIR_LOCKED_EXIT_CODE,
+                DataDirLockError::Io(_) => 2,
+            };
+            std::process::exit(code);
+        }
+    };
+
     runtime.block_on(async move {
         let (events_tx, _events_rx) = broadcast::channel::<DaemonEvent>(2048);
         let event_sink = DaemonEventSink {
             tx: events_tx.clone(),
         };
+        if let Err(err) = daemon_identity_core::ensure_daemon_identity(&config.data_dir) {
+            eprintln!("failed to prepare daemon identity: {err}");
+        }
         let state = Arc::new(DaemonState::load(&config, event_sink));
         let config = Arc::new(config);
 
+        {
+            let state = Arc::clone(&state);
+            tokio::spawn(async move {
+                loop {
+                    tokio::time::sleep(scheduler_core::SCHEDULER_TICK).await;
+                    let _ = state.run_due_schedules().await;
+                }
+            });
+        }
+
         let listener = match TcpListener::bind(config.listen).await {
             Ok(listener) => listener,
             Err(err) => {
@@ -1949,6 +3907,28 @@ fn main() {
                 .display()
         );
 
+        let (bridge_enabled, bridge_port) = {
+            let settings = state.app_settings.read().await;
+            (
+                settings.daemon_http_bridge_enabled,
+                settings.daemon_http_bridge_port,
+            )
+        };
+        if bridge_enabled {
+            let bridge_addr = SocketAddr::from(([127, 0, 0, 1], bridge_port));
+            match TcpListener::bind(bridge_addr).await {
+                Ok(bridge_listener) => {
+                    eprintln!("codex-monitor-daemon HTTP bridge listening on http://{bridge_addr}");
+                    tokio::spawn(http_bridge::serve(
+                        bridge_listener,
+                        Arc::clone(&config),
+                        Arc::clone(&state),
+                    ));
+                }
+                Err(err) => eprintln!("failed to bind HTTP bridge on {bridge_addr}: {err}"),
+            }
+        }
+
         loop {
             match listener.accept().await {
                 Ok((socket, _addr)) => {
@@ -1956,7 +3936,7 @@ fn main() {
                     let state = Arc::clone(&state);
                     let events = events_tx.clone();
                     tokio::spawn(async move {
-                        transport::handle_client(socket, config, state, events).await;
+                        transport::accept_client(socket, config, state, events).await;
                     });
                 }
                 Err(_) => continue,
@@ -0,0 +1,239 @@
+//! Decides which auth providers admit a connection: the daemon or guest
+//! token a client sends with `auth`, and the tailnet identity behind its
+//! source address.
+
+use std::net::IpAddr;
+use std::time::Duration;
+
+use super::*;
+use crate::types::{DaemonAuthMode, DaemonAuthProvider, DaemonAuthSettings};
+
+const WHOIS_TIMEOUT: Duration = Duration::from_secs(2);
+#[cfg(target_os = "macos")]
+const TAILSCALE_BINARIES: &[&str] = &[
+    "tailscale",
+    "/Applications/Tailscale.app/Contents/MacOS/Tailscale",
+];
+#[cfg(not(target_os = "macos"))]
+const TAILSCALE_BINARIES: &[&str] = &["tailscale"];
+
+#[derive(Debug, PartialEq)]
+pub(super) enum AuthDecision {
+    /// Admitted on the token it sent, as owner or guest.
+    Token,
+    /// Admitted as owner on its tailnet identity.
+    Tailnet,
+    Denied,
+}
+
+pub(super) fn decide(
+    auth: &DaemonAuthSettings,
+    token_valid: bool,
+    tailnet_peer: bool,
+    loopback: bool,
+) -> AuthDecision {
+    if loopback && token_valid {
+        return AuthDecision::Token;
+    }
+    let token_enabled =
+        auth.providers.is_empty() || auth.providers.contains(&DaemonAuthProvider::Token);
+    let tailnet_enabled = auth
+        .providers
+        .contains(&DaemonAuthProvider::TailnetIdentity);
+    match auth.mode {
+        DaemonAuthMode::Any if token_enabled && token_valid => AuthDecision::Token,
+        DaemonAuthMode::Any if tailnet_enabled && tailnet_peer => AuthDecision::Tailnet,
+        DaemonAuthMode::Any => AuthDecision::Denied,
+        DaemonAuthMode::All
+            if (token_enabled && !token_valid) || (tailnet_enabled && !tailnet_peer) =>
+        {
+            AuthDecision::Denied
+        }
+        DaemonAuthMode::All if token_enabled => AuthDecision::Token,
+        DaemonAuthMode::All => AuthDecision::Tailnet,
+    }
+}
+
+/// Tailscale's CGNAT range and ULA prefix.
+fn is_tailnet_address(ip: IpAddr) -> bool {
+    match ip {
+        IpAddr::V4(ip) => {
+            let octets = ip.octets();
+            octets[0] == 100 && octets[1] & 0xc0 == 64
+        }
+        IpAddr::V6(ip) => ip.segments()[..3] == [0xfd7a, 0x115c, 0xa1e0],
+    }
+}
+
+/// The names tailscaled vouches for in a `tailscale whois --json` payload:
+/// the full MagicDNS name and the stable node ID. `Hostinfo.Hostname` and
+/// `ComputedName` are left out since a node picks those for itself.
+fn whois_names(payload: &str) -> Vec<String> {
+    let Ok(json) = serde_json::from_str::<Value>(payload) else {
+        return Vec::new();
+    };
+    let node = &json["Node"];
+    let mut names = Vec::new();
+    if let Some(dns_name) = node["Name"].as_str() {
+        names.push(dns_name.trim().trim_end_matches('.').to_ascii_lowercase());
+    }
+    if let Some(stable_id) = node["StableID"].as_str() {
+        names.push(stable_id.trim().to_ascii_lowercase());
+    }
+    names.retain(|name| !name.is_empty());
+    names
+}
+
+fn approved_entry<'a>(approved: &'a [String], names: &[String]) -> Option<&'a String> {
+    approved.iter().find(|entry| {
+        let entry = entry.trim().trim_end_matches('.').to_ascii_lowercase();
+        names.contains(&entry)
+    })
+}
+
+async fn whois(ip: IpAddr) -> Option<String> {
+    for binary in TAILSCALE_BINARIES {
+        let output = process_core::tokio_command(binary)
+            .args(["whois", "--json", &ip.to_string()])
+            .output();
+        match tokio::time::timeout(WHOIS_TIMEOUT, output).await {
+            Ok(Ok(output)) if output.status.success() => {
+                return String::from_utf8(output.stdout).ok();
+            }
+            Ok(Err(err)) if err.kind() == std::io::ErrorKind::NotFound => continue,
+            _ => return None,
+        }
+    }
+    None
+}
+
+/// The `approved_tailnet_peers` entry a connection from `addr` maps to.
+/// Listed tailnet IPs match without asking tailscaled; MagicDNS names and
+/// stable node IDs are looked up with `tailscale whois`.
+pub(super) async fn tailnet_peer(
+    auth: &DaemonAuthSettings,
+    addr: Option<SocketAddr>,
+) -> Option<String> {
+    let ip = addr?.ip();
+    if !auth
+        .providers
+        .contains(&DaemonAuthProvider::TailnetIdentity)
+        || !is_tailnet_address(ip)
+    {
+        return None;
+    }
+    let ip_name = ip.to_string();
+    if let Some(entry) = approved_entry(&auth.approved_tailnet_peers, &[ip_name]) {
+        return Some(entry.clone());
+    }
+    let payload = whois(ip).await?;
+    approved_entry(&auth.approved_tailnet_peers, &whois_names(&payload)).cloned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn settings(providers: Vec<DaemonAuthProvider>, mode: DaemonAuthMode) -> DaemonAuthSettings {
+        DaemonAuthSettings {
+            providers,
+            mode,
+            approved_tailnet_peers: Vec::new(),
+            require_request_signing: false,
+        }
+    }
+
+    #[test]
+    fn providers_combine_by_mode() {
+        use DaemonAuthProvider::{TailnetIdentity, Token};
+
+        let token_only = DaemonAuthSettings::default();
+        assert_eq!(decide(&token_only, true, false, false), AuthDecision::Token);
+        assert_eq!(
+            decide(&token_only, false, true, false),
+            AuthDecision::Denied
+        );
+
+        let either = settings(vec![Token, TailnetIdentity], DaemonAuthMode::Any);
+        assert_eq!(decide(&either, true, false, false), AuthDecision::Token);
+        assert_eq!(decide(&either, false, true, false), AuthDecision::Tailnet);
+        assert_eq!(decide(&either, false, false, false), AuthDecision::Denied);
+
+        let both = settings(vec![Token, TailnetIdentity], DaemonAuthMode::All);
+        assert_eq!(decide(&both, true, true, false), AuthDecision::Token);
+        assert_eq!(decide(&both, true, false, false), AuthDecision::Denied);
+        assert_eq!(decide(&both, false, true, false), AuthDecision::Denied);
+
+        // The desktop app keeps its own daemon even with the token provider off.
+        let tailnet_only = settings(vec![TailnetIdentity], DaemonAuthMode::Any);
+        assert_eq!(
+            decide(&tailnet_only, true, false, false),
+            AuthDecision::Denied
+        );
+        assert_eq!(
+            decide(&tailnet_only, true, false, true),
+            AuthDecision::Token
+        );
+    }
+
+    #[test]
+    fn tailnet_peers_match_by_address_or_whois_name() {
+        assert!(is_tailnet_address("100.101.2.3".parse().expect("ip")));
+        assert!(!is_tailnet_address("100.128.0.1".parse().expect("ip")));
+        assert!(is_tailnet_address("fd7a:115c:a1e0::1".parse().expect("ip")));
+        assert!(!is_tailnet_address("192.168.1.10".parse().expect("ip")));
+
+        let names = whois_names(
+            r#"{"Node":{"Name":"Phone.example.ts.net.","StableID":"nABC123","ComputedName":"phone","Hostinfo":{"Hostname":"iPhone"}},"UserProfile":{"LoginName":"a@example.com"}}"#,
+        );
+        assert_eq!(names, ["phone.example.ts.net", "nabc123"]);
+
+        let approved = vec!["laptop".to_string(), "phone.example.ts.net.".to_string()];
+        assert_eq!(
+            approved_entry(&approved, &names).map(String::as_str),
+            Some("phone.example.ts.net.")
+        );
+        let by_id = vec!["nABC123".to_string()];
+        assert_eq!(
+            approved_entry(&by_id, &names).map(String::as_str),
+            Some("nABC123")
+        );
+        assert_eq!(approved_entry(&approved, &whois_names("not json")), None);
+    }
+
+    #[test]
+    fn self_chosen_host_names_do_not_match() {
+        let spoofed = whois_names(
+            r#"{"Node":{"Name":"intruder.example.ts.net.","StableID":"nXYZ","ComputedName":"laptop","Hostinfo":{"Hostname":"laptop"}}}"#,
+        );
+        let approved = vec!["laptop".to_string()];
+        assert_eq!(approved_entry(&approved, &spoofed), None);
+    }
+
+    #[test]
+    fn untrusted_addresses_skip_the_lookup() {
+        let auth = DaemonAuthSettings {
+            providers: vec![DaemonAuthProvider::TailnetIdentity],
+            mode: DaemonAuthMode::Any,
+            approved_tailnet_peers: vec!["100.64.0.7".to_string()],
+            require_request_signing: false,
+        };
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .expect("runtime");
+        runtime.block_on(async {
+            let listed = "100.64.0.7:50000".parse().ok();
+            assert_eq!(
+                tailnet_peer(&auth, listed).await.as_deref(),
+                Some("100.64.0.7")
+            );
+            let lan = "192.168.1.10:50000".parse().ok();
+            assert_eq!(tailnet_peer(&auth, lan).await, None);
+            assert_eq!(
+                tailnet_peer(&DaemonAuthSettings::default(), listed).await,
+                None
+            );
+        });
+    }
+}
@@ -0,0 +1,197 @@
+use super::*;
+use crate::shared::storage_health_core::free_disk_bytes;
+use std::path::Path;
+use std::time::Duration;
+
+/// Below this the daemon cannot be trusted to persist settings or sessions.
+const MIN_FREE_DISK_BYTES: u64 = 100 * 1024 * 1024;
+const LOW_FREE_DISK_BYTES: u64 = 1024 * 1024 * 1024;
+const PROBE_TIMEOUT: Duration = Duration::from_secs(2);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub(super) enum HealthStatus {
+    Ok,
+    Warn,
+    Fail,
+}
+
+#[derive(Debug, Serialize)]
+struct HealthCheck {
+    name: &'static str,
+    status: HealthStatus,
+    detail: String,
+}
+
+fn check(name: &'static str, status: HealthStatus, detail: impl Into<String>) -> HealthCheck {
+    HealthCheck {
+        name,
+        status,
+        detail: detail.into(),
+    }
+}
+
+/// Overall status is the worst individual check.
+#[derive(Debug, Serialize)]
+pub(super) struct HealthReport {
+    pub(super) status: HealthStatus,
+    version: &'static str,
+    checks: Vec<HealthCheck>,
+}
+
+impl HealthReport {
+    fn new(checks: Vec<HealthCheck>) -> Self {
+        let status = checks
+            .iter()
+            .map(|check| check.status)
+            .max()
+            .unwrap_or(HealthStatus::Ok);
+        Self {
+            status,
+            version: env!("CARGO_PKG_VERSION"),
+            checks,
+        }
+    }
+}
+
+fn disk_check(free_bytes: Option<u64>) -> HealthCheck {
+    let Some(free) = free_bytes else {
+        return check("disk", HealthStatus::Warn, "free space could not be read");
+    };
+    let detail = format!("{} MiB free in data dir", free / (1024 * 1024));
+    let status = if free < MIN_FREE_DISK_BYTES {
+        HealthStatus::Fail
+    } else if free < LOW_FREE_DISK_BYTES {
+        HealthStatus::Warn
+    } else {
+        HealthStatus::Ok
+    };
+    check("disk", status, detail)
+}
+
+fn data_dir_check(data_dir: &Path) -> HealthCheck {
+    let probe = data_dir.join(format!(".healthcheck-{}", std::process::id()));
+    let result = std::fs::create_dir_all(data_dir)
+        .and_then(|_| std::fs::write(&probe, b"ok"))
+        .and_then(|_| std::fs::remove_file(&probe));
+    match result {
+        Ok(()) => check("dataDir", HealthStatus::Ok, data_dir.display().to_string()),
+        Err(err) => check(
+            "dataDir",
+            HealthStatus::Fail,
+            format!("{} is not writable: {err}", data_dir.display()),
+        ),
+    }
+}
+
+fn settings_check(settings_path: &PathBuf) -> HealthCheck {
+    match read_settings(settings_path) {
+        Ok(_) => check("settings", HealthStatus::Ok, "settings.json is readable"),
+        Err(err) => check(
+            "settings",
+            HealthStatus::Fail,
+            format!("settings.json could not be read: {err}"),
+        ),
+    }
+}
+
+fn storage_checks(data_dir: &Path, app_data_dir: &Path) -> Vec<HealthCheck> {
+    vec![
+        data_dir_check(data_dir),
+        disk_check(free_disk_bytes(app_data_dir)),
+        settings_check(&app_data_dir.join("settings.json")),
+    ]
+}
+
+/// Health as seen from inside a running daemon, served by the `health` RPC.
+pub(super) async fn daemon_health(state: &DaemonState) -> HealthReport {
+    let workspace_sessions = state.sessions.lock().await.len();
+    let codex_sessions = state.session_runtimes.running.lock().await.len();
+    let mut checks = vec![
+        check(
+            "daemon",
+            HealthStatus::Ok,
+            format!("pid {}", std::process::id()),
+        ),
+        check(
+            "runner",
+            HealthStatus::Ok,
+            format!("{workspace_sessions} workspace session(s), {codex_sessions} Codex session(s)"),
+        ),
+    ];
+    checks.extend(storage_checks(&state.data_dir, &state.app_data_dir));
+    HealthReport::new(checks)
+}
+
+/// Asks whatever listens on the configured address to prove it is this
+/// install's daemon. Not running is a warning so the check also works as
+/// an `ExecStartPre` before the daemon starts.
+async fn probe_running_daemon(config: &DaemonConfig) -> HealthCheck {
+    let mut addr = config.listen;
+    if addr.ip().is_unspecified() {
+        addr.set_ip(match addr {
+            SocketAddr::V4(_) => std::net::Ipv4Addr::LOCALHOST.into(),
+            SocketAddr::V6(_) => std::net::Ipv6Addr::LOCALHOST.into(),
+        });
+    }
+    let probe = async {
+        let stream = TcpStream::connect(addr).await.ok()?;
+        let (reader, mut writer) = stream.into_split();
+        let challenge = daemon_identity_core::new_identity_challenge();
+        let request = json!({
+            "id": 1,
+            "method": daemon_identity_core::DAEMON_IDENTIFY_METHOD,
+            "params": { "challenge": challenge },
+        });
+        writer
+            .write_all(format!("{request}\n").as_bytes())
+            .await
+            .ok()?;
+        let line = BufReader::new(reader).lines().next_line().await.ok()??;
+        let response: Value = serde_json::from_str(&line).unwrap_or(Value::Null);
+        Some((challenge, response))
+    };
+
+    let Ok(Some((challenge, response))) = tokio::time::timeout(PROBE_TIMEOUT, probe).await else {
+        return check(
+            "daemon",
+            HealthStatus::Warn,
+            format!("not running on {addr}"),
+        );
+    };
+    if let Some(message) = response["error"]["message"].as_str() {
+        return check(
+            "daemon",
+            HealthStatus::Warn,
+            format!("daemon on {addr} could not prove its identity: {message}"),
+        );
+    }
+    let Some(proof) = response["result"]["proof"].as_str() else {
+        return check(
+            "daemon",
+            HealthStatus::Fail,
+            format!("{addr} is held by a process that is not this daemon"),
+        );
+    };
+    let verified =
+        daemon_identity_core::read_daemon_identity(&config.data_dir).is_some_and(|secret| {
+            daemon_identity_core::verify_daemon_identity_proof(&secret, &challenge, proof)
+        });
+    if verified {
+        check("daemon", HealthStatus::Ok, format!("running on {addr}"))
+    } else {
+        check(
+            "daemon",
+            HealthStatus::Fail,
+            format!("{addr} is held by a daemon using a different data dir"),
+        )
+    }
+}
+
+/// Standalone `--healthcheck`: storage checks plus a probe of the listen
+/// address.
+pub(super) async fn run_healthcheck(config: &DaemonConfig) -> HealthReport {
+    let mut checks = vec![probe_running_daemon(config).await];
+    checks.extend(storage_checks(&config.data_dir, &config.app_data_dir));
+    HealthReport::new(checks)
+}
@@ -0,0 +1,281 @@
+use super::transport::hold_for_confirmation;
+use super::*;
+use std::time::Duration;
+use tokio::io::AsyncReadExt;
+
+const API_PREFIX: &str = "/v1/";
+const MAX_HEADER_BYTES: usize = 16 * 1024;
+const MAX_BODY_BYTES: usize = 4 * 1024 * 1024;
+const REQUEST_READ_TIMEOUT: Duration = Duration::from_secs(10);
+
+type HttpError = (u16, String);
+
+#[derive(Debug, PartialEq, Eq)]
+struct HttpRequestHead {
+    method: String,
+    path: String,
+    query: Option<String>,
+    content_length: usize,
+    bearer_token: Option<String>,
+}
+
+fn parse_request_head(head: &str) -> Result<HttpRequestHead, HttpError> {
+    let mut lines = head.split("\r\n");
+    let mut request_line = lines.next().unwrap_or_default().split_whitespace();
+    let (Some(method), Some(target)) = (request_line.next(), request_line.next()) else {
+        return Err((400, "malformed request line".to_string()));
+    };
+    let (path, query) = match target.split_once('?') {
+        Some((path, query)) => (path, Some(query.to_string())),
+        None => (target, None),
+    };
+
+    let mut content_length = 0;
+    let mut bearer_token = None;
+    for line in lines {
+        let Some((name, value)) = line.split_once(':') else {
+            continue;
+        };
+        let value = value.trim();
+        if name.eq_ignore_ascii_case("content-length") {
+            content_length = value
+                .parse()
+                .map_err(|_| (400, "invalid Content-Length".to_string()))?;
+        } else if name.eq_ignore_ascii_case("authorization") {
+            bearer_token = value
+                .split_once(' ')
+                .filter(|(scheme, _)| scheme.eq_ignore_ascii_case("bearer"))
+                .map(|(_, token)| token.trim().to_string())
+                .filter(|token| !token.is_empty());
+        }
+    }
+    if content_length > MAX_BODY_BYTES {
+        return Err((413, "request body too large".to_string()));
+    }
+
+    Ok(HttpRequestHead {
+        method: method.to_ascii_uppercase(),
+        path: path.to_string(),
+        query,
+        content_length,
+        bearer_token,
+    })
+}
+
+/// `/v1/list_threads` maps to the `list_threads` RPC method.
+fn rpc_method_for_path(path: &str) -> Option<&str> {
+    let method = path.strip_prefix(API_PREFIX)?.trim_end_matches('/');
+    // Plugin methods look like `ext.<plugin>.<method>`.
+    let valid = !method.is_empty()
+        && method.bytes().all(|byte| {
+            byte.is_ascii_lowercase() || byte.is_ascii_digit() || matches!(byte, b'_' | b'-' | b'.')
+        });
+    valid.then_some(method)
+}
+
+fn percent_decode(value: &str) -> String {
+    let bytes = value.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut index = 0;
+    while index < bytes.len() {
+        match bytes[index] {
+            b'+' => decoded.push(b' '),
+            b'%' if index + 2 < bytes.len()
+                && bytes[index + 1].is_ascii_hexdigit()
+                && bytes[index + 2].is_ascii_hexdigit() =>
+            {
+                let hex = std::str::from_utf8(&bytes[index + 1..index + 3]).unwrap_or("00");
+                decoded.push(u8::from_str_radix(hex, 16).unwrap_or_default());
+                index += 2;
+            }
+            byte => decoded.push(byte),
+        }
+        index += 1;
+    }
+    String::from_utf8_lossy(&decoded).to_string()
+}
+
+/// GET parameters arrive as query strings, so every value is a string.
+/// Methods that need numbers, booleans or arrays are called with POST.
+fn query_params(query: Option<&str>) -> Value {
+    let mut params = serde_json::Map::new();
+    for pair in query.unwrap_or_default().split('&') {
+        if pair.is_empty() {
+            continue;
+        }
+        let (key, value) = pair.split_once('=').unwrap_or((pair, ""));
+        params.insert(percent_decode(key), Value::String(percent_decode(value)));
+    }
+    Value::Object(params)
+}
+
+fn authorize(
+    config: &DaemonConfig,
+    state: &DaemonState,
+    bearer_token: Option<&str>,
+    method: &str,
+    required_scope: Option<&str>,
+) -> Result<(), HttpError> {
+    let Some(owner_token) = config.token.as_deref() else {
+        return Ok(());
+    };
+    let Some(provided) = bearer_token else {
+        return Err((401, "missing bearer token".to_string()));
+    };
+    if client_trust_core::owner_token_matches(owner_token, provided) {
+        return Ok(());
+    }
+    let guest = guest_tokens_core::authenticate_guest_token(&state.app_data_dir, provided)
+        .ok_or_else(|| (401, "invalid token".to_string()))?;
+    guest_tokens_core::authorize_guest_scope(&state.app_data_dir, &guest.id, method, required_scope)
+        .map_err(|err| (403, err))
+}
+
+async fn read_request(socket: &mut TcpStream) -> Result<(HttpRequestHead, Vec<u8>), HttpError> {
+    let mut buffer = Vec::new();
+    let mut chunk = [0u8; 4096];
+    let header_end = loop {
+        if let Some(position) = buffer.windows(4).position(|window| window == b"\r\n\r\n") {
+            break position;
+        }
+        if buffer.len() > MAX_HEADER_BYTES {
+            return Err((431, "request headers too large".to_string()));
+        }
+        let read = socket
+            .read(&mut chunk)
+            .await
+            .map_err(|err| (400, err.to_string()))?;
+        if read == 0 {
+            return Err((
+                400,
+                "connection closed before request completed".to_string(),
+            ));
+        }
+        buffer.extend_from_slice(&chunk[..read]);
+    };
+
+    let head = parse_request_head(&String::from_utf8_lossy(&buffer[..header_end]))?;
+    let mut body = buffer.split_off(header_end + 4);
+    if body.len() < head.content_length {
+        let mut rest = vec![0u8; head.content_length - body.len()];
+        socket
+            .read_exact(&mut rest)
+            .await
+            .map_err(|err| (400, err.to_string()))?;
+        body.extend_from_slice(&rest);
+    }
+    body.truncate(head.content_length);
+    Ok((head, body))
+}
+
+async fn respond(
+    config: &DaemonConfig,
+    state: &DaemonState,
+    head: HttpRequestHead,
+    body: Vec<u8>,
+) -> Result<Value, HttpError> {
+    let method = rpc_method_for_path(&head.path)
+        .ok_or_else(|| (404, format!("no endpoint at {}", head.path)))?;
+    let params = match head.method.as_str() {
+        "GET" => query_params(head.query.as_deref()),
+        "POST" if body.iter().all(u8::is_ascii_whitespace) => json!({}),
+        "POST" => serde_json::from_slice(&body)
+            .map_err(|err| (400, format!("invalid JSON body: {err}")))?,
+        _ => return Err((405, "use GET or POST".to_string())),
+    };
+    let required_scope = state.required_method_scope(method).await;
+    authorize(
+        config,
+        state,
+        head.bearer_token.as_deref(),
+        method,
+        required_scope,
+    )?;
+
+    // HTTP callers carry no client identity, so they are never the host.
+    let confirmation = hold_for_confirmation(
+        state,
+        &state.event_sink.tx,
+        &(None, None),
+        false,
+        method,
+        &params,
+    )
+    .map_err(|err| (500, err))?;
+    if let Some(confirmation) = confirmation {
+        remote_action_confirm_core::await_confirmation(&state.data_dir, &confirmation)
+            .await
+            .map_err(|err| (403, err))?;
+    }
+
+    let client_version = format!("http-{}", env!("CARGO_PKG_VERSION"));
+    rpc::handle_rpc_request(state, method, params, client_version)
+        .await
+        .map_err(|err| {
+            let status = if err.starts_with("unknown method") {
+                404
+            } else {
+                400
+            };
+            (status, err)
+        })
+}
+
+fn status_text(status: u16) -> &'static str {
+    match status {
+        200 => "OK",
+        400 => "Bad Request",
+        401 => "Unauthorized",
+        403 => "Forbidden",
+        404 => "Not Found",
+        405 => "Method Not Allowed",
+        408 => "Request Timeout",
+        413 => "Payload Too Large",
+        431 => "Request Header Fields Too Large",
+        _ => "Internal Server Error",
+    }
+}
+
+async fn handle_http_client(
+    mut socket: TcpStream,
+    config: Arc<DaemonConfig>,
+    state: Arc<DaemonState>,
+) {
+    let outcome = match tokio::time::timeout(REQUEST_READ_TIMEOUT, read_request(&mut socket)).await
+    {
+        Ok(Ok((head, body))) => respond(&config, &state, head, body).await,
+        Ok(Err(err)) => Err(err),
+        Err(_) => Err((408, "timed out reading request".to_string())),
+    };
+    let (status, payload) = match outcome {
+        Ok(result) => (200, json!({ "result": result })),
+        Err((status, message)) => (status, json!({ "error": message })),
+    };
+    let body = payload.to_string();
+    let response = format!(
+        "HTTP/1.1 {status} {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+        status_text(status),
+        body.len()
+    );
+    let _ = socket.write_all(response.as_bytes()).await;
+    let _ = socket.shutdown().await;
+}
+
+/// Serves the RPC surface as `GET|POST /v1/<method>` on localhost, one
+/// request per connection, authenticated with `Authorization: Bearer`.
+pub(super) async fn serve(
+    listener: TcpListener,
+    config: Arc<DaemonConfig>,
+    state: Arc<DaemonState>,
+) {
+    loop {
+        let Ok((socket, _)) = listener.accept().await else {
+            continue;
+        };
+        let config = Arc::clone(&config);
+        let state = Arc::clone(&state);
+        tokio::spawn(async move {
+            handle_http_client(socket, config, state).await;
+        });
+    }
+}
@@ -1,4 +1,5 @@
 use super::*;
+use shared::redaction_core;
 
 #[path = "rpc/codex.rs"]
 mod codex;
@@ -8,6 +9,8 @@ mod daemon;
 mod dispatcher;
 #[path = "rpc/git.rs"]
 mod git;
+#[path = "rpc/plugins.rs"]
+mod plugins;
 #[path = "rpc/prompts.rs"]
 mod prompts;
 #[path = "rpc/workspace.rs"]
@@ -26,6 +29,20 @@ pub(super) fn build_error_response(id: Option<u64>, message: &str) -> Option<Str
     )
 }
 
+/// Like `build_error_response`, with the error's category alongside the
+/// message.
+pub(super) fn build_categorized_error_response(
+    id: Option<u64>,
+    error: &MonitorError,
+) -> Option<String> {
+    let id = id?;
+    Some(
+        serde_json::to_string(&json!({ "id": id, "error": error })).unwrap_or_else(|_| {
+            "{\"id\":0,\"error\":{\"message\":\"serialization failed\"}}".to_string()
+        }),
+    )
+}
+
 pub(super) fn build_result_response(id: Option<u64>, result: Value) -> Option<String> {
     let id = id?;
     Some(
@@ -36,7 +53,7 @@ pub(super) fn build_result_response(id: Option<u64>, result: Value) -> Option<St
 }
 
 fn build_event_notification(event: DaemonEvent) -> Option<String> {
-    let payload = match event {
+    let mut payload = match event {
         DaemonEvent::AppServer(payload) => json!({
             "method": "app-server-event",
             "params": payload,
@@ -49,7 +66,45 @@ fn build_event_notification(event: DaemonEvent) -> Option<String> {
             "method": "terminal-exit",
             "params": payload,
         }),
+        DaemonEvent::RemoteCommandOutput(payload) => json!({
+            "method": "remote-command-output",
+            "params": payload,
+        }),
+        DaemonEvent::CodexSessionOutput(payload) => json!({
+            "method": "codex-session-output",
+            "params": payload,
+        }),
+        DaemonEvent::CodexSessionExit(payload) => json!({
+            "method": "codex-session-exit",
+            "params": payload,
+        }),
+        DaemonEvent::CodexSessionQueue(payload) => json!({
+            "method": "codex-session-queue",
+            "params": payload,
+        }),
+        DaemonEvent::CodexLoginProgress(payload) => json!({
+            "method": "codex-login-progress",
+            "params": payload,
+        }),
+        DaemonEvent::ClientApprovalRequested(payload) => json!({
+            "method": client_trust_core::CLIENT_APPROVAL_REQUESTED_EVENT,
+            "params": payload,
+        }),
+        DaemonEvent::RemoteActionConfirmationRequested(payload) => json!({
+            "method": remote_action_confirm_core::REMOTE_ACTION_CONFIRMATION_REQUESTED_EVENT,
+            "params": payload,
+        }),
+        DaemonEvent::ShuttingDown { drain_ms } => json!({
+            "method": "daemon-shutting-down",
+            "params": { "drainMs": drain_ms },
+        }),
     };
+    let redacted = payload["method"]
+        .as_str()
+        .is_some_and(|method| redaction_core::REDACTED_EVENTS.contains(&method));
+    if redacted {
+        redaction_core::redactor().redact_value(&mut payload["params"]);
+    }
     serde_json::to_string(&payload).ok()
 }
 
@@ -85,10 +140,7 @@ pub(super) fn parse_optional_string(value: &Value, key: &str) -> Option<String>
     }
 }
 
-pub(super) fn parse_optional_nullable_string(
-    value: &Value,
-    key: &str,
-) -> Option<Option<String>> {
+pub(super) fn parse_optional_nullable_string(value: &Value, key: &str) -> Option<Option<String>> {
     match value {
         Value::Object(map) => match map.get(key) {
             Some(Value::Null) => Some(None),
@@ -152,7 +204,12 @@ pub(super) async fn handle_rpc_request(
     params: Value,
     client_version: String,
 ) -> Result<Value, String> {
-    dispatcher::dispatch_rpc_request(state, method, &params, &client_version).await
+    let mut result =
+        dispatcher::dispatch_rpc_request(state, method, &params, &client_version).await?;
+    if redaction_core::REDACTED_METHODS.contains(&method) {
+        redaction_core::redactor().redact_value(&mut result);
+    }
+    Ok(result)
 }
 
 pub(super) async fn forward_events(
@@ -176,6 +233,10 @@ pub(super) async fn forward_events(
     }
 }
 
+/// Runs a request off the connection's read loop. A request held for
+/// `confirmation` waits for the owner's decision first, without taking one
+/// of the connection's in-flight slots.
+#[allow(clippy::too_many_arguments)]
 pub(super) fn spawn_rpc_response_task(
     state: Arc<DaemonState>,
     out_tx: mpsc::UnboundedSender<String>,
@@ -184,8 +245,19 @@ pub(super) fn spawn_rpc_response_task(
     params: Value,
     client_version: String,
     request_limiter: Arc<Semaphore>,
+    confirmation: Option<RemoteActionConfirmation>,
 ) {
     tokio::spawn(async move {
+        if let Some(confirmation) = confirmation {
+            if let Err(message) =
+                remote_action_confirm_core::await_confirmation(&state.data_dir, &confirmation).await
+            {
+                if let Some(response) = build_error_response(id, &message) {
+                    let _ = out_tx.send(response);
+                }
+                return;
+            }
+        }
         let Ok(_permit) = request_limiter.acquire_owned().await else {
             return;
         };
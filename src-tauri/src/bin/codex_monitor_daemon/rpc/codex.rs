@@ -19,10 +19,51 @@ pub(super) async fn try_handle(
         "get_codex_config_path" => {
             let path = match settings_core::get_codex_config_path_core() {
                 Ok(value) => value,
-                Err(err) => return Some(Err(err)),
+                Err(err) => return Some(Err(err.into())),
             };
             Some(Ok(Value::String(path)))
         }
+        "get_codex_home" => Some(
+            serde_json::to_value(settings_core::get_codex_home_core())
+                .map_err(|err| err.to_string()),
+        ),
+        "set_codex_home_override" => {
+            let path = parse_optional_string(params, "path");
+            Some(
+                state
+                    .set_codex_home_override(path)
+                    .await
+                    .and_then(|value| serde_json::to_value(value).map_err(|err| err.to_string())),
+            )
+        }
+        "snapshot_codex_home" => {
+            let label = parse_optional_string(params, "label");
+            let exclude_secrets = parse_optional_bool(params, "excludeSecrets").unwrap_or(false);
+            Some(
+                state
+                    .snapshot_codex_home(label, exclude_secrets)
+                    .await
+                    .and_then(|value| serde_json::to_value(value).map_err(|err| err.to_string())),
+            )
+        }
+        "list_codex_home_snapshots" => Some(
+            state
+                .list_codex_home_snapshots()
+                .await
+                .and_then(|value| serde_json::to_value(value).map_err(|err| err.to_string())),
+        ),
+        "restore_codex_home_snapshot" => {
+            let id = match parse_string(params, "id") {
+                Ok(value) => value,
+                Err(err) => return Some(Err(err)),
+            };
+            Some(
+                state
+                    .restore_codex_home_snapshot(id)
+                    .await
+                    .and_then(|value| serde_json::to_value(value).map_err(|err| err.to_string())),
+            )
+        }
         "get_config_model" => {
             let workspace_id = match parse_string(params, "workspaceId") {
                 Ok(value) => value,
@@ -473,6 +514,28 @@ pub(super) async fn try_handle(
             };
             Some(state.remember_approval_rule(workspace_id, command).await)
         }
+        "codex_auth_status" => Some(
+            state
+                .codex_auth_status()
+                .await
+                .and_then(|value| serde_json::to_value(value).map_err(|err| err.to_string())),
+        ),
+        "codex_login_start" => Some(
+            state
+                .codex_login_start()
+                .await
+                .and_then(|value| serde_json::to_value(value).map_err(|err| err.to_string())),
+        ),
+        "codex_login_flow_status" => Some(
+            serde_json::to_value(state.codex_login_flow_status().await)
+                .map_err(|err| err.to_string()),
+        ),
+        "codex_login_flow_cancel" => Some(
+            state
+                .codex_login_flow_cancel()
+                .await
+                .and_then(|value| serde_json::to_value(value).map_err(|err| err.to_string())),
+        ),
         "codex_doctor" => {
             let codex_bin = parse_optional_string(params, "codexBin");
             let codex_args = parse_optional_string(params, "codexArgs");
@@ -1,4 +1,10 @@
 use super::*;
+use crate::shared::{reachability_core, rpc_timeouts_core};
+
+/// Long enough to flush the response to the caller.
+const DEFAULT_SHUTDOWN_DRAIN_MS: u64 = 100;
+const MAX_SHUTDOWN_DRAIN_MS: u64 = 10_000;
+const SESSION_STOP_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(3);
 
 pub(super) async fn try_handle(
     state: &DaemonState,
@@ -6,14 +12,60 @@ pub(super) async fn try_handle(
     params: &Value,
 ) -> Option<Result<Value, String>> {
     match method {
-        "ping" => Some(Ok(json!({ "ok": true }))),
+        // Echoes `payload` so clients can time a round trip with data.
+        "ping" => Some(Ok(match params.get("payload") {
+            Some(payload) => json!({ "ok": true, "payload": payload }),
+            None => json!({ "ok": true }),
+        })),
         "daemon_info" => Some(Ok(state.daemon_info())),
+        rpc_timeouts_core::RPC_TIMEOUTS_METHOD => Some(
+            serde_json::to_value(rpc_timeouts_core::advertised_rpc_timeouts())
+                .map_err(|err| err.to_string()),
+        ),
+        // Another node asks this one to connect back to its daemon port.
+        reachability_core::REACHABILITY_PROBE_METHOD => {
+            let host = match parse_string(params, "host") {
+                Ok(value) => value,
+                Err(err) => return Some(Err(err)),
+            };
+            let Some(port) = params
+                .get("port")
+                .and_then(Value::as_u64)
+                .and_then(|port| u16::try_from(port).ok())
+            else {
+                return Some(Err("missing or invalid `port`".to_string()));
+            };
+            let result = reachability_core::probe_tcp(&host, port).await;
+            Some(serde_json::to_value(result).map_err(|err| err.to_string()))
+        }
+        "health" => Some(
+            serde_json::to_value(health::daemon_health(state).await).map_err(|err| err.to_string()),
+        ),
+        "host_capabilities" => Some(
+            serde_json::to_value(host_capabilities_core::host_capabilities_core().await)
+                .map_err(|err| err.to_string()),
+        ),
         "daemon_shutdown" => {
-            tokio::spawn(async {
-                tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+            let drain_ms = params
+                .get("drainMs")
+                .and_then(Value::as_u64)
+                .unwrap_or(DEFAULT_SHUTDOWN_DRAIN_MS)
+                .min(MAX_SHUTDOWN_DRAIN_MS);
+            let _ = state
+                .event_sink
+                .tx
+                .send(DaemonEvent::ShuttingDown { drain_ms });
+            let session_runtimes = Arc::clone(&state.session_runtimes);
+            tokio::spawn(async move {
+                tokio::time::sleep(std::time::Duration::from_millis(drain_ms)).await;
+                let _ = tokio::time::timeout(
+                    SESSION_STOP_TIMEOUT,
+                    codex_session_core::terminate_all_sessions(&session_runtimes),
+                )
+                .await;
                 std::process::exit(0);
             });
-            Some(Ok(json!({ "ok": true })))
+            Some(Ok(json!({ "ok": true, "drainMs": drain_ms })))
         }
         "menu_set_accelerators" => {
             let updates: Vec<Value> = match params {
@@ -26,5 +26,9 @@ pub(super) async fn dispatch_rpc_request(
         return result;
     }
 
+    if let Some(result) = plugins::try_handle(state, method, params).await {
+        return result;
+    }
+
     Err(format!("unknown method: {method}"))
 }
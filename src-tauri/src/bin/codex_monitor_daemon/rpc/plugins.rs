@@ -0,0 +1,34 @@
+use super::*;
+use crate::shared::plugins_core::PLUGIN_METHOD_PREFIX;
+use crate::shared::workspace_rpc;
+
+pub(super) async fn try_handle(
+    state: &DaemonState,
+    method: &str,
+    params: &Value,
+) -> Option<Result<Value, String>> {
+    match method {
+        "list_plugins" => {
+            Some(serde_json::to_value(state.list_plugins().await).map_err(|err| err.to_string()))
+        }
+        "enable_plugin" => {
+            let request: workspace_rpc::EnablePluginRequest =
+                match workspace_rpc::from_params(params) {
+                    Ok(value) => value,
+                    Err(err) => return Some(Err(err)),
+                };
+            Some(
+                state
+                    .enable_plugin(request.id, request.enabled)
+                    .await
+                    .and_then(|plugins| {
+                        serde_json::to_value(plugins).map_err(|err| err.to_string())
+                    }),
+            )
+        }
+        _ if method.starts_with(PLUGIN_METHOD_PREFIX) => {
+            Some(state.call_plugin_method(method, params).await)
+        }
+        _ => None,
+    }
+}
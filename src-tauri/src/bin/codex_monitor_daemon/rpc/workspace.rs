@@ -229,7 +229,217 @@ pub(super) async fn try_handle(
                 .await,
             )
         }
+        "remote_list_dir" => {
+            let request = parse_request_or_err!(params, workspace_rpc::RemoteListDirRequest);
+            Some(
+                serialize_result(state.remote_list_dir(request.workspace_id, request.rel_path))
+                    .await,
+            )
+        }
+        "search_sessions" => {
+            let request = parse_request_or_err!(params, workspace_rpc::SearchSessionsRequest);
+            Some(serialize_result(state.search_sessions(request.query, request.filters)).await)
+        }
+        "archive_sessions" => {
+            let request = parse_request_or_err!(params, workspace_rpc::ArchiveSessionsRequest);
+            Some(
+                serialize_result(
+                    state.archive_sessions(request.before, request.dry_run.unwrap_or(false)),
+                )
+                .await,
+            )
+        }
+        "list_archives" => {
+            let request = parse_request_or_err!(params, workspace_rpc::PageRequest);
+            Some(serialize_result(state.list_archives(request.page)).await)
+        }
+        "restore_archive" => {
+            let request = parse_request_or_err!(params, workspace_rpc::RestoreArchiveRequest);
+            Some(serialize_result(state.restore_archive(request.name)).await)
+        }
+        "start_codex_session" => {
+            let request = parse_request_or_err!(params, workspace_rpc::StartCodexSessionRequest);
+            Some(
+                serialize_result(state.start_codex_session(
+                    codex_session_core::StartSessionOptions {
+                        project_path: request.project_path,
+                        prompt: request.prompt,
+                        model: request.model,
+                        approval_mode: request.approval_mode,
+                        template_id: request.template_id,
+                        isolate_worktree: request.isolate_worktree.unwrap_or(false),
+                    },
+                ))
+                .await,
+            )
+        }
+        "send_session_input" => {
+            let request = parse_request_or_err!(params, workspace_rpc::SendSessionInputRequest);
+            Some(serialize_ok(state.send_session_input(request.session_id, request.text)).await)
+        }
+        "list_codex_sessions" => {
+            let request = parse_request_or_err!(params, workspace_rpc::ListCodexSessionsRequest);
+            Some(serialize_result(state.list_codex_sessions(request.tags, request.page)).await)
+        }
+        "get_codex_session" => {
+            let request = parse_request_or_err!(params, workspace_rpc::SessionIdRequest);
+            Some(serialize_result(state.get_codex_session(request.session_id)).await)
+        }
+        "export_session" => {
+            let request = parse_request_or_err!(params, workspace_rpc::ExportSessionRequest);
+            Some(
+                serialize_result(state.export_session(
+                    request.session_id,
+                    request.format,
+                    request.include_tool_output,
+                ))
+                .await,
+            )
+        }
+        "read_session_export" => {
+            let request = parse_request_or_err!(params, workspace_rpc::ReadSessionExportRequest);
+            Some(
+                serialize_result(state.read_session_export(
+                    request.file_name,
+                    request.offset,
+                    request.length,
+                ))
+                .await,
+            )
+        }
+        "get_process_stats" => {
+            let request = parse_request_or_err!(params, workspace_rpc::ProcessStatsRequest);
+            Some(serialize_result(state.get_process_stats(request.pid)).await)
+        }
+        "cancel_queued_session" => {
+            let request = parse_request_or_err!(params, workspace_rpc::SessionIdRequest);
+            Some(serialize_ok(state.cancel_queued_session(request.session_id)).await)
+        }
+        "terminate_session" => {
+            let request = parse_request_or_err!(params, workspace_rpc::TerminateSessionRequest);
+            Some(
+                serialize_ok(state.terminate_session(
+                    request.session_id,
+                    request.pid,
+                    request.force,
+                ))
+                .await,
+            )
+        }
+        "interrupt_session" => {
+            let request = parse_request_or_err!(params, workspace_rpc::SessionIdRequest);
+            Some(serialize_ok(state.interrupt_session(request.session_id)).await)
+        }
+        "merge_session_worktree" => {
+            let request = parse_request_or_err!(params, workspace_rpc::SessionIdRequest);
+            Some(serialize_result(state.merge_session_worktree(request.session_id)).await)
+        }
+        "discard_session_worktree" => {
+            let request = parse_request_or_err!(params, workspace_rpc::SessionIdRequest);
+            Some(serialize_ok(state.discard_session_worktree(request.session_id)).await)
+        }
+        "get_project_ci_status" => {
+            let request = parse_request_or_err!(params, workspace_rpc::ProjectCiStatusRequest);
+            Some(
+                serialize_result(state.project_ci_status(request.project_id, request.branch)).await,
+            )
+        }
+        "create_pr_from_session" => {
+            let request = parse_request_or_err!(params, workspace_rpc::CreatePrFromSessionRequest);
+            Some(
+                serialize_result(state.create_pr_from_session(
+                    request.session_id,
+                    request.title,
+                    request.body,
+                    request.base,
+                ))
+                .await,
+            )
+        }
+        "list_editors" => Some(serialize_value(state.list_editors().await)),
+        "open_in_editor" => {
+            let request = parse_request_or_err!(params, workspace_rpc::OpenInEditorRequest);
+            Some(serialize_ok(state.open_in_editor(request.path, request.line)).await)
+        }
+        "open_diff_in_editor" => {
+            let request = parse_request_or_err!(params, workspace_rpc::OpenDiffInEditorRequest);
+            Some(serialize_ok(state.open_diff_in_editor(request.session_id, request.file)).await)
+        }
+        "create_guest_token" => {
+            let request = parse_request_or_err!(params, workspace_rpc::CreateGuestTokenRequest);
+            Some(
+                serialize_result(state.create_guest_token(
+                    request.scopes,
+                    request.ttl_seconds,
+                    request.label,
+                ))
+                .await,
+            )
+        }
+        "list_guest_tokens" => Some(serialize_result(state.list_guest_tokens()).await),
+        "revoke_guest_token" => {
+            let request = parse_request_or_err!(params, workspace_rpc::IdRequest);
+            Some(serialize_ok(state.revoke_guest_token(request.id)).await)
+        }
+        "list_client_approvals" => Some(serialize_result(state.list_client_approvals()).await),
+        "decide_client_approval" => {
+            let request = parse_request_or_err!(params, workspace_rpc::DecideClientApprovalRequest);
+            Some(
+                serialize_result(state.decide_client_approval(request.client_id, request.approved))
+                    .await,
+            )
+        }
+        "list_remote_action_confirmations" => {
+            Some(serialize_result(state.list_remote_action_confirmations()).await)
+        }
+        "list_session_templates" => Some(serialize_result(state.list_session_templates()).await),
+        "save_session_template" => {
+            let request = parse_request_or_err!(params, workspace_rpc::SaveSessionTemplateRequest);
+            Some(serialize_result(state.save_session_template(request.template)).await)
+        }
+        "delete_session_template" => {
+            let request = parse_request_or_err!(params, workspace_rpc::IdRequest);
+            Some(serialize_ok(state.delete_session_template(request.id)).await)
+        }
+        "list_tags" => Some(serialize_result(state.list_tags()).await),
+        "save_tag" => {
+            let request = parse_request_or_err!(params, workspace_rpc::SaveTagRequest);
+            Some(serialize_result(state.save_tag(request.tag)).await)
+        }
+        "delete_tag" => {
+            let request = parse_request_or_err!(params, workspace_rpc::IdRequest);
+            Some(serialize_ok(state.delete_tag(request.id)).await)
+        }
+        "set_tags" => {
+            let request = parse_request_or_err!(params, workspace_rpc::SetTagsRequest);
+            Some(
+                serialize_result(state.set_tags(request.target, request.id, request.tag_ids)).await,
+            )
+        }
+        "get_tag_assignments" => Some(serialize_result(state.get_tag_assignments()).await),
+        "list_schedules" => Some(serialize_result(state.list_schedules()).await),
+        "upsert_schedule" => {
+            let request = parse_request_or_err!(params, workspace_rpc::UpsertScheduleRequest);
+            Some(serialize_result(state.upsert_schedule(request.schedule)).await)
+        }
+        "run_schedule_now" => {
+            let request = parse_request_or_err!(params, workspace_rpc::IdRequest);
+            Some(serialize_result(state.run_schedule_now(request.id)).await)
+        }
+        "remote_read_file" => {
+            let request = parse_request_or_err!(params, workspace_rpc::RemoteReadFileRequest);
+            Some(
+                serialize_result(state.remote_read_file(
+                    request.workspace_id,
+                    request.rel_path,
+                    request.offset,
+                    request.length,
+                ))
+                .await,
+            )
+        }
         "get_app_settings" => Some(serialize_value(state.get_app_settings().await)),
+        "settings_sync_snapshot" => Some(serialize_result(state.settings_sync_snapshot()).await),
         "update_app_settings" => {
             let settings_value = match params {
                 Value::Object(map) => map.get("settings").cloned().unwrap_or(Value::Null),
@@ -241,6 +451,13 @@ pub(super) async fn try_handle(
             };
             Some(serialize_result(state.update_app_settings(settings)).await)
         }
+        "test_redaction_rules" => {
+            let request = parse_request_or_err!(params, workspace_rpc::TestRedactionRulesRequest);
+            Some(
+                serialize_result(state.test_redaction_rules(request.sample, request.settings))
+                    .await,
+            )
+        }
         "apply_worktree_changes" => {
             let request = parse_request_or_err!(params, workspace_rpc::WorkspaceIdRequest);
             Some(serialize_ok(state.apply_worktree_changes(request.workspace_id)).await)
@@ -268,6 +485,34 @@ pub(super) async fn try_handle(
             let workspace_path = parse_optional_string(params, "workspacePath");
             Some(serialize_result(state.local_usage_snapshot(days, workspace_path)).await)
         }
+        "activity_report" => {
+            let request = parse_request_or_err!(params, workspace_rpc::ActivityReportRequest);
+            Some(serialize_result(state.activity_report(request.range)).await)
+        }
+        "run_remote_command" => {
+            let request = parse_request_or_err!(params, workspace_rpc::RunRemoteCommandRequest);
+            Some(
+                serialize_result(state.run_remote_command(
+                    request.workspace_id,
+                    request.command,
+                    request.cwd,
+                    request.run_id,
+                ))
+                .await,
+            )
+        }
+        "list_remote_command_audit" => {
+            let request = parse_request_or_err!(params, workspace_rpc::PageRequest);
+            Some(serialize_result(state.list_remote_command_audit(request.page)).await)
+        }
+        "list_config_changes" => {
+            let request = parse_request_or_err!(params, workspace_rpc::PageRequest);
+            Some(serialize_result(state.list_config_changes(request.page)).await)
+        }
+        "revert_config_change" => {
+            let request = parse_request_or_err!(params, workspace_rpc::IdRequest);
+            Some(serialize_result(state.revert_config_change(request.id)).await)
+        }
         _ => None,
     }
 }
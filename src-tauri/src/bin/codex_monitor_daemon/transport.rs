@@ -1,42 +1,300 @@
+use super::auth::{self, AuthDecision};
 use super::rpc::{
-    build_error_response, build_result_response, forward_events, parse_auth_token,
-    spawn_rpc_response_task,
+    build_categorized_error_response, build_error_response, build_result_response, forward_events,
+    parse_auth_token, spawn_rpc_response_task,
 };
 use super::*;
+use crate::shared::client_trust_core::{self, ClientAdmission};
+use crate::shared::remote_action_confirm_core::{self, DECIDE_REMOTE_ACTION_CONFIRMATION_METHOD};
+use crate::shared::request_signing_core::{
+    self, SigningSession, REQUEST_SIGNING_PARAM, SIGNATURE_FIELD, SIGNATURE_WINDOW_MS,
+};
+use crate::shared::rpc_framing_core::{
+    self, encode_message, FrameReader, Framing, DEFAULT_MAX_FRAME_BYTES, RPC_FRAMING_METHOD,
+};
+use crate::shared::workspace_rpc::DecideRemoteActionConfirmationRequest;
+use futures_util::{SinkExt, StreamExt};
+use std::time::Duration;
+use tokio::io::{AsyncRead, AsyncWrite, DuplexStream};
+use tokio_tungstenite::tungstenite::Message;
+use tokio_tungstenite::WebSocketStream;
+
+const PROTOCOL_SNIFF_TIMEOUT: Duration = Duration::from_secs(1);
+const HEARTBEAT_SUBSCRIBE_METHOD: &str = "daemon_heartbeat_subscribe";
+const DEFAULT_HEARTBEAT_INTERVAL_MS: u64 = 2_000;
+const MIN_HEARTBEAT_INTERVAL_MS: u64 = 250;
+const MAX_HEARTBEAT_INTERVAL_MS: u64 = 60_000;
+const WEBSOCKET_PIPE_CAPACITY: usize = 64 * 1024;
+/// Queued on a connection's outbound channel right after the reply agreeing
+/// to length-prefixed frames; the writer switches framing when it reaches
+/// it. Real messages are JSON objects, so never empty.
+const SWITCH_TO_FRAMES: &str = "";
+
+/// Who a connection authenticated as. Guests are re-checked against the
+/// token store on every request.
+enum ClientAccess {
+    Owner,
+    Guest(String),
+}
+
+/// Proves this daemon holds the per-install identity secret for the
+/// challenge the client picked. Answered without authentication so clients
+/// can verify the daemon before sending a token.
+fn identify_daemon(config: &DaemonConfig, params: &Value) -> Result<Value, String> {
+    let challenge = params
+        .get("challenge")
+        .and_then(Value::as_str)
+        .ok_or_else(|| "missing `challenge`".to_string())?;
+    let secret = daemon_identity_core::read_daemon_identity(&config.data_dir)
+        .ok_or_else(|| "daemon identity is not configured".to_string())?;
+    let proof = daemon_identity_core::daemon_identity_proof(&secret, challenge)?;
+    Ok(json!({ "proof": proof }))
+}
+
+/// Checks the client identifier sent with a valid token against the
+/// approvals store, announcing clients that are waiting for the owner.
+fn admit_client(
+    state: &DaemonState,
+    events: &broadcast::Sender<DaemonEvent>,
+    params: &Value,
+) -> Result<(), MonitorError> {
+    let admission = client_trust_core::admit_client(
+        &state.data_dir,
+        params.get("clientId").and_then(Value::as_str),
+        params.get("clientName").and_then(Value::as_str),
+        state.requires_client_approval(),
+    )
+    .map_err(MonitorError::Process)?;
+    let refusal = match admission {
+        ClientAdmission::Allowed => return Ok(()),
+        ClientAdmission::Pending(approval) => {
+            let _ = events.send(DaemonEvent::ClientApprovalRequested(approval));
+            "client is waiting for approval on the host"
+        }
+        ClientAdmission::Denied => "client was denied access",
+        ClientAdmission::Unidentified => "client approval requires a client id",
+    };
+    Err(MonitorError::Auth(refusal.to_string()))
+}
+
+/// The client identifier and name a connection sent with `auth`.
+pub(super) type ClientIdentity = (Option<String>, Option<String>);
+
+fn client_identity(params: &Value) -> ClientIdentity {
+    let field = |key: &str| {
+        params
+            .get(key)
+            .and_then(Value::as_str)
+            .map(str::trim)
+            .filter(|value| !value.is_empty())
+            .map(str::to_string)
+    };
+    (field("clientId"), field("clientName"))
+}
+
+/// Whether a connection is the desktop app on this machine: it must come
+/// over loopback, where a remote client cannot reach, and carry the client
+/// id of the install whose data directory the daemon serves. The id alone
+/// proves nothing, since clients choose their own.
+fn is_host_connection(state: &DaemonState, loopback: bool, identity: &ClientIdentity) -> bool {
+    loopback
+        && identity
+            .0
+            .as_deref()
+            .is_some_and(|id| client_trust_core::is_host_client(&state.app_data_dir, id))
+}
+
+/// Holds `method` for the owner's confirmation when confirmations are on
+/// and the request is destructive, announcing it to connected clients.
+/// Requests from the host connection are never held.
+pub(super) fn hold_for_confirmation(
+    state: &DaemonState,
+    events: &broadcast::Sender<DaemonEvent>,
+    identity: &ClientIdentity,
+    from_host: bool,
+    method: &str,
+    params: &Value,
+) -> Result<Option<RemoteActionConfirmation>, String> {
+    if from_host {
+        return Ok(None);
+    }
+    let Some(timeout) = state.remote_action_confirm_timeout() else {
+        return Ok(None);
+    };
+    let Some(summary) = remote_action_confirm_core::confirmation_summary(method, params) else {
+        return Ok(None);
+    };
+    let (client_id, client_name) = identity;
+    let confirmation = remote_action_confirm_core::request_confirmation(
+        &state.data_dir,
+        method,
+        summary,
+        client_id.clone(),
+        client_name.clone(),
+        timeout,
+    )?;
+    let _ = events.send(DaemonEvent::RemoteActionConfirmationRequested(
+        confirmation.clone(),
+    ));
+    Ok(Some(confirmation))
+}
 
-pub(super) async fn handle_client(
+/// Pushes `daemon-heartbeat` notifications so a watchdog holding this
+/// connection learns the daemon is alive without polling it.
+async fn send_heartbeats(out_tx: mpsc::UnboundedSender<String>, interval: Duration) {
+    let heartbeat = json!({
+        "method": "daemon-heartbeat",
+        "params": {
+            "pid": std::process::id(),
+            "version": env!("CARGO_PKG_VERSION"),
+        },
+    })
+    .to_string();
+    while out_tx.send(heartbeat.clone()).is_ok() {
+        tokio::time::sleep(interval).await;
+    }
+}
+
+/// Serves a new connection, upgrading it to WebSocket when the client opens
+/// with an HTTP `GET`. Clients that stay silent (event listeners on an
+/// unauthenticated daemon) fall back to the line protocol.
+pub(super) async fn accept_client(
     socket: TcpStream,
     config: Arc<DaemonConfig>,
     state: Arc<DaemonState>,
     events: broadcast::Sender<DaemonEvent>,
 ) {
-    let (reader, mut writer) = socket.into_split();
-    let mut lines = BufReader::new(reader).lines();
+    let peer_addr = socket.peer_addr().ok();
+    let mut first = [0u8; 1];
+    let is_http = matches!(
+        tokio::time::timeout(PROTOCOL_SNIFF_TIMEOUT, socket.peek(&mut first)).await,
+        Ok(Ok(1))
+    ) && first[0] == b'G';
+    if !is_http {
+        handle_client(socket, config, state, events, peer_addr).await;
+        return;
+    }
+
+    let Ok(websocket) = tokio_tungstenite::accept_async(socket).await else {
+        return;
+    };
+    let (client_end, daemon_end) = tokio::io::duplex(WEBSOCKET_PIPE_CAPACITY);
+    let bridge = tokio::spawn(bridge_websocket(websocket, client_end));
+    // WebSocket messages are already framed, and the bridge speaks lines.
+    serve_client(daemon_end, config, state, events, false, peer_addr).await;
+    let _ = bridge.await;
+}
+
+/// Maps WebSocket text frames onto the newline-delimited protocol so
+/// `handle_client` serves both transports unchanged.
+async fn bridge_websocket(websocket: WebSocketStream<TcpStream>, pipe: DuplexStream) {
+    let (mut sink, mut source) = websocket.split();
+    let (pipe_reader, mut pipe_writer) = tokio::io::split(pipe);
+
+    let inbound = async move {
+        while let Some(Ok(message)) = source.next().await {
+            let text = match message {
+                Message::Text(text) => text,
+                Message::Close(_) => break,
+                _ => continue,
+            };
+            if pipe_writer.write_all(text.as_bytes()).await.is_err()
+                || pipe_writer.write_all(b"\n").await.is_err()
+            {
+                break;
+            }
+        }
+        let _ = pipe_writer.shutdown().await;
+    };
+    let outbound = async move {
+        let mut lines = BufReader::new(pipe_reader).lines();
+        while let Ok(Some(line)) = lines.next_line().await {
+            if sink.send(Message::Text(line)).await.is_err() {
+                break;
+            }
+        }
+        let _ = sink.close().await;
+    };
+    futures_util::future::join(inbound, outbound).await;
+}
+
+pub(super) async fn handle_client<S>(
+    socket: S,
+    config: Arc<DaemonConfig>,
+    state: Arc<DaemonState>,
+    events: broadcast::Sender<DaemonEvent>,
+    peer_addr: Option<SocketAddr>,
+) where
+    S: AsyncRead + AsyncWrite + Send + 'static,
+{
+    serve_client(socket, config, state, events, true, peer_addr).await;
+}
+
+async fn serve_client<S>(
+    socket: S,
+    config: Arc<DaemonConfig>,
+    state: Arc<DaemonState>,
+    events: broadcast::Sender<DaemonEvent>,
+    framing_allowed: bool,
+    peer_addr: Option<SocketAddr>,
+) where
+    S: AsyncRead + AsyncWrite + Send + 'static,
+{
+    let (reader, mut writer) = tokio::io::split(socket);
+    let mut frames = FrameReader::new(reader, DEFAULT_MAX_FRAME_BYTES);
 
     let (out_tx, mut out_rx) = mpsc::unbounded_channel::<String>();
     let write_task = tokio::spawn(async move {
+        let mut framing = Framing::Lines;
         while let Some(message) = out_rx.recv().await {
-            if writer.write_all(message.as_bytes()).await.is_err() {
-                break;
+            if message == SWITCH_TO_FRAMES {
+                framing = Framing::LengthPrefixed;
+                continue;
             }
-            if writer.write_all(b"\n").await.is_err() {
+            if writer
+                .write_all(&encode_message(&message, framing))
+                .await
+                .is_err()
+            {
                 break;
             }
         }
     });
 
-    let mut authenticated = config.token.is_none();
+    let mut access = config.token.is_none().then_some(ClientAccess::Owner);
+    // Connections without a socket address are local pipes.
+    let loopback = peer_addr.is_none_or(|addr| addr.ip().is_loopback());
+    let auth_settings = state.daemon_auth_settings();
+    let tailnet_peer = match access {
+        Some(_) => None,
+        None => auth::tailnet_peer(&auth_settings, peer_addr).await,
+    };
+    // Approved peers need no token unless every provider must pass.
+    let tailnet_owner = access.is_none()
+        && auth::decide(&auth_settings, false, tailnet_peer.is_some(), loopback)
+            == AuthDecision::Tailnet;
+    if tailnet_owner {
+        access = Some(ClientAccess::Owner);
+    }
+    // Set once a token client negotiates signing in `auth`.
+    let mut signing: Option<SigningSession> = None;
+    let signing_required = auth_settings.require_request_signing
+        && config.token.is_some()
+        && !loopback
+        && !tailnet_owner;
+    let mut identity: ClientIdentity = (None, None);
     let mut events_task: Option<tokio::task::JoinHandle<()>> = None;
+    let mut heartbeat_task: Option<tokio::task::JoinHandle<()>> = None;
     let request_limiter = Arc::new(Semaphore::new(MAX_IN_FLIGHT_RPC_PER_CONNECTION));
     let client_version = format!("daemon-{}", env!("CARGO_PKG_VERSION"));
 
-    if authenticated {
+    if access.is_some() {
         let rx = events.subscribe();
         let out_tx_events = out_tx.clone();
         events_task = Some(tokio::spawn(forward_events(rx, out_tx_events)));
     }
 
-    while let Ok(Some(line)) = lines.next_line().await {
+    while let Ok(Some(line)) = frames.next_message().await {
         let line = line.trim();
         if line.is_empty() {
             continue;
@@ -55,25 +313,117 @@ pub(super) async fn handle_client(
             .to_string();
         let params = message.get("params").cloned().unwrap_or(Value::Null);
 
-        if !authenticated {
-            if method != "auth" {
-                if let Some(response) = build_error_response(id, "unauthorized") {
+        if method == RPC_FRAMING_METHOD {
+            let negotiated = framing_allowed
+                .then(|| {
+                    rpc_framing_core::negotiate_max_frame_bytes(&params, DEFAULT_MAX_FRAME_BYTES)
+                })
+                .flatten();
+            let Some(max_frame_bytes) = negotiated else {
+                if let Some(response) =
+                    build_error_response(id, "length-prefixed framing is not available")
+                {
                     let _ = out_tx.send(response);
                 }
                 continue;
+            };
+            if let Some(response) =
+                build_result_response(id, rpc_framing_core::framing_result(max_frame_bytes))
+            {
+                let _ = out_tx.send(response);
+                let _ = out_tx.send(SWITCH_TO_FRAMES.to_string());
+                frames.use_length_prefixed(max_frame_bytes);
             }
+            continue;
+        }
 
-            let expected = config.token.clone().unwrap_or_default();
-            let provided = parse_auth_token(&params).unwrap_or_default();
-            if expected != provided {
-                if let Some(response) = build_error_response(id, "invalid token") {
+        if method == daemon_identity_core::DAEMON_IDENTIFY_METHOD {
+            let response = match identify_daemon(&config, &params) {
+                Ok(result) => build_result_response(id, result),
+                Err(err) => build_error_response(id, &err),
+            };
+            if let Some(response) = response {
+                let _ = out_tx.send(response);
+            }
+            continue;
+        }
+
+        let Some(client_access) = access.as_ref() else {
+            if method != "auth" {
+                let error = MonitorError::Auth("unauthorized".to_string());
+                if let Some(response) = build_categorized_error_response(id, &error) {
                     let _ = out_tx.send(response);
                 }
                 continue;
             }
 
-            authenticated = true;
-            if let Some(response) = build_result_response(id, json!({ "ok": true })) {
+            let provided = parse_auth_token(&params).unwrap_or_default();
+            let granted = if config
+                .token
+                .as_deref()
+                .is_some_and(|owner| client_trust_core::owner_token_matches(owner, &provided))
+            {
+                Some((ClientAccess::Owner, json!({ "ok": true })))
+            } else {
+                guest_tokens_core::authenticate_guest_token(&state.app_data_dir, &provided).map(
+                    |guest| {
+                        (
+                            ClientAccess::Guest(guest.id),
+                            json!({
+                                "ok": true,
+                                "guest": true,
+                                "scopes": guest.scopes,
+                                "expiresAtMs": guest.expires_at_ms,
+                            }),
+                        )
+                    },
+                )
+            };
+            let decision = auth::decide(
+                &auth_settings,
+                granted.is_some(),
+                tailnet_peer.is_some(),
+                loopback,
+            );
+            let (granted, result) = match (decision, granted) {
+                (AuthDecision::Token, Some((granted, mut result))) => {
+                    if let Err(error) = admit_client(&state, &events, &params) {
+                        if let Some(response) = build_categorized_error_response(id, &error) {
+                            let _ = out_tx.send(response);
+                        }
+                        continue;
+                    }
+                    if params.get(REQUEST_SIGNING_PARAM).and_then(Value::as_bool) == Some(true) {
+                        let nonce = request_signing_core::new_session_nonce();
+                        signing = Some(SigningSession::new(&provided, &nonce));
+                        result["signing"] = json!({
+                            "nonce": nonce,
+                            "windowMs": SIGNATURE_WINDOW_MS,
+                        });
+                    }
+                    (granted, result)
+                }
+                (AuthDecision::Tailnet, _) => (
+                    ClientAccess::Owner,
+                    json!({ "ok": true, "tailnetPeer": tailnet_peer }),
+                ),
+                (_, granted) => {
+                    let message = if granted.is_some() {
+                        "connection is not from an approved tailnet peer"
+                    } else {
+                        "invalid token"
+                    };
+                    let error = MonitorError::Auth(message.to_string());
+                    if let Some(response) = build_categorized_error_response(id, &error) {
+                        let _ = out_tx.send(response);
+                    }
+                    continue;
+                }
+            };
+
+            access = Some(granted);
+            identity = client_identity(&params);
+            if let Some(response) = build_result_response(id, result) {
                 let _ = out_tx.send(response);
             }
 
@@ -82,8 +432,124 @@ pub(super) async fn handle_client(
             events_task = Some(tokio::spawn(forward_events(rx, out_tx_events)));
 
             continue;
+        };
+
+        // Clients configured with a token still send it to a peer that was
+        // already admitted on its tailnet identity.
+        if method == "auth" && tailnet_owner {
+            let result = json!({ "ok": true, "tailnetPeer": tailnet_peer });
+            if let Some(response) = build_result_response(id, result) {
+                let _ = out_tx.send(response);
+            }
+            continue;
+        }
+
+        let required_scope = state.required_method_scope(&method).await;
+        if required_scope != Some(guest_tokens_core::GUEST_SCOPE_READ) {
+            let verified = match signing.as_mut() {
+                Some(session) => session.verify(
+                    message.get(SIGNATURE_FIELD),
+                    &method,
+                    &params,
+                    request_signing_core::now_ms(),
+                ),
+                None if signing_required => Err(
+                    "this daemon requires signed requests; reconnect with request signing"
+                        .to_string(),
+                ),
+                None => Ok(()),
+            };
+            if let Err(err) = verified {
+                let error = MonitorError::Auth(err);
+                if let Some(response) = build_categorized_error_response(id, &error) {
+                    let _ = out_tx.send(response);
+                }
+                continue;
+            }
         }
 
+        if let ClientAccess::Guest(guest_id) = client_access {
+            if let Err(err) = guest_tokens_core::authorize_guest_scope(
+                &state.app_data_dir,
+                guest_id,
+                &method,
+                required_scope,
+            ) {
+                if let Some(response) = build_error_response(id, &err) {
+                    let _ = out_tx.send(response);
+                }
+                continue;
+            }
+        }
+
+        if method == HEARTBEAT_SUBSCRIBE_METHOD {
+            let interval_ms = params
+                .get("intervalMs")
+                .and_then(Value::as_u64)
+                .unwrap_or(DEFAULT_HEARTBEAT_INTERVAL_MS)
+                .clamp(MIN_HEARTBEAT_INTERVAL_MS, MAX_HEARTBEAT_INTERVAL_MS);
+            // Watchdogs only need liveness, not the app-server event stream.
+            if params.get("events").and_then(Value::as_bool) == Some(false) {
+                if let Some(task) = events_task.take() {
+                    task.abort();
+                }
+            }
+            if let Some(task) = heartbeat_task.take() {
+                task.abort();
+            }
+            heartbeat_task = Some(tokio::spawn(send_heartbeats(
+                out_tx.clone(),
+                Duration::from_millis(interval_ms),
+            )));
+            if let Some(response) = build_result_response(id, json!({ "intervalMs": interval_ms }))
+            {
+                let _ = out_tx.send(response);
+            }
+            continue;
+        }
+
+        let from_host = is_host_connection(&state, loopback, &identity);
+
+        // Decided here rather than by the dispatcher, which does not know
+        // whether the connection is the host's.
+        if method == DECIDE_REMOTE_ACTION_CONFIRMATION_METHOD {
+            let result = if from_host {
+                serde_json::from_value::<DecideRemoteActionConfirmationRequest>(params)
+                    .map_err(|err| err.to_string())
+                    .and_then(|request| {
+                        remote_action_confirm_core::decide_remote_action_confirmation_core(
+                            &state.data_dir,
+                            &request.id,
+                            request.approved,
+                        )
+                    })
+                    .and_then(|decided| {
+                        serde_json::to_value(decided).map_err(|err| err.to_string())
+                    })
+            } else {
+                Err("remote actions can only be confirmed on the host".to_string())
+            };
+            let response = match result {
+                Ok(result) => build_result_response(id, result),
+                Err(err) => build_error_response(id, &err),
+            };
+            if let Some(response) = response {
+                let _ = out_tx.send(response);
+            }
+            continue;
+        }
+
+        let confirmation =
+            match hold_for_confirmation(&state, &events, &identity, from_host, &method, &params) {
+                Ok(confirmation) => confirmation,
+                Err(err) => {
+                    if let Some(response) = build_error_response(id, &err) {
+                        let _ = out_tx.send(response);
+                    }
+                    continue;
+                }
+            };
+
         spawn_rpc_response_task(
             Arc::clone(&state),
             out_tx.clone(),
@@ -92,6 +558,7 @@ pub(super) async fn handle_client(
             params,
             client_version.clone(),
             Arc::clone(&request_limiter),
+            confirmation,
         );
     }
 
@@ -99,5 +566,8 @@ pub(super) async fn handle_client(
     if let Some(task) = events_task {
         task.abort();
     }
+    if let Some(task) = heartbeat_task {
+        task.abort();
+    }
     write_task.abort();
 }
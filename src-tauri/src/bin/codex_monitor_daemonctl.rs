@@ -1,5 +1,20 @@
+#[allow(dead_code)]
+#[path = "../shared/client_trust_core.rs"]
+mod client_trust_core;
 #[path = "../daemon_binary.rs"]
 mod daemon_binary;
+#[path = "../shared/daemon_data_dir_core.rs"]
+mod daemon_data_dir_core;
+#[path = "../shared/daemon_identity_core.rs"]
+mod daemon_identity_core;
+#[path = "../shared/monitor_error.rs"]
+mod monitor_error;
+#[path = "../shared/process_core.rs"]
+mod process_core;
+#[path = "../shared/process_env_core.rs"]
+mod process_env_core;
+#[path = "../shared/rpc_framing_core.rs"]
+mod rpc_framing_core;
 #[allow(dead_code)]
 #[path = "../storage.rs"]
 mod storage;
@@ -8,19 +23,26 @@ mod storage;
 mod types;
 
 use daemon_binary::resolve_daemon_binary_path;
+use monitor_error::MonitorError;
+use process_core::CommandPreview;
+use rpc_framing_core::{FrameReader, MIN_MAX_FRAME_BYTES};
 use serde_json::{json, Value};
+use std::collections::BTreeMap;
 use std::env;
 use std::net::SocketAddr;
 use std::path::{Path, PathBuf};
 use std::process::Stdio;
 use std::time::{Duration, SystemTime, UNIX_EPOCH};
-use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+use tokio::io::AsyncWriteExt;
 use tokio::net::tcp::{OwnedReadHalf, OwnedWriteHalf};
 use tokio::net::TcpStream;
 use tokio::process::Command;
 use tokio::time::{sleep, timeout, Instant};
 
-use types::{AppSettings, TailscaleDaemonCommandPreview, TcpDaemonState, TcpDaemonStatus};
+use types::{
+    AppSettings, PreviewShell, ProcessEnvConfig, TailscaleDaemonCommandPreview, TcpDaemonState,
+    TcpDaemonStatus,
+};
 
 const EXPECTED_DAEMON_NAME: &str = "codex-monitor-daemon";
 const EXPECTED_DAEMON_MODE: &str = "tcp";
@@ -69,7 +91,13 @@ enum DaemonProbe {
     NotDaemon,
 }
 
-type DaemonLines = tokio::io::Lines<BufReader<OwnedReadHalf>>;
+type DaemonLines = FrameReader<OwnedReadHalf>;
+
+/// Replies to daemonctl's requests are small, so a larger line means
+/// something other than the daemon answered.
+fn daemon_lines(reader: OwnedReadHalf) -> DaemonLines {
+    FrameReader::new(reader, MIN_MAX_FRAME_BYTES)
+}
 
 fn main() {
     let runtime = tokio::runtime::Builder::new_current_thread()
@@ -96,16 +124,33 @@ async fn run() -> Result<(), String> {
     } else {
         resolve_token(args.token.as_deref(), settings.as_ref())
     };
+    let daemon_dir = daemon_data_dir_core::resolve_daemon_data_dir(
+        &data_dir,
+        settings
+            .as_ref()
+            .and_then(|value| value.daemon_data_dir.as_deref()),
+    );
+    if let Err(err) = daemon_data_dir_core::migrate_daemon_state(&data_dir, &daemon_dir) {
+        eprintln!("Failed to move daemon state: {err}");
+    }
+    let identity = daemon_identity_core::read_daemon_identity(&daemon_dir);
+    client_trust_core::set_local_client(&data_dir);
+    let daemon_env = settings
+        .as_ref()
+        .map(|value| value.daemon_env.clone())
+        .unwrap_or_default();
 
     match args.command {
         CliCommand::CommandPreview => {
             let daemon_path = resolve_daemon_path(args.daemon_path.as_deref())?;
             let preview = daemon_command_preview(
                 &daemon_path,
+                &daemon_dir,
                 &data_dir,
                 token.is_some(),
                 &listen_addr,
                 args.insecure_no_auth,
+                process_env_core::env_preview(&process_env_core::effective_env(&daemon_env, None)),
             );
             if args.json {
                 println!(
@@ -117,11 +162,11 @@ async fn run() -> Result<(), String> {
             }
         }
         CliCommand::Status => {
-            let status = daemon_status(&listen_addr, token.as_deref()).await;
+            let status = daemon_status(&listen_addr, token.as_deref(), identity.as_deref()).await;
             print_status(&status, args.json)?;
         }
         CliCommand::Stop => {
-            let status = daemon_stop(&listen_addr, token.as_deref()).await;
+            let status = daemon_stop(&listen_addr, token.as_deref(), identity.as_deref()).await;
             print_status(&status, args.json)?;
             if !matches!(status.state, TcpDaemonState::Stopped) {
                 return Err(status
@@ -134,9 +179,12 @@ async fn run() -> Result<(), String> {
             let status = daemon_start(
                 &listen_addr,
                 token.as_deref(),
+                identity.as_deref(),
                 args.insecure_no_auth,
+                &daemon_dir,
                 &data_dir,
                 &daemon_path,
+                &daemon_env,
             )
             .await?;
             print_status(&status, args.json)?;
@@ -382,55 +430,39 @@ fn daemon_connect_addr(listen_addr: &str) -> Option<String> {
 fn daemon_command_preview(
     daemon_path: &Path,
     data_dir: &Path,
+    app_data_dir: &Path,
     token_configured: bool,
     listen_addr: &str,
     insecure_no_auth: bool,
+    env: BTreeMap<String, String>,
 ) -> TailscaleDaemonCommandPreview {
-    let daemon_path_str = daemon_path.to_string_lossy().to_string();
-    let data_dir_str = data_dir.to_string_lossy().to_string();
-
-    let args = if insecure_no_auth {
-        vec![
-            "--listen".to_string(),
-            listen_addr.to_string(),
-            "--data-dir".to_string(),
-            data_dir_str.clone(),
-            "--insecure-no-auth".to_string(),
-        ]
+    let preview = CommandPreview::new(daemon_path)
+        .arg("--listen")
+        .arg(listen_addr)
+        .arg("--data-dir")
+        .arg(data_dir)
+        .arg("--app-data-dir")
+        .arg(app_data_dir);
+    let preview = if insecure_no_auth {
+        preview.arg("--insecure-no-auth")
     } else {
-        vec![
-            "--listen".to_string(),
-            listen_addr.to_string(),
-            "--data-dir".to_string(),
-            data_dir_str.clone(),
-            "--token".to_string(),
-            REMOTE_TOKEN_PLACEHOLDER.to_string(),
-        ]
-    };
-
-    let mut rendered = Vec::with_capacity(args.len() + 1);
-    rendered.push(shell_quote(&daemon_path_str));
-    rendered.extend(args.iter().map(|value| shell_quote(value)));
+        preview.secret_arg("--token", REMOTE_TOKEN_PLACEHOLDER)
+    }
+    .envs(env)
+    .current_dir(std::env::current_dir().ok());
+    let shell = PreviewShell::native();
 
     TailscaleDaemonCommandPreview {
-        command: rendered.join(" "),
-        daemon_path: daemon_path_str,
-        args,
+        command: preview.render(shell),
+        shell,
+        daemon_path: preview.program().to_string(),
+        args: preview.args().to_vec(),
+        env: preview.env().clone(),
+        working_dir: preview.working_dir().map(str::to_string),
         token_configured,
     }
 }
 
-fn shell_quote(value: &str) -> String {
-    if value.is_empty() {
-        return "''".to_string();
-    }
-    if cfg!(windows) {
-        format!("\"{}\"", value.replace('"', "\\\""))
-    } else {
-        format!("'{}'", value.replace('\'', "'\"'\"'"))
-    }
-}
-
 fn trim_non_empty(value: Option<&str>) -> Option<String> {
     value
         .map(str::trim)
@@ -438,19 +470,6 @@ fn trim_non_empty(value: Option<&str>) -> Option<String> {
         .map(str::to_string)
 }
 
-fn parse_daemon_error_message(response: &Value) -> Option<String> {
-    response
-        .get("error")
-        .and_then(|error| error.get("message"))
-        .and_then(Value::as_str)
-        .map(str::to_string)
-}
-
-fn is_auth_error_message(message: &str) -> bool {
-    let lower = message.to_ascii_lowercase();
-    lower.contains("unauthorized") || lower.contains("invalid token")
-}
-
 fn parse_daemon_info(value: &Value) -> Result<DaemonInfo, String> {
     let name = value
         .get("name")
@@ -521,7 +540,7 @@ async fn read_rpc_response(lines: &mut DaemonLines, expected_id: u64) -> Result<
         }
         let remaining = deadline - now;
 
-        let line = match timeout(remaining, lines.next_line()).await {
+        let line = match timeout(remaining, lines.next_message()).await {
             Ok(Ok(Some(line))) => line,
             Ok(Ok(None)) => return Err("connection closed".to_string()),
             Ok(Err(err)) => return Err(err.to_string()),
@@ -545,16 +564,22 @@ async fn send_and_expect_result(
     id: u64,
     method: &str,
     params: Value,
-) -> Result<Value, String> {
-    send_rpc_request(writer, id, method, params).await?;
-    let response = read_rpc_response(lines, id).await?;
-    if let Some(message) = parse_daemon_error_message(&response) {
-        return Err(message);
+) -> Result<Value, MonitorError> {
+    send_rpc_request(writer, id, method, params)
+        .await
+        .map_err(MonitorError::Network)?;
+    let response = read_rpc_response(lines, id)
+        .await
+        .map_err(MonitorError::Network)?;
+    // The daemon tags each error with its kind; `from_rpc_error` only falls
+    // back to the wording for daemons that predate the tag.
+    if let Some(error) = response.get("error") {
+        return Err(MonitorError::from_rpc_error(error));
     }
     response
         .get("result")
         .cloned()
-        .ok_or_else(|| "daemon response missing result".to_string())
+        .ok_or_else(|| MonitorError::Process("daemon response missing result".to_string()))
 }
 
 async fn request_daemon_info(
@@ -566,7 +591,42 @@ async fn request_daemon_info(
     parse_daemon_info(&result)
 }
 
-async fn probe_daemon(listen_addr: &str, token: Option<&str>) -> DaemonProbe {
+const UNVERIFIED_DAEMON_ERROR: &str =
+    "Daemon is running, but this install has no daemon identity to check it against, so the token was not sent.";
+
+/// Challenges the listener to prove it holds the per-install daemon secret.
+/// Runs before any token is sent so an impostor on the port learns nothing;
+/// without a secret nothing vouches for the listener and no token is sent.
+async fn verify_daemon_identity(
+    writer: &mut OwnedWriteHalf,
+    lines: &mut DaemonLines,
+    secret: &str,
+) -> bool {
+    let challenge = daemon_identity_core::new_identity_challenge();
+    let Ok(result) = send_and_expect_result(
+        writer,
+        lines,
+        0,
+        daemon_identity_core::DAEMON_IDENTIFY_METHOD,
+        json!({ "challenge": challenge }),
+    )
+    .await
+    else {
+        return false;
+    };
+    result
+        .get("proof")
+        .and_then(Value::as_str)
+        .is_some_and(|proof| {
+            daemon_identity_core::verify_daemon_identity_proof(secret, &challenge, proof)
+        })
+}
+
+async fn probe_daemon(
+    listen_addr: &str,
+    token: Option<&str>,
+    identity: Option<&str>,
+) -> DaemonProbe {
     let Some(connect_addr) = daemon_connect_addr(listen_addr) else {
         return DaemonProbe::NotReachable;
     };
@@ -577,7 +637,13 @@ async fn probe_daemon(listen_addr: &str, token: Option<&str>) -> DaemonProbe {
     };
 
     let (reader, mut writer) = stream.into_split();
-    let mut lines = BufReader::new(reader).lines();
+    let mut lines = daemon_lines(reader);
+
+    if let Some(secret) = identity {
+        if !verify_daemon_identity(&mut writer, &mut lines, secret).await {
+            return DaemonProbe::NotDaemon;
+        }
+    }
 
     match send_and_expect_result(&mut writer, &mut lines, 1, "ping", json!({})).await {
         Ok(_) => DaemonProbe::Running {
@@ -585,10 +651,17 @@ async fn probe_daemon(listen_addr: &str, token: Option<&str>) -> DaemonProbe {
             auth_error: None,
             info: request_daemon_info(&mut writer, &mut lines, 2).await.ok(),
         },
-        Err(message) => {
-            if !is_auth_error_message(&message) {
+        Err(error) => {
+            if !matches!(error, MonitorError::Auth(_)) {
                 return DaemonProbe::NotDaemon;
             }
+            if identity.is_none() {
+                return DaemonProbe::Running {
+                    auth_ok: false,
+                    auth_error: Some(UNVERIFIED_DAEMON_ERROR.to_string()),
+                    info: None,
+                };
+            }
 
             let auth_token = token
                 .map(str::trim)
@@ -609,7 +682,7 @@ async fn probe_daemon(listen_addr: &str, token: Option<&str>) -> DaemonProbe {
                 &mut lines,
                 10,
                 "auth",
-                json!({ "token": auth_token }),
+                client_trust_core::client_auth_params(&auth_token),
             )
             .await
             {
@@ -632,7 +705,7 @@ async fn probe_daemon(listen_addr: &str, token: Option<&str>) -> DaemonProbe {
                     }
                 }
                 Err(auth_error) => {
-                    if is_auth_error_message(&auth_error) {
+                    if matches!(auth_error, MonitorError::Auth(_)) {
                         DaemonProbe::Running {
                             auth_ok: false,
                             auth_error: Some(format!(
@@ -649,7 +722,11 @@ async fn probe_daemon(listen_addr: &str, token: Option<&str>) -> DaemonProbe {
     }
 }
 
-async fn request_daemon_shutdown(listen_addr: &str, token: Option<&str>) -> Result<(), String> {
+async fn request_daemon_shutdown(
+    listen_addr: &str,
+    token: Option<&str>,
+    identity: Option<&str>,
+) -> Result<(), String> {
     let Some(connect_addr) = daemon_connect_addr(listen_addr) else {
         return Err("invalid daemon listen address".to_string());
     };
@@ -660,11 +737,22 @@ async fn request_daemon_shutdown(listen_addr: &str, token: Option<&str>) -> Resu
         .map_err(|err| format!("Failed to connect to daemon at {connect_addr}: {err}"))?;
 
     let (reader, mut writer) = stream.into_split();
-    let mut lines = BufReader::new(reader).lines();
+    let mut lines = daemon_lines(reader);
+
+    if let Some(secret) = identity {
+        if !verify_daemon_identity(&mut writer, &mut lines, secret).await {
+            return Err(format!(
+                "Process at {connect_addr} could not prove it is this install's daemon."
+            ));
+        }
+    }
 
     match send_and_expect_result(&mut writer, &mut lines, 1, "ping", json!({})).await {
         Ok(_) => {}
-        Err(message) if is_auth_error_message(&message) => {
+        Err(MonitorError::Auth(_)) => {
+            if identity.is_none() {
+                return Err(UNVERIFIED_DAEMON_ERROR.to_string());
+            }
             let auth_token = token
                 .map(str::trim)
                 .filter(|value| !value.is_empty())
@@ -676,13 +764,13 @@ async fn request_daemon_shutdown(listen_addr: &str, token: Option<&str>) -> Resu
                 &mut lines,
                 2,
                 "auth",
-                json!({ "token": auth_token }),
+                client_trust_core::client_auth_params(auth_token),
             )
             .await
             .map_err(|err| format!("Daemon authentication failed: {err}"))?;
         }
-        Err(message) => {
-            return Err(format!("Daemon ping failed: {message}"));
+        Err(error) => {
+            return Err(format!("Daemon ping failed: {error}"));
         }
     }
 
@@ -692,10 +780,14 @@ async fn request_daemon_shutdown(listen_addr: &str, token: Option<&str>) -> Resu
         .map_err(|err| format!("Daemon shutdown request failed: {err}"))
 }
 
-async fn wait_for_daemon_shutdown(listen_addr: &str, token: Option<&str>) -> bool {
+async fn wait_for_daemon_shutdown(
+    listen_addr: &str,
+    token: Option<&str>,
+    identity: Option<&str>,
+) -> bool {
     for _ in 0..20 {
         if matches!(
-            probe_daemon(listen_addr, token).await,
+            probe_daemon(listen_addr, token, identity).await,
             DaemonProbe::NotReachable
         ) {
             return true;
@@ -990,12 +1082,16 @@ async fn resolve_daemon_pid(listen_addr: &str, expected_pid: Option<u32>) -> Opt
     Some(pid)
 }
 
+#[allow(clippy::too_many_arguments)]
 async fn daemon_start(
     listen_addr: &str,
     token: Option<&str>,
+    identity: Option<&str>,
     insecure_no_auth: bool,
     data_dir: &Path,
+    app_data_dir: &Path,
     daemon_binary: &Path,
+    daemon_env: &ProcessEnvConfig,
 ) -> Result<TcpDaemonStatus, String> {
     if !insecure_no_auth && token.is_none() {
         return Err("Set a Remote backend token before starting mobile access daemon (or pass --insecure-no-auth for development).".to_string());
@@ -1004,7 +1100,7 @@ async fn daemon_start(
     parse_port_from_remote_host(listen_addr)
         .ok_or_else(|| format!("Invalid daemon listen address: {listen_addr}"))?;
 
-    match probe_daemon(listen_addr, token).await {
+    match probe_daemon(listen_addr, token, identity).await {
         DaemonProbe::Running {
             auth_ok,
             auth_error,
@@ -1031,12 +1127,20 @@ async fn daemon_start(
                     started_at_ms: None,
                     last_error: None,
                     listen_addr: Some(listen_addr.to_string()),
+                    firewall_trust: None,
+                    service_manager: None,
+                    operation: None,
+                    launch: None,
+                    managed_externally: false,
+                    last_stop: None,
+                    update_pending: false,
                 });
             }
 
             let force_kill_allowed = can_force_stop_daemon(auth_ok, info.as_ref());
             let pid_for_control = pid;
-            if let Err(shutdown_error) = request_daemon_shutdown(listen_addr, token).await {
+            if let Err(shutdown_error) = request_daemon_shutdown(listen_addr, token, identity).await
+            {
                 if !force_kill_allowed {
                     return Err(format!(
                         "{}; automatic restart aborted because daemon ownership could not be verified: {}",
@@ -1061,7 +1165,7 @@ async fn daemon_start(
                 }
             }
 
-            if !wait_for_daemon_shutdown(listen_addr, token).await {
+            if !wait_for_daemon_shutdown(listen_addr, token, identity).await {
                 if !force_kill_allowed {
                     return Err(format!(
                         "{}; daemon acknowledged shutdown but is still reachable",
@@ -1094,6 +1198,8 @@ async fn daemon_start(
     }
 
     ensure_listen_addr_available(listen_addr).await?;
+    daemon_identity_core::ensure_daemon_identity(data_dir)
+        .map_err(|err| format!("Failed to prepare daemon identity: {err}"))?;
 
     let mut command = Command::new(daemon_binary);
     command
@@ -1101,6 +1207,8 @@ async fn daemon_start(
         .arg(listen_addr)
         .arg("--data-dir")
         .arg(data_dir)
+        .arg("--app-data-dir")
+        .arg(app_data_dir)
         .stdin(Stdio::null())
         .stdout(Stdio::null())
         .stderr(Stdio::null());
@@ -1111,6 +1219,7 @@ async fn daemon_start(
         let token = token.ok_or_else(|| "Missing remote backend token".to_string())?;
         command.arg("--token").arg(token);
     }
+    process_env_core::apply_process_env(&mut command, daemon_env, None)?;
 
     let child = command
         .spawn()
@@ -1122,18 +1231,31 @@ async fn daemon_start(
         started_at_ms: Some(now_unix_ms()),
         last_error: None,
         listen_addr: Some(listen_addr.to_string()),
+        firewall_trust: None,
+        service_manager: None,
+        operation: None,
+        launch: None,
+        managed_externally: false,
+        last_stop: None,
+        update_pending: false,
     })
 }
 
-async fn daemon_stop(listen_addr: &str, token: Option<&str>) -> TcpDaemonStatus {
+async fn daemon_stop(
+    listen_addr: &str,
+    token: Option<&str>,
+    identity: Option<&str>,
+) -> TcpDaemonStatus {
     let mut stop_error: Option<String> = None;
 
     if let Some(port) = parse_port_from_remote_host(listen_addr) {
-        match probe_daemon(listen_addr, token).await {
+        match probe_daemon(listen_addr, token, identity).await {
             DaemonProbe::Running { auth_ok, info, .. } => {
                 let force_kill_allowed = can_force_stop_daemon(auth_ok, info.as_ref());
                 let expected_pid = info.as_ref().and_then(|value| value.pid);
-                if let Err(shutdown_error) = request_daemon_shutdown(listen_addr, token).await {
+                if let Err(shutdown_error) =
+                    request_daemon_shutdown(listen_addr, token, identity).await
+                {
                     let pid = resolve_daemon_pid(listen_addr, expected_pid).await;
                     if let Some(pid) = pid {
                         if force_kill_allowed {
@@ -1150,7 +1272,7 @@ async fn daemon_stop(listen_addr: &str, token: Option<&str>) -> TcpDaemonStatus
                     } else {
                         stop_error = Some(shutdown_error);
                     }
-                } else if !wait_for_daemon_shutdown(listen_addr, token).await {
+                } else if !wait_for_daemon_shutdown(listen_addr, token, identity).await {
                     if force_kill_allowed {
                         let pid = resolve_daemon_pid(listen_addr, expected_pid).await;
                         if let Some(pid) = pid {
@@ -1184,7 +1306,7 @@ async fn daemon_stop(listen_addr: &str, token: Option<&str>) -> TcpDaemonStatus
         }
     }
 
-    let probe_after_stop = probe_daemon(listen_addr, token).await;
+    let probe_after_stop = probe_daemon(listen_addr, token, identity).await;
     let pid_after_stop = resolve_daemon_pid(listen_addr, None).await;
 
     match probe_after_stop {
@@ -1198,6 +1320,13 @@ async fn daemon_stop(listen_addr: &str, token: Option<&str>) -> TcpDaemonStatus
                     .unwrap_or_else(|| "Daemon is still running after stop attempt.".to_string()),
             ),
             listen_addr: Some(listen_addr.to_string()),
+            firewall_trust: None,
+            service_manager: None,
+            operation: None,
+            launch: None,
+            managed_externally: false,
+            last_stop: None,
+            update_pending: false,
         },
         DaemonProbe::NotDaemon => TcpDaemonStatus {
             state: TcpDaemonState::Error,
@@ -1207,6 +1336,13 @@ async fn daemon_stop(listen_addr: &str, token: Option<&str>) -> TcpDaemonStatus
                 "Configured port is now occupied by a non-daemon process.".to_string()
             })),
             listen_addr: Some(listen_addr.to_string()),
+            firewall_trust: None,
+            service_manager: None,
+            operation: None,
+            launch: None,
+            managed_externally: false,
+            last_stop: None,
+            update_pending: false,
         },
         DaemonProbe::NotReachable => TcpDaemonStatus {
             state: TcpDaemonState::Stopped,
@@ -1214,20 +1350,38 @@ async fn daemon_stop(listen_addr: &str, token: Option<&str>) -> TcpDaemonStatus
             started_at_ms: None,
             last_error: stop_error,
             listen_addr: Some(listen_addr.to_string()),
+            firewall_trust: None,
+            service_manager: None,
+            operation: None,
+            launch: None,
+            managed_externally: false,
+            last_stop: None,
+            update_pending: false,
         },
     }
 }
 
-async fn daemon_status(listen_addr: &str, token: Option<&str>) -> TcpDaemonStatus {
+async fn daemon_status(
+    listen_addr: &str,
+    token: Option<&str>,
+    identity: Option<&str>,
+) -> TcpDaemonStatus {
     let pid = resolve_daemon_pid(listen_addr, None).await;
 
-    match probe_daemon(listen_addr, token).await {
+    match probe_daemon(listen_addr, token, identity).await {
         DaemonProbe::Running { auth_error, .. } => TcpDaemonStatus {
             state: TcpDaemonState::Running,
             pid,
             started_at_ms: None,
             last_error: auth_error,
             listen_addr: Some(listen_addr.to_string()),
+            firewall_trust: None,
+            service_manager: None,
+            operation: None,
+            launch: None,
+            managed_externally: false,
+            last_stop: None,
+            update_pending: false,
         },
         DaemonProbe::NotDaemon => TcpDaemonStatus {
             state: TcpDaemonState::Error,
@@ -1237,6 +1391,13 @@ async fn daemon_status(listen_addr: &str, token: Option<&str>) -> TcpDaemonStatu
                 "Configured daemon port {listen_addr} is occupied by a non-daemon process."
             )),
             listen_addr: Some(listen_addr.to_string()),
+            firewall_trust: None,
+            service_manager: None,
+            operation: None,
+            launch: None,
+            managed_externally: false,
+            last_stop: None,
+            update_pending: false,
         },
         DaemonProbe::NotReachable => TcpDaemonStatus {
             state: TcpDaemonState::Stopped,
@@ -1244,6 +1405,13 @@ async fn daemon_status(listen_addr: &str, token: Option<&str>) -> TcpDaemonStatu
             started_at_ms: None,
             last_error: None,
             listen_addr: Some(listen_addr.to_string()),
+            firewall_trust: None,
+            service_manager: None,
+            operation: None,
+            launch: None,
+            managed_externally: false,
+            last_stop: None,
+            update_pending: false,
         },
     }
 }
@@ -1259,7 +1427,9 @@ fn print_status(status: &TcpDaemonStatus, as_json: bool) -> Result<(), String> {
 
     let state = match status.state {
         TcpDaemonState::Stopped => "stopped",
+        TcpDaemonState::Starting => "starting",
         TcpDaemonState::Running => "running",
+        TcpDaemonState::Stopping => "stopping",
         TcpDaemonState::Error => "error",
     };
     println!("state: {state}");
@@ -1278,10 +1448,65 @@ fn print_status(status: &TcpDaemonStatus, as_json: bool) -> Result<(), String> {
 #[cfg(test)]
 mod tests {
     use super::{
-        daemon_connect_addr, daemon_listen_addr, local_listener_port, parse_netstat_listener_pid,
-        parse_port_from_remote_host, parse_ss_listener_pid, resolve_listen_addr, safe_force_stop_pid,
-        shell_quote,
+        daemon_command_preview, daemon_connect_addr, daemon_identity_core, daemon_listen_addr,
+        local_listener_port, parse_netstat_listener_pid, parse_port_from_remote_host,
+        parse_ss_listener_pid, probe_daemon, resolve_listen_addr, safe_force_stop_pid, DaemonProbe,
     };
+    use serde_json::{json, Value};
+    use std::collections::BTreeMap;
+    use std::path::Path;
+    use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+    use tokio::net::TcpListener;
+
+    /// Answers every request successfully, proving identity with `secret`.
+    async fn spawn_fake_daemon(secret: &'static str) -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").await.expect("bind");
+        let addr = listener.local_addr().expect("local addr").to_string();
+        tokio::spawn(async move {
+            while let Ok((socket, _)) = listener.accept().await {
+                let (reader, mut writer) = socket.into_split();
+                let mut lines = BufReader::new(reader).lines();
+                while let Ok(Some(line)) = lines.next_line().await {
+                    let request: Value = serde_json::from_str(&line).expect("json");
+                    let result = match request["params"]["challenge"].as_str() {
+                        Some(challenge) => json!({
+                            "proof": daemon_identity_core::daemon_identity_proof(secret, challenge)
+                                .expect("proof"),
+                        }),
+                        None => json!({}),
+                    };
+                    let response = json!({ "id": request["id"], "result": result });
+                    if writer
+                        .write_all(format!("{response}\n").as_bytes())
+                        .await
+                        .is_err()
+                    {
+                        break;
+                    }
+                }
+            }
+        });
+        addr
+    }
+
+    #[test]
+    fn probe_rejects_listeners_that_cannot_prove_identity() {
+        tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .expect("runtime")
+            .block_on(async {
+                let addr = spawn_fake_daemon("install-secret").await;
+                assert!(matches!(
+                    probe_daemon(&addr, Some("token"), Some("install-secret")).await,
+                    DaemonProbe::Running { auth_ok: true, .. }
+                ));
+                assert!(matches!(
+                    probe_daemon(&addr, Some("token"), Some("other-secret")).await,
+                    DaemonProbe::NotDaemon
+                ));
+            });
+    }
 
     #[test]
     fn parses_listen_port_from_host() {
@@ -1308,23 +1533,35 @@ mod tests {
     }
 
     #[test]
-    fn shell_quote_handles_single_quotes() {
-        let rendered = shell_quote("abc'def");
-        if cfg!(windows) {
-            assert_eq!(rendered, "\"abc'def\"");
-        } else {
-            assert_eq!(rendered, "'abc'\"'\"'def'");
-        }
-    }
-
-    #[test]
-    fn shell_quote_handles_double_quotes() {
-        let rendered = shell_quote("abc\"def");
-        if cfg!(windows) {
-            assert_eq!(rendered, "\"abc\\\"def\"");
-        } else {
-            assert_eq!(rendered, "'abc\"def'");
-        }
+    fn command_preview_masks_the_token_unless_insecure() {
+        let preview = daemon_command_preview(
+            Path::new("/opt/daemon"),
+            Path::new("/tmp/it's data/daemon"),
+            Path::new("/tmp/it's data"),
+            true,
+            "0.0.0.0:4800",
+            false,
+            BTreeMap::from([("GH_TOKEN".to_string(), "ghp_live".to_string())]),
+        );
+        assert!(preview.command.contains("<remote-backend-token>"));
+        assert!(!preview.command.contains("ghp_live"));
+        assert_eq!(preview.args[1], "0.0.0.0:4800");
+        assert_eq!(preview.args[3], "/tmp/it's data/daemon");
+        assert_eq!(preview.args[5], "/tmp/it's data");
+
+        let insecure = daemon_command_preview(
+            Path::new("/opt/daemon"),
+            Path::new("/tmp/data/daemon"),
+            Path::new("/tmp/data"),
+            false,
+            "0.0.0.0:4800",
+            true,
+            BTreeMap::new(),
+        );
+        assert_eq!(
+            insecure.args.last().map(String::as_str),
+            Some("--insecure-no-auth")
+        );
     }
 
     #[test]
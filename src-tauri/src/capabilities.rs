@@ -0,0 +1,656 @@
+//! Every Tauri command the app registers, with enough metadata for the
+//! command palette and the mobile client to discover them at runtime.
+//!
+//! `registered_commands!` is the single list: `lib.rs` feeds it to
+//! `command_handler!` to build the invoke handler, and `list_capabilities`
+//! feeds it to `capability_list!`. Entries read
+//! `remote|local module::command(params) [as "daemon_method"];`, where
+//! `remote` commands forward to the daemon method of the same name (or the
+//! one after `as`) in remote mode.
+
+use crate::shared::guest_tokens_core::{self, GUEST_SCOPE_READ};
+use crate::types::{CommandCapability, CommandParam, CommandPermission};
+
+macro_rules! registered_commands {
+    ($callback:ident) => {
+        $callback! {
+            local settings::get_app_settings();
+            local settings::update_app_settings(settings: AppSettings);
+            remote settings::test_redaction_rules(
+                sample: String,
+                settings: Option<RedactionSettings>,
+            );
+            local settings::migrate_data_dir(new_path: String, overwrite: Option<bool>);
+            local settings::create_backup(path: String, passphrase: Option<String>);
+            local settings::restore_backup(path: String, passphrase: Option<String>);
+            local settings::sync_settings_now();
+            remote settings::get_codex_config_path();
+            remote settings::get_codex_home();
+            remote settings::set_codex_home_override(path: Option<String>);
+            remote settings::snapshot_codex_home(
+                label: Option<String>,
+                exclude_secrets: Option<bool>,
+            );
+            remote settings::list_codex_home_snapshots();
+            remote settings::restore_codex_home_snapshot(id: String);
+            remote settings::list_config_changes(page: Option<Page>);
+            remote settings::revert_config_change(id: String);
+            local settings::test_proxy(proxy: ProxySettings, target: Option<String>);
+            remote files::file_read(scope: FileScope, kind: FileKind, workspace_id: Option<String>);
+            remote files::file_write(
+                scope: FileScope,
+                kind: FileKind,
+                workspace_id: Option<String>,
+                content: String,
+            );
+            local files::read_image_as_data_url(path: String);
+            local files::write_text_file(path: String, content: String);
+            remote files::remote_list_dir(workspace_id: String, rel_path: Option<String>);
+            remote files::remote_read_file(
+                workspace_id: String,
+                rel_path: String,
+                offset: Option<u64>,
+                length: Option<u64>,
+            );
+            remote codex::get_config_model(workspace_id: String);
+            local menu::menu_set_accelerators(updates: Vec<MenuAcceleratorUpdate>);
+            local tray::set_tray_recent_threads(entries: Vec<TrayRecentThreadEntry>);
+            local tray::set_tray_session_usage(usage: Option<TraySessionUsage>);
+            local tray::get_tray_summary();
+            local window_state::get_window_state(label: Option<String>);
+            local window_state::reset_window_state(label: Option<String>);
+            local shortcuts::list_registered_shortcuts();
+            local window::get_activity_alert();
+            local window::dismiss_activity_alert(open: Option<bool>);
+            local deep_link::take_deep_link_project();
+            remote codex::codex_doctor(codex_bin: Option<String>, codex_args: Option<String>);
+            remote codex::codex_auth_status();
+            remote codex::codex_login_start();
+            remote codex::codex_login_flow_status();
+            remote codex::codex_login_flow_cancel();
+            local codex::codex_update(codex_bin: Option<String>, codex_args: Option<String>);
+            remote workspaces::list_workspaces();
+            remote workspaces::is_workspace_path_dir(path: String);
+            remote workspaces::add_workspace(path: String);
+            remote workspaces::add_workspace_from_git_url(
+                url: String,
+                destination_path: String,
+                target_folder_name: Option<String>,
+            );
+            remote workspaces::add_clone(
+                source_workspace_id: String,
+                copy_name: String,
+                copies_folder: String,
+            );
+            remote workspaces::add_worktree(
+                parent_id: String,
+                branch: String,
+                name: Option<String>,
+                copy_agents_md: Option<bool>,
+            );
+            remote workspaces::worktree_setup_status(workspace_id: String);
+            remote workspaces::worktree_setup_mark_ran(workspace_id: String);
+            remote workspaces::remove_workspace(id: String);
+            remote workspaces::remove_worktree(id: String);
+            remote workspaces::rename_worktree(id: String, branch: String);
+            remote workspaces::rename_worktree_upstream(
+                id: String,
+                old_branch: String,
+                new_branch: String,
+            );
+            remote workspaces::apply_worktree_changes(workspace_id: String);
+            remote workspaces::update_workspace_settings(id: String, settings: WorkspaceSettings);
+            remote workspaces::set_workspace_runtime_codex_args(
+                workspace_id: String,
+                codex_args: Option<String>,
+            );
+            remote codex::start_thread(workspace_id: String);
+            remote codex::send_user_message(
+                workspace_id: String,
+                thread_id: String,
+                text: String,
+                model: Option<String>,
+                effort: Option<String>,
+                service_tier: Option<Option<String>>,
+                access_mode: Option<String>,
+                images: Option<Vec<String>>,
+                app_mentions: Option<Vec<Value>>,
+                collaboration_mode: Option<Value>,
+            );
+            remote codex::turn_steer(
+                workspace_id: String,
+                thread_id: String,
+                turn_id: String,
+                text: String,
+                images: Option<Vec<String>>,
+                app_mentions: Option<Vec<Value>>,
+            );
+            remote codex::turn_interrupt(workspace_id: String, thread_id: String, turn_id: String);
+            remote codex::start_review(
+                workspace_id: String,
+                thread_id: String,
+                target: Value,
+                delivery: Option<String>,
+            );
+            remote codex::respond_to_server_request(
+                workspace_id: String,
+                request_id: Value,
+                result: Value,
+            );
+            remote codex::remember_approval_rule(workspace_id: String, command: Vec<String>);
+            remote codex::generate_commit_message(
+                workspace_id: String,
+                commit_message_model_id: Option<String>,
+            );
+            remote codex::generate_run_metadata(workspace_id: String, prompt: String);
+            remote codex::generate_agent_description(workspace_id: String, description: String);
+            remote codex::resume_thread(workspace_id: String, thread_id: String);
+            remote codex::read_thread(workspace_id: String, thread_id: String);
+            remote codex::thread_live_subscribe(workspace_id: String, thread_id: String);
+            remote codex::thread_live_unsubscribe(workspace_id: String, thread_id: String);
+            remote codex::fork_thread(workspace_id: String, thread_id: String);
+            remote codex::list_threads(
+                workspace_id: String,
+                cursor: Option<String>,
+                limit: Option<u32>,
+                sort_key: Option<String>,
+            );
+            remote codex::list_mcp_server_status(
+                workspace_id: String,
+                cursor: Option<String>,
+                limit: Option<u32>,
+            );
+            remote codex::archive_thread(workspace_id: String, thread_id: String);
+            remote codex::compact_thread(workspace_id: String, thread_id: String);
+            remote codex::set_thread_name(workspace_id: String, thread_id: String, name: String);
+            remote codex::collaboration_mode_list(workspace_id: String);
+            remote workspaces::connect_workspace(id: String);
+            remote git::get_git_status(workspace_id: String);
+            remote git::init_git_repo(workspace_id: String, branch: String, force: Option<bool>);
+            remote git::create_github_repo(
+                workspace_id: String,
+                repo: String,
+                visibility: String,
+                branch: Option<String>,
+            );
+            remote git::list_git_roots(workspace_id: String, depth: Option<usize>);
+            remote git::get_git_diffs(workspace_id: String);
+            remote git::get_git_log(workspace_id: String, limit: Option<usize>);
+            remote git::get_git_commit_diff(workspace_id: String, sha: String);
+            remote git::get_git_remote(workspace_id: String);
+            remote git::stage_git_file(workspace_id: String, path: String);
+            remote git::stage_git_all(workspace_id: String);
+            remote git::unstage_git_file(workspace_id: String, path: String);
+            remote git::revert_git_file(workspace_id: String, path: String);
+            remote git::revert_git_all(workspace_id: String);
+            remote git::commit_git(workspace_id: String, message: String);
+            remote git::push_git(workspace_id: String);
+            remote git::pull_git(workspace_id: String);
+            remote git::fetch_git(workspace_id: String);
+            remote git::sync_git(workspace_id: String);
+            remote git::get_github_issues(workspace_id: String);
+            remote git::get_github_pull_requests(workspace_id: String);
+            remote git::get_github_pull_request_diff(workspace_id: String, pr_number: u64);
+            remote git::get_github_pull_request_comments(workspace_id: String, pr_number: u64);
+            remote git::checkout_github_pull_request(workspace_id: String, pr_number: u64);
+            remote workspaces::list_workspace_files(workspace_id: String);
+            remote workspaces::read_workspace_file(workspace_id: String, path: String);
+            local workspaces::open_workspace_in(
+                path: String,
+                app: Option<String>,
+                args: Vec<String>,
+                command: Option<String>,
+                line: Option<u32>,
+                column: Option<u32>,
+            );
+            local workspaces::get_open_app_icon(app_name: String);
+            remote git::list_git_branches(workspace_id: String);
+            remote git::checkout_git_branch(workspace_id: String, name: String);
+            remote git::create_git_branch(workspace_id: String, name: String);
+            remote codex::model_list(workspace_id: String);
+            remote codex::experimental_feature_list(
+                workspace_id: String,
+                cursor: Option<String>,
+                limit: Option<u32>,
+            );
+            remote codex::set_codex_feature_flag(feature_key: String, enabled: bool);
+            remote codex::get_agents_settings();
+            remote codex::set_agents_core_settings(input: agents_config_core::SetAgentsCoreInput);
+            remote codex::create_agent(input: agents_config_core::CreateAgentInput);
+            remote codex::update_agent(input: agents_config_core::UpdateAgentInput);
+            remote codex::delete_agent(input: agents_config_core::DeleteAgentInput);
+            remote codex::read_agent_config_toml(agent_name: String);
+            remote codex::write_agent_config_toml(agent_name: String, content: String);
+            remote codex::account_rate_limits(workspace_id: String);
+            remote codex::account_read(workspace_id: String);
+            remote codex::codex_login(workspace_id: String);
+            remote codex::codex_login_cancel(workspace_id: String);
+            remote codex::skills_list(workspace_id: String);
+            remote codex::apps_list(
+                workspace_id: String,
+                cursor: Option<String>,
+                limit: Option<u32>,
+                thread_id: Option<String>,
+            );
+            remote prompts::prompts_list(workspace_id: String);
+            remote prompts::prompts_create(
+                workspace_id: String,
+                scope: String,
+                name: String,
+                description: Option<String>,
+                argument_hint: Option<String>,
+                content: String,
+            );
+            remote prompts::prompts_update(
+                workspace_id: String,
+                path: String,
+                name: String,
+                description: Option<String>,
+                argument_hint: Option<String>,
+                content: String,
+            );
+            remote prompts::prompts_delete(workspace_id: String, path: String);
+            remote prompts::prompts_move(workspace_id: String, path: String, scope: String);
+            remote prompts::prompts_workspace_dir(workspace_id: String);
+            remote prompts::prompts_global_dir(workspace_id: String);
+            local terminal::terminal_open(
+                workspace_id: String,
+                terminal_id: String,
+                cols: u16,
+                rows: u16,
+            );
+            local terminal::terminal_write(workspace_id: String, terminal_id: String, data: String);
+            local terminal::terminal_resize(
+                workspace_id: String,
+                terminal_id: String,
+                cols: u16,
+                rows: u16,
+            );
+            local terminal::terminal_close(workspace_id: String, terminal_id: String);
+            local dictation::dictation_model_status(model_id: Option<String>);
+            local dictation::dictation_download_model(model_id: Option<String>);
+            local dictation::dictation_cancel_download(model_id: Option<String>);
+            local dictation::dictation_remove_model(model_id: Option<String>);
+            local dictation::dictation_start(preferred_language: Option<String>);
+            local dictation::dictation_request_permission();
+            local dictation::dictation_stop();
+            local dictation::dictation_cancel();
+            remote local_usage::local_usage_snapshot(
+                days: Option<u32>,
+                workspace_path: Option<String>,
+            );
+            remote local_usage::generate_activity_report(
+                range: ActivityReportRange,
+                format: ActivityReportFormat,
+                save: Option<bool>,
+            ) as "activity_report";
+            remote host_capabilities::get_host_capabilities() as "host_capabilities";
+            remote remote_command::run_remote_command(
+                workspace_id: String,
+                command: String,
+                cwd: Option<String>,
+                run_id: Option<String>,
+            );
+            remote remote_command::list_remote_command_audit(page: Option<Page>);
+            remote sessions::search_sessions(query: String, filters: Option<SessionSearchFilters>);
+            remote sessions::archive_sessions(before: Option<i64>, dry_run: Option<bool>);
+            remote sessions::list_archives(page: Option<Page>);
+            remote sessions::restore_archive(name: String);
+            remote sessions::start_codex_session(
+                project_path: Option<String>,
+                prompt: Option<String>,
+                model: Option<String>,
+                approval_mode: Option<String>,
+                template_id: Option<String>,
+                isolate_worktree: Option<bool>,
+            );
+            remote sessions::merge_session_worktree(session_id: String);
+            remote sessions::discard_session_worktree(session_id: String);
+            remote ci_status::get_project_ci_status(project_id: String);
+            remote sessions::create_pr_from_session(
+                session_id: String,
+                title: String,
+                body: Option<String>,
+                base: Option<String>,
+            );
+            remote editor::list_editors();
+            remote editor::open_in_editor(path: String, line: Option<u32>);
+            remote editor::open_diff_in_editor(session_id: String, file: String);
+            remote plugins::list_plugins();
+            remote plugins::enable_plugin(id: String, enabled: bool);
+            remote sessions::send_session_input(session_id: String, text: String);
+            remote sessions::list_codex_sessions(
+                tags: Option<Vec<String>>,
+                page: Option<Page>,
+            );
+            remote sessions::get_codex_session(session_id: String);
+            remote sessions::export_session(
+                session_id: String,
+                format: SessionExportFormat,
+                include_tool_output: Option<bool>,
+            );
+            remote sessions::get_process_stats(pid: Option<u32>);
+            remote sessions::cancel_queued_session(session_id: String);
+            remote sessions::interrupt_session(session_id: String);
+            remote sessions::terminate_session(
+                session_id: String,
+                pid: Option<u32>,
+                force: Option<bool>,
+            );
+            remote guest_tokens::create_guest_token(
+                scopes: Vec<String>,
+                ttl_seconds: u64,
+                label: Option<String>,
+            );
+            remote guest_tokens::list_guest_tokens();
+            remote guest_tokens::revoke_guest_token(id: String);
+            remote client_approvals::list_client_approvals();
+            remote client_approvals::decide_client_approval(client_id: String, approved: bool);
+            remote remote_action_confirmations::list_remote_action_confirmations();
+            remote remote_action_confirmations::decide_remote_action_confirmation(
+                id: String,
+                approved: bool,
+            );
+            remote sessions::list_session_templates();
+            remote sessions::save_session_template(template: SessionTemplateInput);
+            remote sessions::delete_session_template(id: String);
+            remote tags::list_tags();
+            remote tags::save_tag(tag: TagInput);
+            remote tags::delete_tag(id: String);
+            remote tags::set_tags(target: TagTarget, id: String, tag_ids: Vec<String>);
+            remote tags::get_tag_assignments();
+            remote sessions::list_schedules();
+            remote sessions::upsert_schedule(schedule: ScheduleInput);
+            remote sessions::run_schedule_now(id: String);
+            local notifications::is_macos_debug_build();
+            local notifications::app_build_type();
+            local notifications::send_notification_fallback(title: String, body: String);
+            local notifications::should_show_notification(urgent: Option<bool>);
+            local notifications::notifications_snooze(minutes: u32);
+            local tailscale::tailscale_status(force_refresh: Option<bool>);
+            local tailscale::tailscale_daemon_command_preview();
+            local tailscale::tailscale_daemon_identity();
+            local tailscale::tailscale_daemon_start(takeover: Option<bool>);
+            local tailscale::tailscale_daemon_stop();
+            local tailscale::tailscale_daemon_restart();
+            local tailscale::tailscale_daemon_apply_update();
+            local tailscale::tailscale_daemon_status(verbose: Option<bool>);
+            local tailscale::adopt_external_daemon();
+            local tailscale::tailscale_daemon_preflight();
+            local tailscale::tailscale_daemon_register_firewall(verify_signature: Option<bool>);
+            local tailscale::install_daemon_service();
+            local tailscale::uninstall_daemon_service();
+            local tailscale::probe_remote_quality(pings: Option<u32>);
+            local tailscale::check_tailnet_reachability(peer: Option<String>);
+            local tailscale::tailscale_ping_peer(target: String);
+            local tailscale::tailscale_ping_history(target: Option<String>);
+            local tailscale::set_mock_backend_script(script: MockBackendScript);
+            local remote_backend::remote_backend_status();
+            local remote_backend::get_remote_backend_metrics();
+            local overview::get_monitor_overview();
+            local storage_health::get_storage_health();
+            local storage_health::clean_storage(targets: Vec<StorageTarget>);
+            local webhooks::list_webhook_deliveries(
+                endpoint_id: Option<String>,
+                page: Option<Page>,
+            );
+            local webhooks::send_test_message(integration: String);
+            local hooks::list_hook_runs(hook_id: Option<String>, page: Option<Page>);
+            local capabilities::list_capabilities();
+            local platform::get_platform_capabilities();
+            local startup::get_startup_report();
+            local crate::is_mobile_runtime();
+        }
+    };
+}
+
+/// Builds the invoke handler from `registered_commands!`.
+macro_rules! command_handler {
+    ($($availability:ident $module:ident :: $command:ident (
+        $($params:tt)*
+    ) $(as $method:literal)?;)*) => {
+        tauri::generate_handler![$($module::$command),*]
+    };
+}
+
+macro_rules! capability_list {
+    ($($availability:ident $module:ident :: $command:ident (
+        $($param:ident : $ty:ty),* $(,)?
+    ) $(as $method:literal)?;)*) => {
+        vec![$(
+            capability(
+                stringify!($module),
+                stringify!($command),
+                stringify!($availability),
+                [$($method,)? stringify!($command)][0],
+                vec![$(param(stringify!($param), stringify!($ty))),*],
+            )
+        ),*]
+    };
+}
+
+fn camel_case(name: &str) -> String {
+    let mut camel = String::with_capacity(name.len());
+    let mut upper = false;
+    for ch in name.chars() {
+        if ch == '_' {
+            upper = !camel.is_empty();
+        } else if upper {
+            camel.push(ch.to_ascii_uppercase());
+            upper = false;
+        } else {
+            camel.push(ch);
+        }
+    }
+    camel
+}
+
+fn param(name: &str, rust_type: &str) -> CommandParam {
+    let rust_type: String = rust_type.split_whitespace().collect();
+    CommandParam {
+        name: camel_case(name),
+        required: !rust_type.starts_with("Option<"),
+        rust_type,
+    }
+}
+
+fn capability(
+    module: &str,
+    name: &str,
+    availability: &str,
+    method: &str,
+    params: Vec<CommandParam>,
+) -> CommandCapability {
+    let remote = availability == "remote";
+    let permission = if !remote {
+        CommandPermission::Local
+    } else {
+        match guest_tokens_core::required_guest_scope(method) {
+            None => CommandPermission::Owner,
+            Some(GUEST_SCOPE_READ) => CommandPermission::Read,
+            Some(_) => CommandPermission::Write,
+        }
+    };
+    CommandCapability {
+        name: name.to_string(),
+        module: if module == "crate" { "app" } else { module }.to_string(),
+        params,
+        permission,
+        remote_method: remote.then(|| method.to_string()),
+    }
+}
+
+pub(crate) fn capabilities() -> Vec<CommandCapability> {
+    registered_commands!(capability_list)
+}
+
+#[tauri::command]
+pub(crate) fn list_capabilities() -> Vec<CommandCapability> {
+    capabilities()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::{HashMap, HashSet};
+    use std::path::Path;
+
+    use super::*;
+
+    const INJECTED_TYPES: &[&str] = &[
+        "State<",
+        "tauri::State<",
+        "AppHandle",
+        "tauri::AppHandle",
+        "Window",
+        "tauri::Window",
+        "WebviewWindow",
+        "tauri::WebviewWindow",
+    ];
+
+    fn collect_sources(dir: &Path, sources: &mut Vec<String>) {
+        for entry in std::fs::read_dir(dir).expect("read src dir") {
+            let path = entry.expect("dir entry").path();
+            if path.is_dir() {
+                if path.file_name().is_some_and(|name| name != "bin") {
+                    collect_sources(&path, sources);
+                }
+            } else if path.extension().is_some_and(|ext| ext == "rs") {
+                sources.push(std::fs::read_to_string(&path).expect("read source"));
+            }
+        }
+    }
+
+    /// Finds every `#[tauri::command]` function and its frontend-visible
+    /// parameters as `name -> [(camelName, type)]`.
+    fn scanned_commands() -> HashMap<String, Vec<(String, String)>> {
+        let mut sources = Vec::new();
+        collect_sources(
+            &Path::new(env!("CARGO_MANIFEST_DIR")).join("src"),
+            &mut sources,
+        );
+        let mut commands = HashMap::new();
+        for source in sources {
+            for chunk in source.split("#[tauri::command]").skip(1) {
+                // Skip mentions of the attribute that are not on an item.
+                if !chunk.starts_with('\n') {
+                    continue;
+                }
+                let Some(fn_start) = chunk.find("fn ") else {
+                    continue;
+                };
+                let rest = &chunk[fn_start + 3..];
+                let name_end = rest.find(['(', '<']).expect("command name");
+                let name = rest[..name_end].trim().to_string();
+                let open = rest.find('(').expect("param list");
+                let mut depth = 0;
+                let mut close = open;
+                for (index, ch) in rest[open..].char_indices() {
+                    match ch {
+                        '(' => depth += 1,
+                        ')' => depth -= 1,
+                        _ => {}
+                    }
+                    if depth == 0 {
+                        close = open + index;
+                        break;
+                    }
+                }
+                let mut params = Vec::new();
+                let mut depth = 0;
+                let mut current = String::new();
+                for ch in rest[open + 1..close].chars().chain([',']) {
+                    match ch {
+                        '<' | '(' => depth += 1,
+                        '>' | ')' => depth -= 1,
+                        ',' if depth == 0 => {
+                            let param = std::mem::take(&mut current);
+                            let Some((name, ty)) = param.split_once(':') else {
+                                continue;
+                            };
+                            let ty: String = ty.split_whitespace().collect();
+                            if INJECTED_TYPES.iter().any(|prefix| ty.starts_with(prefix)) {
+                                continue;
+                            }
+                            let name = name
+                                .trim()
+                                .trim_start_matches("mut ")
+                                .trim_start_matches('_');
+                            params.push((camel_case(name), ty));
+                            continue;
+                        }
+                        _ => {}
+                    }
+                    current.push(ch);
+                }
+                commands.insert(name, params);
+            }
+        }
+        commands
+    }
+
+    #[test]
+    fn registry_lists_every_command_once() {
+        let registered = capabilities();
+        let mut names = HashSet::new();
+        for capability in &registered {
+            assert!(
+                names.insert(capability.name.clone()),
+                "{} registered twice",
+                capability.name
+            );
+        }
+
+        let scanned = scanned_commands();
+        for (name, params) in &scanned {
+            let capability = registered
+                .iter()
+                .find(|capability| &capability.name == name)
+                .unwrap_or_else(|| panic!("{name} is a command but is not registered"));
+            let listed: Vec<(String, String)> = capability
+                .params
+                .iter()
+                .map(|param| (param.name.clone(), param.rust_type.clone()))
+                .collect();
+            assert_eq!(&listed, params, "params for {name} drifted");
+        }
+        assert_eq!(registered.len(), scanned.len());
+    }
+
+    #[test]
+    fn capabilities_report_permissions_and_remote_methods() {
+        let registered = capabilities();
+        let find = |name: &str| {
+            registered
+                .iter()
+                .find(|capability| capability.name == name)
+                .unwrap_or_else(|| panic!("{name} missing"))
+        };
+
+        let start_thread = find("start_thread");
+        assert_eq!(start_thread.remote_method.as_deref(), Some("start_thread"));
+        assert_eq!(start_thread.permission, CommandPermission::Write);
+        assert_eq!(start_thread.params[0].name, "workspaceId");
+        assert!(start_thread.params[0].required);
+
+        let report = find("generate_activity_report");
+        assert_eq!(report.remote_method.as_deref(), Some("activity_report"));
+        assert!(
+            !report
+                .params
+                .iter()
+                .find(|param| param.name == "save")
+                .unwrap()
+                .required
+        );
+
+        let settings = find("get_app_settings");
+        assert_eq!(settings.permission, CommandPermission::Local);
+        assert_eq!(settings.remote_method, None);
+
+        assert_eq!(find("is_mobile_runtime").module, "app");
+        assert_eq!(
+            find("list_client_approvals").permission,
+            CommandPermission::Owner
+        );
+    }
+}
@@ -0,0 +1,234 @@
+//! `get_project_ci_status`, and the watcher behind the "CI failed on a
+//! branch a session touched" notification. A finished session's branch is
+//! watched when its project has `ciFailureNotifications` on, until CI
+//! passes, fails or gives no answer for a few hours.
+
+use std::path::Path;
+use std::time::Duration;
+
+use serde_json::json;
+use tauri::{AppHandle, Manager, State};
+use tokio::time::Instant;
+
+use crate::command_error::{CommandError, CommandResult};
+use crate::notifications::show_system_notification;
+use crate::remote_backend;
+use crate::shared::ci_status_core::{failed_check_names, project_ci_status_core};
+use crate::shared::workspace_rpc;
+use crate::shared::workspaces_core::list_workspaces_core;
+use crate::state::AppState;
+use crate::types::{CiState, ProjectCiStatus, WebhookEvent, WorkspaceInfo};
+use crate::webhooks::{notify_event, session_detail};
+
+const CI_WATCH_INTERVAL: Duration = Duration::from_secs(2 * 60);
+const CI_WATCH_TTL: Duration = Duration::from_secs(3 * 60 * 60);
+
+/// A branch waiting for CI to finish, keyed by project and branch.
+pub(crate) struct CiWatch {
+    session_id: String,
+    expires_at: Instant,
+}
+
+async fn project_ci_status(
+    state: &AppState,
+    app: AppHandle,
+    project_id: String,
+    branch: Option<String>,
+) -> CommandResult<ProjectCiStatus> {
+    if remote_backend::is_remote_mode(state).await {
+        let request = workspace_rpc::ProjectCiStatusRequest { project_id, branch };
+        let response = remote_backend::call_remote(
+            state,
+            app,
+            "get_project_ci_status",
+            workspace_rpc::to_params(&request)?,
+        )
+        .await?;
+        return serde_json::from_value(response).map_err(|err| CommandError::from(err.to_string()));
+    }
+
+    project_ci_status_core(&state.workspaces, &state.ci_status, project_id, branch)
+        .await
+        .map_err(CommandError::from)
+}
+
+/// Check runs of the latest commit on the project's current branch, cached
+/// for a minute.
+#[tauri::command]
+pub(crate) async fn get_project_ci_status(
+    project_id: String,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> CommandResult<ProjectCiStatus> {
+    project_ci_status(&state, app, project_id, None).await
+}
+
+/// `None` when a remote backend cannot be asked.
+async fn workspaces(state: &AppState, app: &AppHandle) -> Option<Vec<WorkspaceInfo>> {
+    if remote_backend::is_remote_mode(state).await {
+        let response =
+            remote_backend::call_remote(state, app.clone(), "list_workspaces", json!({}))
+                .await
+                .ok()?;
+        return serde_json::from_value(response).ok();
+    }
+    Some(list_workspaces_core(&state.workspaces, &state.sessions).await)
+}
+
+/// The most specific project whose folder contains `path`.
+fn session_project<'a>(workspaces: &'a [WorkspaceInfo], path: &str) -> Option<&'a WorkspaceInfo> {
+    workspaces
+        .iter()
+        .filter(|workspace| Path::new(path).starts_with(&workspace.path))
+        .max_by_key(|workspace| workspace.path.len())
+}
+
+/// Starts watching the branch a finished session worked on: its own branch
+/// for a worktree session, otherwise the branch its project is on.
+pub(crate) fn watch_session_branch(app: &AppHandle, session_id: String) {
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        let state = app.state::<AppState>();
+        let Some(detail) = session_detail(&state, &app, &session_id).await else {
+            return;
+        };
+        let info = detail.session;
+        let checkout = info
+            .worktree
+            .as_ref()
+            .map_or(&info.project_path, |worktree| &worktree.source_path);
+        let Some(workspaces) = workspaces(&state, &app).await else {
+            return;
+        };
+        let Some(project) = session_project(&workspaces, checkout)
+            .filter(|project| project.settings.ci_failure_notifications)
+        else {
+            return;
+        };
+        let branch = match info.worktree {
+            Some(worktree) => worktree.branch,
+            None => match project_ci_status(&state, app.clone(), project.id.clone(), None).await {
+                Ok(status) => status.branch,
+                Err(_) => return,
+            },
+        };
+        state.ci_watches.lock().await.insert(
+            (project.id.clone(), branch),
+            CiWatch {
+                session_id,
+                expires_at: Instant::now() + CI_WATCH_TTL,
+            },
+        );
+    });
+}
+
+async fn notify_ci_failed(
+    app: &AppHandle,
+    project: &WorkspaceInfo,
+    session_id: &str,
+    status: &ProjectCiStatus,
+) {
+    let failed_checks = failed_check_names(status);
+    let body = format!(
+        "{} failed on {} in {}.",
+        failed_checks.join(", "),
+        status.branch,
+        project.name
+    );
+    show_system_notification(app, "CI failed", &body, false).await;
+    notify_event(
+        app,
+        WebhookEvent::CiFailed,
+        json!({
+            "sessionId": session_id,
+            "workspaceId": project.id,
+            "repo": status.repo,
+            "branch": status.branch,
+            "commit": status.commit,
+            "failedChecks": failed_checks,
+        }),
+    );
+}
+
+async fn check_watched_branches(app: &AppHandle) {
+    let state = app.state::<AppState>();
+    let watched: Vec<((String, String), String)> = {
+        let mut watches = state.ci_watches.lock().await;
+        let now = Instant::now();
+        watches.retain(|_, watch| watch.expires_at > now);
+        watches
+            .iter()
+            .map(|(key, watch)| (key.clone(), watch.session_id.clone()))
+            .collect()
+    };
+    if watched.is_empty() {
+        return;
+    }
+    let Some(workspaces) = workspaces(&state, app).await else {
+        return;
+    };
+    for ((project_id, branch), session_id) in watched {
+        let key = (project_id.clone(), branch.clone());
+        // Turning the setting off drops what the project was waiting on.
+        let Some(project) = workspaces
+            .iter()
+            .find(|workspace| workspace.id == project_id)
+            .filter(|project| project.settings.ci_failure_notifications)
+        else {
+            state.ci_watches.lock().await.remove(&key);
+            continue;
+        };
+        let Ok(status) = project_ci_status(&state, app.clone(), project_id, Some(branch)).await
+        else {
+            continue;
+        };
+        match status.state {
+            CiState::Pending | CiState::None => continue,
+            CiState::Failure => notify_ci_failed(app, project, &session_id, &status).await,
+            CiState::Success => {}
+        }
+        state.ci_watches.lock().await.remove(&key);
+    }
+}
+
+pub(crate) fn spawn_ci_watcher(app: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        loop {
+            tokio::time::sleep(CI_WATCH_INTERVAL).await;
+            check_watched_branches(&app).await;
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{WorkspaceKind, WorkspaceSettings};
+
+    fn workspace(id: &str, path: &str) -> WorkspaceInfo {
+        WorkspaceInfo {
+            id: id.to_string(),
+            name: id.to_string(),
+            path: path.to_string(),
+            connected: false,
+            kind: WorkspaceKind::Main,
+            parent_id: None,
+            worktree: None,
+            settings: WorkspaceSettings::default(),
+        }
+    }
+
+    #[test]
+    fn sessions_belong_to_the_innermost_project() {
+        let workspaces = [
+            workspace("mono", "/src/mono"),
+            workspace("api", "/src/mono/api"),
+            workspace("web", "/src/web"),
+        ];
+        let project = |path: &str| session_project(&workspaces, path).map(|ws| ws.id.as_str());
+        assert_eq!(project("/src/mono/api/handlers"), Some("api"));
+        assert_eq!(project("/src/mono/docs"), Some("mono"));
+        assert_eq!(project("/src/web"), Some("web"));
+        assert_eq!(project("/src/website"), None);
+    }
+}
@@ -0,0 +1,93 @@
+use std::collections::HashSet;
+use std::time::Duration;
+
+use tauri::{AppHandle, Emitter, Manager, State};
+
+use crate::command_error::{CommandError, CommandResult};
+use crate::remote_backend;
+use crate::shared::{client_trust_core, workspace_rpc};
+use crate::state::AppState;
+use crate::tailscale::daemon_data_dir;
+use crate::types::{ClientApproval, ClientApprovalStatus};
+
+const APPROVAL_POLL_INTERVAL: Duration = Duration::from_secs(2);
+
+#[tauri::command]
+pub(crate) async fn list_client_approvals(
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> CommandResult<Vec<ClientApproval>> {
+    if remote_backend::is_remote_mode(&*state).await {
+        let response = remote_backend::call_remote(
+            &*state,
+            app,
+            "list_client_approvals",
+            serde_json::json!({}),
+        )
+        .await?;
+        return serde_json::from_value(response).map_err(|err| CommandError::from(err.to_string()));
+    }
+
+    client_trust_core::list_client_approvals_core(&daemon_data_dir(&state).await)
+        .map_err(CommandError::from)
+}
+
+#[tauri::command]
+pub(crate) async fn decide_client_approval(
+    client_id: String,
+    approved: bool,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> CommandResult<ClientApproval> {
+    if remote_backend::is_remote_mode(&*state).await {
+        let request = workspace_rpc::DecideClientApprovalRequest {
+            client_id,
+            approved,
+        };
+        let response = remote_backend::call_remote(
+            &*state,
+            app,
+            "decide_client_approval",
+            workspace_rpc::to_params(&request)?,
+        )
+        .await?;
+        return serde_json::from_value(response).map_err(|err| CommandError::from(err.to_string()));
+    }
+
+    client_trust_core::decide_client_approval_core(
+        &daemon_data_dir(&state).await,
+        &client_id,
+        approved,
+    )
+    .map_err(CommandError::from)
+}
+
+/// Announces clients waiting on the daemon this app manages. A remote
+/// daemon sends the same event over the backend connection instead.
+pub(crate) fn spawn_client_approval_watcher(app: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        let mut announced: HashSet<String> = HashSet::new();
+        loop {
+            tokio::time::sleep(APPROVAL_POLL_INTERVAL).await;
+            let state = app.state::<AppState>();
+            if remote_backend::is_remote_mode(&*state).await {
+                continue;
+            }
+            let Ok(approvals) =
+                client_trust_core::list_client_approvals_core(&daemon_data_dir(&state).await)
+            else {
+                continue;
+            };
+            let pending: Vec<ClientApproval> = approvals
+                .into_iter()
+                .filter(|approval| approval.status == ClientApprovalStatus::Pending)
+                .collect();
+            announced.retain(|id| pending.iter().any(|approval| &approval.client_id == id));
+            for approval in pending {
+                if announced.insert(approval.client_id.clone()) {
+                    let _ = app.emit(client_trust_core::CLIENT_APPROVAL_REQUESTED_EVENT, approval);
+                }
+            }
+        }
+    });
+}
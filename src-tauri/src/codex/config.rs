@@ -1,29 +1,63 @@
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::SystemTime;
 
 use crate::shared::config_toml_core;
 
-pub(crate) fn read_steer_enabled() -> Result<Option<bool>, String> {
-    read_feature_flag("steer")
+/// The config.toml values `get_app_settings` reports on top of the stored
+/// settings.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct CodexConfigFlags {
+    pub(crate) collaboration_modes_enabled: Option<bool>,
+    pub(crate) steer_enabled: Option<bool>,
+    pub(crate) unified_exec_enabled: Option<bool>,
+    pub(crate) apps_enabled: Option<bool>,
+    pub(crate) personality: Option<String>,
 }
 
-pub(crate) fn read_collaboration_modes_enabled() -> Result<Option<bool>, String> {
-    read_feature_flag("collaboration_modes")
-}
-
-pub(crate) fn read_unified_exec_enabled() -> Result<Option<bool>, String> {
-    read_feature_flag("unified_exec")
-}
+// Keyed by (path, size, mtime) so settings reads only parse config.toml
+// after it changed on disk.
+type ConfigFlagsKey = (PathBuf, u64, SystemTime);
+static CONFIG_FLAGS: Mutex<Option<(ConfigFlagsKey, CodexConfigFlags)>> = Mutex::new(None);
 
-pub(crate) fn read_apps_enabled() -> Result<Option<bool>, String> {
-    read_feature_flag("apps")
-}
-
-pub(crate) fn read_personality() -> Result<Option<String>, String> {
+pub(crate) fn read_config_flags() -> Result<CodexConfigFlags, String> {
     let Some(root) = resolve_default_codex_home() else {
-        return Ok(None);
+        return Ok(CodexConfigFlags::default());
     };
-    let (_, document) = config_toml_core::load_global_config_document(&root)?;
-    Ok(read_personality_from_document(&document))
+    read_config_flags_at(&root)
+}
+
+fn read_config_flags_at(root: &Path) -> Result<CodexConfigFlags, String> {
+    let path = root.join("config.toml");
+    let key = std::fs::metadata(&path)
+        .ok()
+        .and_then(|metadata| Some((path, metadata.len(), metadata.modified().ok()?)));
+    if let Some(key) = key.as_ref() {
+        if let Some(flags) = CONFIG_FLAGS.lock().ok().and_then(|cache| {
+            cache
+                .as_ref()
+                .filter(|(cached_key, _)| cached_key == key)
+                .map(|(_, flags)| flags.clone())
+        }) {
+            return Ok(flags);
+        }
+    }
+
+    let (_, document) = config_toml_core::load_global_config_document(root)?;
+    let flags = CodexConfigFlags {
+        collaboration_modes_enabled: config_toml_core::read_feature_flag(
+            &document,
+            "collaboration_modes",
+        ),
+        steer_enabled: config_toml_core::read_feature_flag(&document, "steer"),
+        unified_exec_enabled: config_toml_core::read_feature_flag(&document, "unified_exec"),
+        apps_enabled: config_toml_core::read_feature_flag(&document, "apps"),
+        personality: read_personality_from_document(&document),
+    };
+    if let (Some(key), Ok(mut cache)) = (key, CONFIG_FLAGS.lock()) {
+        *cache = Some((key, flags.clone()));
+    }
+    Ok(flags)
 }
 
 pub(crate) fn write_steer_enabled(enabled: bool) -> Result<(), String> {
@@ -63,14 +97,6 @@ pub(crate) fn write_personality(personality: &str) -> Result<(), String> {
     config_toml_core::persist_global_config_document(&root, &document)
 }
 
-fn read_feature_flag(key: &str) -> Result<Option<bool>, String> {
-    let Some(root) = resolve_default_codex_home() else {
-        return Ok(None);
-    };
-    let (_, document) = config_toml_core::load_global_config_document(&root)?;
-    Ok(config_toml_core::read_feature_flag(&document, key))
-}
-
 fn write_feature_flag(key: &str, enabled: bool) -> Result<(), String> {
     let Some(root) = resolve_default_codex_home() else {
         return Ok(());
@@ -114,7 +140,9 @@ fn normalize_personality_value(value: &str) -> Option<&'static str> {
 
 #[cfg(test)]
 mod tests {
-    use super::{normalize_personality_value, read_personality_from_document};
+    use super::{
+        normalize_personality_value, read_config_flags_at, read_personality_from_document,
+    };
     use crate::shared::config_toml_core;
 
     #[test]
@@ -143,4 +171,48 @@ mod tests {
         assert_eq!(normalize_personality_value("PRAGMATIC"), Some("pragmatic"));
         assert_eq!(normalize_personality_value("unknown"), None);
     }
+
+    #[test]
+    fn config_flags_are_reread_only_after_the_file_changes() {
+        let root =
+            std::env::temp_dir().join(format!("codex-monitor-config-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&root).expect("create dir");
+        let path = root.join("config.toml");
+        std::fs::write(
+            &path,
+            "personality = \"pragmatic\"\n[features]\nsteer = true\n",
+        )
+        .expect("write");
+        let modified = std::fs::metadata(&path)
+            .and_then(|metadata| metadata.modified())
+            .expect("mtime");
+
+        let flags = read_config_flags_at(&root).expect("read");
+        assert_eq!(flags.steer_enabled, Some(true));
+        assert_eq!(flags.unified_exec_enabled, None);
+        assert_eq!(flags.personality.as_deref(), Some("pragmatic"));
+
+        // Same size and mtime: the cached flags are served without parsing.
+        std::fs::write(
+            &path,
+            "personality = \"pragmatic\"\n[features]\nsteer = fals\n",
+        )
+        .expect("rewrite");
+        let file = std::fs::File::options()
+            .write(true)
+            .open(&path)
+            .expect("open");
+        file.set_modified(modified).expect("set mtime");
+        assert_eq!(
+            read_config_flags_at(&root).expect("cached").steer_enabled,
+            Some(true)
+        );
+
+        std::fs::write(&path, "[features]\nsteer = false\n").expect("change");
+        let flags = read_config_flags_at(&root).expect("reread");
+        assert_eq!(flags.steer_enabled, Some(false));
+        assert_eq!(flags.personality, None);
+
+        let _ = std::fs::remove_dir_all(&root);
+    }
 }
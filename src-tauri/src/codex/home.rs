@@ -1,7 +1,26 @@
 use std::env;
-use std::path::PathBuf;
-
-use crate::types::WorkspaceEntry;
+use std::fs;
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+use std::sync::RwLock;
+
+use crate::types::{CodexHomeSource, WorkspaceEntry};
+
+/// `codexHomeOverride` from settings, applied process-wide like the Codex
+/// process environment.
+static CODEX_HOME_OVERRIDE: RwLock<Option<PathBuf>> = RwLock::new(None);
+
+/// Entries a Codex home holds. A non-empty directory with none of them is
+/// more likely a mistyped path than a Codex home.
+const CODEX_HOME_MARKERS: &[&str] = &[
+    "config.toml",
+    "auth.json",
+    "AGENTS.md",
+    "history.jsonl",
+    "prompts",
+    "sessions",
+    "skills",
+];
 
 pub(crate) fn resolve_workspace_codex_home(
     _entry: &WorkspaceEntry,
@@ -10,16 +29,90 @@ pub(crate) fn resolve_workspace_codex_home(
     resolve_default_codex_home()
 }
 
-pub(crate) fn resolve_default_codex_home() -> Option<PathBuf> {
+pub(crate) fn set_codex_home_override(value: Option<&str>) {
+    let path = value.and_then(normalize_codex_home);
+    if let Ok(mut current) = CODEX_HOME_OVERRIDE.write() {
+        *current = path;
+    }
+}
+
+/// The Codex home in use and where it came from: the settings override,
+/// then `CODEX_HOME`, then `~/.codex`.
+pub(crate) fn resolve_codex_home_with_source() -> (Option<PathBuf>, CodexHomeSource) {
+    let overridden = CODEX_HOME_OVERRIDE
+        .read()
+        .ok()
+        .and_then(|current| current.clone());
+    if let Some(path) = overridden {
+        return (Some(path), CodexHomeSource::Override);
+    }
     if let Ok(value) = env::var("CODEX_HOME") {
         if let Some(path) = normalize_codex_home(&value) {
-            return Some(path);
+            return (Some(path), CodexHomeSource::Env);
+        }
+    }
+    (
+        resolve_home_dir().map(|home| home.join(".codex")),
+        CodexHomeSource::Default,
+    )
+}
+
+pub(crate) fn resolve_default_codex_home() -> Option<PathBuf> {
+    resolve_codex_home_with_source().0
+}
+
+/// Checks that `path` is a Codex home or can become one: an absolute path
+/// to an empty directory, a directory holding Codex files with a readable
+/// `config.toml`, or a missing directory whose parent exists.
+pub(crate) fn validate_codex_home(path: &Path) -> Result<(), String> {
+    let display = path.display();
+    if !path.is_absolute() {
+        return Err(format!("Codex home {display} must be an absolute path"));
+    }
+    let metadata = match fs::metadata(path) {
+        Ok(metadata) => metadata,
+        Err(err) if err.kind() == ErrorKind::NotFound => {
+            return match path.parent() {
+                Some(parent) if parent.is_dir() => Ok(()),
+                _ => Err(format!(
+                    "Codex home {display} does not exist and neither does its parent"
+                )),
+            };
         }
+        Err(err) => return Err(format!("Cannot read Codex home {display}: {err}")),
+    };
+    if !metadata.is_dir() {
+        return Err(format!("Codex home {display} is not a directory"));
+    }
+    if metadata.permissions().readonly() {
+        return Err(format!("Codex home {display} is read-only"));
+    }
+    let mut entries =
+        fs::read_dir(path).map_err(|err| format!("Cannot read Codex home {display}: {err}"))?;
+    if entries.next().is_none() {
+        return Ok(());
+    }
+    if !CODEX_HOME_MARKERS
+        .iter()
+        .any(|marker| path.join(marker).exists())
+    {
+        return Err(format!(
+            "{display} is not empty and holds no Codex files such as config.toml, \
+             auth.json or sessions"
+        ));
+    }
+    let config = path.join("config.toml");
+    if config.is_file() {
+        let contents = fs::read_to_string(&config)
+            .map_err(|err| format!("Cannot read {}: {err}", config.display()))?;
+        contents
+            .parse::<toml_edit::Document>()
+            .map_err(|err| format!("{} is not valid TOML: {err}", config.display()))?;
     }
-    resolve_home_dir().map(|home| home.join(".codex"))
+    Ok(())
 }
 
-fn normalize_codex_home(value: &str) -> Option<PathBuf> {
+pub(crate) fn normalize_codex_home(value: &str) -> Option<PathBuf> {
     let trimmed = value.trim();
     if trimmed.is_empty() {
         return None;
@@ -213,6 +306,56 @@ mod tests {
         }
     }
 
+    #[test]
+    fn override_wins_over_codex_home_env() {
+        let _guard = ENV_LOCK.lock().expect("lock env");
+        let prev_codex_home = std::env::var("CODEX_HOME").ok();
+        std::env::set_var("CODEX_HOME", "/tmp/codex-global");
+
+        set_codex_home_override(Some("/tmp/codex-override"));
+        assert_eq!(
+            resolve_codex_home_with_source(),
+            (
+                Some(PathBuf::from("/tmp/codex-override")),
+                CodexHomeSource::Override
+            )
+        );
+        set_codex_home_override(Some("  "));
+        assert_eq!(
+            resolve_codex_home_with_source(),
+            (
+                Some(PathBuf::from("/tmp/codex-global")),
+                CodexHomeSource::Env
+            )
+        );
+
+        match prev_codex_home {
+            Some(value) => std::env::set_var("CODEX_HOME", value),
+            None => std::env::remove_var("CODEX_HOME"),
+        }
+    }
+
+    #[test]
+    fn validates_codex_home_layouts() {
+        let root = std::env::temp_dir().join(format!("codex-home-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&root).expect("create root");
+
+        assert!(validate_codex_home(Path::new("relative/codex")).is_err());
+        assert!(validate_codex_home(&root.join("fresh")).is_ok());
+        assert!(validate_codex_home(&root.join("missing").join("codex")).is_err());
+        assert!(validate_codex_home(&root).is_ok());
+
+        std::fs::write(root.join("notes.txt"), "hello").expect("write notes");
+        assert!(validate_codex_home(&root).is_err());
+        std::fs::write(root.join("config.toml"), "model = ").expect("write config");
+        assert!(validate_codex_home(&root).is_err());
+        std::fs::write(root.join("config.toml"), "model = \"o3\"").expect("write config");
+        assert!(validate_codex_home(&root).is_ok());
+        assert!(validate_codex_home(&root.join("config.toml")).is_err());
+
+        let _ = std::fs::remove_dir_all(&root);
+    }
+
     #[test]
     fn codex_home_expands_tilde_and_env_vars() {
         let _guard = ENV_LOCK.lock().expect("lock env");
@@ -1,8 +1,9 @@
 use serde_json::{json, Map, Value};
 use std::path::PathBuf;
 use std::sync::Arc;
+use std::time::Duration;
 
-use tauri::{AppHandle, Emitter, State};
+use tauri::{AppHandle, Emitter, Manager, State};
 
 pub(crate) mod args;
 pub(crate) mod config;
@@ -11,12 +12,19 @@ pub(crate) mod home;
 use crate::backend::app_server::spawn_workspace_session as spawn_workspace_session_inner;
 pub(crate) use crate::backend::app_server::WorkspaceSession;
 use crate::backend::events::AppServerEvent;
+use crate::command_error::{CommandError, CommandResult};
 use crate::event_sink::TauriEventSink;
+use crate::notifications::show_system_notification;
 use crate::remote_backend;
 use crate::shared::agents_config_core;
+use crate::shared::codex_auth_core;
 use crate::shared::codex_core::{self, insert_optional_nullable_string};
+use crate::shared::codex_login_core;
 use crate::state::AppState;
-use crate::types::WorkspaceEntry;
+use crate::tray;
+use crate::types::{CodexAuthStatus, CodexLoginFlow, WorkspaceEntry};
+
+const AUTH_CHECK_INTERVAL: Duration = Duration::from_secs(6 * 60 * 60);
 
 fn emit_thread_live_event(app: &AppHandle, workspace_id: &str, method: &str, params: Value) {
     let _ = app.emit(
@@ -56,9 +64,122 @@ pub(crate) async fn codex_doctor(
     codex_bin: Option<String>,
     codex_args: Option<String>,
     state: State<'_, AppState>,
-) -> Result<Value, String> {
+    app: AppHandle,
+) -> CommandResult<Value> {
+    if remote_backend::is_remote_mode(&*state).await {
+        return remote_backend::call_remote(
+            &*state,
+            app,
+            "codex_doctor",
+            json!({ "codexBin": codex_bin, "codexArgs": codex_args }),
+        )
+        .await
+        .map_err(CommandError::from);
+    }
+
     crate::shared::codex_aux_core::codex_doctor_core(&state.app_settings, codex_bin, codex_args)
         .await
+        .map_err(CommandError::from)
+}
+
+/// Whether the Codex CLI is signed in, with which account and plan, and
+/// when its credentials expire.
+#[tauri::command]
+pub(crate) async fn codex_auth_status(
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> CommandResult<CodexAuthStatus> {
+    if remote_backend::is_remote_mode(&*state).await {
+        let response =
+            remote_backend::call_remote(&*state, app, "codex_auth_status", json!({})).await?;
+        return serde_json::from_value(response).map_err(|err| CommandError::from(err.to_string()));
+    }
+
+    codex_auth_core::codex_auth_status_core(&state.app_settings)
+        .await
+        .map_err(CommandError::from)
+}
+
+/// Runs `codex login --device-auth` and returns the verification URL and
+/// code to enter, from this or any other device. Progress, including the
+/// outcome, is sent as `codex-login-progress`.
+#[tauri::command]
+pub(crate) async fn codex_login_start(
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> CommandResult<CodexLoginFlow> {
+    if remote_backend::is_remote_mode(&*state).await {
+        let response =
+            remote_backend::call_remote(&*state, app, "codex_login_start", json!({})).await?;
+        return serde_json::from_value(response).map_err(|err| CommandError::from(err.to_string()));
+    }
+
+    codex_login_core::codex_login_start_core(
+        &state.codex_cli_login,
+        &state.app_settings,
+        TauriEventSink::new(app),
+    )
+    .await
+    .map_err(CommandError::from)
+}
+
+/// The sign-in `codex_login_start` started last, if any.
+#[tauri::command]
+pub(crate) async fn codex_login_flow_status(
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> CommandResult<Option<CodexLoginFlow>> {
+    if remote_backend::is_remote_mode(&*state).await {
+        let response =
+            remote_backend::call_remote(&*state, app, "codex_login_flow_status", json!({})).await?;
+        return serde_json::from_value(response).map_err(|err| CommandError::from(err.to_string()));
+    }
+
+    Ok(codex_login_core::codex_login_flow_status_core(&state.codex_cli_login).await)
+}
+
+#[tauri::command]
+pub(crate) async fn codex_login_flow_cancel(
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> CommandResult<CodexLoginFlow> {
+    if remote_backend::is_remote_mode(&*state).await {
+        let response =
+            remote_backend::call_remote(&*state, app, "codex_login_flow_cancel", json!({})).await?;
+        return serde_json::from_value(response).map_err(|err| CommandError::from(err.to_string()));
+    }
+
+    codex_login_core::codex_login_flow_cancel_core(&state.codex_cli_login)
+        .await
+        .map_err(CommandError::from)
+}
+
+/// Checks the Codex sign-in every few hours and notifies once per expiry
+/// when credentials that cannot renew themselves are about to run out,
+/// since sessions started remotely otherwise just fail.
+pub(crate) fn spawn_codex_auth_watcher(app: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        let mut warned_for = None;
+        loop {
+            tokio::time::sleep(AUTH_CHECK_INTERVAL).await;
+            let state = app.state::<AppState>();
+            let Ok(status) = codex_auth_status(state.clone(), app.clone()).await else {
+                continue;
+            };
+            if warned_for == status.expires_at_ms {
+                continue;
+            }
+            let Some(body) =
+                codex_auth_core::auth_expiry_warning(&status, codex_auth_core::now_ms())
+            else {
+                continue;
+            };
+            warned_for = status.expires_at_ms;
+            if state.app_settings.read().await.system_notifications_enabled {
+                show_system_notification(&app, "Codex sign-in expiring", &body, true).await;
+            }
+        }
+    });
 }
 
 #[tauri::command]
@@ -66,9 +187,10 @@ pub(crate) async fn codex_update(
     codex_bin: Option<String>,
     codex_args: Option<String>,
     state: State<'_, AppState>,
-) -> Result<Value, String> {
+) -> CommandResult<Value> {
     crate::shared::codex_update_core::codex_update_core(&state.app_settings, codex_bin, codex_args)
         .await
+        .map_err(CommandError::from)
 }
 
 #[tauri::command]
@@ -76,7 +198,7 @@ pub(crate) async fn start_thread(
     workspace_id: String,
     state: State<'_, AppState>,
     app: AppHandle,
-) -> Result<Value, String> {
+) -> CommandResult<Value> {
     if remote_backend::is_remote_mode(&*state).await {
         return remote_backend::call_remote(
             &*state,
@@ -84,10 +206,13 @@ pub(crate) async fn start_thread(
             "start_thread",
             json!({ "workspaceId": workspace_id }),
         )
-        .await;
+        .await
+        .map_err(CommandError::from);
     }
 
-    codex_core::start_thread_core(&state.sessions, &state.workspaces, workspace_id).await
+    codex_core::start_thread_core(&state.sessions, &state.workspaces, workspace_id)
+        .await
+        .map_err(CommandError::from)
 }
 
 #[tauri::command]
@@ -96,7 +221,7 @@ pub(crate) async fn resume_thread(
     thread_id: String,
     state: State<'_, AppState>,
     app: AppHandle,
-) -> Result<Value, String> {
+) -> CommandResult<Value> {
     if remote_backend::is_remote_mode(&*state).await {
         return remote_backend::call_remote(
             &*state,
@@ -104,10 +229,13 @@ pub(crate) async fn resume_thread(
             "resume_thread",
             json!({ "workspaceId": workspace_id, "threadId": thread_id }),
         )
-        .await;
+        .await
+        .map_err(CommandError::from);
     }
 
-    codex_core::resume_thread_core(&state.sessions, workspace_id, thread_id).await
+    codex_core::resume_thread_core(&state.sessions, workspace_id, thread_id)
+        .await
+        .map_err(CommandError::from)
 }
 
 #[tauri::command]
@@ -116,7 +244,7 @@ pub(crate) async fn read_thread(
     thread_id: String,
     state: State<'_, AppState>,
     app: AppHandle,
-) -> Result<Value, String> {
+) -> CommandResult<Value> {
     if remote_backend::is_remote_mode(&*state).await {
         return remote_backend::call_remote(
             &*state,
@@ -124,10 +252,13 @@ pub(crate) async fn read_thread(
             "read_thread",
             json!({ "workspaceId": workspace_id, "threadId": thread_id }),
         )
-        .await;
+        .await
+        .map_err(CommandError::from);
     }
 
-    codex_core::read_thread_core(&state.sessions, workspace_id, thread_id).await
+    codex_core::read_thread_core(&state.sessions, workspace_id, thread_id)
+        .await
+        .map_err(CommandError::from)
 }
 
 #[tauri::command]
@@ -136,15 +267,17 @@ pub(crate) async fn thread_live_subscribe(
     thread_id: String,
     state: State<'_, AppState>,
     app: AppHandle,
-) -> Result<Value, String> {
+) -> CommandResult<Value> {
     if remote_backend::is_remote_mode(&*state).await {
-        return remote_backend::call_remote(
+        let response = remote_backend::call_remote(
             &*state,
             app,
             "thread_live_subscribe",
             json!({ "workspaceId": workspace_id, "threadId": thread_id }),
         )
-        .await;
+        .await?;
+        remote_backend::record_live_subscription(&state, &workspace_id, &thread_id, true).await;
+        return Ok(response);
     }
 
     codex_core::thread_live_subscribe_core(
@@ -176,15 +309,17 @@ pub(crate) async fn thread_live_unsubscribe(
     thread_id: String,
     state: State<'_, AppState>,
     app: AppHandle,
-) -> Result<Value, String> {
+) -> CommandResult<Value> {
     if remote_backend::is_remote_mode(&*state).await {
-        return remote_backend::call_remote(
+        let response = remote_backend::call_remote(
             &*state,
             app,
             "thread_live_unsubscribe",
             json!({ "workspaceId": workspace_id, "threadId": thread_id }),
         )
-        .await;
+        .await?;
+        remote_backend::record_live_subscription(&state, &workspace_id, &thread_id, false).await;
+        return Ok(response);
     }
 
     codex_core::thread_live_unsubscribe_core(
@@ -212,7 +347,7 @@ pub(crate) async fn fork_thread(
     thread_id: String,
     state: State<'_, AppState>,
     app: AppHandle,
-) -> Result<Value, String> {
+) -> CommandResult<Value> {
     if remote_backend::is_remote_mode(&*state).await {
         return remote_backend::call_remote(
             &*state,
@@ -220,10 +355,13 @@ pub(crate) async fn fork_thread(
             "fork_thread",
             json!({ "workspaceId": workspace_id, "threadId": thread_id }),
         )
-        .await;
+        .await
+        .map_err(CommandError::from);
     }
 
-    codex_core::fork_thread_core(&state.sessions, workspace_id, thread_id).await
+    codex_core::fork_thread_core(&state.sessions, workspace_id, thread_id)
+        .await
+        .map_err(CommandError::from)
 }
 
 #[tauri::command]
@@ -234,7 +372,7 @@ pub(crate) async fn list_threads(
     sort_key: Option<String>,
     state: State<'_, AppState>,
     app: AppHandle,
-) -> Result<Value, String> {
+) -> CommandResult<Value> {
     if remote_backend::is_remote_mode(&*state).await {
         return remote_backend::call_remote(
             &*state,
@@ -247,10 +385,13 @@ pub(crate) async fn list_threads(
                 "sortKey": sort_key
             }),
         )
-        .await;
+        .await
+        .map_err(CommandError::from);
     }
 
-    codex_core::list_threads_core(&state.sessions, workspace_id, cursor, limit, sort_key).await
+    codex_core::list_threads_core(&state.sessions, workspace_id, cursor, limit, sort_key)
+        .await
+        .map_err(CommandError::from)
 }
 
 #[tauri::command]
@@ -260,7 +401,7 @@ pub(crate) async fn list_mcp_server_status(
     limit: Option<u32>,
     state: State<'_, AppState>,
     app: AppHandle,
-) -> Result<Value, String> {
+) -> CommandResult<Value> {
     if remote_backend::is_remote_mode(&*state).await {
         return remote_backend::call_remote(
             &*state,
@@ -268,10 +409,13 @@ pub(crate) async fn list_mcp_server_status(
             "list_mcp_server_status",
             json!({ "workspaceId": workspace_id, "cursor": cursor, "limit": limit }),
         )
-        .await;
+        .await
+        .map_err(CommandError::from);
     }
 
-    codex_core::list_mcp_server_status_core(&state.sessions, workspace_id, cursor, limit).await
+    codex_core::list_mcp_server_status_core(&state.sessions, workspace_id, cursor, limit)
+        .await
+        .map_err(CommandError::from)
 }
 
 #[tauri::command]
@@ -280,7 +424,7 @@ pub(crate) async fn archive_thread(
     thread_id: String,
     state: State<'_, AppState>,
     app: AppHandle,
-) -> Result<Value, String> {
+) -> CommandResult<Value> {
     if remote_backend::is_remote_mode(&*state).await {
         return remote_backend::call_remote(
             &*state,
@@ -288,10 +432,13 @@ pub(crate) async fn archive_thread(
             "archive_thread",
             json!({ "workspaceId": workspace_id, "threadId": thread_id }),
         )
-        .await;
+        .await
+        .map_err(CommandError::from);
     }
 
-    codex_core::archive_thread_core(&state.sessions, workspace_id, thread_id).await
+    codex_core::archive_thread_core(&state.sessions, workspace_id, thread_id)
+        .await
+        .map_err(CommandError::from)
 }
 
 #[tauri::command]
@@ -300,7 +447,7 @@ pub(crate) async fn compact_thread(
     thread_id: String,
     state: State<'_, AppState>,
     app: AppHandle,
-) -> Result<Value, String> {
+) -> CommandResult<Value> {
     if remote_backend::is_remote_mode(&*state).await {
         return remote_backend::call_remote(
             &*state,
@@ -308,10 +455,13 @@ pub(crate) async fn compact_thread(
             "compact_thread",
             json!({ "workspaceId": workspace_id, "threadId": thread_id }),
         )
-        .await;
+        .await
+        .map_err(CommandError::from);
     }
 
-    codex_core::compact_thread_core(&state.sessions, workspace_id, thread_id).await
+    codex_core::compact_thread_core(&state.sessions, workspace_id, thread_id)
+        .await
+        .map_err(CommandError::from)
 }
 
 #[tauri::command]
@@ -321,7 +471,7 @@ pub(crate) async fn set_thread_name(
     name: String,
     state: State<'_, AppState>,
     app: AppHandle,
-) -> Result<Value, String> {
+) -> CommandResult<Value> {
     if remote_backend::is_remote_mode(&*state).await {
         return remote_backend::call_remote(
             &*state,
@@ -329,10 +479,13 @@ pub(crate) async fn set_thread_name(
             "set_thread_name",
             json!({ "workspaceId": workspace_id, "threadId": thread_id, "name": name }),
         )
-        .await;
+        .await
+        .map_err(CommandError::from);
     }
 
-    codex_core::set_thread_name_core(&state.sessions, workspace_id, thread_id, name).await
+    codex_core::set_thread_name_core(&state.sessions, workspace_id, thread_id, name)
+        .await
+        .map_err(CommandError::from)
 }
 
 #[tauri::command]
@@ -349,7 +502,7 @@ pub(crate) async fn send_user_message(
     collaboration_mode: Option<Value>,
     state: State<'_, AppState>,
     app: AppHandle,
-) -> Result<Value, String> {
+) -> CommandResult<Value> {
     if remote_backend::is_remote_mode(&*state).await {
         let images = images.map(|paths| {
             paths
@@ -378,7 +531,8 @@ pub(crate) async fn send_user_message(
             "send_user_message",
             Value::Object(payload),
         )
-        .await;
+        .await
+        .map_err(CommandError::from);
     }
 
     codex_core::send_user_message_core(
@@ -396,6 +550,7 @@ pub(crate) async fn send_user_message(
         collaboration_mode,
     )
     .await
+    .map_err(CommandError::from)
 }
 
 #[tauri::command]
@@ -408,7 +563,7 @@ pub(crate) async fn turn_steer(
     app_mentions: Option<Vec<Value>>,
     state: State<'_, AppState>,
     app: AppHandle,
-) -> Result<Value, String> {
+) -> CommandResult<Value> {
     if remote_backend::is_remote_mode(&*state).await {
         let images = images.map(|paths| {
             paths
@@ -429,7 +584,8 @@ pub(crate) async fn turn_steer(
                 "appMentions": app_mentions,
             }),
         )
-        .await;
+        .await
+        .map_err(CommandError::from);
     }
 
     codex_core::turn_steer_core(
@@ -442,6 +598,7 @@ pub(crate) async fn turn_steer(
         app_mentions,
     )
     .await
+    .map_err(CommandError::from)
 }
 
 #[tauri::command]
@@ -449,7 +606,7 @@ pub(crate) async fn collaboration_mode_list(
     workspace_id: String,
     state: State<'_, AppState>,
     app: AppHandle,
-) -> Result<Value, String> {
+) -> CommandResult<Value> {
     if remote_backend::is_remote_mode(&*state).await {
         return remote_backend::call_remote(
             &*state,
@@ -457,10 +614,13 @@ pub(crate) async fn collaboration_mode_list(
             "collaboration_mode_list",
             json!({ "workspaceId": workspace_id }),
         )
-        .await;
+        .await
+        .map_err(CommandError::from);
     }
 
-    codex_core::collaboration_mode_list_core(&state.sessions, workspace_id).await
+    codex_core::collaboration_mode_list_core(&state.sessions, workspace_id)
+        .await
+        .map_err(CommandError::from)
 }
 
 #[tauri::command]
@@ -470,7 +630,7 @@ pub(crate) async fn turn_interrupt(
     turn_id: String,
     state: State<'_, AppState>,
     app: AppHandle,
-) -> Result<Value, String> {
+) -> CommandResult<Value> {
     if remote_backend::is_remote_mode(&*state).await {
         return remote_backend::call_remote(
             &*state,
@@ -478,10 +638,13 @@ pub(crate) async fn turn_interrupt(
             "turn_interrupt",
             json!({ "workspaceId": workspace_id, "threadId": thread_id, "turnId": turn_id }),
         )
-        .await;
+        .await
+        .map_err(CommandError::from);
     }
 
-    codex_core::turn_interrupt_core(&state.sessions, workspace_id, thread_id, turn_id).await
+    codex_core::turn_interrupt_core(&state.sessions, workspace_id, thread_id, turn_id)
+        .await
+        .map_err(CommandError::from)
 }
 
 #[tauri::command]
@@ -492,7 +655,7 @@ pub(crate) async fn start_review(
     delivery: Option<String>,
     state: State<'_, AppState>,
     app: AppHandle,
-) -> Result<Value, String> {
+) -> CommandResult<Value> {
     if remote_backend::is_remote_mode(&*state).await {
         return remote_backend::call_remote(
             &*state,
@@ -505,17 +668,13 @@ pub(crate) async fn start_review(
                 "delivery": delivery,
             }),
         )
-        .await;
+        .await
+        .map_err(CommandError::from);
     }
 
-    codex_core::start_review_core(
-        &state.sessions,
-        workspace_id,
-        thread_id,
-        target,
-        delivery,
-    )
-    .await
+    codex_core::start_review_core(&state.sessions, workspace_id, thread_id, target, delivery)
+        .await
+        .map_err(CommandError::from)
 }
 
 #[tauri::command]
@@ -523,7 +682,7 @@ pub(crate) async fn model_list(
     workspace_id: String,
     state: State<'_, AppState>,
     app: AppHandle,
-) -> Result<Value, String> {
+) -> CommandResult<Value> {
     if remote_backend::is_remote_mode(&*state).await {
         return remote_backend::call_remote(
             &*state,
@@ -531,10 +690,13 @@ pub(crate) async fn model_list(
             "model_list",
             json!({ "workspaceId": workspace_id }),
         )
-        .await;
+        .await
+        .map_err(CommandError::from);
     }
 
-    codex_core::model_list_core(&state.sessions, workspace_id).await
+    codex_core::model_list_core(&state.sessions, workspace_id)
+        .await
+        .map_err(CommandError::from)
 }
 
 #[tauri::command]
@@ -544,7 +706,7 @@ pub(crate) async fn experimental_feature_list(
     limit: Option<u32>,
     state: State<'_, AppState>,
     app: AppHandle,
-) -> Result<Value, String> {
+) -> CommandResult<Value> {
     if remote_backend::is_remote_mode(&*state).await {
         return remote_backend::call_remote(
             &*state,
@@ -556,10 +718,13 @@ pub(crate) async fn experimental_feature_list(
                 "limit": limit
             }),
         )
-        .await;
+        .await
+        .map_err(CommandError::from);
     }
 
-    codex_core::experimental_feature_list_core(&state.sessions, workspace_id, cursor, limit).await
+    codex_core::experimental_feature_list_core(&state.sessions, workspace_id, cursor, limit)
+        .await
+        .map_err(CommandError::from)
 }
 
 #[tauri::command]
@@ -568,7 +733,7 @@ pub(crate) async fn set_codex_feature_flag(
     enabled: bool,
     state: State<'_, AppState>,
     app: AppHandle,
-) -> Result<(), String> {
+) -> CommandResult<()> {
     if remote_backend::is_remote_mode(&*state).await {
         remote_backend::call_remote(
             &*state,
@@ -583,21 +748,21 @@ pub(crate) async fn set_codex_feature_flag(
         return Ok(());
     }
 
-    config::write_feature_enabled(feature_key.as_str(), enabled)
+    config::write_feature_enabled(feature_key.as_str(), enabled).map_err(CommandError::from)
 }
 
 #[tauri::command]
 pub(crate) async fn get_agents_settings(
     state: State<'_, AppState>,
     app: AppHandle,
-) -> Result<agents_config_core::AgentsSettingsDto, String> {
+) -> CommandResult<agents_config_core::AgentsSettingsDto> {
     if remote_backend::is_remote_mode(&*state).await {
         let response =
             remote_backend::call_remote(&*state, app, "get_agents_settings", json!({})).await?;
-        return serde_json::from_value(response).map_err(|err| err.to_string());
+        return serde_json::from_value(response).map_err(|err| CommandError::from(err.to_string()));
     }
 
-    agents_config_core::get_agents_settings_core()
+    agents_config_core::get_agents_settings_core().map_err(CommandError::from)
 }
 
 #[tauri::command]
@@ -605,7 +770,7 @@ pub(crate) async fn set_agents_core_settings(
     input: agents_config_core::SetAgentsCoreInput,
     state: State<'_, AppState>,
     app: AppHandle,
-) -> Result<agents_config_core::AgentsSettingsDto, String> {
+) -> CommandResult<agents_config_core::AgentsSettingsDto> {
     if remote_backend::is_remote_mode(&*state).await {
         let response = remote_backend::call_remote(
             &*state,
@@ -614,10 +779,10 @@ pub(crate) async fn set_agents_core_settings(
             json!({ "input": input }),
         )
         .await?;
-        return serde_json::from_value(response).map_err(|err| err.to_string());
+        return serde_json::from_value(response).map_err(|err| CommandError::from(err.to_string()));
     }
 
-    agents_config_core::set_agents_core_settings_core(input)
+    agents_config_core::set_agents_core_settings_core(input).map_err(CommandError::from)
 }
 
 #[tauri::command]
@@ -625,15 +790,15 @@ pub(crate) async fn create_agent(
     input: agents_config_core::CreateAgentInput,
     state: State<'_, AppState>,
     app: AppHandle,
-) -> Result<agents_config_core::AgentsSettingsDto, String> {
+) -> CommandResult<agents_config_core::AgentsSettingsDto> {
     if remote_backend::is_remote_mode(&*state).await {
         let response =
             remote_backend::call_remote(&*state, app, "create_agent", json!({ "input": input }))
                 .await?;
-        return serde_json::from_value(response).map_err(|err| err.to_string());
+        return serde_json::from_value(response).map_err(|err| CommandError::from(err.to_string()));
     }
 
-    agents_config_core::create_agent_core(input)
+    agents_config_core::create_agent_core(input).map_err(CommandError::from)
 }
 
 #[tauri::command]
@@ -641,15 +806,15 @@ pub(crate) async fn update_agent(
     input: agents_config_core::UpdateAgentInput,
     state: State<'_, AppState>,
     app: AppHandle,
-) -> Result<agents_config_core::AgentsSettingsDto, String> {
+) -> CommandResult<agents_config_core::AgentsSettingsDto> {
     if remote_backend::is_remote_mode(&*state).await {
         let response =
             remote_backend::call_remote(&*state, app, "update_agent", json!({ "input": input }))
                 .await?;
-        return serde_json::from_value(response).map_err(|err| err.to_string());
+        return serde_json::from_value(response).map_err(|err| CommandError::from(err.to_string()));
     }
 
-    agents_config_core::update_agent_core(input)
+    agents_config_core::update_agent_core(input).map_err(CommandError::from)
 }
 
 #[tauri::command]
@@ -657,15 +822,15 @@ pub(crate) async fn delete_agent(
     input: agents_config_core::DeleteAgentInput,
     state: State<'_, AppState>,
     app: AppHandle,
-) -> Result<agents_config_core::AgentsSettingsDto, String> {
+) -> CommandResult<agents_config_core::AgentsSettingsDto> {
     if remote_backend::is_remote_mode(&*state).await {
         let response =
             remote_backend::call_remote(&*state, app, "delete_agent", json!({ "input": input }))
                 .await?;
-        return serde_json::from_value(response).map_err(|err| err.to_string());
+        return serde_json::from_value(response).map_err(|err| CommandError::from(err.to_string()));
     }
 
-    agents_config_core::delete_agent_core(input)
+    agents_config_core::delete_agent_core(input).map_err(CommandError::from)
 }
 
 #[tauri::command]
@@ -673,7 +838,7 @@ pub(crate) async fn read_agent_config_toml(
     agent_name: String,
     state: State<'_, AppState>,
     app: AppHandle,
-) -> Result<String, String> {
+) -> CommandResult<String> {
     if remote_backend::is_remote_mode(&*state).await {
         let response = remote_backend::call_remote(
             &*state,
@@ -682,10 +847,10 @@ pub(crate) async fn read_agent_config_toml(
             json!({ "agentName": agent_name }),
         )
         .await?;
-        return serde_json::from_value(response).map_err(|err| err.to_string());
+        return serde_json::from_value(response).map_err(|err| CommandError::from(err.to_string()));
     }
 
-    agents_config_core::read_agent_config_toml_core(agent_name.as_str())
+    agents_config_core::read_agent_config_toml_core(agent_name.as_str()).map_err(CommandError::from)
 }
 
 #[tauri::command]
@@ -694,7 +859,7 @@ pub(crate) async fn write_agent_config_toml(
     content: String,
     state: State<'_, AppState>,
     app: AppHandle,
-) -> Result<(), String> {
+) -> CommandResult<()> {
     if remote_backend::is_remote_mode(&*state).await {
         remote_backend::call_remote(
             &*state,
@@ -710,6 +875,7 @@ pub(crate) async fn write_agent_config_toml(
     }
 
     agents_config_core::write_agent_config_toml_core(agent_name.as_str(), content.as_str())
+        .map_err(CommandError::from)
 }
 
 #[tauri::command]
@@ -717,7 +883,7 @@ pub(crate) async fn account_rate_limits(
     workspace_id: String,
     state: State<'_, AppState>,
     app: AppHandle,
-) -> Result<Value, String> {
+) -> CommandResult<Value> {
     if remote_backend::is_remote_mode(&*state).await {
         return remote_backend::call_remote(
             &*state,
@@ -725,10 +891,13 @@ pub(crate) async fn account_rate_limits(
             "account_rate_limits",
             json!({ "workspaceId": workspace_id }),
         )
-        .await;
+        .await
+        .map_err(CommandError::from);
     }
 
-    codex_core::account_rate_limits_core(&state.sessions, workspace_id).await
+    codex_core::account_rate_limits_core(&state.sessions, workspace_id)
+        .await
+        .map_err(CommandError::from)
 }
 
 #[tauri::command]
@@ -736,7 +905,7 @@ pub(crate) async fn account_read(
     workspace_id: String,
     state: State<'_, AppState>,
     app: AppHandle,
-) -> Result<Value, String> {
+) -> CommandResult<Value> {
     if remote_backend::is_remote_mode(&*state).await {
         return remote_backend::call_remote(
             &*state,
@@ -744,10 +913,13 @@ pub(crate) async fn account_read(
             "account_read",
             json!({ "workspaceId": workspace_id }),
         )
-        .await;
+        .await
+        .map_err(CommandError::from);
     }
 
-    codex_core::account_read_core(&state.sessions, &state.workspaces, workspace_id).await
+    codex_core::account_read_core(&state.sessions, &state.workspaces, workspace_id)
+        .await
+        .map_err(CommandError::from)
 }
 
 #[tauri::command]
@@ -755,7 +927,7 @@ pub(crate) async fn codex_login(
     workspace_id: String,
     state: State<'_, AppState>,
     app: AppHandle,
-) -> Result<Value, String> {
+) -> CommandResult<Value> {
     if remote_backend::is_remote_mode(&*state).await {
         return remote_backend::call_remote(
             &*state,
@@ -763,10 +935,13 @@ pub(crate) async fn codex_login(
             "codex_login",
             json!({ "workspaceId": workspace_id }),
         )
-        .await;
+        .await
+        .map_err(CommandError::from);
     }
 
-    codex_core::codex_login_core(&state.sessions, &state.codex_login_cancels, workspace_id).await
+    codex_core::codex_login_core(&state.sessions, &state.codex_login_cancels, workspace_id)
+        .await
+        .map_err(CommandError::from)
 }
 
 #[tauri::command]
@@ -774,7 +949,7 @@ pub(crate) async fn codex_login_cancel(
     workspace_id: String,
     state: State<'_, AppState>,
     app: AppHandle,
-) -> Result<Value, String> {
+) -> CommandResult<Value> {
     if remote_backend::is_remote_mode(&*state).await {
         return remote_backend::call_remote(
             &*state,
@@ -782,11 +957,13 @@ pub(crate) async fn codex_login_cancel(
             "codex_login_cancel",
             json!({ "workspaceId": workspace_id }),
         )
-        .await;
+        .await
+        .map_err(CommandError::from);
     }
 
     codex_core::codex_login_cancel_core(&state.sessions, &state.codex_login_cancels, workspace_id)
         .await
+        .map_err(CommandError::from)
 }
 
 #[tauri::command]
@@ -794,7 +971,7 @@ pub(crate) async fn skills_list(
     workspace_id: String,
     state: State<'_, AppState>,
     app: AppHandle,
-) -> Result<Value, String> {
+) -> CommandResult<Value> {
     if remote_backend::is_remote_mode(&*state).await {
         return remote_backend::call_remote(
             &*state,
@@ -802,10 +979,13 @@ pub(crate) async fn skills_list(
             "skills_list",
             json!({ "workspaceId": workspace_id }),
         )
-        .await;
+        .await
+        .map_err(CommandError::from);
     }
 
-    codex_core::skills_list_core(&state.sessions, &state.workspaces, workspace_id).await
+    codex_core::skills_list_core(&state.sessions, &state.workspaces, workspace_id)
+        .await
+        .map_err(CommandError::from)
 }
 
 #[tauri::command]
@@ -816,7 +996,7 @@ pub(crate) async fn apps_list(
     thread_id: Option<String>,
     state: State<'_, AppState>,
     app: AppHandle,
-) -> Result<Value, String> {
+) -> CommandResult<Value> {
     if remote_backend::is_remote_mode(&*state).await {
         return remote_backend::call_remote(
             &*state,
@@ -829,10 +1009,13 @@ pub(crate) async fn apps_list(
                 "threadId": thread_id
             }),
         )
-        .await;
+        .await
+        .map_err(CommandError::from);
     }
 
-    codex_core::apps_list_core(&state.sessions, workspace_id, cursor, limit, thread_id).await
+    codex_core::apps_list_core(&state.sessions, workspace_id, cursor, limit, thread_id)
+        .await
+        .map_err(CommandError::from)
 }
 
 #[tauri::command]
@@ -842,20 +1025,29 @@ pub(crate) async fn respond_to_server_request(
     result: Value,
     state: State<'_, AppState>,
     app: AppHandle,
-) -> Result<(), String> {
+) -> CommandResult<()> {
     if remote_backend::is_remote_mode(&*state).await {
         remote_backend::call_remote(
             &*state,
-            app,
+            app.clone(),
             "respond_to_server_request",
             json!({ "workspaceId": workspace_id, "requestId": request_id, "result": result }),
         )
         .await?;
+        tray::note_approval_answered(&app, &workspace_id, &request_id);
         return Ok(());
     }
 
-    codex_core::respond_to_server_request_core(&state.sessions, workspace_id, request_id, result)
-        .await
+    codex_core::respond_to_server_request_core(
+        &state.sessions,
+        workspace_id.clone(),
+        request_id.clone(),
+        result,
+    )
+    .await
+    .map_err(CommandError::from)?;
+    tray::note_approval_answered(&app, &workspace_id, &request_id);
+    Ok(())
 }
 
 #[tauri::command]
@@ -863,8 +1055,22 @@ pub(crate) async fn remember_approval_rule(
     workspace_id: String,
     command: Vec<String>,
     state: State<'_, AppState>,
-) -> Result<Value, String> {
-    codex_core::remember_approval_rule_core(&state.workspaces, workspace_id, command).await
+    app: AppHandle,
+) -> CommandResult<Value> {
+    if remote_backend::is_remote_mode(&*state).await {
+        return remote_backend::call_remote(
+            &*state,
+            app,
+            "remember_approval_rule",
+            json!({ "workspaceId": workspace_id, "command": command }),
+        )
+        .await
+        .map_err(CommandError::from);
+    }
+
+    codex_core::remember_approval_rule_core(&state.workspaces, workspace_id, command)
+        .await
+        .map_err(CommandError::from)
 }
 
 #[tauri::command]
@@ -872,7 +1078,7 @@ pub(crate) async fn get_config_model(
     workspace_id: String,
     state: State<'_, AppState>,
     app: AppHandle,
-) -> Result<Value, String> {
+) -> CommandResult<Value> {
     if remote_backend::is_remote_mode(&*state).await {
         return remote_backend::call_remote(
             &*state,
@@ -880,10 +1086,13 @@ pub(crate) async fn get_config_model(
             "get_config_model",
             json!({ "workspaceId": workspace_id }),
         )
-        .await;
+        .await
+        .map_err(CommandError::from);
     }
 
-    codex_core::get_config_model_core(&state.workspaces, workspace_id).await
+    codex_core::get_config_model_core(&state.workspaces, workspace_id)
+        .await
+        .map_err(CommandError::from)
 }
 
 /// Generates a commit message in the background without showing in the main chat
@@ -893,7 +1102,7 @@ pub(crate) async fn generate_commit_message(
     commit_message_model_id: Option<String>,
     state: State<'_, AppState>,
     app: AppHandle,
-) -> Result<String, String> {
+) -> CommandResult<String> {
     if remote_backend::is_remote_mode(&*state).await {
         let value = remote_backend::call_remote(
             &*state,
@@ -905,13 +1114,13 @@ pub(crate) async fn generate_commit_message(
             }),
         )
         .await?;
-        return serde_json::from_value(value).map_err(|err| err.to_string());
+        return serde_json::from_value(value).map_err(|err| CommandError::from(err.to_string()));
     }
 
     let diff = crate::git::get_workspace_diff(&workspace_id, &state).await?;
 
     let commit_message_prompt = {
-        let settings = state.app_settings.lock().await;
+        let settings = state.app_settings.read().await;
         settings.commit_message_prompt.clone()
     };
     crate::shared::codex_aux_core::generate_commit_message_core(
@@ -938,6 +1147,7 @@ pub(crate) async fn generate_commit_message(
         },
     )
     .await
+    .map_err(CommandError::from)
 }
 
 #[tauri::command]
@@ -946,7 +1156,7 @@ pub(crate) async fn generate_run_metadata(
     prompt: String,
     state: State<'_, AppState>,
     app: AppHandle,
-) -> Result<Value, String> {
+) -> CommandResult<Value> {
     if remote_backend::is_remote_mode(&*state).await {
         return remote_backend::call_remote(
             &*state,
@@ -954,7 +1164,8 @@ pub(crate) async fn generate_run_metadata(
             "generate_run_metadata",
             json!({ "workspaceId": workspace_id, "prompt": prompt }),
         )
-        .await;
+        .await
+        .map_err(CommandError::from);
     }
 
     crate::shared::codex_aux_core::generate_run_metadata_core(
@@ -979,6 +1190,7 @@ pub(crate) async fn generate_run_metadata(
         },
     )
     .await
+    .map_err(CommandError::from)
 }
 
 #[tauri::command]
@@ -987,7 +1199,7 @@ pub(crate) async fn generate_agent_description(
     description: String,
     state: State<'_, AppState>,
     app: AppHandle,
-) -> Result<crate::shared::codex_aux_core::GeneratedAgentConfiguration, String> {
+) -> CommandResult<crate::shared::codex_aux_core::GeneratedAgentConfiguration> {
     if remote_backend::is_remote_mode(&*state).await {
         let value = remote_backend::call_remote(
             &*state,
@@ -996,7 +1208,7 @@ pub(crate) async fn generate_agent_description(
             json!({ "workspaceId": workspace_id, "description": description }),
         )
         .await?;
-        return serde_json::from_value(value).map_err(|err| err.to_string());
+        return serde_json::from_value(value).map_err(|err| CommandError::from(err.to_string()));
     }
 
     crate::shared::codex_aux_core::generate_agent_description_core(
@@ -1021,4 +1233,5 @@ pub(crate) async fn generate_agent_description(
         },
     )
     .await
+    .map_err(CommandError::from)
 }
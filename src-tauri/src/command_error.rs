@@ -0,0 +1,182 @@
+use std::fmt;
+
+use serde::Serialize;
+use serde_json::{json, Value};
+
+use crate::shared::monitor_error::MonitorError;
+
+/// Machine-readable failure kinds the frontend can branch on and localize.
+#[derive(Debug, Clone, Copy, Serialize, PartialEq, Eq)]
+pub(crate) enum ErrorCode {
+    DaemonPortInUse,
+    TokenMissing,
+    RemoteUnreachable,
+    RemoteDisconnected,
+    AuthFailed,
+    /// Opening the connection timed out; the host may be down or filtered.
+    ConnectTimeout,
+    /// The host is on the tailnet but Tailscale on this device is stopped,
+    /// signed out or awaiting approval.
+    TailscaleNotConnected,
+    /// The system routes traffic through a proxy the app is not set to use.
+    ProxyRequired,
+    /// The connection was up but the reply took too long.
+    Timeout,
+    WorkspaceNotFound,
+    ThreadNotFound,
+    /// A start or stop of the same process is already under way.
+    OperationInProgress,
+    /// Another live daemon holds the daemon data directory; `details` names
+    /// its pid so the user can take it over.
+    DataDirLocked,
+    /// The requested data directory already holds files; `details` names it
+    /// so the user can confirm overwriting them.
+    DataDirNotEmpty,
+    Unsupported,
+    Unknown,
+}
+
+/// The error half of every Tauri command result.
+#[derive(Debug, Clone, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct CommandError {
+    pub(crate) code: ErrorCode,
+    /// English text, shown as-is when the frontend has no translation.
+    pub(crate) message: String,
+    pub(crate) details: Option<Value>,
+}
+
+pub(crate) type CommandResult<T> = Result<T, CommandError>;
+
+impl CommandError {
+    pub(crate) fn new(code: ErrorCode, message: impl Into<String>) -> Self {
+        Self {
+            code,
+            message: message.into(),
+            details: None,
+        }
+    }
+
+    pub(crate) fn with_details(mut self, details: Value) -> Self {
+        self.details = Some(details);
+        self
+    }
+}
+
+// Plain string errors carry no kind; code paths whose failures the frontend
+// tells apart return `MonitorError` or build a `CommandError` directly.
+impl From<String> for CommandError {
+    fn from(message: String) -> Self {
+        Self::new(ErrorCode::Unknown, message)
+    }
+}
+
+impl From<&str> for CommandError {
+    fn from(message: &str) -> Self {
+        Self::from(message.to_string())
+    }
+}
+
+// The category travels in `details` so the frontend can group failures that
+// share a code, such as a network timeout and a process timeout.
+impl From<MonitorError> for CommandError {
+    fn from(error: MonitorError) -> Self {
+        let code = match &error {
+            MonitorError::Auth(_) => ErrorCode::AuthFailed,
+            MonitorError::Unreachable(_) => ErrorCode::RemoteUnreachable,
+            MonitorError::Disconnected(_) => ErrorCode::RemoteDisconnected,
+            MonitorError::ConnectTimeout(_) => ErrorCode::ConnectTimeout,
+            MonitorError::Timeout(_) => ErrorCode::Timeout,
+            MonitorError::TailscaleNotConnected(_) => ErrorCode::TailscaleNotConnected,
+            MonitorError::ProxyRequired(_) => ErrorCode::ProxyRequired,
+            MonitorError::WorkspaceNotFound(_) => ErrorCode::WorkspaceNotFound,
+            MonitorError::ThreadNotFound(_) => ErrorCode::ThreadNotFound,
+            MonitorError::InProgress(_) => ErrorCode::OperationInProgress,
+            MonitorError::NotSupported(_) => ErrorCode::Unsupported,
+            MonitorError::Network(_) | MonitorError::Process(_) | MonitorError::Config(_) => {
+                ErrorCode::Unknown
+            }
+        };
+        let category = error.category();
+        Self::new(code, error).with_details(json!({ "category": category }))
+    }
+}
+
+// Lets commands keep being called from code that still deals in strings.
+impl From<CommandError> for String {
+    fn from(error: CommandError) -> Self {
+        error.message
+    }
+}
+
+impl fmt::Display for CommandError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn codes_come_from_the_monitor_error_variant() {
+        let code = |error: MonitorError| CommandError::from(error).code;
+        assert_eq!(
+            code(MonitorError::Disconnected("gone".to_string())),
+            ErrorCode::RemoteDisconnected
+        );
+        assert_eq!(
+            code(MonitorError::Unreachable("refused".to_string())),
+            ErrorCode::RemoteUnreachable
+        );
+        assert_eq!(
+            code(MonitorError::ConnectTimeout("slow".to_string())),
+            ErrorCode::ConnectTimeout
+        );
+        assert_eq!(
+            code(MonitorError::WorkspaceNotFound("missing".to_string())),
+            ErrorCode::WorkspaceNotFound
+        );
+        assert_eq!(
+            code(MonitorError::InProgress("busy".to_string())),
+            ErrorCode::OperationInProgress
+        );
+        // The wording no longer decides the code.
+        assert_eq!(
+            code(MonitorError::Network("timed out".to_string())),
+            ErrorCode::Unknown
+        );
+        assert_eq!(
+            CommandError::from("workspace not found").code,
+            ErrorCode::Unknown
+        );
+    }
+
+    #[test]
+    fn monitor_errors_keep_their_category() {
+        let error = CommandError::from(MonitorError::Auth("invalid token".to_string()));
+        assert_eq!(error.code, ErrorCode::AuthFailed);
+        assert_eq!(error.details, Some(json!({ "category": "auth" })));
+        let error = CommandError::from(MonitorError::Timeout(
+            "timed out waiting for daemon response".to_string(),
+        ));
+        assert_eq!(error.code, ErrorCode::Timeout);
+        assert_eq!(error.message, "timed out waiting for daemon response");
+        assert_eq!(error.details, Some(json!({ "category": "network" })));
+    }
+
+    #[test]
+    fn serializes_code_message_and_details() {
+        let error = CommandError::new(ErrorCode::DaemonPortInUse, "port busy")
+            .with_details(serde_json::json!({ "listenAddr": "0.0.0.0:4732" }));
+        assert_eq!(
+            serde_json::to_value(&error).expect("serialize"),
+            serde_json::json!({
+                "code": "DaemonPortInUse",
+                "message": "port busy",
+                "details": { "listenAddr": "0.0.0.0:4732" },
+            })
+        );
+    }
+}
@@ -0,0 +1,298 @@
+//! `codexmonitor://` links, so launchers such as Raycast, Alfred or
+//! Shortcuts can drive the monitor:
+//!
+//! - `codexmonitor://open-project?id=<workspace id>` (or `?path=<folder>`)
+//! - `codexmonitor://start-session?template=<template id>[&project=<id or folder>]`
+//! - `codexmonitor://approve?id=<client id>`
+//!
+//! Each action runs through the command it stands for, so in remote mode
+//! the daemon applies the same scope checks. Any program can open a link,
+//! so links only reach projects that are already added and saved
+//! templates, and never carry a prompt. Starting a session asks first, and
+//! `approve` only brings up the approval prompt for that client.
+
+use serde::Serialize;
+use tauri::{AppHandle, Emitter, Manager, Url};
+use tauri_plugin_deep_link::DeepLinkExt;
+use tauri_plugin_dialog::{DialogExt, MessageDialogButtons, MessageDialogKind};
+
+use crate::client_approvals;
+use crate::command_error::{CommandError, CommandResult};
+use crate::notifications::show_system_notification;
+use crate::sessions;
+use crate::state::AppState;
+use crate::types::{ClientApprovalStatus, WorkspaceInfo};
+use crate::workspaces;
+
+pub(crate) const DEEP_LINK_SCHEME: &str = "codexmonitor";
+pub(crate) const DEEP_LINK_OPEN_PROJECT_EVENT: &str = "deep-link-open-project";
+/// Carries the pending `ClientApproval` an `approve` link named, for the
+/// frontend to prompt about ahead of any others.
+pub(crate) const DEEP_LINK_REVIEW_CLIENT_EVENT: &str = "deep-link-review-client";
+
+#[derive(Debug, PartialEq, Eq)]
+enum ProjectRef {
+    Id(String),
+    Path(String),
+}
+
+#[derive(Debug, PartialEq, Eq)]
+enum DeepLinkAction {
+    OpenProject(ProjectRef),
+    StartSession {
+        template_id: String,
+        project: Option<ProjectRef>,
+    },
+    Approve {
+        client_id: String,
+    },
+}
+
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+struct OpenProjectPayload {
+    workspace_id: String,
+}
+
+/// Project a link opened before the frontend was listening, kept until the
+/// frontend asks for it.
+#[derive(Default)]
+pub(crate) struct PendingDeepLink(std::sync::Mutex<Option<String>>);
+
+fn query_value(url: &Url, key: &str) -> Option<String> {
+    url.query_pairs()
+        .find(|(name, _)| name == key)
+        .map(|(_, value)| value.trim().to_string())
+        .filter(|value| !value.is_empty())
+}
+
+fn project_ref(id: Option<String>, path: Option<String>) -> Option<ProjectRef> {
+    id.map(ProjectRef::Id).or(path.map(ProjectRef::Path))
+}
+
+fn parse_deep_link(url: &Url) -> Result<DeepLinkAction, String> {
+    if url.scheme() != DEEP_LINK_SCHEME {
+        return Err(format!("Not a {DEEP_LINK_SCHEME}:// link"));
+    }
+    // `codexmonitor://approve?…` puts the action in the host,
+    // `codexmonitor:approve?…` in the path.
+    let action = url
+        .host_str()
+        .filter(|host| !host.is_empty())
+        .unwrap_or_else(|| url.path().trim_matches('/'));
+    match action {
+        "open-project" => project_ref(query_value(url, "id"), query_value(url, "path"))
+            .map(DeepLinkAction::OpenProject)
+            .ok_or_else(|| "open-project needs an id or a path".to_string()),
+        "start-session" => {
+            let template_id = query_value(url, "template")
+                .ok_or_else(|| "start-session needs a template".to_string())?;
+            let project = query_value(url, "project").map(|project| {
+                if project.contains('/') || project.contains('\\') {
+                    ProjectRef::Path(project)
+                } else {
+                    ProjectRef::Id(project)
+                }
+            });
+            Ok(DeepLinkAction::StartSession {
+                template_id,
+                project,
+            })
+        }
+        "approve" => query_value(url, "id")
+            .map(|client_id| DeepLinkAction::Approve { client_id })
+            .ok_or_else(|| "approve needs an id".to_string()),
+        other => Err(format!("Unknown link action `{other}`")),
+    }
+}
+
+fn find_project<'a>(
+    workspaces: &'a [WorkspaceInfo],
+    project: &ProjectRef,
+) -> Option<&'a WorkspaceInfo> {
+    workspaces.iter().find(|workspace| match project {
+        ProjectRef::Id(id) => workspace.id == *id,
+        ProjectRef::Path(path) => {
+            workspace.path.trim_end_matches(['/', '\\']) == path.trim_end_matches(['/', '\\'])
+        }
+    })
+}
+
+async fn resolve_project(app: &AppHandle, project: &ProjectRef) -> CommandResult<WorkspaceInfo> {
+    let workspaces = workspaces::list_workspaces(app.state::<AppState>(), app.clone()).await?;
+    find_project(&workspaces, project)
+        .cloned()
+        .ok_or_else(|| CommandError::from("No added project matches the link".to_string()))
+}
+
+fn show_main_window(app: &AppHandle) {
+    if let Some(window) = app.get_webview_window("main") {
+        let _ = window.show();
+        let _ = window.unminimize();
+        let _ = window.set_focus();
+    }
+}
+
+/// Asks the owner in a native dialog before a link does anything that
+/// cannot be taken back.
+async fn confirm_link(app: &AppHandle, title: &str, message: String, ok_label: &str) -> bool {
+    let (tx, rx) = tokio::sync::oneshot::channel();
+    app.dialog()
+        .message(message)
+        .title(title)
+        .kind(MessageDialogKind::Warning)
+        .buttons(MessageDialogButtons::OkCancelCustom(
+            ok_label.to_string(),
+            "Cancel".to_string(),
+        ))
+        .show(move |confirmed| {
+            let _ = tx.send(confirmed);
+        });
+    rx.await.unwrap_or(false)
+}
+
+async fn dispatch(app: &AppHandle, action: DeepLinkAction, cold_start: bool) -> CommandResult<()> {
+    match action {
+        DeepLinkAction::OpenProject(project) => {
+            let project = resolve_project(app, &project).await?;
+            show_main_window(app);
+            if cold_start {
+                if let Ok(mut pending) = app.state::<PendingDeepLink>().0.lock() {
+                    *pending = Some(project.id.clone());
+                }
+            }
+            let _ = app.emit(
+                DEEP_LINK_OPEN_PROJECT_EVENT,
+                OpenProjectPayload {
+                    workspace_id: project.id,
+                },
+            );
+        }
+        DeepLinkAction::StartSession {
+            template_id,
+            project,
+        } => {
+            let project = match project {
+                Some(project) => Some(resolve_project(app, &project).await?),
+                None => None,
+            };
+            show_main_window(app);
+            let target = match &project {
+                Some(project) => format!("in {}", project.name),
+                None => "in the template's project".to_string(),
+            };
+            let message = format!(
+                "A link is asking to start a Codex session from template {template_id} {target}."
+            );
+            if !confirm_link(app, "Start Codex Session?", message, "Start").await {
+                return Ok(());
+            }
+            sessions::start_codex_session(
+                project.map(|project| project.path),
+                None,
+                None,
+                None,
+                Some(template_id),
+                None,
+                app.state::<AppState>(),
+                app.clone(),
+            )
+            .await?;
+        }
+        DeepLinkAction::Approve { client_id } => {
+            let approvals =
+                client_approvals::list_client_approvals(app.state::<AppState>(), app.clone())
+                    .await?;
+            let approval = approvals
+                .into_iter()
+                .find(|approval| {
+                    approval.client_id == client_id
+                        && approval.status == ClientApprovalStatus::Pending
+                })
+                .ok_or_else(|| {
+                    CommandError::from("No client with that id is waiting for approval".to_string())
+                })?;
+            show_main_window(app);
+            let _ = app.emit(DEEP_LINK_REVIEW_CLIENT_EVENT, approval);
+        }
+    }
+    Ok(())
+}
+
+fn handle_urls(app: &AppHandle, urls: Vec<Url>, cold_start: bool) {
+    for url in urls {
+        let app = app.clone();
+        tauri::async_runtime::spawn(async move {
+            let result = match parse_deep_link(&url) {
+                Ok(action) => dispatch(&app, action, cold_start).await,
+                Err(err) => Err(CommandError::from(err)),
+            };
+            if let Err(err) = result {
+                show_system_notification(&app, "Link failed", &err.to_string(), false).await;
+            }
+        });
+    }
+}
+
+/// Handles the link the app was started with and every later one.
+pub(crate) fn initialize(app: &AppHandle) {
+    app.manage(PendingDeepLink::default());
+    #[cfg(any(target_os = "linux", target_os = "windows"))]
+    if let Err(err) = app.deep_link().register_all() {
+        eprintln!("Failed to register {DEEP_LINK_SCHEME}:// links: {err}");
+    }
+    let handle = app.clone();
+    app.deep_link()
+        .on_open_url(move |event| handle_urls(&handle, event.urls(), false));
+    if let Ok(Some(urls)) = app.deep_link().get_current() {
+        handle_urls(app, urls, true);
+    }
+}
+
+/// The project a link opened at launch, once.
+#[tauri::command]
+pub(crate) fn take_deep_link_project(app: AppHandle) -> Option<String> {
+    app.try_state::<PendingDeepLink>()?.0.lock().ok()?.take()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(link: &str) -> Result<DeepLinkAction, String> {
+        parse_deep_link(&Url::parse(link).expect("url"))
+    }
+
+    #[test]
+    fn links_parse_into_actions() {
+        assert_eq!(
+            parse("codexmonitor://open-project?id=ws-1"),
+            Ok(DeepLinkAction::OpenProject(ProjectRef::Id(
+                "ws-1".to_string()
+            )))
+        );
+        assert_eq!(
+            parse("codexmonitor:open-project?path=%2FUsers%2Fme%2Fapp"),
+            Ok(DeepLinkAction::OpenProject(ProjectRef::Path(
+                "/Users/me/app".to_string()
+            )))
+        );
+        assert_eq!(
+            parse("codexmonitor://start-session?template=t-1&project=ws-2"),
+            Ok(DeepLinkAction::StartSession {
+                template_id: "t-1".to_string(),
+                project: Some(ProjectRef::Id("ws-2".to_string())),
+            })
+        );
+        assert_eq!(
+            parse("codexmonitor://approve/?id=client-9"),
+            Ok(DeepLinkAction::Approve {
+                client_id: "client-9".to_string()
+            })
+        );
+        assert!(parse("codexmonitor://start-session?project=ws-2").is_err());
+        assert!(parse("codexmonitor://approve?id=").is_err());
+        assert!(parse("codexmonitor://delete-everything").is_err());
+        assert!(parse("https://open-project?id=ws-1").is_err());
+    }
+}
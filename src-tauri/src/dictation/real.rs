@@ -8,6 +8,7 @@ use tauri::{AppHandle, Emitter, Manager, State};
 use tokio::io::AsyncWriteExt;
 use tokio::sync::oneshot;
 
+use crate::command_error::{CommandError, CommandResult};
 use crate::state::AppState;
 
 use cpal::traits::{DeviceTrait, HostTrait, StreamTrait};
@@ -278,8 +279,8 @@ fn ready_status(model_id: &str, path: &PathBuf) -> DictationModelStatus {
     }
 }
 
-fn emit_status(app: &AppHandle, status: &DictationModelStatus) {
-    let _ = app.emit("dictation-download", status);
+fn emit_status(state: &AppState, status: &DictationModelStatus) {
+    state.event_bus.publish("dictation-download", status);
 }
 
 fn emit_event(app: &AppHandle, event: DictationEvent) {
@@ -300,12 +301,12 @@ async fn clear_processing_cancel(app: &AppHandle, cancel_flag: &Arc<AtomicBool>)
     false
 }
 
-async fn update_status(app: &AppHandle, state: &State<'_, AppState>, status: DictationModelStatus) {
+async fn update_status(state: &State<'_, AppState>, status: DictationModelStatus) {
     {
         let mut dictation = state.dictation.lock().await;
         dictation.model_status = status.clone();
     }
-    emit_status(app, &status);
+    emit_status(state, &status);
 }
 
 async fn clear_download_state(state: &State<'_, AppState>) {
@@ -318,7 +319,7 @@ async fn resolve_model_id(state: &State<'_, AppState>, model_id: Option<String>)
     let candidate = if let Some(model_id) = model_id {
         model_id
     } else {
-        let settings = state.app_settings.lock().await;
+        let settings = state.app_settings.read().await;
         if settings.dictation_model_id.trim().is_empty() {
             DEFAULT_MODEL_ID.to_string()
         } else {
@@ -371,7 +372,7 @@ pub(crate) async fn dictation_model_status(
     app: AppHandle,
     state: State<'_, AppState>,
     model_id: Option<String>,
-) -> Result<DictationModelStatus, String> {
+) -> CommandResult<DictationModelStatus> {
     let model_id = resolve_model_id(&state, model_id).await;
     Ok(refresh_status(&app, &state, &model_id).await)
 }
@@ -381,7 +382,7 @@ pub(crate) async fn dictation_download_model(
     app: AppHandle,
     state: State<'_, AppState>,
     model_id: Option<String>,
-) -> Result<DictationModelStatus, String> {
+) -> CommandResult<DictationModelStatus> {
     let model_id = resolve_model_id(&state, model_id).await;
     let current = refresh_status(&app, &state, &model_id).await;
     if current.state == DictationModelState::Ready {
@@ -416,7 +417,7 @@ pub(crate) async fn dictation_download_model(
             path: None,
         };
     }
-    emit_status(&app, &refresh_status(&app, &state, &model_id).await);
+    emit_status(&state, &refresh_status(&app, &state, &model_id).await);
 
     let app_handle = app.clone();
     let model_id_clone = model_id.clone();
@@ -433,7 +434,7 @@ pub(crate) async fn dictation_download_model(
                     error: Some(error),
                     path: None,
                 };
-                update_status(&app_handle, &state, status).await;
+                update_status(&state, status).await;
                 clear_download_state(&state).await;
                 return;
             }
@@ -448,7 +449,7 @@ pub(crate) async fn dictation_download_model(
                     error: Some(error),
                     path: None,
                 };
-                update_status(&app_handle, &state, status).await;
+                update_status(&state, status).await;
                 clear_download_state(&state).await;
                 return;
             }
@@ -462,7 +463,7 @@ pub(crate) async fn dictation_download_model(
                 error: Some(format!("Failed to create model directory: {error}")),
                 path: None,
             };
-            update_status(&app_handle, &state, status).await;
+            update_status(&state, status).await;
             clear_download_state(&state).await;
             return;
         }
@@ -477,7 +478,7 @@ pub(crate) async fn dictation_download_model(
                     error: Some("Unknown dictation model.".to_string()),
                     path: None,
                 };
-                update_status(&app_handle, &state, status).await;
+                update_status(&state, status).await;
                 clear_download_state(&state).await;
                 return;
             }
@@ -496,7 +497,7 @@ pub(crate) async fn dictation_download_model(
                     error: Some(format!("Failed to configure download client: {error}")),
                     path: None,
                 };
-                update_status(&app_handle, &state, status).await;
+                update_status(&state, status).await;
                 clear_download_state(&state).await;
                 return;
             }
@@ -511,7 +512,7 @@ pub(crate) async fn dictation_download_model(
                     error: Some(format!("Failed to download model: {error}")),
                     path: None,
                 };
-                update_status(&app_handle, &state, status).await;
+                update_status(&state, status).await;
                 clear_download_state(&state).await;
                 return;
             }
@@ -526,7 +527,7 @@ pub(crate) async fn dictation_download_model(
                     error: Some(format!("Model download failed: {error}")),
                     path: None,
                 };
-                update_status(&app_handle, &state, status).await;
+                update_status(&state, status).await;
                 clear_download_state(&state).await;
                 return;
             }
@@ -544,7 +545,7 @@ pub(crate) async fn dictation_download_model(
                     error: Some(format!("Failed to write model: {error}")),
                     path: None,
                 };
-                update_status(&app_handle, &state, status).await;
+                update_status(&state, status).await;
                 clear_download_state(&state).await;
                 return;
             }
@@ -565,7 +566,7 @@ pub(crate) async fn dictation_download_model(
             if cancel {
                 let _ = tokio::fs::remove_file(&temp_path).await;
                 let status = missing_status(&model_id_clone);
-                update_status(&app_handle, &state, status).await;
+                update_status(&state, status).await;
                 clear_download_state(&state).await;
                 return;
             }
@@ -582,7 +583,7 @@ pub(crate) async fn dictation_download_model(
                         error: Some(format!("Model download failed: {error}")),
                         path: None,
                     };
-                    update_status(&app_handle, &state, status).await;
+                    update_status(&state, status).await;
                     clear_download_state(&state).await;
                     return;
                 }
@@ -597,7 +598,7 @@ pub(crate) async fn dictation_download_model(
                     error: Some(format!("Failed to write model: {error}")),
                     path: None,
                 };
-                update_status(&app_handle, &state, status).await;
+                update_status(&state, status).await;
                 clear_download_state(&state).await;
                 return;
             }
@@ -616,7 +617,7 @@ pub(crate) async fn dictation_download_model(
                     error: None,
                     path: None,
                 };
-                update_status(&app_handle, &state, status).await;
+                update_status(&state, status).await;
             }
         }
 
@@ -635,7 +636,7 @@ pub(crate) async fn dictation_download_model(
                 error: Some("Model hash mismatch; download canceled.".to_string()),
                 path: None,
             };
-            update_status(&app_handle, &state, status).await;
+            update_status(&state, status).await;
             clear_download_state(&state).await;
             return;
         }
@@ -649,7 +650,7 @@ pub(crate) async fn dictation_download_model(
                 error: Some(format!("Failed to finalize model: {error}")),
                 path: None,
             };
-            update_status(&app_handle, &state, status).await;
+            update_status(&state, status).await;
             clear_download_state(&state).await;
             return;
         }
@@ -663,13 +664,13 @@ pub(crate) async fn dictation_download_model(
                 error: Some(format!("Failed to move model into place: {error}")),
                 path: None,
             };
-            update_status(&app_handle, &state, status).await;
+            update_status(&state, status).await;
             clear_download_state(&state).await;
             return;
         }
 
         let status = ready_status(&model_id_clone, &model_path);
-        update_status(&app_handle, &state, status).await;
+        update_status(&state, status).await;
         clear_download_state(&state).await;
     });
 
@@ -686,7 +687,7 @@ pub(crate) async fn dictation_cancel_download(
     app: AppHandle,
     state: State<'_, AppState>,
     model_id: Option<String>,
-) -> Result<DictationModelStatus, String> {
+) -> CommandResult<DictationModelStatus> {
     let model_id = resolve_model_id(&state, model_id).await;
     {
         let mut dictation = state.dictation.lock().await;
@@ -702,7 +703,7 @@ pub(crate) async fn dictation_cancel_download(
         let _ = tokio::fs::remove_file(&temp_path).await;
     }
     let status = refresh_status(&app, &state, &model_id).await;
-    emit_status(&app, &status);
+    emit_status(&state, &status);
     Ok(status)
 }
 
@@ -711,7 +712,7 @@ pub(crate) async fn dictation_remove_model(
     app: AppHandle,
     state: State<'_, AppState>,
     model_id: Option<String>,
-) -> Result<DictationModelStatus, String> {
+) -> CommandResult<DictationModelStatus> {
     let model_id = resolve_model_id(&state, model_id).await;
     let model_path = model_path(&app, &model_id)?;
     if model_path.exists() {
@@ -732,7 +733,7 @@ pub(crate) async fn dictation_remove_model(
         dictation.model_status = missing_status(&model_id);
     }
     let status = refresh_status(&app, &state, &model_id).await;
-    emit_status(&app, &status);
+    emit_status(&state, &status);
     Ok(status)
 }
 
@@ -741,7 +742,7 @@ pub(crate) async fn dictation_start(
     preferred_language: Option<String>,
     app: AppHandle,
     state: State<'_, AppState>,
-) -> Result<DictationSessionState, String> {
+) -> CommandResult<DictationSessionState> {
     let model_id = resolve_model_id(&state, None).await;
     let model_status = refresh_status(&app, &state, &model_id).await;
     if model_status.state != DictationModelState::Ready {
@@ -752,7 +753,7 @@ pub(crate) async fn dictation_start(
                 message: message.clone(),
             },
         );
-        return Err(message);
+        return Err(message.into());
     }
     {
         let dictation = state.dictation.lock().await;
@@ -764,7 +765,7 @@ pub(crate) async fn dictation_start(
                     message: message.clone(),
                 },
             );
-            return Err(message);
+            return Err(message.into());
         }
     }
 
@@ -781,7 +782,7 @@ pub(crate) async fn dictation_start(
                     message: message.clone(),
                 },
             );
-            return Err(message);
+            return Err(message.into());
         }
         Err(error) => {
             emit_event(
@@ -790,7 +791,7 @@ pub(crate) async fn dictation_start(
                     message: error.clone(),
                 },
             );
-            return Err(error);
+            return Err(error.into());
         }
     }
 
@@ -823,7 +824,7 @@ pub(crate) async fn dictation_start(
                     message: message.clone(),
                 },
             );
-            return Err(message);
+            return Err(message.into());
         }
         Err(_) => {
             let message = "Failed to start microphone capture.".to_string();
@@ -833,7 +834,7 @@ pub(crate) async fn dictation_start(
                     message: message.clone(),
                 },
             );
-            return Err(message);
+            return Err(message.into());
         }
     };
 
@@ -861,15 +862,17 @@ pub(crate) async fn dictation_start(
 }
 
 #[tauri::command]
-pub(crate) async fn dictation_request_permission(app: AppHandle) -> Result<bool, String> {
-    request_microphone_permission(&app).await
+pub(crate) async fn dictation_request_permission(app: AppHandle) -> CommandResult<bool> {
+    request_microphone_permission(&app)
+        .await
+        .map_err(CommandError::from)
 }
 
 #[tauri::command]
 pub(crate) async fn dictation_stop(
     app: AppHandle,
     state: State<'_, AppState>,
-) -> Result<DictationSessionState, String> {
+) -> CommandResult<DictationSessionState> {
     let cancel_flag = Arc::new(AtomicBool::new(false));
     let (audio, sample_rate, model_id, preferred_language, stopped, stop_tx) = {
         let mut dictation = state.dictation.lock().await;
@@ -881,7 +884,7 @@ pub(crate) async fn dictation_stop(
                     message: message.clone(),
                 },
             );
-            return Err(message);
+            return Err(message.into());
         }
         dictation.session_state = DictationSessionState::Processing;
         dictation.processing_cancel = Some(Arc::clone(&cancel_flag));
@@ -1047,7 +1050,7 @@ pub(crate) async fn dictation_stop(
 pub(crate) async fn dictation_cancel(
     app: AppHandle,
     state: State<'_, AppState>,
-) -> Result<DictationSessionState, String> {
+) -> CommandResult<DictationSessionState> {
     {
         let mut dictation = state.dictation.lock().await;
         if dictation.session_state == DictationSessionState::Processing {
@@ -1080,7 +1083,7 @@ pub(crate) async fn dictation_cancel(
                     message: message.clone(),
                 },
             );
-            return Err(message);
+            return Err(message.into());
         }
         dictation.session_state = DictationSessionState::Idle;
         let session = dictation
@@ -1,10 +1,17 @@
 use serde::Serialize;
 use tauri::{AppHandle, State};
 
+use crate::command_error::CommandResult;
+use crate::platform;
+use crate::shared::monitor_error::MonitorError;
 use crate::state::AppState;
+use crate::types::PlatformSubsystem;
 
 const DEFAULT_MODEL_ID: &str = "base";
-const UNSUPPORTED_MESSAGE: &str = "Dictation is not available on mobile builds.";
+
+fn unsupported() -> MonitorError {
+    platform::unsupported(PlatformSubsystem::Dictation)
+}
 
 #[derive(Debug, Serialize, Clone, Copy, PartialEq, Eq)]
 #[serde(rename_all = "lowercase")]
@@ -53,7 +60,7 @@ impl Default for DictationState {
                 state: DictationModelState::Missing,
                 model_id: DEFAULT_MODEL_ID.to_string(),
                 progress: None,
-                error: Some(UNSUPPORTED_MESSAGE.to_string()),
+                error: Some(unsupported().to_string()),
                 path: None,
             },
             session_state: DictationSessionState::Idle,
@@ -66,12 +73,12 @@ pub(crate) async fn dictation_model_status(
     _app: AppHandle,
     _state: State<'_, AppState>,
     model_id: Option<String>,
-) -> Result<DictationModelStatus, String> {
+) -> CommandResult<DictationModelStatus> {
     Ok(DictationModelStatus {
         state: DictationModelState::Missing,
         model_id: model_id.unwrap_or_else(|| DEFAULT_MODEL_ID.to_string()),
         progress: None,
-        error: Some(UNSUPPORTED_MESSAGE.to_string()),
+        error: Some(unsupported().to_string()),
         path: None,
     })
 }
@@ -81,7 +88,7 @@ pub(crate) async fn dictation_download_model(
     app: AppHandle,
     state: State<'_, AppState>,
     model_id: Option<String>,
-) -> Result<DictationModelStatus, String> {
+) -> CommandResult<DictationModelStatus> {
     dictation_model_status(app, state, model_id).await
 }
 
@@ -90,7 +97,7 @@ pub(crate) async fn dictation_cancel_download(
     app: AppHandle,
     state: State<'_, AppState>,
     model_id: Option<String>,
-) -> Result<DictationModelStatus, String> {
+) -> CommandResult<DictationModelStatus> {
     dictation_model_status(app, state, model_id).await
 }
 
@@ -99,7 +106,7 @@ pub(crate) async fn dictation_remove_model(
     app: AppHandle,
     state: State<'_, AppState>,
     model_id: Option<String>,
-) -> Result<DictationModelStatus, String> {
+) -> CommandResult<DictationModelStatus> {
     dictation_model_status(app, state, model_id).await
 }
 
@@ -108,12 +115,12 @@ pub(crate) async fn dictation_start(
     _preferred_language: Option<String>,
     _app: AppHandle,
     _state: State<'_, AppState>,
-) -> Result<DictationSessionState, String> {
-    Err(UNSUPPORTED_MESSAGE.to_string())
+) -> CommandResult<DictationSessionState> {
+    Err(unsupported().into())
 }
 
 #[tauri::command]
-pub(crate) async fn dictation_request_permission(_app: AppHandle) -> Result<bool, String> {
+pub(crate) async fn dictation_request_permission(_app: AppHandle) -> CommandResult<bool> {
     Ok(false)
 }
 
@@ -121,14 +128,14 @@ pub(crate) async fn dictation_request_permission(_app: AppHandle) -> Result<bool
 pub(crate) async fn dictation_stop(
     _app: AppHandle,
     _state: State<'_, AppState>,
-) -> Result<DictationSessionState, String> {
-    Err(UNSUPPORTED_MESSAGE.to_string())
+) -> CommandResult<DictationSessionState> {
+    Err(unsupported().into())
 }
 
 #[tauri::command]
 pub(crate) async fn dictation_cancel(
     _app: AppHandle,
     _state: State<'_, AppState>,
-) -> Result<DictationSessionState, String> {
-    Err(UNSUPPORTED_MESSAGE.to_string())
+) -> CommandResult<DictationSessionState> {
+    Err(unsupported().into())
 }
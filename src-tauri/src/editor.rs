@@ -0,0 +1,85 @@
+//! Editor commands. In remote mode they are forwarded to the daemon, which
+//! launches the editor configured on its own host.
+
+use serde_json::json;
+use tauri::{AppHandle, State};
+
+use crate::command_error::{CommandError, CommandResult};
+use crate::remote_backend;
+use crate::shared::editor_core::{
+    list_editors_core, open_diff_in_editor_core, open_in_editor_core,
+};
+use crate::shared::workspace_rpc;
+use crate::state::AppState;
+use crate::types::EditorInfo;
+
+/// Configured launchers and whether each command is on `PATH`.
+#[tauri::command]
+pub(crate) async fn list_editors(
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> CommandResult<Vec<EditorInfo>> {
+    if remote_backend::is_remote_mode(&*state).await {
+        let response = remote_backend::call_remote(&*state, app, "list_editors", json!({})).await?;
+        return serde_json::from_value(response).map_err(|err| CommandError::from(err.to_string()));
+    }
+
+    Ok(list_editors_core(&*state.app_settings.read().await))
+}
+
+#[tauri::command]
+pub(crate) async fn open_in_editor(
+    path: String,
+    line: Option<u32>,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> CommandResult<()> {
+    if remote_backend::is_remote_mode(&*state).await {
+        let request = workspace_rpc::OpenInEditorRequest { path, line };
+        remote_backend::call_remote(
+            &*state,
+            app,
+            "open_in_editor",
+            workspace_rpc::to_params(&request)?,
+        )
+        .await?;
+        return Ok(());
+    }
+
+    let settings = state.app_settings.read().await.clone();
+    open_in_editor_core(&settings, path, line)
+        .await
+        .map_err(CommandError::from)
+}
+
+/// Opens the editor's diff view of a file a session changed.
+#[tauri::command]
+pub(crate) async fn open_diff_in_editor(
+    session_id: String,
+    file: String,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> CommandResult<()> {
+    if remote_backend::is_remote_mode(&*state).await {
+        let request = workspace_rpc::OpenDiffInEditorRequest { session_id, file };
+        remote_backend::call_remote(
+            &*state,
+            app,
+            "open_diff_in_editor",
+            workspace_rpc::to_params(&request)?,
+        )
+        .await?;
+        return Ok(());
+    }
+
+    let settings = state.app_settings.read().await.clone();
+    open_diff_in_editor_core(
+        &state.session_runtimes,
+        state.data_dir(),
+        &settings,
+        session_id,
+        file,
+    )
+    .await
+    .map_err(CommandError::from)
+}
@@ -0,0 +1,256 @@
+//! One place for status-style events to reach the webview. Each event name
+//! (and key, for events about one of many things) is held to at most one
+//! emission per window from `AppSettings::event_coalescing_ms`: the first
+//! update goes out at once and later ones inside the window collapse into a
+//! single trailing emission of the latest payload. The bus lives in
+//! `AppState`, so every emission goes through the app that owns it.
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use serde::Serialize;
+use serde_json::Value;
+use tauri::{AppHandle, Emitter, Manager};
+use tokio::time::{sleep, Instant};
+
+use crate::state::AppState;
+
+type Topics = Arc<Mutex<HashMap<(String, String), Topic>>>;
+
+#[derive(Default)]
+struct Topic {
+    last_emitted: Option<Instant>,
+    pending: Option<Value>,
+    flush_scheduled: bool,
+}
+
+#[derive(Debug, PartialEq)]
+enum Offer {
+    EmitNow(Value),
+    /// Held as the pending payload; the caller schedules one flush.
+    FlushAfter(Duration),
+    /// Replaced a pending payload whose flush is already scheduled.
+    Coalesced,
+}
+
+fn offer(topic: &mut Topic, payload: Value, window: Duration, now: Instant) -> Offer {
+    let elapsed = topic
+        .last_emitted
+        .map(|last| now.saturating_duration_since(last));
+    match elapsed {
+        Some(elapsed) if elapsed < window => {
+            topic.pending = Some(payload);
+            if topic.flush_scheduled {
+                Offer::Coalesced
+            } else {
+                topic.flush_scheduled = true;
+                Offer::FlushAfter(window - elapsed)
+            }
+        }
+        _ => {
+            // A late flush must not follow up with an older payload.
+            topic.pending = None;
+            topic.last_emitted = Some(now);
+            Offer::EmitNow(payload)
+        }
+    }
+}
+
+fn take_pending(topic: &mut Topic, now: Instant) -> Option<Value> {
+    topic.flush_scheduled = false;
+    let payload = topic.pending.take()?;
+    topic.last_emitted = Some(now);
+    Some(payload)
+}
+
+/// Whether `topic` holds nothing a fresh one would not: no pending payload
+/// and a window that has run out, so its entry can go.
+fn is_quiet(topic: &Topic, window: Duration, now: Instant) -> bool {
+    !topic.flush_scheduled
+        && topic.pending.is_none()
+        && topic
+            .last_emitted
+            .is_none_or(|last| now.saturating_duration_since(last) >= window)
+}
+
+/// Coalescing state for the app's status events. Without an app (state
+/// built for tests) every event is dropped.
+#[derive(Default)]
+pub(crate) struct EventBus {
+    app: Option<AppHandle>,
+    topics: Topics,
+}
+
+impl EventBus {
+    pub(crate) fn new(app: AppHandle) -> Self {
+        Self {
+            app: Some(app),
+            topics: Topics::default(),
+        }
+    }
+
+    pub(crate) fn publish<T: Serialize>(&self, event: &'static str, payload: &T) {
+        self.publish_keyed(event, "", payload);
+    }
+
+    /// Like `publish`, coalescing only with earlier updates for the same
+    /// `key` (a session id, for example).
+    pub(crate) fn publish_keyed<T: Serialize>(&self, event: &'static str, key: &str, payload: &T) {
+        let Some(app) = self.app.as_ref() else {
+            return;
+        };
+        let Ok(payload) = serde_json::to_value(payload) else {
+            return;
+        };
+        let window = coalescing_window(app, event);
+        if window.is_zero() {
+            let _ = app.emit(event, payload);
+            return;
+        }
+
+        let topic_key = (event.to_string(), key.to_string());
+        let now = Instant::now();
+        let decision = {
+            let Ok(mut topics) = self.topics.lock() else {
+                return;
+            };
+            // Keys of this event whose windows ran out would start afresh
+            // anyway; dropping them keeps one-off keys from piling up.
+            topics.retain(|(name, _), topic| name != event || !is_quiet(topic, window, now));
+            let topic = topics.entry(topic_key.clone()).or_default();
+            offer(topic, payload, window, now)
+        };
+        match decision {
+            Offer::EmitNow(payload) => {
+                let _ = app.emit(event, payload);
+            }
+            Offer::FlushAfter(delay) => {
+                let app = app.clone();
+                let topics = Arc::clone(&self.topics);
+                tauri::async_runtime::spawn(async move {
+                    sleep(delay).await;
+                    let payload = topics.lock().ok().and_then(|mut topics| {
+                        let now = Instant::now();
+                        let topic = topics.get_mut(&topic_key)?;
+                        let payload = take_pending(topic, now);
+                        if is_quiet(topic, window, now) {
+                            topics.remove(&topic_key);
+                        }
+                        payload
+                    });
+                    if let Some(payload) = payload {
+                        let _ = app.emit(event, payload);
+                    }
+                });
+            }
+            Offer::Coalesced => {}
+        }
+    }
+
+    /// Drops every topic coalescing under `key`, once the thing it names
+    /// (a session, for example) has ended.
+    pub(crate) fn forget_key(&self, key: &str) {
+        if let Ok(mut topics) = self.topics.lock() {
+            topics.retain(|(_, topic_key), _| topic_key != key);
+        }
+    }
+}
+
+fn coalescing_window(app: &AppHandle, event: &str) -> Duration {
+    // A settings write in progress only costs this update its coalescing.
+    let window_ms = app
+        .try_state::<AppState>()
+        .and_then(|state| {
+            state
+                .app_settings
+                .try_read()
+                .ok()
+                .and_then(|settings| settings.event_coalescing_ms.get(event).copied())
+        })
+        .unwrap_or(0);
+    Duration::from_millis(u64::from(window_ms))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn updates_inside_the_window_collapse_into_the_latest() {
+        let window = Duration::from_millis(250);
+        let start = Instant::now();
+        let mut topic = Topic::default();
+
+        assert_eq!(
+            offer(&mut topic, json!(1), window, start),
+            Offer::EmitNow(json!(1))
+        );
+        assert_eq!(
+            offer(
+                &mut topic,
+                json!(2),
+                window,
+                start + Duration::from_millis(100)
+            ),
+            Offer::FlushAfter(Duration::from_millis(150))
+        );
+        assert_eq!(
+            offer(
+                &mut topic,
+                json!(3),
+                window,
+                start + Duration::from_millis(200)
+            ),
+            Offer::Coalesced
+        );
+
+        let flushed_at = start + window;
+        assert_eq!(take_pending(&mut topic, flushed_at), Some(json!(3)));
+        assert_eq!(take_pending(&mut topic, flushed_at), None);
+
+        // The flush starts a new window.
+        assert!(matches!(
+            offer(
+                &mut topic,
+                json!(4),
+                window,
+                flushed_at + Duration::from_millis(10)
+            ),
+            Offer::FlushAfter(_)
+        ));
+        assert_eq!(
+            offer(&mut topic, json!(5), window, flushed_at + window + window),
+            Offer::EmitNow(json!(5))
+        );
+        assert_eq!(take_pending(&mut topic, flushed_at + window + window), None);
+    }
+
+    #[test]
+    fn topics_are_quiet_once_their_window_passes_with_nothing_pending() {
+        let window = Duration::from_millis(250);
+        let start = Instant::now();
+        let mut topic = Topic::default();
+        assert!(is_quiet(&topic, window, start));
+
+        offer(&mut topic, json!(1), window, start);
+        assert!(!is_quiet(
+            &topic,
+            window,
+            start + Duration::from_millis(100)
+        ));
+        assert!(is_quiet(&topic, window, start + window));
+
+        offer(
+            &mut topic,
+            json!(2),
+            window,
+            start + Duration::from_millis(100),
+        );
+        assert!(!is_quiet(&topic, window, start + window));
+        assert_eq!(take_pending(&mut topic, start + window), Some(json!(2)));
+        assert!(!is_quiet(&topic, window, start + window));
+        assert!(is_quiet(&topic, window, start + window + window));
+    }
+}
@@ -1,6 +1,18 @@
-use tauri::{AppHandle, Emitter};
+use tauri::{AppHandle, Emitter, Manager};
 
-use crate::backend::events::{AppServerEvent, EventSink, TerminalExit, TerminalOutput};
+use crate::backend::events::{
+    AppServerEvent, CodexSessionExit, CodexSessionOutput, CodexSessionQueue, EventSink,
+    RemoteCommandOutput, TerminalExit, TerminalOutput,
+};
+use crate::ci_status::watch_session_branch;
+use crate::hooks::run_hooks;
+use crate::shared::hooks_core::session_queue_hook_event;
+use crate::shared::webhooks_core::app_server_webhook_event;
+use crate::state::AppState;
+use crate::tray::{note_app_server_message, note_session_exit};
+use crate::types::{CodexLoginFlow, HookEvent, WebhookEvent};
+use crate::webhooks::notify_event;
+use crate::window::raise_for_activity;
 
 #[derive(Clone)]
 pub(crate) struct TauriEventSink {
@@ -15,6 +27,13 @@ impl TauriEventSink {
 
 impl EventSink for TauriEventSink {
     fn emit_app_server_event(&self, event: AppServerEvent) {
+        if let Some((webhook, data)) = app_server_webhook_event(&event.workspace_id, &event.message)
+        {
+            run_hooks(&self.app, HookEvent::ApprovalRequested, data.clone());
+            raise_for_activity(&self.app, webhook, &data);
+            notify_event(&self.app, webhook, data);
+        }
+        note_app_server_message(&self.app, &event.workspace_id, &event.message);
         let _ = self.app.emit("app-server-event", event);
     }
 
@@ -25,4 +44,43 @@ impl EventSink for TauriEventSink {
     fn emit_terminal_exit(&self, event: TerminalExit) {
         let _ = self.app.emit("terminal-exit", event);
     }
+
+    fn emit_remote_command_output(&self, event: RemoteCommandOutput) {
+        let _ = self.app.emit("remote-command-output", event);
+    }
+
+    fn emit_codex_session_output(&self, event: CodexSessionOutput) {
+        let _ = self.app.emit("codex-session-output", event);
+    }
+
+    fn emit_codex_session_exit(&self, event: CodexSessionExit) {
+        if let Ok(data) = serde_json::to_value(&event) {
+            run_hooks(&self.app, HookEvent::SessionFinished, data.clone());
+            raise_for_activity(&self.app, WebhookEvent::SessionCompleted, &data);
+            notify_event(&self.app, WebhookEvent::SessionCompleted, data);
+        }
+        note_session_exit(&self.app, &event.session_id, event.exit_code);
+        watch_session_branch(&self.app, event.session_id.clone());
+        if let Some(state) = self.app.try_state::<AppState>() {
+            state.event_bus.forget_key(&event.session_id);
+        }
+        let _ = self.app.emit("codex-session-exit", event);
+    }
+
+    fn emit_codex_session_queue(&self, event: CodexSessionQueue) {
+        if let Some(hook) = session_queue_hook_event(&event.status) {
+            if let Ok(data) = serde_json::to_value(&event) {
+                run_hooks(&self.app, hook, data);
+            }
+        }
+        if let Some(state) = self.app.try_state::<AppState>() {
+            state
+                .event_bus
+                .publish_keyed("codex-session-queue", &event.session_id, &event);
+        }
+    }
+
+    fn emit_codex_login_progress(&self, event: CodexLoginFlow) {
+        let _ = self.app.emit("codex-login-progress", event);
+    }
 }
@@ -4,10 +4,12 @@ use tauri::{AppHandle, State};
 
 use self::io::TextFileResponse;
 use self::policy::{FileKind, FileScope};
+use crate::command_error::{CommandError, CommandResult};
 use crate::remote_backend;
-use crate::shared::codex_core;
 use crate::shared::files_core::{file_read_core, file_write_core};
+use crate::shared::{codex_core, file_browser_core, workspace_rpc};
 use crate::state::AppState;
+use crate::types::{RemoteDirListing, RemoteFileChunk};
 
 pub(crate) mod io;
 pub(crate) mod ops;
@@ -68,8 +70,10 @@ pub(crate) async fn file_read(
     workspace_id: Option<String>,
     state: State<'_, AppState>,
     app: AppHandle,
-) -> Result<TextFileResponse, String> {
-    file_read_impl(scope, kind, workspace_id, &*state, &app).await
+) -> CommandResult<TextFileResponse> {
+    file_read_impl(scope, kind, workspace_id, &*state, &app)
+        .await
+        .map_err(CommandError::from)
 }
 
 #[tauri::command]
@@ -80,8 +84,74 @@ pub(crate) async fn file_write(
     content: String,
     state: State<'_, AppState>,
     app: AppHandle,
-) -> Result<(), String> {
-    file_write_impl(scope, kind, workspace_id, content, &*state, &app).await
+) -> CommandResult<()> {
+    file_write_impl(scope, kind, workspace_id, content, &*state, &app)
+        .await
+        .map_err(CommandError::from)
+}
+
+#[tauri::command]
+pub(crate) async fn remote_list_dir(
+    workspace_id: String,
+    rel_path: Option<String>,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> CommandResult<RemoteDirListing> {
+    if remote_backend::is_remote_mode(&*state).await {
+        let request = workspace_rpc::RemoteListDirRequest {
+            workspace_id,
+            rel_path,
+        };
+        let response = remote_backend::call_remote(
+            &*state,
+            app,
+            "remote_list_dir",
+            workspace_rpc::to_params(&request)?,
+        )
+        .await?;
+        return serde_json::from_value(response).map_err(|err| CommandError::from(err.to_string()));
+    }
+
+    file_browser_core::remote_list_dir_core(&state.workspaces, workspace_id, rel_path)
+        .await
+        .map_err(CommandError::from)
+}
+
+#[tauri::command]
+pub(crate) async fn remote_read_file(
+    workspace_id: String,
+    rel_path: String,
+    offset: Option<u64>,
+    length: Option<u64>,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> CommandResult<RemoteFileChunk> {
+    if remote_backend::is_remote_mode(&*state).await {
+        let request = workspace_rpc::RemoteReadFileRequest {
+            workspace_id,
+            rel_path,
+            offset,
+            length,
+        };
+        let response = remote_backend::call_remote(
+            &*state,
+            app,
+            "remote_read_file",
+            workspace_rpc::to_params(&request)?,
+        )
+        .await?;
+        return serde_json::from_value(response).map_err(|err| CommandError::from(err.to_string()));
+    }
+
+    file_browser_core::remote_read_file_core(
+        &state.workspaces,
+        workspace_id,
+        rel_path,
+        offset,
+        length,
+    )
+    .await
+    .map_err(CommandError::from)
 }
 
 #[tauri::command]
@@ -89,32 +159,33 @@ pub(crate) async fn read_image_as_data_url(
     path: String,
     state: State<'_, AppState>,
     app: AppHandle,
-) -> Result<String, String> {
+) -> CommandResult<String> {
     let trimmed_path = path.trim();
     if trimmed_path.is_empty() {
-        return Err("Image path is required".to_string());
+        return Err("Image path is required".to_string().into());
     }
 
-    let mobile_runtime = cfg!(any(target_os = "ios", target_os = "android"));
-    let remote_mode = remote_backend::is_remote_mode(&*state).await;
-    if !mobile_runtime && !remote_mode {
-        return Err("Image conversion is only supported in remote backend mode or on mobile runtimes".to_string());
+    // Mobile builds are always in remote mode.
+    if !remote_backend::is_remote_mode(&*state).await {
+        return Err("Image conversion is only supported in remote backend mode"
+            .to_string()
+            .into());
     }
 
     let normalized = codex_core::normalize_file_path(trimmed_path);
     if normalized.is_empty() {
-        return Err("Image path is required".to_string());
+        return Err("Image path is required".to_string().into());
     }
 
     let _ = app;
-    codex_core::read_image_as_data_url_core(&normalized)
+    codex_core::read_image_as_data_url_core(&normalized).map_err(CommandError::from)
 }
 
 #[tauri::command]
-pub(crate) fn write_text_file(path: String, content: String) -> Result<(), String> {
+pub(crate) fn write_text_file(path: String, content: String) -> CommandResult<()> {
     let target = PathBuf::from(path.trim());
     if target.as_os_str().is_empty() {
-        return Err("Path is required".to_string());
+        return Err("Path is required".to_string().into());
     }
     if let Some(parent) = target.parent() {
         if !parent.as_os_str().is_empty() {
@@ -122,5 +193,7 @@ pub(crate) fn write_text_file(path: String, content: String) -> Result<(), Strin
                 .map_err(|err| format!("Failed to create export directory: {err}"))?;
         }
     }
-    std::fs::write(&target, content).map_err(|err| format!("Failed to write export file: {err}"))
+    std::fs::write(&target, content)
+        .map_err(|err| format!("Failed to write export file: {err}"))
+        .map_err(CommandError::from)
 }
@@ -3,6 +3,7 @@ use serde::Serialize;
 use serde_json::Value;
 use tauri::{AppHandle, State};
 
+use crate::command_error::{CommandError, CommandResult};
 use crate::remote_backend;
 use crate::shared::{git_rpc, git_ui_core};
 use crate::state::AppState;
@@ -32,6 +33,7 @@ async fn call_remote_if_enabled(
     remote_backend::call_remote(state, app.clone(), method, params)
         .await
         .map(Some)
+        .map_err(String::from)
 }
 
 async fn call_remote_typed_if_enabled<T: DeserializeOwned>(
@@ -83,7 +85,7 @@ pub(crate) async fn get_git_status(
     workspace_id: String,
     state: State<'_, AppState>,
     app: AppHandle,
-) -> Result<Value, String> {
+) -> CommandResult<Value> {
     let request = git_rpc::WorkspaceIdRequest {
         workspace_id: workspace_id.clone(),
     };
@@ -93,7 +95,9 @@ pub(crate) async fn get_git_status(
         git_rpc::METHOD_GET_GIT_STATUS,
         git_remote_params(&request)?
     );
-    git_ui_core::get_git_status_core(&state.workspaces, workspace_id).await
+    git_ui_core::get_git_status_core(&state.workspaces, workspace_id)
+        .await
+        .map_err(CommandError::from)
 }
 
 #[tauri::command]
@@ -103,7 +107,7 @@ pub(crate) async fn init_git_repo(
     force: Option<bool>,
     state: State<'_, AppState>,
     app: AppHandle,
-) -> Result<Value, String> {
+) -> CommandResult<Value> {
     let request = git_rpc::InitGitRepoRequest {
         workspace_id: workspace_id.clone(),
         branch: branch.clone(),
@@ -122,6 +126,7 @@ pub(crate) async fn init_git_repo(
         force.unwrap_or(false),
     )
     .await
+    .map_err(CommandError::from)
 }
 
 #[tauri::command]
@@ -132,7 +137,7 @@ pub(crate) async fn create_github_repo(
     branch: Option<String>,
     state: State<'_, AppState>,
     app: AppHandle,
-) -> Result<Value, String> {
+) -> CommandResult<Value> {
     let request = git_rpc::CreateGitHubRepoRequest {
         workspace_id: workspace_id.clone(),
         repo: repo.clone(),
@@ -147,6 +152,7 @@ pub(crate) async fn create_github_repo(
     );
     git_ui_core::create_github_repo_core(&state.workspaces, workspace_id, repo, visibility, branch)
         .await
+        .map_err(CommandError::from)
 }
 
 #[tauri::command]
@@ -155,7 +161,7 @@ pub(crate) async fn stage_git_file(
     path: String,
     state: State<'_, AppState>,
     app: AppHandle,
-) -> Result<(), String> {
+) -> CommandResult<()> {
     let request = git_rpc::WorkspacePathRequest {
         workspace_id: workspace_id.clone(),
         path: path.clone(),
@@ -166,7 +172,9 @@ pub(crate) async fn stage_git_file(
         git_rpc::METHOD_STAGE_GIT_FILE,
         git_remote_params(&request)?
     );
-    git_ui_core::stage_git_file_core(&state.workspaces, workspace_id, path).await
+    git_ui_core::stage_git_file_core(&state.workspaces, workspace_id, path)
+        .await
+        .map_err(CommandError::from)
 }
 
 #[tauri::command]
@@ -174,7 +182,7 @@ pub(crate) async fn stage_git_all(
     workspace_id: String,
     state: State<'_, AppState>,
     app: AppHandle,
-) -> Result<(), String> {
+) -> CommandResult<()> {
     let request = git_rpc::WorkspaceIdRequest {
         workspace_id: workspace_id.clone(),
     };
@@ -184,7 +192,9 @@ pub(crate) async fn stage_git_all(
         git_rpc::METHOD_STAGE_GIT_ALL,
         git_remote_params(&request)?
     );
-    git_ui_core::stage_git_all_core(&state.workspaces, workspace_id).await
+    git_ui_core::stage_git_all_core(&state.workspaces, workspace_id)
+        .await
+        .map_err(CommandError::from)
 }
 
 #[tauri::command]
@@ -193,7 +203,7 @@ pub(crate) async fn unstage_git_file(
     path: String,
     state: State<'_, AppState>,
     app: AppHandle,
-) -> Result<(), String> {
+) -> CommandResult<()> {
     let request = git_rpc::WorkspacePathRequest {
         workspace_id: workspace_id.clone(),
         path: path.clone(),
@@ -204,7 +214,9 @@ pub(crate) async fn unstage_git_file(
         git_rpc::METHOD_UNSTAGE_GIT_FILE,
         git_remote_params(&request)?
     );
-    git_ui_core::unstage_git_file_core(&state.workspaces, workspace_id, path).await
+    git_ui_core::unstage_git_file_core(&state.workspaces, workspace_id, path)
+        .await
+        .map_err(CommandError::from)
 }
 
 #[tauri::command]
@@ -213,7 +225,7 @@ pub(crate) async fn revert_git_file(
     path: String,
     state: State<'_, AppState>,
     app: AppHandle,
-) -> Result<(), String> {
+) -> CommandResult<()> {
     let request = git_rpc::WorkspacePathRequest {
         workspace_id: workspace_id.clone(),
         path: path.clone(),
@@ -224,7 +236,9 @@ pub(crate) async fn revert_git_file(
         git_rpc::METHOD_REVERT_GIT_FILE,
         git_remote_params(&request)?
     );
-    git_ui_core::revert_git_file_core(&state.workspaces, workspace_id, path).await
+    git_ui_core::revert_git_file_core(&state.workspaces, workspace_id, path)
+        .await
+        .map_err(CommandError::from)
 }
 
 #[tauri::command]
@@ -232,7 +246,7 @@ pub(crate) async fn revert_git_all(
     workspace_id: String,
     state: State<'_, AppState>,
     app: AppHandle,
-) -> Result<(), String> {
+) -> CommandResult<()> {
     let request = git_rpc::WorkspaceIdRequest {
         workspace_id: workspace_id.clone(),
     };
@@ -242,7 +256,9 @@ pub(crate) async fn revert_git_all(
         git_rpc::METHOD_REVERT_GIT_ALL,
         git_remote_params(&request)?
     );
-    git_ui_core::revert_git_all_core(&state.workspaces, workspace_id).await
+    git_ui_core::revert_git_all_core(&state.workspaces, workspace_id)
+        .await
+        .map_err(CommandError::from)
 }
 
 #[tauri::command]
@@ -251,7 +267,7 @@ pub(crate) async fn commit_git(
     message: String,
     state: State<'_, AppState>,
     app: AppHandle,
-) -> Result<(), String> {
+) -> CommandResult<()> {
     let request = git_rpc::WorkspaceMessageRequest {
         workspace_id: workspace_id.clone(),
         message: message.clone(),
@@ -262,7 +278,9 @@ pub(crate) async fn commit_git(
         git_rpc::METHOD_COMMIT_GIT,
         git_remote_params(&request)?
     );
-    git_ui_core::commit_git_core(&state.workspaces, workspace_id, message).await
+    git_ui_core::commit_git_core(&state.workspaces, workspace_id, message)
+        .await
+        .map_err(CommandError::from)
 }
 
 #[tauri::command]
@@ -270,7 +288,7 @@ pub(crate) async fn push_git(
     workspace_id: String,
     state: State<'_, AppState>,
     app: AppHandle,
-) -> Result<(), String> {
+) -> CommandResult<()> {
     let request = git_rpc::WorkspaceIdRequest {
         workspace_id: workspace_id.clone(),
     };
@@ -280,7 +298,9 @@ pub(crate) async fn push_git(
         git_rpc::METHOD_PUSH_GIT,
         git_remote_params(&request)?
     );
-    git_ui_core::push_git_core(&state.workspaces, workspace_id).await
+    git_ui_core::push_git_core(&state.workspaces, workspace_id)
+        .await
+        .map_err(CommandError::from)
 }
 
 #[tauri::command]
@@ -288,7 +308,7 @@ pub(crate) async fn pull_git(
     workspace_id: String,
     state: State<'_, AppState>,
     app: AppHandle,
-) -> Result<(), String> {
+) -> CommandResult<()> {
     let request = git_rpc::WorkspaceIdRequest {
         workspace_id: workspace_id.clone(),
     };
@@ -298,7 +318,9 @@ pub(crate) async fn pull_git(
         git_rpc::METHOD_PULL_GIT,
         git_remote_params(&request)?
     );
-    git_ui_core::pull_git_core(&state.workspaces, workspace_id).await
+    git_ui_core::pull_git_core(&state.workspaces, workspace_id)
+        .await
+        .map_err(CommandError::from)
 }
 
 #[tauri::command]
@@ -306,7 +328,7 @@ pub(crate) async fn fetch_git(
     workspace_id: String,
     state: State<'_, AppState>,
     app: AppHandle,
-) -> Result<(), String> {
+) -> CommandResult<()> {
     let request = git_rpc::WorkspaceIdRequest {
         workspace_id: workspace_id.clone(),
     };
@@ -316,7 +338,9 @@ pub(crate) async fn fetch_git(
         git_rpc::METHOD_FETCH_GIT,
         git_remote_params(&request)?
     );
-    git_ui_core::fetch_git_core(&state.workspaces, workspace_id).await
+    git_ui_core::fetch_git_core(&state.workspaces, workspace_id)
+        .await
+        .map_err(CommandError::from)
 }
 
 #[tauri::command]
@@ -324,7 +348,7 @@ pub(crate) async fn sync_git(
     workspace_id: String,
     state: State<'_, AppState>,
     app: AppHandle,
-) -> Result<(), String> {
+) -> CommandResult<()> {
     let request = git_rpc::WorkspaceIdRequest {
         workspace_id: workspace_id.clone(),
     };
@@ -334,7 +358,9 @@ pub(crate) async fn sync_git(
         git_rpc::METHOD_SYNC_GIT,
         git_remote_params(&request)?
     );
-    git_ui_core::sync_git_core(&state.workspaces, workspace_id).await
+    git_ui_core::sync_git_core(&state.workspaces, workspace_id)
+        .await
+        .map_err(CommandError::from)
 }
 
 #[tauri::command]
@@ -343,7 +369,7 @@ pub(crate) async fn list_git_roots(
     depth: Option<usize>,
     state: State<'_, AppState>,
     app: AppHandle,
-) -> Result<Vec<String>, String> {
+) -> CommandResult<Vec<String>> {
     let request = git_rpc::ListGitRootsRequest {
         workspace_id: workspace_id.clone(),
         depth: optional_usize_to_u32(depth),
@@ -355,7 +381,9 @@ pub(crate) async fn list_git_roots(
         git_remote_params(&request)?,
         Vec<String>
     );
-    git_ui_core::list_git_roots_core(&state.workspaces, workspace_id, depth).await
+    git_ui_core::list_git_roots_core(&state.workspaces, workspace_id, depth)
+        .await
+        .map_err(CommandError::from)
 }
 
 /// Helper function to get the combined diff for a workspace (used by commit message generation)
@@ -376,7 +404,7 @@ pub(crate) async fn get_git_diffs(
     workspace_id: String,
     state: State<'_, AppState>,
     app: AppHandle,
-) -> Result<Vec<GitFileDiff>, String> {
+) -> CommandResult<Vec<GitFileDiff>> {
     let request = git_rpc::WorkspaceIdRequest {
         workspace_id: workspace_id.clone(),
     };
@@ -387,7 +415,9 @@ pub(crate) async fn get_git_diffs(
         git_remote_params(&request)?,
         Vec<GitFileDiff>
     );
-    git_ui_core::get_git_diffs_core(&state.workspaces, &state.app_settings, workspace_id).await
+    git_ui_core::get_git_diffs_core(&state.workspaces, &state.app_settings, workspace_id)
+        .await
+        .map_err(CommandError::from)
 }
 
 #[tauri::command]
@@ -396,7 +426,7 @@ pub(crate) async fn get_git_log(
     limit: Option<usize>,
     state: State<'_, AppState>,
     app: AppHandle,
-) -> Result<GitLogResponse, String> {
+) -> CommandResult<GitLogResponse> {
     let request = git_rpc::GetGitLogRequest {
         workspace_id: workspace_id.clone(),
         limit: optional_usize_to_u32(limit),
@@ -408,7 +438,9 @@ pub(crate) async fn get_git_log(
         git_remote_params(&request)?,
         GitLogResponse
     );
-    git_ui_core::get_git_log_core(&state.workspaces, workspace_id, limit).await
+    git_ui_core::get_git_log_core(&state.workspaces, workspace_id, limit)
+        .await
+        .map_err(CommandError::from)
 }
 
 #[tauri::command]
@@ -417,7 +449,7 @@ pub(crate) async fn get_git_commit_diff(
     sha: String,
     state: State<'_, AppState>,
     app: AppHandle,
-) -> Result<Vec<GitCommitDiff>, String> {
+) -> CommandResult<Vec<GitCommitDiff>> {
     let request = git_rpc::WorkspaceShaRequest {
         workspace_id: workspace_id.clone(),
         sha: sha.clone(),
@@ -431,6 +463,7 @@ pub(crate) async fn get_git_commit_diff(
     );
     git_ui_core::get_git_commit_diff_core(&state.workspaces, &state.app_settings, workspace_id, sha)
         .await
+        .map_err(CommandError::from)
 }
 
 #[tauri::command]
@@ -438,7 +471,7 @@ pub(crate) async fn get_git_remote(
     workspace_id: String,
     state: State<'_, AppState>,
     app: AppHandle,
-) -> Result<Option<String>, String> {
+) -> CommandResult<Option<String>> {
     let request = git_rpc::WorkspaceIdRequest {
         workspace_id: workspace_id.clone(),
     };
@@ -449,7 +482,9 @@ pub(crate) async fn get_git_remote(
         git_remote_params(&request)?,
         Option<String>
     );
-    git_ui_core::get_git_remote_core(&state.workspaces, workspace_id).await
+    git_ui_core::get_git_remote_core(&state.workspaces, workspace_id)
+        .await
+        .map_err(CommandError::from)
 }
 
 #[tauri::command]
@@ -457,7 +492,7 @@ pub(crate) async fn get_github_issues(
     workspace_id: String,
     state: State<'_, AppState>,
     app: AppHandle,
-) -> Result<GitHubIssuesResponse, String> {
+) -> CommandResult<GitHubIssuesResponse> {
     let request = git_rpc::WorkspaceIdRequest {
         workspace_id: workspace_id.clone(),
     };
@@ -468,7 +503,9 @@ pub(crate) async fn get_github_issues(
         git_remote_params(&request)?,
         GitHubIssuesResponse
     );
-    git_ui_core::get_github_issues_core(&state.workspaces, workspace_id).await
+    git_ui_core::get_github_issues_core(&state.workspaces, workspace_id)
+        .await
+        .map_err(CommandError::from)
 }
 
 #[tauri::command]
@@ -476,7 +513,7 @@ pub(crate) async fn get_github_pull_requests(
     workspace_id: String,
     state: State<'_, AppState>,
     app: AppHandle,
-) -> Result<GitHubPullRequestsResponse, String> {
+) -> CommandResult<GitHubPullRequestsResponse> {
     let request = git_rpc::WorkspaceIdRequest {
         workspace_id: workspace_id.clone(),
     };
@@ -487,7 +524,9 @@ pub(crate) async fn get_github_pull_requests(
         git_remote_params(&request)?,
         GitHubPullRequestsResponse
     );
-    git_ui_core::get_github_pull_requests_core(&state.workspaces, workspace_id).await
+    git_ui_core::get_github_pull_requests_core(&state.workspaces, workspace_id)
+        .await
+        .map_err(CommandError::from)
 }
 
 #[tauri::command]
@@ -496,7 +535,7 @@ pub(crate) async fn get_github_pull_request_diff(
     pr_number: u64,
     state: State<'_, AppState>,
     app: AppHandle,
-) -> Result<Vec<GitHubPullRequestDiff>, String> {
+) -> CommandResult<Vec<GitHubPullRequestDiff>> {
     let request = git_rpc::GitHubPullRequestRequest {
         workspace_id: workspace_id.clone(),
         pr_number,
@@ -508,7 +547,9 @@ pub(crate) async fn get_github_pull_request_diff(
         git_remote_params(&request)?,
         Vec<GitHubPullRequestDiff>
     );
-    git_ui_core::get_github_pull_request_diff_core(&state.workspaces, workspace_id, pr_number).await
+    git_ui_core::get_github_pull_request_diff_core(&state.workspaces, workspace_id, pr_number)
+        .await
+        .map_err(CommandError::from)
 }
 
 #[tauri::command]
@@ -517,7 +558,7 @@ pub(crate) async fn get_github_pull_request_comments(
     pr_number: u64,
     state: State<'_, AppState>,
     app: AppHandle,
-) -> Result<Vec<GitHubPullRequestComment>, String> {
+) -> CommandResult<Vec<GitHubPullRequestComment>> {
     let request = git_rpc::GitHubPullRequestRequest {
         workspace_id: workspace_id.clone(),
         pr_number,
@@ -531,6 +572,7 @@ pub(crate) async fn get_github_pull_request_comments(
     );
     git_ui_core::get_github_pull_request_comments_core(&state.workspaces, workspace_id, pr_number)
         .await
+        .map_err(CommandError::from)
 }
 
 #[tauri::command]
@@ -539,7 +581,7 @@ pub(crate) async fn checkout_github_pull_request(
     pr_number: u64,
     state: State<'_, AppState>,
     app: AppHandle,
-) -> Result<(), String> {
+) -> CommandResult<()> {
     let request = git_rpc::GitHubPullRequestRequest {
         workspace_id: workspace_id.clone(),
         pr_number,
@@ -550,7 +592,9 @@ pub(crate) async fn checkout_github_pull_request(
         git_rpc::METHOD_CHECKOUT_GITHUB_PULL_REQUEST,
         git_remote_params(&request)?
     );
-    git_ui_core::checkout_github_pull_request_core(&state.workspaces, workspace_id, pr_number).await
+    git_ui_core::checkout_github_pull_request_core(&state.workspaces, workspace_id, pr_number)
+        .await
+        .map_err(CommandError::from)
 }
 
 #[tauri::command]
@@ -558,7 +602,7 @@ pub(crate) async fn list_git_branches(
     workspace_id: String,
     state: State<'_, AppState>,
     app: AppHandle,
-) -> Result<Value, String> {
+) -> CommandResult<Value> {
     let request = git_rpc::WorkspaceIdRequest {
         workspace_id: workspace_id.clone(),
     };
@@ -568,7 +612,9 @@ pub(crate) async fn list_git_branches(
         git_rpc::METHOD_LIST_GIT_BRANCHES,
         git_remote_params(&request)?
     );
-    git_ui_core::list_git_branches_core(&state.workspaces, workspace_id).await
+    git_ui_core::list_git_branches_core(&state.workspaces, workspace_id)
+        .await
+        .map_err(CommandError::from)
 }
 
 #[tauri::command]
@@ -577,7 +623,7 @@ pub(crate) async fn checkout_git_branch(
     name: String,
     state: State<'_, AppState>,
     app: AppHandle,
-) -> Result<(), String> {
+) -> CommandResult<()> {
     let request = git_rpc::WorkspaceNameRequest {
         workspace_id: workspace_id.clone(),
         name: name.clone(),
@@ -588,7 +634,9 @@ pub(crate) async fn checkout_git_branch(
         git_rpc::METHOD_CHECKOUT_GIT_BRANCH,
         git_remote_params(&request)?
     );
-    git_ui_core::checkout_git_branch_core(&state.workspaces, workspace_id, name).await
+    git_ui_core::checkout_git_branch_core(&state.workspaces, workspace_id, name)
+        .await
+        .map_err(CommandError::from)
 }
 
 #[tauri::command]
@@ -597,7 +645,7 @@ pub(crate) async fn create_git_branch(
     name: String,
     state: State<'_, AppState>,
     app: AppHandle,
-) -> Result<(), String> {
+) -> CommandResult<()> {
     let request = git_rpc::WorkspaceNameRequest {
         workspace_id: workspace_id.clone(),
         name: name.clone(),
@@ -608,5 +656,7 @@ pub(crate) async fn create_git_branch(
         git_rpc::METHOD_CREATE_GIT_BRANCH,
         git_remote_params(&request)?
     );
-    git_ui_core::create_git_branch_core(&state.workspaces, workspace_id, name).await
+    git_ui_core::create_git_branch_core(&state.workspaces, workspace_id, name)
+        .await
+        .map_err(CommandError::from)
 }
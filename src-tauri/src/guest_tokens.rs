@@ -0,0 +1,71 @@
+use tauri::{AppHandle, State};
+
+use crate::command_error::{CommandError, CommandResult};
+use crate::remote_backend;
+use crate::shared::{guest_tokens_core, workspace_rpc};
+use crate::state::AppState;
+use crate::types::{GuestToken, IssuedGuestToken};
+
+#[tauri::command]
+pub(crate) async fn create_guest_token(
+    scopes: Vec<String>,
+    ttl_seconds: u64,
+    label: Option<String>,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> CommandResult<IssuedGuestToken> {
+    if remote_backend::is_remote_mode(&*state).await {
+        let request = workspace_rpc::CreateGuestTokenRequest {
+            scopes,
+            ttl_seconds,
+            label,
+        };
+        let response = remote_backend::call_remote(
+            &*state,
+            app,
+            "create_guest_token",
+            workspace_rpc::to_params(&request)?,
+        )
+        .await?;
+        return serde_json::from_value(response).map_err(|err| CommandError::from(err.to_string()));
+    }
+
+    guest_tokens_core::create_guest_token_core(state.data_dir(), scopes, ttl_seconds, label)
+        .map_err(CommandError::from)
+}
+
+#[tauri::command]
+pub(crate) async fn list_guest_tokens(
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> CommandResult<Vec<GuestToken>> {
+    if remote_backend::is_remote_mode(&*state).await {
+        let response =
+            remote_backend::call_remote(&*state, app, "list_guest_tokens", serde_json::json!({}))
+                .await?;
+        return serde_json::from_value(response).map_err(|err| CommandError::from(err.to_string()));
+    }
+
+    guest_tokens_core::list_guest_tokens_core(state.data_dir()).map_err(CommandError::from)
+}
+
+#[tauri::command]
+pub(crate) async fn revoke_guest_token(
+    id: String,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> CommandResult<()> {
+    if remote_backend::is_remote_mode(&*state).await {
+        let request = workspace_rpc::IdRequest { id };
+        remote_backend::call_remote(
+            &*state,
+            app,
+            "revoke_guest_token",
+            workspace_rpc::to_params(&request)?,
+        )
+        .await?;
+        return Ok(());
+    }
+
+    guest_tokens_core::revoke_guest_token_core(state.data_dir(), &id).map_err(CommandError::from)
+}
@@ -0,0 +1,61 @@
+use std::path::PathBuf;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use serde_json::Value;
+use tauri::{AppHandle, Manager, State};
+
+use crate::command_error::{CommandError, CommandResult};
+use crate::shared::hooks_core::{
+    hook_payload, list_hook_runs_core, record_hook_run, run_hook, subscribed_hooks,
+};
+use crate::state::AppState;
+use crate::types::{HookEvent, HookRun, Page, PageResult};
+
+fn now_ms() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_millis() as i64)
+        .unwrap_or(0)
+}
+
+/// Runs every hook subscribed to `event` in the background, each with the
+/// event on its stdin, and logs what each one printed.
+pub(crate) fn run_hooks(app: &AppHandle, event: HookEvent, data: Value) {
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        let state = app.state::<AppState>();
+        let hooks = subscribed_hooks(&state.app_settings.read().await.hooks, event);
+        if hooks.is_empty() {
+            return;
+        }
+        let data_dir = state.data_dir().to_path_buf();
+        let started_at_ms = now_ms();
+        let payload = hook_payload(event, data, started_at_ms);
+        for hook in hooks {
+            let data_dir = data_dir.clone();
+            let payload = payload.clone();
+            tauri::async_runtime::spawn(async move {
+                let run = run_hook(&hook, event, &payload, started_at_ms).await;
+                let recorded =
+                    tokio::task::spawn_blocking(move || record_hook_run(&data_dir, &run)).await;
+                if let Ok(Err(err)) = recorded {
+                    eprintln!("hooks: {err}");
+                }
+            });
+        }
+    });
+}
+
+#[tauri::command]
+pub(crate) async fn list_hook_runs(
+    hook_id: Option<String>,
+    page: Option<Page>,
+    state: State<'_, AppState>,
+) -> CommandResult<PageResult<HookRun>> {
+    let data_dir = state.data_dir().to_path_buf();
+    let page = page.unwrap_or_default();
+    tokio::task::spawn_blocking(move || list_hook_runs_core(&data_dir, hook_id.as_deref(), &page))
+        .await
+        .map_err(|err| err.to_string())?
+        .map_err(CommandError::from)
+}
@@ -0,0 +1,24 @@
+use serde_json::json;
+use tauri::{AppHandle, State};
+
+use crate::command_error::{CommandError, CommandResult};
+use crate::remote_backend;
+use crate::shared::host_capabilities_core;
+use crate::state::AppState;
+use crate::types::HostCapabilities;
+
+/// OS, CPU, memory and runtimes of the machine sessions run on, which is
+/// the daemon's host in remote mode.
+#[tauri::command]
+pub(crate) async fn get_host_capabilities(
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> CommandResult<HostCapabilities> {
+    if remote_backend::is_remote_mode(&*state).await {
+        let response =
+            remote_backend::call_remote(&*state, app, "host_capabilities", json!({})).await?;
+        return serde_json::from_value(response).map_err(|err| CommandError::from(err.to_string()));
+    }
+
+    Ok(host_capabilities_core::host_capabilities_core().await)
+}
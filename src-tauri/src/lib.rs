@@ -7,27 +7,52 @@ use tauri::RunEvent;
 use tauri::WindowEvent;
 
 mod backend;
+#[macro_use]
+mod capabilities;
+mod ci_status;
+mod client_approvals;
 mod codex;
+mod command_error;
 mod daemon_binary;
+mod deep_link;
 mod dictation;
+mod editor;
+mod event_bus;
 mod event_sink;
 mod files;
 mod git;
 mod git_utils;
+mod guest_tokens;
+mod hooks;
+mod host_capabilities;
 mod local_usage;
 #[cfg(desktop)]
 mod menu;
 #[cfg(not(desktop))]
 #[path = "menu_mobile.rs"]
 mod menu;
+#[cfg(test)]
+mod mock_daemon;
 mod notifications;
+mod overview;
+mod platform;
+mod plugins;
 mod prompts;
+mod remote_action_confirmations;
 mod remote_backend;
+mod remote_command;
 mod rules;
+mod sessions;
 mod settings;
 mod shared;
+mod shortcuts;
+#[cfg(desktop)]
+mod shutdown;
+mod startup;
 mod state;
 mod storage;
+mod storage_health;
+mod tags;
 mod tailscale;
 #[cfg(desktop)]
 mod terminal;
@@ -35,39 +60,26 @@ mod terminal;
 #[path = "terminal_mobile.rs"]
 mod terminal;
 mod tray;
+#[cfg(test)]
+mod ts_bindings;
 mod types;
 mod utils;
+mod webhooks;
 mod window;
+mod window_state;
 mod workspaces;
 
 #[cfg(desktop)]
 static EXIT_CLEANUP_IN_PROGRESS: AtomicBool = AtomicBool::new(false);
 
-#[cfg(desktop)]
-fn keep_daemon_running_after_close(app_handle: &tauri::AppHandle) -> bool {
-    let state = app_handle.state::<state::AppState>();
-    tauri::async_runtime::block_on(async {
-        state
-            .app_settings
-            .lock()
-            .await
-            .keep_daemon_running_after_app_close
-    })
-}
-
-#[cfg(desktop)]
-async fn stop_managed_daemons_for_exit(app_handle: tauri::AppHandle) {
-    let state = app_handle.state::<state::AppState>();
-    let _ = tailscale::tailscale_daemon_stop(state).await;
-}
-
 #[tauri::command]
 fn is_mobile_runtime() -> bool {
-    cfg!(any(target_os = "ios", target_os = "android"))
+    platform::is_mobile()
 }
 
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
+    startup::mark_process_start();
     #[cfg(target_os = "linux")]
     {
         // Avoid WebKit compositing issues on NVIDIA Linux setups (GBM buffer errors).
@@ -93,6 +105,14 @@ pub fn run() {
 
     #[cfg(desktop)]
     let builder = tauri::Builder::default()
+        // Registered first so a second launch, such as one opening a
+        // `codexmonitor://` link, hands over to the running app.
+        .plugin(tauri_plugin_single_instance::init(|app, _args, _cwd| {
+            if let Some(window) = app.get_webview_window("main") {
+                let _ = window.show();
+                let _ = window.set_focus();
+            }
+        }))
         .manage(menu::MenuItemRegistry::<tauri::Wry>::default())
         .manage(tray::TrayState::default())
         .on_menu_event(menu::handle_menu_event)
@@ -103,7 +123,11 @@ pub fn run() {
     let builder = tauri::Builder::default();
 
     let builder = builder
+        .manage(shortcuts::ShortcutRegistry::default())
+        .manage(window::ActivityAlertState::default())
         .on_window_event(|window, event| {
+            #[cfg(desktop)]
+            window_state::handle_window_event(window, event);
             if window.label() != "main" {
                 return;
             }
@@ -114,8 +138,14 @@ pub fn run() {
             }
         })
         .setup(|app| {
-            let state = state::AppState::load(&app.handle());
+            let state = startup::measure("state_init", || state::AppState::load(app.handle()));
+            let window_states = window_state::WindowStateStore::load(state.data_dir());
             app.manage(state);
+            app.manage(window_states);
+            #[cfg(desktop)]
+            for window in app.webview_windows().values() {
+                window_state::restore(window);
+            }
             #[cfg(target_os = "macos")]
             {
                 let tray_state = app.state::<tray::TrayState>();
@@ -129,32 +159,87 @@ pub fn run() {
                     let _ = main_window.hide_menu();
                 }
             }
+            sessions::spawn_scheduler(app.handle().clone());
+            storage_health::spawn_storage_monitor(app.handle().clone());
+            settings::spawn_startup_settings_sync(app.handle().clone());
+            client_approvals::spawn_client_approval_watcher(app.handle().clone());
+            remote_action_confirmations::spawn_remote_action_confirmation_watcher(
+                app.handle().clone(),
+            );
+            ci_status::spawn_ci_watcher(app.handle().clone());
+            codex::spawn_codex_auth_watcher(app.handle().clone());
+            deep_link::initialize(app.handle());
+            #[cfg(desktop)]
+            {
+                // Clean up daemons and sessions left behind by a crash.
+                let data_dir = app.state::<state::AppState>().data_dir().to_path_buf();
+                tauri::async_runtime::spawn(startup::measure_async(
+                    "process_reconciliation",
+                    types::StartupPhaseKind::Background,
+                    async move {
+                        let daemon_binary = daemon_binary::resolve_daemon_binary_path().ok();
+                        shared::process_registry_core::reap_orphaned_processes(
+                            &data_dir,
+                            daemon_binary.as_deref(),
+                        )
+                        .await;
+                    },
+                ));
+            }
             #[cfg(desktop)]
             {
+                app.handle().plugin(
+                    tauri_plugin_global_shortcut::Builder::new()
+                        .with_handler(shortcuts::handle_shortcut)
+                        .build(),
+                )?;
                 let app_handle = app.handle().clone();
                 tauri::async_runtime::spawn(async move {
                     let state = app_handle.state::<state::AppState>();
-                    let settings = state.app_settings.lock().await.clone();
-                    if matches!(
-                        settings.remote_backend_provider,
-                        crate::types::RemoteBackendProvider::Tcp
-                    ) {
-                        if matches!(settings.backend_mode, crate::types::BackendMode::Remote) {
-                            // Remote mode: ensure daemon is up and version-current.
-                            let state = app_handle.state::<state::AppState>();
-                            let _ = tailscale::tailscale_daemon_start(state).await;
-                        } else {
-                            // Local mode: only enforce version if daemon is already running.
-                            let state = app_handle.state::<state::AppState>();
-                            if let Ok(status) = tailscale::tailscale_daemon_status(state).await {
-                                if matches!(status.state, crate::types::TcpDaemonState::Running) {
-                                    let state = app_handle.state::<state::AppState>();
-                                    let _ = tailscale::tailscale_daemon_start(state).await;
+                    let settings = state.app_settings.read().await.clone();
+                    shortcuts::apply_global_shortcuts(&app_handle, &settings);
+                });
+            }
+            #[cfg(desktop)]
+            tailscale::spawn_daemon_watchdog(app.handle().clone());
+            #[cfg(desktop)]
+            tray::spawn_tray_summary_refresher(app.handle().clone());
+            #[cfg(desktop)]
+            tailscale::spawn_tailscale_status_refresher(app.handle().clone());
+            #[cfg(desktop)]
+            {
+                let app_handle = app.handle().clone();
+                tauri::async_runtime::spawn(startup::measure_async(
+                    "daemon_autostart",
+                    types::StartupPhaseKind::Background,
+                    async move {
+                        let state = app_handle.state::<state::AppState>();
+                        let settings = state.app_settings.read().await.clone();
+                        if matches!(
+                            settings.remote_backend_provider,
+                            crate::types::RemoteBackendProvider::Tcp
+                        ) {
+                            if matches!(settings.backend_mode, crate::types::BackendMode::Remote) {
+                                // Remote mode: ensure daemon is up and version-current.
+                                let state = app_handle.state::<state::AppState>();
+                                let _ = tailscale::tailscale_daemon_start(None, state).await;
+                            } else {
+                                // Local mode: only enforce version if daemon is already running.
+                                let state = app_handle.state::<state::AppState>();
+                                if let Ok(status) =
+                                    tailscale::tailscale_daemon_status(None, state).await
+                                {
+                                    if matches!(status.state, crate::types::TcpDaemonState::Running)
+                                    {
+                                        let state = app_handle.state::<state::AppState>();
+                                        let _ =
+                                            tailscale::tailscale_daemon_start(None, state).await;
+                                    }
                                 }
                             }
                         }
-                    }
-                });
+                    },
+                ));
             }
             #[cfg(target_os = "ios")]
             {
@@ -167,161 +252,27 @@ pub fn run() {
                 app.handle()
                     .plugin(tauri_plugin_updater::Builder::new().build())?;
             }
+            startup::mark_ready();
             Ok(())
         });
 
-    #[cfg(desktop)]
-    let builder = builder.plugin(tauri_plugin_window_state::Builder::default().build());
-
     let app = builder
+        .plugin(tauri_plugin_deep_link::init())
         .plugin(tauri_plugin_liquid_glass::init())
         .plugin(tauri_plugin_opener::init())
         .plugin(tauri_plugin_dialog::init())
         .plugin(tauri_plugin_process::init())
         .plugin(tauri_plugin_notification::init())
-        .invoke_handler(tauri::generate_handler![
-            settings::get_app_settings,
-            settings::update_app_settings,
-            settings::get_codex_config_path,
-            files::file_read,
-            files::file_write,
-            files::read_image_as_data_url,
-            files::write_text_file,
-            codex::get_config_model,
-            menu::menu_set_accelerators,
-            tray::set_tray_recent_threads,
-            tray::set_tray_session_usage,
-            codex::codex_doctor,
-            codex::codex_update,
-            workspaces::list_workspaces,
-            workspaces::is_workspace_path_dir,
-            workspaces::add_workspace,
-            workspaces::add_workspace_from_git_url,
-            workspaces::add_clone,
-            workspaces::add_worktree,
-            workspaces::worktree_setup_status,
-            workspaces::worktree_setup_mark_ran,
-            workspaces::remove_workspace,
-            workspaces::remove_worktree,
-            workspaces::rename_worktree,
-            workspaces::rename_worktree_upstream,
-            workspaces::apply_worktree_changes,
-            workspaces::update_workspace_settings,
-            workspaces::set_workspace_runtime_codex_args,
-            codex::start_thread,
-            codex::send_user_message,
-            codex::turn_steer,
-            codex::turn_interrupt,
-            codex::start_review,
-            codex::respond_to_server_request,
-            codex::remember_approval_rule,
-            codex::generate_commit_message,
-            codex::generate_run_metadata,
-            codex::generate_agent_description,
-            codex::resume_thread,
-            codex::read_thread,
-            codex::thread_live_subscribe,
-            codex::thread_live_unsubscribe,
-            codex::fork_thread,
-            codex::list_threads,
-            codex::list_mcp_server_status,
-            codex::archive_thread,
-            codex::compact_thread,
-            codex::set_thread_name,
-            codex::collaboration_mode_list,
-            workspaces::connect_workspace,
-            git::get_git_status,
-            git::init_git_repo,
-            git::create_github_repo,
-            git::list_git_roots,
-            git::get_git_diffs,
-            git::get_git_log,
-            git::get_git_commit_diff,
-            git::get_git_remote,
-            git::stage_git_file,
-            git::stage_git_all,
-            git::unstage_git_file,
-            git::revert_git_file,
-            git::revert_git_all,
-            git::commit_git,
-            git::push_git,
-            git::pull_git,
-            git::fetch_git,
-            git::sync_git,
-            git::get_github_issues,
-            git::get_github_pull_requests,
-            git::get_github_pull_request_diff,
-            git::get_github_pull_request_comments,
-            git::checkout_github_pull_request,
-            workspaces::list_workspace_files,
-            workspaces::read_workspace_file,
-            workspaces::open_workspace_in,
-            workspaces::get_open_app_icon,
-            git::list_git_branches,
-            git::checkout_git_branch,
-            git::create_git_branch,
-            codex::model_list,
-            codex::experimental_feature_list,
-            codex::set_codex_feature_flag,
-            codex::get_agents_settings,
-            codex::set_agents_core_settings,
-            codex::create_agent,
-            codex::update_agent,
-            codex::delete_agent,
-            codex::read_agent_config_toml,
-            codex::write_agent_config_toml,
-            codex::account_rate_limits,
-            codex::account_read,
-            codex::codex_login,
-            codex::codex_login_cancel,
-            codex::skills_list,
-            codex::apps_list,
-            prompts::prompts_list,
-            prompts::prompts_create,
-            prompts::prompts_update,
-            prompts::prompts_delete,
-            prompts::prompts_move,
-            prompts::prompts_workspace_dir,
-            prompts::prompts_global_dir,
-            terminal::terminal_open,
-            terminal::terminal_write,
-            terminal::terminal_resize,
-            terminal::terminal_close,
-            dictation::dictation_model_status,
-            dictation::dictation_download_model,
-            dictation::dictation_cancel_download,
-            dictation::dictation_remove_model,
-            dictation::dictation_start,
-            dictation::dictation_request_permission,
-            dictation::dictation_stop,
-            dictation::dictation_cancel,
-            local_usage::local_usage_snapshot,
-            notifications::is_macos_debug_build,
-            notifications::app_build_type,
-            notifications::send_notification_fallback,
-            tailscale::tailscale_status,
-            tailscale::tailscale_daemon_command_preview,
-            tailscale::tailscale_daemon_start,
-            tailscale::tailscale_daemon_stop,
-            tailscale::tailscale_daemon_status,
-            is_mobile_runtime
-        ])
+        .invoke_handler(registered_commands!(command_handler))
         .build(tauri::generate_context!())
         .expect("error while running tauri application");
 
     app.run(|app_handle, event| {
         #[cfg(desktop)]
         if let RunEvent::ExitRequested { api, .. } = event {
-            if !EXIT_CLEANUP_IN_PROGRESS.load(Ordering::SeqCst)
-                && !keep_daemon_running_after_close(app_handle)
-            {
+            if !EXIT_CLEANUP_IN_PROGRESS.swap(true, Ordering::SeqCst) {
                 api.prevent_exit();
-                EXIT_CLEANUP_IN_PROGRESS.store(true, Ordering::SeqCst);
-                let app_handle = app_handle.clone();
-                tauri::async_runtime::spawn(async move {
-                    stop_managed_daemons_for_exit(app_handle.clone()).await;
-                    app_handle.exit(0);
-                });
+                tauri::async_runtime::spawn(shutdown::shutdown_and_exit(app_handle.clone()));
             }
             return;
         }
@@ -1,10 +1,15 @@
 use serde_json::json;
-use tauri::{AppHandle, State};
+use tauri::{AppHandle, Manager, State};
 
+use crate::command_error::{CommandError, CommandResult};
+use crate::notifications;
 use crate::remote_backend;
-use crate::shared::local_usage_core;
+use crate::shared::{activity_report_core, local_usage_core, workspace_rpc};
 use crate::state::AppState;
-use crate::types::LocalUsageSnapshot;
+use crate::types::{
+    ActivityReport, ActivityReportFormat, ActivityReportRange, GeneratedActivityReport,
+    LocalUsageSnapshot,
+};
 
 #[tauri::command]
 pub(crate) async fn local_usage_snapshot(
@@ -12,7 +17,7 @@ pub(crate) async fn local_usage_snapshot(
     workspace_path: Option<String>,
     state: State<'_, AppState>,
     app: AppHandle,
-) -> Result<LocalUsageSnapshot, String> {
+) -> CommandResult<LocalUsageSnapshot> {
     if remote_backend::is_remote_mode(&*state).await {
         let response = remote_backend::call_remote(
             &*state,
@@ -21,8 +26,86 @@ pub(crate) async fn local_usage_snapshot(
             json!({ "days": days, "workspacePath": workspace_path }),
         )
         .await?;
+        return serde_json::from_value(response).map_err(|err| CommandError::from(err.to_string()));
+    }
+
+    local_usage_core::local_usage_snapshot_core(&state.workspaces, days, workspace_path)
+        .await
+        .map_err(CommandError::from)
+}
+
+/// Builds the report where the sessions live: on the daemon in remote mode.
+async fn activity_report(
+    state: &AppState,
+    app: AppHandle,
+    range: ActivityReportRange,
+) -> Result<ActivityReport, String> {
+    if remote_backend::is_remote_mode(state).await {
+        let response = remote_backend::call_remote(
+            state,
+            app,
+            "activity_report",
+            workspace_rpc::to_params(&workspace_rpc::ActivityReportRequest { range })?,
+        )
+        .await?;
         return serde_json::from_value(response).map_err(|err| err.to_string());
     }
 
-    local_usage_core::local_usage_snapshot_core(&state.workspaces, days, workspace_path).await
+    activity_report_core::activity_report_core(&state.workspaces, state.data_dir(), range).await
+}
+
+/// Renders an activity report, saving it under `reports/` in the data
+/// directory when `save` is set.
+#[tauri::command]
+pub(crate) async fn generate_activity_report(
+    range: ActivityReportRange,
+    format: ActivityReportFormat,
+    save: Option<bool>,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> CommandResult<GeneratedActivityReport> {
+    let report = activity_report(&state, app, range).await?;
+    let content = activity_report_core::render_report(&report, format)?;
+    let path = if save.unwrap_or(false) {
+        let path = activity_report_core::report_path(
+            state.data_dir(),
+            &activity_report_core::report_stem(&report),
+            format,
+        );
+        activity_report_core::save_report(&path, &content)?;
+        Some(path.to_string_lossy().to_string())
+    } else {
+        None
+    };
+    Ok(GeneratedActivityReport {
+        format,
+        content,
+        path,
+    })
+}
+
+/// Writes this week's Markdown report once, when the weekly report is
+/// enabled, and notifies that it is ready.
+pub(crate) async fn write_weekly_activity_report(app: &AppHandle) -> Result<(), String> {
+    let state = app.state::<AppState>();
+    if !state.app_settings.read().await.weekly_activity_report {
+        return Ok(());
+    }
+    let stem = activity_report_core::weekly_report_stem(chrono::Local::now().date_naive());
+    let path =
+        activity_report_core::report_path(state.data_dir(), &stem, ActivityReportFormat::Markdown);
+    if path.exists() {
+        return Ok(());
+    }
+    let report = activity_report(&state, app.clone(), ActivityReportRange::Week).await?;
+    activity_report_core::save_report(&path, &activity_report_core::render_markdown(&report))?;
+    let body = format!(
+        "{} sessions across {} projects. Saved to {}.",
+        report.sessions,
+        report.projects.len(),
+        path.display()
+    );
+    notifications::show_system_notification(app, "Weekly activity report is ready", &body, false)
+        .await;
+    Ok(())
 }
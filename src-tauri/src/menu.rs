@@ -2,11 +2,13 @@ use std::collections::HashMap;
 use std::sync::Mutex;
 
 use serde::Deserialize;
-use tauri::menu::{Menu, MenuItemBuilder, PredefinedMenuItem, Submenu};
 use tauri::menu::MenuItem;
+use tauri::menu::{Menu, MenuItemBuilder, PredefinedMenuItem, Submenu};
 use tauri::{Emitter, WebviewUrl, WebviewWindowBuilder};
 use tauri::{Manager, Runtime};
 
+use crate::command_error::CommandResult;
+
 pub struct MenuItemRegistry<R: Runtime> {
     items: Mutex<HashMap<String, MenuItem<R>>>,
 }
@@ -50,7 +52,7 @@ pub struct MenuAcceleratorUpdate {
 pub fn menu_set_accelerators<R: Runtime>(
     app: tauri::AppHandle<R>,
     updates: Vec<MenuAcceleratorUpdate>,
-) -> Result<(), String> {
+) -> CommandResult<()> {
     let registry = app.state::<MenuItemRegistry<R>>();
     for update in updates {
         registry
@@ -335,12 +337,16 @@ pub(crate) fn handle_menu_event<R: tauri::Runtime>(
                 let _ = window.set_focus();
                 return;
             }
-            let _ = WebviewWindowBuilder::new(app, "about", WebviewUrl::App("index.html".into()))
-                .title("About Codex Monitor")
-                .resizable(false)
-                .inner_size(360.0, 240.0)
-                .center()
-                .build();
+            if let Ok(window) =
+                WebviewWindowBuilder::new(app, "about", WebviewUrl::App("index.html".into()))
+                    .title("About Codex Monitor")
+                    .resizable(false)
+                    .inner_size(360.0, 240.0)
+                    .center()
+                    .build()
+            {
+                crate::window_state::restore(&window);
+            }
         }
         "check_for_updates" => {
             let _ = app.emit("updater-check", ());
@@ -2,6 +2,8 @@ use serde::Deserialize;
 use std::marker::PhantomData;
 use tauri::Runtime;
 
+use crate::command_error::CommandResult;
+
 pub struct MenuItemRegistry<R: Runtime> {
     _runtime: PhantomData<R>,
 }
@@ -24,7 +26,7 @@ pub struct MenuAcceleratorUpdate {
 pub fn menu_set_accelerators<R: Runtime>(
     _app: tauri::AppHandle<R>,
     updates: Vec<MenuAcceleratorUpdate>,
-) -> Result<(), String> {
+) -> CommandResult<()> {
     for update in updates {
         let _ = (update.id, update.accelerator);
     }
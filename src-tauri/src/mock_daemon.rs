@@ -0,0 +1,272 @@
+// In-process stand-in for `codex_monitor_daemon` that speaks its JSON-RPC
+// protocol, in lines or negotiated frames, for testing the clients that talk
+// to it.
+
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use serde_json::{json, Value};
+use tokio::io::AsyncWriteExt;
+use tokio::net::tcp::OwnedWriteHalf;
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::{broadcast, Mutex as AsyncMutex};
+use tokio::task::{AbortHandle, JoinHandle};
+
+use crate::shared::daemon_identity_core;
+use crate::shared::monitor_error::MonitorError;
+use crate::shared::rpc_framing_core::{
+    encode_message, framing_result, negotiate_max_frame_bytes, FrameReader, Framing,
+    DEFAULT_MAX_FRAME_BYTES, MIN_MAX_FRAME_BYTES, RPC_FRAMING_METHOD,
+};
+
+#[derive(Debug, Clone, Default)]
+pub(crate) struct MockDaemonConfig {
+    /// Token clients must send with `auth` before any other method; `None`
+    /// lets every client in.
+    pub(crate) token: Option<String>,
+    /// Install secret used to answer `daemon_identify`; `None` leaves the
+    /// method unknown, as an impostor would.
+    pub(crate) identity_secret: Option<String>,
+    /// Sends auth failures without `error.category`, like daemons that
+    /// predate it.
+    pub(crate) legacy_errors: bool,
+    /// Leaves `rpc_framing` unknown, like daemons that predate
+    /// length-prefixed frames. Otherwise the mock agrees to the smallest
+    /// frame limit allowed, so tests can exceed it cheaply.
+    pub(crate) lines_only: bool,
+    /// Delay before every response.
+    pub(crate) latency: Duration,
+    /// Extra delay before responding to particular methods. Requests are
+    /// answered concurrently, so a delayed response can arrive after ones
+    /// for later requests.
+    pub(crate) delays: HashMap<String, Duration>,
+    /// Methods that fail with the given message.
+    pub(crate) failures: HashMap<String, String>,
+    /// Methods that make the daemon drop the connection instead of replying.
+    pub(crate) hang_up_on: Vec<String>,
+    /// Results for methods other than `ping`, `daemon_info` and
+    /// `daemon_shutdown`.
+    pub(crate) results: HashMap<String, Value>,
+}
+
+/// A listener on a free loopback port. It stops accepting connections, and
+/// closes the open ones, after answering `daemon_shutdown` or when dropped.
+pub(crate) struct MockDaemon {
+    pub(crate) addr: String,
+    shared: Arc<Shared>,
+}
+
+struct Shared {
+    config: MockDaemonConfig,
+    methods: Mutex<Vec<String>>,
+    notifications: broadcast::Sender<String>,
+    stopped: AtomicBool,
+    tasks: Mutex<Vec<AbortHandle>>,
+}
+
+impl Shared {
+    fn track(&self, task: &JoinHandle<()>) {
+        self.tasks.lock().expect("tasks").push(task.abort_handle());
+        if self.stopped.load(Ordering::SeqCst) {
+            task.abort();
+        }
+    }
+
+    fn stop(&self) {
+        self.stopped.store(true, Ordering::SeqCst);
+        for task in self.tasks.lock().expect("tasks").drain(..) {
+            task.abort();
+        }
+    }
+}
+
+impl MockDaemon {
+    pub(crate) async fn spawn(config: MockDaemonConfig) -> Self {
+        let listener = TcpListener::bind("127.0.0.1:0").await.expect("bind");
+        let addr = listener.local_addr().expect("local addr").to_string();
+        let shared = Arc::new(Shared {
+            config,
+            methods: Mutex::new(Vec::new()),
+            notifications: broadcast::channel(16).0,
+            stopped: AtomicBool::new(false),
+            tasks: Mutex::new(Vec::new()),
+        });
+
+        let accept_shared = Arc::clone(&shared);
+        let accept = tokio::spawn(async move {
+            while let Ok((socket, _)) = listener.accept().await {
+                let connection = tokio::spawn(serve_connection(socket, Arc::clone(&accept_shared)));
+                accept_shared.track(&connection);
+            }
+        });
+        shared.track(&accept);
+
+        Self { addr, shared }
+    }
+
+    /// Methods received so far on every connection, in arrival order.
+    pub(crate) fn methods(&self) -> Vec<String> {
+        self.shared.methods.lock().expect("methods").clone()
+    }
+
+    /// Pushes a notification to every authenticated connection.
+    pub(crate) fn notify(&self, method: &str, params: Value) {
+        let line = json!({ "method": method, "params": params }).to_string();
+        let _ = self.shared.notifications.send(line);
+    }
+}
+
+impl Drop for MockDaemon {
+    fn drop(&mut self) {
+        self.shared.stop();
+    }
+}
+
+struct Outbound {
+    writer: OwnedWriteHalf,
+    framing: Framing,
+}
+
+async fn send(outbound: &AsyncMutex<Outbound>, message: &str) -> std::io::Result<()> {
+    let mut outbound = outbound.lock().await;
+    let bytes = encode_message(message, outbound.framing);
+    outbound.writer.write_all(&bytes).await
+}
+
+async fn serve_connection(socket: TcpStream, shared: Arc<Shared>) {
+    let config = &shared.config;
+    let (reader, writer) = socket.into_split();
+    let outbound = Arc::new(AsyncMutex::new(Outbound {
+        writer,
+        framing: Framing::Lines,
+    }));
+    let authenticated = Arc::new(AtomicBool::new(config.token.is_none()));
+
+    let mut notifications = shared.notifications.subscribe();
+    let notification_outbound = Arc::clone(&outbound);
+    let notification_authenticated = Arc::clone(&authenticated);
+    let forwarder = tokio::spawn(async move {
+        while let Ok(line) = notifications.recv().await {
+            if notification_authenticated.load(Ordering::SeqCst)
+                && send(&notification_outbound, &line).await.is_err()
+            {
+                break;
+            }
+        }
+    });
+    shared.track(&forwarder);
+
+    let mut frames = FrameReader::new(reader, DEFAULT_MAX_FRAME_BYTES);
+    while let Ok(Some(line)) = frames.next_message().await {
+        let Ok(request) = serde_json::from_str::<Value>(&line) else {
+            continue;
+        };
+        let method = request["method"].as_str().unwrap_or_default().to_string();
+        shared.methods.lock().expect("methods").push(method.clone());
+        if config.hang_up_on.contains(&method) {
+            break;
+        }
+
+        if method == RPC_FRAMING_METHOD && !config.lines_only {
+            tokio::time::sleep(config.latency).await;
+            let Some(max_frame_bytes) =
+                negotiate_max_frame_bytes(&request["params"], MIN_MAX_FRAME_BYTES)
+            else {
+                break;
+            };
+            let response =
+                json!({ "id": request["id"], "result": framing_result(max_frame_bytes) });
+            // Holding the lock keeps notifications from slipping in between
+            // the reply and the switch.
+            let mut outbound = outbound.lock().await;
+            let bytes = encode_message(&response.to_string(), Framing::Lines);
+            if outbound.writer.write_all(&bytes).await.is_err() {
+                break;
+            }
+            outbound.framing = Framing::LengthPrefixed;
+            frames.use_length_prefixed(max_frame_bytes);
+            continue;
+        }
+        let answer = tokio::spawn(answer(
+            Arc::clone(&shared),
+            Arc::clone(&authenticated),
+            Arc::clone(&outbound),
+            request,
+        ));
+        shared.track(&answer);
+    }
+    forwarder.abort();
+}
+
+async fn answer(
+    shared: Arc<Shared>,
+    authenticated: Arc<AtomicBool>,
+    outbound: Arc<AsyncMutex<Outbound>>,
+    request: Value,
+) {
+    let config = &shared.config;
+    let method = request["method"].as_str().unwrap_or_default();
+    let delay = config.delays.get(method).copied().unwrap_or_default();
+    tokio::time::sleep(config.latency + delay).await;
+    let response = match respond(config, &authenticated, method, &request["params"]) {
+        Ok(result) => json!({ "id": request["id"], "result": result }),
+        Err(MonitorError::Auth(message)) if config.legacy_errors => {
+            json!({ "id": request["id"], "error": { "message": message } })
+        }
+        Err(error @ MonitorError::Auth(_)) => json!({ "id": request["id"], "error": error }),
+        Err(error) => {
+            json!({ "id": request["id"], "error": { "message": error.message() } })
+        }
+    };
+    if send(&outbound, &response.to_string()).await.is_ok() && method == "daemon_shutdown" {
+        shared.stop();
+    }
+}
+
+fn respond(
+    config: &MockDaemonConfig,
+    authenticated: &AtomicBool,
+    method: &str,
+    params: &Value,
+) -> Result<Value, MonitorError> {
+    if let Some(message) = config.failures.get(method) {
+        return Err(MonitorError::Process(message.clone()));
+    }
+    if method == daemon_identity_core::DAEMON_IDENTIFY_METHOD {
+        let Some(secret) = config.identity_secret.as_deref() else {
+            return Err(MonitorError::Process(format!("unknown method: {method}")));
+        };
+        let challenge = params["challenge"].as_str().unwrap_or_default();
+        let proof = daemon_identity_core::daemon_identity_proof(secret, challenge)
+            .map_err(MonitorError::Process)?;
+        return Ok(json!({ "proof": proof }));
+    }
+    if method == "auth" {
+        if config.token.is_some() && config.token.as_deref() != params["token"].as_str() {
+            return Err(MonitorError::Auth("invalid token".to_string()));
+        }
+        authenticated.store(true, Ordering::SeqCst);
+        return Ok(json!({ "ok": true }));
+    }
+    if !authenticated.load(Ordering::SeqCst) {
+        return Err(MonitorError::Auth("unauthorized".to_string()));
+    }
+    match method {
+        "ping" | "daemon_shutdown" => Ok(json!({})),
+        "daemon_info" => Ok(config.results.get(method).cloned().unwrap_or_else(|| {
+            json!({
+                "name": "codex-monitor-daemon",
+                "version": env!("CARGO_PKG_VERSION"),
+                "pid": std::process::id(),
+                "mode": "tcp",
+            })
+        })),
+        _ => config
+            .results
+            .get(method)
+            .cloned()
+            .ok_or_else(|| MonitorError::Process(format!("unknown method: {method}"))),
+    }
+}
@@ -1,6 +1,102 @@
 #[cfg(all(target_os = "macos", debug_assertions))]
 use std::process::Command;
 
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use tauri::{AppHandle, Manager, State};
+use tauri_plugin_notification::NotificationExt;
+
+use crate::command_error::CommandResult;
+use crate::shared::do_not_disturb_core;
+use crate::state::AppState;
+
+fn now_ms() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_millis() as i64)
+        .unwrap_or(0)
+}
+
+/// Whether the screen is being shared, judged by the macOS Screen Sharing
+/// and Zoom sharing processes running.
+#[cfg(target_os = "macos")]
+async fn screen_sharing_active() -> bool {
+    for process in ["screensharingd", "CptHost"] {
+        let running = crate::shared::process_core::tokio_command("/usr/bin/pgrep")
+            .arg("-x")
+            .arg(process)
+            .output()
+            .await
+            .map(|output| output.status.success())
+            .unwrap_or(false);
+        if running {
+            return true;
+        }
+    }
+    false
+}
+
+#[cfg(not(target_os = "macos"))]
+async fn screen_sharing_active() -> bool {
+    false
+}
+
+/// The single check every notification goes through: the snooze, quiet
+/// hours and screen sharing from the Do Not Disturb settings.
+pub(crate) async fn notification_allowed(state: &AppState, urgent: bool) -> bool {
+    let settings = state.app_settings.read().await.do_not_disturb.clone();
+    let snoozed_until_ms = *state.notifications_snoozed_until_ms.lock().await;
+    let screen_sharing =
+        settings.enabled && settings.suppress_while_screen_sharing && screen_sharing_active().await;
+    do_not_disturb_core::notification_allowed(
+        &settings,
+        snoozed_until_ms,
+        now_ms(),
+        chrono::Local::now().naive_local(),
+        screen_sharing,
+        urgent,
+    )
+}
+
+/// Shows a system notification from the backend unless Do Not Disturb holds
+/// it back.
+pub(crate) async fn show_system_notification(
+    app: &AppHandle,
+    title: &str,
+    body: &str,
+    urgent: bool,
+) {
+    if !notification_allowed(&app.state::<AppState>(), urgent).await {
+        return;
+    }
+    let _ = app.notification().builder().title(title).body(body).show();
+}
+
+/// Lets the frontend ask before it shows a notification of its own.
+#[tauri::command]
+pub(crate) async fn should_show_notification(
+    urgent: Option<bool>,
+    state: State<'_, AppState>,
+) -> CommandResult<bool> {
+    Ok(notification_allowed(&state, urgent.unwrap_or(false)).await)
+}
+
+/// Holds notifications back for `minutes`, returning when they resume.
+/// Zero minutes ends a snooze.
+#[tauri::command]
+pub(crate) async fn notifications_snooze(
+    minutes: u32,
+    state: State<'_, AppState>,
+) -> CommandResult<Option<i64>> {
+    Ok(snooze_notifications(&state, minutes).await)
+}
+
+pub(crate) async fn snooze_notifications(state: &AppState, minutes: u32) -> Option<i64> {
+    let until = (minutes > 0).then(|| now_ms() + i64::from(minutes) * 60_000);
+    *state.notifications_snoozed_until_ms.lock().await = until;
+    until
+}
+
 #[tauri::command]
 pub(crate) async fn is_macos_debug_build() -> bool {
     cfg!(all(target_os = "macos", debug_assertions))
@@ -25,7 +121,7 @@ pub(crate) async fn app_build_type() -> String {
 /// This fallback uses AppleScript via `osascript` so the developer still gets
 /// a visible notification during local development.
 #[tauri::command]
-pub(crate) async fn send_notification_fallback(title: String, body: String) -> Result<(), String> {
+pub(crate) async fn send_notification_fallback(title: String, body: String) -> CommandResult<()> {
     #[cfg(all(target_os = "macos", debug_assertions))]
     {
         let escape = |value: &str| value.replace('\\', "\\\\").replace('"', "\\\"");
@@ -44,13 +140,13 @@ pub(crate) async fn send_notification_fallback(title: String, body: String) -> R
         if status.success() {
             Ok(())
         } else {
-            Err(format!("osascript exited with status: {status}"))
+            Err(format!("osascript exited with status: {status}").into())
         }
     }
 
     #[cfg(not(all(target_os = "macos", debug_assertions)))]
     {
         let _ = (title, body);
-        Err("Notification fallback is only available on macOS debug builds.".to_string())
+        Err("Notification fallback is only available on macOS debug builds.".into())
     }
 }
@@ -0,0 +1,95 @@
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use futures_util::future::join3;
+use serde::Serialize;
+use tauri::State;
+
+use crate::command_error::{CommandError, CommandResult};
+use crate::shared::settings_core::get_app_settings_core;
+use crate::state::AppState;
+use crate::tailscale;
+use crate::types::{AppSettings, TailscaleStatus, TcpDaemonStatus};
+
+/// One part of the overview. A failing part carries its error and leaves
+/// the others intact.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct OverviewSection<T> {
+    pub(crate) value: Option<T>,
+    pub(crate) error: Option<CommandError>,
+    /// When this part finished loading, in Unix milliseconds.
+    pub(crate) fetched_at_ms: i64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct MonitorOverview {
+    pub(crate) tailscale: OverviewSection<TailscaleStatus>,
+    pub(crate) daemon: OverviewSection<TcpDaemonStatus>,
+    pub(crate) settings: OverviewSection<AppSettings>,
+}
+
+fn now_ms() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_millis() as i64)
+        .unwrap_or(0)
+}
+
+fn section<T>(result: CommandResult<T>) -> OverviewSection<T> {
+    let fetched_at_ms = now_ms();
+    match result {
+        Ok(value) => OverviewSection {
+            value: Some(value),
+            error: None,
+            fetched_at_ms,
+        },
+        Err(error) => OverviewSection {
+            value: None,
+            error: Some(error),
+            fetched_at_ms,
+        },
+    }
+}
+
+/// Everything the dashboard shows, gathered concurrently in one call. The
+/// Tailscale and daemon parts come from the same caches their own commands
+/// use.
+#[tauri::command]
+pub(crate) async fn get_monitor_overview(
+    state: State<'_, AppState>,
+) -> CommandResult<MonitorOverview> {
+    let (tailscale, daemon, settings) = join3(
+        async { section(tailscale::tailscale_status(None, state.clone()).await) },
+        async { section(tailscale::tailscale_daemon_status(None, state.clone()).await) },
+        async { section(Ok(get_app_settings_core(&state.app_settings).await)) },
+    )
+    .await;
+    Ok(MonitorOverview {
+        tailscale,
+        daemon,
+        settings,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::command_error::ErrorCode;
+
+    #[test]
+    fn failed_sections_keep_their_error_and_timestamp() {
+        let failed = section::<()>(Err(CommandError::new(
+            ErrorCode::Unsupported,
+            "Tailscale daemon start is only supported on desktop.",
+        )));
+        let value = serde_json::to_value(&failed).expect("serialize");
+        assert_eq!(value["value"], serde_json::Value::Null);
+        assert_eq!(value["error"]["code"], "Unsupported");
+        assert!(value["fetchedAtMs"].as_i64().expect("timestamp") > 0);
+
+        let loaded = section(Ok(7));
+        assert_eq!(loaded.value, Some(7));
+        assert!(loaded.error.is_none());
+    }
+}
@@ -0,0 +1,139 @@
+//! What the current build can do on its own device. Mobile builds have no
+//! local backend, so they run as pure remote clients: `remote_backend`
+//! treats them as always in remote mode, and the subsystems below that need
+//! the device itself report `NotSupported` instead of ad-hoc errors.
+
+use crate::shared::monitor_error::MonitorError;
+use crate::types::{PlatformCapabilities, PlatformSubsystem, PlatformSubsystemStatus};
+
+const SUBSYSTEMS: &[PlatformSubsystem] = &[
+    PlatformSubsystem::LocalBackend,
+    PlatformSubsystem::Terminal,
+    PlatformSubsystem::Dictation,
+    PlatformSubsystem::Tailscale,
+    PlatformSubsystem::DaemonManagement,
+    PlatformSubsystem::Tray,
+    PlatformSubsystem::Menu,
+    PlatformSubsystem::GlobalShortcuts,
+];
+
+pub(crate) const fn is_mobile() -> bool {
+    cfg!(any(target_os = "ios", target_os = "android"))
+}
+
+fn subsystem_label(subsystem: PlatformSubsystem) -> &'static str {
+    match subsystem {
+        PlatformSubsystem::LocalBackend => "The local backend",
+        PlatformSubsystem::Terminal => "Terminal",
+        PlatformSubsystem::Dictation => "Dictation",
+        PlatformSubsystem::Tailscale => "Tailscale integration",
+        PlatformSubsystem::DaemonManagement => "Daemon management",
+        PlatformSubsystem::Tray => "The tray icon",
+        PlatformSubsystem::Menu => "The app menu",
+        PlatformSubsystem::GlobalShortcuts => "Global shortcuts",
+    }
+}
+
+fn unavailable_reason(
+    platform: &str,
+    mobile: bool,
+    subsystem: PlatformSubsystem,
+) -> Option<String> {
+    let label = subsystem_label(subsystem);
+    if mobile {
+        return Some(format!("{label} is not available on mobile builds."));
+    }
+    match subsystem {
+        PlatformSubsystem::Tray if platform != "macos" => {
+            Some(format!("{label} is only available on macOS."))
+        }
+        _ => None,
+    }
+}
+
+/// The error a command for `subsystem` returns on a build without it.
+pub(crate) fn unsupported(subsystem: PlatformSubsystem) -> MonitorError {
+    MonitorError::NotSupported(
+        unavailable_reason(std::env::consts::OS, is_mobile(), subsystem)
+            .unwrap_or_else(|| format!("{} is not available.", subsystem_label(subsystem))),
+    )
+}
+
+fn platform_capabilities(platform: &str, mobile: bool) -> PlatformCapabilities {
+    let subsystems = SUBSYSTEMS
+        .iter()
+        .map(|&subsystem| {
+            let reason = unavailable_reason(platform, mobile, subsystem);
+            PlatformSubsystemStatus {
+                subsystem,
+                available: reason.is_none(),
+                reason,
+            }
+        })
+        .collect();
+    PlatformCapabilities {
+        platform: platform.to_string(),
+        mobile,
+        remote_only: mobile,
+        subsystems,
+    }
+}
+
+/// Which subsystems this build has, so the frontend can hide what it lacks
+/// rather than surface errors.
+#[tauri::command]
+pub(crate) fn get_platform_capabilities() -> PlatformCapabilities {
+    platform_capabilities(std::env::consts::OS, is_mobile())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn status(
+        capabilities: &PlatformCapabilities,
+        subsystem: PlatformSubsystem,
+    ) -> &PlatformSubsystemStatus {
+        capabilities
+            .subsystems
+            .iter()
+            .find(|status| status.subsystem == subsystem)
+            .expect("subsystem listed")
+    }
+
+    #[test]
+    fn mobile_builds_are_remote_only_without_device_subsystems() {
+        let capabilities = platform_capabilities("ios", true);
+        assert!(capabilities.remote_only);
+        assert_eq!(capabilities.subsystems.len(), SUBSYSTEMS.len());
+        for status in &capabilities.subsystems {
+            assert!(!status.available);
+            assert!(status
+                .reason
+                .as_deref()
+                .is_some_and(|reason| reason.ends_with("not available on mobile builds.")));
+        }
+        assert_eq!(
+            status(&capabilities, PlatformSubsystem::Terminal)
+                .reason
+                .as_deref(),
+            Some("Terminal is not available on mobile builds.")
+        );
+    }
+
+    #[test]
+    fn desktop_builds_have_every_subsystem_but_the_tray_outside_macos() {
+        let macos = platform_capabilities("macos", false);
+        assert!(!macos.remote_only);
+        assert!(macos.subsystems.iter().all(|status| status.available));
+
+        let linux = platform_capabilities("linux", false);
+        let tray = status(&linux, PlatformSubsystem::Tray);
+        assert!(!tray.available);
+        assert_eq!(
+            tray.reason.as_deref(),
+            Some("The tray icon is only available on macOS.")
+        );
+        assert!(status(&linux, PlatformSubsystem::LocalBackend).available);
+    }
+}
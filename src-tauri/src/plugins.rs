@@ -0,0 +1,56 @@
+//! Plugin commands. Plugins extend the daemon, so in remote mode these
+//! manage the daemon's plugins; locally they manage the ones in the app's
+//! data directory, which a daemon started by the app serves.
+
+use serde_json::json;
+use tauri::{AppHandle, State};
+
+use crate::command_error::{CommandError, CommandResult};
+use crate::remote_backend;
+use crate::shared::plugins_core::{list_plugins_core, toggle_plugin};
+use crate::shared::settings_core::update_app_settings_core;
+use crate::shared::workspace_rpc;
+use crate::state::AppState;
+use crate::types::PluginInfo;
+
+#[tauri::command]
+pub(crate) async fn list_plugins(
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> CommandResult<Vec<PluginInfo>> {
+    if remote_backend::is_remote_mode(&*state).await {
+        let response = remote_backend::call_remote(&*state, app, "list_plugins", json!({})).await?;
+        return serde_json::from_value(response).map_err(|err| CommandError::from(err.to_string()));
+    }
+
+    let enabled = state.app_settings.read().await.enabled_plugins.clone();
+    Ok(list_plugins_core(state.data_dir(), &enabled))
+}
+
+/// Turns a plugin's `ext.*` methods on or off, returning every plugin.
+#[tauri::command]
+pub(crate) async fn enable_plugin(
+    id: String,
+    enabled: bool,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> CommandResult<Vec<PluginInfo>> {
+    if remote_backend::is_remote_mode(&*state).await {
+        let request = workspace_rpc::EnablePluginRequest { id, enabled };
+        let response = remote_backend::call_remote(
+            &*state,
+            app,
+            "enable_plugin",
+            workspace_rpc::to_params(&request)?,
+        )
+        .await?;
+        return serde_json::from_value(response).map_err(|err| CommandError::from(err.to_string()));
+    }
+
+    let data_dir = state.data_dir().to_path_buf();
+    let mut settings = state.app_settings.read().await.clone();
+    settings.enabled_plugins = toggle_plugin(&data_dir, settings.enabled_plugins, &id, enabled)?;
+    let updated =
+        update_app_settings_core(settings, &state.app_settings, &state.settings_path).await?;
+    Ok(list_plugins_core(&data_dir, &updated.enabled_plugins))
+}
@@ -1,43 +1,114 @@
-use tauri::State;
+use serde::de::DeserializeOwned;
+use serde_json::{json, Value};
+use tauri::{AppHandle, State};
 
+use crate::command_error::{CommandError, CommandResult};
+use crate::remote_backend;
 use crate::shared::prompts_core::{self, CustomPromptEntry};
 use crate::state::AppState;
 
+async fn call_remote<T: DeserializeOwned>(
+    state: &AppState,
+    app: AppHandle,
+    method: &str,
+    params: Value,
+) -> CommandResult<T> {
+    let response = remote_backend::call_remote(state, app, method, params).await?;
+    serde_json::from_value(response).map_err(|err| CommandError::from(err.to_string()))
+}
+
 #[tauri::command]
 pub(crate) async fn prompts_list(
     state: State<'_, AppState>,
+    app: AppHandle,
     workspace_id: String,
-) -> Result<Vec<CustomPromptEntry>, String> {
-    prompts_core::prompts_list_core(&state.workspaces, &state.settings_path, workspace_id).await
+) -> CommandResult<Vec<CustomPromptEntry>> {
+    if remote_backend::is_remote_mode(&*state).await {
+        return call_remote(
+            &*state,
+            app,
+            "prompts_list",
+            json!({ "workspaceId": workspace_id }),
+        )
+        .await;
+    }
+
+    prompts_core::prompts_list_core(&state.workspaces, &state.settings_path, workspace_id)
+        .await
+        .map_err(CommandError::from)
 }
 
 #[tauri::command]
 pub(crate) async fn prompts_workspace_dir(
     state: State<'_, AppState>,
+    app: AppHandle,
     workspace_id: String,
-) -> Result<String, String> {
+) -> CommandResult<String> {
+    if remote_backend::is_remote_mode(&*state).await {
+        return call_remote(
+            &*state,
+            app,
+            "prompts_workspace_dir",
+            json!({ "workspaceId": workspace_id }),
+        )
+        .await;
+    }
+
     prompts_core::prompts_workspace_dir_core(&state.workspaces, &state.settings_path, workspace_id)
         .await
+        .map_err(CommandError::from)
 }
 
 #[tauri::command]
 pub(crate) async fn prompts_global_dir(
     state: State<'_, AppState>,
+    app: AppHandle,
     workspace_id: String,
-) -> Result<String, String> {
-    prompts_core::prompts_global_dir_core(&state.workspaces, workspace_id).await
+) -> CommandResult<String> {
+    if remote_backend::is_remote_mode(&*state).await {
+        return call_remote(
+            &*state,
+            app,
+            "prompts_global_dir",
+            json!({ "workspaceId": workspace_id }),
+        )
+        .await;
+    }
+
+    prompts_core::prompts_global_dir_core(&state.workspaces, workspace_id)
+        .await
+        .map_err(CommandError::from)
 }
 
 #[tauri::command]
+#[allow(clippy::too_many_arguments)]
 pub(crate) async fn prompts_create(
     state: State<'_, AppState>,
+    app: AppHandle,
     workspace_id: String,
     scope: String,
     name: String,
     description: Option<String>,
     argument_hint: Option<String>,
     content: String,
-) -> Result<CustomPromptEntry, String> {
+) -> CommandResult<CustomPromptEntry> {
+    if remote_backend::is_remote_mode(&*state).await {
+        return call_remote(
+            &*state,
+            app,
+            "prompts_create",
+            json!({
+                "workspaceId": workspace_id,
+                "scope": scope,
+                "name": name,
+                "description": description,
+                "argumentHint": argument_hint,
+                "content": content,
+            }),
+        )
+        .await;
+    }
+
     prompts_core::prompts_create_core(
         &state.workspaces,
         &state.settings_path,
@@ -49,18 +120,38 @@ pub(crate) async fn prompts_create(
         content,
     )
     .await
+    .map_err(CommandError::from)
 }
 
 #[tauri::command]
+#[allow(clippy::too_many_arguments)]
 pub(crate) async fn prompts_update(
     state: State<'_, AppState>,
+    app: AppHandle,
     workspace_id: String,
     path: String,
     name: String,
     description: Option<String>,
     argument_hint: Option<String>,
     content: String,
-) -> Result<CustomPromptEntry, String> {
+) -> CommandResult<CustomPromptEntry> {
+    if remote_backend::is_remote_mode(&*state).await {
+        return call_remote(
+            &*state,
+            app,
+            "prompts_update",
+            json!({
+                "workspaceId": workspace_id,
+                "path": path,
+                "name": name,
+                "description": description,
+                "argumentHint": argument_hint,
+                "content": content,
+            }),
+        )
+        .await;
+    }
+
     prompts_core::prompts_update_core(
         &state.workspaces,
         &state.settings_path,
@@ -72,25 +163,50 @@ pub(crate) async fn prompts_update(
         content,
     )
     .await
+    .map_err(CommandError::from)
 }
 
 #[tauri::command]
 pub(crate) async fn prompts_delete(
     state: State<'_, AppState>,
+    app: AppHandle,
     workspace_id: String,
     path: String,
-) -> Result<(), String> {
+) -> CommandResult<()> {
+    if remote_backend::is_remote_mode(&*state).await {
+        remote_backend::call_remote(
+            &*state,
+            app,
+            "prompts_delete",
+            json!({ "workspaceId": workspace_id, "path": path }),
+        )
+        .await?;
+        return Ok(());
+    }
+
     prompts_core::prompts_delete_core(&state.workspaces, &state.settings_path, workspace_id, path)
         .await
+        .map_err(CommandError::from)
 }
 
 #[tauri::command]
 pub(crate) async fn prompts_move(
     state: State<'_, AppState>,
+    app: AppHandle,
     workspace_id: String,
     path: String,
     scope: String,
-) -> Result<CustomPromptEntry, String> {
+) -> CommandResult<CustomPromptEntry> {
+    if remote_backend::is_remote_mode(&*state).await {
+        return call_remote(
+            &*state,
+            app,
+            "prompts_move",
+            json!({ "workspaceId": workspace_id, "path": path, "scope": scope }),
+        )
+        .await;
+    }
+
     prompts_core::prompts_move_core(
         &state.workspaces,
         &state.settings_path,
@@ -99,4 +215,5 @@ pub(crate) async fn prompts_move(
         scope,
     )
     .await
+    .map_err(CommandError::from)
 }
@@ -0,0 +1,101 @@
+use std::collections::HashSet;
+use std::time::Duration;
+
+use tauri::{AppHandle, Emitter, Manager, State};
+
+use crate::command_error::{CommandError, CommandResult};
+use crate::remote_backend;
+use crate::shared::{remote_action_confirm_core, workspace_rpc};
+use crate::state::AppState;
+use crate::tailscale::daemon_data_dir;
+use crate::types::{RemoteActionConfirmation, RemoteActionConfirmationStatus};
+
+/// Held requests expire within a minute by default, so this polls faster
+/// than the client approval watcher.
+const CONFIRMATION_POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+#[tauri::command]
+pub(crate) async fn list_remote_action_confirmations(
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> CommandResult<Vec<RemoteActionConfirmation>> {
+    if remote_backend::is_remote_mode(&*state).await {
+        let response = remote_backend::call_remote(
+            &*state,
+            app,
+            "list_remote_action_confirmations",
+            serde_json::json!({}),
+        )
+        .await?;
+        return serde_json::from_value(response).map_err(|err| CommandError::from(err.to_string()));
+    }
+
+    remote_action_confirm_core::list_remote_action_confirmations_core(
+        &daemon_data_dir(&state).await,
+    )
+    .map_err(CommandError::from)
+}
+
+#[tauri::command]
+pub(crate) async fn decide_remote_action_confirmation(
+    id: String,
+    approved: bool,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> CommandResult<RemoteActionConfirmation> {
+    if remote_backend::is_remote_mode(&*state).await {
+        let request = workspace_rpc::DecideRemoteActionConfirmationRequest { id, approved };
+        let response = remote_backend::call_remote(
+            &*state,
+            app,
+            remote_action_confirm_core::DECIDE_REMOTE_ACTION_CONFIRMATION_METHOD,
+            workspace_rpc::to_params(&request)?,
+        )
+        .await?;
+        return serde_json::from_value(response).map_err(|err| CommandError::from(err.to_string()));
+    }
+
+    remote_action_confirm_core::decide_remote_action_confirmation_core(
+        &daemon_data_dir(&state).await,
+        &id,
+        approved,
+    )
+    .map_err(CommandError::from)
+}
+
+/// Announces requests held by the daemon this app manages. A remote daemon
+/// sends the same event over the backend connection instead.
+pub(crate) fn spawn_remote_action_confirmation_watcher(app: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        let mut announced: HashSet<String> = HashSet::new();
+        loop {
+            tokio::time::sleep(CONFIRMATION_POLL_INTERVAL).await;
+            let state = app.state::<AppState>();
+            if remote_backend::is_remote_mode(&*state).await {
+                continue;
+            }
+            let Ok(confirmations) =
+                remote_action_confirm_core::list_remote_action_confirmations_core(
+                    &daemon_data_dir(&state).await,
+                )
+            else {
+                continue;
+            };
+            let pending: Vec<RemoteActionConfirmation> = confirmations
+                .into_iter()
+                .filter(|confirmation| {
+                    confirmation.status == RemoteActionConfirmationStatus::Pending
+                })
+                .collect();
+            announced.retain(|id| pending.iter().any(|confirmation| &confirmation.id == id));
+            for confirmation in pending {
+                if announced.insert(confirmation.id.clone()) {
+                    let _ = app.emit(
+                        remote_action_confirm_core::REMOTE_ACTION_CONFIRMATION_REQUESTED_EVENT,
+                        confirmation,
+                    );
+                }
+            }
+        }
+    });
+}
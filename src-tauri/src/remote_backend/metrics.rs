@@ -0,0 +1,190 @@
+//! Per-method counts and latency of remote backend calls, and a log of the
+//! ones slower than `remoteSlowCallMs`, so a sluggish screen can be traced
+//! to the RPCs behind it.
+
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use crate::shared::monitor_error::MonitorError;
+use crate::state::{AppState, RemoteMetrics};
+use crate::types::{RemoteBackendMetrics, RemoteMethodMetrics, RemoteSlowCall};
+
+/// Upper bounds of the latency histogram; calls slower than the last one
+/// land in an extra bucket.
+const LATENCY_BUCKETS_MS: [u64; 9] = [25, 50, 100, 250, 500, 1_000, 2_500, 5_000, 10_000];
+const SLOW_CALL_LOG_LIMIT: usize = 50;
+
+fn now_ms() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_millis() as i64)
+        .unwrap_or(0)
+}
+
+/// A fresh id for one call, sent with its request (and any retry).
+pub(super) fn new_trace_id() -> String {
+    uuid::Uuid::new_v4().simple().to_string()
+}
+
+fn record(
+    metrics: &mut RemoteMetrics,
+    method: &str,
+    trace_id: &str,
+    elapsed: Duration,
+    error: Option<&MonitorError>,
+    slow_call_ms: u64,
+    finished_at_ms: i64,
+) {
+    let duration_ms = elapsed.as_millis() as u64;
+    let entry = metrics
+        .methods
+        .entry(method.to_string())
+        .or_insert_with(|| RemoteMethodMetrics {
+            method: method.to_string(),
+            calls: 0,
+            errors: 0,
+            total_ms: 0,
+            max_ms: 0,
+            histogram: vec![0; LATENCY_BUCKETS_MS.len() + 1],
+        });
+    entry.calls += 1;
+    entry.errors += u64::from(error.is_some());
+    entry.total_ms = entry.total_ms.saturating_add(duration_ms);
+    entry.max_ms = entry.max_ms.max(duration_ms);
+    let bucket = LATENCY_BUCKETS_MS
+        .iter()
+        .position(|bound| duration_ms <= *bound)
+        .unwrap_or(LATENCY_BUCKETS_MS.len());
+    entry.histogram[bucket] += 1;
+
+    if duration_ms > slow_call_ms {
+        if metrics.slow_calls.len() == SLOW_CALL_LOG_LIMIT {
+            metrics.slow_calls.pop_front();
+        }
+        metrics.slow_calls.push_back(RemoteSlowCall {
+            method: method.to_string(),
+            trace_id: trace_id.to_string(),
+            duration_ms,
+            finished_at_ms,
+            error: error.map(ToString::to_string),
+        });
+    }
+}
+
+/// Records one `call_remote`, retries included.
+pub(super) async fn record_call(
+    state: &AppState,
+    method: &str,
+    trace_id: &str,
+    elapsed: Duration,
+    error: Option<&MonitorError>,
+) {
+    let slow_call_ms = state.app_settings.read().await.remote_slow_call_ms;
+    if elapsed.as_millis() as u64 > slow_call_ms {
+        eprintln!(
+            "remote backend: {method} (trace {trace_id}) took {} ms",
+            elapsed.as_millis()
+        );
+    }
+    let mut metrics = state.remote_metrics.lock().await;
+    record(
+        &mut metrics,
+        method,
+        trace_id,
+        elapsed,
+        error,
+        slow_call_ms,
+        now_ms(),
+    );
+}
+
+fn report(metrics: &RemoteMetrics, slow_call_ms: u64) -> RemoteBackendMetrics {
+    let mut methods = metrics.methods.values().cloned().collect::<Vec<_>>();
+    methods.sort_by(|a, b| {
+        b.total_ms
+            .cmp(&a.total_ms)
+            .then_with(|| a.method.cmp(&b.method))
+    });
+    RemoteBackendMetrics {
+        latency_buckets_ms: LATENCY_BUCKETS_MS.to_vec(),
+        slow_call_ms,
+        methods,
+        slow_calls: metrics.slow_calls.iter().rev().cloned().collect(),
+    }
+}
+
+pub(super) async fn remote_backend_metrics(state: &AppState) -> RemoteBackendMetrics {
+    let slow_call_ms = state.app_settings.read().await.remote_slow_call_ms;
+    report(&*state.remote_metrics.lock().await, slow_call_ms)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn calls_fill_the_histogram_and_slow_ones_are_logged() {
+        let mut metrics = RemoteMetrics::default();
+        let timeout = MonitorError::Network("timed out".to_string());
+        record(
+            &mut metrics,
+            "list_threads",
+            "a",
+            Duration::from_millis(20),
+            None,
+            1_000,
+            1,
+        );
+        record(
+            &mut metrics,
+            "list_threads",
+            "b",
+            Duration::from_millis(1_500),
+            None,
+            1_000,
+            2,
+        );
+        record(
+            &mut metrics,
+            "get_git_diffs",
+            "c",
+            Duration::from_millis(60_000),
+            Some(&timeout),
+            1_000,
+            3,
+        );
+
+        let report = report(&metrics, 1_000);
+        assert_eq!(report.methods[0].method, "get_git_diffs");
+        assert_eq!(report.methods[0].errors, 1);
+        assert_eq!(report.methods[0].histogram[LATENCY_BUCKETS_MS.len()], 1);
+        let list_threads = &report.methods[1];
+        assert_eq!(list_threads.calls, 2);
+        assert_eq!(list_threads.total_ms, 1_520);
+        assert_eq!(list_threads.max_ms, 1_500);
+        assert_eq!(list_threads.histogram[0], 1);
+        assert_eq!(list_threads.histogram[6], 1);
+        assert_eq!(
+            report
+                .slow_calls
+                .iter()
+                .map(|call| call.trace_id.as_str())
+                .collect::<Vec<_>>(),
+            ["c", "b"]
+        );
+        assert_eq!(report.slow_calls[0].error.as_deref(), Some("timed out"));
+
+        for index in 0..SLOW_CALL_LOG_LIMIT {
+            record(
+                &mut metrics,
+                "ping",
+                &index.to_string(),
+                Duration::from_millis(2_000),
+                None,
+                1_000,
+                4,
+            );
+        }
+        assert_eq!(metrics.slow_calls.len(), SLOW_CALL_LOG_LIMIT);
+        assert_eq!(metrics.slow_calls[0].trace_id, "0");
+    }
+}
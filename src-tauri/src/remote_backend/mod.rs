@@ -1,25 +1,55 @@
+mod connect_failure;
+mod metrics;
+mod poll_hint;
 mod protocol;
+mod proxy;
 mod tcp_transport;
 mod transport;
+mod ws_transport;
 
 use serde_json::{json, Value};
 use std::sync::atomic::{AtomicU64, Ordering};
-use std::sync::Arc;
+use std::sync::{Arc, OnceLock, RwLock};
 use std::time::Duration;
 
-use tauri::AppHandle;
+use tauri::{AppHandle, Emitter, Manager, State};
 use tokio::sync::Mutex;
 use tokio::time::timeout;
 
+use crate::ci_status::watch_session_branch;
+use crate::command_error::CommandResult;
+use crate::hooks::run_hooks;
+use crate::platform;
+use crate::shared::client_trust_core;
+use crate::shared::daemon_identity_core;
+use crate::shared::hooks_core::remote_hook_event;
+use crate::shared::monitor_error::MonitorError;
+use crate::shared::request_signing_core::{RequestSigner, REQUEST_SIGNING_PARAM};
+use crate::shared::rpc_timeouts_core::{
+    connect_timeout_error, read_timeout_error, RpcTimeoutTable, RPC_TIMEOUTS_METHOD,
+};
+use crate::shared::webhooks_core::remote_webhook_event;
 use crate::state::AppState;
-use crate::types::BackendMode;
+use crate::tray::{note_app_server_message, note_session_exit};
+use crate::types::{
+    BackendMode, ProxySettings, ProxyTestResult, RemoteBackendMetrics, RemoteBackendStatus,
+    RemoteBackendTarget, RpcTimeoutSettings, RpcTimeouts,
+};
+use crate::webhooks::notify_event;
+use crate::window::raise_for_activity;
 
 use self::protocol::{build_request_line, DEFAULT_REMOTE_HOST, DISCONNECTED_MESSAGE};
 use self::tcp_transport::TcpTransport;
-use self::transport::{PendingMap, RemoteTransport, RemoteTransportConfig, RemoteTransportKind};
+use self::transport::{
+    PendingMap, RemoteEventSink, RemoteTransport, RemoteTransportConfig, RemoteTransportKind,
+};
+use self::ws_transport::WebSocketTransport;
+
+pub(crate) use self::connect_failure::explain_connect_failure;
+pub(crate) use self::poll_hint::{record_link_quality, record_live_subscription};
 
-const REMOTE_REQUEST_TIMEOUT: Duration = Duration::from_secs(300);
 const REMOTE_SEND_TIMEOUT: Duration = Duration::from_secs(15);
+const PROXY_TEST_TIMEOUT: Duration = Duration::from_secs(10);
 
 pub(crate) fn normalize_path_for_remote(path: String) -> String {
     let trimmed = path.trim();
@@ -65,50 +95,176 @@ struct RemoteBackendInner {
     pending: Arc<Mutex<PendingMap>>,
     next_id: AtomicU64,
     connected: Arc<std::sync::atomic::AtomicBool>,
+    /// Set when the daemon agreed to request signing during `auth`.
+    signer: OnceLock<RequestSigner>,
+    /// This build's table until the daemon answers `rpc_timeouts`.
+    timeouts: RwLock<RpcTimeoutTable>,
 }
 
 impl RemoteBackend {
-    pub(crate) async fn call(&self, method: &str, params: Value) -> Result<Value, String> {
+    /// Connects with `config`, authenticating when it carries a token, then
+    /// asks the daemon how long each class of method may take. `overrides`
+    /// win over what it advertises. The token is only sent once the daemon
+    /// proves it holds the identity secret shared when it was paired.
+    async fn connect(
+        config: RemoteTransportConfig,
+        overrides: &RpcTimeoutSettings,
+        events: RemoteEventSink,
+    ) -> Result<Self, MonitorError> {
+        let auth_token = config.auth_token().map(|value| value.to_string());
+        let identity = config
+            .identity()
+            .map(str::trim)
+            .filter(|value| !value.is_empty())
+            .map(str::to_string);
+        let transport: Box<dyn RemoteTransport> = match config.kind() {
+            RemoteTransportKind::Tcp => Box::new(TcpTransport),
+            RemoteTransportKind::WebSocket => Box::new(WebSocketTransport),
+        };
+        let timeouts = RpcTimeoutTable::negotiate(None, overrides);
+        let target = format!("remote backend at {}", config.target());
+        let connection = timeout(timeouts.connect(), transport.connect(events, config))
+            .await
+            .map_err(|_| connect_timeout_error(&target, timeouts.connect()))??;
+
+        let client = Self {
+            inner: Arc::new(RemoteBackendInner {
+                out_tx: connection.out_tx,
+                pending: connection.pending,
+                next_id: AtomicU64::new(1),
+                connected: connection.connected,
+                signer: OnceLock::new(),
+                timeouts: RwLock::new(timeouts),
+            }),
+        };
+
+        if let Some(token) = auth_token {
+            let Some(identity) = identity else {
+                return Err(MonitorError::Config(format!(
+                    "No daemon identity is saved for the {target}, so the token was not sent. Copy it from Settings > Server on the daemon's host."
+                )));
+            };
+            client.verify_identity(&identity, &target).await?;
+            let mut params = client_trust_core::client_auth_params(&token);
+            params[REQUEST_SIGNING_PARAM] = Value::Bool(true);
+            let result = client.call("auth", params).await?;
+            // Older daemons ignore the request and never send a nonce.
+            if let Some(nonce) = result
+                .get("signing")
+                .and_then(|signing| signing.get("nonce"))
+                .and_then(Value::as_str)
+            {
+                let _ = client.inner.signer.set(RequestSigner::new(&token, nonce));
+            }
+        }
+        // Daemons that predate timeout classes answer with an error.
+        if let Ok(advertised) = client.call(RPC_TIMEOUTS_METHOD, json!({})).await {
+            if let Ok(advertised) = serde_json::from_value::<RpcTimeouts>(advertised) {
+                *client
+                    .inner
+                    .timeouts
+                    .write()
+                    .unwrap_or_else(|poisoned| poisoned.into_inner()) =
+                    RpcTimeoutTable::negotiate(Some(&advertised), overrides);
+            }
+        }
+        Ok(client)
+    }
+
+    /// Challenges the daemon to prove it holds `secret`.
+    async fn verify_identity(&self, secret: &str, target: &str) -> Result<(), MonitorError> {
+        let challenge = daemon_identity_core::new_identity_challenge();
+        let proof = self
+            .call(
+                daemon_identity_core::DAEMON_IDENTIFY_METHOD,
+                json!({ "challenge": challenge }),
+            )
+            .await
+            .ok()
+            .and_then(|result| result.get("proof")?.as_str().map(str::to_string));
+        match proof {
+            Some(proof)
+                if daemon_identity_core::verify_daemon_identity_proof(
+                    secret, &challenge, &proof,
+                ) =>
+            {
+                Ok(())
+            }
+            _ => Err(MonitorError::Auth(format!(
+                "The {target} could not prove it is the paired daemon, so the token was not sent."
+            ))),
+        }
+    }
+
+    pub(crate) async fn call(&self, method: &str, params: Value) -> Result<Value, MonitorError> {
+        self.call_traced(method, params, None).await
+    }
+
+    /// Like `call`, sending `trace_id` with the request so the daemon's side
+    /// of a slow call can be found.
+    async fn call_traced(
+        &self,
+        method: &str,
+        params: Value,
+        trace_id: Option<&str>,
+    ) -> Result<Value, MonitorError> {
         if !self.inner.connected.load(Ordering::SeqCst) {
-            return Err(DISCONNECTED_MESSAGE.to_string());
+            return Err(disconnected());
         }
 
         let id = self.inner.next_id.fetch_add(1, Ordering::SeqCst);
         let (tx, rx) = tokio::sync::oneshot::channel();
         self.inner.pending.lock().await.insert(id, tx);
 
-        let message = build_request_line(id, method, params)?;
+        let signature = self
+            .inner
+            .signer
+            .get()
+            .map(|signer| signer.signature(id, method, &params));
+        let message = build_request_line(id, method, params, signature, trace_id)
+            .map_err(MonitorError::Process)?;
         match timeout(REMOTE_SEND_TIMEOUT, self.inner.out_tx.send(message)).await {
             Ok(Ok(())) => {}
             Ok(Err(_)) => {
                 self.inner.pending.lock().await.remove(&id);
-                return Err(DISCONNECTED_MESSAGE.to_string());
+                return Err(disconnected());
             }
             Err(_) => {
                 self.inner.pending.lock().await.remove(&id);
-                return Err(format!(
+                return Err(MonitorError::Timeout(format!(
                     "remote backend request dispatch timed out after {} seconds",
                     REMOTE_SEND_TIMEOUT.as_secs()
-                ));
+                )));
             }
         }
 
-        match timeout(REMOTE_REQUEST_TIMEOUT, rx).await {
+        let limit = self
+            .inner
+            .timeouts
+            .read()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .for_method(method);
+        match timeout(limit, rx).await {
             Ok(Ok(result)) => result,
-            Ok(Err(_)) => Err(DISCONNECTED_MESSAGE.to_string()),
+            Ok(Err(_)) => Err(disconnected()),
             Err(_) => {
                 self.inner.pending.lock().await.remove(&id);
-                Err(format!(
-                    "remote backend request timed out after {} seconds",
-                    REMOTE_REQUEST_TIMEOUT.as_secs()
-                ))
+                Err(read_timeout_error("remote backend", method, limit))
             }
         }
     }
 }
 
+fn disconnected() -> MonitorError {
+    MonitorError::Disconnected(DISCONNECTED_MESSAGE.to_string())
+}
+
+/// Mobile builds have no local backend, so they are always remote clients.
 pub(crate) async fn is_remote_mode(state: &AppState) -> bool {
-    let settings = state.app_settings.lock().await;
+    if platform::is_mobile() {
+        return true;
+    }
+    let settings = state.app_settings.read().await;
     matches!(settings.backend_mode, BackendMode::Remote)
 }
 
@@ -117,36 +273,70 @@ pub(crate) async fn call_remote(
     app: AppHandle,
     method: &str,
     params: Value,
-) -> Result<Value, String> {
+) -> Result<Value, MonitorError> {
+    let trace_id = metrics::new_trace_id();
+    let started = std::time::Instant::now();
+    let result = call_remote_traced(state, app, method, params, &trace_id).await;
+    metrics::record_call(
+        state,
+        method,
+        &trace_id,
+        started.elapsed(),
+        result.as_ref().err(),
+    )
+    .await;
+    result
+}
+
+async fn call_remote_traced(
+    state: &AppState,
+    app: AppHandle,
+    method: &str,
+    params: Value,
+    trace_id: &str,
+) -> Result<Value, MonitorError> {
     let client = ensure_remote_backend(state, app.clone()).await?;
-    match client.call(method, params.clone()).await {
+    match client
+        .call_traced(method, params.clone(), Some(trace_id))
+        .await
+    {
         Ok(value) => Ok(value),
-        Err(err) if err == DISCONNECTED_MESSAGE => {
-            *state.remote_backend.lock().await = None;
+        Err(err) if err == disconnected() => {
+            reset_remote_backend(state).await;
             if !can_retry_after_disconnect(method) {
                 return Err(err);
             }
             let retry_client = ensure_remote_backend(state, app).await?;
-            match retry_client.call(method, params).await {
+            match retry_client
+                .call_traced(method, params, Some(trace_id))
+                .await
+            {
                 Ok(value) => Ok(value),
                 Err(retry_err) => {
-                    *state.remote_backend.lock().await = None;
+                    reset_remote_backend(state).await;
                     Err(retry_err)
                 }
             }
         }
         Err(err) => {
-            *state.remote_backend.lock().await = None;
+            reset_remote_backend(state).await;
             Err(err)
         }
     }
 }
 
+/// Drops the shared client; the next call reconnects.
+pub(crate) async fn reset_remote_backend(state: &AppState) {
+    *state.remote_backend.lock().await = None;
+    poll_hint::mark_change(&mut *state.remote_activity.lock().await, true);
+}
+
 fn can_retry_after_disconnect(method: &str) -> bool {
     matches!(
         method,
         "account_rate_limits"
             | "account_read"
+            | "activity_report"
             | "apps_list"
             | "collaboration_mode_list"
             | "connect_workspace"
@@ -154,6 +344,12 @@ fn can_retry_after_disconnect(method: &str) -> bool {
             | "set_workspace_runtime_codex_args"
             | "file_read"
             | "get_agents_settings"
+            | "get_codex_config_path"
+            | "codex_auth_status"
+            | "codex_login_flow_status"
+            | "get_codex_home"
+            | "list_codex_home_snapshots"
+            | "list_config_changes"
             | "get_config_model"
             | "get_git_commit_diff"
             | "get_git_diffs"
@@ -164,19 +360,41 @@ fn can_retry_after_disconnect(method: &str) -> bool {
             | "get_github_pull_request_comments"
             | "get_github_pull_request_diff"
             | "get_github_pull_requests"
+            | "get_open_app_icon"
+            | "get_project_ci_status"
             | "is_workspace_path_dir"
             | "list_git_branches"
+            | "list_archives"
+            | "list_client_approvals"
+            | "list_editors"
+            | "list_plugins"
             | "list_git_roots"
+            | "list_session_templates"
+            | "list_tags"
+            | "get_tag_assignments"
+            | "list_codex_sessions"
+            | "list_remote_command_audit"
+            | "list_schedules"
+            | "get_codex_session"
+            | "get_process_stats"
             | "list_mcp_server_status"
             | "list_threads"
             | "local_usage_snapshot"
             | "list_workspace_files"
             | "list_workspaces"
             | "model_list"
+            | "prompts_global_dir"
+            | "prompts_list"
+            | "prompts_workspace_dir"
             | "read_thread"
             | "read_agent_config_toml"
             | "read_workspace_file"
+            | "remote_list_dir"
+            | "remote_read_file"
+            | "read_session_export"
+            | "test_redaction_rules"
             | "resume_thread"
+            | "search_sessions"
             | "thread_live_subscribe"
             | "thread_live_unsubscribe"
             | "skills_list"
@@ -184,7 +402,10 @@ fn can_retry_after_disconnect(method: &str) -> bool {
     )
 }
 
-async fn ensure_remote_backend(state: &AppState, app: AppHandle) -> Result<RemoteBackend, String> {
+async fn ensure_remote_backend(
+    state: &AppState,
+    app: AppHandle,
+) -> Result<RemoteBackend, MonitorError> {
     {
         let guard = state.remote_backend.lock().await;
         if let Some(client) = guard.as_ref() {
@@ -192,63 +413,254 @@ async fn ensure_remote_backend(state: &AppState, app: AppHandle) -> Result<Remot
         }
     }
 
-    let transport_config = {
-        let settings = state.app_settings.lock().await;
-        resolve_transport_config(&settings)?
-    };
-    let transport_kind = transport_config.kind();
-    let auth_token = transport_config.auth_token().map(|value| value.to_string());
-
-    let transport: Box<dyn RemoteTransport> = match transport_config.kind() {
-        RemoteTransportKind::Tcp => Box::new(TcpTransport),
-    };
-    let connection = transport.connect(app, transport_config).await?;
-
-    let client = RemoteBackend {
-        inner: Arc::new(RemoteBackendInner {
-            out_tx: connection.out_tx,
-            pending: connection.pending,
-            next_id: AtomicU64::new(1),
-            connected: connection.connected,
-        }),
+    let (transport_config, overrides) = {
+        let settings = state.app_settings.read().await;
+        (
+            resolve_transport_config(&settings).map_err(MonitorError::Config)?,
+            settings.rpc_timeouts.clone(),
+        )
     };
-
-    if matches!(transport_kind, RemoteTransportKind::Tcp) {
-        if let Some(token) = auth_token {
-            client
-                .call("auth", json!({ "token": token }))
-                .await
-                .map(|_| ())?;
+    let events: RemoteEventSink = Arc::new(move |event: &str, params: Value| {
+        // Skipping a mark under contention only delays the next hint.
+        if let Ok(mut activity) = app.state::<AppState>().remote_activity.try_lock() {
+            poll_hint::mark_change(&mut activity, false);
         }
-    }
+        if let Some((hook, data)) = remote_hook_event(event, &params) {
+            run_hooks(&app, hook, data);
+        }
+        if let Some((webhook, data)) = remote_webhook_event(event, &params) {
+            raise_for_activity(&app, webhook, &data);
+            notify_event(&app, webhook, data);
+        }
+        if event == "app-server-event" {
+            if let (Some(workspace_id), Some(message)) = (
+                params.get("workspace_id").and_then(Value::as_str),
+                params.get("message"),
+            ) {
+                note_app_server_message(&app, workspace_id, message);
+            }
+        }
+        if event == "codex-session-exit" {
+            if let Some(session_id) = params.get("sessionId").and_then(Value::as_str) {
+                let exit_code = params
+                    .get("exitCode")
+                    .and_then(Value::as_i64)
+                    .and_then(|code| i32::try_from(code).ok());
+                note_session_exit(&app, session_id, exit_code);
+                watch_session_branch(&app, session_id.to_string());
+            }
+        }
+        let _ = app.emit(event, params);
+    });
+    let client = match RemoteBackend::connect(transport_config, &overrides, events).await {
+        Ok(client) => client,
+        Err(err) => return Err(explain_connect_failure(state, err).await),
+    };
 
     {
         let mut guard = state.remote_backend.lock().await;
         *guard = Some(client.clone());
     }
+    poll_hint::mark_change(&mut *state.remote_activity.lock().await, false);
 
     Ok(client)
 }
 
+/// The shared remote backend connection and how often to poll it.
+#[tauri::command]
+pub(crate) async fn remote_backend_status(
+    state: State<'_, AppState>,
+) -> CommandResult<RemoteBackendStatus> {
+    Ok(poll_hint::remote_backend_status(&state).await)
+}
+
+/// Per-method counts and latency of remote backend calls since launch, and
+/// the recent slow ones.
+#[tauri::command]
+pub(crate) async fn get_remote_backend_metrics(
+    state: State<'_, AppState>,
+) -> CommandResult<RemoteBackendMetrics> {
+    Ok(metrics::remote_backend_metrics(&state).await)
+}
+
+/// Opens (and drops) a connection to `target`, a remote backend host or
+/// `ws(s)://` URL, the way the remote transports would.
+pub(crate) async fn test_proxy_connection(
+    proxy: &ProxySettings,
+    target: &str,
+) -> Result<ProxyTestResult, MonitorError> {
+    let target = target.trim();
+    let (host, port) = if is_websocket_url(target) {
+        proxy::websocket_host_port(target).map_err(MonitorError::Config)?
+    } else {
+        proxy::split_host_port(target).ok_or_else(|| {
+            MonitorError::Config(format!("Expected host:port or a ws:// URL, got `{target}`"))
+        })?
+    };
+    let started = std::time::Instant::now();
+    let (_, proxied) = timeout(PROXY_TEST_TIMEOUT, proxy::connect_tcp(proxy, &host, port))
+        .await
+        .map_err(|_| {
+            MonitorError::ConnectTimeout(format!(
+                "Connecting to {target} timed out after {} seconds",
+                PROXY_TEST_TIMEOUT.as_secs()
+            ))
+        })?
+        .map_err(MonitorError::Network)?;
+    Ok(ProxyTestResult {
+        target: target.to_string(),
+        proxied,
+        latency_ms: started.elapsed().as_millis() as u64,
+    })
+}
+
+fn is_websocket_url(host: &str) -> bool {
+    let lower = host.trim().to_ascii_lowercase();
+    lower.starts_with("ws://") || lower.starts_with("wss://")
+}
+
+/// Opens a separate, authenticated connection to one of the saved remote
+/// backends, for calls that are not meant for the active backend. Its
+/// notifications are dropped.
+pub(crate) async fn connect_to_target(
+    target: &RemoteBackendTarget,
+    settings: &crate::types::AppSettings,
+) -> Result<RemoteBackend, MonitorError> {
+    let config = transport_config(
+        &target.host,
+        target.token.clone(),
+        target.identity.clone(),
+        &settings.proxy,
+    );
+    RemoteBackend::connect(
+        config,
+        &settings.rpc_timeouts,
+        Arc::new(|_: &str, _: Value| {}),
+    )
+    .await
+}
+
+/// Opens a separate connection to the configured remote backend, so probes
+/// do not queue behind the shared client. Its notifications are dropped.
+pub(crate) async fn connect_configured(
+    settings: &crate::types::AppSettings,
+) -> Result<RemoteBackend, MonitorError> {
+    let config = resolve_transport_config(settings).map_err(MonitorError::Config)?;
+    RemoteBackend::connect(
+        config,
+        &settings.rpc_timeouts,
+        Arc::new(|_: &str, _: Value| {}),
+    )
+    .await
+}
+
+/// The configured remote backend's host, without scheme or port.
+pub(crate) fn configured_host(settings: &crate::types::AppSettings) -> Option<String> {
+    let target = settings.remote_backend_host.trim();
+    let target = if target.is_empty() {
+        DEFAULT_REMOTE_HOST
+    } else {
+        target
+    };
+    if is_websocket_url(target) {
+        proxy::websocket_host_port(target)
+            .ok()
+            .map(|(host, _)| host)
+    } else {
+        proxy::split_host_port(target).map(|(host, _)| host)
+    }
+}
+
 fn resolve_transport_config(
     settings: &crate::types::AppSettings,
 ) -> Result<RemoteTransportConfig, String> {
-    let host = if settings.remote_backend_host.trim().is_empty() {
+    Ok(transport_config(
+        &settings.remote_backend_host,
+        settings.remote_backend_token.clone(),
+        settings.remote_backend_identity.clone(),
+        &settings.proxy,
+    ))
+}
+
+fn transport_config(
+    host: &str,
+    auth_token: Option<String>,
+    identity: Option<String>,
+    proxy: &ProxySettings,
+) -> RemoteTransportConfig {
+    let host = if host.trim().is_empty() {
         DEFAULT_REMOTE_HOST.to_string()
     } else {
-        settings.remote_backend_host.clone()
+        host.to_string()
     };
-    Ok(RemoteTransportConfig::Tcp {
+    if is_websocket_url(&host) {
+        return RemoteTransportConfig::WebSocket {
+            url: host.trim().to_string(),
+            auth_token,
+            identity,
+            proxy: proxy.clone(),
+        };
+    }
+    RemoteTransportConfig::Tcp {
         host,
-        auth_token: settings.remote_backend_token.clone(),
-    })
+        auth_token,
+        identity,
+        proxy: proxy.clone(),
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use super::{can_retry_after_disconnect, resolve_transport_config};
+    use std::collections::HashMap;
+    use std::sync::{Arc, Mutex};
+
+    use serde_json::{json, Value};
+
+    use std::time::Duration;
+
+    use super::{
+        can_retry_after_disconnect, disconnected, resolve_transport_config, RemoteBackend,
+        RemoteEventSink,
+    };
+    use crate::command_error::{CommandError, ErrorCode};
+    use crate::mock_daemon::{MockDaemon, MockDaemonConfig};
     use crate::remote_backend::transport::RemoteTransportConfig;
-    use crate::types::AppSettings;
+    use crate::shared::monitor_error::MonitorError;
+    use crate::shared::rpc_framing_core::MIN_MAX_FRAME_BYTES;
+    use crate::types::{AppSettings, ProxySettings, RpcTimeoutSettings};
+
+    fn run<F: std::future::Future>(future: F) -> F::Output {
+        tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .expect("runtime")
+            .block_on(future)
+    }
+
+    const IDENTITY: &str = "install-secret";
+
+    fn tcp_config(daemon: &MockDaemon, token: Option<&str>) -> RemoteTransportConfig {
+        RemoteTransportConfig::Tcp {
+            host: daemon.addr.clone(),
+            auth_token: token.map(str::to_string),
+            identity: Some(IDENTITY.to_string()),
+            proxy: ProxySettings::default(),
+        }
+    }
+
+    type ReceivedEvents = Arc<Mutex<Vec<(String, Value)>>>;
+
+    fn recording_sink() -> (RemoteEventSink, ReceivedEvents) {
+        let received = Arc::new(Mutex::new(Vec::new()));
+        let sink_received = Arc::clone(&received);
+        let sink: RemoteEventSink = Arc::new(move |event: &str, params: Value| {
+            sink_received
+                .lock()
+                .expect("events")
+                .push((event.to_string(), params));
+        });
+        (sink, received)
+    }
 
     #[test]
     fn resolve_tcp_transport_uses_remote_host() {
@@ -262,6 +674,20 @@ mod tests {
         assert_eq!(host, "tcp.example:4732");
     }
 
+    #[test]
+    fn resolve_websocket_transport_from_host_scheme() {
+        let settings = AppSettings {
+            remote_backend_host: " WSS://mac.example.ts.net/rpc ".to_string(),
+            ..AppSettings::default()
+        };
+
+        let config = resolve_transport_config(&settings).expect("transport config");
+        let RemoteTransportConfig::WebSocket { url, .. } = config else {
+            panic!("expected websocket transport config");
+        };
+        assert_eq!(url, "WSS://mac.example.ts.net/rpc");
+    }
+
     #[test]
     fn retries_only_retry_safe_methods_after_disconnect() {
         assert!(can_retry_after_disconnect("resume_thread"));
@@ -271,4 +697,234 @@ mod tests {
         assert!(!can_retry_after_disconnect("start_thread"));
         assert!(!can_retry_after_disconnect("remove_workspace"));
     }
+
+    #[test]
+    fn calls_mock_daemon_after_authenticating() {
+        run(async {
+            let daemon = MockDaemon::spawn(MockDaemonConfig {
+                token: Some("secret-token".to_string()),
+                identity_secret: Some(IDENTITY.to_string()),
+                results: HashMap::from([("list_workspaces".to_string(), json!([]))]),
+                failures: HashMap::from([(
+                    "start_thread".to_string(),
+                    "workspace not connected".to_string(),
+                )]),
+                ..MockDaemonConfig::default()
+            })
+            .await;
+            let (events, _) = recording_sink();
+
+            let client = RemoteBackend::connect(
+                tcp_config(&daemon, Some("secret-token")),
+                &RpcTimeoutSettings::default(),
+                events,
+            )
+            .await
+            .expect("connect");
+            assert_eq!(
+                client.call("list_workspaces", json!({})).await,
+                Ok(json!([]))
+            );
+            assert_eq!(
+                client.call("start_thread", json!({})).await,
+                Err(MonitorError::Process("workspace not connected".to_string()))
+            );
+            assert_eq!(
+                daemon.methods()[..4],
+                ["rpc_framing", "daemon_identify", "auth", "rpc_timeouts"]
+            );
+        });
+    }
+
+    #[test]
+    fn keeps_the_token_from_daemons_that_cannot_prove_their_identity() {
+        run(async {
+            let daemon = MockDaemon::spawn(MockDaemonConfig {
+                token: Some("secret-token".to_string()),
+                identity_secret: Some("another-install".to_string()),
+                ..MockDaemonConfig::default()
+            })
+            .await;
+            let (events, _) = recording_sink();
+            assert!(matches!(
+                RemoteBackend::connect(
+                    tcp_config(&daemon, Some("secret-token")),
+                    &RpcTimeoutSettings::default(),
+                    events.clone()
+                )
+                .await,
+                Err(MonitorError::Auth(_))
+            ));
+
+            let unpaired = RemoteTransportConfig::Tcp {
+                host: daemon.addr.clone(),
+                auth_token: Some("secret-token".to_string()),
+                identity: None,
+                proxy: ProxySettings::default(),
+            };
+            assert!(matches!(
+                RemoteBackend::connect(unpaired, &RpcTimeoutSettings::default(), events).await,
+                Err(MonitorError::Config(_))
+            ));
+            assert!(!daemon.methods().iter().any(|method| method == "auth"));
+        });
+    }
+
+    #[test]
+    fn negotiated_timeout_classes_bound_each_call() {
+        run(async {
+            let daemon = MockDaemon::spawn(MockDaemonConfig {
+                delays: HashMap::from([
+                    ("get_git_diffs".to_string(), Duration::from_millis(300)),
+                    ("start_thread".to_string(), Duration::from_millis(300)),
+                ]),
+                results: HashMap::from([
+                    (
+                        "rpc_timeouts".to_string(),
+                        json!({
+                            "fastMs": 1_000,
+                            "normalMs": 100,
+                            "longMs": 5_000,
+                            "fastMethods": ["ping"],
+                            "longMethods": ["get_git_diffs"],
+                        }),
+                    ),
+                    ("get_git_diffs".to_string(), json!([])),
+                    ("start_thread".to_string(), json!({})),
+                ]),
+                ..MockDaemonConfig::default()
+            })
+            .await;
+            let (events, _) = recording_sink();
+            let client = RemoteBackend::connect(
+                tcp_config(&daemon, None),
+                &RpcTimeoutSettings::default(),
+                events,
+            )
+            .await
+            .expect("connect");
+
+            assert_eq!(client.call("get_git_diffs", json!({})).await, Ok(json!([])));
+            let Err(error @ MonitorError::Timeout(_)) =
+                client.call("start_thread", json!({})).await
+            else {
+                panic!("expected a read timeout");
+            };
+            assert_eq!(CommandError::from(error).code, ErrorCode::Timeout);
+
+            let (events, _) = recording_sink();
+            let overrides = RpcTimeoutSettings {
+                normal_ms: Some(5_000),
+                ..RpcTimeoutSettings::default()
+            };
+            let client = RemoteBackend::connect(tcp_config(&daemon, None), &overrides, events)
+                .await
+                .expect("connect");
+            assert_eq!(client.call("start_thread", json!({})).await, Ok(json!({})));
+        });
+    }
+
+    #[test]
+    fn rejected_tokens_and_refused_connections_are_categorized() {
+        run(async {
+            let daemon = MockDaemon::spawn(MockDaemonConfig {
+                token: Some("secret-token".to_string()),
+                identity_secret: Some(IDENTITY.to_string()),
+                ..MockDaemonConfig::default()
+            })
+            .await;
+            let (events, _) = recording_sink();
+            assert!(matches!(
+                RemoteBackend::connect(
+                    tcp_config(&daemon, Some("wrong")),
+                    &RpcTimeoutSettings::default(),
+                    events.clone()
+                )
+                .await,
+                Err(MonitorError::Auth(_))
+            ));
+
+            let config = tcp_config(&daemon, None);
+            drop(daemon);
+            tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+            assert!(matches!(
+                RemoteBackend::connect(config, &RpcTimeoutSettings::default(), events).await,
+                Err(MonitorError::Unreachable(_))
+            ));
+        });
+    }
+
+    #[test]
+    fn falls_back_to_lines_and_drops_oversized_frames() {
+        run(async {
+            let daemon = MockDaemon::spawn(MockDaemonConfig {
+                token: Some("secret-token".to_string()),
+                identity_secret: Some(IDENTITY.to_string()),
+                lines_only: true,
+                ..MockDaemonConfig::default()
+            })
+            .await;
+            let (events, _) = recording_sink();
+            let client = RemoteBackend::connect(
+                tcp_config(&daemon, Some("secret-token")),
+                &RpcTimeoutSettings::default(),
+                events,
+            )
+            .await
+            .expect("connect over lines");
+            client.call("ping", json!({})).await.expect("ping");
+
+            let daemon = MockDaemon::spawn(MockDaemonConfig::default()).await;
+            let (events, _) = recording_sink();
+            let client = RemoteBackend::connect(
+                tcp_config(&daemon, None),
+                &RpcTimeoutSettings::default(),
+                events,
+            )
+            .await
+            .expect("connect over frames");
+            let oversized = json!({ "text": "x".repeat(MIN_MAX_FRAME_BYTES) });
+            assert_eq!(
+                client.call("send_user_message", oversized).await,
+                Err(disconnected())
+            );
+        });
+    }
+
+    #[test]
+    fn forwards_notifications_and_reports_disconnects() {
+        run(async {
+            let daemon = MockDaemon::spawn(MockDaemonConfig {
+                hang_up_on: vec!["send_user_message".to_string()],
+                ..MockDaemonConfig::default()
+            })
+            .await;
+            let (events, received) = recording_sink();
+            let client = RemoteBackend::connect(
+                tcp_config(&daemon, None),
+                &RpcTimeoutSettings::default(),
+                events,
+            )
+            .await
+            .expect("connect");
+            client.call("ping", json!({})).await.expect("ping");
+
+            daemon.notify("app-server-event", json!({ "workspace_id": "ws-1" }));
+            daemon.notify("internal-event", json!({}));
+            client.call("ping", json!({})).await.expect("ping");
+            assert_eq!(
+                *received.lock().expect("events"),
+                [(
+                    "app-server-event".to_string(),
+                    json!({ "workspace_id": "ws-1" })
+                )]
+            );
+
+            assert_eq!(
+                client.call("send_user_message", json!({})).await,
+                Err(disconnected())
+            );
+            assert_eq!(client.call("ping", json!({})).await, Err(disconnected()));
+        });
+    }
 }
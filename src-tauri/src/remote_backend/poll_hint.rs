@@ -0,0 +1,116 @@
+//! How often the frontend should poll the remote backend, from the link the
+//! last quality probe saw, how recently anything happened on the connection
+//! and whether live thread subscriptions already push updates.
+
+use std::time::Duration;
+
+use tokio::time::Instant;
+
+use crate::state::{AppState, RemoteActivity};
+use crate::types::{RemoteBackendStatus, RemoteLinkPath};
+
+/// Nothing else known; the frontend's former fixed thread poll.
+const DEFAULT_POLL_MS: u64 = 12_000;
+/// Something changed within `RECENT_CHANGE`: catch up sooner.
+const ACTIVE_POLL_MS: u64 = 5_000;
+const RECENT_CHANGE: Duration = Duration::from_secs(30);
+/// Quiet for `IDLE_AFTER`: little is likely to change.
+const IDLE_POLL_MS: u64 = 30_000;
+const IDLE_AFTER: Duration = Duration::from_secs(5 * 60);
+/// Live subscriptions push updates; polling is only a safety net.
+const SUBSCRIBED_POLL_MS: u64 = 60_000;
+const MAX_POLL_MS: u64 = 60_000;
+
+fn suggested_poll_ms(
+    idle: Option<Duration>,
+    live_subscriptions: usize,
+    link_poll_ms: Option<u64>,
+    link_path: Option<&RemoteLinkPath>,
+) -> u64 {
+    let mut poll_ms = if live_subscriptions > 0 {
+        SUBSCRIBED_POLL_MS
+    } else {
+        match idle {
+            Some(idle) if idle < RECENT_CHANGE => ACTIVE_POLL_MS,
+            Some(idle) if idle >= IDLE_AFTER => IDLE_POLL_MS,
+            _ => DEFAULT_POLL_MS,
+        }
+    };
+    // Relayed links are usually phones on cellular; never poll them faster
+    // than the default.
+    if link_path == Some(&RemoteLinkPath::Relay) {
+        poll_ms = poll_ms.max(DEFAULT_POLL_MS);
+    }
+    poll_ms.max(link_poll_ms.unwrap_or(0)).min(MAX_POLL_MS)
+}
+
+pub(super) fn mark_change(activity: &mut RemoteActivity, disconnected: bool) {
+    activity.last_change = Some(Instant::now());
+    if disconnected {
+        // Nothing is pushed until the frontend subscribes again.
+        activity.live_subscriptions.clear();
+    }
+}
+
+pub(crate) async fn record_live_subscription(
+    state: &AppState,
+    workspace_id: &str,
+    thread_id: &str,
+    live: bool,
+) {
+    let key = format!("{workspace_id}:{thread_id}");
+    let mut activity = state.remote_activity.lock().await;
+    if live {
+        activity.live_subscriptions.insert(key);
+    } else {
+        activity.live_subscriptions.remove(&key);
+    }
+}
+
+pub(crate) async fn record_link_quality(state: &AppState, poll_ms: u64, path: RemoteLinkPath) {
+    let mut activity = state.remote_activity.lock().await;
+    activity.link_poll_ms = Some(poll_ms);
+    activity.link_path = Some(path);
+}
+
+pub(super) async fn remote_backend_status(state: &AppState) -> RemoteBackendStatus {
+    let connected = state.remote_backend.lock().await.is_some();
+    let activity = state.remote_activity.lock().await;
+    let idle = activity.last_change.map(|last| last.elapsed());
+    RemoteBackendStatus {
+        connected,
+        live_subscriptions: activity.live_subscriptions.len() as u32,
+        idle_ms: idle.map(|idle| idle.as_millis() as u64),
+        link_path: activity.link_path.clone(),
+        suggested_poll_ms: suggested_poll_ms(
+            idle,
+            activity.live_subscriptions.len(),
+            activity.link_poll_ms,
+            activity.link_path.as_ref(),
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn poll_hint_follows_activity_subscriptions_and_link() {
+        let recent = Some(Duration::from_secs(5));
+        let idle = Some(Duration::from_secs(600));
+
+        assert_eq!(suggested_poll_ms(None, 0, None, None), DEFAULT_POLL_MS);
+        assert_eq!(suggested_poll_ms(recent, 0, None, None), ACTIVE_POLL_MS);
+        assert_eq!(suggested_poll_ms(idle, 0, None, None), IDLE_POLL_MS);
+        assert_eq!(suggested_poll_ms(recent, 1, None, None), SUBSCRIBED_POLL_MS);
+
+        // A slow link outweighs recent activity; a relay keeps the default.
+        assert_eq!(suggested_poll_ms(recent, 0, Some(8_000), None), 8_000);
+        assert_eq!(
+            suggested_poll_ms(recent, 0, Some(1_000), Some(&RemoteLinkPath::Relay)),
+            DEFAULT_POLL_MS
+        );
+        assert_eq!(suggested_poll_ms(idle, 0, Some(120_000), None), MAX_POLL_MS);
+    }
+}
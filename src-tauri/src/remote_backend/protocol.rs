@@ -1,12 +1,17 @@
 use serde_json::{json, Value};
 
+use crate::shared::monitor_error::MonitorError;
+use crate::shared::request_signing_core::SIGNATURE_FIELD;
+
 pub(crate) const DEFAULT_REMOTE_HOST: &str = "127.0.0.1:4732";
 pub(crate) const DISCONNECTED_MESSAGE: &str = "remote backend disconnected";
+/// Request field naming the call for logs on both ends; daemons ignore it.
+const TRACE_ID_FIELD: &str = "traceId";
 
 pub(crate) enum IncomingMessage {
     Response {
         id: u64,
-        payload: Result<Value, String>,
+        payload: Result<Value, MonitorError>,
     },
     Notification {
         method: String,
@@ -14,12 +19,24 @@ pub(crate) enum IncomingMessage {
     },
 }
 
-pub(crate) fn build_request_line(id: u64, method: &str, params: Value) -> Result<String, String> {
-    let request = json!({
+pub(crate) fn build_request_line(
+    id: u64,
+    method: &str,
+    params: Value,
+    signature: Option<Value>,
+    trace_id: Option<&str>,
+) -> Result<String, String> {
+    let mut request = json!({
         "id": id,
         "method": method,
         "params": params,
     });
+    if let Some(signature) = signature {
+        request[SIGNATURE_FIELD] = signature;
+    }
+    if let Some(trace_id) = trace_id {
+        request[TRACE_ID_FIELD] = Value::String(trace_id.to_string());
+    }
     serde_json::to_string(&request).map_err(|err| err.to_string())
 }
 
@@ -28,14 +45,9 @@ pub(crate) fn parse_incoming_line(line: &str) -> Option<IncomingMessage> {
 
     if let Some(id) = message.get("id").and_then(|value| value.as_u64()) {
         if let Some(error) = message.get("error") {
-            let error_message = error
-                .get("message")
-                .and_then(|value| value.as_str())
-                .unwrap_or("remote error")
-                .to_string();
             return Some(IncomingMessage::Response {
                 id,
-                payload: Err(error_message),
+                payload: Err(MonitorError::from_rpc_error(error)),
             });
         }
 
@@ -1,22 +1,42 @@
-use tauri::AppHandle;
 use tokio::net::TcpStream;
 
+use crate::shared::monitor_error::MonitorError;
+use crate::shared::rpc_framing_core::{FrameReader, DEFAULT_MAX_FRAME_BYTES};
+
+use super::proxy::{connect_tcp, split_host_port};
 use super::transport::{
-    spawn_transport_io, RemoteTransport, RemoteTransportConfig, TransportFuture,
+    negotiate_framing, spawn_transport_io, RemoteEventSink, RemoteTransport, RemoteTransportConfig,
+    TransportFuture,
 };
 
 pub(crate) struct TcpTransport;
 
 impl RemoteTransport for TcpTransport {
-    fn connect(&self, app: AppHandle, config: RemoteTransportConfig) -> TransportFuture {
+    fn connect(&self, events: RemoteEventSink, config: RemoteTransportConfig) -> TransportFuture {
         Box::pin(async move {
-            let RemoteTransportConfig::Tcp { host, .. } = config;
+            let RemoteTransportConfig::Tcp { host, proxy, .. } = config else {
+                return Err(MonitorError::Config(
+                    "TCP transport requires a host:port remote backend host".to_string(),
+                ));
+            };
 
-            let stream = TcpStream::connect(host.clone())
-                .await
-                .map_err(|err| format!("Failed to connect to remote backend at {host}: {err}"))?;
-            let (reader, writer) = stream.into_split();
-            Ok(spawn_transport_io(app, reader, writer))
+            let stream = match split_host_port(host.trim()) {
+                Some((name, port)) => connect_tcp(&proxy, &name, port)
+                    .await
+                    .map(|(stream, _)| stream),
+                None => TcpStream::connect(host.clone())
+                    .await
+                    .map_err(|err| err.to_string()),
+            }
+            .map_err(|err| {
+                MonitorError::Unreachable(format!(
+                    "Failed to connect to remote backend at {host}: {err}"
+                ))
+            })?;
+            let (reader, mut writer) = stream.into_split();
+            let mut frames = FrameReader::new(reader, DEFAULT_MAX_FRAME_BYTES);
+            let framing = negotiate_framing(&mut frames, &mut writer).await?;
+            Ok(spawn_transport_io(events, frames, writer, framing))
         })
     }
 }
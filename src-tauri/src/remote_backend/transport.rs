@@ -3,40 +3,75 @@ use std::future::Future;
 use std::pin::Pin;
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
+use std::time::Duration;
 
 use serde_json::Value;
-use tauri::{AppHandle, Emitter};
-use tokio::io::{AsyncBufReadExt, AsyncRead, AsyncWrite, AsyncWriteExt, BufReader};
+use tokio::io::{AsyncRead, AsyncWrite, AsyncWriteExt};
 use tokio::sync::{mpsc, oneshot, Mutex};
+use tokio::time::timeout;
+
+use crate::shared::monitor_error::MonitorError;
+use crate::shared::rpc_framing_core::{
+    accepted_max_frame_bytes, encode_message, framing_request, FrameReader, Framing,
+    DEFAULT_MAX_FRAME_BYTES,
+};
+use crate::types::ProxySettings;
 
 use super::protocol::{parse_incoming_line, IncomingMessage, DISCONNECTED_MESSAGE};
 
-pub(crate) type PendingMap = HashMap<u64, oneshot::Sender<Result<Value, String>>>;
-const OUTBOUND_QUEUE_CAPACITY: usize = 512;
+pub(crate) type PendingMap = HashMap<u64, oneshot::Sender<Result<Value, MonitorError>>>;
+pub(crate) const OUTBOUND_QUEUE_CAPACITY: usize = 512;
 
 #[derive(Clone, Debug)]
 pub(crate) enum RemoteTransportConfig {
     Tcp {
         host: String,
         auth_token: Option<String>,
+        /// The daemon identity secret shared when the backend was paired.
+        identity: Option<String>,
+        proxy: ProxySettings,
+    },
+    WebSocket {
+        url: String,
+        auth_token: Option<String>,
+        identity: Option<String>,
+        proxy: ProxySettings,
     },
 }
 
 #[derive(Copy, Clone, Debug, PartialEq, Eq)]
 pub(crate) enum RemoteTransportKind {
     Tcp,
+    WebSocket,
 }
 
 impl RemoteTransportConfig {
     pub(crate) fn kind(&self) -> RemoteTransportKind {
         match self {
             RemoteTransportConfig::Tcp { .. } => RemoteTransportKind::Tcp,
+            RemoteTransportConfig::WebSocket { .. } => RemoteTransportKind::WebSocket,
+        }
+    }
+
+    /// The host or URL connected to, for messages.
+    pub(crate) fn target(&self) -> &str {
+        match self {
+            RemoteTransportConfig::Tcp { host, .. } => host,
+            RemoteTransportConfig::WebSocket { url, .. } => url,
         }
     }
 
     pub(crate) fn auth_token(&self) -> Option<&str> {
         match self {
-            RemoteTransportConfig::Tcp { auth_token, .. } => auth_token.as_deref(),
+            RemoteTransportConfig::Tcp { auth_token, .. }
+            | RemoteTransportConfig::WebSocket { auth_token, .. } => auth_token.as_deref(),
+        }
+    }
+
+    pub(crate) fn identity(&self) -> Option<&str> {
+        match self {
+            RemoteTransportConfig::Tcp { identity, .. }
+            | RemoteTransportConfig::WebSocket { identity, .. } => identity.as_deref(),
         }
     }
 }
@@ -48,16 +83,78 @@ pub(crate) struct TransportConnection {
 }
 
 pub(crate) type TransportFuture =
-    Pin<Box<dyn Future<Output = Result<TransportConnection, String>> + Send>>;
+    Pin<Box<dyn Future<Output = Result<TransportConnection, MonitorError>> + Send>>;
+
+/// Receives the notifications the remote daemon pushes, by event name.
+pub(crate) type RemoteEventSink = Arc<dyn Fn(&str, Value) + Send + Sync>;
+
+/// Notifications that are passed on to the event sink; others are dropped.
+const FORWARDED_EVENTS: &[&str] = &[
+    "app-server-event",
+    "terminal-output",
+    "terminal-exit",
+    "remote-command-output",
+    "codex-session-output",
+    "codex-session-exit",
+    "codex-session-queue",
+    "codex-login-progress",
+    "remote-action-confirmation-requested",
+];
 
 pub(crate) trait RemoteTransport: Send + Sync {
-    fn connect(&self, app: AppHandle, config: RemoteTransportConfig) -> TransportFuture;
+    fn connect(&self, events: RemoteEventSink, config: RemoteTransportConfig) -> TransportFuture;
+}
+
+const FRAMING_NEGOTIATION_TIMEOUT: Duration = Duration::from_secs(10);
+/// Below the ids `RemoteBackend` hands out, so the reply cannot be mistaken
+/// for a call's.
+const FRAMING_REQUEST_ID: u64 = 0;
+
+/// Asks the daemon for length-prefixed frames, staying on lines when it
+/// predates them. Notifications that arrive meanwhile are dropped.
+pub(crate) async fn negotiate_framing<R, W>(
+    frames: &mut FrameReader<R>,
+    writer: &mut W,
+) -> Result<Framing, MonitorError>
+where
+    R: AsyncRead + Unpin,
+    W: AsyncWrite + Unpin,
+{
+    let request = framing_request(FRAMING_REQUEST_ID, DEFAULT_MAX_FRAME_BYTES);
+    writer
+        .write_all(&encode_message(&request, Framing::Lines))
+        .await
+        .map_err(|err| MonitorError::Network(err.to_string()))?;
+    let reply = timeout(FRAMING_NEGOTIATION_TIMEOUT, async {
+        while let Some(message) = frames.next_message().await? {
+            let Ok(message) = serde_json::from_str::<Value>(&message) else {
+                continue;
+            };
+            if message.get("id").and_then(Value::as_u64) == Some(FRAMING_REQUEST_ID) {
+                return Ok(Some(message));
+            }
+        }
+        Ok::<_, std::io::Error>(None)
+    })
+    .await
+    .map_err(|_| MonitorError::Timeout("timed out negotiating RPC framing".to_string()))?
+    .map_err(|err| MonitorError::Network(err.to_string()))?
+    .ok_or_else(|| MonitorError::Disconnected(DISCONNECTED_MESSAGE.to_string()))?;
+
+    match reply.get("result").and_then(accepted_max_frame_bytes) {
+        Some(max_frame_bytes) => {
+            frames.use_length_prefixed(max_frame_bytes);
+            Ok(Framing::LengthPrefixed)
+        }
+        None => Ok(Framing::Lines),
+    }
 }
 
 pub(crate) fn spawn_transport_io<R, W>(
-    app: AppHandle,
-    reader: R,
+    events: RemoteEventSink,
+    frames: FrameReader<R>,
     mut writer: W,
+    framing: Framing,
 ) -> TransportConnection
 where
     R: AsyncRead + Unpin + Send + 'static,
@@ -74,8 +171,10 @@ where
 
     tokio::spawn(async move {
         while let Some(message) = out_rx.recv().await {
-            if writer.write_all(message.as_bytes()).await.is_err()
-                || writer.write_all(b"\n").await.is_err()
+            if writer
+                .write_all(&encode_message(&message, framing))
+                .await
+                .is_err()
             {
                 mark_disconnected(&pending_for_writer, &connected_for_writer).await;
                 break;
@@ -84,7 +183,7 @@ where
     });
 
     tokio::spawn(async move {
-        read_loop(app, reader, pending_for_reader, connected_for_reader).await;
+        read_loop(events, frames, pending_for_reader, connected_for_reader).await;
     });
 
     TransportConnection {
@@ -95,28 +194,26 @@ where
 }
 
 async fn read_loop<R>(
-    app: AppHandle,
-    reader: R,
+    events: RemoteEventSink,
+    mut frames: FrameReader<R>,
     pending: Arc<Mutex<PendingMap>>,
     connected: Arc<AtomicBool>,
 ) where
     R: AsyncRead + Unpin + Send + 'static,
 {
-    let mut lines = BufReader::new(reader).lines();
-
-    while let Ok(Some(line)) = lines.next_line().await {
+    while let Ok(Some(line)) = frames.next_message().await {
         let trimmed = line.trim();
         if trimmed.is_empty() {
             continue;
         }
-        dispatch_incoming_line(&app, &pending, trimmed).await;
+        dispatch_incoming_line(&events, &pending, trimmed).await;
     }
 
     mark_disconnected(&pending, &connected).await;
 }
 
 pub(crate) async fn dispatch_incoming_line(
-    app: &AppHandle,
+    events: &RemoteEventSink,
     pending: &Arc<Mutex<PendingMap>>,
     line: &str,
 ) {
@@ -131,18 +228,11 @@ pub(crate) async fn dispatch_incoming_line(
                 let _ = sender.send(payload);
             }
         }
-        IncomingMessage::Notification { method, params } => match method.as_str() {
-            "app-server-event" => {
-                let _ = app.emit("app-server-event", params);
-            }
-            "terminal-output" => {
-                let _ = app.emit("terminal-output", params);
+        IncomingMessage::Notification { method, params } => {
+            if FORWARDED_EVENTS.contains(&method.as_str()) {
+                events(&method, params);
             }
-            "terminal-exit" => {
-                let _ = app.emit("terminal-exit", params);
-            }
-            _ => {}
-        },
+        }
     }
 }
 
@@ -153,6 +243,8 @@ pub(crate) async fn mark_disconnected(
     connected.store(false, Ordering::SeqCst);
     let mut pending = pending.lock().await;
     for (_, sender) in pending.drain() {
-        let _ = sender.send(Err(DISCONNECTED_MESSAGE.to_string()));
+        let _ = sender.send(Err(MonitorError::Disconnected(
+            DISCONNECTED_MESSAGE.to_string(),
+        )));
     }
 }
@@ -0,0 +1,88 @@
+use std::sync::atomic::AtomicBool;
+use std::sync::Arc;
+
+use futures_util::{SinkExt, StreamExt};
+use tokio::sync::{mpsc, Mutex};
+use tokio_tungstenite::client_async_tls;
+use tokio_tungstenite::tungstenite::Message;
+
+use crate::shared::monitor_error::MonitorError;
+
+use super::proxy::{connect_tcp, websocket_host_port};
+use super::transport::{
+    dispatch_incoming_line, mark_disconnected, PendingMap, RemoteEventSink, RemoteTransport,
+    RemoteTransportConfig, TransportConnection, TransportFuture, OUTBOUND_QUEUE_CAPACITY,
+};
+
+/// Carries the same JSON messages as the TCP transport, one per text frame.
+pub(crate) struct WebSocketTransport;
+
+impl RemoteTransport for WebSocketTransport {
+    fn connect(&self, events: RemoteEventSink, config: RemoteTransportConfig) -> TransportFuture {
+        Box::pin(async move {
+            let RemoteTransportConfig::WebSocket { url, proxy, .. } = config else {
+                return Err(MonitorError::Config(
+                    "WebSocket transport requires a ws:// or wss:// host".to_string(),
+                ));
+            };
+
+            let (host, port) = websocket_host_port(&url).map_err(MonitorError::Config)?;
+            let unreachable = |err: String| {
+                MonitorError::Unreachable(format!(
+                    "Failed to connect to remote backend at {url}: {err}"
+                ))
+            };
+            let (tcp, _) = connect_tcp(&proxy, &host, port)
+                .await
+                .map_err(unreachable)?;
+            let (stream, _) = client_async_tls(url.as_str(), tcp)
+                .await
+                .map_err(|err| unreachable(err.to_string()))?;
+            let (mut sink, mut source) = stream.split();
+
+            let (out_tx, mut out_rx) = mpsc::channel::<String>(OUTBOUND_QUEUE_CAPACITY);
+            let pending = Arc::new(Mutex::new(PendingMap::new()));
+            let pending_for_writer = Arc::clone(&pending);
+            let pending_for_reader = Arc::clone(&pending);
+
+            let connected = Arc::new(AtomicBool::new(true));
+            let connected_for_writer = Arc::clone(&connected);
+            let connected_for_reader = Arc::clone(&connected);
+
+            tokio::spawn(async move {
+                while let Some(message) = out_rx.recv().await {
+                    if sink.send(Message::Text(message)).await.is_err() {
+                        mark_disconnected(&pending_for_writer, &connected_for_writer).await;
+                        break;
+                    }
+                }
+                let _ = sink.close().await;
+            });
+
+            tokio::spawn(async move {
+                while let Some(Ok(message)) = source.next().await {
+                    match message {
+                        Message::Text(text) => {
+                            for line in text.lines() {
+                                let trimmed = line.trim();
+                                if !trimmed.is_empty() {
+                                    dispatch_incoming_line(&events, &pending_for_reader, trimmed)
+                                        .await;
+                                }
+                            }
+                        }
+                        Message::Close(_) => break,
+                        _ => {}
+                    }
+                }
+                mark_disconnected(&pending_for_reader, &connected_for_reader).await;
+            });
+
+            Ok(TransportConnection {
+                out_tx,
+                pending,
+                connected,
+            })
+        })
+    }
+}
@@ -0,0 +1,78 @@
+use tauri::{AppHandle, State};
+
+use crate::command_error::{CommandError, CommandResult};
+use crate::event_sink::TauriEventSink;
+use crate::remote_backend;
+use crate::shared::{pagination_core, remote_command_core, workspace_rpc};
+use crate::state::AppState;
+use crate::types::{Page, PageResult, RemoteCommandAuditEntry, RemoteCommandResult};
+
+#[tauri::command]
+pub(crate) async fn run_remote_command(
+    workspace_id: String,
+    command: String,
+    cwd: Option<String>,
+    run_id: Option<String>,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> CommandResult<RemoteCommandResult> {
+    if remote_backend::is_remote_mode(&*state).await {
+        let request = workspace_rpc::RunRemoteCommandRequest {
+            workspace_id,
+            command,
+            cwd,
+            run_id,
+        };
+        let response = remote_backend::call_remote(
+            &*state,
+            app,
+            "run_remote_command",
+            workspace_rpc::to_params(&request)?,
+        )
+        .await?;
+        return serde_json::from_value(response).map_err(|err| CommandError::from(err.to_string()));
+    }
+
+    remote_command_core::run_remote_command_core(
+        &state.workspaces,
+        &state.app_settings,
+        state.data_dir(),
+        TauriEventSink::new(app),
+        workspace_id,
+        command,
+        cwd,
+        run_id,
+    )
+    .await
+    .map_err(CommandError::from)
+}
+
+/// Newest first. The log lives with whichever side runs the commands, so in
+/// remote mode it is read from the daemon.
+#[tauri::command]
+pub(crate) async fn list_remote_command_audit(
+    page: Option<Page>,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> CommandResult<PageResult<RemoteCommandAuditEntry>> {
+    let page = page.unwrap_or_default();
+    if remote_backend::is_remote_mode(&*state).await {
+        let request = workspace_rpc::PageRequest { page };
+        let response = remote_backend::call_remote(
+            &*state,
+            app,
+            "list_remote_command_audit",
+            workspace_rpc::to_params(&request)?,
+        )
+        .await?;
+        return pagination_core::page_from_value(response).map_err(CommandError::from);
+    }
+
+    let data_dir = state.data_dir().to_path_buf();
+    tokio::task::spawn_blocking(move || {
+        remote_command_core::list_remote_command_audit_core(&data_dir, &page)
+    })
+    .await
+    .map_err(|err| err.to_string())?
+    .map_err(CommandError::from)
+}
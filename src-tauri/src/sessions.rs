@@ -0,0 +1,679 @@
+use tauri::{AppHandle, Manager, State};
+
+use crate::command_error::{CommandError, CommandResult};
+use crate::event_sink::TauriEventSink;
+use crate::local_usage;
+use crate::remote_backend;
+use crate::shared::{
+    codex_session_core, pagination_core, process_core, scheduler_core, session_archive_core,
+    session_export_core, session_search_core, session_templates_core, session_worktree_core,
+    workspace_rpc,
+};
+use crate::state::AppState;
+use crate::types::{
+    CodexSessionDetail, CodexSessionInfo, Page, PageResult, ProcessStats, RemoteFileChunk,
+    Schedule, ScheduleInput, ScheduleRun, SessionArchiveInfo, SessionArchiveReport, SessionExport,
+    SessionExportFormat, SessionPullRequest, SessionRestoreReport, SessionSearchFilters,
+    SessionSearchHit, SessionTemplate, SessionTemplateInput, SessionWorktreeMergeResult,
+};
+
+#[tauri::command]
+pub(crate) async fn search_sessions(
+    query: String,
+    filters: Option<SessionSearchFilters>,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> CommandResult<Vec<SessionSearchHit>> {
+    if remote_backend::is_remote_mode(&*state).await {
+        let request = workspace_rpc::SearchSessionsRequest { query, filters };
+        let response = remote_backend::call_remote(
+            &*state,
+            app,
+            "search_sessions",
+            workspace_rpc::to_params(&request)?,
+        )
+        .await?;
+        return serde_json::from_value(response).map_err(|err| CommandError::from(err.to_string()));
+    }
+
+    session_search_core::search_sessions_core(
+        &state.workspaces,
+        state.data_dir().to_path_buf(),
+        query,
+        filters,
+    )
+    .await
+    .map_err(CommandError::from)
+}
+
+#[tauri::command]
+pub(crate) async fn archive_sessions(
+    before: Option<i64>,
+    dry_run: Option<bool>,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> CommandResult<SessionArchiveReport> {
+    if remote_backend::is_remote_mode(&*state).await {
+        let request = workspace_rpc::ArchiveSessionsRequest { before, dry_run };
+        let response = remote_backend::call_remote(
+            &*state,
+            app,
+            "archive_sessions",
+            workspace_rpc::to_params(&request)?,
+        )
+        .await?;
+        return serde_json::from_value(response).map_err(|err| CommandError::from(err.to_string()));
+    }
+
+    session_archive_core::archive_sessions_core(
+        &state.workspaces,
+        &state.app_settings,
+        state.data_dir().to_path_buf(),
+        before,
+        dry_run.unwrap_or(false),
+    )
+    .await
+    .map_err(CommandError::from)
+}
+
+#[tauri::command]
+pub(crate) async fn list_archives(
+    page: Option<Page>,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> CommandResult<PageResult<SessionArchiveInfo>> {
+    let page = page.unwrap_or_default();
+    if remote_backend::is_remote_mode(&*state).await {
+        let request = workspace_rpc::PageRequest { page };
+        let response = remote_backend::call_remote(
+            &*state,
+            app,
+            "list_archives",
+            workspace_rpc::to_params(&request)?,
+        )
+        .await?;
+        return pagination_core::page_from_value(response).map_err(CommandError::from);
+    }
+
+    session_archive_core::list_archives_core(state.data_dir().to_path_buf(), page)
+        .await
+        .map_err(CommandError::from)
+}
+
+#[tauri::command]
+pub(crate) async fn restore_archive(
+    name: String,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> CommandResult<SessionRestoreReport> {
+    if remote_backend::is_remote_mode(&*state).await {
+        let request = workspace_rpc::RestoreArchiveRequest { name };
+        let response = remote_backend::call_remote(
+            &*state,
+            app,
+            "restore_archive",
+            workspace_rpc::to_params(&request)?,
+        )
+        .await?;
+        return serde_json::from_value(response).map_err(|err| CommandError::from(err.to_string()));
+    }
+
+    session_archive_core::restore_archive_core(
+        &state.workspaces,
+        state.data_dir().to_path_buf(),
+        name,
+    )
+    .await
+    .map_err(CommandError::from)
+}
+
+#[tauri::command]
+#[allow(clippy::too_many_arguments)]
+pub(crate) async fn start_codex_session(
+    project_path: Option<String>,
+    prompt: Option<String>,
+    model: Option<String>,
+    approval_mode: Option<String>,
+    template_id: Option<String>,
+    isolate_worktree: Option<bool>,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> CommandResult<CodexSessionInfo> {
+    if remote_backend::is_remote_mode(&*state).await {
+        let request = workspace_rpc::StartCodexSessionRequest {
+            project_path,
+            prompt,
+            model,
+            approval_mode,
+            template_id,
+            isolate_worktree,
+        };
+        let response = remote_backend::call_remote(
+            &*state,
+            app,
+            "start_codex_session",
+            workspace_rpc::to_params(&request)?,
+        )
+        .await?;
+        return serde_json::from_value(response).map_err(|err| CommandError::from(err.to_string()));
+    }
+
+    codex_session_core::start_codex_session_core(
+        &state.session_runtimes,
+        &state.app_settings,
+        state.data_dir(),
+        TauriEventSink::new(app),
+        codex_session_core::StartSessionOptions {
+            project_path,
+            prompt,
+            model,
+            approval_mode,
+            template_id,
+            isolate_worktree: isolate_worktree.unwrap_or(false),
+        },
+    )
+    .await
+    .map_err(CommandError::from)
+}
+
+#[tauri::command]
+pub(crate) async fn merge_session_worktree(
+    session_id: String,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> CommandResult<SessionWorktreeMergeResult> {
+    if remote_backend::is_remote_mode(&*state).await {
+        let request = workspace_rpc::SessionIdRequest { session_id };
+        let response = remote_backend::call_remote(
+            &*state,
+            app,
+            "merge_session_worktree",
+            workspace_rpc::to_params(&request)?,
+        )
+        .await?;
+        return serde_json::from_value(response).map_err(|err| CommandError::from(err.to_string()));
+    }
+
+    session_worktree_core::merge_session_worktree_core(
+        &state.session_runtimes,
+        state.data_dir(),
+        session_id,
+    )
+    .await
+    .map_err(CommandError::from)
+}
+
+#[tauri::command]
+pub(crate) async fn discard_session_worktree(
+    session_id: String,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> CommandResult<()> {
+    if remote_backend::is_remote_mode(&*state).await {
+        let request = workspace_rpc::SessionIdRequest { session_id };
+        remote_backend::call_remote(
+            &*state,
+            app,
+            "discard_session_worktree",
+            workspace_rpc::to_params(&request)?,
+        )
+        .await?;
+        return Ok(());
+    }
+
+    session_worktree_core::discard_session_worktree_core(
+        &state.session_runtimes,
+        state.data_dir(),
+        session_id,
+    )
+    .await
+    .map_err(CommandError::from)
+}
+
+#[tauri::command]
+pub(crate) async fn create_pr_from_session(
+    session_id: String,
+    title: String,
+    body: Option<String>,
+    base: Option<String>,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> CommandResult<SessionPullRequest> {
+    if remote_backend::is_remote_mode(&*state).await {
+        let request = workspace_rpc::CreatePrFromSessionRequest {
+            session_id,
+            title,
+            body,
+            base,
+        };
+        let response = remote_backend::call_remote(
+            &*state,
+            app,
+            "create_pr_from_session",
+            workspace_rpc::to_params(&request)?,
+        )
+        .await?;
+        return serde_json::from_value(response).map_err(|err| CommandError::from(err.to_string()));
+    }
+
+    session_worktree_core::create_pr_from_session_core(
+        &state.session_runtimes,
+        state.data_dir(),
+        session_id,
+        title,
+        body,
+        base,
+    )
+    .await
+    .map_err(CommandError::from)
+}
+
+#[tauri::command]
+pub(crate) async fn send_session_input(
+    session_id: String,
+    text: String,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> CommandResult<()> {
+    if remote_backend::is_remote_mode(&*state).await {
+        let request = workspace_rpc::SendSessionInputRequest { session_id, text };
+        remote_backend::call_remote(
+            &*state,
+            app,
+            "send_session_input",
+            workspace_rpc::to_params(&request)?,
+        )
+        .await?;
+        return Ok(());
+    }
+
+    codex_session_core::send_session_input_core(&state.session_runtimes, session_id, text)
+        .await
+        .map_err(CommandError::from)
+}
+
+/// Sessions the monitor started or queued, optionally only those carrying
+/// every tag in `tags`.
+#[tauri::command]
+pub(crate) async fn list_codex_sessions(
+    tags: Option<Vec<String>>,
+    page: Option<Page>,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> CommandResult<PageResult<CodexSessionInfo>> {
+    let tags = tags.unwrap_or_default();
+    let page = page.unwrap_or_default();
+    if remote_backend::is_remote_mode(&*state).await {
+        let request = workspace_rpc::ListCodexSessionsRequest { tags, page };
+        let response = remote_backend::call_remote(
+            &*state,
+            app,
+            "list_codex_sessions",
+            workspace_rpc::to_params(&request)?,
+        )
+        .await?;
+        return pagination_core::page_from_value(response).map_err(CommandError::from);
+    }
+
+    codex_session_core::list_codex_sessions_core(
+        &state.session_runtimes,
+        &state.workspaces,
+        state.data_dir(),
+        tags,
+        &page,
+    )
+    .await
+    .map_err(CommandError::from)
+}
+
+#[tauri::command]
+pub(crate) async fn get_codex_session(
+    session_id: String,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> CommandResult<CodexSessionDetail> {
+    if remote_backend::is_remote_mode(&*state).await {
+        let request = workspace_rpc::SessionIdRequest { session_id };
+        let response = remote_backend::call_remote(
+            &*state,
+            app,
+            "get_codex_session",
+            workspace_rpc::to_params(&request)?,
+        )
+        .await?;
+        return serde_json::from_value(response).map_err(|err| CommandError::from(err.to_string()));
+    }
+
+    codex_session_core::get_codex_session_core(&state.session_runtimes, session_id)
+        .await
+        .map_err(CommandError::from)
+}
+
+/// Exports a Codex session, by the id search results carry, into the
+/// `exports` directory. In remote mode the daemon renders it and the file is
+/// pulled back in chunks into the local data directory.
+#[tauri::command]
+pub(crate) async fn export_session(
+    session_id: String,
+    format: SessionExportFormat,
+    include_tool_output: Option<bool>,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> CommandResult<SessionExport> {
+    let include_tool_output = include_tool_output.unwrap_or(false);
+    if remote_backend::is_remote_mode(&*state).await {
+        let request = workspace_rpc::ExportSessionRequest {
+            session_id,
+            format,
+            include_tool_output,
+        };
+        let response = remote_backend::call_remote(
+            &*state,
+            app.clone(),
+            "export_session",
+            workspace_rpc::to_params(&request)?,
+        )
+        .await?;
+        let remote: SessionExport =
+            serde_json::from_value(response).map_err(|err| CommandError::from(err.to_string()))?;
+        let mut content = String::new();
+        loop {
+            let request = workspace_rpc::ReadSessionExportRequest {
+                file_name: remote.file_name.clone(),
+                offset: Some(content.len() as u64),
+                length: Some(session_export_core::EXPORT_CHUNK_BYTES),
+            };
+            let response = remote_backend::call_remote(
+                &*state,
+                app.clone(),
+                "read_session_export",
+                workspace_rpc::to_params(&request)?,
+            )
+            .await?;
+            let chunk: RemoteFileChunk = serde_json::from_value(response)
+                .map_err(|err| CommandError::from(err.to_string()))?;
+            content.push_str(chunk.content.as_deref().unwrap_or_default());
+            if !chunk.truncated || chunk.length == 0 {
+                break;
+            }
+        }
+        let path = session_export_core::save_export(state.data_dir(), &remote.file_name, &content)?;
+        return Ok(SessionExport {
+            path: path.to_string_lossy().to_string(),
+            size: content.len() as u64,
+            ..remote
+        });
+    }
+
+    session_export_core::export_session_core(
+        &state.workspaces,
+        state.data_dir(),
+        session_id,
+        format,
+        include_tool_output,
+    )
+    .await
+    .map_err(CommandError::from)
+}
+
+/// Samples a process tree; without `pid` this samples the backend itself
+/// (the app, or the daemon in remote mode).
+#[tauri::command]
+pub(crate) async fn get_process_stats(
+    pid: Option<u32>,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> CommandResult<ProcessStats> {
+    if remote_backend::is_remote_mode(&*state).await {
+        let request = workspace_rpc::ProcessStatsRequest { pid };
+        let response = remote_backend::call_remote(
+            &*state,
+            app,
+            "get_process_stats",
+            workspace_rpc::to_params(&request)?,
+        )
+        .await?;
+        return serde_json::from_value(response).map_err(|err| CommandError::from(err.to_string()));
+    }
+
+    process_core::sample_process_stats(pid.unwrap_or_else(std::process::id))
+        .await
+        .map_err(CommandError::from)
+}
+
+#[tauri::command]
+pub(crate) async fn cancel_queued_session(
+    session_id: String,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> CommandResult<()> {
+    if remote_backend::is_remote_mode(&*state).await {
+        let request = workspace_rpc::SessionIdRequest { session_id };
+        remote_backend::call_remote(
+            &*state,
+            app,
+            "cancel_queued_session",
+            workspace_rpc::to_params(&request)?,
+        )
+        .await?;
+        return Ok(());
+    }
+
+    codex_session_core::cancel_queued_session_core(
+        &state.session_runtimes,
+        TauriEventSink::new(app),
+        session_id,
+    )
+    .await
+    .map_err(CommandError::from)
+}
+
+#[tauri::command]
+pub(crate) async fn terminate_session(
+    session_id: String,
+    pid: Option<u32>,
+    force: Option<bool>,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> CommandResult<()> {
+    let force = force.unwrap_or(false);
+    if remote_backend::is_remote_mode(&*state).await {
+        let request = workspace_rpc::TerminateSessionRequest {
+            session_id,
+            pid,
+            force,
+        };
+        remote_backend::call_remote(
+            &*state,
+            app,
+            "terminate_session",
+            workspace_rpc::to_params(&request)?,
+        )
+        .await?;
+        return Ok(());
+    }
+
+    codex_session_core::terminate_session_core(
+        &state.session_runtimes,
+        TauriEventSink::new(app),
+        session_id,
+        pid,
+        force,
+    )
+    .await
+    .map_err(CommandError::from)
+}
+
+#[tauri::command]
+pub(crate) async fn interrupt_session(
+    session_id: String,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> CommandResult<()> {
+    if remote_backend::is_remote_mode(&*state).await {
+        let request = workspace_rpc::SessionIdRequest { session_id };
+        remote_backend::call_remote(
+            &*state,
+            app,
+            "interrupt_session",
+            workspace_rpc::to_params(&request)?,
+        )
+        .await?;
+        return Ok(());
+    }
+
+    codex_session_core::interrupt_session_core(&state.session_runtimes, session_id)
+        .await
+        .map_err(CommandError::from)
+}
+
+#[tauri::command]
+pub(crate) async fn list_session_templates(
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> CommandResult<Vec<SessionTemplate>> {
+    if remote_backend::is_remote_mode(&*state).await {
+        let response = remote_backend::call_remote(
+            &*state,
+            app,
+            "list_session_templates",
+            serde_json::json!({}),
+        )
+        .await?;
+        return serde_json::from_value(response).map_err(|err| CommandError::from(err.to_string()));
+    }
+
+    session_templates_core::list_session_templates_core(state.data_dir())
+        .map_err(CommandError::from)
+}
+
+#[tauri::command]
+pub(crate) async fn save_session_template(
+    template: SessionTemplateInput,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> CommandResult<SessionTemplate> {
+    if remote_backend::is_remote_mode(&*state).await {
+        let request = workspace_rpc::SaveSessionTemplateRequest { template };
+        let response = remote_backend::call_remote(
+            &*state,
+            app,
+            "save_session_template",
+            workspace_rpc::to_params(&request)?,
+        )
+        .await?;
+        return serde_json::from_value(response).map_err(|err| CommandError::from(err.to_string()));
+    }
+
+    session_templates_core::save_session_template_core(state.data_dir(), template)
+        .map_err(CommandError::from)
+}
+
+#[tauri::command]
+pub(crate) async fn delete_session_template(
+    id: String,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> CommandResult<()> {
+    if remote_backend::is_remote_mode(&*state).await {
+        let request = workspace_rpc::IdRequest { id };
+        remote_backend::call_remote(
+            &*state,
+            app,
+            "delete_session_template",
+            workspace_rpc::to_params(&request)?,
+        )
+        .await?;
+        return Ok(());
+    }
+
+    session_templates_core::delete_session_template_core(state.data_dir(), &id)
+        .map_err(CommandError::from)
+}
+
+#[tauri::command]
+pub(crate) async fn list_schedules(
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> CommandResult<Vec<Schedule>> {
+    if remote_backend::is_remote_mode(&*state).await {
+        let response =
+            remote_backend::call_remote(&*state, app, "list_schedules", serde_json::json!({}))
+                .await?;
+        return serde_json::from_value(response).map_err(|err| CommandError::from(err.to_string()));
+    }
+
+    scheduler_core::list_schedules_core(state.data_dir()).map_err(CommandError::from)
+}
+
+#[tauri::command]
+pub(crate) async fn upsert_schedule(
+    schedule: ScheduleInput,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> CommandResult<Schedule> {
+    if remote_backend::is_remote_mode(&*state).await {
+        let request = workspace_rpc::UpsertScheduleRequest { schedule };
+        let response = remote_backend::call_remote(
+            &*state,
+            app,
+            "upsert_schedule",
+            workspace_rpc::to_params(&request)?,
+        )
+        .await?;
+        return serde_json::from_value(response).map_err(|err| CommandError::from(err.to_string()));
+    }
+
+    scheduler_core::upsert_schedule_core(state.data_dir(), schedule).map_err(CommandError::from)
+}
+
+#[tauri::command]
+pub(crate) async fn run_schedule_now(
+    id: String,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> CommandResult<ScheduleRun> {
+    if remote_backend::is_remote_mode(&*state).await {
+        let request = workspace_rpc::IdRequest { id };
+        let response = remote_backend::call_remote(
+            &*state,
+            app,
+            "run_schedule_now",
+            workspace_rpc::to_params(&request)?,
+        )
+        .await?;
+        return serde_json::from_value(response).map_err(|err| CommandError::from(err.to_string()));
+    }
+
+    scheduler_core::run_schedule_now_core(
+        &state.session_runtimes,
+        &state.app_settings,
+        state.data_dir(),
+        TauriEventSink::new(app),
+        id,
+    )
+    .await
+    .map_err(CommandError::from)
+}
+
+/// Ticks local schedules for as long as the app runs. In remote mode the
+/// daemon owns scheduling, so ticks are skipped.
+pub(crate) fn spawn_scheduler(app: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        loop {
+            tokio::time::sleep(scheduler_core::SCHEDULER_TICK).await;
+            let _ = local_usage::write_weekly_activity_report(&app).await;
+            let state = app.state::<AppState>();
+            if remote_backend::is_remote_mode(&*state).await {
+                continue;
+            }
+            let _ = scheduler_core::run_due_schedules_core(
+                &state.session_runtimes,
+                &state.app_settings,
+                state.data_dir(),
+                TauriEventSink::new(app.clone()),
+            )
+            .await;
+        }
+    });
+}
@@ -1,17 +1,43 @@
-use tauri::{State, Window};
+mod sync;
 
+use std::path::PathBuf;
+
+use tauri::{AppHandle, State, Window};
+
+use crate::command_error::{CommandError, CommandResult, ErrorCode};
+use crate::remote_backend;
+use crate::shared::backup_core::{apply_backup, create_backup_core, read_backup};
+use crate::shared::codex_home_snapshot_core::{
+    list_codex_home_snapshots_core, restore_codex_home_snapshot_core, snapshot_codex_home_core,
+};
+use crate::shared::config_changelog_core::{list_config_changes_core, revert_config_change_core};
+use crate::shared::daemon_data_dir_core::{migrate_daemon_state, resolve_daemon_data_dir};
+use crate::shared::data_dir_core::{
+    commit_data_dir_move, migrate_data_dir_core, rollback_data_dir_move, validate_data_dir_target,
+    DataDirTargetError,
+};
+use crate::shared::pagination_core;
+use crate::shared::redaction_core::test_redaction_rules_core;
 use crate::shared::settings_core::{
-    get_app_settings_core, get_codex_config_path_core, update_app_settings_core,
+    get_app_settings_core, get_codex_config_path_core, get_codex_home_core,
+    set_codex_home_override_core, update_app_settings_core,
 };
+use crate::shared::workspace_rpc;
 use crate::state::AppState;
-use crate::types::{AppSettings, BackendMode};
+use crate::types::{
+    AppSettings, BackendMode, BackupReport, BackupRestoreReport, CodexHomeInfo,
+    CodexHomeRestoreReport, CodexHomeSnapshotInfo, ConfigChange, Page, PageResult, ProxySettings,
+    ProxyTestResult, RedactionSettings, RedactionTestResult,
+};
 use crate::window;
 
+pub(crate) use sync::*;
+
 #[tauri::command]
 pub(crate) async fn get_app_settings(
     state: State<'_, AppState>,
     window: Window,
-) -> Result<AppSettings, String> {
+) -> CommandResult<AppSettings> {
     let settings = get_app_settings_core(&state.app_settings).await;
     let _ = window::apply_window_appearance(&window, settings.theme.as_str());
     Ok(settings)
@@ -22,21 +48,434 @@ pub(crate) async fn update_app_settings(
     settings: AppSettings,
     state: State<'_, AppState>,
     window: Window,
-) -> Result<AppSettings, String> {
-    let previous = state.app_settings.lock().await.clone();
+) -> CommandResult<AppSettings> {
+    let previous = state.app_settings.read().await.clone();
     let updated =
         update_app_settings_core(settings, &state.app_settings, &state.settings_path).await?;
     if should_reset_remote_backend(&previous, &updated) {
-        *state.remote_backend.lock().await = None;
+        remote_backend::reset_remote_backend(&state).await;
+    }
+    if previous.daemon_data_dir != updated.daemon_data_dir {
+        move_daemon_state(&state, &previous, &updated);
     }
     ensure_remote_runtime_for_settings(&updated, state).await;
     let _ = window::apply_window_appearance(&window, updated.theme.as_str());
+    #[cfg(desktop)]
+    if global_shortcuts_changed(&previous, &updated) {
+        use tauri::Manager;
+        crate::shortcuts::apply_global_shortcuts(window.app_handle(), &updated);
+    }
     Ok(updated)
 }
 
+#[cfg(desktop)]
+fn global_shortcuts_changed(previous: &AppSettings, updated: &AppSettings) -> bool {
+    previous.global_toggle_window_shortcut != updated.global_toggle_window_shortcut
+        || previous.global_toggle_daemon_shortcut != updated.global_toggle_daemon_shortcut
+        || previous.global_start_session_shortcut != updated.global_start_session_shortcut
+        || previous.global_session_template_id != updated.global_session_template_id
+}
+
+/// Takes the daemon's identity and client approvals along when its data
+/// directory changes. A running daemon keeps using the old directory until
+/// it restarts.
+fn move_daemon_state(state: &AppState, previous: &AppSettings, updated: &AppSettings) {
+    let app_data_dir = state.data_dir();
+    let from = resolve_daemon_data_dir(app_data_dir, previous.daemon_data_dir.as_deref());
+    let to = resolve_daemon_data_dir(app_data_dir, updated.daemon_data_dir.as_deref());
+    if let Err(err) = migrate_daemon_state(&from, &to) {
+        eprintln!("update_app_settings: failed to move daemon state: {err}");
+    }
+}
+
+/// Runs `sample` through `settings`, or through the saved redaction rules,
+/// which are the daemon's in remote mode.
+#[tauri::command]
+pub(crate) async fn test_redaction_rules(
+    sample: String,
+    settings: Option<RedactionSettings>,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> CommandResult<RedactionTestResult> {
+    if remote_backend::is_remote_mode(&*state).await {
+        let request = workspace_rpc::TestRedactionRulesRequest { sample, settings };
+        let response = remote_backend::call_remote(
+            &*state,
+            app,
+            "test_redaction_rules",
+            workspace_rpc::to_params(&request)?,
+        )
+        .await?;
+        return serde_json::from_value(response).map_err(|err| CommandError::from(err.to_string()));
+    }
+
+    test_redaction_rules_core(&sample, settings).map_err(CommandError::from)
+}
+
 #[tauri::command]
-pub(crate) async fn get_codex_config_path() -> Result<String, String> {
-    get_codex_config_path_core()
+pub(crate) async fn get_codex_config_path(
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> CommandResult<String> {
+    if remote_backend::is_remote_mode(&*state).await {
+        let response = remote_backend::call_remote(
+            &*state,
+            app,
+            "get_codex_config_path",
+            serde_json::json!({}),
+        )
+        .await?;
+        return serde_json::from_value(response).map_err(|err| CommandError::from(err.to_string()));
+    }
+
+    get_codex_config_path_core().map_err(CommandError::from)
+}
+
+/// The Codex home config and AGENTS.md commands use, and where it came from.
+#[tauri::command]
+pub(crate) async fn get_codex_home(
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> CommandResult<CodexHomeInfo> {
+    if remote_backend::is_remote_mode(&*state).await {
+        let response =
+            remote_backend::call_remote(&*state, app, "get_codex_home", serde_json::json!({}))
+                .await?;
+        return serde_json::from_value(response).map_err(|err| CommandError::from(err.to_string()));
+    }
+
+    Ok(get_codex_home_core())
+}
+
+/// Uses `path` as the Codex home, or clears the override when it is empty.
+#[tauri::command]
+pub(crate) async fn set_codex_home_override(
+    path: Option<String>,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> CommandResult<CodexHomeInfo> {
+    if remote_backend::is_remote_mode(&*state).await {
+        let response = remote_backend::call_remote(
+            &*state,
+            app,
+            "set_codex_home_override",
+            serde_json::json!({ "path": path }),
+        )
+        .await?;
+        return serde_json::from_value(response).map_err(|err| CommandError::from(err.to_string()));
+    }
+
+    set_codex_home_override_core(path, &state.app_settings, &state.settings_path)
+        .await
+        .map_err(CommandError::from)
+}
+
+/// Saves the Codex home's config.toml, AGENTS.md and, unless
+/// `exclude_secrets` is set, auth.json under the data directory.
+#[tauri::command]
+pub(crate) async fn snapshot_codex_home(
+    label: Option<String>,
+    exclude_secrets: Option<bool>,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> CommandResult<CodexHomeSnapshotInfo> {
+    if remote_backend::is_remote_mode(&*state).await {
+        let response = remote_backend::call_remote(
+            &*state,
+            app,
+            "snapshot_codex_home",
+            serde_json::json!({ "label": label, "excludeSecrets": exclude_secrets }),
+        )
+        .await?;
+        return serde_json::from_value(response).map_err(|err| CommandError::from(err.to_string()));
+    }
+
+    snapshot_codex_home_core(
+        state.data_dir().to_path_buf(),
+        label,
+        exclude_secrets.unwrap_or(false),
+    )
+    .await
+    .map_err(CommandError::from)
+}
+
+/// Codex home snapshots, newest first.
+#[tauri::command]
+pub(crate) async fn list_codex_home_snapshots(
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> CommandResult<Vec<CodexHomeSnapshotInfo>> {
+    if remote_backend::is_remote_mode(&*state).await {
+        let response = remote_backend::call_remote(
+            &*state,
+            app,
+            "list_codex_home_snapshots",
+            serde_json::json!({}),
+        )
+        .await?;
+        return serde_json::from_value(response).map_err(|err| CommandError::from(err.to_string()));
+    }
+
+    list_codex_home_snapshots_core(state.data_dir().to_path_buf())
+        .await
+        .map_err(CommandError::from)
+}
+
+/// Puts snapshot `id` back into the current Codex home after snapshotting
+/// what is there now. Running sessions only pick up the change once they
+/// restart.
+#[tauri::command]
+pub(crate) async fn restore_codex_home_snapshot(
+    id: String,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> CommandResult<CodexHomeRestoreReport> {
+    if remote_backend::is_remote_mode(&*state).await {
+        let response = remote_backend::call_remote(
+            &*state,
+            app,
+            "restore_codex_home_snapshot",
+            serde_json::json!({ "id": id }),
+        )
+        .await?;
+        return serde_json::from_value(response).map_err(|err| CommandError::from(err.to_string()));
+    }
+
+    restore_codex_home_snapshot_core(state.data_dir().to_path_buf(), id)
+        .await
+        .map_err(CommandError::from)
+}
+
+/// Recorded writes to the global config.toml, newest first. In remote mode
+/// this is the daemon's log, since it is the daemon's config that changed.
+#[tauri::command]
+pub(crate) async fn list_config_changes(
+    page: Option<Page>,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> CommandResult<PageResult<ConfigChange>> {
+    let page = page.unwrap_or_default();
+    if remote_backend::is_remote_mode(&*state).await {
+        let request = workspace_rpc::PageRequest { page };
+        let response = remote_backend::call_remote(
+            &*state,
+            app,
+            "list_config_changes",
+            workspace_rpc::to_params(&request)?,
+        )
+        .await?;
+        return pagination_core::page_from_value(response).map_err(CommandError::from);
+    }
+
+    let data_dir = state.data_dir().to_path_buf();
+    tokio::task::spawn_blocking(move || list_config_changes_core(&data_dir, &page))
+        .await
+        .map_err(|err| err.to_string())?
+        .map_err(CommandError::from)
+}
+
+/// Undoes the config.toml change `id`, returning the change the revert
+/// itself made.
+#[tauri::command]
+pub(crate) async fn revert_config_change(
+    id: String,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> CommandResult<ConfigChange> {
+    if remote_backend::is_remote_mode(&*state).await {
+        let response = remote_backend::call_remote(
+            &*state,
+            app,
+            "revert_config_change",
+            serde_json::json!({ "id": id }),
+        )
+        .await?;
+        return serde_json::from_value(response).map_err(|err| CommandError::from(err.to_string()));
+    }
+
+    revert_config_change_core(state.data_dir().to_path_buf(), id)
+        .await
+        .map_err(CommandError::from)
+}
+
+/// Moves the app's state to `new_path` and restarts the app from there.
+/// Refused while Codex sessions are running. The managed daemon is stopped
+/// first so nothing writes to the old directory while it is copied, and
+/// started again if the move fails; the old directory is kept. A
+/// `new_path` that already holds files fails with `DataDirNotEmpty` unless
+/// `overwrite` is set.
+#[tauri::command]
+pub(crate) async fn migrate_data_dir(
+    new_path: String,
+    overwrite: Option<bool>,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> CommandResult<()> {
+    #[cfg(not(desktop))]
+    {
+        let _ = (new_path, overwrite, state, app);
+        Err(CommandError::new(
+            ErrorCode::Unsupported,
+            "Moving the data directory is only supported on desktop.",
+        ))
+    }
+    #[cfg(desktop)]
+    {
+        if crate::tailscale::installed_daemon_service().is_some() {
+            return Err(CommandError::new(
+                ErrorCode::Unknown,
+                "Uninstall the daemon service before moving the data directory.",
+            ));
+        }
+        let current = state.data_dir().to_path_buf();
+        let default_dir = crate::state::default_data_dir(&app);
+        let target = PathBuf::from(new_path.trim());
+        let overwrite = overwrite.unwrap_or(false);
+        match validate_data_dir_target(&current, &target, overwrite) {
+            Ok(()) => {}
+            Err(err @ DataDirTargetError::NotEmpty(_)) => {
+                return Err(CommandError::new(ErrorCode::DataDirNotEmpty, err.message())
+                    .with_details(serde_json::json!({ "dataDir": target })));
+            }
+            Err(err) => return Err(CommandError::from(err.message())),
+        }
+        if !state.session_runtimes.is_idle().await {
+            return Err(CommandError::new(
+                ErrorCode::OperationInProgress,
+                "Stop the running Codex sessions before moving the data directory.",
+            ));
+        }
+
+        let stopped = crate::shutdown::stop_for_data_dir_change(&app).await;
+        let moved = tokio::task::spawn_blocking(move || {
+            let moved = migrate_data_dir_core(&current, &target, &default_dir, overwrite)?;
+            commit_data_dir_move(&moved)?;
+            Ok::<_, String>(moved)
+        })
+        .await
+        .map_err(|err| err.to_string())
+        .and_then(|moved| moved);
+        let moved = match moved {
+            Ok(moved) => moved,
+            Err(err) => {
+                crate::shutdown::resume_after_data_dir_change(&app, stopped).await;
+                return Err(CommandError::from(err));
+            }
+        };
+        // Keeps the exit flush from undoing the pointer in the old settings.
+        state.app_settings.write().await.data_dir = moved.data_dir.clone();
+        app.request_restart();
+
+        // Still running once the exit sequence should have finished, so the
+        // restart did not happen: keep starting from the current directory.
+        tokio::time::sleep(crate::shutdown::RESTART_GRACE).await;
+        state.app_settings.write().await.data_dir = moved.previous.clone();
+        let rolled_back = tokio::task::spawn_blocking(move || rollback_data_dir_move(&moved))
+            .await
+            .map_err(|err| err.to_string())
+            .and_then(|rolled_back| rolled_back);
+        crate::shutdown::resume_after_data_dir_change(&app, stopped).await;
+        rolled_back?;
+        Err(CommandError::from(
+            "The app did not restart, so it keeps using the current data directory.",
+        ))
+    }
+}
+
+/// Writes the app's settings, workspaces, session templates, schedules and
+/// guest tokens to a zip at `path`. Remote backend tokens and proxy
+/// credentials are only included, encrypted, when a `passphrase` is given.
+#[tauri::command]
+pub(crate) async fn create_backup(
+    path: String,
+    passphrase: Option<String>,
+    state: State<'_, AppState>,
+) -> CommandResult<BackupReport> {
+    let data_dir = state.data_dir().to_path_buf();
+    let path = PathBuf::from(path.trim());
+    let passphrase = passphrase.filter(|value| !value.is_empty());
+    let report = tokio::task::spawn_blocking(move || {
+        create_backup_core(&data_dir, &path, passphrase.as_deref())
+    })
+    .await
+    .map_err(|err| err.to_string())??;
+    Ok(report)
+}
+
+/// Replaces the app's state with the backup at `path` and restarts the app.
+/// The backup is fully read and checked before anything is stopped or
+/// overwritten. Without a `passphrase` for its secrets, the current ones
+/// are kept.
+#[tauri::command]
+pub(crate) async fn restore_backup(
+    path: String,
+    passphrase: Option<String>,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> CommandResult<BackupRestoreReport> {
+    #[cfg(not(desktop))]
+    {
+        let _ = (path, passphrase, state, app);
+        Err(CommandError::new(
+            ErrorCode::Unsupported,
+            "Restoring a backup is only supported on desktop.",
+        ))
+    }
+    #[cfg(desktop)]
+    {
+        if crate::tailscale::installed_daemon_service().is_some() {
+            return Err(CommandError::new(
+                ErrorCode::Unknown,
+                "Uninstall the daemon service before restoring a backup.",
+            ));
+        }
+        let data_dir = state.data_dir().to_path_buf();
+        let current = state.app_settings.read().await.clone();
+        let path = PathBuf::from(path.trim());
+        let passphrase = passphrase.filter(|value| !value.is_empty());
+        let staged = tokio::task::spawn_blocking(move || {
+            read_backup(&path, passphrase.as_deref(), &current)
+        })
+        .await
+        .map_err(|err| err.to_string())??;
+
+        let stopped = crate::shutdown::stop_for_data_dir_change(&app).await;
+        let staged =
+            tokio::task::spawn_blocking(move || apply_backup(&data_dir, &staged).map(|_| staged))
+                .await
+                .map_err(|err| err.to_string())
+                .and_then(|staged| staged);
+        let staged = match staged {
+            Ok(staged) => staged,
+            Err(err) => {
+                crate::shutdown::resume_after_data_dir_change(&app, stopped).await;
+                return Err(CommandError::from(err));
+            }
+        };
+        // Keeps the exit flush from writing the replaced state back.
+        *state.app_settings.write().await = staged.settings().clone();
+        *state.workspaces.lock().await = staged.workspaces().clone();
+        *state.remote_backend.lock().await = None;
+        app.request_restart();
+        Ok(staged.report().clone())
+    }
+}
+
+/// Checks that `target` (the remote backend host by default) is reachable
+/// with `proxy`, which may be unsaved.
+#[tauri::command]
+pub(crate) async fn test_proxy(
+    proxy: ProxySettings,
+    target: Option<String>,
+    state: State<'_, AppState>,
+) -> CommandResult<ProxyTestResult> {
+    let target = match target.filter(|value| !value.trim().is_empty()) {
+        Some(target) => target,
+        None => state.app_settings.read().await.remote_backend_host.clone(),
+    };
+    remote_backend::test_proxy_connection(&proxy, &target)
+        .await
+        .map_err(CommandError::from)
 }
 
 fn should_reset_remote_backend(previous: &AppSettings, updated: &AppSettings) -> bool {
@@ -54,17 +493,20 @@ fn should_reset_remote_backend(previous: &AppSettings, updated: &AppSettings) ->
         || previous.remote_backend_provider != updated.remote_backend_provider
         || previous.remote_backend_host != updated.remote_backend_host
         || previous.remote_backend_token != updated.remote_backend_token
+        || previous.remote_backend_identity != updated.remote_backend_identity
+        || previous.proxy != updated.proxy
+        || previous.rpc_timeouts != updated.rpc_timeouts
 }
 
 async fn ensure_remote_runtime_for_settings(settings: &AppSettings, state: State<'_, AppState>) {
-    if cfg!(any(target_os = "android", target_os = "ios")) {
+    if crate::platform::is_mobile() {
         return;
     }
     if !matches!(settings.backend_mode, BackendMode::Remote) {
         return;
     }
 
-    let _ = crate::tailscale::tailscale_daemon_start(state).await;
+    let _ = crate::tailscale::tailscale_daemon_start(None, state).await;
 }
 
 #[cfg(test)]
@@ -0,0 +1,115 @@
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use serde_json::json;
+use tauri::{AppHandle, Emitter, Manager, State};
+
+use crate::command_error::{CommandError, CommandResult};
+use crate::remote_backend;
+use crate::shared::settings_core::update_app_settings_core;
+use crate::shared::settings_sync_core::{
+    apply_synced_templates, merge_synced_settings, projects_to_add, SettingsSyncSnapshot,
+    SETTINGS_SYNC_SNAPSHOT_METHOD,
+};
+use crate::state::AppState;
+use crate::types::{AppSettings, RemoteBackendTarget, SettingsSyncReport};
+
+const SETTINGS_SYNCED_EVENT: &str = "settings-synced";
+
+fn now_ms() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_millis() as i64)
+        .unwrap_or(0)
+}
+
+fn sync_source(settings: &AppSettings) -> Result<RemoteBackendTarget, CommandError> {
+    if !settings.settings_sync.enabled {
+        return Err(CommandError::from("Turn on settings sync first."));
+    }
+    let id = settings
+        .settings_sync
+        .source_backend_id
+        .as_ref()
+        .or(settings.active_remote_backend_id.as_ref())
+        .ok_or_else(|| CommandError::from("Choose a remote backend to sync settings from."))?;
+    settings
+        .remote_backends
+        .iter()
+        .find(|backend| &backend.id == id)
+        .cloned()
+        .ok_or_else(|| CommandError::from(format!("Remote backend `{id}` is not saved.")))
+}
+
+async fn sync_settings(state: &AppState, app: &AppHandle) -> CommandResult<SettingsSyncReport> {
+    let settings = state.app_settings.read().await.clone();
+    let source = sync_source(&settings)?;
+    let client = remote_backend::connect_to_target(&source, &settings).await?;
+    let response = client
+        .call(SETTINGS_SYNC_SNAPSHOT_METHOD, json!({}))
+        .await?;
+    drop(client);
+    let snapshot: SettingsSyncSnapshot =
+        serde_json::from_value(response).map_err(|err| CommandError::from(err.to_string()))?;
+
+    let mut report = SettingsSyncReport {
+        source: source.name,
+        settings_applied: false,
+        templates_updated: 0,
+        projects_added: Vec::new(),
+        synced_at_ms: now_ms(),
+    };
+    if let Some(merged) = merge_synced_settings(&settings, &snapshot)? {
+        let updated =
+            update_app_settings_core(merged, &state.app_settings, &state.settings_path).await?;
+        let _ = app.emit(SETTINGS_SYNCED_EVENT, &updated);
+        report.settings_applied = true;
+    }
+
+    // In remote mode templates and projects live on the daemon, not here.
+    if remote_backend::is_remote_mode(state).await {
+        return Ok(report);
+    }
+    let data_dir = state.data_dir().to_path_buf();
+    let templates = snapshot.templates;
+    report.templates_updated =
+        tokio::task::spawn_blocking(move || apply_synced_templates(&data_dir, templates))
+            .await
+            .map_err(|err| err.to_string())??;
+    let missing = projects_to_add(&*state.workspaces.lock().await, &snapshot.projects);
+    for path in missing {
+        let added =
+            crate::workspaces::add_workspace(path.clone(), app.state::<AppState>(), app.clone())
+                .await;
+        if added.is_ok() {
+            report.projects_added.push(path);
+        }
+    }
+    Ok(report)
+}
+
+/// Pulls shared settings, session templates and projects from the remote
+/// backend chosen for settings sync. Settings follow whichever side changed
+/// them last; templates are merged one by one the same way; projects are
+/// only added, and only when the same folder exists here. Nothing is ever
+/// deleted.
+#[tauri::command]
+pub(crate) async fn sync_settings_now(
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> CommandResult<SettingsSyncReport> {
+    sync_settings(&state, &app).await
+}
+
+/// Pulls once at startup when settings sync is on. Listeners of
+/// `settings-synced` pick up settings that change.
+pub(crate) fn spawn_startup_settings_sync(app: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        let state = app.state::<AppState>();
+        if !state.app_settings.read().await.settings_sync.enabled {
+            return;
+        }
+        if let Err(error) = sync_settings(&state, &app).await {
+            eprintln!("Settings sync failed: {}", error.message);
+        }
+    });
+}
@@ -131,7 +131,7 @@ fn extract_requires_openai_auth(value: &Value) -> Option<bool> {
         .and_then(|value| value.as_bool())
 }
 
-fn decode_jwt_payload(token: &str) -> Option<Value> {
+pub(crate) fn decode_jwt_payload(token: &str) -> Option<Value> {
     let payload = token.split('.').nth(1)?;
     let decoded = base64::engine::general_purpose::URL_SAFE_NO_PAD
         .decode(payload.as_bytes())
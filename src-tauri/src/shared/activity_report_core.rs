@@ -0,0 +1,376 @@
+use std::collections::{BTreeMap, HashMap};
+use std::fs::File;
+use std::io::{BufRead, BufReader};
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use chrono::{Datelike, Local, NaiveDate, TimeZone};
+use serde_json::Value;
+use tokio::sync::Mutex;
+
+use crate::shared::local_usage_core::{
+    day_dir_for_key, extract_cwd, local_usage_snapshot_core, make_day_keys, resolve_sessions_roots,
+};
+use crate::shared::scheduler_core::list_schedules_core;
+use crate::shared::webhooks_core::format_duration;
+use crate::types::{
+    ActivityProject, ActivityReport, ActivityReportFormat, ActivityReportRange, WorkspaceEntry,
+};
+
+const REPORTS_DIR: &str = "reports";
+/// A rollout starts with its `session_meta`; files that do not are skipped.
+const META_SCAN_LINES: usize = 5;
+
+fn range_days(range: ActivityReportRange) -> u32 {
+    match range {
+        ActivityReportRange::Week => 7,
+        ActivityReportRange::Month => 30,
+    }
+}
+
+fn now_ms() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_millis() as i64)
+        .unwrap_or(0)
+}
+
+/// Local midnight at the start of `day_key`.
+fn day_start_ms(day_key: &str) -> Option<i64> {
+    let day = NaiveDate::parse_from_str(day_key, "%Y-%m-%d").ok()?;
+    Local
+        .from_local_datetime(&day.and_hms_opt(0, 0, 0)?)
+        .earliest()
+        .map(|start| start.timestamp_millis())
+}
+
+fn session_cwd(path: &Path) -> Option<String> {
+    let reader = BufReader::new(File::open(path).ok()?);
+    reader
+        .lines()
+        .take(META_SCAN_LINES)
+        .map_while(Result::ok)
+        .filter_map(|line| serde_json::from_str::<Value>(&line).ok())
+        .find(|value| value.get("type").and_then(Value::as_str) == Some("session_meta"))
+        .and_then(|value| extract_cwd(&value))
+}
+
+/// Working directories of the sessions started on `day_keys`; `None` for
+/// sessions that do not record one.
+fn scan_session_cwds(roots: &[PathBuf], day_keys: &[String]) -> Vec<Option<String>> {
+    let mut cwds = Vec::new();
+    for root in roots {
+        for day_key in day_keys {
+            let Ok(entries) = std::fs::read_dir(day_dir_for_key(root, day_key)) else {
+                continue;
+            };
+            for entry in entries.flatten() {
+                let path = entry.path();
+                if path.extension().and_then(|ext| ext.to_str()) == Some("jsonl") {
+                    cwds.push(session_cwd(&path));
+                }
+            }
+        }
+    }
+    cwds
+}
+
+/// Groups session directories under the workspace that holds them, or the
+/// directory itself when no workspace does.
+fn group_projects(
+    workspaces: &HashMap<String, WorkspaceEntry>,
+    cwds: &[Option<String>],
+) -> Vec<ActivityProject> {
+    let mut projects: BTreeMap<String, ActivityProject> = BTreeMap::new();
+    for cwd in cwds.iter().flatten() {
+        let cwd_path = Path::new(cwd);
+        let (name, path) = workspaces
+            .values()
+            .filter(|entry| cwd_path.starts_with(&entry.path))
+            .max_by_key(|entry| entry.path.len())
+            .map(|entry| (entry.name.clone(), entry.path.clone()))
+            .unwrap_or_else(|| {
+                let name = cwd_path
+                    .file_name()
+                    .map(|name| name.to_string_lossy().to_string())
+                    .unwrap_or_else(|| cwd.clone());
+                (name, cwd.clone())
+            });
+        projects
+            .entry(path.clone())
+            .or_insert(ActivityProject {
+                name,
+                path,
+                sessions: 0,
+            })
+            .sessions += 1;
+    }
+    let mut projects: Vec<ActivityProject> = projects.into_values().collect();
+    projects.sort_by(|a, b| {
+        b.sessions
+            .cmp(&a.sessions)
+            .then_with(|| a.name.cmp(&b.name))
+    });
+    projects
+}
+
+/// Builds the report from the Codex session logs and the schedule history;
+/// skipped scheduled runs are not counted. Agent time counts the stretches in which the agent was producing output,
+/// as in the usage view.
+pub(crate) async fn activity_report_core(
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    data_dir: &Path,
+    range: ActivityReportRange,
+) -> Result<ActivityReport, String> {
+    let days = range_days(range);
+    let usage = local_usage_snapshot_core(workspaces, Some(days), None).await?;
+    let workspaces = workspaces.lock().await.clone();
+    let day_keys = make_day_keys(days);
+    let since_ms = day_keys
+        .first()
+        .and_then(|day_key| day_start_ms(day_key))
+        .unwrap_or(0);
+    let data_dir = data_dir.to_path_buf();
+    let scan_keys = day_keys.clone();
+    let (sessions, projects, runs) = tokio::task::spawn_blocking(move || {
+        let roots = resolve_sessions_roots(&workspaces, None);
+        let cwds = scan_session_cwds(&roots, &scan_keys);
+        let runs: Vec<String> = list_schedules_core(&data_dir)?
+            .into_iter()
+            .flat_map(|schedule| schedule.runs)
+            .filter(|run| run.at_ms >= since_ms && run.status != "skipped")
+            .map(|run| run.status)
+            .collect();
+        Ok::<_, String>((cwds.len(), group_projects(&workspaces, &cwds), runs))
+    })
+    .await
+    .map_err(|err| err.to_string())??;
+
+    let sum = |field: fn(&crate::types::LocalUsageDay) -> i64| usage.days.iter().map(field).sum();
+    Ok(ActivityReport {
+        range,
+        from_day: day_keys.first().cloned().unwrap_or_default(),
+        to_day: day_keys.last().cloned().unwrap_or_default(),
+        generated_at_ms: now_ms(),
+        sessions,
+        agent_time_ms: sum(|day| day.agent_time_ms),
+        agent_runs: sum(|day| day.agent_runs),
+        input_tokens: sum(|day| day.input_tokens),
+        cached_input_tokens: sum(|day| day.cached_input_tokens),
+        output_tokens: sum(|day| day.output_tokens),
+        total_tokens: sum(|day| day.total_tokens),
+        projects,
+        scheduled_runs: runs.len(),
+        failed_scheduled_runs: runs.iter().filter(|status| *status == "failed").count(),
+        days: usage.days,
+    })
+}
+
+/// `1234567` as `1,234,567`.
+fn with_separators(value: i64) -> String {
+    let digits = value.unsigned_abs().to_string();
+    let mut grouped = String::new();
+    for (index, digit) in digits.chars().enumerate() {
+        if index > 0 && (digits.len() - index).is_multiple_of(3) {
+            grouped.push(',');
+        }
+        grouped.push(digit);
+    }
+    if value < 0 {
+        format!("-{grouped}")
+    } else {
+        grouped
+    }
+}
+
+pub(crate) fn render_markdown(report: &ActivityReport) -> String {
+    let mut out = format!(
+        "# CodexMonitor activity, {} to {}\n\n",
+        report.from_day, report.to_day
+    );
+    out.push_str(&format!("- Sessions: {}\n", report.sessions));
+    out.push_str(&format!(
+        "- Agent time: {} over {} runs\n",
+        format_duration(report.agent_time_ms),
+        report.agent_runs
+    ));
+    out.push_str(&format!(
+        "- Tokens: {} ({} input, {} of them cached, {} output)\n",
+        with_separators(report.total_tokens),
+        with_separators(report.input_tokens),
+        with_separators(report.cached_input_tokens),
+        with_separators(report.output_tokens)
+    ));
+    out.push_str(&format!(
+        "- Scheduled runs: {} ({} failed)\n",
+        report.scheduled_runs, report.failed_scheduled_runs
+    ));
+
+    out.push_str("\n## Projects\n\n");
+    if report.projects.is_empty() {
+        out.push_str("No sessions in this period.\n");
+    } else {
+        out.push_str("| Project | Path | Sessions |\n| --- | --- | ---: |\n");
+        for project in &report.projects {
+            out.push_str(&format!(
+                "| {} | `{}` | {} |\n",
+                project.name, project.path, project.sessions
+            ));
+        }
+    }
+
+    out.push_str(
+        "\n## Days\n\n| Day | Tokens | Agent time | Runs |\n| --- | ---: | ---: | ---: |\n",
+    );
+    for day in &report.days {
+        out.push_str(&format!(
+            "| {} | {} | {} | {} |\n",
+            day.day,
+            with_separators(day.total_tokens),
+            format_duration(day.agent_time_ms),
+            day.agent_runs
+        ));
+    }
+    out
+}
+
+#[allow(dead_code)]
+pub(crate) fn render_report(
+    report: &ActivityReport,
+    format: ActivityReportFormat,
+) -> Result<String, String> {
+    match format {
+        ActivityReportFormat::Markdown => Ok(render_markdown(report)),
+        ActivityReportFormat::Json => {
+            serde_json::to_string_pretty(report).map_err(|err| err.to_string())
+        }
+    }
+}
+
+/// `activity-week-2026-10-17` for a week ending on that day.
+#[allow(dead_code)]
+pub(crate) fn report_stem(report: &ActivityReport) -> String {
+    let range = match report.range {
+        ActivityReportRange::Week => "week",
+        ActivityReportRange::Month => "month",
+    };
+    format!("activity-{range}-{}", report.to_day)
+}
+
+/// The weekly report's name for the ISO week holding `day`, such as
+/// `activity-2026-W42`, so each week gets one report.
+#[allow(dead_code)]
+pub(crate) fn weekly_report_stem(day: NaiveDate) -> String {
+    let week = day.iso_week();
+    format!("activity-{}-W{:02}", week.year(), week.week())
+}
+
+/// Where a report named `stem` is saved under the data directory.
+#[allow(dead_code)]
+pub(crate) fn report_path(data_dir: &Path, stem: &str, format: ActivityReportFormat) -> PathBuf {
+    let extension = match format {
+        ActivityReportFormat::Markdown => "md",
+        ActivityReportFormat::Json => "json",
+    };
+    data_dir
+        .join(REPORTS_DIR)
+        .join(format!("{stem}.{extension}"))
+}
+
+#[allow(dead_code)]
+pub(crate) fn save_report(path: &Path, content: &str) -> Result<(), String> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent).map_err(|err| err.to_string())?;
+    }
+    std::fs::write(path, content).map_err(|err| format!("Failed to save the report: {err}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{LocalUsageDay, WorkspaceKind, WorkspaceSettings};
+
+    fn workspace(id: &str, path: &str) -> WorkspaceEntry {
+        WorkspaceEntry {
+            id: id.to_string(),
+            name: id.to_string(),
+            path: path.to_string(),
+            kind: WorkspaceKind::Main,
+            parent_id: None,
+            worktree: None,
+            settings: WorkspaceSettings::default(),
+        }
+    }
+
+    #[test]
+    fn sessions_group_under_their_workspace() {
+        let workspaces = HashMap::from([
+            ("api".to_string(), workspace("api", "/src/api")),
+            ("web".to_string(), workspace("web", "/src/web")),
+        ]);
+        let cwds = vec![
+            Some("/src/api".to_string()),
+            Some("/src/api/server".to_string()),
+            Some("/src/web".to_string()),
+            Some("/tmp/scratch".to_string()),
+            None,
+        ];
+        let projects = group_projects(&workspaces, &cwds);
+        let summary: Vec<(&str, usize)> = projects
+            .iter()
+            .map(|project| (project.name.as_str(), project.sessions))
+            .collect();
+        assert_eq!(summary, vec![("api", 2), ("scratch", 1), ("web", 1)]);
+    }
+
+    #[test]
+    fn markdown_lists_totals_projects_and_days() {
+        let report = ActivityReport {
+            range: ActivityReportRange::Week,
+            from_day: "2026-10-11".to_string(),
+            to_day: "2026-10-17".to_string(),
+            generated_at_ms: 0,
+            sessions: 3,
+            agent_time_ms: 200_000,
+            agent_runs: 4,
+            input_tokens: 1_200_000,
+            cached_input_tokens: 300_000,
+            output_tokens: 34_567,
+            total_tokens: 1_234_567,
+            projects: vec![ActivityProject {
+                name: "api".to_string(),
+                path: "/src/api".to_string(),
+                sessions: 3,
+            }],
+            scheduled_runs: 2,
+            failed_scheduled_runs: 1,
+            days: vec![LocalUsageDay {
+                day: "2026-10-17".to_string(),
+                input_tokens: 1_200_000,
+                cached_input_tokens: 300_000,
+                output_tokens: 34_567,
+                total_tokens: 1_234_567,
+                agent_time_ms: 200_000,
+                agent_runs: 4,
+            }],
+        };
+        let markdown = render_markdown(&report);
+        assert!(markdown.starts_with("# CodexMonitor activity, 2026-10-11 to 2026-10-17\n"));
+        assert!(markdown.contains("- Agent time: 3m 20s over 4 runs\n"));
+        assert!(markdown.contains("- Tokens: 1,234,567 (1,200,000 input"));
+        assert!(markdown.contains("- Scheduled runs: 2 (1 failed)\n"));
+        assert!(markdown.contains("| api | `/src/api` | 3 |\n"));
+        assert!(markdown.contains("| 2026-10-17 | 1,234,567 | 3m 20s | 4 |\n"));
+
+        let json = render_report(&report, ActivityReportFormat::Json).expect("json");
+        assert!(json.contains("\"totalTokens\": 1234567"));
+        assert_eq!(report_stem(&report), "activity-week-2026-10-17");
+    }
+
+    #[test]
+    fn weekly_reports_are_named_by_iso_week() {
+        let day = |text: &str| NaiveDate::parse_from_str(text, "%Y-%m-%d").expect("date");
+        assert_eq!(weekly_report_stem(day("2026-10-17")), "activity-2026-W42");
+        assert_eq!(weekly_report_stem(day("2027-01-01")), "activity-2026-W53");
+    }
+}
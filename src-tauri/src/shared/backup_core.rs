@@ -0,0 +1,584 @@
+use std::collections::HashMap;
+use std::ffi::OsString;
+use std::fs::{self, File};
+use std::io::{BufReader, BufWriter, Read, Write};
+use std::num::NonZeroU32;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use ring::aead::{Aad, LessSafeKey, Nonce, UnboundKey, AES_256_GCM, NONCE_LEN};
+use ring::pbkdf2;
+use ring::rand::{SecureRandom, SystemRandom};
+use serde::{Deserialize, Serialize};
+use zip::write::SimpleFileOptions;
+use zip::{CompressionMethod, ZipArchive, ZipWriter};
+
+use crate::storage::{
+    read_guest_tokens, read_schedules, read_session_templates, read_settings, read_workspaces,
+    write_guest_tokens, write_schedules, write_session_templates, write_settings, write_workspaces,
+};
+use crate::types::{
+    AppSettings, BackupReport, BackupRestoreReport, GuestTokenRecord, Schedule, SessionTemplate,
+    WorkspaceEntry,
+};
+
+const BACKUP_KIND: &str = "codex-monitor-backup";
+/// Bumped when the layout changes in a way older versions cannot restore.
+const BACKUP_FORMAT_VERSION: u32 = 1;
+const MANIFEST_ENTRY: &str = "manifest.json";
+const SECRETS_ENTRY: &str = "secrets.bin";
+const SETTINGS_FILE: &str = "settings.json";
+const WORKSPACES_FILE: &str = "workspaces.json";
+const TEMPLATES_FILE: &str = "session-templates.json";
+const SCHEDULES_FILE: &str = "schedules.json";
+const GUEST_TOKENS_FILE: &str = "guest-tokens.json";
+const STATE_FILES: [&str; 5] = [
+    SETTINGS_FILE,
+    WORKSPACES_FILE,
+    TEMPLATES_FILE,
+    SCHEDULES_FILE,
+    GUEST_TOKENS_FILE,
+];
+const SALT_LEN: usize = 16;
+const PBKDF2_ITERATIONS: u32 = 600_000;
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct BackupManifest {
+    kind: String,
+    format_version: u32,
+    app_version: String,
+    created_at_ms: i64,
+    files: Vec<String>,
+    secrets_encrypted: bool,
+}
+
+/// Credentials kept out of the backed-up settings. Guest tokens are stored
+/// hashed, so they are not among them.
+#[derive(Debug, Serialize, Deserialize, Default, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+struct BackupSecrets {
+    remote_backend_token: Option<String>,
+    /// By remote backend id.
+    remote_backend_tokens: HashMap<String, String>,
+    #[serde(default)]
+    remote_backend_identity: Option<String>,
+    /// Daemon identity secrets by remote backend id.
+    #[serde(default)]
+    remote_backend_identities: HashMap<String, String>,
+    /// The proxy URL including its `user:password@` part.
+    proxy_url: Option<String>,
+    /// Signing secrets by webhook id.
+    #[serde(default)]
+    webhook_secrets: HashMap<String, String>,
+}
+
+struct BackupState {
+    settings: AppSettings,
+    workspaces: HashMap<String, WorkspaceEntry>,
+    templates: Vec<SessionTemplate>,
+    schedules: Vec<Schedule>,
+    guest_tokens: Vec<GuestTokenRecord>,
+}
+
+/// A backup that has been read and checked, ready to replace the current
+/// state.
+pub(crate) struct StagedBackup {
+    state: BackupState,
+    report: BackupRestoreReport,
+}
+
+#[allow(dead_code)]
+impl StagedBackup {
+    pub(crate) fn settings(&self) -> &AppSettings {
+        &self.state.settings
+    }
+
+    pub(crate) fn workspaces(&self) -> &HashMap<String, WorkspaceEntry> {
+        &self.state.workspaces
+    }
+
+    pub(crate) fn report(&self) -> &BackupRestoreReport {
+        &self.report
+    }
+}
+
+fn now_ms() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_millis() as i64)
+        .unwrap_or(0)
+}
+
+fn read_state(dir: &Path) -> Result<BackupState, String> {
+    let labeled = |name: &str| {
+        let name = name.to_string();
+        move |err: String| format!("{name}: {err}")
+    };
+    Ok(BackupState {
+        settings: read_settings(&dir.join(SETTINGS_FILE)).map_err(labeled(SETTINGS_FILE))?,
+        workspaces: read_workspaces(&dir.join(WORKSPACES_FILE))
+            .map_err(labeled(WORKSPACES_FILE))?,
+        templates: read_session_templates(&dir.join(TEMPLATES_FILE))
+            .map_err(labeled(TEMPLATES_FILE))?,
+        schedules: read_schedules(&dir.join(SCHEDULES_FILE)).map_err(labeled(SCHEDULES_FILE))?,
+        guest_tokens: read_guest_tokens(&dir.join(GUEST_TOKENS_FILE))
+            .map_err(labeled(GUEST_TOKENS_FILE))?,
+    })
+}
+
+fn write_state(dir: &Path, state: &BackupState) -> Result<(), String> {
+    let workspaces: Vec<WorkspaceEntry> = state.workspaces.values().cloned().collect();
+    write_settings(&dir.join(SETTINGS_FILE), &state.settings)?;
+    write_workspaces(&dir.join(WORKSPACES_FILE), &workspaces)?;
+    write_session_templates(&dir.join(TEMPLATES_FILE), &state.templates)?;
+    write_schedules(&dir.join(SCHEDULES_FILE), &state.schedules)?;
+    write_guest_tokens(&dir.join(GUEST_TOKENS_FILE), &state.guest_tokens)
+}
+
+/// `url` without its `user:password@` part, or `None` when it has none.
+fn without_credentials(url: &str) -> Option<String> {
+    let (scheme, rest) = url.split_once("://")?;
+    let authority_end = rest.find('/').unwrap_or(rest.len());
+    let (authority, path) = rest.split_at(authority_end);
+    let (_, host) = authority.rsplit_once('@')?;
+    Some(format!("{scheme}://{host}{path}"))
+}
+
+/// Removes tokens, daemon identities, proxy credentials and webhook secrets from `settings`
+/// and returns them.
+fn take_secrets(settings: &mut AppSettings) -> BackupSecrets {
+    let mut secrets = BackupSecrets {
+        remote_backend_token: settings.remote_backend_token.take(),
+        remote_backend_identity: settings.remote_backend_identity.take(),
+        ..BackupSecrets::default()
+    };
+    for backend in &mut settings.remote_backends {
+        if let Some(token) = backend.token.take() {
+            secrets
+                .remote_backend_tokens
+                .insert(backend.id.clone(), token);
+        }
+        if let Some(identity) = backend.identity.take() {
+            secrets
+                .remote_backend_identities
+                .insert(backend.id.clone(), identity);
+        }
+    }
+    if let Some(stripped) = settings.proxy.url.as_deref().and_then(without_credentials) {
+        secrets.proxy_url = settings.proxy.url.replace(stripped);
+    }
+    for webhook in &mut settings.webhooks {
+        if let Some(secret) = webhook.secret.take() {
+            secrets.webhook_secrets.insert(webhook.id.clone(), secret);
+        }
+    }
+    secrets
+}
+
+/// Puts back what `take_secrets` removed. Tokens, identities and webhook
+/// secrets go to the entries with the same id, and proxy credentials only
+/// to the same proxy.
+fn restore_secrets(settings: &mut AppSettings, secrets: BackupSecrets) {
+    settings.remote_backend_token = secrets.remote_backend_token;
+    settings.remote_backend_identity = secrets.remote_backend_identity;
+    for backend in &mut settings.remote_backends {
+        backend.token = secrets.remote_backend_tokens.get(&backend.id).cloned();
+        backend.identity = secrets.remote_backend_identities.get(&backend.id).cloned();
+    }
+    for webhook in &mut settings.webhooks {
+        webhook.secret = secrets.webhook_secrets.get(&webhook.id).cloned();
+    }
+    if let Some(url) = secrets.proxy_url {
+        if without_credentials(&url) == settings.proxy.url {
+            settings.proxy.url = Some(url);
+        }
+    }
+}
+
+fn secrets_key(passphrase: &str, salt: &[u8]) -> Result<LessSafeKey, String> {
+    let mut key = [0u8; 32];
+    let iterations = NonZeroU32::new(PBKDF2_ITERATIONS).expect("iterations are nonzero");
+    pbkdf2::derive(
+        pbkdf2::PBKDF2_HMAC_SHA256,
+        iterations,
+        salt,
+        passphrase.as_bytes(),
+        &mut key,
+    );
+    UnboundKey::new(&AES_256_GCM, &key)
+        .map(LessSafeKey::new)
+        .map_err(|_| "Failed to derive the backup key".to_string())
+}
+
+/// Seals `secrets` with AES-256-GCM under a key derived from `passphrase`,
+/// laid out as salt, nonce, then ciphertext.
+fn encrypt_secrets(secrets: &BackupSecrets, passphrase: &str) -> Result<Vec<u8>, String> {
+    let mut header = [0u8; SALT_LEN + NONCE_LEN];
+    SystemRandom::new()
+        .fill(&mut header)
+        .map_err(|_| "Failed to generate a backup key".to_string())?;
+    let (salt, nonce) = header.split_at(SALT_LEN);
+    let key = secrets_key(passphrase, salt)?;
+    let nonce = Nonce::try_assume_unique_for_key(nonce)
+        .map_err(|_| "Failed to generate a backup key".to_string())?;
+    let mut sealed = serde_json::to_vec(secrets).map_err(|err| err.to_string())?;
+    key.seal_in_place_append_tag(nonce, Aad::from(BACKUP_KIND.as_bytes()), &mut sealed)
+        .map_err(|_| "Failed to encrypt backup secrets".to_string())?;
+    Ok([header.as_slice(), &sealed].concat())
+}
+
+fn decrypt_secrets(bytes: &[u8], passphrase: &str) -> Result<BackupSecrets, String> {
+    if bytes.len() < SALT_LEN + NONCE_LEN {
+        return Err("The backup's secrets are damaged.".to_string());
+    }
+    let (salt, rest) = bytes.split_at(SALT_LEN);
+    let (nonce, sealed) = rest.split_at(NONCE_LEN);
+    let key = secrets_key(passphrase, salt)?;
+    let nonce = Nonce::try_assume_unique_for_key(nonce)
+        .map_err(|_| "The backup's secrets are damaged.".to_string())?;
+    let mut sealed = sealed.to_vec();
+    let plain = key
+        .open_in_place(nonce, Aad::from(BACKUP_KIND.as_bytes()), &mut sealed)
+        .map_err(|_| "The passphrase does not unlock this backup's secrets.".to_string())?;
+    serde_json::from_slice(plain).map_err(|err| format!("Invalid backup secrets: {err}"))
+}
+
+fn to_json<T: Serialize>(value: &T) -> Result<Vec<u8>, String> {
+    serde_json::to_vec_pretty(value).map_err(|err| err.to_string())
+}
+
+fn write_archive(path: &Path, entries: &[(&str, Vec<u8>)]) -> Result<(), String> {
+    let mut tmp_path = OsString::from(path.as_os_str());
+    tmp_path.push(".tmp");
+    let tmp_path = PathBuf::from(tmp_path);
+
+    let result = (|| -> Result<(), String> {
+        let file =
+            File::create(&tmp_path).map_err(|err| format!("Failed to create backup: {err}"))?;
+        let mut writer = ZipWriter::new(BufWriter::new(file));
+        let options = SimpleFileOptions::default().compression_method(CompressionMethod::Deflated);
+        for (name, bytes) in entries {
+            writer
+                .start_file(*name, options)
+                .map_err(|err| format!("Failed to write backup: {err}"))?;
+            writer
+                .write_all(bytes)
+                .map_err(|err| format!("Failed to write backup: {err}"))?;
+        }
+        let mut inner = writer
+            .finish()
+            .map_err(|err| format!("Failed to finalize backup: {err}"))?;
+        inner
+            .flush()
+            .map_err(|err| format!("Failed to finalize backup: {err}"))
+    })();
+
+    if let Err(err) = result {
+        let _ = fs::remove_file(&tmp_path);
+        return Err(err);
+    }
+    fs::rename(&tmp_path, path).map_err(|err| format!("Failed to finalize backup: {err}"))
+}
+
+/// Writes settings, workspaces, session templates, schedules and guest
+/// tokens from `data_dir` into a zip at `path`. Secrets are left out unless
+/// a `passphrase` is given to encrypt them with.
+#[allow(dead_code)]
+pub(crate) fn create_backup_core(
+    data_dir: &Path,
+    path: &Path,
+    passphrase: Option<&str>,
+) -> Result<BackupReport, String> {
+    if !path.is_absolute() {
+        return Err("The backup path must be absolute.".to_string());
+    }
+    let mut state = read_state(data_dir)?;
+    let secrets = take_secrets(&mut state.settings);
+    let sealed = passphrase
+        .map(|passphrase| encrypt_secrets(&secrets, passphrase))
+        .transpose()?;
+    let manifest = BackupManifest {
+        kind: BACKUP_KIND.to_string(),
+        format_version: BACKUP_FORMAT_VERSION,
+        app_version: env!("CARGO_PKG_VERSION").to_string(),
+        created_at_ms: now_ms(),
+        files: STATE_FILES.iter().map(ToString::to_string).collect(),
+        secrets_encrypted: sealed.is_some(),
+    };
+
+    let workspaces: Vec<&WorkspaceEntry> = state.workspaces.values().collect();
+    let mut entries = vec![
+        (MANIFEST_ENTRY, to_json(&manifest)?),
+        (SETTINGS_FILE, to_json(&state.settings)?),
+        (WORKSPACES_FILE, to_json(&workspaces)?),
+        (TEMPLATES_FILE, to_json(&state.templates)?),
+        (SCHEDULES_FILE, to_json(&state.schedules)?),
+        (GUEST_TOKENS_FILE, to_json(&state.guest_tokens)?),
+    ];
+    if let Some(sealed) = sealed {
+        entries.push((SECRETS_ENTRY, sealed));
+    }
+    write_archive(path, &entries)?;
+
+    Ok(BackupReport {
+        path: path.to_string_lossy().to_string(),
+        created_at_ms: manifest.created_at_ms,
+        files: manifest.files,
+        secrets_included: manifest.secrets_encrypted,
+    })
+}
+
+fn read_entry<R: Read + std::io::Seek>(
+    archive: &mut ZipArchive<R>,
+    name: &str,
+) -> Result<Vec<u8>, String> {
+    let mut entry = archive
+        .by_name(name)
+        .map_err(|_| format!("The backup is missing {name}."))?;
+    let mut bytes = Vec::new();
+    entry
+        .read_to_end(&mut bytes)
+        .map_err(|err| format!("Failed to read {name} from the backup: {err}"))?;
+    Ok(bytes)
+}
+
+/// Reads the backup at `path` the way the app reads its own files at
+/// startup, failing on anything it could not load. Secrets come from the
+/// backup when a `passphrase` unlocks them and from `current` otherwise;
+/// the data directory location always comes from `current`.
+#[allow(dead_code)]
+pub(crate) fn read_backup(
+    path: &Path,
+    passphrase: Option<&str>,
+    current: &AppSettings,
+) -> Result<StagedBackup, String> {
+    let file = File::open(path).map_err(|err| format!("Failed to open backup: {err}"))?;
+    let mut archive = ZipArchive::new(BufReader::new(file))
+        .map_err(|err| format!("{} is not a backup: {err}", path.display()))?;
+    let manifest: BackupManifest =
+        serde_json::from_slice(&read_entry(&mut archive, MANIFEST_ENTRY)?)
+            .map_err(|err| format!("Invalid backup manifest: {err}"))?;
+    if manifest.kind != BACKUP_KIND {
+        return Err(format!("{} is not a CodexMonitor backup.", path.display()));
+    }
+    if manifest.format_version > BACKUP_FORMAT_VERSION {
+        return Err(format!(
+            "This backup was made by CodexMonitor {} and needs a newer version to restore.",
+            manifest.app_version
+        ));
+    }
+
+    let staging =
+        std::env::temp_dir().join(format!("codex-monitor-restore-{}", uuid::Uuid::new_v4()));
+    let state = fs::create_dir_all(&staging)
+        .map_err(|err| format!("Failed to stage backup: {err}"))
+        .and_then(|_| {
+            for name in STATE_FILES {
+                let bytes = read_entry(&mut archive, name)?;
+                fs::write(staging.join(name), bytes)
+                    .map_err(|err| format!("Failed to stage backup: {err}"))?;
+            }
+            read_state(&staging)
+        });
+    let _ = fs::remove_dir_all(&staging);
+    let mut state = state?;
+
+    let secrets = match passphrase {
+        Some(passphrase) if manifest.secrets_encrypted => Some(decrypt_secrets(
+            &read_entry(&mut archive, SECRETS_ENTRY)?,
+            passphrase,
+        )?),
+        _ => None,
+    };
+    let secrets_restored = secrets.is_some();
+    let secrets = secrets.unwrap_or_else(|| take_secrets(&mut current.clone()));
+    restore_secrets(&mut state.settings, secrets);
+    state.settings.data_dir = current.data_dir.clone();
+
+    Ok(StagedBackup {
+        state,
+        report: BackupRestoreReport {
+            app_version: manifest.app_version,
+            created_at_ms: manifest.created_at_ms,
+            files: STATE_FILES.iter().map(ToString::to_string).collect(),
+            secrets_restored,
+        },
+    })
+}
+
+/// Replaces the state files in `data_dir` with the staged backup.
+#[allow(dead_code)]
+pub(crate) fn apply_backup(data_dir: &Path, staged: &StagedBackup) -> Result<(), String> {
+    write_state(data_dir, &staged.state)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{RemoteBackendProvider, RemoteBackendTarget, WebhookEndpoint};
+
+    fn temp_dir(label: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!(
+            "codex-monitor-backup-{label}-{}",
+            uuid::Uuid::new_v4()
+        ));
+        fs::create_dir_all(&dir).expect("create temp dir");
+        dir
+    }
+
+    fn settings_with_secrets(token: &str) -> AppSettings {
+        let mut settings = AppSettings {
+            theme: "dark".to_string(),
+            remote_backend_token: Some(token.to_string()),
+            ..AppSettings::default()
+        };
+        settings.remote_backends.push(RemoteBackendTarget {
+            id: "mac".to_string(),
+            name: "Mac".to_string(),
+            provider: RemoteBackendProvider::Tcp,
+            host: "mac.tailnet:4732".to_string(),
+            token: Some(format!("{token}-mac")),
+            identity: Some(format!("{token}-mac-identity")),
+            last_connected_at_ms: None,
+        });
+        settings.proxy.url = Some("http://me:pw@proxy.local:8080".to_string());
+        settings.webhooks.push(WebhookEndpoint {
+            id: "slack".to_string(),
+            name: "Slack".to_string(),
+            url: "https://hooks.example.com/slack".to_string(),
+            secret: Some(format!("{token}-hook")),
+            events: Vec::new(),
+            enabled: true,
+        });
+        settings
+    }
+
+    #[test]
+    fn backups_leave_secrets_out_unless_encrypted() {
+        let home = temp_dir("home");
+        write_settings(&home.join(SETTINGS_FILE), &settings_with_secrets("owner"))
+            .expect("write settings");
+        let plain = home.join("plain.zip");
+        let sealed = home.join("sealed.zip");
+
+        let report = create_backup_core(&home, &plain, None).expect("plain backup");
+        assert!(!report.secrets_included);
+        assert_eq!(report.files.len(), STATE_FILES.len());
+        create_backup_core(&home, &sealed, Some("hunter2")).expect("sealed backup");
+
+        let mut archive = ZipArchive::new(File::open(&plain).expect("open")).expect("zip");
+        let settings = String::from_utf8(read_entry(&mut archive, SETTINGS_FILE).expect("entry"))
+            .expect("utf8");
+        assert!(!settings.contains("owner"), "{settings}");
+        assert!(settings.contains("http://proxy.local:8080"), "{settings}");
+        assert!(read_entry(&mut archive, SECRETS_ENTRY).is_err());
+
+        let current = settings_with_secrets("other");
+        let staged = read_backup(&plain, Some("hunter2"), &current).expect("read plain");
+        assert!(!staged.report().secrets_restored);
+        assert_eq!(
+            staged.settings().remote_backend_token.as_deref(),
+            Some("other")
+        );
+
+        let staged = read_backup(&sealed, Some("hunter2"), &current).expect("read sealed");
+        assert!(staged.report().secrets_restored);
+        assert_eq!(
+            staged.settings().remote_backend_token.as_deref(),
+            Some("owner")
+        );
+        assert_eq!(
+            staged.settings().remote_backends[0].token.as_deref(),
+            Some("owner-mac")
+        );
+        assert_eq!(
+            staged.settings().remote_backends[0].identity.as_deref(),
+            Some("owner-mac-identity")
+        );
+        assert_eq!(
+            staged.settings().proxy.url.as_deref(),
+            Some("http://me:pw@proxy.local:8080")
+        );
+        assert_eq!(
+            staged.settings().webhooks[0].secret.as_deref(),
+            Some("owner-hook")
+        );
+        let error = read_backup(&sealed, Some("wrong"), &current)
+            .err()
+            .expect("wrong passphrase");
+        assert!(error.contains("passphrase"), "{error}");
+
+        let _ = fs::remove_dir_all(&home);
+    }
+
+    #[test]
+    fn restoring_replaces_state_but_keeps_the_data_dir() {
+        let source = temp_dir("source");
+        write_settings(&source.join(SETTINGS_FILE), &settings_with_secrets("owner"))
+            .expect("write settings");
+        fs::write(source.join(TEMPLATES_FILE), "[]").expect("templates");
+        let backup = source.join("state.zip");
+        create_backup_core(&source, &backup, None).expect("backup");
+
+        let target = temp_dir("target");
+        fs::write(target.join(SCHEDULES_FILE), "not json").expect("schedules");
+        let current = AppSettings {
+            data_dir: Some(target.to_string_lossy().to_string()),
+            ..AppSettings::default()
+        };
+        let staged = read_backup(&backup, None, &current).expect("read");
+        apply_backup(&target, &staged).expect("apply");
+
+        let restored = read_settings(&target.join(SETTINGS_FILE)).expect("settings");
+        assert_eq!(restored.theme, "dark");
+        assert_eq!(restored.data_dir, current.data_dir);
+        assert!(restored.remote_backend_token.is_none());
+        assert!(read_schedules(&target.join(SCHEDULES_FILE))
+            .expect("schedules")
+            .is_empty());
+
+        let _ = fs::remove_dir_all(&source);
+        let _ = fs::remove_dir_all(&target);
+    }
+
+    #[test]
+    fn restoring_rejects_newer_formats_and_damaged_files() {
+        let dir = temp_dir("reject");
+        let manifest = BackupManifest {
+            kind: BACKUP_KIND.to_string(),
+            format_version: BACKUP_FORMAT_VERSION + 1,
+            app_version: "99.0.0".to_string(),
+            created_at_ms: 0,
+            files: STATE_FILES.iter().map(ToString::to_string).collect(),
+            secrets_encrypted: false,
+        };
+        let newer = dir.join("newer.zip");
+        write_archive(
+            &newer,
+            &[(MANIFEST_ENTRY, to_json(&manifest).expect("json"))],
+        )
+        .expect("write");
+        let error = read_backup(&newer, None, &AppSettings::default())
+            .err()
+            .expect("newer format");
+        assert!(error.contains("99.0.0"), "{error}");
+
+        let damaged = dir.join("damaged.zip");
+        let manifest = BackupManifest {
+            format_version: BACKUP_FORMAT_VERSION,
+            ..manifest
+        };
+        let mut entries = vec![(MANIFEST_ENTRY, to_json(&manifest).expect("json"))];
+        entries.extend(STATE_FILES.iter().map(|name| (*name, b"[]".to_vec())));
+        entries[1].1 = b"{}".to_vec();
+        entries[2].1 = b"{".to_vec();
+        write_archive(&damaged, &entries).expect("write");
+        let error = read_backup(&damaged, None, &AppSettings::default())
+            .err()
+            .expect("damaged file");
+        assert!(error.starts_with(WORKSPACES_FILE), "{error}");
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
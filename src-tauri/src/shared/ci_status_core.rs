@@ -0,0 +1,231 @@
+//! CI status of a project's branch, read from the GitHub check runs of its
+//! latest commit. Answers are kept for a minute so renders and the failure
+//! watcher do not each go to GitHub.
+
+use std::collections::HashMap;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use serde_json::Value;
+use tokio::sync::Mutex;
+
+use crate::git_utils::resolve_git_root;
+use crate::shared::git_core::run_git_command;
+use crate::shared::github_api_core::{api_get, encode_path_segment, origin_github_repo};
+use crate::types::{CiCheckRun, CiState, ProjectCiStatus, WorkspaceEntry};
+
+const CI_STATUS_TTL_MS: i64 = 60_000;
+/// Conclusions of a run that did not pass; `skipped` and `neutral` do not
+/// fail a commit.
+const FAILED_CONCLUSIONS: &[&str] = &[
+    "action_required",
+    "cancelled",
+    "failure",
+    "startup_failure",
+    "timed_out",
+];
+
+/// Last status per project and branch.
+#[derive(Default)]
+pub(crate) struct CiStatusCache {
+    entries: HashMap<(String, String), ProjectCiStatus>,
+}
+
+impl CiStatusCache {
+    fn fresh(&self, project_id: &str, branch: &str, now_ms: i64) -> Option<ProjectCiStatus> {
+        self.entries
+            .get(&(project_id.to_string(), branch.to_string()))
+            .filter(|status| now_ms - status.fetched_at_ms < CI_STATUS_TTL_MS)
+            .cloned()
+    }
+
+    fn store(&mut self, status: ProjectCiStatus) {
+        self.entries
+            .retain(|_, cached| status.fetched_at_ms - cached.fetched_at_ms < CI_STATUS_TTL_MS);
+        self.entries
+            .insert((status.project_id.clone(), status.branch.clone()), status);
+    }
+}
+
+fn now_ms() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_millis() as i64)
+        .unwrap_or(0)
+}
+
+fn is_failed(check: &CiCheckRun) -> bool {
+    check
+        .conclusion
+        .as_deref()
+        .is_some_and(|conclusion| FAILED_CONCLUSIONS.contains(&conclusion))
+}
+
+pub(crate) fn ci_state(checks: &[CiCheckRun]) -> CiState {
+    if checks.is_empty() {
+        CiState::None
+    } else if checks.iter().any(is_failed) {
+        CiState::Failure
+    } else if checks.iter().any(|check| check.status != "completed") {
+        CiState::Pending
+    } else {
+        CiState::Success
+    }
+}
+
+#[allow(dead_code)]
+pub(crate) fn failed_check_names(status: &ProjectCiStatus) -> Vec<String> {
+    status
+        .checks
+        .iter()
+        .filter(|check| is_failed(check))
+        .map(|check| check.name.clone())
+        .collect()
+}
+
+/// The commit and its runs from a `commits/{ref}/check-runs` answer.
+fn parse_check_runs(value: &Value) -> (Option<String>, Vec<CiCheckRun>) {
+    let runs = value["check_runs"].as_array().cloned().unwrap_or_default();
+    let commit = runs
+        .iter()
+        .find_map(|run| run["head_sha"].as_str())
+        .map(str::to_string);
+    let checks = runs
+        .iter()
+        .map(|run| CiCheckRun {
+            name: run["name"].as_str().unwrap_or("unnamed").to_string(),
+            status: run["status"].as_str().unwrap_or("queued").to_string(),
+            conclusion: run["conclusion"].as_str().map(str::to_string),
+            url: run["html_url"].as_str().map(str::to_string),
+        })
+        .collect();
+    (commit, checks)
+}
+
+/// Check runs of the latest commit GitHub has for `branch`, or for the
+/// branch the project's checkout is on.
+pub(crate) async fn project_ci_status_core(
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    cache: &Mutex<CiStatusCache>,
+    project_id: String,
+    branch: Option<String>,
+) -> Result<ProjectCiStatus, String> {
+    let entry = workspaces
+        .lock()
+        .await
+        .get(&project_id)
+        .cloned()
+        .ok_or_else(|| "workspace not found".to_string())?;
+    let repo_root = resolve_git_root(&entry)?;
+    let branch = match branch
+        .map(|branch| branch.trim().to_string())
+        .filter(|branch| !branch.is_empty())
+    {
+        Some(branch) => branch,
+        None => run_git_command(&repo_root, &["rev-parse", "--abbrev-ref", "HEAD"]).await?,
+    };
+    if branch == "HEAD" {
+        return Err("The project is not on a branch".to_string());
+    }
+    if let Some(status) = cache.lock().await.fresh(&project_id, &branch, now_ms()) {
+        return Ok(status);
+    }
+
+    let repo = origin_github_repo(&repo_root).await?;
+    let path = format!(
+        "repos/{repo}/commits/{}/check-runs?per_page=100",
+        encode_path_segment(&branch)
+    );
+    let (commit, checks) = parse_check_runs(&api_get(&repo_root, &path).await?);
+    let status = ProjectCiStatus {
+        project_id,
+        repo,
+        branch,
+        commit,
+        state: ci_state(&checks),
+        checks,
+        fetched_at_ms: now_ms(),
+    };
+    cache.lock().await.store(status.clone());
+    Ok(status)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn check(status: &str, conclusion: Option<&str>) -> CiCheckRun {
+        CiCheckRun {
+            name: "build".to_string(),
+            status: status.to_string(),
+            conclusion: conclusion.map(str::to_string),
+            url: None,
+        }
+    }
+
+    #[test]
+    fn check_runs_roll_up_to_one_state() {
+        assert_eq!(ci_state(&[]), CiState::None);
+        assert_eq!(
+            ci_state(&[
+                check("completed", Some("success")),
+                check("completed", Some("skipped"))
+            ]),
+            CiState::Success
+        );
+        assert_eq!(
+            ci_state(&[
+                check("completed", Some("success")),
+                check("in_progress", None)
+            ]),
+            CiState::Pending
+        );
+        // A failure is known before the other runs finish.
+        assert_eq!(
+            ci_state(&[check("completed", Some("timed_out")), check("queued", None)]),
+            CiState::Failure
+        );
+
+        let (commit, checks) = parse_check_runs(&json!({
+            "total_count": 2,
+            "check_runs": [
+                {
+                    "name": "lint",
+                    "status": "completed",
+                    "conclusion": "failure",
+                    "head_sha": "abc123",
+                    "html_url": "https://github.com/o/r/runs/1"
+                },
+                { "name": "test", "status": "in_progress", "conclusion": null, "head_sha": "abc123" }
+            ]
+        }));
+        assert_eq!(commit.as_deref(), Some("abc123"));
+        assert_eq!(checks.len(), 2);
+        assert_eq!(
+            checks[0].url.as_deref(),
+            Some("https://github.com/o/r/runs/1")
+        );
+        assert_eq!(checks[1].conclusion, None);
+        assert_eq!(parse_check_runs(&json!({})), (None, Vec::new()));
+    }
+
+    #[test]
+    fn cached_statuses_expire() {
+        let status = ProjectCiStatus {
+            project_id: "ws".to_string(),
+            repo: "o/r".to_string(),
+            branch: "main".to_string(),
+            commit: None,
+            state: CiState::Failure,
+            checks: vec![check("completed", Some("failure"))],
+            fetched_at_ms: 1_000,
+        };
+        assert_eq!(failed_check_names(&status), ["build"]);
+
+        let mut cache = CiStatusCache::default();
+        cache.store(status.clone());
+        assert_eq!(cache.fresh("ws", "main", 1_000 + 59_000), Some(status));
+        assert_eq!(cache.fresh("ws", "other", 1_000), None);
+        assert_eq!(cache.fresh("ws", "main", 1_000 + CI_STATUS_TTL_MS), None);
+    }
+}
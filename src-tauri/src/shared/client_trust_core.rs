@@ -0,0 +1,345 @@
+use std::fs::OpenOptions;
+use std::io::{ErrorKind, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex as StdMutex;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use ring::hmac;
+use serde_json::{json, Value};
+
+use crate::storage::{read_client_approvals, write_client_approvals};
+use crate::types::{ClientApproval, ClientApprovalStatus};
+
+const CLIENT_APPROVALS_FILENAME: &str = "client-approvals.json";
+const CLIENT_ID_FILENAME: &str = "client-id";
+const MAX_CLIENT_ID_LEN: usize = 128;
+const MAX_CLIENT_NAME_LEN: usize = 120;
+
+/// Emitted (by the daemon to its clients, and by the app to its frontend)
+/// when a client is waiting for the owner to approve it.
+pub(crate) const CLIENT_APPROVAL_REQUESTED_EVENT: &str = "client-approval-requested";
+
+// Guards read-modify-write cycles of the approvals file.
+static CLIENT_APPROVALS_LOCK: StdMutex<()> = StdMutex::new(());
+
+// The identifier and name this install sends with `auth`.
+static LOCAL_CLIENT: StdMutex<Option<(String, String)>> = StdMutex::new(None);
+
+/// Outcome of checking a token-authenticated client against the store.
+#[allow(dead_code)]
+#[derive(Debug)]
+pub(crate) enum ClientAdmission {
+    Allowed,
+    /// Waiting for the owner, who is told again on every attempt in case no
+    /// desktop app was listening the first time.
+    Pending(ClientApproval),
+    Denied,
+    /// Approval is required but the client sent no identifier.
+    Unidentified,
+}
+
+fn client_approvals_path(data_dir: &Path) -> PathBuf {
+    data_dir.join(CLIENT_APPROVALS_FILENAME)
+}
+
+fn client_id_path(data_dir: &Path) -> PathBuf {
+    data_dir.join(CLIENT_ID_FILENAME)
+}
+
+fn now_ms() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_millis() as i64)
+        .unwrap_or(0)
+}
+
+fn read_client_id(data_dir: &Path) -> Option<String> {
+    std::fs::read_to_string(client_id_path(data_dir))
+        .ok()
+        .map(|value| value.trim().to_string())
+        .filter(|value| !value.is_empty())
+}
+
+/// Returns this install's client identifier, generating it on first use.
+/// A daemon sharing the data directory treats it as already approved.
+#[allow(dead_code)]
+pub(crate) fn ensure_client_id(data_dir: &Path) -> Result<String, String> {
+    if let Some(id) = read_client_id(data_dir) {
+        return Ok(id);
+    }
+    std::fs::create_dir_all(data_dir).map_err(|err| err.to_string())?;
+    let id = uuid::Uuid::new_v4().to_string();
+    match OpenOptions::new()
+        .write(true)
+        .create_new(true)
+        .open(client_id_path(data_dir))
+    {
+        Ok(mut file) => {
+            file.write_all(id.as_bytes())
+                .map_err(|err| err.to_string())?;
+            Ok(id)
+        }
+        Err(err) if err.kind() == ErrorKind::AlreadyExists => {
+            read_client_id(data_dir).ok_or_else(|| "client id file is empty".to_string())
+        }
+        Err(err) => Err(err.to_string()),
+    }
+}
+
+/// Whether `client_id` is the install whose data directory is `data_dir`,
+/// such as the app that spawned a daemon sharing it.
+#[allow(dead_code)]
+pub(crate) fn is_host_client(data_dir: &Path, client_id: &str) -> bool {
+    read_client_id(data_dir).as_deref() == Some(client_id)
+}
+
+/// A label for this machine shown in the approval prompt on the daemon side.
+fn local_client_name() -> String {
+    let host = ["COMPUTERNAME", "HOSTNAME"]
+        .iter()
+        .filter_map(|key| std::env::var(key).ok())
+        .map(|value| value.trim().to_string())
+        .find(|value| !value.is_empty());
+    match host {
+        Some(host) => format!("{host} ({})", std::env::consts::OS),
+        None => format!("CodexMonitor ({})", std::env::consts::OS),
+    }
+}
+
+/// Loads the identifier the app sends with `auth`. Called once when the app
+/// state loads.
+#[allow(dead_code)]
+pub(crate) fn set_local_client(data_dir: &Path) {
+    let Ok(id) = ensure_client_id(data_dir) else {
+        return;
+    };
+    if let Ok(mut current) = LOCAL_CLIENT.lock() {
+        *current = Some((id, local_client_name()));
+    }
+}
+
+/// Params for an `auth` call, identifying this install when it has an id.
+#[allow(dead_code)]
+pub(crate) fn client_auth_params(token: &str) -> Value {
+    let mut params = json!({ "token": token });
+    if let Some((id, name)) = LOCAL_CLIENT.lock().ok().and_then(|current| current.clone()) {
+        params["clientId"] = Value::String(id);
+        params["clientName"] = Value::String(name);
+    }
+    params
+}
+
+/// Whether `provided` is the owner token. Both sides are reduced to HMAC
+/// tags compared in constant time, so neither the position of the first
+/// wrong byte nor the token's length shows in how long a rejection takes.
+#[allow(dead_code)]
+pub(crate) fn owner_token_matches(owner_token: &str, provided: &str) -> bool {
+    let key = hmac::Key::new(hmac::HMAC_SHA256, owner_token.as_bytes());
+    let expected = hmac::sign(&key, owner_token.as_bytes());
+    hmac::verify(&key, provided.as_bytes(), expected.as_ref()).is_ok()
+}
+
+fn normalize_client_id(client_id: Option<&str>) -> Option<String> {
+    client_id
+        .map(str::trim)
+        .filter(|value| {
+            !value.is_empty()
+                && value.len() <= MAX_CLIENT_ID_LEN
+                && !value.chars().any(char::is_control)
+        })
+        .map(str::to_string)
+}
+
+fn normalize_client_name(client_name: Option<&str>) -> Option<String> {
+    client_name
+        .map(|value| {
+            value
+                .chars()
+                .filter(|ch| !ch.is_control())
+                .take(MAX_CLIENT_NAME_LEN)
+                .collect::<String>()
+                .trim()
+                .to_string()
+        })
+        .filter(|value| !value.is_empty())
+}
+
+/// Decides whether a client that presented a valid token may proceed.
+/// Denied clients stay blocked even after approval is switched off; unknown
+/// clients are only recorded while approval is required.
+#[allow(dead_code)]
+pub(crate) fn admit_client(
+    data_dir: &Path,
+    client_id: Option<&str>,
+    client_name: Option<&str>,
+    require_approval: bool,
+) -> Result<ClientAdmission, String> {
+    let Some(client_id) = normalize_client_id(client_id) else {
+        return Ok(if require_approval {
+            ClientAdmission::Unidentified
+        } else {
+            ClientAdmission::Allowed
+        });
+    };
+    if is_host_client(data_dir, &client_id) {
+        return Ok(ClientAdmission::Allowed);
+    }
+
+    let _guard = CLIENT_APPROVALS_LOCK
+        .lock()
+        .map_err(|err| err.to_string())?;
+    let path = client_approvals_path(data_dir);
+    let mut approvals = read_client_approvals(&path)?;
+    let now = now_ms();
+    let client_name = normalize_client_name(client_name);
+    let admission = match approvals
+        .iter_mut()
+        .find(|approval| approval.client_id == client_id)
+    {
+        Some(approval) => {
+            approval.last_seen_at_ms = now;
+            if client_name.is_some() {
+                approval.client_name = client_name;
+            }
+            match approval.status {
+                ClientApprovalStatus::Denied => ClientAdmission::Denied,
+                ClientApprovalStatus::Pending if require_approval => {
+                    ClientAdmission::Pending(approval.clone())
+                }
+                ClientApprovalStatus::Pending | ClientApprovalStatus::Approved => {
+                    ClientAdmission::Allowed
+                }
+            }
+        }
+        None if require_approval => {
+            let approval = ClientApproval {
+                client_id,
+                client_name,
+                status: ClientApprovalStatus::Pending,
+                first_seen_at_ms: now,
+                last_seen_at_ms: now,
+                decided_at_ms: None,
+            };
+            approvals.push(approval.clone());
+            ClientAdmission::Pending(approval)
+        }
+        None => return Ok(ClientAdmission::Allowed),
+    };
+    write_client_approvals(&path, &approvals)?;
+    Ok(admission)
+}
+
+/// Lists known clients, pending ones first, then most recently seen.
+pub(crate) fn list_client_approvals_core(data_dir: &Path) -> Result<Vec<ClientApproval>, String> {
+    let _guard = CLIENT_APPROVALS_LOCK
+        .lock()
+        .map_err(|err| err.to_string())?;
+    let mut approvals = read_client_approvals(&client_approvals_path(data_dir))?;
+    approvals.sort_by_key(|approval| {
+        (
+            approval.status != ClientApprovalStatus::Pending,
+            std::cmp::Reverse(approval.last_seen_at_ms),
+        )
+    });
+    Ok(approvals)
+}
+
+pub(crate) fn decide_client_approval_core(
+    data_dir: &Path,
+    client_id: &str,
+    approved: bool,
+) -> Result<ClientApproval, String> {
+    let _guard = CLIENT_APPROVALS_LOCK
+        .lock()
+        .map_err(|err| err.to_string())?;
+    let path = client_approvals_path(data_dir);
+    let mut approvals = read_client_approvals(&path)?;
+    let approval = approvals
+        .iter_mut()
+        .find(|approval| approval.client_id == client_id)
+        .ok_or_else(|| "client not found".to_string())?;
+    approval.status = if approved {
+        ClientApprovalStatus::Approved
+    } else {
+        ClientApprovalStatus::Denied
+    };
+    approval.decided_at_ms = Some(now_ms());
+    let decided = approval.clone();
+    write_client_approvals(&path, &approvals)?;
+    Ok(decided)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_dir() -> PathBuf {
+        std::env::temp_dir().join(format!("codex-monitor-clients-{}", uuid::Uuid::new_v4()))
+    }
+
+    #[test]
+    fn unknown_clients_wait_for_approval_and_denials_stick() {
+        let dir = temp_dir();
+        assert!(matches!(
+            admit_client(&dir, Some("phone"), Some("Phone"), false),
+            Ok(ClientAdmission::Allowed)
+        ));
+        assert!(list_client_approvals_core(&dir).expect("list").is_empty());
+
+        let Ok(ClientAdmission::Pending(approval)) =
+            admit_client(&dir, Some("phone"), Some("Phone"), true)
+        else {
+            panic!("new client should be pending");
+        };
+        assert_eq!(approval.client_name.as_deref(), Some("Phone"));
+        let Ok(ClientAdmission::Pending(approval)) = admit_client(&dir, Some("phone"), None, true)
+        else {
+            panic!("client should still be pending");
+        };
+        assert_eq!(approval.client_name.as_deref(), Some("Phone"));
+        assert!(matches!(
+            admit_client(&dir, None, None, true),
+            Ok(ClientAdmission::Unidentified)
+        ));
+
+        decide_client_approval_core(&dir, "phone", true).expect("approve");
+        assert!(matches!(
+            admit_client(&dir, Some("phone"), None, true),
+            Ok(ClientAdmission::Allowed)
+        ));
+
+        decide_client_approval_core(&dir, "phone", false).expect("deny");
+        assert!(matches!(
+            admit_client(&dir, Some("phone"), None, false),
+            Ok(ClientAdmission::Denied)
+        ));
+        let approvals = list_client_approvals_core(&dir).expect("list");
+        assert_eq!(approvals.len(), 1);
+        assert_eq!(approvals[0].status, ClientApprovalStatus::Denied);
+        assert!(decide_client_approval_core(&dir, "laptop", true).is_err());
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn own_client_id_is_always_allowed() {
+        let dir = temp_dir();
+        let id = ensure_client_id(&dir).expect("client id");
+        assert_eq!(ensure_client_id(&dir).expect("client id"), id);
+        assert!(matches!(
+            admit_client(&dir, Some(&id), None, true),
+            Ok(ClientAdmission::Allowed)
+        ));
+        assert!(list_client_approvals_core(&dir).expect("list").is_empty());
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn owner_token_must_match_exactly() {
+        assert!(owner_token_matches("owner-token", "owner-token"));
+        assert!(!owner_token_matches("owner-token", "owner-tokem"));
+        assert!(!owner_token_matches("owner-token", "owner-token-and-more"));
+        assert!(!owner_token_matches("owner-token", ""));
+    }
+}
@@ -0,0 +1,357 @@
+use std::fs;
+use std::path::Path;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use serde_json::Value;
+use tokio::sync::RwLock;
+use tokio::time::timeout;
+
+use crate::backend::app_server::build_codex_command_with_bin;
+use crate::codex::home::resolve_default_codex_home;
+use crate::shared::account::{decode_jwt_payload, read_auth_account};
+use crate::types::{AppSettings, CodexAuthMethod, CodexAuthSource, CodexAuthStatus};
+
+const AUTH_FILE: &str = "auth.json";
+/// How long before expiry credentials that cannot renew themselves count as
+/// expiring soon.
+const EXPIRY_WARNING_MS: i64 = 3 * 24 * 60 * 60 * 1000;
+const LOGIN_STATUS_TIMEOUT: Duration = Duration::from_secs(10);
+
+pub(crate) fn now_ms() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_millis() as i64)
+        .unwrap_or(0)
+}
+
+fn non_empty_str(value: Option<&Value>) -> Option<&str> {
+    value
+        .and_then(Value::as_str)
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+}
+
+/// The `exp` claim of a JWT, in milliseconds.
+fn jwt_expiry_ms(token: &str) -> Option<i64> {
+    decode_jwt_payload(token)?
+        .get("exp")
+        .and_then(Value::as_i64)
+        .map(|exp| exp * 1000)
+}
+
+fn signed_out(
+    source: CodexAuthSource,
+    codex_home: Option<String>,
+    message: String,
+) -> CodexAuthStatus {
+    CodexAuthStatus {
+        logged_in: false,
+        method: CodexAuthMethod::None,
+        source,
+        codex_home,
+        email: None,
+        plan_type: None,
+        expires_at_ms: None,
+        last_refresh_ms: None,
+        refreshable: false,
+        expiring_soon: false,
+        expired: false,
+        message,
+    }
+}
+
+/// Reads the sign-in state from `auth.json`, or `None` when the file does
+/// not exist.
+fn status_from_auth_file(codex_home: &Path, now_ms: i64) -> Option<CodexAuthStatus> {
+    let home = Some(codex_home.to_string_lossy().to_string());
+    let data = fs::read(codex_home.join(AUTH_FILE)).ok()?;
+    let auth: Value = match serde_json::from_slice(&data) {
+        Ok(value) => value,
+        Err(err) => {
+            return Some(signed_out(
+                CodexAuthSource::AuthFile,
+                home,
+                format!("{AUTH_FILE} could not be read: {err}. Sign in to Codex again."),
+            ))
+        }
+    };
+    let tokens = auth.get("tokens").filter(|tokens| tokens.is_object());
+    let id_token = tokens.and_then(|tokens| non_empty_str(tokens.get("id_token")));
+    let access_token = tokens.and_then(|tokens| non_empty_str(tokens.get("access_token")));
+
+    if id_token.is_none() && access_token.is_none() {
+        if non_empty_str(auth.get("OPENAI_API_KEY")).is_some() {
+            return Some(CodexAuthStatus {
+                logged_in: true,
+                method: CodexAuthMethod::ApiKey,
+                message: "Signed in with an API key.".to_string(),
+                ..signed_out(CodexAuthSource::AuthFile, home, String::new())
+            });
+        }
+        return Some(signed_out(
+            CodexAuthSource::AuthFile,
+            home,
+            "Codex is not signed in.".to_string(),
+        ));
+    }
+
+    let account = read_auth_account(Some(codex_home.to_path_buf()));
+    let expires_at_ms = access_token.or(id_token).and_then(jwt_expiry_ms);
+    let refreshable = tokens
+        .and_then(|tokens| non_empty_str(tokens.get("refresh_token")))
+        .is_some();
+    let last_refresh_ms = non_empty_str(auth.get("last_refresh"))
+        .and_then(|value| chrono::DateTime::parse_from_rfc3339(value).ok())
+        .map(|value| value.timestamp_millis());
+    // A refresh token lets Codex renew an expired access token on its own,
+    // so only credentials without one are reported as running out.
+    let expired = !refreshable && expires_at_ms.is_some_and(|expiry| expiry <= now_ms);
+    let expiring_soon =
+        !refreshable && expires_at_ms.is_some_and(|expiry| expiry - now_ms <= EXPIRY_WARNING_MS);
+    let who = account
+        .as_ref()
+        .and_then(|account| account.email.clone())
+        .map(|email| format!(" as {email}"))
+        .unwrap_or_default();
+    let message = if expired {
+        format!("The ChatGPT sign-in{who} has expired. Sign in to Codex again.")
+    } else if expiring_soon {
+        format!("The ChatGPT sign-in{who} expires soon and cannot renew itself.")
+    } else {
+        format!("Signed in with ChatGPT{who}.")
+    };
+    Some(CodexAuthStatus {
+        logged_in: !expired,
+        method: CodexAuthMethod::Chatgpt,
+        source: CodexAuthSource::AuthFile,
+        codex_home: home,
+        email: account.as_ref().and_then(|account| account.email.clone()),
+        plan_type: account.and_then(|account| account.plan_type),
+        expires_at_ms,
+        last_refresh_ms,
+        refreshable,
+        expiring_soon,
+        expired,
+        message,
+    })
+}
+
+/// Parses `codex login status`, which prints one line such as
+/// "Logged in using ChatGPT" and exits non-zero when signed out.
+fn status_from_login_output(
+    success: bool,
+    output: &str,
+    codex_home: Option<String>,
+) -> CodexAuthStatus {
+    let line = output
+        .lines()
+        .map(str::trim)
+        .find(|line| !line.is_empty())
+        .unwrap_or("")
+        .to_string();
+    let lower = line.to_ascii_lowercase();
+    let method = if !success || lower.starts_with("not logged in") {
+        CodexAuthMethod::None
+    } else if lower.contains("chatgpt") {
+        CodexAuthMethod::Chatgpt
+    } else if lower.contains("api key") {
+        CodexAuthMethod::ApiKey
+    } else {
+        CodexAuthMethod::None
+    };
+    let message = if line.is_empty() {
+        "Codex is not signed in.".to_string()
+    } else {
+        line
+    };
+    CodexAuthStatus {
+        logged_in: method != CodexAuthMethod::None,
+        method,
+        ..signed_out(CodexAuthSource::Cli, codex_home, message)
+    }
+}
+
+async fn status_from_login_command(
+    app_settings: &RwLock<AppSettings>,
+    codex_home: Option<&Path>,
+) -> Result<CodexAuthStatus, String> {
+    let (codex_bin, codex_args) = {
+        let settings = app_settings.read().await;
+        (settings.codex_bin.clone(), settings.codex_args.clone())
+    };
+    let mut command = build_codex_command_with_bin(
+        codex_bin,
+        codex_args.as_deref(),
+        vec!["login".to_string(), "status".to_string()],
+    )?;
+    if let Some(codex_home) = codex_home {
+        command.env("CODEX_HOME", codex_home);
+    }
+    command.stdout(std::process::Stdio::piped());
+    command.stderr(std::process::Stdio::piped());
+    let output = timeout(LOGIN_STATUS_TIMEOUT, command.output())
+        .await
+        .map_err(|_| "Timed out running `codex login status`".to_string())?
+        .map_err(|err| format!("Failed to run `codex login status`: {err}"))?;
+    // The CLI prints its status to stderr.
+    let text = format!(
+        "{}\n{}",
+        String::from_utf8_lossy(&output.stderr),
+        String::from_utf8_lossy(&output.stdout)
+    );
+    Ok(status_from_login_output(
+        output.status.success(),
+        &text,
+        codex_home.map(|home| home.to_string_lossy().to_string()),
+    ))
+}
+
+/// The sign-in recorded in `codex_home`'s `auth.json`, if there is one.
+pub(crate) fn auth_file_status(codex_home: &Path) -> Option<CodexAuthStatus> {
+    status_from_auth_file(codex_home, now_ms())
+}
+
+/// Reads the Codex home's `auth.json`, and asks the CLI when there is none
+/// since credentials may live in the system keyring instead.
+pub(crate) async fn codex_auth_status_core(
+    app_settings: &RwLock<AppSettings>,
+) -> Result<CodexAuthStatus, String> {
+    let codex_home = resolve_default_codex_home();
+    if let Some(home) = codex_home.clone() {
+        let status = tokio::task::spawn_blocking(move || status_from_auth_file(&home, now_ms()))
+            .await
+            .map_err(|err| err.to_string())?;
+        if let Some(status) = status {
+            return Ok(status);
+        }
+    }
+    status_from_login_command(app_settings, codex_home.as_deref()).await
+}
+
+/// The notification body for credentials that are about to run out, or
+/// `None` when there is nothing to warn about.
+pub(crate) fn auth_expiry_warning(status: &CodexAuthStatus, now_ms: i64) -> Option<String> {
+    if !status.expiring_soon {
+        return None;
+    }
+    let expiry_ms = status.expires_at_ms?;
+    let account = status
+        .email
+        .as_deref()
+        .map(|email| format!(" for {email}"))
+        .unwrap_or_default();
+    let when = if expiry_ms <= now_ms {
+        "has expired".to_string()
+    } else {
+        match (expiry_ms - now_ms) / (24 * 60 * 60 * 1000) {
+            0 => "expires within a day".to_string(),
+            1 => "expires in 1 day".to_string(),
+            days => format!("expires in {days} days"),
+        }
+    };
+    Some(format!(
+        "The Codex sign-in{account} {when}. Sign in again so remotely started sessions keep working."
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use base64::Engine;
+    use serde_json::json;
+
+    const DAY_MS: i64 = 24 * 60 * 60 * 1000;
+
+    fn jwt(payload: Value) -> String {
+        let encode = |value: &Value| {
+            base64::engine::general_purpose::URL_SAFE_NO_PAD.encode(value.to_string())
+        };
+        format!(
+            "{}.{}.sig",
+            encode(&json!({ "alg": "none" })),
+            encode(&payload)
+        )
+    }
+
+    fn temp_home(auth: Value) -> std::path::PathBuf {
+        let dir = std::env::temp_dir().join(format!(
+            "codex-monitor-auth-status-{}",
+            uuid::Uuid::new_v4()
+        ));
+        fs::create_dir_all(&dir).expect("create temp dir");
+        fs::write(dir.join(AUTH_FILE), auth.to_string()).expect("write auth.json");
+        dir
+    }
+
+    #[test]
+    fn chatgpt_tokens_report_account_plan_and_expiry() {
+        let id_token = jwt(json!({
+            "email": "dev@example.com",
+            "https://api.openai.com/auth": { "chatgpt_plan_type": "pro" },
+        }));
+        let home = temp_home(json!({
+            "OPENAI_API_KEY": null,
+            "tokens": {
+                "id_token": id_token,
+                "access_token": jwt(json!({ "exp": 10 * DAY_MS / 1000 })),
+                "refresh_token": "",
+            },
+            "last_refresh": "2026-10-01T12:00:00Z",
+        }));
+
+        let status = status_from_auth_file(&home, 8 * DAY_MS).expect("status");
+        assert!(status.logged_in);
+        assert_eq!(status.method, CodexAuthMethod::Chatgpt);
+        assert_eq!(status.email.as_deref(), Some("dev@example.com"));
+        assert_eq!(status.plan_type.as_deref(), Some("pro"));
+        assert_eq!(status.expires_at_ms, Some(10 * DAY_MS));
+        assert!(status.last_refresh_ms.is_some());
+        assert!(!status.refreshable);
+        assert!(status.expiring_soon);
+        assert!(auth_expiry_warning(&status, 8 * DAY_MS)
+            .expect("warning")
+            .contains("dev@example.com expires in 2 days"));
+
+        let expired = status_from_auth_file(&home, 11 * DAY_MS).expect("status");
+        assert!(expired.expired);
+        assert!(!expired.logged_in);
+        assert!(status_from_auth_file(&home, DAY_MS).is_some_and(|status| !status.expiring_soon));
+        let _ = fs::remove_dir_all(&home);
+    }
+
+    #[test]
+    fn refreshable_tokens_and_api_keys_never_warn() {
+        let home = temp_home(json!({
+            "tokens": {
+                "id_token": jwt(json!({ "email": "dev@example.com" })),
+                "access_token": jwt(json!({ "exp": 1 })),
+                "refresh_token": "rt",
+            },
+        }));
+        let status = status_from_auth_file(&home, 30 * DAY_MS).expect("status");
+        assert!(status.logged_in && status.refreshable);
+        assert!(!status.expired && !status.expiring_soon);
+        assert!(auth_expiry_warning(&status, 30 * DAY_MS).is_none());
+        let _ = fs::remove_dir_all(&home);
+
+        let home = temp_home(json!({ "OPENAI_API_KEY": "sk-test", "tokens": null }));
+        let status = status_from_auth_file(&home, 0).expect("status");
+        assert_eq!(status.method, CodexAuthMethod::ApiKey);
+        assert!(status.logged_in);
+        assert_eq!(status.expires_at_ms, None);
+        let _ = fs::remove_dir_all(&home);
+    }
+
+    #[test]
+    fn login_status_output_is_parsed() {
+        let chatgpt = status_from_login_output(true, "\nLogged in using ChatGPT\n", None);
+        assert_eq!(chatgpt.method, CodexAuthMethod::Chatgpt);
+        assert_eq!(chatgpt.source, CodexAuthSource::Cli);
+        let api_key =
+            status_from_login_output(true, "Logged in using an API key - sk-***abcd", None);
+        assert_eq!(api_key.method, CodexAuthMethod::ApiKey);
+        let signed_out = status_from_login_output(false, "Not logged in", None);
+        assert!(!signed_out.logged_in);
+        assert_eq!(signed_out.message, "Not logged in");
+    }
+}
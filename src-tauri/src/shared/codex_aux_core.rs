@@ -4,7 +4,7 @@ use std::collections::HashMap;
 use std::io::ErrorKind;
 use std::sync::Arc;
 use std::time::Duration;
-use tokio::sync::{mpsc, Mutex};
+use tokio::sync::{mpsc, Mutex, RwLock};
 use tokio::time::timeout;
 
 use crate::backend::app_server::{
@@ -287,12 +287,12 @@ pub(crate) fn sanitize_run_worktree_name(value: &str) -> String {
 }
 
 pub(crate) async fn codex_doctor_core(
-    app_settings: &Mutex<AppSettings>,
+    app_settings: &RwLock<AppSettings>,
     codex_bin: Option<String>,
     codex_args: Option<String>,
 ) -> Result<Value, String> {
     let (default_bin, default_args) = {
-        let settings = app_settings.lock().await;
+        let settings = app_settings.read().await;
         (settings.codex_bin.clone(), settings.codex_args.clone())
     };
     let resolved = codex_bin
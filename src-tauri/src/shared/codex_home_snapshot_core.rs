@@ -0,0 +1,460 @@
+use std::fs::{self, File, OpenOptions};
+use std::io::{BufReader, BufWriter, Read, Write};
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+use zip::write::SimpleFileOptions;
+use zip::{CompressionMethod, ZipArchive, ZipWriter};
+
+use crate::codex::home::resolve_default_codex_home;
+use crate::shared::config_changelog_core::record_config_change;
+use crate::types::{CodexHomeRestoreReport, CodexHomeSnapshotInfo, ConfigChangeSource};
+
+const SNAPSHOTS_DIRNAME: &str = "codex-home-snapshots";
+const SNAPSHOT_KIND: &str = "codex-home-snapshot";
+/// Bumped when the layout changes in a way older versions cannot restore.
+const SNAPSHOT_FORMAT_VERSION: u32 = 1;
+const MANIFEST_ENTRY: &str = "manifest.json";
+const CONFIG_FILE: &str = "config.toml";
+const SNAPSHOT_FILES: [&str; 2] = [CONFIG_FILE, "AGENTS.md"];
+/// Credentials, saved only when secrets are not left out.
+const SECRET_FILES: [&str; 1] = ["auth.json"];
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SnapshotManifest {
+    kind: String,
+    format_version: u32,
+    app_version: String,
+    created_at_ms: i64,
+    label: Option<String>,
+    codex_home: String,
+    files: Vec<String>,
+    /// Files covered by the snapshot that did not exist when it was taken,
+    /// so restoring removes them.
+    absent: Vec<String>,
+    secrets_included: bool,
+}
+
+fn now_ms() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_millis() as i64)
+        .unwrap_or(0)
+}
+
+fn snapshots_dir(data_dir: &Path) -> PathBuf {
+    data_dir.join(SNAPSHOTS_DIRNAME)
+}
+
+fn covered_files(include_secrets: bool) -> Vec<&'static str> {
+    let mut files = SNAPSHOT_FILES.to_vec();
+    if include_secrets {
+        files.extend(SECRET_FILES);
+    }
+    files
+}
+
+fn is_secret(name: &str) -> bool {
+    SECRET_FILES.contains(&name)
+}
+
+/// Creates `path` readable by the owner only, since snapshots and auth.json
+/// can hold credentials.
+fn create_private(path: &Path) -> std::io::Result<File> {
+    let mut options = OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+    options.open(path)
+}
+
+fn read_manifest<R: Read + std::io::Seek>(
+    archive: &mut ZipArchive<R>,
+) -> Result<SnapshotManifest, String> {
+    let mut entry = archive
+        .by_name(MANIFEST_ENTRY)
+        .map_err(|_| "Snapshot manifest is missing".to_string())?;
+    let mut raw = String::new();
+    entry
+        .read_to_string(&mut raw)
+        .map_err(|err| format!("Failed to read snapshot manifest: {err}"))?;
+    let manifest: SnapshotManifest =
+        serde_json::from_str(&raw).map_err(|err| format!("Invalid snapshot manifest: {err}"))?;
+    if manifest.kind != SNAPSHOT_KIND {
+        return Err("Not a Codex home snapshot".to_string());
+    }
+    Ok(manifest)
+}
+
+fn snapshot_info(path: &Path) -> Result<CodexHomeSnapshotInfo, String> {
+    let file = File::open(path).map_err(|err| format!("Failed to open snapshot: {err}"))?;
+    let size_bytes = file.metadata().map(|metadata| metadata.len()).unwrap_or(0);
+    let mut archive = ZipArchive::new(BufReader::new(file))
+        .map_err(|err| format!("Failed to read snapshot: {err}"))?;
+    let manifest = read_manifest(&mut archive)?;
+    Ok(CodexHomeSnapshotInfo {
+        id: path
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().to_string())
+            .unwrap_or_default(),
+        label: manifest.label,
+        created_at_ms: manifest.created_at_ms,
+        codex_home: manifest.codex_home,
+        files: manifest.files,
+        secrets_included: manifest.secrets_included,
+        size_bytes,
+    })
+}
+
+fn new_snapshot_path(data_dir: &Path) -> Result<PathBuf, String> {
+    let dir = snapshots_dir(data_dir);
+    fs::create_dir_all(&dir)
+        .map_err(|err| format!("Failed to create snapshot directory: {err}"))?;
+    let stamp = chrono::Local::now().format("%Y%m%d-%H%M%S");
+    let mut path = dir.join(format!("codex-home-{stamp}.zip"));
+    let mut suffix = 1;
+    while path.exists() {
+        path = dir.join(format!("codex-home-{stamp}-{suffix}.zip"));
+        suffix += 1;
+    }
+    Ok(path)
+}
+
+fn write_snapshot(
+    path: &Path,
+    manifest: &SnapshotManifest,
+    entries: &[(&str, Vec<u8>)],
+) -> Result<(), String> {
+    let tmp_path = path.with_extension("zip.tmp");
+    let result = (|| -> Result<(), String> {
+        let file =
+            create_private(&tmp_path).map_err(|err| format!("Failed to create snapshot: {err}"))?;
+        let mut writer = ZipWriter::new(BufWriter::new(file));
+        let options = SimpleFileOptions::default().compression_method(CompressionMethod::Deflated);
+        let manifest = serde_json::to_vec_pretty(manifest).map_err(|err| err.to_string())?;
+        for (name, bytes) in std::iter::once((MANIFEST_ENTRY, &manifest))
+            .chain(entries.iter().map(|(name, bytes)| (*name, bytes)))
+        {
+            writer
+                .start_file(name, options)
+                .map_err(|err| format!("Failed to write snapshot: {err}"))?;
+            writer
+                .write_all(bytes)
+                .map_err(|err| format!("Failed to write snapshot: {err}"))?;
+        }
+        let mut inner = writer
+            .finish()
+            .map_err(|err| format!("Failed to finalize snapshot: {err}"))?;
+        inner
+            .flush()
+            .map_err(|err| format!("Failed to finalize snapshot: {err}"))
+    })();
+    if let Err(err) = result {
+        let _ = fs::remove_file(&tmp_path);
+        return Err(err);
+    }
+    fs::rename(&tmp_path, path).map_err(|err| format!("Failed to finalize snapshot: {err}"))
+}
+
+fn snapshot_inner(
+    data_dir: &Path,
+    codex_home: &Path,
+    label: Option<String>,
+    include_secrets: bool,
+    created_at_ms: i64,
+) -> Result<CodexHomeSnapshotInfo, String> {
+    let mut entries = Vec::new();
+    let mut absent = Vec::new();
+    for name in covered_files(include_secrets) {
+        match fs::read(codex_home.join(name)) {
+            Ok(bytes) => entries.push((name, bytes)),
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => absent.push(name.to_string()),
+            Err(err) => return Err(format!("Failed to read {name}: {err}")),
+        }
+    }
+    let manifest = SnapshotManifest {
+        kind: SNAPSHOT_KIND.to_string(),
+        format_version: SNAPSHOT_FORMAT_VERSION,
+        app_version: env!("CARGO_PKG_VERSION").to_string(),
+        created_at_ms,
+        label: label
+            .map(|label| label.trim().to_string())
+            .filter(|label| !label.is_empty()),
+        codex_home: codex_home.to_string_lossy().to_string(),
+        files: entries.iter().map(|(name, _)| name.to_string()).collect(),
+        absent,
+        secrets_included: include_secrets,
+    };
+    let path = new_snapshot_path(data_dir)?;
+    write_snapshot(&path, &manifest, &entries)?;
+    snapshot_info(&path)
+}
+
+fn list_snapshots_inner(data_dir: &Path) -> Vec<CodexHomeSnapshotInfo> {
+    let Ok(entries) = fs::read_dir(snapshots_dir(data_dir)) else {
+        return Vec::new();
+    };
+    let mut snapshots: Vec<CodexHomeSnapshotInfo> = entries
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| path.extension().and_then(|ext| ext.to_str()) == Some("zip"))
+        .filter_map(|path| snapshot_info(&path).ok())
+        .collect();
+    snapshots.sort_by(|a, b| {
+        b.created_at_ms
+            .cmp(&a.created_at_ms)
+            .then_with(|| b.id.cmp(&a.id))
+    });
+    snapshots
+}
+
+fn resolve_snapshot_path(data_dir: &Path, id: &str) -> Result<PathBuf, String> {
+    let id = id.trim();
+    if id.is_empty()
+        || !id
+            .chars()
+            .all(|ch| ch.is_ascii_alphanumeric() || ch == '-' || ch == '_')
+    {
+        return Err("Invalid snapshot id".to_string());
+    }
+    let path = snapshots_dir(data_dir).join(format!("{id}.zip"));
+    if !path.is_file() {
+        return Err("Snapshot not found".to_string());
+    }
+    Ok(path)
+}
+
+/// Writes `bytes` next to `target` and renames it into place, so a failed
+/// write never leaves a truncated file behind.
+fn replace_file(target: &Path, bytes: &[u8], private: bool) -> Result<(), String> {
+    let name = target
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let tmp_path = target.with_file_name(format!(".{name}.restore"));
+    let result = if private {
+        create_private(&tmp_path).and_then(|mut file| file.write_all(bytes))
+    } else {
+        fs::write(&tmp_path, bytes)
+    };
+    result
+        .and_then(|_| fs::rename(&tmp_path, target))
+        .map_err(|err| {
+            let _ = fs::remove_file(&tmp_path);
+            format!("Failed to restore {name}: {err}")
+        })
+}
+
+/// Puts the files saved in snapshot `id` back into `codex_home` and removes
+/// the ones that did not exist then. Credentials are left alone when the
+/// snapshot did not save them. The current state is snapshotted first, so
+/// the restore can itself be undone.
+fn restore_inner(
+    data_dir: &Path,
+    codex_home: &Path,
+    id: &str,
+    now_ms: i64,
+) -> Result<CodexHomeRestoreReport, String> {
+    let path = resolve_snapshot_path(data_dir, id)?;
+    let file = File::open(&path).map_err(|err| format!("Failed to open snapshot: {err}"))?;
+    let mut archive = ZipArchive::new(BufReader::new(file))
+        .map_err(|err| format!("Failed to read snapshot: {err}"))?;
+    let manifest = read_manifest(&mut archive)?;
+    if manifest.format_version > SNAPSHOT_FORMAT_VERSION {
+        return Err(format!(
+            "This snapshot was made by CodexMonitor {} and needs a newer version to restore",
+            manifest.app_version
+        ));
+    }
+
+    let covered = covered_files(manifest.secrets_included);
+    let mut files = Vec::new();
+    for name in &manifest.files {
+        if !covered.contains(&name.as_str()) {
+            return Err(format!("Snapshot holds an unexpected file {name}"));
+        }
+        let mut entry = archive
+            .by_name(name)
+            .map_err(|_| format!("Snapshot is missing {name}"))?;
+        let mut bytes = Vec::new();
+        entry
+            .read_to_end(&mut bytes)
+            .map_err(|err| format!("Failed to read {name} from the snapshot: {err}"))?;
+        if name == CONFIG_FILE {
+            std::str::from_utf8(&bytes)
+                .map_err(|err| err.to_string())
+                .and_then(|contents| {
+                    contents
+                        .parse::<toml_edit::Document>()
+                        .map_err(|err| err.to_string())
+                })
+                .map_err(|err| format!("The snapshot's {CONFIG_FILE} is not valid TOML: {err}"))?;
+        }
+        files.push((name.clone(), bytes));
+    }
+    let absent: Vec<&String> = manifest
+        .absent
+        .iter()
+        .filter(|name| covered.contains(&name.as_str()))
+        .collect();
+
+    let previous = snapshot_inner(
+        data_dir,
+        codex_home,
+        Some(format!(
+            "Before restoring {}",
+            manifest.label.as_deref().unwrap_or(id.trim())
+        )),
+        manifest.secrets_included,
+        now_ms,
+    )?;
+    fs::create_dir_all(codex_home)
+        .map_err(|err| format!("Failed to create {}: {err}", codex_home.display()))?;
+    let mut report = CodexHomeRestoreReport {
+        codex_home: codex_home.to_string_lossy().to_string(),
+        restored: Vec::new(),
+        removed: Vec::new(),
+        previous,
+    };
+    let config_before = fs::read_to_string(codex_home.join(CONFIG_FILE)).ok();
+    for (name, bytes) in files {
+        replace_file(&codex_home.join(&name), &bytes, is_secret(&name))?;
+        report.restored.push(name);
+    }
+    for name in absent {
+        match fs::remove_file(codex_home.join(name)) {
+            Ok(()) => report.removed.push(name.clone()),
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => {}
+            Err(err) => return Err(format!("Failed to remove {name}: {err}")),
+        }
+    }
+    record_config_change(
+        codex_home,
+        ConfigChangeSource::SnapshotRestore,
+        config_before.as_deref(),
+        fs::read_to_string(codex_home.join(CONFIG_FILE))
+            .ok()
+            .as_deref(),
+    );
+    Ok(report)
+}
+
+fn current_codex_home() -> Result<PathBuf, String> {
+    resolve_default_codex_home().ok_or_else(|| "Unable to resolve the Codex home".to_string())
+}
+
+pub(crate) async fn snapshot_codex_home_core(
+    data_dir: PathBuf,
+    label: Option<String>,
+    exclude_secrets: bool,
+) -> Result<CodexHomeSnapshotInfo, String> {
+    let codex_home = current_codex_home()?;
+    tokio::task::spawn_blocking(move || {
+        snapshot_inner(&data_dir, &codex_home, label, !exclude_secrets, now_ms())
+    })
+    .await
+    .map_err(|err| err.to_string())?
+}
+
+pub(crate) async fn list_codex_home_snapshots_core(
+    data_dir: PathBuf,
+) -> Result<Vec<CodexHomeSnapshotInfo>, String> {
+    tokio::task::spawn_blocking(move || list_snapshots_inner(&data_dir))
+        .await
+        .map_err(|err| err.to_string())
+}
+
+pub(crate) async fn restore_codex_home_snapshot_core(
+    data_dir: PathBuf,
+    id: String,
+) -> Result<CodexHomeRestoreReport, String> {
+    let codex_home = current_codex_home()?;
+    tokio::task::spawn_blocking(move || restore_inner(&data_dir, &codex_home, &id, now_ms()))
+        .await
+        .map_err(|err| err.to_string())?
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_dir(label: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!(
+            "codex-monitor-home-snapshot-{label}-{}",
+            uuid::Uuid::new_v4()
+        ));
+        fs::create_dir_all(&dir).expect("create temp dir");
+        dir
+    }
+
+    #[test]
+    fn restoring_brings_back_config_and_keeps_unsaved_secrets() {
+        let data_dir = temp_dir("data");
+        let home = temp_dir("home");
+        fs::write(home.join(CONFIG_FILE), "model = \"gpt-5\"\n").expect("config");
+        fs::write(home.join("auth.json"), "{\"token\":\"old\"}").expect("auth");
+
+        let snapshot = snapshot_inner(&data_dir, &home, Some(" Baseline ".into()), false, 1)
+            .expect("snapshot");
+        assert_eq!(snapshot.label.as_deref(), Some("Baseline"));
+        assert_eq!(snapshot.files, [CONFIG_FILE]);
+        assert!(!snapshot.secrets_included);
+
+        fs::write(home.join(CONFIG_FILE), "model = \"o3\"\n").expect("config");
+        fs::write(home.join("AGENTS.md"), "Be terse.").expect("agents");
+        fs::write(home.join("auth.json"), "{\"token\":\"new\"}").expect("auth");
+
+        let report = restore_inner(&data_dir, &home, &snapshot.id, 2).expect("restore");
+        assert_eq!(report.restored, [CONFIG_FILE]);
+        assert_eq!(report.removed, ["AGENTS.md"]);
+        assert_eq!(
+            fs::read_to_string(home.join(CONFIG_FILE)).expect("config"),
+            "model = \"gpt-5\"\n"
+        );
+        assert!(!home.join("AGENTS.md").exists());
+        assert_eq!(
+            fs::read_to_string(home.join("auth.json")).expect("auth"),
+            "{\"token\":\"new\"}"
+        );
+        assert_eq!(
+            report.previous.label.as_deref(),
+            Some("Before restoring Baseline")
+        );
+        assert_eq!(report.previous.files, [CONFIG_FILE, "AGENTS.md"]);
+
+        let listed = list_snapshots_inner(&data_dir);
+        assert_eq!(listed.len(), 2);
+        assert_eq!(listed[0].id, report.previous.id);
+
+        let _ = fs::remove_dir_all(&data_dir);
+        let _ = fs::remove_dir_all(&home);
+    }
+
+    #[test]
+    fn restoring_rejects_bad_ids_and_invalid_config() {
+        let data_dir = temp_dir("reject");
+        let home = temp_dir("reject-home");
+        assert_eq!(
+            restore_inner(&data_dir, &home, "../settings", 1).unwrap_err(),
+            "Invalid snapshot id"
+        );
+        assert_eq!(
+            restore_inner(&data_dir, &home, "missing", 1).unwrap_err(),
+            "Snapshot not found"
+        );
+
+        fs::write(home.join(CONFIG_FILE), "model = [").expect("config");
+        let snapshot = snapshot_inner(&data_dir, &home, None, true, 1).expect("snapshot");
+        let error = restore_inner(&data_dir, &home, &snapshot.id, 2).unwrap_err();
+        assert!(error.contains("not valid TOML"), "{error}");
+        assert_eq!(list_snapshots_inner(&data_dir).len(), 1);
+
+        let _ = fs::remove_dir_all(&data_dir);
+        let _ = fs::remove_dir_all(&home);
+    }
+}
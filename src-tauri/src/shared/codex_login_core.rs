@@ -0,0 +1,371 @@
+use std::path::PathBuf;
+use std::process::Stdio;
+use std::sync::Arc;
+use std::time::Duration;
+
+use tokio::io::{AsyncBufReadExt, AsyncRead, BufReader};
+use tokio::process::Child;
+use tokio::sync::{mpsc, oneshot, Mutex, RwLock};
+
+use crate::backend::app_server::build_codex_command_with_bin;
+use crate::backend::events::EventSink;
+use crate::codex::home::resolve_default_codex_home;
+use crate::shared::codex_auth_core::{auth_file_status, now_ms};
+use crate::types::{AppSettings, CodexLoginFlow, CodexLoginFlowState};
+
+/// How long `codex_login_start` waits for the CLI to print the code.
+const CODE_WAIT: Duration = Duration::from_secs(30);
+/// Device codes expire after 15 minutes; give the CLI a little longer to
+/// give up on its own.
+const FLOW_TIMEOUT: Duration = Duration::from_secs(20 * 60);
+
+/// The sign-in `codex_login_start` runs, and how to cancel it while it is in
+/// progress.
+pub(crate) struct CodexCliLogin {
+    flow: CodexLoginFlow,
+    cancel: Option<oneshot::Sender<()>>,
+}
+
+pub(crate) type CodexCliLoginSlot = Arc<Mutex<Option<CodexCliLogin>>>;
+
+fn is_in_progress(state: CodexLoginFlowState) -> bool {
+    matches!(
+        state,
+        CodexLoginFlowState::Starting | CodexLoginFlowState::AwaitingUser
+    )
+}
+
+fn strip_ansi(line: &str) -> String {
+    let mut out = String::with_capacity(line.len());
+    let mut chars = line.chars();
+    while let Some(ch) = chars.next() {
+        if ch != '\u{1b}' {
+            out.push(ch);
+            continue;
+        }
+        // CSI sequences end with a letter; skip everything up to it.
+        if chars.next() == Some('[') {
+            for ch in chars.by_ref() {
+                if ch.is_ascii_alphabetic() {
+                    break;
+                }
+            }
+        }
+    }
+    out
+}
+
+/// Whether `line` is a device code such as `ABCD-1234`.
+fn looks_like_user_code(line: &str) -> bool {
+    let groups: Vec<&str> = line.split('-').collect();
+    groups.len() >= 2
+        && groups.iter().all(|group| {
+            group.len() >= 4
+                && group
+                    .chars()
+                    .all(|ch| ch.is_ascii_uppercase() || ch.is_ascii_digit())
+        })
+}
+
+/// Folds one line of CLI output into `flow`, returning whether it changed.
+fn apply_login_output(flow: &mut CodexLoginFlow, line: &str) -> bool {
+    let line = strip_ansi(line);
+    let line = line.trim();
+    if line.is_empty() {
+        return false;
+    }
+    if flow.verification_url.is_none() {
+        flow.verification_url = line
+            .split_whitespace()
+            .find(|word| word.starts_with("https://"))
+            .map(str::to_string);
+    }
+    if flow.user_code.is_none() && looks_like_user_code(line) {
+        flow.user_code = Some(line.to_string());
+    }
+    if flow.state == CodexLoginFlowState::Starting
+        && flow.verification_url.is_some()
+        && flow.user_code.is_some()
+    {
+        flow.state = CodexLoginFlowState::AwaitingUser;
+    }
+    flow.message = Some(line.to_string());
+    true
+}
+
+async fn forward_lines<R: AsyncRead + Unpin>(stream: R, tx: mpsc::UnboundedSender<String>) {
+    let mut lines = BufReader::new(stream).lines();
+    while let Ok(Some(line)) = lines.next_line().await {
+        if tx.send(line).is_err() {
+            break;
+        }
+    }
+}
+
+async fn update_flow<E: EventSink>(
+    slot: &CodexCliLoginSlot,
+    event_sink: &E,
+    id: &str,
+    update: impl FnOnce(&mut CodexLoginFlow) -> bool,
+) -> Option<CodexLoginFlow> {
+    let mut guard = slot.lock().await;
+    let login = guard.as_mut().filter(|login| login.flow.id == id)?;
+    if update(&mut login.flow) {
+        event_sink.emit_codex_login_progress(login.flow.clone());
+    }
+    Some(login.flow.clone())
+}
+
+async fn drive_login<E: EventSink>(
+    slot: CodexCliLoginSlot,
+    id: String,
+    mut child: Child,
+    mut cancel_rx: oneshot::Receiver<()>,
+    ready: oneshot::Sender<()>,
+    codex_home: Option<PathBuf>,
+    event_sink: E,
+) {
+    let (line_tx, mut line_rx) = mpsc::unbounded_channel();
+    if let Some(stdout) = child.stdout.take() {
+        tokio::spawn(forward_lines(stdout, line_tx.clone()));
+    }
+    if let Some(stderr) = child.stderr.take() {
+        tokio::spawn(forward_lines(stderr, line_tx.clone()));
+    }
+    drop(line_tx);
+
+    let mut ready = Some(ready);
+    let deadline = tokio::time::sleep(FLOW_TIMEOUT);
+    tokio::pin!(deadline);
+    let interrupted = loop {
+        tokio::select! {
+            line = line_rx.recv() => {
+                // Both pipes close when the CLI exits.
+                let Some(line) = line else { break None };
+                let flow = update_flow(&slot, &event_sink, &id, |flow| {
+                    apply_login_output(flow, &line)
+                })
+                .await;
+                if flow.is_some_and(|flow| flow.state == CodexLoginFlowState::AwaitingUser) {
+                    if let Some(ready) = ready.take() {
+                        let _ = ready.send(());
+                    }
+                }
+            }
+            _ = &mut cancel_rx => {
+                break Some((CodexLoginFlowState::Canceled, "Sign-in canceled.".to_string()))
+            }
+            _ = &mut deadline => {
+                break Some((CodexLoginFlowState::Failed, "Sign-in timed out.".to_string()))
+            }
+        }
+    };
+
+    let exited = interrupted.is_none();
+    let (state, message, auth) = match interrupted {
+        Some((state, message)) => {
+            let _ = child.kill().await;
+            (state, Some(message), None)
+        }
+        None => match child.wait().await {
+            Ok(status) if status.success() => {
+                let auth = codex_home.as_deref().and_then(auth_file_status);
+                (
+                    CodexLoginFlowState::Succeeded,
+                    Some("Signed in to Codex.".to_string()),
+                    auth,
+                )
+            }
+            Ok(status) => (
+                CodexLoginFlowState::Failed,
+                Some(format!("`codex login` exited with {status}")),
+                None,
+            ),
+            Err(err) => (
+                CodexLoginFlowState::Failed,
+                Some(format!("Failed to wait for `codex login`: {err}")),
+                None,
+            ),
+        },
+    };
+    update_flow(&slot, &event_sink, &id, |flow| {
+        flow.state = state;
+        flow.finished_at_ms = Some(now_ms());
+        // When the CLI fails on its own, its last line says why.
+        if !(exited && state == CodexLoginFlowState::Failed && flow.message.is_some()) {
+            flow.message = message;
+        }
+        flow.auth = auth;
+        true
+    })
+    .await;
+    if let Some(login) = slot
+        .lock()
+        .await
+        .as_mut()
+        .filter(|login| login.flow.id == id)
+    {
+        login.cancel = None;
+    }
+    if let Some(ready) = ready.take() {
+        let _ = ready.send(());
+    }
+}
+
+/// Starts `codex login --device-auth`, which signs in with a code entered
+/// on any device, so a phone can sign the desktop's Codex in. Returns once
+/// the CLI has printed the verification URL and code, or has exited. A
+/// flow already in progress is returned as is.
+pub(crate) async fn codex_login_start_core<E: EventSink>(
+    slot: &CodexCliLoginSlot,
+    app_settings: &RwLock<AppSettings>,
+    event_sink: E,
+) -> Result<CodexLoginFlow, String> {
+    let (id, ready_rx) = {
+        let mut guard = slot.lock().await;
+        if let Some(login) = guard
+            .as_ref()
+            .filter(|login| is_in_progress(login.flow.state))
+        {
+            return Ok(login.flow.clone());
+        }
+
+        let (codex_bin, codex_args) = {
+            let settings = app_settings.read().await;
+            (settings.codex_bin.clone(), settings.codex_args.clone())
+        };
+        let codex_home = resolve_default_codex_home();
+        let mut command = build_codex_command_with_bin(
+            codex_bin,
+            codex_args.as_deref(),
+            vec!["login".to_string(), "--device-auth".to_string()],
+        )?;
+        if let Some(codex_home) = codex_home.as_ref() {
+            command.env("CODEX_HOME", codex_home);
+        }
+        command
+            .env("NO_COLOR", "1")
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .kill_on_drop(true);
+        let child = command
+            .spawn()
+            .map_err(|err| format!("Failed to start `codex login`: {err}"))?;
+
+        let flow = CodexLoginFlow {
+            id: uuid::Uuid::new_v4().to_string(),
+            state: CodexLoginFlowState::Starting,
+            verification_url: None,
+            user_code: None,
+            started_at_ms: now_ms(),
+            finished_at_ms: None,
+            message: None,
+            auth: None,
+        };
+        let (cancel_tx, cancel_rx) = oneshot::channel();
+        let (ready_tx, ready_rx) = oneshot::channel();
+        *guard = Some(CodexCliLogin {
+            flow: flow.clone(),
+            cancel: Some(cancel_tx),
+        });
+        event_sink.emit_codex_login_progress(flow.clone());
+        tokio::spawn(drive_login(
+            Arc::clone(slot),
+            flow.id.clone(),
+            child,
+            cancel_rx,
+            ready_tx,
+            codex_home,
+            event_sink,
+        ));
+        (flow.id, ready_rx)
+    };
+
+    let _ = tokio::time::timeout(CODE_WAIT, ready_rx).await;
+    let guard = slot.lock().await;
+    guard
+        .as_ref()
+        .filter(|login| login.flow.id == id)
+        .map(|login| login.flow.clone())
+        .ok_or_else(|| "The Codex sign-in was replaced".to_string())
+}
+
+/// The sign-in `codex_login_start` started last, if any.
+pub(crate) async fn codex_login_flow_status_core(
+    slot: &CodexCliLoginSlot,
+) -> Option<CodexLoginFlow> {
+    slot.lock().await.as_ref().map(|login| login.flow.clone())
+}
+
+/// Stops the sign-in in progress. The flow reports `canceled` once the CLI
+/// has been stopped.
+pub(crate) async fn codex_login_flow_cancel_core(
+    slot: &CodexCliLoginSlot,
+) -> Result<CodexLoginFlow, String> {
+    let mut guard = slot.lock().await;
+    let login = guard
+        .as_mut()
+        .filter(|login| is_in_progress(login.flow.state))
+        .ok_or_else(|| "No Codex sign-in is in progress".to_string())?;
+    if let Some(cancel) = login.cancel.take() {
+        let _ = cancel.send(());
+    }
+    Ok(login.flow.clone())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn starting_flow() -> CodexLoginFlow {
+        CodexLoginFlow {
+            id: "flow-1".to_string(),
+            state: CodexLoginFlowState::Starting,
+            verification_url: None,
+            user_code: None,
+            started_at_ms: 0,
+            finished_at_ms: None,
+            message: None,
+            auth: None,
+        }
+    }
+
+    #[test]
+    fn device_auth_output_yields_url_and_code() {
+        let mut flow = starting_flow();
+        let output = [
+            "Welcome to Codex",
+            "",
+            "1. Open this link in your browser and sign in to your account",
+            "   \u{1b}[94mhttps://auth.openai.com/codex/device\u{1b}[0m",
+            "2. Enter this one-time code (expires in 15 minutes)",
+        ];
+        for line in output {
+            apply_login_output(&mut flow, line);
+        }
+        assert_eq!(
+            flow.verification_url.as_deref(),
+            Some("https://auth.openai.com/codex/device")
+        );
+        assert_eq!(flow.state, CodexLoginFlowState::Starting);
+
+        assert!(apply_login_output(
+            &mut flow,
+            "   \u{1b}[1mWXYZ-12AB\u{1b}[0m"
+        ));
+        assert_eq!(flow.user_code.as_deref(), Some("WXYZ-12AB"));
+        assert_eq!(flow.state, CodexLoginFlowState::AwaitingUser);
+        assert!(!apply_login_output(&mut flow, "  "));
+        assert_eq!(flow.message.as_deref(), Some("WXYZ-12AB"));
+    }
+
+    #[test]
+    fn only_dash_separated_upper_case_groups_count_as_codes() {
+        assert!(looks_like_user_code("ABCD-EFGH"));
+        assert!(looks_like_user_code("ABCD-1234-EF56"));
+        assert!(!looks_like_user_code("ABCD"));
+        assert!(!looks_like_user_code("abcd-efgh"));
+        assert!(!looks_like_user_code("one-time code"));
+    }
+}
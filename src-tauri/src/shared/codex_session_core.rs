@@ -0,0 +1,773 @@
+use std::collections::{BTreeMap, HashMap, VecDeque};
+use std::path::{Path, PathBuf};
+use std::process::Stdio;
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use futures_util::future::{select, BoxFuture, Either};
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWriteExt};
+use tokio::process::ChildStdin;
+use tokio::sync::{oneshot, Mutex, RwLock};
+
+use crate::backend::app_server::build_codex_command_with_bin;
+use crate::backend::events::{CodexSessionExit, CodexSessionOutput, CodexSessionQueue, EventSink};
+use crate::shared::pagination_core::paginate;
+use crate::shared::process_core::{
+    forget_process_samples, interrupt_process, kill_child_process_tree, kill_pid_gracefully,
+    request_process_stop, sample_process_stats,
+};
+use crate::shared::process_registry_core::{
+    forget_spawned_process, record_spawned_process, SpawnedProcessKind,
+};
+use crate::shared::remote_command_core::drain_utf8;
+use crate::shared::session_summary_core::SessionActivity;
+use crate::shared::session_templates_core::get_session_template;
+use crate::shared::session_worktree_core::create_session_worktree;
+use crate::shared::tags_core::TagFilter;
+use crate::types::{
+    AppSettings, CodexSessionDetail, CodexSessionInfo, Page, PageResult, ProcessStats,
+    WorkspaceEntry,
+};
+
+const READ_CHUNK_BYTES: usize = 8 * 1024;
+const GRACEFUL_STOP_TIMEOUT: Duration = Duration::from_millis(1200);
+const STATS_SAMPLE_INTERVAL: Duration = Duration::from_secs(5);
+/// Ten minutes of history at the sampling interval.
+const MAX_STATS_SAMPLES: usize = 120;
+const APPROVAL_MODES: [&str; 4] = ["untrusted", "on-failure", "on-request", "never"];
+
+/// A `codex exec` child started by the monitor. The child itself is owned by
+/// the task that waits on it; callers interact through `stdin` and the pid.
+pub(crate) struct SessionRuntime {
+    pub(crate) info: Mutex<CodexSessionInfo>,
+    pub(crate) stdin: Mutex<Option<ChildStdin>>,
+    /// Rolling resource samples, oldest first.
+    pub(crate) stats: Mutex<VecDeque<ProcessStats>>,
+    /// Read from stdout for the summary written when the session ends.
+    activity: Mutex<SessionActivity>,
+    /// Asks the wait task to stop the child; the flag requests a forced kill.
+    terminate: Mutex<Option<oneshot::Sender<bool>>>,
+}
+
+/// Everything needed to spawn a session, resolved up front so queued
+/// sessions start with the settings that were current when requested.
+struct SessionLaunch {
+    codex_bin: Option<String>,
+    codex_args: Option<String>,
+    args: Vec<String>,
+    env: BTreeMap<String, String>,
+    data_dir: PathBuf,
+}
+
+struct QueuedSession {
+    info: CodexSessionInfo,
+    launch: SessionLaunch,
+}
+
+/// Monitor-started sessions plus the queue of requests waiting for a slot
+/// under `max_concurrent_sessions`.
+#[derive(Default)]
+pub(crate) struct SessionRegistry {
+    pub(crate) running: Mutex<HashMap<String, Arc<SessionRuntime>>>,
+    queue: Mutex<VecDeque<QueuedSession>>,
+    /// Last seen `max_concurrent_sessions`; 0 means unlimited.
+    max_concurrent: AtomicU32,
+}
+
+impl SessionRegistry {
+    pub(crate) async fn running_count(&self) -> usize {
+        let runtimes: Vec<Arc<SessionRuntime>> =
+            self.running.lock().await.values().cloned().collect();
+        let mut count = 0;
+        for runtime in runtimes {
+            if runtime.info.lock().await.status == "running" {
+                count += 1;
+            }
+        }
+        count
+    }
+
+    /// No session is running or waiting in the queue.
+    pub(crate) async fn is_idle(&self) -> bool {
+        self.queue.lock().await.is_empty() && self.running_count().await == 0
+    }
+
+    async fn at_capacity(&self) -> bool {
+        let limit = self.max_concurrent.load(Ordering::SeqCst);
+        limit > 0 && self.running_count().await >= limit as usize
+    }
+}
+
+fn now_ms() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_millis() as i64)
+        .unwrap_or(0)
+}
+
+fn normalize_optional(value: Option<String>) -> Option<String> {
+    value
+        .map(|value| value.trim().to_string())
+        .filter(|value| !value.is_empty())
+}
+
+pub(crate) fn build_exec_args(
+    project_path: &str,
+    prompt: &str,
+    model: Option<&str>,
+    approval_mode: Option<&str>,
+) -> Vec<String> {
+    let mut args = vec![
+        "exec".to_string(),
+        "--json".to_string(),
+        "--cd".to_string(),
+        project_path.to_string(),
+    ];
+    if let Some(model) = model {
+        args.push("--model".to_string());
+        args.push(model.to_string());
+    }
+    if let Some(approval_mode) = approval_mode {
+        args.push("-c".to_string());
+        args.push(format!("approval_policy=\"{approval_mode}\""));
+    }
+    // Prompts come from remote clients, links and templates; `--` keeps one
+    // starting with `-` from being read as a flag.
+    args.push("--".to_string());
+    args.push(prompt.to_string());
+    args
+}
+
+async fn pump_session_stream<R, E>(
+    mut reader: R,
+    stream: &'static str,
+    session_id: String,
+    event_sink: E,
+    runtime: Option<Arc<SessionRuntime>>,
+) where
+    R: AsyncRead + Unpin,
+    E: EventSink,
+{
+    let mut pending = Vec::new();
+    let mut buffer = [0u8; READ_CHUNK_BYTES];
+    loop {
+        let read = match reader.read(&mut buffer).await {
+            Ok(0) | Err(_) => break,
+            Ok(read) => read,
+        };
+        pending.extend_from_slice(&buffer[..read]);
+        let chunk = drain_utf8(&mut pending);
+        if chunk.is_empty() {
+            continue;
+        }
+        if let Some(runtime) = &runtime {
+            runtime.activity.lock().await.push_output(&chunk);
+        }
+        event_sink.emit_codex_session_output(CodexSessionOutput {
+            session_id: session_id.clone(),
+            stream: stream.to_string(),
+            data: chunk,
+        });
+    }
+    if !pending.is_empty() {
+        event_sink.emit_codex_session_output(CodexSessionOutput {
+            session_id,
+            stream: stream.to_string(),
+            data: String::from_utf8_lossy(&pending).into_owned(),
+        });
+    }
+}
+
+async fn prune_exited(registry: &SessionRegistry) {
+    let snapshot: Vec<(String, Arc<SessionRuntime>)> = registry
+        .running
+        .lock()
+        .await
+        .iter()
+        .map(|(id, runtime)| (id.clone(), Arc::clone(runtime)))
+        .collect();
+    let mut exited = Vec::new();
+    for (id, runtime) in snapshot {
+        if runtime.info.lock().await.status != "running" {
+            exited.push(id);
+        }
+    }
+    if exited.is_empty() {
+        return;
+    }
+    let mut runtimes = registry.running.lock().await;
+    for id in exited {
+        runtimes.remove(&id);
+    }
+}
+
+fn emit_queue_positions<E: EventSink>(queue: &VecDeque<QueuedSession>, event_sink: &E) {
+    for (index, entry) in queue.iter().enumerate() {
+        event_sink.emit_codex_session_queue(CodexSessionQueue {
+            session_id: entry.info.session_id.clone(),
+            status: "queued".to_string(),
+            position: Some(index + 1),
+            error: None,
+        });
+    }
+}
+
+async fn launch_session<E: EventSink>(
+    registry: &Arc<SessionRegistry>,
+    mut info: CodexSessionInfo,
+    launch: SessionLaunch,
+    event_sink: E,
+) -> Result<CodexSessionInfo, String> {
+    let mut command =
+        build_codex_command_with_bin(launch.codex_bin, launch.codex_args.as_deref(), launch.args)?;
+    command.current_dir(&info.project_path);
+    command.envs(&launch.env);
+    command.stdin(Stdio::piped());
+    command.stdout(Stdio::piped());
+    command.stderr(Stdio::piped());
+    command.kill_on_drop(true);
+
+    let mut child = command
+        .spawn()
+        .map_err(|err| format!("Failed to start codex: {err}"))?;
+
+    info.pid = child.id();
+    if let Some(pid) = info.pid {
+        let _ = record_spawned_process(&launch.data_dir, SpawnedProcessKind::CodexSession, pid);
+    }
+    info.started_at_ms = now_ms();
+    info.status = "running".to_string();
+    let session_id = info.session_id.clone();
+    let runtime = Arc::new(SessionRuntime {
+        info: Mutex::new(info.clone()),
+        stdin: Mutex::new(child.stdin.take()),
+        stats: Mutex::new(VecDeque::new()),
+        activity: Mutex::new(SessionActivity::default()),
+        terminate: Mutex::new(None),
+    });
+
+    prune_exited(registry).await;
+    registry
+        .running
+        .lock()
+        .await
+        .insert(session_id.clone(), Arc::clone(&runtime));
+
+    let stdout_task = child.stdout.take().map(|stdout| {
+        tokio::spawn(pump_session_stream(
+            stdout,
+            "stdout",
+            session_id.clone(),
+            event_sink.clone(),
+            Some(Arc::clone(&runtime)),
+        ))
+    });
+    let stderr_task = child.stderr.take().map(|stderr| {
+        tokio::spawn(pump_session_stream(
+            stderr,
+            "stderr",
+            session_id.clone(),
+            event_sink.clone(),
+            None,
+        ))
+    });
+
+    if let Some(pid) = info.pid {
+        tokio::spawn(sample_session_stats(Arc::clone(&runtime), pid));
+    }
+
+    let registry = Arc::clone(registry);
+    let pid = info.pid;
+    let data_dir = launch.data_dir;
+    let (terminate_tx, terminate_rx) = oneshot::channel::<bool>();
+    *runtime.terminate.lock().await = Some(terminate_tx);
+    tokio::spawn(async move {
+        let terminate_request = {
+            let wait = std::pin::pin!(child.wait());
+            match select(wait, terminate_rx).await {
+                Either::Left((status, _)) => Err(status.ok().and_then(|status| status.code())),
+                Either::Right((Ok(force), _)) => Ok(force),
+                Either::Right((Err(_), wait)) => {
+                    Err(wait.await.ok().and_then(|status| status.code()))
+                }
+            }
+        };
+        let (exit_code, terminated) = match terminate_request {
+            Err(exit_code) => (exit_code, false),
+            Ok(force) => {
+                if !force {
+                    if let Some(pid) = child.id() {
+                        let _ = request_process_stop(pid, false).await;
+                    }
+                    let _ = tokio::time::timeout(GRACEFUL_STOP_TIMEOUT, child.wait()).await;
+                }
+                kill_child_process_tree(&mut child).await;
+                (
+                    child.wait().await.ok().and_then(|status| status.code()),
+                    true,
+                )
+            }
+        };
+        for mut task in [stdout_task, stderr_task].into_iter().flatten() {
+            if !terminated {
+                let _ = task.await;
+            } else if tokio::time::timeout(GRACEFUL_STOP_TIMEOUT, &mut task)
+                .await
+                .is_err()
+            {
+                // Orphaned grandchildren can keep the pipes open.
+                task.abort();
+            }
+        }
+        runtime.stdin.lock().await.take();
+        runtime.terminate.lock().await.take();
+        if let Some(pid) = pid {
+            forget_spawned_process(&data_dir, pid);
+        }
+        let summary = {
+            let mut info = runtime.info.lock().await;
+            info.status = if terminated { "terminated" } else { "exited" }.to_string();
+            info.exit_code = exit_code;
+            let summary = runtime.activity.lock().await.summarize(
+                now_ms() - info.started_at_ms,
+                &info.status,
+                exit_code,
+            );
+            info.summary = Some(summary.clone());
+            summary
+        };
+        event_sink.emit_codex_session_exit(CodexSessionExit {
+            session_id,
+            exit_code,
+            summary: Some(summary),
+        });
+        start_queued_sessions(registry, event_sink).await;
+    });
+
+    Ok(info)
+}
+
+async fn sample_session_stats(runtime: Arc<SessionRuntime>, pid: u32) {
+    loop {
+        if runtime.info.lock().await.status != "running" {
+            break;
+        }
+        if let Ok(sample) = sample_process_stats(pid).await {
+            let mut stats = runtime.stats.lock().await;
+            if stats.len() >= MAX_STATS_SAMPLES {
+                stats.pop_front();
+            }
+            stats.push_back(sample);
+        }
+        tokio::time::sleep(STATS_SAMPLE_INTERVAL).await;
+    }
+    forget_process_samples(pid);
+}
+
+/// Starts queued sessions while there is capacity. Boxed because it is
+/// reached from the exit task that `launch_session` itself spawns.
+fn start_queued_sessions<E: EventSink>(
+    registry: Arc<SessionRegistry>,
+    event_sink: E,
+) -> BoxFuture<'static, ()> {
+    Box::pin(async move {
+        let mut queue = registry.queue.lock().await;
+        let mut changed = false;
+        while !queue.is_empty() && !registry.at_capacity().await {
+            let Some(entry) = queue.pop_front() else {
+                break;
+            };
+            changed = true;
+            let session_id = entry.info.session_id.clone();
+            let result =
+                launch_session(&registry, entry.info, entry.launch, event_sink.clone()).await;
+            event_sink.emit_codex_session_queue(CodexSessionQueue {
+                session_id,
+                status: if result.is_ok() { "started" } else { "failed" }.to_string(),
+                position: None,
+                error: result.err(),
+            });
+        }
+        if changed {
+            emit_queue_positions(&queue, &event_sink);
+        }
+    })
+}
+
+#[derive(Debug, Default)]
+pub(crate) struct StartSessionOptions {
+    pub(crate) project_path: Option<String>,
+    pub(crate) prompt: Option<String>,
+    pub(crate) model: Option<String>,
+    pub(crate) approval_mode: Option<String>,
+    pub(crate) template_id: Option<String>,
+    /// Run in a fresh git worktree under the data dir instead of the checkout.
+    pub(crate) isolate_worktree: bool,
+}
+
+/// Starts a session, or queues it with status `queued` when
+/// `max_concurrent_sessions` are already running.
+pub(crate) async fn start_codex_session_core<E: EventSink>(
+    registry: &Arc<SessionRegistry>,
+    app_settings: &RwLock<AppSettings>,
+    data_dir: &Path,
+    event_sink: E,
+    options: StartSessionOptions,
+) -> Result<CodexSessionInfo, String> {
+    // Explicit options win; template presets fill in whatever was left out.
+    let template = match normalize_optional(options.template_id) {
+        Some(template_id) => Some(get_session_template(data_dir, &template_id)?),
+        None => None,
+    };
+    let project_path = normalize_optional(options.project_path)
+        .or_else(|| template.as_ref().and_then(|t| t.working_dir.clone()))
+        .unwrap_or_default();
+    if project_path.is_empty() || !PathBuf::from(&project_path).is_dir() {
+        return Err("Project path is not a directory".to_string());
+    }
+    let prompt = normalize_optional(options.prompt)
+        .or_else(|| template.as_ref().map(|t| t.prompt.clone()))
+        .unwrap_or_default();
+    if prompt.is_empty() {
+        return Err("Prompt is empty".to_string());
+    }
+    let model = normalize_optional(options.model)
+        .or_else(|| template.as_ref().and_then(|t| t.model.clone()));
+    let approval_mode = normalize_optional(options.approval_mode)
+        .or_else(|| template.as_ref().and_then(|t| t.approval_mode.clone()));
+    if let Some(mode) = approval_mode.as_deref() {
+        if !APPROVAL_MODES.contains(&mode) {
+            return Err(format!("Unsupported approval mode: {mode}"));
+        }
+    }
+
+    let (codex_bin, codex_args, max_concurrent) = {
+        let settings = app_settings.read().await;
+        (
+            settings.codex_bin.clone(),
+            settings.codex_args.clone(),
+            settings.max_concurrent_sessions.unwrap_or(0),
+        )
+    };
+    registry
+        .max_concurrent
+        .store(max_concurrent, Ordering::SeqCst);
+    let session_id = uuid::Uuid::new_v4().to_string();
+    let (project_path, worktree) = if options.isolate_worktree {
+        let (worktree, session_dir) =
+            create_session_worktree(data_dir, &project_path, &session_id).await?;
+        (session_dir, Some(worktree))
+    } else {
+        (project_path, None)
+    };
+    let launch = SessionLaunch {
+        codex_bin,
+        codex_args,
+        args: build_exec_args(
+            &project_path,
+            &prompt,
+            model.as_deref(),
+            approval_mode.as_deref(),
+        ),
+        env: template.map(|template| template.env).unwrap_or_default(),
+        data_dir: data_dir.to_path_buf(),
+    };
+    let info = CodexSessionInfo {
+        session_id,
+        project_path,
+        model,
+        approval_mode,
+        pid: None,
+        started_at_ms: now_ms(),
+        status: "queued".to_string(),
+        exit_code: None,
+        worktree,
+        summary: None,
+    };
+
+    // Holding the queue lock serializes the capacity check with the launch.
+    let mut queue = registry.queue.lock().await;
+    if !queue.is_empty() || registry.at_capacity().await {
+        queue.push_back(QueuedSession {
+            info: info.clone(),
+            launch,
+        });
+        emit_queue_positions(&queue, &event_sink);
+        return Ok(info);
+    }
+    let session_id = info.session_id.clone();
+    let launched = launch_session(registry, info, launch, event_sink.clone()).await;
+    // Sessions that never queued report `started` too, so every session
+    // has one.
+    if launched.is_ok() {
+        event_sink.emit_codex_session_queue(CodexSessionQueue {
+            session_id,
+            status: "started".to_string(),
+            position: None,
+            error: None,
+        });
+    }
+    launched
+}
+
+pub(crate) async fn cancel_queued_session_core<E: EventSink>(
+    registry: &SessionRegistry,
+    event_sink: E,
+    session_id: String,
+) -> Result<(), String> {
+    let mut queue = registry.queue.lock().await;
+    let index = queue
+        .iter()
+        .position(|entry| entry.info.session_id == session_id)
+        .ok_or_else(|| "queued session not found".to_string())?;
+    queue.remove(index);
+    event_sink.emit_codex_session_queue(CodexSessionQueue {
+        session_id,
+        status: "cancelled".to_string(),
+        position: None,
+        error: None,
+    });
+    emit_queue_positions(&queue, &event_sink);
+    Ok(())
+}
+
+/// Whether `session_id` is queued or still running.
+pub(crate) async fn session_is_active(registry: &SessionRegistry, session_id: &str) -> bool {
+    if registry
+        .queue
+        .lock()
+        .await
+        .iter()
+        .any(|entry| entry.info.session_id == session_id)
+    {
+        return true;
+    }
+    let runtime = registry.running.lock().await.get(session_id).cloned();
+    match runtime {
+        Some(runtime) => runtime.info.lock().await.status == "running",
+        None => false,
+    }
+}
+
+/// Queued and Monitor-started sessions, newest first, narrowed to those
+/// carrying every tag in `tags`. Finished sessions stay listed until the
+/// next session starts.
+pub(crate) async fn list_codex_sessions_core(
+    registry: &SessionRegistry,
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    data_dir: &Path,
+    tags: Vec<String>,
+    page: &Page,
+) -> Result<PageResult<CodexSessionInfo>, String> {
+    let tag_filter = TagFilter::load(data_dir, &tags, &*workspaces.lock().await)?;
+    let runtimes: Vec<Arc<SessionRuntime>> =
+        registry.running.lock().await.values().cloned().collect();
+    let mut sessions = Vec::new();
+    for runtime in runtimes {
+        sessions.push(runtime.info.lock().await.clone());
+    }
+    sessions.extend(
+        registry
+            .queue
+            .lock()
+            .await
+            .iter()
+            .map(|entry| entry.info.clone()),
+    );
+    sessions.retain(|info| {
+        tag_filter
+            .as_ref()
+            .is_none_or(|filter| filter.matches(&info.session_id, Some(&info.project_path)))
+    });
+    paginate(sessions, page, |info| {
+        (info.started_at_ms, info.session_id.as_str())
+    })
+}
+
+pub(crate) async fn get_codex_session_core(
+    registry: &SessionRegistry,
+    session_id: String,
+) -> Result<CodexSessionDetail, String> {
+    let runtime = registry.running.lock().await.get(&session_id).cloned();
+    if let Some(runtime) = runtime {
+        let session = runtime.info.lock().await.clone();
+        let resource_stats = runtime.stats.lock().await.iter().cloned().collect();
+        return Ok(CodexSessionDetail {
+            session,
+            resource_stats,
+        });
+    }
+    registry
+        .queue
+        .lock()
+        .await
+        .iter()
+        .find(|entry| entry.info.session_id == session_id)
+        .map(|entry| CodexSessionDetail {
+            session: entry.info.clone(),
+            resource_stats: Vec::new(),
+        })
+        .ok_or_else(|| "session not found".to_string())
+}
+
+async fn running_session(
+    registry: &SessionRegistry,
+    session_id: &str,
+) -> Result<Arc<SessionRuntime>, String> {
+    let runtime = registry
+        .running
+        .lock()
+        .await
+        .get(session_id)
+        .cloned()
+        .ok_or_else(|| "session not found".to_string())?;
+    if runtime.info.lock().await.status != "running" {
+        return Err("Session has already exited".to_string());
+    }
+    Ok(runtime)
+}
+
+pub(crate) async fn send_session_input_core(
+    registry: &SessionRegistry,
+    session_id: String,
+    text: String,
+) -> Result<(), String> {
+    let runtime = running_session(registry, &session_id).await?;
+    let mut line = text;
+    if !line.ends_with('\n') {
+        line.push('\n');
+    }
+    let mut stdin = runtime.stdin.lock().await;
+    let stdin = stdin
+        .as_mut()
+        .ok_or_else(|| "Session input is closed".to_string())?;
+    stdin
+        .write_all(line.as_bytes())
+        .await
+        .map_err(|err| format!("Failed to write session input: {err}"))?;
+    stdin
+        .flush()
+        .await
+        .map_err(|err| format!("Failed to write session input: {err}"))
+}
+
+pub(crate) async fn interrupt_session_core(
+    registry: &SessionRegistry,
+    session_id: String,
+) -> Result<(), String> {
+    let runtime = running_session(registry, &session_id).await?;
+    let pid = runtime
+        .info
+        .lock()
+        .await
+        .pid
+        .ok_or_else(|| "Session process id is unknown".to_string())?;
+    interrupt_process(pid)
+}
+
+/// Stops a session and its process tree. Monitor-started sessions are
+/// killed through their child handle; any other session needs the `pid` the
+/// caller detected it with. Queued sessions are simply dropped.
+pub(crate) async fn terminate_session_core<E: EventSink>(
+    registry: &SessionRegistry,
+    event_sink: E,
+    session_id: String,
+    pid: Option<u32>,
+    force: bool,
+) -> Result<(), String> {
+    let runtime = registry.running.lock().await.get(&session_id).cloned();
+    let Some(runtime) = runtime else {
+        if cancel_queued_session_core(registry, event_sink.clone(), session_id.clone())
+            .await
+            .is_ok()
+        {
+            return Ok(());
+        }
+        let pid = pid.ok_or_else(|| "session not found".to_string())?;
+        kill_pid_gracefully(pid, force).await?;
+        event_sink.emit_codex_session_exit(CodexSessionExit {
+            session_id,
+            exit_code: None,
+            summary: None,
+        });
+        return Ok(());
+    };
+
+    let terminate = runtime.terminate.lock().await.take();
+    let Some(terminate) = terminate else {
+        return Err("Session has already exited".to_string());
+    };
+    let _ = terminate.send(force);
+    // The wait task marks the session and emits the exit event; wait for it
+    // so callers see the final status.
+    for _ in 0..100 {
+        if runtime.info.lock().await.status != "running" {
+            return Ok(());
+        }
+        tokio::time::sleep(Duration::from_millis(50)).await;
+    }
+    Err("Session is still stopping".to_string())
+}
+
+/// Drops queued sessions, asks every Monitor-started session to stop
+/// gracefully and waits until they have all exited. Callers bound the wait
+/// with their own timeout.
+pub(crate) async fn terminate_all_sessions(registry: &SessionRegistry) {
+    // Cleared first so exiting sessions do not promote queued ones.
+    registry.queue.lock().await.clear();
+    let runtimes: Vec<Arc<SessionRuntime>> =
+        registry.running.lock().await.values().cloned().collect();
+    for runtime in &runtimes {
+        if let Some(terminate) = runtime.terminate.lock().await.take() {
+            let _ = terminate.send(false);
+        }
+    }
+    for runtime in runtimes {
+        while runtime.info.lock().await.status == "running" {
+            tokio::time::sleep(Duration::from_millis(50)).await;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn build_exec_args_places_prompt_last_and_quotes_policy() {
+        let args = build_exec_args("/work", "fix tests", Some("gpt-5"), Some("never"));
+        assert_eq!(
+            args,
+            vec![
+                "exec",
+                "--json",
+                "--cd",
+                "/work",
+                "--model",
+                "gpt-5",
+                "-c",
+                "approval_policy=\"never\"",
+                "--",
+                "fix tests",
+            ]
+        );
+
+        let minimal = build_exec_args("/work", "hi", None, None);
+        assert_eq!(minimal, vec!["exec", "--json", "--cd", "/work", "--", "hi"]);
+    }
+
+    #[test]
+    fn build_exec_args_keeps_dash_prompts_positional() {
+        let args = build_exec_args(
+            "/work",
+            "--dangerously-bypass-approvals-and-sandbox",
+            None,
+            None,
+        );
+        assert_eq!(
+            args[args.len() - 2..],
+            ["--", "--dangerously-bypass-approvals-and-sandbox"]
+        );
+    }
+}
@@ -3,7 +3,7 @@
 use serde_json::Value;
 use std::time::Duration;
 
-use tokio::sync::Mutex;
+use tokio::sync::RwLock;
 use tokio::time::timeout;
 
 use crate::backend::app_server::check_codex_installation;
@@ -144,12 +144,12 @@ async fn run_npm_install_latest(package: &str) -> Result<(bool, String), String>
 }
 
 pub(crate) async fn codex_update_core(
-    app_settings: &Mutex<AppSettings>,
+    app_settings: &RwLock<AppSettings>,
     codex_bin: Option<String>,
     codex_args: Option<String>,
 ) -> Result<Value, String> {
     let (default_bin, default_args) = {
-        let settings = app_settings.lock().await;
+        let settings = app_settings.read().await;
         (settings.codex_bin.clone(), settings.codex_args.clone())
     };
     let resolved = codex_bin
@@ -0,0 +1,564 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex as StdMutex;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use toml_edit::{Item, Table, Value};
+
+use crate::files::ops::{read_with_policy, write_with_policy};
+use crate::files::policy::{policy_for, FileKind, FileScope};
+use crate::shared::config_toml_core::parse_document;
+use crate::shared::pagination_core::paginate;
+use crate::types::{ConfigChange, ConfigChangeSource, Page, PageResult};
+
+const CHANGELOG_FILE: &str = "config-changelog.jsonl";
+/// Entries kept once the log is trimmed.
+const MAX_ENTRIES: usize = 200;
+/// The log is only rewritten once it is this far past `MAX_ENTRIES`, so most
+/// writes are a plain append.
+const TRIM_SLACK: usize = 50;
+const DIFF_CONTEXT: usize = 3;
+/// Past this many line pairs the diff gives up on matching lines and shows
+/// the whole file as replaced.
+const MAX_DIFF_CELLS: usize = 4_000_000;
+
+// Where config writes are recorded. Unset (as in tests), nothing is.
+static CHANGELOG_DIR: StdMutex<Option<PathBuf>> = StdMutex::new(None);
+// Serialises appends with the occasional trim.
+static CHANGELOG_LOCK: StdMutex<()> = StdMutex::new(());
+
+pub(crate) fn set_config_changelog_dir(data_dir: &Path) {
+    if let Ok(mut current) = CHANGELOG_DIR.lock() {
+        *current = Some(data_dir.to_path_buf());
+    }
+}
+
+fn now_ms() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_millis() as i64)
+        .unwrap_or(0)
+}
+
+fn display_key(path: &[String]) -> String {
+    path.iter()
+        .map(|segment| {
+            let bare = !segment.is_empty()
+                && segment
+                    .chars()
+                    .all(|ch| ch.is_ascii_alphanumeric() || ch == '_' || ch == '-');
+            if bare {
+                segment.clone()
+            } else {
+                format!("{segment:?}")
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(".")
+}
+
+fn render_value(value: &Value) -> String {
+    value.clone().decorated("", "").to_string()
+}
+
+/// Every leaf value in `table` by key path. Inline tables and arrays count
+/// as one value.
+fn flatten(table: &Table, prefix: &mut Vec<String>, out: &mut BTreeMap<Vec<String>, String>) {
+    for (key, item) in table.iter() {
+        prefix.push(key.to_string());
+        match item {
+            Item::Table(table) => flatten(table, prefix, out),
+            Item::Value(value) => {
+                out.insert(prefix.clone(), render_value(value));
+            }
+            Item::ArrayOfTables(array) => {
+                out.insert(
+                    prefix.clone(),
+                    render_value(&Value::Array(array.clone().into_array())),
+                );
+            }
+            Item::None => {}
+        }
+        prefix.pop();
+    }
+}
+
+fn leaf_values(contents: Option<&str>) -> BTreeMap<Vec<String>, String> {
+    let mut values = BTreeMap::new();
+    if let Some(document) = contents.and_then(|contents| parse_document(contents).ok()) {
+        flatten(document.as_table(), &mut Vec::new(), &mut values);
+    }
+    values
+}
+
+/// Key paths whose value differs between the two versions. A version that
+/// does not parse contributes no keys.
+fn changed_paths(before: Option<&str>, after: Option<&str>) -> Vec<Vec<String>> {
+    let before = leaf_values(before);
+    let after = leaf_values(after);
+    let keys: BTreeSet<&Vec<String>> = before.keys().chain(after.keys()).collect();
+    keys.into_iter()
+        .filter(|key| before.get(*key) != after.get(*key))
+        .cloned()
+        .collect()
+}
+
+/// A unified diff of `before` against `after`, without file headers.
+pub(crate) fn line_diff(before: &str, after: &str) -> String {
+    let old: Vec<&str> = before.lines().collect();
+    let new: Vec<&str> = after.lines().collect();
+    let (n, m) = (old.len(), new.len());
+
+    let mut ops: Vec<(char, &str)> = Vec::with_capacity(n + m);
+    if n.saturating_mul(m) > MAX_DIFF_CELLS {
+        ops.extend(old.iter().map(|line| ('-', *line)));
+        ops.extend(new.iter().map(|line| ('+', *line)));
+    } else {
+        // lcs[i][j]: longest common subsequence of old[i..] and new[j..].
+        let mut lcs = vec![vec![0u32; m + 1]; n + 1];
+        for i in (0..n).rev() {
+            for j in (0..m).rev() {
+                lcs[i][j] = if old[i] == new[j] {
+                    lcs[i + 1][j + 1] + 1
+                } else {
+                    lcs[i + 1][j].max(lcs[i][j + 1])
+                };
+            }
+        }
+        let (mut i, mut j) = (0, 0);
+        while i < n || j < m {
+            if i < n && j < m && old[i] == new[j] {
+                ops.push((' ', old[i]));
+                i += 1;
+                j += 1;
+            } else if i < n && (j == m || lcs[i + 1][j] >= lcs[i][j + 1]) {
+                ops.push(('-', old[i]));
+                i += 1;
+            } else {
+                ops.push(('+', new[j]));
+                j += 1;
+            }
+        }
+    }
+
+    // Line numbers in each version before each op.
+    let mut positions = Vec::with_capacity(ops.len() + 1);
+    let (mut old_line, mut new_line) = (0, 0);
+    for (tag, _) in &ops {
+        positions.push((old_line, new_line));
+        if *tag != '+' {
+            old_line += 1;
+        }
+        if *tag != '-' {
+            new_line += 1;
+        }
+    }
+
+    let changed: Vec<usize> = ops
+        .iter()
+        .enumerate()
+        .filter(|(_, (tag, _))| *tag != ' ')
+        .map(|(index, _)| index)
+        .collect();
+    let mut out = String::new();
+    let mut next = 0;
+    while next < changed.len() {
+        let start = changed[next].saturating_sub(DIFF_CONTEXT);
+        let mut end = changed[next] + 1;
+        next += 1;
+        while next < changed.len() && changed[next] - end <= 2 * DIFF_CONTEXT {
+            end = changed[next] + 1;
+            next += 1;
+        }
+        let end = (end + DIFF_CONTEXT).min(ops.len());
+        let hunk = &ops[start..end];
+        let old_count = hunk.iter().filter(|(tag, _)| *tag != '+').count();
+        let new_count = hunk.iter().filter(|(tag, _)| *tag != '-').count();
+        // Hunks are numbered from 1, except an empty side, which names the
+        // line it follows.
+        let (old_start, new_start) = positions[start];
+        let old_start = old_start + usize::from(old_count > 0);
+        let new_start = new_start + usize::from(new_count > 0);
+        out.push_str(&format!(
+            "@@ -{old_start},{old_count} +{new_start},{new_count} @@\n"
+        ));
+        for (tag, line) in hunk {
+            out.push(*tag);
+            out.push_str(line);
+            out.push('\n');
+        }
+    }
+    out
+}
+
+fn read_entries(data_dir: &Path) -> Result<Vec<ConfigChange>, String> {
+    let raw = match std::fs::read_to_string(data_dir.join(CHANGELOG_FILE)) {
+        Ok(raw) => raw,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => String::new(),
+        Err(err) => return Err(format!("Failed to read the config changelog: {err}")),
+    };
+    Ok(raw
+        .lines()
+        .filter_map(|line| serde_json::from_str(line).ok())
+        .collect())
+}
+
+fn append_entry(data_dir: &Path, entry: &ConfigChange) -> Result<(), String> {
+    let _guard = CHANGELOG_LOCK.lock().unwrap_or_else(|err| err.into_inner());
+    let line = serde_json::to_string(entry).map_err(|err| err.to_string())?;
+    let path = data_dir.join(CHANGELOG_FILE);
+    std::fs::create_dir_all(data_dir)
+        .and_then(|_| {
+            let mut file = std::fs::OpenOptions::new()
+                .create(true)
+                .append(true)
+                .open(&path)?;
+            writeln!(file, "{line}")
+        })
+        .map_err(|err| format!("Failed to write the config changelog: {err}"))?;
+
+    let raw = std::fs::read_to_string(&path).unwrap_or_default();
+    let lines: Vec<&str> = raw.lines().collect();
+    if lines.len() > MAX_ENTRIES + TRIM_SLACK {
+        let mut kept = lines[lines.len() - MAX_ENTRIES..].join("\n");
+        kept.push('\n');
+        let tmp = path.with_extension("jsonl.tmp");
+        std::fs::write(&tmp, kept)
+            .and_then(|_| std::fs::rename(&tmp, &path))
+            .map_err(|err| format!("Failed to trim the config changelog: {err}"))?;
+    }
+    Ok(())
+}
+
+/// Records a write of `codex_home`'s config.toml in `data_dir`. `None`
+/// contents mean the file did not exist. Writes that change nothing are not
+/// recorded.
+fn record_in(
+    data_dir: &Path,
+    codex_home: &Path,
+    source: ConfigChangeSource,
+    before: Option<&str>,
+    after: Option<&str>,
+    reverts: Option<&str>,
+    timestamp_ms: i64,
+) -> Result<Option<ConfigChange>, String> {
+    if before == after {
+        return Ok(None);
+    }
+    let policy = policy_for(FileScope::Global, FileKind::Config)?;
+    let entry = ConfigChange {
+        id: uuid::Uuid::new_v4().to_string(),
+        timestamp_ms,
+        source,
+        path: codex_home
+            .join(policy.filename)
+            .to_string_lossy()
+            .to_string(),
+        changed_keys: changed_paths(before, after)
+            .iter()
+            .map(|path| display_key(path))
+            .collect(),
+        diff: line_diff(before.unwrap_or_default(), after.unwrap_or_default()),
+        before: before.map(str::to_string),
+        after: after.map(str::to_string),
+        reverts: reverts.map(str::to_string),
+    };
+    append_entry(data_dir, &entry)?;
+    Ok(Some(entry))
+}
+
+/// Records a write of the global config.toml in the changelog, if one has
+/// been set up. Failures are logged rather than failing the write, which has
+/// already happened.
+pub(crate) fn record_config_change(
+    codex_home: &Path,
+    source: ConfigChangeSource,
+    before: Option<&str>,
+    after: Option<&str>,
+) {
+    let Some(data_dir) = CHANGELOG_DIR.lock().ok().and_then(|dir| dir.clone()) else {
+        return;
+    };
+    if let Err(err) = record_in(&data_dir, codex_home, source, before, after, None, now_ms()) {
+        eprintln!("config changelog: {err}");
+    }
+}
+
+fn read_config(codex_home: &Path) -> Result<Option<String>, String> {
+    let policy = policy_for(FileScope::Global, FileKind::Config)?;
+    let response = read_with_policy(&codex_home.to_path_buf(), policy)?;
+    if response.truncated {
+        return Err("config.toml is too large to track".to_string());
+    }
+    Ok(response.exists.then_some(response.content))
+}
+
+/// Writes the global config.toml through the file policy and records the
+/// change.
+pub(crate) fn write_global_config(
+    codex_home: &Path,
+    contents: &str,
+    source: ConfigChangeSource,
+) -> Result<(), String> {
+    let policy = policy_for(FileScope::Global, FileKind::Config)?;
+    let before = read_config(codex_home);
+    write_with_policy(&codex_home.to_path_buf(), policy, contents)?;
+    if let Ok(before) = before {
+        record_config_change(codex_home, source, before.as_deref(), Some(contents));
+    }
+    Ok(())
+}
+
+fn item_at<'a>(table: &'a Table, path: &[String]) -> Option<&'a Item> {
+    let (last, parents) = path.split_last()?;
+    let mut table = table;
+    for key in parents {
+        table = table.get(key)?.as_table()?;
+    }
+    table.get(last)
+}
+
+/// Sets the value at `path`, or removes it for `None`, creating parent
+/// tables as needed.
+fn set_item(table: &mut Table, path: &[String], item: Option<Item>) -> Result<(), String> {
+    let Some((last, parents)) = path.split_last() else {
+        return Ok(());
+    };
+    let mut table = table;
+    for key in parents {
+        if !table.contains_key(key) {
+            if item.is_none() {
+                return Ok(());
+            }
+            table.insert(key, Item::Table(Table::new()));
+        }
+        let Some(child) = table.get_mut(key).and_then(Item::as_table_mut) else {
+            if item.is_none() {
+                return Ok(());
+            }
+            return Err(format!(
+                "`{}` is no longer a table in config.toml",
+                display_key(parents)
+            ));
+        };
+        table = child;
+    }
+    match item {
+        // Assigning in place keeps the key's comments.
+        Some(item) => match table.get_mut(last) {
+            Some(slot) => *slot = item,
+            None => {
+                table.insert(last, item);
+            }
+        },
+        None => {
+            table.remove(last);
+        }
+    }
+    Ok(())
+}
+
+/// Puts back the keys `change` touched, leaving later edits to other keys
+/// in place.
+fn revert_keys(change: &ConfigChange, current: Option<&str>) -> Result<Option<String>, String> {
+    let paths = changed_paths(change.before.as_deref(), change.after.as_deref());
+    if paths.is_empty() {
+        return Err(
+            "config.toml has changed since, and this change touched no settings that can be put back on their own"
+                .to_string(),
+        );
+    }
+    let before = parse_document(change.before.as_deref().unwrap_or_default())?;
+    let mut document = parse_document(current.unwrap_or_default())?;
+    for path in &paths {
+        set_item(
+            document.as_table_mut(),
+            path,
+            item_at(before.as_table(), path).cloned(),
+        )?;
+    }
+    let mut rendered = document.to_string();
+    if !rendered.is_empty() && !rendered.ends_with('\n') {
+        rendered.push('\n');
+    }
+    Ok(Some(rendered))
+}
+
+fn revert_inner(data_dir: &Path, id: &str, timestamp_ms: i64) -> Result<ConfigChange, String> {
+    let change = read_entries(data_dir)?
+        .into_iter()
+        .find(|entry| entry.id == id)
+        .ok_or_else(|| "Config change not found".to_string())?;
+    let path = PathBuf::from(&change.path);
+    let codex_home = path
+        .parent()
+        .ok_or_else(|| "Config change has no Codex home".to_string())?;
+    let current = read_config(codex_home)?;
+    // Untouched since: the file goes back exactly as it was, comments and all.
+    let target = if current == change.after {
+        change.before.clone()
+    } else {
+        revert_keys(&change, current.as_deref())?
+    };
+    if target == current {
+        return Err("config.toml already matches how it was before this change".to_string());
+    }
+
+    let policy = policy_for(FileScope::Global, FileKind::Config)?;
+    match target.as_deref() {
+        Some(contents) => write_with_policy(&codex_home.to_path_buf(), policy, contents)?,
+        None => std::fs::remove_file(&path)
+            .map_err(|err| format!("Failed to remove config.toml: {err}"))?,
+    }
+    record_in(
+        data_dir,
+        codex_home,
+        ConfigChangeSource::Revert,
+        current.as_deref(),
+        target.as_deref(),
+        Some(id),
+        timestamp_ms,
+    )?
+    .ok_or_else(|| "Nothing to revert".to_string())
+}
+
+/// Recorded config.toml writes, newest first. Lines that do not parse are
+/// skipped.
+pub(crate) fn list_config_changes_core(
+    data_dir: &Path,
+    page: &Page,
+) -> Result<PageResult<ConfigChange>, String> {
+    paginate(read_entries(data_dir)?, page, |entry: &ConfigChange| {
+        (entry.timestamp_ms, entry.id.as_str())
+    })
+}
+
+/// Undoes change `id`. When config.toml is as that change left it, the file
+/// goes back to exactly what it was before; otherwise only the keys the
+/// change touched are put back. The revert is recorded as a change of its
+/// own.
+pub(crate) async fn revert_config_change_core(
+    data_dir: PathBuf,
+    id: String,
+) -> Result<ConfigChange, String> {
+    tokio::task::spawn_blocking(move || revert_inner(&data_dir, id.trim(), now_ms()))
+        .await
+        .map_err(|err| err.to_string())?
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use super::*;
+
+    fn temp_dir(label: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!(
+            "codex-monitor-config-changelog-{label}-{}",
+            uuid::Uuid::new_v4()
+        ));
+        fs::create_dir_all(&dir).expect("temp dir");
+        dir
+    }
+
+    fn write(
+        data_dir: &Path,
+        codex_home: &Path,
+        contents: &str,
+        timestamp_ms: i64,
+    ) -> ConfigChange {
+        let before = read_config(codex_home).expect("read");
+        fs::write(codex_home.join("config.toml"), contents).expect("write");
+        record_in(
+            data_dir,
+            codex_home,
+            ConfigChangeSource::Settings,
+            before.as_deref(),
+            Some(contents),
+            None,
+            timestamp_ms,
+        )
+        .expect("record")
+        .expect("changed")
+    }
+
+    #[test]
+    fn line_diff_marks_changed_lines_with_context() {
+        let before = "a\nb\nc\nd\ne\nf\ng\nh\n";
+        let after = "a\nb\nc\nd\nE\nf\ng\nh\n";
+        assert_eq!(
+            line_diff(before, after),
+            "@@ -2,7 +2,7 @@\n b\n c\n d\n-e\n+E\n f\n g\n h\n"
+        );
+        assert_eq!(line_diff("", "x = 1\n"), "@@ -0,0 +1,1 @@\n+x = 1\n");
+        assert_eq!(line_diff("same\n", "same\n"), "");
+    }
+
+    #[test]
+    fn changes_list_keys_and_skip_no_op_writes() {
+        let data_dir = temp_dir("data");
+        let home = temp_dir("home");
+        write(&data_dir, &home, "model = \"o3\"\n", 1);
+        let change = write(
+            &data_dir,
+            &home,
+            "model = \"gpt-5\"\n\n[features]\nweb_search = true\n",
+            2,
+        );
+        assert_eq!(change.changed_keys, ["features.web_search", "model"]);
+        assert_eq!(change.before.as_deref(), Some("model = \"o3\"\n"));
+        let same = record_in(
+            &data_dir,
+            &home,
+            ConfigChangeSource::Editor,
+            change.after.as_deref(),
+            change.after.as_deref(),
+            None,
+            3,
+        )
+        .expect("record");
+        assert!(same.is_none());
+
+        let page = list_config_changes_core(&data_dir, &Page::default()).expect("list");
+        assert_eq!(page.items.len(), 2);
+        assert_eq!(page.items[0].id, change.id);
+    }
+
+    #[test]
+    fn reverting_restores_the_file_or_only_the_changed_keys() {
+        let data_dir = temp_dir("data");
+        let home = temp_dir("home");
+        write(&data_dir, &home, "# mine\nmodel = \"o3\"\n", 1);
+        let flag = write(
+            &data_dir,
+            &home,
+            "# mine\nmodel = \"o3\"\n\n[features]\nunified_exec = true\n",
+            2,
+        );
+
+        // Nothing written since: the exact previous file comes back.
+        let reverted = revert_inner(&data_dir, &flag.id, 3).expect("revert");
+        assert_eq!(reverted.reverts.as_deref(), Some(flag.id.as_str()));
+        assert_eq!(reverted.source, ConfigChangeSource::Revert);
+        assert_eq!(
+            fs::read_to_string(home.join("config.toml")).expect("read"),
+            "# mine\nmodel = \"o3\"\n"
+        );
+        assert!(revert_inner(&data_dir, &flag.id, 4).is_err());
+
+        // A later edit to another key survives reverting an earlier change.
+        let model = write(&data_dir, &home, "# mine\nmodel = \"gpt-5\"\n", 5);
+        write(
+            &data_dir,
+            &home,
+            "# mine\nmodel = \"gpt-5\"\napproval_policy = \"never\"\n",
+            6,
+        );
+        revert_inner(&data_dir, &model.id, 7).expect("revert model");
+        assert_eq!(
+            fs::read_to_string(home.join("config.toml")).expect("read"),
+            "# mine\nmodel = \"o3\"\napproval_policy = \"never\"\n"
+        );
+    }
+}
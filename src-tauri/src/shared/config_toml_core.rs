@@ -2,8 +2,10 @@ use std::path::Path;
 
 use toml_edit::{value, Document, Item, Table};
 
-use crate::files::ops::{read_with_policy, write_with_policy};
+use crate::files::ops::read_with_policy;
 use crate::files::policy::{policy_for, FileKind, FileScope};
+use crate::shared::config_changelog_core::write_global_config;
+use crate::types::ConfigChangeSource;
 
 pub(crate) fn load_global_config_document(codex_home: &Path) -> Result<(bool, Document), String> {
     let policy = policy_for(FileScope::Global, FileKind::Config)?;
@@ -21,13 +23,11 @@ pub(crate) fn persist_global_config_document(
     codex_home: &Path,
     document: &Document,
 ) -> Result<(), String> {
-    let policy = policy_for(FileScope::Global, FileKind::Config)?;
-    let root = codex_home.to_path_buf();
     let mut rendered = document.to_string();
     if !rendered.ends_with('\n') {
         rendered.push('\n');
     }
-    write_with_policy(&root, policy, rendered.as_str())
+    write_global_config(codex_home, rendered.as_str(), ConfigChangeSource::Settings)
 }
 
 pub(crate) fn parse_document(contents: &str) -> Result<Document, String> {
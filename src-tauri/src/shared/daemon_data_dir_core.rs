@@ -0,0 +1,106 @@
+//! The daemon keeps its own state, its identity key and the clients it has
+//! admitted, in a directory of its own. Workspaces, settings and the other
+//! stores the app shares with it stay in the app data directory, which the
+//! daemon is given as `--app-data-dir`.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+const DAEMON_DATA_SUBDIR: &str = "daemon";
+
+/// Files the daemon owns, which used to live in the app data directory.
+const DAEMON_STATE_FILES: &[&str] = &["daemon-identity.key", "client-approvals.json"];
+
+/// The configured daemon data directory, relative paths taken from the app
+/// data directory, or its `daemon` subdirectory when none is set.
+#[allow(dead_code)]
+pub(crate) fn resolve_daemon_data_dir(app_data_dir: &Path, configured: Option<&str>) -> PathBuf {
+    match configured.map(str::trim).filter(|value| !value.is_empty()) {
+        Some(dir) => app_data_dir.join(dir),
+        None => app_data_dir.join(DAEMON_DATA_SUBDIR),
+    }
+}
+
+/// Moves daemon state from `from` into `to`, returning the names of the
+/// files moved. A file `to` already has is left where it is, so moving from
+/// the app data directory only does anything on the first run after the
+/// directories split.
+#[allow(dead_code)]
+pub(crate) fn migrate_daemon_state(from: &Path, to: &Path) -> Result<Vec<String>, String> {
+    if from == to {
+        return Ok(Vec::new());
+    }
+    let mut moved = Vec::new();
+    for name in DAEMON_STATE_FILES {
+        let source = from.join(name);
+        let target = to.join(name);
+        if !source.is_file() || target.exists() {
+            continue;
+        }
+        fs::create_dir_all(to)
+            .map_err(|err| format!("Failed to create {}: {err}", to.display()))?;
+        // A rename fails across file systems; copy and remove instead.
+        if fs::rename(&source, &target).is_err() {
+            fs::copy(&source, &target)
+                .map_err(|err| format!("Failed to copy {}: {err}", source.display()))?;
+            fs::remove_file(&source)
+                .map_err(|err| format!("Failed to remove {}: {err}", source.display()))?;
+        }
+        moved.push(name.to_string());
+    }
+    Ok(moved)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn daemon_data_dir_defaults_to_a_subdirectory() {
+        let app_data_dir = Path::new("/data/app");
+        assert_eq!(
+            resolve_daemon_data_dir(app_data_dir, None),
+            PathBuf::from("/data/app/daemon")
+        );
+        assert_eq!(
+            resolve_daemon_data_dir(app_data_dir, Some("  ")),
+            PathBuf::from("/data/app/daemon")
+        );
+        assert_eq!(
+            resolve_daemon_data_dir(app_data_dir, Some("/srv/daemon")),
+            PathBuf::from("/srv/daemon")
+        );
+        assert_eq!(
+            resolve_daemon_data_dir(app_data_dir, Some("state")),
+            PathBuf::from("/data/app/state")
+        );
+    }
+
+    #[test]
+    fn migration_moves_daemon_state_once() {
+        let app_data_dir =
+            std::env::temp_dir().join(format!("codex-monitor-app-{}", uuid::Uuid::new_v4()));
+        let daemon_data_dir = app_data_dir.join(DAEMON_DATA_SUBDIR);
+        fs::create_dir_all(&app_data_dir).expect("create app data dir");
+        fs::write(app_data_dir.join("daemon-identity.key"), "secret").expect("write identity");
+        fs::write(app_data_dir.join("settings.json"), "{}").expect("write settings");
+
+        let moved = migrate_daemon_state(&app_data_dir, &daemon_data_dir).expect("migrate");
+        assert_eq!(moved, vec!["daemon-identity.key".to_string()]);
+        assert!(!app_data_dir.join("daemon-identity.key").exists());
+        assert!(app_data_dir.join("settings.json").exists());
+        assert_eq!(
+            fs::read_to_string(daemon_data_dir.join("daemon-identity.key")).expect("identity"),
+            "secret"
+        );
+
+        fs::write(app_data_dir.join("daemon-identity.key"), "stale").expect("write identity");
+        let moved = migrate_daemon_state(&app_data_dir, &daemon_data_dir).expect("migrate");
+        assert!(moved.is_empty());
+        assert_eq!(
+            fs::read_to_string(daemon_data_dir.join("daemon-identity.key")).expect("identity"),
+            "secret"
+        );
+        let _ = fs::remove_dir_all(&app_data_dir);
+    }
+}
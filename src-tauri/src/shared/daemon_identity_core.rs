@@ -0,0 +1,137 @@
+use std::fs::OpenOptions;
+use std::io::{ErrorKind, Write};
+use std::path::{Path, PathBuf};
+
+use ring::hmac;
+
+const DAEMON_IDENTITY_FILENAME: &str = "daemon-identity.key";
+const MAX_CHALLENGE_LEN: usize = 256;
+
+/// Answered before authentication so a client can check who is listening
+/// before it sends any credentials.
+pub(crate) const DAEMON_IDENTIFY_METHOD: &str = "daemon_identify";
+
+fn daemon_identity_path(data_dir: &Path) -> PathBuf {
+    data_dir.join(DAEMON_IDENTITY_FILENAME)
+}
+
+/// Returns the per-install daemon secret, if one has been generated.
+pub(crate) fn read_daemon_identity(data_dir: &Path) -> Option<String> {
+    std::fs::read_to_string(daemon_identity_path(data_dir))
+        .ok()
+        .map(|value| value.trim().to_string())
+        .filter(|value| !value.is_empty())
+}
+
+/// Returns the per-install daemon secret, generating it on first use. The
+/// file is created exclusively so the app and daemon never race to
+/// different secrets.
+pub(crate) fn ensure_daemon_identity(data_dir: &Path) -> Result<String, String> {
+    if let Some(secret) = read_daemon_identity(data_dir) {
+        return Ok(secret);
+    }
+    std::fs::create_dir_all(data_dir).map_err(|err| err.to_string())?;
+    let secret = format!(
+        "{}{}",
+        uuid::Uuid::new_v4().simple(),
+        uuid::Uuid::new_v4().simple()
+    );
+    let mut options = OpenOptions::new();
+    options.write(true).create_new(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+    match options.open(daemon_identity_path(data_dir)) {
+        Ok(mut file) => {
+            file.write_all(secret.as_bytes())
+                .map_err(|err| err.to_string())?;
+            Ok(secret)
+        }
+        Err(err) if err.kind() == ErrorKind::AlreadyExists => read_daemon_identity(data_dir)
+            .ok_or_else(|| "daemon identity file is empty".to_string()),
+        Err(err) => Err(err.to_string()),
+    }
+}
+
+#[allow(dead_code)]
+pub(crate) fn new_identity_challenge() -> String {
+    uuid::Uuid::new_v4().simple().to_string()
+}
+
+fn identity_message(challenge: &str) -> Result<Vec<u8>, String> {
+    if challenge.is_empty() || challenge.len() > MAX_CHALLENGE_LEN {
+        return Err("invalid identity challenge".to_string());
+    }
+    Ok(format!("codex-monitor-daemon-identity\n{challenge}").into_bytes())
+}
+
+fn from_hex(value: &str) -> Option<Vec<u8>> {
+    if !value.len().is_multiple_of(2) {
+        return None;
+    }
+    (0..value.len())
+        .step_by(2)
+        .map(|index| u8::from_str_radix(value.get(index..index + 2)?, 16).ok())
+        .collect()
+}
+
+/// Proof that the responder holds `secret`: an HMAC-SHA256 of the caller's
+/// challenge keyed with it.
+pub(crate) fn daemon_identity_proof(secret: &str, challenge: &str) -> Result<String, String> {
+    let key = hmac::Key::new(hmac::HMAC_SHA256, secret.as_bytes());
+    let tag = hmac::sign(&key, &identity_message(challenge)?);
+    Ok(tag
+        .as_ref()
+        .iter()
+        .map(|byte| format!("{byte:02x}"))
+        .collect())
+}
+
+#[allow(dead_code)]
+pub(crate) fn verify_daemon_identity_proof(secret: &str, challenge: &str, proof: &str) -> bool {
+    let (Ok(message), Some(tag)) = (identity_message(challenge), from_hex(proof)) else {
+        return false;
+    };
+    let key = hmac::Key::new(hmac::HMAC_SHA256, secret.as_bytes());
+    hmac::verify(&key, &message, &tag).is_ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_dir() -> PathBuf {
+        std::env::temp_dir().join(format!("codex-monitor-identity-{}", uuid::Uuid::new_v4()))
+    }
+
+    #[test]
+    fn identity_is_generated_once_and_reused() {
+        let data_dir = temp_dir();
+        assert!(read_daemon_identity(&data_dir).is_none());
+        let first = ensure_daemon_identity(&data_dir).expect("generate");
+        assert_eq!(first.len(), 64);
+        assert_eq!(ensure_daemon_identity(&data_dir).expect("reuse"), first);
+        assert_eq!(
+            read_daemon_identity(&data_dir).as_deref(),
+            Some(first.as_str())
+        );
+        let _ = std::fs::remove_dir_all(&data_dir);
+    }
+
+    #[test]
+    fn proofs_only_verify_for_the_same_secret_and_challenge() {
+        let challenge = new_identity_challenge();
+        let proof = daemon_identity_proof("secret", &challenge).expect("proof");
+        assert!(verify_daemon_identity_proof("secret", &challenge, &proof));
+        assert!(!verify_daemon_identity_proof("other", &challenge, &proof));
+        assert!(!verify_daemon_identity_proof(
+            "secret",
+            &new_identity_challenge(),
+            &proof
+        ));
+        assert!(!verify_daemon_identity_proof("secret", &challenge, ""));
+        assert!(daemon_identity_proof("secret", "").is_err());
+    }
+}
@@ -0,0 +1,317 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::storage::{read_settings, read_workspaces, write_settings};
+
+const SETTINGS_FILE: &str = "settings.json";
+const WORKSPACES_FILE: &str = "workspaces.json";
+
+fn configured_data_dir(default_dir: &Path) -> Option<PathBuf> {
+    let settings = read_settings(&default_dir.join(SETTINGS_FILE)).ok()?;
+    settings
+        .data_dir
+        .as_deref()
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+        .map(PathBuf::from)
+}
+
+/// The directory the app keeps its state in: the `dataDir` recorded in the
+/// default directory's settings, or the default itself. Falls back to the
+/// default when the configured directory is missing, such as an unplugged
+/// external disk.
+#[allow(dead_code)]
+pub(crate) fn resolve_data_dir(default_dir: &Path) -> PathBuf {
+    match configured_data_dir(default_dir) {
+        Some(dir) if dir.is_dir() => dir,
+        Some(dir) => {
+            eprintln!(
+                "Data directory {} is unavailable; using {}",
+                dir.display(),
+                default_dir.display()
+            );
+            default_dir.to_path_buf()
+        }
+        None => default_dir.to_path_buf(),
+    }
+}
+
+/// `path` with symlinks resolved, or, when it does not exist yet, its
+/// resolved parent joined with its name.
+fn resolve_path(path: &Path) -> PathBuf {
+    if let Ok(resolved) = path.canonicalize() {
+        return resolved;
+    }
+    match (path.parent(), path.file_name()) {
+        (Some(parent), Some(name)) => resolve_path(parent).join(name),
+        _ => path.to_path_buf(),
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum DataDirTargetError {
+    /// The target already holds files, such as the copy a previous move
+    /// left in the default directory. Moving there needs `overwrite`.
+    NotEmpty(PathBuf),
+    Invalid(String),
+}
+
+impl DataDirTargetError {
+    #[allow(dead_code)]
+    pub(crate) fn message(&self) -> String {
+        match self {
+            Self::NotEmpty(target) => format!(
+                "{} is not empty. Moving there replaces the files it shares with the current \
+                 data directory.",
+                target.display()
+            ),
+            Self::Invalid(message) => message.clone(),
+        }
+    }
+}
+
+/// Checks that `target` can take the app's state. A directory that already
+/// holds files is only accepted with `overwrite`.
+pub(crate) fn validate_data_dir_target(
+    current: &Path,
+    target: &Path,
+    overwrite: bool,
+) -> Result<(), DataDirTargetError> {
+    if !target.is_absolute() {
+        return Err(DataDirTargetError::Invalid(
+            "The new data directory must be an absolute path.".to_string(),
+        ));
+    }
+    let current = resolve_path(current);
+    let resolved = resolve_path(target);
+    if current == resolved {
+        return Err(DataDirTargetError::Invalid(
+            "The data directory is already there.".to_string(),
+        ));
+    }
+    if resolved.starts_with(&current) || current.starts_with(&resolved) {
+        return Err(DataDirTargetError::Invalid(
+            "The new data directory cannot contain, or be inside, the current one.".to_string(),
+        ));
+    }
+    if !resolved.exists() {
+        return Ok(());
+    }
+    let mut entries = fs::read_dir(&resolved).map_err(|_| {
+        DataDirTargetError::Invalid(format!(
+            "{} exists and is not a readable directory.",
+            resolved.display()
+        ))
+    })?;
+    if entries.next().is_some() && !overwrite {
+        return Err(DataDirTargetError::NotEmpty(target.to_path_buf()));
+    }
+    Ok(())
+}
+
+/// Copies the regular files under `from` into `to`, returning each file's
+/// path relative to `from` and its size. Symlinks and other special files
+/// are skipped.
+fn copy_dir(from: &Path, to: &Path, relative: &Path) -> Result<Vec<(PathBuf, u64)>, String> {
+    fs::create_dir_all(to).map_err(|err| format!("Failed to create {}: {err}", to.display()))?;
+    let mut copied = Vec::new();
+    let entries =
+        fs::read_dir(from).map_err(|err| format!("Failed to read {}: {err}", from.display()))?;
+    for entry in entries {
+        let entry = entry.map_err(|err| err.to_string())?;
+        let file_type = entry.file_type().map_err(|err| err.to_string())?;
+        let name = entry.file_name();
+        let source = entry.path();
+        if file_type.is_dir() {
+            copied.extend(copy_dir(&source, &to.join(&name), &relative.join(&name))?);
+        } else if file_type.is_file() {
+            let size = fs::copy(&source, to.join(&name))
+                .map_err(|err| format!("Failed to copy {}: {err}", source.display()))?;
+            copied.push((relative.join(&name), size));
+        }
+    }
+    Ok(copied)
+}
+
+fn verify_copy(target: &Path, copied: &[(PathBuf, u64)]) -> Result<(), String> {
+    for (relative, size) in copied {
+        let copied_size = fs::metadata(target.join(relative))
+            .map(|metadata| metadata.len())
+            .map_err(|err| format!("{} is missing after copying: {err}", relative.display()))?;
+        if copied_size != *size {
+            return Err(format!("{} did not copy completely.", relative.display()));
+        }
+    }
+    read_settings(&target.join(SETTINGS_FILE))
+        .map_err(|err| format!("Copied settings are unreadable: {err}"))?;
+    read_workspaces(&target.join(WORKSPACES_FILE))
+        .map_err(|err| format!("Copied workspaces are unreadable: {err}"))?;
+    Ok(())
+}
+
+/// Records `data_dir` in the settings at `dir`, keeping everything else.
+fn record_data_dir(dir: &Path, data_dir: Option<&str>) -> Result<(), String> {
+    let path = dir.join(SETTINGS_FILE);
+    let mut settings = read_settings(&path)?;
+    settings.data_dir = data_dir.map(str::to_string);
+    write_settings(&path, &settings)
+}
+
+/// A verified copy of the app's state, not yet in effect. `data_dir` is the
+/// `dataDir` value that points at it and `previous` the one it replaces.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct DataDirMove {
+    pub(crate) default_dir: PathBuf,
+    pub(crate) target: PathBuf,
+    pub(crate) data_dir: Option<String>,
+    pub(crate) previous: Option<String>,
+}
+
+/// Copies everything in `current` to `target` and checks the copy. Nothing
+/// points at it yet; `commit_data_dir_move` does that. `current` is left as
+/// it was so the move can be undone by hand, and a failed copy removes what
+/// it wrote to a new `target`.
+#[allow(dead_code)]
+pub(crate) fn migrate_data_dir_core(
+    current: &Path,
+    target: &Path,
+    default_dir: &Path,
+    overwrite: bool,
+) -> Result<DataDirMove, String> {
+    validate_data_dir_target(current, target, overwrite).map_err(|err| err.message())?;
+    let previous = configured_data_dir(default_dir).map(|dir| dir.to_string_lossy().into_owned());
+    let created = !target.exists();
+    let copied = copy_dir(current, target, Path::new("")).and_then(|copied| {
+        verify_copy(target, &copied)?;
+        Ok(copied)
+    });
+    if let Err(err) = copied {
+        if created {
+            let _ = fs::remove_dir_all(target);
+        }
+        return Err(err);
+    }
+
+    let moving_home = resolve_path(target) == resolve_path(default_dir);
+    Ok(DataDirMove {
+        default_dir: default_dir.to_path_buf(),
+        target: target.to_path_buf(),
+        data_dir: (!moving_home).then(|| target.to_string_lossy().into_owned()),
+        previous,
+    })
+}
+
+/// Points the copy, and then the default directory, at the moved state.
+/// Until the default directory is updated the app keeps starting from the
+/// current one. Moving home, the copied settings still name the directory
+/// being left, so updating them is the whole commit.
+#[allow(dead_code)]
+pub(crate) fn commit_data_dir_move(moved: &DataDirMove) -> Result<(), String> {
+    if moved.data_dir.is_some() {
+        record_data_dir(&moved.target, moved.data_dir.as_deref())?;
+    }
+    record_data_dir(&moved.default_dir, moved.data_dir.as_deref())
+}
+
+/// Puts back the `dataDir` a committed move replaced, so the next start
+/// uses the directory the app was moving away from.
+#[allow(dead_code)]
+pub(crate) fn rollback_data_dir_move(moved: &DataDirMove) -> Result<(), String> {
+    record_data_dir(&moved.default_dir, moved.previous.as_deref())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::AppSettings;
+
+    fn temp_dir(label: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!(
+            "codex-monitor-data-dir-{label}-{}",
+            uuid::Uuid::new_v4()
+        ));
+        fs::create_dir_all(&dir).expect("create temp dir");
+        dir
+    }
+
+    #[test]
+    fn migration_copies_state_and_points_the_default_dir_at_it() {
+        let home = temp_dir("home");
+        let settings = AppSettings {
+            theme: "dark".to_string(),
+            ..AppSettings::default()
+        };
+        write_settings(&home.join(SETTINGS_FILE), &settings).expect("write settings");
+        fs::write(home.join(WORKSPACES_FILE), "[]").expect("write workspaces");
+        fs::create_dir_all(home.join("prompts")).expect("prompts dir");
+        fs::write(home.join("prompts").join("fix.md"), "fix it").expect("write prompt");
+        let external = temp_dir("external").join("CodexMonitor");
+
+        let moved = migrate_data_dir_core(&home, &external, &home, false).expect("migrate");
+        assert_eq!(
+            moved.data_dir.as_deref(),
+            Some(external.to_string_lossy().as_ref())
+        );
+        assert_eq!(moved.previous, None);
+        assert_eq!(
+            fs::read_to_string(external.join("prompts").join("fix.md")).expect("copied prompt"),
+            "fix it"
+        );
+        // Nothing points at the copy until the move is committed.
+        assert_eq!(resolve_data_dir(&home), home);
+        commit_data_dir_move(&moved).expect("commit");
+        assert_eq!(resolve_data_dir(&home), external);
+        let settings = read_settings(&external.join(SETTINGS_FILE)).expect("moved settings");
+        assert_eq!(settings.theme, "dark");
+
+        // Home still holds the copy the first move left there.
+        let error = migrate_data_dir_core(&external, &home, &home, false).expect_err("occupied");
+        assert!(error.contains("not empty"), "{error}");
+        let back = migrate_data_dir_core(&external, &home, &home, true).expect("move back");
+        assert_eq!(back.data_dir, None);
+        assert_eq!(resolve_data_dir(&home), external);
+        commit_data_dir_move(&back).expect("commit");
+        assert_eq!(resolve_data_dir(&home), home);
+
+        let _ = fs::remove_dir_all(&home);
+        let _ = fs::remove_dir_all(external.parent().expect("parent"));
+    }
+
+    #[test]
+    fn migration_refuses_nested_relative_and_occupied_targets() {
+        let home = temp_dir("refuse");
+        let occupied = temp_dir("occupied");
+        fs::write(occupied.join("notes.txt"), "mine").expect("write");
+
+        assert!(migrate_data_dir_core(&home, &home.join("inner"), &home, true).is_err());
+        assert!(migrate_data_dir_core(&home, Path::new("relative/dir"), &home, true).is_err());
+        assert_eq!(
+            validate_data_dir_target(&home, &occupied, false),
+            Err(DataDirTargetError::NotEmpty(occupied.clone()))
+        );
+        assert_eq!(validate_data_dir_target(&home, &occupied, true), Ok(()));
+        let error = migrate_data_dir_core(&home, &occupied, &home, false).expect_err("occupied");
+        assert!(error.contains("not empty"), "{error}");
+        assert_eq!(resolve_data_dir(&home), home);
+
+        let _ = fs::remove_dir_all(&home);
+        let _ = fs::remove_dir_all(&occupied);
+    }
+
+    #[test]
+    fn a_rolled_back_move_starts_from_the_previous_directory() {
+        let home = temp_dir("rollback");
+        write_settings(&home.join(SETTINGS_FILE), &AppSettings::default()).expect("settings");
+        fs::write(home.join(WORKSPACES_FILE), "[]").expect("write workspaces");
+        let external = temp_dir("rollback-target").join("CodexMonitor");
+
+        let moved = migrate_data_dir_core(&home, &external, &home, false).expect("migrate");
+        commit_data_dir_move(&moved).expect("commit");
+        assert_eq!(resolve_data_dir(&home), external);
+        rollback_data_dir_move(&moved).expect("rollback");
+        assert_eq!(resolve_data_dir(&home), home);
+
+        let _ = fs::remove_dir_all(&home);
+        let _ = fs::remove_dir_all(external.parent().expect("parent"));
+    }
+}
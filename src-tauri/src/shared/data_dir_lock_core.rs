@@ -0,0 +1,259 @@
+//! An advisory lock on a daemon data directory, so a daemon the app spawned
+//! and one started by hand never share state. The daemon holds an OS file
+//! lock (`flock` or `LockFileEx`) on `daemon.lock` for its lifetime, which
+//! the OS drops when the process dies. Next to it, `daemon.lock.json` names
+//! the holder by pid and OS start time so the app can report and stop it; a
+//! record whose holder has exited, or whose pid now belongs to another
+//! process, is stale.
+
+use std::fs::{self, File, OpenOptions, TryLockError};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+
+use crate::shared::process_core::kill_pid_gracefully;
+use crate::shared::process_registry_core::{is_alive, process_identity, ProcessIdentity};
+
+const DATA_DIR_LOCK_FILE: &str = "daemon.lock";
+const DATA_DIR_HOLDER_FILE: &str = "daemon.lock.json";
+/// How long a daemon that lost the lock waits for the winner to name itself.
+const HOLDER_WAIT: Duration = Duration::from_millis(500);
+
+/// What the daemon exits with when another live daemon holds its data dir,
+/// so the app can tell a lost race apart from a crash.
+pub(crate) const DATA_DIR_LOCKED_EXIT_CODE: i32 = 3;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct DataDirLock {
+    pub(crate) holder: ProcessIdentity,
+    pub(crate) locked_at_ms: u64,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum DataDirLockError {
+    /// The holder, once it has named itself.
+    Locked(Option<DataDirLock>),
+    Io(String),
+}
+
+impl DataDirLockError {
+    #[allow(dead_code)]
+    pub(crate) fn message(&self, dir: &Path) -> String {
+        match self {
+            Self::Locked(Some(lock)) => locked_message(dir, lock),
+            Self::Locked(None) => {
+                format!("Data dir {} is locked by another daemon.", dir.display())
+            }
+            Self::Io(err) => err.clone(),
+        }
+    }
+}
+
+/// Holds the OS lock until dropped, and clears the holder record unless
+/// someone has since taken it over.
+pub(crate) struct DataDirLockGuard {
+    _file: File,
+    holder_path: PathBuf,
+    holder: ProcessIdentity,
+}
+
+impl Drop for DataDirLockGuard {
+    fn drop(&mut self) {
+        let still_ours =
+            read_holder_file(&self.holder_path).is_some_and(|lock| lock.holder == self.holder);
+        if still_ours {
+            let _ = fs::remove_file(&self.holder_path);
+        }
+    }
+}
+
+pub(crate) fn locked_message(dir: &Path, lock: &DataDirLock) -> String {
+    format!(
+        "Data dir {} is locked by pid {}.",
+        dir.display(),
+        lock.holder.pid
+    )
+}
+
+fn lock_path(dir: &Path) -> PathBuf {
+    dir.join(DATA_DIR_LOCK_FILE)
+}
+
+fn holder_path(dir: &Path) -> PathBuf {
+    dir.join(DATA_DIR_HOLDER_FILE)
+}
+
+fn read_holder_file(path: &Path) -> Option<DataDirLock> {
+    let raw = fs::read_to_string(path).ok()?;
+    serde_json::from_str(&raw).ok()
+}
+
+fn now_ms() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_millis() as u64)
+        .unwrap_or(0)
+}
+
+/// The lock on `dir` when its holder is still running. Always `None` on a
+/// platform where process identity can't be verified, since a record there
+/// could name a pid that was reused.
+pub(crate) fn live_lock(dir: &Path) -> Option<DataDirLock> {
+    read_holder_file(&holder_path(dir)).filter(|lock| is_alive(&lock.holder))
+}
+
+/// Writes the holder record through a temp file, so readers never see it
+/// half written.
+fn write_holder_file(dir: &Path, lock: &DataDirLock) -> Result<(), String> {
+    let path = holder_path(dir);
+    let tmp = dir.join(format!(
+        "{DATA_DIR_HOLDER_FILE}.{}.tmp",
+        uuid::Uuid::new_v4()
+    ));
+    let body = serde_json::to_string_pretty(lock).map_err(|err| err.to_string())?;
+    let written = File::create(&tmp)
+        .and_then(|mut file| file.write_all(body.as_bytes()))
+        .and_then(|_| fs::rename(&tmp, &path));
+    if let Err(err) = written {
+        let _ = fs::remove_file(&tmp);
+        return Err(format!("Failed to write {}: {err}", path.display()));
+    }
+    Ok(())
+}
+
+/// Takes the lock on `dir` for this process. Fails with `Locked` while any
+/// other process holds it, whether or not it has named itself yet.
+#[allow(dead_code)]
+pub(crate) fn acquire_data_dir_lock(dir: &Path) -> Result<DataDirLockGuard, DataDirLockError> {
+    fs::create_dir_all(dir).map_err(|err| {
+        DataDirLockError::Io(format!("Failed to create {}: {err}", dir.display()))
+    })?;
+    let path = lock_path(dir);
+    let file = OpenOptions::new()
+        .read(true)
+        .write(true)
+        .create(true)
+        .truncate(false)
+        .open(&path)
+        .map_err(|err| DataDirLockError::Io(format!("Failed to open {}: {err}", path.display())))?;
+    match file.try_lock() {
+        Ok(()) => {}
+        Err(TryLockError::WouldBlock) => {
+            // The winner may still be writing its record.
+            let waited = std::time::Instant::now();
+            let mut holder = live_lock(dir);
+            while holder.is_none() && waited.elapsed() < HOLDER_WAIT {
+                std::thread::sleep(Duration::from_millis(50));
+                holder = live_lock(dir);
+            }
+            return Err(DataDirLockError::Locked(holder));
+        }
+        Err(TryLockError::Error(err)) => {
+            return Err(DataDirLockError::Io(format!(
+                "Failed to lock {}: {err}",
+                path.display()
+            )))
+        }
+    }
+    // Without process identity the OS lock still keeps other daemons out;
+    // the record just can't be checked, so `live_lock` never reports it.
+    let pid = std::process::id();
+    let holder = process_identity(pid).unwrap_or_else(|| ProcessIdentity {
+        pid,
+        exe_path: String::new(),
+        start_time: String::new(),
+    });
+    let lock = DataDirLock {
+        holder: holder.clone(),
+        locked_at_ms: now_ms(),
+    };
+    write_holder_file(dir, &lock).map_err(DataDirLockError::Io)?;
+    Ok(DataDirLockGuard {
+        _file: file,
+        holder_path: holder_path(dir),
+        holder,
+    })
+}
+
+/// Stops the live holder of the lock on `dir` and clears the lock, so a new
+/// daemon can start there. Does nothing when the lock is free or stale.
+#[allow(dead_code)]
+pub(crate) async fn take_over_data_dir(dir: &Path) -> Result<(), String> {
+    let Some(lock) = live_lock(dir) else {
+        return Ok(());
+    };
+    kill_pid_gracefully(lock.holder.pid, false)
+        .await
+        .map_err(|err| format!("Failed to stop pid {}: {err}", lock.holder.pid))?;
+    if is_alive(&lock.holder) {
+        return Err(format!(
+            "Pid {} is still running and holds {}.",
+            lock.holder.pid,
+            dir.display()
+        ));
+    }
+    let path = holder_path(dir);
+    if read_holder_file(&path).is_some_and(|current| current.holder == lock.holder) {
+        let _ = fs::remove_file(&path);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn lock_is_exclusive_and_stale_records_are_taken_over() {
+        let dir = std::env::temp_dir().join(format!("codex-monitor-lock-{}", uuid::Uuid::new_v4()));
+        let guard = acquire_data_dir_lock(&dir).expect("acquire");
+        let recorded = read_holder_file(&holder_path(&dir)).expect("holder record");
+        assert_eq!(recorded.holder.pid, std::process::id());
+        match acquire_data_dir_lock(&dir) {
+            Err(DataDirLockError::Locked(existing)) => {
+                if let Some(existing) = existing {
+                    assert_eq!(existing, recorded);
+                }
+            }
+            _ => panic!("expected the data dir to be locked"),
+        }
+        drop(guard);
+        assert!(!holder_path(&dir).exists());
+
+        // A record left by a crash no longer stops anyone: the OS lock went
+        // with the process.
+        let stale = DataDirLock {
+            holder: ProcessIdentity {
+                start_time: "0".to_string(),
+                ..recorded.holder.clone()
+            },
+            locked_at_ms: 1,
+        };
+        fs::write(
+            holder_path(&dir),
+            serde_json::to_string(&stale).expect("json"),
+        )
+        .expect("write stale record");
+        assert!(live_lock(&dir).is_none());
+        let guard = acquire_data_dir_lock(&dir).expect("take over stale record");
+        drop(guard);
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn a_lock_without_a_record_still_excludes() {
+        let dir = std::env::temp_dir().join(format!("codex-monitor-lock-{}", uuid::Uuid::new_v4()));
+        let guard = acquire_data_dir_lock(&dir).expect("acquire");
+        // As if the holder were between taking the lock and naming itself.
+        fs::remove_file(holder_path(&dir)).expect("remove record");
+        match acquire_data_dir_lock(&dir) {
+            Err(DataDirLockError::Locked(None)) => {}
+            _ => panic!("expected the data dir to be locked"),
+        }
+        drop(guard);
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
@@ -0,0 +1,131 @@
+use chrono::{Datelike, NaiveDateTime, Timelike};
+
+use crate::types::{DoNotDisturbSettings, QuietHoursWindow};
+
+/// Minutes past midnight for a `HH:MM` time.
+pub(crate) fn parse_minutes(value: &str) -> Option<u32> {
+    let (hours, minutes) = value.trim().split_once(':')?;
+    let hours: u32 = hours.parse().ok()?;
+    let minutes: u32 = minutes.parse().ok()?;
+    (hours < 24 && minutes < 60).then_some(hours * 60 + minutes)
+}
+
+fn covers_day(window: &QuietHoursWindow, weekday: u32) -> bool {
+    window.days.is_empty() || window.days.iter().any(|day| u32::from(*day) == weekday)
+}
+
+/// Whether local time `at` falls inside any of `windows`. Windows that do
+/// not parse are ignored.
+pub(crate) fn in_quiet_hours(windows: &[QuietHoursWindow], at: NaiveDateTime) -> bool {
+    let weekday = at.weekday().num_days_from_sunday();
+    let yesterday = (weekday + 6) % 7;
+    let minute = at.hour() * 60 + at.minute();
+    windows.iter().any(|window| {
+        let (Some(start), Some(end)) = (parse_minutes(&window.start), parse_minutes(&window.end))
+        else {
+            return false;
+        };
+        if start <= end {
+            covers_day(window, weekday) && start <= minute && minute < end
+        } else {
+            (covers_day(window, weekday) && minute >= start)
+                || (covers_day(window, yesterday) && minute < end)
+        }
+    })
+}
+
+/// Whether a notification may be shown now. A snooze holds back everything
+/// but urgent notifications the user lets through; quiet hours and screen
+/// sharing only count while Do Not Disturb is on.
+#[allow(dead_code)]
+pub(crate) fn notification_allowed(
+    settings: &DoNotDisturbSettings,
+    snoozed_until_ms: Option<i64>,
+    now_ms: i64,
+    at: NaiveDateTime,
+    screen_sharing: bool,
+    urgent: bool,
+) -> bool {
+    if urgent && settings.allow_urgent {
+        return true;
+    }
+    if snoozed_until_ms.is_some_and(|until| now_ms < until) {
+        return false;
+    }
+    if !settings.enabled {
+        return true;
+    }
+    if settings.suppress_while_screen_sharing && screen_sharing {
+        return false;
+    }
+    !in_quiet_hours(&settings.windows, at)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDate;
+
+    fn window(days: &[u8], start: &str, end: &str) -> QuietHoursWindow {
+        QuietHoursWindow {
+            days: days.to_vec(),
+            start: start.to_string(),
+            end: end.to_string(),
+        }
+    }
+
+    // 2026-10-16 is a Friday.
+    fn at(day: u32, hour: u32, minute: u32) -> NaiveDateTime {
+        NaiveDate::from_ymd_opt(2026, 10, day)
+            .and_then(|date| date.and_hms_opt(hour, minute, 0))
+            .expect("valid time")
+    }
+
+    #[test]
+    fn times_parse_as_minutes() {
+        assert_eq!(parse_minutes("00:00"), Some(0));
+        assert_eq!(parse_minutes("22:30"), Some(1350));
+        assert_eq!(parse_minutes("24:00"), None);
+        assert_eq!(parse_minutes("7pm"), None);
+    }
+
+    #[test]
+    fn windows_past_midnight_belong_to_their_start_day() {
+        let weeknights = [window(&[1, 2, 3, 4, 5], "22:00", "07:00")];
+        assert!(in_quiet_hours(&weeknights, at(16, 23, 0)));
+        assert!(in_quiet_hours(&weeknights, at(17, 6, 59)));
+        assert!(!in_quiet_hours(&weeknights, at(17, 7, 0)));
+        assert!(!in_quiet_hours(&weeknights, at(17, 23, 0)));
+        assert!(!in_quiet_hours(&weeknights, at(16, 12, 0)));
+
+        let lunch = [window(&[], "12:00", "13:00")];
+        assert!(in_quiet_hours(&lunch, at(18, 12, 30)));
+        assert!(!in_quiet_hours(&lunch, at(18, 13, 0)));
+    }
+
+    #[test]
+    fn snoozes_and_quiet_hours_let_allowed_urgent_notifications_through() {
+        let mut settings = DoNotDisturbSettings {
+            enabled: true,
+            windows: vec![window(&[], "09:00", "17:00")],
+            suppress_while_screen_sharing: true,
+            allow_urgent: false,
+        };
+        let noon = at(16, 12, 0);
+        let evening = at(16, 19, 0);
+        let allowed = |settings: &DoNotDisturbSettings, snoozed_until_ms, at, sharing, urgent| {
+            notification_allowed(settings, snoozed_until_ms, 5, at, sharing, urgent)
+        };
+        assert!(!allowed(&settings, None, noon, false, true));
+        assert!(allowed(&settings, None, evening, false, false));
+        assert!(!allowed(&settings, None, evening, true, false));
+        assert!(!allowed(&settings, Some(10), evening, false, false));
+        assert!(allowed(&settings, Some(5), evening, false, false));
+
+        settings.allow_urgent = true;
+        assert!(allowed(&settings, Some(10), noon, true, true));
+
+        settings.enabled = false;
+        assert!(allowed(&settings, None, noon, true, false));
+    }
+}
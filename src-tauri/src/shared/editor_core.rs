@@ -0,0 +1,298 @@
+//! Opens files, and a session's changes to a file, in the user's editor.
+//! Launchers come from `editorLaunchers`, or are the built-in VS Code,
+//! Cursor and IntelliJ IDEA commands; each runs on the host that received
+//! the request, so a remote client opens files on the daemon's desktop.
+
+use std::path::{Component, Path, PathBuf};
+use std::process::Stdio;
+
+use crate::shared::codex_session_core::{get_codex_session_core, SessionRegistry};
+use crate::shared::git_core::{run_git_command, run_git_command_bytes};
+use crate::shared::process_core::tokio_command;
+#[cfg(target_os = "windows")]
+use crate::shared::process_core::{build_cmd_c_command, resolve_windows_executable};
+#[cfg(not(target_os = "windows"))]
+use crate::shared::workspaces_core::find_executable_in_path;
+use crate::types::{AppSettings, EditorInfo, EditorKind, EditorLauncher};
+
+const EDITOR_DIFFS_DIR: &str = "editor-diffs";
+
+fn builtin_launchers() -> Vec<EditorLauncher> {
+    [
+        ("vscode", "VS Code", "code", EditorKind::Vscode),
+        ("cursor", "Cursor", "cursor", EditorKind::Vscode),
+        ("idea", "IntelliJ IDEA", "idea", EditorKind::Jetbrains),
+    ]
+    .into_iter()
+    .map(|(id, label, command, kind)| EditorLauncher {
+        id: id.to_string(),
+        label: label.to_string(),
+        command: command.to_string(),
+        kind,
+    })
+    .collect()
+}
+
+fn configured_launchers(settings: &AppSettings) -> Vec<EditorLauncher> {
+    if settings.editor_launchers.is_empty() {
+        builtin_launchers()
+    } else {
+        settings.editor_launchers.clone()
+    }
+}
+
+fn resolve_command(command: &str) -> Option<PathBuf> {
+    #[cfg(target_os = "windows")]
+    return resolve_windows_executable(command, None);
+    #[cfg(not(target_os = "windows"))]
+    return find_executable_in_path(command);
+}
+
+pub(crate) fn list_editors_core(settings: &AppSettings) -> Vec<EditorInfo> {
+    configured_launchers(settings)
+        .into_iter()
+        .map(|launcher| EditorInfo {
+            available: resolve_command(&launcher.command).is_some(),
+            label: if launcher.label.trim().is_empty() {
+                launcher.id.clone()
+            } else {
+                launcher.label
+            },
+            id: launcher.id,
+            command: launcher.command,
+            kind: launcher.kind,
+        })
+        .collect()
+}
+
+/// The preferred launcher when set, otherwise the first one whose command
+/// `resolve` finds.
+fn pick_launcher(
+    launchers: Vec<EditorLauncher>,
+    preferred: Option<&str>,
+    resolve: impl Fn(&str) -> Option<PathBuf>,
+) -> Result<(EditorLauncher, PathBuf), String> {
+    if let Some(preferred) = preferred.map(str::trim).filter(|id| !id.is_empty()) {
+        let launcher = launchers
+            .into_iter()
+            .find(|launcher| launcher.id == preferred)
+            .ok_or_else(|| format!("Editor `{preferred}` is not configured"))?;
+        let program = resolve(&launcher.command)
+            .ok_or_else(|| format!("`{}` was not found on PATH", launcher.command))?;
+        return Ok((launcher, program));
+    }
+    launchers
+        .into_iter()
+        .find_map(|launcher| {
+            let program = resolve(&launcher.command)?;
+            Some((launcher, program))
+        })
+        .ok_or_else(|| "No editor was found on PATH; configure one in settings".to_string())
+}
+
+fn open_args(kind: EditorKind, path: &str, line: Option<u32>) -> Vec<String> {
+    let Some(line) = line.filter(|line| *line > 0) else {
+        return vec![path.to_string()];
+    };
+    match kind {
+        EditorKind::Vscode => vec!["--goto".to_string(), format!("{path}:{line}")],
+        EditorKind::Jetbrains => vec!["--line".to_string(), line.to_string(), path.to_string()],
+        EditorKind::Other => vec![path.to_string()],
+    }
+}
+
+fn diff_args(kind: EditorKind, left: &str, right: &str) -> Result<Vec<String>, String> {
+    let flag = match kind {
+        EditorKind::Vscode => "--diff",
+        EditorKind::Jetbrains => "diff",
+        EditorKind::Other => return Err("This editor cannot open diffs".to_string()),
+    };
+    Ok(vec![flag.to_string(), left.to_string(), right.to_string()])
+}
+
+/// Starts the editor without waiting for it; editor CLIs hand off to a
+/// running window, and tokio reaps the child once it exits.
+fn launch(program: &Path, args: &[String]) -> Result<(), String> {
+    #[cfg(target_os = "windows")]
+    let mut command = {
+        let is_script = program
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .is_some_and(|ext| ext.eq_ignore_ascii_case("cmd") || ext.eq_ignore_ascii_case("bat"));
+        if is_script {
+            let mut command = tokio_command("cmd");
+            command.args(["/D", "/S", "/C"]);
+            command.raw_arg(build_cmd_c_command(program, args)?);
+            command
+        } else {
+            let mut command = tokio_command(program);
+            command.args(args);
+            command
+        }
+    };
+    #[cfg(not(target_os = "windows"))]
+    let mut command = {
+        let mut command = tokio_command(program);
+        command.args(args);
+        command
+    };
+    command
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()
+        .map(drop)
+        .map_err(|err| format!("Failed to start editor: {err}"))
+}
+
+pub(crate) async fn open_in_editor_core(
+    settings: &AppSettings,
+    path: String,
+    line: Option<u32>,
+) -> Result<(), String> {
+    if !Path::new(&path).exists() {
+        return Err(format!("`{path}` does not exist"));
+    }
+    let (launcher, program) = pick_launcher(
+        configured_launchers(settings),
+        settings.preferred_editor.as_deref(),
+        resolve_command,
+    )?;
+    launch(&program, &open_args(launcher.kind, &path, line))
+}
+
+/// A path inside the session's checkout, refusing anything that would
+/// leave it.
+fn checkout_relative_path(file: &str) -> Result<PathBuf, String> {
+    let path = Path::new(file.trim());
+    let inside = !path.as_os_str().is_empty()
+        && path
+            .components()
+            .all(|component| matches!(component, Component::Normal(_) | Component::CurDir));
+    if inside {
+        Ok(path.to_path_buf())
+    } else {
+        Err("File must be a path relative to the session's checkout".to_string())
+    }
+}
+
+/// Opens the editor's diff view of `file` as it was before the session and
+/// as it is now. A worktree session is compared with the commit its branch
+/// started from, any other session with its project's `HEAD`.
+pub(crate) async fn open_diff_in_editor_core(
+    registry: &SessionRegistry,
+    data_dir: &Path,
+    settings: &AppSettings,
+    session_id: String,
+    file: String,
+) -> Result<(), String> {
+    let relative = checkout_relative_path(&file)?;
+    let session = get_codex_session_core(registry, session_id.clone())
+        .await?
+        .session;
+    let (checkout, base) = match &session.worktree {
+        Some(worktree) => (
+            PathBuf::from(&worktree.worktree_path),
+            worktree.base_commit.clone(),
+        ),
+        None => (PathBuf::from(&session.project_path), "HEAD".to_string()),
+    };
+    let (launcher, program) = pick_launcher(
+        configured_launchers(settings),
+        settings.preferred_editor.as_deref(),
+        resolve_command,
+    )?;
+    let git_path = relative.to_string_lossy().replace('\\', "/");
+    let tracked = run_git_command(
+        &checkout,
+        &["ls-tree", "--name-only", &base, "--", &git_path],
+    )
+    .await?;
+    let before = if tracked.is_empty() {
+        Vec::new()
+    } else {
+        run_git_command_bytes(&checkout, &["show", &format!("{base}:./{git_path}")]).await?
+    };
+
+    let diff_dir = data_dir.join(EDITOR_DIFFS_DIR).join(&session_id);
+    let before_path = diff_dir.join("before").join(&relative);
+    let mut after_path = checkout.join(&relative);
+    if !after_path.is_file() {
+        // Deleted by the session; compare against an empty file.
+        after_path = diff_dir.join("after").join(&relative);
+        write_snapshot(&after_path, &[])?;
+    }
+    write_snapshot(&before_path, &before)?;
+    let args = diff_args(
+        launcher.kind,
+        &before_path.to_string_lossy(),
+        &after_path.to_string_lossy(),
+    )?;
+    launch(&program, &args)
+}
+
+fn write_snapshot(path: &Path, contents: &[u8]) -> Result<(), String> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)
+            .map_err(|err| format!("Failed to create {}: {err}", parent.display()))?;
+    }
+    std::fs::write(path, contents)
+        .map_err(|err| format!("Failed to write {}: {err}", path.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn editors_get_their_own_line_and_diff_arguments() {
+        assert_eq!(
+            open_args(EditorKind::Vscode, "/src/main.rs", Some(12)),
+            ["--goto", "/src/main.rs:12"]
+        );
+        assert_eq!(
+            open_args(EditorKind::Jetbrains, "/src/main.rs", Some(12)),
+            ["--line", "12", "/src/main.rs"]
+        );
+        assert_eq!(
+            open_args(EditorKind::Other, "/src/main.rs", Some(12)),
+            ["/src/main.rs"]
+        );
+        assert_eq!(
+            open_args(EditorKind::Vscode, "/src/main.rs", Some(0)),
+            ["/src/main.rs"]
+        );
+        assert_eq!(
+            diff_args(EditorKind::Jetbrains, "a", "b"),
+            Ok(vec!["diff".to_string(), "a".to_string(), "b".to_string()])
+        );
+        assert!(diff_args(EditorKind::Other, "a", "b").is_err());
+
+        assert!(checkout_relative_path("src/lib.rs").is_ok());
+        assert!(checkout_relative_path("../secrets").is_err());
+        assert!(checkout_relative_path("/etc/passwd").is_err());
+        assert!(checkout_relative_path(" ").is_err());
+    }
+
+    #[test]
+    fn the_preferred_editor_wins_over_path_order() {
+        let on_path = |command: &str| {
+            ["cursor", "idea"]
+                .contains(&command)
+                .then(|| PathBuf::from(format!("/usr/bin/{command}")))
+        };
+        let (launcher, program) =
+            pick_launcher(builtin_launchers(), None, on_path).expect("launcher");
+        assert_eq!(launcher.id, "cursor");
+        assert_eq!(program, PathBuf::from("/usr/bin/cursor"));
+
+        let (launcher, _) =
+            pick_launcher(builtin_launchers(), Some("idea"), on_path).expect("launcher");
+        assert_eq!(launcher.kind, EditorKind::Jetbrains);
+        assert_eq!(
+            pick_launcher(builtin_launchers(), Some("vscode"), on_path).map(|(l, _)| l.id),
+            Err("`code` was not found on PATH".to_string())
+        );
+        assert!(pick_launcher(builtin_launchers(), None, |_| None).is_err());
+    }
+}
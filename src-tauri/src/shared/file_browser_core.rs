@@ -0,0 +1,264 @@
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{Read, Seek, SeekFrom};
+use std::path::{Path, PathBuf};
+use std::time::UNIX_EPOCH;
+
+use tokio::sync::Mutex;
+
+use crate::types::{RemoteDirEntry, RemoteDirListing, RemoteFileChunk, WorkspaceEntry};
+use crate::utils::normalize_git_path;
+
+const MAX_DIR_ENTRIES: usize = 2_000;
+const MAX_READ_BYTES: u64 = 512 * 1024;
+const BINARY_SNIFF_BYTES: usize = 8 * 1024;
+
+async fn resolve_workspace_root(
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    workspace_id: &str,
+) -> Result<PathBuf, String> {
+    let workspaces = workspaces.lock().await;
+    let entry = workspaces
+        .get(workspace_id)
+        .ok_or_else(|| "workspace not found".to_string())?;
+    Ok(PathBuf::from(&entry.path))
+}
+
+/// Resolves `rel_path` against the canonical workspace root and rejects
+/// anything (including symlink targets) that escapes it.
+pub(crate) fn resolve_within_root(
+    root: &Path,
+    rel_path: &str,
+) -> Result<(PathBuf, PathBuf), String> {
+    let canonical_root = root
+        .canonicalize()
+        .map_err(|err| format!("Failed to resolve workspace root: {err}"))?;
+    let trimmed = rel_path.trim();
+    if Path::new(trimmed).is_absolute() {
+        return Err("Path must be relative to the workspace root".to_string());
+    }
+    let canonical_path = canonical_root
+        .join(trimmed)
+        .canonicalize()
+        .map_err(|err| format!("Failed to open path: {err}"))?;
+    if !canonical_path.starts_with(&canonical_root) {
+        return Err("Invalid file path".to_string());
+    }
+    Ok((canonical_root, canonical_path))
+}
+
+fn relative_display(root: &Path, path: &Path) -> String {
+    path.strip_prefix(root)
+        .map(|rel| normalize_git_path(&rel.to_string_lossy()))
+        .unwrap_or_default()
+}
+
+fn modified_ms(metadata: &std::fs::Metadata) -> Option<i64> {
+    metadata
+        .modified()
+        .ok()
+        .and_then(|time| time.duration_since(UNIX_EPOCH).ok())
+        .map(|duration| duration.as_millis() as i64)
+}
+
+pub(crate) fn list_dir_inner(root: &Path, rel_path: &str) -> Result<RemoteDirListing, String> {
+    let (canonical_root, dir) = resolve_within_root(root, rel_path)?;
+    if !dir.is_dir() {
+        return Err("Path is not a directory".to_string());
+    }
+    let read_dir =
+        std::fs::read_dir(&dir).map_err(|err| format!("Failed to read directory: {err}"))?;
+
+    let mut entries = Vec::new();
+    let mut truncated = false;
+    for entry in read_dir.flatten() {
+        if entries.len() >= MAX_DIR_ENTRIES {
+            truncated = true;
+            break;
+        }
+        let Ok(file_type) = entry.file_type() else {
+            continue;
+        };
+        let kind = if file_type.is_symlink() {
+            "symlink"
+        } else if file_type.is_dir() {
+            "dir"
+        } else {
+            "file"
+        };
+        let metadata = entry.metadata().ok();
+        let name = entry.file_name().to_string_lossy().to_string();
+        entries.push(RemoteDirEntry {
+            path: relative_display(&canonical_root, &dir.join(&name)),
+            name,
+            kind: kind.to_string(),
+            size: metadata
+                .as_ref()
+                .filter(|metadata| metadata.is_file())
+                .map(|metadata| metadata.len()),
+            modified_ms: metadata.as_ref().and_then(modified_ms),
+        });
+    }
+
+    entries.sort_by(|a, b| {
+        (a.kind != "dir")
+            .cmp(&(b.kind != "dir"))
+            .then_with(|| a.name.to_lowercase().cmp(&b.name.to_lowercase()))
+    });
+
+    Ok(RemoteDirListing {
+        path: relative_display(&canonical_root, &dir),
+        entries,
+        truncated,
+    })
+}
+
+fn looks_binary(bytes: &[u8]) -> bool {
+    let sniff = &bytes[..bytes.len().min(BINARY_SNIFF_BYTES)];
+    if sniff.contains(&0) {
+        return true;
+    }
+    match std::str::from_utf8(sniff) {
+        Ok(_) => false,
+        // A multi-byte character cut off at the sniff boundary is still text.
+        Err(err) => err.error_len().is_some(),
+    }
+}
+
+pub(crate) fn read_file_inner(
+    root: &Path,
+    rel_path: &str,
+    offset: Option<u64>,
+    length: Option<u64>,
+) -> Result<RemoteFileChunk, String> {
+    let (canonical_root, path) = resolve_within_root(root, rel_path)?;
+    let metadata =
+        std::fs::metadata(&path).map_err(|err| format!("Failed to read file metadata: {err}"))?;
+    if !metadata.is_file() {
+        return Err("Path is not a file".to_string());
+    }
+    let size = metadata.len();
+    let offset = offset.unwrap_or(0).min(size);
+    let length = length.unwrap_or(MAX_READ_BYTES).min(MAX_READ_BYTES);
+
+    let mut file = File::open(&path).map_err(|err| format!("Failed to open file: {err}"))?;
+    file.seek(SeekFrom::Start(offset))
+        .map_err(|err| format!("Failed to read file: {err}"))?;
+    let mut buffer = Vec::new();
+    file.take(length)
+        .read_to_end(&mut buffer)
+        .map_err(|err| format!("Failed to read file: {err}"))?;
+
+    let truncated = offset + (buffer.len() as u64) < size;
+    let binary = looks_binary(&buffer);
+    let content = if binary {
+        None
+    } else {
+        Some(String::from_utf8_lossy(&buffer).into_owned())
+    };
+
+    Ok(RemoteFileChunk {
+        path: relative_display(&canonical_root, &path),
+        size,
+        offset,
+        length: buffer.len() as u64,
+        binary,
+        truncated,
+        content,
+    })
+}
+
+pub(crate) async fn remote_list_dir_core(
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    workspace_id: String,
+    rel_path: Option<String>,
+) -> Result<RemoteDirListing, String> {
+    let root = resolve_workspace_root(workspaces, &workspace_id).await?;
+    let rel_path = rel_path.unwrap_or_default();
+    tokio::task::spawn_blocking(move || list_dir_inner(&root, &rel_path))
+        .await
+        .map_err(|err| err.to_string())?
+}
+
+pub(crate) async fn remote_read_file_core(
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    workspace_id: String,
+    rel_path: String,
+    offset: Option<u64>,
+    length: Option<u64>,
+) -> Result<RemoteFileChunk, String> {
+    let root = resolve_workspace_root(workspaces, &workspace_id).await?;
+    tokio::task::spawn_blocking(move || read_file_inner(&root, &rel_path, offset, length))
+        .await
+        .map_err(|err| err.to_string())?
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn make_root(prefix: &str) -> PathBuf {
+        let unique = std::time::SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("time")
+            .as_nanos();
+        let root = std::env::temp_dir().join(format!(
+            "codex-monitor-{prefix}-{}-{unique}",
+            std::process::id()
+        ));
+        std::fs::create_dir_all(root.join("src")).expect("create root");
+        root
+    }
+
+    #[test]
+    fn list_dir_puts_directories_first_and_reports_relative_paths() {
+        let root = make_root("browse-list");
+        std::fs::write(root.join("b.txt"), "b").expect("write");
+        std::fs::write(root.join("src/main.rs"), "fn main() {}").expect("write");
+
+        let listing = list_dir_inner(&root, "").expect("list root");
+        assert_eq!(listing.path, "");
+        let names: Vec<_> = listing
+            .entries
+            .iter()
+            .map(|entry| entry.name.as_str())
+            .collect();
+        assert_eq!(names, vec!["src", "b.txt"]);
+        assert_eq!(listing.entries[1].size, Some(1));
+
+        let nested = list_dir_inner(&root, "src").expect("list src");
+        assert_eq!(nested.path, "src");
+        assert_eq!(nested.entries[0].path, "src/main.rs");
+
+        let _ = std::fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn paths_outside_root_are_rejected() {
+        let root = make_root("browse-escape");
+        assert!(list_dir_inner(&root, "..").is_err());
+        assert!(list_dir_inner(&root, "/").is_err());
+        assert!(read_file_inner(&root, "../outside.txt", None, None).is_err());
+        let _ = std::fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn read_file_supports_ranges_and_binary_detection() {
+        let root = make_root("browse-read");
+        std::fs::write(root.join("notes.txt"), "hello world").expect("write");
+        std::fs::write(root.join("blob.bin"), [0u8, 159, 146, 150]).expect("write");
+
+        let chunk = read_file_inner(&root, "notes.txt", Some(6), Some(3)).expect("read");
+        assert_eq!(chunk.content.as_deref(), Some("wor"));
+        assert_eq!(chunk.size, 11);
+        assert!(chunk.truncated);
+        assert!(!chunk.binary);
+
+        let binary = read_file_inner(&root, "blob.bin", None, None).expect("read binary");
+        assert!(binary.binary);
+        assert!(binary.content.is_none());
+
+        assert!(read_file_inner(&root, "src", None, None).is_err());
+        let _ = std::fs::remove_dir_all(&root);
+    }
+}
@@ -7,7 +7,8 @@ use crate::codex::home as codex_home;
 use crate::files::io::TextFileResponse;
 use crate::files::ops::{read_with_policy, write_with_policy};
 use crate::files::policy::{policy_for, FileKind, FileScope};
-use crate::types::WorkspaceEntry;
+use crate::shared::config_changelog_core::write_global_config;
+use crate::types::{ConfigChangeSource, WorkspaceEntry};
 
 fn resolve_default_codex_home() -> Result<PathBuf, String> {
     codex_home::resolve_default_codex_home()
@@ -59,5 +60,8 @@ pub(crate) async fn file_write_core(
 ) -> Result<(), String> {
     let policy = policy_for(scope, kind)?;
     let root = resolve_root_core(workspaces, scope, workspace_id.as_deref()).await?;
+    if scope == FileScope::Global && kind == FileKind::Config {
+        return write_global_config(&root, &content, ConfigChangeSource::Editor);
+    }
     write_with_policy(&root, policy, &content)
 }
@@ -2,7 +2,7 @@ use std::collections::HashMap;
 use std::path::{Path, PathBuf};
 
 use serde_json::Value;
-use tokio::sync::Mutex;
+use tokio::sync::{Mutex, RwLock};
 
 use crate::types::{
     AppSettings, GitCommitDiff, GitFileDiff, GitHubIssuesResponse, GitHubPullRequestComment,
@@ -71,7 +71,7 @@ pub(crate) async fn list_git_roots_core(
 
 pub(crate) async fn get_git_diffs_core(
     workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
-    app_settings: &Mutex<AppSettings>,
+    app_settings: &RwLock<AppSettings>,
     workspace_id: String,
 ) -> Result<Vec<GitFileDiff>, String> {
     diff::get_git_diffs_inner(workspaces, app_settings, workspace_id).await
@@ -87,7 +87,7 @@ pub(crate) async fn get_git_log_core(
 
 pub(crate) async fn get_git_commit_diff_core(
     workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
-    app_settings: &Mutex<AppSettings>,
+    app_settings: &RwLock<AppSettings>,
     workspace_id: String,
     sha: String,
 ) -> Result<Vec<GitCommitDiff>, String> {
@@ -7,7 +7,7 @@ use std::process::Stdio;
 use base64::{engine::general_purpose::STANDARD, Engine as _};
 use git2::{DiffOptions, Repository, Status, StatusOptions};
 use serde_json::{json, Value};
-use tokio::sync::Mutex;
+use tokio::sync::{Mutex, RwLock};
 
 use crate::git_utils::{
     diff_patch_to_string, diff_stats_for_path, image_mime_type, resolve_git_root,
@@ -464,13 +464,13 @@ pub(super) async fn get_git_status_inner(
 
 pub(super) async fn get_git_diffs_inner(
     workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
-    app_settings: &Mutex<AppSettings>,
+    app_settings: &RwLock<AppSettings>,
     workspace_id: String,
 ) -> Result<Vec<GitFileDiff>, String> {
     let entry = workspace_entry_for_id(workspaces, &workspace_id).await?;
     let repo_root = resolve_git_root(&entry)?;
     let ignore_whitespace_changes = {
-        let settings = app_settings.lock().await;
+        let settings = app_settings.read().await;
         settings.git_diff_ignore_whitespace_changes
     };
 
@@ -617,14 +617,14 @@ pub(super) async fn get_git_diffs_inner(
 
 pub(super) async fn get_git_commit_diff_inner(
     workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
-    app_settings: &Mutex<AppSettings>,
+    app_settings: &RwLock<AppSettings>,
     workspace_id: String,
     sha: String,
 ) -> Result<Vec<GitCommitDiff>, String> {
     let entry = workspace_entry_for_id(workspaces, &workspace_id).await?;
 
     let ignore_whitespace_changes = {
-        let settings = app_settings.lock().await;
+        let settings = app_settings.read().await;
         settings.git_diff_ignore_whitespace_changes
     };
 
@@ -5,7 +5,7 @@ use std::path::{Path, PathBuf};
 use git2::Repository;
 use serde_json::Value;
 use tokio::runtime::Runtime;
-use tokio::sync::Mutex;
+use tokio::sync::{Mutex, RwLock};
 
 use crate::types::{AppSettings, WorkspaceEntry, WorkspaceKind, WorkspaceSettings};
 
@@ -209,7 +209,7 @@ fn get_git_diffs_omits_global_ignored_paths() {
     let mut entries = HashMap::new();
     entries.insert("w1".to_string(), workspace);
     let workspaces = Mutex::new(entries);
-    let app_settings = Mutex::new(AppSettings::default());
+    let app_settings = RwLock::new(AppSettings::default());
 
     let runtime = Runtime::new().expect("create tokio runtime");
     let diffs = runtime
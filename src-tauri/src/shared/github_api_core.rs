@@ -0,0 +1,161 @@
+//! GitHub calls made on behalf of a repository checkout: through the `gh`
+//! CLI when it is installed, otherwise straight to the REST API with a
+//! token kept in the keychain.
+
+use std::path::PathBuf;
+use std::time::Duration;
+
+use reqwest::header::{ACCEPT, AUTHORIZATION, CONTENT_TYPE, USER_AGENT};
+use reqwest::Method;
+use serde_json::Value;
+
+use crate::git_utils::parse_github_repo;
+use crate::shared::git_core::run_git_command;
+use crate::shared::process_core::tokio_command;
+use crate::shared::process_env_core::read_keychain_secret;
+
+/// Keychain account (service `CodexMonitor`) holding the GitHub token used
+/// when the `gh` CLI is not installed.
+pub(crate) const GITHUB_TOKEN_KEYCHAIN_ACCOUNT: &str = "github-token";
+const GITHUB_API_URL: &str = "https://api.github.com";
+const GITHUB_API_TIMEOUT: Duration = Duration::from_secs(30);
+
+pub(crate) fn command_failure_detail(stdout: &[u8], stderr: &[u8], fallback: &str) -> String {
+    let stderr = String::from_utf8_lossy(stderr);
+    let stdout = String::from_utf8_lossy(stdout);
+    let detail = if stderr.trim().is_empty() {
+        stdout.trim()
+    } else {
+        stderr.trim()
+    };
+    if detail.is_empty() {
+        fallback.to_string()
+    } else {
+        detail.to_string()
+    }
+}
+
+/// `owner/name` of the checkout's `origin` remote.
+pub(crate) async fn origin_github_repo(repo_root: &PathBuf) -> Result<String, String> {
+    let remote = run_git_command(repo_root, &["remote", "get-url", "origin"]).await?;
+    parse_github_repo(&remote).ok_or_else(|| "Remote is not a GitHub repository.".to_string())
+}
+
+/// Runs `gh` with `args` in `dir` and returns its stdout; `None` when `gh`
+/// is not installed.
+pub(crate) async fn run_gh(dir: &PathBuf, args: &[&str]) -> Result<Option<String>, String> {
+    let output = match tokio_command("gh")
+        .args(args)
+        .current_dir(dir)
+        .output()
+        .await
+    {
+        Ok(output) => output,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+        Err(err) => return Err(format!("Failed to run gh: {err}")),
+    };
+    if !output.status.success() {
+        return Err(command_failure_detail(
+            &output.stdout,
+            &output.stderr,
+            "GitHub CLI command failed.",
+        ));
+    }
+    Ok(Some(String::from_utf8_lossy(&output.stdout).to_string()))
+}
+
+/// The body of a REST API answer, or the error GitHub gave for it.
+pub(crate) fn api_response(status: u16, body: &str) -> Result<Value, String> {
+    let value = serde_json::from_str::<Value>(body).unwrap_or(Value::Null);
+    if !(200..300).contains(&status) {
+        let message = value["message"].as_str().unwrap_or("request failed");
+        return Err(format!("GitHub API answered with HTTP {status}: {message}"));
+    }
+    Ok(value)
+}
+
+/// Calls the REST API at `path` (such as `repos/o/r/pulls`) with the
+/// keychain token.
+pub(crate) async fn api_request(
+    method: Method,
+    path: &str,
+    body: Option<&Value>,
+) -> Result<Value, String> {
+    let token = tokio::task::spawn_blocking(|| read_keychain_secret(GITHUB_TOKEN_KEYCHAIN_ACCOUNT))
+        .await
+        .map_err(|err| err.to_string())?
+        .map_err(|err| format!("gh is not installed and no GitHub token is available: {err}"))?;
+    let client = reqwest::Client::builder()
+        .timeout(GITHUB_API_TIMEOUT)
+        .build()
+        .map_err(|err| format!("Failed to configure GitHub client: {err}"))?;
+    let mut request = client
+        .request(method, format!("{GITHUB_API_URL}/{path}"))
+        .header(AUTHORIZATION, format!("Bearer {}", token.trim()))
+        .header(ACCEPT, "application/vnd.github+json")
+        .header(USER_AGENT, "CodexMonitor");
+    if let Some(body) = body {
+        request = request
+            .header(CONTENT_TYPE, "application/json")
+            .body(body.to_string());
+    }
+    let response = request
+        .send()
+        .await
+        .map_err(|err| format!("GitHub API request failed: {err}"))?;
+    let status = response.status().as_u16();
+    let text = response
+        .text()
+        .await
+        .map_err(|err| format!("GitHub API request failed: {err}"))?;
+    api_response(status, &text)
+}
+
+/// GETs `path` with `gh api`, falling back to the REST API.
+pub(crate) async fn api_get(dir: &PathBuf, path: &str) -> Result<Value, String> {
+    match run_gh(dir, &["api", path]).await? {
+        Some(stdout) => {
+            serde_json::from_str(&stdout).map_err(|err| format!("gh returned invalid JSON: {err}"))
+        }
+        None => api_request(Method::GET, path, None).await,
+    }
+}
+
+/// Escapes a branch name for use as one path segment.
+pub(crate) fn encode_path_segment(value: &str) -> String {
+    value
+        .bytes()
+        .map(|byte| match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                (byte as char).to_string()
+            }
+            _ => format!("%{byte:02X}"),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn api_errors_and_path_segments() {
+        assert_eq!(
+            api_response(201, r#"{"html_url":"https://github.com/o/r/pull/8"}"#),
+            Ok(json!({ "html_url": "https://github.com/o/r/pull/8" }))
+        );
+        assert_eq!(
+            api_response(422, r#"{"message":"Validation Failed"}"#),
+            Err("GitHub API answered with HTTP 422: Validation Failed".to_string())
+        );
+        assert_eq!(
+            api_response(502, "<html>"),
+            Err("GitHub API answered with HTTP 502: request failed".to_string())
+        );
+        assert_eq!(
+            encode_path_segment("codex-session/abc 1"),
+            "codex-session%2Fabc%201"
+        );
+    }
+}
@@ -0,0 +1,323 @@
+use std::path::{Path, PathBuf};
+use std::sync::Mutex as StdMutex;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use sha2::{Digest, Sha256};
+
+use crate::storage::{read_guest_tokens, write_guest_tokens};
+use crate::types::{GuestToken, GuestTokenRecord, IssuedGuestToken};
+
+const GUEST_TOKENS_FILENAME: &str = "guest-tokens.json";
+const GUEST_TOKEN_PREFIX: &str = "cmg_";
+const MIN_TTL_SECONDS: u64 = 60;
+const MAX_TTL_SECONDS: u64 = 30 * 24 * 60 * 60;
+
+pub(crate) const GUEST_SCOPE_READ: &str = "read";
+pub(crate) const GUEST_SCOPE_WRITE: &str = "write";
+
+/// Methods a `read` guest may call. Everything here only observes state;
+/// `connect_workspace` and `resume_thread` are included because a client
+/// cannot list or open threads without them.
+const READ_SCOPE_METHODS: &[&str] = &[
+    "ping",
+    "daemon_info",
+    "rpc_timeouts",
+    "activity_report",
+    "apps_list",
+    "collaboration_mode_list",
+    "connect_workspace",
+    "experimental_feature_list",
+    "get_codex_session",
+    "get_config_model",
+    "get_git_commit_diff",
+    "get_git_diffs",
+    "get_git_log",
+    "get_git_remote",
+    "get_git_status",
+    "get_process_stats",
+    "get_tag_assignments",
+    "health",
+    "list_archives",
+    "list_codex_sessions",
+    "list_editors",
+    "list_git_branches",
+    "list_git_roots",
+    "list_mcp_server_status",
+    "list_plugins",
+    "list_schedules",
+    "list_session_templates",
+    "list_tags",
+    "list_threads",
+    "list_workspace_files",
+    "list_workspaces",
+    "local_usage_snapshot",
+    "model_list",
+    "read_thread",
+    "read_workspace_file",
+    "resume_thread",
+    "search_sessions",
+    "skills_list",
+    "thread_live_subscribe",
+    "thread_live_unsubscribe",
+    "worktree_setup_status",
+];
+
+/// Methods no guest may call regardless of scope: they expose the owner
+/// token or manage credentials and the daemon itself.
+const OWNER_ONLY_METHODS: &[&str] = &[
+    "create_guest_token",
+    "daemon_shutdown",
+    "decide_client_approval",
+    "decide_remote_action_confirmation",
+    "enable_plugin",
+    "get_app_settings",
+    "list_client_approvals",
+    "list_guest_tokens",
+    "list_remote_action_confirmations",
+    "reachability_probe",
+    "revoke_guest_token",
+    "settings_sync_snapshot",
+    "update_app_settings",
+];
+
+// Guards read-modify-write cycles of the guest token file.
+static GUEST_TOKENS_LOCK: StdMutex<()> = StdMutex::new(());
+
+fn guest_tokens_path(data_dir: &Path) -> PathBuf {
+    data_dir.join(GUEST_TOKENS_FILENAME)
+}
+
+fn now_ms() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_millis() as i64)
+        .unwrap_or(0)
+}
+
+fn hash_token(token: &str) -> String {
+    Sha256::digest(token.as_bytes())
+        .iter()
+        .map(|byte| format!("{byte:02x}"))
+        .collect()
+}
+
+fn normalize_scopes(scopes: Vec<String>) -> Result<Vec<String>, String> {
+    let mut normalized: Vec<String> = Vec::new();
+    for scope in scopes {
+        let scope = scope.trim().to_ascii_lowercase();
+        if scope != GUEST_SCOPE_READ && scope != GUEST_SCOPE_WRITE {
+            return Err(format!("Unknown guest scope: {scope}"));
+        }
+        if !normalized.contains(&scope) {
+            normalized.push(scope);
+        }
+    }
+    if normalized.is_empty() {
+        return Err("At least one scope is required".to_string());
+    }
+    normalized.sort();
+    Ok(normalized)
+}
+
+/// The narrowest scope that lets a guest call `method`, or `None` when only
+/// the owner may call it.
+#[allow(dead_code)]
+pub(crate) fn required_guest_scope(method: &str) -> Option<&'static str> {
+    if OWNER_ONLY_METHODS.contains(&method) {
+        None
+    } else if READ_SCOPE_METHODS.contains(&method) {
+        Some(GUEST_SCOPE_READ)
+    } else {
+        Some(GUEST_SCOPE_WRITE)
+    }
+}
+
+#[cfg(test)]
+fn guest_scope_allows(scopes: &[String], method: &str) -> bool {
+    scopes_cover(scopes, required_guest_scope(method))
+}
+
+/// Whether a guest with `scopes` may call a method needing `required`, as
+/// returned by `required_guest_scope`.
+fn scopes_cover(scopes: &[String], required: Option<&str>) -> bool {
+    let Some(required) = required else {
+        return false;
+    };
+    scopes
+        .iter()
+        .any(|scope| scope == GUEST_SCOPE_WRITE || scope == required)
+}
+
+fn read_active_tokens(path: &PathBuf, now: i64) -> Result<Vec<GuestTokenRecord>, String> {
+    let mut records = read_guest_tokens(path)?;
+    records.retain(|record| record.token.expires_at_ms > now);
+    Ok(records)
+}
+
+pub(crate) fn create_guest_token_core(
+    data_dir: &Path,
+    scopes: Vec<String>,
+    ttl_seconds: u64,
+    label: Option<String>,
+) -> Result<IssuedGuestToken, String> {
+    let scopes = normalize_scopes(scopes)?;
+    if !(MIN_TTL_SECONDS..=MAX_TTL_SECONDS).contains(&ttl_seconds) {
+        return Err(format!(
+            "Guest token lifetime must be between {MIN_TTL_SECONDS} and {MAX_TTL_SECONDS} seconds"
+        ));
+    }
+    let _guard = GUEST_TOKENS_LOCK.lock().map_err(|err| err.to_string())?;
+    let path = guest_tokens_path(data_dir);
+    let now = now_ms();
+    let mut records = read_active_tokens(&path, now)?;
+    let token = format!(
+        "{GUEST_TOKEN_PREFIX}{}{}",
+        uuid::Uuid::new_v4().simple(),
+        uuid::Uuid::new_v4().simple()
+    );
+    let guest = GuestToken {
+        id: uuid::Uuid::new_v4().to_string(),
+        label: label
+            .map(|value| value.trim().to_string())
+            .filter(|value| !value.is_empty()),
+        scopes,
+        created_at_ms: now,
+        expires_at_ms: now + (ttl_seconds as i64) * 1000,
+    };
+    records.push(GuestTokenRecord {
+        token: guest.clone(),
+        token_hash: hash_token(&token),
+    });
+    write_guest_tokens(&path, &records)?;
+    Ok(IssuedGuestToken { guest, token })
+}
+
+/// Lists unexpired guest tokens, newest first.
+pub(crate) fn list_guest_tokens_core(data_dir: &Path) -> Result<Vec<GuestToken>, String> {
+    let _guard = GUEST_TOKENS_LOCK.lock().map_err(|err| err.to_string())?;
+    let mut tokens: Vec<GuestToken> = read_active_tokens(&guest_tokens_path(data_dir), now_ms())?
+        .into_iter()
+        .map(|record| record.token)
+        .collect();
+    tokens.sort_by_key(|token| std::cmp::Reverse(token.created_at_ms));
+    Ok(tokens)
+}
+
+pub(crate) fn revoke_guest_token_core(data_dir: &Path, id: &str) -> Result<(), String> {
+    let _guard = GUEST_TOKENS_LOCK.lock().map_err(|err| err.to_string())?;
+    let path = guest_tokens_path(data_dir);
+    let mut records = read_active_tokens(&path, now_ms())?;
+    let before = records.len();
+    records.retain(|record| record.token.id != id);
+    if records.len() == before {
+        return Err("guest token not found".to_string());
+    }
+    write_guest_tokens(&path, &records)
+}
+
+/// Resolves a presented token to its unexpired guest record.
+#[allow(dead_code)]
+pub(crate) fn authenticate_guest_token(data_dir: &Path, token: &str) -> Option<GuestToken> {
+    if !token.starts_with(GUEST_TOKEN_PREFIX) {
+        return None;
+    }
+    let hash = hash_token(token);
+    let _guard = GUEST_TOKENS_LOCK.lock().ok()?;
+    read_active_tokens(&guest_tokens_path(data_dir), now_ms())
+        .ok()?
+        .into_iter()
+        .find(|record| record.token_hash == hash)
+        .map(|record| record.token)
+}
+
+/// Checks a guest call against the stored record on every request so that
+/// revocation and expiry apply to already-open connections.
+#[allow(dead_code)]
+pub(crate) fn authorize_guest_method(
+    data_dir: &Path,
+    guest_id: &str,
+    method: &str,
+) -> Result<(), String> {
+    authorize_guest_scope(data_dir, guest_id, method, required_guest_scope(method))
+}
+
+/// `authorize_guest_method` for a method whose scope is not in the lists
+/// above, such as a plugin method declaring its own.
+#[allow(dead_code)]
+pub(crate) fn authorize_guest_scope(
+    data_dir: &Path,
+    guest_id: &str,
+    method: &str,
+    required: Option<&str>,
+) -> Result<(), String> {
+    let _guard = GUEST_TOKENS_LOCK.lock().map_err(|err| err.to_string())?;
+    let record = read_active_tokens(&guest_tokens_path(data_dir), now_ms())?
+        .into_iter()
+        .find(|record| record.token.id == guest_id)
+        .ok_or_else(|| "guest token expired or revoked".to_string())?;
+    if scopes_cover(&record.token.scopes, required) {
+        Ok(())
+    } else {
+        Err(format!("guest token is not allowed to call {method}"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_dir() -> PathBuf {
+        std::env::temp_dir().join(format!("codex-monitor-guests-{}", uuid::Uuid::new_v4()))
+    }
+
+    #[test]
+    fn read_scope_blocks_mutations_and_owner_methods() {
+        let read = vec![GUEST_SCOPE_READ.to_string()];
+        let write = vec![GUEST_SCOPE_WRITE.to_string()];
+        assert!(guest_scope_allows(&read, "list_threads"));
+        assert!(!guest_scope_allows(&read, "send_user_message"));
+        assert!(guest_scope_allows(&write, "send_user_message"));
+        assert!(!guest_scope_allows(&write, "get_app_settings"));
+        assert!(!guest_scope_allows(&write, "create_guest_token"));
+        assert!(!guest_scope_allows(&write, "enable_plugin"));
+        assert!(guest_scope_allows(&read, "list_plugins"));
+        assert_eq!(required_guest_scope("list_threads"), Some(GUEST_SCOPE_READ));
+        assert_eq!(
+            required_guest_scope("send_user_message"),
+            Some(GUEST_SCOPE_WRITE)
+        );
+        assert_eq!(required_guest_scope("create_guest_token"), None);
+    }
+
+    #[test]
+    fn issued_tokens_authenticate_until_revoked() {
+        let data_dir = temp_dir();
+        assert!(create_guest_token_core(&data_dir, vec!["admin".to_string()], 600, None).is_err());
+        assert!(create_guest_token_core(&data_dir, vec!["read".to_string()], 5, None).is_err());
+
+        let issued = create_guest_token_core(
+            &data_dir,
+            vec!["READ".to_string(), "read".to_string()],
+            600,
+            Some(" Phone ".to_string()),
+        )
+        .expect("create");
+        assert_eq!(issued.guest.scopes, vec!["read".to_string()]);
+        assert_eq!(issued.guest.label.as_deref(), Some("Phone"));
+        let stored = std::fs::read_to_string(guest_tokens_path(&data_dir)).expect("read");
+        assert!(!stored.contains(&issued.token));
+
+        let guest = authenticate_guest_token(&data_dir, &issued.token).expect("authenticate");
+        assert_eq!(guest.id, issued.guest.id);
+        assert!(authenticate_guest_token(&data_dir, "cmg_wrong").is_none());
+        assert!(authorize_guest_method(&data_dir, &guest.id, "list_threads").is_ok());
+        assert!(authorize_guest_method(&data_dir, &guest.id, "remove_workspace").is_err());
+
+        assert_eq!(list_guest_tokens_core(&data_dir).expect("list").len(), 1);
+        revoke_guest_token_core(&data_dir, &guest.id).expect("revoke");
+        assert!(authenticate_guest_token(&data_dir, &issued.token).is_none());
+        assert!(authorize_guest_method(&data_dir, &guest.id, "list_threads").is_err());
+        assert!(revoke_guest_token_core(&data_dir, &guest.id).is_err());
+        let _ = std::fs::remove_dir_all(&data_dir);
+    }
+}
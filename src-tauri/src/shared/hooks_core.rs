@@ -0,0 +1,376 @@
+use std::fs;
+use std::path::Path;
+use std::process::Stdio;
+use std::time::{Duration, Instant};
+
+use serde_json::{json, Value};
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWriteExt};
+
+use crate::shared::pagination_core::paginate;
+use crate::shared::process_core::{kill_child_process_tree, tokio_command};
+use crate::shared::webhooks_core::app_server_webhook_event;
+use crate::types::{AutomationHook, HookEvent, HookRun, Page, PageResult};
+
+const RUN_LOG_FILE: &str = "hook-runs.jsonl";
+/// Older runs are dropped from the log beyond this many.
+const MAX_LOGGED_RUNS: usize = 500;
+/// Output past this many bytes per stream is read but not kept.
+const MAX_CAPTURED_BYTES: usize = 16 * 1024;
+pub(crate) const EVENT_ENV_VAR: &str = "CODEX_MONITOR_HOOK_EVENT";
+
+pub(crate) fn hook_event_name(event: HookEvent) -> &'static str {
+    match event {
+        HookEvent::SessionStarted => "session_started",
+        HookEvent::SessionFinished => "session_finished",
+        HookEvent::DaemonStarted => "daemon_started",
+        HookEvent::DaemonStopped => "daemon_stopped",
+        HookEvent::ApprovalRequested => "approval_requested",
+    }
+}
+
+/// Hooks that are turned on and subscribed to `event`.
+#[allow(dead_code)]
+pub(crate) fn subscribed_hooks(hooks: &[AutomationHook], event: HookEvent) -> Vec<AutomationHook> {
+    hooks
+        .iter()
+        .filter(|hook| hook.enabled && !hook.command.trim().is_empty())
+        .filter(|hook| hook.events.contains(&event))
+        .cloned()
+        .collect()
+}
+
+/// The JSON written to a hook's stdin.
+#[allow(dead_code)]
+pub(crate) fn hook_payload(event: HookEvent, data: Value, occurred_at_ms: i64) -> Value {
+    json!({
+        "event": hook_event_name(event),
+        "occurredAtMs": occurred_at_ms,
+        "data": data,
+    })
+}
+
+/// The hook event for a session queue transition; only `started` is one.
+pub(crate) fn session_queue_hook_event(status: &str) -> Option<HookEvent> {
+    (status == "started").then_some(HookEvent::SessionStarted)
+}
+
+/// The hook event, and its data, for a notification forwarded by a remote
+/// daemon.
+#[allow(dead_code)]
+pub(crate) fn remote_hook_event(event: &str, params: &Value) -> Option<(HookEvent, Value)> {
+    match event {
+        "codex-session-queue" => {
+            let status = params.get("status").and_then(Value::as_str)?;
+            session_queue_hook_event(status).map(|event| (event, params.clone()))
+        }
+        "codex-session-exit" => Some((HookEvent::SessionFinished, params.clone())),
+        "app-server-event" => app_server_webhook_event(
+            params
+                .get("workspace_id")
+                .and_then(Value::as_str)
+                .unwrap_or_default(),
+            params.get("message")?,
+        )
+        .map(|(_, data)| (HookEvent::ApprovalRequested, data)),
+        _ => None,
+    }
+}
+
+/// Reads `stream` to the end, keeping the first `MAX_CAPTURED_BYTES` so a
+/// chatty hook never blocks on a full pipe.
+async fn capture(mut stream: impl AsyncRead + Unpin) -> String {
+    let mut kept = Vec::new();
+    let mut chunk = [0u8; 4096];
+    let mut truncated = false;
+    loop {
+        match stream.read(&mut chunk).await {
+            Ok(0) | Err(_) => break,
+            Ok(read) => {
+                let room = MAX_CAPTURED_BYTES.saturating_sub(kept.len());
+                truncated |= read > room;
+                kept.extend_from_slice(&chunk[..read.min(room)]);
+            }
+        }
+    }
+    let mut text = String::from_utf8_lossy(&kept).into_owned();
+    if truncated {
+        text.push_str("\n[output truncated]");
+    }
+    text
+}
+
+fn shell_command(command: &str) -> tokio::process::Command {
+    #[cfg(target_os = "windows")]
+    {
+        let mut shell = tokio_command("cmd");
+        shell.args(["/D", "/C"]).raw_arg(command);
+        shell
+    }
+    #[cfg(not(target_os = "windows"))]
+    {
+        let mut shell = tokio_command("sh");
+        // Its own process group, so a timeout also stops what it started.
+        shell.args(["-c", command]).process_group(0);
+        shell
+    }
+}
+
+/// Runs `hook` through the shell with `payload` on stdin, killing it once
+/// its timeout passes.
+#[allow(dead_code)]
+pub(crate) async fn run_hook(
+    hook: &AutomationHook,
+    event: HookEvent,
+    payload: &Value,
+    started_at_ms: i64,
+) -> HookRun {
+    let mut run = HookRun {
+        id: uuid::Uuid::new_v4().to_string(),
+        hook_id: hook.id.clone(),
+        event,
+        started_at_ms,
+        duration_ms: 0,
+        exit_code: None,
+        timed_out: false,
+        ok: false,
+        stdout: String::new(),
+        stderr: String::new(),
+        error: None,
+    };
+    let started = Instant::now();
+    let mut child = match shell_command(&hook.command)
+        .env(EVENT_ENV_VAR, hook_event_name(event))
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .kill_on_drop(true)
+        .spawn()
+    {
+        Ok(child) => child,
+        Err(err) => {
+            run.error = Some(format!("Failed to start hook: {err}"));
+            return run;
+        }
+    };
+
+    if let Some(mut stdin) = child.stdin.take() {
+        let body = payload.to_string();
+        // A hook that ignores its stdin may exit before reading it.
+        tokio::spawn(async move {
+            let _ = stdin.write_all(body.as_bytes()).await;
+        });
+    }
+    let stdout_task = child
+        .stdout
+        .take()
+        .map(|stream| tokio::spawn(capture(stream)));
+    let stderr_task = child
+        .stderr
+        .take()
+        .map(|stream| tokio::spawn(capture(stream)));
+
+    let timeout = Duration::from_secs(u64::from(hook.timeout_secs.max(1)));
+    match tokio::time::timeout(timeout, child.wait()).await {
+        Ok(Ok(status)) => run.exit_code = status.code(),
+        Ok(Err(err)) => run.error = Some(format!("Failed to wait for hook: {err}")),
+        Err(_) => {
+            #[cfg(unix)]
+            if let Some(pid) = child.id() {
+                unsafe {
+                    libc::kill(-(pid as i32), libc::SIGKILL);
+                }
+            }
+            kill_child_process_tree(&mut child).await;
+            let _ = child.wait().await;
+            run.timed_out = true;
+        }
+    }
+    if let Some(task) = stdout_task {
+        run.stdout = task.await.unwrap_or_default();
+    }
+    if let Some(task) = stderr_task {
+        run.stderr = task.await.unwrap_or_default();
+    }
+    run.duration_ms = started.elapsed().as_millis() as u64;
+    run.ok = run.exit_code == Some(0);
+    run
+}
+
+fn read_runs(data_dir: &Path) -> Vec<HookRun> {
+    fs::read_to_string(data_dir.join(RUN_LOG_FILE))
+        .map(|raw| {
+            raw.lines()
+                .filter_map(|line| serde_json::from_str(line).ok())
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Appends `run` to the log, keeping the newest entries only.
+#[allow(dead_code)]
+pub(crate) fn record_hook_run(data_dir: &Path, run: &HookRun) -> Result<(), String> {
+    let mut runs = read_runs(data_dir);
+    runs.push(run.clone());
+    let skip = runs.len().saturating_sub(MAX_LOGGED_RUNS);
+    let mut raw = String::new();
+    for entry in &runs[skip..] {
+        raw.push_str(&serde_json::to_string(entry).map_err(|err| err.to_string())?);
+        raw.push('\n');
+    }
+    fs::create_dir_all(data_dir).map_err(|err| err.to_string())?;
+    fs::write(data_dir.join(RUN_LOG_FILE), raw)
+        .map_err(|err| format!("Failed to write hook run log: {err}"))
+}
+
+/// Newest first, optionally for one hook only.
+#[allow(dead_code)]
+pub(crate) fn list_hook_runs_core(
+    data_dir: &Path,
+    hook_id: Option<&str>,
+    page: &Page,
+) -> Result<PageResult<HookRun>, String> {
+    let runs = read_runs(data_dir)
+        .into_iter()
+        .filter(|run| hook_id.is_none_or(|id| run.hook_id == id))
+        .collect();
+    paginate(runs, page, |run| (run.started_at_ms, run.id.as_str()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hook(command: &str, events: Vec<HookEvent>) -> AutomationHook {
+        AutomationHook {
+            id: "hook-1".to_string(),
+            name: String::new(),
+            command: command.to_string(),
+            events,
+            timeout_secs: 5,
+            enabled: true,
+        }
+    }
+
+    #[test]
+    fn only_enabled_subscribed_hooks_run() {
+        let mut disabled = hook("true", vec![HookEvent::SessionStarted]);
+        disabled.enabled = false;
+        let hooks = vec![
+            hook("true", vec![HookEvent::SessionStarted]),
+            hook("true", vec![HookEvent::DaemonStopped]),
+            hook("  ", vec![HookEvent::SessionStarted]),
+            disabled,
+        ];
+        assert_eq!(subscribed_hooks(&hooks, HookEvent::SessionStarted).len(), 1);
+        assert!(subscribed_hooks(&hooks, HookEvent::ApprovalRequested).is_empty());
+    }
+
+    #[test]
+    fn remote_notifications_map_to_hook_events() {
+        let started = json!({ "sessionId": "s-1", "status": "started" });
+        assert_eq!(
+            remote_hook_event("codex-session-queue", &started).map(|(event, _)| event),
+            Some(HookEvent::SessionStarted)
+        );
+        let queued = json!({ "sessionId": "s-1", "status": "queued" });
+        assert!(remote_hook_event("codex-session-queue", &queued).is_none());
+        assert_eq!(
+            remote_hook_event("codex-session-exit", &json!({ "sessionId": "s-1" }))
+                .map(|(event, _)| event),
+            Some(HookEvent::SessionFinished)
+        );
+        let approval = json!({
+            "workspace_id": "ws-1",
+            "message": { "method": "item/commandExecution/requestApproval" },
+        });
+        let (event, data) = remote_hook_event("app-server-event", &approval).expect("approval");
+        assert_eq!(event, HookEvent::ApprovalRequested);
+        assert_eq!(data["workspaceId"], "ws-1");
+        assert!(remote_hook_event("terminal-output", &json!({})).is_none());
+    }
+
+    #[cfg(not(target_os = "windows"))]
+    #[test]
+    fn hooks_read_the_payload_and_time_out() {
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .expect("runtime");
+        let payload = hook_payload(HookEvent::SessionFinished, json!({ "sessionId": "s-1" }), 7);
+        let run_one = |hook: AutomationHook| {
+            runtime.block_on(run_hook(&hook, HookEvent::SessionFinished, &payload, 7))
+        };
+
+        let run = run_one(hook(
+            "cat; echo \"$CODEX_MONITOR_HOOK_EVENT\" >&2",
+            vec![HookEvent::SessionFinished],
+        ));
+        assert!(run.ok, "{run:?}");
+        assert_eq!(run.exit_code, Some(0));
+        let echoed: Value = serde_json::from_str(&run.stdout).expect("payload");
+        assert_eq!(echoed["event"], "session_finished");
+        assert_eq!(echoed["data"]["sessionId"], "s-1");
+        assert_eq!(run.stderr.trim(), "session_finished");
+
+        let run = run_one(hook("exit 3", vec![HookEvent::SessionFinished]));
+        assert!(!run.ok);
+        assert_eq!(run.exit_code, Some(3));
+
+        let mut slow = hook("sleep 30", vec![HookEvent::SessionFinished]);
+        slow.timeout_secs = 1;
+        let run = run_one(slow);
+        assert!(run.timed_out);
+        assert!(!run.ok);
+        assert!(run.duration_ms < 10_000);
+    }
+
+    #[test]
+    fn run_log_keeps_the_newest_entries() {
+        let dir =
+            std::env::temp_dir().join(format!("codex-monitor-hooks-{}", uuid::Uuid::new_v4()));
+        for index in 0..MAX_LOGGED_RUNS + 2 {
+            let mut run = run_entry(&index.to_string(), index as i64);
+            if index % 2 == 1 {
+                run.hook_id = "other".to_string();
+            }
+            record_hook_run(&dir, &run).expect("record");
+        }
+        let all_pages = Page {
+            cursor: None,
+            limit: Some(MAX_LOGGED_RUNS as u32),
+        };
+        let all = list_hook_runs_core(&dir, None, &all_pages).expect("list");
+        assert_eq!(all.items.len(), MAX_LOGGED_RUNS);
+        assert_eq!(all.items[0].id, (MAX_LOGGED_RUNS + 1).to_string());
+
+        let first_two = Page {
+            cursor: None,
+            limit: Some(2),
+        };
+        let ids: Vec<String> = list_hook_runs_core(&dir, Some("hook-1"), &first_two)
+            .expect("list")
+            .items
+            .into_iter()
+            .map(|run| run.id)
+            .collect();
+        assert_eq!(ids, vec!["500", "498"]);
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    fn run_entry(id: &str, started_at_ms: i64) -> HookRun {
+        HookRun {
+            id: id.to_string(),
+            hook_id: "hook-1".to_string(),
+            event: HookEvent::SessionStarted,
+            started_at_ms,
+            duration_ms: 1,
+            exit_code: Some(0),
+            timed_out: false,
+            ok: true,
+            stdout: String::new(),
+            stderr: String::new(),
+            error: None,
+        }
+    }
+}
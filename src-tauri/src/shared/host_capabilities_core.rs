@@ -0,0 +1,118 @@
+use std::time::Duration;
+
+use tokio::time::timeout;
+
+use crate::shared::process_core::tokio_command;
+use crate::types::{HostCapabilities, HostRuntime};
+
+const RUNTIME_PROBE_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Runtimes a job is likely to need, as `(name, program)`.
+const RUNTIMES: &[(&str, &str)] = &[
+    ("node", "node"),
+    ("python", "python3"),
+    ("git", "git"),
+    ("docker", "docker"),
+];
+
+/// Total physical memory, when the platform reports it.
+#[cfg(unix)]
+fn total_memory_bytes() -> Option<u64> {
+    let pages = unsafe { libc::sysconf(libc::_SC_PHYS_PAGES) };
+    let page_size = unsafe { libc::sysconf(libc::_SC_PAGESIZE) };
+    if pages <= 0 || page_size <= 0 {
+        return None;
+    }
+    Some(pages as u64 * page_size as u64)
+}
+
+#[cfg(not(unix))]
+fn total_memory_bytes() -> Option<u64> {
+    None
+}
+
+/// The first version number in `--version` output such as `v20.11.0`,
+/// `Python 3.12.1` or `Docker version 27.3.1, build ce12230`.
+fn parse_version(output: &str) -> Option<String> {
+    output
+        .lines()
+        .next()?
+        .split_whitespace()
+        .map(|token| {
+            token
+                .strip_prefix('v')
+                .unwrap_or(token)
+                .trim_end_matches(',')
+        })
+        .find(|token| token.starts_with(|ch: char| ch.is_ascii_digit()))
+        .map(str::to_string)
+}
+
+async fn probe_runtime(name: &str, program: &str) -> Option<HostRuntime> {
+    let mut command = tokio_command(program);
+    command.arg("--version");
+    command.stdout(std::process::Stdio::piped());
+    command.stderr(std::process::Stdio::piped());
+    let output = timeout(RUNTIME_PROBE_TIMEOUT, command.output())
+        .await
+        .ok()?
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    // Older Pythons print their version to stderr.
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let text = if stdout.trim().is_empty() {
+        String::from_utf8_lossy(&output.stderr)
+    } else {
+        stdout
+    };
+    Some(HostRuntime {
+        name: name.to_string(),
+        version: parse_version(&text)?,
+    })
+}
+
+/// Describes this machine so work can be sent where it will run: OS, CPU,
+/// memory and the runtimes found on `PATH`. Runtimes that are missing or
+/// do not answer `--version` are left out.
+pub(crate) async fn host_capabilities_core() -> HostCapabilities {
+    let probes = RUNTIMES
+        .iter()
+        .map(|(name, program)| probe_runtime(name, program));
+    let runtimes = futures_util::future::join_all(probes)
+        .await
+        .into_iter()
+        .flatten()
+        .collect();
+    HostCapabilities {
+        os: std::env::consts::OS.to_string(),
+        arch: std::env::consts::ARCH.to_string(),
+        cpu_cores: std::thread::available_parallelism()
+            .map(|cores| cores.get())
+            .unwrap_or(1),
+        total_memory_bytes: total_memory_bytes(),
+        runtimes,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn versions_are_read_from_common_outputs() {
+        assert_eq!(parse_version("v20.11.0\n").as_deref(), Some("20.11.0"));
+        assert_eq!(parse_version("Python 3.12.1").as_deref(), Some("3.12.1"));
+        assert_eq!(
+            parse_version("git version 2.43.0\n").as_deref(),
+            Some("2.43.0")
+        );
+        assert_eq!(
+            parse_version("Docker version 27.3.1, build ce12230").as_deref(),
+            Some("27.3.1")
+        );
+        assert_eq!(parse_version("").as_deref(), None);
+        assert_eq!(parse_version("command not found").as_deref(), None);
+    }
+}
@@ -456,7 +456,7 @@ fn read_i64(map: &serde_json::Map<String, Value>, keys: &[&str]) -> i64 {
         .unwrap_or(0)
 }
 
-fn read_timestamp_ms(value: &Value) -> Option<i64> {
+pub(crate) fn read_timestamp_ms(value: &Value) -> Option<i64> {
     let raw = value.get("timestamp")?;
     if let Some(text) = raw.as_str() {
         return DateTime::parse_from_rfc3339(text)
@@ -495,7 +495,7 @@ fn day_key_for_timestamp_ms(timestamp_ms: i64) -> Option<String> {
     Some(utc.with_timezone(&Local).format("%Y-%m-%d").to_string())
 }
 
-fn extract_cwd(value: &Value) -> Option<String> {
+pub(crate) fn extract_cwd(value: &Value) -> Option<String> {
     value
         .get("payload")
         .and_then(|payload| payload.get("cwd"))
@@ -508,7 +508,7 @@ fn path_matches_workspace(cwd: &str, workspace_path: &Path) -> bool {
     cwd_path == workspace_path || cwd_path.starts_with(workspace_path)
 }
 
-fn make_day_keys(days: u32) -> Vec<String> {
+pub(crate) fn make_day_keys(days: u32) -> Vec<String> {
     let today = Local::now().date_naive();
     (0..days)
         .rev()
@@ -525,7 +525,7 @@ fn resolve_codex_sessions_root(codex_home_override: Option<PathBuf>) -> Option<P
         .map(|home| home.join("sessions"))
 }
 
-fn resolve_sessions_roots(
+pub(crate) fn resolve_sessions_roots(
     workspaces: &HashMap<String, WorkspaceEntry>,
     workspace_path: Option<&Path>,
 ) -> Vec<PathBuf> {
@@ -585,7 +585,7 @@ fn resolve_workspace_codex_home_for_path(
     resolve_workspace_codex_home(entry, parent_entry)
 }
 
-fn day_dir_for_key(root: &Path, day_key: &str) -> PathBuf {
+pub(crate) fn day_dir_for_key(root: &Path, day_key: &str) -> PathBuf {
     let mut parts = day_key.split('-');
     let year = parts.next().unwrap_or("1970");
     let month = parts.next().unwrap_or("01");
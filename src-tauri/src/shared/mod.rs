@@ -1,17 +1,61 @@
 pub(crate) mod account;
+pub(crate) mod activity_report_core;
 pub(crate) mod agents_config_core;
+pub(crate) mod backup_core;
+pub(crate) mod ci_status_core;
+pub(crate) mod client_trust_core;
+pub(crate) mod codex_auth_core;
 pub(crate) mod codex_aux_core;
 pub(crate) mod codex_core;
+pub(crate) mod codex_home_snapshot_core;
+pub(crate) mod codex_login_core;
+pub(crate) mod codex_session_core;
 pub(crate) mod codex_update_core;
+pub(crate) mod config_changelog_core;
 pub(crate) mod config_toml_core;
+pub(crate) mod daemon_data_dir_core;
+pub(crate) mod daemon_identity_core;
+pub(crate) mod data_dir_core;
+pub(crate) mod data_dir_lock_core;
+pub(crate) mod do_not_disturb_core;
+pub(crate) mod editor_core;
+pub(crate) mod file_browser_core;
 pub(crate) mod files_core;
 pub(crate) mod git_core;
 pub(crate) mod git_rpc;
 pub(crate) mod git_ui_core;
+pub(crate) mod github_api_core;
+pub(crate) mod guest_tokens_core;
+pub(crate) mod hooks_core;
+pub(crate) mod host_capabilities_core;
 pub(crate) mod local_usage_core;
+pub(crate) mod monitor_error;
+pub(crate) mod pagination_core;
+pub(crate) mod plugins_core;
 pub(crate) mod process_core;
+pub(crate) mod process_env_core;
+pub(crate) mod process_registry_core;
 pub(crate) mod prompts_core;
+pub(crate) mod reachability_core;
+pub(crate) mod redaction_core;
+pub(crate) mod remote_action_confirm_core;
+pub(crate) mod remote_command_core;
+pub(crate) mod request_signing_core;
+pub(crate) mod rpc_framing_core;
+pub(crate) mod rpc_timeouts_core;
+pub(crate) mod scheduler_core;
+pub(crate) mod session_archive_core;
+pub(crate) mod session_export_core;
+pub(crate) mod session_search_core;
+pub(crate) mod session_summary_core;
+pub(crate) mod session_templates_core;
+pub(crate) mod session_transcript_core;
+pub(crate) mod session_worktree_core;
 pub(crate) mod settings_core;
+pub(crate) mod settings_sync_core;
+pub(crate) mod storage_health_core;
+pub(crate) mod tags_core;
+pub(crate) mod webhooks_core;
 pub(crate) mod workspace_rpc;
 pub(crate) mod workspaces_core;
 pub(crate) mod worktree_core;
@@ -0,0 +1,322 @@
+use serde::ser::SerializeStruct;
+use serde::{Serialize, Serializer};
+use serde_json::Value;
+
+/// Wire value the daemon puts in `error.category` for authentication
+/// failures, so clients need not match on the message.
+pub(crate) const AUTH_ERROR_CATEGORY: &str = "auth";
+
+/// Categorised failure shared by the daemon, tailscale, settings and remote
+/// backend code paths. The message is the same text a `String` error used
+/// to carry. The narrower variants name a failure the frontend tells apart
+/// and belong to the category of the broad variant listed above them.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub(crate) enum MonitorError {
+    /// Missing, rejected or expired credentials.
+    #[error("{0}")]
+    Auth(String),
+    /// Connecting to, or talking with, a daemon, proxy or remote backend.
+    #[error("{0}")]
+    Network(String),
+    /// The remote backend refused or could not take the connection.
+    #[error("{0}")]
+    Unreachable(String),
+    /// An established remote backend connection went away.
+    #[error("{0}")]
+    Disconnected(String),
+    /// Opening the connection took too long.
+    #[error("{0}")]
+    ConnectTimeout(String),
+    /// The connection was up but the reply took too long.
+    #[error("{0}")]
+    Timeout(String),
+    /// The host is on the tailnet but Tailscale on this device is not
+    /// connected.
+    #[error("{0}")]
+    TailscaleNotConnected(String),
+    /// The system routes traffic through a proxy the app is not set to use.
+    #[error("{0}")]
+    ProxyRequired(String),
+    /// Starting, stopping or inspecting a local process, or a request the
+    /// daemon rejected.
+    #[error("{0}")]
+    Process(String),
+    #[error("{0}")]
+    WorkspaceNotFound(String),
+    #[error("{0}")]
+    ThreadNotFound(String),
+    /// A start or stop of the same process is already under way.
+    #[error("{0}")]
+    InProgress(String),
+    /// Invalid or unreadable settings.
+    #[error("{0}")]
+    Config(String),
+    #[error("{0}")]
+    NotSupported(String),
+}
+
+impl MonitorError {
+    pub(crate) fn category(&self) -> &'static str {
+        match self {
+            Self::Auth(_) => AUTH_ERROR_CATEGORY,
+            Self::Network(_)
+            | Self::Unreachable(_)
+            | Self::Disconnected(_)
+            | Self::ConnectTimeout(_)
+            | Self::Timeout(_)
+            | Self::TailscaleNotConnected(_)
+            | Self::ProxyRequired(_) => "network",
+            Self::Process(_)
+            | Self::WorkspaceNotFound(_)
+            | Self::ThreadNotFound(_)
+            | Self::InProgress(_) => "process",
+            Self::Config(_) => "config",
+            Self::NotSupported(_) => "notSupported",
+        }
+    }
+
+    /// Wire name of the variant, sent as `error.kind` so clients rebuild
+    /// the same variant instead of reading the message.
+    pub(crate) fn kind(&self) -> &'static str {
+        match self {
+            Self::Auth(_) => "auth",
+            Self::Network(_) => "network",
+            Self::Unreachable(_) => "unreachable",
+            Self::Disconnected(_) => "disconnected",
+            Self::ConnectTimeout(_) => "connectTimeout",
+            Self::Timeout(_) => "timeout",
+            Self::TailscaleNotConnected(_) => "tailscaleNotConnected",
+            Self::ProxyRequired(_) => "proxyRequired",
+            Self::Process(_) => "process",
+            Self::WorkspaceNotFound(_) => "workspaceNotFound",
+            Self::ThreadNotFound(_) => "threadNotFound",
+            Self::InProgress(_) => "inProgress",
+            Self::Config(_) => "config",
+            Self::NotSupported(_) => "notSupported",
+        }
+    }
+
+    fn from_kind(kind: &str, message: String) -> Option<Self> {
+        Some(match kind {
+            "auth" => Self::Auth(message),
+            "network" => Self::Network(message),
+            "unreachable" => Self::Unreachable(message),
+            "disconnected" => Self::Disconnected(message),
+            "connectTimeout" => Self::ConnectTimeout(message),
+            "timeout" => Self::Timeout(message),
+            "tailscaleNotConnected" => Self::TailscaleNotConnected(message),
+            "proxyRequired" => Self::ProxyRequired(message),
+            "process" => Self::Process(message),
+            "workspaceNotFound" => Self::WorkspaceNotFound(message),
+            "threadNotFound" => Self::ThreadNotFound(message),
+            "inProgress" => Self::InProgress(message),
+            "config" => Self::Config(message),
+            "notSupported" => Self::NotSupported(message),
+            _ => return None,
+        })
+    }
+
+    #[allow(dead_code)]
+    pub(crate) fn is_network(&self) -> bool {
+        self.category() == "network"
+    }
+
+    /// Reads the `error` object of a daemon response. Current daemons name
+    /// the variant in `kind`. Older daemons, and errors the daemon still
+    /// reports as plain strings, only have a message (and maybe `category`
+    /// for auth failures), so those are recognised by their wording.
+    #[allow(dead_code)]
+    pub(crate) fn from_rpc_error(error: &Value) -> Self {
+        let message = error
+            .get("message")
+            .and_then(Value::as_str)
+            .unwrap_or("remote error")
+            .to_string();
+        if let Some(kind) = error.get("kind").and_then(Value::as_str) {
+            if let Some(error) = Self::from_kind(kind, message.clone()) {
+                return error;
+            }
+        }
+        let is_auth = match error.get("category").and_then(Value::as_str) {
+            Some(category) => category == AUTH_ERROR_CATEGORY,
+            None => is_legacy_auth_message(&message),
+        };
+        if is_auth {
+            Self::Auth(message)
+        } else {
+            from_legacy_message(message)
+        }
+    }
+
+    pub(crate) fn message(&self) -> &str {
+        match self {
+            Self::Auth(message)
+            | Self::Network(message)
+            | Self::Unreachable(message)
+            | Self::Disconnected(message)
+            | Self::ConnectTimeout(message)
+            | Self::Timeout(message)
+            | Self::TailscaleNotConnected(message)
+            | Self::ProxyRequired(message)
+            | Self::Process(message)
+            | Self::WorkspaceNotFound(message)
+            | Self::ThreadNotFound(message)
+            | Self::InProgress(message)
+            | Self::Config(message)
+            | Self::NotSupported(message) => message,
+        }
+    }
+
+    fn into_message(self) -> String {
+        match self {
+            Self::Auth(message)
+            | Self::Network(message)
+            | Self::Unreachable(message)
+            | Self::Disconnected(message)
+            | Self::ConnectTimeout(message)
+            | Self::Timeout(message)
+            | Self::TailscaleNotConnected(message)
+            | Self::ProxyRequired(message)
+            | Self::Process(message)
+            | Self::WorkspaceNotFound(message)
+            | Self::ThreadNotFound(message)
+            | Self::InProgress(message)
+            | Self::Config(message)
+            | Self::NotSupported(message) => message,
+        }
+    }
+}
+
+fn is_legacy_auth_message(message: &str) -> bool {
+    let lower = message.to_ascii_lowercase();
+    lower.contains("unauthorized") || lower.contains("invalid token")
+}
+
+/// The variant for an uncategorised daemon error, going by the wording the
+/// daemon and the remote backend have used for each failure.
+fn from_legacy_message(message: String) -> MonitorError {
+    let lower = message.to_ascii_lowercase();
+    if lower.contains("until tailscale connects") {
+        MonitorError::TailscaleNotConnected(message)
+    } else if lower.contains("which the app does not use") {
+        MonitorError::ProxyRequired(message)
+    } else if lower.contains("remote backend disconnected") {
+        MonitorError::Disconnected(message)
+    } else if lower.contains("failed to connect to remote backend") {
+        MonitorError::Unreachable(message)
+    } else if lower.contains("connect timed out") || lower.contains("timed out connecting") {
+        MonitorError::ConnectTimeout(message)
+    } else if lower.contains("timed out") {
+        MonitorError::Timeout(message)
+    } else if lower.contains("workspace not found") {
+        MonitorError::WorkspaceNotFound(message)
+    } else if lower.contains("thread not found") {
+        MonitorError::ThreadNotFound(message)
+    } else if lower.contains("already in progress") {
+        MonitorError::InProgress(message)
+    } else if lower.contains("not supported") || lower.contains("only supported") {
+        MonitorError::NotSupported(message)
+    } else {
+        MonitorError::Process(message)
+    }
+}
+
+impl Serialize for MonitorError {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut state = serializer.serialize_struct("MonitorError", 3)?;
+        state.serialize_field("category", self.category())?;
+        state.serialize_field("kind", self.kind())?;
+        state.serialize_field("message", self.message())?;
+        state.end()
+    }
+}
+
+// Lets typed errors flow through code that still returns `Result<_, String>`.
+impl From<MonitorError> for String {
+    fn from(error: MonitorError) -> Self {
+        error.into_message()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn serializes_category_and_message() {
+        let error = MonitorError::Auth("invalid token".to_string());
+        assert_eq!(
+            serde_json::to_value(&error).expect("serialize"),
+            serde_json::json!({ "category": "auth", "kind": "auth", "message": "invalid token" })
+        );
+        assert_eq!(error.to_string(), "invalid token");
+        assert_eq!(String::from(error), "invalid token");
+    }
+
+    #[test]
+    fn rpc_errors_use_kind_then_category_then_legacy_wording() {
+        let timeout = MonitorError::Timeout("request timed out".to_string());
+        let wire = serde_json::to_value(&timeout).expect("serialize");
+        assert_eq!(wire["category"], "network");
+        assert_eq!(MonitorError::from_rpc_error(&wire), timeout);
+
+        let categorized = serde_json::json!({ "category": "auth", "message": "token expired" });
+        assert_eq!(
+            MonitorError::from_rpc_error(&categorized),
+            MonitorError::Auth("token expired".to_string())
+        );
+        let legacy = serde_json::json!({ "message": "unauthorized" });
+        assert_eq!(
+            MonitorError::from_rpc_error(&legacy),
+            MonitorError::Auth("unauthorized".to_string())
+        );
+        let rejected =
+            serde_json::json!({ "category": "process", "message": "invalid token format" });
+        assert_eq!(
+            MonitorError::from_rpc_error(&rejected),
+            MonitorError::Process("invalid token format".to_string())
+        );
+    }
+
+    #[test]
+    fn uncategorised_rpc_errors_are_recognised_by_wording() {
+        let read = |message: &str| {
+            MonitorError::from_rpc_error(&serde_json::json!({ "message": message }))
+        };
+        assert_eq!(
+            read("workspace not found"),
+            MonitorError::WorkspaceNotFound("workspace not found".to_string())
+        );
+        assert!(matches!(
+            read("connect timed out after 10s reaching remote backend"),
+            MonitorError::ConnectTimeout(_)
+        ));
+        assert!(matches!(
+            read("remote backend request `get_git_diffs` timed out after 300s waiting for a response"),
+            MonitorError::Timeout(_)
+        ));
+        assert!(matches!(
+            read("A daemon start/stop is already in progress."),
+            MonitorError::InProgress(_)
+        ));
+        assert!(matches!(
+            read("Tailscale daemon start is only supported on desktop."),
+            MonitorError::NotSupported(_)
+        ));
+        assert!(matches!(
+            read(
+                "Tailscale is installed but not connected (Stopped). mac.ts.net is on the \
+                 tailnet and cannot be reached until Tailscale connects. (connect timed out)"
+            ),
+            MonitorError::TailscaleNotConnected(_)
+        ));
+        assert!(matches!(
+            read(
+                "This system sends traffic through the proxy http://proxy:3128, which the app \
+                 does not use. (Failed to connect to backend:4732)"
+            ),
+            MonitorError::ProxyRequired(_)
+        ));
+        assert_eq!(read("boom"), MonitorError::Process("boom".to_string()));
+    }
+}
@@ -0,0 +1,118 @@
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+
+use crate::types::{Page, PageResult};
+
+pub(crate) const DEFAULT_PAGE_LIMIT: usize = 50;
+pub(crate) const MAX_PAGE_LIMIT: usize = 500;
+
+fn encode_cursor(timestamp_ms: i64, id: &str) -> String {
+    format!("{timestamp_ms}:{id}")
+}
+
+fn decode_cursor(cursor: &str) -> Result<(i64, &str), String> {
+    cursor
+        .split_once(':')
+        .and_then(|(timestamp, id)| Some((timestamp.parse().ok()?, id)))
+        .ok_or_else(|| "Invalid page cursor.".to_string())
+}
+
+/// Returns one page of `items`, newest first by `key` (a timestamp, then an
+/// id to break ties). The cursor names the last item handed out rather than
+/// an offset, so later pages do not shift when items are added or removed
+/// between calls, and a daemon and the app agree on it without sharing state.
+pub(crate) fn paginate<T>(
+    mut items: Vec<T>,
+    page: &Page,
+    key: impl Fn(&T) -> (i64, &str),
+) -> Result<PageResult<T>, String> {
+    let limit = page
+        .limit
+        .map_or(DEFAULT_PAGE_LIMIT, |limit| limit as usize)
+        .clamp(1, MAX_PAGE_LIMIT);
+    items.sort_by(|a, b| key(b).cmp(&key(a)));
+    if let Some(cursor) = page.cursor.as_deref().filter(|cursor| !cursor.is_empty()) {
+        let after = decode_cursor(cursor)?;
+        items.retain(|item| key(item) < after);
+    }
+    let next_cursor = if items.len() > limit {
+        items.truncate(limit);
+        items.last().map(|item| {
+            let (timestamp_ms, id) = key(item);
+            encode_cursor(timestamp_ms, id)
+        })
+    } else {
+        None
+    };
+    Ok(PageResult { items, next_cursor })
+}
+
+/// Reads a paged daemon response. Daemons from before pagination answer
+/// with the whole list, which becomes a single page.
+#[allow(dead_code)]
+pub(crate) fn page_from_value<T: DeserializeOwned>(value: Value) -> Result<PageResult<T>, String> {
+    if value.is_array() {
+        let items = serde_json::from_value(value).map_err(|err| err.to_string())?;
+        return Ok(PageResult {
+            items,
+            next_cursor: None,
+        });
+    }
+    serde_json::from_value(value).map_err(|err| err.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn page(cursor: Option<&str>, limit: u32) -> Page {
+        Page {
+            cursor: cursor.map(str::to_string),
+            limit: Some(limit),
+        }
+    }
+
+    fn key(item: &(i64, String)) -> (i64, &str) {
+        (item.0, item.1.as_str())
+    }
+
+    #[test]
+    fn cursors_stay_stable_when_newer_items_arrive() {
+        let items: Vec<(i64, String)> = vec![
+            (10, "a".to_string()),
+            (30, "c".to_string()),
+            (20, "b".to_string()),
+            (20, "d".to_string()),
+        ];
+
+        let first = paginate(items.clone(), &page(None, 2), key).expect("first page");
+        assert_eq!(
+            first.items,
+            vec![(30, "c".to_string()), (20, "d".to_string())]
+        );
+        assert_eq!(first.next_cursor.as_deref(), Some("20:d"));
+
+        let mut grown = items;
+        grown.push((40, "e".to_string()));
+        let second = paginate(grown, &page(first.next_cursor.as_deref(), 2), key).expect("second");
+        assert_eq!(
+            second.items,
+            vec![(20, "b".to_string()), (10, "a".to_string())]
+        );
+        assert_eq!(second.next_cursor, None);
+
+        assert!(paginate(Vec::new(), &page(Some("oops"), 2), key).is_err());
+    }
+
+    #[test]
+    fn legacy_list_responses_become_one_page() {
+        let page: PageResult<String> = page_from_value(json!(["a", "b"])).expect("legacy");
+        assert_eq!(page.items, vec!["a", "b"]);
+        assert_eq!(page.next_cursor, None);
+
+        let page: PageResult<String> =
+            page_from_value(json!({ "items": ["c"], "nextCursor": "1:c" })).expect("paged");
+        assert_eq!(page.next_cursor.as_deref(), Some("1:c"));
+    }
+}
@@ -0,0 +1,341 @@
+//! Plugins add daemon RPC methods without changing the daemon. Each one is
+//! a manifest, `plugins/<id>.json` in the data directory, naming an
+//! executable and the methods it serves with the scope each needs. Once the
+//! plugin is enabled, `ext.<id>.<method>` runs the executable with
+//! `{ "method", "params" }` on its stdin and answers with what it prints:
+//! `{ "result": … }` or `{ "error": "…" }`. Manifests are read on every
+//! call, so adding or editing one needs no restart.
+
+use std::fs;
+use std::path::Path;
+use std::process::Stdio;
+use std::time::Duration;
+
+use serde_json::{json, Value};
+use tokio::io::AsyncWriteExt;
+
+use crate::shared::guest_tokens_core::{required_guest_scope, GUEST_SCOPE_READ, GUEST_SCOPE_WRITE};
+use crate::shared::process_core::tokio_command;
+use crate::types::{PluginInfo, PluginManifest, PluginMethod, PluginScope};
+
+pub(crate) const PLUGIN_METHOD_PREFIX: &str = "ext.";
+const PLUGINS_DIR: &str = "plugins";
+pub(crate) const METHOD_ENV_VAR: &str = "CODEX_MONITOR_PLUGIN_METHOD";
+/// Error output kept for the message when a plugin fails.
+const MAX_STDERR_CHARS: usize = 2000;
+
+/// Ids and method names are lowercase so `ext.*` methods also fit the HTTP
+/// bridge's `/v1/<method>` paths.
+fn is_valid_name(name: &str) -> bool {
+    !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_' || c == '-')
+}
+
+fn read_manifest(path: &Path) -> Result<PluginManifest, String> {
+    let raw = fs::read_to_string(path).map_err(|err| err.to_string())?;
+    let manifest: PluginManifest = serde_json::from_str(&raw).map_err(|err| err.to_string())?;
+    if manifest.command.trim().is_empty() {
+        return Err("`command` is empty".to_string());
+    }
+    if let Some(method) = manifest
+        .methods
+        .iter()
+        .find(|method| !is_valid_name(&method.name))
+    {
+        return Err(format!("Invalid method name `{}`", method.name));
+    }
+    Ok(manifest)
+}
+
+/// Every manifest in `plugins/`, by id, with the error for those that do
+/// not load.
+fn read_manifests(data_dir: &Path) -> Vec<(String, Result<PluginManifest, String>)> {
+    let Ok(entries) = fs::read_dir(data_dir.join(PLUGINS_DIR)) else {
+        return Vec::new();
+    };
+    let mut manifests: Vec<_> = entries
+        .filter_map(Result::ok)
+        .map(|entry| entry.path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "json"))
+        .filter_map(|path| {
+            let id = path.file_stem()?.to_str()?.to_string();
+            is_valid_name(&id).then(|| {
+                let manifest = read_manifest(&path);
+                (id, manifest)
+            })
+        })
+        .collect();
+    manifests.sort_by(|a, b| a.0.cmp(&b.0));
+    manifests
+}
+
+pub(crate) fn list_plugins_core(data_dir: &Path, enabled: &[String]) -> Vec<PluginInfo> {
+    read_manifests(data_dir)
+        .into_iter()
+        .map(|(id, manifest)| {
+            let enabled = enabled.contains(&id);
+            match manifest {
+                Ok(manifest) => PluginInfo {
+                    name: if manifest.name.trim().is_empty() {
+                        id.clone()
+                    } else {
+                        manifest.name
+                    },
+                    id,
+                    command: manifest.command,
+                    methods: manifest.methods,
+                    enabled,
+                    error: None,
+                },
+                Err(err) => PluginInfo {
+                    name: id.clone(),
+                    id,
+                    command: String::new(),
+                    methods: Vec::new(),
+                    enabled,
+                    error: Some(err),
+                },
+            }
+        })
+        .collect()
+}
+
+/// `enabled` with `id` added or removed. Only a plugin whose manifest loads
+/// can be enabled; any id can be disabled.
+pub(crate) fn toggle_plugin(
+    data_dir: &Path,
+    mut enabled: Vec<String>,
+    id: &str,
+    enable: bool,
+) -> Result<Vec<String>, String> {
+    enabled.retain(|entry| entry != id);
+    if enable {
+        let path = data_dir.join(PLUGINS_DIR).join(format!("{id}.json"));
+        if !is_valid_name(id) || !path.is_file() {
+            return Err(format!("Plugin `{id}` is not installed"));
+        }
+        read_manifest(&path).map_err(|err| format!("Plugin `{id}` does not load: {err}"))?;
+        enabled.push(id.to_string());
+    }
+    Ok(enabled)
+}
+
+/// The plugin id and method name of `ext.<id>.<name>`.
+fn split_method(method: &str) -> Option<(&str, &str)> {
+    method.strip_prefix(PLUGIN_METHOD_PREFIX)?.split_once('.')
+}
+
+/// The enabled plugin serving `method`, and that method.
+fn resolve_method(
+    data_dir: &Path,
+    enabled: &[String],
+    method: &str,
+) -> Option<(String, PluginManifest, PluginMethod)> {
+    let (id, name) = split_method(method)?;
+    if !is_valid_name(id) || !enabled.iter().any(|entry| entry == id) {
+        return None;
+    }
+    let manifest = read_manifest(&data_dir.join(PLUGINS_DIR).join(format!("{id}.json"))).ok()?;
+    let found = manifest
+        .methods
+        .iter()
+        .find(|entry| entry.name == name)
+        .cloned()?;
+    Some((id.to_string(), manifest, found))
+}
+
+/// The narrowest guest scope for `method`, like `required_guest_scope`, but
+/// taking it from the manifest for an `ext.*` method. An `ext.*` method no
+/// enabled plugin serves is owner-only.
+#[allow(dead_code)]
+pub(crate) fn required_method_scope(
+    data_dir: &Path,
+    enabled: &[String],
+    method: &str,
+) -> Option<&'static str> {
+    if !method.starts_with(PLUGIN_METHOD_PREFIX) {
+        return required_guest_scope(method);
+    }
+    match resolve_method(data_dir, enabled, method)?.2.scope {
+        PluginScope::Read => Some(GUEST_SCOPE_READ),
+        PluginScope::Write => Some(GUEST_SCOPE_WRITE),
+        PluginScope::Owner => None,
+    }
+}
+
+/// What the plugin printed, as the call's result.
+fn parse_reply(stdout: &[u8]) -> Result<Value, String> {
+    let reply: Value = serde_json::from_slice(stdout)
+        .map_err(|err| format!("Plugin replied with invalid JSON: {err}"))?;
+    if let Some(error) = reply.get("error").filter(|error| !error.is_null()) {
+        return Err(error
+            .as_str()
+            .map(str::to_string)
+            .unwrap_or_else(|| error.to_string()));
+    }
+    Ok(reply.get("result").cloned().unwrap_or(Value::Null))
+}
+
+/// Runs the enabled plugin serving `method` with `params`.
+#[allow(dead_code)]
+pub(crate) async fn call_plugin_method_core(
+    data_dir: &Path,
+    enabled: &[String],
+    method: &str,
+    params: &Value,
+) -> Result<Value, String> {
+    let (id, manifest, plugin_method) = resolve_method(data_dir, enabled, method)
+        .ok_or_else(|| format!("unknown method: {method}"))?;
+    let mut child = tokio_command(&manifest.command)
+        .args(&manifest.args)
+        .current_dir(data_dir.join(PLUGINS_DIR))
+        .env(METHOD_ENV_VAR, &plugin_method.name)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .kill_on_drop(true)
+        .spawn()
+        .map_err(|err| format!("Failed to start plugin `{id}`: {err}"))?;
+    if let Some(mut stdin) = child.stdin.take() {
+        let body = json!({ "method": plugin_method.name, "params": params }).to_string();
+        tokio::spawn(async move {
+            let _ = stdin.write_all(body.as_bytes()).await;
+        });
+    }
+    let timeout = Duration::from_secs(u64::from(manifest.timeout_secs.max(1)));
+    let output = tokio::time::timeout(timeout, child.wait_with_output())
+        .await
+        .map_err(|_| format!("Plugin `{id}` timed out after {}s", timeout.as_secs()))?
+        .map_err(|err| format!("Failed to run plugin `{id}`: {err}"))?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        let stderr: String = stderr.trim().chars().take(MAX_STDERR_CHARS).collect();
+        let status = output
+            .status
+            .code()
+            .map(|code| format!("exit code {code}"))
+            .unwrap_or_else(|| "a signal".to_string());
+        return Err(if stderr.is_empty() {
+            format!("Plugin `{id}` failed with {status}")
+        } else {
+            format!("Plugin `{id}` failed with {status}: {stderr}")
+        });
+    }
+    parse_reply(&output.stdout)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn install(dir: &Path, id: &str, manifest: Value) {
+        let plugins = dir.join(PLUGINS_DIR);
+        fs::create_dir_all(&plugins).expect("plugins dir");
+        fs::write(plugins.join(format!("{id}.json")), manifest.to_string()).expect("manifest");
+    }
+
+    #[test]
+    fn manifests_declare_methods_and_their_scopes() {
+        let dir =
+            std::env::temp_dir().join(format!("codex-monitor-plugins-{}", uuid::Uuid::new_v4()));
+        install(
+            &dir,
+            "deploy",
+            json!({
+                "name": "Deploy",
+                "command": "deploy-plugin",
+                "methods": [
+                    { "name": "status", "scope": "read" },
+                    { "name": "ship" },
+                    { "name": "rollback", "scope": "owner" },
+                ],
+            }),
+        );
+        install(&dir, "broken", json!({ "command": "" }));
+
+        let plugins = list_plugins_core(&dir, &[]);
+        let ids: Vec<&str> = plugins.iter().map(|plugin| plugin.id.as_str()).collect();
+        assert_eq!(ids, vec!["broken", "deploy"]);
+        assert!(plugins[0].error.is_some());
+        assert_eq!(plugins[1].name, "Deploy");
+        assert!(!plugins[1].enabled);
+
+        assert!(toggle_plugin(&dir, Vec::new(), "broken", true).is_err());
+        assert!(toggle_plugin(&dir, Vec::new(), "missing", true).is_err());
+        assert!(toggle_plugin(&dir, Vec::new(), "../deploy", true).is_err());
+        let enabled = toggle_plugin(&dir, Vec::new(), "deploy", true).expect("enable");
+        assert_eq!(enabled, vec!["deploy".to_string()]);
+
+        let scope = |method| required_method_scope(&dir, &enabled, method);
+        assert_eq!(scope("ext.deploy.status"), Some(GUEST_SCOPE_READ));
+        assert_eq!(scope("ext.deploy.ship"), Some(GUEST_SCOPE_WRITE));
+        assert_eq!(scope("ext.deploy.rollback"), None);
+        assert_eq!(scope("ext.deploy.unknown"), None);
+        assert_eq!(required_method_scope(&dir, &[], "ext.deploy.status"), None);
+        assert_eq!(scope("list_threads"), Some(GUEST_SCOPE_READ));
+
+        assert!(toggle_plugin(&dir, enabled, "deploy", false)
+            .expect("disable")
+            .is_empty());
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn replies_carry_a_result_or_an_error() {
+        assert_eq!(
+            parse_reply(br#"{"result":{"ok":true}}"#),
+            Ok(json!({ "ok": true }))
+        );
+        assert_eq!(
+            parse_reply(br#"{"error":"no such env"}"#),
+            Err("no such env".to_string())
+        );
+        assert!(parse_reply(b"not json").is_err());
+    }
+
+    #[cfg(not(target_os = "windows"))]
+    #[test]
+    fn enabled_plugins_run_with_the_call_on_stdin() {
+        let dir =
+            std::env::temp_dir().join(format!("codex-monitor-plugins-{}", uuid::Uuid::new_v4()));
+        install(
+            &dir,
+            "echo",
+            json!({
+                "command": "sh",
+                "args": [
+                    "-c",
+                    "printf '{\"result\":{\"call\":%s,\"method\":\"%s\"}}' \"$(cat)\" \"$CODEX_MONITOR_PLUGIN_METHOD\"",
+                ],
+                "methods": [{ "name": "hello" }],
+            }),
+        );
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .expect("runtime");
+        let enabled = vec!["echo".to_string()];
+        let params = json!({ "who": "team" });
+        let result = runtime
+            .block_on(call_plugin_method_core(
+                &dir,
+                &enabled,
+                "ext.echo.hello",
+                &params,
+            ))
+            .expect("call");
+        assert_eq!(result["method"], "hello");
+        assert_eq!(result["call"]["params"]["who"], "team");
+
+        let disabled = runtime.block_on(call_plugin_method_core(
+            &dir,
+            &[],
+            "ext.echo.hello",
+            &params,
+        ));
+        assert_eq!(disabled, Err("unknown method: ext.echo.hello".to_string()));
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
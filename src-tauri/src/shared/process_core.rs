@@ -1,3 +1,4 @@
+use std::collections::BTreeMap;
 #[cfg(target_os = "windows")]
 use std::env;
 use std::ffi::OsStr;
@@ -5,8 +6,13 @@ use std::ffi::OsStr;
 use std::path::{Path, PathBuf};
 #[cfg(windows)]
 use std::process::Stdio;
+use std::sync::Mutex as StdMutex;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
 
 use tokio::process::{Child, Command};
+use tokio::time::sleep;
+
+use crate::types::{PreviewShell, ProcessLaunch, ProcessStats};
 
 /// On Windows, spawning a console app from a GUI subsystem app will open a new
 /// console window unless we explicitly disable it.
@@ -25,12 +31,14 @@ pub(crate) fn tokio_command(program: impl AsRef<OsStr>) -> Command {
     command
 }
 
+#[allow(dead_code)]
 pub(crate) fn std_command(program: impl AsRef<OsStr>) -> std::process::Command {
     let mut command = std::process::Command::new(program);
     hide_console_on_windows(&mut command);
     command
 }
 
+#[allow(dead_code)]
 pub(crate) async fn kill_child_process_tree(child: &mut Child) {
     #[cfg(windows)]
     {
@@ -50,6 +58,247 @@ pub(crate) async fn kill_child_process_tree(child: &mut Child) {
     let _ = child.kill().await;
 }
 
+/// Sends SIGINT to a running process, the equivalent of pressing Ctrl+C.
+#[allow(dead_code)]
+#[cfg(unix)]
+pub(crate) fn interrupt_process(pid: u32) -> Result<(), String> {
+    let result = unsafe { libc::kill(pid as i32, libc::SIGINT) };
+    if result != 0 {
+        let err = std::io::Error::last_os_error();
+        return Err(format!("Failed to interrupt process {pid}: {err}"));
+    }
+    Ok(())
+}
+
+#[allow(dead_code)]
+#[cfg(not(unix))]
+pub(crate) fn interrupt_process(_pid: u32) -> Result<(), String> {
+    Err("Interrupting processes is not supported on this platform".to_string())
+}
+
+#[cfg(unix)]
+async fn pid_is_running(pid: u32) -> bool {
+    let result = unsafe { libc::kill(pid as i32, 0) };
+    if result == 0 {
+        return true;
+    }
+    match std::io::Error::last_os_error().raw_os_error() {
+        Some(code) => code != libc::ESRCH,
+        None => false,
+    }
+}
+
+#[cfg(windows)]
+async fn pid_is_running(pid: u32) -> bool {
+    let filter = format!("PID eq {pid}");
+    let Ok(output) = tokio_command("tasklist")
+        .args(["/FI", filter.as_str(), "/NH", "/FO", "CSV"])
+        .output()
+        .await
+    else {
+        return false;
+    };
+    String::from_utf8_lossy(&output.stdout).contains(&format!("\"{pid}\""))
+}
+
+/// Asks `pid` to exit (SIGTERM, or `taskkill /T` without `/F` on Windows),
+/// or kills it outright when `force` is set. Returns false when the process
+/// no longer exists.
+#[cfg(unix)]
+pub(crate) async fn request_process_stop(pid: u32, force: bool) -> Result<bool, String> {
+    let signal = if force { libc::SIGKILL } else { libc::SIGTERM };
+    if unsafe { libc::kill(pid as i32, signal) } == 0 {
+        return Ok(true);
+    }
+    let err = std::io::Error::last_os_error();
+    if err.raw_os_error() == Some(libc::ESRCH) {
+        return Ok(false);
+    }
+    Err(format!("Failed to stop process {pid}: {err}"))
+}
+
+#[cfg(windows)]
+pub(crate) async fn request_process_stop(pid: u32, force: bool) -> Result<bool, String> {
+    let mut command = tokio_command("taskkill");
+    command.arg("/PID").arg(pid.to_string()).arg("/T");
+    if force {
+        command.arg("/F");
+    }
+    let status = command
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .status()
+        .await
+        .map_err(|err| format!("Failed to stop process {pid}: {err}"))?;
+    // Console processes often refuse a polite taskkill; report them as still
+    // running so callers escalate.
+    Ok(status.success() || pid_is_running(pid).await)
+}
+
+#[cfg(not(any(unix, windows)))]
+pub(crate) async fn request_process_stop(_pid: u32, _force: bool) -> Result<bool, String> {
+    Err("Stopping processes by pid is not supported on this platform".to_string())
+}
+
+/// Stops a process we did not spawn: a polite stop request first, escalating
+/// to a forced kill if it is still alive after ~1.2s. `force` skips straight
+/// to the forced kill.
+#[allow(dead_code)]
+#[cfg(any(unix, windows))]
+pub(crate) async fn kill_pid_gracefully(pid: u32, force: bool) -> Result<(), String> {
+    if !force {
+        if !request_process_stop(pid, false).await? {
+            return Ok(());
+        }
+        for _ in 0..12 {
+            if !pid_is_running(pid).await {
+                return Ok(());
+            }
+            sleep(Duration::from_millis(100)).await;
+        }
+    }
+
+    if !request_process_stop(pid, true).await? {
+        return Ok(());
+    }
+    for _ in 0..8 {
+        if !pid_is_running(pid).await {
+            return Ok(());
+        }
+        sleep(Duration::from_millis(100)).await;
+    }
+
+    Err(format!("Process {pid} is still running."))
+}
+
+#[allow(dead_code)]
+#[cfg(not(any(unix, windows)))]
+pub(crate) async fn kill_pid_gracefully(_pid: u32, _force: bool) -> Result<(), String> {
+    Err("Stopping processes by pid is not supported on this platform".to_string())
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct ProcessEntry {
+    pid: u32,
+    ppid: u32,
+    rss_kb: u64,
+    cpu_secs: f64,
+    cpu_percent: f64,
+}
+
+// Cumulative tree CPU time from the previous sample of each root pid, used to
+// turn `ps` CPU time into a percentage over the sampling interval.
+static CPU_SAMPLES: StdMutex<BTreeMap<u32, (f64, Instant)>> = StdMutex::new(BTreeMap::new());
+
+/// Parses `ps` cumulative CPU time: `[[dd-]hh:]mm:ss[.ss]`.
+fn parse_ps_cpu_time(value: &str) -> Option<f64> {
+    let (days, clock) = match value.split_once('-') {
+        Some((days, clock)) => (days.parse::<f64>().ok()?, clock),
+        None => (0.0, value),
+    };
+    let mut seconds = 0.0;
+    for part in clock.split(':') {
+        seconds = seconds * 60.0 + part.parse::<f64>().ok()?;
+    }
+    Some(days * 86_400.0 + seconds)
+}
+
+fn parse_ps_line(line: &str) -> Option<ProcessEntry> {
+    let mut fields = line.split_whitespace();
+    Some(ProcessEntry {
+        pid: fields.next()?.parse().ok()?,
+        ppid: fields.next()?.parse().ok()?,
+        rss_kb: fields.next()?.parse().ok()?,
+        cpu_secs: parse_ps_cpu_time(fields.next()?)?,
+        cpu_percent: fields.next()?.replace(',', ".").parse().ok()?,
+    })
+}
+
+/// Returns `root` followed by all of its descendants.
+fn collect_process_tree(entries: &[ProcessEntry], root: u32) -> Vec<&ProcessEntry> {
+    let Some(root_entry) = entries.iter().find(|entry| entry.pid == root) else {
+        return Vec::new();
+    };
+    let mut tree = vec![root_entry];
+    let mut index = 0;
+    while index < tree.len() {
+        let parent = tree[index].pid;
+        tree.extend(
+            entries
+                .iter()
+                .filter(|entry| entry.ppid == parent && entry.pid != parent),
+        );
+        index += 1;
+    }
+    tree
+}
+
+#[cfg(unix)]
+async fn read_process_table() -> Result<Vec<ProcessEntry>, String> {
+    let output = tokio_command("ps")
+        .args([
+            "-A", "-o", "pid=", "-o", "ppid=", "-o", "rss=", "-o", "time=", "-o", "%cpu=",
+        ])
+        .output()
+        .await
+        .map_err(|err| format!("Failed to run ps: {err}"))?;
+    if !output.status.success() {
+        return Err("Failed to read the process table".to_string());
+    }
+    Ok(String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .filter_map(parse_ps_line)
+        .collect())
+}
+
+#[cfg(not(unix))]
+async fn read_process_table() -> Result<Vec<ProcessEntry>, String> {
+    Err("Process stats are not supported on this platform".to_string())
+}
+
+/// Samples CPU, RSS and descendant count for `pid` and its process tree.
+/// CPU is measured since the previous sample of the same pid; the first
+/// sample falls back to the lifetime average reported by `ps`.
+#[allow(dead_code)]
+pub(crate) async fn sample_process_stats(pid: u32) -> Result<ProcessStats, String> {
+    let entries = read_process_table().await?;
+    let tree = collect_process_tree(&entries, pid);
+    if tree.is_empty() {
+        return Err(format!("Process {pid} is not running"));
+    }
+    let cpu_secs: f64 = tree.iter().map(|entry| entry.cpu_secs).sum();
+    let now = Instant::now();
+    let previous = CPU_SAMPLES
+        .lock()
+        .map_err(|err| err.to_string())?
+        .insert(pid, (cpu_secs, now));
+    let cpu_percent = match previous {
+        Some((previous_secs, at)) if now.duration_since(at).as_secs_f64() >= 0.5 => {
+            ((cpu_secs - previous_secs) / now.duration_since(at).as_secs_f64() * 100.0).max(0.0)
+        }
+        _ => tree.iter().map(|entry| entry.cpu_percent).sum(),
+    };
+    Ok(ProcessStats {
+        pid,
+        cpu_percent,
+        rss_bytes: tree.iter().map(|entry| entry.rss_kb * 1024).sum(),
+        descendant_count: (tree.len() - 1) as u32,
+        sampled_at_ms: SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|duration| duration.as_millis() as i64)
+            .unwrap_or(0),
+    })
+}
+
+/// Drops the CPU baseline kept for `pid` once it is no longer sampled.
+#[allow(dead_code)]
+pub(crate) fn forget_process_samples(pid: u32) {
+    if let Ok(mut samples) = CPU_SAMPLES.lock() {
+        samples.remove(&pid);
+    }
+}
+
+#[allow(dead_code)]
 #[cfg(target_os = "windows")]
 pub(crate) fn resolve_windows_executable(program: &str, path_env: Option<&str>) -> Option<PathBuf> {
     let trimmed = program.trim();
@@ -150,6 +399,7 @@ fn quote_cmd_token(value: &str) -> Result<String, String> {
 ///
 /// Returns a string that already includes the required outer quotes, suitable to be passed as
 /// *one* argument after `/C` (usually with `/S`).
+#[allow(dead_code)]
 #[cfg(target_os = "windows")]
 pub(crate) fn build_cmd_c_command(program: &Path, args: &[String]) -> Result<String, String> {
     let program_str = program.to_string_lossy();
@@ -161,3 +411,272 @@ pub(crate) fn build_cmd_c_command(program: &Path, args: &[String]) -> Result<Str
     let inner = parts.join(" ");
     Ok(format!("\"{inner}\""))
 }
+
+/// Environment variable names whose plain values are never shown in a
+/// preview.
+const SECRET_ENV_MARKERS: &[&str] = &["TOKEN", "SECRET", "PASSWORD", "PASSWD", "API_KEY"];
+const MASKED_ENV_VALUE: &str = "<secret>";
+
+fn is_secret_env_name(name: &str) -> bool {
+    let name = name.to_ascii_uppercase();
+    SECRET_ENV_MARKERS
+        .iter()
+        .any(|marker| name.contains(marker))
+}
+
+impl PreviewShell {
+    /// The shell a preview on this platform is pasted into.
+    #[allow(dead_code)]
+    pub(crate) fn native() -> Self {
+        if cfg!(windows) {
+            Self::PowerShell
+        } else {
+            Self::Posix
+        }
+    }
+}
+
+/// Quotes `value` as a single word for `shell`.
+pub(crate) fn shell_quote(shell: PreviewShell, value: &str) -> String {
+    match shell {
+        PreviewShell::Posix => format!("'{}'", value.replace('\'', "'\"'\"'")),
+        PreviewShell::PowerShell => {
+            // PowerShell also closes single-quoted strings on typographic
+            // quotes; doubling escapes any of them.
+            let mut quoted = String::with_capacity(value.len() + 2);
+            quoted.push('\'');
+            for ch in value.chars() {
+                if matches!(ch, '\'' | '\u{2018}' | '\u{2019}' | '\u{201a}' | '\u{201b}') {
+                    quoted.push(ch);
+                }
+                quoted.push(ch);
+            }
+            quoted.push('\'');
+            quoted
+        }
+    }
+}
+
+/// A copy-to-shell rendering of a process the app spawns: working
+/// directory, environment assignments, program and arguments. Secrets never
+/// reach it; callers pass placeholders for secret arguments, and plain
+/// environment values with secret-looking names are masked.
+#[allow(dead_code)]
+#[derive(Debug, Clone)]
+pub(crate) struct CommandPreview {
+    program: String,
+    args: Vec<String>,
+    env: BTreeMap<String, String>,
+    working_dir: Option<String>,
+}
+
+#[allow(dead_code)]
+impl CommandPreview {
+    pub(crate) fn new(program: impl AsRef<OsStr>) -> Self {
+        Self {
+            program: program.as_ref().to_string_lossy().into_owned(),
+            args: Vec::new(),
+            env: BTreeMap::new(),
+            working_dir: None,
+        }
+    }
+
+    pub(crate) fn arg(mut self, value: impl AsRef<OsStr>) -> Self {
+        self.args
+            .push(value.as_ref().to_string_lossy().into_owned());
+        self
+    }
+
+    /// `flag` followed by `placeholder` where the real command has a secret.
+    pub(crate) fn secret_arg(self, flag: &str, placeholder: &str) -> Self {
+        self.arg(flag).arg(placeholder)
+    }
+
+    pub(crate) fn envs(mut self, env: BTreeMap<String, String>) -> Self {
+        for (name, value) in env {
+            let shown = if is_secret_env_name(&name) && !value.starts_with("<keychain:") {
+                MASKED_ENV_VALUE.to_string()
+            } else {
+                value
+            };
+            self.env.insert(name, shown);
+        }
+        self
+    }
+
+    pub(crate) fn current_dir(mut self, dir: Option<impl AsRef<OsStr>>) -> Self {
+        self.working_dir = dir.map(|dir| dir.as_ref().to_string_lossy().into_owned());
+        self
+    }
+
+    pub(crate) fn program(&self) -> &str {
+        &self.program
+    }
+
+    pub(crate) fn args(&self) -> &[String] {
+        &self.args
+    }
+
+    pub(crate) fn env(&self) -> &BTreeMap<String, String> {
+        &self.env
+    }
+
+    pub(crate) fn working_dir(&self) -> Option<&str> {
+        self.working_dir.as_deref()
+    }
+
+    /// The record kept for a process spawned from the command this previews.
+    pub(crate) fn launch(&self, shell: PreviewShell) -> ProcessLaunch {
+        ProcessLaunch {
+            command: self.render(shell),
+            shell,
+            program: self.program.clone(),
+            args: self.args.clone(),
+            env: self.env.clone(),
+            working_dir: self.working_dir.clone(),
+        }
+    }
+
+    pub(crate) fn render(&self, shell: PreviewShell) -> String {
+        let quote = |value: &str| shell_quote(shell, value);
+        let words = std::iter::once(quote(&self.program))
+            .chain(self.args.iter().map(|arg| quote(arg)))
+            .collect::<Vec<_>>()
+            .join(" ");
+        match shell {
+            PreviewShell::Posix => {
+                let mut line = String::new();
+                if let Some(dir) = &self.working_dir {
+                    line.push_str(&format!("cd {} && ", quote(dir)));
+                }
+                for (name, value) in &self.env {
+                    line.push_str(&format!("{name}={} ", quote(value)));
+                }
+                line + &words
+            }
+            PreviewShell::PowerShell => {
+                let mut statements = Vec::new();
+                if let Some(dir) = &self.working_dir {
+                    statements.push(format!("Set-Location -LiteralPath {}", quote(dir)));
+                }
+                for (name, value) in &self.env {
+                    statements.push(format!("$env:{name} = {}", quote(value)));
+                }
+                // A quoted program path needs the call operator.
+                statements.push(format!("& {words}"));
+                statements.join("; ")
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn shell_quote_escapes_per_shell() {
+        assert_eq!(
+            shell_quote(PreviewShell::Posix, "abc'def"),
+            "'abc'\"'\"'def'"
+        );
+        assert_eq!(shell_quote(PreviewShell::Posix, "abc\"def"), "'abc\"def'");
+        assert_eq!(
+            shell_quote(PreviewShell::PowerShell, "abc'def"),
+            "'abc''def'"
+        );
+        assert_eq!(
+            shell_quote(PreviewShell::PowerShell, "$HOME `x`"),
+            "'$HOME `x`'"
+        );
+        assert_eq!(
+            shell_quote(PreviewShell::PowerShell, "it\u{2019}s"),
+            "'it\u{2019}\u{2019}s'"
+        );
+        assert_eq!(shell_quote(PreviewShell::Posix, ""), "''");
+    }
+
+    #[test]
+    fn command_preview_renders_dir_env_and_masked_secrets() {
+        let preview = CommandPreview::new("/opt/codex monitor/daemon")
+            .arg("--listen")
+            .arg("0.0.0.0:4732")
+            .secret_arg("--token", "<remote-backend-token>")
+            .envs(BTreeMap::from([
+                ("GITHUB_TOKEN".to_string(), "ghp_live".to_string()),
+                (
+                    "OPENAI_API_KEY".to_string(),
+                    "<keychain:openai>".to_string(),
+                ),
+                ("RUST_LOG".to_string(), "debug".to_string()),
+            ]))
+            .current_dir(Some("/srv/it's"));
+
+        assert_eq!(preview.env()["GITHUB_TOKEN"], "<secret>");
+        assert_eq!(preview.env()["OPENAI_API_KEY"], "<keychain:openai>");
+        assert_eq!(
+            preview.render(PreviewShell::Posix),
+            "cd '/srv/it'\"'\"'s' && GITHUB_TOKEN='<secret>' OPENAI_API_KEY='<keychain:openai>' \
+             RUST_LOG='debug' '/opt/codex monitor/daemon' '--listen' '0.0.0.0:4732' '--token' \
+             '<remote-backend-token>'"
+        );
+        assert_eq!(
+            preview.render(PreviewShell::PowerShell),
+            "Set-Location -LiteralPath '/srv/it''s'; $env:GITHUB_TOKEN = '<secret>'; \
+             $env:OPENAI_API_KEY = '<keychain:openai>'; $env:RUST_LOG = 'debug'; \
+             & '/opt/codex monitor/daemon' '--listen' '0.0.0.0:4732' '--token' \
+             '<remote-backend-token>'"
+        );
+        assert!(!preview.render(PreviewShell::Posix).contains("ghp_live"));
+
+        let launch = preview.launch(PreviewShell::Posix);
+        assert_eq!(launch.command, preview.render(PreviewShell::Posix));
+        assert_eq!(launch.args[3], "<remote-backend-token>");
+        assert_eq!(launch.env["GITHUB_TOKEN"], "<secret>");
+        assert_eq!(launch.working_dir.as_deref(), Some("/srv/it's"));
+    }
+
+    #[test]
+    fn parse_ps_cpu_time_handles_linux_and_macos_formats() {
+        assert_eq!(parse_ps_cpu_time("00:01:05"), Some(65.0));
+        assert_eq!(parse_ps_cpu_time("2-01:00:00"), Some(176_400.0));
+        assert_eq!(parse_ps_cpu_time("1:02.50"), Some(62.5));
+        assert_eq!(parse_ps_cpu_time("bogus"), None);
+    }
+
+    #[test]
+    fn collect_process_tree_includes_nested_descendants() {
+        let entries: Vec<ProcessEntry> = [
+            "  10     1  2048 00:00:01  1.0",
+            "  11    10  1024 00:00:02  2.0",
+            "  12    11   512 00:00:03  3,5",
+            "  13     1   256 00:00:04  4.0",
+        ]
+        .iter()
+        .filter_map(|line| parse_ps_line(line))
+        .collect();
+        assert_eq!(entries.len(), 4);
+        assert_eq!(entries[2].cpu_percent, 3.5);
+
+        let tree = collect_process_tree(&entries, 10);
+        let pids: Vec<u32> = tree.iter().map(|entry| entry.pid).collect();
+        assert_eq!(pids, vec![10, 11, 12]);
+        assert!(collect_process_tree(&entries, 99).is_empty());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn sample_process_stats_reports_current_process() {
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .expect("runtime");
+        let Ok(stats) = runtime.block_on(sample_process_stats(std::process::id())) else {
+            // `ps` may be unavailable in minimal sandboxes.
+            return;
+        };
+        assert_eq!(stats.pid, std::process::id());
+        assert!(stats.rss_bytes > 0);
+        forget_process_samples(stats.pid);
+    }
+}
@@ -0,0 +1,242 @@
+use std::collections::BTreeMap;
+use std::ffi::OsString;
+use std::path::PathBuf;
+use std::sync::Mutex as StdMutex;
+
+use tokio::process::Command;
+
+use crate::types::ProcessEnvConfig;
+
+/// Keychain service that `keychainVars` accounts are looked up under.
+const KEYCHAIN_SERVICE: &str = "CodexMonitor";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum EnvValue {
+    Plain(String),
+    /// Account name of a keychain item, resolved only at spawn time.
+    Keychain(String),
+}
+
+// Codex is spawned from many places that never see `AppSettings`, so its
+// environment is mirrored here whenever settings are loaded or saved.
+static CODEX_PROCESS_ENV: StdMutex<Option<ProcessEnvConfig>> = StdMutex::new(None);
+
+#[allow(dead_code)]
+pub(crate) fn set_codex_process_env(config: &ProcessEnvConfig) {
+    if let Ok(mut current) = CODEX_PROCESS_ENV.lock() {
+        *current = Some(config.clone());
+    }
+}
+
+#[allow(dead_code)]
+pub(crate) fn codex_process_env() -> ProcessEnvConfig {
+    CODEX_PROCESS_ENV
+        .lock()
+        .ok()
+        .and_then(|current| current.clone())
+        .unwrap_or_default()
+}
+
+fn non_empty(value: Option<&String>) -> Option<&str> {
+    value
+        .map(|value| value.trim())
+        .filter(|value| !value.is_empty())
+}
+
+fn valid_name(name: &str) -> bool {
+    !name.is_empty() && !name.contains('=') && !name.contains('\0')
+}
+
+/// Overrides `config` adds on top of the inherited environment. Keychain
+/// variables win over plain ones, which win over the proxy fields; PATH
+/// entries go in front of `base_path` (the process's own PATH otherwise).
+pub(crate) fn effective_env(
+    config: &ProcessEnvConfig,
+    base_path: Option<&str>,
+) -> BTreeMap<String, EnvValue> {
+    let mut env = BTreeMap::new();
+    let proxies = [
+        (non_empty(config.http_proxy.as_ref()), "HTTP_PROXY"),
+        (non_empty(config.https_proxy.as_ref()), "HTTPS_PROXY"),
+        (non_empty(config.no_proxy.as_ref()), "NO_PROXY"),
+    ];
+    for (value, name) in proxies {
+        let Some(value) = value else {
+            continue;
+        };
+        // Tools disagree on the casing they read, so set both.
+        env.insert(name.to_string(), EnvValue::Plain(value.to_string()));
+        env.insert(name.to_lowercase(), EnvValue::Plain(value.to_string()));
+    }
+    for (name, value) in &config.vars {
+        if valid_name(name.trim()) {
+            env.insert(name.trim().to_string(), EnvValue::Plain(value.clone()));
+        }
+    }
+    for (name, account) in &config.keychain_vars {
+        if valid_name(name.trim()) && !account.trim().is_empty() {
+            env.insert(
+                name.trim().to_string(),
+                EnvValue::Keychain(account.trim().to_string()),
+            );
+        }
+    }
+
+    let prepend: Vec<PathBuf> = config
+        .path_prepend
+        .iter()
+        .map(|entry| entry.trim())
+        .filter(|entry| !entry.is_empty())
+        .map(PathBuf::from)
+        .collect();
+    if !prepend.is_empty() {
+        let base = match env.get("PATH") {
+            Some(EnvValue::Plain(path)) => Some(OsString::from(path)),
+            _ => base_path
+                .map(OsString::from)
+                .or_else(|| std::env::var_os("PATH")),
+        };
+        let rest = base
+            .as_deref()
+            .map(|path| std::env::split_paths(path).collect::<Vec<_>>())
+            .unwrap_or_default();
+        if let Ok(joined) = std::env::join_paths(prepend.into_iter().chain(rest)) {
+            env.insert(
+                "PATH".to_string(),
+                EnvValue::Plain(joined.to_string_lossy().to_string()),
+            );
+        }
+    }
+    env
+}
+
+#[cfg(target_os = "macos")]
+pub(crate) fn read_keychain_secret(account: &str) -> Result<String, String> {
+    let output = std::process::Command::new("/usr/bin/security")
+        .args([
+            "find-generic-password",
+            "-s",
+            KEYCHAIN_SERVICE,
+            "-a",
+            account,
+            "-w",
+        ])
+        .output()
+        .map_err(|err| format!("Failed to read keychain item `{account}`: {err}"))?;
+    keychain_output(account, output)
+}
+
+#[cfg(all(unix, not(target_os = "macos")))]
+pub(crate) fn read_keychain_secret(account: &str) -> Result<String, String> {
+    let output = std::process::Command::new("secret-tool")
+        .args(["lookup", "service", KEYCHAIN_SERVICE, "account", account])
+        .output()
+        .map_err(|err| format!("Failed to read keychain item `{account}`: {err}"))?;
+    keychain_output(account, output)
+}
+
+#[cfg(not(unix))]
+pub(crate) fn read_keychain_secret(account: &str) -> Result<String, String> {
+    Err(format!(
+        "Keychain item `{account}` cannot be read: keychain variables are only supported on macOS and Linux."
+    ))
+}
+
+#[cfg(unix)]
+fn keychain_output(account: &str, output: std::process::Output) -> Result<String, String> {
+    if !output.status.success() {
+        return Err(format!(
+            "Keychain item `{account}` (service {KEYCHAIN_SERVICE}) was not found."
+        ));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout)
+        .trim_end_matches('\n')
+        .to_string())
+}
+
+/// Applies `config` to `command`, reading keychain items now so secrets are
+/// never written to settings. Fails when a referenced item is missing.
+pub(crate) fn apply_process_env(
+    command: &mut Command,
+    config: &ProcessEnvConfig,
+    base_path: Option<&str>,
+) -> Result<(), String> {
+    for (name, value) in effective_env(config, base_path) {
+        let value = match value {
+            EnvValue::Plain(value) => value,
+            EnvValue::Keychain(account) => read_keychain_secret(&account)?,
+        };
+        command.env(name, value);
+    }
+    Ok(())
+}
+
+/// The environment as shown in previews, with keychain values left
+/// unresolved.
+#[allow(dead_code)]
+pub(crate) fn env_preview(env: &BTreeMap<String, EnvValue>) -> BTreeMap<String, String> {
+    env.iter()
+        .map(|(name, value)| {
+            let shown = match value {
+                EnvValue::Plain(value) => value.clone(),
+                EnvValue::Keychain(account) => format!("<keychain:{account}>"),
+            };
+            (name.clone(), shown)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn keychain_vars_override_plain_vars_and_proxies() {
+        let config = ProcessEnvConfig {
+            vars: BTreeMap::from([
+                ("HTTPS_PROXY".to_string(), "http://vars:1".to_string()),
+                ("API_KEY".to_string(), "inline".to_string()),
+                ("BAD=NAME".to_string(), "x".to_string()),
+            ]),
+            https_proxy: Some("http://proxy:8080".to_string()),
+            no_proxy: Some("  ".to_string()),
+            keychain_vars: BTreeMap::from([("API_KEY".to_string(), "openai".to_string())]),
+            ..ProcessEnvConfig::default()
+        };
+        let env = effective_env(&config, None);
+        assert_eq!(
+            env.get("HTTPS_PROXY"),
+            Some(&EnvValue::Plain("http://vars:1".to_string()))
+        );
+        assert_eq!(
+            env.get("https_proxy"),
+            Some(&EnvValue::Plain("http://proxy:8080".to_string()))
+        );
+        assert_eq!(
+            env.get("API_KEY"),
+            Some(&EnvValue::Keychain("openai".to_string()))
+        );
+        assert!(!env.contains_key("NO_PROXY"));
+        assert!(!env.contains_key("BAD=NAME"));
+        assert_eq!(
+            env_preview(&env).get("API_KEY").map(String::as_str),
+            Some("<keychain:openai>")
+        );
+    }
+
+    #[test]
+    fn path_entries_are_prepended_to_the_base_path() {
+        let config = ProcessEnvConfig {
+            path_prepend: vec!["/opt/tools/bin".to_string(), " ".to_string()],
+            ..ProcessEnvConfig::default()
+        };
+        let base = std::env::join_paths(["/usr/bin", "/bin"]).expect("join");
+        let env = effective_env(&config, base.to_str());
+        let expected = std::env::join_paths(["/opt/tools/bin", "/usr/bin", "/bin"]).expect("join");
+        assert_eq!(
+            env.get("PATH"),
+            Some(&EnvValue::Plain(expected.to_string_lossy().to_string()))
+        );
+        assert!(effective_env(&ProcessEnvConfig::default(), base.to_str()).is_empty());
+    }
+}
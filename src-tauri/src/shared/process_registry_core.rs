@@ -0,0 +1,412 @@
+use std::collections::HashMap;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex as StdMutex, OnceLock};
+use std::time::SystemTime;
+
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::shared::process_core::kill_pid_gracefully;
+
+/// One file per spawned pid, so the app and a daemon sharing the data dir
+/// never rewrite each other's records.
+const PROCESS_REGISTRY_DIR: &str = "processes";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub(crate) enum SpawnedProcessKind {
+    Daemon,
+    CodexSession,
+}
+
+/// What makes a pid this process rather than a later one that reused the
+/// number: its executable and the OS-reported start time.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct ProcessIdentity {
+    pub(crate) pid: u32,
+    pub(crate) exe_path: String,
+    pub(crate) start_time: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct SpawnedProcess {
+    pub(crate) kind: SpawnedProcessKind,
+    pub(crate) identity: ProcessIdentity,
+    pub(crate) binary_sha256: Option<String>,
+    /// The app or daemon that spawned it; records are only reaped once the
+    /// owner is gone.
+    pub(crate) owner: Option<ProcessIdentity>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum OrphanAction {
+    /// The owner is still running.
+    Keep,
+    /// The pid no longer belongs to the recorded process.
+    Forget,
+    Adopt,
+    Terminate,
+}
+
+#[cfg(target_os = "linux")]
+pub(crate) fn process_identity(pid: u32) -> Option<ProcessIdentity> {
+    let exe_path = std::fs::read_link(format!("/proc/{pid}/exe")).ok()?;
+    let stat = std::fs::read_to_string(format!("/proc/{pid}/stat")).ok()?;
+    // `comm` may contain spaces and parentheses; fields resume after the
+    // last `)`. `starttime` is field 22, the 20th after it.
+    let (_, fields) = stat.rsplit_once(')')?;
+    let start_time = fields.split_whitespace().nth(19)?.to_string();
+    Some(ProcessIdentity {
+        pid,
+        exe_path: exe_path.to_string_lossy().to_string(),
+        start_time,
+    })
+}
+
+#[cfg(target_os = "macos")]
+pub(crate) fn process_identity(pid: u32) -> Option<ProcessIdentity> {
+    let mut path = vec![0u8; libc::PROC_PIDPATHINFO_MAXSIZE as usize];
+    let len = unsafe {
+        libc::proc_pidpath(
+            pid as libc::c_int,
+            path.as_mut_ptr().cast(),
+            path.len() as u32,
+        )
+    };
+    if len <= 0 {
+        return None;
+    }
+    path.truncate(len as usize);
+
+    let mut info: libc::proc_bsdinfo = unsafe { std::mem::zeroed() };
+    let size = std::mem::size_of::<libc::proc_bsdinfo>() as libc::c_int;
+    let read = unsafe {
+        libc::proc_pidinfo(
+            pid as libc::c_int,
+            libc::PROC_PIDTBSDINFO,
+            0,
+            (&mut info as *mut libc::proc_bsdinfo).cast(),
+            size,
+        )
+    };
+    if read != size {
+        return None;
+    }
+    Some(ProcessIdentity {
+        pid,
+        exe_path: String::from_utf8_lossy(&path).to_string(),
+        start_time: format!("{}.{:06}", info.pbi_start_tvsec, info.pbi_start_tvusec),
+    })
+}
+
+#[cfg(windows)]
+pub(crate) fn process_identity(pid: u32) -> Option<ProcessIdentity> {
+    use windows_sys::Win32::Foundation::{CloseHandle, FILETIME, STILL_ACTIVE};
+    use windows_sys::Win32::System::Threading::{
+        GetExitCodeProcess, GetProcessTimes, OpenProcess, QueryFullProcessImageNameW,
+        PROCESS_NAME_WIN32, PROCESS_QUERY_LIMITED_INFORMATION,
+    };
+
+    let handle = unsafe { OpenProcess(PROCESS_QUERY_LIMITED_INFORMATION, 0, pid) };
+    if handle.is_null() {
+        return None;
+    }
+    // An exited process stays openable while anyone holds a handle to it.
+    let mut exit_code = 0u32;
+    let running = unsafe { GetExitCodeProcess(handle, &mut exit_code) } != 0
+        && exit_code == STILL_ACTIVE as u32;
+    let mut path = vec![0u16; 32 * 1024];
+    let mut len = path.len() as u32;
+    let named = unsafe {
+        QueryFullProcessImageNameW(handle, PROCESS_NAME_WIN32, path.as_mut_ptr(), &mut len)
+    } != 0;
+    let empty = FILETIME {
+        dwLowDateTime: 0,
+        dwHighDateTime: 0,
+    };
+    let (mut created, mut exited, mut kernel, mut user) = (empty, empty, empty, empty);
+    let timed =
+        unsafe { GetProcessTimes(handle, &mut created, &mut exited, &mut kernel, &mut user) } != 0;
+    unsafe { CloseHandle(handle) };
+    if !running || !named || !timed {
+        return None;
+    }
+    path.truncate(len as usize);
+    let start_time = (u64::from(created.dwHighDateTime) << 32) | u64::from(created.dwLowDateTime);
+    Some(ProcessIdentity {
+        pid,
+        exe_path: String::from_utf16_lossy(&path),
+        start_time: start_time.to_string(),
+    })
+}
+
+/// Without a way to verify identity nothing is recorded, so nothing is ever
+/// reaped.
+#[cfg(not(any(target_os = "linux", target_os = "macos", windows)))]
+pub(crate) fn process_identity(_pid: u32) -> Option<ProcessIdentity> {
+    None
+}
+
+fn current_process_identity() -> Option<ProcessIdentity> {
+    static CURRENT: OnceLock<Option<ProcessIdentity>> = OnceLock::new();
+    CURRENT
+        .get_or_init(|| process_identity(std::process::id()))
+        .clone()
+}
+
+// Hashes keyed by (path, size, mtime) so launching many sessions from the
+// same binary only reads it once.
+type BinaryHashCache = HashMap<(PathBuf, u64, SystemTime), String>;
+static BINARY_HASHES: StdMutex<Option<BinaryHashCache>> = StdMutex::new(None);
+
+pub(crate) fn binary_sha256(path: &Path) -> Option<String> {
+    let metadata = std::fs::metadata(path).ok()?;
+    let key = (
+        path.to_path_buf(),
+        metadata.len(),
+        metadata.modified().ok()?,
+    );
+    if let Some(hash) = BINARY_HASHES
+        .lock()
+        .ok()
+        .and_then(|cache| cache.as_ref()?.get(&key).cloned())
+    {
+        return Some(hash);
+    }
+
+    let mut file = std::fs::File::open(path).ok()?;
+    let mut hasher = Sha256::new();
+    let mut buffer = [0u8; 64 * 1024];
+    loop {
+        let read = file.read(&mut buffer).ok()?;
+        if read == 0 {
+            break;
+        }
+        hasher.update(&buffer[..read]);
+    }
+    let hash: String = hasher
+        .finalize()
+        .iter()
+        .map(|byte| format!("{byte:02x}"))
+        .collect();
+    if let Ok(mut cache) = BINARY_HASHES.lock() {
+        cache
+            .get_or_insert_with(HashMap::new)
+            .insert(key, hash.clone());
+    }
+    Some(hash)
+}
+
+fn registry_dir(data_dir: &Path) -> PathBuf {
+    data_dir.join(PROCESS_REGISTRY_DIR)
+}
+
+fn record_path(data_dir: &Path, pid: u32) -> PathBuf {
+    registry_dir(data_dir).join(format!("{pid}.json"))
+}
+
+fn write_record(data_dir: &Path, record: &SpawnedProcess) -> Result<(), String> {
+    std::fs::create_dir_all(registry_dir(data_dir)).map_err(|err| err.to_string())?;
+    let data = serde_json::to_string_pretty(record).map_err(|err| err.to_string())?;
+    std::fs::write(record_path(data_dir, record.identity.pid), data).map_err(|err| err.to_string())
+}
+
+/// Records a process this process just spawned so a later startup can tell
+/// whether it was orphaned by a crash.
+pub(crate) fn record_spawned_process(
+    data_dir: &Path,
+    kind: SpawnedProcessKind,
+    pid: u32,
+) -> Result<(), String> {
+    let identity =
+        process_identity(pid).ok_or_else(|| format!("Unable to read identity of process {pid}"))?;
+    let record = SpawnedProcess {
+        kind,
+        binary_sha256: binary_sha256(Path::new(&identity.exe_path)),
+        identity,
+        owner: current_process_identity(),
+    };
+    write_record(data_dir, &record)
+}
+
+pub(crate) fn forget_spawned_process(data_dir: &Path, pid: u32) {
+    let _ = std::fs::remove_file(record_path(data_dir, pid));
+}
+
+pub(crate) fn list_spawned_processes(data_dir: &Path) -> Vec<SpawnedProcess> {
+    let Ok(entries) = std::fs::read_dir(registry_dir(data_dir)) else {
+        return Vec::new();
+    };
+    entries
+        .flatten()
+        .filter_map(|entry| std::fs::read_to_string(entry.path()).ok())
+        .filter_map(|data| serde_json::from_str(&data).ok())
+        .collect()
+}
+
+/// A recorded daemon whose pid still belongs to it, for callers that would
+/// otherwise have to guess the pid from the listening port.
+#[allow(dead_code)]
+pub(crate) fn recorded_daemon_pid(data_dir: &Path) -> Option<u32> {
+    list_spawned_processes(data_dir)
+        .into_iter()
+        .filter(|record| record.kind == SpawnedProcessKind::Daemon)
+        .find(|record| process_identity(record.identity.pid).as_ref() == Some(&record.identity))
+        .map(|record| record.identity.pid)
+}
+
+/// The hash of the binary the recorded process `pid` was spawned from, while
+/// that pid still belongs to it.
+#[allow(dead_code)]
+pub(crate) fn recorded_binary_sha256(data_dir: &Path, pid: u32) -> Option<String> {
+    list_spawned_processes(data_dir)
+        .into_iter()
+        .find(|record| record.identity.pid == pid && is_alive(&record.identity))?
+        .binary_sha256
+}
+
+pub(crate) fn is_alive(identity: &ProcessIdentity) -> bool {
+    process_identity(identity.pid).as_ref() == Some(identity)
+}
+
+fn orphan_action(
+    record: &SpawnedProcess,
+    owner_alive: bool,
+    process_alive: bool,
+    daemon_sha256: Option<&str>,
+) -> OrphanAction {
+    if !process_alive {
+        return OrphanAction::Forget;
+    }
+    if owner_alive {
+        return OrphanAction::Keep;
+    }
+    // A daemon from the current build can be managed again; anything else
+    // has lost the process that was reading its output.
+    let current_daemon = record.kind == SpawnedProcessKind::Daemon
+        && daemon_sha256.is_some()
+        && record.binary_sha256.as_deref() == daemon_sha256;
+    if current_daemon {
+        OrphanAction::Adopt
+    } else {
+        OrphanAction::Terminate
+    }
+}
+
+/// Cleans up after a crash: processes whose owner is gone are adopted when
+/// they are a daemon built from `daemon_binary`, and stopped otherwise. A
+/// pid is only signalled after its exe path and start time match the
+/// record.
+#[allow(dead_code)]
+pub(crate) async fn reap_orphaned_processes(data_dir: &Path, daemon_binary: Option<&Path>) {
+    let daemon_sha256 = daemon_binary.and_then(binary_sha256);
+    for record in list_spawned_processes(data_dir) {
+        let pid = record.identity.pid;
+        let owner_alive = record.owner.as_ref().is_some_and(is_alive);
+        let action = orphan_action(
+            &record,
+            owner_alive,
+            is_alive(&record.identity),
+            daemon_sha256.as_deref(),
+        );
+        match action {
+            OrphanAction::Keep => {}
+            OrphanAction::Forget => forget_spawned_process(data_dir, pid),
+            OrphanAction::Adopt => {
+                let adopted = SpawnedProcess {
+                    owner: current_process_identity(),
+                    ..record
+                };
+                let _ = write_record(data_dir, &adopted);
+            }
+            OrphanAction::Terminate => {
+                if kill_pid_gracefully(pid, false).await.is_ok() {
+                    forget_spawned_process(data_dir, pid);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn identity(pid: u32) -> ProcessIdentity {
+        ProcessIdentity {
+            pid,
+            exe_path: "/usr/bin/example".to_string(),
+            start_time: "100".to_string(),
+        }
+    }
+
+    fn record(kind: SpawnedProcessKind, hash: Option<&str>) -> SpawnedProcess {
+        SpawnedProcess {
+            kind,
+            identity: identity(42),
+            binary_sha256: hash.map(str::to_string),
+            owner: Some(identity(7)),
+        }
+    }
+
+    #[test]
+    fn orphans_are_adopted_only_when_they_are_the_current_daemon() {
+        let daemon = record(SpawnedProcessKind::Daemon, Some("abc"));
+        let session = record(SpawnedProcessKind::CodexSession, Some("abc"));
+        assert_eq!(
+            orphan_action(&daemon, false, true, Some("abc")),
+            OrphanAction::Adopt
+        );
+        assert_eq!(
+            orphan_action(&daemon, false, true, Some("def")),
+            OrphanAction::Terminate
+        );
+        assert_eq!(
+            orphan_action(&daemon, false, true, None),
+            OrphanAction::Terminate
+        );
+        assert_eq!(
+            orphan_action(&session, false, true, Some("abc")),
+            OrphanAction::Terminate
+        );
+        assert_eq!(
+            orphan_action(&session, true, true, None),
+            OrphanAction::Keep
+        );
+        assert_eq!(
+            orphan_action(&session, false, false, None),
+            OrphanAction::Forget
+        );
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn records_round_trip_and_verify_the_live_process() {
+        let data_dir =
+            std::env::temp_dir().join(format!("codex-monitor-processes-{}", uuid::Uuid::new_v4()));
+        let pid = std::process::id();
+        record_spawned_process(&data_dir, SpawnedProcessKind::Daemon, pid).expect("record");
+
+        let records = list_spawned_processes(&data_dir);
+        assert_eq!(records.len(), 1);
+        assert_eq!(
+            records[0].identity,
+            process_identity(pid).expect("identity")
+        );
+        assert!(records[0].binary_sha256.is_some());
+        assert_eq!(recorded_daemon_pid(&data_dir), Some(pid));
+
+        // A different start time means the pid was reused.
+        let mut stale = records[0].clone();
+        stale.identity.start_time = "0".to_string();
+        write_record(&data_dir, &stale).expect("write");
+        assert_eq!(recorded_daemon_pid(&data_dir), None);
+
+        forget_spawned_process(&data_dir, pid);
+        assert!(list_spawned_processes(&data_dir).is_empty());
+        let _ = std::fs::remove_dir_all(&data_dir);
+    }
+}
@@ -1,4 +1,4 @@
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::fs;
 use std::path::{Path, PathBuf};
@@ -8,7 +8,7 @@ use tokio::task;
 use crate::codex::home::{resolve_default_codex_home, resolve_workspace_codex_home};
 use crate::types::WorkspaceEntry;
 
-#[derive(Serialize, Clone)]
+#[derive(Serialize, Deserialize, Clone)]
 pub(crate) struct CustomPromptEntry {
     pub(crate) name: String,
     pub(crate) path: String,
@@ -16,7 +16,7 @@ pub(crate) struct CustomPromptEntry {
     #[serde(rename = "argumentHint")]
     pub(crate) argument_hint: Option<String>,
     pub(crate) content: String,
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub(crate) scope: Option<String>,
 }
 
@@ -0,0 +1,66 @@
+//! Plain TCP connects to a daemon port, used to tell whether a tailnet
+//! policy lets one node reach another.
+
+use std::io::ErrorKind;
+use std::time::{Duration, Instant};
+
+use tokio::net::TcpStream;
+
+use crate::types::{TcpProbeOutcome, TcpProbeResult};
+
+/// Daemon RPC a peer answers by connecting to `host:port` itself.
+pub(crate) const REACHABILITY_PROBE_METHOD: &str = "reachability_probe";
+const PROBE_TIMEOUT: Duration = Duration::from_secs(3);
+
+pub(crate) async fn probe_tcp(host: &str, port: u16) -> TcpProbeResult {
+    let started = Instant::now();
+    let connect = tokio::time::timeout(PROBE_TIMEOUT, TcpStream::connect((host, port))).await;
+    let (outcome, error) = match connect {
+        Ok(Ok(_)) => (TcpProbeOutcome::Connected, None),
+        Ok(Err(err)) if err.kind() == ErrorKind::ConnectionRefused => {
+            (TcpProbeOutcome::Refused, Some(err.to_string()))
+        }
+        Ok(Err(err)) => (TcpProbeOutcome::Failed, Some(err.to_string())),
+        Err(_) => (
+            TcpProbeOutcome::TimedOut,
+            Some(format!(
+                "no answer within {} seconds",
+                PROBE_TIMEOUT.as_secs()
+            )),
+        ),
+    };
+    TcpProbeResult {
+        latency_ms: (outcome == TcpProbeOutcome::Connected)
+            .then(|| started.elapsed().as_millis() as u64),
+        outcome,
+        error,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reports_connected_and_refused_ports() {
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .expect("runtime");
+        runtime.block_on(async {
+            let listener = tokio::net::TcpListener::bind("127.0.0.1:0")
+                .await
+                .expect("bind");
+            let port = listener.local_addr().expect("local addr").port();
+
+            let open = probe_tcp("127.0.0.1", port).await;
+            assert_eq!(open.outcome, TcpProbeOutcome::Connected);
+            assert!(open.latency_ms.is_some());
+
+            drop(listener);
+            let closed = probe_tcp("127.0.0.1", port).await;
+            assert_eq!(closed.outcome, TcpProbeOutcome::Refused);
+            assert!(closed.error.is_some());
+        });
+    }
+}
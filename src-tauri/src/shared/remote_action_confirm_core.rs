@@ -0,0 +1,342 @@
+//! Destructive requests from remote clients are held by the daemon until
+//! the owner confirms them on the desktop. Confirmations live in a file in
+//! the daemon data directory, so the app answers them by writing to it
+//! when it manages the daemon, and over the connection otherwise.
+
+use std::path::{Path, PathBuf};
+use std::sync::Mutex as StdMutex;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use serde_json::Value;
+
+use crate::types::{RemoteActionConfirmation, RemoteActionConfirmationStatus};
+
+const REMOTE_ACTION_CONFIRMATIONS_FILENAME: &str = "remote-action-confirmations.json";
+/// Finished confirmations kept for the history in settings.
+const MAX_FINISHED_CONFIRMATIONS: usize = 50;
+const DECISION_POLL_INTERVAL: Duration = Duration::from_millis(250);
+
+/// Emitted (by the daemon to its clients, and by the app to its frontend)
+/// when a request is waiting for the owner to confirm it.
+pub(crate) const REMOTE_ACTION_CONFIRMATION_REQUESTED_EVENT: &str =
+    "remote-action-confirmation-requested";
+
+/// Answered by the daemon's transport, which knows whether the host is
+/// asking, rather than by the RPC dispatcher.
+pub(crate) const DECIDE_REMOTE_ACTION_CONFIRMATION_METHOD: &str =
+    "decide_remote_action_confirmation";
+
+// Guards read-modify-write cycles of the confirmations file.
+static CONFIRMATIONS_LOCK: StdMutex<()> = StdMutex::new(());
+
+fn confirmations_path(data_dir: &Path) -> PathBuf {
+    data_dir.join(REMOTE_ACTION_CONFIRMATIONS_FILENAME)
+}
+
+fn now_ms() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_millis() as i64)
+        .unwrap_or(0)
+}
+
+fn read_confirmations(path: &Path) -> Result<Vec<RemoteActionConfirmation>, String> {
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+    let data = std::fs::read_to_string(path).map_err(|err| err.to_string())?;
+    serde_json::from_str(&data).map_err(|err| err.to_string())
+}
+
+fn write_confirmations(
+    path: &Path,
+    confirmations: &[RemoteActionConfirmation],
+) -> Result<(), String> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent).map_err(|err| err.to_string())?;
+    }
+    let data = serde_json::to_string_pretty(confirmations).map_err(|err| err.to_string())?;
+    std::fs::write(path, data).map_err(|err| err.to_string())
+}
+
+/// Marks pending confirmations past their deadline as expired, covering
+/// requests a daemon stopped waiting on without recording it.
+fn expire_overdue(confirmations: &mut [RemoteActionConfirmation], now: i64) -> bool {
+    let mut changed = false;
+    for confirmation in confirmations.iter_mut() {
+        if confirmation.status == RemoteActionConfirmationStatus::Pending
+            && confirmation.expires_at_ms <= now
+        {
+            confirmation.status = RemoteActionConfirmationStatus::Expired;
+            confirmation.decided_at_ms = Some(now);
+            changed = true;
+        }
+    }
+    changed
+}
+
+fn param<'a>(params: &'a Value, key: &str) -> Option<&'a str> {
+    params
+        .get(key)
+        .and_then(Value::as_str)
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+}
+
+/// What a request held for confirmation will do, or `None` when `method`
+/// runs without one.
+#[allow(dead_code)]
+pub(crate) fn confirmation_summary(method: &str, params: &Value) -> Option<String> {
+    match method {
+        "terminate_session" => {
+            let session = param(params, "sessionId").unwrap_or("an unknown session");
+            let force = params.get("force").and_then(Value::as_bool) == Some(true);
+            Some(if force {
+                format!("Force-terminate session {session}")
+            } else {
+                format!("Terminate session {session}")
+            })
+        }
+        "run_remote_command" => {
+            let command = param(params, "command").unwrap_or("");
+            Some(match param(params, "cwd") {
+                Some(cwd) => format!("Run `{command}` in {cwd}"),
+                None => format!("Run `{command}` in the workspace root"),
+            })
+        }
+        "file_write"
+            if param(params, "scope") == Some("global")
+                && param(params, "kind") == Some("config") =>
+        {
+            Some("Overwrite the global Codex config.toml".to_string())
+        }
+        _ => None,
+    }
+}
+
+/// Records a pending confirmation for `method`, due to expire after
+/// `timeout`.
+#[allow(dead_code)]
+pub(crate) fn request_confirmation(
+    data_dir: &Path,
+    method: &str,
+    summary: String,
+    client_id: Option<String>,
+    client_name: Option<String>,
+    timeout: Duration,
+) -> Result<RemoteActionConfirmation, String> {
+    let _guard = CONFIRMATIONS_LOCK.lock().map_err(|err| err.to_string())?;
+    let path = confirmations_path(data_dir);
+    let mut confirmations = read_confirmations(&path)?;
+    let now = now_ms();
+    expire_overdue(&mut confirmations, now);
+    let confirmation = RemoteActionConfirmation {
+        id: uuid::Uuid::new_v4().to_string(),
+        method: method.to_string(),
+        summary,
+        client_id,
+        client_name,
+        status: RemoteActionConfirmationStatus::Pending,
+        requested_at_ms: now,
+        expires_at_ms: now + timeout.as_millis() as i64,
+        decided_at_ms: None,
+    };
+    confirmations.push(confirmation.clone());
+    let finished = confirmations
+        .iter()
+        .filter(|entry| entry.status != RemoteActionConfirmationStatus::Pending)
+        .count();
+    let mut excess = finished.saturating_sub(MAX_FINISHED_CONFIRMATIONS);
+    confirmations.retain(|entry| {
+        if excess > 0 && entry.status != RemoteActionConfirmationStatus::Pending {
+            excess -= 1;
+            return false;
+        }
+        true
+    });
+    write_confirmations(&path, &confirmations)?;
+    Ok(confirmation)
+}
+
+fn status_of(data_dir: &Path, id: &str) -> Option<RemoteActionConfirmationStatus> {
+    let _guard = CONFIRMATIONS_LOCK.lock().ok()?;
+    read_confirmations(&confirmations_path(data_dir))
+        .ok()?
+        .into_iter()
+        .find(|confirmation| confirmation.id == id)
+        .map(|confirmation| confirmation.status)
+}
+
+/// Waits for the owner to decide on `confirmation`, failing when they
+/// reject it or do not answer before it expires.
+#[allow(dead_code)]
+pub(crate) async fn await_confirmation(
+    data_dir: &Path,
+    confirmation: &RemoteActionConfirmation,
+) -> Result<(), String> {
+    loop {
+        match status_of(data_dir, &confirmation.id) {
+            Some(RemoteActionConfirmationStatus::Approved) => return Ok(()),
+            Some(RemoteActionConfirmationStatus::Rejected) => {
+                return Err(format!(
+                    "`{}` was rejected on the host",
+                    confirmation.method
+                ))
+            }
+            Some(RemoteActionConfirmationStatus::Expired) | None => break,
+            Some(RemoteActionConfirmationStatus::Pending) => {}
+        }
+        if now_ms() >= confirmation.expires_at_ms {
+            break;
+        }
+        tokio::time::sleep(DECISION_POLL_INTERVAL).await;
+    }
+    if let Ok(_guard) = CONFIRMATIONS_LOCK.lock() {
+        let path = confirmations_path(data_dir);
+        if let Ok(mut confirmations) = read_confirmations(&path) {
+            if expire_overdue(&mut confirmations, now_ms()) {
+                let _ = write_confirmations(&path, &confirmations);
+            }
+        }
+    }
+    Err(format!(
+        "`{}` timed out waiting for confirmation on the host",
+        confirmation.method
+    ))
+}
+
+/// Lists confirmations, pending ones first, then most recent.
+pub(crate) fn list_remote_action_confirmations_core(
+    data_dir: &Path,
+) -> Result<Vec<RemoteActionConfirmation>, String> {
+    let _guard = CONFIRMATIONS_LOCK.lock().map_err(|err| err.to_string())?;
+    let mut confirmations = read_confirmations(&confirmations_path(data_dir))?;
+    expire_overdue(&mut confirmations, now_ms());
+    confirmations.sort_by_key(|confirmation| {
+        (
+            confirmation.status != RemoteActionConfirmationStatus::Pending,
+            std::cmp::Reverse(confirmation.requested_at_ms),
+        )
+    });
+    Ok(confirmations)
+}
+
+/// Approves or rejects a pending confirmation. Callers only decide for the
+/// host: the app writing to the daemon it manages, or the daemon answering
+/// the host's own connection.
+pub(crate) fn decide_remote_action_confirmation_core(
+    data_dir: &Path,
+    id: &str,
+    approved: bool,
+) -> Result<RemoteActionConfirmation, String> {
+    let _guard = CONFIRMATIONS_LOCK.lock().map_err(|err| err.to_string())?;
+    let path = confirmations_path(data_dir);
+    let mut confirmations = read_confirmations(&path)?;
+    let now = now_ms();
+    let expired = expire_overdue(&mut confirmations, now);
+    let confirmation = confirmations
+        .iter_mut()
+        .find(|confirmation| confirmation.id == id)
+        .ok_or_else(|| "confirmation not found".to_string())?;
+    if confirmation.status != RemoteActionConfirmationStatus::Pending {
+        if expired {
+            write_confirmations(&path, &confirmations)?;
+        }
+        return Err("confirmation is no longer pending".to_string());
+    }
+    confirmation.status = if approved {
+        RemoteActionConfirmationStatus::Approved
+    } else {
+        RemoteActionConfirmationStatus::Rejected
+    };
+    confirmation.decided_at_ms = Some(now);
+    let decided = confirmation.clone();
+    write_confirmations(&path, &confirmations)?;
+    Ok(decided)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn temp_dir() -> PathBuf {
+        std::env::temp_dir().join(format!("codex-monitor-confirm-{}", uuid::Uuid::new_v4()))
+    }
+
+    #[test]
+    fn only_destructive_methods_need_confirmation() {
+        assert_eq!(
+            confirmation_summary("terminate_session", &json!({ "sessionId": "s1" })).as_deref(),
+            Some("Terminate session s1")
+        );
+        assert_eq!(
+            confirmation_summary(
+                "run_remote_command",
+                &json!({ "workspaceId": "w", "command": "rm -rf target", "cwd": "app" })
+            )
+            .as_deref(),
+            Some("Run `rm -rf target` in app")
+        );
+        assert!(confirmation_summary(
+            "file_write",
+            &json!({ "scope": "global", "kind": "config", "content": "" })
+        )
+        .is_some());
+        assert!(confirmation_summary(
+            "file_write",
+            &json!({ "scope": "workspace", "kind": "config", "content": "" })
+        )
+        .is_none());
+        assert!(confirmation_summary("list_workspaces", &json!({})).is_none());
+    }
+
+    #[test]
+    fn held_requests_wait_for_the_host_to_decide() {
+        tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .expect("runtime")
+            .block_on(async {
+                let dir = temp_dir();
+                let confirmation = request_confirmation(
+                    &dir,
+                    "terminate_session",
+                    "Terminate session s1".to_string(),
+                    Some("phone".to_string()),
+                    None,
+                    Duration::from_secs(60),
+                )
+                .expect("request");
+                decide_remote_action_confirmation_core(&dir, &confirmation.id, false)
+                    .expect("reject");
+                let err = await_confirmation(&dir, &confirmation)
+                    .await
+                    .expect_err("rejected");
+                assert!(err.contains("rejected"));
+                assert!(
+                    decide_remote_action_confirmation_core(&dir, &confirmation.id, true).is_err()
+                );
+
+                let confirmation = request_confirmation(
+                    &dir,
+                    "run_remote_command",
+                    "Run `ls`".to_string(),
+                    None,
+                    None,
+                    Duration::from_millis(0),
+                )
+                .expect("request");
+                let err = await_confirmation(&dir, &confirmation)
+                    .await
+                    .expect_err("expired");
+                assert!(err.contains("timed out"));
+                let listed = list_remote_action_confirmations_core(&dir).expect("list");
+                assert_eq!(listed.len(), 2);
+                assert!(listed
+                    .iter()
+                    .all(|entry| entry.status != RemoteActionConfirmationStatus::Pending));
+                let _ = std::fs::remove_dir_all(&dir);
+            });
+    }
+}
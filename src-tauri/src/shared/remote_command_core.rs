@@ -0,0 +1,507 @@
+use serde_json::json;
+use std::collections::HashMap;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::Stdio;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+use tokio::io::{AsyncRead, AsyncReadExt};
+use tokio::sync::{Mutex, RwLock};
+
+use crate::backend::events::{EventSink, RemoteCommandOutput};
+use crate::shared::pagination_core::paginate;
+use crate::shared::process_core::{kill_child_process_tree, tokio_command};
+use crate::types::{
+    AppSettings, Page, PageResult, RemoteCommandAuditEntry, RemoteCommandResult, WorkspaceEntry,
+};
+
+const AUDIT_LOG_FILE: &str = "remote-command-audit.jsonl";
+const MAX_CAPTURED_OUTPUT_BYTES: usize = 256 * 1024;
+const MIN_TIMEOUT_SECS: u32 = 1;
+const MAX_TIMEOUT_SECS: u32 = 60 * 60;
+const READ_CHUNK_BYTES: usize = 4096;
+
+pub(crate) fn parse_command_line(command: &str) -> Result<Vec<String>, String> {
+    let argv =
+        shell_words::split(command.trim()).map_err(|err| format!("Invalid command line: {err}"))?;
+    if argv.is_empty() {
+        return Err("Command is required.".to_string());
+    }
+    Ok(argv)
+}
+
+const RULE_WILDCARD: &str = "*";
+
+/// The rule's leading tokens and whether it ended in a `*` token.
+fn rule_tokens(rule: &str) -> Option<(Vec<String>, bool)> {
+    let mut tokens = shell_words::split(rule.trim()).ok()?;
+    let wildcard = tokens.last().is_some_and(|token| token == RULE_WILDCARD);
+    if wildcard {
+        tokens.pop();
+    }
+    (!tokens.is_empty()).then_some((tokens, wildcard))
+}
+
+fn starts_with_tokens(argv: &[String], tokens: &[String]) -> bool {
+    argv.len() >= tokens.len() && argv.iter().zip(tokens).all(|(arg, token)| arg == token)
+}
+
+/// Allowlist rules match the whole command, since extra arguments such as
+/// `--upload-pack=<cmd>` can turn an allowed command into any other. A
+/// trailing `*` lets further arguments follow.
+fn allow_rule_matches(argv: &[String], rule: &str) -> bool {
+    match rule_tokens(rule) {
+        Some((tokens, true)) => starts_with_tokens(argv, &tokens),
+        Some((tokens, false)) => argv == tokens.as_slice(),
+        None => false,
+    }
+}
+
+/// Denylist rules block the command with any further arguments.
+fn deny_rule_matches(argv: &[String], rule: &str) -> bool {
+    rule_tokens(rule).is_some_and(|(tokens, _)| starts_with_tokens(argv, &tokens))
+}
+
+/// Denylist entries win over allowlist entries. An empty allowlist disables
+/// remote commands entirely.
+pub(crate) fn check_command_policy(
+    argv: &[String],
+    allowlist: &[String],
+    denylist: &[String],
+) -> Result<(), String> {
+    if let Some(rule) = denylist.iter().find(|rule| deny_rule_matches(argv, rule)) {
+        return Err(format!(
+            "Command is blocked by the denylist rule `{}`.",
+            rule.trim()
+        ));
+    }
+    if allowlist.iter().all(|rule| rule.trim().is_empty()) {
+        return Err(
+            "Remote commands are disabled. Add allowed commands in settings first.".to_string(),
+        );
+    }
+    if !allowlist.iter().any(|rule| allow_rule_matches(argv, rule)) {
+        return Err("Command is not in the remote command allowlist.".to_string());
+    }
+    Ok(())
+}
+
+pub(crate) fn resolve_command_cwd(
+    workspace_root: &Path,
+    cwd: Option<&str>,
+) -> Result<PathBuf, String> {
+    let root = workspace_root
+        .canonicalize()
+        .map_err(|err| format!("Failed to resolve workspace root: {err}"))?;
+    let Some(relative) = cwd.map(str::trim).filter(|value| !value.is_empty()) else {
+        return Ok(root);
+    };
+    let candidate = Path::new(relative);
+    if candidate.is_absolute() {
+        return Err("Working directory must be relative to the workspace root.".to_string());
+    }
+    let resolved = root
+        .join(candidate)
+        .canonicalize()
+        .map_err(|err| format!("Failed to resolve working directory: {err}"))?;
+    if !resolved.starts_with(&root) {
+        return Err("Working directory must stay inside the workspace.".to_string());
+    }
+    if !resolved.is_dir() {
+        return Err("Working directory is not a directory.".to_string());
+    }
+    Ok(resolved)
+}
+
+fn clamp_timeout_secs(value: u32) -> u32 {
+    value.clamp(MIN_TIMEOUT_SECS, MAX_TIMEOUT_SECS)
+}
+
+/// Decodes the longest valid UTF-8 prefix of `pending`, leaving an incomplete
+/// trailing sequence in place so multi-byte characters split across reads are
+/// not mangled.
+pub(crate) fn drain_utf8(pending: &mut Vec<u8>) -> String {
+    let valid_up_to = match std::str::from_utf8(pending) {
+        Ok(_) => pending.len(),
+        Err(err) if err.error_len().is_none() => err.valid_up_to(),
+        Err(_) => {
+            let text = String::from_utf8_lossy(pending).into_owned();
+            pending.clear();
+            return text;
+        }
+    };
+    let text = String::from_utf8_lossy(&pending[..valid_up_to]).into_owned();
+    pending.drain(..valid_up_to);
+    text
+}
+
+struct CapturedStream {
+    text: String,
+    truncated: bool,
+}
+
+async fn pump_stream<R, E>(
+    mut reader: R,
+    stream: &'static str,
+    workspace_id: String,
+    run_id: String,
+    event_sink: E,
+) -> CapturedStream
+where
+    R: AsyncRead + Unpin,
+    E: EventSink,
+{
+    let mut captured = CapturedStream {
+        text: String::new(),
+        truncated: false,
+    };
+    let mut pending = Vec::new();
+    let mut buffer = [0u8; READ_CHUNK_BYTES];
+    loop {
+        let read = match reader.read(&mut buffer).await {
+            Ok(0) | Err(_) => break,
+            Ok(read) => read,
+        };
+        pending.extend_from_slice(&buffer[..read]);
+        let chunk = drain_utf8(&mut pending);
+        if chunk.is_empty() {
+            continue;
+        }
+        append_capped(&mut captured, &chunk);
+        event_sink.emit_remote_command_output(RemoteCommandOutput {
+            workspace_id: workspace_id.clone(),
+            run_id: run_id.clone(),
+            stream: stream.to_string(),
+            data: chunk,
+        });
+    }
+    if !pending.is_empty() {
+        let chunk = String::from_utf8_lossy(&pending).into_owned();
+        append_capped(&mut captured, &chunk);
+        event_sink.emit_remote_command_output(RemoteCommandOutput {
+            workspace_id,
+            run_id,
+            stream: stream.to_string(),
+            data: chunk,
+        });
+    }
+    captured
+}
+
+fn append_capped(captured: &mut CapturedStream, chunk: &str) {
+    let remaining = MAX_CAPTURED_OUTPUT_BYTES.saturating_sub(captured.text.len());
+    if chunk.len() <= remaining {
+        captured.text.push_str(chunk);
+        return;
+    }
+    let mut end = remaining;
+    while end > 0 && !chunk.is_char_boundary(end) {
+        end -= 1;
+    }
+    captured.text.push_str(&chunk[..end]);
+    captured.truncated = true;
+}
+
+async fn join_captured(task: Option<tokio::task::JoinHandle<CapturedStream>>) -> CapturedStream {
+    let captured = match task {
+        Some(task) => task.await.ok(),
+        None => None,
+    };
+    captured.unwrap_or(CapturedStream {
+        text: String::new(),
+        truncated: false,
+    })
+}
+
+fn now_unix_ms() -> u128 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis()
+}
+
+fn append_audit_entry(data_dir: &Path, entry: serde_json::Value) {
+    let path = data_dir.join(AUDIT_LOG_FILE);
+    let result = std::fs::create_dir_all(data_dir).and_then(|_| {
+        let mut file = std::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&path)?;
+        writeln!(file, "{entry}")
+    });
+    if let Err(err) = result {
+        eprintln!(
+            "remote command: failed to write audit log {}: {err}",
+            path.display()
+        );
+    }
+}
+
+/// Audit log entries, newest first. Lines that do not parse, such as one cut
+/// short by a crash, are skipped.
+pub(crate) fn list_remote_command_audit_core(
+    data_dir: &Path,
+    page: &Page,
+) -> Result<PageResult<RemoteCommandAuditEntry>, String> {
+    let raw = match std::fs::read_to_string(data_dir.join(AUDIT_LOG_FILE)) {
+        Ok(raw) => raw,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => String::new(),
+        Err(err) => return Err(format!("Failed to read remote command audit log: {err}")),
+    };
+    let entries = raw
+        .lines()
+        .filter_map(|line| serde_json::from_str(line).ok())
+        .collect();
+    paginate(entries, page, |entry: &RemoteCommandAuditEntry| {
+        (entry.timestamp_ms, entry.run_id.as_str())
+    })
+}
+
+#[allow(clippy::too_many_arguments)]
+pub(crate) async fn run_remote_command_core<E: EventSink>(
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    app_settings: &RwLock<AppSettings>,
+    data_dir: &Path,
+    event_sink: E,
+    workspace_id: String,
+    command: String,
+    cwd: Option<String>,
+    run_id: Option<String>,
+) -> Result<RemoteCommandResult, String> {
+    let run_id = run_id
+        .map(|value| value.trim().to_string())
+        .filter(|value| !value.is_empty())
+        .unwrap_or_else(|| uuid::Uuid::new_v4().to_string());
+    let audit = |outcome: &str, extra: serde_json::Value| {
+        let mut entry = json!({
+            "timestampMs": now_unix_ms() as u64,
+            "runId": run_id,
+            "workspaceId": workspace_id,
+            "command": command,
+            "cwd": cwd,
+            "outcome": outcome,
+        });
+        if let (Some(entry), Some(extra)) = (entry.as_object_mut(), extra.as_object()) {
+            entry.extend(extra.clone());
+        }
+        append_audit_entry(data_dir, entry);
+    };
+
+    let prepared = async {
+        let argv = parse_command_line(&command)?;
+        let (allowlist, denylist, timeout_secs) = {
+            let settings = app_settings.read().await;
+            (
+                settings.remote_command_allowlist.clone(),
+                settings.remote_command_denylist.clone(),
+                settings.remote_command_timeout_secs,
+            )
+        };
+        check_command_policy(&argv, &allowlist, &denylist)?;
+        let workspace_root = {
+            let workspaces = workspaces.lock().await;
+            workspaces
+                .get(&workspace_id)
+                .map(|entry| PathBuf::from(&entry.path))
+                .ok_or_else(|| "workspace not found".to_string())?
+        };
+        let resolved_cwd = resolve_command_cwd(&workspace_root, cwd.as_deref())?;
+        Ok::<_, String>((argv, resolved_cwd, clamp_timeout_secs(timeout_secs)))
+    }
+    .await;
+    let (argv, resolved_cwd, timeout_secs) = match prepared {
+        Ok(value) => value,
+        Err(err) => {
+            audit("denied", json!({ "error": err }));
+            return Err(err);
+        }
+    };
+
+    let started = Instant::now();
+    let mut child = match tokio_command(&argv[0])
+        .args(&argv[1..])
+        .current_dir(&resolved_cwd)
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .kill_on_drop(true)
+        .spawn()
+    {
+        Ok(child) => child,
+        Err(err) => {
+            let message = format!("Failed to start `{}`: {err}", argv[0]);
+            audit("failed", json!({ "error": message }));
+            return Err(message);
+        }
+    };
+
+    let stdout_task = child.stdout.take().map(|stdout| {
+        tokio::spawn(pump_stream(
+            stdout,
+            "stdout",
+            workspace_id.clone(),
+            run_id.clone(),
+            event_sink.clone(),
+        ))
+    });
+    let stderr_task = child.stderr.take().map(|stderr| {
+        tokio::spawn(pump_stream(
+            stderr,
+            "stderr",
+            workspace_id.clone(),
+            run_id.clone(),
+            event_sink.clone(),
+        ))
+    });
+
+    let (exit_code, timed_out) = match tokio::time::timeout(
+        Duration::from_secs(u64::from(timeout_secs)),
+        child.wait(),
+    )
+    .await
+    {
+        Ok(Ok(status)) => (status.code(), false),
+        Ok(Err(err)) => {
+            kill_child_process_tree(&mut child).await;
+            let _ = child.wait().await;
+            let message = format!("Failed to wait for command: {err}");
+            audit("failed", json!({ "error": message }));
+            return Err(message);
+        }
+        Err(_) => {
+            kill_child_process_tree(&mut child).await;
+            let _ = child.wait().await;
+            (None, true)
+        }
+    };
+
+    let stdout = join_captured(stdout_task).await;
+    let stderr = join_captured(stderr_task).await;
+    let truncated = stdout.truncated || stderr.truncated;
+    let (stdout, stderr) = (stdout.text, stderr.text);
+
+    let duration_ms = started.elapsed().as_millis() as u64;
+    audit(
+        if timed_out { "timed_out" } else { "completed" },
+        json!({
+            "resolvedCwd": resolved_cwd.to_string_lossy(),
+            "exitCode": exit_code,
+            "durationMs": duration_ms,
+        }),
+    );
+
+    Ok(RemoteCommandResult {
+        run_id,
+        exit_code,
+        timed_out,
+        duration_ms,
+        stdout,
+        stderr,
+        truncated,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn argv(items: &[&str]) -> Vec<String> {
+        items.iter().map(|item| item.to_string()).collect()
+    }
+
+    fn rules(items: &[&str]) -> Vec<String> {
+        items.iter().map(|item| item.to_string()).collect()
+    }
+
+    #[test]
+    fn parse_command_line_splits_quoted_arguments() {
+        assert_eq!(
+            parse_command_line("git commit -m \"fix bug\"").expect("parse"),
+            argv(&["git", "commit", "-m", "fix bug"])
+        );
+        assert!(parse_command_line("   ").is_err());
+        assert!(parse_command_line("echo \"unterminated").is_err());
+    }
+
+    #[test]
+    fn policy_matches_allowlist_exactly_unless_it_ends_in_a_wildcard() {
+        let allow = rules(&["git pull", "npm test *"]);
+        assert!(check_command_policy(&argv(&["git", "pull"]), &allow, &[]).is_ok());
+        assert!(check_command_policy(
+            &argv(&["git", "pull", "--upload-pack=touch /tmp/x"]),
+            &allow,
+            &[]
+        )
+        .is_err());
+        assert!(check_command_policy(&argv(&["npm", "test"]), &allow, &[]).is_ok());
+        assert!(
+            check_command_policy(&argv(&["npm", "test", "--", "--watch"]), &allow, &[]).is_ok()
+        );
+        assert!(check_command_policy(&argv(&["git", "push"]), &allow, &[]).is_err());
+        assert!(check_command_policy(&argv(&["npm"]), &allow, &[]).is_err());
+        assert!(check_command_policy(&argv(&["ls"]), &rules(&["*"]), &[]).is_err());
+    }
+
+    #[test]
+    fn policy_denylist_wins_over_allowlist() {
+        let allow = rules(&["git *"]);
+        let deny = rules(&["git push --force"]);
+        let err = check_command_policy(&argv(&["git", "push", "--force"]), &allow, &deny)
+            .expect_err("denied");
+        assert!(err.contains("git push --force"));
+        assert!(check_command_policy(&argv(&["git", "push"]), &allow, &deny).is_ok());
+    }
+
+    #[test]
+    fn policy_rejects_everything_when_allowlist_is_empty() {
+        let err = check_command_policy(&argv(&["ls"]), &rules(&["  "]), &[]).expect_err("disabled");
+        assert!(err.contains("disabled"));
+    }
+
+    #[test]
+    fn resolve_command_cwd_stays_inside_workspace() {
+        let root = std::env::temp_dir().join(format!(
+            "codex-monitor-remote-cmd-{}-{}",
+            std::process::id(),
+            now_unix_ms()
+        ));
+        std::fs::create_dir_all(root.join("packages/app")).expect("create dirs");
+
+        let canonical_root = root.canonicalize().expect("canonical root");
+        assert_eq!(
+            resolve_command_cwd(&root, None).expect("root cwd"),
+            canonical_root
+        );
+        assert_eq!(
+            resolve_command_cwd(&root, Some("packages/app")).expect("nested cwd"),
+            canonical_root.join("packages/app")
+        );
+        assert!(resolve_command_cwd(&root, Some("..")).is_err());
+        assert!(resolve_command_cwd(&root, Some("/tmp")).is_err());
+        assert!(resolve_command_cwd(&root, Some("missing")).is_err());
+
+        let _ = std::fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn drain_utf8_keeps_incomplete_trailing_sequence() {
+        let mut pending = "hé".as_bytes().to_vec();
+        let last = pending.pop().expect("last byte");
+        assert_eq!(drain_utf8(&mut pending), "h");
+        assert_eq!(pending.len(), 1);
+        pending.push(last);
+        assert_eq!(drain_utf8(&mut pending), "é");
+        assert!(pending.is_empty());
+    }
+
+    #[test]
+    fn append_capped_truncates_on_char_boundary() {
+        let mut captured = CapturedStream {
+            text: "a".repeat(MAX_CAPTURED_OUTPUT_BYTES - 1),
+            truncated: false,
+        };
+        append_capped(&mut captured, "éé");
+        assert!(captured.truncated);
+        assert_eq!(captured.text.len(), MAX_CAPTURED_OUTPUT_BYTES - 1);
+    }
+}
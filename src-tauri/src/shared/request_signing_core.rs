@@ -0,0 +1,182 @@
+//! Optional per-request HMAC signing for daemon connections. A client asks
+//! for it in `auth`; the daemon answers with a session nonce, and both sides
+//! derive a key from the token and that nonce. Signed requests carry a
+//! timestamp, a sequence number and a MAC over both plus the method and
+//! params, so a captured request can be neither altered nor replayed.
+
+use std::collections::HashMap;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use ring::hmac;
+use serde_json::{json, Value};
+
+/// `auth` param a client sets to ask for signing.
+pub(crate) const REQUEST_SIGNING_PARAM: &str = "requestSigning";
+/// Request field that carries the signature.
+pub(crate) const SIGNATURE_FIELD: &str = "sig";
+/// How far a signature's timestamp may be from the daemon's clock.
+pub(crate) const SIGNATURE_WINDOW_MS: i64 = 30_000;
+
+pub(crate) fn now_ms() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_millis() as i64)
+        .unwrap_or(0)
+}
+
+#[allow(dead_code)]
+pub(crate) fn new_session_nonce() -> String {
+    uuid::Uuid::new_v4().simple().to_string()
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{byte:02x}")).collect()
+}
+
+fn from_hex(value: &str) -> Option<Vec<u8>> {
+    if !value.len().is_multiple_of(2) {
+        return None;
+    }
+    (0..value.len())
+        .step_by(2)
+        .map(|index| u8::from_str_radix(value.get(index..index + 2)?, 16).ok())
+        .collect()
+}
+
+/// Params are covered as compact JSON with sorted keys, which is how both
+/// sides serialize a parsed `Value`.
+fn signed_message(ts: i64, seq: u64, method: &str, params: &Value) -> Vec<u8> {
+    format!("{ts}\n{seq}\n{method}\n{params}").into_bytes()
+}
+
+pub(crate) struct RequestSigner {
+    key: hmac::Key,
+}
+
+impl RequestSigner {
+    pub(crate) fn new(token: &str, session_nonce: &str) -> Self {
+        let root = hmac::Key::new(hmac::HMAC_SHA256, token.as_bytes());
+        let derived = hmac::sign(
+            &root,
+            format!("codex-monitor-request-signing\n{session_nonce}").as_bytes(),
+        );
+        Self {
+            key: hmac::Key::new(hmac::HMAC_SHA256, derived.as_ref()),
+        }
+    }
+
+    /// The signature field for request `seq`, stamped with the current time.
+    #[allow(dead_code)]
+    pub(crate) fn signature(&self, seq: u64, method: &str, params: &Value) -> Value {
+        self.signature_at(now_ms(), seq, method, params)
+    }
+
+    fn signature_at(&self, ts: i64, seq: u64, method: &str, params: &Value) -> Value {
+        let tag = hmac::sign(&self.key, &signed_message(ts, seq, method, params));
+        json!({ "ts": ts, "seq": seq, "mac": to_hex(tag.as_ref()) })
+    }
+}
+
+/// The daemon's side of a signing session: the key, and the sequence
+/// numbers seen while their timestamps are inside the window.
+#[allow(dead_code)]
+pub(crate) struct SigningSession {
+    signer: RequestSigner,
+    seen: HashMap<u64, i64>,
+}
+
+#[allow(dead_code)]
+impl SigningSession {
+    pub(crate) fn new(token: &str, session_nonce: &str) -> Self {
+        Self {
+            signer: RequestSigner::new(token, session_nonce),
+            seen: HashMap::new(),
+        }
+    }
+
+    pub(crate) fn verify(
+        &mut self,
+        signature: Option<&Value>,
+        method: &str,
+        params: &Value,
+        now_ms: i64,
+    ) -> Result<(), String> {
+        let signature = signature.ok_or_else(|| "request signature is missing".to_string())?;
+        let (Some(ts), Some(seq), Some(mac)) = (
+            signature.get("ts").and_then(Value::as_i64),
+            signature.get("seq").and_then(Value::as_u64),
+            signature
+                .get("mac")
+                .and_then(Value::as_str)
+                .and_then(from_hex),
+        ) else {
+            return Err("request signature is malformed".to_string());
+        };
+        if (now_ms - ts).abs() > SIGNATURE_WINDOW_MS {
+            return Err("request signature is outside the time window".to_string());
+        }
+        hmac::verify(
+            &self.signer.key,
+            &signed_message(ts, seq, method, params),
+            &mac,
+        )
+        .map_err(|_| "request signature is invalid".to_string())?;
+        // Anything older is rejected by the window check above.
+        self.seen
+            .retain(|_, seen_ts| (now_ms - *seen_ts).abs() <= SIGNATURE_WINDOW_MS);
+        if self.seen.insert(seq, ts).is_some() {
+            return Err("request was already used".to_string());
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn signed_requests_verify_once_inside_the_window() {
+        let signer = RequestSigner::new("token", "nonce");
+        let mut session = SigningSession::new("token", "nonce");
+        let params = json!({ "workspaceId": "ws", "text": "hi" });
+        let now = 1_700_000_000_000;
+        let signature = signer.signature_at(now, 7, "send_user_message", &params);
+
+        assert_eq!(
+            session.verify(Some(&signature), "send_user_message", &params, now + 1_000),
+            Ok(())
+        );
+        assert_eq!(
+            session.verify(Some(&signature), "send_user_message", &params, now + 2_000),
+            Err("request was already used".to_string())
+        );
+
+        let signature = signer.signature_at(now, 8, "send_user_message", &params);
+        let tampered = json!({ "workspaceId": "ws", "text": "rm -rf" });
+        assert!(session
+            .verify(Some(&signature), "send_user_message", &tampered, now)
+            .is_err());
+        assert!(session
+            .verify(Some(&signature), "remove_workspace", &params, now)
+            .is_err());
+        assert_eq!(
+            session.verify(
+                Some(&signature),
+                "send_user_message",
+                &params,
+                now + SIGNATURE_WINDOW_MS + 1
+            ),
+            Err("request signature is outside the time window".to_string())
+        );
+        assert!(session
+            .verify(None, "send_user_message", &params, now)
+            .is_err());
+
+        // Another session's nonce derives another key.
+        let mut other = SigningSession::new("token", "other-nonce");
+        assert!(other
+            .verify(Some(&signature), "send_user_message", &params, now)
+            .is_err());
+    }
+}
@@ -0,0 +1,258 @@
+use std::io::{Error, ErrorKind};
+
+use serde_json::{json, Value};
+use tokio::io::{AsyncBufReadExt, AsyncRead, AsyncReadExt, BufReader};
+
+/// Asks the peer to switch the connection to length-prefixed frames.
+/// Answered before auth; daemons that predate it reply with an error and the
+/// connection stays on newline-delimited JSON.
+pub(crate) const RPC_FRAMING_METHOD: &str = "rpc_framing";
+pub(crate) const LENGTH_PREFIXED_FRAMING: &str = "length-prefixed";
+/// Largest message accepted in either framing unless a smaller limit was
+/// negotiated.
+#[allow(dead_code)]
+pub(crate) const DEFAULT_MAX_FRAME_BYTES: usize = 16 * 1024 * 1024;
+/// Floor for negotiated limits, so ordinary responses and errors always fit.
+pub(crate) const MIN_MAX_FRAME_BYTES: usize = 64 * 1024;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Framing {
+    /// One JSON message per line.
+    Lines,
+    /// A 4-byte big-endian length, then that many bytes of JSON.
+    LengthPrefixed,
+}
+
+/// Reads whole messages in either framing, refusing any message larger than
+/// the limit instead of buffering it. After an error the stream position is
+/// unknown, so callers should drop the connection.
+pub(crate) struct FrameReader<R> {
+    reader: BufReader<R>,
+    framing: Framing,
+    max_frame_bytes: usize,
+}
+
+impl<R: AsyncRead + Unpin> FrameReader<R> {
+    pub(crate) fn new(reader: R, max_frame_bytes: usize) -> Self {
+        Self {
+            reader: BufReader::new(reader),
+            framing: Framing::Lines,
+            max_frame_bytes,
+        }
+    }
+
+    /// Switches to length-prefixed frames; the peer must send nothing else in
+    /// line framing after the message that agreed to it.
+    #[allow(dead_code)]
+    pub(crate) fn use_length_prefixed(&mut self, max_frame_bytes: usize) {
+        self.framing = Framing::LengthPrefixed;
+        self.max_frame_bytes = max_frame_bytes;
+    }
+
+    /// The next message, or `None` once the peer closes the connection.
+    pub(crate) async fn next_message(&mut self) -> std::io::Result<Option<String>> {
+        match self.framing {
+            Framing::Lines => self.next_line().await,
+            Framing::LengthPrefixed => self.next_frame().await,
+        }
+    }
+
+    async fn next_line(&mut self) -> std::io::Result<Option<String>> {
+        let mut line = Vec::new();
+        loop {
+            let available = self.reader.fill_buf().await?;
+            if available.is_empty() {
+                if line.is_empty() {
+                    return Ok(None);
+                }
+                break;
+            }
+            let newline = available.iter().position(|byte| *byte == b'\n');
+            let take = newline.unwrap_or(available.len());
+            if line.len() + take > self.max_frame_bytes {
+                return Err(too_large(self.max_frame_bytes));
+            }
+            line.extend_from_slice(&available[..take]);
+            self.reader.consume(take + usize::from(newline.is_some()));
+            if newline.is_some() {
+                break;
+            }
+        }
+        if line.last() == Some(&b'\r') {
+            line.pop();
+        }
+        utf8(line).map(Some)
+    }
+
+    async fn next_frame(&mut self) -> std::io::Result<Option<String>> {
+        let mut header = [0u8; 4];
+        match self.reader.read_exact(&mut header).await {
+            Ok(_) => {}
+            Err(err) if err.kind() == ErrorKind::UnexpectedEof => return Ok(None),
+            Err(err) => return Err(err),
+        }
+        let len = u32::from_be_bytes(header) as usize;
+        if len > self.max_frame_bytes {
+            return Err(too_large(self.max_frame_bytes));
+        }
+        let mut frame = vec![0u8; len];
+        self.reader.read_exact(&mut frame).await?;
+        utf8(frame).map(Some)
+    }
+}
+
+fn too_large(max_frame_bytes: usize) -> Error {
+    Error::new(
+        ErrorKind::InvalidData,
+        format!("RPC message exceeds {max_frame_bytes} bytes"),
+    )
+}
+
+fn utf8(bytes: Vec<u8>) -> std::io::Result<String> {
+    String::from_utf8(bytes).map_err(|err| Error::new(ErrorKind::InvalidData, err))
+}
+
+/// `message` as it goes on the wire in `framing`.
+#[allow(dead_code)]
+pub(crate) fn encode_message(message: &str, framing: Framing) -> Vec<u8> {
+    match framing {
+        Framing::Lines => {
+            let mut bytes = Vec::with_capacity(message.len() + 1);
+            bytes.extend_from_slice(message.as_bytes());
+            bytes.push(b'\n');
+            bytes
+        }
+        Framing::LengthPrefixed => {
+            let mut bytes = Vec::with_capacity(message.len() + 4);
+            bytes.extend_from_slice(&(message.len() as u32).to_be_bytes());
+            bytes.extend_from_slice(message.as_bytes());
+            bytes
+        }
+    }
+}
+
+/// The request a client sends, in line framing, to propose frames of at most
+/// `max_frame_bytes`.
+#[allow(dead_code)]
+pub(crate) fn framing_request(id: u64, max_frame_bytes: usize) -> String {
+    json!({
+        "id": id,
+        "method": RPC_FRAMING_METHOD,
+        "params": {
+            "framing": LENGTH_PREFIXED_FRAMING,
+            "maxFrameBytes": max_frame_bytes,
+        },
+    })
+    .to_string()
+}
+
+/// Server side: the limit to agree to for a framing request, the smaller of
+/// the client's and `server_max`. `None` when the request is not one this
+/// side understands.
+#[allow(dead_code)]
+pub(crate) fn negotiate_max_frame_bytes(params: &Value, server_max: usize) -> Option<usize> {
+    if params.get("framing").and_then(Value::as_str) != Some(LENGTH_PREFIXED_FRAMING) {
+        return None;
+    }
+    let requested = params
+        .get("maxFrameBytes")
+        .and_then(Value::as_u64)
+        .and_then(|value| usize::try_from(value).ok())?;
+    Some(requested.clamp(MIN_MAX_FRAME_BYTES, server_max.max(MIN_MAX_FRAME_BYTES)))
+}
+
+/// The result a server returns when it agrees to `max_frame_bytes`.
+#[allow(dead_code)]
+pub(crate) fn framing_result(max_frame_bytes: usize) -> Value {
+    json!({
+        "framing": LENGTH_PREFIXED_FRAMING,
+        "maxFrameBytes": max_frame_bytes,
+    })
+}
+
+/// Client side: the limit the server agreed to, if it switched framing.
+#[allow(dead_code)]
+pub(crate) fn accepted_max_frame_bytes(result: &Value) -> Option<usize> {
+    if result.get("framing").and_then(Value::as_str) != Some(LENGTH_PREFIXED_FRAMING) {
+        return None;
+    }
+    result
+        .get("maxFrameBytes")
+        .and_then(Value::as_u64)
+        .and_then(|value| usize::try_from(value).ok())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn read_all(bytes: Vec<u8>, framing: Framing, max: usize) -> Vec<std::io::Result<String>> {
+        tokio::runtime::Builder::new_current_thread()
+            .build()
+            .expect("runtime")
+            .block_on(async move {
+                let mut reader = FrameReader::new(bytes.as_slice(), max);
+                if framing == Framing::LengthPrefixed {
+                    reader.use_length_prefixed(max);
+                }
+                let mut messages = Vec::new();
+                loop {
+                    match reader.next_message().await {
+                        Ok(Some(message)) => messages.push(Ok(message)),
+                        Ok(None) => break,
+                        Err(err) => {
+                            messages.push(Err(err));
+                            break;
+                        }
+                    }
+                }
+                messages
+            })
+    }
+
+    #[test]
+    fn frames_carry_embedded_newlines_and_enforce_the_limit() {
+        let mut bytes = encode_message("{\"a\":\"x\ny\"}", Framing::LengthPrefixed);
+        bytes.extend(encode_message(&"z".repeat(33), Framing::LengthPrefixed));
+        let messages = read_all(bytes, Framing::LengthPrefixed, 32);
+        assert_eq!(messages[0].as_ref().expect("first"), "{\"a\":\"x\ny\"}");
+        let error = messages[1].as_ref().expect_err("oversized");
+        assert_eq!(error.kind(), ErrorKind::InvalidData);
+    }
+
+    #[test]
+    fn lines_are_bounded_without_buffering_the_rest() {
+        let mut bytes = encode_message("{\"id\":1}", Framing::Lines);
+        bytes.extend_from_slice(b"{\"id\":2}\r\n");
+        bytes.extend_from_slice(&[b'x'; 100]);
+        let messages = read_all(bytes, Framing::Lines, 64);
+        assert_eq!(messages[0].as_ref().expect("first"), "{\"id\":1}");
+        assert_eq!(messages[1].as_ref().expect("second"), "{\"id\":2}");
+        assert!(messages[2].is_err());
+    }
+
+    #[test]
+    fn negotiation_takes_the_smaller_limit_above_the_floor() {
+        let params = |max: u64| json!({ "framing": LENGTH_PREFIXED_FRAMING, "maxFrameBytes": max });
+        assert_eq!(
+            negotiate_max_frame_bytes(&params(1 << 20), DEFAULT_MAX_FRAME_BYTES),
+            Some(1 << 20)
+        );
+        assert_eq!(
+            negotiate_max_frame_bytes(&params(1 << 30), DEFAULT_MAX_FRAME_BYTES),
+            Some(DEFAULT_MAX_FRAME_BYTES)
+        );
+        assert_eq!(
+            negotiate_max_frame_bytes(&params(10), DEFAULT_MAX_FRAME_BYTES),
+            Some(MIN_MAX_FRAME_BYTES)
+        );
+        assert_eq!(
+            negotiate_max_frame_bytes(&json!({ "framing": "cbor" }), DEFAULT_MAX_FRAME_BYTES),
+            None
+        );
+        assert_eq!(
+            accepted_max_frame_bytes(&framing_result(MIN_MAX_FRAME_BYTES)),
+            Some(MIN_MAX_FRAME_BYTES)
+        );
+    }
+}
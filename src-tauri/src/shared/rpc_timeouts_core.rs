@@ -0,0 +1,304 @@
+//! Per-method timeouts for daemon RPCs. Methods fall into three classes; the
+//! daemon advertises each class's limit and its non-`normal` methods through
+//! `rpc_timeouts`, and a client merges that with its own table and the
+//! user's overrides, so a newer daemon can classify methods this build does
+//! not know about.
+
+use std::collections::HashSet;
+use std::time::Duration;
+
+use crate::shared::monitor_error::MonitorError;
+use crate::types::{RpcTimeoutClass, RpcTimeoutSettings, RpcTimeouts};
+
+pub(crate) const RPC_TIMEOUTS_METHOD: &str = "rpc_timeouts";
+
+pub(crate) const DEFAULT_CONNECT_TIMEOUT: Duration = Duration::from_secs(10);
+pub(crate) const DEFAULT_FAST_TIMEOUT: Duration = Duration::from_secs(10);
+/// The limit every request had before there were classes.
+pub(crate) const DEFAULT_NORMAL_TIMEOUT: Duration = Duration::from_secs(300);
+pub(crate) const DEFAULT_LONG_TIMEOUT: Duration = Duration::from_secs(30 * 60);
+
+/// Liveness checks and in-memory lookups.
+const FAST_METHODS: &[&str] = &[
+    "auth",
+    "daemon_heartbeat_subscribe",
+    "daemon_identify",
+    "daemon_info",
+    "health",
+    "list_client_approvals",
+    "list_editors",
+    "list_guest_tokens",
+    "list_plugins",
+    "list_remote_action_confirmations",
+    "list_tags",
+    "list_workspaces",
+    "ping",
+    RPC_TIMEOUTS_METHOD,
+    "worktree_setup_status",
+];
+
+/// Diffs, searches, clones, network git operations and model calls, whose
+/// time grows with the repository or the remote.
+const LONG_METHODS: &[&str] = &[
+    "activity_report",
+    "add_clone",
+    "add_workspace_from_git_url",
+    "add_worktree",
+    "archive_sessions",
+    "checkout_github_pull_request",
+    "codex_doctor",
+    "create_github_repo",
+    "create_pr_from_session",
+    "export_session",
+    "fetch_git",
+    "generate_agent_description",
+    "generate_commit_message",
+    "generate_run_metadata",
+    "get_git_commit_diff",
+    "get_git_diffs",
+    "get_github_pull_request_diff",
+    "list_workspace_files",
+    "local_usage_snapshot",
+    "merge_session_worktree",
+    "pull_git",
+    "push_git",
+    "restore_archive",
+    "run_remote_command",
+    "search_sessions",
+    "sync_git",
+];
+
+/// This build's class for `method`, before any negotiation.
+#[allow(dead_code)]
+pub(crate) fn builtin_timeout_class(method: &str) -> RpcTimeoutClass {
+    if FAST_METHODS.contains(&method) {
+        RpcTimeoutClass::Fast
+    } else if LONG_METHODS.contains(&method) {
+        RpcTimeoutClass::Long
+    } else {
+        RpcTimeoutClass::Normal
+    }
+}
+
+/// What a daemon answers to `rpc_timeouts`.
+#[allow(dead_code)]
+pub(crate) fn advertised_rpc_timeouts() -> RpcTimeouts {
+    RpcTimeouts {
+        fast_ms: DEFAULT_FAST_TIMEOUT.as_millis() as u64,
+        normal_ms: DEFAULT_NORMAL_TIMEOUT.as_millis() as u64,
+        long_ms: DEFAULT_LONG_TIMEOUT.as_millis() as u64,
+        fast_methods: FAST_METHODS
+            .iter()
+            .map(|method| method.to_string())
+            .collect(),
+        long_methods: LONG_METHODS
+            .iter()
+            .map(|method| method.to_string())
+            .collect(),
+    }
+}
+
+/// A client's resolved timeouts.
+#[allow(dead_code)]
+#[derive(Debug, Clone)]
+pub(crate) struct RpcTimeoutTable {
+    connect: Duration,
+    fast: Duration,
+    normal: Duration,
+    long: Duration,
+    fast_methods: HashSet<String>,
+    long_methods: HashSet<String>,
+}
+
+impl Default for RpcTimeoutTable {
+    fn default() -> Self {
+        Self::negotiate(None, &RpcTimeoutSettings::default())
+    }
+}
+
+fn resolve(overridden: Option<u64>, advertised: Option<u64>, default: Duration) -> Duration {
+    overridden
+        .filter(|ms| *ms > 0)
+        .or(advertised.filter(|ms| *ms > 0))
+        .map(Duration::from_millis)
+        .unwrap_or(default)
+}
+
+#[allow(dead_code)]
+impl RpcTimeoutTable {
+    /// Merges what the daemon advertised, when it answered `rpc_timeouts`,
+    /// with this build's table. Overrides win over both; zero means unset.
+    pub(crate) fn negotiate(
+        advertised: Option<&RpcTimeouts>,
+        overrides: &RpcTimeoutSettings,
+    ) -> Self {
+        let mut fast_methods: HashSet<String> = FAST_METHODS
+            .iter()
+            .map(|method| method.to_string())
+            .collect();
+        let mut long_methods: HashSet<String> = LONG_METHODS
+            .iter()
+            .map(|method| method.to_string())
+            .collect();
+        if let Some(advertised) = advertised {
+            for method in &advertised.fast_methods {
+                long_methods.remove(method);
+                fast_methods.insert(method.clone());
+            }
+            for method in &advertised.long_methods {
+                fast_methods.remove(method);
+                long_methods.insert(method.clone());
+            }
+        }
+        Self {
+            connect: resolve(overrides.connect_ms, None, DEFAULT_CONNECT_TIMEOUT),
+            fast: resolve(
+                overrides.fast_ms,
+                advertised.map(|value| value.fast_ms),
+                DEFAULT_FAST_TIMEOUT,
+            ),
+            normal: resolve(
+                overrides.normal_ms,
+                advertised.map(|value| value.normal_ms),
+                DEFAULT_NORMAL_TIMEOUT,
+            ),
+            long: resolve(
+                overrides.long_ms,
+                advertised.map(|value| value.long_ms),
+                DEFAULT_LONG_TIMEOUT,
+            ),
+            fast_methods,
+            long_methods,
+        }
+    }
+
+    pub(crate) fn connect(&self) -> Duration {
+        self.connect
+    }
+
+    pub(crate) fn class_of(&self, method: &str) -> RpcTimeoutClass {
+        if self.fast_methods.contains(method) {
+            RpcTimeoutClass::Fast
+        } else if self.long_methods.contains(method) {
+            RpcTimeoutClass::Long
+        } else {
+            RpcTimeoutClass::Normal
+        }
+    }
+
+    pub(crate) fn for_class(&self, class: RpcTimeoutClass) -> Duration {
+        match class {
+            RpcTimeoutClass::Fast => self.fast,
+            RpcTimeoutClass::Normal => self.normal,
+            RpcTimeoutClass::Long => self.long,
+        }
+    }
+
+    pub(crate) fn for_method(&self, method: &str) -> Duration {
+        self.for_class(self.class_of(method))
+    }
+}
+
+fn format_duration(duration: Duration) -> String {
+    if duration.subsec_millis() == 0 {
+        format!("{}s", duration.as_secs())
+    } else {
+        format!("{}ms", duration.as_millis())
+    }
+}
+
+/// Opening the connection to `target` took longer than `after`.
+#[allow(dead_code)]
+pub(crate) fn connect_timeout_error(target: &str, after: Duration) -> MonitorError {
+    MonitorError::ConnectTimeout(format!(
+        "connect timed out after {} reaching {target}",
+        format_duration(after)
+    ))
+}
+
+/// `target` took longer than `after` to answer `method`; the connection
+/// itself was up.
+#[allow(dead_code)]
+pub(crate) fn read_timeout_error(target: &str, method: &str, after: Duration) -> MonitorError {
+    MonitorError::Timeout(format!(
+        "{target} request `{method}` timed out after {} waiting for a response",
+        format_duration(after)
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn builtin_classes_cover_liveness_and_heavy_methods() {
+        assert_eq!(builtin_timeout_class("ping"), RpcTimeoutClass::Fast);
+        assert_eq!(
+            builtin_timeout_class("get_git_diffs"),
+            RpcTimeoutClass::Long
+        );
+        assert_eq!(
+            builtin_timeout_class("search_sessions"),
+            RpcTimeoutClass::Long
+        );
+        assert_eq!(
+            builtin_timeout_class("start_thread"),
+            RpcTimeoutClass::Normal
+        );
+        for method in FAST_METHODS {
+            assert!(
+                !LONG_METHODS.contains(method),
+                "{method} is in both classes"
+            );
+        }
+    }
+
+    #[test]
+    fn negotiation_takes_the_daemon_classes_and_lets_overrides_win() {
+        let advertised = RpcTimeouts {
+            fast_ms: 2_000,
+            normal_ms: 60_000,
+            long_ms: 600_000,
+            fast_methods: vec!["get_git_diffs".to_string()],
+            long_methods: vec!["ext.deploy.run".to_string()],
+        };
+        let overrides = RpcTimeoutSettings {
+            long_ms: Some(900_000),
+            normal_ms: Some(0),
+            ..RpcTimeoutSettings::default()
+        };
+        let table = RpcTimeoutTable::negotiate(Some(&advertised), &overrides);
+
+        assert_eq!(table.class_of("get_git_diffs"), RpcTimeoutClass::Fast);
+        assert_eq!(table.class_of("ext.deploy.run"), RpcTimeoutClass::Long);
+        assert_eq!(table.class_of("search_sessions"), RpcTimeoutClass::Long);
+        assert_eq!(table.for_method("ping"), Duration::from_secs(2));
+        assert_eq!(table.for_method("start_thread"), Duration::from_secs(60));
+        assert_eq!(table.for_method("ext.deploy.run"), Duration::from_secs(900));
+        assert_eq!(table.connect(), DEFAULT_CONNECT_TIMEOUT);
+
+        let fallback = RpcTimeoutTable::default();
+        assert_eq!(fallback.for_method("start_thread"), DEFAULT_NORMAL_TIMEOUT);
+        assert_eq!(fallback.for_method("get_git_diffs"), DEFAULT_LONG_TIMEOUT);
+    }
+
+    #[test]
+    fn connect_and_read_timeouts_read_differently() {
+        let connect = connect_timeout_error("remote backend at host:4732", Duration::from_secs(10));
+        assert_eq!(
+            connect.to_string(),
+            "connect timed out after 10s reaching remote backend at host:4732"
+        );
+        assert!(matches!(connect, MonitorError::ConnectTimeout(_)));
+        let read = read_timeout_error(
+            "remote backend",
+            "get_git_diffs",
+            Duration::from_millis(700),
+        );
+        assert_eq!(
+            read.to_string(),
+            "remote backend request `get_git_diffs` timed out after 700ms waiting for a response"
+        );
+        assert!(matches!(read, MonitorError::Timeout(_)));
+    }
+}
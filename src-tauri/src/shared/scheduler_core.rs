@@ -0,0 +1,463 @@
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex as StdMutex};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use chrono::{DateTime, Datelike, Local, NaiveDate, NaiveDateTime, TimeZone, Timelike};
+use tokio::sync::RwLock;
+
+use crate::backend::events::EventSink;
+use crate::shared::codex_session_core::{
+    start_codex_session_core, SessionRegistry, StartSessionOptions,
+};
+use crate::shared::git_core::run_git_command;
+use crate::shared::session_templates_core::get_session_template;
+use crate::storage::{read_schedules, write_schedules};
+use crate::types::{AppSettings, Schedule, ScheduleInput, ScheduleRun};
+
+const SCHEDULES_FILENAME: &str = "schedules.json";
+const MAX_RECORDED_RUNS: usize = 20;
+/// A run that is overdue by more than this was missed (app closed or machine
+/// asleep) and is skipped instead of fired late.
+const MISSED_RUN_GRACE_MS: i64 = 5 * 60 * 1000;
+pub(crate) const SCHEDULER_TICK: Duration = Duration::from_secs(30);
+
+// Guards read-modify-write cycles of the schedules file between the ticker
+// and command handlers.
+static SCHEDULES_LOCK: StdMutex<()> = StdMutex::new(());
+
+fn schedules_path(data_dir: &Path) -> PathBuf {
+    data_dir.join(SCHEDULES_FILENAME)
+}
+
+fn now_ms() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_millis() as i64)
+        .unwrap_or(0)
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct CronExpr {
+    minutes: u64,
+    hours: u64,
+    days: u64,
+    months: u64,
+    weekdays: u64,
+    any_day: bool,
+    any_weekday: bool,
+}
+
+fn parse_cron_value(value: &str, min: u32, max: u32) -> Result<u32, String> {
+    let parsed = value
+        .parse::<u32>()
+        .map_err(|_| format!("Invalid cron value: {value}"))?;
+    if parsed < min || parsed > max {
+        return Err(format!("Cron value {parsed} is outside {min}-{max}"));
+    }
+    Ok(parsed)
+}
+
+fn parse_cron_field(field: &str, min: u32, max: u32) -> Result<u64, String> {
+    let mut mask = 0u64;
+    for part in field.split(',') {
+        let (range, step) = match part.split_once('/') {
+            Some((range, step)) => {
+                let step = step
+                    .parse::<u32>()
+                    .ok()
+                    .filter(|step| *step > 0)
+                    .ok_or_else(|| format!("Invalid cron step: {part}"))?;
+                (range, step)
+            }
+            None => (part, 1),
+        };
+        let (start, end) = if range == "*" {
+            (min, max)
+        } else if let Some((start, end)) = range.split_once('-') {
+            (
+                parse_cron_value(start, min, max)?,
+                parse_cron_value(end, min, max)?,
+            )
+        } else {
+            let start = parse_cron_value(range, min, max)?;
+            (start, if part.contains('/') { max } else { start })
+        };
+        if start > end {
+            return Err(format!("Invalid cron range: {part}"));
+        }
+        for value in (start..=end).step_by(step as usize) {
+            mask |= 1 << value;
+        }
+    }
+    Ok(mask)
+}
+
+/// Parses a five-field cron expression: minute, hour, day of month, month,
+/// day of week (0-7, both 0 and 7 meaning Sunday).
+pub(crate) fn parse_cron(expr: &str) -> Result<CronExpr, String> {
+    let fields: Vec<&str> = expr.split_whitespace().collect();
+    if fields.len() != 5 {
+        return Err("Cron expression must have five fields".to_string());
+    }
+    let mut weekdays = parse_cron_field(fields[4], 0, 7)?;
+    if weekdays & (1 << 7) != 0 {
+        weekdays = (weekdays | 1) & !(1 << 7);
+    }
+    Ok(CronExpr {
+        minutes: parse_cron_field(fields[0], 0, 59)?,
+        hours: parse_cron_field(fields[1], 0, 23)?,
+        days: parse_cron_field(fields[2], 1, 31)?,
+        months: parse_cron_field(fields[3], 1, 12)?,
+        weekdays,
+        any_day: fields[2] == "*",
+        any_weekday: fields[4] == "*",
+    })
+}
+
+impl CronExpr {
+    fn matches_day(&self, date: NaiveDate) -> bool {
+        let day = self.days & (1 << date.day()) != 0;
+        let weekday = self.weekdays & (1 << date.weekday().num_days_from_sunday()) != 0;
+        // Standard cron: when both fields are restricted either may match.
+        match (self.any_day, self.any_weekday) {
+            (false, false) => day || weekday,
+            _ => day && weekday,
+        }
+    }
+
+    /// Returns the first matching minute strictly after `after`.
+    pub(crate) fn next_after(&self, after: DateTime<Local>) -> Option<DateTime<Local>> {
+        let start = after.naive_local().with_second(0)?.with_nanosecond(0)?;
+        let mut candidate = start + chrono::Duration::minutes(1);
+        // Roughly five years of day/hour/minute skips is plenty for any
+        // satisfiable expression (e.g. Feb 29).
+        for _ in 0..200_000 {
+            if self.months & (1 << candidate.month()) == 0 {
+                let (year, month) = if candidate.month() == 12 {
+                    (candidate.year() + 1, 1)
+                } else {
+                    (candidate.year(), candidate.month() + 1)
+                };
+                candidate = NaiveDate::from_ymd_opt(year, month, 1)?.and_hms_opt(0, 0, 0)?;
+                continue;
+            }
+            if !self.matches_day(candidate.date()) {
+                candidate = candidate.date().succ_opt()?.and_hms_opt(0, 0, 0)?;
+                continue;
+            }
+            if self.hours & (1 << candidate.hour()) == 0 {
+                candidate = candidate.date().and_hms_opt(candidate.hour(), 0, 0)?
+                    + chrono::Duration::hours(1);
+                continue;
+            }
+            if self.minutes & (1 << candidate.minute()) == 0 {
+                candidate += chrono::Duration::minutes(1);
+                continue;
+            }
+            // Local times inside a DST gap do not exist; move past them.
+            if let Some(resolved) = resolve_local(candidate) {
+                return Some(resolved);
+            }
+            candidate += chrono::Duration::minutes(1);
+        }
+        None
+    }
+}
+
+fn resolve_local(naive: NaiveDateTime) -> Option<DateTime<Local>> {
+    Local.from_local_datetime(&naive).earliest()
+}
+
+fn next_run_ms(cron: &str, after_ms: i64) -> Result<Option<i64>, String> {
+    let expr = parse_cron(cron)?;
+    let after = Local
+        .timestamp_millis_opt(after_ms)
+        .single()
+        .ok_or_else(|| "Invalid schedule timestamp".to_string())?;
+    Ok(expr.next_after(after).map(|next| next.timestamp_millis()))
+}
+
+pub(crate) fn list_schedules_core(data_dir: &Path) -> Result<Vec<Schedule>, String> {
+    let mut schedules = read_schedules(&schedules_path(data_dir))?;
+    schedules.sort_by_key(|schedule| schedule.name.to_lowercase());
+    Ok(schedules)
+}
+
+pub(crate) fn upsert_schedule_core(
+    data_dir: &Path,
+    input: ScheduleInput,
+) -> Result<Schedule, String> {
+    let name = input.name.trim().to_string();
+    if name.is_empty() {
+        return Err("Schedule name is required".to_string());
+    }
+    let cron = input.cron.split_whitespace().collect::<Vec<_>>().join(" ");
+    parse_cron(&cron)?;
+    get_session_template(data_dir, input.template_id.trim())?;
+
+    let _guard = SCHEDULES_LOCK.lock().map_err(|err| err.to_string())?;
+    let path = schedules_path(data_dir);
+    let mut schedules = read_schedules(&path)?;
+    let now = now_ms();
+    let existing_index = match input
+        .id
+        .map(|id| id.trim().to_string())
+        .filter(|id| !id.is_empty())
+    {
+        Some(id) => Some(
+            schedules
+                .iter()
+                .position(|schedule| schedule.id == id)
+                .ok_or_else(|| "schedule not found".to_string())?,
+        ),
+        None => None,
+    };
+    let existing = existing_index.map(|index| schedules[index].clone());
+    let schedule = Schedule {
+        id: existing
+            .as_ref()
+            .map(|schedule| schedule.id.clone())
+            .unwrap_or_else(|| uuid::Uuid::new_v4().to_string()),
+        name,
+        next_run_at_ms: if input.enabled {
+            next_run_ms(&cron, now)?
+        } else {
+            None
+        },
+        cron,
+        template_id: input.template_id.trim().to_string(),
+        project_path: input
+            .project_path
+            .map(|path| path.trim().to_string())
+            .filter(|path| !path.is_empty()),
+        enabled: input.enabled,
+        runs: existing
+            .as_ref()
+            .map(|schedule| schedule.runs.clone())
+            .unwrap_or_default(),
+        created_at_ms: existing
+            .as_ref()
+            .map(|schedule| schedule.created_at_ms)
+            .unwrap_or(now),
+        updated_at_ms: now,
+    };
+    match existing_index {
+        Some(index) => schedules[index] = schedule.clone(),
+        None => schedules.push(schedule.clone()),
+    }
+    write_schedules(&path, &schedules)?;
+    Ok(schedule)
+}
+
+/// Advances a due schedule past `now` before it runs so a second scheduler
+/// sharing the data dir (app and local daemon) does not fire it again.
+/// Returns false when another ticker already claimed this run.
+fn claim_due_run(
+    data_dir: &Path,
+    schedule_id: &str,
+    due_at: i64,
+    now: i64,
+) -> Result<bool, String> {
+    let _guard = SCHEDULES_LOCK.lock().map_err(|err| err.to_string())?;
+    let path = schedules_path(data_dir);
+    let mut schedules = read_schedules(&path)?;
+    let Some(schedule) = schedules
+        .iter_mut()
+        .find(|schedule| schedule.id == schedule_id)
+    else {
+        return Ok(false);
+    };
+    if !schedule.enabled || schedule.next_run_at_ms != Some(due_at) {
+        return Ok(false);
+    }
+    schedule.next_run_at_ms = next_run_ms(&schedule.cron, now)?;
+    write_schedules(&path, &schedules)?;
+    Ok(true)
+}
+
+fn record_run(data_dir: &Path, schedule_id: &str, run: &ScheduleRun) -> Result<(), String> {
+    let _guard = SCHEDULES_LOCK.lock().map_err(|err| err.to_string())?;
+    let path = schedules_path(data_dir);
+    let mut schedules = read_schedules(&path)?;
+    let Some(schedule) = schedules
+        .iter_mut()
+        .find(|schedule| schedule.id == schedule_id)
+    else {
+        return Ok(());
+    };
+    schedule.runs.push(run.clone());
+    if schedule.runs.len() > MAX_RECORDED_RUNS {
+        let excess = schedule.runs.len() - MAX_RECORDED_RUNS;
+        schedule.runs.drain(..excess);
+    }
+    write_schedules(&path, &schedules)
+}
+
+fn schedule_run(status: &str, message: Option<String>, session_id: Option<String>) -> ScheduleRun {
+    ScheduleRun {
+        at_ms: now_ms(),
+        status: status.to_string(),
+        message,
+        session_id,
+    }
+}
+
+async fn ensure_clean_worktree(project_path: &str) -> Result<(), String> {
+    let status = run_git_command(
+        &PathBuf::from(project_path),
+        &["status", "--porcelain", "--untracked-files=normal"],
+    )
+    .await?;
+    if status.is_empty() {
+        Ok(())
+    } else {
+        Err("Project has uncommitted changes".to_string())
+    }
+}
+
+async fn execute_schedule<E: EventSink>(
+    runtimes: &Arc<SessionRegistry>,
+    app_settings: &RwLock<AppSettings>,
+    data_dir: &Path,
+    event_sink: E,
+    schedule: &Schedule,
+) -> ScheduleRun {
+    let template = match get_session_template(data_dir, &schedule.template_id) {
+        Ok(template) => template,
+        Err(err) => return schedule_run("failed", Some(err), None),
+    };
+    let Some(project_path) = schedule
+        .project_path
+        .clone()
+        .or_else(|| template.working_dir.clone())
+    else {
+        return schedule_run(
+            "failed",
+            Some("Schedule has no project path".to_string()),
+            None,
+        );
+    };
+    if let Err(err) = ensure_clean_worktree(&project_path).await {
+        return schedule_run("skipped", Some(err), None);
+    }
+    let options = StartSessionOptions {
+        project_path: Some(project_path),
+        template_id: Some(template.id),
+        ..Default::default()
+    };
+    match start_codex_session_core(runtimes, app_settings, data_dir, event_sink, options).await {
+        Ok(info) if info.status == "queued" => schedule_run("queued", None, Some(info.session_id)),
+        Ok(info) => schedule_run("started", None, Some(info.session_id)),
+        Err(err) => schedule_run("failed", Some(err), None),
+    }
+}
+
+pub(crate) async fn run_schedule_now_core<E: EventSink>(
+    runtimes: &Arc<SessionRegistry>,
+    app_settings: &RwLock<AppSettings>,
+    data_dir: &Path,
+    event_sink: E,
+    schedule_id: String,
+) -> Result<ScheduleRun, String> {
+    let schedule = read_schedules(&schedules_path(data_dir))?
+        .into_iter()
+        .find(|schedule| schedule.id == schedule_id)
+        .ok_or_else(|| "schedule not found".to_string())?;
+    let run = execute_schedule(runtimes, app_settings, data_dir, event_sink, &schedule).await;
+    record_run(data_dir, &schedule.id, &run)?;
+    Ok(run)
+}
+
+/// Fires every enabled schedule whose next run is due. Called from the
+/// scheduler ticker in the app and daemon.
+pub(crate) async fn run_due_schedules_core<E: EventSink>(
+    runtimes: &Arc<SessionRegistry>,
+    app_settings: &RwLock<AppSettings>,
+    data_dir: &Path,
+    event_sink: E,
+) -> Result<Vec<ScheduleRun>, String> {
+    let now = now_ms();
+    let due: Vec<Schedule> = read_schedules(&schedules_path(data_dir))?
+        .into_iter()
+        .filter(|schedule| {
+            schedule.enabled
+                && schedule
+                    .next_run_at_ms
+                    .is_some_and(|next_run| next_run <= now)
+        })
+        .collect();
+    let mut runs = Vec::with_capacity(due.len());
+    for schedule in due {
+        let due_at = schedule.next_run_at_ms.unwrap_or(now);
+        if !claim_due_run(data_dir, &schedule.id, due_at, now)? {
+            continue;
+        }
+        let overdue_ms = now - due_at;
+        let run = if overdue_ms > MISSED_RUN_GRACE_MS {
+            schedule_run(
+                "skipped",
+                Some("Missed while the machine was asleep or the app was closed".to_string()),
+                None,
+            )
+        } else {
+            execute_schedule(
+                runtimes,
+                app_settings,
+                data_dir,
+                event_sink.clone(),
+                &schedule,
+            )
+            .await
+        };
+        record_run(data_dir, &schedule.id, &run)?;
+        runs.push(run);
+    }
+    Ok(runs)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn local(year: i32, month: u32, day: u32, hour: u32, minute: u32) -> DateTime<Local> {
+        Local
+            .with_ymd_and_hms(year, month, day, hour, minute, 0)
+            .earliest()
+            .expect("valid local time")
+    }
+
+    #[test]
+    fn parse_cron_rejects_malformed_expressions() {
+        assert!(parse_cron("* * * *").is_err());
+        assert!(parse_cron("60 * * * *").is_err());
+        assert!(parse_cron("*/0 * * * *").is_err());
+        assert!(parse_cron("5-1 * * * *").is_err());
+        assert!(parse_cron("0 9 * * 1-5").is_ok());
+    }
+
+    #[test]
+    fn next_after_finds_following_match() {
+        let weekdays = parse_cron("30 9 * * 1-5").expect("parse");
+        // 2026-01-02 is a Friday; the next weekday run is Monday the 5th.
+        let next = weekdays.next_after(local(2026, 1, 2, 10, 0)).expect("next");
+        assert_eq!(next, local(2026, 1, 5, 9, 30));
+
+        let every_fifteen = parse_cron("*/15 * * * *").expect("parse");
+        let next = every_fifteen
+            .next_after(local(2026, 1, 2, 10, 15))
+            .expect("next");
+        assert_eq!(next, local(2026, 1, 2, 10, 30));
+
+        let sunday = parse_cron("0 0 * * 7").expect("parse");
+        let next = sunday.next_after(local(2026, 1, 2, 10, 0)).expect("next");
+        assert_eq!(next, local(2026, 1, 4, 0, 0));
+    }
+
+    #[test]
+    fn next_after_treats_restricted_day_fields_as_either() {
+        // First of the month or any Monday.
+        let expr = parse_cron("0 12 1 * 1").expect("parse");
+        let next = expr.next_after(local(2026, 1, 2, 0, 0)).expect("next");
+        assert_eq!(next, local(2026, 1, 5, 12, 0));
+    }
+}
@@ -0,0 +1,568 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{BufReader, BufWriter, Read, Write};
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use tokio::sync::{Mutex, RwLock};
+use zip::write::SimpleFileOptions;
+use zip::{CompressionMethod, ZipArchive, ZipWriter};
+
+use crate::shared::local_usage_core::resolve_sessions_roots;
+use crate::shared::pagination_core::paginate;
+use crate::shared::session_transcript_core::collect_session_files;
+use crate::types::{
+    AppSettings, Page, PageResult, SessionArchiveInfo, SessionArchiveReport, SessionRestoreReport,
+    WorkspaceEntry,
+};
+
+const ARCHIVES_DIRNAME: &str = "session-archives";
+const MANIFEST_ENTRY: &str = "manifest.json";
+const DAY_MS: i64 = 24 * 60 * 60 * 1000;
+/// Files touched within this window may still belong to a running session and
+/// are never archived, regardless of policy.
+const MIN_ARCHIVE_AGE_MS: i64 = DAY_MS;
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+struct ManifestEntry {
+    entry_name: String,
+    original_path: String,
+    size: u64,
+    modified_ms: i64,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+struct ArchiveManifest {
+    created_at_ms: i64,
+    files: Vec<ManifestEntry>,
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+pub(crate) struct RetentionPolicy {
+    pub(crate) max_age_days: Option<u32>,
+    pub(crate) max_size_bytes: Option<u64>,
+}
+
+impl RetentionPolicy {
+    fn from_settings(settings: &AppSettings) -> Self {
+        Self {
+            max_age_days: settings
+                .session_retention_max_age_days
+                .filter(|days| *days > 0),
+            max_size_bytes: settings
+                .session_retention_max_size_mb
+                .filter(|mb| *mb > 0)
+                .map(|mb| mb * 1024 * 1024),
+        }
+    }
+
+    fn is_empty(&self) -> bool {
+        self.max_age_days.is_none() && self.max_size_bytes.is_none()
+    }
+}
+
+#[derive(Debug, Clone)]
+struct SessionFile {
+    root_index: usize,
+    root: PathBuf,
+    path: PathBuf,
+    size: u64,
+    modified_ms: i64,
+}
+
+fn now_ms() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_millis() as i64)
+        .unwrap_or(0)
+}
+
+fn archives_dir(data_dir: &Path) -> PathBuf {
+    data_dir.join(ARCHIVES_DIRNAME)
+}
+
+fn scan_session_files(roots: &[PathBuf]) -> Vec<SessionFile> {
+    let mut files = Vec::new();
+    for (root_index, root) in roots.iter().enumerate() {
+        for path in collect_session_files(root) {
+            let Ok(metadata) = std::fs::metadata(&path) else {
+                continue;
+            };
+            let modified_ms = metadata
+                .modified()
+                .ok()
+                .and_then(|time| time.duration_since(UNIX_EPOCH).ok())
+                .map(|duration| duration.as_millis() as i64)
+                .unwrap_or(0);
+            files.push(SessionFile {
+                root_index,
+                root: root.clone(),
+                path,
+                size: metadata.len(),
+                modified_ms,
+            });
+        }
+    }
+    files.sort_by_key(|file| file.modified_ms);
+    files
+}
+
+/// Picks the files to archive. An explicit `before_ms` cutoff wins; otherwise
+/// files older than the max age are selected, followed by the oldest remaining
+/// files until the total size fits under the max size.
+fn select_candidates(
+    files: Vec<SessionFile>,
+    before_ms: Option<i64>,
+    policy: RetentionPolicy,
+    now_ms: i64,
+) -> Vec<SessionFile> {
+    let protected_after = now_ms - MIN_ARCHIVE_AGE_MS;
+    if let Some(before_ms) = before_ms {
+        let cutoff = before_ms.min(protected_after);
+        return files
+            .into_iter()
+            .filter(|file| file.modified_ms < cutoff)
+            .collect();
+    }
+
+    let age_cutoff = policy
+        .max_age_days
+        .map(|days| now_ms - i64::from(days) * DAY_MS);
+    let mut remaining: u64 = files.iter().map(|file| file.size).sum();
+    let mut selected = Vec::new();
+    // `files` is sorted oldest first, so size trimming drops the oldest data.
+    for file in files {
+        if file.modified_ms >= protected_after {
+            continue;
+        }
+        let too_old = age_cutoff.is_some_and(|cutoff| file.modified_ms < cutoff);
+        let over_size = policy
+            .max_size_bytes
+            .is_some_and(|max_size| remaining > max_size);
+        if too_old || over_size {
+            remaining = remaining.saturating_sub(file.size);
+            selected.push(file);
+        }
+    }
+    selected
+}
+
+fn entry_name_for(file: &SessionFile) -> String {
+    let relative = file
+        .path
+        .strip_prefix(&file.root)
+        .unwrap_or(&file.path)
+        .to_string_lossy()
+        .replace('\\', "/");
+    format!("{}/{}", file.root_index, relative)
+}
+
+fn read_manifest<R: Read + std::io::Seek>(
+    archive: &mut ZipArchive<R>,
+) -> Result<ArchiveManifest, String> {
+    let mut entry = archive
+        .by_name(MANIFEST_ENTRY)
+        .map_err(|_| "Archive manifest is missing".to_string())?;
+    let mut raw = String::new();
+    entry
+        .read_to_string(&mut raw)
+        .map_err(|err| format!("Failed to read archive manifest: {err}"))?;
+    serde_json::from_str(&raw).map_err(|err| format!("Invalid archive manifest: {err}"))
+}
+
+fn archive_info(path: &Path) -> Result<SessionArchiveInfo, String> {
+    let file = File::open(path).map_err(|err| format!("Failed to open archive: {err}"))?;
+    let size_bytes = file.metadata().map(|metadata| metadata.len()).unwrap_or(0);
+    let mut archive = ZipArchive::new(BufReader::new(file))
+        .map_err(|err| format!("Failed to read archive: {err}"))?;
+    let manifest = read_manifest(&mut archive)?;
+    Ok(SessionArchiveInfo {
+        name: path
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or_default(),
+        path: path.to_string_lossy().to_string(),
+        size_bytes,
+        created_at_ms: Some(manifest.created_at_ms),
+        file_count: manifest.files.len(),
+        original_bytes: manifest.files.iter().map(|entry| entry.size).sum(),
+    })
+}
+
+fn write_archive(
+    data_dir: &Path,
+    candidates: &[SessionFile],
+    created_at_ms: i64,
+) -> Result<PathBuf, String> {
+    let dir = archives_dir(data_dir);
+    std::fs::create_dir_all(&dir)
+        .map_err(|err| format!("Failed to create archive directory: {err}"))?;
+    let stamp = chrono::Local::now().format("%Y%m%d-%H%M%S");
+    let mut archive_path = dir.join(format!("sessions-{stamp}.zip"));
+    let mut suffix = 1;
+    while archive_path.exists() {
+        archive_path = dir.join(format!("sessions-{stamp}-{suffix}.zip"));
+        suffix += 1;
+    }
+    let tmp_path = archive_path.with_extension("zip.tmp");
+
+    let result = (|| -> Result<(), String> {
+        let file =
+            File::create(&tmp_path).map_err(|err| format!("Failed to create archive: {err}"))?;
+        let mut writer = ZipWriter::new(BufWriter::new(file));
+        let options = SimpleFileOptions::default().compression_method(CompressionMethod::Deflated);
+        let mut manifest = ArchiveManifest {
+            created_at_ms,
+            files: Vec::with_capacity(candidates.len()),
+        };
+        for candidate in candidates {
+            let entry_name = entry_name_for(candidate);
+            writer
+                .start_file(entry_name.as_str(), options)
+                .map_err(|err| format!("Failed to write archive: {err}"))?;
+            let mut source = File::open(&candidate.path)
+                .map_err(|err| format!("Failed to open session file: {err}"))?;
+            std::io::copy(&mut source, &mut writer)
+                .map_err(|err| format!("Failed to write archive: {err}"))?;
+            manifest.files.push(ManifestEntry {
+                entry_name,
+                original_path: candidate.path.to_string_lossy().to_string(),
+                size: candidate.size,
+                modified_ms: candidate.modified_ms,
+            });
+        }
+        let manifest_json = serde_json::to_vec_pretty(&manifest).map_err(|err| err.to_string())?;
+        writer
+            .start_file(MANIFEST_ENTRY, options)
+            .map_err(|err| format!("Failed to write archive: {err}"))?;
+        writer
+            .write_all(&manifest_json)
+            .map_err(|err| format!("Failed to write archive: {err}"))?;
+        let mut inner = writer
+            .finish()
+            .map_err(|err| format!("Failed to finalize archive: {err}"))?;
+        inner
+            .flush()
+            .map_err(|err| format!("Failed to finalize archive: {err}"))
+    })();
+
+    if let Err(err) = result {
+        let _ = std::fs::remove_file(&tmp_path);
+        return Err(err);
+    }
+    std::fs::rename(&tmp_path, &archive_path)
+        .map_err(|err| format!("Failed to finalize archive: {err}"))?;
+    Ok(archive_path)
+}
+
+fn remove_empty_parents(path: &Path, root: &Path) {
+    let mut current = path.parent();
+    while let Some(dir) = current {
+        if dir == root || !dir.starts_with(root) {
+            break;
+        }
+        if std::fs::remove_dir(dir).is_err() {
+            break;
+        }
+        current = dir.parent();
+    }
+}
+
+pub(crate) fn archive_sessions_inner(
+    data_dir: &Path,
+    roots: &[PathBuf],
+    before_ms: Option<i64>,
+    policy: RetentionPolicy,
+    dry_run: bool,
+    now_ms: i64,
+) -> Result<SessionArchiveReport, String> {
+    if before_ms.is_none() && policy.is_empty() {
+        return Err("No cutoff given and no session retention policy is configured".to_string());
+    }
+    let candidates = select_candidates(scan_session_files(roots), before_ms, policy, now_ms);
+    let reclaimed_bytes = candidates.iter().map(|file| file.size).sum();
+    let mut report = SessionArchiveReport {
+        dry_run,
+        file_count: candidates.len(),
+        reclaimed_bytes,
+        archive: None,
+    };
+    if dry_run || candidates.is_empty() {
+        return Ok(report);
+    }
+
+    let archive_path = write_archive(data_dir, &candidates, now_ms)?;
+    // Originals are only removed once the archive is fully written.
+    for candidate in &candidates {
+        if std::fs::remove_file(&candidate.path).is_ok() {
+            remove_empty_parents(&candidate.path, &candidate.root);
+        }
+    }
+    report.archive = Some(archive_info(&archive_path)?);
+    Ok(report)
+}
+
+pub(crate) fn list_archives_inner(data_dir: &Path) -> Result<Vec<SessionArchiveInfo>, String> {
+    let dir = archives_dir(data_dir);
+    let Ok(entries) = std::fs::read_dir(&dir) else {
+        return Ok(Vec::new());
+    };
+    let mut archives: Vec<SessionArchiveInfo> = entries
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| path.extension().and_then(|ext| ext.to_str()) == Some("zip"))
+        .filter_map(|path| archive_info(&path).ok())
+        .collect();
+    archives.sort_by_key(|archive| std::cmp::Reverse(archive.created_at_ms));
+    Ok(archives)
+}
+
+fn resolve_archive_path(data_dir: &Path, name: &str) -> Result<PathBuf, String> {
+    let name = name.trim();
+    if name.is_empty()
+        || name.contains('/')
+        || name.contains('\\')
+        || name.contains("..")
+        || !name.ends_with(".zip")
+    {
+        return Err("Invalid archive name".to_string());
+    }
+    let path = archives_dir(data_dir).join(name);
+    if !path.is_file() {
+        return Err("Archive not found".to_string());
+    }
+    Ok(path)
+}
+
+/// Restores archived sessions to their original locations. Files that already
+/// exist are left untouched and counted as skipped.
+pub(crate) fn restore_archive_inner(
+    data_dir: &Path,
+    roots: &[PathBuf],
+    name: &str,
+) -> Result<SessionRestoreReport, String> {
+    let path = resolve_archive_path(data_dir, name)?;
+    let file = File::open(&path).map_err(|err| format!("Failed to open archive: {err}"))?;
+    let mut archive = ZipArchive::new(BufReader::new(file))
+        .map_err(|err| format!("Failed to read archive: {err}"))?;
+    let manifest = read_manifest(&mut archive)?;
+
+    let mut report = SessionRestoreReport {
+        restored: 0,
+        skipped: 0,
+    };
+    for entry in manifest.files {
+        let target = PathBuf::from(&entry.original_path);
+        // Only write back into a known sessions root so a tampered manifest
+        // cannot place files elsewhere.
+        let inside_root = roots.iter().any(|root| target.starts_with(root))
+            && !target
+                .components()
+                .any(|component| matches!(component, std::path::Component::ParentDir));
+        if !inside_root || target.exists() {
+            report.skipped += 1;
+            continue;
+        }
+        let mut source = archive
+            .by_name(&entry.entry_name)
+            .map_err(|err| format!("Failed to read archive entry: {err}"))?;
+        if let Some(parent) = target.parent() {
+            std::fs::create_dir_all(parent)
+                .map_err(|err| format!("Failed to restore session: {err}"))?;
+        }
+        let mut output =
+            File::create(&target).map_err(|err| format!("Failed to restore session: {err}"))?;
+        std::io::copy(&mut source, &mut output)
+            .map_err(|err| format!("Failed to restore session: {err}"))?;
+        report.restored += 1;
+    }
+    Ok(report)
+}
+
+async fn all_sessions_roots(workspaces: &Mutex<HashMap<String, WorkspaceEntry>>) -> Vec<PathBuf> {
+    let workspaces = workspaces.lock().await;
+    resolve_sessions_roots(&workspaces, None)
+}
+
+pub(crate) async fn archive_sessions_core(
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    app_settings: &RwLock<AppSettings>,
+    data_dir: PathBuf,
+    before_ms: Option<i64>,
+    dry_run: bool,
+) -> Result<SessionArchiveReport, String> {
+    let policy = RetentionPolicy::from_settings(&*app_settings.read().await);
+    let roots = all_sessions_roots(workspaces).await;
+    tokio::task::spawn_blocking(move || {
+        archive_sessions_inner(&data_dir, &roots, before_ms, policy, dry_run, now_ms())
+    })
+    .await
+    .map_err(|err| err.to_string())?
+}
+
+pub(crate) async fn list_archives_core(
+    data_dir: PathBuf,
+    page: Page,
+) -> Result<PageResult<SessionArchiveInfo>, String> {
+    let archives = tokio::task::spawn_blocking(move || list_archives_inner(&data_dir))
+        .await
+        .map_err(|err| err.to_string())??;
+    paginate(archives, &page, |archive| {
+        (archive.created_at_ms.unwrap_or(0), archive.name.as_str())
+    })
+}
+
+pub(crate) async fn restore_archive_core(
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    data_dir: PathBuf,
+    name: String,
+) -> Result<SessionRestoreReport, String> {
+    let roots = all_sessions_roots(workspaces).await;
+    tokio::task::spawn_blocking(move || restore_archive_inner(&data_dir, &roots, &name))
+        .await
+        .map_err(|err| err.to_string())?
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::shared::session_transcript_core::test_support::write_rollout;
+
+    fn temp_dir(prefix: &str) -> PathBuf {
+        let unique = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("time")
+            .as_nanos();
+        std::env::temp_dir().join(format!(
+            "codex-monitor-{prefix}-{}-{unique}",
+            std::process::id()
+        ))
+    }
+
+    fn session_file(modified_ms: i64, size: u64) -> SessionFile {
+        SessionFile {
+            root_index: 0,
+            root: PathBuf::from("/sessions"),
+            path: PathBuf::from(format!("/sessions/{modified_ms}.jsonl")),
+            size,
+            modified_ms,
+        }
+    }
+
+    #[test]
+    fn select_candidates_applies_age_then_size_and_protects_recent_files() {
+        let now = 100 * DAY_MS;
+        let files = vec![
+            session_file(now - 40 * DAY_MS, 10),
+            session_file(now - 20 * DAY_MS, 10),
+            session_file(now - 10 * DAY_MS, 10),
+            session_file(now - 1_000, 100),
+        ];
+
+        let by_age = select_candidates(
+            files.clone(),
+            None,
+            RetentionPolicy {
+                max_age_days: Some(30),
+                max_size_bytes: None,
+            },
+            now,
+        );
+        assert_eq!(by_age.len(), 1);
+
+        let by_size = select_candidates(
+            files.clone(),
+            None,
+            RetentionPolicy {
+                max_age_days: None,
+                max_size_bytes: Some(100),
+            },
+            now,
+        );
+        // The recent 100-byte file is protected, so all older files go.
+        assert_eq!(by_size.len(), 3);
+
+        let by_cutoff = select_candidates(files, Some(now), RetentionPolicy::default(), now);
+        assert_eq!(by_cutoff.len(), 3);
+    }
+
+    #[test]
+    fn archive_dry_run_reports_without_touching_files() {
+        let root = temp_dir("archive-dry-run");
+        let sessions = root.join("sessions");
+        let file = sessions.join("2026/01/05/a.jsonl");
+        write_rollout(&file, "a", "/work", "hello", "hi");
+
+        let report = archive_sessions_inner(
+            &root.join("data"),
+            std::slice::from_ref(&sessions),
+            Some(now_ms() + DAY_MS * 10),
+            RetentionPolicy::default(),
+            true,
+            now_ms() + DAY_MS * 10,
+        )
+        .expect("dry run");
+        assert!(report.dry_run);
+        assert_eq!(report.file_count, 1);
+        assert!(report.reclaimed_bytes > 0);
+        assert!(report.archive.is_none());
+        assert!(file.exists());
+
+        let _ = std::fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn archive_then_restore_round_trips_sessions() {
+        let root = temp_dir("archive-round-trip");
+        let sessions = root.join("sessions");
+        let data_dir = root.join("data");
+        let file = sessions.join("2026/01/05/a.jsonl");
+        write_rollout(&file, "a", "/work", "hello", "hi");
+        let original = std::fs::read(&file).expect("read original");
+        let later = now_ms() + DAY_MS * 10;
+
+        let report = archive_sessions_inner(
+            &data_dir,
+            std::slice::from_ref(&sessions),
+            Some(later),
+            RetentionPolicy::default(),
+            false,
+            later,
+        )
+        .expect("archive");
+        let archive = report.archive.expect("archive info");
+        assert_eq!(archive.file_count, 1);
+        assert!(!file.exists());
+        assert!(!sessions.join("2026").exists());
+
+        let listed = list_archives_inner(&data_dir).expect("list");
+        assert_eq!(listed.len(), 1);
+        assert_eq!(listed[0].name, archive.name);
+
+        let restored =
+            restore_archive_inner(&data_dir, std::slice::from_ref(&sessions), &archive.name)
+                .expect("restore");
+        assert_eq!(restored.restored, 1);
+        assert_eq!(std::fs::read(&file).expect("read restored"), original);
+
+        let again =
+            restore_archive_inner(&data_dir, std::slice::from_ref(&sessions), &archive.name)
+                .expect("restore");
+        assert_eq!(again.restored, 0);
+        assert_eq!(again.skipped, 1);
+
+        assert!(restore_archive_inner(&data_dir, &[sessions], "../x.zip").is_err());
+        let _ = std::fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn archive_requires_cutoff_or_policy() {
+        let root = temp_dir("archive-no-policy");
+        let result =
+            archive_sessions_inner(&root, &[], None, RetentionPolicy::default(), true, now_ms());
+        assert!(result.is_err());
+    }
+}
@@ -0,0 +1,650 @@
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{BufRead, BufReader};
+use std::path::{Path, PathBuf};
+
+use chrono::DateTime;
+use serde_json::Value;
+use tokio::sync::Mutex;
+
+use crate::shared::local_usage_core::{read_timestamp_ms, resolve_sessions_roots};
+use crate::shared::redaction_core::{redactor, Redactor};
+use crate::shared::session_transcript_core::{
+    collect_session_files, extract_message_text, is_injected_context,
+};
+use crate::types::{RemoteFileChunk, SessionExport, SessionExportFormat, WorkspaceEntry};
+
+const EXPORTS_DIR: &str = "exports";
+const MAX_LINE_BYTES: usize = 512_000;
+/// Tool output past this is cut; an export is for reading, not replaying.
+const MAX_TOOL_OUTPUT_CHARS: usize = 20_000;
+/// How much of an export a remote client pulls per request.
+pub(crate) const EXPORT_CHUNK_BYTES: u64 = 256 * 1024;
+
+const HTML_STYLE: &str = "\
+body{font:15px/1.55 -apple-system,BlinkMacSystemFont,\"Segoe UI\",sans-serif;\
+max-width:860px;margin:2rem auto;padding:0 1rem;color:#1f2328;background:#fff}\
+h1{font-size:1.4rem;margin-bottom:.25rem}\
+.meta{color:#656d76;margin-top:0}\
+.message{border-left:3px solid #d0d7de;padding:.25rem 1rem;margin:1.25rem 0}\
+.message.user{border-color:#0969da}\
+.message h2{font-size:.8rem;text-transform:uppercase;letter-spacing:.05em;color:#656d76;margin:.5rem 0}\
+.text{white-space:pre-wrap;overflow-wrap:anywhere}\
+details.tool{margin:.75rem 0 .75rem 1rem;border:1px solid #d0d7de;border-radius:6px;padding:.25rem .75rem}\
+details.tool summary{cursor:pointer;color:#656d76}\
+pre{background:#f6f8fa;padding:.75rem;border-radius:6px;overflow-x:auto;font-size:13px}\
+@media (prefers-color-scheme:dark){body{color:#e6edf3;background:#0d1117}\
+pre{background:#161b22}.message,details.tool{border-color:#30363d}}";
+
+#[derive(Debug, Clone, PartialEq)]
+enum ExportEntry {
+    Message {
+        role: String,
+        text: String,
+    },
+    ToolCall {
+        name: String,
+        input: String,
+        output: Option<String>,
+    },
+}
+
+#[derive(Debug, Default)]
+struct Conversation {
+    session_id: String,
+    cwd: Option<String>,
+    started_at_ms: Option<i64>,
+    entries: Vec<ExportEntry>,
+}
+
+impl Conversation {
+    fn redact(&mut self, redactor: &Redactor) {
+        for entry in &mut self.entries {
+            match entry {
+                ExportEntry::Message { text, .. } => *text = redactor.redact(text),
+                ExportEntry::ToolCall { input, output, .. } => {
+                    *input = redactor.redact(input);
+                    if let Some(output) = output {
+                        *output = redactor.redact(output);
+                    }
+                }
+            }
+        }
+    }
+}
+
+fn tool_input(payload: &Value) -> String {
+    if let Some(arguments) = payload.get("arguments").and_then(Value::as_str) {
+        return serde_json::from_str::<Value>(arguments)
+            .ok()
+            .and_then(|value| serde_json::to_string_pretty(&value).ok())
+            .unwrap_or_else(|| arguments.to_string());
+    }
+    if let Some(input) = payload.get("input").and_then(Value::as_str) {
+        return input.to_string();
+    }
+    payload
+        .pointer("/action/command")
+        .and_then(Value::as_array)
+        .map(|command| {
+            command
+                .iter()
+                .filter_map(Value::as_str)
+                .collect::<Vec<_>>()
+                .join(" ")
+        })
+        .unwrap_or_default()
+}
+
+fn tool_output(output: &Value) -> String {
+    match output {
+        Value::String(text) => text.clone(),
+        other => other
+            .get("content")
+            .and_then(Value::as_str)
+            .map(str::to_string)
+            .unwrap_or_else(|| other.to_string()),
+    }
+}
+
+/// Reads the conversation from a rollout file: user and assistant messages
+/// (without injected context) and tool calls paired with their output.
+fn read_conversation(path: &Path) -> Result<Conversation, String> {
+    let file = File::open(path).map_err(|err| format!("Failed to open session file: {err}"))?;
+    let mut conversation = Conversation::default();
+    let mut calls: HashMap<String, usize> = HashMap::new();
+    for line in BufReader::new(file).lines().map_while(Result::ok) {
+        if line.len() > MAX_LINE_BYTES {
+            continue;
+        }
+        let Ok(value) = serde_json::from_str::<Value>(&line) else {
+            continue;
+        };
+        let Some(payload) = value.get("payload") else {
+            continue;
+        };
+        let item_type = payload.get("type").and_then(Value::as_str);
+        match (value.get("type").and_then(Value::as_str), item_type) {
+            (Some("session_meta"), _) => {
+                if let Some(id) = payload.get("id").and_then(Value::as_str) {
+                    conversation.session_id = id.to_string();
+                }
+                if conversation.cwd.is_none() {
+                    conversation.cwd = payload
+                        .get("cwd")
+                        .and_then(Value::as_str)
+                        .map(str::to_string);
+                }
+                if conversation.started_at_ms.is_none() {
+                    conversation.started_at_ms = read_timestamp_ms(&value);
+                }
+            }
+            (Some("response_item"), Some("message")) => {
+                let role = payload.get("role").and_then(Value::as_str).unwrap_or("");
+                if role != "user" && role != "assistant" {
+                    continue;
+                }
+                let Some(text) = extract_message_text(payload) else {
+                    continue;
+                };
+                if role == "user" && is_injected_context(&text) {
+                    continue;
+                }
+                conversation.entries.push(ExportEntry::Message {
+                    role: role.to_string(),
+                    text,
+                });
+            }
+            (
+                Some("response_item"),
+                Some("function_call") | Some("custom_tool_call") | Some("local_shell_call"),
+            ) => {
+                if let Some(call_id) = payload.get("call_id").and_then(Value::as_str) {
+                    calls.insert(call_id.to_string(), conversation.entries.len());
+                }
+                conversation.entries.push(ExportEntry::ToolCall {
+                    name: payload
+                        .get("name")
+                        .and_then(Value::as_str)
+                        .unwrap_or("shell")
+                        .to_string(),
+                    input: tool_input(payload),
+                    output: None,
+                });
+            }
+            (
+                Some("response_item"),
+                Some("function_call_output") | Some("custom_tool_call_output"),
+            ) => {
+                let index = payload
+                    .get("call_id")
+                    .and_then(Value::as_str)
+                    .and_then(|call_id| calls.get(call_id));
+                if let (Some(index), Some(output)) = (index, payload.get("output")) {
+                    if let Some(ExportEntry::ToolCall { output: slot, .. }) =
+                        conversation.entries.get_mut(*index)
+                    {
+                        *slot = Some(tool_output(output));
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+    Ok(conversation)
+}
+
+fn clip(text: &str) -> String {
+    match text.char_indices().nth(MAX_TOOL_OUTPUT_CHARS) {
+        Some((index, _)) => format!(
+            "{}\n… ({} more characters)",
+            &text[..index],
+            text[index..].chars().count()
+        ),
+        None => text.to_string(),
+    }
+}
+
+fn role_label(role: &str) -> &'static str {
+    if role == "user" {
+        "User"
+    } else {
+        "Assistant"
+    }
+}
+
+fn format_timestamp(timestamp_ms: i64) -> Option<String> {
+    DateTime::from_timestamp_millis(timestamp_ms)
+        .map(|time| time.format("%Y-%m-%d %H:%M UTC").to_string())
+}
+
+/// A code fence longer than any backtick run in `text`.
+fn fence_for(text: &str) -> String {
+    let mut longest = 0;
+    let mut run = 0;
+    for ch in text.chars() {
+        run = if ch == '`' { run + 1 } else { 0 };
+        longest = longest.max(run);
+    }
+    "`".repeat(longest.max(2) + 1)
+}
+
+fn fenced(text: &str) -> String {
+    let fence = fence_for(text);
+    format!("{fence}\n{}\n{fence}\n", text.trim_end_matches('\n'))
+}
+
+fn escape_html(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for ch in text.chars() {
+        match ch {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            _ => escaped.push(ch),
+        }
+    }
+    escaped
+}
+
+/// Tool calls become `<details>` blocks, which GitHub and most Markdown
+/// viewers render collapsed.
+fn render_markdown(conversation: &Conversation, include_tool_output: bool) -> String {
+    let mut out = format!("# Codex session {}\n\n", conversation.session_id);
+    if let Some(cwd) = &conversation.cwd {
+        out.push_str(&format!("- Directory: `{cwd}`\n"));
+    }
+    if let Some(started) = conversation.started_at_ms.and_then(format_timestamp) {
+        out.push_str(&format!("- Started: {started}\n"));
+    }
+    for entry in &conversation.entries {
+        match entry {
+            ExportEntry::Message { role, text } => {
+                out.push_str(&format!("\n## {}\n\n{}\n", role_label(role), text.trim()));
+            }
+            ExportEntry::ToolCall {
+                name,
+                input,
+                output,
+            } => {
+                out.push_str(&format!(
+                    "\n<details>\n<summary>Tool call: <code>{}</code></summary>\n\n",
+                    escape_html(name)
+                ));
+                out.push_str(&fenced(input));
+                if let (true, Some(output)) = (include_tool_output, output) {
+                    out.push_str("\nOutput:\n\n");
+                    out.push_str(&fenced(&clip(output)));
+                }
+                out.push_str("\n</details>\n");
+            }
+        }
+    }
+    out
+}
+
+fn render_html(conversation: &Conversation, include_tool_output: bool) -> String {
+    let title = escape_html(&format!("Codex session {}", conversation.session_id));
+    let meta: Vec<String> = [
+        conversation.cwd.clone(),
+        conversation.started_at_ms.and_then(format_timestamp),
+    ]
+    .into_iter()
+    .flatten()
+    .map(|part| escape_html(&part))
+    .collect();
+    let mut body = format!(
+        "<header><h1>{title}</h1><p class=\"meta\">{}</p></header>\n",
+        meta.join(" · ")
+    );
+    for entry in &conversation.entries {
+        match entry {
+            ExportEntry::Message { role, text } => {
+                body.push_str(&format!(
+                    "<section class=\"message {}\"><h2>{}</h2><div class=\"text\">{}</div></section>\n",
+                    if role == "user" { "user" } else { "assistant" },
+                    role_label(role),
+                    escape_html(text.trim())
+                ));
+            }
+            ExportEntry::ToolCall {
+                name,
+                input,
+                output,
+            } => {
+                body.push_str(&format!(
+                    "<details class=\"tool\"><summary>Tool call: <code>{}</code></summary><pre>{}</pre>",
+                    escape_html(name),
+                    escape_html(input)
+                ));
+                if let (true, Some(output)) = (include_tool_output, output) {
+                    body.push_str(&format!(
+                        "<p>Output</p><pre>{}</pre>",
+                        escape_html(&clip(output))
+                    ));
+                }
+                body.push_str("</details>\n");
+            }
+        }
+    }
+    format!(
+        "<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n\
+<meta name=\"viewport\" content=\"width=device-width, initial-scale=1\">\n\
+<title>{title}</title>\n<style>{HTML_STYLE}</style>\n</head>\n<body>\n{body}</body>\n</html>\n"
+    )
+}
+
+fn rollout_session_id(path: &Path) -> Option<String> {
+    let mut first_line = String::new();
+    BufReader::new(File::open(path).ok()?)
+        .read_line(&mut first_line)
+        .ok()?;
+    let value: Value = serde_json::from_str(&first_line).ok()?;
+    if value.get("type").and_then(Value::as_str) != Some("session_meta") {
+        return None;
+    }
+    value
+        .pointer("/payload/id")
+        .and_then(Value::as_str)
+        .map(str::to_string)
+}
+
+/// Rollout files are named after their session, so names are checked before
+/// any file is opened.
+fn find_rollout(roots: &[PathBuf], session_id: &str) -> Option<PathBuf> {
+    let files: Vec<PathBuf> = roots
+        .iter()
+        .flat_map(|root| collect_session_files(root))
+        .collect();
+    files
+        .iter()
+        .find(|path| {
+            path.file_stem()
+                .is_some_and(|stem| stem.to_string_lossy().ends_with(session_id))
+        })
+        .or_else(|| {
+            files
+                .iter()
+                .find(|path| rollout_session_id(path).as_deref() == Some(session_id))
+        })
+        .cloned()
+}
+
+fn export_file_name(session_id: &str, format: SessionExportFormat) -> String {
+    let stem: String = session_id
+        .chars()
+        .map(|ch| {
+            if ch.is_ascii_alphanumeric() || ch == '-' || ch == '_' {
+                ch
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    let extension = match format {
+        SessionExportFormat::Markdown => "md",
+        SessionExportFormat::Html => "html",
+    };
+    format!("session-{stem}.{extension}")
+}
+
+fn export_path(data_dir: &Path, file_name: &str) -> Result<PathBuf, String> {
+    if file_name.is_empty() || file_name.starts_with('.') || file_name.contains(['/', '\\']) {
+        return Err("Invalid export file name".to_string());
+    }
+    Ok(data_dir.join(EXPORTS_DIR).join(file_name))
+}
+
+/// Writes an export into the `exports` directory and returns its path.
+pub(crate) fn save_export(
+    data_dir: &Path,
+    file_name: &str,
+    content: &str,
+) -> Result<PathBuf, String> {
+    let path = export_path(data_dir, file_name)?;
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent).map_err(|err| err.to_string())?;
+    }
+    std::fs::write(&path, content).map_err(|err| format!("Failed to write export: {err}"))?;
+    Ok(path)
+}
+
+fn export_session_inner(
+    roots: &[PathBuf],
+    data_dir: &Path,
+    session_id: &str,
+    format: SessionExportFormat,
+    include_tool_output: bool,
+    redactor: &Redactor,
+) -> Result<SessionExport, String> {
+    let rollout = find_rollout(roots, session_id).ok_or_else(|| "session not found".to_string())?;
+    let mut conversation = read_conversation(&rollout)?;
+    conversation.redact(redactor);
+    let content = match format {
+        SessionExportFormat::Markdown => render_markdown(&conversation, include_tool_output),
+        SessionExportFormat::Html => render_html(&conversation, include_tool_output),
+    };
+    let file_name = export_file_name(session_id, format);
+    let path = save_export(data_dir, &file_name, &content)?;
+    Ok(SessionExport {
+        session_id: session_id.to_string(),
+        format,
+        file_name,
+        path: path.to_string_lossy().to_string(),
+        size: content.len() as u64,
+    })
+}
+
+/// Renders a Codex session as Markdown or HTML, redacted, and saves it
+/// under `exports/` in the data directory, replacing an earlier export of
+/// the same session and format.
+pub(crate) async fn export_session_core(
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    data_dir: &Path,
+    session_id: String,
+    format: SessionExportFormat,
+    include_tool_output: bool,
+) -> Result<SessionExport, String> {
+    let session_id = session_id.trim().to_string();
+    if session_id.is_empty() {
+        return Err("A session id is required".to_string());
+    }
+    let roots = {
+        let workspaces = workspaces.lock().await;
+        resolve_sessions_roots(&workspaces, None)
+    };
+    let data_dir = data_dir.to_path_buf();
+    tokio::task::spawn_blocking(move || {
+        export_session_inner(
+            &roots,
+            &data_dir,
+            &session_id,
+            format,
+            include_tool_output,
+            &redactor(),
+        )
+    })
+    .await
+    .map_err(|err| err.to_string())?
+}
+
+/// Returns part of an export. Chunks end on character boundaries, so a
+/// client appending `content` and advancing by `length` rebuilds the file.
+/// Only the daemon serves exports this way.
+#[allow(dead_code)]
+pub(crate) fn read_session_export_core(
+    data_dir: &Path,
+    file_name: &str,
+    offset: Option<u64>,
+    length: Option<u64>,
+) -> Result<RemoteFileChunk, String> {
+    let content = std::fs::read_to_string(export_path(data_dir, file_name)?)
+        .map_err(|err| format!("Failed to read export: {err}"))?;
+    let size = content.len();
+    let start = (offset.unwrap_or(0) as usize).min(size);
+    if !content.is_char_boundary(start) {
+        return Err("Offset is not on a character boundary".to_string());
+    }
+    let requested = length.unwrap_or(EXPORT_CHUNK_BYTES).max(1) as usize;
+    let mut end = start.saturating_add(requested).min(size);
+    while !content.is_char_boundary(end) {
+        end -= 1;
+    }
+    if end == start && start < size {
+        end = start + content[start..].chars().next().map_or(0, char::len_utf8);
+    }
+    Ok(RemoteFileChunk {
+        path: file_name.to_string(),
+        size: size as u64,
+        offset: start as u64,
+        length: (end - start) as u64,
+        binary: false,
+        truncated: end < size,
+        content: Some(content[start..end].to_string()),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::RedactionSettings;
+
+    fn temp_dir() -> PathBuf {
+        std::env::temp_dir().join(format!("codex-monitor-export-{}", uuid::Uuid::new_v4()))
+    }
+
+    fn write_rollout_with_tools(path: &Path) {
+        let lines = [
+            serde_json::json!({
+                "timestamp": "2026-01-05T10:00:00.000Z",
+                "type": "session_meta",
+                "payload": { "id": "abc-123", "cwd": "/src/api" }
+            }),
+            serde_json::json!({
+                "type": "response_item",
+                "payload": {
+                    "type": "message",
+                    "role": "user",
+                    "content": [{ "type": "input_text", "text": "<environment_context>x</environment_context>" }]
+                }
+            }),
+            serde_json::json!({
+                "type": "response_item",
+                "payload": {
+                    "type": "message",
+                    "role": "user",
+                    "content": [{ "type": "input_text", "text": "List <files>" }]
+                }
+            }),
+            serde_json::json!({
+                "type": "response_item",
+                "payload": {
+                    "type": "function_call",
+                    "name": "shell",
+                    "arguments": "{\"command\":[\"ls\"]}",
+                    "call_id": "call_1"
+                }
+            }),
+            serde_json::json!({
+                "type": "response_item",
+                "payload": {
+                    "type": "function_call_output",
+                    "call_id": "call_1",
+                    "output": "Cargo.toml\n```weird```\nkey sk-proj-abcdefghijklmnopqrstuvwx"
+                }
+            }),
+            serde_json::json!({
+                "type": "response_item",
+                "payload": {
+                    "type": "message",
+                    "role": "assistant",
+                    "content": [{ "type": "output_text", "text": "There is one file." }]
+                }
+            }),
+        ];
+        std::fs::create_dir_all(path.parent().expect("parent")).expect("create dir");
+        let body: Vec<String> = lines.iter().map(|line| line.to_string()).collect();
+        std::fs::write(path, body.join("\n")).expect("write rollout");
+    }
+
+    #[test]
+    fn exports_render_messages_and_collapsible_tool_calls() {
+        let root = temp_dir();
+        write_rollout_with_tools(
+            &root.join("2026/01/05/rollout-2026-01-05T10-00-00-abc-123.jsonl"),
+        );
+        let roots = vec![root.clone()];
+        let redactor = Redactor::new(&RedactionSettings::default()).expect("redactor");
+
+        let export = export_session_inner(
+            &roots,
+            &root,
+            "abc-123",
+            SessionExportFormat::Markdown,
+            true,
+            &redactor,
+        )
+        .expect("markdown export");
+        assert_eq!(export.file_name, "session-abc-123.md");
+        let markdown = std::fs::read_to_string(&export.path).expect("read export");
+        assert!(markdown.starts_with("# Codex session abc-123\n\n- Directory: `/src/api`\n"));
+        assert!(markdown.contains("- Started: 2026-01-05 10:00 UTC\n"));
+        assert!(!markdown.contains("environment_context"));
+        assert!(markdown.contains("## User\n\nList <files>\n"));
+        assert!(markdown.contains("<summary>Tool call: <code>shell</code></summary>"));
+        assert!(
+            markdown.contains("````\nCargo.toml\n```weird```\nkey [REDACTED:openai-key]\n````\n")
+        );
+        assert!(markdown.contains("## Assistant\n\nThere is one file.\n"));
+
+        let export = export_session_inner(
+            &roots,
+            &root,
+            "abc-123",
+            SessionExportFormat::Html,
+            false,
+            &redactor,
+        )
+        .expect("html export");
+        let html = std::fs::read_to_string(&export.path).expect("read export");
+        assert!(html.starts_with("<!DOCTYPE html>"));
+        assert!(html.contains("List &lt;files&gt;"));
+        assert!(html.contains("<details class=\"tool\">"));
+        assert!(!html.contains("Cargo.toml"));
+
+        assert!(export_session_inner(
+            &roots,
+            &root,
+            "missing",
+            SessionExportFormat::Html,
+            false,
+            &redactor
+        )
+        .is_err());
+        let _ = std::fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn export_chunks_end_on_character_boundaries() {
+        let data_dir = temp_dir();
+        save_export(&data_dir, "session-x.md", "añb").expect("save");
+
+        let first = read_session_export_core(&data_dir, "session-x.md", None, Some(2))
+            .expect("first chunk");
+        assert_eq!(first.content.as_deref(), Some("a"));
+        assert!(first.truncated);
+        let second =
+            read_session_export_core(&data_dir, "session-x.md", Some(first.length), Some(1))
+                .expect("second chunk");
+        assert_eq!(second.content.as_deref(), Some("ñ"));
+        let rest =
+            read_session_export_core(&data_dir, "session-x.md", Some(3), None).expect("rest");
+        assert_eq!(rest.content.as_deref(), Some("b"));
+        assert!(!rest.truncated);
+
+        assert!(read_session_export_core(&data_dir, "../settings.json", None, None).is_err());
+        let _ = std::fs::remove_dir_all(&data_dir);
+    }
+}
@@ -0,0 +1,730 @@
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex as StdMutex;
+use std::time::UNIX_EPOCH;
+
+use tokio::sync::Mutex;
+
+use crate::shared::local_usage_core::resolve_sessions_roots;
+use crate::shared::session_transcript_core::{
+    collect_session_files, read_session_transcript, TranscriptMessage,
+};
+use crate::shared::tags_core::TagFilter;
+use crate::types::{SessionSearchFilters, SessionSearchHit, SnippetSegment, WorkspaceEntry};
+
+const INDEX_DIRNAME: &str = "session-search-index";
+/// The single-file index written by older versions, removed on first use.
+const LEGACY_INDEX_FILENAME: &str = "session-search-index.json";
+const INDEX_VERSION: u32 = 2;
+const MAX_INDEXED_MESSAGE_CHARS: usize = 4_000;
+const DEFAULT_LIMIT: usize = 50;
+const MAX_LIMIT: usize = 200;
+const SNIPPET_CONTEXT_BEFORE: usize = 60;
+const SNIPPET_CONTEXT_AFTER: usize = 140;
+
+type Trigram = [char; 3];
+
+/// One rollout file's entry, stored on disk as its own segment so a changed
+/// session only rewrites its own file. `trigrams` maps every trigram of the
+/// folded message texts to the indexes of the messages containing it.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+struct IndexedSession {
+    version: u32,
+    path: String,
+    modified_ms: i64,
+    size: u64,
+    session_id: String,
+    #[serde(default)]
+    cwd: Option<String>,
+    #[serde(default)]
+    started_at_ms: Option<i64>,
+    messages: Vec<TranscriptMessage>,
+    trigrams: HashMap<String, Vec<u32>>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+struct MessageRef {
+    slot: u32,
+    message: u32,
+}
+
+/// Inverted index over every indexed message. Sessions live in numbered
+/// slots so postings stay small, and `files` maps rollout paths to slots.
+#[derive(Debug, Default)]
+struct SessionSearchIndex {
+    files: HashMap<String, u32>,
+    sessions: HashMap<u32, IndexedSession>,
+    postings: HashMap<Trigram, HashSet<MessageRef>>,
+    next_slot: u32,
+}
+
+/// Rollout paths whose segments need writing or deleting after a refresh.
+#[derive(Debug, Default)]
+struct IndexChanges {
+    updated: Vec<String>,
+    removed: Vec<String>,
+}
+
+impl IndexChanges {
+    fn is_empty(&self) -> bool {
+        self.updated.is_empty() && self.removed.is_empty()
+    }
+}
+
+impl SessionSearchIndex {
+    fn insert(&mut self, session: IndexedSession) {
+        self.remove(&session.path);
+        let slot = self.next_slot;
+        self.next_slot += 1;
+        for (gram, messages) in &session.trigrams {
+            let Ok(gram) = Trigram::try_from(gram.chars().collect::<Vec<_>>()) else {
+                continue;
+            };
+            self.postings
+                .entry(gram)
+                .or_default()
+                .extend(messages.iter().map(|&message| MessageRef { slot, message }));
+        }
+        self.files.insert(session.path.clone(), slot);
+        self.sessions.insert(slot, session);
+    }
+
+    fn remove(&mut self, path: &str) -> bool {
+        let Some(slot) = self.files.remove(path) else {
+            return false;
+        };
+        let Some(session) = self.sessions.remove(&slot) else {
+            return true;
+        };
+        for gram in session.trigrams.keys() {
+            let Ok(gram) = Trigram::try_from(gram.chars().collect::<Vec<_>>()) else {
+                continue;
+            };
+            if let Some(refs) = self.postings.get_mut(&gram) {
+                refs.retain(|entry| entry.slot != slot);
+                if refs.is_empty() {
+                    self.postings.remove(&gram);
+                }
+            }
+        }
+        true
+    }
+
+    /// Messages containing every trigram of the terms, or `None` when no term
+    /// is long enough to have one and every message has to be checked.
+    fn candidates(&self, terms: &[Vec<char>]) -> Option<Vec<MessageRef>> {
+        let mut lists = Vec::new();
+        for window in terms.iter().flat_map(|term| term.windows(3)) {
+            let gram = Trigram::try_from(window).ok()?;
+            match self.postings.get(&gram) {
+                Some(refs) => lists.push(refs),
+                None => return Some(Vec::new()),
+            }
+        }
+        lists.sort_by_key(|refs| refs.len());
+        let (first, rest) = lists.split_first()?;
+        Some(
+            first
+                .iter()
+                .filter(|entry| rest.iter().all(|refs| refs.contains(*entry)))
+                .copied()
+                .collect(),
+        )
+    }
+}
+
+/// The index loaded by this process, keyed by its directory so a moved data
+/// directory starts over. Holding the lock also serializes refreshes.
+static LOADED_INDEX: StdMutex<Option<(PathBuf, SessionSearchIndex)>> = StdMutex::new(None);
+
+fn index_dir(data_dir: &Path) -> PathBuf {
+    data_dir.join(INDEX_DIRNAME)
+}
+
+fn segment_path(dir: &Path, path: &str) -> PathBuf {
+    let digest: String = Sha256::digest(path.as_bytes())
+        .iter()
+        .map(|byte| format!("{byte:02x}"))
+        .collect();
+    dir.join(format!("{digest}.json"))
+}
+
+fn load_index(dir: &Path) -> SessionSearchIndex {
+    let mut index = SessionSearchIndex::default();
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return index;
+    };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if path.extension().and_then(|ext| ext.to_str()) != Some("json") {
+            continue;
+        }
+        let Some(session) = std::fs::read_to_string(&path)
+            .ok()
+            .and_then(|raw| serde_json::from_str::<IndexedSession>(&raw).ok())
+            .filter(|session| session.version == INDEX_VERSION)
+        else {
+            continue;
+        };
+        index.insert(session);
+    }
+    index
+}
+
+/// Writes through a temp file unique to this write, so the app and the
+/// daemon indexing the same data directory never clobber each other.
+fn write_atomically(path: &Path, data: &str) -> Result<(), String> {
+    let tmp_path = path.with_extension(format!("{}.tmp", uuid::Uuid::new_v4()));
+    std::fs::write(&tmp_path, data).map_err(|err| err.to_string())?;
+    std::fs::rename(&tmp_path, path).map_err(|err| {
+        let _ = std::fs::remove_file(&tmp_path);
+        err.to_string()
+    })
+}
+
+fn save_changes(
+    dir: &Path,
+    index: &SessionSearchIndex,
+    changes: &IndexChanges,
+) -> Result<(), String> {
+    std::fs::create_dir_all(dir).map_err(|err| err.to_string())?;
+    for path in &changes.removed {
+        let _ = std::fs::remove_file(segment_path(dir, path));
+    }
+    for path in &changes.updated {
+        let Some(session) = index
+            .files
+            .get(path)
+            .and_then(|slot| index.sessions.get(slot))
+        else {
+            continue;
+        };
+        let data = serde_json::to_string(session).map_err(|err| err.to_string())?;
+        write_atomically(&segment_path(dir, path), &data)?;
+    }
+    Ok(())
+}
+
+fn truncate_chars(text: &str, max_chars: usize) -> String {
+    match text.char_indices().nth(max_chars) {
+        Some((byte_index, _)) => text[..byte_index].to_string(),
+        None => text.to_string(),
+    }
+}
+
+fn message_trigrams(messages: &[TranscriptMessage]) -> HashMap<String, Vec<u32>> {
+    let mut trigrams: HashMap<String, Vec<u32>> = HashMap::new();
+    for (index, message) in messages.iter().enumerate() {
+        let folded = fold_chars(&message.text);
+        let grams: HashSet<&[char]> = folded.windows(3).collect();
+        for gram in grams {
+            trigrams
+                .entry(gram.iter().collect())
+                .or_default()
+                .push(index as u32);
+        }
+    }
+    trigrams
+}
+
+/// Brings the index in line with the rollout files under `roots`: unchanged
+/// files (same mtime and size) are reused, new or modified files are re-read
+/// and re-indexed, and entries for files that disappeared are dropped.
+fn refresh_index(index: &mut SessionSearchIndex, roots: &[PathBuf]) -> IndexChanges {
+    let mut changes = IndexChanges::default();
+    let mut seen = HashSet::new();
+    for root in roots {
+        for path in collect_session_files(root) {
+            let Ok(metadata) = std::fs::metadata(&path) else {
+                continue;
+            };
+            let key = path.to_string_lossy().to_string();
+            let modified_ms = metadata
+                .modified()
+                .ok()
+                .and_then(|time| time.duration_since(UNIX_EPOCH).ok())
+                .map(|duration| duration.as_millis() as i64)
+                .unwrap_or(0);
+            let size = metadata.len();
+            seen.insert(key.clone());
+            if index
+                .files
+                .get(&key)
+                .and_then(|slot| index.sessions.get(slot))
+                .is_some_and(|entry| entry.modified_ms == modified_ms && entry.size == size)
+            {
+                continue;
+            }
+            let Ok(transcript) = read_session_transcript(&path) else {
+                continue;
+            };
+            let messages: Vec<TranscriptMessage> = transcript
+                .messages
+                .into_iter()
+                .map(|message| TranscriptMessage {
+                    text: truncate_chars(&message.text, MAX_INDEXED_MESSAGE_CHARS),
+                    ..message
+                })
+                .collect();
+            index.insert(IndexedSession {
+                version: INDEX_VERSION,
+                path: key.clone(),
+                modified_ms,
+                size,
+                session_id: transcript.session_id,
+                cwd: transcript.cwd,
+                started_at_ms: transcript.started_at_ms,
+                trigrams: message_trigrams(&messages),
+                messages,
+            });
+            changes.updated.push(key);
+        }
+    }
+    let gone: Vec<String> = index
+        .files
+        .keys()
+        .filter(|key| !seen.contains(*key))
+        .cloned()
+        .collect();
+    for key in gone {
+        index.remove(&key);
+        changes.removed.push(key);
+    }
+    changes
+}
+
+fn fold_chars(text: &str) -> Vec<char> {
+    // Fold per character so positions stay aligned with the original text.
+    text.chars()
+        .map(|ch| ch.to_lowercase().next().unwrap_or(ch))
+        .collect()
+}
+
+fn find_all(haystack: &[char], needle: &[char]) -> Vec<usize> {
+    if needle.is_empty() || needle.len() > haystack.len() {
+        return Vec::new();
+    }
+    (0..=haystack.len() - needle.len())
+        .filter(|start| &haystack[*start..*start + needle.len()] == needle)
+        .collect()
+}
+
+/// Returns the snippet for `text` when every term matches, with matched
+/// ranges marked as highlighted segments.
+fn match_message(text: &str, terms: &[Vec<char>]) -> Option<Vec<SnippetSegment>> {
+    let original: Vec<char> = text.chars().collect();
+    let folded = fold_chars(text);
+    let mut ranges = Vec::new();
+    for term in terms {
+        let starts = find_all(&folded, term);
+        if starts.is_empty() {
+            return None;
+        }
+        ranges.extend(starts.into_iter().map(|start| (start, start + term.len())));
+    }
+    ranges.sort();
+
+    let anchor = ranges[0].0;
+    let window_start = anchor.saturating_sub(SNIPPET_CONTEXT_BEFORE);
+    let window_end = (anchor + SNIPPET_CONTEXT_AFTER).min(original.len());
+
+    let mut segments: Vec<SnippetSegment> = Vec::new();
+    let mut push = |text: String, highlight: bool| {
+        if text.is_empty() {
+            return;
+        }
+        match segments.last_mut() {
+            Some(last) if last.highlight == highlight => last.text.push_str(&text),
+            _ => segments.push(SnippetSegment { text, highlight }),
+        }
+    };
+    if window_start > 0 {
+        push("…".to_string(), false);
+    }
+    let mut cursor = window_start;
+    for (start, end) in ranges {
+        if end <= cursor || start >= window_end {
+            continue;
+        }
+        let start = start.max(cursor);
+        let end = end.min(window_end);
+        push(original[cursor..start].iter().collect(), false);
+        push(original[start..end].iter().collect(), true);
+        cursor = end;
+    }
+    push(original[cursor..window_end].iter().collect(), false);
+    if window_end < original.len() {
+        push("…".to_string(), false);
+    }
+    Some(segments)
+}
+
+fn cwd_matches_workspace(cwd: Option<&str>, workspace_path: &Path) -> bool {
+    cwd.map(Path::new)
+        .is_some_and(|cwd| cwd == workspace_path || cwd.starts_with(workspace_path))
+}
+
+fn search_index(
+    index: &SessionSearchIndex,
+    query: &str,
+    filters: &SessionSearchFilters,
+    workspace_path: Option<&Path>,
+    tag_filter: Option<&TagFilter>,
+) -> Vec<SessionSearchHit> {
+    let terms: Vec<Vec<char>> = query.split_whitespace().map(fold_chars).collect();
+    if terms.is_empty() {
+        return Vec::new();
+    }
+    let role = filters
+        .role
+        .as_deref()
+        .map(str::trim)
+        .filter(|role| !role.is_empty());
+
+    // Terms shorter than a trigram cannot narrow the search, so a query made
+    // only of those checks every message.
+    let candidates = index.candidates(&terms).unwrap_or_else(|| {
+        index
+            .sessions
+            .iter()
+            .flat_map(|(&slot, session)| {
+                (0..session.messages.len() as u32).map(move |message| MessageRef { slot, message })
+            })
+            .collect()
+    });
+
+    let mut session_allowed: HashMap<u32, bool> = HashMap::new();
+    let mut hits = Vec::new();
+    for entry in candidates {
+        let Some(session) = index.sessions.get(&entry.slot) else {
+            continue;
+        };
+        let allowed = *session_allowed.entry(entry.slot).or_insert_with(|| {
+            workspace_path.is_none_or(|workspace_path| {
+                cwd_matches_workspace(session.cwd.as_deref(), workspace_path)
+            }) && tag_filter
+                .is_none_or(|filter| filter.matches(&session.session_id, session.cwd.as_deref()))
+        });
+        if !allowed {
+            continue;
+        }
+        let Some(message) = session.messages.get(entry.message as usize) else {
+            continue;
+        };
+        if role.is_some_and(|role| role != message.role) {
+            continue;
+        }
+        let timestamp_ms = message.timestamp_ms.or(session.started_at_ms);
+        if let Some(since_ms) = filters.since_ms {
+            if timestamp_ms.is_none_or(|value| value < since_ms) {
+                continue;
+            }
+        }
+        if let Some(until_ms) = filters.until_ms {
+            if timestamp_ms.is_none_or(|value| value > until_ms) {
+                continue;
+            }
+        }
+        let Some(snippet) = match_message(&message.text, &terms) else {
+            continue;
+        };
+        hits.push(SessionSearchHit {
+            session_id: session.session_id.clone(),
+            path: session.path.clone(),
+            cwd: session.cwd.clone(),
+            timestamp_ms,
+            role: message.role.clone(),
+            snippet,
+        });
+    }
+
+    hits.sort_by(|a, b| {
+        b.timestamp_ms
+            .cmp(&a.timestamp_ms)
+            .then_with(|| a.path.cmp(&b.path))
+    });
+    let limit = filters.limit.unwrap_or(DEFAULT_LIMIT).clamp(1, MAX_LIMIT);
+    hits.truncate(limit);
+    hits
+}
+
+fn search_sessions_inner(
+    data_dir: &Path,
+    roots: &[PathBuf],
+    query: &str,
+    filters: &SessionSearchFilters,
+    workspace_path: Option<&Path>,
+    tag_filter: Option<&TagFilter>,
+) -> Result<Vec<SessionSearchHit>, String> {
+    let _ = std::fs::remove_file(data_dir.join(LEGACY_INDEX_FILENAME));
+    let dir = index_dir(data_dir);
+    let mut loaded = LOADED_INDEX
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+    // Reload when the data directory changed or the index was cleaned away.
+    if !dir.is_dir()
+        || loaded
+            .as_ref()
+            .is_none_or(|(loaded_dir, _)| *loaded_dir != dir)
+    {
+        *loaded = Some((dir.clone(), load_index(&dir)));
+    }
+    let Some((_, index)) = loaded.as_mut() else {
+        return Ok(Vec::new());
+    };
+    let changes = refresh_index(index, roots);
+    if !changes.is_empty() {
+        save_changes(&dir, index, &changes)?;
+    }
+    Ok(search_index(
+        index,
+        query,
+        filters,
+        workspace_path,
+        tag_filter,
+    ))
+}
+
+pub(crate) async fn search_sessions_core(
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    data_dir: PathBuf,
+    query: String,
+    filters: Option<SessionSearchFilters>,
+) -> Result<Vec<SessionSearchHit>, String> {
+    if query.trim().is_empty() {
+        return Err("Search query is empty".to_string());
+    }
+    let filters = filters.unwrap_or_default();
+    let (roots, workspace_path, tag_filter) = {
+        let workspaces = workspaces.lock().await;
+        let workspace_path = match filters.workspace_id.as_deref() {
+            Some(workspace_id) => Some(PathBuf::from(
+                &workspaces
+                    .get(workspace_id)
+                    .ok_or_else(|| "workspace not found".to_string())?
+                    .path,
+            )),
+            None => None,
+        };
+        let roots = resolve_sessions_roots(&workspaces, workspace_path.as_deref());
+        let tag_filter = TagFilter::load(&data_dir, &filters.tags, &workspaces)?;
+        (roots, workspace_path, tag_filter)
+    };
+
+    tokio::task::spawn_blocking(move || {
+        search_sessions_inner(
+            &data_dir,
+            &roots,
+            &query,
+            &filters,
+            workspace_path.as_deref(),
+            tag_filter.as_ref(),
+        )
+    })
+    .await
+    .map_err(|err| err.to_string())?
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::shared::session_transcript_core::test_support::write_rollout;
+    use crate::shared::tags_core::{save_tag_core, set_tags_core};
+    use crate::types::{TagInput, TagTarget};
+
+    fn temp_dir(prefix: &str) -> PathBuf {
+        let unique = std::time::SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("time")
+            .as_nanos();
+        std::env::temp_dir().join(format!(
+            "codex-monitor-{prefix}-{}-{unique}",
+            std::process::id()
+        ))
+    }
+
+    fn highlighted(snippet: &[SnippetSegment]) -> Vec<&str> {
+        snippet
+            .iter()
+            .filter(|segment| segment.highlight)
+            .map(|segment| segment.text.as_str())
+            .collect()
+    }
+
+    #[test]
+    fn match_message_requires_all_terms_and_highlights_them() {
+        let terms = vec![fold_chars("flaky"), fold_chars("TEST")];
+        let snippet = match_message("Fix the Flaky login test", &terms).expect("match");
+        assert_eq!(highlighted(&snippet), vec!["Flaky", "test"]);
+        let joined: String = snippet
+            .iter()
+            .map(|segment| segment.text.as_str())
+            .collect();
+        assert_eq!(joined, "Fix the Flaky login test");
+
+        assert!(match_message("Fix the login test", &terms).is_none());
+    }
+
+    #[test]
+    fn search_applies_workspace_and_role_filters() {
+        let root = temp_dir("search-filters");
+        let sessions = root.join("sessions");
+        let data_dir = root.join("data");
+        write_rollout(
+            &sessions.join("2026/01/05/a.jsonl"),
+            "session-a",
+            "/work/alpha",
+            "migrate the database",
+            "Database migrated.",
+        );
+        write_rollout(
+            &sessions.join("2026/01/05/b.jsonl"),
+            "session-b",
+            "/work/beta",
+            "database backup",
+            "Backup done.",
+        );
+
+        let roots = vec![sessions.clone()];
+        let all = search_sessions_inner(
+            &data_dir,
+            &roots,
+            "database",
+            &SessionSearchFilters::default(),
+            None,
+            None,
+        )
+        .expect("search");
+        assert_eq!(all.len(), 3);
+
+        let filters = SessionSearchFilters {
+            role: Some("assistant".to_string()),
+            ..Default::default()
+        };
+        let assistant = search_sessions_inner(&data_dir, &roots, "database", &filters, None, None)
+            .expect("search");
+        assert_eq!(assistant.len(), 1);
+        assert_eq!(assistant[0].session_id, "session-a");
+
+        let scoped = search_sessions_inner(
+            &data_dir,
+            &roots,
+            "database",
+            &SessionSearchFilters::default(),
+            Some(Path::new("/work/beta")),
+            None,
+        )
+        .expect("search");
+        assert_eq!(scoped.len(), 1);
+        assert_eq!(scoped[0].session_id, "session-b");
+
+        let tag = save_tag_core(
+            &data_dir,
+            TagInput {
+                id: None,
+                name: "backups".to_string(),
+                color: None,
+            },
+        )
+        .expect("tag");
+        set_tags_core(
+            &data_dir,
+            TagTarget::Session,
+            "session-a",
+            vec![tag.id.clone()],
+        )
+        .expect("assign");
+        let tag_filter = TagFilter::load(&data_dir, &[tag.id], &HashMap::new()).expect("filter");
+        let tagged = search_sessions_inner(
+            &data_dir,
+            &roots,
+            "database",
+            &SessionSearchFilters::default(),
+            None,
+            tag_filter.as_ref(),
+        )
+        .expect("search");
+        assert_eq!(tagged.len(), 2);
+        assert!(tagged.iter().all(|hit| hit.session_id == "session-a"));
+
+        let _ = std::fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn index_refresh_picks_up_new_and_removed_files() {
+        let root = temp_dir("search-refresh");
+        let sessions = root.join("sessions");
+        let first = sessions.join("2026/01/05/a.jsonl");
+        write_rollout(&first, "session-a", "/work", "hello", "hi");
+
+        let roots = vec![sessions.clone()];
+        let mut index = SessionSearchIndex::default();
+        assert!(!refresh_index(&mut index, &roots).is_empty());
+        assert!(refresh_index(&mut index, &roots).is_empty());
+
+        let second = sessions.join("2026/01/06/b.jsonl");
+        write_rollout(&second, "session-b", "/work", "hello again", "hi");
+        let changes = refresh_index(&mut index, &roots);
+        assert_eq!(changes.updated, vec![second.to_string_lossy().to_string()]);
+        assert_eq!(index.files.len(), 2);
+
+        std::fs::remove_file(&first).expect("remove");
+        let changes = refresh_index(&mut index, &roots);
+        assert_eq!(changes.removed, vec![first.to_string_lossy().to_string()]);
+        assert_eq!(index.files.len(), 1);
+        assert!(index
+            .candidates(&[fold_chars("again")])
+            .is_some_and(|refs| refs.len() == 1));
+        assert!(index
+            .candidates(&[fold_chars("hello")])
+            .is_some_and(|refs| refs.len() == 1));
+
+        let _ = std::fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn segments_are_reloaded_and_postings_narrow_candidates() {
+        let root = temp_dir("search-segments");
+        let sessions = root.join("sessions");
+        let dir = root.join("data").join(INDEX_DIRNAME);
+        write_rollout(
+            &sessions.join("2026/01/05/a.jsonl"),
+            "session-a",
+            "/work",
+            "rotate the signing keys",
+            "Keys rotated.",
+        );
+        write_rollout(
+            &sessions.join("2026/01/05/b.jsonl"),
+            "session-b",
+            "/work",
+            "update the docs",
+            "Docs updated.",
+        );
+
+        let mut index = SessionSearchIndex::default();
+        let changes = refresh_index(&mut index, &[sessions.clone()]);
+        save_changes(&dir, &index, &changes).expect("save");
+        let reloaded = load_index(&dir);
+        assert_eq!(reloaded.files.len(), 2);
+        assert!(refresh_index(&mut load_index(&dir), &[sessions]).is_empty());
+
+        let keys = reloaded
+            .candidates(&[fold_chars("KEYS")])
+            .expect("indexed term");
+        assert_eq!(keys.len(), 2);
+        assert!(keys
+            .iter()
+            .all(|entry| reloaded.sessions[&entry.slot].session_id == "session-a"));
+        assert_eq!(
+            reloaded.candidates(&[fold_chars("signing"), fold_chars("docs")]),
+            Some(Vec::new())
+        );
+        assert_eq!(reloaded.candidates(&[fold_chars("do")]), None);
+
+        let _ = std::fs::remove_dir_all(&root);
+    }
+}
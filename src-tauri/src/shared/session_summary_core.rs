@@ -0,0 +1,201 @@
+use serde_json::Value;
+
+use crate::types::{SessionCommandRun, SessionSummary, SessionTokenUsage};
+
+/// Longer lines are not events worth reading; a whole-file diff or command
+/// output can make them huge.
+const MAX_LINE_BYTES: usize = 512_000;
+const MAX_FILES: usize = 200;
+const MAX_COMMANDS: usize = 100;
+const MAX_MESSAGE_CHARS: usize = 500;
+
+/// Activity read from a session's `codex exec --json` stdout as it streams.
+/// Output arrives in arbitrary chunks, so partial lines are held until
+/// their newline.
+#[derive(Debug, Default)]
+pub(crate) struct SessionActivity {
+    partial: String,
+    files_touched: Vec<String>,
+    commands_run: Vec<SessionCommandRun>,
+    token_usage: Option<SessionTokenUsage>,
+    last_message: Option<String>,
+    error: Option<String>,
+}
+
+fn shorten(text: &str) -> String {
+    let text = text.trim();
+    match text.char_indices().nth(MAX_MESSAGE_CHARS) {
+        Some((index, _)) => format!("{}…", &text[..index]),
+        None => text.to_string(),
+    }
+}
+
+fn read_u64(value: &Value, key: &str) -> u64 {
+    value.get(key).and_then(Value::as_u64).unwrap_or(0)
+}
+
+impl SessionActivity {
+    pub(crate) fn push_output(&mut self, chunk: &str) {
+        self.partial.push_str(chunk);
+        while let Some(newline) = self.partial.find('\n') {
+            let line: String = self.partial.drain(..=newline).collect();
+            self.record_line(&line);
+        }
+        if self.partial.len() > MAX_LINE_BYTES {
+            self.partial.clear();
+        }
+    }
+
+    fn record_line(&mut self, line: &str) {
+        let Ok(event) = serde_json::from_str::<Value>(line.trim()) else {
+            return;
+        };
+        match event.get("type").and_then(Value::as_str) {
+            Some("item.completed") => {
+                if let Some(item) = event.get("item") {
+                    self.record_item(item);
+                }
+            }
+            Some("turn.completed") => {
+                if let Some(usage) = event.get("usage") {
+                    let total = self.token_usage.get_or_insert_with(Default::default);
+                    total.input_tokens += read_u64(usage, "input_tokens");
+                    total.cached_input_tokens += read_u64(usage, "cached_input_tokens");
+                    total.output_tokens += read_u64(usage, "output_tokens");
+                }
+            }
+            Some("turn.failed") | Some("error") => {
+                let message = event
+                    .pointer("/error/message")
+                    .or_else(|| event.get("message"))
+                    .and_then(Value::as_str);
+                if let Some(message) = message {
+                    self.error = Some(shorten(message));
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn record_item(&mut self, item: &Value) {
+        match item.get("type").and_then(Value::as_str) {
+            Some("command_execution") => {
+                let Some(command) = item.get("command").and_then(Value::as_str) else {
+                    return;
+                };
+                if self.commands_run.len() < MAX_COMMANDS {
+                    self.commands_run.push(SessionCommandRun {
+                        command: command.to_string(),
+                        exit_code: item
+                            .get("exit_code")
+                            .and_then(Value::as_i64)
+                            .map(|code| code as i32),
+                    });
+                }
+            }
+            Some("file_change") => {
+                let changes = item.get("changes").and_then(Value::as_array);
+                for change in changes.into_iter().flatten() {
+                    let Some(path) = change.get("path").and_then(Value::as_str) else {
+                        continue;
+                    };
+                    if self.files_touched.len() < MAX_FILES
+                        && !self.files_touched.iter().any(|seen| seen == path)
+                    {
+                        self.files_touched.push(path.to_string());
+                    }
+                }
+            }
+            Some("agent_message") => {
+                if let Some(text) = item.get("text").and_then(Value::as_str) {
+                    self.last_message = Some(shorten(text));
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// The summary for a session that ended after `duration_ms`. A final
+    /// line without a newline is still read.
+    pub(crate) fn summarize(
+        &mut self,
+        duration_ms: i64,
+        status: &str,
+        exit_code: Option<i32>,
+    ) -> SessionSummary {
+        let rest = std::mem::take(&mut self.partial);
+        self.record_line(&rest);
+        SessionSummary {
+            files_touched: self.files_touched.clone(),
+            commands_run: self.commands_run.clone(),
+            token_usage: self.token_usage.clone(),
+            duration_ms: duration_ms.max(0),
+            status: status.to_string(),
+            exit_code,
+            last_message: self.last_message.clone(),
+            error: self.error.clone(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn exec_events_add_up_to_a_summary() {
+        let output = [
+            r#"{"type":"thread.started","thread_id":"t-1"}"#,
+            r#"{"type":"item.started","item":{"id":"item_0","type":"command_execution","command":"bash -lc ls","exit_code":null,"status":"in_progress"}}"#,
+            r#"{"type":"item.completed","item":{"id":"item_0","type":"command_execution","command":"bash -lc ls","exit_code":0,"status":"completed"}}"#,
+            r#"{"type":"item.completed","item":{"id":"item_1","type":"file_change","changes":[{"path":"src/main.rs","kind":"update"},{"path":"README.md","kind":"add"}],"status":"completed"}}"#,
+            r#"{"type":"item.completed","item":{"id":"item_2","type":"file_change","changes":[{"path":"src/main.rs","kind":"update"}],"status":"completed"}}"#,
+            r#"{"type":"item.completed","item":{"id":"item_3","type":"agent_message","text":"Done: added the README."}}"#,
+            r#"{"type":"turn.completed","usage":{"input_tokens":1200,"cached_input_tokens":200,"output_tokens":300}}"#,
+        ]
+        .join("\n");
+        let mut activity = SessionActivity::default();
+        // Split mid-line to exercise the partial line buffer.
+        let (head, tail) = output.split_at(output.len() / 2);
+        activity.push_output(head);
+        activity.push_output(tail);
+
+        let summary = activity.summarize(4_000, "exited", Some(0));
+        assert_eq!(summary.files_touched, vec!["src/main.rs", "README.md"]);
+        assert_eq!(
+            summary.commands_run,
+            vec![SessionCommandRun {
+                command: "bash -lc ls".to_string(),
+                exit_code: Some(0),
+            }]
+        );
+        assert_eq!(
+            summary.token_usage,
+            Some(SessionTokenUsage {
+                input_tokens: 1200,
+                cached_input_tokens: 200,
+                output_tokens: 300,
+            })
+        );
+        assert_eq!(
+            summary.last_message.as_deref(),
+            Some("Done: added the README.")
+        );
+        assert_eq!(summary.error, None);
+        assert_eq!(summary.duration_ms, 4_000);
+    }
+
+    #[test]
+    fn failed_turns_and_noise_are_handled() {
+        let mut activity = SessionActivity::default();
+        activity.push_output("Reading prompt from stdin...\n");
+        activity.push_output(r#"{"type":"turn.failed","error":{"message":"stream disconnected"}}"#);
+
+        let summary = activity.summarize(10, "exited", Some(1));
+        assert!(summary.files_touched.is_empty());
+        assert!(summary.commands_run.is_empty());
+        assert_eq!(summary.token_usage, None);
+        assert_eq!(summary.error.as_deref(), Some("stream disconnected"));
+        assert_eq!(summary.exit_code, Some(1));
+    }
+}
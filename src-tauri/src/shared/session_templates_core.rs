@@ -0,0 +1,162 @@
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::storage::{read_session_templates, write_session_templates};
+use crate::types::{SessionTemplate, SessionTemplateInput};
+
+const TEMPLATES_FILENAME: &str = "session-templates.json";
+
+pub(crate) fn session_templates_path(data_dir: &Path) -> PathBuf {
+    data_dir.join(TEMPLATES_FILENAME)
+}
+
+fn now_ms() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_millis() as i64)
+        .unwrap_or(0)
+}
+
+fn normalize_optional(value: Option<String>) -> Option<String> {
+    value
+        .map(|value| value.trim().to_string())
+        .filter(|value| !value.is_empty())
+}
+
+fn validate_input(input: &SessionTemplateInput) -> Result<(), String> {
+    if input.name.trim().is_empty() {
+        return Err("Template name is required".to_string());
+    }
+    if input.prompt.trim().is_empty() {
+        return Err("Template prompt is required".to_string());
+    }
+    for key in input.env.keys() {
+        if key.trim().is_empty() || key.contains('=') || key.contains('\0') {
+            return Err(format!("Invalid environment variable name: {key:?}"));
+        }
+    }
+    Ok(())
+}
+
+pub(crate) fn list_session_templates_core(data_dir: &Path) -> Result<Vec<SessionTemplate>, String> {
+    let mut templates = read_session_templates(&session_templates_path(data_dir))?;
+    templates.sort_by_key(|template| template.name.to_lowercase());
+    Ok(templates)
+}
+
+pub(crate) fn get_session_template(data_dir: &Path, id: &str) -> Result<SessionTemplate, String> {
+    read_session_templates(&session_templates_path(data_dir))?
+        .into_iter()
+        .find(|template| template.id == id)
+        .ok_or_else(|| "template not found".to_string())
+}
+
+/// Creates a template when `input.id` is empty, otherwise updates the
+/// existing template with that id.
+pub(crate) fn save_session_template_core(
+    data_dir: &Path,
+    input: SessionTemplateInput,
+) -> Result<SessionTemplate, String> {
+    validate_input(&input)?;
+    let path = session_templates_path(data_dir);
+    let mut templates = read_session_templates(&path)?;
+    let now = now_ms();
+    let existing_index = match normalize_optional(input.id.clone()) {
+        Some(id) => Some(
+            templates
+                .iter()
+                .position(|template| template.id == id)
+                .ok_or_else(|| "template not found".to_string())?,
+        ),
+        None => None,
+    };
+    let template = SessionTemplate {
+        id: existing_index
+            .map(|index| templates[index].id.clone())
+            .unwrap_or_else(|| uuid::Uuid::new_v4().to_string()),
+        name: input.name.trim().to_string(),
+        prompt: input.prompt.trim().to_string(),
+        model: normalize_optional(input.model),
+        approval_mode: normalize_optional(input.approval_mode),
+        working_dir: normalize_optional(input.working_dir),
+        env: input.env,
+        created_at_ms: existing_index
+            .map(|index| templates[index].created_at_ms)
+            .unwrap_or(now),
+        updated_at_ms: now,
+    };
+    match existing_index {
+        Some(index) => templates[index] = template.clone(),
+        None => templates.push(template.clone()),
+    }
+    write_session_templates(&path, &templates)?;
+    Ok(template)
+}
+
+pub(crate) fn delete_session_template_core(data_dir: &Path, id: &str) -> Result<(), String> {
+    let path = session_templates_path(data_dir);
+    let mut templates = read_session_templates(&path)?;
+    let before = templates.len();
+    templates.retain(|template| template.id != id);
+    if templates.len() == before {
+        return Err("template not found".to_string());
+    }
+    write_session_templates(&path, &templates)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::BTreeMap;
+
+    fn temp_dir() -> PathBuf {
+        std::env::temp_dir().join(format!("codex-monitor-templates-{}", uuid::Uuid::new_v4()))
+    }
+
+    fn input(id: Option<&str>, name: &str) -> SessionTemplateInput {
+        SessionTemplateInput {
+            id: id.map(str::to_string),
+            name: name.to_string(),
+            prompt: "Update dependencies".to_string(),
+            model: Some(" ".to_string()),
+            approval_mode: Some("never".to_string()),
+            working_dir: None,
+            env: BTreeMap::new(),
+        }
+    }
+
+    #[test]
+    fn save_creates_then_updates_in_place() {
+        let data_dir = temp_dir();
+        let created =
+            save_session_template_core(&data_dir, input(None, "Update deps")).expect("create");
+        assert!(created.model.is_none());
+
+        let updated =
+            save_session_template_core(&data_dir, input(Some(&created.id), "Update all deps"))
+                .expect("update");
+        assert_eq!(updated.id, created.id);
+        assert_eq!(updated.created_at_ms, created.created_at_ms);
+
+        let listed = list_session_templates_core(&data_dir).expect("list");
+        assert_eq!(listed.len(), 1);
+        assert_eq!(listed[0].name, "Update all deps");
+
+        assert!(save_session_template_core(&data_dir, input(Some("missing"), "x")).is_err());
+        delete_session_template_core(&data_dir, &created.id).expect("delete");
+        assert!(list_session_templates_core(&data_dir)
+            .expect("list")
+            .is_empty());
+        assert!(delete_session_template_core(&data_dir, &created.id).is_err());
+        let _ = std::fs::remove_dir_all(&data_dir);
+    }
+
+    #[test]
+    fn save_rejects_invalid_env_names() {
+        let data_dir = temp_dir();
+        let mut bad = input(None, "Bad env");
+        bad.env.insert("A=B".to_string(), "1".to_string());
+        assert!(save_session_template_core(&data_dir, bad).is_err());
+        let _ = std::fs::remove_dir_all(&data_dir);
+    }
+}
@@ -0,0 +1,262 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::fs::File;
+use std::io::{BufRead, BufReader};
+use std::path::{Path, PathBuf};
+
+use crate::shared::local_usage_core::read_timestamp_ms;
+
+const MAX_LINE_BYTES: usize = 512_000;
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct TranscriptMessage {
+    pub(crate) role: String,
+    pub(crate) text: String,
+    #[serde(default)]
+    pub(crate) timestamp_ms: Option<i64>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct SessionTranscript {
+    pub(crate) session_id: String,
+    pub(crate) path: String,
+    #[serde(default)]
+    pub(crate) cwd: Option<String>,
+    #[serde(default)]
+    pub(crate) started_at_ms: Option<i64>,
+    pub(crate) messages: Vec<TranscriptMessage>,
+}
+
+/// Recursively collects rollout files (`*.jsonl`) below a sessions root.
+pub(crate) fn collect_session_files(root: &Path) -> Vec<PathBuf> {
+    let mut files = Vec::new();
+    let mut pending = vec![root.to_path_buf()];
+    while let Some(dir) = pending.pop() {
+        let Ok(entries) = std::fs::read_dir(&dir) else {
+            continue;
+        };
+        for entry in entries.flatten() {
+            let path = entry.path();
+            let Ok(file_type) = entry.file_type() else {
+                continue;
+            };
+            if file_type.is_dir() {
+                pending.push(path);
+            } else if file_type.is_file()
+                && path.extension().and_then(|ext| ext.to_str()) == Some("jsonl")
+            {
+                files.push(path);
+            }
+        }
+    }
+    files.sort();
+    files
+}
+
+pub(crate) fn is_injected_context(text: &str) -> bool {
+    let trimmed = text.trim_start();
+    trimmed.starts_with("<environment_context>")
+        || trimmed.starts_with("<user_instructions>")
+        || trimmed.starts_with("# AGENTS.md instructions")
+}
+
+pub(crate) fn extract_message_text(payload: &Value) -> Option<String> {
+    let content = payload.get("content")?.as_array()?;
+    let parts: Vec<&str> = content
+        .iter()
+        .filter(|part| {
+            matches!(
+                part.get("type").and_then(Value::as_str),
+                Some("input_text") | Some("output_text") | Some("text")
+            )
+        })
+        .filter_map(|part| part.get("text").and_then(Value::as_str))
+        .collect();
+    if parts.is_empty() {
+        return None;
+    }
+    Some(parts.join("\n"))
+}
+
+fn session_id_from_path(path: &Path) -> String {
+    path.file_stem()
+        .map(|stem| stem.to_string_lossy().to_string())
+        .unwrap_or_default()
+}
+
+/// Reads user/assistant messages from a Codex rollout file, skipping
+/// injected environment and instruction blocks.
+pub(crate) fn read_session_transcript(path: &Path) -> Result<SessionTranscript, String> {
+    let file = File::open(path).map_err(|err| format!("Failed to open session file: {err}"))?;
+    let reader = BufReader::new(file);
+    let mut transcript = SessionTranscript {
+        session_id: session_id_from_path(path),
+        path: path.to_string_lossy().to_string(),
+        cwd: None,
+        started_at_ms: None,
+        messages: Vec::new(),
+    };
+
+    for line in reader.lines() {
+        let Ok(line) = line else {
+            continue;
+        };
+        if line.len() > MAX_LINE_BYTES {
+            continue;
+        }
+        let Ok(value) = serde_json::from_str::<Value>(&line) else {
+            continue;
+        };
+        let timestamp_ms = read_timestamp_ms(&value);
+        let Some(payload) = value.get("payload") else {
+            continue;
+        };
+        match value.get("type").and_then(Value::as_str) {
+            Some("session_meta") => {
+                if let Some(id) = payload.get("id").and_then(Value::as_str) {
+                    transcript.session_id = id.to_string();
+                }
+                if transcript.cwd.is_none() {
+                    transcript.cwd = payload
+                        .get("cwd")
+                        .and_then(Value::as_str)
+                        .map(str::to_string);
+                }
+                if transcript.started_at_ms.is_none() {
+                    transcript.started_at_ms = timestamp_ms;
+                }
+            }
+            Some("response_item") => {
+                if payload.get("type").and_then(Value::as_str) != Some("message") {
+                    continue;
+                }
+                let role = payload.get("role").and_then(Value::as_str).unwrap_or("");
+                if role != "user" && role != "assistant" {
+                    continue;
+                }
+                let Some(text) = extract_message_text(payload) else {
+                    continue;
+                };
+                if role == "user" && is_injected_context(&text) {
+                    continue;
+                }
+                if transcript.started_at_ms.is_none() {
+                    transcript.started_at_ms = timestamp_ms;
+                }
+                transcript.messages.push(TranscriptMessage {
+                    role: role.to_string(),
+                    text,
+                    timestamp_ms,
+                });
+            }
+            _ => {}
+        }
+    }
+
+    Ok(transcript)
+}
+
+#[cfg(test)]
+pub(crate) mod test_support {
+    use std::path::Path;
+
+    /// Writes a minimal rollout file with one user and one assistant message.
+    pub(crate) fn write_rollout(path: &Path, session_id: &str, cwd: &str, user: &str, reply: &str) {
+        let lines = [
+            serde_json::json!({
+                "timestamp": "2026-01-05T10:00:00.000Z",
+                "type": "session_meta",
+                "payload": { "id": session_id, "cwd": cwd }
+            }),
+            serde_json::json!({
+                "timestamp": "2026-01-05T10:00:01.000Z",
+                "type": "response_item",
+                "payload": {
+                    "type": "message",
+                    "role": "user",
+                    "content": [{ "type": "input_text", "text": "<environment_context>cwd</environment_context>" }]
+                }
+            }),
+            serde_json::json!({
+                "timestamp": "2026-01-05T10:00:02.000Z",
+                "type": "response_item",
+                "payload": {
+                    "type": "message",
+                    "role": "user",
+                    "content": [{ "type": "input_text", "text": user }]
+                }
+            }),
+            serde_json::json!({
+                "timestamp": "2026-01-05T10:00:03.000Z",
+                "type": "response_item",
+                "payload": {
+                    "type": "message",
+                    "role": "assistant",
+                    "content": [{ "type": "output_text", "text": reply }]
+                }
+            }),
+        ];
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent).expect("create rollout dir");
+        }
+        let body = lines
+            .iter()
+            .map(|line| line.to_string())
+            .collect::<Vec<_>>()
+            .join("\n");
+        std::fs::write(path, body).expect("write rollout");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::test_support::write_rollout;
+    use super::*;
+
+    fn temp_dir(prefix: &str) -> PathBuf {
+        let unique = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .expect("time")
+            .as_nanos();
+        std::env::temp_dir().join(format!(
+            "codex-monitor-{prefix}-{}-{unique}",
+            std::process::id()
+        ))
+    }
+
+    #[test]
+    fn read_session_transcript_skips_injected_context() {
+        let root = temp_dir("transcript-read");
+        let path = root.join("2026/01/05/rollout-a.jsonl");
+        write_rollout(&path, "session-a", "/tmp/project", "fix the build", "Done.");
+
+        let transcript = read_session_transcript(&path).expect("read transcript");
+        assert_eq!(transcript.session_id, "session-a");
+        assert_eq!(transcript.cwd.as_deref(), Some("/tmp/project"));
+        assert!(transcript.started_at_ms.is_some());
+        assert_eq!(transcript.messages.len(), 2);
+        assert_eq!(transcript.messages[0].role, "user");
+        assert_eq!(transcript.messages[0].text, "fix the build");
+        assert_eq!(transcript.messages[1].role, "assistant");
+
+        let _ = std::fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn collect_session_files_walks_nested_day_dirs() {
+        let root = temp_dir("transcript-collect");
+        write_rollout(&root.join("2026/01/05/a.jsonl"), "a", "/tmp", "x", "y");
+        write_rollout(&root.join("2026/02/01/b.jsonl"), "b", "/tmp", "x", "y");
+        std::fs::write(root.join("2026/notes.txt"), "ignored").expect("write");
+
+        let files = collect_session_files(&root);
+        assert_eq!(files.len(), 2);
+        assert!(files
+            .iter()
+            .all(|path| path.extension().unwrap() == "jsonl"));
+
+        let _ = std::fs::remove_dir_all(&root);
+    }
+}
@@ -0,0 +1,395 @@
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use reqwest::Method;
+use serde_json::json;
+
+use crate::shared::codex_session_core::{session_is_active, SessionRegistry};
+use crate::shared::git_core::run_git_command;
+use crate::shared::github_api_core::{api_request, origin_github_repo, run_gh};
+use crate::types::{SessionPullRequest, SessionWorktree, SessionWorktreeMergeResult};
+
+const SESSION_WORKTREES_DIR: &str = "session-worktrees";
+
+fn now_ms() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_millis() as i64)
+        .unwrap_or(0)
+}
+
+fn session_worktrees_root(data_dir: &Path) -> PathBuf {
+    data_dir.join(SESSION_WORKTREES_DIR)
+}
+
+// Metadata lives next to (not inside) the checkout so git never sees it.
+fn metadata_path(data_dir: &Path, session_id: &str) -> PathBuf {
+    session_worktrees_root(data_dir).join(format!("{session_id}.json"))
+}
+
+fn validate_session_id(session_id: &str) -> Result<(), String> {
+    let valid = !session_id.is_empty()
+        && session_id
+            .chars()
+            .all(|ch| ch.is_ascii_alphanumeric() || ch == '-');
+    if valid {
+        Ok(())
+    } else {
+        Err("Invalid session id".to_string())
+    }
+}
+
+fn read_session_worktree(data_dir: &Path, session_id: &str) -> Result<SessionWorktree, String> {
+    validate_session_id(session_id)?;
+    let path = metadata_path(data_dir, session_id);
+    let data =
+        std::fs::read_to_string(&path).map_err(|_| "session worktree not found".to_string())?;
+    serde_json::from_str(&data).map_err(|err| err.to_string())
+}
+
+/// Creates `codex-session/<id>` from the current HEAD of the repository that
+/// contains `project_path`. Returns the worktree plus the directory the
+/// session should run in (the same subdirectory inside the new checkout).
+pub(crate) async fn create_session_worktree(
+    data_dir: &Path,
+    project_path: &str,
+    session_id: &str,
+) -> Result<(SessionWorktree, String), String> {
+    validate_session_id(session_id)?;
+    let project = PathBuf::from(project_path);
+    let source_path = run_git_command(&project, &["rev-parse", "--show-toplevel"])
+        .await
+        .map_err(|_| "Worktree isolation requires a git repository".to_string())?;
+    let prefix = run_git_command(&project, &["rev-parse", "--show-prefix"]).await?;
+    let base_commit = run_git_command(&project, &["rev-parse", "HEAD"])
+        .await
+        .map_err(|_| "Worktree isolation requires at least one commit".to_string())?;
+
+    let root = session_worktrees_root(data_dir);
+    std::fs::create_dir_all(&root)
+        .map_err(|err| format!("Failed to create worktree directory: {err}"))?;
+    let worktree_path = root.join(session_id);
+    let worktree_path_string = worktree_path.to_string_lossy().to_string();
+    let branch = format!("codex-session/{session_id}");
+    run_git_command(
+        &PathBuf::from(&source_path),
+        &[
+            "worktree",
+            "add",
+            "-b",
+            &branch,
+            &worktree_path_string,
+            &base_commit,
+        ],
+    )
+    .await?;
+
+    let worktree = SessionWorktree {
+        session_id: session_id.to_string(),
+        source_path,
+        worktree_path: worktree_path_string,
+        branch,
+        base_commit,
+        created_at_ms: now_ms(),
+    };
+    let data = serde_json::to_string_pretty(&worktree).map_err(|err| err.to_string())?;
+    std::fs::write(metadata_path(data_dir, session_id), data).map_err(|err| err.to_string())?;
+    let prefix = prefix.trim_end_matches('/');
+    let session_dir = if prefix.is_empty() {
+        worktree_path
+    } else {
+        worktree_path.join(prefix)
+    };
+    Ok((worktree, session_dir.to_string_lossy().to_string()))
+}
+
+async fn ensure_session_stopped(
+    registry: &SessionRegistry,
+    session_id: &str,
+) -> Result<(), String> {
+    if session_is_active(registry, session_id).await {
+        return Err(
+            "Stop the session before merging, discarding or publishing its worktree".to_string(),
+        );
+    }
+    Ok(())
+}
+
+async fn remove_session_worktree(
+    data_dir: &Path,
+    worktree: &SessionWorktree,
+) -> Result<(), String> {
+    let source = PathBuf::from(&worktree.source_path);
+    if Path::new(&worktree.worktree_path).exists() {
+        run_git_command(
+            &source,
+            &["worktree", "remove", "--force", &worktree.worktree_path],
+        )
+        .await?;
+    }
+    let _ = run_git_command(&source, &["worktree", "prune"]).await;
+    let _ = run_git_command(&source, &["branch", "-D", &worktree.branch]).await;
+    let _ = std::fs::remove_file(metadata_path(data_dir, &worktree.session_id));
+    Ok(())
+}
+
+async fn commit_pending_changes(worktree_path: &PathBuf, message: &str) -> Result<(), String> {
+    run_git_command(worktree_path, &["add", "-A"]).await?;
+    let pending = run_git_command(worktree_path, &["status", "--porcelain"]).await?;
+    if !pending.is_empty() {
+        run_git_command(worktree_path, &["commit", "-m", message]).await?;
+    }
+    Ok(())
+}
+
+/// Commits whatever the session left in its worktree and merges the session
+/// branch into the source checkout. On conflicts the merge is aborted and the
+/// worktree is kept so nothing is lost.
+pub(crate) async fn merge_session_worktree_core(
+    registry: &SessionRegistry,
+    data_dir: &Path,
+    session_id: String,
+) -> Result<SessionWorktreeMergeResult, String> {
+    ensure_session_stopped(registry, &session_id).await?;
+    let worktree = read_session_worktree(data_dir, &session_id)?;
+    let worktree_path = PathBuf::from(&worktree.worktree_path);
+    let source = PathBuf::from(&worktree.source_path);
+
+    commit_pending_changes(&worktree_path, &format!("Codex session {session_id}")).await?;
+
+    let range = format!("{}..{}", worktree.base_commit, worktree.branch);
+    let ahead = run_git_command(&source, &["rev-list", "--count", &range]).await?;
+    if ahead.trim() == "0" {
+        remove_session_worktree(data_dir, &worktree).await?;
+        return Ok(SessionWorktreeMergeResult {
+            merged: false,
+            commit: None,
+        });
+    }
+
+    if let Err(err) = run_git_command(
+        &source,
+        &["merge", "--no-ff", "--no-edit", &worktree.branch],
+    )
+    .await
+    {
+        let _ = run_git_command(&source, &["merge", "--abort"]).await;
+        return Err(format!(
+            "Merge failed; the session worktree was kept: {err}"
+        ));
+    }
+    let commit = run_git_command(&source, &["rev-parse", "HEAD"]).await?;
+    remove_session_worktree(data_dir, &worktree).await?;
+    Ok(SessionWorktreeMergeResult {
+        merged: true,
+        commit: Some(commit),
+    })
+}
+
+pub(crate) async fn discard_session_worktree_core(
+    registry: &SessionRegistry,
+    data_dir: &Path,
+    session_id: String,
+) -> Result<(), String> {
+    ensure_session_stopped(registry, &session_id).await?;
+    let worktree = read_session_worktree(data_dir, &session_id)?;
+    remove_session_worktree(data_dir, &worktree).await
+}
+
+/// `gh pr create` prints the new pull request's URL as its last line.
+fn gh_pull_request_url(stdout: &str) -> Option<String> {
+    stdout
+        .lines()
+        .rev()
+        .map(str::trim)
+        .find(|line| line.starts_with("https://"))
+        .map(str::to_string)
+}
+
+/// Opens the pull request with `gh`; `None` when `gh` is not installed.
+async fn create_pr_with_gh(
+    worktree_path: &PathBuf,
+    head: &str,
+    base: &str,
+    title: &str,
+    body: &str,
+) -> Result<Option<String>, String> {
+    let args = [
+        "pr", "create", "--head", head, "--base", base, "--title", title, "--body", body,
+    ];
+    let Some(stdout) = run_gh(worktree_path, &args).await? else {
+        return Ok(None);
+    };
+    gh_pull_request_url(&stdout)
+        .map(Some)
+        .ok_or_else(|| "gh did not report the pull request URL".to_string())
+}
+
+async fn create_pr_with_api(
+    source: &PathBuf,
+    head: &str,
+    base: &str,
+    title: &str,
+    body: &str,
+) -> Result<String, String> {
+    let repo = origin_github_repo(source).await?;
+    let payload = json!({ "title": title, "body": body, "head": head, "base": base });
+    let response =
+        api_request(Method::POST, &format!("repos/{repo}/pulls"), Some(&payload)).await?;
+    response["html_url"]
+        .as_str()
+        .map(str::to_string)
+        .ok_or_else(|| "GitHub did not return the pull request URL".to_string())
+}
+
+/// Commits whatever the session left in its worktree under `title`, pushes
+/// the session branch to `origin` and opens a pull request into `base`
+/// (the branch the source checkout is on when omitted). Uses `gh`, or the
+/// GitHub API with the keychain token when `gh` is not installed. The
+/// worktree is kept so the pull request can still be updated.
+pub(crate) async fn create_pr_from_session_core(
+    registry: &SessionRegistry,
+    data_dir: &Path,
+    session_id: String,
+    title: String,
+    body: Option<String>,
+    base: Option<String>,
+) -> Result<SessionPullRequest, String> {
+    ensure_session_stopped(registry, &session_id).await?;
+    let title = title.trim();
+    if title.is_empty() {
+        return Err("A pull request title is required".to_string());
+    }
+    let worktree = read_session_worktree(data_dir, &session_id)?;
+    let worktree_path = PathBuf::from(&worktree.worktree_path);
+    let source = PathBuf::from(&worktree.source_path);
+
+    commit_pending_changes(&worktree_path, title).await?;
+    let range = format!("{}..{}", worktree.base_commit, worktree.branch);
+    let ahead = run_git_command(&source, &["rev-list", "--count", &range]).await?;
+    if ahead.trim() == "0" {
+        return Err("The session made no changes to open a pull request for".to_string());
+    }
+
+    let base = match base
+        .map(|base| base.trim().to_string())
+        .filter(|base| !base.is_empty())
+    {
+        Some(base) => base,
+        None => run_git_command(&source, &["rev-parse", "--abbrev-ref", "HEAD"]).await?,
+    };
+    if base == "HEAD" {
+        return Err("The source checkout has no current branch; pass a base branch".to_string());
+    }
+
+    run_git_command(&worktree_path, &["push", "-u", "origin", &worktree.branch]).await?;
+    let commit = run_git_command(&worktree_path, &["rev-parse", "HEAD"]).await?;
+    let body = body.unwrap_or_default();
+    let url = match create_pr_with_gh(&worktree_path, &worktree.branch, &base, title, &body).await?
+    {
+        Some(url) => url,
+        None => create_pr_with_api(&source, &worktree.branch, &base, title, &body).await?,
+    };
+    Ok(SessionPullRequest {
+        url,
+        branch: worktree.branch,
+        base,
+        commit,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn git(dir: &Path, args: &[&str]) -> bool {
+        std::process::Command::new("git")
+            .args(args)
+            .current_dir(dir)
+            .output()
+            .map(|output| output.status.success())
+            .unwrap_or(false)
+    }
+
+    fn init_repo(root: &Path) -> Option<PathBuf> {
+        let repo = root.join("repo");
+        std::fs::create_dir_all(repo.join("app")).ok()?;
+        std::fs::write(repo.join("app").join("main.txt"), "v1\n").ok()?;
+        let ok = git(&repo, &["init", "-q"])
+            && git(&repo, &["config", "user.name", "Test"])
+            && git(&repo, &["config", "user.email", "test@example.com"])
+            && git(&repo, &["add", "-A"])
+            && git(&repo, &["commit", "-q", "-m", "init"]);
+        ok.then_some(repo)
+    }
+
+    #[test]
+    fn merge_and_discard_session_worktrees() {
+        let root =
+            std::env::temp_dir().join(format!("codex-monitor-session-wt-{}", uuid::Uuid::new_v4()));
+        let Some(repo) = init_repo(&root) else {
+            let _ = std::fs::remove_dir_all(&root);
+            return;
+        };
+        let data_dir = root.join("data");
+        let registry = SessionRegistry::default();
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .expect("runtime");
+        runtime.block_on(async {
+            let project = repo.join("app").to_string_lossy().to_string();
+            let (worktree, session_dir) = create_session_worktree(&data_dir, &project, "s-merge")
+                .await
+                .expect("create worktree");
+            assert!(session_dir.ends_with("app"));
+            std::fs::write(Path::new(&session_dir).join("main.txt"), "v2\n").expect("edit");
+            assert_eq!(
+                std::fs::read_to_string(repo.join("app").join("main.txt")).expect("read"),
+                "v1\n"
+            );
+
+            let result = merge_session_worktree_core(&registry, &data_dir, "s-merge".to_string())
+                .await
+                .expect("merge");
+            assert!(result.merged);
+            assert_eq!(
+                std::fs::read_to_string(repo.join("app").join("main.txt")).expect("read"),
+                "v2\n"
+            );
+            assert!(!Path::new(&worktree.worktree_path).exists());
+
+            let (discarded, session_dir) =
+                create_session_worktree(&data_dir, &project, "s-discard")
+                    .await
+                    .expect("create worktree");
+            std::fs::write(Path::new(&session_dir).join("main.txt"), "v3\n").expect("edit");
+            discard_session_worktree_core(&registry, &data_dir, "s-discard".to_string())
+                .await
+                .expect("discard");
+            assert!(!Path::new(&discarded.worktree_path).exists());
+            assert_eq!(
+                std::fs::read_to_string(repo.join("app").join("main.txt")).expect("read"),
+                "v2\n"
+            );
+            assert!(
+                discard_session_worktree_core(&registry, &data_dir, "s-discard".to_string())
+                    .await
+                    .is_err()
+            );
+        });
+        let _ = std::fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn gh_reports_the_pull_request_url() {
+        assert_eq!(
+            gh_pull_request_url(
+                "Creating pull request for codex-session/s1 into main\n\nhttps://github.com/o/r/pull/7\n"
+            )
+            .as_deref(),
+            Some("https://github.com/o/r/pull/7")
+        );
+        assert_eq!(gh_pull_request_url("nothing here"), None);
+    }
+}
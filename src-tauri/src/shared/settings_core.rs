@@ -1,10 +1,19 @@
 use std::path::PathBuf;
+use std::time::{SystemTime, UNIX_EPOCH};
 
-use tokio::sync::Mutex;
+use tokio::sync::RwLock;
 
 use crate::codex::config as codex_config;
+use crate::codex::home::{
+    normalize_codex_home, resolve_codex_home_with_source, set_codex_home_override,
+    validate_codex_home,
+};
+use crate::shared::monitor_error::MonitorError;
+use crate::shared::process_env_core::set_codex_process_env;
+use crate::shared::redaction_core::{set_redaction, Redactor};
+use crate::shared::settings_sync_core::stamp_settings_update;
 use crate::storage::write_settings;
-use crate::types::AppSettings;
+use crate::types::{AppSettings, CodexHomeInfo};
 use crate::utils::normalize_windows_namespace_path;
 
 fn normalize_personality(value: &str) -> Option<&'static str> {
@@ -15,36 +24,52 @@ fn normalize_personality(value: &str) -> Option<&'static str> {
     }
 }
 
-pub(crate) async fn get_app_settings_core(app_settings: &Mutex<AppSettings>) -> AppSettings {
-    let mut settings = app_settings.lock().await.clone();
-    if let Ok(Some(collaboration_modes_enabled)) = codex_config::read_collaboration_modes_enabled()
-    {
+fn now_ms() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_millis() as i64)
+        .unwrap_or(0)
+}
+
+pub(crate) async fn get_app_settings_core(app_settings: &RwLock<AppSettings>) -> AppSettings {
+    let mut settings = app_settings.read().await.clone();
+    let Ok(flags) = codex_config::read_config_flags() else {
+        return settings;
+    };
+    if let Some(collaboration_modes_enabled) = flags.collaboration_modes_enabled {
         settings.collaboration_modes_enabled = collaboration_modes_enabled;
     }
-    if let Ok(Some(steer_enabled)) = codex_config::read_steer_enabled() {
+    if let Some(steer_enabled) = flags.steer_enabled {
         settings.steer_enabled = steer_enabled;
     }
-    if let Ok(Some(unified_exec_enabled)) = codex_config::read_unified_exec_enabled() {
+    if let Some(unified_exec_enabled) = flags.unified_exec_enabled {
         settings.unified_exec_enabled = unified_exec_enabled;
     }
-    if let Ok(Some(apps_enabled)) = codex_config::read_apps_enabled() {
+    if let Some(apps_enabled) = flags.apps_enabled {
         settings.experimental_apps_enabled = apps_enabled;
     }
-    if let Ok(personality) = codex_config::read_personality() {
-        settings.personality = personality
-            .as_deref()
-            .and_then(normalize_personality)
-            .unwrap_or("friendly")
-            .to_string();
-    }
+    settings.personality = flags
+        .personality
+        .as_deref()
+        .and_then(normalize_personality)
+        .unwrap_or("friendly")
+        .to_string();
     settings
 }
 
 pub(crate) async fn update_app_settings_core(
     mut settings: AppSettings,
-    app_settings: &Mutex<AppSettings>,
+    app_settings: &RwLock<AppSettings>,
     settings_path: &PathBuf,
-) -> Result<AppSettings, String> {
+) -> Result<AppSettings, MonitorError> {
+    let previous = app_settings.read().await.clone();
+    // The data directory only moves through `migrate_data_dir`, which has to
+    // copy the data along with it.
+    settings.data_dir = previous.data_dir.clone();
+    // Likewise the Codex home override, through `set_codex_home_override_core`.
+    settings.codex_home_override = previous.codex_home_override.clone();
+    Redactor::new(&settings.redaction).map_err(MonitorError::Config)?;
+    stamp_settings_update(&previous, &mut settings, now_ms());
     settings.global_worktrees_folder = settings
         .global_worktrees_folder
         .map(|path| normalize_windows_namespace_path(&path));
@@ -53,18 +78,56 @@ pub(crate) async fn update_app_settings_core(
     let _ = codex_config::write_unified_exec_enabled(settings.unified_exec_enabled);
     let _ = codex_config::write_apps_enabled(settings.experimental_apps_enabled);
     let _ = codex_config::write_personality(settings.personality.as_str());
-    write_settings(settings_path, &settings)?;
-    let mut current = app_settings.lock().await;
+    write_settings(settings_path, &settings).map_err(MonitorError::Config)?;
+    set_codex_process_env(&settings.codex_env);
+    set_redaction(&settings.redaction);
+    let mut current = app_settings.write().await;
     *current = settings.clone();
     Ok(settings)
 }
 
-pub(crate) fn get_codex_config_path_core() -> Result<String, String> {
+pub(crate) fn get_codex_config_path_core() -> Result<String, MonitorError> {
+    let unresolved = || MonitorError::Config("Unable to resolve CODEX_HOME".to_string());
     codex_config::config_toml_path()
-        .ok_or_else(|| "Unable to resolve CODEX_HOME".to_string())
+        .ok_or_else(unresolved)
         .and_then(|path| {
             path.to_str()
                 .map(|value| value.to_string())
-                .ok_or_else(|| "Unable to resolve CODEX_HOME".to_string())
+                .ok_or_else(unresolved)
         })
 }
+
+pub(crate) fn get_codex_home_core() -> CodexHomeInfo {
+    let (path, source) = resolve_codex_home_with_source();
+    CodexHomeInfo {
+        exists: path.as_ref().is_some_and(|path| path.is_dir()),
+        path: path.map(|path| path.to_string_lossy().to_string()),
+        source,
+    }
+}
+
+/// Points config, AGENTS.md and Codex sessions at `path`, or back at
+/// `CODEX_HOME` or `~/.codex` when it is empty. The path must hold a Codex
+/// layout or be able to host one. Sessions already running keep the home
+/// they started with.
+pub(crate) async fn set_codex_home_override_core(
+    path: Option<String>,
+    app_settings: &RwLock<AppSettings>,
+    settings_path: &PathBuf,
+) -> Result<CodexHomeInfo, MonitorError> {
+    let path = path
+        .map(|path| path.trim().to_string())
+        .filter(|path| !path.is_empty());
+    if let Some(path) = path.as_deref() {
+        let resolved = normalize_codex_home(path)
+            .ok_or_else(|| MonitorError::Config(format!("Invalid Codex home `{path}`")))?;
+        validate_codex_home(&resolved).map_err(MonitorError::Config)?;
+    }
+    let mut settings = app_settings.write().await;
+    let mut updated = settings.clone();
+    updated.codex_home_override = path;
+    write_settings(settings_path, &updated).map_err(MonitorError::Config)?;
+    set_codex_home_override(updated.codex_home_override.as_deref());
+    *settings = updated;
+    Ok(get_codex_home_core())
+}
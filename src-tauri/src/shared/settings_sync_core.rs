@@ -0,0 +1,298 @@
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+use crate::shared::session_templates_core::{list_session_templates_core, session_templates_path};
+use crate::storage::{read_settings, read_workspaces, write_session_templates};
+use crate::types::{AppSettings, SessionTemplate, WorkspaceEntry, WorkspaceKind};
+
+#[allow(dead_code)]
+pub(crate) const SETTINGS_SYNC_SNAPSHOT_METHOD: &str = "settings_sync_snapshot";
+
+/// Settings that follow the user between machines: appearance, shortcuts,
+/// notification and composer preferences. Anything tied to one machine, its
+/// paths, its Codex config or its credentials is left out.
+const SYNCED_SETTINGS: &[&str] = &[
+    "defaultAccessMode",
+    "reviewDeliveryMode",
+    "composerModelShortcut",
+    "composerAccessShortcut",
+    "composerReasoningShortcut",
+    "composerCollaborationShortcut",
+    "interruptShortcut",
+    "newAgentShortcut",
+    "newWorktreeAgentShortcut",
+    "newCloneAgentShortcut",
+    "archiveThreadShortcut",
+    "toggleProjectsSidebarShortcut",
+    "toggleGitSidebarShortcut",
+    "branchSwitcherShortcut",
+    "toggleDebugPanelShortcut",
+    "toggleTerminalShortcut",
+    "cycleAgentNextShortcut",
+    "cycleAgentPrevShortcut",
+    "cycleWorkspaceNextShortcut",
+    "cycleWorkspacePrevShortcut",
+    "uiScale",
+    "theme",
+    "usageShowRemaining",
+    "showMessageFilePath",
+    "chatHistoryScrollbackItems",
+    "threadTitleAutogenerationEnabled",
+    "automaticAppUpdateChecksEnabled",
+    "uiFontFamily",
+    "codeFontFamily",
+    "codeFontSize",
+    "notificationSoundsEnabled",
+    "systemNotificationsEnabled",
+    "subagentSystemNotificationsEnabled",
+    "splitChatDiffView",
+    "preloadGitDiffs",
+    "gitDiffIgnoreWhitespaceChanges",
+    "commitMessagePrompt",
+    "commitMessageModelId",
+    "followUpMessageBehavior",
+    "composerFollowUpHintEnabled",
+    "pauseQueuedMessagesWhenResponseRequired",
+    "composerEditorPreset",
+    "composerFenceExpandOnSpace",
+    "composerFenceExpandOnEnter",
+    "composerFenceLanguageTags",
+    "composerFenceWrapSelection",
+    "composerFenceAutoWrapPasteMultiline",
+    "composerFenceAutoWrapPasteCodeLike",
+    "composerListContinuation",
+    "composerCodeBlockCopyUseModifier",
+];
+
+/// What one instance shares with another through `settings_sync_snapshot`.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct SettingsSyncSnapshot {
+    pub(crate) settings: Map<String, Value>,
+    pub(crate) settings_updated_at_ms: i64,
+    pub(crate) templates: Vec<SessionTemplate>,
+    /// Paths of the registered projects; worktrees are not shared.
+    pub(crate) projects: Vec<String>,
+}
+
+pub(crate) fn synced_settings(settings: &AppSettings) -> Map<String, Value> {
+    let Ok(Value::Object(all)) = serde_json::to_value(settings) else {
+        return Map::new();
+    };
+    all.into_iter()
+        .filter(|(key, _)| SYNCED_SETTINGS.contains(&key.as_str()))
+        .collect()
+}
+
+/// Stamps `updated` with `now_ms` when it changes a shared setting. A
+/// newer stamp than `previous` is kept, since only a sync carries one.
+pub(crate) fn stamp_settings_update(
+    previous: &AppSettings,
+    updated: &mut AppSettings,
+    now_ms: i64,
+) {
+    if updated.settings_updated_at_ms > previous.settings_updated_at_ms {
+        return;
+    }
+    updated.settings_updated_at_ms = if synced_settings(previous) == synced_settings(updated) {
+        previous.settings_updated_at_ms
+    } else {
+        now_ms
+    };
+}
+
+#[allow(dead_code)]
+pub(crate) fn settings_sync_snapshot_core(data_dir: &Path) -> Result<SettingsSyncSnapshot, String> {
+    let settings = read_settings(&data_dir.join("settings.json"))?;
+    let workspaces = read_workspaces(&data_dir.join("workspaces.json"))?;
+    let mut projects: Vec<String> = workspaces
+        .into_values()
+        .filter(|entry| matches!(entry.kind, WorkspaceKind::Main))
+        .map(|entry| entry.path)
+        .collect();
+    projects.sort();
+    Ok(SettingsSyncSnapshot {
+        settings: synced_settings(&settings),
+        settings_updated_at_ms: settings.settings_updated_at_ms,
+        templates: list_session_templates_core(data_dir)?,
+        projects,
+    })
+}
+
+/// `local` with the snapshot's shared settings, or `None` when `local`
+/// changed as recently or later. Shared settings the other side does not
+/// know about keep their local values.
+#[allow(dead_code)]
+pub(crate) fn merge_synced_settings(
+    local: &AppSettings,
+    snapshot: &SettingsSyncSnapshot,
+) -> Result<Option<AppSettings>, String> {
+    if snapshot.settings_updated_at_ms <= local.settings_updated_at_ms {
+        return Ok(None);
+    }
+    let mut value = serde_json::to_value(local).map_err(|err| err.to_string())?;
+    if let Value::Object(map) = &mut value {
+        for (key, synced) in &snapshot.settings {
+            if SYNCED_SETTINGS.contains(&key.as_str()) {
+                map.insert(key.clone(), synced.clone());
+            }
+        }
+    }
+    let mut merged: AppSettings = serde_json::from_value(value)
+        .map_err(|err| format!("Invalid settings from the sync source: {err}"))?;
+    merged.settings_updated_at_ms = snapshot.settings_updated_at_ms;
+    Ok(Some(merged))
+}
+
+/// Takes each template that is missing locally or was changed more recently
+/// on the other side, returning how many were taken. Deleting a template on
+/// one side does not delete it on the other.
+fn merge_templates(local: &mut Vec<SessionTemplate>, remote: Vec<SessionTemplate>) -> usize {
+    let mut taken = 0;
+    for template in remote {
+        match local.iter_mut().find(|existing| existing.id == template.id) {
+            Some(existing) if existing.updated_at_ms >= template.updated_at_ms => {}
+            Some(existing) => {
+                *existing = template;
+                taken += 1;
+            }
+            None => {
+                local.push(template);
+                taken += 1;
+            }
+        }
+    }
+    taken
+}
+
+#[allow(dead_code)]
+pub(crate) fn apply_synced_templates(
+    data_dir: &Path,
+    remote: Vec<SessionTemplate>,
+) -> Result<usize, String> {
+    let mut templates = list_session_templates_core(data_dir)?;
+    let taken = merge_templates(&mut templates, remote);
+    if taken > 0 {
+        write_session_templates(&session_templates_path(data_dir), &templates)?;
+    }
+    Ok(taken)
+}
+
+/// Project paths from the other side that are folders here and not yet
+/// registered.
+#[allow(dead_code)]
+pub(crate) fn projects_to_add(
+    local: &HashMap<String, WorkspaceEntry>,
+    remote: &[String],
+) -> Vec<String> {
+    let registered: HashSet<&str> = local.values().map(|entry| entry.path.as_str()).collect();
+    remote
+        .iter()
+        .filter(|path| !registered.contains(path.as_str()) && Path::new(path).is_dir())
+        .cloned()
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn template(id: &str, prompt: &str, updated_at_ms: i64) -> SessionTemplate {
+        SessionTemplate {
+            id: id.to_string(),
+            name: id.to_string(),
+            prompt: prompt.to_string(),
+            model: None,
+            approval_mode: None,
+            working_dir: None,
+            env: Default::default(),
+            created_at_ms: 0,
+            updated_at_ms,
+        }
+    }
+
+    #[test]
+    fn newer_shared_settings_win_and_private_ones_stay() {
+        let remote = AppSettings {
+            theme: "dark".to_string(),
+            codex_bin: Some("/remote/codex".to_string()),
+            remote_backend_token: Some("secret".to_string()),
+            settings_updated_at_ms: 20,
+            ..AppSettings::default()
+        };
+        let local = AppSettings {
+            theme: "light".to_string(),
+            settings_updated_at_ms: 10,
+            ..AppSettings::default()
+        };
+        let snapshot = SettingsSyncSnapshot {
+            settings: synced_settings(&remote),
+            settings_updated_at_ms: remote.settings_updated_at_ms,
+            templates: Vec::new(),
+            projects: Vec::new(),
+        };
+        assert!(!snapshot.settings.contains_key("codexBin"));
+        assert!(!snapshot.settings.contains_key("remoteBackendToken"));
+
+        let merged = merge_synced_settings(&local, &snapshot)
+            .expect("merge")
+            .expect("remote is newer");
+        assert_eq!(merged.theme, "dark");
+        assert_eq!(merged.codex_bin, None);
+        assert_eq!(merged.remote_backend_token, None);
+        assert_eq!(merged.settings_updated_at_ms, 20);
+
+        let stale = SettingsSyncSnapshot {
+            settings_updated_at_ms: 10,
+            ..snapshot
+        };
+        assert!(merge_synced_settings(&local, &stale)
+            .expect("merge")
+            .is_none());
+    }
+
+    #[test]
+    fn only_shared_changes_move_the_stamp() {
+        let previous = AppSettings {
+            settings_updated_at_ms: 5,
+            ..AppSettings::default()
+        };
+        let mut private_change = AppSettings {
+            codex_bin: Some("/usr/local/bin/codex".to_string()),
+            ..previous.clone()
+        };
+        stamp_settings_update(&previous, &mut private_change, 50);
+        assert_eq!(private_change.settings_updated_at_ms, 5);
+
+        let mut shared_change = AppSettings {
+            theme: "dim".to_string(),
+            ..previous.clone()
+        };
+        stamp_settings_update(&previous, &mut shared_change, 50);
+        assert_eq!(shared_change.settings_updated_at_ms, 50);
+
+        let mut synced = AppSettings {
+            theme: "dim".to_string(),
+            settings_updated_at_ms: 30,
+            ..previous.clone()
+        };
+        stamp_settings_update(&previous, &mut synced, 50);
+        assert_eq!(synced.settings_updated_at_ms, 30);
+    }
+
+    #[test]
+    fn templates_merge_by_last_writer() {
+        let mut local = vec![template("a", "local a", 10), template("b", "local b", 30)];
+        let remote = vec![
+            template("a", "remote a", 20),
+            template("b", "remote b", 20),
+            template("c", "remote c", 5),
+        ];
+        assert_eq!(merge_templates(&mut local, remote), 2);
+        let prompts: Vec<&str> = local.iter().map(|t| t.prompt.as_str()).collect();
+        assert_eq!(prompts, vec!["remote a", "local b", "remote c"]);
+    }
+}
@@ -0,0 +1,198 @@
+use std::fs;
+use std::path::Path;
+
+use crate::types::{StorageCleanReport, StorageHealth, StorageTarget, StorageUsage};
+
+const LOG_FILES: &[&str] = &["daemon-service.log", "remote-command-audit.jsonl"];
+const CACHE_DIRS: &[&str] = &["session-search-index"];
+const ARCHIVES_DIR: &str = "session-archives";
+const BYTES_PER_MB: u64 = 1024 * 1024;
+
+/// Space available to unprivileged users on the volume holding `path`.
+#[cfg(unix)]
+pub(crate) fn free_disk_bytes(path: &Path) -> Option<u64> {
+    use std::os::unix::ffi::OsStrExt;
+
+    let path = std::ffi::CString::new(path.as_os_str().as_bytes()).ok()?;
+    let mut stats: libc::statvfs = unsafe { std::mem::zeroed() };
+    if unsafe { libc::statvfs(path.as_ptr(), &mut stats) } != 0 {
+        return None;
+    }
+    Some(stats.f_bavail as u64 * stats.f_frsize as u64)
+}
+
+#[cfg(not(unix))]
+pub(crate) fn free_disk_bytes(_path: &Path) -> Option<u64> {
+    None
+}
+
+fn file_size(path: &Path) -> u64 {
+    fs::symlink_metadata(path)
+        .ok()
+        .filter(|metadata| metadata.is_file())
+        .map(|metadata| metadata.len())
+        .unwrap_or(0)
+}
+
+/// Total size of the regular files under `path`, not following symlinks.
+fn dir_size(path: &Path) -> u64 {
+    let Ok(entries) = fs::read_dir(path) else {
+        return 0;
+    };
+    entries
+        .flatten()
+        .map(|entry| match entry.file_type() {
+            Ok(file_type) if file_type.is_dir() => dir_size(&entry.path()),
+            Ok(file_type) if file_type.is_file() => {
+                entry.metadata().map(|metadata| metadata.len()).unwrap_or(0)
+            }
+            _ => 0,
+        })
+        .sum()
+}
+
+pub(crate) fn storage_usage(data_dir: &Path) -> StorageUsage {
+    StorageUsage {
+        logs_bytes: LOG_FILES
+            .iter()
+            .map(|name| file_size(&data_dir.join(name)))
+            .sum(),
+        archives_bytes: dir_size(&data_dir.join(ARCHIVES_DIR)),
+        caches_bytes: CACHE_DIRS
+            .iter()
+            .map(|name| dir_size(&data_dir.join(name)))
+            .sum(),
+        total_bytes: dir_size(data_dir),
+    }
+}
+
+/// Whether `free_bytes` is under a threshold of `threshold_mb` MiB. An
+/// unreadable free space or a zero threshold never counts as low.
+pub(crate) fn is_low_space(free_bytes: Option<u64>, threshold_mb: u32) -> bool {
+    match free_bytes {
+        Some(free) if threshold_mb > 0 => free < u64::from(threshold_mb) * BYTES_PER_MB,
+        _ => false,
+    }
+}
+
+/// Walks the data directory, so callers on an async runtime should run it
+/// on a blocking thread.
+#[allow(dead_code)]
+pub(crate) fn storage_health_core(data_dir: &Path, threshold_mb: u32) -> StorageHealth {
+    let free_bytes = free_disk_bytes(data_dir);
+    StorageHealth {
+        data_dir: data_dir.display().to_string(),
+        free_bytes,
+        low_space: is_low_space(free_bytes, threshold_mb),
+        usage: storage_usage(data_dir),
+    }
+}
+
+/// Empties the logs in place, so a daemon still appending to one keeps
+/// working, and deletes caches, which are rebuilt on next use. Archives are
+/// never touched. Files that do not exist are skipped.
+#[allow(dead_code)]
+pub(crate) fn clean_storage_core(
+    data_dir: &Path,
+    targets: &[StorageTarget],
+) -> Result<StorageCleanReport, String> {
+    if targets.is_empty() {
+        return Err("Choose at least one kind of storage to clean.".to_string());
+    }
+    let mut report = StorageCleanReport {
+        freed_bytes: 0,
+        cleaned: Vec::new(),
+    };
+    for target in targets {
+        let names = match target {
+            StorageTarget::Logs => LOG_FILES,
+            StorageTarget::Caches => CACHE_DIRS,
+        };
+        for name in names {
+            let path = data_dir.join(name);
+            let (size, result) = match target {
+                StorageTarget::Logs if path.is_file() => {
+                    (file_size(&path), fs::File::create(&path).map(|_| ()))
+                }
+                StorageTarget::Caches if path.is_dir() => {
+                    (dir_size(&path), fs::remove_dir_all(&path))
+                }
+                _ => continue,
+            };
+            result.map_err(|err| format!("Failed to clean {}: {err}", path.display()))?;
+            report.freed_bytes += size;
+            report.cleaned.push((*name).to_string());
+        }
+    }
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    fn temp_dir() -> PathBuf {
+        let dir = std::env::temp_dir().join(format!(
+            "codex-monitor-storage-health-{}",
+            uuid::Uuid::new_v4()
+        ));
+        fs::create_dir_all(dir.join(ARCHIVES_DIR)).expect("create temp dir");
+        dir
+    }
+
+    #[test]
+    fn usage_splits_logs_archives_and_caches() {
+        let dir = temp_dir();
+        fs::write(dir.join("daemon-service.log"), [b'l'; 300]).expect("log");
+        fs::create_dir_all(dir.join("session-search-index")).expect("cache dir");
+        fs::write(dir.join("session-search-index/a.json"), [b'c'; 50]).expect("cache");
+        fs::write(dir.join(ARCHIVES_DIR).join("a.tar.gz"), [b'a'; 20]).expect("archive");
+        fs::write(dir.join("settings.json"), "{}").expect("settings");
+
+        let health = storage_health_core(&dir, 0);
+        assert_eq!(
+            health.usage,
+            StorageUsage {
+                logs_bytes: 300,
+                archives_bytes: 20,
+                caches_bytes: 50,
+                total_bytes: 372,
+            }
+        );
+        assert!(!health.low_space);
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn cleaning_empties_logs_deletes_caches_and_keeps_archives() {
+        let dir = temp_dir();
+        fs::write(dir.join("remote-command-audit.jsonl"), [b'l'; 100]).expect("log");
+        fs::create_dir_all(dir.join("session-search-index")).expect("cache dir");
+        fs::write(dir.join("session-search-index/a.json"), [b'c'; 40]).expect("cache");
+        fs::write(dir.join(ARCHIVES_DIR).join("a.tar.gz"), [b'a'; 20]).expect("archive");
+
+        let report =
+            clean_storage_core(&dir, &[StorageTarget::Logs, StorageTarget::Caches]).expect("clean");
+        assert_eq!(report.freed_bytes, 140);
+        assert_eq!(
+            report.cleaned,
+            vec!["remote-command-audit.jsonl", "session-search-index"]
+        );
+        assert_eq!(file_size(&dir.join("remote-command-audit.jsonl")), 0);
+        assert!(!dir.join("session-search-index").exists());
+        assert_eq!(storage_usage(&dir).archives_bytes, 20);
+        assert!(clean_storage_core(&dir, &[]).is_err());
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn low_space_needs_a_reading_and_a_threshold() {
+        assert!(is_low_space(Some(BYTES_PER_MB - 1), 1));
+        assert!(!is_low_space(Some(BYTES_PER_MB), 1));
+        assert!(!is_low_space(Some(0), 0));
+        assert!(!is_low_space(None, 1024));
+    }
+}
@@ -0,0 +1,287 @@
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex as StdMutex;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::storage::{read_tags, write_tags};
+use crate::types::{Tag, TagAssignments, TagInput, TagStore, TagTarget, WorkspaceEntry};
+
+const TAGS_FILENAME: &str = "tags.json";
+
+// Guards read-modify-write cycles of the tags file.
+static TAGS_LOCK: StdMutex<()> = StdMutex::new(());
+
+fn tags_path(data_dir: &Path) -> PathBuf {
+    data_dir.join(TAGS_FILENAME)
+}
+
+fn now_ms() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_millis() as i64)
+        .unwrap_or(0)
+}
+
+fn normalize_optional(value: Option<String>) -> Option<String> {
+    value
+        .map(|value| value.trim().to_string())
+        .filter(|value| !value.is_empty())
+}
+
+pub(crate) fn list_tags_core(data_dir: &Path) -> Result<Vec<Tag>, String> {
+    let mut tags = read_tags(&tags_path(data_dir))?.tags;
+    tags.sort_by_key(|tag| tag.name.to_lowercase());
+    Ok(tags)
+}
+
+pub(crate) fn get_tag_assignments_core(data_dir: &Path) -> Result<TagAssignments, String> {
+    Ok(read_tags(&tags_path(data_dir))?.assignments)
+}
+
+/// Creates a tag when `input.id` is empty, otherwise renames or recolors the
+/// existing tag with that id. Names are unique regardless of case.
+pub(crate) fn save_tag_core(data_dir: &Path, input: TagInput) -> Result<Tag, String> {
+    let name = input.name.trim().to_string();
+    if name.is_empty() {
+        return Err("Tag name is required".to_string());
+    }
+    let _guard = TAGS_LOCK.lock().map_err(|err| err.to_string())?;
+    let path = tags_path(data_dir);
+    let mut store = read_tags(&path)?;
+    let id = normalize_optional(input.id);
+    if store
+        .tags
+        .iter()
+        .any(|tag| tag.name.to_lowercase() == name.to_lowercase() && Some(&tag.id) != id.as_ref())
+    {
+        return Err(format!("A tag named `{name}` already exists"));
+    }
+    let color = normalize_optional(input.color);
+    let tag = match id {
+        Some(id) => {
+            let tag = store
+                .tags
+                .iter_mut()
+                .find(|tag| tag.id == id)
+                .ok_or_else(|| "tag not found".to_string())?;
+            tag.name = name;
+            tag.color = color;
+            tag.clone()
+        }
+        None => {
+            let tag = Tag {
+                id: uuid::Uuid::new_v4().to_string(),
+                name,
+                color,
+                created_at_ms: now_ms(),
+            };
+            store.tags.push(tag.clone());
+            tag
+        }
+    };
+    write_tags(&path, &store)?;
+    Ok(tag)
+}
+
+/// Deletes a tag and takes it off every session and project.
+pub(crate) fn delete_tag_core(data_dir: &Path, id: &str) -> Result<(), String> {
+    let _guard = TAGS_LOCK.lock().map_err(|err| err.to_string())?;
+    let path = tags_path(data_dir);
+    let mut store = read_tags(&path)?;
+    let before = store.tags.len();
+    store.tags.retain(|tag| tag.id != id);
+    if store.tags.len() == before {
+        return Err("tag not found".to_string());
+    }
+    let TagAssignments { sessions, projects } = &mut store.assignments;
+    for assigned in [sessions, projects] {
+        for tag_ids in assigned.values_mut() {
+            tag_ids.retain(|tag_id| tag_id != id);
+        }
+        assigned.retain(|_, tag_ids| !tag_ids.is_empty());
+    }
+    write_tags(&path, &store)
+}
+
+/// Replaces the tags of one session or project (by workspace id). An empty
+/// list clears them.
+pub(crate) fn set_tags_core(
+    data_dir: &Path,
+    target: TagTarget,
+    id: &str,
+    tag_ids: Vec<String>,
+) -> Result<Vec<String>, String> {
+    if id.trim().is_empty() {
+        return Err("An id to tag is required".to_string());
+    }
+    let _guard = TAGS_LOCK.lock().map_err(|err| err.to_string())?;
+    let path = tags_path(data_dir);
+    let mut store = read_tags(&path)?;
+    let mut seen = HashSet::new();
+    let mut assigned = Vec::new();
+    for tag_id in tag_ids {
+        if !store.tags.iter().any(|tag| tag.id == tag_id) {
+            return Err(format!("Unknown tag `{tag_id}`"));
+        }
+        if seen.insert(tag_id.clone()) {
+            assigned.push(tag_id);
+        }
+    }
+    let map = match target {
+        TagTarget::Session => &mut store.assignments.sessions,
+        TagTarget::Project => &mut store.assignments.projects,
+    };
+    if assigned.is_empty() {
+        map.remove(id);
+    } else {
+        map.insert(id.to_string(), assigned.clone());
+    }
+    write_tags(&path, &store)?;
+    Ok(assigned)
+}
+
+/// Decides whether a session carries every required tag, either directly
+/// or through the project its working directory belongs to.
+pub(crate) struct TagFilter {
+    required: Vec<String>,
+    sessions: BTreeMap<String, Vec<String>>,
+    projects: Vec<(PathBuf, Vec<String>)>,
+}
+
+impl TagFilter {
+    /// `None` when no tags are required.
+    pub(crate) fn load(
+        data_dir: &Path,
+        required: &[String],
+        workspaces: &HashMap<String, WorkspaceEntry>,
+    ) -> Result<Option<Self>, String> {
+        if required.is_empty() {
+            return Ok(None);
+        }
+        Ok(Some(Self::new(
+            read_tags(&tags_path(data_dir))?,
+            required,
+            workspaces,
+        )))
+    }
+
+    fn new(
+        store: TagStore,
+        required: &[String],
+        workspaces: &HashMap<String, WorkspaceEntry>,
+    ) -> Self {
+        let TagAssignments { sessions, projects } = store.assignments;
+        let projects = projects
+            .into_iter()
+            .filter_map(|(workspace_id, tag_ids)| {
+                let entry = workspaces.get(&workspace_id)?;
+                Some((PathBuf::from(&entry.path), tag_ids))
+            })
+            .collect();
+        Self {
+            required: required.to_vec(),
+            sessions,
+            projects,
+        }
+    }
+
+    pub(crate) fn matches(&self, session_id: &str, cwd: Option<&str>) -> bool {
+        let cwd = cwd.map(Path::new);
+        let mut carried: HashSet<&str> = HashSet::new();
+        if let Some(tag_ids) = self.sessions.get(session_id) {
+            carried.extend(tag_ids.iter().map(String::as_str));
+        }
+        for (project_path, tag_ids) in &self.projects {
+            if cwd.is_some_and(|cwd| cwd.starts_with(project_path)) {
+                carried.extend(tag_ids.iter().map(String::as_str));
+            }
+        }
+        self.required
+            .iter()
+            .all(|tag_id| carried.contains(tag_id.as_str()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{WorkspaceKind, WorkspaceSettings};
+
+    fn temp_dir() -> PathBuf {
+        std::env::temp_dir().join(format!("codex-monitor-tags-{}", uuid::Uuid::new_v4()))
+    }
+
+    fn input(id: Option<&str>, name: &str) -> TagInput {
+        TagInput {
+            id: id.map(str::to_string),
+            name: name.to_string(),
+            color: None,
+        }
+    }
+
+    #[test]
+    fn tags_are_saved_assigned_and_deleted() {
+        let data_dir = temp_dir();
+        let bug = save_tag_core(&data_dir, input(None, "bug-fix")).expect("create");
+        let exploration = save_tag_core(&data_dir, input(None, "exploration")).expect("create");
+        assert!(save_tag_core(&data_dir, input(None, "Bug-Fix")).is_err());
+        let renamed = save_tag_core(&data_dir, input(Some(&bug.id), "Bug-Fix")).expect("rename");
+        assert_eq!(renamed.created_at_ms, bug.created_at_ms);
+
+        let assigned = set_tags_core(
+            &data_dir,
+            TagTarget::Session,
+            "s-1",
+            vec![bug.id.clone(), bug.id.clone(), exploration.id.clone()],
+        )
+        .expect("assign");
+        assert_eq!(assigned, vec![bug.id.clone(), exploration.id.clone()]);
+        assert!(set_tags_core(&data_dir, TagTarget::Project, "ws-1", vec!["nope".into()]).is_err());
+
+        delete_tag_core(&data_dir, &bug.id).expect("delete");
+        let assignments = get_tag_assignments_core(&data_dir).expect("assignments");
+        assert_eq!(assignments.sessions["s-1"], vec![exploration.id.clone()]);
+        let names: Vec<String> = list_tags_core(&data_dir)
+            .expect("list")
+            .into_iter()
+            .map(|tag| tag.name)
+            .collect();
+        assert_eq!(names, vec!["exploration"]);
+
+        set_tags_core(&data_dir, TagTarget::Session, "s-1", Vec::new()).expect("clear");
+        assert!(get_tag_assignments_core(&data_dir)
+            .expect("assignments")
+            .sessions
+            .is_empty());
+        let _ = std::fs::remove_dir_all(&data_dir);
+    }
+
+    #[test]
+    fn sessions_inherit_their_project_tags() {
+        let mut store = TagStore::default();
+        store
+            .assignments
+            .sessions
+            .insert("s-1".to_string(), vec!["bug".to_string()]);
+        store
+            .assignments
+            .projects
+            .insert("ws-1".to_string(), vec!["api".to_string()]);
+        let workspaces = HashMap::from([(
+            "ws-1".to_string(),
+            WorkspaceEntry {
+                id: "ws-1".to_string(),
+                name: "api".to_string(),
+                path: "/src/api".to_string(),
+                kind: WorkspaceKind::Main,
+                parent_id: None,
+                worktree: None,
+                settings: WorkspaceSettings::default(),
+            },
+        )]);
+        let filter = TagFilter::new(store, &["bug".to_string(), "api".to_string()], &workspaces);
+        assert!(filter.matches("s-1", Some("/src/api/server")));
+        assert!(!filter.matches("s-1", Some("/src/web")));
+        assert!(!filter.matches("s-2", Some("/src/api")));
+    }
+}
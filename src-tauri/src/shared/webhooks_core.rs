@@ -0,0 +1,499 @@
+use std::fs;
+use std::path::Path;
+use std::time::Duration;
+
+use ring::hmac;
+use serde_json::{json, Value};
+
+use crate::shared::pagination_core::paginate;
+use crate::types::{
+    NotificationIntegration, NotificationIntegrationKind, Page, PageResult, WebhookDelivery,
+    WebhookEndpoint, WebhookEvent,
+};
+
+const DELIVERY_LOG_FILE: &str = "webhook-deliveries.jsonl";
+/// Older deliveries are dropped from the log beyond this many.
+const MAX_LOGGED_DELIVERIES: usize = 500;
+#[allow(dead_code)]
+pub(crate) const MAX_DELIVERY_ATTEMPTS: u32 = 4;
+const FIRST_RETRY_DELAY: Duration = Duration::from_secs(2);
+#[allow(dead_code)]
+pub(crate) const SIGNATURE_HEADER: &str = "X-CodexMonitor-Signature";
+#[allow(dead_code)]
+pub(crate) const EVENT_HEADER: &str = "X-CodexMonitor-Event";
+/// Discord rejects longer messages.
+const DISCORD_MAX_CONTENT_CHARS: usize = 2000;
+
+pub(crate) fn event_name(event: WebhookEvent) -> &'static str {
+    match event {
+        WebhookEvent::SessionCompleted => "session_completed",
+        WebhookEvent::ApprovalNeeded => "approval_needed",
+        WebhookEvent::DaemonDown => "daemon_down",
+        WebhookEvent::CiFailed => "ci_failed",
+    }
+}
+
+fn field<'a>(data: &'a Value, name: &str) -> &'a str {
+    data.get(name).and_then(Value::as_str).unwrap_or("unknown")
+}
+
+/// The session's project name, or its id when that is all there is.
+fn session_name(data: &Value) -> &str {
+    data.get("session")
+        .and_then(Value::as_str)
+        .unwrap_or_else(|| field(data, "sessionId"))
+}
+
+fn session_result(data: &Value) -> String {
+    match data.get("exitCode").and_then(Value::as_i64) {
+        Some(code) => format!("exit code {code}"),
+        None if data.get("status").and_then(Value::as_str) == Some("terminated") => {
+            "terminated".to_string()
+        }
+        None => "finished".to_string(),
+    }
+}
+
+/// `1h 5m`, `3m 20s` or `42s`.
+pub(crate) fn format_duration(duration_ms: i64) -> String {
+    let secs = duration_ms.max(0) / 1000;
+    let (hours, minutes, seconds) = (secs / 3600, secs % 3600 / 60, secs % 60);
+    if hours > 0 {
+        format!("{hours}h {minutes}m")
+    } else if minutes > 0 {
+        format!("{minutes}m {seconds}s")
+    } else {
+        format!("{seconds}s")
+    }
+}
+
+fn plural(count: usize, noun: &str) -> String {
+    if count == 1 {
+        format!("1 {noun}")
+    } else {
+        format!("{count} {noun}s")
+    }
+}
+
+/// The gist of a finished session's `summary`, such as `Touched 2 files,
+/// ran 3 commands, used 1.5k tokens.`
+pub(crate) fn session_outcome(data: &Value) -> Option<String> {
+    let summary = data.get("summary").filter(|summary| summary.is_object())?;
+    let count = |key: &str| {
+        summary
+            .get(key)
+            .and_then(Value::as_array)
+            .map_or(0, Vec::len)
+    };
+    let mut parts = vec![
+        format!("touched {}", plural(count("filesTouched"), "file")),
+        format!("ran {}", plural(count("commandsRun"), "command")),
+    ];
+    if let Some(usage) = summary.get("tokenUsage").filter(|usage| usage.is_object()) {
+        let tokens = ["inputTokens", "outputTokens"]
+            .iter()
+            .filter_map(|key| usage.get(*key).and_then(Value::as_u64))
+            .sum::<u64>();
+        parts.push(if tokens >= 1000 {
+            format!("used {:.1}k tokens", tokens as f64 / 1000.0)
+        } else {
+            format!("used {tokens} tokens")
+        });
+    }
+    let outcome = parts.join(", ");
+    let mut chars = outcome.chars();
+    let first = chars.next()?.to_uppercase();
+    Some(format!("{first}{}.", chars.as_str()))
+}
+
+fn summary(event: WebhookEvent, data: &Value) -> String {
+    let field = |name: &str| field(data, name);
+    match event {
+        WebhookEvent::SessionCompleted => {
+            let duration = data
+                .get("durationMs")
+                .and_then(Value::as_i64)
+                .map(|ms| format!(" after {}", format_duration(ms)))
+                .unwrap_or_default();
+            let outcome = session_outcome(data)
+                .map(|outcome| format!(" {outcome}"))
+                .unwrap_or_default();
+            format!(
+                "CodexMonitor session {} finished{duration} ({}).{outcome}",
+                session_name(data),
+                session_result(data)
+            )
+        }
+        WebhookEvent::ApprovalNeeded => format!(
+            "CodexMonitor is waiting for an approval in workspace {}.",
+            field("workspaceId")
+        ),
+        WebhookEvent::DaemonDown => format!(
+            "The CodexMonitor daemon at {} stopped responding.",
+            field("listenAddr")
+        ),
+        WebhookEvent::CiFailed => format!(
+            "CI failed on {} in {} after CodexMonitor session {}.",
+            field("branch"),
+            field("repo"),
+            session_name(data)
+        ),
+    }
+}
+
+/// The JSON body sent for `event`. `text` carries a one-line summary so
+/// chat services that accept incoming webhooks, such as Slack, can post it
+/// as is.
+#[allow(dead_code)]
+pub(crate) fn webhook_payload(event: WebhookEvent, data: Value, occurred_at_ms: i64) -> Value {
+    json!({
+        "event": event_name(event),
+        "text": summary(event, &data),
+        "occurredAtMs": occurred_at_ms,
+        "data": data,
+    })
+}
+
+/// `sha256=` followed by the hex HMAC-SHA256 of `body` under `secret`.
+#[allow(dead_code)]
+pub(crate) fn sign_payload(secret: &str, body: &[u8]) -> String {
+    let key = hmac::Key::new(hmac::HMAC_SHA256, secret.as_bytes());
+    let tag = hmac::sign(&key, body);
+    let hex: String = tag
+        .as_ref()
+        .iter()
+        .map(|byte| format!("{byte:02x}"))
+        .collect();
+    format!("sha256={hex}")
+}
+
+/// Fills the placeholders of an integration's message template; unknown
+/// ones are left as they are.
+pub(crate) fn render_template(template: &str, event: WebhookEvent, data: &Value) -> String {
+    let duration = data
+        .get("durationMs")
+        .and_then(Value::as_i64)
+        .map(format_duration)
+        .unwrap_or_else(|| "unknown".to_string());
+    let result = match event {
+        WebhookEvent::SessionCompleted => session_result(data),
+        WebhookEvent::ApprovalNeeded => "waiting for approval".to_string(),
+        WebhookEvent::DaemonDown => "daemon down".to_string(),
+        WebhookEvent::CiFailed => "CI failed".to_string(),
+    };
+    template
+        .replace("{summary}", &summary(event, data))
+        .replace("{event}", event_name(event))
+        .replace("{session}", session_name(data))
+        .replace("{project}", field(data, "projectPath"))
+        .replace("{duration}", &duration)
+        .replace("{result}", &result)
+        .replace("{workspace}", field(data, "workspaceId"))
+        .replace("{outcome}", &session_outcome(data).unwrap_or_default())
+}
+
+/// The body to post to an integration's incoming webhook.
+#[allow(dead_code)]
+pub(crate) fn integration_payload(
+    integration: &NotificationIntegration,
+    event: WebhookEvent,
+    data: &Value,
+) -> Value {
+    let text = match integration.message_template.as_deref() {
+        Some(template) if !template.trim().is_empty() => render_template(template, event, data),
+        _ => summary(event, data),
+    };
+    match integration.kind {
+        NotificationIntegrationKind::Slack => json!({ "text": text }),
+        NotificationIntegrationKind::Discord => json!({
+            "content": text.chars().take(DISCORD_MAX_CONTENT_CHARS).collect::<String>(),
+        }),
+    }
+}
+
+/// Integrations that are turned on and subscribed to `event`.
+#[allow(dead_code)]
+pub(crate) fn subscribed_integrations(
+    integrations: &[NotificationIntegration],
+    event: WebhookEvent,
+) -> Vec<NotificationIntegration> {
+    integrations
+        .iter()
+        .filter(|integration| integration.enabled && integration.events.contains(&event))
+        .cloned()
+        .collect()
+}
+
+/// Endpoints that are turned on and subscribed to `event`.
+#[allow(dead_code)]
+pub(crate) fn subscribed_endpoints(
+    endpoints: &[WebhookEndpoint],
+    event: WebhookEvent,
+) -> Vec<WebhookEndpoint> {
+    endpoints
+        .iter()
+        .filter(|endpoint| endpoint.enabled && endpoint.events.contains(&event))
+        .cloned()
+        .collect()
+}
+
+/// Server errors and rate limiting are worth another attempt; other client
+/// errors are not.
+#[allow(dead_code)]
+pub(crate) fn is_retryable_status(status: u16) -> bool {
+    status == 408 || status == 429 || status >= 500
+}
+
+/// How long to wait before attempt `attempt` (2 or later): 2s, 8s, 32s.
+#[allow(dead_code)]
+pub(crate) fn retry_delay(attempt: u32) -> Duration {
+    FIRST_RETRY_DELAY * 4u32.pow(attempt.saturating_sub(2))
+}
+
+pub(crate) fn is_approval_request(message: &Value) -> bool {
+    message
+        .get("method")
+        .and_then(Value::as_str)
+        .is_some_and(|method| method.ends_with("requestApproval"))
+}
+
+/// The webhook event, and its data, for an app-server message from
+/// `workspace_id`, if it is one webhooks report.
+pub(crate) fn app_server_webhook_event(
+    workspace_id: &str,
+    message: &Value,
+) -> Option<(WebhookEvent, Value)> {
+    if !is_approval_request(message) {
+        return None;
+    }
+    Some((
+        WebhookEvent::ApprovalNeeded,
+        json!({
+            "workspaceId": workspace_id,
+            "method": message.get("method"),
+            "threadId": message.pointer("/params/threadId"),
+        }),
+    ))
+}
+
+/// The webhook event for a notification forwarded by a remote daemon.
+#[allow(dead_code)]
+pub(crate) fn remote_webhook_event(event: &str, params: &Value) -> Option<(WebhookEvent, Value)> {
+    match event {
+        "codex-session-exit" => Some((WebhookEvent::SessionCompleted, params.clone())),
+        "app-server-event" => app_server_webhook_event(
+            params
+                .get("workspace_id")
+                .and_then(Value::as_str)
+                .unwrap_or_default(),
+            params.get("message")?,
+        ),
+        _ => None,
+    }
+}
+
+fn read_deliveries(data_dir: &Path) -> Vec<WebhookDelivery> {
+    fs::read_to_string(data_dir.join(DELIVERY_LOG_FILE))
+        .map(|raw| {
+            raw.lines()
+                .filter_map(|line| serde_json::from_str(line).ok())
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Appends `delivery` to the log, keeping the newest entries only.
+#[allow(dead_code)]
+pub(crate) fn record_delivery(data_dir: &Path, delivery: &WebhookDelivery) -> Result<(), String> {
+    let mut deliveries = read_deliveries(data_dir);
+    deliveries.push(delivery.clone());
+    let skip = deliveries.len().saturating_sub(MAX_LOGGED_DELIVERIES);
+    let mut raw = String::new();
+    for entry in &deliveries[skip..] {
+        raw.push_str(&serde_json::to_string(entry).map_err(|err| err.to_string())?);
+        raw.push('\n');
+    }
+    fs::create_dir_all(data_dir).map_err(|err| err.to_string())?;
+    fs::write(data_dir.join(DELIVERY_LOG_FILE), raw)
+        .map_err(|err| format!("Failed to write webhook delivery log: {err}"))
+}
+
+/// Newest first, optionally for one endpoint only.
+#[allow(dead_code)]
+pub(crate) fn list_webhook_deliveries_core(
+    data_dir: &Path,
+    endpoint_id: Option<&str>,
+    page: &Page,
+) -> Result<PageResult<WebhookDelivery>, String> {
+    let deliveries = read_deliveries(data_dir)
+        .into_iter()
+        .filter(|delivery| endpoint_id.is_none_or(|id| delivery.endpoint_id == id))
+        .collect();
+    paginate(deliveries, page, |delivery| {
+        (delivery.created_at_ms, delivery.id.as_str())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn delivery(id: &str, endpoint_id: &str, created_at_ms: i64) -> WebhookDelivery {
+        WebhookDelivery {
+            id: id.to_string(),
+            endpoint_id: endpoint_id.to_string(),
+            event: WebhookEvent::SessionCompleted,
+            attempts: 1,
+            status_code: Some(200),
+            ok: true,
+            error: None,
+            created_at_ms,
+            finished_at_ms: created_at_ms,
+        }
+    }
+
+    #[test]
+    fn signatures_match_a_known_hmac() {
+        // RFC 4231 test case 2.
+        assert_eq!(
+            sign_payload("Jefe", b"what do ya want for nothing?"),
+            "sha256=5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843"
+        );
+    }
+
+    #[test]
+    fn approvals_are_picked_out_of_forwarded_events() {
+        let params = json!({
+            "workspace_id": "ws-1",
+            "message": {
+                "method": "item/commandExecution/requestApproval",
+                "params": { "threadId": "t-1" },
+            },
+        });
+        let (event, data) = remote_webhook_event("app-server-event", &params).expect("approval");
+        assert_eq!(event, WebhookEvent::ApprovalNeeded);
+        assert_eq!(data["workspaceId"], "ws-1");
+        assert_eq!(data["threadId"], "t-1");
+
+        let delta =
+            json!({ "workspace_id": "ws-1", "message": { "method": "item/agentMessage/delta" } });
+        assert!(remote_webhook_event("app-server-event", &delta).is_none());
+        assert!(remote_webhook_event("terminal-output", &params).is_none());
+
+        let exit = json!({ "sessionId": "s-1", "exitCode": 0 });
+        let (event, data) = remote_webhook_event("codex-session-exit", &exit).expect("exit");
+        assert_eq!(event, WebhookEvent::SessionCompleted);
+        assert_eq!(
+            webhook_payload(event, data, 5)["text"],
+            "CodexMonitor session s-1 finished (exit code 0)."
+        );
+    }
+
+    #[test]
+    fn integrations_render_their_templates() {
+        let mut integration = NotificationIntegration {
+            id: "team".to_string(),
+            kind: NotificationIntegrationKind::Discord,
+            name: "Team".to_string(),
+            keychain_account: "discord-team".to_string(),
+            events: vec![WebhookEvent::SessionCompleted],
+            message_template: None,
+            enabled: true,
+        };
+        let data = json!({
+            "sessionId": "s-1",
+            "session": "api",
+            "projectPath": "/src/api",
+            "durationMs": 200_000,
+            "exitCode": 1,
+        });
+        assert_eq!(
+            integration_payload(&integration, WebhookEvent::SessionCompleted, &data),
+            json!({ "content": "CodexMonitor session api finished after 3m 20s (exit code 1)." })
+        );
+
+        integration.kind = NotificationIntegrationKind::Slack;
+        integration.message_template = Some("{session} in {project}: {result} ({duration})".into());
+        assert_eq!(
+            integration_payload(&integration, WebhookEvent::SessionCompleted, &data),
+            json!({ "text": "api in /src/api: exit code 1 (3m 20s)" })
+        );
+        assert_eq!(format_duration(3_900_000), "1h 5m");
+    }
+
+    #[test]
+    fn finished_sessions_report_their_outcome() {
+        let data = json!({
+            "sessionId": "s-1",
+            "exitCode": 0,
+            "summary": {
+                "filesTouched": ["src/main.rs"],
+                "commandsRun": [{ "command": "cargo test", "exitCode": 0 }, { "command": "ls" }],
+                "tokenUsage": { "inputTokens": 1200, "cachedInputTokens": 200, "outputTokens": 300 },
+            },
+        });
+        assert_eq!(
+            summary(WebhookEvent::SessionCompleted, &data),
+            "CodexMonitor session s-1 finished (exit code 0). \
+             Touched 1 file, ran 2 commands, used 1.5k tokens."
+        );
+        assert_eq!(
+            render_template("{outcome}", WebhookEvent::SessionCompleted, &json!({})),
+            ""
+        );
+        assert_eq!(
+            summary(
+                WebhookEvent::CiFailed,
+                &json!({ "sessionId": "s-1", "repo": "o/r", "branch": "main" })
+            ),
+            "CI failed on main in o/r after CodexMonitor session s-1."
+        );
+    }
+
+    #[test]
+    fn retries_back_off_and_skip_client_errors() {
+        assert_eq!(retry_delay(2), Duration::from_secs(2));
+        assert_eq!(retry_delay(4), Duration::from_secs(32));
+        assert!(is_retryable_status(503));
+        assert!(is_retryable_status(429));
+        assert!(!is_retryable_status(404));
+    }
+
+    #[test]
+    fn delivery_log_keeps_the_newest_entries() {
+        let dir =
+            std::env::temp_dir().join(format!("codex-monitor-webhooks-{}", uuid::Uuid::new_v4()));
+        for index in 0..MAX_LOGGED_DELIVERIES + 2 {
+            let endpoint = if index % 2 == 0 { "a" } else { "b" };
+            let created_at_ms = index as i64;
+            record_delivery(&dir, &delivery(&index.to_string(), endpoint, created_at_ms))
+                .expect("record");
+        }
+        let all_pages = Page {
+            cursor: None,
+            limit: Some(MAX_LOGGED_DELIVERIES as u32),
+        };
+        let all = list_webhook_deliveries_core(&dir, None, &all_pages).expect("list");
+        assert_eq!(all.items.len(), MAX_LOGGED_DELIVERIES);
+        assert_eq!(all.items[0].id, (MAX_LOGGED_DELIVERIES + 1).to_string());
+        assert_eq!(all.next_cursor, None);
+
+        let first_two = Page {
+            cursor: None,
+            limit: Some(2),
+        };
+        let only_a = list_webhook_deliveries_core(&dir, Some("a"), &first_two).expect("list");
+        let ids: Vec<&str> = only_a.items.iter().map(|entry| entry.id.as_str()).collect();
+        assert_eq!(ids, vec!["500", "498"]);
+
+        let next_two = Page {
+            cursor: only_a.next_cursor,
+            limit: Some(2),
+        };
+        let next = list_webhook_deliveries_core(&dir, Some("a"), &next_two).expect("list");
+        let ids: Vec<&str> = next.items.iter().map(|entry| entry.id.as_str()).collect();
+        assert_eq!(ids, vec!["496", "494"]);
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
@@ -2,7 +2,10 @@ use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 
-use crate::types::WorkspaceSettings;
+use crate::types::{
+    ActivityReportRange, Page, RedactionSettings, ScheduleInput, SessionExportFormat,
+    SessionSearchFilters, SessionTemplateInput, TagInput, TagTarget, WorkspaceSettings,
+};
 
 #[allow(dead_code)]
 pub(crate) fn to_params<T: Serialize>(request: &T) -> Result<Value, String> {
@@ -108,3 +111,244 @@ pub(crate) struct OpenWorkspaceInRequest {
 pub(crate) struct GetOpenAppIconRequest {
     pub(crate) app_name: String,
 }
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct RunRemoteCommandRequest {
+    pub(crate) workspace_id: String,
+    pub(crate) command: String,
+    #[serde(default)]
+    pub(crate) cwd: Option<String>,
+    #[serde(default)]
+    pub(crate) run_id: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct RemoteListDirRequest {
+    pub(crate) workspace_id: String,
+    #[serde(default)]
+    pub(crate) rel_path: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct RemoteReadFileRequest {
+    pub(crate) workspace_id: String,
+    pub(crate) rel_path: String,
+    #[serde(default)]
+    pub(crate) offset: Option<u64>,
+    #[serde(default)]
+    pub(crate) length: Option<u64>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct SearchSessionsRequest {
+    pub(crate) query: String,
+    #[serde(default)]
+    pub(crate) filters: Option<SessionSearchFilters>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct ArchiveSessionsRequest {
+    #[serde(default)]
+    pub(crate) before: Option<i64>,
+    #[serde(default)]
+    pub(crate) dry_run: Option<bool>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct RestoreArchiveRequest {
+    pub(crate) name: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct StartCodexSessionRequest {
+    #[serde(default)]
+    pub(crate) project_path: Option<String>,
+    #[serde(default)]
+    pub(crate) prompt: Option<String>,
+    #[serde(default)]
+    pub(crate) model: Option<String>,
+    #[serde(default)]
+    pub(crate) approval_mode: Option<String>,
+    #[serde(default)]
+    pub(crate) template_id: Option<String>,
+    #[serde(default)]
+    pub(crate) isolate_worktree: Option<bool>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct SendSessionInputRequest {
+    pub(crate) session_id: String,
+    pub(crate) text: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct SessionIdRequest {
+    pub(crate) session_id: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct ProjectCiStatusRequest {
+    pub(crate) project_id: String,
+    /// The checkout's current branch when unset.
+    #[serde(default)]
+    pub(crate) branch: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct CreatePrFromSessionRequest {
+    pub(crate) session_id: String,
+    pub(crate) title: String,
+    #[serde(default)]
+    pub(crate) body: Option<String>,
+    #[serde(default)]
+    pub(crate) base: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct OpenInEditorRequest {
+    pub(crate) path: String,
+    #[serde(default)]
+    pub(crate) line: Option<u32>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct OpenDiffInEditorRequest {
+    pub(crate) session_id: String,
+    pub(crate) file: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct EnablePluginRequest {
+    pub(crate) id: String,
+    pub(crate) enabled: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct ProcessStatsRequest {
+    #[serde(default)]
+    pub(crate) pid: Option<u32>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct TerminateSessionRequest {
+    pub(crate) session_id: String,
+    #[serde(default)]
+    pub(crate) pid: Option<u32>,
+    #[serde(default)]
+    pub(crate) force: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct CreateGuestTokenRequest {
+    pub(crate) scopes: Vec<String>,
+    pub(crate) ttl_seconds: u64,
+    #[serde(default)]
+    pub(crate) label: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct DecideClientApprovalRequest {
+    pub(crate) client_id: String,
+    pub(crate) approved: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct DecideRemoteActionConfirmationRequest {
+    pub(crate) id: String,
+    pub(crate) approved: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct SaveSessionTemplateRequest {
+    pub(crate) template: SessionTemplateInput,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct ListCodexSessionsRequest {
+    #[serde(default)]
+    pub(crate) tags: Vec<String>,
+    #[serde(default)]
+    pub(crate) page: Page,
+}
+
+/// Params of list methods that take nothing but a page.
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct PageRequest {
+    #[serde(default)]
+    pub(crate) page: Page,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct ExportSessionRequest {
+    pub(crate) session_id: String,
+    pub(crate) format: SessionExportFormat,
+    #[serde(default)]
+    pub(crate) include_tool_output: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct ReadSessionExportRequest {
+    pub(crate) file_name: String,
+    #[serde(default)]
+    pub(crate) offset: Option<u64>,
+    #[serde(default)]
+    pub(crate) length: Option<u64>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct TestRedactionRulesRequest {
+    pub(crate) sample: String,
+    #[serde(default)]
+    pub(crate) settings: Option<RedactionSettings>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct SaveTagRequest {
+    pub(crate) tag: TagInput,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct SetTagsRequest {
+    pub(crate) target: TagTarget,
+    pub(crate) id: String,
+    pub(crate) tag_ids: Vec<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct ActivityReportRequest {
+    pub(crate) range: ActivityReportRange,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct UpsertScheduleRequest {
+    pub(crate) schedule: ScheduleInput,
+}
@@ -14,8 +14,8 @@ pub(crate) use crud_persistence::{
 pub(crate) use git_orchestration::{apply_worktree_changes_core, run_git_command_unit};
 pub(crate) use helpers::{is_workspace_path_dir_core, list_workspaces_core};
 pub(crate) use io::{
-    get_open_app_icon_core, list_workspace_files_core, open_workspace_in_core,
-    read_workspace_file_core,
+    find_executable_in_path, get_open_app_icon_core, list_workspace_files_core,
+    open_workspace_in_core, read_workspace_file_core,
 };
 pub(crate) use runtime_codex_args::{
     set_workspace_runtime_codex_args_core, WorkspaceRuntimeCodexArgsResult,
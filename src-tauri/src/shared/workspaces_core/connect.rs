@@ -4,7 +4,7 @@ use std::path::PathBuf;
 use std::sync::OnceLock;
 use std::sync::Arc;
 
-use tokio::sync::Mutex;
+use tokio::sync::{Mutex, RwLock};
 
 use crate::backend::app_server::WorkspaceSession;
 use crate::codex::args::resolve_workspace_codex_args;
@@ -55,7 +55,7 @@ pub(crate) async fn connect_workspace_core<F, Fut>(
     workspace_id: String,
     workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
     sessions: &Mutex<HashMap<String, Arc<WorkspaceSession>>>,
-    app_settings: &Mutex<AppSettings>,
+    app_settings: &RwLock<AppSettings>,
     spawn_session: F,
 ) -> Result<(), String>
 where
@@ -84,7 +84,7 @@ where
         return Ok(());
     }
     let (default_bin, codex_args) = {
-        let settings = app_settings.lock().await;
+        let settings = app_settings.read().await;
         (
             settings.codex_bin.clone(),
             resolve_workspace_codex_args(&entry, parent_entry.as_ref(), Some(&settings)),
@@ -192,7 +192,7 @@ mod tests {
                 entry.id.clone(),
                 make_session(entry.clone()),
             )]));
-            let app_settings = Mutex::new(AppSettings::default());
+            let app_settings = RwLock::new(AppSettings::default());
             let spawn_calls = Arc::new(AtomicUsize::new(0));
             let spawn_calls_ref = spawn_calls.clone();
 
@@ -223,7 +223,7 @@ mod tests {
             let entry = make_workspace_entry("ws-2");
             let workspaces = Mutex::new(HashMap::from([(entry.id.clone(), entry.clone())]));
             let sessions = Mutex::new(HashMap::<String, Arc<WorkspaceSession>>::new());
-            let app_settings = Mutex::new(AppSettings::default());
+            let app_settings = RwLock::new(AppSettings::default());
             let spawn_calls = Arc::new(AtomicUsize::new(0));
             let spawn_calls_ref = spawn_calls.clone();
             let entry_for_spawn = entry.clone();
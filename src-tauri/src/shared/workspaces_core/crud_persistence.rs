@@ -3,7 +3,7 @@ use std::future::Future;
 use std::path::{Component, Path, PathBuf};
 use std::sync::Arc;
 
-use tokio::sync::Mutex;
+use tokio::sync::{Mutex, RwLock};
 use uuid::Uuid;
 
 use crate::backend::app_server::WorkspaceSession;
@@ -24,7 +24,7 @@ pub(crate) async fn add_workspace_core<F, Fut>(
     path: String,
     workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
     sessions: &Mutex<HashMap<String, Arc<WorkspaceSession>>>,
-    app_settings: &Mutex<AppSettings>,
+    app_settings: &RwLock<AppSettings>,
     storage_path: &PathBuf,
     spawn_session: F,
 ) -> Result<WorkspaceInfo, String>
@@ -59,7 +59,7 @@ where
         (existing_session, false)
     } else {
         let (default_bin, codex_args) = {
-            let settings = app_settings.lock().await;
+            let settings = app_settings.read().await;
             (
                 settings.codex_bin.clone(),
                 resolve_workspace_codex_args(&entry, None, Some(&settings)),
@@ -112,7 +112,7 @@ pub(crate) async fn add_clone_core<F, Fut>(
     copies_folder: String,
     workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
     sessions: &Mutex<HashMap<String, Arc<WorkspaceSession>>>,
-    app_settings: &Mutex<AppSettings>,
+    app_settings: &RwLock<AppSettings>,
     storage_path: &PathBuf,
     spawn_session: F,
 ) -> Result<WorkspaceInfo, String>
@@ -210,7 +210,7 @@ where
         (existing_session, false)
     } else {
         let (default_bin, codex_args) = {
-            let settings = app_settings.lock().await;
+            let settings = app_settings.read().await;
             (
                 settings.codex_bin.clone(),
                 resolve_workspace_codex_args(&entry, None, Some(&settings)),
@@ -304,7 +304,7 @@ pub(crate) async fn add_workspace_from_git_url_core<F, Fut>(
     target_folder_name: Option<String>,
     workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
     sessions: &Mutex<HashMap<String, Arc<WorkspaceSession>>>,
-    app_settings: &Mutex<AppSettings>,
+    app_settings: &RwLock<AppSettings>,
     storage_path: &PathBuf,
     spawn_session: F,
 ) -> Result<WorkspaceInfo, String>
@@ -375,7 +375,7 @@ where
         (existing_session, false)
     } else {
         let (default_bin, codex_args) = {
-            let settings = app_settings.lock().await;
+            let settings = app_settings.read().await;
             (
                 settings.codex_bin.clone(),
                 resolve_workspace_codex_args(&entry, None, Some(&settings)),
@@ -543,7 +543,7 @@ pub(crate) async fn update_workspace_settings_core<FApplySettings, FSpawn, FutSp
     mut settings: WorkspaceSettings,
     workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
     sessions: &Mutex<HashMap<String, Arc<WorkspaceSession>>>,
-    _app_settings: &Mutex<AppSettings>,
+    _app_settings: &RwLock<AppSettings>,
     storage_path: &PathBuf,
     apply_settings_update: FApplySettings,
     _spawn_session: FSpawn,
@@ -103,7 +103,7 @@ fn normalize_app_identifier(app: &str) -> String {
         .join(" ")
 }
 
-fn find_executable_in_path(program: &str) -> Option<PathBuf> {
+pub(crate) fn find_executable_in_path(program: &str) -> Option<PathBuf> {
     let trimmed = program.trim();
     if trimmed.is_empty() {
         return None;
@@ -4,7 +4,7 @@ use std::path::PathBuf;
 use std::sync::Arc;
 
 use serde::{Deserialize, Serialize};
-use tokio::sync::Mutex;
+use tokio::sync::{Mutex, RwLock};
 
 use crate::backend::app_server::WorkspaceSession;
 use crate::codex::args::resolve_workspace_codex_args;
@@ -27,7 +27,7 @@ pub(crate) async fn set_workspace_runtime_codex_args_core<F, Fut>(
     codex_args_override: Option<String>,
     workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
     sessions: &Mutex<HashMap<String, Arc<WorkspaceSession>>>,
-    app_settings: &Mutex<AppSettings>,
+    app_settings: &RwLock<AppSettings>,
     spawn_session: F,
 ) -> Result<WorkspaceRuntimeCodexArgsResult, String>
 where
@@ -38,7 +38,7 @@ where
     let _spawn_guard = workspace_session_spawn_lock().lock().await;
 
     let (default_bin, resolved_args) = {
-        let settings = app_settings.lock().await;
+        let settings = app_settings.read().await;
         (
             settings.codex_bin.clone(),
             resolve_workspace_codex_args(&entry, parent_entry.as_ref(), Some(&settings)),
@@ -189,7 +189,7 @@ mod tests {
             let entry = make_workspace_entry("ws-1");
             let workspaces = Mutex::new(HashMap::from([(entry.id.clone(), entry.clone())]));
             let sessions = Mutex::new(HashMap::<String, Arc<WorkspaceSession>>::new());
-            let app_settings = Mutex::new(AppSettings::default());
+            let app_settings = RwLock::new(AppSettings::default());
 
             let spawn_calls = Arc::new(AtomicUsize::new(0));
             let spawn_calls_ref = spawn_calls.clone();
@@ -229,7 +229,7 @@ mod tests {
             let workspaces = Mutex::new(HashMap::from([(entry.id.clone(), entry.clone())]));
             let current_session = Arc::new(make_session(entry.clone(), Some("--same".to_string())));
             let sessions = Mutex::new(HashMap::from([(entry.id.clone(), current_session)]));
-            let app_settings = Mutex::new(AppSettings::default());
+            let app_settings = RwLock::new(AppSettings::default());
 
             let spawn_calls = Arc::new(AtomicUsize::new(0));
             let spawn_calls_ref = spawn_calls.clone();
@@ -269,7 +269,7 @@ mod tests {
             let workspaces = Mutex::new(HashMap::from([(entry.id.clone(), entry.clone())]));
             let current_session = Arc::new(make_session(entry.clone(), Some("--old".to_string())));
             let sessions = Mutex::new(HashMap::from([(entry.id.clone(), current_session)]));
-            let app_settings = Mutex::new(AppSettings::default());
+            let app_settings = RwLock::new(AppSettings::default());
 
             let spawn_calls = Arc::new(AtomicUsize::new(0));
             let spawn_calls_ref = spawn_calls.clone();
@@ -3,7 +3,7 @@ use std::future::Future;
 use std::path::PathBuf;
 use std::sync::Arc;
 
-use tokio::sync::Mutex;
+use tokio::sync::{Mutex, RwLock};
 use uuid::Uuid;
 
 use crate::backend::app_server::WorkspaceSession;
@@ -93,7 +93,7 @@ pub(crate) async fn add_worktree_core<
     data_dir: &PathBuf,
     workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
     sessions: &Mutex<HashMap<String, Arc<WorkspaceSession>>>,
-    app_settings: &Mutex<AppSettings>,
+    app_settings: &RwLock<AppSettings>,
     storage_path: &PathBuf,
     sanitize_worktree_name: FSanitize,
     unique_worktree_path: FUniquePath,
@@ -139,7 +139,7 @@ where
         PathBuf::from(workspace_path_to_string(&PathBuf::from(custom_folder)))
     } else {
         let global_folder = {
-            let settings = app_settings.lock().await;
+            let settings = app_settings.read().await;
             settings.global_worktrees_folder.clone()
         };
         if let Some(global_folder) = global_folder {
@@ -226,7 +226,7 @@ where
         existing_session
     } else {
         let (default_bin, codex_args) = {
-            let settings = app_settings.lock().await;
+            let settings = app_settings.read().await;
             (
                 settings.codex_bin.clone(),
                 resolve_workspace_codex_args(&entry, Some(&parent_entry), Some(&settings)),
@@ -348,7 +348,7 @@ pub(crate) async fn rename_worktree_core<
     data_dir: &PathBuf,
     workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
     sessions: &Mutex<HashMap<String, Arc<WorkspaceSession>>>,
-    app_settings: &Mutex<AppSettings>,
+    app_settings: &RwLock<AppSettings>,
     storage_path: &PathBuf,
     resolve_git_root: FResolveGitRoot,
     unique_branch_name: FUniqueBranch,
@@ -414,7 +414,7 @@ where
         PathBuf::from(workspace_path_to_string(&PathBuf::from(custom_folder)))
     } else {
         let global_folder = {
-            let settings = app_settings.lock().await;
+            let settings = app_settings.read().await;
             settings.global_worktrees_folder.clone()
         };
         if let Some(global_folder) = global_folder {
@@ -0,0 +1,475 @@
+//! System-wide hotkeys for quick actions. They are configured in app settings
+//! in the same `cmd+shift+k` form as in-app shortcuts, registered through the
+//! global-shortcut plugin, and registered again whenever settings change.
+//! What was registered, and why anything was not, is kept for
+//! `list_registered_shortcuts`.
+
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+use tauri::State;
+#[cfg(desktop)]
+use tauri::{AppHandle, Manager};
+#[cfg(desktop)]
+use tauri_plugin_global_shortcut::{GlobalShortcutExt, Shortcut, ShortcutEvent, ShortcutState};
+
+use crate::command_error::CommandResult;
+use crate::platform;
+use crate::types::{AppSettings, GlobalShortcutAction, PlatformSubsystem, RegisteredShortcut};
+
+#[derive(Default)]
+pub(crate) struct ShortcutRegistry {
+    entries: Mutex<Vec<RegisteredShortcut>>,
+    /// Registered actions by the plugin's shortcut id.
+    actions: Mutex<HashMap<u32, GlobalShortcutAction>>,
+}
+
+/// A configured shortcut and the accelerator to register for it, which is
+/// `None` once `entry.error` says why it cannot be.
+struct Planned {
+    entry: RegisteredShortcut,
+    accelerator: Option<String>,
+}
+
+fn action_label(action: GlobalShortcutAction) -> &'static str {
+    match action {
+        GlobalShortcutAction::ToggleWindow => "showing and hiding the window",
+        GlobalShortcutAction::ToggleDaemon => "starting and stopping the daemon",
+        GlobalShortcutAction::StartSession => "starting a session",
+    }
+}
+
+fn configured(settings: &AppSettings) -> Vec<(GlobalShortcutAction, &str)> {
+    [
+        (
+            GlobalShortcutAction::ToggleWindow,
+            settings.global_toggle_window_shortcut.as_deref(),
+        ),
+        (
+            GlobalShortcutAction::ToggleDaemon,
+            settings.global_toggle_daemon_shortcut.as_deref(),
+        ),
+        (
+            GlobalShortcutAction::StartSession,
+            settings.global_start_session_shortcut.as_deref(),
+        ),
+    ]
+    .into_iter()
+    .filter_map(|(action, value)| {
+        let value = value?.trim();
+        (!value.is_empty()).then_some((action, value))
+    })
+    .collect()
+}
+
+fn in_app_shortcuts(settings: &AppSettings) -> [(&'static str, Option<&str>); 17] {
+    [
+        (
+            "composerModelShortcut",
+            settings.composer_model_shortcut.as_deref(),
+        ),
+        (
+            "composerAccessShortcut",
+            settings.composer_access_shortcut.as_deref(),
+        ),
+        (
+            "composerReasoningShortcut",
+            settings.composer_reasoning_shortcut.as_deref(),
+        ),
+        ("interruptShortcut", settings.interrupt_shortcut.as_deref()),
+        (
+            "composerCollaborationShortcut",
+            settings.composer_collaboration_shortcut.as_deref(),
+        ),
+        ("newAgentShortcut", settings.new_agent_shortcut.as_deref()),
+        (
+            "newWorktreeAgentShortcut",
+            settings.new_worktree_agent_shortcut.as_deref(),
+        ),
+        (
+            "newCloneAgentShortcut",
+            settings.new_clone_agent_shortcut.as_deref(),
+        ),
+        (
+            "archiveThreadShortcut",
+            settings.archive_thread_shortcut.as_deref(),
+        ),
+        (
+            "toggleProjectsSidebarShortcut",
+            settings.toggle_projects_sidebar_shortcut.as_deref(),
+        ),
+        (
+            "toggleGitSidebarShortcut",
+            settings.toggle_git_sidebar_shortcut.as_deref(),
+        ),
+        (
+            "toggleDebugPanelShortcut",
+            settings.toggle_debug_panel_shortcut.as_deref(),
+        ),
+        (
+            "toggleTerminalShortcut",
+            settings.toggle_terminal_shortcut.as_deref(),
+        ),
+        (
+            "cycleAgentNextShortcut",
+            settings.cycle_agent_next_shortcut.as_deref(),
+        ),
+        (
+            "cycleAgentPrevShortcut",
+            settings.cycle_agent_prev_shortcut.as_deref(),
+        ),
+        (
+            "cycleWorkspaceNextShortcut",
+            settings.cycle_workspace_next_shortcut.as_deref(),
+        ),
+        (
+            "cycleWorkspacePrevShortcut",
+            settings.cycle_workspace_prev_shortcut.as_deref(),
+        ),
+    ]
+}
+
+fn accelerator_key(key: &str) -> String {
+    match key {
+        "space" => "Space".to_string(),
+        "escape" | "esc" => "Escape".to_string(),
+        "enter" | "return" => "Enter".to_string(),
+        "tab" => "Tab".to_string(),
+        "backspace" => "Backspace".to_string(),
+        "delete" => "Delete".to_string(),
+        "arrowup" | "up" => "Up".to_string(),
+        "arrowdown" | "down" => "Down".to_string(),
+        "arrowleft" | "left" => "Left".to_string(),
+        "arrowright" | "right" => "Right".to_string(),
+        other => other.to_uppercase(),
+    }
+}
+
+/// Turns a `cmd+shift+k` setting into a plugin accelerator. As in the
+/// frontend, `cmd` is Ctrl outside macOS, where `cmd+ctrl` becomes Ctrl+Alt.
+/// Modifiers come out in a fixed order, so equal shortcuts compare equal.
+fn to_accelerator(value: &str, mac: bool) -> Result<String, String> {
+    let parts: Vec<String> = value
+        .split('+')
+        .map(|part| part.trim().to_lowercase())
+        .filter(|part| !part.is_empty())
+        .collect();
+    let Some((key, modifiers)) = parts.split_last() else {
+        return Err("The shortcut is empty.".to_string());
+    };
+    let has = |names: &[&str]| modifiers.iter().any(|part| names.contains(&part.as_str()));
+    let mut meta = has(&["cmd", "meta"]);
+    let mut ctrl = has(&["ctrl", "control"]);
+    let mut alt = has(&["alt", "option"]);
+    let shift = has(&["shift"]);
+    if let Some(unknown) = modifiers.iter().find(|part| {
+        !["cmd", "meta", "ctrl", "control", "alt", "option", "shift"].contains(&part.as_str())
+    }) {
+        return Err(format!("Unknown modifier \"{unknown}\"."));
+    }
+    if ["cmd", "meta", "ctrl", "control", "alt", "option", "shift"].contains(&key.as_str()) {
+        return Err("The shortcut needs a key besides its modifiers.".to_string());
+    }
+    if !mac {
+        if meta && ctrl {
+            alt = true;
+        }
+        ctrl |= meta;
+        meta = false;
+    }
+    if !(meta || ctrl || alt) {
+        return Err("Global shortcuts need Cmd, Ctrl or Alt.".to_string());
+    }
+    let mut accelerator = Vec::new();
+    if meta {
+        accelerator.push("Super".to_string());
+    }
+    if ctrl {
+        accelerator.push("Ctrl".to_string());
+    }
+    if alt {
+        accelerator.push("Alt".to_string());
+    }
+    if shift {
+        accelerator.push("Shift".to_string());
+    }
+    accelerator.push(accelerator_key(key));
+    Ok(accelerator.join("+"))
+}
+
+/// Works out what to register for `settings`, catching everything that can
+/// be caught before asking the OS: invalid shortcuts, a missing session
+/// template, and a shortcut set for two actions, where the first keeps it.
+fn plan(settings: &AppSettings, mac: bool) -> Vec<Planned> {
+    let in_app: Vec<(&str, String)> = in_app_shortcuts(settings)
+        .into_iter()
+        .filter_map(|(name, value)| Some((name, to_accelerator(value?, mac).ok()?)))
+        .collect();
+    let mut taken: HashMap<String, GlobalShortcutAction> = HashMap::new();
+    configured(settings)
+        .into_iter()
+        .map(|(action, value)| {
+            let mut entry = RegisteredShortcut {
+                action,
+                shortcut: value.to_string(),
+                registered: false,
+                error: None,
+                shadows: Vec::new(),
+            };
+            let accelerator = match to_accelerator(value, mac) {
+                Ok(accelerator) => accelerator,
+                Err(err) => {
+                    entry.error = Some(err);
+                    return Planned {
+                        entry,
+                        accelerator: None,
+                    };
+                }
+            };
+            entry.shadows = in_app
+                .iter()
+                .filter(|(_, other)| *other == accelerator)
+                .map(|(name, _)| name.to_string())
+                .collect();
+            if action == GlobalShortcutAction::StartSession
+                && settings
+                    .global_session_template_id
+                    .as_deref()
+                    .map_or(true, |id| id.trim().is_empty())
+            {
+                entry.error = Some("Choose a session template for this shortcut.".to_string());
+            } else if let Some(owner) = taken.get(&accelerator) {
+                entry.error = Some(format!("Already used for {}.", action_label(*owner)));
+            }
+            if entry.error.is_some() {
+                return Planned {
+                    entry,
+                    accelerator: None,
+                };
+            }
+            taken.insert(accelerator.clone(), action);
+            Planned {
+                entry,
+                accelerator: Some(accelerator),
+            }
+        })
+        .collect()
+}
+
+/// Replaces every global shortcut with the ones `settings` configures.
+#[cfg(desktop)]
+pub(crate) fn apply_global_shortcuts(app: &AppHandle, settings: &AppSettings) {
+    let Some(registry) = app.try_state::<ShortcutRegistry>() else {
+        return;
+    };
+    let global_shortcut = app.global_shortcut();
+    if let Err(err) = global_shortcut.unregister_all() {
+        eprintln!("shortcuts: failed to unregister global shortcuts: {err}");
+    }
+    let mut actions = HashMap::new();
+    let entries = plan(settings, cfg!(target_os = "macos"))
+        .into_iter()
+        .map(
+            |Planned {
+                 mut entry,
+                 accelerator,
+             }| {
+                let Some(accelerator) = accelerator else {
+                    return entry;
+                };
+                match accelerator.parse::<Shortcut>() {
+                    Ok(shortcut) => {
+                        let id = shortcut.id();
+                        match global_shortcut.register(shortcut) {
+                            Ok(()) => {
+                                entry.registered = true;
+                                actions.insert(id, entry.action);
+                            }
+                            Err(err) => {
+                                entry.error = Some(format!(
+                                "The system refused it, so another app may already use it: {err}"
+                            ));
+                            }
+                        }
+                    }
+                    Err(err) => entry.error = Some(format!("Unsupported shortcut: {err}")),
+                }
+                entry
+            },
+        )
+        .collect();
+    if let Ok(mut registered) = registry.actions.lock() {
+        *registered = actions;
+    }
+    if let Ok(mut registered) = registry.entries.lock() {
+        *registered = entries;
+    }
+}
+
+/// The global-shortcut plugin's handler.
+#[cfg(desktop)]
+pub(crate) fn handle_shortcut(app: &AppHandle, shortcut: &Shortcut, event: ShortcutEvent) {
+    if event.state() != ShortcutState::Pressed {
+        return;
+    }
+    let Some(action) = app
+        .try_state::<ShortcutRegistry>()
+        .and_then(|registry| registry.actions.lock().ok()?.get(&shortcut.id()).copied())
+    else {
+        return;
+    };
+    match action {
+        GlobalShortcutAction::ToggleWindow => toggle_main_window(app),
+        GlobalShortcutAction::ToggleDaemon => {
+            tauri::async_runtime::spawn(toggle_daemon(app.clone()));
+        }
+        GlobalShortcutAction::StartSession => {
+            tauri::async_runtime::spawn(start_session(app.clone()));
+        }
+    }
+}
+
+#[cfg(desktop)]
+fn toggle_main_window(app: &AppHandle) {
+    let Some(window) = app.get_webview_window("main") else {
+        return;
+    };
+    let visible = window.is_visible().unwrap_or(false);
+    let minimized = window.is_minimized().unwrap_or(false);
+    if visible && !minimized && window.is_focused().unwrap_or(false) {
+        let _ = window.hide();
+        return;
+    }
+    let _ = window.show();
+    let _ = window.unminimize();
+    let _ = window.set_focus();
+}
+
+#[cfg(desktop)]
+async fn toggle_daemon(app: AppHandle) {
+    let running = matches!(
+        crate::tailscale::tailscale_daemon_status(None, app.state()).await,
+        Ok(status) if matches!(status.state, crate::types::TcpDaemonState::Running)
+    );
+    let result = if running {
+        crate::tailscale::tailscale_daemon_stop(app.state()).await
+    } else {
+        crate::tailscale::tailscale_daemon_start(None, app.state()).await
+    };
+    if let Err(err) = result {
+        eprintln!("shortcuts: failed to toggle the daemon: {err}");
+    }
+}
+
+#[cfg(desktop)]
+async fn start_session(app: AppHandle) {
+    let template_id = app
+        .state::<crate::state::AppState>()
+        .app_settings
+        .read()
+        .await
+        .global_session_template_id
+        .clone();
+    let Some(template_id) = template_id else {
+        return;
+    };
+    if let Err(err) = crate::sessions::start_codex_session(
+        None,
+        None,
+        None,
+        None,
+        Some(template_id),
+        None,
+        app.state(),
+        app.clone(),
+    )
+    .await
+    {
+        eprintln!("shortcuts: failed to start a session: {err}");
+    }
+}
+
+/// The configured global shortcuts, whether each one is registered, and
+/// which in-app shortcuts each one hides.
+#[tauri::command]
+pub(crate) fn list_registered_shortcuts(
+    registry: State<'_, ShortcutRegistry>,
+) -> CommandResult<Vec<RegisteredShortcut>> {
+    if platform::is_mobile() {
+        return Err(platform::unsupported(PlatformSubsystem::GlobalShortcuts).into());
+    }
+    Ok(registry
+        .entries
+        .lock()
+        .map(|entries| entries.clone())
+        .unwrap_or_default())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn settings() -> AppSettings {
+        AppSettings {
+            global_toggle_window_shortcut: Some("cmd+shift+space".to_string()),
+            global_toggle_daemon_shortcut: Some("cmd+alt+d".to_string()),
+            ..AppSettings::default()
+        }
+    }
+
+    #[test]
+    fn accelerators_follow_the_platform_and_normalize_order() {
+        assert_eq!(
+            to_accelerator("shift+cmd+space", true).as_deref(),
+            Ok("Super+Shift+Space")
+        );
+        assert_eq!(
+            to_accelerator("cmd+shift+space", false).as_deref(),
+            Ok("Ctrl+Shift+Space")
+        );
+        assert_eq!(
+            to_accelerator("cmd+ctrl+a", false).as_deref(),
+            Ok("Ctrl+Alt+A")
+        );
+        assert_eq!(to_accelerator("alt+arrowup", true).as_deref(), Ok("Alt+Up"));
+        assert!(to_accelerator("shift+tab", true).is_err());
+        assert!(to_accelerator("cmd+shift", true).is_err());
+        assert!(to_accelerator("hyper+k", true).is_err());
+    }
+
+    #[test]
+    fn plan_reports_duplicates_and_shadowed_in_app_shortcuts() {
+        let mut settings = settings();
+        settings.global_toggle_daemon_shortcut = Some("shift+cmd+space".to_string());
+        settings.new_agent_shortcut = Some("cmd+shift+space".to_string());
+        let planned = plan(&settings, true);
+        assert_eq!(planned.len(), 2);
+        assert_eq!(planned[0].accelerator.as_deref(), Some("Super+Shift+Space"));
+        assert_eq!(
+            planned[0].entry.shadows,
+            vec!["newAgentShortcut".to_string()]
+        );
+        assert!(planned[1].accelerator.is_none());
+        assert_eq!(
+            planned[1].entry.error.as_deref(),
+            Some("Already used for showing and hiding the window.")
+        );
+    }
+
+    #[test]
+    fn starting_a_session_needs_a_template() {
+        let mut settings = settings();
+        settings.global_start_session_shortcut = Some("cmd+alt+s".to_string());
+        let planned = plan(&settings, true);
+        let start = planned
+            .iter()
+            .find(|planned| planned.entry.action == GlobalShortcutAction::StartSession)
+            .expect("start session planned");
+        assert!(start.accelerator.is_none());
+        assert!(start.entry.error.is_some());
+
+        settings.global_session_template_id = Some("template-1".to_string());
+        let planned = plan(&settings, true);
+        assert!(planned.iter().all(|planned| planned.accelerator.is_some()));
+    }
+}
@@ -0,0 +1,99 @@
+use std::time::Duration;
+
+use tauri::{AppHandle, Manager};
+
+use crate::shared::codex_session_core::terminate_all_sessions;
+use crate::state::AppState;
+use crate::storage::{write_settings, write_workspaces};
+use crate::types::{DaemonStopReason, TcpDaemonState, WorkspaceEntry};
+
+/// Upper bound for the whole exit sequence; whatever has not finished by
+/// then is abandoned so quitting never hangs.
+const SHUTDOWN_TIMEOUT: Duration = Duration::from_secs(8);
+/// Leaves part of `SHUTDOWN_TIMEOUT` for flushing storage.
+const PROCESS_STOP_TIMEOUT: Duration = Duration::from_secs(6);
+/// How long a requested restart may take before the caller treats it as
+/// failed: the whole exit sequence plus a margin for the exit itself.
+pub(crate) const RESTART_GRACE: Duration = Duration::from_secs(SHUTDOWN_TIMEOUT.as_secs() + 5);
+
+/// Stops the app-managed daemon unless it is meant to outlive the app. The
+/// daemon announces the shutdown to its other clients and drains before it
+/// exits.
+async fn stop_managed_daemon(app: &AppHandle) {
+    let state = app.state::<AppState>();
+    let keep_running = state
+        .app_settings
+        .read()
+        .await
+        .keep_daemon_running_after_app_close;
+    // A service-managed daemon is meant to outlive the app.
+    if keep_running || crate::tailscale::installed_daemon_service().is_some() {
+        return;
+    }
+    let _ = crate::tailscale::stop_daemon_for(state, DaemonStopReason::AppExit).await;
+}
+
+/// Rewrites settings and workspaces from memory so a write that raced the
+/// quit is not lost.
+async fn flush_storage(state: &AppState) {
+    let settings = state.app_settings.read().await.clone();
+    let _ = write_settings(&state.settings_path, &settings);
+    let workspaces: Vec<WorkspaceEntry> = state.workspaces.lock().await.values().cloned().collect();
+    let _ = write_workspaces(&state.storage_path, &workspaces);
+}
+
+/// What `stop_for_data_dir_change` stopped, so a change that fails can
+/// start it again.
+pub(crate) struct DataDirChangeStop {
+    daemon_was_running: bool,
+}
+
+/// Stops the app-managed daemon, even one meant to outlive the app, and
+/// every Codex session, then flushes storage, so the data directory can be
+/// copied or replaced while the app keeps running.
+pub(crate) async fn stop_for_data_dir_change(app: &AppHandle) -> DataDirChangeStop {
+    let state = app.state::<AppState>();
+    let daemon = state.backends.daemon.status(&state, false).await;
+    let daemon_was_running = daemon.is_ok_and(|status| {
+        matches!(
+            status.state,
+            TcpDaemonState::Running | TcpDaemonState::Starting
+        )
+    });
+    let stop_processes = futures_util::future::join(
+        crate::tailscale::stop_daemon_for(app.state::<AppState>(), DaemonStopReason::DataDirChange),
+        terminate_all_sessions(&state.session_runtimes),
+    );
+    let _ = tokio::time::timeout(PROCESS_STOP_TIMEOUT, stop_processes).await;
+    flush_storage(&state).await;
+    DataDirChangeStop { daemon_was_running }
+}
+
+/// Starts the daemon `stop_for_data_dir_change` stopped again, for a change
+/// that failed and left the app on its current data directory.
+pub(crate) async fn resume_after_data_dir_change(app: &AppHandle, stopped: DataDirChangeStop) {
+    if stopped.daemon_was_running {
+        let state = app.state::<AppState>();
+        let _ = state.backends.daemon.start(&state, false).await;
+    }
+}
+
+async fn shutdown(app: &AppHandle) {
+    crate::window_state::save_all(app);
+    let state = app.state::<AppState>();
+    // The daemon and Codex sessions stop independently; storage is flushed
+    // afterwards, even when they time out.
+    let stop_processes = futures_util::future::join(
+        stop_managed_daemon(app),
+        terminate_all_sessions(&state.session_runtimes),
+    );
+    let _ = tokio::time::timeout(PROCESS_STOP_TIMEOUT, stop_processes).await;
+    flush_storage(&state).await;
+}
+
+/// Runs the coordinated exit sequence, bounded by `SHUTDOWN_TIMEOUT`, then
+/// exits the app.
+pub(crate) async fn shutdown_and_exit(app: AppHandle) {
+    let _ = tokio::time::timeout(SHUTDOWN_TIMEOUT, shutdown(&app)).await;
+    app.exit(0);
+}
@@ -0,0 +1,120 @@
+//! Startup timing, so a slow launch can be traced to the phase that caused
+//! it. Phases are recorded once per process; later runs of a lazily
+//! initialized subsystem are not startup cost.
+
+use std::future::Future;
+use std::sync::{Mutex, OnceLock};
+use std::time::Instant;
+
+use crate::types::{StartupPhase, StartupPhaseKind, StartupReport};
+
+static PROCESS_START: OnceLock<Instant> = OnceLock::new();
+static REPORT: Mutex<StartupReport> = Mutex::new(StartupReport {
+    ready_after_ms: None,
+    phases: Vec::new(),
+});
+
+fn millis_since_start(at: Instant) -> u64 {
+    let start = *PROCESS_START.get_or_init(|| at);
+    at.saturating_duration_since(start).as_millis() as u64
+}
+
+/// Anchors phase offsets. Called first thing in `run`.
+pub(crate) fn mark_process_start() {
+    millis_since_start(Instant::now());
+}
+
+/// Marks the end of `setup`, after which the window can appear.
+pub(crate) fn mark_ready() {
+    let ready_after_ms = millis_since_start(Instant::now());
+    if let Ok(mut report) = REPORT.lock() {
+        report.ready_after_ms.get_or_insert(ready_after_ms);
+    }
+}
+
+fn record(name: &str, kind: StartupPhaseKind, started: Instant) {
+    let phase = StartupPhase {
+        name: name.to_string(),
+        kind,
+        started_after_ms: millis_since_start(started),
+        duration_ms: started.elapsed().as_millis() as u64,
+    };
+    if let Ok(mut report) = REPORT.lock() {
+        if report.phases.iter().all(|existing| existing.name != name) {
+            report.phases.push(phase);
+        }
+    }
+}
+
+/// Runs a phase on the setup path.
+pub(crate) fn measure<T>(name: &str, phase: impl FnOnce() -> T) -> T {
+    let started = Instant::now();
+    let result = phase();
+    record(name, StartupPhaseKind::Setup, started);
+    result
+}
+
+/// Runs a phase spawned from setup, or the first run of a lazily
+/// initialized subsystem.
+pub(crate) async fn measure_async<T>(
+    name: &str,
+    kind: StartupPhaseKind,
+    phase: impl Future<Output = T>,
+) -> T {
+    let started = Instant::now();
+    let result = phase.await;
+    record(name, kind, started);
+    result
+}
+
+pub(crate) fn startup_report() -> StartupReport {
+    let mut report = REPORT
+        .lock()
+        .map(|report| report.clone())
+        .unwrap_or(StartupReport {
+            ready_after_ms: None,
+            phases: Vec::new(),
+        });
+    report
+        .phases
+        .sort_by_key(|phase| (phase.started_after_ms, phase.name.clone()));
+    report
+}
+
+#[tauri::command]
+pub(crate) fn get_startup_report() -> StartupReport {
+    startup_report()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn phases_are_recorded_once_in_start_order() {
+        mark_process_start();
+        measure("test_phase_b", || {
+            std::thread::sleep(std::time::Duration::from_millis(5))
+        });
+        measure("test_phase_b", || ());
+        tokio::runtime::Runtime::new()
+            .expect("runtime")
+            .block_on(measure_async(
+                "test_phase_c",
+                StartupPhaseKind::Lazy,
+                async {},
+            ));
+
+        let phases: Vec<StartupPhase> = startup_report()
+            .phases
+            .into_iter()
+            .filter(|phase| phase.name.starts_with("test_phase_"))
+            .collect();
+        assert_eq!(phases.len(), 2);
+        assert_eq!(phases[0].name, "test_phase_b");
+        assert_eq!(phases[0].kind, StartupPhaseKind::Setup);
+        assert!(phases[0].duration_ms >= 5);
+        assert_eq!(phases[1].kind, StartupPhaseKind::Lazy);
+        assert!(phases[1].started_after_ms >= phases[0].started_after_ms);
+    }
+}
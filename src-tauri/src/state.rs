@@ -1,18 +1,172 @@
-use std::collections::HashMap;
-use std::path::PathBuf;
-use std::sync::Arc;
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex as StdMutex, PoisonError};
 use tauri::{AppHandle, Manager};
 use tokio::process::Child;
-use tokio::sync::Mutex;
+use tokio::sync::{Mutex, MutexGuard, RwLock};
+use tokio::time::Instant;
 
+use crate::codex::home::set_codex_home_override;
 use crate::dictation::DictationState;
+use crate::shared::ci_status_core::CiStatusCache;
+use crate::shared::client_trust_core::set_local_client;
 use crate::shared::codex_core::CodexLoginCancelState;
+use crate::shared::codex_login_core::CodexCliLoginSlot;
+use crate::shared::codex_session_core::SessionRegistry;
+use crate::shared::config_changelog_core::set_config_changelog_dir;
+use crate::shared::daemon_data_dir_core::{migrate_daemon_state, resolve_daemon_data_dir};
+use crate::shared::data_dir_core::resolve_data_dir;
+use crate::shared::process_env_core::set_codex_process_env;
+use crate::shared::redaction_core::set_redaction;
 use crate::storage::{read_settings, read_workspaces};
-use crate::types::{AppSettings, TcpDaemonState, TcpDaemonStatus, WorkspaceEntry};
+use crate::types::{
+    AppSettings, ProcessLaunch, ProcessOperation, RemoteLinkPath, RemoteMethodMetrics,
+    RemoteSlowCall, TailscalePingResult, TailscaleStatus, TcpDaemonState, TcpDaemonStatus,
+    WorkspaceEntry,
+};
 
 pub(crate) struct TcpDaemonRuntime {
     pub(crate) child: Option<Child>,
     pub(crate) status: TcpDaemonStatus,
+    /// How `child` was spawned, for verbose status.
+    pub(crate) launch: Option<ProcessLaunch>,
+    /// A daemon started outside the app that proved its identity and was
+    /// adopted; stop and process stats act on it like on `child`.
+    pub(crate) adopted_pid: Option<u32>,
+}
+
+/// Last `tailscale status` result, shared by commands and the background
+/// refresher so the CLI is not forked on every settings render.
+#[derive(Default)]
+pub(crate) struct TailscaleStatusCache {
+    pub(crate) status: Option<TailscaleStatus>,
+    pub(crate) fetched_at: Option<Instant>,
+}
+
+/// What the remote backend connection has been doing, for polling hints.
+#[derive(Default)]
+pub(crate) struct RemoteActivity {
+    /// Last connect, disconnect or event from the remote backend.
+    pub(crate) last_change: Option<Instant>,
+    /// `workspace_id:thread_id` of the live thread subscriptions.
+    pub(crate) live_subscriptions: HashSet<String>,
+    /// From the last `probe_remote_quality`.
+    pub(crate) link_poll_ms: Option<u64>,
+    pub(crate) link_path: Option<RemoteLinkPath>,
+}
+
+/// Calls to the remote backend since launch, for
+/// `get_remote_backend_metrics`.
+#[derive(Default)]
+pub(crate) struct RemoteMetrics {
+    pub(crate) methods: HashMap<String, RemoteMethodMetrics>,
+    /// Oldest first.
+    pub(crate) slow_calls: VecDeque<RemoteSlowCall>,
+}
+
+/// Serializes starts and stops of one managed process and records which is
+/// under way. A request for an operation that is already running or queued
+/// is rejected, since it would only repeat it; a conflicting one, like a
+/// stop during a start, queues behind it.
+#[derive(Default)]
+pub(crate) struct OperationGuard {
+    lock: Mutex<()>,
+    slots: StdMutex<OperationSlots>,
+}
+
+#[derive(Default)]
+struct OperationSlots {
+    in_flight: Option<ProcessOperation>,
+    queued: Vec<ProcessOperation>,
+}
+
+/// Held for the length of an operation; frees the guard when dropped.
+pub(crate) struct OperationPermit<'a> {
+    guard: &'a OperationGuard,
+    _lock: MutexGuard<'a, ()>,
+}
+
+/// Takes a queued operation off the list if its caller gives up waiting.
+struct QueuedOperation<'a> {
+    guard: &'a OperationGuard,
+    operation: ProcessOperation,
+}
+
+impl OperationGuard {
+    fn slots(&self) -> std::sync::MutexGuard<'_, OperationSlots> {
+        self.slots.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    pub(crate) fn in_flight(&self) -> Option<ProcessOperation> {
+        self.slots().in_flight
+    }
+
+    /// Waits for any other operation to finish, then marks `operation` as
+    /// under way.
+    pub(crate) async fn begin(
+        &self,
+        operation: ProcessOperation,
+    ) -> Result<OperationPermit<'_>, String> {
+        {
+            let mut slots = self.slots();
+            if slots.in_flight == Some(operation) || slots.queued.contains(&operation) {
+                let action = match operation {
+                    ProcessOperation::Starting => "start",
+                    ProcessOperation::Stopping => "stop",
+                    ProcessOperation::Restarting => "restart",
+                };
+                return Err(format!("A daemon {action} is already in progress."));
+            }
+            slots.queued.push(operation);
+        }
+        let queued = QueuedOperation {
+            guard: self,
+            operation,
+        };
+        let lock = self.lock.lock().await;
+        drop(queued);
+        self.slots().in_flight = Some(operation);
+        Ok(OperationPermit {
+            guard: self,
+            _lock: lock,
+        })
+    }
+
+    /// Holds the guard only when nothing is under way.
+    pub(crate) fn try_idle(&self) -> Option<MutexGuard<'_, ()>> {
+        self.lock.try_lock().ok()
+    }
+}
+
+impl Drop for QueuedOperation<'_> {
+    fn drop(&mut self) {
+        let mut slots = self.guard.slots();
+        if let Some(index) = slots.queued.iter().position(|op| *op == self.operation) {
+            slots.queued.remove(index);
+        }
+    }
+}
+
+impl Drop for OperationPermit<'_> {
+    fn drop(&mut self) {
+        self.guard.slots().in_flight = None;
+    }
+}
+
+/// What the daemon watchdog last saw on its heartbeat connection.
+#[derive(Clone, Debug, Default)]
+pub(crate) enum DaemonLiveness {
+    #[default]
+    Unknown,
+    Alive {
+        listen_addr: String,
+        pid: Option<u32>,
+        last_beat: Instant,
+    },
+    Unreachable {
+        listen_addr: String,
+        checked_at: Instant,
+    },
 }
 
 impl Default for TcpDaemonRuntime {
@@ -25,7 +179,16 @@ impl Default for TcpDaemonRuntime {
                 started_at_ms: None,
                 last_error: None,
                 listen_addr: None,
+                firewall_trust: None,
+                service_manager: None,
+                operation: None,
+                launch: None,
+                managed_externally: false,
+                last_stop: None,
+                update_pending: false,
             },
+            launch: None,
+            adopted_pid: None,
         }
     }
 }
@@ -34,36 +197,178 @@ pub(crate) struct AppState {
     pub(crate) workspaces: Mutex<HashMap<String, WorkspaceEntry>>,
     pub(crate) sessions: Mutex<HashMap<String, Arc<crate::codex::WorkspaceSession>>>,
     pub(crate) terminal_sessions: Mutex<HashMap<String, Arc<crate::terminal::TerminalSession>>>,
+    pub(crate) session_runtimes: Arc<SessionRegistry>,
     pub(crate) remote_backend: Mutex<Option<crate::remote_backend::RemoteBackend>>,
+    pub(crate) remote_activity: Mutex<RemoteActivity>,
+    pub(crate) remote_metrics: Mutex<RemoteMetrics>,
     pub(crate) storage_path: PathBuf,
     pub(crate) settings_path: PathBuf,
-    pub(crate) app_settings: Mutex<AppSettings>,
+    pub(crate) app_settings: RwLock<AppSettings>,
     pub(crate) dictation: Mutex<DictationState>,
     pub(crate) codex_login_cancels: Mutex<HashMap<String, CodexLoginCancelState>>,
+    /// The `codex login --device-auth` run `codex_login_start` started.
+    pub(crate) codex_cli_login: CodexCliLoginSlot,
     pub(crate) tcp_daemon: Mutex<TcpDaemonRuntime>,
+    /// What the Tailscale and daemon commands act on: the real ones, or the
+    /// fakes `--mock-backends` selects.
+    pub(crate) backends: crate::tailscale::Backends,
+    /// Serializes daemon start and stop. Held across their probes and kill
+    /// loops so `tcp_daemon` itself is only locked briefly.
+    pub(crate) tcp_daemon_lifecycle: OperationGuard,
+    pub(crate) daemon_liveness: Mutex<DaemonLiveness>,
+    pub(crate) tailscale_status: Mutex<TailscaleStatusCache>,
+    /// Recent `tailscale_ping_peer` results, oldest first.
+    pub(crate) tailscale_pings: Mutex<VecDeque<TailscalePingResult>>,
+    /// Unix milliseconds until which notifications are snoozed.
+    pub(crate) notifications_snoozed_until_ms: Mutex<Option<i64>>,
+    pub(crate) ci_status: Mutex<CiStatusCache>,
+    /// Branches sessions touched, by project id and branch, waiting for CI.
+    pub(crate) ci_watches: Mutex<HashMap<(String, String), crate::ci_status::CiWatch>>,
+    /// Coalesces the status events sent to the webview.
+    pub(crate) event_bus: crate::event_bus::EventBus,
+}
+
+/// The platform's app data directory. Its `settings.json` records where the
+/// data actually lives when it has been moved.
+pub(crate) fn default_data_dir(app: &AppHandle) -> PathBuf {
+    app.path()
+        .app_data_dir()
+        .unwrap_or_else(|_| std::env::current_dir().unwrap_or_else(|_| ".".into()))
 }
 
 impl AppState {
     pub(crate) fn load(app: &AppHandle) -> Self {
-        let data_dir = app
-            .path()
-            .app_data_dir()
-            .unwrap_or_else(|_| std::env::current_dir().unwrap_or_else(|_| ".".into()));
+        let data_dir = resolve_data_dir(&default_data_dir(app));
         let storage_path = data_dir.join("workspaces.json");
         let settings_path = data_dir.join("settings.json");
         let workspaces = read_workspaces(&storage_path).unwrap_or_default();
-        let app_settings = read_settings(&settings_path).unwrap_or_default();
+        let app_settings = crate::startup::measure("settings_load", || {
+            read_settings(&settings_path).unwrap_or_default()
+        });
+        set_codex_process_env(&app_settings.codex_env);
+        set_codex_home_override(app_settings.codex_home_override.as_deref());
+        set_redaction(&app_settings.redaction);
+        set_local_client(&data_dir);
+        set_config_changelog_dir(&data_dir);
+        let daemon_data_dir =
+            resolve_daemon_data_dir(&data_dir, app_settings.daemon_data_dir.as_deref());
+        match migrate_daemon_state(&data_dir, &daemon_data_dir) {
+            Ok(moved) if !moved.is_empty() => eprintln!(
+                "Moved daemon state to {}: {}",
+                daemon_data_dir.display(),
+                moved.join(", ")
+            ),
+            Ok(_) => {}
+            Err(err) => eprintln!("Failed to move daemon state: {err}"),
+        }
+        let backends = crate::tailscale::Backends::for_launch(&app_settings, &data_dir);
+        let mut tcp_daemon = TcpDaemonRuntime::default();
+        tcp_daemon.status.last_stop = crate::tailscale::last_daemon_stop(&data_dir);
+        Self::from_parts(
+            &data_dir,
+            workspaces,
+            app_settings,
+            tcp_daemon,
+            backends,
+            crate::event_bus::EventBus::new(app.clone()),
+        )
+    }
+
+    /// State over `data_dir` with nothing loaded from it, acting through
+    /// `backends`.
+    #[cfg(test)]
+    pub(crate) fn for_tests(data_dir: &Path, backends: crate::tailscale::Backends) -> Self {
+        Self::from_parts(
+            data_dir,
+            HashMap::new(),
+            AppSettings::default(),
+            TcpDaemonRuntime::default(),
+            backends,
+            crate::event_bus::EventBus::default(),
+        )
+    }
+
+    /// The directory holding `settings.json` and the rest of the app's
+    /// state, wherever `dataDir` moved it.
+    pub(crate) fn data_dir(&self) -> &Path {
+        self.settings_path.parent().unwrap_or(Path::new("."))
+    }
+
+    fn from_parts(
+        data_dir: &Path,
+        workspaces: HashMap<String, WorkspaceEntry>,
+        app_settings: AppSettings,
+        tcp_daemon: TcpDaemonRuntime,
+        backends: crate::tailscale::Backends,
+        event_bus: crate::event_bus::EventBus,
+    ) -> Self {
         Self {
             workspaces: Mutex::new(workspaces),
             sessions: Mutex::new(HashMap::new()),
             terminal_sessions: Mutex::new(HashMap::new()),
+            session_runtimes: Arc::new(SessionRegistry::default()),
             remote_backend: Mutex::new(None),
-            storage_path,
-            settings_path,
-            app_settings: Mutex::new(app_settings),
+            remote_activity: Mutex::new(RemoteActivity::default()),
+            remote_metrics: Mutex::new(RemoteMetrics::default()),
+            storage_path: data_dir.join("workspaces.json"),
+            settings_path: data_dir.join("settings.json"),
+            app_settings: RwLock::new(app_settings),
             dictation: Mutex::new(DictationState::default()),
             codex_login_cancels: Mutex::new(HashMap::new()),
-            tcp_daemon: Mutex::new(TcpDaemonRuntime::default()),
+            codex_cli_login: CodexCliLoginSlot::default(),
+            tcp_daemon: Mutex::new(tcp_daemon),
+            backends,
+            tcp_daemon_lifecycle: OperationGuard::default(),
+            daemon_liveness: Mutex::new(DaemonLiveness::default()),
+            tailscale_status: Mutex::new(TailscaleStatusCache::default()),
+            tailscale_pings: Mutex::new(VecDeque::new()),
+            notifications_snoozed_until_ms: Mutex::new(None),
+            ci_status: Mutex::new(CiStatusCache::default()),
+            ci_watches: Mutex::new(HashMap::new()),
+            event_bus,
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::task::Context;
+
+    use futures_util::task::noop_waker_ref;
+    use futures_util::FutureExt;
+
+    use super::*;
+
+    #[test]
+    fn operation_guard_rejects_repeats_and_queues_conflicts() {
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .expect("runtime");
+        let guard = OperationGuard::default();
+
+        let start = runtime
+            .block_on(guard.begin(ProcessOperation::Starting))
+            .expect("start");
+        assert_eq!(guard.in_flight(), Some(ProcessOperation::Starting));
+        assert!(runtime
+            .block_on(guard.begin(ProcessOperation::Starting))
+            .is_err());
+        assert!(guard.try_idle().is_none());
+
+        // A stop waits for the start, and a second stop is refused meanwhile.
+        let mut stop = Box::pin(guard.begin(ProcessOperation::Stopping));
+        let mut cx = Context::from_waker(noop_waker_ref());
+        assert!(stop.poll_unpin(&mut cx).is_pending());
+        assert!(runtime
+            .block_on(guard.begin(ProcessOperation::Stopping))
+            .is_err());
+
+        drop(start);
+        let stop = runtime.block_on(stop).expect("stop");
+        assert_eq!(guard.in_flight(), Some(ProcessOperation::Stopping));
+        drop(stop);
+        assert_eq!(guard.in_flight(), None);
+        assert!(guard.try_idle().is_some());
+    }
+}
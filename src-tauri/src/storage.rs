@@ -1,7 +1,10 @@
 use std::collections::HashMap;
 use std::path::PathBuf;
 
-use crate::types::{AppSettings, WorkspaceEntry, WorkspaceSettings};
+use crate::types::{
+    AppSettings, ClientApproval, GuestTokenRecord, Schedule, SessionTemplate, TagStore,
+    WorkspaceEntry, WorkspaceSettings,
+};
 use serde_json::Value;
 
 fn normalize_windows_namespace_path(path: &str) -> String {
@@ -179,6 +182,95 @@ pub(crate) fn write_settings(path: &PathBuf, settings: &AppSettings) -> Result<(
     std::fs::write(path, data).map_err(|e| e.to_string())
 }
 
+pub(crate) fn read_session_templates(path: &PathBuf) -> Result<Vec<SessionTemplate>, String> {
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+    let data = std::fs::read_to_string(path).map_err(|e| e.to_string())?;
+    serde_json::from_str(&data).map_err(|e| e.to_string())
+}
+
+pub(crate) fn write_session_templates(
+    path: &PathBuf,
+    templates: &[SessionTemplate],
+) -> Result<(), String> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+    }
+    let data = serde_json::to_string_pretty(templates).map_err(|e| e.to_string())?;
+    std::fs::write(path, data).map_err(|e| e.to_string())
+}
+
+pub(crate) fn read_tags(path: &PathBuf) -> Result<TagStore, String> {
+    if !path.exists() {
+        return Ok(TagStore::default());
+    }
+    let data = std::fs::read_to_string(path).map_err(|e| e.to_string())?;
+    serde_json::from_str(&data).map_err(|e| e.to_string())
+}
+
+pub(crate) fn write_tags(path: &PathBuf, store: &TagStore) -> Result<(), String> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+    }
+    let data = serde_json::to_string_pretty(store).map_err(|e| e.to_string())?;
+    std::fs::write(path, data).map_err(|e| e.to_string())
+}
+
+pub(crate) fn read_schedules(path: &PathBuf) -> Result<Vec<Schedule>, String> {
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+    let data = std::fs::read_to_string(path).map_err(|e| e.to_string())?;
+    serde_json::from_str(&data).map_err(|e| e.to_string())
+}
+
+pub(crate) fn write_schedules(path: &PathBuf, schedules: &[Schedule]) -> Result<(), String> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+    }
+    let data = serde_json::to_string_pretty(schedules).map_err(|e| e.to_string())?;
+    std::fs::write(path, data).map_err(|e| e.to_string())
+}
+
+pub(crate) fn read_guest_tokens(path: &PathBuf) -> Result<Vec<GuestTokenRecord>, String> {
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+    let data = std::fs::read_to_string(path).map_err(|e| e.to_string())?;
+    serde_json::from_str(&data).map_err(|e| e.to_string())
+}
+
+pub(crate) fn write_guest_tokens(
+    path: &PathBuf,
+    tokens: &[GuestTokenRecord],
+) -> Result<(), String> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+    }
+    let data = serde_json::to_string_pretty(tokens).map_err(|e| e.to_string())?;
+    std::fs::write(path, data).map_err(|e| e.to_string())
+}
+
+pub(crate) fn read_client_approvals(path: &PathBuf) -> Result<Vec<ClientApproval>, String> {
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+    let data = std::fs::read_to_string(path).map_err(|e| e.to_string())?;
+    serde_json::from_str(&data).map_err(|e| e.to_string())
+}
+
+pub(crate) fn write_client_approvals(
+    path: &PathBuf,
+    approvals: &[ClientApproval],
+) -> Result<(), String> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+    }
+    let data = serde_json::to_string_pretty(approvals).map_err(|e| e.to_string())?;
+    std::fs::write(path, data).map_err(|e| e.to_string())
+}
+
 fn finalize_loaded_settings(path: &PathBuf, settings: AppSettings) -> AppSettings {
     let (settings, changed) = normalize_app_settings(settings);
     if changed {
@@ -232,8 +324,13 @@ fn migrate_follow_up_message_behavior(value: &mut Value) {
 
 #[cfg(test)]
 mod tests {
-    use super::{read_settings, read_workspaces, write_settings, write_workspaces};
-    use crate::types::{AppSettings, WorkspaceEntry, WorkspaceKind, WorkspaceSettings};
+    use super::{
+        read_session_templates, read_settings, read_workspaces, write_session_templates,
+        write_settings, write_workspaces,
+    };
+    use crate::types::{
+        AppSettings, SessionTemplate, WorkspaceEntry, WorkspaceKind, WorkspaceSettings,
+    };
     use uuid::Uuid;
 
     #[test]
@@ -469,4 +566,29 @@ mod tests {
         let settings = read_settings(&path).expect("read settings");
         assert_eq!(settings.follow_up_message_behavior, "queue");
     }
+
+    #[test]
+    fn write_read_session_templates_round_trips() {
+        let temp_dir = std::env::temp_dir().join(format!("codex-monitor-test-{}", Uuid::new_v4()));
+        let path = temp_dir.join("session-templates.json");
+        assert!(read_session_templates(&path)
+            .expect("missing file reads as empty")
+            .is_empty());
+
+        let template = SessionTemplate {
+            id: "t1".to_string(),
+            name: "Fix failing tests".to_string(),
+            prompt: "Run the test suite and fix failures".to_string(),
+            model: Some("gpt-5".to_string()),
+            approval_mode: Some("on-request".to_string()),
+            working_dir: Some("/tmp".to_string()),
+            env: [("CI".to_string(), "1".to_string())].into_iter().collect(),
+            created_at_ms: 1,
+            updated_at_ms: 2,
+        };
+        write_session_templates(&path, std::slice::from_ref(&template)).expect("write templates");
+        let read = read_session_templates(&path).expect("read templates");
+        assert_eq!(read, vec![template]);
+        let _ = std::fs::remove_dir_all(&temp_dir);
+    }
 }
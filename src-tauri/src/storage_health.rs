@@ -0,0 +1,69 @@
+use std::path::PathBuf;
+use std::time::Duration;
+
+use tauri::{AppHandle, Manager, State};
+
+use crate::command_error::CommandResult;
+use crate::notifications::show_system_notification;
+use crate::shared::storage_health_core::{
+    clean_storage_core, free_disk_bytes, is_low_space, storage_health_core,
+};
+use crate::state::AppState;
+use crate::types::{StorageCleanReport, StorageHealth, StorageTarget};
+
+const LOW_SPACE_CHECK_INTERVAL: Duration = Duration::from_secs(5 * 60);
+
+/// Free space and what the data directory holds. Measured on demand since
+/// session worktrees can make the directory slow to walk.
+#[tauri::command]
+pub(crate) async fn get_storage_health(state: State<'_, AppState>) -> CommandResult<StorageHealth> {
+    let data_dir = state.data_dir().to_path_buf();
+    let threshold_mb = state.app_settings.read().await.low_disk_space_threshold_mb;
+    let health = tokio::task::spawn_blocking(move || storage_health_core(&data_dir, threshold_mb))
+        .await
+        .map_err(|err| err.to_string())?;
+    Ok(health)
+}
+
+#[tauri::command]
+pub(crate) async fn clean_storage(
+    targets: Vec<StorageTarget>,
+    state: State<'_, AppState>,
+) -> CommandResult<StorageCleanReport> {
+    let data_dir = state.data_dir().to_path_buf();
+    let report = tokio::task::spawn_blocking(move || clean_storage_core(&data_dir, &targets))
+        .await
+        .map_err(|err| err.to_string())??;
+    Ok(report)
+}
+
+async fn notify_low_space(app: &AppHandle, free_bytes: u64) {
+    let body = format!(
+        "Only {} MiB left for CodexMonitor's data. Clean up logs and caches in Settings.",
+        free_bytes / (1024 * 1024)
+    );
+    show_system_notification(app, "Disk space is low", &body, false).await;
+}
+
+/// Checks free space in the data directory every few minutes and notifies
+/// once each time it drops below `lowDiskSpaceThresholdMb`.
+pub(crate) fn spawn_storage_monitor(app: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        let mut notified = false;
+        loop {
+            let state = app.state::<AppState>();
+            let data_dir = state.data_dir().to_path_buf();
+            let threshold_mb = state.app_settings.read().await.low_disk_space_threshold_mb;
+            let free_bytes = tokio::task::spawn_blocking(move || free_disk_bytes(&data_dir))
+                .await
+                .ok()
+                .flatten();
+            let low = is_low_space(free_bytes, threshold_mb);
+            if low && !notified {
+                notify_low_space(&app, free_bytes.unwrap_or(0)).await;
+            }
+            notified = low;
+            tokio::time::sleep(LOW_SPACE_CHECK_INTERVAL).await;
+        }
+    });
+}
@@ -0,0 +1,106 @@
+use tauri::{AppHandle, State};
+
+use crate::command_error::{CommandError, CommandResult};
+use crate::remote_backend;
+use crate::shared::{tags_core, workspace_rpc};
+use crate::state::AppState;
+use crate::types::{Tag, TagAssignments, TagInput, TagTarget};
+
+#[tauri::command]
+pub(crate) async fn list_tags(
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> CommandResult<Vec<Tag>> {
+    if remote_backend::is_remote_mode(&*state).await {
+        let response =
+            remote_backend::call_remote(&*state, app, "list_tags", serde_json::json!({})).await?;
+        return serde_json::from_value(response).map_err(|err| CommandError::from(err.to_string()));
+    }
+
+    tags_core::list_tags_core(state.data_dir()).map_err(CommandError::from)
+}
+
+#[tauri::command]
+pub(crate) async fn save_tag(
+    tag: TagInput,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> CommandResult<Tag> {
+    if remote_backend::is_remote_mode(&*state).await {
+        let request = workspace_rpc::SaveTagRequest { tag };
+        let response = remote_backend::call_remote(
+            &*state,
+            app,
+            "save_tag",
+            workspace_rpc::to_params(&request)?,
+        )
+        .await?;
+        return serde_json::from_value(response).map_err(|err| CommandError::from(err.to_string()));
+    }
+
+    tags_core::save_tag_core(state.data_dir(), tag).map_err(CommandError::from)
+}
+
+#[tauri::command]
+pub(crate) async fn delete_tag(
+    id: String,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> CommandResult<()> {
+    if remote_backend::is_remote_mode(&*state).await {
+        let request = workspace_rpc::IdRequest { id };
+        remote_backend::call_remote(
+            &*state,
+            app,
+            "delete_tag",
+            workspace_rpc::to_params(&request)?,
+        )
+        .await?;
+        return Ok(());
+    }
+
+    tags_core::delete_tag_core(state.data_dir(), &id).map_err(CommandError::from)
+}
+
+/// Replaces the tags of a session, or of a project by its workspace id.
+#[tauri::command]
+pub(crate) async fn set_tags(
+    target: TagTarget,
+    id: String,
+    tag_ids: Vec<String>,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> CommandResult<Vec<String>> {
+    if remote_backend::is_remote_mode(&*state).await {
+        let request = workspace_rpc::SetTagsRequest {
+            target,
+            id,
+            tag_ids,
+        };
+        let response = remote_backend::call_remote(
+            &*state,
+            app,
+            "set_tags",
+            workspace_rpc::to_params(&request)?,
+        )
+        .await?;
+        return serde_json::from_value(response).map_err(|err| CommandError::from(err.to_string()));
+    }
+
+    tags_core::set_tags_core(state.data_dir(), target, &id, tag_ids).map_err(CommandError::from)
+}
+
+#[tauri::command]
+pub(crate) async fn get_tag_assignments(
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> CommandResult<TagAssignments> {
+    if remote_backend::is_remote_mode(&*state).await {
+        let response =
+            remote_backend::call_remote(&*state, app, "get_tag_assignments", serde_json::json!({}))
+                .await?;
+        return serde_json::from_value(response).map_err(|err| CommandError::from(err.to_string()));
+    }
+
+    tags_core::get_tag_assignments_core(state.data_dir()).map_err(CommandError::from)
+}
@@ -1,12 +1,16 @@
+use std::collections::BTreeMap;
 use std::path::Path;
 
 use serde::Deserialize;
 use serde_json::Value;
 
-use crate::types::{TailscaleDaemonCommandPreview, TailscaleStatus};
+use crate::shared::process_core::CommandPreview;
+use crate::types::{PreviewShell, RemoteLinkPath, TailscaleDaemonCommandPreview, TailscaleStatus};
 
 const DEFAULT_DAEMON_LISTEN_ADDR: &str = "0.0.0.0:4732";
-const REMOTE_TOKEN_PLACEHOLDER: &str = "<remote-backend-token>";
+pub(super) const REMOTE_TOKEN_PLACEHOLDER: &str = "<remote-backend-token>";
+/// How far ahead of its expiry a node key counts as expiring soon.
+const KEY_EXPIRY_WARNING_MS: i64 = 7 * 24 * 60 * 60 * 1000;
 
 pub(crate) fn unavailable_status(version: Option<String>, message: String) -> TailscaleStatus {
     TailscaleStatus {
@@ -20,6 +24,11 @@ pub(crate) fn unavailable_status(version: Option<String>, message: String) -> Ta
         ipv6: Vec::new(),
         suggested_remote_host: None,
         message,
+        tags: Vec::new(),
+        key_expiry_ms: None,
+        key_expiring_soon: false,
+        stale: false,
+        last_seen_ms: None,
     }
 }
 
@@ -178,6 +187,26 @@ pub(crate) fn status_from_json(
         .filter(|value| !value.is_empty())
         .map(str::to_string);
 
+    let tags = self_node
+        .and_then(|node| node.get("Tags"))
+        .and_then(Value::as_array)
+        .map(|items| {
+            items
+                .iter()
+                .filter_map(Value::as_str)
+                .map(str::trim)
+                .filter(|value| !value.is_empty())
+                .map(str::to_string)
+                .collect::<Vec<_>>()
+        })
+        .unwrap_or_default();
+    let key_expiry_ms = self_node
+        .and_then(|node| node.get("KeyExpiry"))
+        .and_then(Value::as_str)
+        .and_then(|value| chrono::DateTime::parse_from_rfc3339(value.trim()).ok())
+        .map(|expiry| expiry.timestamp_millis());
+    let key_expiring_soon = key_expiring_soon(key_expiry_ms, chrono::Utc::now().timestamp_millis());
+
     let ip_values = self_node
         .and_then(|node| node.get("TailscaleIPs"))
         .and_then(Value::as_array)
@@ -209,7 +238,7 @@ pub(crate) fn status_from_json(
             "Tailscale is connected.".to_string()
         }
     } else if let Some(state) = backend_state.as_deref() {
-        format!("Tailscale backend state: {state}.")
+        backend_state_message(state)
     } else {
         "Tailscale is not running.".to_string()
     };
@@ -225,9 +254,39 @@ pub(crate) fn status_from_json(
         ipv6,
         suggested_remote_host,
         message,
+        tags,
+        key_expiry_ms,
+        key_expiring_soon,
+        stale: false,
+        last_seen_ms: None,
     })
 }
 
+/// What keeps a Tailscale that is not `Running` off the tailnet, and how to
+/// get it on.
+fn backend_state_message(state: &str) -> String {
+    match state {
+        "Stopped" | "NoState" => format!(
+            "Tailscale is installed but not connected ({state}). Connect it from the \
+             Tailscale menu or run `tailscale up`."
+        ),
+        "NeedsLogin" => "Tailscale is installed but not connected: it is signed out \
+             (NeedsLogin). Sign in from the Tailscale menu or run `tailscale login`."
+            .to_string(),
+        "NeedsMachineAuth" => "Tailscale is installed but not connected: an admin has \
+             to approve this device in the tailnet admin console (NeedsMachineAuth)."
+            .to_string(),
+        "Starting" => "Tailscale is connecting.".to_string(),
+        other => format!("Tailscale backend state: {other}."),
+    }
+}
+
+/// Whether a node key expiring at `key_expiry_ms` is within
+/// `KEY_EXPIRY_WARNING_MS` of `now_ms` or already expired.
+pub(crate) fn key_expiring_soon(key_expiry_ms: Option<i64>, now_ms: i64) -> bool {
+    key_expiry_ms.is_some_and(|expiry| expiry - now_ms <= KEY_EXPIRY_WARNING_MS)
+}
+
 pub(crate) fn suggested_remote_host(
     dns_name: Option<&str>,
     ipv4: &[String],
@@ -248,29 +307,101 @@ pub(crate) fn suggested_remote_host(
     None
 }
 
+/// Finds the node `host` names (MagicDNS name, short host name or tailnet
+/// IP) in a status payload and reports how traffic reaches it, with the
+/// DERP region when it is relayed.
+pub(crate) fn peer_link_path(payload: &str, host: &str) -> (RemoteLinkPath, Option<String>) {
+    let Ok(json) = parse_status_json(payload) else {
+        return (RemoteLinkPath::Unknown, None);
+    };
+    let host = trim_dns_name(host).to_ascii_lowercase();
+    let names_host = |node: &Value| {
+        let dns_name = node
+            .get("DNSName")
+            .and_then(Value::as_str)
+            .map(|value| trim_dns_name(value).to_ascii_lowercase())
+            .unwrap_or_default();
+        let short_name = dns_name.split('.').next().unwrap_or_default();
+        let host_name = node
+            .get("HostName")
+            .and_then(Value::as_str)
+            .map(|value| value.trim().to_ascii_lowercase())
+            .unwrap_or_default();
+        let has_ip = node
+            .get("TailscaleIPs")
+            .and_then(Value::as_array)
+            .is_some_and(|ips| ips.iter().any(|ip| ip.as_str() == Some(host.as_str())));
+        (!dns_name.is_empty() && (dns_name == host || short_name == host))
+            || (!host_name.is_empty() && host_name == host)
+            || has_ip
+    };
+
+    if json.get("Self").is_some_and(names_host) {
+        return (RemoteLinkPath::Local, None);
+    }
+    let Some(peer) = json
+        .get("Peer")
+        .and_then(Value::as_object)
+        .and_then(|peers| peers.values().find(|peer| names_host(peer)))
+    else {
+        return (RemoteLinkPath::Unknown, None);
+    };
+    let field = |name: &str| {
+        peer.get(name)
+            .and_then(Value::as_str)
+            .map(str::trim)
+            .filter(|value| !value.is_empty())
+    };
+    if field("CurAddr").is_some() {
+        return (RemoteLinkPath::Direct, None);
+    }
+    match field("Relay") {
+        Some(region) => (RemoteLinkPath::Relay, Some(region.to_string())),
+        None => (RemoteLinkPath::Unknown, None),
+    }
+}
+
+/// The login of the tailnet user this machine is signed in as, from the
+/// `User` table of a status payload.
+pub(crate) fn self_user_login(payload: &str) -> Option<String> {
+    let json = parse_status_json(payload).ok()?;
+    let user_id = json.get("Self")?.get("UserID")?.as_u64()?;
+    let user = json.get("User")?.get(user_id.to_string())?;
+    ["LoginName", "DisplayName"].iter().find_map(|field| {
+        user.get(field)
+            .and_then(Value::as_str)
+            .map(str::trim)
+            .filter(|value| !value.is_empty())
+            .map(str::to_string)
+    })
+}
+
 pub(crate) fn daemon_command_preview(
     daemon_path: &Path,
     data_dir: &Path,
+    app_data_dir: &Path,
     token_configured: bool,
+    env: BTreeMap<String, String>,
 ) -> TailscaleDaemonCommandPreview {
-    let daemon_path_str = daemon_path.to_string_lossy().to_string();
-    let data_dir_str = data_dir.to_string_lossy().to_string();
-    let args = vec![
-        "--listen".to_string(),
-        DEFAULT_DAEMON_LISTEN_ADDR.to_string(),
-        "--data-dir".to_string(),
-        data_dir_str.clone(),
-        "--token".to_string(),
-        REMOTE_TOKEN_PLACEHOLDER.to_string(),
-    ];
-    let mut rendered = Vec::with_capacity(args.len() + 1);
-    rendered.push(shell_quote(&daemon_path_str));
-    rendered.extend(args.iter().map(|value| shell_quote(value)));
+    let preview = CommandPreview::new(daemon_path)
+        .arg("--listen")
+        .arg(DEFAULT_DAEMON_LISTEN_ADDR)
+        .arg("--data-dir")
+        .arg(data_dir)
+        .arg("--app-data-dir")
+        .arg(app_data_dir)
+        .secret_arg("--token", REMOTE_TOKEN_PLACEHOLDER)
+        .envs(env)
+        .current_dir(std::env::current_dir().ok());
+    let shell = PreviewShell::native();
 
     TailscaleDaemonCommandPreview {
-        command: rendered.join(" "),
-        daemon_path: daemon_path_str,
-        args,
+        command: preview.render(shell),
+        shell,
+        daemon_path: preview.program().to_string(),
+        args: preview.args().to_vec(),
+        env: preview.env().clone(),
+        working_dir: preview.working_dir().map(str::to_string),
         token_configured,
     }
 }
@@ -279,22 +410,16 @@ fn trim_dns_name(value: &str) -> &str {
     value.trim().trim_end_matches('.')
 }
 
-fn shell_quote(value: &str) -> String {
-    if value.is_empty() {
-        return "''".to_string();
-    }
-    if cfg!(windows) {
-        format!("\"{}\"", value.replace('"', "\\\""))
-    } else {
-        format!("'{}'", value.replace('\'', "'\"'\"'"))
-    }
-}
-
 #[cfg(test)]
 mod tests {
+    use std::collections::BTreeMap;
     use std::path::Path;
 
-    use super::{daemon_command_preview, status_from_json, suggested_remote_host};
+    use super::{
+        daemon_command_preview, key_expiring_soon, peer_link_path, self_user_login,
+        status_from_json, suggested_remote_host,
+    };
+    use crate::types::RemoteLinkPath;
 
     #[test]
     fn status_from_json_extracts_running_fields() {
@@ -320,6 +445,34 @@ mod tests {
             status.suggested_remote_host.as_deref(),
             Some("macbook.example.ts.net:4732")
         );
+        assert!(status.tags.is_empty());
+        assert!(status.key_expiry_ms.is_none());
+        assert!(!status.key_expiring_soon);
+    }
+
+    #[test]
+    fn status_from_json_reads_tags_and_key_expiry() {
+        let payload = r#"{
+          "BackendState": "Running",
+          "Self": {
+            "DNSName": "server.example.ts.net.",
+            "Tags": ["tag:server", " tag:codex "],
+            "KeyExpiry": "2020-01-02T03:04:05Z"
+          }
+        }"#;
+
+        let status = status_from_json(None, payload).expect("status");
+        assert_eq!(
+            status.tags,
+            vec!["tag:server".to_string(), "tag:codex".to_string()]
+        );
+        assert_eq!(status.key_expiry_ms, Some(1_577_934_245_000));
+        assert!(status.key_expiring_soon);
+
+        let day_ms = 24 * 60 * 60 * 1000;
+        assert!(key_expiring_soon(Some(6 * day_ms), 0));
+        assert!(!key_expiring_soon(Some(8 * day_ms), 0));
+        assert!(!key_expiring_soon(None, 0));
     }
 
     #[test]
@@ -329,6 +482,12 @@ mod tests {
         let status = status_from_json(None, payload).expect("status");
         assert!(!status.running);
         assert!(status.message.contains("NeedsLogin"));
+        assert!(status.message.contains("tailscale login"));
+
+        let status = status_from_json(None, r#"{"BackendState":"Stopped"}"#).expect("status");
+        assert!(status
+            .message
+            .starts_with("Tailscale is installed but not connected"));
     }
 
     #[test]
@@ -418,6 +577,67 @@ extra diagnostics line"#;
         assert_eq!(status.dns_name.as_deref(), Some("host.example.ts.net"));
     }
 
+    #[test]
+    fn peer_link_path_reads_current_address_and_relay() {
+        let payload = r#"{
+          "BackendState": "Running",
+          "Self": { "DNSName": "laptop.example.ts.net.", "TailscaleIPs": ["100.64.0.1"] },
+          "Peer": {
+            "nodekey:a": {
+              "DNSName": "studio.example.ts.net.",
+              "HostName": "Studio",
+              "TailscaleIPs": ["100.64.0.2"],
+              "CurAddr": "192.0.2.10:41641",
+              "Relay": "fra"
+            },
+            "nodekey:b": {
+              "DNSName": "builder.example.ts.net.",
+              "TailscaleIPs": ["100.64.0.3"],
+              "CurAddr": "",
+              "Relay": "nyc"
+            }
+          }
+        }"#;
+
+        assert_eq!(
+            peer_link_path(payload, "studio.example.ts.net"),
+            (RemoteLinkPath::Direct, None)
+        );
+        assert_eq!(
+            peer_link_path(payload, "studio"),
+            (RemoteLinkPath::Direct, None)
+        );
+        assert_eq!(
+            peer_link_path(payload, "100.64.0.3"),
+            (RemoteLinkPath::Relay, Some("nyc".to_string()))
+        );
+        assert_eq!(
+            peer_link_path(payload, "laptop.example.ts.net."),
+            (RemoteLinkPath::Local, None)
+        );
+        assert_eq!(
+            peer_link_path(payload, "example.com"),
+            (RemoteLinkPath::Unknown, None)
+        );
+    }
+
+    #[test]
+    fn self_user_login_reads_the_user_table() {
+        let payload = r#"{
+          "BackendState": "Running",
+          "Self": { "DNSName": "laptop.example.ts.net.", "UserID": 42 },
+          "User": { "42": { "LoginName": "admin@example.com", "DisplayName": "Admin" } }
+        }"#;
+        assert_eq!(
+            self_user_login(payload).as_deref(),
+            Some("admin@example.com")
+        );
+        assert_eq!(
+            self_user_login(r#"{"BackendState":"Running","Self":{}}"#),
+            None
+        );
+    }
+
     #[test]
     fn suggested_remote_host_falls_back_to_ipv6() {
         let host = suggested_remote_host(None, &[], &[String::from("fd7a:115c:a1e0::1")]);
@@ -428,10 +648,13 @@ extra diagnostics line"#;
     fn daemon_command_preview_uses_placeholder_token() {
         let preview = daemon_command_preview(
             Path::new("/tmp/codex_monitor_daemon"),
+            Path::new("/tmp/data-dir/daemon"),
             Path::new("/tmp/data-dir"),
             true,
+            BTreeMap::new(),
         );
         assert!(preview.command.contains("--listen"));
+        assert!(preview.command.contains("--app-data-dir"));
         assert!(preview.command.contains("0.0.0.0:4732"));
         assert!(preview.command.contains("<remote-backend-token>"));
         assert!(preview.token_configured);
@@ -1,11 +1,36 @@
+use std::path::{Path, PathBuf};
+
+use super::firewall;
 use super::rpc_client::{
     probe_daemon, request_daemon_shutdown, wait_for_daemon_shutdown, DaemonInfo, DaemonProbe,
 };
+use super::service;
+use super::stop_history;
+use super::watchdog::cached_daemon_probe;
 use super::*;
+use crate::command_error::{CommandError, CommandResult, ErrorCode};
+use crate::platform;
+use crate::shared::daemon_data_dir_core::resolve_daemon_data_dir;
+use crate::shared::data_dir_lock_core::{self, DataDirLock};
+use crate::shared::monitor_error::MonitorError;
+use crate::shared::process_core::CommandPreview;
+use crate::shared::process_env_core;
+use crate::shared::process_registry_core::{self, SpawnedProcessKind};
+use crate::state::DaemonLiveness;
+use crate::types::{
+    DaemonStop, DaemonStopReason, PlatformSubsystem, PreviewShell, ProcessOperation,
+};
 
 const EXPECTED_DAEMON_NAME: &str = "codex-monitor-daemon";
 const EXPECTED_DAEMON_MODE: &str = "tcp";
 const CURRENT_APP_VERSION: &str = env!("CARGO_PKG_VERSION");
+const SERVICE_START_TIMEOUT: Duration = Duration::from_secs(5);
+/// How long a spawned daemon has to answer on its port before the start is
+/// reported as failed.
+const SPAWN_READY_TIMEOUT: Duration = Duration::from_secs(10);
+/// Covers the daemon's drain period plus stopping its Codex sessions.
+const CHILD_SHUTDOWN_TIMEOUT: Duration = Duration::from_secs(5);
+const TCP_DAEMON_STATUS_CHANGED_EVENT: &str = "tcp-daemon-status-changed";
 
 fn is_managed_daemon(info: &DaemonInfo) -> bool {
     info.name == EXPECTED_DAEMON_NAME
@@ -46,8 +71,118 @@ fn daemon_restart_reason(info: Option<&DaemonInfo>) -> String {
     "Daemon restart required".to_string()
 }
 
-async fn resolve_daemon_pid(listen_port: u16, info: Option<&DaemonInfo>) -> Option<u32> {
-    match info.and_then(|entry| entry.pid) {
+/// Both hashes are known and differ, so the binary was replaced after the
+/// daemon was spawned from it.
+fn binary_replaced(spawned_sha256: Option<&str>, on_disk_sha256: Option<&str>) -> bool {
+    matches!((spawned_sha256, on_disk_sha256), (Some(spawned), Some(on_disk)) if spawned != on_disk)
+}
+
+/// Whether the daemon at `pid` is older than the daemon binary now on disk:
+/// built for another app version, or spawned from a binary that has since
+/// been replaced. Hashes are cached by size and mtime, so this only reads the
+/// binary again after it changes.
+fn daemon_update_pending(
+    data_dir: Option<&Path>,
+    pid: Option<u32>,
+    info: Option<&DaemonInfo>,
+) -> bool {
+    if info.is_some_and(|info| is_managed_daemon(info) && info.version != CURRENT_APP_VERSION) {
+        return true;
+    }
+    let spawned = data_dir
+        .zip(pid)
+        .and_then(|(data_dir, pid)| process_registry_core::recorded_binary_sha256(data_dir, pid));
+    if spawned.is_none() {
+        return false;
+    }
+    let on_disk = resolve_daemon_binary_path()
+        .ok()
+        .and_then(|path| process_registry_core::binary_sha256(&path));
+    binary_replaced(spawned.as_deref(), on_disk.as_deref())
+}
+
+/// The per-install secret a daemon must prove before it is sent the token.
+/// Absent until the app first pairs with a daemon by starting it.
+pub(super) async fn daemon_identity(state: &AppState) -> Option<String> {
+    daemon_identity_core::read_daemon_identity(&daemon_data_dir(state).await)
+}
+
+/// Where the managed daemon keeps its own state, from the `daemonDataDir`
+/// setting.
+pub(crate) async fn daemon_data_dir(state: &AppState) -> PathBuf {
+    let configured = state.app_settings.read().await.daemon_data_dir.clone();
+    resolve_daemon_data_dir(state.data_dir(), configured.as_deref())
+}
+
+/// Reaps an exited child and copies the runtime status. `tcp_daemon` is
+/// only held for this and for storing results; probes and kill loops run
+/// outside it, under `tcp_daemon_lifecycle`.
+async fn tcp_daemon_snapshot(state: &AppState) -> TcpDaemonStatus {
+    let mut runtime = state.tcp_daemon.lock().await;
+    reap_tcp_daemon(state, &mut runtime).await;
+    runtime.status.clone()
+}
+
+/// Runs `refresh_tcp_daemon_runtime`, recording a stop it discovers.
+async fn reap_tcp_daemon(state: &AppState, runtime: &mut TcpDaemonRuntime) {
+    let before = runtime.status.last_stop.clone();
+    refresh_tcp_daemon_runtime(runtime).await;
+    if runtime.status.last_stop != before {
+        if let Some(stop) = &runtime.status.last_stop {
+            record_stop(state, stop);
+        }
+    }
+}
+
+/// Appends `stop` to the stop history in the data directory.
+fn record_stop(state: &AppState, stop: &DaemonStop) {
+    if let Err(err) = stop_history::record_stop(state.data_dir(), stop) {
+        eprintln!("tailscale_daemon: failed to record daemon stop: {err}");
+    }
+}
+
+/// A stop of `reason` happening now, recorded in the stop history.
+fn stop_now(state: &AppState, reason: DaemonStopReason, detail: Option<String>) -> DaemonStop {
+    let stop = DaemonStop {
+        reason,
+        at_ms: now_unix_ms(),
+        detail,
+    };
+    record_stop(state, &stop);
+    stop
+}
+
+/// Stores `status`, announcing it on `tcp-daemon-status-changed` when it
+/// differs from the stored one. A status without a `last_stop` keeps the
+/// stored one.
+pub(super) fn store_tcp_daemon_status(
+    state: &AppState,
+    runtime: &mut TcpDaemonRuntime,
+    status: &TcpDaemonStatus,
+) {
+    let mut status = status.clone();
+    if status.last_stop.is_none() {
+        status.last_stop = runtime.status.last_stop.clone();
+    }
+    if runtime.status != status {
+        state
+            .event_bus
+            .publish(TCP_DAEMON_STATUS_CHANGED_EVENT, &status);
+    }
+    runtime.status = status;
+}
+
+/// Prefers the pid the daemon reports, then a spawn record whose exe and
+/// start time still match, and only then whoever listens on the port.
+async fn resolve_daemon_pid(
+    data_dir: Option<&Path>,
+    listen_port: u16,
+    info: Option<&DaemonInfo>,
+) -> Option<u32> {
+    if let Some(pid) = info.and_then(|entry| entry.pid) {
+        return Some(pid);
+    }
+    match data_dir.and_then(process_registry_core::recorded_daemon_pid) {
         Some(pid) => Some(pid),
         None => find_listener_pid(listen_port).await,
     }
@@ -55,19 +190,15 @@ async fn resolve_daemon_pid(listen_port: u16, info: Option<&DaemonInfo>) -> Opti
 
 pub(super) async fn tailscale_daemon_command_preview(
     state: State<'_, AppState>,
-) -> Result<TailscaleDaemonCommandPreview, String> {
+) -> Result<TailscaleDaemonCommandPreview, MonitorError> {
     #[cfg(any(target_os = "android", target_os = "ios"))]
     {
-        return Err(UNSUPPORTED_MESSAGE.to_string());
+        return Err(platform::unsupported(PlatformSubsystem::DaemonManagement));
     }
 
-    let daemon_path = resolve_daemon_binary_path()?;
-    let data_dir = state
-        .settings_path
-        .parent()
-        .map(|path| path.to_path_buf())
-        .ok_or_else(|| "Unable to resolve app data directory".to_string())?;
-    let settings = state.app_settings.lock().await.clone();
+    let daemon_path = resolve_daemon_binary_path().map_err(MonitorError::Process)?;
+    let data_dir = state.data_dir().to_path_buf();
+    let settings = state.app_settings.read().await.clone();
     let token_configured = settings
         .remote_backend_token
         .as_deref()
@@ -75,50 +206,130 @@ pub(super) async fn tailscale_daemon_command_preview(
         .map(|value| !value.is_empty())
         .unwrap_or(false);
 
+    let daemon_dir = resolve_daemon_data_dir(&data_dir, settings.daemon_data_dir.as_deref());
+
     Ok(tailscale_core::daemon_command_preview(
         &daemon_path,
+        &daemon_dir,
         &data_dir,
         token_configured,
+        process_env_core::env_preview(&process_env_core::effective_env(&settings.daemon_env, None)),
     ))
 }
 
+/// The managed daemon's identity secret, created if it does not exist yet,
+/// for pairing other devices with this host's daemon.
+pub(super) async fn tailscale_daemon_identity(state: &AppState) -> Result<String, MonitorError> {
+    #[cfg(any(target_os = "android", target_os = "ios"))]
+    {
+        return Err(platform::unsupported(PlatformSubsystem::DaemonManagement));
+    }
+
+    daemon_identity_core::ensure_daemon_identity(&daemon_data_dir(state).await)
+        .map_err(|err| MonitorError::Config(format!("Failed to prepare daemon identity: {err}")))
+}
+
+/// Marks the stored status with the operation now under way and the
+/// transition it puts the daemon in.
+async fn announce_operation(
+    state: &AppState,
+    operation: ProcessOperation,
+    transition: TcpDaemonState,
+) {
+    let mut runtime = state.tcp_daemon.lock().await;
+    let mut status = runtime.status.clone();
+    status.state = transition;
+    status.operation = Some(operation);
+    store_tcp_daemon_status(state, &mut runtime, &status);
+}
+
+/// Clears the operation from the stored status when the operation failed
+/// before storing its result, settling a transition it left behind on what
+/// the spawned child shows.
+async fn settle_operation(state: &AppState) {
+    let mut runtime = state.tcp_daemon.lock().await;
+    let announced = runtime.status.clone();
+    if announced.operation.is_none() && !is_transition(&announced.state) {
+        return;
+    }
+    if is_transition(&announced.state) {
+        reap_tcp_daemon(state, &mut runtime).await;
+    }
+    let mut status = std::mem::replace(&mut runtime.status, announced);
+    status.operation = None;
+    store_tcp_daemon_status(state, &mut runtime, &status);
+}
+
 pub(super) async fn tailscale_daemon_start(
-    state: State<'_, AppState>,
-) -> Result<TcpDaemonStatus, String> {
-    if cfg!(any(target_os = "android", target_os = "ios")) {
-        return Err("Tailscale daemon start is only supported on desktop.".to_string());
+    state: &AppState,
+    takeover: bool,
+) -> CommandResult<TcpDaemonStatus> {
+    if platform::is_mobile() {
+        return Err(platform::unsupported(PlatformSubsystem::DaemonManagement).into());
     }
 
-    let settings = state.app_settings.lock().await.clone();
+    let _lifecycle = state
+        .tcp_daemon_lifecycle
+        .begin(ProcessOperation::Starting)
+        .await
+        .map_err(|err| CommandError::new(ErrorCode::OperationInProgress, err))?;
+    announce_operation(state, ProcessOperation::Starting, TcpDaemonState::Starting).await;
+    let result = match start_daemon(state, takeover).await {
+        Ok(status) => await_daemon_ready(state, status).await,
+        Err(err) => Err(err),
+    };
+    settle_operation(state).await;
+    result
+}
+
+/// The error for a daemon data directory another live daemon holds.
+fn data_dir_locked_error(daemon_dir: &Path, lock: &DataDirLock) -> CommandError {
+    CommandError::new(
+        ErrorCode::DataDirLocked,
+        data_dir_lock_core::locked_message(daemon_dir, lock),
+    )
+    .with_details(json!({
+        "dataDir": daemon_dir,
+        "pid": lock.holder.pid,
+        "lockedAtMs": lock.locked_at_ms,
+    }))
+}
+
+/// Starts the daemon unless one is already running and current. With
+/// `takeover`, a daemon holding the data directory elsewhere is stopped
+/// first instead of failing with `DataDirLocked`.
+async fn start_daemon(state: &AppState, takeover: bool) -> CommandResult<TcpDaemonStatus> {
+    let settings = state.app_settings.read().await.clone();
     let token = settings
         .remote_backend_token
         .as_deref()
         .map(str::trim)
         .filter(|value| !value.is_empty())
         .ok_or_else(|| {
-            "Set a Remote backend token before starting mobile access daemon.".to_string()
+            CommandError::new(
+                ErrorCode::TokenMissing,
+                "Set a Remote backend token before starting mobile access daemon.",
+            )
         })?;
     let listen_addr = configured_daemon_listen_addr(&settings);
     let listen_port = parse_port_from_remote_host(&listen_addr)
         .ok_or_else(|| format!("Invalid daemon listen address: {listen_addr}"))?;
     let daemon_binary = resolve_daemon_binary_path()?;
 
-    let data_dir = state
-        .settings_path
-        .parent()
-        .map(|path| path.to_path_buf())
-        .ok_or_else(|| "Unable to resolve app data directory".to_string())?;
+    let data_dir = state.data_dir().to_path_buf();
+    let daemon_dir = resolve_daemon_data_dir(&data_dir, settings.daemon_data_dir.as_deref());
 
-    let mut runtime = state.tcp_daemon.lock().await;
-    refresh_tcp_daemon_runtime(&mut runtime).await;
+    let identity = daemon_identity_core::read_daemon_identity(&daemon_dir);
+
+    let previous = tcp_daemon_snapshot(state).await;
 
-    match probe_daemon(&listen_addr, Some(token)).await {
+    match probe_daemon(&listen_addr, Some(token), identity.as_deref()).await {
         DaemonProbe::Running {
             auth_ok,
             auth_error,
             info,
         } => {
-            let pid = resolve_daemon_pid(listen_port, info.as_ref()).await;
+            let pid = resolve_daemon_pid(Some(&data_dir), listen_port, info.as_ref()).await;
             let restart_required = should_restart_daemon(info.as_ref());
             let restart_reason = if restart_required {
                 Some(daemon_restart_reason(info.as_ref()))
@@ -126,35 +337,52 @@ pub(super) async fn tailscale_daemon_start(
                 None
             };
 
-            runtime.child = None;
-            runtime.status = TcpDaemonStatus {
+            let status = TcpDaemonStatus {
                 state: TcpDaemonState::Running,
                 pid,
-                started_at_ms: runtime.status.started_at_ms,
+                started_at_ms: previous.started_at_ms,
                 last_error: auth_error.clone(),
                 listen_addr: Some(listen_addr.clone()),
+                firewall_trust: None,
+                service_manager: None,
+                operation: None,
+                launch: None,
+                managed_externally: false,
+                last_stop: None,
+                update_pending: false,
             };
+            {
+                let mut runtime = state.tcp_daemon.lock().await;
+                runtime.child = None;
+                runtime.launch = None;
+                store_tcp_daemon_status(state, &mut runtime, &status);
+            }
             if !auth_ok {
-                return Err(auth_error.unwrap_or_else(|| {
-                    "Daemon is already running but authentication failed.".to_string()
-                }));
+                return Err(auth_error
+                    .unwrap_or_else(|| {
+                        "Daemon is already running but authentication failed.".to_string()
+                    })
+                    .into());
             }
             if !restart_required {
-                return Ok(runtime.status.clone());
+                return Ok(status);
             }
 
             let force_kill_allowed = can_force_stop_daemon(auth_ok, info.as_ref());
             let pid_for_control = pid;
-            if let Err(shutdown_error) = request_daemon_shutdown(&listen_addr, Some(token)).await {
+            if let Err(shutdown_error) =
+                request_daemon_shutdown(&listen_addr, Some(token), identity.as_deref()).await
+            {
                 if !force_kill_allowed {
                     return Err(format!(
                         "{}; automatic restart aborted because daemon ownership could not be verified: {}",
                         restart_reason.unwrap_or_else(|| "Daemon restart required".to_string()),
                         shutdown_error
-                    ));
+                    )
+                    .into());
                 }
                 if let Some(pid) = pid_for_control {
-                    kill_pid_gracefully(pid).await.map_err(|err| {
+                    kill_pid_gracefully(pid, false).await.map_err(|err| {
                         format!(
                             "{}; graceful shutdown failed ({shutdown_error}) and forced stop failed: {err}",
                             restart_reason
@@ -166,19 +394,23 @@ pub(super) async fn tailscale_daemon_start(
                     return Err(format!(
                         "{}; daemon did not stop and no PID could be resolved for safe forced stop ({shutdown_error})",
                         restart_reason.unwrap_or_else(|| "Daemon restart required".to_string())
-                    ));
+                    )
+                    .into());
                 }
             }
 
-            if !wait_for_daemon_shutdown(&listen_addr, Some(token)).await {
+            if !wait_for_daemon_shutdown(&listen_addr, Some(token), identity.as_deref()).await {
                 if !force_kill_allowed {
                     return Err(format!(
                         "{}; daemon acknowledged shutdown but is still reachable",
                         restart_reason.unwrap_or_else(|| "Daemon restart required".to_string())
-                    ));
+                    )
+                    .into());
                 }
-                if let Some(pid) = resolve_daemon_pid(listen_port, info.as_ref()).await {
-                    kill_pid_gracefully(pid).await.map_err(|err| {
+                if let Some(pid) =
+                    resolve_daemon_pid(Some(&data_dir), listen_port, info.as_ref()).await
+                {
+                    kill_pid_gracefully(pid, false).await.map_err(|err| {
                         format!(
                             "{}; daemon remained reachable and forced stop failed: {err}",
                             restart_reason
@@ -190,84 +422,269 @@ pub(super) async fn tailscale_daemon_start(
                     return Err(format!(
                         "{}; daemon remained reachable and no PID could be resolved for safe forced stop",
                         restart_reason.unwrap_or_else(|| "Daemon restart required".to_string())
-                    ));
+                    )
+                    .into());
                 }
             }
 
-            runtime.status = TcpDaemonStatus {
-                state: TcpDaemonState::Stopped,
+            let replacing = TcpDaemonStatus {
+                state: TcpDaemonState::Starting,
                 pid: None,
                 started_at_ms: None,
                 last_error: None,
                 listen_addr: Some(listen_addr.clone()),
+                firewall_trust: None,
+                service_manager: None,
+                operation: Some(ProcessOperation::Starting),
+                launch: None,
+                managed_externally: false,
+                last_stop: Some(stop_now(state, DaemonStopReason::Update, restart_reason)),
+                update_pending: false,
             };
+            store_tcp_daemon_status(state, &mut *state.tcp_daemon.lock().await, &replacing);
         }
         DaemonProbe::NotDaemon => {
-            return Err(format!(
-                "Cannot start mobile access daemon because {listen_addr} is already in use by another process."
-            ));
+            return Err(CommandError::new(
+                ErrorCode::DaemonPortInUse,
+                format!(
+                    "Cannot start mobile access daemon because {listen_addr} is already in use by another process."
+                ),
+            )
+            .with_details(json!({ "listenAddr": listen_addr })));
         }
         DaemonProbe::NotReachable => {}
     }
 
-    ensure_listen_addr_available(&listen_addr).await?;
+    ensure_listen_addr_available(&listen_addr)
+        .await
+        .map_err(|err| {
+            CommandError::new(ErrorCode::DaemonPortInUse, err)
+                .with_details(json!({ "listenAddr": listen_addr }))
+        })?;
+    if let Some(lock) = data_dir_lock_core::live_lock(&daemon_dir) {
+        if !takeover {
+            return Err(data_dir_locked_error(&daemon_dir, &lock));
+        }
+        data_dir_lock_core::take_over_data_dir(&daemon_dir).await?;
+    }
+    daemon_identity_core::ensure_daemon_identity(&daemon_dir)
+        .map_err(|err| format!("Failed to prepare daemon identity: {err}"))?;
 
-    let child = tokio_command(&daemon_binary)
+    let mut command = tokio_command(&daemon_binary);
+    command
         .arg("--listen")
         .arg(&listen_addr)
         .arg("--data-dir")
-        .arg(data_dir)
+        .arg(&daemon_dir)
+        .arg("--app-data-dir")
+        .arg(&data_dir)
         .arg("--token")
         .arg(token)
         .stdin(std::process::Stdio::null())
         .stdout(std::process::Stdio::null())
-        .stderr(std::process::Stdio::null())
+        .stderr(std::process::Stdio::null());
+    process_env_core::apply_process_env(&mut command, &settings.daemon_env, None)?;
+    let launch = CommandPreview::new(&daemon_binary)
+        .arg("--listen")
+        .arg(&listen_addr)
+        .arg("--data-dir")
+        .arg(&daemon_dir)
+        .arg("--app-data-dir")
+        .arg(&data_dir)
+        .secret_arg("--token", tailscale_core::REMOTE_TOKEN_PLACEHOLDER)
+        .envs(process_env_core::env_preview(
+            &process_env_core::effective_env(&settings.daemon_env, None),
+        ))
+        .current_dir(std::env::current_dir().ok())
+        .launch(PreviewShell::native());
+    let child = command
         .spawn()
         .map_err(|err| format!("Failed to start mobile access daemon: {err}"))?;
 
-    runtime.status = TcpDaemonStatus {
-        state: TcpDaemonState::Running,
+    if let Some(pid) = child.id() {
+        let _ = process_registry_core::record_spawned_process(
+            &data_dir,
+            SpawnedProcessKind::Daemon,
+            pid,
+        );
+    }
+    let mut runtime = state.tcp_daemon.lock().await;
+    let status = TcpDaemonStatus {
+        state: TcpDaemonState::Starting,
         pid: child.id(),
         started_at_ms: Some(now_unix_ms()),
         last_error: None,
         listen_addr: Some(listen_addr),
+        firewall_trust: firewall::daemon_firewall_trust().await,
+        service_manager: None,
+        operation: runtime.status.operation,
+        launch: None,
+        managed_externally: false,
+        last_stop: None,
+        update_pending: false,
     };
     runtime.child = Some(child);
+    runtime.launch = Some(launch);
+    store_tcp_daemon_status(state, &mut runtime, &status);
+
+    Ok(status)
+}
+
+/// Reports a daemon `start_daemon` spawned as `Running` only once it
+/// answers on its port, and as `Error` if it exits or stays silent until
+/// `SPAWN_READY_TIMEOUT`. Other statuses pass through.
+async fn await_daemon_ready(
+    state: &AppState,
+    mut status: TcpDaemonStatus,
+) -> CommandResult<TcpDaemonStatus> {
+    if !matches!(status.state, TcpDaemonState::Starting) {
+        return Ok(status);
+    }
+    let settings = state.app_settings.read().await.clone();
+    let identity = daemon_identity(state).await;
+    let daemon_dir = daemon_data_dir(state).await;
+    let listen_addr = status
+        .listen_addr
+        .clone()
+        .unwrap_or_else(|| configured_daemon_listen_addr(&settings));
+    let deadline = Instant::now() + SPAWN_READY_TIMEOUT;
+    let failure = loop {
+        {
+            let mut runtime = state.tcp_daemon.lock().await;
+            if let Some(Ok(Some(exit))) = runtime.child.as_mut().map(|child| child.try_wait()) {
+                runtime.child = None;
+                runtime.launch = None;
+                if let Some(pid) = status.pid {
+                    process_registry_core::forget_spawned_process(state.data_dir(), pid);
+                }
+                // Lost the data dir to a daemon started since the check in
+                // `start_daemon`.
+                let locked = if exit.code() == Some(data_dir_lock_core::DATA_DIR_LOCKED_EXIT_CODE) {
+                    data_dir_lock_core::live_lock(&daemon_dir)
+                } else {
+                    None
+                };
+                if let Some(lock) = locked {
+                    let error = data_dir_locked_error(&daemon_dir, &lock);
+                    status.state = TcpDaemonState::Error;
+                    status.last_error = Some(error.message.clone());
+                    status.operation = None;
+                    store_tcp_daemon_status(state, &mut runtime, &status);
+                    return Err(error);
+                }
+                let failure_hint = if exit.code() == Some(101) {
+                    " This usually indicates a startup panic (often due to an unavailable listen port)."
+                } else {
+                    ""
+                };
+                break format!(
+                    "Daemon exited with status: {exit} before it answered on {listen_addr}.{failure_hint}"
+                );
+            }
+        }
+        if matches!(
+            probe_daemon(
+                &listen_addr,
+                settings.remote_backend_token.as_deref(),
+                identity.as_deref(),
+            )
+            .await,
+            DaemonProbe::Running { .. }
+        ) {
+            status.state = TcpDaemonState::Running;
+            status.operation = None;
+            store_tcp_daemon_status(state, &mut *state.tcp_daemon.lock().await, &status);
+            return Ok(status);
+        }
+        if Instant::now() >= deadline {
+            break format!(
+                "Daemon did not answer on {listen_addr} within {}s of starting.",
+                SPAWN_READY_TIMEOUT.as_secs()
+            );
+        }
+        sleep(Duration::from_millis(200)).await;
+    };
 
-    Ok(runtime.status.clone())
+    status.state = TcpDaemonState::Error;
+    status.last_error = Some(failure.clone());
+    status.operation = None;
+    store_tcp_daemon_status(state, &mut *state.tcp_daemon.lock().await, &status);
+    Err(CommandError::new(ErrorCode::Timeout, failure)
+        .with_details(json!({ "listenAddr": listen_addr, "status": status })))
 }
 
 pub(super) async fn tailscale_daemon_stop(
-    state: State<'_, AppState>,
-) -> Result<TcpDaemonStatus, String> {
-    let settings = state.app_settings.lock().await.clone();
+    state: &AppState,
+    reason: DaemonStopReason,
+) -> Result<TcpDaemonStatus, MonitorError> {
+    let _lifecycle = state
+        .tcp_daemon_lifecycle
+        .begin(ProcessOperation::Stopping)
+        .await
+        .map_err(MonitorError::Process)?;
+    announce_operation(state, ProcessOperation::Stopping, TcpDaemonState::Stopping).await;
+    let result = stop_daemon(state, reason).await;
+    settle_operation(state).await;
+    result
+}
+
+/// Stops the daemon, recording `reason` when there was one to stop.
+async fn stop_daemon(
+    state: &AppState,
+    reason: DaemonStopReason,
+) -> Result<TcpDaemonStatus, MonitorError> {
+    let settings = state.app_settings.read().await.clone();
+    let identity = daemon_identity(state).await;
     let configured_listen_addr = configured_daemon_listen_addr(&settings);
     let listen_port = parse_port_from_remote_host(&configured_listen_addr);
 
-    let mut runtime = state.tcp_daemon.lock().await;
+    let (child, previous) = {
+        let mut runtime = state.tcp_daemon.lock().await;
+        runtime.launch = None;
+        (runtime.child.take(), runtime.status.clone())
+    };
     let mut stop_error: Option<String> = None;
-    if let Some(mut child) = runtime.child.take() {
-        kill_child_process_tree(&mut child).await;
+    let mut was_running = child.is_some();
+    if let Some(mut child) = child {
+        let pid = child.id();
+        // Let the daemon drain its clients, killing it only if that fails.
+        let drained = request_daemon_shutdown(
+            &configured_listen_addr,
+            settings.remote_backend_token.as_deref(),
+            identity.as_deref(),
+        )
+        .await
+        .is_ok()
+            && timeout(CHILD_SHUTDOWN_TIMEOUT, child.wait()).await.is_ok();
+        if !drained {
+            kill_child_process_tree(&mut child).await;
+        }
         let _ = child.wait().await;
+        if let Some(pid) = pid {
+            process_registry_core::forget_spawned_process(state.data_dir(), pid);
+        }
     } else if let Some(port) = listen_port {
         match probe_daemon(
             &configured_listen_addr,
             settings.remote_backend_token.as_deref(),
+            identity.as_deref(),
         )
         .await
         {
             DaemonProbe::Running { auth_ok, info, .. } => {
+                was_running = true;
                 let force_kill_allowed = can_force_stop_daemon(auth_ok, info.as_ref());
                 if let Err(shutdown_error) = request_daemon_shutdown(
                     &configured_listen_addr,
                     settings.remote_backend_token.as_deref(),
+                    identity.as_deref(),
                 )
                 .await
                 {
-                    let pid = resolve_daemon_pid(port, info.as_ref()).await;
+                    let pid = resolve_daemon_pid(Some(state.data_dir()), port, info.as_ref()).await;
                     if let Some(pid) = pid {
                         if force_kill_allowed {
-                            if let Err(err) = kill_pid_gracefully(pid).await {
+                            if let Err(err) = kill_pid_gracefully(pid, false).await {
                                 stop_error = Some(format!("{shutdown_error}; {err}"));
                             } else {
                                 stop_error = None;
@@ -278,18 +695,20 @@ pub(super) async fn tailscale_daemon_stop(
                             ));
                         }
                     } else {
-                        stop_error = Some(shutdown_error);
+                        stop_error = Some(shutdown_error.into());
                     }
                 } else if !wait_for_daemon_shutdown(
                     &configured_listen_addr,
                     settings.remote_backend_token.as_deref(),
+                    identity.as_deref(),
                 )
                 .await
                 {
                     if force_kill_allowed {
-                        let pid = resolve_daemon_pid(port, info.as_ref()).await;
+                        let pid =
+                            resolve_daemon_pid(Some(state.data_dir()), port, info.as_ref()).await;
                         if let Some(pid) = pid {
-                            if let Err(err) = kill_pid_gracefully(pid).await {
+                            if let Err(err) = kill_pid_gracefully(pid, false).await {
                                 stop_error = Some(format!(
                                     "Daemon acknowledged shutdown but remained reachable; {err}"
                                 ));
@@ -322,106 +741,490 @@ pub(super) async fn tailscale_daemon_stop(
     let probe_after_stop = probe_daemon(
         &configured_listen_addr,
         settings.remote_backend_token.as_deref(),
+        identity.as_deref(),
     )
     .await;
     let pid_after_stop = match listen_port {
         Some(port) => find_listener_pid(port).await,
         None => None,
     };
-    runtime.status = match probe_after_stop {
+    let mut status = match probe_after_stop {
         DaemonProbe::Running { auth_error, .. } => TcpDaemonStatus {
             state: TcpDaemonState::Error,
             pid: pid_after_stop,
-            started_at_ms: runtime.status.started_at_ms,
+            started_at_ms: previous.started_at_ms,
             last_error: Some(
                 stop_error
                     .or(auth_error)
                     .unwrap_or_else(|| "Daemon is still running after stop attempt.".to_string()),
             ),
-            listen_addr: runtime.status.listen_addr.clone(),
+            listen_addr: previous.listen_addr.clone(),
+            firewall_trust: None,
+            service_manager: None,
+            operation: None,
+            launch: None,
+            managed_externally: false,
+            last_stop: None,
+            update_pending: false,
         },
         DaemonProbe::NotDaemon => TcpDaemonStatus {
             state: TcpDaemonState::Error,
             pid: pid_after_stop,
-            started_at_ms: runtime.status.started_at_ms,
+            started_at_ms: previous.started_at_ms,
             last_error: Some(stop_error.unwrap_or_else(|| {
                 "Configured port is now occupied by a non-daemon process.".to_string()
             })),
-            listen_addr: runtime.status.listen_addr.clone(),
+            listen_addr: previous.listen_addr.clone(),
+            firewall_trust: None,
+            service_manager: None,
+            operation: None,
+            launch: None,
+            managed_externally: false,
+            last_stop: None,
+            update_pending: false,
         },
         DaemonProbe::NotReachable => TcpDaemonStatus {
             state: TcpDaemonState::Stopped,
             pid: None,
             started_at_ms: None,
             last_error: stop_error,
-            listen_addr: runtime.status.listen_addr.clone(),
+            listen_addr: previous.listen_addr.clone(),
+            firewall_trust: None,
+            service_manager: None,
+            operation: None,
+            launch: None,
+            managed_externally: false,
+            last_stop: was_running.then(|| stop_now(state, reason, None)),
+            update_pending: false,
         },
     };
-    sync_tcp_daemon_listen_addr(&mut runtime.status, &configured_listen_addr);
+    sync_tcp_daemon_listen_addr(&mut status, &configured_listen_addr);
+    let mut runtime = state.tcp_daemon.lock().await;
+    sync_adopted_daemon(&mut runtime, &mut status);
+    store_tcp_daemon_status(state, &mut runtime, &status);
+
+    Ok(status)
+}
+
+fn elapsed_ms(since: Instant) -> u64 {
+    since.elapsed().as_millis() as u64
+}
+
+/// Stops and starts the daemon under one hold of `tcp_daemon_lifecycle`, so
+/// status probes and the watchdog see `restarting` throughout instead of a
+/// stopped daemon between two commands, and returns once the new daemon
+/// accepts the token.
+pub(super) async fn tailscale_daemon_restart(state: &AppState) -> CommandResult<TcpDaemonRestart> {
+    if platform::is_mobile() {
+        return Err(platform::unsupported(PlatformSubsystem::DaemonManagement).into());
+    }
+
+    let _lifecycle = state
+        .tcp_daemon_lifecycle
+        .begin(ProcessOperation::Restarting)
+        .await
+        .map_err(|err| CommandError::new(ErrorCode::OperationInProgress, err))?;
+    announce_operation(
+        state,
+        ProcessOperation::Restarting,
+        TcpDaemonState::Stopping,
+    )
+    .await;
+    let result = restart_daemon(state, DaemonStopReason::User).await;
+    settle_operation(state).await;
+    result
+}
+
+/// Restarts a daemon whose status reports `update_pending` onto the binary
+/// now on disk. The stop drains its clients first. Returns the status
+/// unchanged when there is no update to apply.
+pub(super) async fn tailscale_daemon_apply_update(
+    state: &AppState,
+) -> CommandResult<TcpDaemonStatus> {
+    if platform::is_mobile() {
+        return Err(platform::unsupported(PlatformSubsystem::DaemonManagement).into());
+    }
+
+    let current = tailscale_daemon_status(state, false).await?;
+    if !current.update_pending {
+        return Ok(current);
+    }
+    let _lifecycle = state
+        .tcp_daemon_lifecycle
+        .begin(ProcessOperation::Restarting)
+        .await
+        .map_err(|err| CommandError::new(ErrorCode::OperationInProgress, err))?;
+    announce_operation(
+        state,
+        ProcessOperation::Restarting,
+        TcpDaemonState::Stopping,
+    )
+    .await;
+    let result = restart_daemon(state, DaemonStopReason::Update).await;
+    settle_operation(state).await;
+    result.map(|restart| restart.status)
+}
+
+async fn restart_daemon(
+    state: &AppState,
+    reason: DaemonStopReason,
+) -> CommandResult<TcpDaemonRestart> {
+    let began = Instant::now();
+    let stopped = stop_daemon(state, reason).await?;
+    if matches!(stopped.state, TcpDaemonState::Error) {
+        return Err(format!(
+            "Restart aborted: {}",
+            stopped
+                .last_error
+                .as_deref()
+                .unwrap_or("the daemon did not stop.")
+        )
+        .into());
+    }
+    let stop_ms = elapsed_ms(began);
+    announce_operation(
+        state,
+        ProcessOperation::Restarting,
+        TcpDaemonState::Starting,
+    )
+    .await;
+
+    let starting = Instant::now();
+    let status = start_daemon(state, false).await?;
+    let start_ms = elapsed_ms(starting);
+
+    let readying = Instant::now();
+    let status = await_daemon_ready(state, status).await?;
+    let ready_ms = elapsed_ms(readying);
 
-    Ok(runtime.status.clone())
+    Ok(TcpDaemonRestart {
+        status,
+        stop_ms,
+        start_ms,
+        ready_ms,
+        total_ms: elapsed_ms(began),
+    })
+}
+
+/// Records why a daemon the watchdog lost went away. A spawned child is
+/// reaped, which tells a crash from a clean exit; any other daemon is
+/// taken to have crashed.
+pub(super) async fn note_daemon_lost(state: &AppState) {
+    let mut runtime = state.tcp_daemon.lock().await;
+    if runtime.child.is_some() {
+        reap_tcp_daemon(state, &mut runtime).await;
+        return;
+    }
+    if !matches!(runtime.status.state, TcpDaemonState::Running) {
+        return;
+    }
+    let mut status = runtime.status.clone();
+    status.last_stop = Some(stop_now(
+        state,
+        DaemonStopReason::Crash,
+        Some("The daemon stopped answering heartbeats.".to_string()),
+    ));
+    store_tcp_daemon_status(state, &mut runtime, &status);
+}
+
+/// Keeps an adopted daemon's pid in `status` while it still runs, and lets
+/// go of it once the daemon is gone or another process serves the port.
+fn sync_adopted_daemon(runtime: &mut TcpDaemonRuntime, status: &mut TcpDaemonStatus) {
+    let Some(adopted_pid) = runtime.adopted_pid else {
+        status.managed_externally = false;
+        return;
+    };
+    let running =
+        status.state == TcpDaemonState::Running && status.pid.is_none_or(|pid| pid == adopted_pid);
+    if running {
+        status.pid = Some(adopted_pid);
+    } else {
+        runtime.adopted_pid = None;
+    }
+    status.managed_externally = running;
 }
 
 pub(super) async fn tailscale_daemon_status(
-    state: State<'_, AppState>,
-) -> Result<TcpDaemonStatus, String> {
-    let settings = state.app_settings.lock().await.clone();
+    state: &AppState,
+    verbose: bool,
+) -> Result<TcpDaemonStatus, MonitorError> {
+    let settings = state.app_settings.read().await.clone();
+    let identity = daemon_identity(state).await;
     let configured_listen_addr = configured_daemon_listen_addr(&settings);
     let listen_port = parse_port_from_remote_host(&configured_listen_addr);
 
-    let mut runtime = state.tcp_daemon.lock().await;
-    refresh_tcp_daemon_runtime(&mut runtime).await;
+    // A start or stop in progress owns the runtime and stores fresher state
+    // when it finishes, so until then this only reports what it observes.
+    let lifecycle = state.tcp_daemon_lifecycle.try_idle();
+    let previous = if lifecycle.is_some() {
+        tcp_daemon_snapshot(state).await
+    } else {
+        state.tcp_daemon.lock().await.status.clone()
+    };
+    let mut status = previous.clone();
 
-    if !matches!(runtime.status.state, TcpDaemonState::Running) {
-        let pid = match listen_port {
-            Some(port) => find_listener_pid(port).await,
-            None => None,
-        };
-        runtime.status = match probe_daemon(
+    // A transition is reported as is; its operation stores the outcome.
+    if !matches!(previous.state, TcpDaemonState::Running) && !is_transition(&previous.state) {
+        let cached = cached_daemon_probe(
+            &*state.daemon_liveness.lock().await,
             &configured_listen_addr,
-            settings.remote_backend_token.as_deref(),
-        )
-        .await
-        {
+            Instant::now(),
+        );
+        let (probe, pid) = match cached {
+            Some(cached) => cached,
+            None => {
+                let pid = match listen_port {
+                    Some(port) => find_listener_pid(port).await,
+                    None => None,
+                };
+                let probe = probe_daemon(
+                    &configured_listen_addr,
+                    settings.remote_backend_token.as_deref(),
+                    identity.as_deref(),
+                )
+                .await;
+                (probe, pid)
+            }
+        };
+        status = match probe {
             DaemonProbe::Running {
                 auth_ok: _,
                 auth_error,
-                info: _,
+                info,
             } => TcpDaemonStatus {
                 state: TcpDaemonState::Running,
                 pid,
-                started_at_ms: runtime.status.started_at_ms,
+                started_at_ms: previous.started_at_ms,
                 last_error: auth_error,
-                listen_addr: runtime.status.listen_addr.clone(),
+                listen_addr: previous.listen_addr.clone(),
+                firewall_trust: None,
+                service_manager: None,
+                operation: None,
+                launch: None,
+                managed_externally: false,
+                last_stop: None,
+                update_pending: daemon_update_pending(
+                    Some(state.data_dir()),
+                    pid,
+                    info.as_ref(),
+                ),
             },
             DaemonProbe::NotDaemon => TcpDaemonStatus {
                 state: TcpDaemonState::Error,
                 pid,
-                started_at_ms: runtime.status.started_at_ms,
+                started_at_ms: previous.started_at_ms,
                 last_error: Some(format!(
                     "Configured daemon port {configured_listen_addr} is occupied by a non-daemon process."
                 )),
-                listen_addr: runtime.status.listen_addr.clone(),
+                listen_addr: previous.listen_addr.clone(),
+                firewall_trust: None,
+                service_manager: None,
+                operation: None,
+                launch: None,
+                managed_externally: false,
+                last_stop: None,
+                update_pending: false,
             },
             DaemonProbe::NotReachable => TcpDaemonStatus {
-                state: runtime.status.state.clone(),
-                pid: runtime.status.pid,
-                started_at_ms: runtime.status.started_at_ms,
-                last_error: runtime.status.last_error.clone(),
-                listen_addr: runtime.status.listen_addr.clone(),
+                state: previous.state.clone(),
+                pid: previous.pid,
+                started_at_ms: previous.started_at_ms,
+                last_error: previous.last_error.clone(),
+                listen_addr: previous.listen_addr.clone(),
+                firewall_trust: None,
+                service_manager: None,
+                operation: None,
+                launch: None,
+                managed_externally: false,
+                last_stop: None,
+                update_pending: false,
             },
         };
     }
 
-    sync_tcp_daemon_listen_addr(&mut runtime.status, &configured_listen_addr);
+    if status.last_stop.is_none() {
+        status.last_stop = previous.last_stop.clone();
+    }
+    status.update_pending = matches!(status.state, TcpDaemonState::Running)
+        && (status.update_pending
+            || daemon_update_pending(Some(state.data_dir()), status.pid, None));
+    sync_tcp_daemon_listen_addr(&mut status, &configured_listen_addr);
+    status.firewall_trust = firewall::daemon_firewall_trust().await;
+    status.service_manager = service::installed_daemon_service();
+    status.operation = state.tcp_daemon_lifecycle.in_flight();
+    {
+        let mut runtime = state.tcp_daemon.lock().await;
+        sync_adopted_daemon(&mut runtime, &mut status);
+        if lifecycle.is_some() {
+            store_tcp_daemon_status(state, &mut runtime, &status);
+        }
+    }
+    if verbose {
+        status.launch = state.tcp_daemon.lock().await.launch.clone();
+    }
+
+    Ok(status)
+}
+
+/// Takes over a daemon started outside the app, for example from a
+/// terminal, once it proves it holds this install's identity and accepts
+/// the token. It is not put in the spawn registry, so a later startup never
+/// treats it as an orphan of the app.
+pub(super) async fn adopt_external_daemon(
+    state: &AppState,
+) -> Result<TcpDaemonStatus, MonitorError> {
+    if platform::is_mobile() {
+        return Err(platform::unsupported(PlatformSubsystem::DaemonManagement));
+    }
+
+    let lifecycle = state
+        .tcp_daemon_lifecycle
+        .begin(ProcessOperation::Starting)
+        .await
+        .map_err(MonitorError::Process)?;
+    let settings = state.app_settings.read().await.clone();
+    let listen_addr = configured_daemon_listen_addr(&settings);
+    let listen_port = parse_port_from_remote_host(&listen_addr).ok_or_else(|| {
+        MonitorError::Config(format!("Invalid daemon listen address: {listen_addr}"))
+    })?;
+    if state.tcp_daemon.lock().await.child.is_some() {
+        return Err(MonitorError::Process(
+            "The app already runs the daemon it started.".to_string(),
+        ));
+    }
+    let identity = daemon_identity(state).await.ok_or_else(|| {
+        MonitorError::Config(
+            "No daemon identity exists in the daemon data directory, so an external daemon cannot be verified."
+                .to_string(),
+        )
+    })?;
+
+    let info = match probe_daemon(
+        &listen_addr,
+        settings.remote_backend_token.as_deref(),
+        Some(&identity),
+    )
+    .await
+    {
+        DaemonProbe::Running {
+            auth_ok: true,
+            info,
+            ..
+        } => info,
+        DaemonProbe::Running { auth_error, .. } => {
+            return Err(MonitorError::Auth(auth_error.unwrap_or_else(|| {
+                "Daemon rejected the remote backend token.".to_string()
+            })));
+        }
+        DaemonProbe::NotDaemon => {
+            return Err(MonitorError::Process(format!(
+                "The listener on {listen_addr} could not prove it is this install's daemon."
+            )));
+        }
+        DaemonProbe::NotReachable => {
+            return Err(MonitorError::Network(format!(
+                "No daemon is listening on {listen_addr}."
+            )));
+        }
+    };
+    if !info.as_ref().is_some_and(is_managed_daemon) {
+        return Err(MonitorError::Process(format!(
+            "The listener on {listen_addr} is not a Codex Monitor daemon."
+        )));
+    }
+    let pid = resolve_daemon_pid(Some(state.data_dir()), listen_port, info.as_ref())
+        .await
+        .ok_or_else(|| {
+            MonitorError::Process(format!(
+                "Unable to resolve the pid of the daemon on {listen_addr}."
+            ))
+        })?;
+    state.tcp_daemon.lock().await.adopted_pid = Some(pid);
+    drop(lifecycle);
+
+    tailscale_daemon_status(state, false).await
+}
+
+/// Hands the daemon over to launchd/systemd so it outlives the app. Any
+/// daemon already on the port is stopped first so the service can bind it.
+pub(super) async fn install_daemon_service(
+    state: &AppState,
+) -> Result<TcpDaemonStatus, MonitorError> {
+    if platform::is_mobile() {
+        return Err(platform::unsupported(PlatformSubsystem::DaemonManagement));
+    }
+
+    let settings = state.app_settings.read().await.clone();
+    let token = settings
+        .remote_backend_token
+        .as_deref()
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+        .ok_or_else(|| {
+            MonitorError::Auth(
+                "Set a Remote backend token before installing the daemon service.".to_string(),
+            )
+        })?
+        .to_string();
+    let listen_addr = configured_daemon_listen_addr(&settings);
+    let data_dir = state.data_dir().to_path_buf();
+    let daemon_dir = resolve_daemon_data_dir(&data_dir, settings.daemon_data_dir.as_deref());
+    let spec = service::DaemonServiceSpec {
+        daemon_binary: resolve_daemon_binary_path().map_err(MonitorError::Process)?,
+        listen_addr: listen_addr.clone(),
+        data_dir: daemon_dir.clone(),
+        app_data_dir: data_dir.clone(),
+        token: token.clone(),
+    };
+    daemon_identity_core::ensure_daemon_identity(&daemon_dir)
+        .map_err(|err| MonitorError::Config(format!("Failed to prepare daemon identity: {err}")))?;
+
+    tailscale_daemon_stop(state, DaemonStopReason::User).await?;
+    service::install_service(&spec)
+        .await
+        .map_err(MonitorError::Process)?;
+
+    let identity = daemon_identity(state).await;
+    let deadline = Instant::now() + SERVICE_START_TIMEOUT;
+    while Instant::now() < deadline {
+        if matches!(
+            probe_daemon(&listen_addr, Some(&token), identity.as_deref()).await,
+            DaemonProbe::Running { .. }
+        ) {
+            break;
+        }
+        sleep(Duration::from_millis(200)).await;
+    }
+    // The watchdog may still hold the "unreachable" result from the stop.
+    *state.daemon_liveness.lock().await = DaemonLiveness::Unknown;
+    tailscale_daemon_status(state, false).await
+}
+
+pub(super) async fn uninstall_daemon_service(
+    state: &AppState,
+) -> Result<TcpDaemonStatus, MonitorError> {
+    service::uninstall_service()
+        .await
+        .map_err(MonitorError::Process)?;
+    *state.daemon_liveness.lock().await = DaemonLiveness::Unknown;
+    tailscale_daemon_status(state, false).await
+}
 
-    Ok(runtime.status.clone())
+pub(super) async fn tailscale_daemon_register_firewall(
+    state: &AppState,
+    verify_signature: bool,
+) -> Result<TcpDaemonStatus, MonitorError> {
+    firewall::register_daemon_with_firewall(verify_signature)
+        .await
+        .map_err(MonitorError::Process)?;
+    tailscale_daemon_status(state, false).await
 }
 
 #[cfg(test)]
 mod tests {
     use super::{
-        can_force_stop_daemon, should_restart_daemon, DaemonInfo, CURRENT_APP_VERSION,
+        binary_replaced, can_force_stop_daemon, daemon_update_pending, should_restart_daemon,
+        sync_adopted_daemon, DaemonInfo, TcpDaemonRuntime, TcpDaemonState, CURRENT_APP_VERSION,
         EXPECTED_DAEMON_MODE, EXPECTED_DAEMON_NAME,
     };
 
@@ -455,4 +1258,49 @@ mod tests {
         assert!(!can_force_stop_daemon(false, Some(&info)));
         assert!(!can_force_stop_daemon(true, None));
     }
+
+    #[test]
+    fn update_is_pending_for_another_version_or_a_replaced_binary() {
+        assert!(daemon_update_pending(
+            None,
+            None,
+            Some(&daemon_info("0.0.1"))
+        ));
+        assert!(!daemon_update_pending(
+            None,
+            None,
+            Some(&daemon_info(CURRENT_APP_VERSION))
+        ));
+        assert!(!daemon_update_pending(None, Some(42), None));
+
+        assert!(binary_replaced(Some("old"), Some("new")));
+        assert!(!binary_replaced(Some("same"), Some("same")));
+        assert!(!binary_replaced(None, Some("new")));
+        assert!(!binary_replaced(Some("old"), None));
+    }
+
+    #[test]
+    fn adopted_daemon_is_released_once_gone_or_replaced() {
+        let mut runtime = TcpDaemonRuntime {
+            adopted_pid: Some(42),
+            ..TcpDaemonRuntime::default()
+        };
+        let mut status = runtime.status.clone();
+        status.state = TcpDaemonState::Running;
+        sync_adopted_daemon(&mut runtime, &mut status);
+        assert!(status.managed_externally);
+        assert_eq!(status.pid, Some(42));
+
+        status.pid = Some(7);
+        sync_adopted_daemon(&mut runtime, &mut status);
+        assert!(!status.managed_externally);
+        assert_eq!(runtime.adopted_pid, None);
+
+        runtime.adopted_pid = Some(42);
+        status.state = TcpDaemonState::Stopped;
+        status.pid = None;
+        sync_adopted_daemon(&mut runtime, &mut status);
+        assert!(!status.managed_externally);
+        assert_eq!(runtime.adopted_pid, None);
+    }
 }
@@ -0,0 +1,288 @@
+#[cfg(target_os = "macos")]
+use super::*;
+use crate::types::DaemonFirewallTrust;
+
+#[cfg(target_os = "macos")]
+const SOCKETFILTERFW_PATH: &str = "/usr/libexec/ApplicationFirewall/socketfilterfw";
+
+/// `socketfilterfw --getglobalstate` prints e.g. "Firewall is enabled. (State = 1)".
+#[cfg(any(target_os = "macos", test))]
+fn parse_macos_firewall_enabled(stdout: &str) -> Option<bool> {
+    let lower = stdout.to_ascii_lowercase();
+    if lower.contains("disabled") || lower.contains("state = 0") {
+        Some(false)
+    } else if lower.contains("enabled")
+        || lower.contains("state = 1")
+        || lower.contains("state = 2")
+    {
+        Some(true)
+    } else {
+        None
+    }
+}
+
+/// `socketfilterfw --getblockall` wording differs between macOS releases
+/// ("Block all ENABLED!" vs "Firewall has block all state set to enabled.").
+#[cfg(any(target_os = "macos", test))]
+fn parse_macos_block_all(stdout: &str) -> Option<bool> {
+    let lower = stdout.to_ascii_lowercase();
+    if !lower.contains("block all") {
+        return None;
+    }
+    if lower.contains("disabled") {
+        Some(false)
+    } else if lower.contains("enabled") {
+        Some(true)
+    } else {
+        None
+    }
+}
+
+/// `socketfilterfw --getappblocked <path>` reports "permitted" or "blocked";
+/// anything else means the binary has no firewall entry yet.
+#[cfg(any(target_os = "macos", test))]
+fn parse_macos_app_blocked(stdout: &str) -> Option<bool> {
+    let lower = stdout.to_ascii_lowercase();
+    if lower.contains("is blocked") {
+        Some(true)
+    } else if lower.contains("is permitted") {
+        Some(false)
+    } else {
+        None
+    }
+}
+
+/// `codesign -dv` writes its report to stderr; ad-hoc signed binaries print
+/// "TeamIdentifier=not set".
+#[cfg(any(target_os = "macos", test))]
+fn parse_codesign_team_identifier(stderr: &str) -> Option<String> {
+    stderr
+        .lines()
+        .find_map(|line| line.trim().strip_prefix("TeamIdentifier="))
+        .map(str::trim)
+        .filter(|value| !value.is_empty() && *value != "not set")
+        .map(str::to_string)
+}
+
+/// Builds the AppleScript that runs the firewall registration through the
+/// standard administrator prompt.
+#[cfg(any(target_os = "macos", test))]
+fn firewall_registration_script(socketfilterfw: &str, binary_path: &str) -> String {
+    let quote = |value: &str| format!("'{}'", value.replace('\'', "'\\''"));
+    let shell = format!(
+        "{fw} --add {path} && {fw} --unblockapp {path}",
+        fw = quote(socketfilterfw),
+        path = quote(binary_path),
+    );
+    let escaped = shell.replace('\\', "\\\\").replace('"', "\\\"");
+    format!("do shell script \"{escaped}\" with administrator privileges")
+}
+
+#[cfg(target_os = "macos")]
+async fn socketfilterfw(args: &[&str]) -> Option<String> {
+    let output = tokio_command(SOCKETFILTERFW_PATH)
+        .args(args)
+        .output()
+        .await
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    Some(String::from_utf8_lossy(&output.stdout).to_string())
+}
+
+#[cfg(target_os = "macos")]
+pub(super) async fn firewall_trust_for(binary: &std::path::Path) -> DaemonFirewallTrust {
+    let Some(global) = socketfilterfw(&["--getglobalstate"]).await else {
+        return DaemonFirewallTrust::Unknown;
+    };
+    match parse_macos_firewall_enabled(&global) {
+        Some(false) => return DaemonFirewallTrust::Off,
+        None => return DaemonFirewallTrust::Unknown,
+        Some(true) => {}
+    }
+    let block_all = socketfilterfw(&["--getblockall"]).await;
+    if block_all.as_deref().and_then(parse_macos_block_all) == Some(true) {
+        return DaemonFirewallTrust::BlockAll;
+    }
+    let binary_path = binary.to_string_lossy().to_string();
+    match socketfilterfw(&["--getappblocked", &binary_path])
+        .await
+        .as_deref()
+        .and_then(parse_macos_app_blocked)
+    {
+        Some(true) => DaemonFirewallTrust::Blocked,
+        Some(false) => DaemonFirewallTrust::Allowed,
+        None => DaemonFirewallTrust::Unregistered,
+    }
+}
+
+/// Firewall trust for the bundled daemon binary. Only the macOS application
+/// firewall is per-binary, so other platforms report `None`.
+pub(super) async fn daemon_firewall_trust() -> Option<DaemonFirewallTrust> {
+    #[cfg(target_os = "macos")]
+    {
+        return Some(match resolve_daemon_binary_path() {
+            Ok(binary) => firewall_trust_for(&binary).await,
+            Err(_) => DaemonFirewallTrust::Unknown,
+        });
+    }
+    #[cfg(not(target_os = "macos"))]
+    {
+        None
+    }
+}
+
+#[cfg(target_os = "macos")]
+async fn codesign_team_identifier(path: &std::path::Path) -> Result<Option<String>, String> {
+    let output = tokio_command("/usr/bin/codesign")
+        .arg("-dv")
+        .arg("--verbose=2")
+        .arg(path)
+        .output()
+        .await
+        .map_err(|err| format!("Failed to run codesign: {err}"))?;
+    if !output.status.success() {
+        return Ok(None);
+    }
+    Ok(parse_codesign_team_identifier(&String::from_utf8_lossy(
+        &output.stderr,
+    )))
+}
+
+/// Refuses to trust a daemon binary unless it has a valid signature from the
+/// same team as the running app, so a swapped binary is never allowlisted.
+#[cfg(target_os = "macos")]
+async fn verify_daemon_signature(binary: &std::path::Path) -> Result<(), String> {
+    let verify = tokio_command("/usr/bin/codesign")
+        .args(["--verify", "--strict"])
+        .arg(binary)
+        .output()
+        .await
+        .map_err(|err| format!("Failed to run codesign: {err}"))?;
+    if !verify.status.success() {
+        let stderr = String::from_utf8_lossy(&verify.stderr).trim().to_string();
+        return Err(format!("Daemon binary signature is invalid: {stderr}"));
+    }
+    let app_binary =
+        std::env::current_exe().map_err(|err| format!("Unable to resolve app binary: {err}"))?;
+    let app_team = codesign_team_identifier(&app_binary).await?.ok_or_else(|| {
+        "Codex Monitor is not signed with a Team ID, so the daemon signature cannot be verified."
+            .to_string()
+    })?;
+    match codesign_team_identifier(binary).await? {
+        Some(team) if team == app_team => Ok(()),
+        Some(team) => Err(format!(
+            "Daemon binary is signed by team {team}, expected {app_team}."
+        )),
+        None => Err("Daemon binary is not signed with a Team ID.".to_string()),
+    }
+}
+
+/// Adds the daemon binary to the application firewall allowlist. macOS shows
+/// its administrator prompt; cancelling it returns an error.
+pub(super) async fn register_daemon_with_firewall(verify_signature: bool) -> Result<(), String> {
+    #[cfg(target_os = "macos")]
+    {
+        let binary = resolve_daemon_binary_path()?;
+        if verify_signature {
+            verify_daemon_signature(&binary).await?;
+        }
+        let script = firewall_registration_script(SOCKETFILTERFW_PATH, &binary.to_string_lossy());
+        let output = tokio_command("/usr/bin/osascript")
+            .arg("-e")
+            .arg(script)
+            .output()
+            .await
+            .map_err(|err| format!("Failed to run osascript: {err}"))?;
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr).trim().to_string();
+            if stderr.contains("-128") {
+                return Err("Firewall registration was cancelled.".to_string());
+            }
+            return Err(format!("Firewall registration failed: {stderr}"));
+        }
+        match firewall_trust_for(&binary).await {
+            DaemonFirewallTrust::Allowed | DaemonFirewallTrust::Off => Ok(()),
+            DaemonFirewallTrust::BlockAll => Err(
+                "Daemon was registered, but the firewall still blocks all incoming connections."
+                    .to_string(),
+            ),
+            _ => Err(
+                "Daemon was registered, but the firewall does not report it as allowed."
+                    .to_string(),
+            ),
+        }
+    }
+    #[cfg(not(target_os = "macos"))]
+    {
+        let _ = verify_signature;
+        Err("Firewall registration is only needed on macOS.".to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        firewall_registration_script, parse_codesign_team_identifier, parse_macos_app_blocked,
+        parse_macos_block_all, parse_macos_firewall_enabled,
+    };
+
+    #[test]
+    fn parses_macos_socketfilterfw_output() {
+        assert_eq!(
+            parse_macos_firewall_enabled("Firewall is enabled. (State = 1)\n"),
+            Some(true)
+        );
+        assert_eq!(
+            parse_macos_firewall_enabled("Firewall is disabled. (State = 0)\n"),
+            Some(false)
+        );
+        assert_eq!(parse_macos_block_all("Block all ENABLED!\n"), Some(true));
+        assert_eq!(
+            parse_macos_block_all("Firewall has block all state set to disabled.\n"),
+            Some(false)
+        );
+        assert_eq!(
+            parse_macos_app_blocked("The application /tmp/codex-monitor-daemon is blocked.\n"),
+            Some(true)
+        );
+        assert_eq!(
+            parse_macos_app_blocked("The application /tmp/codex-monitor-daemon is permitted\n"),
+            Some(false)
+        );
+        assert_eq!(
+            parse_macos_app_blocked("The application is not part of the firewall\n"),
+            None
+        );
+    }
+
+    #[test]
+    fn parses_codesign_team_identifier() {
+        let signed = "Executable=/Applications/Codex Monitor.app/Contents/MacOS/codex-monitor-daemon\nIdentifier=codex-monitor-daemon\nTeamIdentifier=ABCDE12345\n";
+        assert_eq!(
+            parse_codesign_team_identifier(signed).as_deref(),
+            Some("ABCDE12345")
+        );
+        assert_eq!(
+            parse_codesign_team_identifier("Signature=adhoc\nTeamIdentifier=not set\n"),
+            None
+        );
+    }
+
+    #[test]
+    fn escapes_paths_in_registration_script() {
+        let script = firewall_registration_script(
+            "/usr/libexec/ApplicationFirewall/socketfilterfw",
+            "/Applications/Bob's \"Monitor\".app/codex-monitor-daemon",
+        );
+        assert!(script.starts_with("do shell script \""));
+        assert!(script.ends_with("\" with administrator privileges"));
+        assert!(script.contains(
+            "--add '/Applications/Bob'\\\\''s \\\"Monitor\\\".app/codex-monitor-daemon'"
+        ));
+        assert!(
+            script.contains("&& '/usr/libexec/ApplicationFirewall/socketfilterfw' --unblockapp")
+        );
+    }
+}
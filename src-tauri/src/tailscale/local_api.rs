@@ -0,0 +1,263 @@
+use std::sync::Arc;
+
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite};
+use tokio::sync::Notify;
+
+use super::*;
+
+const LOCAL_API_HOST: &str = "local-tailscaled.sock";
+const STATUS_PATH: &str = "/localapi/v0/status";
+/// `NotifyNoPrivateKeys | NotifyRateLimit`: change notifications only, at
+/// most a few per second.
+const WATCH_IPN_BUS_PATH: &str = "/localapi/v0/watch-ipn-bus?mask=272";
+const LOCAL_API_TIMEOUT: Duration = Duration::from_secs(2);
+const MAX_RESPONSE_BYTES: usize = 4 * 1024 * 1024;
+const BUS_RECONNECT_DELAY: Duration = Duration::from_secs(10);
+
+#[cfg(target_os = "macos")]
+const SOCKET_CANDIDATES: &[&str] = &["/var/run/tailscaled.socket"];
+#[cfg(all(unix, not(target_os = "macos")))]
+const SOCKET_CANDIDATES: &[&str] = &[
+    "/var/run/tailscale/tailscaled.sock",
+    "/run/tailscale/tailscaled.sock",
+];
+#[cfg(windows)]
+const NAMED_PIPE: &str = r"\\.\pipe\ProtectedPrefix\Administrators\Tailscale\tailscaled";
+
+#[cfg(unix)]
+type LocalApiStream = tokio::net::UnixStream;
+#[cfg(windows)]
+type LocalApiStream = tokio::net::windows::named_pipe::NamedPipeClient;
+
+/// Connects to tailscaled's LocalAPI socket. The macOS GUI builds expose
+/// the LocalAPI differently; callers fall back to the CLI there.
+#[cfg(unix)]
+async fn connect() -> Result<LocalApiStream, String> {
+    let mut last_error = "tailscaled socket not found".to_string();
+    for path in SOCKET_CANDIDATES {
+        match tokio::net::UnixStream::connect(path).await {
+            Ok(stream) => return Ok(stream),
+            Err(err) => last_error = format!("{path}: {err}"),
+        }
+    }
+    Err(last_error)
+}
+
+#[cfg(windows)]
+async fn connect() -> Result<LocalApiStream, String> {
+    tokio::net::windows::named_pipe::ClientOptions::new()
+        .open(NAMED_PIPE)
+        .map_err(|err| format!("{NAMED_PIPE}: {err}"))
+}
+
+fn build_request(path: &str) -> String {
+    format!(
+        "GET {path} HTTP/1.1\r\nHost: {LOCAL_API_HOST}\r\nSec-Tailscale: localapi\r\nConnection: close\r\n\r\n"
+    )
+}
+
+struct ResponseHead {
+    status: u16,
+    chunked: bool,
+}
+
+fn parse_response_head(head: &str) -> Result<ResponseHead, String> {
+    let mut lines = head.split("\r\n");
+    let status = lines
+        .next()
+        .and_then(|line| line.split_whitespace().nth(1))
+        .and_then(|code| code.parse().ok())
+        .ok_or_else(|| "malformed LocalAPI response".to_string())?;
+    let chunked = lines.any(|line| {
+        line.split_once(':').is_some_and(|(name, value)| {
+            name.trim().eq_ignore_ascii_case("transfer-encoding")
+                && value.trim().eq_ignore_ascii_case("chunked")
+        })
+    });
+    Ok(ResponseHead { status, chunked })
+}
+
+fn decode_chunked(body: &[u8]) -> Result<Vec<u8>, String> {
+    let mut decoded = Vec::new();
+    let mut rest = body;
+    loop {
+        let line_end = rest
+            .windows(2)
+            .position(|window| window == b"\r\n")
+            .ok_or_else(|| "truncated chunked body".to_string())?;
+        let size_line = std::str::from_utf8(&rest[..line_end]).map_err(|err| err.to_string())?;
+        let size_hex = size_line.split(';').next().unwrap_or_default().trim();
+        let size = usize::from_str_radix(size_hex, 16)
+            .map_err(|_| format!("invalid chunk size `{size_hex}`"))?;
+        rest = &rest[line_end + 2..];
+        if size == 0 {
+            return Ok(decoded);
+        }
+        if rest.len() < size {
+            return Err("truncated chunked body".to_string());
+        }
+        decoded.extend_from_slice(&rest[..size]);
+        rest = rest.get(size + 2..).unwrap_or_default();
+    }
+}
+
+fn header_end(buffer: &[u8]) -> Option<usize> {
+    buffer.windows(4).position(|window| window == b"\r\n\r\n")
+}
+
+async fn get<S>(stream: &mut S, path: &str) -> Result<Vec<u8>, String>
+where
+    S: AsyncRead + AsyncWrite + Unpin,
+{
+    stream
+        .write_all(build_request(path).as_bytes())
+        .await
+        .map_err(|err| err.to_string())?;
+    let mut response = Vec::new();
+    let mut chunk = [0u8; 8192];
+    loop {
+        let read = stream
+            .read(&mut chunk)
+            .await
+            .map_err(|err| err.to_string())?;
+        if read == 0 {
+            break;
+        }
+        response.extend_from_slice(&chunk[..read]);
+        if response.len() > MAX_RESPONSE_BYTES {
+            return Err("LocalAPI response too large".to_string());
+        }
+    }
+
+    let end = header_end(&response).ok_or_else(|| "truncated LocalAPI response".to_string())?;
+    let head = parse_response_head(&String::from_utf8_lossy(&response[..end]))?;
+    let body = &response[end + 4..];
+    if head.status != 200 {
+        return Err(format!(
+            "LocalAPI returned {}: {}",
+            head.status,
+            String::from_utf8_lossy(body).trim()
+        ));
+    }
+    if head.chunked {
+        decode_chunked(body)
+    } else {
+        Ok(body.to_vec())
+    }
+}
+
+/// `Version` in LocalAPI status is the long form (`1.80.0-t1234-g5678`);
+/// the CLI path reports the short form, so both render the same.
+fn short_version(payload: &str) -> Option<String> {
+    let json: Value = serde_json::from_str(payload).ok()?;
+    let version = json.get("Version")?.as_str()?.trim();
+    let short = version.split('-').next().unwrap_or(version);
+    (!short.is_empty()).then(|| short.to_string())
+}
+
+/// The raw status JSON, peers included.
+pub(super) async fn local_api_status_payload() -> Result<String, String> {
+    let body = timeout(LOCAL_API_TIMEOUT, async {
+        let mut stream = connect().await?;
+        get(&mut stream, STATUS_PATH).await
+    })
+    .await
+    .map_err(|_| "timed out waiting for tailscaled".to_string())??;
+    String::from_utf8(body).map_err(|err| err.to_string())
+}
+
+/// Reads status straight from tailscaled. Returns an error when the
+/// LocalAPI is unavailable so the caller can fall back to the CLI.
+pub(super) async fn local_api_status() -> Result<TailscaleStatus, String> {
+    let payload = local_api_status_payload().await?;
+    tailscale_core::status_from_json(short_version(&payload), &payload)
+}
+
+/// Holds a `watch-ipn-bus` stream open and signals `changed` for every
+/// notification (state, prefs or netmap updates). Returns when the stream
+/// ends.
+async fn watch_ipn_bus(changed: &Notify) -> Result<(), String> {
+    let mut stream = timeout(LOCAL_API_TIMEOUT, connect())
+        .await
+        .map_err(|_| "timed out connecting to tailscaled".to_string())??;
+    stream
+        .write_all(build_request(WATCH_IPN_BUS_PATH).as_bytes())
+        .await
+        .map_err(|err| err.to_string())?;
+
+    let mut buffer = Vec::new();
+    let mut chunk = [0u8; 8192];
+    let mut streaming = false;
+    loop {
+        let read = stream
+            .read(&mut chunk)
+            .await
+            .map_err(|err| err.to_string())?;
+        if read == 0 {
+            return Ok(());
+        }
+        if streaming {
+            changed.notify_one();
+            continue;
+        }
+        buffer.extend_from_slice(&chunk[..read]);
+        let Some(end) = header_end(&buffer) else {
+            if buffer.len() > MAX_RESPONSE_BYTES {
+                return Err("LocalAPI response headers too large".to_string());
+            }
+            continue;
+        };
+        let head = parse_response_head(&String::from_utf8_lossy(&buffer[..end]))?;
+        if head.status != 200 {
+            return Err(format!("LocalAPI returned {}", head.status));
+        }
+        streaming = true;
+        if buffer.len() > end + 4 {
+            changed.notify_one();
+        }
+    }
+}
+
+/// Keeps watching the IPN bus, reconnecting while tailscaled is down.
+pub(super) async fn watch_ipn_bus_forever(changed: Arc<Notify>) {
+    loop {
+        let _ = watch_ipn_bus(&changed).await;
+        sleep(BUS_RECONNECT_DELAY).await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decodes_chunked_bodies() {
+        let body = b"5\r\nhello\r\n7;ext=1\r\n, world\r\n0\r\n\r\n";
+        assert_eq!(decode_chunked(body).expect("decode"), b"hello, world");
+        assert!(decode_chunked(b"5\r\nhel").is_err());
+        assert!(decode_chunked(b"zz\r\n").is_err());
+    }
+
+    #[test]
+    fn parses_status_and_transfer_encoding() {
+        let head = parse_response_head(
+            "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nTransfer-Encoding: chunked",
+        )
+        .expect("head");
+        assert_eq!(head.status, 200);
+        assert!(head.chunked);
+        let head = parse_response_head("HTTP/1.1 403 Forbidden").expect("head");
+        assert_eq!(head.status, 403);
+        assert!(!head.chunked);
+        assert!(parse_response_head("garbage").is_err());
+    }
+
+    #[test]
+    fn reports_short_version_from_status_payload() {
+        assert_eq!(
+            short_version(r#"{"Version":"1.80.0-t1234abcd-g5678"}"#).as_deref(),
+            Some("1.80.0")
+        );
+        assert_eq!(short_version(r#"{"BackendState":"Running"}"#), None);
+    }
+}
@@ -0,0 +1,469 @@
+//! Deterministic stand-ins for Tailscale and the managed daemon, used with
+//! `--mock-backends`. Tailscale follows the script's timeline; the daemon
+//! walks through the same states as a spawned one, taking the scripted time
+//! for each transition, but nothing is spawned or listened on.
+
+use std::sync::{Arc, PoisonError};
+
+use super::daemon_commands::store_tcp_daemon_status;
+use super::providers::{DaemonController, MockScriptRun, ProviderFuture, TailscaleProvider};
+use super::*;
+use crate::command_error::ErrorCode;
+use crate::shared::monitor_error::MonitorError;
+use crate::types::{
+    DaemonStop, MockBackendScript, MockTailscaleState, ProcessOperation, RemoteLinkPath,
+    TailscalePong,
+};
+
+const MOCK_TAILSCALE_VERSION: &str = "1.80.0-mock";
+const MOCK_TAILNET_NAME: &str = "mock.ts.net";
+const MOCK_DNS_NAME: &str = "codex-monitor.mock.ts.net.";
+const MOCK_HOST_NAME: &str = "codex-monitor";
+const MOCK_TAILSCALE_IPS: [&str; 2] = ["100.64.0.1", "fd7a:115c:a1e0::1"];
+const MOCK_LOGIN_NAME: &str = "dev@mock.ts.net";
+const MOCK_PING_LATENCY_MS: f64 = 12.0;
+
+fn current_script(run: &StdMutex<MockScriptRun>) -> (MockBackendScript, Instant) {
+    let run = run.lock().unwrap_or_else(PoisonError::into_inner);
+    (run.script.clone(), run.loaded_at)
+}
+
+/// The state of the last step due `elapsed_ms` into the script, or `None`
+/// before the first one.
+fn scripted_tailscale_state(
+    script: &MockBackendScript,
+    elapsed_ms: u64,
+) -> Option<MockTailscaleState> {
+    script
+        .tailscale
+        .iter()
+        .take_while(|step| step.after_ms <= elapsed_ms)
+        .last()
+        .map(|step| step.state)
+}
+
+/// What `tailscale status --json` would print in `state`; `None` when it
+/// is not installed.
+fn mock_status_payload(state: Option<MockTailscaleState>) -> Option<Value> {
+    let backend_state = match state {
+        None | Some(MockTailscaleState::NotInstalled) => return None,
+        Some(MockTailscaleState::Stopped) => "Stopped",
+        Some(MockTailscaleState::NeedsLogin) => "NeedsLogin",
+        Some(MockTailscaleState::NeedsMachineAuth) => "NeedsMachineAuth",
+        Some(MockTailscaleState::Starting) => "Starting",
+        Some(MockTailscaleState::Running) => "Running",
+    };
+    let mut payload = json!({
+        "BackendState": backend_state,
+        "CurrentTailnet": { "Name": MOCK_TAILNET_NAME },
+    });
+    if state == Some(MockTailscaleState::Running) {
+        payload["Self"] = json!({
+            "DNSName": MOCK_DNS_NAME,
+            "HostName": MOCK_HOST_NAME,
+            "TailscaleIPs": MOCK_TAILSCALE_IPS,
+            "UserID": 1,
+        });
+        payload["User"] = json!({ "1": { "LoginName": MOCK_LOGIN_NAME } });
+    }
+    Some(payload)
+}
+
+/// The status for `state`, read back through the same parser as the real
+/// output.
+fn mock_tailscale_status(state: Option<MockTailscaleState>) -> Result<TailscaleStatus, String> {
+    match mock_status_payload(state) {
+        Some(payload) => tailscale_core::status_from_json(
+            Some(MOCK_TAILSCALE_VERSION.to_string()),
+            &payload.to_string(),
+        ),
+        None => Ok(tailscale_core::unavailable_status(
+            None,
+            missing_tailscale_message(),
+        )),
+    }
+}
+
+pub(super) struct MockTailscale {
+    run: Arc<StdMutex<MockScriptRun>>,
+}
+
+impl MockTailscale {
+    pub(super) fn new(run: Arc<StdMutex<MockScriptRun>>) -> Self {
+        Self { run }
+    }
+
+    fn state(&self) -> Option<MockTailscaleState> {
+        let (script, loaded_at) = current_script(&self.run);
+        scripted_tailscale_state(&script, elapsed_ms(loaded_at))
+    }
+}
+
+impl TailscaleProvider for MockTailscale {
+    fn fetch_status(&self) -> ProviderFuture<'_, Result<TailscaleStatus, String>> {
+        Box::pin(async move { mock_tailscale_status(self.state()) })
+    }
+
+    fn fetch_status_payload(&self) -> ProviderFuture<'_, Result<String, String>> {
+        Box::pin(async move {
+            mock_status_payload(self.state())
+                .map(|payload| payload.to_string())
+                .ok_or_else(missing_tailscale_message)
+        })
+    }
+
+    /// Every peer answers directly while the mock Tailscale runs.
+    fn ping<'a>(
+        &'a self,
+        target: &'a str,
+    ) -> ProviderFuture<'a, Result<TailscalePingResult, String>> {
+        Box::pin(async move {
+            match self.state() {
+                None | Some(MockTailscaleState::NotInstalled) => Err(missing_tailscale_message()),
+                Some(MockTailscaleState::Running) => Ok(TailscalePingResult {
+                    target: target.to_string(),
+                    node_name: Some(target.to_string()),
+                    node_ip: None,
+                    pongs: vec![TailscalePong {
+                        latency_ms: Some(MOCK_PING_LATENCY_MS),
+                        endpoint: Some("192.168.1.20:41641".to_string()),
+                        derp_region: None,
+                        error: None,
+                    }],
+                    path: RemoteLinkPath::Direct,
+                    latency_ms: Some(MOCK_PING_LATENCY_MS),
+                    error: None,
+                    pinged_at_ms: now_unix_ms(),
+                }),
+                Some(_) => Err("Tailscale is not running.".to_string()),
+            }
+        })
+    }
+}
+
+pub(super) struct MockDaemon {
+    run: Arc<StdMutex<MockScriptRun>>,
+}
+
+impl MockDaemon {
+    pub(super) fn new(run: Arc<StdMutex<MockScriptRun>>) -> Self {
+        Self { run }
+    }
+
+    fn script(&self) -> MockBackendScript {
+        current_script(&self.run).0
+    }
+}
+
+async fn store_status(state: &AppState, status: &TcpDaemonStatus) {
+    let mut runtime = state.tcp_daemon.lock().await;
+    store_tcp_daemon_status(state, &mut runtime, status);
+}
+
+async fn stored_status(state: &AppState) -> TcpDaemonStatus {
+    state.tcp_daemon.lock().await.status.clone()
+}
+
+/// Moves a stopped mock daemon to `running`, or to `error` when the script
+/// says starts fail. A running one is returned as is.
+async fn start_mock_daemon(
+    state: &AppState,
+    script: &MockBackendScript,
+    operation: ProcessOperation,
+) -> CommandResult<TcpDaemonStatus> {
+    let current = stored_status(state).await;
+    if current.state == TcpDaemonState::Running {
+        return Ok(current);
+    }
+    let listen_addr = configured_daemon_listen_addr(&*state.app_settings.read().await);
+    store_status(
+        state,
+        &TcpDaemonStatus {
+            state: TcpDaemonState::Starting,
+            operation: Some(operation),
+            last_error: None,
+            ..current.clone()
+        },
+    )
+    .await;
+    sleep(Duration::from_millis(script.daemon_start_ms)).await;
+    if let Some(error) = &script.daemon_start_error {
+        store_status(
+            state,
+            &TcpDaemonStatus {
+                state: TcpDaemonState::Error,
+                operation: None,
+                last_error: Some(error.clone()),
+                ..current
+            },
+        )
+        .await;
+        return Err(CommandError::from(error.clone()));
+    }
+    let status = TcpDaemonStatus {
+        state: TcpDaemonState::Running,
+        pid: None,
+        started_at_ms: Some(now_unix_ms()),
+        last_error: None,
+        listen_addr: Some(listen_addr),
+        operation: None,
+        ..current
+    };
+    store_status(state, &status).await;
+    Ok(stored_status(state).await)
+}
+
+/// Moves a running mock daemon to `stopped`, recording `reason`.
+async fn stop_mock_daemon(
+    state: &AppState,
+    script: &MockBackendScript,
+    operation: ProcessOperation,
+    reason: DaemonStopReason,
+) -> TcpDaemonStatus {
+    let current = stored_status(state).await;
+    if current.state == TcpDaemonState::Stopped {
+        return current;
+    }
+    store_status(
+        state,
+        &TcpDaemonStatus {
+            state: TcpDaemonState::Stopping,
+            operation: Some(operation),
+            ..current.clone()
+        },
+    )
+    .await;
+    sleep(Duration::from_millis(script.daemon_stop_ms)).await;
+    let status = TcpDaemonStatus {
+        state: TcpDaemonState::Stopped,
+        pid: None,
+        started_at_ms: None,
+        last_error: None,
+        listen_addr: None,
+        operation: None,
+        last_stop: Some(DaemonStop {
+            reason,
+            at_ms: now_unix_ms(),
+            detail: None,
+        }),
+        ..current
+    };
+    store_status(state, &status).await;
+    status
+}
+
+fn elapsed_ms(since: Instant) -> u64 {
+    since.elapsed().as_millis() as u64
+}
+
+impl DaemonController for MockDaemon {
+    fn start<'a>(
+        &'a self,
+        state: &'a AppState,
+        _takeover: bool,
+    ) -> ProviderFuture<'a, CommandResult<TcpDaemonStatus>> {
+        Box::pin(async move {
+            let _lifecycle = state
+                .tcp_daemon_lifecycle
+                .begin(ProcessOperation::Starting)
+                .await
+                .map_err(|err| CommandError::new(ErrorCode::OperationInProgress, err))?;
+            start_mock_daemon(state, &self.script(), ProcessOperation::Starting).await
+        })
+    }
+
+    fn stop<'a>(
+        &'a self,
+        state: &'a AppState,
+        reason: DaemonStopReason,
+    ) -> ProviderFuture<'a, Result<TcpDaemonStatus, MonitorError>> {
+        Box::pin(async move {
+            let _lifecycle = state
+                .tcp_daemon_lifecycle
+                .begin(ProcessOperation::Stopping)
+                .await
+                .map_err(MonitorError::Process)?;
+            Ok(stop_mock_daemon(state, &self.script(), ProcessOperation::Stopping, reason).await)
+        })
+    }
+
+    fn restart<'a>(
+        &'a self,
+        state: &'a AppState,
+    ) -> ProviderFuture<'a, CommandResult<TcpDaemonRestart>> {
+        Box::pin(async move {
+            let _lifecycle = state
+                .tcp_daemon_lifecycle
+                .begin(ProcessOperation::Restarting)
+                .await
+                .map_err(|err| CommandError::new(ErrorCode::OperationInProgress, err))?;
+            let script = self.script();
+            let began = Instant::now();
+            stop_mock_daemon(
+                state,
+                &script,
+                ProcessOperation::Restarting,
+                DaemonStopReason::User,
+            )
+            .await;
+            let stop_ms = elapsed_ms(began);
+            let starting = Instant::now();
+            let status = start_mock_daemon(state, &script, ProcessOperation::Restarting).await?;
+            Ok(TcpDaemonRestart {
+                status,
+                stop_ms,
+                start_ms: elapsed_ms(starting),
+                ready_ms: 0,
+                total_ms: elapsed_ms(began),
+            })
+        })
+    }
+
+    /// The mock daemon always runs the build on disk.
+    fn apply_update<'a>(
+        &'a self,
+        state: &'a AppState,
+    ) -> ProviderFuture<'a, CommandResult<TcpDaemonStatus>> {
+        Box::pin(async move { Ok(stored_status(state).await) })
+    }
+
+    fn status<'a>(
+        &'a self,
+        state: &'a AppState,
+        _verbose: bool,
+    ) -> ProviderFuture<'a, Result<TcpDaemonStatus, MonitorError>> {
+        Box::pin(async move { Ok(stored_status(state).await) })
+    }
+
+    fn adopt_external<'a>(
+        &'a self,
+        state: &'a AppState,
+    ) -> ProviderFuture<'a, Result<TcpDaemonStatus, MonitorError>> {
+        Box::pin(async move {
+            let listen_addr = configured_daemon_listen_addr(&*state.app_settings.read().await);
+            Err(MonitorError::Process(format!(
+                "No daemon started outside the app answers on {listen_addr}."
+            )))
+        })
+    }
+
+    fn install_service<'a>(
+        &'a self,
+        _state: &'a AppState,
+    ) -> ProviderFuture<'a, Result<TcpDaemonStatus, MonitorError>> {
+        Box::pin(async move { Err(mock_service_error()) })
+    }
+
+    fn uninstall_service<'a>(
+        &'a self,
+        _state: &'a AppState,
+    ) -> ProviderFuture<'a, Result<TcpDaemonStatus, MonitorError>> {
+        Box::pin(async move { Err(mock_service_error()) })
+    }
+
+    /// There is no binary to allowlist, so the status comes back unchanged.
+    fn register_firewall<'a>(
+        &'a self,
+        state: &'a AppState,
+        _verify_signature: bool,
+    ) -> ProviderFuture<'a, Result<TcpDaemonStatus, MonitorError>> {
+        Box::pin(async move { Ok(stored_status(state).await) })
+    }
+}
+
+fn mock_service_error() -> MonitorError {
+    MonitorError::Process("The mock daemon cannot run as a system service.".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::MockTailscaleStep;
+
+    #[test]
+    fn scripted_tailscale_follows_its_timeline() {
+        let script = MockBackendScript {
+            tailscale: vec![
+                MockTailscaleStep {
+                    after_ms: 100,
+                    state: MockTailscaleState::NeedsLogin,
+                },
+                MockTailscaleStep {
+                    after_ms: 500,
+                    state: MockTailscaleState::Running,
+                },
+            ],
+            ..MockBackendScript::default()
+        };
+
+        assert_eq!(scripted_tailscale_state(&script, 0), None);
+        assert_eq!(
+            scripted_tailscale_state(&script, 100),
+            Some(MockTailscaleState::NeedsLogin)
+        );
+        assert_eq!(
+            scripted_tailscale_state(&script, 10_000),
+            Some(MockTailscaleState::Running)
+        );
+
+        let missing = mock_tailscale_status(None).expect("status");
+        assert!(!missing.installed);
+        let signed_out =
+            mock_tailscale_status(Some(MockTailscaleState::NeedsLogin)).expect("status");
+        assert!(signed_out.installed && !signed_out.running);
+        assert!(signed_out.message.contains("NeedsLogin"));
+        let running = mock_tailscale_status(Some(MockTailscaleState::Running)).expect("status");
+        assert!(running.running);
+        assert_eq!(
+            running.suggested_remote_host.as_deref(),
+            Some("codex-monitor.mock.ts.net:4732")
+        );
+    }
+
+    #[test]
+    fn mock_daemon_walks_through_start_and_stop() {
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .expect("runtime");
+        let script = MockBackendScript {
+            daemon_start_ms: 0,
+            daemon_stop_ms: 0,
+            ..MockBackendScript::default()
+        };
+        let state = AppState::for_tests(&std::env::temp_dir(), Backends::mock(script.clone()));
+        let daemon = &state.backends.daemon;
+
+        let started = runtime
+            .block_on(daemon.start(&state, false))
+            .expect("start");
+        assert_eq!(started.state, TcpDaemonState::Running);
+        assert!(started.listen_addr.is_some());
+
+        let stopped = runtime
+            .block_on(daemon.stop(&state, DaemonStopReason::User))
+            .expect("stop");
+        assert_eq!(stopped.state, TcpDaemonState::Stopped);
+        assert_eq!(
+            stopped.last_stop.map(|stop| stop.reason),
+            Some(DaemonStopReason::User)
+        );
+
+        state
+            .backends
+            .set_mock_script(MockBackendScript {
+                daemon_start_error: Some("Address already in use.".to_string()),
+                ..script
+            })
+            .expect("set script");
+        assert!(runtime.block_on(daemon.start(&state, false)).is_err());
+        let failed = runtime
+            .block_on(daemon.status(&state, false))
+            .expect("status");
+        assert_eq!(failed.state, TcpDaemonState::Error);
+        assert_eq!(
+            failed.last_error.as_deref(),
+            Some("Address already in use.")
+        );
+    }
+}
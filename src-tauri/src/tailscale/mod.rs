@@ -1,30 +1,59 @@
 mod core;
 mod daemon_commands;
+mod firewall;
+mod local_api;
+mod mock;
+mod ping;
+mod preflight;
+mod providers;
+mod quality;
+mod reachability;
 mod rpc_client;
+mod service;
+mod status_cache;
+mod stop_history;
+mod watchdog;
 
 use std::ffi::{OsStr, OsString};
 use std::io::ErrorKind;
 use std::process::Output;
+use std::sync::Mutex as StdMutex;
 use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
 use serde_json::{json, Value};
-use tauri::State;
-use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+use tauri::{AppHandle, State};
+use tokio::io::AsyncWriteExt;
 use tokio::net::tcp::{OwnedReadHalf, OwnedWriteHalf};
 use tokio::net::TcpStream;
 use tokio::time::{sleep, timeout, Instant};
 
+use crate::command_error::{CommandError, CommandResult};
 use crate::daemon_binary::resolve_daemon_binary_path;
-use crate::shared::process_core::{kill_child_process_tree, tokio_command};
+use crate::shared::process_core::{kill_child_process_tree, kill_pid_gracefully, tokio_command};
+use crate::shared::{client_trust_core, daemon_identity_core};
 use crate::state::{AppState, TcpDaemonRuntime};
 use crate::types::{
-    TailscaleDaemonCommandPreview, TailscaleStatus, TcpDaemonState, TcpDaemonStatus,
+    DaemonPreflightReport, DaemonStop, DaemonStopReason, MockBackendScript, RemoteQualityReport,
+    StartupPhaseKind, TailnetReachabilityReport, TailscaleDaemonCommandPreview,
+    TailscalePingResult, TailscaleStatus, TcpDaemonRestart, TcpDaemonState, TcpDaemonStatus,
 };
 
 use self::core as tailscale_core;
+pub(crate) use self::daemon_commands::daemon_data_dir;
+pub(crate) use self::providers::Backends;
+pub(crate) use self::service::installed_daemon_service;
+pub(crate) use self::status_cache::spawn_tailscale_status_refresher;
+pub(crate) use self::stop_history::last_daemon_stop;
+pub(crate) use self::watchdog::spawn_daemon_watchdog;
 
 #[cfg(any(target_os = "android", target_os = "ios"))]
-const UNSUPPORTED_MESSAGE: &str = "Tailscale integration is only available on desktop.";
+fn unsupported_message() -> String {
+    crate::platform::unsupported(crate::types::PlatformSubsystem::Tailscale).to_string()
+}
+
+/// The candidate that last answered `tailscale version`, tried first so a
+/// refresh does not walk every install path again.
+static RESOLVED_TAILSCALE_BINARY: StdMutex<Option<OsString>> = StdMutex::new(None);
 
 fn apply_tailscale_command_env(command: &mut tokio::process::Command) {
     #[cfg(target_os = "macos")]
@@ -168,8 +197,17 @@ fn looks_like_tailscale_version(stdout: &str) -> bool {
 }
 
 async fn resolve_tailscale_binary() -> Result<Option<(OsString, Output)>, String> {
+    let mut candidates = tailscale_binary_candidates();
+    if let Some(resolved) = RESOLVED_TAILSCALE_BINARY
+        .lock()
+        .ok()
+        .and_then(|resolved| resolved.clone())
+    {
+        candidates.retain(|candidate| candidate != &resolved);
+        candidates.insert(0, resolved);
+    }
     let mut failures: Vec<String> = Vec::new();
-    for binary in tailscale_binary_candidates() {
+    for binary in candidates {
         let output = tailscale_output(binary.as_os_str(), &["version"]).await;
         match output {
             Ok(version_output) => {
@@ -178,6 +216,9 @@ async fn resolve_tailscale_binary() -> Result<Option<(OsString, Output)>, String
                 if version_output.status.success()
                     && stdout.as_deref().is_some_and(looks_like_tailscale_version)
                 {
+                    if let Ok(mut resolved) = RESOLVED_TAILSCALE_BINARY.lock() {
+                        *resolved = Some(binary.clone());
+                    }
                     return Ok(Some((binary, version_output)));
                 }
                 let detail = match (stdout, stderr) {
@@ -218,6 +259,11 @@ fn degraded_tailscale_status(version: Option<String>, message: String) -> Tailsc
         ipv6: Vec::new(),
         suggested_remote_host: None,
         message,
+        tags: Vec::new(),
+        key_expiry_ms: None,
+        key_expiring_soon: false,
+        stale: false,
+        last_seen_ms: None,
     }
 }
 
@@ -274,6 +320,11 @@ async fn ensure_listen_addr_available(listen_addr: &str) -> Result<(), String> {
     }
 }
 
+/// A start or stop is under way; only the operation doing it settles it.
+fn is_transition(state: &TcpDaemonState) -> bool {
+    matches!(state, TcpDaemonState::Starting | TcpDaemonState::Stopping)
+}
+
 async fn refresh_tcp_daemon_runtime(runtime: &mut TcpDaemonRuntime) {
     let Some(child) = runtime.child.as_mut() else {
         runtime.status.state = TcpDaemonState::Stopped;
@@ -285,6 +336,7 @@ async fn refresh_tcp_daemon_runtime(runtime: &mut TcpDaemonRuntime) {
         Ok(Some(status)) => {
             let pid = child.id();
             runtime.child = None;
+            runtime.launch = None;
             if status.success() {
                 runtime.status = TcpDaemonStatus {
                     state: TcpDaemonState::Stopped,
@@ -292,6 +344,17 @@ async fn refresh_tcp_daemon_runtime(runtime: &mut TcpDaemonRuntime) {
                     started_at_ms: None,
                     last_error: None,
                     listen_addr: runtime.status.listen_addr.clone(),
+                    firewall_trust: None,
+                    service_manager: None,
+                    operation: None,
+                    launch: None,
+                    managed_externally: false,
+                    last_stop: Some(DaemonStop {
+                        reason: DaemonStopReason::Exited,
+                        at_ms: now_unix_ms(),
+                        detail: None,
+                    }),
+                    update_pending: false,
                 };
             } else {
                 let failure_hint = if status.code() == Some(101) {
@@ -299,14 +362,24 @@ async fn refresh_tcp_daemon_runtime(runtime: &mut TcpDaemonRuntime) {
                 } else {
                     ""
                 };
+                let last_error = format!("Daemon exited with status: {status}.{failure_hint}");
                 runtime.status = TcpDaemonStatus {
                     state: TcpDaemonState::Error,
                     pid,
                     started_at_ms: runtime.status.started_at_ms,
-                    last_error: Some(format!(
-                        "Daemon exited with status: {status}.{failure_hint}"
-                    )),
+                    last_error: Some(last_error.clone()),
                     listen_addr: runtime.status.listen_addr.clone(),
+                    firewall_trust: None,
+                    service_manager: None,
+                    operation: None,
+                    launch: None,
+                    managed_externally: false,
+                    last_stop: Some(DaemonStop {
+                        reason: DaemonStopReason::Crash,
+                        at_ms: now_unix_ms(),
+                        detail: Some(last_error),
+                    }),
+                    update_pending: false,
                 };
             }
         }
@@ -322,23 +395,18 @@ async fn refresh_tcp_daemon_runtime(runtime: &mut TcpDaemonRuntime) {
                 started_at_ms: runtime.status.started_at_ms,
                 last_error: Some(format!("Failed to inspect daemon process: {err}")),
                 listen_addr: runtime.status.listen_addr.clone(),
+                firewall_trust: None,
+                service_manager: None,
+                operation: None,
+                launch: None,
+                managed_externally: false,
+                last_stop: runtime.status.last_stop.clone(),
+                update_pending: false,
             };
         }
     }
 }
 
-#[cfg(unix)]
-fn is_pid_running(pid: u32) -> bool {
-    let result = unsafe { libc::kill(pid as i32, 0) };
-    if result == 0 {
-        return true;
-    }
-    match std::io::Error::last_os_error().raw_os_error() {
-        Some(code) => code != libc::ESRCH,
-        None => false,
-    }
-}
-
 #[cfg(unix)]
 async fn find_listener_pid(port: u16) -> Option<u32> {
     let target = format!(":{port}");
@@ -369,63 +437,34 @@ async fn find_listener_pid(port: u16) -> Option<u32> {
         .find_map(|line| line.trim().parse::<u32>().ok())
 }
 
-#[cfg(unix)]
-async fn kill_pid_gracefully(pid: u32) -> Result<(), String> {
-    let term_result = unsafe { libc::kill(pid as i32, libc::SIGTERM) };
-    if term_result != 0 {
-        let err = std::io::Error::last_os_error();
-        if err.raw_os_error() != Some(libc::ESRCH) {
-            return Err(format!("Failed to stop daemon process {pid}: {err}"));
-        }
-        return Ok(());
-    }
-
-    for _ in 0..12 {
-        if !is_pid_running(pid) {
-            return Ok(());
-        }
-        sleep(Duration::from_millis(100)).await;
-    }
-
-    let kill_result = unsafe { libc::kill(pid as i32, libc::SIGKILL) };
-    if kill_result != 0 {
-        let err = std::io::Error::last_os_error();
-        if err.raw_os_error() != Some(libc::ESRCH) {
-            return Err(format!("Failed to force-stop daemon process {pid}: {err}"));
-        }
-    }
-
-    for _ in 0..8 {
-        if !is_pid_running(pid) {
-            return Ok(());
-        }
-        sleep(Duration::from_millis(100)).await;
-    }
-
-    Err(format!("Daemon process {pid} is still running."))
-}
-
 #[cfg(not(unix))]
 async fn find_listener_pid(_port: u16) -> Option<u32> {
     None
 }
 
-#[cfg(not(unix))]
-async fn kill_pid_gracefully(_pid: u32) -> Result<(), String> {
-    Err("Stopping external daemon by pid is not supported on this platform.".to_string())
-}
-
-#[tauri::command]
-pub(crate) async fn tailscale_status() -> Result<TailscaleStatus, String> {
+/// Reads a fresh status from the tailscaled LocalAPI, falling back to the
+/// tailscale CLI when the socket is unavailable. Commands go through the
+/// cache in `status_cache` instead.
+async fn fetch_tailscale_status() -> Result<TailscaleStatus, String> {
     #[cfg(any(target_os = "android", target_os = "ios"))]
     {
         return Ok(tailscale_core::unavailable_status(
             None,
-            UNSUPPORTED_MESSAGE.to_string(),
+            unsupported_message(),
         ));
     }
 
-    let resolved_tailscale_binary = match resolve_tailscale_binary().await {
+    if let Ok(status) = local_api::local_api_status().await {
+        return Ok(status);
+    }
+
+    let resolved_tailscale_binary = match crate::startup::measure_async(
+        "tailscale_detection",
+        StartupPhaseKind::Lazy,
+        resolve_tailscale_binary(),
+    )
+    .await
+    {
         Ok(result) => result,
         Err(err) => {
             return Ok(degraded_tailscale_status(None, err));
@@ -466,6 +505,11 @@ pub(crate) async fn tailscale_status() -> Result<TailscaleStatus, String> {
             ipv6: Vec::new(),
             suggested_remote_host: None,
             message: stderr_text,
+            tags: Vec::new(),
+            key_expiry_ms: None,
+            key_expiring_soon: false,
+            stale: false,
+            last_seen_ms: None,
         });
     }
 
@@ -615,6 +659,13 @@ mod tests {
             started_at_ms: None,
             last_error: None,
             listen_addr: Some("0.0.0.0:4732".to_string()),
+            firewall_trust: None,
+            service_manager: None,
+            operation: None,
+            launch: None,
+            managed_externally: false,
+            last_stop: None,
+            update_pending: false,
         };
 
         sync_tcp_daemon_listen_addr(&mut status, "0.0.0.0:7777");
@@ -629,6 +680,13 @@ mod tests {
             started_at_ms: Some(1),
             last_error: None,
             listen_addr: Some("0.0.0.0:4732".to_string()),
+            firewall_trust: None,
+            service_manager: None,
+            operation: None,
+            launch: None,
+            managed_externally: false,
+            last_stop: None,
+            update_pending: false,
         };
 
         sync_tcp_daemon_listen_addr(&mut status, "0.0.0.0:7777");
@@ -656,30 +714,210 @@ mod tests {
     }
 }
 
+/// The cached Tailscale status, refreshed once older than the configured
+/// TTL; `None` when Tailscale cannot be queried at all.
+pub(crate) async fn current_tailscale_status(state: &AppState) -> Option<TailscaleStatus> {
+    status_cache::tailscale_status(state, false).await.ok()
+}
+
+#[tauri::command]
+pub(crate) async fn tailscale_status(
+    force_refresh: Option<bool>,
+    state: State<'_, AppState>,
+) -> CommandResult<TailscaleStatus> {
+    status_cache::tailscale_status(&state, force_refresh.unwrap_or(false))
+        .await
+        .map_err(CommandError::from)
+}
+
 #[tauri::command]
 pub(crate) async fn tailscale_daemon_command_preview(
     state: State<'_, AppState>,
-) -> Result<TailscaleDaemonCommandPreview, String> {
-    daemon_commands::tailscale_daemon_command_preview(state).await
+) -> CommandResult<TailscaleDaemonCommandPreview> {
+    daemon_commands::tailscale_daemon_command_preview(state)
+        .await
+        .map_err(CommandError::from)
 }
 
+#[tauri::command]
+pub(crate) async fn tailscale_daemon_identity(state: State<'_, AppState>) -> CommandResult<String> {
+    daemon_commands::tailscale_daemon_identity(&state)
+        .await
+        .map_err(CommandError::from)
+}
+
+/// With `takeover`, stops a daemon that holds the data directory without
+/// answering on the configured port rather than failing with
+/// `DataDirLocked`.
 #[tauri::command]
 pub(crate) async fn tailscale_daemon_start(
+    takeover: Option<bool>,
     state: State<'_, AppState>,
-) -> Result<TcpDaemonStatus, String> {
-    daemon_commands::tailscale_daemon_start(state).await
+) -> CommandResult<TcpDaemonStatus> {
+    state
+        .backends
+        .daemon
+        .start(&state, takeover.unwrap_or(false))
+        .await
 }
 
 #[tauri::command]
 pub(crate) async fn tailscale_daemon_stop(
     state: State<'_, AppState>,
-) -> Result<TcpDaemonStatus, String> {
-    daemon_commands::tailscale_daemon_stop(state).await
+) -> CommandResult<TcpDaemonStatus> {
+    state
+        .backends
+        .daemon
+        .stop(&state, DaemonStopReason::User)
+        .await
+        .map_err(CommandError::from)
+}
+
+/// Stops the daemon for the app itself rather than at the user's request.
+pub(crate) async fn stop_daemon_for(
+    state: State<'_, AppState>,
+    reason: DaemonStopReason,
+) -> CommandResult<TcpDaemonStatus> {
+    state
+        .backends
+        .daemon
+        .stop(&state, reason)
+        .await
+        .map_err(CommandError::from)
+}
+
+#[tauri::command]
+pub(crate) async fn tailscale_daemon_restart(
+    state: State<'_, AppState>,
+) -> CommandResult<TcpDaemonRestart> {
+    state.backends.daemon.restart(&state).await
+}
+
+#[tauri::command]
+pub(crate) async fn tailscale_daemon_apply_update(
+    state: State<'_, AppState>,
+) -> CommandResult<TcpDaemonStatus> {
+    state.backends.daemon.apply_update(&state).await
 }
 
 #[tauri::command]
 pub(crate) async fn tailscale_daemon_status(
+    verbose: Option<bool>,
+    state: State<'_, AppState>,
+) -> CommandResult<TcpDaemonStatus> {
+    state
+        .backends
+        .daemon
+        .status(&state, verbose.unwrap_or(false))
+        .await
+        .map_err(CommandError::from)
+}
+
+#[tauri::command]
+pub(crate) async fn adopt_external_daemon(
+    state: State<'_, AppState>,
+) -> CommandResult<TcpDaemonStatus> {
+    state
+        .backends
+        .daemon
+        .adopt_external(&state)
+        .await
+        .map_err(CommandError::from)
+}
+
+#[tauri::command]
+pub(crate) async fn install_daemon_service(
+    state: State<'_, AppState>,
+) -> CommandResult<TcpDaemonStatus> {
+    state
+        .backends
+        .daemon
+        .install_service(&state)
+        .await
+        .map_err(CommandError::from)
+}
+
+#[tauri::command]
+pub(crate) async fn uninstall_daemon_service(
+    state: State<'_, AppState>,
+) -> CommandResult<TcpDaemonStatus> {
+    state
+        .backends
+        .daemon
+        .uninstall_service(&state)
+        .await
+        .map_err(CommandError::from)
+}
+
+#[tauri::command]
+pub(crate) async fn tailscale_daemon_preflight(
+    state: State<'_, AppState>,
+) -> CommandResult<DaemonPreflightReport> {
+    preflight::tailscale_daemon_preflight(state)
+        .await
+        .map_err(CommandError::from)
+}
+
+#[tauri::command]
+pub(crate) async fn tailscale_daemon_register_firewall(
+    verify_signature: Option<bool>,
+    state: State<'_, AppState>,
+) -> CommandResult<TcpDaemonStatus> {
+    state
+        .backends
+        .daemon
+        .register_firewall(&state, verify_signature.unwrap_or(true))
+        .await
+        .map_err(CommandError::from)
+}
+
+#[tauri::command]
+pub(crate) async fn probe_remote_quality(
+    pings: Option<u32>,
+    state: State<'_, AppState>,
+) -> CommandResult<RemoteQualityReport> {
+    quality::probe_remote_quality(&state, pings)
+        .await
+        .map_err(CommandError::from)
+}
+
+#[tauri::command]
+pub(crate) async fn check_tailnet_reachability(
+    peer: Option<String>,
+    state: State<'_, AppState>,
+) -> CommandResult<TailnetReachabilityReport> {
+    reachability::check_tailnet_reachability(&state, peer)
+        .await
+        .map_err(CommandError::from)
+}
+
+#[tauri::command]
+pub(crate) async fn tailscale_ping_peer(
+    target: String,
+    state: State<'_, AppState>,
+) -> CommandResult<TailscalePingResult> {
+    ping::tailscale_ping_peer(&state, &target)
+        .await
+        .map_err(CommandError::from)
+}
+
+#[tauri::command]
+pub(crate) async fn tailscale_ping_history(
+    target: Option<String>,
+    state: State<'_, AppState>,
+) -> CommandResult<Vec<TailscalePingResult>> {
+    Ok(ping::tailscale_ping_history(&state, target.as_deref()).await)
+}
+
+/// Replaces the script the mock backends follow and restarts its timeline.
+/// Only available when the app was launched with mock backends.
+#[tauri::command]
+pub(crate) async fn set_mock_backend_script(
+    script: MockBackendScript,
     state: State<'_, AppState>,
-) -> Result<TcpDaemonStatus, String> {
-    daemon_commands::tailscale_daemon_status(state).await
+) -> CommandResult<()> {
+    state
+        .backends
+        .set_mock_script(script)
+        .map_err(CommandError::from)
 }
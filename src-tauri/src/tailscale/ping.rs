@@ -0,0 +1,200 @@
+//! `tailscale ping` against a tailnet peer. Results are kept for the app
+//! run so settings can show the link to a paired device over time.
+
+use super::*;
+use crate::types::{RemoteLinkPath, TailscalePingResult, TailscalePong};
+
+const PING_COUNT: &str = "5";
+/// Per pong; `tailscale ping` gives up on a peer after this.
+const PONG_TIMEOUT: &str = "3s";
+const PING_RUN_TIMEOUT: Duration = Duration::from_secs(30);
+const PING_HISTORY_LIMIT: usize = 50;
+
+fn json_str(value: &Value, field: &str) -> Option<String> {
+    value
+        .get(field)
+        .and_then(Value::as_str)
+        .map(|value| value.trim().trim_end_matches('.'))
+        .filter(|value| !value.is_empty())
+        .map(str::to_string)
+}
+
+/// Reads the `ipnstate.PingResult` objects `tailscale ping --json` prints,
+/// one per pong.
+fn parse_ping_output(target: &str, stdout: &str, pinged_at_ms: i64) -> TailscalePingResult {
+    let replies: Vec<Value> = serde_json::Deserializer::from_str(stdout)
+        .into_iter::<Value>()
+        .map_while(Result::ok)
+        .collect();
+    let pongs: Vec<TailscalePong> = replies
+        .iter()
+        .map(|reply| TailscalePong {
+            latency_ms: reply
+                .get("LatencySeconds")
+                .and_then(Value::as_f64)
+                .filter(|seconds| *seconds > 0.0)
+                .map(|seconds| (seconds * 10_000.0).round() / 10.0),
+            endpoint: json_str(reply, "Endpoint"),
+            derp_region: json_str(reply, "DERPRegionCode"),
+            error: json_str(reply, "Err"),
+        })
+        .collect();
+    let last_answered = pongs.iter().rev().find(|pong| pong.error.is_none());
+    let path = match last_answered {
+        Some(pong) if pong.endpoint.is_some() => RemoteLinkPath::Direct,
+        Some(pong) if pong.derp_region.is_some() => RemoteLinkPath::Relay,
+        _ => RemoteLinkPath::Unknown,
+    };
+    let node = replies.iter().rev();
+    TailscalePingResult {
+        target: target.to_string(),
+        node_name: node.clone().find_map(|reply| json_str(reply, "NodeName")),
+        node_ip: node
+            .clone()
+            .find_map(|reply| json_str(reply, "NodeIP").or_else(|| json_str(reply, "IP"))),
+        latency_ms: last_answered.and_then(|pong| pong.latency_ms),
+        error: match last_answered {
+            Some(_) => None,
+            None => pongs.iter().rev().find_map(|pong| pong.error.clone()),
+        },
+        pongs,
+        path,
+        pinged_at_ms,
+    }
+}
+
+pub(super) async fn run_ping(target: &str) -> Result<TailscalePingResult, String> {
+    #[cfg(any(target_os = "android", target_os = "ios"))]
+    {
+        return Err(unsupported_message());
+    }
+
+    let Some((binary, _)) = resolve_tailscale_binary().await? else {
+        return Err(missing_tailscale_message());
+    };
+    let args = [
+        "ping",
+        "--json",
+        "--c",
+        PING_COUNT,
+        "--timeout",
+        PONG_TIMEOUT,
+        target,
+    ];
+    let output = timeout(
+        PING_RUN_TIMEOUT,
+        tailscale_output(binary.as_os_str(), &args),
+    )
+    .await
+    .map_err(|_| format!("tailscale ping {target} timed out"))?
+    .map_err(|err| format!("Failed to run tailscale ping: {err}"))?;
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let mut result = parse_ping_output(target, &stdout, now_unix_ms());
+    // Exits non-zero when no direct path came up, after printing its pongs.
+    if result.pongs.is_empty() && !output.status.success() {
+        let stderr = trim_to_non_empty(std::str::from_utf8(&output.stderr).ok());
+        return Err(stderr.unwrap_or_else(|| "tailscale ping failed.".to_string()));
+    }
+    if result.error.is_none() && result.latency_ms.is_none() {
+        result.error = Some("No pong received.".to_string());
+    }
+    Ok(result)
+}
+
+pub(super) async fn tailscale_ping_peer(
+    state: &AppState,
+    target: &str,
+) -> Result<TailscalePingResult, String> {
+    let target = target.trim();
+    if target.is_empty() {
+        return Err("target is required".to_string());
+    }
+    let result = state.backends.tailscale.ping(target).await?;
+    let mut history = state.tailscale_pings.lock().await;
+    if history.len() == PING_HISTORY_LIMIT {
+        history.pop_front();
+    }
+    history.push_back(result.clone());
+    Ok(result)
+}
+
+pub(super) async fn tailscale_ping_history(
+    state: &AppState,
+    target: Option<&str>,
+) -> Vec<TailscalePingResult> {
+    let target = target.map(str::trim).filter(|target| !target.is_empty());
+    state
+        .tailscale_pings
+        .lock()
+        .await
+        .iter()
+        .filter(|result| target.is_none_or(|target| result.target == target))
+        .cloned()
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_relayed_then_direct_pongs() {
+        let stdout = r#"{"IP":"100.64.0.2","NodeIP":"100.64.0.2","NodeName":"phone.example.ts.net.","LatencySeconds":0.1204,"DERPRegionID":4,"DERPRegionCode":"fra"}
+{"IP":"100.64.0.2","NodeIP":"100.64.0.2","NodeName":"phone.example.ts.net.","LatencySeconds":0.0231,"Endpoint":"192.0.2.10:41641"}
+"#;
+        let result = parse_ping_output("phone", stdout, 1);
+        assert_eq!(result.pongs.len(), 2);
+        assert_eq!(result.pongs[0].derp_region.as_deref(), Some("fra"));
+        assert_eq!(result.pongs[0].latency_ms, Some(120.4));
+        assert_eq!(result.path, RemoteLinkPath::Direct);
+        assert_eq!(result.latency_ms, Some(23.1));
+        assert_eq!(result.node_name.as_deref(), Some("phone.example.ts.net"));
+        assert_eq!(result.node_ip.as_deref(), Some("100.64.0.2"));
+        assert_eq!(result.error, None);
+    }
+
+    #[test]
+    fn reports_the_error_when_nothing_answered() {
+        let stdout = r#"{"IP":"100.64.0.9","Err":"timeout waiting for ping reply"}"#;
+        let result = parse_ping_output("laptop", stdout, 1);
+        assert_eq!(result.path, RemoteLinkPath::Unknown);
+        assert_eq!(result.latency_ms, None);
+        assert_eq!(
+            result.error.as_deref(),
+            Some("timeout waiting for ping reply")
+        );
+        assert!(parse_ping_output("laptop", "pong from laptop", 1)
+            .pongs
+            .is_empty());
+    }
+
+    #[test]
+    fn keeps_pings_in_history_by_target() {
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .expect("runtime");
+        let state = AppState::for_tests(
+            &std::env::temp_dir(),
+            Backends::mock(MockBackendScript::default()),
+        );
+
+        assert!(runtime.block_on(tailscale_ping_peer(&state, "  ")).is_err());
+        let result = runtime
+            .block_on(tailscale_ping_peer(&state, " phone "))
+            .expect("ping");
+        assert_eq!(result.target, "phone");
+        assert_eq!(result.path, RemoteLinkPath::Direct);
+        runtime
+            .block_on(tailscale_ping_peer(&state, "laptop"))
+            .expect("ping");
+
+        assert_eq!(
+            runtime.block_on(tailscale_ping_history(&state, None)).len(),
+            2
+        );
+        let phone = runtime.block_on(tailscale_ping_history(&state, Some("phone")));
+        assert_eq!(phone.len(), 1);
+        assert_eq!(phone[0].latency_ms, result.latency_ms);
+    }
+}
@@ -0,0 +1,454 @@
+use std::path::Path;
+
+use super::daemon_commands::daemon_identity;
+#[cfg(target_os = "macos")]
+use super::firewall::firewall_trust_for;
+use super::rpc_client::{probe_daemon, DaemonProbe};
+use super::*;
+#[cfg(target_os = "macos")]
+use crate::types::DaemonFirewallTrust;
+use crate::types::{DaemonPreflightCheck, DaemonPreflightCheckStatus, DaemonPreflightReport};
+
+fn check(
+    id: &str,
+    label: &str,
+    status: DaemonPreflightCheckStatus,
+    detail: impl Into<String>,
+    fix: Option<String>,
+) -> DaemonPreflightCheck {
+    DaemonPreflightCheck {
+        id: id.to_string(),
+        label: label.to_string(),
+        status,
+        detail: detail.into(),
+        fix,
+    }
+}
+
+#[cfg(any(target_os = "windows", test))]
+#[derive(Debug, PartialEq, Eq)]
+struct WindowsFirewallProfile {
+    name: String,
+    enabled: bool,
+    blocks_inbound: bool,
+}
+
+/// Parses `netsh advfirewall show allprofiles`.
+#[cfg(any(target_os = "windows", test))]
+fn parse_windows_firewall_profiles(stdout: &str) -> Vec<WindowsFirewallProfile> {
+    let mut profiles: Vec<WindowsFirewallProfile> = Vec::new();
+    for line in stdout.lines() {
+        let trimmed = line.trim();
+        if let Some(name) = trimmed.strip_suffix("Profile Settings:") {
+            profiles.push(WindowsFirewallProfile {
+                name: name.trim().to_string(),
+                enabled: false,
+                blocks_inbound: false,
+            });
+            continue;
+        }
+        let Some(profile) = profiles.last_mut() else {
+            continue;
+        };
+        let mut parts = trimmed.split_whitespace();
+        match (parts.next(), parts.next(), parts.next()) {
+            (Some("State"), Some(value), None) => {
+                profile.enabled = value.eq_ignore_ascii_case("on");
+            }
+            (Some("Firewall"), Some("Policy"), Some(value)) => {
+                profile.blocks_inbound = value.split(',').any(|entry| {
+                    entry.eq_ignore_ascii_case("BlockInbound")
+                        || entry.eq_ignore_ascii_case("BlockInboundAlways")
+                });
+            }
+            _ => {}
+        }
+    }
+    profiles
+}
+
+/// Returns true when `netsh advfirewall firewall show rule name=all dir=in verbose`
+/// lists an enabled allow rule for `program`.
+#[cfg(any(target_os = "windows", test))]
+fn windows_rules_allow_program(stdout: &str, program: &str) -> bool {
+    let mut enabled = false;
+    let mut allow = false;
+    let mut matches_program = false;
+    let mut found = false;
+    let mut finish = |enabled: bool, allow: bool, matches_program: bool| {
+        if enabled && allow && matches_program {
+            found = true;
+        }
+    };
+    for line in stdout.lines() {
+        let Some((key, value)) = line.split_once(':') else {
+            continue;
+        };
+        let key = key.trim();
+        let value = value.trim();
+        if key.eq_ignore_ascii_case("Rule Name") {
+            finish(enabled, allow, matches_program);
+            enabled = false;
+            allow = false;
+            matches_program = false;
+        } else if key.eq_ignore_ascii_case("Enabled") {
+            enabled = value.eq_ignore_ascii_case("yes");
+        } else if key.eq_ignore_ascii_case("Action") {
+            allow = value.eq_ignore_ascii_case("allow");
+        } else if key.eq_ignore_ascii_case("Program") {
+            matches_program = value.eq_ignore_ascii_case(program);
+        }
+    }
+    finish(enabled, allow, matches_program);
+    found
+}
+
+#[cfg(target_os = "windows")]
+async fn command_stdout(program: &str, args: &[&str]) -> Option<String> {
+    let output = tokio_command(program).args(args).output().await.ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    Some(String::from_utf8_lossy(&output.stdout).to_string())
+}
+
+#[cfg(target_os = "macos")]
+async fn firewall_check(daemon_binary: Option<&Path>) -> DaemonPreflightCheck {
+    const LABEL: &str = "macOS application firewall";
+    let Some(binary) = daemon_binary else {
+        return check(
+            "firewall",
+            LABEL,
+            DaemonPreflightCheckStatus::Warn,
+            "The daemon binary could not be resolved to check its firewall rule.",
+            None,
+        );
+    };
+    let allow_fix = "Use \"Allow through firewall\" in Settings → Server, or allow incoming connections for the daemon in System Settings → Network → Firewall → Options.".to_string();
+    match firewall_trust_for(binary).await {
+        DaemonFirewallTrust::Off => check(
+            "firewall",
+            LABEL,
+            DaemonPreflightCheckStatus::Pass,
+            "The application firewall is off.",
+            None,
+        ),
+        DaemonFirewallTrust::Allowed => check(
+            "firewall",
+            LABEL,
+            DaemonPreflightCheckStatus::Pass,
+            "The daemon is allowed through the firewall.",
+            None,
+        ),
+        DaemonFirewallTrust::BlockAll => check(
+            "firewall",
+            LABEL,
+            DaemonPreflightCheckStatus::Fail,
+            "The firewall blocks all incoming connections.",
+            Some(
+                "Turn off \"Block all incoming connections\" in System Settings → Network → Firewall → Options."
+                    .to_string(),
+            ),
+        ),
+        DaemonFirewallTrust::Blocked => check(
+            "firewall",
+            LABEL,
+            DaemonPreflightCheckStatus::Fail,
+            "The firewall blocks incoming connections to the daemon.",
+            Some(allow_fix),
+        ),
+        DaemonFirewallTrust::Unregistered => check(
+            "firewall",
+            LABEL,
+            DaemonPreflightCheckStatus::Warn,
+            "The firewall is on and has no rule for the daemon yet; macOS may prompt or silently drop connections.",
+            Some(allow_fix),
+        ),
+        DaemonFirewallTrust::Unknown => check(
+            "firewall",
+            LABEL,
+            DaemonPreflightCheckStatus::Warn,
+            "Unable to read the application firewall state.",
+            None,
+        ),
+    }
+}
+
+#[cfg(target_os = "windows")]
+async fn firewall_check(daemon_binary: Option<&Path>) -> DaemonPreflightCheck {
+    const LABEL: &str = "Windows Defender Firewall";
+    let Some(profiles_output) =
+        command_stdout("netsh", &["advfirewall", "show", "allprofiles"]).await
+    else {
+        return check(
+            "firewall",
+            LABEL,
+            DaemonPreflightCheckStatus::Warn,
+            "Unable to read the firewall profiles.",
+            None,
+        );
+    };
+    let blocking: Vec<String> = parse_windows_firewall_profiles(&profiles_output)
+        .into_iter()
+        .filter(|profile| profile.enabled && profile.blocks_inbound)
+        .map(|profile| profile.name)
+        .collect();
+    if blocking.is_empty() {
+        return check(
+            "firewall",
+            LABEL,
+            DaemonPreflightCheckStatus::Pass,
+            "No active firewall profile blocks inbound connections.",
+            None,
+        );
+    }
+    let Some(binary) = daemon_binary else {
+        return check(
+            "firewall",
+            LABEL,
+            DaemonPreflightCheckStatus::Warn,
+            format!(
+                "Inbound connections are blocked by default ({} profile); the daemon binary could not be resolved to check its rule.",
+                blocking.join(", ")
+            ),
+            None,
+        );
+    };
+    let binary_path = binary.to_string_lossy().to_string();
+    let rules = command_stdout(
+        "netsh",
+        &[
+            "advfirewall",
+            "firewall",
+            "show",
+            "rule",
+            "name=all",
+            "dir=in",
+            "verbose",
+        ],
+    )
+    .await
+    .unwrap_or_default();
+    if windows_rules_allow_program(&rules, &binary_path) {
+        return check(
+            "firewall",
+            LABEL,
+            DaemonPreflightCheckStatus::Pass,
+            "An inbound allow rule exists for the daemon.",
+            None,
+        );
+    }
+    check(
+        "firewall",
+        LABEL,
+        DaemonPreflightCheckStatus::Fail,
+        format!(
+            "Inbound connections are blocked by default ({} profile) and no allow rule exists for the daemon.",
+            blocking.join(", ")
+        ),
+        Some(format!(
+            "Run as administrator: netsh advfirewall firewall add rule name=\"Codex Monitor Daemon\" dir=in action=allow program=\"{binary_path}\""
+        )),
+    )
+}
+
+#[cfg(not(any(target_os = "macos", target_os = "windows")))]
+async fn firewall_check(_daemon_binary: Option<&Path>) -> DaemonPreflightCheck {
+    check(
+        "firewall",
+        "Firewall",
+        DaemonPreflightCheckStatus::Skipped,
+        "Firewall rules are not inspected on this platform.",
+        None,
+    )
+}
+
+async fn port_check(
+    listen_addr: &str,
+    token: Option<&str>,
+    identity: Option<&str>,
+) -> DaemonPreflightCheck {
+    const LABEL: &str = "Listen port";
+    match probe_daemon(listen_addr, token, identity).await {
+        DaemonProbe::Running { .. } => check(
+            "port",
+            LABEL,
+            DaemonPreflightCheckStatus::Pass,
+            format!("A daemon is already listening on {listen_addr}."),
+            None,
+        ),
+        DaemonProbe::NotDaemon => check(
+            "port",
+            LABEL,
+            DaemonPreflightCheckStatus::Fail,
+            format!("{listen_addr} is in use by another process."),
+            Some(
+                "Stop the process using the port or change the port in Remote backend host."
+                    .to_string(),
+            ),
+        ),
+        DaemonProbe::NotReachable => match ensure_listen_addr_available(listen_addr).await {
+            Ok(()) => check(
+                "port",
+                LABEL,
+                DaemonPreflightCheckStatus::Pass,
+                format!("{listen_addr} is available."),
+                None,
+            ),
+            Err(err) => check(
+                "port",
+                LABEL,
+                DaemonPreflightCheckStatus::Fail,
+                err,
+                Some(
+                    "Stop the process using the port or change the port in Remote backend host."
+                        .to_string(),
+                ),
+            ),
+        },
+    }
+}
+
+async fn tailscale_check(state: &AppState) -> DaemonPreflightCheck {
+    const LABEL: &str = "Tailscale interface";
+    let status = match state.backends.tailscale.fetch_status().await {
+        Ok(status) => status,
+        Err(err) => {
+            return check(
+                "tailscale",
+                LABEL,
+                DaemonPreflightCheckStatus::Warn,
+                err,
+                None,
+            );
+        }
+    };
+    if !status.installed {
+        return check(
+            "tailscale",
+            LABEL,
+            DaemonPreflightCheckStatus::Warn,
+            status.message,
+            Some("Install Tailscale to reach the daemon from other devices.".to_string()),
+        );
+    }
+    match status.ipv4.first().or(status.ipv6.first()) {
+        Some(address) if status.running => check(
+            "tailscale",
+            LABEL,
+            DaemonPreflightCheckStatus::Pass,
+            format!("Tailscale is up at {address}."),
+            None,
+        ),
+        _ => check(
+            "tailscale",
+            LABEL,
+            DaemonPreflightCheckStatus::Fail,
+            status.message,
+            Some("Open Tailscale and sign in, or run `tailscale up`.".to_string()),
+        ),
+    }
+}
+
+/// Runs every check even when an earlier one fails so the UI can show the
+/// whole checklist at once.
+pub(super) async fn tailscale_daemon_preflight(
+    state: State<'_, AppState>,
+) -> Result<DaemonPreflightReport, String> {
+    let settings = state.app_settings.read().await.clone();
+    let listen_addr = configured_daemon_listen_addr(&settings);
+    let token = settings
+        .remote_backend_token
+        .as_deref()
+        .map(str::trim)
+        .filter(|value| !value.is_empty());
+
+    let token_check = if token.is_some() {
+        check(
+            "token",
+            "Remote backend token",
+            DaemonPreflightCheckStatus::Pass,
+            "A token is configured.",
+            None,
+        )
+    } else {
+        check(
+            "token",
+            "Remote backend token",
+            DaemonPreflightCheckStatus::Fail,
+            "No remote backend token is set.",
+            Some("Set a Remote backend token in Settings → Server.".to_string()),
+        )
+    };
+    let identity = daemon_identity(&state).await;
+    let daemon_binary = resolve_daemon_binary_path().ok();
+    let checks = vec![
+        token_check,
+        port_check(&listen_addr, token, identity.as_deref()).await,
+        firewall_check(daemon_binary.as_deref()).await,
+        tailscale_check(&state).await,
+    ];
+    let ready = !checks
+        .iter()
+        .any(|entry| entry.status == DaemonPreflightCheckStatus::Fail);
+    Ok(DaemonPreflightReport {
+        listen_addr,
+        ready,
+        checks,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        parse_windows_firewall_profiles, windows_rules_allow_program, WindowsFirewallProfile,
+    };
+
+    #[test]
+    fn parses_windows_firewall_profiles() {
+        let output = "\r\nDomain Profile Settings: \r\n----------------------------------------------------------------------\r\nState                                 OFF\r\nFirewall Policy                       BlockInbound,AllowOutbound\r\n\r\nPrivate Profile Settings: \r\n----------------------------------------------------------------------\r\nState                                 ON\r\nFirewall Policy                       BlockInbound,AllowOutbound\r\n\r\nPublic Profile Settings: \r\n----------------------------------------------------------------------\r\nState                                 ON\r\nFirewall Policy                       AllowInbound,AllowOutbound\r\nOk.\r\n";
+        assert_eq!(
+            parse_windows_firewall_profiles(output),
+            vec![
+                WindowsFirewallProfile {
+                    name: "Domain".to_string(),
+                    enabled: false,
+                    blocks_inbound: true,
+                },
+                WindowsFirewallProfile {
+                    name: "Private".to_string(),
+                    enabled: true,
+                    blocks_inbound: true,
+                },
+                WindowsFirewallProfile {
+                    name: "Public".to_string(),
+                    enabled: true,
+                    blocks_inbound: false,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn finds_enabled_windows_allow_rule_for_program() {
+        let program = "C:\\Program Files\\Codex Monitor\\codex-monitor-daemon.exe";
+        let rules = format!(
+            "Rule Name:                            Disabled rule\n\
+             Enabled:                              No\n\
+             Action:                               Allow\n\
+             Program:                              {program}\n\
+             \n\
+             Rule Name:                            Codex Monitor Daemon\n\
+             Enabled:                              Yes\n\
+             Action:                               Allow\n\
+             Program:                              {program}\n"
+        );
+        assert!(windows_rules_allow_program(&rules, program));
+        assert!(windows_rules_allow_program(
+            &rules,
+            &program.to_ascii_lowercase()
+        ));
+        let disabled_only = rules.split("\n\n").next().unwrap_or_default().to_string();
+        assert!(!windows_rules_allow_program(&disabled_only, program));
+    }
+}
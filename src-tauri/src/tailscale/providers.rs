@@ -0,0 +1,264 @@
+//! What the Tailscale and daemon commands call into. Commands reach them
+//! through `AppState::backends` rather than the CLI and process helpers, so
+//! a launch with `--mock-backends` can swap in the fakes in `mock` and tests
+//! can exercise command logic with their own.
+
+use std::future::Future;
+use std::path::Path;
+use std::pin::Pin;
+use std::sync::{Arc, PoisonError};
+
+use super::daemon_commands;
+use super::mock::{MockDaemon, MockTailscale};
+use super::*;
+use crate::shared::monitor_error::MonitorError;
+use crate::types::{AppSettings, MockBackendScript};
+
+const MOCK_BACKENDS_FLAG: &str = "--mock-backends";
+const MOCK_SCRIPT_FILENAME: &str = "mock-backends.json";
+
+pub(crate) type ProviderFuture<'a, T> = Pin<Box<dyn Future<Output = T> + Send + 'a>>;
+
+/// Reports this device's Tailscale state. Caching, persistence of the last
+/// known identity and change events stay in `status_cache`.
+pub(crate) trait TailscaleProvider: Send + Sync {
+    fn fetch_status(&self) -> ProviderFuture<'_, Result<TailscaleStatus, String>>;
+
+    /// The status JSON with peers and users, for callers that need more
+    /// than `TailscaleStatus` carries.
+    fn fetch_status_payload(&self) -> ProviderFuture<'_, Result<String, String>>;
+
+    fn ping<'a>(
+        &'a self,
+        target: &'a str,
+    ) -> ProviderFuture<'a, Result<TailscalePingResult, String>>;
+}
+
+/// Starts, stops and reports on the daemon the app manages.
+pub(crate) trait DaemonController: Send + Sync {
+    fn start<'a>(
+        &'a self,
+        state: &'a AppState,
+        takeover: bool,
+    ) -> ProviderFuture<'a, CommandResult<TcpDaemonStatus>>;
+
+    fn stop<'a>(
+        &'a self,
+        state: &'a AppState,
+        reason: DaemonStopReason,
+    ) -> ProviderFuture<'a, Result<TcpDaemonStatus, MonitorError>>;
+
+    fn restart<'a>(
+        &'a self,
+        state: &'a AppState,
+    ) -> ProviderFuture<'a, CommandResult<TcpDaemonRestart>>;
+
+    fn apply_update<'a>(
+        &'a self,
+        state: &'a AppState,
+    ) -> ProviderFuture<'a, CommandResult<TcpDaemonStatus>>;
+
+    fn status<'a>(
+        &'a self,
+        state: &'a AppState,
+        verbose: bool,
+    ) -> ProviderFuture<'a, Result<TcpDaemonStatus, MonitorError>>;
+
+    fn adopt_external<'a>(
+        &'a self,
+        state: &'a AppState,
+    ) -> ProviderFuture<'a, Result<TcpDaemonStatus, MonitorError>>;
+
+    fn install_service<'a>(
+        &'a self,
+        state: &'a AppState,
+    ) -> ProviderFuture<'a, Result<TcpDaemonStatus, MonitorError>>;
+
+    fn uninstall_service<'a>(
+        &'a self,
+        state: &'a AppState,
+    ) -> ProviderFuture<'a, Result<TcpDaemonStatus, MonitorError>>;
+
+    fn register_firewall<'a>(
+        &'a self,
+        state: &'a AppState,
+        verify_signature: bool,
+    ) -> ProviderFuture<'a, Result<TcpDaemonStatus, MonitorError>>;
+}
+
+/// The `tailscale` CLI and local API.
+struct CliTailscale;
+
+impl TailscaleProvider for CliTailscale {
+    fn fetch_status(&self) -> ProviderFuture<'_, Result<TailscaleStatus, String>> {
+        Box::pin(fetch_tailscale_status())
+    }
+
+    fn fetch_status_payload(&self) -> ProviderFuture<'_, Result<String, String>> {
+        Box::pin(quality::status_payload())
+    }
+
+    fn ping<'a>(
+        &'a self,
+        target: &'a str,
+    ) -> ProviderFuture<'a, Result<TailscalePingResult, String>> {
+        Box::pin(ping::run_ping(target))
+    }
+}
+
+/// The `codex_monitor_daemon` binary, spawned as a child process.
+struct SpawnedDaemon;
+
+impl DaemonController for SpawnedDaemon {
+    fn start<'a>(
+        &'a self,
+        state: &'a AppState,
+        takeover: bool,
+    ) -> ProviderFuture<'a, CommandResult<TcpDaemonStatus>> {
+        Box::pin(daemon_commands::tailscale_daemon_start(state, takeover))
+    }
+
+    fn stop<'a>(
+        &'a self,
+        state: &'a AppState,
+        reason: DaemonStopReason,
+    ) -> ProviderFuture<'a, Result<TcpDaemonStatus, MonitorError>> {
+        Box::pin(daemon_commands::tailscale_daemon_stop(state, reason))
+    }
+
+    fn restart<'a>(
+        &'a self,
+        state: &'a AppState,
+    ) -> ProviderFuture<'a, CommandResult<TcpDaemonRestart>> {
+        Box::pin(daemon_commands::tailscale_daemon_restart(state))
+    }
+
+    fn apply_update<'a>(
+        &'a self,
+        state: &'a AppState,
+    ) -> ProviderFuture<'a, CommandResult<TcpDaemonStatus>> {
+        Box::pin(daemon_commands::tailscale_daemon_apply_update(state))
+    }
+
+    fn status<'a>(
+        &'a self,
+        state: &'a AppState,
+        verbose: bool,
+    ) -> ProviderFuture<'a, Result<TcpDaemonStatus, MonitorError>> {
+        Box::pin(daemon_commands::tailscale_daemon_status(state, verbose))
+    }
+
+    fn adopt_external<'a>(
+        &'a self,
+        state: &'a AppState,
+    ) -> ProviderFuture<'a, Result<TcpDaemonStatus, MonitorError>> {
+        Box::pin(daemon_commands::adopt_external_daemon(state))
+    }
+
+    fn install_service<'a>(
+        &'a self,
+        state: &'a AppState,
+    ) -> ProviderFuture<'a, Result<TcpDaemonStatus, MonitorError>> {
+        Box::pin(daemon_commands::install_daemon_service(state))
+    }
+
+    fn uninstall_service<'a>(
+        &'a self,
+        state: &'a AppState,
+    ) -> ProviderFuture<'a, Result<TcpDaemonStatus, MonitorError>> {
+        Box::pin(daemon_commands::uninstall_daemon_service(state))
+    }
+
+    fn register_firewall<'a>(
+        &'a self,
+        state: &'a AppState,
+        verify_signature: bool,
+    ) -> ProviderFuture<'a, Result<TcpDaemonStatus, MonitorError>> {
+        Box::pin(daemon_commands::tailscale_daemon_register_firewall(
+            state,
+            verify_signature,
+        ))
+    }
+}
+
+/// The providers this run uses, chosen once at launch.
+pub(crate) struct Backends {
+    pub(crate) tailscale: Arc<dyn TailscaleProvider>,
+    pub(crate) daemon: Arc<dyn DaemonController>,
+    /// Shared by both fakes when mocked, so a new script reaches them at once.
+    mock_script: Option<Arc<StdMutex<MockScriptRun>>>,
+}
+
+/// A mock script and when it was loaded, which its step times count from.
+pub(crate) struct MockScriptRun {
+    pub(crate) script: MockBackendScript,
+    pub(crate) loaded_at: Instant,
+}
+
+impl Backends {
+    /// The mock backends when the app was launched with `--mock-backends`
+    /// or `mockBackends` is on, following `mock-backends.json` in
+    /// `data_dir` when there is one; the real ones otherwise.
+    pub(crate) fn for_launch(settings: &AppSettings, data_dir: &Path) -> Self {
+        let flagged = std::env::args().any(|arg| arg == MOCK_BACKENDS_FLAG);
+        if !flagged && !settings.mock_backends {
+            return Self::real();
+        }
+        let path = data_dir.join(MOCK_SCRIPT_FILENAME);
+        let script = match std::fs::read_to_string(&path) {
+            Ok(raw) => serde_json::from_str(&raw).unwrap_or_else(|err| {
+                eprintln!("Ignoring {}: {err}", path.display());
+                MockBackendScript::default()
+            }),
+            Err(_) => MockBackendScript::default(),
+        };
+        eprintln!("Using mock Tailscale and daemon backends.");
+        Self::mock(script)
+    }
+
+    pub(crate) fn real() -> Self {
+        Self::new(Arc::new(CliTailscale), Arc::new(SpawnedDaemon))
+    }
+
+    pub(crate) fn new(
+        tailscale: Arc<dyn TailscaleProvider>,
+        daemon: Arc<dyn DaemonController>,
+    ) -> Self {
+        Self {
+            tailscale,
+            daemon,
+            mock_script: None,
+        }
+    }
+
+    pub(crate) fn mock(script: MockBackendScript) -> Self {
+        let run = Arc::new(StdMutex::new(MockScriptRun {
+            script,
+            loaded_at: Instant::now(),
+        }));
+        Self {
+            tailscale: Arc::new(MockTailscale::new(run.clone())),
+            daemon: Arc::new(MockDaemon::new(run.clone())),
+            mock_script: Some(run),
+        }
+    }
+
+    pub(crate) fn is_mock(&self) -> bool {
+        self.mock_script.is_some()
+    }
+
+    /// Replaces the mock script and restarts its timeline. Fails when the
+    /// real backends are in use.
+    pub(crate) fn set_mock_script(&self, script: MockBackendScript) -> Result<(), String> {
+        let Some(run) = &self.mock_script else {
+            return Err(
+                "Mock backends are off. Launch with --mock-backends or turn on mockBackends."
+                    .to_string(),
+            );
+        };
+        let mut run = run.lock().unwrap_or_else(PoisonError::into_inner);
+        run.script = script;
+        run.loaded_at = Instant::now();
+        Ok(())
+    }
+}
@@ -0,0 +1,248 @@
+//! Measures the link to the configured remote backend: ping round trips, one
+//! payload round trip for throughput, and the tailnet path the traffic
+//! takes. Clients size their polling and payloads from the suggestions.
+
+use super::*;
+use crate::remote_backend;
+use crate::shared::monitor_error::MonitorError;
+use crate::types::{RemoteLinkPath, RemoteQualityReport};
+
+const DEFAULT_PINGS: u32 = 5;
+const MAX_PINGS: u32 = 20;
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(10);
+const PING_TIMEOUT: Duration = Duration::from_secs(5);
+/// Well under the smallest frame limit a daemon accepts.
+const THROUGHPUT_PAYLOAD_BYTES: usize = 32 * 1024;
+const MIN_POLL_INTERVAL_MS: u64 = 1_000;
+const MAX_POLL_INTERVAL_MS: u64 = 15_000;
+const MIN_PAYLOAD_BYTES: u64 = 16 * 1024;
+const MAX_PAYLOAD_BYTES: u64 = 1024 * 1024;
+/// Used when the payload round trip failed but pings got through.
+const DEFAULT_PAYLOAD_BYTES: u64 = 256 * 1024;
+
+#[derive(Debug, PartialEq)]
+struct RttStats {
+    min_ms: f64,
+    avg_ms: f64,
+    max_ms: f64,
+    jitter_ms: f64,
+}
+
+fn rtt_stats(samples: &[f64]) -> Option<RttStats> {
+    if samples.is_empty() {
+        return None;
+    }
+    let min_ms = samples.iter().copied().fold(f64::INFINITY, f64::min);
+    let max_ms = samples.iter().copied().fold(0.0, f64::max);
+    let avg_ms = samples.iter().sum::<f64>() / samples.len() as f64;
+    let jitter_ms = if samples.len() < 2 {
+        0.0
+    } else {
+        samples
+            .windows(2)
+            .map(|pair| (pair[1] - pair[0]).abs())
+            .sum::<f64>()
+            / (samples.len() - 1) as f64
+    };
+    Some(RttStats {
+        min_ms: round_ms(min_ms),
+        avg_ms: round_ms(avg_ms),
+        max_ms: round_ms(max_ms),
+        jitter_ms: round_ms(jitter_ms),
+    })
+}
+
+fn round_ms(value: f64) -> f64 {
+    (value * 10.0).round() / 10.0
+}
+
+/// `bytes` moved in one round trip of `elapsed_ms`, less the base latency
+/// `rtt_min_ms`, in kilobits per second.
+fn throughput_kbps(bytes: usize, elapsed_ms: f64, rtt_min_ms: f64) -> u64 {
+    let transfer_ms = (elapsed_ms - rtt_min_ms).max(1.0);
+    (bytes as f64 * 8.0 / transfer_ms).round() as u64
+}
+
+/// Polls every twenty round trips and caps payloads at what the link moves
+/// in a quarter second, both clamped to sane bounds. An unreachable host
+/// gets the slowest poll and smallest payload.
+fn suggestions(rtt_avg_ms: Option<f64>, throughput_kbps: Option<u64>) -> (u64, u64) {
+    let Some(rtt_avg_ms) = rtt_avg_ms else {
+        return (MAX_POLL_INTERVAL_MS, MIN_PAYLOAD_BYTES);
+    };
+    let poll_ms = ((rtt_avg_ms * 20.0 / 100.0).round() as u64 * 100)
+        .clamp(MIN_POLL_INTERVAL_MS, MAX_POLL_INTERVAL_MS);
+    let payload_bytes = throughput_kbps
+        .map(|kbps| (kbps * 1000 / 8 / 4).clamp(MIN_PAYLOAD_BYTES, MAX_PAYLOAD_BYTES))
+        .unwrap_or(DEFAULT_PAYLOAD_BYTES);
+    (poll_ms, payload_bytes)
+}
+
+fn is_loopback(host: &str) -> bool {
+    host.eq_ignore_ascii_case("localhost")
+        || host
+            .parse::<std::net::IpAddr>()
+            .is_ok_and(|ip| ip.is_loopback())
+}
+
+/// The status JSON with peers, from the LocalAPI or `tailscale status
+/// --json`.
+pub(super) async fn status_payload() -> Result<String, String> {
+    #[cfg(any(target_os = "android", target_os = "ios"))]
+    {
+        return Err(unsupported_message());
+    }
+
+    if let Ok(payload) = local_api::local_api_status_payload().await {
+        return Ok(payload);
+    }
+    let Some((binary, _)) = resolve_tailscale_binary().await? else {
+        return Err(missing_tailscale_message());
+    };
+    let output = tailscale_output(binary.as_os_str(), &["status", "--json"])
+        .await
+        .map_err(|err| format!("Failed to run tailscale status --json: {err}"))?;
+    if !output.status.success() {
+        return Err("tailscale status returned a non-zero exit code.".to_string());
+    }
+    String::from_utf8(output.stdout).map_err(|err| err.to_string())
+}
+
+async fn link_path(state: &AppState, host: &str) -> (RemoteLinkPath, Option<String>) {
+    if is_loopback(host) {
+        return (RemoteLinkPath::Local, None);
+    }
+    match state.backends.tailscale.fetch_status_payload().await {
+        Ok(payload) => tailscale_core::peer_link_path(&payload, host),
+        Err(_) => (RemoteLinkPath::Unknown, None),
+    }
+}
+
+pub(super) async fn probe_remote_quality(
+    state: &AppState,
+    pings: Option<u32>,
+) -> Result<RemoteQualityReport, MonitorError> {
+    let settings = state.app_settings.read().await.clone();
+    let host = remote_backend::configured_host(&settings).ok_or_else(|| {
+        MonitorError::Config(format!(
+            "Invalid remote backend host `{}`",
+            settings.remote_backend_host
+        ))
+    })?;
+    let connected = timeout(
+        CONNECT_TIMEOUT,
+        remote_backend::connect_configured(&settings),
+    )
+    .await
+    .map_err(|_| {
+        MonitorError::ConnectTimeout(format!(
+            "Connecting to {host} timed out after {} seconds",
+            CONNECT_TIMEOUT.as_secs()
+        ))
+    })
+    .and_then(|connected| connected);
+    let client = match connected {
+        Ok(client) => client,
+        Err(err) => return Err(remote_backend::explain_connect_failure(state, err).await),
+    };
+
+    let pings_sent = pings.unwrap_or(DEFAULT_PINGS).clamp(1, MAX_PINGS);
+    let mut samples = Vec::with_capacity(pings_sent as usize);
+    for _ in 0..pings_sent {
+        let started = Instant::now();
+        if let Ok(Ok(_)) = timeout(PING_TIMEOUT, client.call("ping", json!({}))).await {
+            samples.push(started.elapsed().as_secs_f64() * 1000.0);
+        }
+    }
+    let stats = rtt_stats(&samples);
+
+    let throughput = match stats.as_ref() {
+        Some(stats) => {
+            let payload = "x".repeat(THROUGHPUT_PAYLOAD_BYTES);
+            let started = Instant::now();
+            let response = timeout(
+                PING_TIMEOUT,
+                client.call("ping", json!({ "payload": payload })),
+            )
+            .await;
+            let elapsed_ms = started.elapsed().as_secs_f64() * 1000.0;
+            match response {
+                Ok(Ok(value)) => {
+                    // Older daemons do not echo; then only the upload counts.
+                    let echoed = value
+                        .get("payload")
+                        .and_then(Value::as_str)
+                        .map_or(0, str::len);
+                    Some(throughput_kbps(
+                        THROUGHPUT_PAYLOAD_BYTES + echoed,
+                        elapsed_ms,
+                        stats.min_ms,
+                    ))
+                }
+                _ => None,
+            }
+        }
+        None => None,
+    };
+
+    let (path, relay_region) = link_path(state, &host).await;
+    let (suggested_poll_interval_ms, suggested_max_payload_bytes) =
+        suggestions(stats.as_ref().map(|stats| stats.avg_ms), throughput);
+    remote_backend::record_link_quality(state, suggested_poll_interval_ms, path.clone()).await;
+    Ok(RemoteQualityReport {
+        host,
+        pings_sent,
+        pings_lost: pings_sent - samples.len() as u32,
+        rtt_min_ms: stats.as_ref().map(|stats| stats.min_ms),
+        rtt_avg_ms: stats.as_ref().map(|stats| stats.avg_ms),
+        rtt_max_ms: stats.as_ref().map(|stats| stats.max_ms),
+        jitter_ms: stats.as_ref().map(|stats| stats.jitter_ms),
+        throughput_kbps: throughput,
+        path,
+        relay_region,
+        suggested_poll_interval_ms,
+        suggested_max_payload_bytes,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn summarizes_round_trips() {
+        assert_eq!(rtt_stats(&[]), None);
+        assert_eq!(
+            rtt_stats(&[10.0, 30.0, 20.0]),
+            Some(RttStats {
+                min_ms: 10.0,
+                avg_ms: 20.0,
+                max_ms: 30.0,
+                jitter_ms: 15.0,
+            })
+        );
+        // 64 KiB in 100 ms over a 20 ms base latency.
+        assert_eq!(throughput_kbps(64 * 1024, 100.0, 20.0), 6554);
+        assert_eq!(throughput_kbps(1024, 5.0, 20.0), 8192);
+    }
+
+    #[test]
+    fn suggestions_scale_with_the_link() {
+        assert_eq!(
+            suggestions(None, None),
+            (MAX_POLL_INTERVAL_MS, MIN_PAYLOAD_BYTES)
+        );
+        assert_eq!(
+            suggestions(Some(2.0), Some(100_000)),
+            (MIN_POLL_INTERVAL_MS, MAX_PAYLOAD_BYTES)
+        );
+        assert_eq!(suggestions(Some(180.0), Some(2_000)), (3_600, 62_500));
+        assert_eq!(
+            suggestions(Some(180.0), None),
+            (3_600, DEFAULT_PAYLOAD_BYTES)
+        );
+        assert!(is_loopback("127.0.0.1"));
+        assert!(is_loopback("localhost"));
+        assert!(!is_loopback("studio.example.ts.net"));
+    }
+}
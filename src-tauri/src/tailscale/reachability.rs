@@ -0,0 +1,219 @@
+//! Checks that the tailnet policy lets a peer reach this machine's daemon
+//! port. A peer running the daemon connects back on request; without a
+//! peer, this device connects to the configured remote backend itself, as a
+//! phone would.
+
+use super::*;
+use crate::remote_backend;
+use crate::shared::monitor_error::MonitorError;
+use crate::shared::reachability_core::{self, REACHABILITY_PROBE_METHOD};
+use crate::types::{TailnetReachabilityReport, TcpProbeOutcome, TcpProbeResult};
+
+const DEFAULT_DAEMON_PORT: u16 = 4732;
+const PEER_CONNECT_TIMEOUT: Duration = Duration::from_secs(10);
+/// The peer's own connect gives up after a few seconds.
+const PEER_PROBE_TIMEOUT: Duration = Duration::from_secs(8);
+
+fn peer_daemon_host(peer: &str, port: u16) -> String {
+    let peer = peer.trim();
+    match peer.parse::<std::net::IpAddr>() {
+        Ok(std::net::IpAddr::V6(ip)) => format!("[{ip}]:{port}"),
+        Ok(ip) => format!("{ip}:{port}"),
+        Err(_) if parse_port_from_remote_host(peer).is_some() => peer.to_string(),
+        Err(_) => format!("{peer}:{port}"),
+    }
+}
+
+fn guidance(
+    prober: &str,
+    target: &str,
+    probe: &TcpProbeResult,
+    tailnet_name: Option<&str>,
+    policy_owner: Option<&str>,
+) -> String {
+    let policy = match tailnet_name {
+        Some(name) => format!("the {name} tailnet policy"),
+        None => "the tailnet policy".to_string(),
+    };
+    let signed_in = policy_owner
+        .map(|owner| format!(" This machine is signed in as {owner}."))
+        .unwrap_or_default();
+    let error = probe.error.as_deref().unwrap_or("unknown error");
+    match probe.outcome {
+        TcpProbeOutcome::Connected => {
+            format!("{prober} reached {target}, so {policy} allows the connection.")
+        }
+        TcpProbeOutcome::Refused => format!(
+            "{prober} reached the host of {target} but nothing listens on that port, \
+             so {policy} is not the problem. Start the daemon or check its listen address."
+        ),
+        TcpProbeOutcome::TimedOut => format!(
+            "{prober} got no answer from {target}, so {policy} most likely drops \
+             the traffic: an admin needs to add an ACL rule or grant letting {prober} reach \
+             that TCP port.{signed_in}"
+        ),
+        TcpProbeOutcome::Failed => format!(
+            "{prober} could not connect to {target}: {error}. Check that both devices \
+             are signed in to the same tailnet.{signed_in}"
+        ),
+    }
+}
+
+/// Asks the daemon on `peer` to connect to this machine's tailnet address.
+async fn reflected_probe(
+    settings: &crate::types::AppSettings,
+    peer: &str,
+    target_host: &str,
+    port: u16,
+) -> Result<TcpProbeResult, MonitorError> {
+    let mut peer_settings = settings.clone();
+    peer_settings.remote_backend_host = peer_daemon_host(peer, port);
+    let client = timeout(
+        PEER_CONNECT_TIMEOUT,
+        remote_backend::connect_configured(&peer_settings),
+    )
+    .await
+    .map_err(|_| {
+        MonitorError::ConnectTimeout(format!("Connecting to the daemon on {peer} timed out"))
+    })?
+    .map_err(|err| {
+        MonitorError::Network(format!(
+            "Could not reach the daemon on {peer} to probe from it: {err}"
+        ))
+    })?;
+    let result = timeout(
+        PEER_PROBE_TIMEOUT,
+        client.call(
+            REACHABILITY_PROBE_METHOD,
+            json!({ "host": target_host, "port": port }),
+        ),
+    )
+    .await
+    .map_err(|_| MonitorError::Timeout(format!("The daemon on {peer} did not answer")))??;
+    serde_json::from_value(result).map_err(|err| MonitorError::Network(err.to_string()))
+}
+
+pub(super) async fn check_tailnet_reachability(
+    state: &AppState,
+    peer: Option<String>,
+) -> Result<TailnetReachabilityReport, MonitorError> {
+    let settings = state.app_settings.read().await.clone();
+    let port =
+        parse_port_from_remote_host(&settings.remote_backend_host).unwrap_or(DEFAULT_DAEMON_PORT);
+    let status_payload = state.backends.tailscale.fetch_status_payload().await.ok();
+    let status = status_payload
+        .as_deref()
+        .and_then(|payload| tailscale_core::status_from_json(None, payload).ok());
+    let policy_owner = status_payload
+        .as_deref()
+        .and_then(tailscale_core::self_user_login);
+    let tailnet_name = status
+        .as_ref()
+        .and_then(|status| status.tailnet_name.clone());
+
+    let peer = peer
+        .map(|peer| peer.trim().to_string())
+        .filter(|peer| !peer.is_empty());
+    let (prober, target_host, probe) = match peer {
+        Some(peer) => {
+            // Policies match tailnet addresses, so probe the IP when known.
+            let target_host = status
+                .as_ref()
+                .and_then(|status| {
+                    status
+                        .ipv4
+                        .first()
+                        .or(status.ipv6.first())
+                        .or(status.dns_name.as_ref())
+                        .cloned()
+                })
+                .ok_or_else(|| {
+                    MonitorError::Config(
+                        "This machine has no tailnet address; is Tailscale running?".to_string(),
+                    )
+                })?;
+            let probe = reflected_probe(&settings, &peer, &target_host, port).await?;
+            (peer, target_host, probe)
+        }
+        None => {
+            let target_host = remote_backend::configured_host(&settings).ok_or_else(|| {
+                MonitorError::Config(format!(
+                    "Invalid remote backend host `{}`",
+                    settings.remote_backend_host
+                ))
+            })?;
+            let probe = reachability_core::probe_tcp(&target_host, port).await;
+            ("This device".to_string(), target_host, probe)
+        }
+    };
+
+    let target = if target_host.contains(':') {
+        format!("[{target_host}]:{port}")
+    } else {
+        format!("{target_host}:{port}")
+    };
+    let guidance = guidance(
+        &prober,
+        &target,
+        &probe,
+        tailnet_name.as_deref(),
+        policy_owner.as_deref(),
+    );
+    Ok(TailnetReachabilityReport {
+        prober,
+        target_host,
+        target_port: port,
+        reachable: probe.outcome == TcpProbeOutcome::Connected,
+        probe,
+        tailnet_name,
+        policy_owner,
+        guidance,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn probe(outcome: TcpProbeOutcome) -> TcpProbeResult {
+        TcpProbeResult {
+            outcome,
+            latency_ms: None,
+            error: Some("no answer within 3 seconds".to_string()),
+        }
+    }
+
+    #[test]
+    fn peer_hosts_get_the_daemon_port() {
+        assert_eq!(peer_daemon_host("studio", 4732), "studio:4732");
+        assert_eq!(peer_daemon_host("studio:9000", 4732), "studio:9000");
+        assert_eq!(peer_daemon_host("100.64.0.2", 4732), "100.64.0.2:4732");
+        assert_eq!(
+            peer_daemon_host("fd7a:115c:a1e0::2", 4732),
+            "[fd7a:115c:a1e0::2]:4732"
+        );
+    }
+
+    #[test]
+    fn guidance_blames_the_policy_only_for_dropped_traffic() {
+        let blocked = guidance(
+            "studio",
+            "100.64.0.1:4732",
+            &probe(TcpProbeOutcome::TimedOut),
+            Some("example.com"),
+            Some("admin@example.com"),
+        );
+        assert!(blocked.contains("the example.com tailnet policy most likely drops"));
+        assert!(blocked.contains("signed in as admin@example.com"));
+
+        let refused = guidance(
+            "studio",
+            "100.64.0.1:4732",
+            &probe(TcpProbeOutcome::Refused),
+            None,
+            None,
+        );
+        assert!(refused.contains("the tailnet policy is not the problem"));
+        assert!(!refused.contains("signed in"));
+    }
+}
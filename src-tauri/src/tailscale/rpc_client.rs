@@ -1,6 +1,27 @@
 use super::*;
 
-const DAEMON_RPC_TIMEOUT: Duration = Duration::from_millis(700);
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+
+use futures_util::future::join;
+use tokio::sync::{mpsc, oneshot, Mutex};
+use tokio::task::JoinHandle;
+
+use crate::shared::monitor_error::MonitorError;
+use crate::shared::rpc_framing_core::{FrameReader, MIN_MAX_FRAME_BYTES};
+use crate::shared::rpc_timeouts_core::{
+    builtin_timeout_class, connect_timeout_error, read_timeout_error, RpcTimeoutTable,
+};
+use crate::types::RpcTimeoutClass;
+
+/// Connect and `fast` limit for the local daemon, which answers liveness
+/// checks at once or is not worth waiting for.
+pub(super) const DAEMON_RPC_TIMEOUT: Duration = Duration::from_millis(700);
+/// Time the daemon gives its other clients between announcing shutdown and
+/// stopping its Codex sessions; `wait_for_daemon_shutdown` polls for longer
+/// than both together.
+const DAEMON_SHUTDOWN_DRAIN: Duration = Duration::from_millis(1000);
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub(super) struct DaemonInfo {
@@ -22,19 +43,12 @@ pub(super) enum DaemonProbe {
     NotDaemon,
 }
 
-type DaemonLines = tokio::io::Lines<BufReader<OwnedReadHalf>>;
+type DaemonLines = FrameReader<OwnedReadHalf>;
 
-fn parse_daemon_error_message(response: &Value) -> Option<String> {
-    response
-        .get("error")
-        .and_then(|error| error.get("message"))
-        .and_then(Value::as_str)
-        .map(str::to_string)
-}
-
-fn is_auth_error_message(message: &str) -> bool {
-    let lower = message.to_ascii_lowercase();
-    lower.contains("unauthorized") || lower.contains("invalid token")
+/// Everything the app asks the daemon over these connections has a small
+/// reply, so a larger line means something other than the daemon answered.
+fn daemon_lines(reader: OwnedReadHalf) -> DaemonLines {
+    FrameReader::new(reader, MIN_MAX_FRAME_BYTES)
 }
 
 fn parse_daemon_info(value: &Value) -> Result<DaemonInfo, String> {
@@ -79,79 +93,192 @@ fn parse_daemon_info(value: &Value) -> Result<DaemonInfo, String> {
     })
 }
 
-async fn send_rpc_request(
-    writer: &mut OwnedWriteHalf,
-    id: u64,
-    method: &str,
-    params: Value,
-) -> Result<(), String> {
-    let mut payload = serde_json::to_string(&json!({
-        "id": id,
-        "method": method,
-        "params": params,
-    }))
-    .map_err(|err| err.to_string())?;
-    payload.push('\n');
-    writer
-        .write_all(payload.as_bytes())
-        .await
-        .map_err(|err| err.to_string())
+/// Raw lines of the messages a daemon sends without an id.
+pub(super) type DaemonEvents = mpsc::UnboundedReceiver<String>;
+
+type PendingResponses = HashMap<u64, oneshot::Sender<Result<Value, MonitorError>>>;
+
+#[derive(Default)]
+struct Pending {
+    waiting: PendingResponses,
+    /// Why the reader stopped; later requests fail with it straight away.
+    closed: Option<MonitorError>,
+}
+
+/// One connection to the daemon. A reader task hands each response to the
+/// request with the same id, so several requests can be in flight at once,
+/// and forwards messages without an id (server events) to the receiver
+/// returned by `open`.
+pub(super) struct DaemonConnection {
+    writer: Mutex<OwnedWriteHalf>,
+    pending: Arc<Mutex<Pending>>,
+    next_id: AtomicU64,
+    reader_task: JoinHandle<()>,
 }
 
-async fn read_rpc_response(lines: &mut DaemonLines, expected_id: u64) -> Result<Value, String> {
-    let deadline = Instant::now() + DAEMON_RPC_TIMEOUT;
-    loop {
-        let now = Instant::now();
-        if now >= deadline {
-            return Err("timed out waiting for daemon response".to_string());
+impl DaemonConnection {
+    pub(super) fn open(stream: TcpStream) -> (Self, DaemonEvents) {
+        let (reader, writer) = stream.into_split();
+        let pending = Arc::new(Mutex::new(Pending::default()));
+        let (events_tx, events_rx) = mpsc::unbounded_channel();
+        let reader_task = tokio::spawn(route_responses(
+            daemon_lines(reader),
+            Arc::clone(&pending),
+            events_tx,
+        ));
+        let connection = Self {
+            writer: Mutex::new(writer),
+            pending,
+            next_id: AtomicU64::new(1),
+            reader_task,
+        };
+        (connection, events_rx)
+    }
+
+    pub(super) async fn call(&self, method: &str, params: Value) -> Result<Value, MonitorError> {
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed);
+        let (tx, rx) = oneshot::channel();
+        {
+            let mut pending = self.pending.lock().await;
+            if let Some(error) = &pending.closed {
+                return Err(error.clone());
+            }
+            pending.waiting.insert(id, tx);
+        }
+
+        let mut payload = serde_json::to_string(&json!({
+            "id": id,
+            "method": method,
+            "params": params,
+        }))
+        .map_err(|err| MonitorError::Process(err.to_string()))?;
+        payload.push('\n');
+        if let Err(err) = self.writer.lock().await.write_all(payload.as_bytes()).await {
+            self.pending.lock().await.waiting.remove(&id);
+            return Err(MonitorError::Network(err.to_string()));
         }
-        let remaining = deadline - now;
 
-        let line = match timeout(remaining, lines.next_line()).await {
-            Ok(Ok(Some(line))) => line,
-            Ok(Ok(None)) => return Err("connection closed".to_string()),
-            Ok(Err(err)) => return Err(err.to_string()),
-            Err(_) => return Err("timed out waiting for daemon response".to_string()),
+        let limit = daemon_call_timeout(method);
+        let response = match timeout(limit, rx).await {
+            Ok(Ok(response)) => response?,
+            Ok(Err(_)) => return Err(MonitorError::Network("connection closed".to_string())),
+            Err(_) => {
+                self.pending.lock().await.waiting.remove(&id);
+                return Err(read_timeout_error("daemon", method, limit));
+            }
+        };
+        if let Some(error) = response.get("error") {
+            return Err(MonitorError::from_rpc_error(error));
+        }
+        response
+            .get("result")
+            .cloned()
+            .ok_or_else(|| MonitorError::Process("daemon response missing result".to_string()))
+    }
+}
+
+/// Liveness calls keep the short local limit; anything heavier gets the
+/// same limit a remote client would.
+fn daemon_call_timeout(method: &str) -> Duration {
+    match builtin_timeout_class(method) {
+        RpcTimeoutClass::Fast => DAEMON_RPC_TIMEOUT,
+        class => RpcTimeoutTable::default().for_class(class),
+    }
+}
+
+impl Drop for DaemonConnection {
+    fn drop(&mut self) {
+        self.reader_task.abort();
+    }
+}
+
+async fn route_responses(
+    mut lines: DaemonLines,
+    pending: Arc<Mutex<Pending>>,
+    events: mpsc::UnboundedSender<String>,
+) {
+    let failure = loop {
+        let line = match lines.next_message().await {
+            Ok(Some(line)) => line,
+            Ok(None) => break MonitorError::Network("connection closed".to_string()),
+            Err(err) => break MonitorError::Network(err.to_string()),
         };
         if line.trim().is_empty() {
             continue;
         }
-        let parsed: Value = serde_json::from_str(&line).map_err(|err| err.to_string())?;
-        let id = parsed.get("id").and_then(Value::as_u64);
-        if id == Some(expected_id) {
-            return Ok(parsed);
+        // Anything that is not JSON means something other than the daemon
+        // is listening.
+        let message: Value = match serde_json::from_str(&line) {
+            Ok(message) => message,
+            Err(err) => break MonitorError::Process(err.to_string()),
+        };
+        match message.get("id").and_then(Value::as_u64) {
+            Some(id) => {
+                if let Some(sender) = pending.lock().await.waiting.remove(&id) {
+                    let _ = sender.send(Ok(message));
+                }
+            }
+            None => {
+                let _ = events.send(line);
+            }
         }
+    };
+
+    let mut pending = pending.lock().await;
+    for (_, sender) in pending.waiting.drain() {
+        let _ = sender.send(Err(failure.clone()));
     }
+    pending.closed = Some(failure);
 }
 
-async fn send_and_expect_result(
-    writer: &mut OwnedWriteHalf,
-    lines: &mut DaemonLines,
-    id: u64,
-    method: &str,
-    params: Value,
-) -> Result<Value, String> {
-    send_rpc_request(writer, id, method, params).await?;
-    let response = read_rpc_response(lines, id).await?;
-    if let Some(message) = parse_daemon_error_message(&response) {
-        return Err(message);
-    }
-    response
-        .get("result")
-        .cloned()
-        .ok_or_else(|| "daemon response missing result".to_string())
+pub(super) async fn request_daemon_info(
+    connection: &DaemonConnection,
+) -> Result<DaemonInfo, MonitorError> {
+    let result = connection.call("daemon_info", json!({})).await?;
+    parse_daemon_info(&result).map_err(MonitorError::Process)
 }
 
-async fn request_daemon_info(
-    writer: &mut OwnedWriteHalf,
-    lines: &mut DaemonLines,
-    id: u64,
-) -> Result<DaemonInfo, String> {
-    let result = send_and_expect_result(writer, lines, id, "daemon_info", json!({})).await?;
-    parse_daemon_info(&result)
+/// Pings and fetches `daemon_info` together, saving a round trip when the
+/// connection is already allowed in. The info is dropped if the ping fails.
+async fn ping_with_info(connection: &DaemonConnection) -> Result<Option<DaemonInfo>, MonitorError> {
+    let (ping, info) = join(
+        connection.call("ping", json!({})),
+        request_daemon_info(connection),
+    )
+    .await;
+    ping.map(|_| info.ok())
+}
+
+pub(super) const UNVERIFIED_DAEMON_ERROR: &str =
+    "Daemon is running, but this install has no daemon identity to check it against, so the token was not sent.";
+
+/// Challenges the listener to prove it holds the per-install daemon secret.
+/// Runs before any token is sent so an impostor on the port learns nothing;
+/// without a secret nothing vouches for the listener and no token is sent.
+pub(super) async fn verify_daemon_identity(connection: &DaemonConnection, secret: &str) -> bool {
+    let challenge = daemon_identity_core::new_identity_challenge();
+    let Ok(result) = connection
+        .call(
+            daemon_identity_core::DAEMON_IDENTIFY_METHOD,
+            json!({ "challenge": challenge }),
+        )
+        .await
+    else {
+        return false;
+    };
+    result
+        .get("proof")
+        .and_then(Value::as_str)
+        .is_some_and(|proof| {
+            daemon_identity_core::verify_daemon_identity_proof(secret, &challenge, proof)
+        })
 }
 
-pub(super) async fn probe_daemon(listen_addr: &str, token: Option<&str>) -> DaemonProbe {
+pub(super) async fn probe_daemon(
+    listen_addr: &str,
+    token: Option<&str>,
+    identity: Option<&str>,
+) -> DaemonProbe {
     let Some(connect_addr) = daemon_connect_addr(listen_addr) else {
         return DaemonProbe::NotReachable;
     };
@@ -161,20 +288,28 @@ pub(super) async fn probe_daemon(listen_addr: &str, token: Option<&str>) -> Daem
         Ok(Err(_)) | Err(_) => return DaemonProbe::NotReachable,
     };
 
-    let (reader, mut writer) = stream.into_split();
-    let mut lines = BufReader::new(reader).lines();
+    let (connection, _) = DaemonConnection::open(stream);
 
-    match send_and_expect_result(&mut writer, &mut lines, 1, "ping", json!({})).await {
-        Ok(_) => DaemonProbe::Running {
+    if let Some(secret) = identity {
+        if !verify_daemon_identity(&connection, secret).await {
+            return DaemonProbe::NotDaemon;
+        }
+    }
+
+    match ping_with_info(&connection).await {
+        Ok(info) => DaemonProbe::Running {
             auth_ok: true,
             auth_error: None,
-            info: request_daemon_info(&mut writer, &mut lines, 2).await.ok(),
+            info,
         },
-        Err(message) => {
-            if !is_auth_error_message(&message) {
-                return DaemonProbe::NotDaemon;
+        Err(MonitorError::Auth(_)) => {
+            if identity.is_none() {
+                return DaemonProbe::Running {
+                    auth_ok: false,
+                    auth_error: Some(UNVERIFIED_DAEMON_ERROR.to_string()),
+                    info: None,
+                };
             }
-
             let trimmed_token = token.map(str::trim).filter(|value| !value.is_empty());
             let Some(auth_token) = trimmed_token else {
                 return DaemonProbe::Running {
@@ -186,101 +321,121 @@ pub(super) async fn probe_daemon(listen_addr: &str, token: Option<&str>) -> Daem
                 };
             };
 
-            match send_and_expect_result(
-                &mut writer,
-                &mut lines,
-                10,
-                "auth",
-                json!({ "token": auth_token }),
-            )
-            .await
+            match connection
+                .call("auth", client_trust_core::client_auth_params(auth_token))
+                .await
             {
-                Ok(_) => {
-                    match send_and_expect_result(&mut writer, &mut lines, 11, "ping", json!({}))
-                        .await
-                    {
-                        Ok(_) => DaemonProbe::Running {
-                            auth_ok: true,
-                            auth_error: None,
-                            info: request_daemon_info(&mut writer, &mut lines, 12).await.ok(),
-                        },
-                        Err(ping_error) => DaemonProbe::Running {
-                            auth_ok: false,
-                            auth_error: Some(format!(
-                                "Daemon is running but ping failed after auth: {ping_error}"
-                            )),
-                            info: None,
-                        },
-                    }
-                }
-                Err(auth_error) => {
-                    if is_auth_error_message(&auth_error) {
-                        DaemonProbe::Running {
-                            auth_ok: false,
-                            auth_error: Some(format!(
-                                "Daemon is running but token authentication failed: {auth_error}"
-                            )),
-                            info: None,
-                        }
-                    } else {
-                        DaemonProbe::NotDaemon
-                    }
-                }
+                Ok(_) => match ping_with_info(&connection).await {
+                    Ok(info) => DaemonProbe::Running {
+                        auth_ok: true,
+                        auth_error: None,
+                        info,
+                    },
+                    Err(ping_error) => DaemonProbe::Running {
+                        auth_ok: false,
+                        auth_error: Some(format!(
+                            "Daemon is running but ping failed after auth: {ping_error}"
+                        )),
+                        info: None,
+                    },
+                },
+                Err(MonitorError::Auth(auth_error)) => DaemonProbe::Running {
+                    auth_ok: false,
+                    auth_error: Some(format!(
+                        "Daemon is running but token authentication failed: {auth_error}"
+                    )),
+                    info: None,
+                },
+                Err(_) => DaemonProbe::NotDaemon,
             }
         }
+        Err(_) => DaemonProbe::NotDaemon,
     }
 }
 
 pub(super) async fn request_daemon_shutdown(
     listen_addr: &str,
     token: Option<&str>,
-) -> Result<(), String> {
+    identity: Option<&str>,
+) -> Result<(), MonitorError> {
     let Some(connect_addr) = daemon_connect_addr(listen_addr) else {
-        return Err("invalid daemon listen address".to_string());
+        return Err(MonitorError::Config(
+            "invalid daemon listen address".to_string(),
+        ));
     };
 
     let stream = timeout(DAEMON_RPC_TIMEOUT, TcpStream::connect(&connect_addr))
         .await
-        .map_err(|_| format!("Timed out connecting to daemon at {connect_addr}"))?
-        .map_err(|err| format!("Failed to connect to daemon at {connect_addr}: {err}"))?;
+        .map_err(|_| {
+            connect_timeout_error(&format!("daemon at {connect_addr}"), DAEMON_RPC_TIMEOUT)
+        })?
+        .map_err(|err| {
+            MonitorError::Network(format!(
+                "Failed to connect to daemon at {connect_addr}: {err}"
+            ))
+        })?;
 
-    let (reader, mut writer) = stream.into_split();
-    let mut lines = BufReader::new(reader).lines();
+    let (connection, _) = DaemonConnection::open(stream);
 
-    match send_and_expect_result(&mut writer, &mut lines, 1, "ping", json!({})).await {
+    if let Some(secret) = identity {
+        if !verify_daemon_identity(&connection, secret).await {
+            return Err(MonitorError::Auth(format!(
+                "Process at {connect_addr} could not prove it is this install's daemon."
+            )));
+        }
+    }
+
+    match connection.call("ping", json!({})).await {
         Ok(_) => {}
-        Err(message) if is_auth_error_message(&message) => {
+        Err(MonitorError::Auth(_)) => {
+            if identity.is_none() {
+                return Err(MonitorError::Auth(UNVERIFIED_DAEMON_ERROR.to_string()));
+            }
             let auth_token = token
                 .map(str::trim)
                 .filter(|value| !value.is_empty())
                 .ok_or_else(|| {
-                    "Daemon is running but requires a remote backend token.".to_string()
+                    MonitorError::Auth(
+                        "Daemon is running but requires a remote backend token.".to_string(),
+                    )
                 })?;
-            send_and_expect_result(
-                &mut writer,
-                &mut lines,
-                2,
-                "auth",
-                json!({ "token": auth_token }),
-            )
-            .await
-            .map_err(|err| format!("Daemon authentication failed: {err}"))?;
+            connection
+                .call("auth", client_trust_core::client_auth_params(auth_token))
+                .await
+                .map_err(|err| {
+                    MonitorError::Auth(format!("Daemon authentication failed: {err}"))
+                })?;
+        }
+        Err(error) if error.is_network() => {
+            return Err(MonitorError::Network(format!(
+                "Daemon ping failed: {error}"
+            )));
         }
-        Err(message) => {
-            return Err(format!("Daemon ping failed: {message}"));
+        Err(error) => {
+            return Err(MonitorError::Process(format!(
+                "Daemon ping failed: {error}"
+            )));
         }
     }
 
-    send_and_expect_result(&mut writer, &mut lines, 3, "daemon_shutdown", json!({}))
+    connection
+        .call(
+            "daemon_shutdown",
+            json!({ "drainMs": DAEMON_SHUTDOWN_DRAIN.as_millis() as u64 }),
+        )
         .await
         .map(|_| ())
-        .map_err(|err| format!("Daemon shutdown request failed: {err}"))
+        .map_err(|err| MonitorError::Process(format!("Daemon shutdown request failed: {err}")))
 }
 
-pub(super) async fn wait_for_daemon_shutdown(listen_addr: &str, token: Option<&str>) -> bool {
-    for _ in 0..20 {
+pub(super) async fn wait_for_daemon_shutdown(
+    listen_addr: &str,
+    token: Option<&str>,
+    identity: Option<&str>,
+) -> bool {
+    for _ in 0..50 {
         if matches!(
-            probe_daemon(listen_addr, token).await,
+            probe_daemon(listen_addr, token, identity).await,
             DaemonProbe::NotReachable
         ) {
             return true;
@@ -289,3 +444,216 @@ pub(super) async fn wait_for_daemon_shutdown(listen_addr: &str, token: Option<&s
     }
     false
 }
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::*;
+    use crate::mock_daemon::{MockDaemon, MockDaemonConfig};
+
+    fn run<F: std::future::Future>(future: F) -> F::Output {
+        tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .expect("runtime")
+            .block_on(future)
+    }
+
+    fn token_config(legacy_errors: bool) -> MockDaemonConfig {
+        MockDaemonConfig {
+            token: Some("secret-token".to_string()),
+            identity_secret: Some("install-secret".to_string()),
+            legacy_errors,
+            ..MockDaemonConfig::default()
+        }
+    }
+
+    #[test]
+    fn probe_reports_token_state_for_current_and_legacy_daemons() {
+        run(async {
+            for legacy_errors in [false, true] {
+                let daemon = MockDaemon::spawn(token_config(legacy_errors)).await;
+                let identity = Some("install-secret");
+
+                match probe_daemon(&daemon.addr, Some("secret-token"), identity).await {
+                    DaemonProbe::Running {
+                        auth_ok: true,
+                        info: Some(info),
+                        ..
+                    } => assert_eq!(info.name, "codex-monitor-daemon"),
+                    other => panic!("expected authenticated daemon, got {other:?}"),
+                }
+                assert!(matches!(
+                    probe_daemon(&daemon.addr, None, identity).await,
+                    DaemonProbe::Running { auth_ok: false, .. }
+                ));
+                match probe_daemon(&daemon.addr, Some("wrong"), identity).await {
+                    DaemonProbe::Running {
+                        auth_ok: false,
+                        auth_error: Some(error),
+                        ..
+                    } => assert!(error.contains("token authentication failed")),
+                    other => panic!("expected auth failure, got {other:?}"),
+                }
+            }
+        });
+    }
+
+    #[test]
+    fn probe_distinguishes_impostors_failures_and_silence() {
+        run(async {
+            let daemon = MockDaemon::spawn(token_config(false)).await;
+            assert!(matches!(
+                probe_daemon(&daemon.addr, Some("secret-token"), Some("other-secret")).await,
+                DaemonProbe::NotDaemon
+            ));
+            // Without this install's identity the token is never sent.
+            match probe_daemon(&daemon.addr, Some("secret-token"), None).await {
+                DaemonProbe::Running {
+                    auth_ok: false,
+                    auth_error: Some(error),
+                    ..
+                } => assert_eq!(error, UNVERIFIED_DAEMON_ERROR),
+                other => panic!("expected an unverified daemon, got {other:?}"),
+            }
+
+            let failing = MockDaemon::spawn(MockDaemonConfig {
+                failures: HashMap::from([("ping".to_string(), "boom".to_string())]),
+                ..MockDaemonConfig::default()
+            })
+            .await;
+            assert!(matches!(
+                probe_daemon(&failing.addr, None, None).await,
+                DaemonProbe::NotDaemon
+            ));
+
+            let slow = MockDaemon::spawn(MockDaemonConfig {
+                latency: DAEMON_RPC_TIMEOUT * 2,
+                ..MockDaemonConfig::default()
+            })
+            .await;
+            assert!(matches!(
+                probe_daemon(&slow.addr, None, None).await,
+                DaemonProbe::NotDaemon
+            ));
+
+            let addr = daemon.addr.clone();
+            drop(daemon);
+            sleep(Duration::from_millis(50)).await;
+            assert!(matches!(
+                probe_daemon(&addr, None, None).await,
+                DaemonProbe::NotReachable
+            ));
+        });
+    }
+
+    #[test]
+    fn concurrent_requests_get_their_own_responses_and_events_pass_through() {
+        run(async {
+            let daemon = MockDaemon::spawn(MockDaemonConfig {
+                delays: HashMap::from([("daemon_info".to_string(), Duration::from_millis(300))]),
+                ..MockDaemonConfig::default()
+            })
+            .await;
+            let stream = TcpStream::connect(&daemon.addr).await.expect("connect");
+            let (connection, mut events) = DaemonConnection::open(stream);
+
+            let finished = std::sync::Mutex::new(Vec::new());
+            let (info, ping) = join(
+                async {
+                    let info = request_daemon_info(&connection).await;
+                    finished.lock().expect("finished").push("daemon_info");
+                    info
+                },
+                async {
+                    let ping = connection.call("ping", json!({})).await;
+                    finished.lock().expect("finished").push("ping");
+                    daemon.notify("daemon-heartbeat", json!({}));
+                    ping
+                },
+            )
+            .await;
+            assert_eq!(info.expect("info").name, "codex-monitor-daemon");
+            ping.expect("ping");
+            assert_eq!(*finished.lock().expect("finished"), ["ping", "daemon_info"]);
+            let event = timeout(DAEMON_RPC_TIMEOUT, events.recv())
+                .await
+                .expect("event")
+                .expect("events open");
+            assert!(event.contains("daemon-heartbeat"), "{event}");
+        });
+    }
+
+    #[test]
+    fn closed_connections_fail_waiting_and_later_requests() {
+        run(async {
+            let daemon = MockDaemon::spawn(MockDaemonConfig {
+                hang_up_on: vec!["ping".to_string()],
+                ..MockDaemonConfig::default()
+            })
+            .await;
+            let stream = TcpStream::connect(&daemon.addr).await.expect("connect");
+            let (connection, _) = DaemonConnection::open(stream);
+
+            let closed = MonitorError::Network("connection closed".to_string());
+            assert_eq!(
+                connection.call("ping", json!({})).await,
+                Err(closed.clone())
+            );
+            assert_eq!(connection.call("daemon_info", json!({})).await, Err(closed));
+        });
+    }
+
+    #[test]
+    fn shutdown_authenticates_and_stops_the_daemon() {
+        run(async {
+            let daemon = MockDaemon::spawn(token_config(false)).await;
+            let identity = Some("install-secret");
+
+            assert!(matches!(
+                request_daemon_shutdown(&daemon.addr, None, identity).await,
+                Err(MonitorError::Auth(_))
+            ));
+            assert!(matches!(
+                request_daemon_shutdown(&daemon.addr, Some("wrong"), identity).await,
+                Err(MonitorError::Auth(_))
+            ));
+            request_daemon_shutdown(&daemon.addr, Some("secret-token"), identity)
+                .await
+                .expect("shutdown");
+            assert!(wait_for_daemon_shutdown(&daemon.addr, Some("secret-token"), identity).await);
+            assert_eq!(
+                daemon.methods().iter().rev().take(3).collect::<Vec<_>>(),
+                ["daemon_shutdown", "auth", "ping"]
+            );
+        });
+    }
+
+    #[test]
+    fn shutdown_reports_network_and_daemon_failures() {
+        run(async {
+            let hanging = MockDaemon::spawn(MockDaemonConfig {
+                hang_up_on: vec!["ping".to_string()],
+                ..MockDaemonConfig::default()
+            })
+            .await;
+            assert!(matches!(
+                request_daemon_shutdown(&hanging.addr, None, None).await,
+                Err(MonitorError::Network(_))
+            ));
+
+            let refusing = MockDaemon::spawn(MockDaemonConfig {
+                failures: HashMap::from([("daemon_shutdown".to_string(), "busy".to_string())]),
+                ..MockDaemonConfig::default()
+            })
+            .await;
+            match request_daemon_shutdown(&refusing.addr, None, None).await {
+                Err(MonitorError::Process(message)) => {
+                    assert_eq!(message, "Daemon shutdown request failed: busy")
+                }
+                other => panic!("expected process error, got {other:?}"),
+            }
+        });
+    }
+}
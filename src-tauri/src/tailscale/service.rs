@@ -0,0 +1,316 @@
+// Only the launchd and systemd paths are wired up; other targets keep the
+// shared helpers for tests.
+#![cfg_attr(not(unix), allow(dead_code))]
+
+use std::path::{Path, PathBuf};
+
+use crate::types::DaemonServiceManager;
+
+use super::*;
+
+#[cfg(target_os = "macos")]
+const LAUNCHD_LABEL: &str = "com.dimillian.codexmonitor.daemon";
+#[cfg(all(unix, not(target_os = "macos")))]
+const SYSTEMD_UNIT_NAME: &str = "codex-monitor-daemon.service";
+
+/// Everything a service definition needs to start the daemon the same way
+/// `tailscale_daemon_start` does.
+pub(super) struct DaemonServiceSpec {
+    pub(super) daemon_binary: PathBuf,
+    pub(super) listen_addr: String,
+    pub(super) data_dir: PathBuf,
+    pub(super) app_data_dir: PathBuf,
+    pub(super) token: String,
+}
+
+fn home_dir() -> Result<PathBuf, String> {
+    std::env::var_os("HOME")
+        .filter(|value| !value.is_empty())
+        .map(PathBuf::from)
+        .ok_or_else(|| "Unable to resolve home directory".to_string())
+}
+
+fn xml_escape(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/// The token goes in the environment rather than the argument list so it
+/// does not show up in `ps`. Clean exits (`daemon_shutdown`) are not
+/// restarted, so stopping from the app still works.
+#[cfg_attr(not(target_os = "macos"), allow(dead_code))]
+fn launchd_plist(spec: &DaemonServiceSpec, label: &str, log_path: &Path) -> String {
+    let arguments = [
+        spec.daemon_binary.to_string_lossy().to_string(),
+        "--listen".to_string(),
+        spec.listen_addr.clone(),
+        "--data-dir".to_string(),
+        spec.data_dir.to_string_lossy().to_string(),
+        "--app-data-dir".to_string(),
+        spec.app_data_dir.to_string_lossy().to_string(),
+    ]
+    .iter()
+    .map(|value| format!("        <string>{}</string>\n", xml_escape(value)))
+    .collect::<String>();
+    format!(
+        r#"<?xml version="1.0" encoding="UTF-8"?>
+<!DOCTYPE plist PUBLIC "-//Apple//DTD PLIST 1.0//EN" "http://www.apple.com/DTDs/PropertyList-1.0.dtd">
+<plist version="1.0">
+<dict>
+    <key>Label</key>
+    <string>{label}</string>
+    <key>ProgramArguments</key>
+    <array>
+{arguments}    </array>
+    <key>EnvironmentVariables</key>
+    <dict>
+        <key>CODEX_MONITOR_DAEMON_TOKEN</key>
+        <string>{token}</string>
+    </dict>
+    <key>RunAtLoad</key>
+    <true/>
+    <key>KeepAlive</key>
+    <dict>
+        <key>SuccessfulExit</key>
+        <false/>
+    </dict>
+    <key>StandardErrorPath</key>
+    <string>{log}</string>
+</dict>
+</plist>
+"#,
+        label = xml_escape(label),
+        token = xml_escape(&spec.token),
+        log = xml_escape(&log_path.to_string_lossy()),
+    )
+}
+
+/// Quotes one systemd `ExecStart` word; `%` is a specifier prefix.
+fn systemd_quote(value: &str) -> String {
+    let escaped = value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('%', "%%");
+    format!("\"{escaped}\"")
+}
+
+/// Runs `--healthcheck` first so a port held by another process fails the
+/// unit instead of looping on bind errors.
+#[cfg_attr(target_os = "macos", allow(dead_code))]
+fn systemd_unit(spec: &DaemonServiceSpec) -> String {
+    let binary = systemd_quote(&spec.daemon_binary.to_string_lossy());
+    let shared_args = format!(
+        "--listen {} --data-dir {} --app-data-dir {}",
+        systemd_quote(&spec.listen_addr),
+        systemd_quote(&spec.data_dir.to_string_lossy()),
+        systemd_quote(&spec.app_data_dir.to_string_lossy())
+    );
+    format!(
+        "[Unit]\n\
+Description=CodexMonitor mobile access daemon\n\
+After=network-online.target\n\
+\n\
+[Service]\n\
+Environment={token}\n\
+ExecStartPre={binary} --healthcheck {shared_args}\n\
+ExecStart={binary} {shared_args}\n\
+Restart=on-failure\n\
+RestartSec=5\n\
+\n\
+[Install]\n\
+WantedBy=default.target\n",
+        token = systemd_quote(&format!("CODEX_MONITOR_DAEMON_TOKEN={}", spec.token)),
+    )
+}
+
+fn write_private_file(path: &Path, contents: &str) -> Result<(), String> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent).map_err(|err| err.to_string())?;
+    }
+    std::fs::write(path, contents).map_err(|err| err.to_string())?;
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        std::fs::set_permissions(path, std::fs::Permissions::from_mode(0o600))
+            .map_err(|err| err.to_string())?;
+    }
+    Ok(())
+}
+
+async fn run_service_command(program: &str, args: &[&str]) -> Result<(), String> {
+    let output = tokio_command(program)
+        .args(args)
+        .output()
+        .await
+        .map_err(|err| format!("Failed to run {program}: {err}"))?;
+    if output.status.success() {
+        return Ok(());
+    }
+    let stderr = String::from_utf8_lossy(&output.stderr).trim().to_string();
+    Err(format!(
+        "{program} {} failed: {}",
+        args.join(" "),
+        if stderr.is_empty() {
+            output.status.to_string()
+        } else {
+            stderr
+        }
+    ))
+}
+
+#[cfg(target_os = "macos")]
+fn service_definition_path() -> Result<PathBuf, String> {
+    Ok(home_dir()?
+        .join("Library")
+        .join("LaunchAgents")
+        .join(format!("{LAUNCHD_LABEL}.plist")))
+}
+
+#[cfg(all(unix, not(target_os = "macos")))]
+fn service_definition_path() -> Result<PathBuf, String> {
+    let config_home = std::env::var_os("XDG_CONFIG_HOME")
+        .filter(|value| !value.is_empty())
+        .map(PathBuf::from)
+        .map(Ok)
+        .unwrap_or_else(|| home_dir().map(|home| home.join(".config")))?;
+    Ok(config_home
+        .join("systemd")
+        .join("user")
+        .join(SYSTEMD_UNIT_NAME))
+}
+
+/// Which service manager owns the daemon, if a service is installed.
+#[cfg(unix)]
+pub(crate) fn installed_daemon_service() -> Option<DaemonServiceManager> {
+    let installed = service_definition_path().is_ok_and(|path| path.exists());
+    let manager = if cfg!(target_os = "macos") {
+        DaemonServiceManager::Launchd
+    } else {
+        DaemonServiceManager::Systemd
+    };
+    installed.then_some(manager)
+}
+
+#[cfg(not(unix))]
+pub(crate) fn installed_daemon_service() -> Option<DaemonServiceManager> {
+    None
+}
+
+/// Installs a per-user LaunchAgent. It starts at login and survives the
+/// app quitting, but like all LaunchAgents it stops when the user logs out.
+#[cfg(target_os = "macos")]
+pub(super) async fn install_service(spec: &DaemonServiceSpec) -> Result<(), String> {
+    let path = service_definition_path()?;
+    let log_path = spec.app_data_dir.join("daemon-service.log");
+    write_private_file(&path, &launchd_plist(spec, LAUNCHD_LABEL, &log_path))?;
+    let domain = format!("gui/{}", unsafe { libc::geteuid() });
+    // Replace a previously loaded definition; failure just means none was loaded.
+    let _ = run_service_command(
+        "/bin/launchctl",
+        &["bootout", &format!("{domain}/{LAUNCHD_LABEL}")],
+    )
+    .await;
+    run_service_command(
+        "/bin/launchctl",
+        &["bootstrap", &domain, &path.to_string_lossy()],
+    )
+    .await
+}
+
+#[cfg(target_os = "macos")]
+pub(super) async fn uninstall_service() -> Result<(), String> {
+    let path = service_definition_path()?;
+    let domain = format!("gui/{}", unsafe { libc::geteuid() });
+    let _ = run_service_command(
+        "/bin/launchctl",
+        &["bootout", &format!("{domain}/{LAUNCHD_LABEL}")],
+    )
+    .await;
+    match std::fs::remove_file(&path) {
+        Ok(()) => Ok(()),
+        Err(err) if err.kind() == ErrorKind::NotFound => Ok(()),
+        Err(err) => Err(err.to_string()),
+    }
+}
+
+/// Installs a systemd user unit and enables lingering so it keeps running
+/// after logout.
+#[cfg(all(unix, not(target_os = "macos")))]
+pub(super) async fn install_service(spec: &DaemonServiceSpec) -> Result<(), String> {
+    let path = service_definition_path()?;
+    write_private_file(&path, &systemd_unit(spec))?;
+    run_service_command("systemctl", &["--user", "daemon-reload"]).await?;
+    run_service_command(
+        "systemctl",
+        &["--user", "enable", "--now", SYSTEMD_UNIT_NAME],
+    )
+    .await?;
+    // Lingering may need polkit approval; without it the unit still runs
+    // for as long as the user has a session.
+    let _ = run_service_command("loginctl", &["enable-linger"]).await;
+    Ok(())
+}
+
+#[cfg(all(unix, not(target_os = "macos")))]
+pub(super) async fn uninstall_service() -> Result<(), String> {
+    let path = service_definition_path()?;
+    let _ = run_service_command(
+        "systemctl",
+        &["--user", "disable", "--now", SYSTEMD_UNIT_NAME],
+    )
+    .await;
+    match std::fs::remove_file(&path) {
+        Ok(()) => {}
+        Err(err) if err.kind() == ErrorKind::NotFound => {}
+        Err(err) => return Err(err.to_string()),
+    }
+    run_service_command("systemctl", &["--user", "daemon-reload"]).await
+}
+
+#[cfg(not(unix))]
+pub(super) async fn install_service(_spec: &DaemonServiceSpec) -> Result<(), String> {
+    Err("Daemon service installation is only supported on macOS and Linux.".to_string())
+}
+
+#[cfg(not(unix))]
+pub(super) async fn uninstall_service() -> Result<(), String> {
+    Err("Daemon service installation is only supported on macOS and Linux.".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn spec() -> DaemonServiceSpec {
+        DaemonServiceSpec {
+            daemon_binary: PathBuf::from("/Applications/Codex Monitor.app/codex-monitor-daemon"),
+            listen_addr: "0.0.0.0:4732".to_string(),
+            data_dir: PathBuf::from("/home/me/data/daemon"),
+            app_data_dir: PathBuf::from("/home/me/data"),
+            token: "tok&<en>%".to_string(),
+        }
+    }
+
+    #[test]
+    fn launchd_plist_escapes_values_and_keeps_token_out_of_arguments() {
+        let plist = launchd_plist(&spec(), "com.example.daemon", Path::new("/tmp/log"));
+        assert!(plist.contains("<string>tok&amp;&lt;en&gt;%</string>"));
+        let arguments = &plist[plist.find("<array>").unwrap()..plist.find("</array>").unwrap()];
+        assert!(!arguments.contains("tok"));
+        assert!(arguments.contains("<string>--data-dir</string>"));
+        assert!(arguments.contains("<string>--app-data-dir</string>"));
+    }
+
+    #[test]
+    fn systemd_unit_quotes_paths_and_specifiers() {
+        let unit = systemd_unit(&spec());
+        assert!(unit.contains(
+            "ExecStart=\"/Applications/Codex Monitor.app/codex-monitor-daemon\" --listen \"0.0.0.0:4732\""
+        ));
+        assert!(unit.contains("Environment=\"CODEX_MONITOR_DAEMON_TOKEN=tok&<en>%%\""));
+        assert!(unit.contains("ExecStartPre=") && unit.contains("--healthcheck"));
+    }
+}
@@ -0,0 +1,399 @@
+use std::path::{Path, PathBuf};
+use std::pin::pin;
+use std::sync::Arc;
+
+use futures_util::future::{select, Either};
+use tauri::Manager;
+use tokio::sync::Notify;
+
+use super::local_api;
+use super::*;
+use crate::notifications::show_system_notification;
+use crate::state::TailscaleStatusCache;
+
+const TAILSCALE_STATUS_CHANGED_EVENT: &str = "tailscale-status-changed";
+const MIN_STATUS_TTL_SECS: u32 = 5;
+const BUS_CHANGE_DEBOUNCE: Duration = Duration::from_millis(500);
+const LAST_KNOWN_STATUS_FILENAME: &str = "tailscale-last-status.json";
+
+fn status_ttl(ttl_secs: u32) -> Duration {
+    Duration::from_secs(u64::from(ttl_secs.max(MIN_STATUS_TTL_SECS)))
+}
+
+fn fresh_status(
+    cache: &TailscaleStatusCache,
+    ttl: Duration,
+    now: Instant,
+) -> Option<TailscaleStatus> {
+    let fetched_at = cache.fetched_at?;
+    if now.saturating_duration_since(fetched_at) >= ttl {
+        return None;
+    }
+    cache.status.clone()
+}
+
+fn last_known_status_path(data_dir: &Path) -> PathBuf {
+    data_dir.join(LAST_KNOWN_STATUS_FILENAME)
+}
+
+fn read_last_known_status(data_dir: &Path) -> Option<TailscaleStatus> {
+    let data = std::fs::read_to_string(last_known_status_path(data_dir)).ok()?;
+    serde_json::from_str(&data).ok()
+}
+
+fn write_last_known_status(data_dir: &Path, status: &TailscaleStatus) -> Result<(), String> {
+    std::fs::create_dir_all(data_dir).map_err(|err| err.to_string())?;
+    let data = serde_json::to_string_pretty(status).map_err(|err| err.to_string())?;
+    std::fs::write(last_known_status_path(data_dir), data).map_err(|err| err.to_string())
+}
+
+/// Whether `status` names this device on the tailnet, and so is worth
+/// keeping for when Tailscale cannot be reached.
+fn has_identity(status: &TailscaleStatus) -> bool {
+    status.running
+        && (status.dns_name.is_some() || !status.ipv4.is_empty() || !status.ipv6.is_empty())
+}
+
+/// `live` when it names this device, otherwise the last status that did,
+/// marked stale and carrying the live message, so pairing details stay
+/// visible while tailscaled is down.
+fn with_last_known(live: TailscaleStatus, last_known: Option<TailscaleStatus>) -> TailscaleStatus {
+    let Some(last_known) = last_known.filter(|_| !has_identity(&live)) else {
+        return live;
+    };
+    TailscaleStatus {
+        installed: live.installed,
+        running: false,
+        version: live.version.or(last_known.version),
+        message: live.message,
+        stale: true,
+        ..last_known
+    }
+}
+
+async fn configured_ttl(state: &AppState) -> Duration {
+    status_ttl(state.app_settings.read().await.tailscale_status_ttl_secs)
+}
+
+/// Returns the cached status while it is younger than the configured TTL,
+/// otherwise asks the Tailscale provider and emits `tailscale-status-changed` when the
+/// result differs. A status that names this device is persisted; when a
+/// later one does not, the persisted one is returned marked `stale`. The cache lock is held across the fetch so concurrent
+/// callers share one CLI run.
+pub(super) async fn tailscale_status(
+    state: &AppState,
+    force_refresh: bool,
+) -> Result<TailscaleStatus, String> {
+    let ttl = configured_ttl(state).await;
+    let mut cache = state.tailscale_status.lock().await;
+    if !force_refresh {
+        if let Some(status) = fresh_status(&cache, ttl, Instant::now()) {
+            return Ok(status);
+        }
+    }
+
+    // A mock status is neither persisted nor mixed with a real last known one.
+    let data_dir = Some(state.data_dir()).filter(|_| !state.backends.is_mock());
+    let status = match state.backends.tailscale.fetch_status().await {
+        Ok(live) if has_identity(&live) => {
+            if let Some(data_dir) = data_dir {
+                let last_known = TailscaleStatus {
+                    last_seen_ms: Some(now_unix_ms()),
+                    ..live.clone()
+                };
+                if let Err(err) = write_last_known_status(data_dir, &last_known) {
+                    eprintln!("tailscale_status: failed to persist last known status: {err}");
+                }
+            }
+            live
+        }
+        Ok(live) => with_last_known(live, data_dir.and_then(read_last_known_status)),
+        Err(err) => match data_dir.and_then(read_last_known_status) {
+            Some(last_known) => {
+                with_last_known(degraded_tailscale_status(None, err), Some(last_known))
+            }
+            None => return Err(err),
+        },
+    };
+    let changed = cache.status.as_ref() != Some(&status);
+    cache.status = Some(status.clone());
+    cache.fetched_at = Some(Instant::now());
+    drop(cache);
+
+    if changed {
+        state
+            .event_bus
+            .publish(TAILSCALE_STATUS_CHANGED_EVENT, &status);
+    }
+    Ok(status)
+}
+
+/// The notification body for a live status whose node key expires soon, or
+/// `None` when there is nothing to warn about.
+fn key_expiry_warning(status: &TailscaleStatus, now_ms: i64) -> Option<String> {
+    if status.stale || !status.running || !status.key_expiring_soon {
+        return None;
+    }
+    let expiry_ms = status.key_expiry_ms?;
+    let node = status
+        .dns_name
+        .as_deref()
+        .or(status.host_name.as_deref())
+        .unwrap_or("this device");
+    let when = if expiry_ms <= now_ms {
+        "has expired".to_string()
+    } else {
+        let days = (expiry_ms - now_ms) / (24 * 60 * 60 * 1000);
+        match days {
+            0 => "expires within a day".to_string(),
+            1 => "expires in 1 day".to_string(),
+            days => format!("expires in {days} days"),
+        }
+    };
+    Some(format!(
+        "The Tailscale key for {node} {when}. Re-authenticate it or disable key expiry \
+         so remote access keeps working."
+    ))
+}
+
+/// Notifies once per node key about an imminent expiry, since an expired
+/// key cuts off mobile access without any other sign.
+async fn warn_key_expiry(
+    app: &AppHandle,
+    state: &AppState,
+    status: &TailscaleStatus,
+    warned_for: &mut Option<i64>,
+) {
+    if *warned_for == status.key_expiry_ms {
+        return;
+    }
+    let Some(body) = key_expiry_warning(status, now_unix_ms()) else {
+        return;
+    };
+    *warned_for = status.key_expiry_ms;
+    if state.app_settings.read().await.system_notifications_enabled {
+        show_system_notification(app, "Tailscale key expiring", &body, true).await;
+    }
+}
+
+/// Refreshes the cached status every TTL, and shortly after tailscaled
+/// reports a change on its IPN bus, so listeners of
+/// `tailscale-status-changed` see changes without polling. Idle until the
+/// first status request, so users who never open the server settings never
+/// query Tailscale. Warns when the node key is about to expire.
+pub(crate) fn spawn_tailscale_status_refresher(app: AppHandle) {
+    let changed = Arc::new(Notify::new());
+    tauri::async_runtime::spawn(local_api::watch_ipn_bus_forever(Arc::clone(&changed)));
+    tauri::async_runtime::spawn(async move {
+        let mut key_expiry_warned = None;
+        loop {
+            let state = app.state::<AppState>();
+            let ttl = configured_ttl(&state).await;
+            let pushed = matches!(
+                select(pin!(changed.notified()), pin!(sleep(ttl))).await,
+                Either::Left(_)
+            );
+            if pushed {
+                // Let a burst of bus notifications settle into one refresh.
+                sleep(BUS_CHANGE_DEBOUNCE).await;
+            }
+            if state.tailscale_status.lock().await.status.is_none() {
+                continue;
+            }
+            if let Ok(status) = tailscale_status(&state, pushed).await {
+                warn_key_expiry(&app, &state, &status, &mut key_expiry_warned).await;
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::VecDeque;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use super::super::providers::{ProviderFuture, TailscaleProvider};
+    use super::*;
+
+    fn sample_status() -> TailscaleStatus {
+        TailscaleStatus {
+            installed: true,
+            running: true,
+            version: Some("1.80.0".to_string()),
+            dns_name: Some("mac.example.ts.net".to_string()),
+            host_name: Some("mac".to_string()),
+            tailnet_name: Some("example.ts.net".to_string()),
+            ipv4: vec!["100.64.0.1".to_string()],
+            ipv6: Vec::new(),
+            suggested_remote_host: Some("mac.example.ts.net:4732".to_string()),
+            message: "Tailscale is running.".to_string(),
+            tags: Vec::new(),
+            key_expiry_ms: None,
+            key_expiring_soon: false,
+            stale: false,
+            last_seen_ms: None,
+        }
+    }
+
+    #[test]
+    fn cached_status_expires_after_ttl() {
+        let now = Instant::now();
+        let ttl = status_ttl(30);
+        assert!(fresh_status(&TailscaleStatusCache::default(), ttl, now).is_none());
+
+        let cache = TailscaleStatusCache {
+            status: Some(sample_status()),
+            fetched_at: Some(now),
+        };
+        assert_eq!(
+            fresh_status(&cache, ttl, now + Duration::from_secs(29)),
+            Some(sample_status())
+        );
+        assert!(fresh_status(&cache, ttl, now + ttl).is_none());
+    }
+
+    #[test]
+    fn ttl_has_a_floor() {
+        assert_eq!(status_ttl(0), Duration::from_secs(5));
+        assert_eq!(status_ttl(120), Duration::from_secs(120));
+    }
+
+    #[test]
+    fn unreachable_tailscale_falls_back_to_the_last_known_identity() {
+        let last_known = TailscaleStatus {
+            last_seen_ms: Some(1_700_000_000_000),
+            ..sample_status()
+        };
+        let down = degraded_tailscale_status(
+            Some("1.82.0".to_string()),
+            "Failed to connect to local Tailscale daemon.".to_string(),
+        );
+
+        let status = with_last_known(down.clone(), Some(last_known));
+        assert!(status.stale);
+        assert!(!status.running);
+        assert_eq!(status.last_seen_ms, Some(1_700_000_000_000));
+        assert_eq!(status.dns_name.as_deref(), Some("mac.example.ts.net"));
+        assert_eq!(status.ipv4, vec!["100.64.0.1".to_string()]);
+        assert_eq!(status.version.as_deref(), Some("1.82.0"));
+        assert_eq!(status.message, down.message);
+
+        assert_eq!(with_last_known(down.clone(), None), down);
+        assert_eq!(
+            with_last_known(sample_status(), Some(down)),
+            sample_status()
+        );
+    }
+
+    #[test]
+    fn last_known_status_round_trips_through_the_data_dir() {
+        let data_dir = std::env::temp_dir().join(format!(
+            "codex-monitor-tailscale-last-{}",
+            uuid::Uuid::new_v4()
+        ));
+        assert!(read_last_known_status(&data_dir).is_none());
+
+        let last_known = TailscaleStatus {
+            last_seen_ms: Some(42),
+            ..sample_status()
+        };
+        write_last_known_status(&data_dir, &last_known).expect("write last known status");
+        assert_eq!(read_last_known_status(&data_dir), Some(last_known));
+        let _ = std::fs::remove_dir_all(&data_dir);
+    }
+
+    /// Answers `fetch_status` with queued results, counting the calls.
+    struct ScriptedTailscale {
+        results: StdMutex<VecDeque<Result<TailscaleStatus, String>>>,
+        fetches: AtomicUsize,
+    }
+
+    impl TailscaleProvider for ScriptedTailscale {
+        fn fetch_status(&self) -> ProviderFuture<'_, Result<TailscaleStatus, String>> {
+            self.fetches.fetch_add(1, Ordering::SeqCst);
+            let result = self
+                .results
+                .lock()
+                .unwrap()
+                .pop_front()
+                .expect("a queued status");
+            Box::pin(async move { result })
+        }
+
+        fn fetch_status_payload(&self) -> ProviderFuture<'_, Result<String, String>> {
+            Box::pin(async { Err("unused".to_string()) })
+        }
+
+        fn ping<'a>(
+            &'a self,
+            _target: &'a str,
+        ) -> ProviderFuture<'a, Result<TailscalePingResult, String>> {
+            Box::pin(async { Err("unused".to_string()) })
+        }
+    }
+
+    #[test]
+    fn status_is_cached_persisted_and_kept_while_tailscale_is_down() {
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .expect("runtime");
+        let data_dir = std::env::temp_dir().join(format!(
+            "codex-monitor-tailscale-cache-{}",
+            uuid::Uuid::new_v4()
+        ));
+        let tailscale = Arc::new(ScriptedTailscale {
+            results: StdMutex::new(VecDeque::from([
+                Ok(sample_status()),
+                Err("Failed to connect to local Tailscale daemon.".to_string()),
+            ])),
+            fetches: AtomicUsize::new(0),
+        });
+        let mut backends = Backends::real();
+        backends.tailscale = tailscale.clone();
+        let state = AppState::for_tests(&data_dir, backends);
+
+        let live = runtime
+            .block_on(tailscale_status(&state, false))
+            .expect("status");
+        assert_eq!(live, sample_status());
+        assert!(read_last_known_status(&data_dir).is_some());
+        let cached = runtime
+            .block_on(tailscale_status(&state, false))
+            .expect("status");
+        assert_eq!(cached, live);
+        assert_eq!(tailscale.fetches.load(Ordering::SeqCst), 1);
+
+        let down = runtime
+            .block_on(tailscale_status(&state, true))
+            .expect("status");
+        assert_eq!(tailscale.fetches.load(Ordering::SeqCst), 2);
+        assert!(down.stale);
+        assert!(!down.running);
+        assert_eq!(down.dns_name.as_deref(), Some("mac.example.ts.net"));
+        assert!(down.last_seen_ms.is_some());
+        let _ = std::fs::remove_dir_all(&data_dir);
+    }
+
+    #[test]
+    fn key_expiry_warning_only_fires_for_a_live_expiring_key() {
+        let day_ms = 24 * 60 * 60 * 1000;
+        let expiring = TailscaleStatus {
+            key_expiry_ms: Some(3 * day_ms),
+            key_expiring_soon: true,
+            ..sample_status()
+        };
+        let body = key_expiry_warning(&expiring, 0).expect("warning");
+        assert!(body.contains("mac.example.ts.net expires in 3 days"));
+        assert!(key_expiry_warning(&expiring, 4 * day_ms)
+            .expect("warning")
+            .contains("has expired"));
+
+        assert!(key_expiry_warning(&sample_status(), 0).is_none());
+        let stale = TailscaleStatus {
+            stale: true,
+            ..expiring
+        };
+        assert!(key_expiry_warning(&stale, 0).is_none());
+    }
+}
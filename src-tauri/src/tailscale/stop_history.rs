@@ -0,0 +1,72 @@
+//! Recent daemon stops, kept in the data directory so the reason the daemon
+//! last stopped survives an app restart.
+
+use std::path::{Path, PathBuf};
+
+use crate::types::DaemonStop;
+
+const STOP_HISTORY_FILENAME: &str = "daemon-stop-history.json";
+const STOP_HISTORY_LIMIT: usize = 20;
+
+fn stop_history_path(data_dir: &Path) -> PathBuf {
+    data_dir.join(STOP_HISTORY_FILENAME)
+}
+
+/// Recorded stops, oldest first.
+pub(super) fn read_stop_history(data_dir: &Path) -> Vec<DaemonStop> {
+    std::fs::read_to_string(stop_history_path(data_dir))
+        .ok()
+        .and_then(|data| serde_json::from_str(&data).ok())
+        .unwrap_or_default()
+}
+
+/// Appends `stop`, dropping the oldest entries past `STOP_HISTORY_LIMIT`.
+pub(super) fn record_stop(data_dir: &Path, stop: &DaemonStop) -> Result<(), String> {
+    let mut history = read_stop_history(data_dir);
+    history.push(stop.clone());
+    let excess = history.len().saturating_sub(STOP_HISTORY_LIMIT);
+    history.drain(..excess);
+    std::fs::create_dir_all(data_dir).map_err(|err| err.to_string())?;
+    let data = serde_json::to_string_pretty(&history).map_err(|err| err.to_string())?;
+    std::fs::write(stop_history_path(data_dir), data).map_err(|err| err.to_string())
+}
+
+/// The stop to show for a daemon the app has not seen since it launched.
+pub(crate) fn last_daemon_stop(data_dir: &Path) -> Option<DaemonStop> {
+    read_stop_history(data_dir).pop()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::DaemonStopReason;
+
+    #[test]
+    fn history_keeps_the_most_recent_stops() {
+        let data_dir =
+            std::env::temp_dir().join(format!("codex-monitor-stops-{}", uuid::Uuid::new_v4()));
+        assert!(last_daemon_stop(&data_dir).is_none());
+
+        for at_ms in 0..25 {
+            let reason = if at_ms == 24 {
+                DaemonStopReason::Crash
+            } else {
+                DaemonStopReason::User
+            };
+            let stop = DaemonStop {
+                reason,
+                at_ms,
+                detail: None,
+            };
+            record_stop(&data_dir, &stop).expect("record stop");
+        }
+
+        let history = read_stop_history(&data_dir);
+        assert_eq!(history.len(), STOP_HISTORY_LIMIT);
+        assert_eq!(history[0].at_ms, 5);
+        let last = last_daemon_stop(&data_dir).expect("last stop");
+        assert_eq!(last.reason, DaemonStopReason::Crash);
+        assert_eq!(last.at_ms, 24);
+        let _ = std::fs::remove_dir_all(&data_dir);
+    }
+}
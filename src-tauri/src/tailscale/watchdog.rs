@@ -0,0 +1,336 @@
+use futures_util::future::join;
+use tauri::{AppHandle, Manager};
+
+use super::daemon_commands::{daemon_identity, note_daemon_lost};
+use super::rpc_client::{
+    request_daemon_info, verify_daemon_identity, DaemonConnection, DaemonEvents, DaemonProbe,
+    DAEMON_RPC_TIMEOUT,
+};
+use super::*;
+use crate::hooks::run_hooks;
+use crate::notifications::show_system_notification;
+use crate::state::DaemonLiveness;
+use crate::types::{HookEvent, WebhookEvent};
+use crate::webhooks::notify_event;
+
+const HEARTBEAT_SUBSCRIBE_METHOD: &str = "daemon_heartbeat_subscribe";
+const HEARTBEAT_INTERVAL: Duration = Duration::from_secs(2);
+/// A daemon that misses this many intervals is treated as gone.
+const LIVENESS_STALE_AFTER: Duration = Duration::from_secs(6);
+const RECONNECT_DELAY: Duration = Duration::from_secs(2);
+/// Daemons without heartbeat support (or with a token mismatch) are retried
+/// less often; status calls probe them directly in the meantime.
+const UNSUPPORTED_RETRY_DELAY: Duration = Duration::from_secs(15);
+
+/// Answers a status call from the watchdog's cached liveness, returning the
+/// probe result and listener pid. `None` means the cache cannot vouch for
+/// `listen_addr` and the caller should probe.
+pub(super) fn cached_daemon_probe(
+    liveness: &DaemonLiveness,
+    listen_addr: &str,
+    now: Instant,
+) -> Option<(DaemonProbe, Option<u32>)> {
+    match liveness {
+        DaemonLiveness::Alive {
+            listen_addr: watched,
+            pid,
+            last_beat,
+        } if watched == listen_addr
+            && now.saturating_duration_since(*last_beat) < LIVENESS_STALE_AFTER =>
+        {
+            Some((
+                DaemonProbe::Running {
+                    auth_ok: true,
+                    auth_error: None,
+                    info: None,
+                },
+                *pid,
+            ))
+        }
+        DaemonLiveness::Unreachable {
+            listen_addr: watched,
+            checked_at,
+        } if watched == listen_addr
+            && now.saturating_duration_since(*checked_at) < RECONNECT_DELAY =>
+        {
+            Some((DaemonProbe::NotReachable, None))
+        }
+        _ => None,
+    }
+}
+
+fn is_heartbeat(line: &str) -> bool {
+    serde_json::from_str::<Value>(line)
+        .ok()
+        .and_then(|message| {
+            message
+                .get("method")
+                .and_then(Value::as_str)
+                .map(|method| method == "daemon-heartbeat")
+        })
+        .unwrap_or(false)
+}
+
+async fn set_liveness(state: &AppState, liveness: DaemonLiveness) {
+    *state.daemon_liveness.lock().await = liveness;
+}
+
+async fn configured_listen_addr(state: &AppState) -> String {
+    configured_daemon_listen_addr(&*state.app_settings.read().await)
+}
+
+/// Opens an authenticated connection and subscribes to heartbeats. Returns
+/// the connection, its event stream and the daemon pid on success, or the
+/// liveness to record on failure.
+async fn subscribe_heartbeats(
+    listen_addr: &str,
+    token: Option<&str>,
+    identity: Option<&str>,
+) -> Result<(DaemonConnection, DaemonEvents, Option<u32>), DaemonLiveness> {
+    let unreachable = || DaemonLiveness::Unreachable {
+        listen_addr: listen_addr.to_string(),
+        checked_at: Instant::now(),
+    };
+    let connect_addr = daemon_connect_addr(listen_addr).ok_or(DaemonLiveness::Unknown)?;
+    let stream = match timeout(DAEMON_RPC_TIMEOUT, TcpStream::connect(&connect_addr)).await {
+        Ok(Ok(stream)) => stream,
+        Ok(Err(_)) | Err(_) => return Err(unreachable()),
+    };
+    let (connection, events) = DaemonConnection::open(stream);
+
+    if let Some(secret) = identity {
+        if !verify_daemon_identity(&connection, secret).await {
+            return Err(DaemonLiveness::Unknown);
+        }
+    }
+    // The token is only sent to a listener that proved its identity.
+    let token = identity.and(token);
+    if let Some(token) = token.map(str::trim).filter(|value| !value.is_empty()) {
+        connection
+            .call("auth", client_trust_core::client_auth_params(token))
+            .await
+            .map_err(|_| DaemonLiveness::Unknown)?;
+    }
+    let subscribe = connection.call(
+        HEARTBEAT_SUBSCRIBE_METHOD,
+        json!({
+            "intervalMs": HEARTBEAT_INTERVAL.as_millis() as u64,
+            "events": false,
+        }),
+    );
+    let (info, subscribed) = join(request_daemon_info(&connection), subscribe).await;
+    let info = info.map_err(|_| DaemonLiveness::Unknown)?;
+    subscribed.map_err(|_| DaemonLiveness::Unknown)?;
+    Ok((connection, events, info.pid))
+}
+
+/// Holds one heartbeat connection until it drops, goes quiet, or the
+/// configured listen address changes. Returns the liveness to record and
+/// how long to wait before reconnecting.
+async fn watch_daemon(state: &AppState) -> (DaemonLiveness, Duration) {
+    let (listen_addr, token) = {
+        let settings = state.app_settings.read().await;
+        (
+            configured_daemon_listen_addr(&settings),
+            settings.remote_backend_token.clone(),
+        )
+    };
+    let identity = daemon_identity(state).await;
+    let (_connection, mut events, pid) =
+        match subscribe_heartbeats(&listen_addr, token.as_deref(), identity.as_deref()).await {
+            Ok(connection) => connection,
+            Err(DaemonLiveness::Unknown) => {
+                return (DaemonLiveness::Unknown, UNSUPPORTED_RETRY_DELAY)
+            }
+            Err(liveness) => return (liveness, RECONNECT_DELAY),
+        };
+
+    let alive = |last_beat| DaemonLiveness::Alive {
+        listen_addr: listen_addr.clone(),
+        pid,
+        last_beat,
+    };
+    set_liveness(state, alive(Instant::now())).await;
+    loop {
+        match timeout(LIVENESS_STALE_AFTER, events.recv()).await {
+            Ok(Some(line)) => {
+                if !is_heartbeat(&line) {
+                    continue;
+                }
+                if configured_listen_addr(state).await != listen_addr {
+                    return (DaemonLiveness::Unknown, Duration::ZERO);
+                }
+                set_liveness(state, alive(Instant::now())).await;
+            }
+            Ok(None) => {
+                let liveness = DaemonLiveness::Unreachable {
+                    listen_addr,
+                    checked_at: Instant::now(),
+                };
+                return (liveness, RECONNECT_DELAY);
+            }
+            Err(_) => return (DaemonLiveness::Unknown, RECONNECT_DELAY),
+        }
+    }
+}
+
+/// The listen address of a daemon that was alive and no longer is. A
+/// changed listen address (no reconnect delay) is not an outage.
+fn lost_daemon<'a>(
+    previous: &'a DaemonLiveness,
+    next: &DaemonLiveness,
+    delay: Duration,
+) -> Option<&'a str> {
+    match (previous, next) {
+        (_, DaemonLiveness::Alive { .. }) => None,
+        (DaemonLiveness::Alive { listen_addr, .. }, _) if !delay.is_zero() => Some(listen_addr),
+        _ => None,
+    }
+}
+
+/// The listen address of a daemon that was unreachable and now answers.
+/// The first heartbeat after launch is not a start.
+fn started_daemon<'a>(previous: &DaemonLiveness, next: &'a DaemonLiveness) -> Option<&'a str> {
+    match (previous, next) {
+        (DaemonLiveness::Unreachable { .. }, DaemonLiveness::Alive { listen_addr, .. }) => {
+            Some(listen_addr)
+        }
+        _ => None,
+    }
+}
+
+/// Keeps a heartbeat connection to the configured local daemon so that
+/// `tailscale_daemon_status` can answer from `AppState` instead of probing.
+/// Webhooks and integrations subscribed to `daemon_down` hear when that
+/// connection is lost, as does the user through an urgent system
+/// notification when those are on, unless the app is stopping the daemon
+/// itself. Hooks hear both that and the daemon coming back.
+pub(crate) fn spawn_daemon_watchdog(app: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        loop {
+            let state = app.state::<AppState>();
+            let (liveness, delay) = watch_daemon(&state).await;
+            let previous = state.daemon_liveness.lock().await.clone();
+            if let Some(listen_addr) = started_daemon(&previous, &liveness) {
+                run_hooks(
+                    &app,
+                    HookEvent::DaemonStarted,
+                    json!({ "listenAddr": listen_addr }),
+                );
+            }
+            if let Some(listen_addr) = lost_daemon(&previous, &liveness, delay) {
+                run_hooks(
+                    &app,
+                    HookEvent::DaemonStopped,
+                    json!({ "listenAddr": listen_addr }),
+                );
+                // A stop or restart from the app is not an outage.
+                let stopping = is_transition(&state.tcp_daemon.lock().await.status.state);
+                if !stopping {
+                    note_daemon_lost(&state).await;
+                    notify_event(
+                        &app,
+                        WebhookEvent::DaemonDown,
+                        json!({ "listenAddr": listen_addr }),
+                    );
+                    if state.app_settings.read().await.system_notifications_enabled {
+                        let body = format!("The daemon at {listen_addr} stopped answering.");
+                        show_system_notification(&app, "Daemon is down", &body, true).await;
+                    }
+                }
+            }
+            set_liveness(&state, liveness).await;
+            sleep(delay).await;
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cached_probe_only_vouches_for_fresh_matching_entries() {
+        let now = Instant::now();
+        let alive = DaemonLiveness::Alive {
+            listen_addr: "0.0.0.0:4732".to_string(),
+            pid: Some(42),
+            last_beat: now,
+        };
+        assert!(matches!(
+            cached_daemon_probe(&alive, "0.0.0.0:4732", now + Duration::from_secs(1)),
+            Some((DaemonProbe::Running { auth_ok: true, .. }, Some(42)))
+        ));
+        assert!(cached_daemon_probe(&alive, "0.0.0.0:4733", now).is_none());
+        assert!(cached_daemon_probe(&alive, "0.0.0.0:4732", now + LIVENESS_STALE_AFTER).is_none());
+
+        let unreachable = DaemonLiveness::Unreachable {
+            listen_addr: "0.0.0.0:4732".to_string(),
+            checked_at: now,
+        };
+        assert!(matches!(
+            cached_daemon_probe(&unreachable, "0.0.0.0:4732", now),
+            Some((DaemonProbe::NotReachable, None))
+        ));
+        assert!(cached_daemon_probe(&unreachable, "0.0.0.0:4732", now + RECONNECT_DELAY).is_none());
+        assert!(cached_daemon_probe(&DaemonLiveness::Unknown, "0.0.0.0:4732", now).is_none());
+    }
+
+    #[test]
+    fn only_losing_a_live_daemon_counts_as_down() {
+        let now = Instant::now();
+        let alive = DaemonLiveness::Alive {
+            listen_addr: "0.0.0.0:4732".to_string(),
+            pid: None,
+            last_beat: now,
+        };
+        let unreachable = DaemonLiveness::Unreachable {
+            listen_addr: "0.0.0.0:4732".to_string(),
+            checked_at: now,
+        };
+        assert_eq!(
+            lost_daemon(&alive, &unreachable, RECONNECT_DELAY),
+            Some("0.0.0.0:4732")
+        );
+        assert_eq!(
+            lost_daemon(&alive, &DaemonLiveness::Unknown, RECONNECT_DELAY),
+            Some("0.0.0.0:4732")
+        );
+        assert_eq!(
+            lost_daemon(&alive, &DaemonLiveness::Unknown, Duration::ZERO),
+            None
+        );
+        assert_eq!(
+            lost_daemon(&unreachable, &unreachable, RECONNECT_DELAY),
+            None
+        );
+        assert_eq!(lost_daemon(&alive, &alive, RECONNECT_DELAY), None);
+    }
+
+    #[test]
+    fn only_a_daemon_coming_back_counts_as_started() {
+        let now = Instant::now();
+        let alive = DaemonLiveness::Alive {
+            listen_addr: "0.0.0.0:4732".to_string(),
+            pid: None,
+            last_beat: now,
+        };
+        let unreachable = DaemonLiveness::Unreachable {
+            listen_addr: "0.0.0.0:4732".to_string(),
+            checked_at: now,
+        };
+        assert_eq!(started_daemon(&unreachable, &alive), Some("0.0.0.0:4732"));
+        assert_eq!(started_daemon(&DaemonLiveness::Unknown, &alive), None);
+        assert_eq!(started_daemon(&alive, &alive), None);
+        assert_eq!(started_daemon(&alive, &unreachable), None);
+    }
+
+    #[test]
+    fn only_heartbeat_notifications_refresh_liveness() {
+        assert!(is_heartbeat(
+            r#"{"method":"daemon-heartbeat","params":{"pid":1}}"#
+        ));
+        assert!(!is_heartbeat(r#"{"method":"app-server-event"}"#));
+        assert!(!is_heartbeat("not json"));
+    }
+}
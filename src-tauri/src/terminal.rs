@@ -8,6 +8,7 @@ use tauri::{AppHandle, Manager, State};
 use tokio::sync::Mutex;
 
 use crate::backend::events::{EventSink, TerminalExit, TerminalOutput};
+use crate::command_error::CommandResult;
 use crate::event_sink::TauriEventSink;
 use crate::state::AppState;
 
@@ -203,9 +204,9 @@ pub(crate) async fn terminal_open(
     rows: u16,
     state: State<'_, AppState>,
     app: AppHandle,
-) -> Result<TerminalSessionInfo, String> {
+) -> CommandResult<TerminalSessionInfo> {
     if terminal_id.is_empty() {
-        return Err("Terminal id is required".to_string());
+        return Err("Terminal id is required".to_string().into());
     }
     let key = terminal_key(&workspace_id, &terminal_id);
     {
@@ -292,7 +293,7 @@ pub(crate) async fn terminal_write(
     terminal_id: String,
     data: String,
     state: State<'_, AppState>,
-) -> Result<(), String> {
+) -> CommandResult<()> {
     let key = terminal_key(&workspace_id, &terminal_id);
     let session = get_terminal_session(&state, &key).await?;
     let write_result = tokio::task::spawn_blocking(move || {
@@ -313,7 +314,7 @@ pub(crate) async fn terminal_write(
             let mut sessions = state.terminal_sessions.lock().await;
             sessions.remove(&key);
         }
-        return Err(err);
+        return Err(err.into());
     }
     Ok(())
 }
@@ -325,7 +326,7 @@ pub(crate) async fn terminal_resize(
     cols: u16,
     rows: u16,
     state: State<'_, AppState>,
-) -> Result<(), String> {
+) -> CommandResult<()> {
     let key = terminal_key(&workspace_id, &terminal_id);
     let session = get_terminal_session(&state, &key).await?;
     let size = PtySize {
@@ -347,7 +348,7 @@ pub(crate) async fn terminal_resize(
             let mut sessions = state.terminal_sessions.lock().await;
             sessions.remove(&key);
         }
-        return Err(err);
+        return Err(err.into());
     }
     Ok(())
 }
@@ -357,7 +358,7 @@ pub(crate) async fn terminal_close(
     workspace_id: String,
     terminal_id: String,
     state: State<'_, AppState>,
-) -> Result<(), String> {
+) -> CommandResult<()> {
     let key = terminal_key(&workspace_id, &terminal_id);
     let mut sessions = state.terminal_sessions.lock().await;
     let session = sessions
@@ -1,9 +1,10 @@
 use serde::Serialize;
 use tauri::{AppHandle, State};
 
+use crate::command_error::CommandResult;
+use crate::platform;
 use crate::state::AppState;
-
-const UNSUPPORTED_MESSAGE: &str = "Terminal is not available on mobile builds.";
+use crate::types::PlatformSubsystem;
 
 pub(crate) struct TerminalSession {
     pub(crate) id: String,
@@ -22,11 +23,11 @@ pub(crate) async fn terminal_open(
     _rows: u16,
     _state: State<'_, AppState>,
     _app: AppHandle,
-) -> Result<TerminalSessionInfo, String> {
+) -> CommandResult<TerminalSessionInfo> {
     if terminal_id.trim().is_empty() {
-        return Err("Terminal id is required".to_string());
+        return Err("Terminal id is required".into());
     }
-    Err(UNSUPPORTED_MESSAGE.to_string())
+    Err(platform::unsupported(PlatformSubsystem::Terminal).into())
 }
 
 #[tauri::command]
@@ -35,8 +36,8 @@ pub(crate) async fn terminal_write(
     _terminal_id: String,
     _data: String,
     _state: State<'_, AppState>,
-) -> Result<(), String> {
-    Err(UNSUPPORTED_MESSAGE.to_string())
+) -> CommandResult<()> {
+    Err(platform::unsupported(PlatformSubsystem::Terminal).into())
 }
 
 #[tauri::command]
@@ -46,8 +47,8 @@ pub(crate) async fn terminal_resize(
     _cols: u16,
     _rows: u16,
     _state: State<'_, AppState>,
-) -> Result<(), String> {
-    Err(UNSUPPORTED_MESSAGE.to_string())
+) -> CommandResult<()> {
+    Err(platform::unsupported(PlatformSubsystem::Terminal).into())
 }
 
 #[tauri::command]
@@ -55,6 +56,6 @@ pub(crate) async fn terminal_close(
     _workspace_id: String,
     _terminal_id: String,
     _state: State<'_, AppState>,
-) -> Result<(), String> {
-    Err(UNSUPPORTED_MESSAGE.to_string())
+) -> CommandResult<()> {
+    Err(platform::unsupported(PlatformSubsystem::Terminal).into())
 }
@@ -1,8 +1,11 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::sync::Mutex;
+#[cfg(desktop)]
+use std::time::Duration;
 
 use serde::{Deserialize, Serialize};
-use tauri::AppHandle;
+use serde_json::Value;
+use tauri::{AppHandle, Manager};
 
 #[cfg(target_os = "macos")]
 use tauri::image::Image;
@@ -11,7 +14,11 @@ use tauri::menu::{Menu, MenuEvent, MenuItemBuilder, PredefinedMenuItem};
 #[cfg(target_os = "macos")]
 use tauri::tray::TrayIconBuilder;
 #[cfg(target_os = "macos")]
-use tauri::{Emitter, Manager, Runtime};
+use tauri::{Emitter, Runtime};
+
+use crate::command_error::CommandResult;
+use crate::shared::webhooks_core::is_approval_request;
+use crate::types::TcpDaemonState;
 
 const MAX_RECENT_THREADS: usize = 8;
 #[cfg(target_os = "macos")]
@@ -19,6 +26,10 @@ const TRAY_ID: &str = "codex-monitor-tray";
 #[cfg(target_os = "macos")]
 const TRAY_QUIT_ID: &str = "tray_quit";
 #[cfg(target_os = "macos")]
+const TRAY_SNOOZE_ID: &str = "tray_snooze_notifications";
+#[cfg(target_os = "macos")]
+const TRAY_SNOOZE_MINUTES: u32 = 60;
+#[cfg(target_os = "macos")]
 const TRAY_EMPTY_ID: &str = "tray_recent_empty";
 #[cfg(target_os = "macos")]
 const TRAY_USAGE_HEADER_ID: &str = "tray_usage_header";
@@ -27,6 +38,9 @@ const TRAY_USAGE_SESSION_ID: &str = "tray_usage_session";
 #[cfg(target_os = "macos")]
 const TRAY_USAGE_WEEKLY_ID: &str = "tray_usage_weekly";
 pub(crate) const TRAY_OPEN_THREAD_EVENT: &str = "tray-open-thread";
+const TRAY_SUMMARY_CHANGED_EVENT: &str = "tray-summary-changed";
+#[cfg(desktop)]
+const TRAY_SUMMARY_REFRESH_INTERVAL: Duration = Duration::from_secs(2);
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 #[serde(rename_all = "camelCase")]
@@ -52,11 +66,226 @@ pub(crate) struct TraySessionUsage {
     pub(crate) weekly_label: Option<String>,
 }
 
+/// Where Codex runs: on this device, or on a remote backend that is or is
+/// not connected.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub(crate) enum TrayRunnerState {
+    Local,
+    Connected,
+    Disconnected,
+}
+
+/// What the tray popover shows, as returned by `get_tray_summary`.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct TraySummary {
+    pub(crate) daemon_state: TcpDaemonState,
+    pub(crate) runner_state: TrayRunnerState,
+    /// Codex sessions running on this device; unset while they run on a
+    /// remote backend.
+    pub(crate) active_sessions: Option<u32>,
+    pub(crate) pending_approvals: u32,
+    /// The daemon's last error, or else the last session that failed.
+    pub(crate) last_error: Option<String>,
+    /// When the background refresher last rebuilt this; 0 before it has.
+    pub(crate) updated_at_ms: i64,
+}
+
+impl Default for TraySummary {
+    fn default() -> Self {
+        Self {
+            daemon_state: TcpDaemonState::Stopped,
+            runner_state: TrayRunnerState::Local,
+            active_sessions: None,
+            pending_approvals: 0,
+            last_error: None,
+            updated_at_ms: 0,
+        }
+    }
+}
+
 #[derive(Default)]
 pub(crate) struct TrayState {
     recent_threads: Mutex<Vec<TrayRecentThreadEntry>>,
     session_usage: Mutex<Option<TraySessionUsage>>,
     recent_targets_by_menu_id: Mutex<HashMap<String, TrayOpenThreadPayload>>,
+    summary: Mutex<TraySummary>,
+    /// Approval requests not answered yet, as `workspace_id:request_id`.
+    pending_approvals: Mutex<HashSet<String>>,
+    last_session_error: Mutex<Option<String>>,
+}
+
+fn approval_key(workspace_id: &str, request_id: &Value) -> String {
+    format!("{workspace_id}:{request_id}")
+}
+
+/// Counts `message` from `workspace_id` as pending when it asks for an
+/// approval. Returns whether it did.
+fn record_approval_request(state: &TrayState, workspace_id: &str, message: &Value) -> bool {
+    let Some(request_id) = message.get("id").filter(|_| is_approval_request(message)) else {
+        return false;
+    };
+    let Ok(mut pending) = state.pending_approvals.lock() else {
+        return false;
+    };
+    pending.insert(approval_key(workspace_id, request_id))
+}
+
+/// Stops counting request `request_id` from `workspace_id` once answered.
+/// Returns whether it was pending.
+fn resolve_approval_request(state: &TrayState, workspace_id: &str, request_id: &Value) -> bool {
+    state
+        .pending_approvals
+        .lock()
+        .map(|mut pending| pending.remove(&approval_key(workspace_id, request_id)))
+        .unwrap_or(false)
+}
+
+/// Replaces the cached summary, announcing it when it changed beyond its
+/// timestamp.
+fn store_summary(app: &AppHandle, state: &TrayState, summary: TraySummary) {
+    let Ok(mut current) = state.summary.lock() else {
+        return;
+    };
+    let changed = TraySummary {
+        updated_at_ms: current.updated_at_ms,
+        ..summary.clone()
+    } != *current;
+    *current = summary;
+    if changed {
+        if let Some(app_state) = app.try_state::<crate::state::AppState>() {
+            app_state
+                .event_bus
+                .publish(TRAY_SUMMARY_CHANGED_EVENT, &*current);
+        }
+    }
+}
+
+fn update_pending_approvals(app: &AppHandle, state: &TrayState) {
+    let count = state
+        .pending_approvals
+        .lock()
+        .map(|pending| pending.len() as u32)
+        .unwrap_or(0);
+    let summary = state.summary.lock().ok().map(|summary| TraySummary {
+        pending_approvals: count,
+        ..summary.clone()
+    });
+    if let Some(summary) = summary {
+        store_summary(app, state, summary);
+    }
+}
+
+/// Follows approval requests in app-server messages, local or forwarded by
+/// a remote daemon.
+pub(crate) fn note_app_server_message(app: &AppHandle, workspace_id: &str, message: &Value) {
+    let Some(state) = app.try_state::<TrayState>() else {
+        return;
+    };
+    if record_approval_request(&state, workspace_id, message) {
+        update_pending_approvals(app, &state);
+    }
+}
+
+/// Drops an approval request from the count once it has been answered.
+pub(crate) fn note_approval_answered(app: &AppHandle, workspace_id: &str, request_id: &Value) {
+    let Some(state) = app.try_state::<TrayState>() else {
+        return;
+    };
+    if resolve_approval_request(&state, workspace_id, request_id) {
+        update_pending_approvals(app, &state);
+    }
+}
+
+/// Remembers a session that exited with a non-zero code, for `last_error`.
+pub(crate) fn note_session_exit(app: &AppHandle, session_id: &str, exit_code: Option<i32>) {
+    let Some(code) = exit_code.filter(|code| *code != 0) else {
+        return;
+    };
+    let Some(state) = app.try_state::<TrayState>() else {
+        return;
+    };
+    if let Ok(mut last) = state.last_session_error.lock() {
+        *last = Some(format!("Session {session_id} exited with code {code}"));
+    }
+}
+
+/// Rebuilds the tray summary from app state every couple of seconds, so
+/// `get_tray_summary` never waits on a lock or the network.
+#[cfg(desktop)]
+pub(crate) fn spawn_tray_summary_refresher(app: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        loop {
+            let summary = build_summary(&app).await;
+            if let Some(tray_state) = app.try_state::<TrayState>() {
+                store_summary(&app, &tray_state, summary);
+            }
+            tokio::time::sleep(TRAY_SUMMARY_REFRESH_INTERVAL).await;
+        }
+    });
+}
+
+#[cfg(desktop)]
+async fn build_summary(app: &AppHandle) -> TraySummary {
+    use crate::state::AppState;
+    use crate::types::BackendMode;
+
+    let state = app.state::<AppState>();
+    let tray_state = app.state::<TrayState>();
+    let (daemon_state, daemon_error) = {
+        let runtime = state.tcp_daemon.lock().await;
+        (
+            runtime.status.state.clone(),
+            runtime.status.last_error.clone(),
+        )
+    };
+    let remote = matches!(
+        state.app_settings.read().await.backend_mode,
+        BackendMode::Remote
+    );
+    let runner_state = if !remote {
+        TrayRunnerState::Local
+    } else if state.remote_backend.lock().await.is_some() {
+        TrayRunnerState::Connected
+    } else {
+        TrayRunnerState::Disconnected
+    };
+    let active_sessions = if remote {
+        None
+    } else {
+        Some(state.session_runtimes.running_count().await as u32)
+    };
+    let pending_approvals = tray_state
+        .pending_approvals
+        .lock()
+        .map(|pending| pending.len() as u32)
+        .unwrap_or(0);
+    let last_error = daemon_error.or_else(|| {
+        tray_state
+            .last_session_error
+            .lock()
+            .ok()
+            .and_then(|last| last.clone())
+    });
+    TraySummary {
+        daemon_state,
+        runner_state,
+        active_sessions,
+        pending_approvals,
+        last_error,
+        updated_at_ms: chrono::Utc::now().timestamp_millis(),
+    }
+}
+
+/// The tray popover's summary, from what the background refresher cached.
+#[tauri::command]
+pub(crate) fn get_tray_summary(state: tauri::State<'_, TrayState>) -> CommandResult<TraySummary> {
+    state
+        .summary
+        .lock()
+        .map(|summary| summary.clone())
+        .map_err(|_| "failed to lock tray summary".into())
 }
 
 #[tauri::command]
@@ -64,7 +293,7 @@ pub(crate) fn set_tray_recent_threads<R: tauri::Runtime>(
     app: AppHandle<R>,
     state: tauri::State<'_, TrayState>,
     entries: Vec<TrayRecentThreadEntry>,
-) -> Result<(), String> {
+) -> CommandResult<()> {
     let normalized = normalize_recent_threads(entries);
     {
         let mut recent_threads = state
@@ -88,7 +317,7 @@ pub(crate) fn set_tray_session_usage<R: tauri::Runtime>(
     app: AppHandle<R>,
     state: tauri::State<'_, TrayState>,
     usage: Option<TraySessionUsage>,
-) -> Result<(), String> {
+) -> CommandResult<()> {
     let normalized = normalize_session_usage(usage);
     {
         let mut session_usage = state
@@ -240,6 +469,9 @@ fn build_tray_menu<R: Runtime>(
     }
     let usage_separator = PredefinedMenuItem::separator(app)?;
     menu.append(&usage_separator)?;
+    let snooze_item =
+        MenuItemBuilder::with_id(TRAY_SNOOZE_ID, "Snooze Notifications for 1 Hour").build(app)?;
+    menu.append(&snooze_item)?;
     let quit_item = MenuItemBuilder::with_id(TRAY_QUIT_ID, "Quit").build(app)?;
     menu.append(&quit_item)?;
     Ok(menu)
@@ -307,7 +539,10 @@ fn build_usage_menu_labels(usage: Option<&TraySessionUsage>) -> (String, String,
         usage
             .map(|usage| format!("Session: {}", usage.session_label))
             .unwrap_or_else(|| "No active session".to_string()),
-        usage.map(|usage| usage.weekly_label.clone()).unwrap_or(None).map(|label| format!("Weekly: {label}")),
+        usage
+            .map(|usage| usage.weekly_label.clone())
+            .unwrap_or(None)
+            .map(|label| format!("Weekly: {label}")),
     )
 }
 
@@ -315,6 +550,13 @@ fn build_usage_menu_labels(usage: Option<&TraySessionUsage>) -> (String, String,
 fn handle_tray_menu_event<R: Runtime>(app: &tauri::AppHandle<R>, event: MenuEvent) {
     match event.id().as_ref() {
         TRAY_QUIT_ID => app.exit(0),
+        TRAY_SNOOZE_ID => {
+            let app = app.clone();
+            tauri::async_runtime::spawn(async move {
+                let state = app.state::<crate::state::AppState>();
+                crate::notifications::snooze_notifications(&state, TRAY_SNOOZE_MINUTES).await;
+            });
+        }
         id => {
             let state = app.state::<TrayState>();
             let payload = state
@@ -357,8 +599,10 @@ fn load_tray_icon() -> tauri::Result<Image<'static>> {
 mod tests {
     use super::{
         build_usage_menu_labels, normalize_recent_threads, normalize_session_usage,
-        TrayOpenThreadPayload, TrayRecentThreadEntry, TraySessionUsage, MAX_RECENT_THREADS,
+        record_approval_request, resolve_approval_request, TrayOpenThreadPayload,
+        TrayRecentThreadEntry, TraySessionUsage, TrayState, MAX_RECENT_THREADS,
     };
+    use serde_json::json;
 
     fn recent_entry(
         workspace_id: &str,
@@ -458,4 +702,27 @@ mod tests {
             ("Current Usage".into(), "No active session".into(), None)
         );
     }
+
+    #[test]
+    fn approval_requests_count_until_answered() {
+        let state = TrayState::default();
+        let request = json!({
+            "id": 7,
+            "method": "item/commandExecution/requestApproval",
+            "params": { "threadId": "thread-1" },
+        });
+        assert!(record_approval_request(&state, "ws-1", &request));
+        assert!(!record_approval_request(&state, "ws-1", &request));
+        assert!(record_approval_request(&state, "ws-2", &request));
+        assert!(!record_approval_request(
+            &state,
+            "ws-1",
+            &json!({ "id": 8, "method": "item/agentMessage/delta" })
+        ));
+
+        assert!(resolve_approval_request(&state, "ws-1", &json!(7)));
+        assert!(!resolve_approval_request(&state, "ws-1", &json!(7)));
+        assert!(!resolve_approval_request(&state, "ws-2", &json!("7")));
+        assert_eq!(state.pending_approvals.lock().unwrap().len(), 1);
+    }
 }
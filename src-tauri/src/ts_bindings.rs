@@ -0,0 +1,531 @@
+// Generates `src/types.generated.ts` from the serde types in `types.rs` and
+// the parameters of every registered command, and fails when the committed
+// file is stale. Regenerate with:
+//
+//     UPDATE_TS_BINDINGS=1 cargo test ts_bindings
+//
+// Only the shapes `types.rs` uses are understood: named-field structs
+// (optionally generic), unit enums, `rename_all`/`rename`/`flatten`, and
+// std/serde_json containers. Command parameter types declared elsewhere are
+// read from `COMMAND_TYPE_SOURCES`. A type with no mapping fails generation
+// rather than coming out as `unknown`.
+
+use std::collections::HashSet;
+use std::path::Path;
+
+use crate::capabilities;
+
+const TYPES_SOURCE: &str = include_str!("types.rs");
+/// Modules declaring command parameter types outside `types.rs`. Only the
+/// items that command parameters reach are generated from them.
+const COMMAND_TYPE_SOURCES: &[&str] = &[
+    include_str!("files/policy.rs"),
+    include_str!("tray.rs"),
+    include_str!("menu.rs"),
+    include_str!("shared/agents_config_core.rs"),
+];
+const BINDINGS_PATH: &str = "../src/types.generated.ts";
+/// Prettier's default, which the frontend uses.
+const PRINT_WIDTH: usize = 80;
+const HEADER: &str = "// Generated from src-tauri/src/types.rs and the command registry.\n\
+// Do not edit; run `npm run bindings` after changing the Rust types.\n";
+/// What `serde_json::Value` maps to.
+const JSON_VALUE: &str = concat!(
+    "export type JsonValue =\n",
+    "  | null\n",
+    "  | boolean\n",
+    "  | number\n",
+    "  | string\n",
+    "  | JsonValue[]\n",
+    "  | { [key: string]: JsonValue };\n",
+);
+
+enum Item {
+    Struct {
+        name: String,
+        generics: Vec<String>,
+        fields: Vec<Field>,
+    },
+    Enum {
+        name: String,
+        variants: Vec<String>,
+    },
+}
+
+impl Item {
+    fn name(&self) -> &str {
+        match self {
+            Item::Struct { name, .. } | Item::Enum { name, .. } => name,
+        }
+    }
+}
+
+struct Field {
+    name: String,
+    rust_type: String,
+    flatten: bool,
+}
+
+fn serde_value(attr: &str, key: &str) -> Option<String> {
+    let start = attr.find(&format!("{key} = \""))? + key.len() + 4;
+    let end = attr[start..].find('"')?;
+    Some(attr[start..start + end].to_string())
+}
+
+fn snake_to_camel(name: &str) -> String {
+    let mut camel = String::with_capacity(name.len());
+    let mut upper = false;
+    for ch in name.chars() {
+        if ch == '_' {
+            upper = true;
+        } else if upper {
+            camel.push(ch.to_ascii_uppercase());
+            upper = false;
+        } else {
+            camel.push(ch);
+        }
+    }
+    camel
+}
+
+fn pascal_to_snake(name: &str) -> String {
+    let mut snake = String::with_capacity(name.len() + 4);
+    for (index, ch) in name.chars().enumerate() {
+        if ch.is_ascii_uppercase() && index > 0 {
+            snake.push('_');
+        }
+        snake.push(ch.to_ascii_lowercase());
+    }
+    snake
+}
+
+fn rename_variant(variant: &str, rule: Option<&str>) -> String {
+    match rule {
+        Some("lowercase") => variant.to_ascii_lowercase(),
+        Some("snake_case") => pascal_to_snake(variant),
+        Some("camelCase") => {
+            let mut chars = variant.chars();
+            chars
+                .next()
+                .map(|first| first.to_ascii_lowercase().to_string() + chars.as_str())
+                .unwrap_or_default()
+        }
+        Some(other) => panic!("unsupported rename_all rule {other}"),
+        None => variant.to_string(),
+    }
+}
+
+/// A serializable declaration, read but not yet checked.
+struct Declaration {
+    name: String,
+    generics: Vec<String>,
+    is_struct: bool,
+    attrs: String,
+    /// Each member line with the attributes before it; `None` for
+    /// declarations without a braced body.
+    members: Option<Vec<(String, String)>>,
+}
+
+fn strip_visibility(line: &str) -> Option<&str> {
+    line.strip_prefix("pub(crate) ")
+        .or_else(|| line.strip_prefix("pub "))
+}
+
+/// Finds the serializable declarations of `source` in declaration order.
+fn declarations(source: &str) -> Vec<Declaration> {
+    let mut found = Vec::new();
+    let mut lines = source.lines();
+    let mut container_attrs = String::new();
+    while let Some(line) = lines.next() {
+        if line.starts_with("#[") {
+            container_attrs.push_str(line);
+            continue;
+        }
+        let declaration = strip_visibility(line).and_then(|rest| {
+            rest.strip_prefix("struct ")
+                .map(|rest| (true, rest))
+                .or_else(|| rest.strip_prefix("enum ").map(|rest| (false, rest)))
+        });
+        let Some((is_struct, rest)) = declaration else {
+            if !line.starts_with("///") {
+                container_attrs.clear();
+            }
+            continue;
+        };
+        let attrs = std::mem::take(&mut container_attrs);
+        let declared = rest.trim_end_matches(" {");
+        let (name, generics) = match declared.split_once('<') {
+            Some((name, params)) => (
+                name.to_string(),
+                split_args(params.trim_end_matches('>'))
+                    .into_iter()
+                    .map(String::from)
+                    .collect(),
+            ),
+            None => (declared.to_string(), Vec::new()),
+        };
+        if !attrs.contains("Serialize") && !attrs.contains("Deserialize") {
+            continue;
+        }
+        if !rest.ends_with(" {") {
+            found.push(Declaration {
+                name,
+                generics,
+                is_struct,
+                attrs,
+                members: None,
+            });
+            continue;
+        }
+
+        let mut members = Vec::new();
+        let mut member_attrs = String::new();
+        for line in lines.by_ref() {
+            if line == "}" {
+                break;
+            }
+            let line = line.trim();
+            if line.is_empty() || line.starts_with("//") {
+                continue;
+            }
+            if line.starts_with("#[") || !member_attrs.ends_with(']') && !member_attrs.is_empty() {
+                member_attrs.push_str(line);
+                continue;
+            }
+            members.push((line.to_string(), std::mem::take(&mut member_attrs)));
+        }
+        found.push(Declaration {
+            name,
+            generics,
+            is_struct,
+            attrs,
+            members: Some(members),
+        });
+    }
+    found
+}
+
+fn parse_declaration(declaration: Declaration) -> Item {
+    let Declaration {
+        name,
+        generics,
+        is_struct,
+        attrs,
+        members,
+    } = declaration;
+    let members = members.unwrap_or_else(|| panic!("unsupported declaration of {name}"));
+    let rename_all = serde_value(&attrs, "rename_all");
+
+    if is_struct {
+        let fields = members
+            .into_iter()
+            .map(|(line, attrs)| {
+                let field = strip_visibility(&line)
+                    .and_then(|field| field.strip_suffix(','))
+                    .unwrap_or_else(|| panic!("unsupported field in {name}: {line}"));
+                let (field_name, rust_type) = field.split_once(": ").expect("field type");
+                let renamed =
+                    serde_value(&attrs, "rename").unwrap_or_else(|| match rename_all.as_deref() {
+                        Some("camelCase") => snake_to_camel(field_name),
+                        None => field_name.to_string(),
+                        Some(other) => panic!("unsupported rename_all rule {other}"),
+                    });
+                Field {
+                    name: renamed,
+                    rust_type: rust_type.to_string(),
+                    flatten: attrs.contains("flatten"),
+                }
+            })
+            .collect();
+        Item::Struct {
+            name,
+            generics,
+            fields,
+        }
+    } else {
+        assert!(generics.is_empty(), "unsupported generic enum {name}");
+        let variants = members
+            .into_iter()
+            .map(|(line, attrs)| {
+                let variant = line
+                    .strip_suffix(',')
+                    .filter(|variant| variant.chars().all(|ch| ch.is_ascii_alphanumeric()))
+                    .unwrap_or_else(|| panic!("unsupported variant in {name}: {line}"));
+                serde_value(&attrs, "rename")
+                    .unwrap_or_else(|| rename_variant(variant, rename_all.as_deref()))
+            })
+            .collect();
+        Item::Enum { name, variants }
+    }
+}
+
+/// Reads the serializable items of `source` in declaration order.
+fn parse_items(source: &str) -> Vec<Item> {
+    declarations(source)
+        .into_iter()
+        .map(parse_declaration)
+        .collect()
+}
+
+/// The type names `rust_type` mentions, without module paths.
+fn type_names(rust_type: &str) -> Vec<&str> {
+    rust_type
+        .split(|ch: char| !(ch.is_ascii_alphanumeric() || ch == '_' || ch == ':'))
+        .filter_map(|path| path.rsplit("::").next())
+        .filter(|name| name.starts_with(|ch: char| ch.is_ascii_uppercase()))
+        .collect()
+}
+
+/// Every item to generate: all of `types.rs`, plus the items of
+/// `COMMAND_TYPE_SOURCES` that command parameters reach.
+fn collect_items() -> Vec<Item> {
+    let mut items = parse_items(TYPES_SOURCE);
+    let known: HashSet<String> = items.iter().map(|item| item.name().to_string()).collect();
+    let mut extra: Vec<Declaration> = COMMAND_TYPE_SOURCES
+        .iter()
+        .flat_map(|source| declarations(source))
+        .filter(|declaration| !known.contains(&declaration.name))
+        .collect();
+    let mut wanted: Vec<String> = capabilities::capabilities()
+        .iter()
+        .flat_map(|capability| capability.params.iter())
+        .flat_map(|param| type_names(&param.rust_type))
+        .map(str::to_string)
+        .collect();
+    let mut reached = HashSet::new();
+    while let Some(name) = wanted.pop() {
+        if known.contains(&name) || !reached.insert(name.clone()) {
+            continue;
+        }
+        let Some(declaration) = extra.iter().find(|declaration| declaration.name == name) else {
+            continue;
+        };
+        for (line, _) in declaration.members.iter().flatten() {
+            if let Some((_, rust_type)) = line.split_once(": ") {
+                wanted.extend(type_names(rust_type).into_iter().map(str::to_string));
+            }
+        }
+    }
+    extra.retain(|declaration| reached.contains(&declaration.name));
+    items.extend(extra.into_iter().map(parse_declaration));
+    items
+}
+
+/// Splits `A, B<C, D>` at top-level commas.
+fn split_args(args: &str) -> Vec<&str> {
+    let mut parts = Vec::new();
+    let mut depth = 0;
+    let mut start = 0;
+    for (index, ch) in args.char_indices() {
+        match ch {
+            '<' | '(' => depth += 1,
+            '>' | ')' => depth -= 1,
+            ',' if depth == 0 => {
+                parts.push(args[start..index].trim());
+                start = index + 1;
+            }
+            _ => {}
+        }
+    }
+    parts.push(args[start..].trim());
+    parts
+}
+
+/// Maps a Rust type to TypeScript. A name outside `known` is an error.
+fn ts_type(rust_type: &str, known: &HashSet<String>) -> String {
+    let rust_type: String = rust_type.split_whitespace().collect();
+    let (outer, args) = match rust_type.find('<') {
+        Some(open) if rust_type.ends_with('>') => (
+            &rust_type[..open],
+            split_args(&rust_type[open + 1..rust_type.len() - 1]),
+        ),
+        _ => (rust_type.as_str(), Vec::new()),
+    };
+    let outer = outer.rsplit("::").next().unwrap_or(outer);
+    match (outer, args.as_slice()) {
+        ("String" | "&str" | "PathBuf", []) => "string".to_string(),
+        ("bool", []) => "boolean".to_string(),
+        ("u8" | "u16" | "u32" | "u64" | "usize" | "i32" | "i64" | "f32" | "f64", []) => {
+            "number".to_string()
+        }
+        ("Value", []) => "JsonValue".to_string(),
+        ("Option", [inner]) => {
+            let inner = ts_type(inner, known);
+            if inner.ends_with(" | null") || inner == "JsonValue" {
+                inner
+            } else {
+                format!("{inner} | null")
+            }
+        }
+        ("Vec", [inner]) => {
+            let inner = ts_type(inner, known);
+            if inner.contains(' ') {
+                format!("({inner})[]")
+            } else {
+                format!("{inner}[]")
+            }
+        }
+        ("BTreeMap" | "HashMap", [key, value]) => {
+            format!("Record<{}, {}>", ts_type(key, known), ts_type(value, known))
+        }
+        (name, []) if known.contains(name) => name.to_string(),
+        (name, args) if known.contains(name) => {
+            let args: Vec<String> = args.iter().map(|arg| ts_type(arg, known)).collect();
+            format!("{name}<{}>", args.join(", "))
+        }
+        _ => panic!("no TypeScript mapping for {rust_type}"),
+    }
+}
+
+fn render(items: &[Item]) -> String {
+    let known: HashSet<String> = items.iter().map(|item| item.name().to_string()).collect();
+    let mut out = String::from(HEADER);
+    out.push('\n');
+    out.push_str(JSON_VALUE);
+    for item in items {
+        out.push('\n');
+        match item {
+            Item::Enum { name, variants } => {
+                let variants: Vec<String> = variants
+                    .iter()
+                    .map(|variant| format!("\"{variant}\""))
+                    .collect();
+                let single = format!("export type {name} = {};", variants.join(" | "));
+                if single.len() <= PRINT_WIDTH {
+                    out.push_str(&single);
+                } else {
+                    out.push_str(&format!(
+                        "export type {name} =\n  | {};",
+                        variants.join("\n  | ")
+                    ));
+                }
+                out.push('\n');
+            }
+            Item::Struct {
+                name,
+                generics,
+                fields,
+            } => {
+                let mut known = known.clone();
+                known.extend(generics.iter().cloned());
+                let name = if generics.is_empty() {
+                    name.clone()
+                } else {
+                    format!("{name}<{}>", generics.join(", "))
+                };
+                let flattened: Vec<String> = fields
+                    .iter()
+                    .filter(|field| field.flatten)
+                    .map(|field| ts_type(&field.rust_type, &known))
+                    .collect();
+                out.push_str(&format!("export type {name} = "));
+                for base in &flattened {
+                    out.push_str(&format!("{base} & "));
+                }
+                out.push_str("{\n");
+                for field in fields.iter().filter(|field| !field.flatten) {
+                    out.push_str(&format!(
+                        "  {}: {};\n",
+                        field.name,
+                        ts_type(&field.rust_type, &known)
+                    ));
+                }
+                out.push_str("};\n");
+            }
+        }
+    }
+
+    out.push_str("\n/** Arguments for each registered command, keyed by command name. */\n");
+    out.push_str("export type CommandArgs = {\n");
+    for capability in capabilities::capabilities() {
+        if capability.params.is_empty() {
+            out.push_str(&format!("  {}: Record<string, never>;\n", capability.name));
+            continue;
+        }
+        out.push_str(&format!("  {}: {{\n", capability.name));
+        for param in &capability.params {
+            out.push_str(&format!(
+                "    {}{}: {};\n",
+                param.name,
+                if param.required { "" } else { "?" },
+                ts_type(&param.rust_type, &known)
+            ));
+        }
+        out.push_str("  };\n");
+    }
+    out.push_str("};\n");
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn generated_bindings_are_current() {
+        let generated = render(&collect_items());
+        let path = Path::new(env!("CARGO_MANIFEST_DIR")).join(BINDINGS_PATH);
+        if std::env::var_os("UPDATE_TS_BINDINGS").is_some() {
+            std::fs::write(&path, &generated).expect("write bindings");
+            return;
+        }
+        let committed = std::fs::read_to_string(&path).unwrap_or_default();
+        assert!(
+            committed == generated,
+            "{} is stale; run `npm run bindings`",
+            path.display()
+        );
+    }
+
+    #[test]
+    fn maps_serde_shapes_to_typescript() {
+        let items = parse_items(
+            "#[derive(Serialize)]\n\
+             #[serde(rename_all = \"snake_case\")]\n\
+             pub(crate) enum Trust {\n    #[default]\n    BlockAll,\n    #[serde(rename = \"ok\")]\n    Allowed,\n}\n\
+             \n\
+             #[derive(Serialize)]\n\
+             #[serde(rename_all = \"camelCase\")]\n\
+             pub(crate) struct Status {\n    /// Docs are skipped.\n    #[serde(\n        default,\n        rename = \"pid\"\n    )]\n    pub(crate) process_id: Option<u32>,\n    pub(crate) trust_levels: Vec<Option<Trust>>,\n    pub(crate) env: BTreeMap<String, String>,\n}\n\
+             \n\
+             pub(crate) struct Internal {\n    value: u32,\n}\n",
+        );
+        let known: HashSet<String> = ["Trust".to_string(), "Status".to_string()].into();
+        assert_eq!(items.len(), 2);
+        let Item::Enum { variants, .. } = &items[0] else {
+            panic!("expected enum");
+        };
+        assert_eq!(variants, &["block_all", "ok"]);
+        let Item::Struct { fields, .. } = &items[1] else {
+            panic!("expected struct");
+        };
+        let rendered: Vec<(String, String)> = fields
+            .iter()
+            .map(|field| (field.name.clone(), ts_type(&field.rust_type, &known)))
+            .collect();
+        assert_eq!(
+            rendered,
+            vec![
+                ("pid".to_string(), "number | null".to_string()),
+                ("trustLevels".to_string(), "(Trust | null)[]".to_string()),
+                ("env".to_string(), "Record<string, string>".to_string()),
+            ]
+        );
+        assert_eq!(ts_type("Option<serde_json::Value>", &known), "JsonValue");
+        assert_eq!(
+            ts_type("policy::FileScope", &["FileScope".to_string()].into()),
+            "FileScope"
+        );
+        let generic: HashSet<String> = ["Page".to_string(), "Trust".to_string()].into();
+        assert_eq!(
+            ts_type("Page<Option<Trust>>", &generic),
+            "Page<Trust | null>"
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "no TypeScript mapping for FileScope")]
+    fn unmapped_types_fail_generation() {
+        ts_type("FileScope", &HashSet::new());
+    }
+}
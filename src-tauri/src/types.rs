@@ -1,4 +1,5 @@
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub(crate) struct GitFileStatus {
@@ -187,57 +188,1592 @@ pub(crate) struct LocalUsageSnapshot {
     pub(crate) top_models: Vec<LocalUsageModel>,
 }
 
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub(crate) enum ActivityReportRange {
+    /// The last 7 days, today included.
+    Week,
+    /// The last 30 days, today included.
+    Month,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub(crate) enum ActivityReportFormat {
+    Markdown,
+    Json,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct ActivityProject {
+    pub(crate) name: String,
+    pub(crate) path: String,
+    pub(crate) sessions: usize,
+}
+
+/// Sessions, agent time, token usage, projects and scheduled runs over a
+/// range of days.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct ActivityReport {
+    pub(crate) range: ActivityReportRange,
+    /// First and last day covered, as `YYYY-MM-DD`.
+    pub(crate) from_day: String,
+    pub(crate) to_day: String,
+    pub(crate) generated_at_ms: i64,
+    pub(crate) sessions: usize,
+    pub(crate) agent_time_ms: i64,
+    pub(crate) agent_runs: i64,
+    pub(crate) input_tokens: i64,
+    pub(crate) cached_input_tokens: i64,
+    pub(crate) output_tokens: i64,
+    pub(crate) total_tokens: i64,
+    /// Most sessions first.
+    pub(crate) projects: Vec<ActivityProject>,
+    pub(crate) scheduled_runs: usize,
+    pub(crate) failed_scheduled_runs: usize,
+    pub(crate) days: Vec<LocalUsageDay>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct GeneratedActivityReport {
+    pub(crate) format: ActivityReportFormat,
+    pub(crate) content: String,
+    /// Where the report was saved, when it was.
+    #[serde(default)]
+    pub(crate) path: Option<String>,
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
 #[serde(rename_all = "snake_case")]
 pub(crate) enum TcpDaemonState {
     Stopped,
+    /// Spawned but not yet answering on its port.
+    Starting,
     Running,
+    /// Being drained or killed.
+    Stopping,
     Error,
 }
 
+/// A start, stop or restart of a managed process that is under way.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub(crate) enum ProcessOperation {
+    Starting,
+    Stopping,
+    Restarting,
+}
+
+/// What the app spawned a child process with, as a command preview: secret
+/// arguments replaced by placeholders and secret-looking variables masked.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct ProcessLaunch {
+    pub(crate) command: String,
+    pub(crate) shell: PreviewShell,
+    pub(crate) program: String,
+    pub(crate) args: Vec<String>,
+    /// Variables set on top of the inherited environment.
+    pub(crate) env: BTreeMap<String, String>,
+    #[serde(default)]
+    pub(crate) working_dir: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct TcpDaemonStatus {
+    pub(crate) state: TcpDaemonState,
+    #[serde(default)]
+    pub(crate) pid: Option<u32>,
+    #[serde(default)]
+    pub(crate) started_at_ms: Option<i64>,
+    #[serde(default)]
+    pub(crate) last_error: Option<String>,
+    #[serde(default)]
+    pub(crate) listen_addr: Option<String>,
+    /// macOS application firewall state for the daemon binary; `None` on
+    /// other platforms.
+    #[serde(default)]
+    pub(crate) firewall_trust: Option<DaemonFirewallTrust>,
+    /// Set when a launchd or systemd service owns the daemon, so it keeps
+    /// running without the app.
+    #[serde(default)]
+    pub(crate) service_manager: Option<DaemonServiceManager>,
+    /// Set while a start, stop or restart is in progress.
+    #[serde(default)]
+    pub(crate) operation: Option<ProcessOperation>,
+    /// How the app spawned the daemon it runs; only in verbose status.
+    #[serde(default)]
+    pub(crate) launch: Option<ProcessLaunch>,
+    /// Started outside the app and taken over with `adopt_external_daemon`.
+    #[serde(default)]
+    pub(crate) managed_externally: bool,
+    /// Why and when the daemon last stopped; kept across app restarts.
+    #[serde(default)]
+    pub(crate) last_stop: Option<DaemonStop>,
+    /// The daemon runs an older build than the one now on disk, after an
+    /// app update it was not restarted for; see
+    /// `tailscale_daemon_apply_update`.
+    #[serde(default)]
+    pub(crate) update_pending: bool,
+}
+
+/// What stopped the daemon.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub(crate) enum DaemonStopReason {
+    /// `tailscale_daemon_stop` or a restart.
+    User,
+    /// The app quit and the daemon was not meant to outlive it.
+    AppExit,
+    /// Stopped while the data directory was moved or restored from a backup.
+    DataDirChange,
+    /// Replaced because it was built for another app version or mode.
+    Update,
+    /// Exited with a failure, or stopped answering heartbeats.
+    Crash,
+    /// Exited cleanly on its own, for instance shut down by another client.
+    Exited,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct DaemonStop {
+    pub(crate) reason: DaemonStopReason,
+    pub(crate) at_ms: i64,
+    #[serde(default)]
+    pub(crate) detail: Option<String>,
+}
+
+/// A finished `tailscale_daemon_restart`: the daemon's status once it
+/// answered an authenticated probe, and how long each step took.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct TcpDaemonRestart {
+    pub(crate) status: TcpDaemonStatus,
+    pub(crate) stop_ms: u64,
+    pub(crate) start_ms: u64,
+    /// From spawning the new daemon until it answered a probe.
+    pub(crate) ready_ms: u64,
+    pub(crate) total_ms: u64,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub(crate) enum DaemonServiceManager {
+    Launchd,
+    Systemd,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub(crate) enum DaemonFirewallTrust {
+    /// The application firewall is turned off.
+    Off,
+    Allowed,
+    Blocked,
+    /// The firewall is on and has no entry for the daemon, so macOS will
+    /// prompt (or silently drop connections when nobody is logged in).
+    Unregistered,
+    /// "Block all incoming connections" overrides per-app rules.
+    BlockAll,
+    Unknown,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct TailscaleStatus {
+    pub(crate) installed: bool,
+    pub(crate) running: bool,
+    #[serde(default)]
+    pub(crate) version: Option<String>,
+    #[serde(default)]
+    pub(crate) dns_name: Option<String>,
+    #[serde(default)]
+    pub(crate) host_name: Option<String>,
+    #[serde(default)]
+    pub(crate) tailnet_name: Option<String>,
+    #[serde(default)]
+    pub(crate) ipv4: Vec<String>,
+    #[serde(default)]
+    pub(crate) ipv6: Vec<String>,
+    #[serde(default)]
+    pub(crate) suggested_remote_host: Option<String>,
+    pub(crate) message: String,
+    /// ACL tags the node carries, such as `tag:server`.
+    #[serde(default)]
+    pub(crate) tags: Vec<String>,
+    /// Unix milliseconds when the node key expires; `None` when key expiry
+    /// is disabled for the node.
+    #[serde(default)]
+    pub(crate) key_expiry_ms: Option<i64>,
+    /// The node key expires within a week or already has.
+    #[serde(default)]
+    pub(crate) key_expiring_soon: bool,
+    /// The identity fields come from the last status that had them, because
+    /// Tailscale could not be reached now.
+    #[serde(default)]
+    pub(crate) stale: bool,
+    /// Unix milliseconds when a stale identity was last seen live.
+    #[serde(default)]
+    pub(crate) last_seen_ms: Option<i64>,
+}
+
+/// A Tailscale state the mock backends can be scripted into.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub(crate) enum MockTailscaleState {
+    NotInstalled,
+    Stopped,
+    NeedsLogin,
+    NeedsMachineAuth,
+    Starting,
+    Running,
+}
+
+/// Moves the mock Tailscale into `state` once `after_ms` have passed since
+/// the script was loaded.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct MockTailscaleStep {
+    #[serde(default)]
+    pub(crate) after_ms: u64,
+    pub(crate) state: MockTailscaleState,
+}
+
+/// How the mock backends behave, from `mock-backends.json` in the data
+/// directory or `set_mock_backend_script`.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct MockBackendScript {
+    /// Applied in order; before the first step Tailscale is not installed.
+    #[serde(default = "default_mock_tailscale_steps")]
+    pub(crate) tailscale: Vec<MockTailscaleStep>,
+    /// How long a mock daemon start stays in `starting`.
+    #[serde(default = "default_mock_daemon_start_ms")]
+    pub(crate) daemon_start_ms: u64,
+    #[serde(default = "default_mock_daemon_stop_ms")]
+    pub(crate) daemon_stop_ms: u64,
+    /// Makes every mock daemon start fail with this error.
+    #[serde(default)]
+    pub(crate) daemon_start_error: Option<String>,
+}
+
+impl Default for MockBackendScript {
+    fn default() -> Self {
+        Self {
+            tailscale: default_mock_tailscale_steps(),
+            daemon_start_ms: default_mock_daemon_start_ms(),
+            daemon_stop_ms: default_mock_daemon_stop_ms(),
+            daemon_start_error: None,
+        }
+    }
+}
+
+fn default_mock_tailscale_steps() -> Vec<MockTailscaleStep> {
+    vec![MockTailscaleStep {
+        after_ms: 0,
+        state: MockTailscaleState::Running,
+    }]
+}
+
+fn default_mock_daemon_start_ms() -> u64 {
+    1_500
+}
+
+fn default_mock_daemon_stop_ms() -> u64 {
+    300
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub(crate) enum DaemonPreflightCheckStatus {
+    Pass,
+    Warn,
+    Fail,
+    Skipped,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct DaemonPreflightCheck {
+    pub(crate) id: String,
+    pub(crate) label: String,
+    pub(crate) status: DaemonPreflightCheckStatus,
+    pub(crate) detail: String,
+    /// Suggested remedy shown next to failing or warning checks.
+    #[serde(default)]
+    pub(crate) fix: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct DaemonPreflightReport {
+    pub(crate) listen_addr: String,
+    /// False when any check failed.
+    pub(crate) ready: bool,
+    pub(crate) checks: Vec<DaemonPreflightCheck>,
+}
+
+/// The shell a command preview is escaped for.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub(crate) enum PreviewShell {
+    Posix,
+    PowerShell,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct TailscaleDaemonCommandPreview {
+    /// Ready to paste into `shell`.
+    pub(crate) command: String,
+    pub(crate) shell: PreviewShell,
+    pub(crate) daemon_path: String,
+    pub(crate) args: Vec<String>,
+    /// Variables from the daemon environment settings, keychain values and
+    /// secret-looking names masked.
+    pub(crate) env: BTreeMap<String, String>,
+    /// Inherited from the process that starts the daemon.
+    #[serde(default)]
+    pub(crate) working_dir: Option<String>,
+    pub(crate) token_configured: bool,
+}
+
+/// How traffic reaches the remote backend host.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub(crate) enum RemoteLinkPath {
+    /// Same machine.
+    Local,
+    /// Peer-to-peer WireGuard connection.
+    Direct,
+    /// Relayed through a Tailscale DERP server.
+    Relay,
+    /// Not a tailnet peer, or tailscale status was unavailable.
+    Unknown,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct RemoteQualityReport {
+    pub(crate) host: String,
+    pub(crate) pings_sent: u32,
+    pub(crate) pings_lost: u32,
+    /// Round-trip times; unset when every ping was lost.
+    #[serde(default)]
+    pub(crate) rtt_min_ms: Option<f64>,
+    #[serde(default)]
+    pub(crate) rtt_avg_ms: Option<f64>,
+    #[serde(default)]
+    pub(crate) rtt_max_ms: Option<f64>,
+    /// Mean difference between consecutive round trips.
+    #[serde(default)]
+    pub(crate) jitter_ms: Option<f64>,
+    /// Estimated from one payload round trip; unset when it failed.
+    #[serde(default)]
+    pub(crate) throughput_kbps: Option<u64>,
+    pub(crate) path: RemoteLinkPath,
+    /// DERP region code when `path` is `relay`.
+    #[serde(default)]
+    pub(crate) relay_region: Option<String>,
+    pub(crate) suggested_poll_interval_ms: u64,
+    pub(crate) suggested_max_payload_bytes: u64,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct RemoteBackendStatus {
+    pub(crate) connected: bool,
+    pub(crate) live_subscriptions: u32,
+    /// Milliseconds since the last connect, disconnect or event; unset
+    /// before the first connection.
+    #[serde(default)]
+    pub(crate) idle_ms: Option<u64>,
+    /// From the last `probe_remote_quality`, if any.
+    #[serde(default)]
+    pub(crate) link_path: Option<RemoteLinkPath>,
+    /// How often the frontend should poll the remote backend.
+    pub(crate) suggested_poll_ms: u64,
+}
+
+/// Calls to one remote backend method since launch.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct RemoteMethodMetrics {
+    pub(crate) method: String,
+    pub(crate) calls: u64,
+    pub(crate) errors: u64,
+    pub(crate) total_ms: u64,
+    pub(crate) max_ms: u64,
+    /// Calls per latency bucket: `histogram[i]` counts calls that took at
+    /// most `latencyBucketsMs[i]`, the last entry the ones that took longer.
+    pub(crate) histogram: Vec<u64>,
+}
+
+/// A remote backend call that took longer than `remoteSlowCallMs`.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct RemoteSlowCall {
+    pub(crate) method: String,
+    /// Sent to the daemon with the request as `traceId`.
+    pub(crate) trace_id: String,
+    pub(crate) duration_ms: u64,
+    pub(crate) finished_at_ms: i64,
+    #[serde(default)]
+    pub(crate) error: Option<String>,
+}
+
+/// Remote backend calls since launch, as reported by
+/// `get_remote_backend_metrics`.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct RemoteBackendMetrics {
+    pub(crate) latency_buckets_ms: Vec<u64>,
+    pub(crate) slow_call_ms: u64,
+    /// Slowest total first.
+    pub(crate) methods: Vec<RemoteMethodMetrics>,
+    /// Most recent first.
+    pub(crate) slow_calls: Vec<RemoteSlowCall>,
+}
+
+/// One reply to `tailscale ping`.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct TailscalePong {
+    #[serde(default)]
+    pub(crate) latency_ms: Option<f64>,
+    /// `ip:port` the reply came from over a direct path.
+    #[serde(default)]
+    pub(crate) endpoint: Option<String>,
+    /// DERP region code when the reply was relayed.
+    #[serde(default)]
+    pub(crate) derp_region: Option<String>,
+    #[serde(default)]
+    pub(crate) error: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct TailscalePingResult {
+    pub(crate) target: String,
+    #[serde(default)]
+    pub(crate) node_name: Option<String>,
+    #[serde(default)]
+    pub(crate) node_ip: Option<String>,
+    pub(crate) pongs: Vec<TailscalePong>,
+    /// How the last answered pong travelled.
+    pub(crate) path: RemoteLinkPath,
+    /// Latency of the last answered pong.
+    #[serde(default)]
+    pub(crate) latency_ms: Option<f64>,
+    #[serde(default)]
+    pub(crate) error: Option<String>,
+    pub(crate) pinged_at_ms: i64,
+}
+
+/// How a TCP connect to a daemon port ended.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub(crate) enum TcpProbeOutcome {
+    Connected,
+    /// The host answered but nothing listens on the port.
+    Refused,
+    /// No answer at all, as when a tailnet policy drops the packets.
+    TimedOut,
+    Failed,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct TcpProbeResult {
+    pub(crate) outcome: TcpProbeOutcome,
+    #[serde(default)]
+    pub(crate) latency_ms: Option<u64>,
+    #[serde(default)]
+    pub(crate) error: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct TailnetReachabilityReport {
+    /// The node that made the connection: a peer's daemon, or this device.
+    pub(crate) prober: String,
+    pub(crate) target_host: String,
+    pub(crate) target_port: u16,
+    pub(crate) reachable: bool,
+    pub(crate) probe: TcpProbeResult,
+    #[serde(default)]
+    pub(crate) tailnet_name: Option<String>,
+    /// Login of the tailnet user this machine is signed in as.
+    #[serde(default)]
+    pub(crate) policy_owner: Option<String>,
+    pub(crate) guidance: String,
+}
+
+/// Requested page of a list command. `cursor` is the previous page's
+/// `next_cursor` and is opaque to clients; omit it for the first page.
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct Page {
+    #[serde(default)]
+    pub(crate) cursor: Option<String>,
+    #[serde(default)]
+    pub(crate) limit: Option<u32>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct PageResult<T> {
+    pub(crate) items: Vec<T>,
+    /// Set while more items follow.
+    #[serde(default)]
+    pub(crate) next_cursor: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct RemoteCommandResult {
+    pub(crate) run_id: String,
+    #[serde(default)]
+    pub(crate) exit_code: Option<i32>,
+    pub(crate) timed_out: bool,
+    pub(crate) duration_ms: u64,
+    pub(crate) stdout: String,
+    pub(crate) stderr: String,
+    #[serde(default)]
+    pub(crate) truncated: bool,
+}
+
+/// One line of `remote-command-audit.jsonl`. Fields after `outcome` depend
+/// on how far the command got.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct RemoteCommandAuditEntry {
+    pub(crate) timestamp_ms: i64,
+    pub(crate) run_id: String,
+    pub(crate) workspace_id: String,
+    pub(crate) command: String,
+    #[serde(default)]
+    pub(crate) cwd: Option<String>,
+    pub(crate) outcome: String,
+    #[serde(default)]
+    pub(crate) error: Option<String>,
+    #[serde(default)]
+    pub(crate) resolved_cwd: Option<String>,
+    #[serde(default)]
+    pub(crate) exit_code: Option<i32>,
+    #[serde(default)]
+    pub(crate) duration_ms: Option<u64>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct RemoteDirEntry {
+    pub(crate) name: String,
+    pub(crate) path: String,
+    pub(crate) kind: String,
+    #[serde(default)]
+    pub(crate) size: Option<u64>,
+    #[serde(default)]
+    pub(crate) modified_ms: Option<i64>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct RemoteDirListing {
+    pub(crate) path: String,
+    pub(crate) entries: Vec<RemoteDirEntry>,
+    #[serde(default)]
+    pub(crate) truncated: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct RemoteFileChunk {
+    pub(crate) path: String,
+    pub(crate) size: u64,
+    pub(crate) offset: u64,
+    pub(crate) length: u64,
+    pub(crate) binary: bool,
+    pub(crate) truncated: bool,
+    #[serde(default)]
+    pub(crate) content: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct SessionSearchFilters {
+    #[serde(default)]
+    pub(crate) workspace_id: Option<String>,
+    #[serde(default)]
+    pub(crate) role: Option<String>,
+    #[serde(default)]
+    pub(crate) since_ms: Option<i64>,
+    #[serde(default)]
+    pub(crate) until_ms: Option<i64>,
+    #[serde(default)]
+    pub(crate) limit: Option<usize>,
+    /// Tag ids a session must all carry, itself or through its project.
+    #[serde(default)]
+    pub(crate) tags: Vec<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct SnippetSegment {
+    pub(crate) text: String,
+    pub(crate) highlight: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct SessionSearchHit {
+    pub(crate) session_id: String,
+    pub(crate) path: String,
+    #[serde(default)]
+    pub(crate) cwd: Option<String>,
+    #[serde(default)]
+    pub(crate) timestamp_ms: Option<i64>,
+    pub(crate) role: String,
+    pub(crate) snippet: Vec<SnippetSegment>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub(crate) enum SessionExportFormat {
+    Markdown,
+    /// A standalone page with its styles inlined.
+    Html,
+}
+
+/// A session transcript written to the `exports` directory.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct SessionExport {
+    pub(crate) session_id: String,
+    pub(crate) format: SessionExportFormat,
+    pub(crate) file_name: String,
+    pub(crate) path: String,
+    pub(crate) size: u64,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct SessionArchiveInfo {
+    pub(crate) name: String,
+    pub(crate) path: String,
+    pub(crate) size_bytes: u64,
+    #[serde(default)]
+    pub(crate) created_at_ms: Option<i64>,
+    pub(crate) file_count: usize,
+    pub(crate) original_bytes: u64,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct SessionArchiveReport {
+    pub(crate) dry_run: bool,
+    pub(crate) file_count: usize,
+    pub(crate) reclaimed_bytes: u64,
+    #[serde(default)]
+    pub(crate) archive: Option<SessionArchiveInfo>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct SessionRestoreReport {
+    pub(crate) restored: usize,
+    pub(crate) skipped: usize,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct BackupReport {
+    pub(crate) path: String,
+    pub(crate) created_at_ms: i64,
+    pub(crate) files: Vec<String>,
+    /// Whether remote backend tokens and proxy credentials were included,
+    /// encrypted with the passphrase.
+    pub(crate) secrets_included: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct BackupRestoreReport {
+    /// Version of the app that made the backup.
+    pub(crate) app_version: String,
+    pub(crate) created_at_ms: i64,
+    pub(crate) files: Vec<String>,
+    /// False when the current install's secrets were kept instead.
+    pub(crate) secrets_restored: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct StorageUsage {
+    pub(crate) logs_bytes: u64,
+    pub(crate) archives_bytes: u64,
+    pub(crate) caches_bytes: u64,
+    /// Everything in the data directory, including the parts above.
+    pub(crate) total_bytes: u64,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct StorageHealth {
+    pub(crate) data_dir: String,
+    /// `None` where free space cannot be read.
+    #[serde(default)]
+    pub(crate) free_bytes: Option<u64>,
+    pub(crate) low_space: bool,
+    pub(crate) usage: StorageUsage,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub(crate) enum StorageTarget {
+    Logs,
+    Caches,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct StorageCleanReport {
+    pub(crate) freed_bytes: u64,
+    pub(crate) cleaned: Vec<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct HostRuntime {
+    pub(crate) name: String,
+    pub(crate) version: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct HostCapabilities {
+    /// `std::env::consts::OS`, e.g. `macos`, `linux` or `windows`.
+    pub(crate) os: String,
+    pub(crate) arch: String,
+    pub(crate) cpu_cores: usize,
+    pub(crate) total_memory_bytes: Option<u64>,
+    pub(crate) runtimes: Vec<HostRuntime>,
+}
+
+/// A part of the app that needs the device it runs on, as opposed to the
+/// daemon, so may be missing on some platforms.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub(crate) enum PlatformSubsystem {
+    /// Running Codex and workspaces on this device rather than a daemon.
+    LocalBackend,
+    Terminal,
+    Dictation,
+    Tailscale,
+    /// Starting, adopting and installing the daemon on this device.
+    DaemonManagement,
+    Tray,
+    Menu,
+    GlobalShortcuts,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct PlatformSubsystemStatus {
+    pub(crate) subsystem: PlatformSubsystem,
+    pub(crate) available: bool,
+    /// Why the subsystem is unavailable, when it is.
+    pub(crate) reason: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct PlatformCapabilities {
+    /// `std::env::consts::OS`, e.g. `macos`, `ios` or `android`.
+    pub(crate) platform: String,
+    pub(crate) mobile: bool,
+    /// Every command goes to the remote daemon, whatever `backendMode` says.
+    pub(crate) remote_only: bool,
+    pub(crate) subsystems: Vec<PlatformSubsystemStatus>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub(crate) enum WebhookEvent {
+    SessionCompleted,
+    ApprovalNeeded,
+    DaemonDown,
+    CiFailed,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct WebhookEndpoint {
+    pub(crate) id: String,
+    #[serde(default)]
+    pub(crate) name: String,
+    pub(crate) url: String,
+    /// Signs each body with HMAC-SHA256 when set.
+    #[serde(default)]
+    pub(crate) secret: Option<String>,
+    #[serde(default)]
+    pub(crate) events: Vec<WebhookEvent>,
+    #[serde(default = "default_webhook_enabled")]
+    pub(crate) enabled: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub(crate) enum NotificationIntegrationKind {
+    Slack,
+    Discord,
+}
+
+/// A Slack or Discord channel that hears about the chosen `events`. Its
+/// incoming webhook URL lives in the keychain, under `keychainAccount`.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct NotificationIntegration {
+    pub(crate) id: String,
+    pub(crate) kind: NotificationIntegrationKind,
+    #[serde(default)]
+    pub(crate) name: String,
+    pub(crate) keychain_account: String,
+    #[serde(default)]
+    pub(crate) events: Vec<WebhookEvent>,
+    /// Message text with `{summary}`, `{event}`, `{session}`, `{project}`,
+    /// `{duration}`, `{result}` and `{workspace}` placeholders; the event
+    /// summary when unset.
+    #[serde(default)]
+    pub(crate) message_template: Option<String>,
+    #[serde(default = "default_webhook_enabled")]
+    pub(crate) enabled: bool,
+}
+
+/// One event sent to one endpoint or integration, after its last attempt.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct WebhookDelivery {
+    pub(crate) id: String,
+    pub(crate) endpoint_id: String,
+    pub(crate) event: WebhookEvent,
+    pub(crate) attempts: u32,
+    pub(crate) status_code: Option<u16>,
+    pub(crate) ok: bool,
+    pub(crate) error: Option<String>,
+    pub(crate) created_at_ms: i64,
+    pub(crate) finished_at_ms: i64,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub(crate) enum HookEvent {
+    SessionStarted,
+    SessionFinished,
+    DaemonStarted,
+    DaemonStopped,
+    ApprovalRequested,
+}
+
+/// A shell command run on the chosen `events`, with the event as JSON on
+/// its stdin.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct AutomationHook {
+    pub(crate) id: String,
+    #[serde(default)]
+    pub(crate) name: String,
+    pub(crate) command: String,
+    #[serde(default)]
+    pub(crate) events: Vec<HookEvent>,
+    /// The command is killed after this many seconds.
+    #[serde(default = "default_hook_timeout_secs")]
+    pub(crate) timeout_secs: u32,
+    #[serde(default = "default_webhook_enabled")]
+    pub(crate) enabled: bool,
+}
+
+fn default_hook_timeout_secs() -> u32 {
+    30
+}
+
+/// One run of a hook, with what it printed.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct HookRun {
+    pub(crate) id: String,
+    pub(crate) hook_id: String,
+    pub(crate) event: HookEvent,
+    pub(crate) started_at_ms: i64,
+    pub(crate) duration_ms: u64,
+    pub(crate) exit_code: Option<i32>,
+    pub(crate) timed_out: bool,
+    pub(crate) ok: bool,
+    pub(crate) stdout: String,
+    pub(crate) stderr: String,
+    /// Why the command could not be run at all.
+    pub(crate) error: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct CodexSessionInfo {
+    pub(crate) session_id: String,
+    pub(crate) project_path: String,
+    #[serde(default)]
+    pub(crate) model: Option<String>,
+    #[serde(default)]
+    pub(crate) approval_mode: Option<String>,
+    #[serde(default)]
+    pub(crate) pid: Option<u32>,
+    pub(crate) started_at_ms: i64,
+    pub(crate) status: String,
+    #[serde(default)]
+    pub(crate) exit_code: Option<i32>,
+    /// Set when the session runs in an isolated git worktree.
+    #[serde(default)]
+    pub(crate) worktree: Option<SessionWorktree>,
+    /// Set once the session has finished.
+    #[serde(default)]
+    pub(crate) summary: Option<SessionSummary>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct SessionCommandRun {
+    pub(crate) command: String,
+    #[serde(default)]
+    pub(crate) exit_code: Option<i32>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct SessionTokenUsage {
+    pub(crate) input_tokens: u64,
+    pub(crate) cached_input_tokens: u64,
+    pub(crate) output_tokens: u64,
+}
+
+/// What a finished session did, read from its `codex exec --json` output.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct SessionSummary {
+    /// In the order they were first changed.
+    pub(crate) files_touched: Vec<String>,
+    pub(crate) commands_run: Vec<SessionCommandRun>,
+    /// Totals over every turn; `None` when Codex reported no usage.
+    #[serde(default)]
+    pub(crate) token_usage: Option<SessionTokenUsage>,
+    pub(crate) duration_ms: i64,
+    /// `exited` or `terminated`.
+    pub(crate) status: String,
+    #[serde(default)]
+    pub(crate) exit_code: Option<i32>,
+    /// The agent's last message, shortened.
+    #[serde(default)]
+    pub(crate) last_message: Option<String>,
+    /// Why the last turn failed, when it did.
+    #[serde(default)]
+    pub(crate) error: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct SessionWorktree {
+    pub(crate) session_id: String,
+    /// Root of the repository the worktree was created from.
+    pub(crate) source_path: String,
+    pub(crate) worktree_path: String,
+    pub(crate) branch: String,
+    pub(crate) base_commit: String,
+    pub(crate) created_at_ms: i64,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct SessionWorktreeMergeResult {
+    /// False when the session left no changes to merge.
+    pub(crate) merged: bool,
+    #[serde(default)]
+    pub(crate) commit: Option<String>,
+}
+
+/// Overall state of a commit's check runs.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub(crate) enum CiState {
+    Success,
+    Failure,
+    Pending,
+    /// The commit has no check runs.
+    None,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct CiCheckRun {
+    pub(crate) name: String,
+    /// `queued`, `in_progress` or `completed`.
+    pub(crate) status: String,
+    /// Set once completed, such as `success` or `failure`.
+    #[serde(default)]
+    pub(crate) conclusion: Option<String>,
+    #[serde(default)]
+    pub(crate) url: Option<String>,
+}
+
+/// Check runs of the latest commit on a project's branch.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct ProjectCiStatus {
+    pub(crate) project_id: String,
+    /// `owner/name` on GitHub.
+    pub(crate) repo: String,
+    pub(crate) branch: String,
+    #[serde(default)]
+    pub(crate) commit: Option<String>,
+    pub(crate) state: CiState,
+    pub(crate) checks: Vec<CiCheckRun>,
+    pub(crate) fetched_at_ms: i64,
+}
+
+/// A pull request opened from a session worktree.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct SessionPullRequest {
+    pub(crate) url: String,
+    /// The pushed session branch.
+    pub(crate) branch: String,
+    pub(crate) base: String,
+    /// Tip of the session branch.
+    pub(crate) commit: String,
+}
+
+/// Resource usage of a process and its descendants at one point in time.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct ProcessStats {
+    pub(crate) pid: u32,
+    pub(crate) cpu_percent: f64,
+    pub(crate) rss_bytes: u64,
+    pub(crate) descendant_count: u32,
+    pub(crate) sampled_at_ms: i64,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct CodexSessionDetail {
+    pub(crate) session: CodexSessionInfo,
+    /// Oldest first; sampled while the session runs.
+    pub(crate) resource_stats: Vec<ProcessStats>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct SessionTemplate {
+    pub(crate) id: String,
+    pub(crate) name: String,
+    pub(crate) prompt: String,
+    #[serde(default)]
+    pub(crate) model: Option<String>,
+    #[serde(default)]
+    pub(crate) approval_mode: Option<String>,
+    #[serde(default)]
+    pub(crate) working_dir: Option<String>,
+    #[serde(default)]
+    pub(crate) env: BTreeMap<String, String>,
+    #[serde(default)]
+    pub(crate) created_at_ms: i64,
+    #[serde(default)]
+    pub(crate) updated_at_ms: i64,
+}
+
+/// A scoped, expiring credential for the daemon. The secret is only returned
+/// once, from `create_guest_token`; storage keeps a SHA-256 hash.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct GuestToken {
+    pub(crate) id: String,
+    #[serde(default)]
+    pub(crate) label: Option<String>,
+    pub(crate) scopes: Vec<String>,
+    pub(crate) created_at_ms: i64,
+    pub(crate) expires_at_ms: i64,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct GuestTokenRecord {
+    #[serde(flatten)]
+    pub(crate) token: GuestToken,
+    pub(crate) token_hash: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct IssuedGuestToken {
+    pub(crate) guest: GuestToken,
+    pub(crate) token: String,
+}
+
+/// What a caller needs to run a command. `Local` commands only act on the
+/// machine running the app; the rest map to the guest token scope the
+/// daemon checks for the matching method.
+/// Where a startup phase ran. `Setup` phases delay the window; the others
+/// run after it can appear, `Lazy` ones only when first needed.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub(crate) enum StartupPhaseKind {
+    Setup,
+    Background,
+    Lazy,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct StartupPhase {
+    pub(crate) name: String,
+    pub(crate) kind: StartupPhaseKind,
+    /// Milliseconds from process start until the phase began.
+    pub(crate) started_after_ms: u64,
+    pub(crate) duration_ms: u64,
+}
+
+/// Where the Codex home in use came from.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub(crate) enum CodexHomeSource {
+    /// `codexHomeOverride` in settings.
+    Override,
+    /// The `CODEX_HOME` environment variable.
+    Env,
+    /// `~/.codex`.
+    Default,
+}
+
+/// The Codex home config and AGENTS.md commands read and write.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct CodexHomeInfo {
+    /// `None` when no home directory can be found either.
+    pub(crate) path: Option<String>,
+    pub(crate) source: CodexHomeSource,
+    /// Whether `path` exists yet; Codex creates it on first use.
+    pub(crate) exists: bool,
+}
+
+/// A saved copy of the Codex home's config, AGENTS.md and, unless left
+/// out, its credentials.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct CodexHomeSnapshotInfo {
+    pub(crate) id: String,
+    pub(crate) label: Option<String>,
+    pub(crate) created_at_ms: i64,
+    /// The Codex home the snapshot was taken from.
+    pub(crate) codex_home: String,
+    /// Files the snapshot holds.
+    pub(crate) files: Vec<String>,
+    /// Whether credentials such as auth.json were saved.
+    pub(crate) secrets_included: bool,
+    pub(crate) size_bytes: u64,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct CodexHomeRestoreReport {
+    /// The Codex home that was restored into.
+    pub(crate) codex_home: String,
+    /// Files written back from the snapshot.
+    pub(crate) restored: Vec<String>,
+    /// Files removed because they did not exist when the snapshot was taken.
+    pub(crate) removed: Vec<String>,
+    /// The snapshot of the state just before the restore.
+    pub(crate) previous: CodexHomeSnapshotInfo,
+}
+
+/// How the Codex CLI is signed in.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub(crate) enum CodexAuthMethod {
+    Chatgpt,
+    ApiKey,
+    None,
+}
+
+/// Where `codex_auth_status` learned the sign-in state.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub(crate) enum CodexAuthSource {
+    /// `auth.json` in the Codex home.
+    AuthFile,
+    /// `codex login status`, for credentials kept outside the Codex home.
+    Cli,
+}
+
+/// Whether the Codex CLI is signed in, as `codex_auth_status` reports it.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct CodexAuthStatus {
+    pub(crate) logged_in: bool,
+    pub(crate) method: CodexAuthMethod,
+    pub(crate) source: CodexAuthSource,
+    pub(crate) codex_home: Option<String>,
+    pub(crate) email: Option<String>,
+    pub(crate) plan_type: Option<String>,
+    /// When the current access token expires. `None` for API keys.
+    pub(crate) expires_at_ms: Option<i64>,
+    /// When Codex last refreshed the tokens.
+    pub(crate) last_refresh_ms: Option<i64>,
+    /// Whether a refresh token lets Codex renew the access token itself.
+    pub(crate) refreshable: bool,
+    /// Set when the credentials cannot renew themselves and expire within
+    /// a few days, or already have.
+    pub(crate) expiring_soon: bool,
+    pub(crate) expired: bool,
+    pub(crate) message: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub(crate) enum CodexLoginFlowState {
+    /// The CLI is running but has not printed the code yet.
+    Starting,
+    /// The verification URL and code are known; waiting for the user.
+    AwaitingUser,
+    Succeeded,
+    Failed,
+    Canceled,
+}
+
+/// A `codex login --device-auth` run started by `codex_login_start`, also
+/// sent as `codex-login-progress` whenever it changes.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct CodexLoginFlow {
+    pub(crate) id: String,
+    pub(crate) state: CodexLoginFlowState,
+    /// Where to enter `user_code`, on any device.
+    pub(crate) verification_url: Option<String>,
+    pub(crate) user_code: Option<String>,
+    pub(crate) started_at_ms: i64,
+    pub(crate) finished_at_ms: Option<i64>,
+    /// The CLI's last line of output, or why the flow ended.
+    pub(crate) message: Option<String>,
+    /// The sign-in once the flow succeeded.
+    pub(crate) auth: Option<CodexAuthStatus>,
+}
+
+/// What wrote a recorded config.toml change.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub(crate) enum ConfigChangeSource {
+    /// A settings toggle, such as an experimental flag or agent setting.
+    Settings,
+    /// The config.toml editor.
+    Editor,
+    SnapshotRestore,
+    Revert,
+}
+
+/// One write to the global config.toml, as listed by `list_config_changes`.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct ConfigChange {
+    pub(crate) id: String,
+    pub(crate) timestamp_ms: i64,
+    pub(crate) source: ConfigChangeSource,
+    pub(crate) path: String,
+    /// Dotted paths of the settings whose value changed.
+    pub(crate) changed_keys: Vec<String>,
+    /// Unified diff of the file, without file headers.
+    pub(crate) diff: String,
+    /// The file before and after, `None` when it did not exist.
+    pub(crate) before: Option<String>,
+    pub(crate) after: Option<String>,
+    /// The change this one reverted.
+    #[serde(default)]
+    pub(crate) reverts: Option<String>,
+}
+
+/// A window's size and place, kept per window label in `window-state.json`
+/// and reapplied when the window is created.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct WindowState {
+    pub(crate) label: String,
+    /// Outer position and inner size in physical pixels, from before the
+    /// window was last maximized.
+    pub(crate) x: i32,
+    pub(crate) y: i32,
+    pub(crate) width: u32,
+    pub(crate) height: u32,
+    pub(crate) maximized: bool,
+    /// The monitor the window was on, when the platform names it.
+    #[serde(default)]
+    pub(crate) monitor: Option<String>,
+    pub(crate) saved_at_ms: i64,
+}
+
+/// What a global shortcut does when pressed.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub(crate) enum GlobalShortcutAction {
+    /// Shows and focuses the main window, or hides it when focused.
+    ToggleWindow,
+    /// Starts the managed daemon, or stops it when running.
+    ToggleDaemon,
+    /// Starts a session from the configured template.
+    StartSession,
+}
+
+/// A configured global shortcut, as listed by `list_registered_shortcuts`.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct RegisteredShortcut {
+    pub(crate) action: GlobalShortcutAction,
+    pub(crate) shortcut: String,
+    pub(crate) registered: bool,
+    /// Why it was not registered: it is invalid, used twice, or taken by
+    /// another app.
+    pub(crate) error: Option<String>,
+    /// In-app shortcut settings it also matches. Those stop working while
+    /// the global one is registered.
+    #[serde(default)]
+    pub(crate) shadows: Vec<String>,
+}
+
+/// What brought the app forward.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub(crate) enum ActivityTrigger {
+    ApprovalRequested,
+    SessionFailed,
+}
+
+/// The activity shown in the compact alert window, as returned by
+/// `get_activity_alert`.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct ActivityAlert {
+    pub(crate) trigger: ActivityTrigger,
+    pub(crate) workspace_id: Option<String>,
+    pub(crate) thread_id: Option<String>,
+    pub(crate) session_id: Option<String>,
+    /// The approval method, or how the session exited.
+    pub(crate) detail: Option<String>,
+    pub(crate) raised_at_ms: i64,
+}
+
+/// Timing of this launch, as reported by `get_startup_report`.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct StartupReport {
+    /// Milliseconds from process start until setup finished and the window
+    /// could appear.
+    pub(crate) ready_after_ms: Option<u64>,
+    pub(crate) phases: Vec<StartupPhase>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub(crate) enum CommandPermission {
+    Read,
+    Write,
+    Owner,
+    Local,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct CommandParam {
+    /// The argument name as passed to `invoke`.
+    pub(crate) name: String,
+    pub(crate) rust_type: String,
+    pub(crate) required: bool,
+}
+
+/// One registered Tauri command, as reported by `list_capabilities`.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct CommandCapability {
+    pub(crate) name: String,
+    pub(crate) module: String,
+    pub(crate) params: Vec<CommandParam>,
+    pub(crate) permission: CommandPermission,
+    /// Daemon method serving the command in remote mode, when there is one.
+    #[serde(default)]
+    pub(crate) remote_method: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub(crate) enum ClientApprovalStatus {
+    Pending,
+    Approved,
+    Denied,
+}
+
+/// A client the daemon has seen authenticate, keyed by the identifier it
+/// sends with `auth`. Only recorded while client approval is required.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct ClientApproval {
+    pub(crate) client_id: String,
+    #[serde(default)]
+    pub(crate) client_name: Option<String>,
+    pub(crate) status: ClientApprovalStatus,
+    pub(crate) first_seen_at_ms: i64,
+    pub(crate) last_seen_at_ms: i64,
+    #[serde(default)]
+    pub(crate) decided_at_ms: Option<i64>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub(crate) enum RemoteActionConfirmationStatus {
+    Pending,
+    Approved,
+    Rejected,
+    /// Nobody answered before the request's deadline.
+    Expired,
+}
+
+/// A destructive request from a remote client that the daemon holds until
+/// the owner confirms it on the desktop.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct RemoteActionConfirmation {
+    pub(crate) id: String,
+    pub(crate) method: String,
+    /// What the request will do, for the prompt.
+    pub(crate) summary: String,
+    #[serde(default)]
+    pub(crate) client_id: Option<String>,
+    #[serde(default)]
+    pub(crate) client_name: Option<String>,
+    pub(crate) status: RemoteActionConfirmationStatus,
+    pub(crate) requested_at_ms: i64,
+    pub(crate) expires_at_ms: i64,
+    #[serde(default)]
+    pub(crate) decided_at_ms: Option<i64>,
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 #[serde(rename_all = "camelCase")]
-pub(crate) struct TcpDaemonStatus {
-    pub(crate) state: TcpDaemonState,
+pub(crate) struct SessionTemplateInput {
     #[serde(default)]
-    pub(crate) pid: Option<u32>,
+    pub(crate) id: Option<String>,
+    pub(crate) name: String,
+    pub(crate) prompt: String,
     #[serde(default)]
-    pub(crate) started_at_ms: Option<i64>,
+    pub(crate) model: Option<String>,
     #[serde(default)]
-    pub(crate) last_error: Option<String>,
+    pub(crate) approval_mode: Option<String>,
     #[serde(default)]
-    pub(crate) listen_addr: Option<String>,
+    pub(crate) working_dir: Option<String>,
+    #[serde(default)]
+    pub(crate) env: BTreeMap<String, String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct Tag {
+    pub(crate) id: String,
+    pub(crate) name: String,
+    #[serde(default)]
+    pub(crate) color: Option<String>,
+    pub(crate) created_at_ms: i64,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
 #[serde(rename_all = "camelCase")]
-pub(crate) struct TailscaleStatus {
-    pub(crate) installed: bool,
-    pub(crate) running: bool,
+pub(crate) struct TagInput {
     #[serde(default)]
-    pub(crate) version: Option<String>,
+    pub(crate) id: Option<String>,
+    pub(crate) name: String,
     #[serde(default)]
-    pub(crate) dns_name: Option<String>,
+    pub(crate) color: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub(crate) enum TagTarget {
+    Session,
+    Project,
+}
+
+/// Tag ids by session id and by workspace id.
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct TagAssignments {
     #[serde(default)]
-    pub(crate) host_name: Option<String>,
+    pub(crate) sessions: BTreeMap<String, Vec<String>>,
     #[serde(default)]
-    pub(crate) tailnet_name: Option<String>,
+    pub(crate) projects: BTreeMap<String, Vec<String>>,
+}
+
+/// The contents of `tags.json`.
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct TagStore {
     #[serde(default)]
-    pub(crate) ipv4: Vec<String>,
+    pub(crate) tags: Vec<Tag>,
     #[serde(default)]
-    pub(crate) ipv6: Vec<String>,
+    pub(crate) assignments: TagAssignments,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct ScheduleRun {
+    pub(crate) at_ms: i64,
+    /// `started`, `queued`, `skipped` or `failed`.
+    pub(crate) status: String,
     #[serde(default)]
-    pub(crate) suggested_remote_host: Option<String>,
-    pub(crate) message: String,
+    pub(crate) message: Option<String>,
+    #[serde(default)]
+    pub(crate) session_id: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct Schedule {
+    pub(crate) id: String,
+    pub(crate) name: String,
+    /// Five-field cron expression evaluated in local time.
+    pub(crate) cron: String,
+    pub(crate) template_id: String,
+    #[serde(default)]
+    pub(crate) project_path: Option<String>,
+    #[serde(default = "default_schedule_enabled")]
+    pub(crate) enabled: bool,
+    #[serde(default)]
+    pub(crate) next_run_at_ms: Option<i64>,
+    #[serde(default)]
+    pub(crate) runs: Vec<ScheduleRun>,
+    #[serde(default)]
+    pub(crate) created_at_ms: i64,
+    #[serde(default)]
+    pub(crate) updated_at_ms: i64,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
 #[serde(rename_all = "camelCase")]
-pub(crate) struct TailscaleDaemonCommandPreview {
-    pub(crate) command: String,
-    pub(crate) daemon_path: String,
-    pub(crate) args: Vec<String>,
-    pub(crate) token_configured: bool,
+pub(crate) struct ScheduleInput {
+    #[serde(default)]
+    pub(crate) id: Option<String>,
+    pub(crate) name: String,
+    pub(crate) cron: String,
+    pub(crate) template_id: String,
+    #[serde(default)]
+    pub(crate) project_path: Option<String>,
+    #[serde(default = "default_schedule_enabled")]
+    pub(crate) enabled: bool,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -331,6 +1867,9 @@ pub(crate) struct WorkspaceSettings {
     pub(crate) worktree_setup_script: Option<String>,
     #[serde(default, rename = "worktreesFolder")]
     pub(crate) worktrees_folder: Option<String>,
+    /// Notify when CI fails on a branch a session in this project touched.
+    #[serde(default, rename = "ciFailureNotifications")]
+    pub(crate) ci_failure_notifications: bool,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -362,6 +1901,96 @@ pub(crate) struct OpenAppTarget {
     pub(crate) args: Vec<String>,
 }
 
+/// How a launcher's command takes a file, a line and a diff.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub(crate) enum EditorKind {
+    /// `code`, `cursor` and other VS Code builds: `--goto` and `--diff`.
+    #[default]
+    Vscode,
+    /// `idea` and the other JetBrains launchers: `--line` and `diff`.
+    Jetbrains,
+    /// Any other command, given only the path.
+    Other,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct EditorLauncher {
+    pub(crate) id: String,
+    #[serde(default)]
+    pub(crate) label: String,
+    /// Program name looked up on `PATH`, or an absolute path.
+    pub(crate) command: String,
+    #[serde(default)]
+    pub(crate) kind: EditorKind,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct EditorInfo {
+    pub(crate) id: String,
+    pub(crate) label: String,
+    pub(crate) command: String,
+    pub(crate) kind: EditorKind,
+    /// Whether `command` was found on the host that would launch it.
+    pub(crate) available: bool,
+}
+
+/// Who may call a plugin method: `read` and `write` match guest token
+/// scopes, `owner` admits only the owner.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub(crate) enum PluginScope {
+    Read,
+    #[default]
+    Write,
+    Owner,
+}
+
+/// A method a plugin adds, called as `ext.<plugin id>.<name>`.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct PluginMethod {
+    pub(crate) name: String,
+    #[serde(default)]
+    pub(crate) scope: PluginScope,
+    #[serde(default)]
+    pub(crate) description: String,
+}
+
+/// `plugins/<id>.json` in the data directory.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct PluginManifest {
+    #[serde(default)]
+    pub(crate) name: String,
+    /// Run once per call, with the call as JSON on its stdin.
+    pub(crate) command: String,
+    #[serde(default)]
+    pub(crate) args: Vec<String>,
+    #[serde(default)]
+    pub(crate) methods: Vec<PluginMethod>,
+    #[serde(default = "default_plugin_timeout_secs")]
+    pub(crate) timeout_secs: u32,
+}
+
+fn default_plugin_timeout_secs() -> u32 {
+    30
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct PluginInfo {
+    pub(crate) id: String,
+    pub(crate) name: String,
+    pub(crate) command: String,
+    pub(crate) methods: Vec<PluginMethod>,
+    pub(crate) enabled: bool,
+    /// Why the manifest could not be loaded; such a plugin has no methods.
+    pub(crate) error: Option<String>,
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub(crate) struct RemoteBackendTarget {
     pub(crate) id: String,
@@ -371,16 +2000,287 @@ pub(crate) struct RemoteBackendTarget {
     pub(crate) host: String,
     #[serde(default)]
     pub(crate) token: Option<String>,
+    /// The daemon identity secret copied from the daemon's host when
+    /// pairing; the token is only sent to a daemon that proves it holds it.
+    #[serde(default)]
+    pub(crate) identity: Option<String>,
     #[serde(default, rename = "lastConnectedAtMs")]
     pub(crate) last_connected_at_ms: Option<i64>,
 }
 
+/// Extra environment for a spawned process, layered over the app's own.
+/// Secrets are referenced by keychain account instead of stored here.
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct ProcessEnvConfig {
+    #[serde(default)]
+    pub(crate) vars: BTreeMap<String, String>,
+    #[serde(default)]
+    pub(crate) path_prepend: Vec<String>,
+    #[serde(default)]
+    pub(crate) http_proxy: Option<String>,
+    #[serde(default)]
+    pub(crate) https_proxy: Option<String>,
+    #[serde(default)]
+    pub(crate) no_proxy: Option<String>,
+    /// Variable name to the keychain account holding its value.
+    #[serde(default)]
+    pub(crate) keychain_vars: BTreeMap<String, String>,
+}
+
+/// Proxy for remote backend connections.
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct ProxySettings {
+    /// `http://` or `socks5://` URL, optionally with `user:password@`.
+    #[serde(default)]
+    pub(crate) url: Option<String>,
+    /// Hosts reached directly: exact names or IPs, `.domain` suffixes, or
+    /// `*`. Loopback and tailnet addresses always bypass the proxy.
+    #[serde(default)]
+    pub(crate) bypass: Vec<String>,
+}
+
+/// How long a daemon RPC may take to answer: `fast` for liveness and
+/// lookups, `long` for diffs, searches, clones and the like, `normal` for
+/// everything else.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub(crate) enum RpcTimeoutClass {
+    Fast,
+    #[default]
+    Normal,
+    Long,
+}
+
+/// The daemon's reply to `rpc_timeouts`: its limit for each class and the
+/// methods outside `normal`.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct RpcTimeouts {
+    pub(crate) fast_ms: u64,
+    pub(crate) normal_ms: u64,
+    pub(crate) long_ms: u64,
+    pub(crate) fast_methods: Vec<String>,
+    pub(crate) long_methods: Vec<String>,
+}
+
+/// Local overrides for remote backend timeouts; unset values use what the
+/// daemon advertises.
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct RpcTimeoutSettings {
+    #[serde(default)]
+    pub(crate) connect_ms: Option<u64>,
+    #[serde(default)]
+    pub(crate) fast_ms: Option<u64>,
+    #[serde(default)]
+    pub(crate) normal_ms: Option<u64>,
+    #[serde(default)]
+    pub(crate) long_ms: Option<u64>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct ProxyTestResult {
+    pub(crate) target: String,
+    /// False when the target bypasses the proxy.
+    pub(crate) proxied: bool,
+    pub(crate) latency_ms: u64,
+}
+
+/// Quiet hours on `days`, from `start` to `end` in local `HH:MM`. A window
+/// that ends before it starts runs past midnight and belongs to the day it
+/// starts on.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct QuietHoursWindow {
+    /// 0 is Sunday, as in cron; every day when empty.
+    #[serde(default)]
+    pub(crate) days: Vec<u8>,
+    pub(crate) start: String,
+    pub(crate) end: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct DoNotDisturbSettings {
+    #[serde(default)]
+    pub(crate) enabled: bool,
+    #[serde(default)]
+    pub(crate) windows: Vec<QuietHoursWindow>,
+    /// Also holds notifications back while the screen is shared (macOS).
+    #[serde(default)]
+    pub(crate) suppress_while_screen_sharing: bool,
+    /// Lets urgent notifications, such as the daemon stopping, through quiet
+    /// hours and snoozes.
+    #[serde(default)]
+    pub(crate) allow_urgent: bool,
+}
+
+/// How the app comes forward for activity that arrives while none of its
+/// windows has focus.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub(crate) enum RaiseWindowMode {
+    #[default]
+    Off,
+    /// Shows and focuses the main window on the monitor and space in use.
+    Window,
+    /// Shows a small alert window on top instead, leaving the main one be.
+    Compact,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct FocusFollowsActivitySettings {
+    #[serde(default)]
+    pub(crate) approval_requested: RaiseWindowMode,
+    /// Sessions that exit with a non-zero code.
+    #[serde(default)]
+    pub(crate) session_failed: RaiseWindowMode,
+}
+
+/// A custom redaction rule. `replacement` may refer to capture groups as
+/// `$1`; it defaults to `[REDACTED:<name>]`.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct RedactionRule {
+    pub(crate) name: String,
+    pub(crate) pattern: String,
+    #[serde(default)]
+    pub(crate) replacement: Option<String>,
+}
+
+/// A way clients can authenticate to the daemon.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub(crate) enum DaemonAuthProvider {
+    /// The daemon's `--token`, or a guest token.
+    Token,
+    /// Connections from a tailnet peer in `approved_tailnet_peers`.
+    TailnetIdentity,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub(crate) enum DaemonAuthMode {
+    /// Any one of the providers admits a client.
+    #[default]
+    Any,
+    /// Every provider has to admit it.
+    All,
+}
+
+/// How the daemon authenticates clients. Loopback clients holding the
+/// daemon token are always admitted, so the desktop app keeps control of
+/// the daemon it runs.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct DaemonAuthSettings {
+    #[serde(default = "default_daemon_auth_providers")]
+    pub(crate) providers: Vec<DaemonAuthProvider>,
+    #[serde(default)]
+    pub(crate) mode: DaemonAuthMode,
+    /// Full MagicDNS names, stable node IDs or tailnet IPs of the peers
+    /// `tailnet_identity` trusts.
+    #[serde(default)]
+    pub(crate) approved_tailnet_peers: Vec<String>,
+    /// Refuse mutating requests from remote token clients that did not
+    /// negotiate request signing in `auth`.
+    #[serde(default)]
+    pub(crate) require_request_signing: bool,
+}
+
+fn default_daemon_auth_providers() -> Vec<DaemonAuthProvider> {
+    vec![DaemonAuthProvider::Token]
+}
+
+impl Default for DaemonAuthSettings {
+    fn default() -> Self {
+        Self {
+            providers: default_daemon_auth_providers(),
+            mode: DaemonAuthMode::default(),
+            approved_tailnet_peers: Vec::new(),
+            require_request_signing: false,
+        }
+    }
+}
+
+/// What is scrubbed from transcripts and logs the daemon serves, and from
+/// session exports.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct RedactionSettings {
+    #[serde(default = "default_redaction_enabled")]
+    pub(crate) enabled: bool,
+    /// Detectors for common key and token formats, run before `rules`.
+    #[serde(default = "default_redaction_enabled")]
+    pub(crate) built_in_detectors: bool,
+    #[serde(default)]
+    pub(crate) rules: Vec<RedactionRule>,
+}
+
+fn default_redaction_enabled() -> bool {
+    true
+}
+
+impl Default for RedactionSettings {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            built_in_detectors: true,
+            rules: Vec::new(),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct RedactionHit {
+    pub(crate) rule: String,
+    pub(crate) count: usize,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct RedactionTestResult {
+    pub(crate) redacted: String,
+    /// Rules that matched, in the order they ran.
+    pub(crate) hits: Vec<RedactionHit>,
+}
+
+/// Pulling shared settings from another CodexMonitor through its daemon.
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct SettingsSyncConfig {
+    #[serde(default)]
+    pub(crate) enabled: bool,
+    /// The saved remote backend to pull from; the active one when unset.
+    #[serde(default)]
+    pub(crate) source_backend_id: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct SettingsSyncReport {
+    pub(crate) source: String,
+    /// False when the local settings were as new or newer.
+    pub(crate) settings_applied: bool,
+    pub(crate) templates_updated: usize,
+    /// Paths of projects registered because they exist here too.
+    pub(crate) projects_added: Vec<String>,
+    pub(crate) synced_at_ms: i64,
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub(crate) struct AppSettings {
     #[serde(default, rename = "codexBin")]
     pub(crate) codex_bin: Option<String>,
     #[serde(default, rename = "codexArgs")]
     pub(crate) codex_args: Option<String>,
+    #[serde(default, rename = "codexEnv")]
+    pub(crate) codex_env: ProcessEnvConfig,
     #[serde(default, rename = "backendMode")]
     pub(crate) backend_mode: BackendMode,
     #[serde(default, rename = "remoteBackendProvider")]
@@ -389,12 +2289,122 @@ pub(crate) struct AppSettings {
     pub(crate) remote_backend_host: String,
     #[serde(default, rename = "remoteBackendToken")]
     pub(crate) remote_backend_token: Option<String>,
+    #[serde(default, rename = "remoteBackendIdentity")]
+    pub(crate) remote_backend_identity: Option<String>,
     #[serde(default = "default_remote_backends", rename = "remoteBackends")]
     pub(crate) remote_backends: Vec<RemoteBackendTarget>,
     #[serde(default, rename = "activeRemoteBackendId")]
     pub(crate) active_remote_backend_id: Option<String>,
+    #[serde(default)]
+    pub(crate) proxy: ProxySettings,
+    #[serde(default, rename = "rpcTimeouts")]
+    pub(crate) rpc_timeouts: RpcTimeoutSettings,
+    /// Remote backend calls slower than this land in the slow-call log of
+    /// `get_remote_backend_metrics`.
+    #[serde(default = "default_remote_slow_call_ms", rename = "remoteSlowCallMs")]
+    pub(crate) remote_slow_call_ms: u64,
     #[serde(default, rename = "keepDaemonRunningAfterAppClose")]
     pub(crate) keep_daemon_running_after_app_close: bool,
+    #[serde(default, rename = "daemonEnv")]
+    pub(crate) daemon_env: ProcessEnvConfig,
+    #[serde(default, rename = "daemonHttpBridgeEnabled")]
+    pub(crate) daemon_http_bridge_enabled: bool,
+    #[serde(
+        default = "default_daemon_http_bridge_port",
+        rename = "daemonHttpBridgePort"
+    )]
+    pub(crate) daemon_http_bridge_port: u16,
+    /// Holds token-authenticated clients the daemon has not seen before
+    /// until the owner approves them from the desktop app.
+    #[serde(default, rename = "requireClientApproval")]
+    pub(crate) require_client_approval: bool,
+    /// Holds `terminate_session`, `run_remote_command` and writes to the
+    /// global Codex config from remote clients until the owner confirms them
+    /// on the desktop.
+    #[serde(default, rename = "confirmRemoteActions")]
+    pub(crate) confirm_remote_actions: bool,
+    /// How long a held request waits for the owner before it is rejected.
+    #[serde(
+        default = "default_remote_action_confirm_timeout_secs",
+        rename = "remoteActionConfirmTimeoutSecs"
+    )]
+    pub(crate) remote_action_confirm_timeout_secs: u64,
+    /// Swaps Tailscale and the managed daemon for in-process fakes from the
+    /// next launch, for UI work without either installed. `--mock-backends`
+    /// does the same for one run.
+    #[serde(default, rename = "mockBackends")]
+    pub(crate) mock_backends: bool,
+    #[serde(default, rename = "daemonAuth")]
+    pub(crate) daemon_auth: DaemonAuthSettings,
+    #[serde(
+        default = "default_tailscale_status_ttl_secs",
+        rename = "tailscaleStatusTtlSecs"
+    )]
+    pub(crate) tailscale_status_ttl_secs: u32,
+    /// Where the app keeps its state when not in the platform default. Only
+    /// the copy in the default directory's settings is read at startup.
+    #[serde(default, rename = "dataDir")]
+    pub(crate) data_dir: Option<String>,
+    /// Where the managed daemon keeps its own state. Relative paths are
+    /// taken from the data directory; unset means its `daemon` subdirectory.
+    #[serde(default, rename = "daemonDataDir")]
+    pub(crate) daemon_data_dir: Option<String>,
+    /// Codex home used instead of `CODEX_HOME` or `~/.codex` for config,
+    /// AGENTS.md and everything else Codex keeps there. Only changes through
+    /// `set_codex_home_override`, which validates it.
+    #[serde(default, rename = "codexHomeOverride")]
+    pub(crate) codex_home_override: Option<String>,
+    /// Free space in the data directory, in MiB, below which the app warns
+    /// once. `0` turns the warning off.
+    #[serde(
+        default = "default_low_disk_space_threshold_mb",
+        rename = "lowDiskSpaceThresholdMb"
+    )]
+    pub(crate) low_disk_space_threshold_mb: u32,
+    /// Minimum milliseconds between two emissions of an event, by event
+    /// name. Updates inside the window are coalesced into one carrying the
+    /// latest payload; events not listed are emitted as they happen.
+    #[serde(default = "default_event_coalescing_ms", rename = "eventCoalescingMs")]
+    pub(crate) event_coalescing_ms: BTreeMap<String, u32>,
+    #[serde(default, rename = "settingsSync")]
+    pub(crate) settings_sync: SettingsSyncConfig,
+    /// When a setting shared by settings sync last changed, in Unix
+    /// milliseconds; the newer side wins a sync.
+    #[serde(default, rename = "settingsUpdatedAtMs")]
+    pub(crate) settings_updated_at_ms: i64,
+    /// HTTP endpoints told about session, approval and daemon events.
+    #[serde(default)]
+    pub(crate) webhooks: Vec<WebhookEndpoint>,
+    #[serde(default, rename = "notificationIntegrations")]
+    pub(crate) notification_integrations: Vec<NotificationIntegration>,
+    /// Commands run at session, daemon and approval lifecycle points.
+    #[serde(default)]
+    pub(crate) hooks: Vec<AutomationHook>,
+    #[serde(default, rename = "doNotDisturb")]
+    pub(crate) do_not_disturb: DoNotDisturbSettings,
+    #[serde(default, rename = "focusFollowsActivity")]
+    pub(crate) focus_follows_activity: FocusFollowsActivitySettings,
+    /// Writes a Markdown activity report for the past week into the data
+    /// directory once a week and notifies when it is ready.
+    #[serde(default, rename = "weeklyActivityReport")]
+    pub(crate) weekly_activity_report: bool,
+    #[serde(default)]
+    pub(crate) redaction: RedactionSettings,
+    #[serde(default, rename = "remoteCommandAllowlist")]
+    pub(crate) remote_command_allowlist: Vec<String>,
+    #[serde(default, rename = "remoteCommandDenylist")]
+    pub(crate) remote_command_denylist: Vec<String>,
+    #[serde(
+        default = "default_remote_command_timeout_secs",
+        rename = "remoteCommandTimeoutSecs"
+    )]
+    pub(crate) remote_command_timeout_secs: u32,
+    #[serde(default, rename = "sessionRetentionMaxAgeDays")]
+    pub(crate) session_retention_max_age_days: Option<u32>,
+    #[serde(default, rename = "sessionRetentionMaxSizeMb")]
+    pub(crate) session_retention_max_size_mb: Option<u64>,
+    #[serde(default, rename = "maxConcurrentSessions")]
+    pub(crate) max_concurrent_sessions: Option<u32>,
     #[serde(default = "default_access_mode", rename = "defaultAccessMode")]
     pub(crate) default_access_mode: String,
     #[serde(
@@ -481,6 +2491,17 @@ pub(crate) struct AppSettings {
         rename = "cycleWorkspacePrevShortcut"
     )]
     pub(crate) cycle_workspace_prev_shortcut: Option<String>,
+    /// System-wide hotkeys, registered by `shortcuts` even while the app is
+    /// in the background. All are unset by default.
+    #[serde(default, rename = "globalToggleWindowShortcut")]
+    pub(crate) global_toggle_window_shortcut: Option<String>,
+    #[serde(default, rename = "globalToggleDaemonShortcut")]
+    pub(crate) global_toggle_daemon_shortcut: Option<String>,
+    #[serde(default, rename = "globalStartSessionShortcut")]
+    pub(crate) global_start_session_shortcut: Option<String>,
+    /// The session template `global_start_session_shortcut` starts.
+    #[serde(default, rename = "globalSessionTemplateId")]
+    pub(crate) global_session_template_id: Option<String>,
     #[serde(default, rename = "lastComposerModelId")]
     pub(crate) last_composer_model_id: Option<String>,
     #[serde(default, rename = "lastComposerReasoningEffort")]
@@ -648,6 +2669,16 @@ pub(crate) struct AppSettings {
     pub(crate) open_app_targets: Vec<OpenAppTarget>,
     #[serde(default = "default_selected_open_app_id", rename = "selectedOpenAppId")]
     pub(crate) selected_open_app_id: String,
+    /// Editors `open_in_editor` and `open_diff_in_editor` can launch. Empty
+    /// means the built-in VS Code, Cursor and IntelliJ IDEA launchers.
+    #[serde(default, rename = "editorLaunchers")]
+    pub(crate) editor_launchers: Vec<EditorLauncher>,
+    /// Launcher id to use; otherwise the first one found on `PATH`.
+    #[serde(default, rename = "preferredEditor")]
+    pub(crate) preferred_editor: Option<String>,
+    /// Ids of the plugins in `plugins/` whose `ext.*` methods are served.
+    #[serde(default, rename = "enabledPlugins")]
+    pub(crate) enabled_plugins: Vec<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -684,7 +2715,7 @@ fn default_review_delivery_mode() -> String {
 }
 
 fn default_backend_mode() -> BackendMode {
-    if cfg!(target_os = "ios") {
+    if cfg!(any(target_os = "ios", target_os = "android")) {
         BackendMode::Remote
     } else {
         BackendMode::Local
@@ -699,6 +2730,42 @@ fn default_remote_backends() -> Vec<RemoteBackendTarget> {
     Vec::new()
 }
 
+fn default_daemon_http_bridge_port() -> u16 {
+    4733
+}
+
+fn default_remote_action_confirm_timeout_secs() -> u64 {
+    60
+}
+
+fn default_tailscale_status_ttl_secs() -> u32 {
+    30
+}
+
+fn default_remote_slow_call_ms() -> u64 {
+    2_000
+}
+
+fn default_low_disk_space_threshold_mb() -> u32 {
+    1024
+}
+
+fn default_event_coalescing_ms() -> BTreeMap<String, u32> {
+    [
+        "tcp-daemon-status-changed",
+        "tailscale-status-changed",
+        "dictation-download",
+        "codex-session-queue",
+    ]
+    .into_iter()
+    .map(|event| (event.to_string(), 250))
+    .collect()
+}
+
+fn default_remote_command_timeout_secs() -> u32 {
+    120
+}
+
 fn default_ui_scale() -> f64 {
     1.0
 }
@@ -707,6 +2774,14 @@ fn default_theme() -> String {
     "system".to_string()
 }
 
+fn default_schedule_enabled() -> bool {
+    true
+}
+
+fn default_webhook_enabled() -> bool {
+    true
+}
+
 fn default_usage_show_remaining() -> bool {
     false
 }
@@ -1125,13 +3200,47 @@ impl Default for AppSettings {
         Self {
             codex_bin: None,
             codex_args: None,
+            codex_env: ProcessEnvConfig::default(),
             backend_mode: default_backend_mode(),
             remote_backend_provider: RemoteBackendProvider::Tcp,
             remote_backend_host: default_remote_backend_host(),
             remote_backend_token: None,
+            remote_backend_identity: None,
             remote_backends: default_remote_backends(),
             active_remote_backend_id: None,
+            proxy: ProxySettings::default(),
+            rpc_timeouts: RpcTimeoutSettings::default(),
+            remote_slow_call_ms: default_remote_slow_call_ms(),
             keep_daemon_running_after_app_close: false,
+            daemon_env: ProcessEnvConfig::default(),
+            daemon_http_bridge_enabled: false,
+            daemon_http_bridge_port: default_daemon_http_bridge_port(),
+            require_client_approval: false,
+            confirm_remote_actions: false,
+            remote_action_confirm_timeout_secs: default_remote_action_confirm_timeout_secs(),
+            mock_backends: false,
+            daemon_auth: DaemonAuthSettings::default(),
+            tailscale_status_ttl_secs: default_tailscale_status_ttl_secs(),
+            data_dir: None,
+            daemon_data_dir: None,
+            codex_home_override: None,
+            low_disk_space_threshold_mb: default_low_disk_space_threshold_mb(),
+            event_coalescing_ms: default_event_coalescing_ms(),
+            settings_sync: SettingsSyncConfig::default(),
+            settings_updated_at_ms: 0,
+            webhooks: Vec::new(),
+            notification_integrations: Vec::new(),
+            hooks: Vec::new(),
+            do_not_disturb: DoNotDisturbSettings::default(),
+            focus_follows_activity: FocusFollowsActivitySettings::default(),
+            weekly_activity_report: false,
+            redaction: RedactionSettings::default(),
+            remote_command_allowlist: Vec::new(),
+            remote_command_denylist: Vec::new(),
+            remote_command_timeout_secs: default_remote_command_timeout_secs(),
+            session_retention_max_age_days: None,
+            session_retention_max_size_mb: None,
+            max_concurrent_sessions: None,
             default_access_mode: "current".to_string(),
             review_delivery_mode: default_review_delivery_mode(),
             composer_model_shortcut: default_composer_model_shortcut(),
@@ -1151,6 +3260,10 @@ impl Default for AppSettings {
             cycle_agent_prev_shortcut: default_cycle_agent_prev_shortcut(),
             cycle_workspace_next_shortcut: default_cycle_workspace_next_shortcut(),
             cycle_workspace_prev_shortcut: default_cycle_workspace_prev_shortcut(),
+            global_toggle_window_shortcut: None,
+            global_toggle_daemon_shortcut: None,
+            global_start_session_shortcut: None,
+            global_session_template_id: None,
             last_composer_model_id: None,
             last_composer_reasoning_effort: None,
             ui_scale: 1.0,
@@ -1199,6 +3312,9 @@ impl Default for AppSettings {
             global_worktrees_folder: None,
             open_app_targets: default_open_app_targets(),
             selected_open_app_id: default_selected_open_app_id(),
+            editor_launchers: Vec::new(),
+            preferred_editor: None,
+            enabled_plugins: Vec::new(),
         }
     }
 }
@@ -1206,15 +3322,15 @@ impl Default for AppSettings {
 #[cfg(test)]
 mod tests {
     use super::{
-        AppSettings, BackendMode, RemoteBackendProvider, WorkspaceEntry, WorkspaceGroup,
-        WorkspaceKind, WorkspaceSettings,
+        AppSettings, BackendMode, ProcessEnvConfig, ProxySettings, RemoteBackendProvider,
+        RpcTimeoutSettings, WorkspaceEntry, WorkspaceGroup, WorkspaceKind, WorkspaceSettings,
     };
 
     #[test]
     fn app_settings_defaults_from_empty_json() {
         let settings: AppSettings = serde_json::from_str("{}").expect("settings deserialize");
         assert!(settings.codex_bin.is_none());
-        let expected_backend_mode = if cfg!(target_os = "ios") {
+        let expected_backend_mode = if cfg!(any(target_os = "ios", target_os = "android")) {
             BackendMode::Remote
         } else {
             BackendMode::Local
@@ -1231,7 +3347,50 @@ mod tests {
         assert!(settings.remote_backend_token.is_none());
         assert!(settings.remote_backends.is_empty());
         assert!(settings.active_remote_backend_id.is_none());
+        assert_eq!(settings.proxy, ProxySettings::default());
+        assert_eq!(settings.rpc_timeouts, RpcTimeoutSettings::default());
+        assert_eq!(settings.remote_slow_call_ms, 2_000);
         assert!(!settings.keep_daemon_running_after_app_close);
+        assert!(!settings.daemon_http_bridge_enabled);
+        assert_eq!(settings.daemon_http_bridge_port, 4733);
+        assert!(!settings.require_client_approval);
+        assert!(!settings.confirm_remote_actions);
+        assert_eq!(settings.remote_action_confirm_timeout_secs, 60);
+        assert!(!settings.mock_backends);
+        assert_eq!(settings.tailscale_status_ttl_secs, 30);
+        assert!(settings.data_dir.is_none());
+        assert!(settings.codex_home_override.is_none());
+        assert_eq!(settings.low_disk_space_threshold_mb, 1024);
+        assert_eq!(
+            settings
+                .event_coalescing_ms
+                .get("tcp-daemon-status-changed"),
+            Some(&250)
+        );
+        assert!(settings.webhooks.is_empty());
+        assert!(settings.notification_integrations.is_empty());
+        assert!(settings.hooks.is_empty());
+        assert!(!settings.do_not_disturb.enabled);
+        assert_eq!(
+            settings.focus_follows_activity.approval_requested,
+            RaiseWindowMode::Off
+        );
+        assert!(!settings.weekly_activity_report);
+        assert!(settings.redaction.enabled);
+        assert!(settings.redaction.built_in_detectors);
+        assert!(!settings.settings_sync.enabled);
+        assert_eq!(settings.settings_updated_at_ms, 0);
+        assert!(settings.editor_launchers.is_empty());
+        assert!(settings.preferred_editor.is_none());
+        assert!(settings.enabled_plugins.is_empty());
+        assert_eq!(settings.codex_env, ProcessEnvConfig::default());
+        assert_eq!(settings.daemon_env, ProcessEnvConfig::default());
+        assert!(settings.remote_command_allowlist.is_empty());
+        assert!(settings.remote_command_denylist.is_empty());
+        assert_eq!(settings.remote_command_timeout_secs, 120);
+        assert!(settings.session_retention_max_age_days.is_none());
+        assert!(settings.session_retention_max_size_mb.is_none());
+        assert!(settings.max_concurrent_sessions.is_none());
         assert_eq!(settings.default_access_mode, "current");
         assert_eq!(settings.review_delivery_mode, "inline");
         let expected_primary = if cfg!(target_os = "macos") {
@@ -1317,6 +3476,8 @@ mod tests {
                 "ctrl+alt+shift+up"
             })
         );
+        assert!(settings.global_toggle_window_shortcut.is_none());
+        assert!(settings.global_start_session_shortcut.is_none());
         assert!(settings.last_composer_model_id.is_none());
         assert!(settings.last_composer_reasoning_effort.is_none());
         assert!((settings.ui_scale - 1.0).abs() < f64::EPSILON);
@@ -0,0 +1,318 @@
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use reqwest::header::CONTENT_TYPE;
+use serde_json::{json, Value};
+use tauri::{AppHandle, Manager, State};
+
+use crate::command_error::{CommandError, CommandResult};
+use crate::remote_backend;
+use crate::shared::codex_session_core::get_codex_session_core;
+use crate::shared::process_env_core::read_keychain_secret;
+use crate::shared::webhooks_core::{
+    event_name, integration_payload, is_retryable_status, list_webhook_deliveries_core,
+    record_delivery, retry_delay, sign_payload, subscribed_endpoints, subscribed_integrations,
+    webhook_payload, EVENT_HEADER, MAX_DELIVERY_ATTEMPTS, SIGNATURE_HEADER,
+};
+use crate::state::AppState;
+use crate::types::{
+    CodexSessionDetail, NotificationIntegration, Page, PageResult, WebhookDelivery, WebhookEvent,
+};
+
+const WEBHOOK_TIMEOUT: Duration = Duration::from_secs(10);
+
+fn now_ms() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_millis() as i64)
+        .unwrap_or(0)
+}
+
+fn http_client() -> Result<reqwest::Client, String> {
+    reqwest::Client::builder()
+        .timeout(WEBHOOK_TIMEOUT)
+        .build()
+        .map_err(|err| format!("Failed to configure webhook client: {err}"))
+}
+
+/// Where one event goes: a webhook endpoint or an integration's incoming
+/// webhook.
+struct DeliveryTarget {
+    id: String,
+    url: String,
+    body: String,
+    signature: Option<String>,
+}
+
+/// Posts once, returning the HTTP status, or the error when there was no
+/// response.
+async fn post(
+    client: &reqwest::Client,
+    target: &DeliveryTarget,
+    event: WebhookEvent,
+) -> Result<u16, String> {
+    let mut request = client
+        .post(&target.url)
+        .header(CONTENT_TYPE, "application/json")
+        .header(EVENT_HEADER, event_name(event))
+        .body(target.body.clone());
+    if let Some(signature) = &target.signature {
+        request = request.header(SIGNATURE_HEADER, signature);
+    }
+    request
+        .send()
+        .await
+        .map(|response| response.status().as_u16())
+        .map_err(|err| err.to_string())
+}
+
+fn status_error(status: u16) -> Option<String> {
+    (!(200..300).contains(&status)).then(|| format!("The endpoint answered with HTTP {status}."))
+}
+
+async fn deliver(
+    client: reqwest::Client,
+    data_dir: PathBuf,
+    target: DeliveryTarget,
+    event: WebhookEvent,
+    created_at_ms: i64,
+) {
+    let mut delivery = WebhookDelivery {
+        id: uuid::Uuid::new_v4().to_string(),
+        endpoint_id: target.id.clone(),
+        event,
+        attempts: 0,
+        status_code: None,
+        ok: false,
+        error: None,
+        created_at_ms,
+        finished_at_ms: 0,
+    };
+    while delivery.attempts < MAX_DELIVERY_ATTEMPTS {
+        delivery.attempts += 1;
+        if delivery.attempts > 1 {
+            tokio::time::sleep(retry_delay(delivery.attempts)).await;
+        }
+        match post(&client, &target, event).await {
+            Ok(status) => {
+                delivery.status_code = Some(status);
+                delivery.error = status_error(status);
+                delivery.ok = delivery.error.is_none();
+                if delivery.ok || !is_retryable_status(status) {
+                    break;
+                }
+            }
+            Err(err) => {
+                delivery.status_code = None;
+                delivery.error = Some(err);
+            }
+        }
+    }
+    delivery.finished_at_ms = now_ms();
+    log_delivery(data_dir, delivery).await;
+}
+
+async fn log_delivery(data_dir: PathBuf, delivery: WebhookDelivery) {
+    let recorded = tokio::task::spawn_blocking(move || record_delivery(&data_dir, &delivery)).await;
+    if let Ok(Err(err)) = recorded {
+        eprintln!("webhooks: {err}");
+    }
+}
+
+/// Reads the integration's incoming webhook URL from the keychain.
+async fn integration_url(integration: &NotificationIntegration) -> Result<String, String> {
+    let account = integration.keychain_account.trim().to_string();
+    if account.is_empty() {
+        return Err(format!(
+            "Integration `{}` has no keychain account.",
+            integration.id
+        ));
+    }
+    tokio::task::spawn_blocking(move || read_keychain_secret(&account))
+        .await
+        .map_err(|err| err.to_string())?
+        .map(|url| url.trim().to_string())
+}
+
+pub(crate) async fn session_detail(
+    state: &AppState,
+    app: &AppHandle,
+    session_id: &str,
+) -> Option<CodexSessionDetail> {
+    if remote_backend::is_remote_mode(state).await {
+        let response = remote_backend::call_remote(
+            state,
+            app.clone(),
+            "get_codex_session",
+            json!({ "sessionId": session_id }),
+        )
+        .await
+        .ok()?;
+        return serde_json::from_value(response).ok();
+    }
+    get_codex_session_core(&state.session_runtimes, session_id.to_string())
+        .await
+        .ok()
+}
+
+/// Adds the session's project, status and run time to a
+/// `session_completed` event, which only carries its id and exit code.
+async fn with_session_details(state: &AppState, app: &AppHandle, mut data: Value) -> Value {
+    let Some(session_id) = data.get("sessionId").and_then(Value::as_str) else {
+        return data;
+    };
+    let Some(detail) = session_detail(state, app, session_id).await else {
+        return data;
+    };
+    let info = detail.session;
+    if let Value::Object(map) = &mut data {
+        let name = Path::new(&info.project_path)
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or_else(|| info.project_path.clone());
+        map.insert("session".to_string(), json!(name));
+        map.insert("projectPath".to_string(), json!(info.project_path));
+        map.insert("status".to_string(), json!(info.status));
+        map.insert(
+            "durationMs".to_string(),
+            json!(now_ms() - info.started_at_ms),
+        );
+    }
+    data
+}
+
+/// Sends `event` to every webhook and Slack or Discord integration
+/// subscribed to it, in the background. Failed deliveries are retried with
+/// backoff and every outcome ends up in the delivery log.
+pub(crate) fn notify_event(app: &AppHandle, event: WebhookEvent, data: Value) {
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        let state = app.state::<AppState>();
+        let (endpoints, integrations) = {
+            let settings = state.app_settings.read().await;
+            (
+                subscribed_endpoints(&settings.webhooks, event),
+                subscribed_integrations(&settings.notification_integrations, event),
+            )
+        };
+        if endpoints.is_empty() && integrations.is_empty() {
+            return;
+        }
+        let client = match http_client() {
+            Ok(client) => client,
+            Err(err) => {
+                eprintln!("webhooks: {err}");
+                return;
+            }
+        };
+        let data = match event {
+            WebhookEvent::SessionCompleted => with_session_details(&state, &app, data).await,
+            _ => data,
+        };
+        let data_dir = state.data_dir().to_path_buf();
+        let created_at_ms = now_ms();
+
+        let body = webhook_payload(event, data.clone(), created_at_ms).to_string();
+        let mut targets: Vec<DeliveryTarget> = endpoints
+            .into_iter()
+            .map(|endpoint| DeliveryTarget {
+                signature: endpoint
+                    .secret
+                    .as_deref()
+                    .filter(|secret| !secret.is_empty())
+                    .map(|secret| sign_payload(secret, body.as_bytes())),
+                id: endpoint.id,
+                url: endpoint.url,
+                body: body.clone(),
+            })
+            .collect();
+        for integration in integrations {
+            match integration_url(&integration).await {
+                Ok(url) => targets.push(DeliveryTarget {
+                    body: integration_payload(&integration, event, &data).to_string(),
+                    id: integration.id,
+                    url,
+                    signature: None,
+                }),
+                Err(err) => {
+                    let now = now_ms();
+                    let delivery = WebhookDelivery {
+                        id: uuid::Uuid::new_v4().to_string(),
+                        endpoint_id: integration.id,
+                        event,
+                        attempts: 0,
+                        status_code: None,
+                        ok: false,
+                        error: Some(err),
+                        created_at_ms: now,
+                        finished_at_ms: now,
+                    };
+                    log_delivery(data_dir.clone(), delivery).await;
+                }
+            }
+        }
+        for target in targets {
+            tauri::async_runtime::spawn(deliver(
+                client.clone(),
+                data_dir.clone(),
+                target,
+                event,
+                created_at_ms,
+            ));
+        }
+    });
+}
+
+#[tauri::command]
+pub(crate) async fn list_webhook_deliveries(
+    endpoint_id: Option<String>,
+    page: Option<Page>,
+    state: State<'_, AppState>,
+) -> CommandResult<PageResult<WebhookDelivery>> {
+    let data_dir = state.data_dir().to_path_buf();
+    let page = page.unwrap_or_default();
+    tokio::task::spawn_blocking(move || {
+        list_webhook_deliveries_core(&data_dir, endpoint_id.as_deref(), &page)
+    })
+    .await
+    .map_err(|err| err.to_string())?
+    .map_err(CommandError::from)
+}
+
+/// Posts a sample `session_completed` message through the integration with
+/// id `integration`, once and without retries, so its keychain item and
+/// template can be checked.
+#[tauri::command]
+pub(crate) async fn send_test_message(
+    integration: String,
+    state: State<'_, AppState>,
+) -> CommandResult<()> {
+    let found = state
+        .app_settings
+        .read()
+        .await
+        .notification_integrations
+        .iter()
+        .find(|entry| entry.id == integration)
+        .cloned()
+        .ok_or_else(|| CommandError::from(format!("Integration `{integration}` is not saved.")))?;
+    let sample = json!({
+        "sessionId": "test",
+        "session": "CodexMonitor test",
+        "projectPath": "~/projects/example",
+        "status": "exited",
+        "exitCode": 0,
+        "durationMs": 125_000,
+    });
+    let target = DeliveryTarget {
+        id: found.id.clone(),
+        url: integration_url(&found).await?,
+        body: integration_payload(&found, WebhookEvent::SessionCompleted, &sample).to_string(),
+        signature: None,
+    };
+    let status = post(&http_client()?, &target, WebhookEvent::SessionCompleted).await?;
+    match status_error(status) {
+        Some(error) => Err(error.into()),
+        None => Ok(()),
+    }
+}
@@ -1,9 +1,37 @@
+use std::sync::Mutex;
+#[cfg(test)]
+use std::sync::OnceLock;
 #[cfg(desktop)]
-use tauri::Theme;
-use tauri::Window;
+use std::time::{SystemTime, UNIX_EPOCH};
 
-#[cfg(test)]
-use std::sync::{Mutex, OnceLock};
+use serde_json::Value;
+#[cfg(desktop)]
+use tauri::window::Monitor;
+use tauri::{AppHandle, Manager, State, Window};
+#[cfg(desktop)]
+use tauri::{
+    Emitter, PhysicalPosition, PhysicalSize, Theme, WebviewUrl, WebviewWindow, WebviewWindowBuilder,
+};
+
+use crate::command_error::CommandResult;
+use crate::state::AppState;
+use crate::types::{
+    ActivityAlert, ActivityTrigger, FocusFollowsActivitySettings, RaiseWindowMode, WebhookEvent,
+};
+
+#[cfg(desktop)]
+const MAIN_WINDOW: &str = "main";
+#[cfg(desktop)]
+const ACTIVITY_WINDOW: &str = "activity";
+#[cfg(desktop)]
+const ACTIVITY_WINDOW_WIDTH: f64 = 360.0;
+#[cfg(desktop)]
+const ACTIVITY_WINDOW_HEIGHT: f64 = 112.0;
+/// Gap between the alert window and the corner of its monitor.
+#[cfg(desktop)]
+const ACTIVITY_WINDOW_MARGIN: f64 = 16.0;
+#[cfg(desktop)]
+const ACTIVITY_ALERT_EVENT: &str = "activity-alert";
 
 #[cfg(test)]
 type WindowAppearanceOverride =
@@ -114,3 +142,344 @@ pub(crate) fn configure_ios_webview_edge_to_edge(
         })
         .map_err(|error| error.to_string())
 }
+
+/// The latest activity that raised the app, for the compact alert window.
+#[derive(Default)]
+pub(crate) struct ActivityAlertState {
+    latest: Mutex<Option<ActivityAlert>>,
+}
+
+/// The alert for an event webhooks report, when it is one that can raise
+/// the app. Sessions count as failed only when they exit with a non-zero
+/// code, so ones the user stopped do not.
+fn activity_alert(event: WebhookEvent, data: &Value, raised_at_ms: i64) -> Option<ActivityAlert> {
+    let text = |key: &str| data.get(key).and_then(Value::as_str).map(str::to_string);
+    match event {
+        WebhookEvent::ApprovalNeeded => Some(ActivityAlert {
+            trigger: ActivityTrigger::ApprovalRequested,
+            workspace_id: text("workspaceId"),
+            thread_id: text("threadId"),
+            session_id: None,
+            detail: text("method"),
+            raised_at_ms,
+        }),
+        WebhookEvent::SessionCompleted => {
+            let code = data
+                .get("exitCode")
+                .and_then(Value::as_i64)
+                .filter(|code| *code != 0)?;
+            Some(ActivityAlert {
+                trigger: ActivityTrigger::SessionFailed,
+                workspace_id: None,
+                thread_id: None,
+                session_id: text("sessionId"),
+                detail: Some(format!("Exited with code {code}")),
+                raised_at_ms,
+            })
+        }
+        WebhookEvent::DaemonDown | WebhookEvent::CiFailed => None,
+    }
+}
+
+fn raise_mode(
+    settings: &FocusFollowsActivitySettings,
+    trigger: ActivityTrigger,
+) -> RaiseWindowMode {
+    match trigger {
+        ActivityTrigger::ApprovalRequested => settings.approval_requested,
+        ActivityTrigger::SessionFailed => settings.session_failed,
+    }
+}
+
+#[cfg(desktop)]
+fn now_ms() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_millis() as i64)
+        .unwrap_or(0)
+}
+
+/// Brings the app forward for `event`, as `focus_follows_activity` says,
+/// while none of its windows has focus. Called wherever webhooks are
+/// notified, so local and remote activity both count.
+pub(crate) fn raise_for_activity(app: &AppHandle, event: WebhookEvent, data: &Value) {
+    #[cfg(desktop)]
+    let raised_at_ms = now_ms();
+    #[cfg(not(desktop))]
+    let raised_at_ms = 0;
+    let Some(alert) = activity_alert(event, data, raised_at_ms) else {
+        return;
+    };
+    // A settings write in progress only costs this one alert.
+    let Some(mode) = app
+        .state::<AppState>()
+        .app_settings
+        .try_read()
+        .ok()
+        .map(|settings| raise_mode(&settings.focus_follows_activity, alert.trigger))
+    else {
+        return;
+    };
+    if mode == RaiseWindowMode::Off {
+        return;
+    }
+    #[cfg(desktop)]
+    {
+        let app_has_focus = app.webview_windows().values().any(|window| {
+            window.label() != ACTIVITY_WINDOW && window.is_focused().unwrap_or(false)
+        });
+        if app_has_focus {
+            return;
+        }
+        if let Some(state) = app.try_state::<ActivityAlertState>() {
+            if let Ok(mut latest) = state.latest.lock() {
+                *latest = Some(alert.clone());
+            }
+        }
+        match mode {
+            RaiseWindowMode::Window => raise_main_window(app),
+            RaiseWindowMode::Compact => show_activity_window(app, &alert),
+            RaiseWindowMode::Off => {}
+        }
+    }
+}
+
+/// A rectangle in physical pixels.
+#[cfg(desktop)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Rect {
+    x: i32,
+    y: i32,
+    width: u32,
+    height: u32,
+}
+
+/// Where a `width` by `height` window goes to sit centered on `monitor`,
+/// shrunk to fit.
+#[cfg(desktop)]
+fn centered_on(monitor: Rect, width: u32, height: u32) -> Rect {
+    let width = width.min(monitor.width);
+    let height = height.min(monitor.height);
+    Rect {
+        x: monitor.x + ((monitor.width - width) / 2) as i32,
+        y: monitor.y + ((monitor.height - height) / 2) as i32,
+        width,
+        height,
+    }
+}
+
+/// Where a window `width` wide goes to sit `margin` in from the top-right
+/// corner of `monitor`.
+#[cfg(desktop)]
+fn top_right_of(monitor: Rect, width: u32, margin: u32) -> (i32, i32) {
+    let right = monitor.x.saturating_add(monitor.width as i32);
+    (
+        (right - width as i32 - margin as i32).max(monitor.x),
+        monitor.y + margin as i32,
+    )
+}
+
+#[cfg(desktop)]
+fn monitor_rect(monitor: &Monitor) -> Rect {
+    Rect {
+        x: monitor.position().x,
+        y: monitor.position().y,
+        width: monitor.size().width,
+        height: monitor.size().height,
+    }
+}
+
+/// The monitor under the pointer, which is taken to be the one in use.
+#[cfg(desktop)]
+fn active_monitor(app: &AppHandle) -> Option<Monitor> {
+    let cursor = app.cursor_position().ok()?;
+    app.monitor_from_point(cursor.x, cursor.y).ok().flatten()
+}
+
+/// Shows and focuses the main window, moving it onto the monitor in use
+/// and, on macOS, onto the current space.
+#[cfg(desktop)]
+fn raise_main_window(app: &AppHandle) {
+    let Some(window) = app.get_webview_window(MAIN_WINDOW) else {
+        return;
+    };
+    move_to_active_monitor(app, &window);
+    // Showing a window visible on every space brings it to the current one;
+    // it then stays there once that is turned back off.
+    #[cfg(target_os = "macos")]
+    let _ = window.set_visible_on_all_workspaces(true);
+    let _ = window.show();
+    let _ = window.unminimize();
+    let _ = window.set_focus();
+    #[cfg(target_os = "macos")]
+    let _ = window.set_visible_on_all_workspaces(false);
+}
+
+#[cfg(desktop)]
+fn move_to_active_monitor(app: &AppHandle, window: &WebviewWindow) {
+    let Some(active) = active_monitor(app) else {
+        return;
+    };
+    let current = window.current_monitor().ok().flatten();
+    if current.is_some_and(|current| monitor_rect(&current) == monitor_rect(&active)) {
+        return;
+    }
+    if window.is_maximized().unwrap_or(false) {
+        let _ = window.unmaximize();
+    }
+    let Ok(size) = window.outer_size() else {
+        return;
+    };
+    let target = centered_on(monitor_rect(&active), size.width, size.height);
+    let _ = window.set_size(PhysicalSize::new(target.width, target.height));
+    let _ = window.set_position(PhysicalPosition::new(target.x, target.y));
+}
+
+/// Shows `alert` in the compact window, opening it in the top-right corner
+/// of the monitor in use without taking focus.
+#[cfg(desktop)]
+fn show_activity_window(app: &AppHandle, alert: &ActivityAlert) {
+    let window = match app.get_webview_window(ACTIVITY_WINDOW) {
+        Some(window) => window,
+        None => match WebviewWindowBuilder::new(
+            app,
+            ACTIVITY_WINDOW,
+            WebviewUrl::App("index.html".into()),
+        )
+        .title("Codex Monitor")
+        .inner_size(ACTIVITY_WINDOW_WIDTH, ACTIVITY_WINDOW_HEIGHT)
+        .resizable(false)
+        .decorations(false)
+        .always_on_top(true)
+        .skip_taskbar(true)
+        .visible_on_all_workspaces(true)
+        .focused(false)
+        .build()
+        {
+            Ok(window) => window,
+            Err(err) => {
+                eprintln!("activity alert: failed to open the window: {err}");
+                return;
+            }
+        },
+    };
+    let monitor = active_monitor(app).or_else(|| window.primary_monitor().ok().flatten());
+    if let Some(monitor) = monitor {
+        let scale = monitor.scale_factor();
+        let (x, y) = top_right_of(
+            monitor_rect(&monitor),
+            (ACTIVITY_WINDOW_WIDTH * scale) as u32,
+            (ACTIVITY_WINDOW_MARGIN * scale) as u32,
+        );
+        let _ = window.set_position(PhysicalPosition::new(x, y));
+    }
+    let _ = window.show();
+    let _ = app.emit_to(ACTIVITY_WINDOW, ACTIVITY_ALERT_EVENT, alert);
+}
+
+/// The activity the compact alert window shows.
+#[tauri::command]
+pub(crate) fn get_activity_alert(
+    state: State<'_, ActivityAlertState>,
+) -> CommandResult<Option<ActivityAlert>> {
+    Ok(state.latest.lock().ok().and_then(|latest| latest.clone()))
+}
+
+/// Closes the compact alert window, bringing the main window forward when
+/// `open` is set.
+#[tauri::command]
+pub(crate) fn dismiss_activity_alert(
+    open: Option<bool>,
+    app: AppHandle,
+    state: State<'_, ActivityAlertState>,
+) -> CommandResult<()> {
+    if let Ok(mut latest) = state.latest.lock() {
+        *latest = None;
+    }
+    #[cfg(desktop)]
+    {
+        if let Some(window) = app.get_webview_window(ACTIVITY_WINDOW) {
+            let _ = window.close();
+        }
+        if open.unwrap_or(false) {
+            raise_main_window(&app);
+        }
+    }
+    #[cfg(not(desktop))]
+    let _ = (open, app);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn only_approvals_and_failed_sessions_raise_the_app() {
+        let approval = activity_alert(
+            WebhookEvent::ApprovalNeeded,
+            &json!({
+                "workspaceId": "ws-1",
+                "method": "item/commandExecution/requestApproval",
+                "threadId": "thread-1",
+            }),
+            5,
+        )
+        .expect("approval alert");
+        assert_eq!(approval.trigger, ActivityTrigger::ApprovalRequested);
+        assert_eq!(approval.thread_id.as_deref(), Some("thread-1"));
+        assert_eq!(approval.raised_at_ms, 5);
+
+        let failed = activity_alert(
+            WebhookEvent::SessionCompleted,
+            &json!({ "sessionId": "session-1", "exitCode": 2 }),
+            5,
+        )
+        .expect("failure alert");
+        assert_eq!(failed.trigger, ActivityTrigger::SessionFailed);
+        assert_eq!(failed.detail.as_deref(), Some("Exited with code 2"));
+
+        for data in [
+            json!({ "sessionId": "session-1", "exitCode": 0 }),
+            json!({ "sessionId": "session-1", "exitCode": null }),
+        ] {
+            assert!(activity_alert(WebhookEvent::SessionCompleted, &data, 5).is_none());
+        }
+        assert!(activity_alert(WebhookEvent::DaemonDown, &json!({}), 5).is_none());
+
+        let settings = FocusFollowsActivitySettings {
+            approval_requested: RaiseWindowMode::Compact,
+            session_failed: RaiseWindowMode::Off,
+        };
+        assert_eq!(
+            raise_mode(&settings, ActivityTrigger::ApprovalRequested),
+            RaiseWindowMode::Compact
+        );
+        assert_eq!(
+            raise_mode(&settings, ActivityTrigger::SessionFailed),
+            RaiseWindowMode::Off
+        );
+    }
+
+    #[test]
+    fn windows_land_inside_the_monitor_in_use() {
+        let monitor = Rect {
+            x: 1920,
+            y: 0,
+            width: 1280,
+            height: 800,
+        };
+        assert_eq!(
+            centered_on(monitor, 1600, 600),
+            Rect {
+                x: 1920,
+                y: 100,
+                width: 1280,
+                height: 600,
+            }
+        );
+        assert_eq!(top_right_of(monitor, 360, 16), (2824, 16));
+        assert_eq!(top_right_of(monitor, 2000, 16), (1920, 16));
+    }
+}
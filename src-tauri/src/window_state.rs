@@ -0,0 +1,435 @@
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+#[cfg(desktop)]
+use std::time::{SystemTime, UNIX_EPOCH};
+
+#[cfg(desktop)]
+use tauri::window::Monitor;
+use tauri::{AppHandle, State};
+#[cfg(desktop)]
+use tauri::{
+    LogicalSize, Manager, PhysicalPosition, PhysicalSize, Runtime, WebviewWindow, Window,
+    WindowEvent,
+};
+
+use crate::command_error::{CommandError, CommandResult};
+use crate::types::WindowState;
+
+const WINDOW_STATE_FILE: &str = "window-state.json";
+const MAIN_WINDOW: &str = "main";
+/// Height of the strip along a window's top edge that must stay on a
+/// monitor, so the title bar can still be grabbed.
+#[cfg(desktop)]
+const TITLE_BAR_PX: i64 = 32;
+/// How much of that strip's width must be on the monitor.
+#[cfg(desktop)]
+const MIN_VISIBLE_TITLE_PX: i64 = 100;
+
+/// Saved window sizes and places by label. Kept in memory as windows move
+/// and written to the data dir when a window closes or the app quits.
+pub(crate) struct WindowStateStore {
+    path: PathBuf,
+    states: Mutex<HashMap<String, WindowState>>,
+}
+
+impl WindowStateStore {
+    pub(crate) fn load(data_dir: &Path) -> Self {
+        let path = data_dir.join(WINDOW_STATE_FILE);
+        let states = read_window_states(&path)
+            .unwrap_or_default()
+            .into_iter()
+            .map(|state| (state.label.clone(), state))
+            .collect();
+        Self {
+            path,
+            states: Mutex::new(states),
+        }
+    }
+
+    fn get(&self, label: &str) -> Option<WindowState> {
+        self.states.lock().ok()?.get(label).cloned()
+    }
+
+    #[cfg(desktop)]
+    fn update(&self, state: WindowState) {
+        if let Ok(mut states) = self.states.lock() {
+            states.insert(state.label.clone(), state);
+        }
+    }
+
+    fn remove(&self, label: &str) {
+        if let Ok(mut states) = self.states.lock() {
+            states.remove(label);
+        }
+    }
+
+    fn persist(&self) -> Result<(), String> {
+        let mut states: Vec<WindowState> = self
+            .states
+            .lock()
+            .map_err(|_| "failed to lock window state".to_string())?
+            .values()
+            .cloned()
+            .collect();
+        states.sort_by(|a, b| a.label.cmp(&b.label));
+        write_window_states(&self.path, &states)
+    }
+}
+
+fn read_window_states(path: &PathBuf) -> Result<Vec<WindowState>, String> {
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+    let data = std::fs::read_to_string(path).map_err(|e| e.to_string())?;
+    serde_json::from_str(&data).map_err(|e| e.to_string())
+}
+
+fn write_window_states(path: &PathBuf, states: &[WindowState]) -> Result<(), String> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+    }
+    let data = serde_json::to_string_pretty(states).map_err(|e| e.to_string())?;
+    std::fs::write(path, data).map_err(|e| e.to_string())
+}
+
+#[cfg(desktop)]
+fn now_ms() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_millis() as i64)
+        .unwrap_or(0)
+}
+
+/// A monitor's bounds in physical pixels.
+#[cfg(desktop)]
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct MonitorArea {
+    name: Option<String>,
+    x: i32,
+    y: i32,
+    width: u32,
+    height: u32,
+}
+
+#[cfg(desktop)]
+impl From<&Monitor> for MonitorArea {
+    fn from(monitor: &Monitor) -> Self {
+        Self {
+            name: monitor.name().cloned(),
+            x: monitor.position().x,
+            y: monitor.position().y,
+            width: monitor.size().width,
+            height: monitor.size().height,
+        }
+    }
+}
+
+#[cfg(desktop)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Placement {
+    x: i32,
+    y: i32,
+    width: u32,
+    height: u32,
+}
+
+#[cfg(desktop)]
+fn overlap(a_start: i64, a_len: i64, b_start: i64, b_len: i64) -> i64 {
+    ((a_start + a_len).min(b_start + b_len) - a_start.max(b_start)).max(0)
+}
+
+/// Where to put a window saved as `saved`, given the connected monitors
+/// with the primary first. The saved place is kept while enough of the
+/// title bar lands on a monitor. Otherwise, as after unplugging the
+/// monitor it was on, the window is centered on the monitor it was saved
+/// on if that is still connected, or else on the primary. Either way it is
+/// shrunk to fit.
+#[cfg(desktop)]
+fn placement(saved: &WindowState, monitors: &[MonitorArea]) -> Option<Placement> {
+    let title_on = |monitor: &MonitorArea| {
+        let across = overlap(
+            saved.x.into(),
+            saved.width.into(),
+            monitor.x.into(),
+            monitor.width.into(),
+        );
+        let down = overlap(
+            saved.y.into(),
+            TITLE_BAR_PX,
+            monitor.y.into(),
+            monitor.height.into(),
+        );
+        (across >= MIN_VISIBLE_TITLE_PX && down >= TITLE_BAR_PX / 2).then_some(across * down)
+    };
+    let visible_on = monitors
+        .iter()
+        .filter_map(|monitor| title_on(monitor).map(|area| (monitor, area)))
+        .max_by_key(|(_, area)| *area)
+        .map(|(monitor, _)| monitor);
+    if let Some(monitor) = visible_on {
+        return Some(Placement {
+            x: saved.x,
+            y: saved.y,
+            width: saved.width.min(monitor.width),
+            height: saved.height.min(monitor.height),
+        });
+    }
+
+    let monitor = monitors
+        .iter()
+        .find(|monitor| monitor.name.is_some() && monitor.name == saved.monitor)
+        .or_else(|| monitors.first())?;
+    let width = saved.width.min(monitor.width);
+    let height = saved.height.min(monitor.height);
+    Some(Placement {
+        x: monitor.x + ((monitor.width - width) / 2) as i32,
+        y: monitor.y + ((monitor.height - height) / 2) as i32,
+        width,
+        height,
+    })
+}
+
+/// The window's current size and place. While it is maximized the size and
+/// place it will return to are kept from `previous`.
+#[cfg(desktop)]
+fn capture<R: Runtime>(
+    window: &WebviewWindow<R>,
+    previous: Option<&WindowState>,
+) -> Option<WindowState> {
+    // Minimized windows report placeholder positions on some platforms.
+    if window.is_minimized().unwrap_or(false) {
+        return None;
+    }
+    let maximized = window.is_maximized().unwrap_or(false);
+    let (x, y, width, height) = match previous.filter(|_| maximized) {
+        Some(previous) => (previous.x, previous.y, previous.width, previous.height),
+        None => {
+            let position = window.outer_position().ok()?;
+            let size = window.inner_size().ok()?;
+            (position.x, position.y, size.width, size.height)
+        }
+    };
+    let monitor = window
+        .current_monitor()
+        .ok()
+        .flatten()
+        .and_then(|monitor| monitor.name().cloned());
+    Some(WindowState {
+        label: window.label().to_string(),
+        x,
+        y,
+        width,
+        height,
+        maximized,
+        monitor,
+        saved_at_ms: now_ms(),
+    })
+}
+
+#[cfg(desktop)]
+fn track<R: Runtime>(store: &WindowStateStore, window: &WebviewWindow<R>) {
+    let previous = store.get(window.label());
+    if let Some(state) = capture(window, previous.as_ref()) {
+        store.update(state);
+    }
+}
+
+/// Follows moves and resizes, and saves when a window closes.
+#[cfg(desktop)]
+pub(crate) fn handle_window_event<R: Runtime>(window: &Window<R>, event: &WindowEvent) {
+    if !matches!(
+        event,
+        WindowEvent::Moved(_) | WindowEvent::Resized(_) | WindowEvent::CloseRequested { .. }
+    ) {
+        return;
+    }
+    let Some(store) = window.try_state::<WindowStateStore>() else {
+        return;
+    };
+    let Some(webview_window) = window.get_webview_window(window.label()) else {
+        return;
+    };
+    track(&store, &webview_window);
+    if matches!(event, WindowEvent::CloseRequested { .. }) {
+        if let Err(err) = store.persist() {
+            eprintln!("window state: failed to save: {err}");
+        }
+    }
+}
+
+/// Puts `window` back where it was last saved, if it was.
+#[cfg(desktop)]
+pub(crate) fn restore<R: Runtime>(window: &WebviewWindow<R>) {
+    let Some(store) = window.try_state::<WindowStateStore>() else {
+        return;
+    };
+    let Some(saved) = store.get(window.label()) else {
+        return;
+    };
+    let primary = window
+        .primary_monitor()
+        .ok()
+        .flatten()
+        .map(|monitor| MonitorArea::from(&monitor));
+    let mut monitors: Vec<MonitorArea> = window
+        .available_monitors()
+        .unwrap_or_default()
+        .iter()
+        .map(MonitorArea::from)
+        .collect();
+    if let Some(primary) = primary {
+        monitors.retain(|monitor| *monitor != primary);
+        monitors.insert(0, primary);
+    }
+    let Some(placement) = placement(&saved, &monitors) else {
+        return;
+    };
+    let _ = window.set_size(PhysicalSize::new(placement.width, placement.height));
+    let _ = window.set_position(PhysicalPosition::new(placement.x, placement.y));
+    if saved.maximized {
+        let _ = window.maximize();
+    }
+}
+
+/// Records every open window and writes the result, for when the app quits.
+#[cfg(desktop)]
+pub(crate) fn save_all<R: Runtime>(app: &AppHandle<R>) {
+    let Some(store) = app.try_state::<WindowStateStore>() else {
+        return;
+    };
+    for window in app.webview_windows().values() {
+        track(&store, window);
+    }
+    if let Err(err) = store.persist() {
+        eprintln!("window state: failed to save: {err}");
+    }
+}
+
+/// The size and place saved for window `label` (`main` when omitted),
+/// brought up to date first when the window is open.
+#[tauri::command]
+pub(crate) fn get_window_state(
+    label: Option<String>,
+    app: AppHandle,
+    store: State<'_, WindowStateStore>,
+) -> CommandResult<Option<WindowState>> {
+    let label = label.unwrap_or_else(|| MAIN_WINDOW.to_string());
+    #[cfg(desktop)]
+    if let Some(window) = app.get_webview_window(&label) {
+        track(&store, &window);
+    }
+    Ok(store.get(&label))
+}
+
+/// Forgets what was saved for window `label` (`main` when omitted) and puts
+/// the window back to its configured size, centered on its monitor.
+#[tauri::command]
+pub(crate) fn reset_window_state(
+    label: Option<String>,
+    app: AppHandle,
+    store: State<'_, WindowStateStore>,
+) -> CommandResult<()> {
+    let label = label.unwrap_or_else(|| MAIN_WINDOW.to_string());
+    store.remove(&label);
+    store.persist().map_err(CommandError::from)?;
+    #[cfg(desktop)]
+    if let Some(window) = app.get_webview_window(&label) {
+        let _ = window.unmaximize();
+        if let Some(config) = app
+            .config()
+            .app
+            .windows
+            .iter()
+            .find(|config| config.label == label)
+        {
+            let _ = window.set_size(LogicalSize::new(config.width, config.height));
+        }
+        let _ = window.center();
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn monitor(name: &str, x: i32, width: u32) -> MonitorArea {
+        MonitorArea {
+            name: Some(name.to_string()),
+            x,
+            y: 0,
+            width,
+            height: 1080,
+        }
+    }
+
+    fn saved(x: i32, y: i32, width: u32, height: u32) -> WindowState {
+        WindowState {
+            label: "main".to_string(),
+            x,
+            y,
+            width,
+            height,
+            maximized: false,
+            monitor: Some("Studio Display".to_string()),
+            saved_at_ms: 0,
+        }
+    }
+
+    #[test]
+    fn saved_place_is_kept_while_the_title_bar_is_on_a_monitor() {
+        let monitors = [
+            monitor("Built-in", 0, 1920),
+            monitor("Studio Display", 1920, 2560),
+        ];
+        assert_eq!(
+            placement(&saved(2000, 100, 1200, 700), &monitors),
+            Some(Placement {
+                x: 2000,
+                y: 100,
+                width: 1200,
+                height: 700,
+            })
+        );
+        // Hanging mostly off the right edge, but the title bar is reachable.
+        assert_eq!(
+            placement(&saved(1700, 40, 1200, 700), &monitors[..1]).map(|p| (p.x, p.y)),
+            Some((1700, 40))
+        );
+    }
+
+    #[test]
+    fn windows_on_a_disconnected_monitor_are_centered_on_the_primary() {
+        let monitors = [monitor("Built-in", 0, 1920)];
+        assert_eq!(
+            placement(&saved(2000, 100, 2400, 1300), &monitors),
+            Some(Placement {
+                x: 0,
+                y: 0,
+                width: 1920,
+                height: 1080,
+            })
+        );
+        assert_eq!(
+            placement(&saved(2000, 100, 1200, 700), &monitors),
+            Some(Placement {
+                x: 360,
+                y: 190,
+                width: 1200,
+                height: 700,
+            })
+        );
+        assert_eq!(placement(&saved(0, 0, 1200, 700), &[]), None);
+    }
+
+    #[test]
+    fn off_screen_windows_go_back_to_their_monitor_when_it_is_connected() {
+        let monitors = [
+            monitor("Built-in", 0, 1920),
+            monitor("Studio Display", 1920, 2560),
+        ];
+        let placed = placement(&saved(9000, -500, 1200, 700), &monitors).expect("placement");
+        assert_eq!((placed.x, placed.y), (1920 + 680, 190));
+    }
+}
@@ -18,7 +18,9 @@ use super::worktree::{
 
 use crate::backend::app_server::WorkspaceSession;
 use crate::codex::spawn_workspace_session;
+use crate::command_error::{CommandError, CommandResult};
 use crate::git_utils::resolve_git_root;
+use crate::platform;
 use crate::remote_backend;
 use crate::shared::{workspace_rpc, workspaces_core};
 use crate::state::AppState;
@@ -48,7 +50,7 @@ pub(crate) async fn read_workspace_file(
     path: String,
     state: State<'_, AppState>,
     app: AppHandle,
-) -> Result<WorkspaceFileResponse, String> {
+) -> CommandResult<WorkspaceFileResponse> {
     if remote_backend::is_remote_mode(&*state).await {
         let request = workspace_rpc::ReadWorkspaceFileRequest { workspace_id, path };
         let response = remote_backend::call_remote(
@@ -58,7 +60,7 @@ pub(crate) async fn read_workspace_file(
             workspace_remote_params(&request)?,
         )
         .await?;
-        return serde_json::from_value(response).map_err(|err| err.to_string());
+        return serde_json::from_value(response).map_err(|err| CommandError::from(err.to_string()));
     }
 
     workspaces_core::read_workspace_file_core(
@@ -68,13 +70,14 @@ pub(crate) async fn read_workspace_file(
         |root, rel_path| read_workspace_file_inner(root, rel_path),
     )
     .await
+    .map_err(CommandError::from)
 }
 
 #[tauri::command]
 pub(crate) async fn list_workspaces(
     state: State<'_, AppState>,
     app: AppHandle,
-) -> Result<Vec<WorkspaceInfo>, String> {
+) -> CommandResult<Vec<WorkspaceInfo>> {
     if remote_backend::is_remote_mode(&*state).await {
         let response = remote_backend::call_remote(
             &*state,
@@ -83,7 +86,7 @@ pub(crate) async fn list_workspaces(
             workspace_remote_empty_params(),
         )
         .await?;
-        return serde_json::from_value(response).map_err(|err| err.to_string());
+        return serde_json::from_value(response).map_err(|err| CommandError::from(err.to_string()));
     }
 
     Ok(workspaces_core::list_workspaces_core(&state.workspaces, &state.sessions).await)
@@ -95,7 +98,7 @@ pub(crate) async fn set_workspace_runtime_codex_args(
     codex_args: Option<String>,
     state: State<'_, AppState>,
     app: AppHandle,
-) -> Result<workspaces_core::WorkspaceRuntimeCodexArgsResult, String> {
+) -> CommandResult<workspaces_core::WorkspaceRuntimeCodexArgsResult> {
     if remote_backend::is_remote_mode(&*state).await {
         let request = workspace_rpc::SetWorkspaceRuntimeCodexArgsRequest {
             workspace_id,
@@ -108,7 +111,7 @@ pub(crate) async fn set_workspace_runtime_codex_args(
             workspace_remote_params(&request)?,
         )
         .await?;
-        return serde_json::from_value(response).map_err(|err| err.to_string());
+        return serde_json::from_value(response).map_err(|err| CommandError::from(err.to_string()));
     }
 
     workspaces_core::set_workspace_runtime_codex_args_core(
@@ -120,6 +123,7 @@ pub(crate) async fn set_workspace_runtime_codex_args(
         |entry, default_bin, args, home| spawn_with_app(&app, entry, default_bin, args, home),
     )
     .await
+    .map_err(CommandError::from)
 }
 
 #[tauri::command]
@@ -127,7 +131,7 @@ pub(crate) async fn is_workspace_path_dir(
     path: String,
     state: State<'_, AppState>,
     app: AppHandle,
-) -> Result<bool, String> {
+) -> CommandResult<bool> {
     if remote_backend::is_remote_mode(&*state).await {
         let request = workspace_rpc::IsWorkspacePathDirRequest { path };
         let response = remote_backend::call_remote(
@@ -137,7 +141,7 @@ pub(crate) async fn is_workspace_path_dir(
             workspace_remote_params(&request)?,
         )
         .await?;
-        return serde_json::from_value(response).map_err(|err| err.to_string());
+        return serde_json::from_value(response).map_err(|err| CommandError::from(err.to_string()));
     }
     Ok(workspaces_core::is_workspace_path_dir_core(&path))
 }
@@ -147,7 +151,7 @@ pub(crate) async fn add_workspace(
     path: String,
     state: State<'_, AppState>,
     app: AppHandle,
-) -> Result<WorkspaceInfo, String> {
+) -> CommandResult<WorkspaceInfo> {
     if remote_backend::is_remote_mode(&*state).await {
         let path = remote_backend::normalize_path_for_remote(path);
         let request = workspace_rpc::AddWorkspaceRequest { path };
@@ -158,7 +162,7 @@ pub(crate) async fn add_workspace(
             workspace_remote_params(&request)?,
         )
         .await?;
-        return serde_json::from_value(response).map_err(|err| err.to_string());
+        return serde_json::from_value(response).map_err(|err| CommandError::from(err.to_string()));
     }
 
     workspaces_core::add_workspace_core(
@@ -172,6 +176,7 @@ pub(crate) async fn add_workspace(
         },
     )
     .await
+    .map_err(CommandError::from)
 }
 
 #[tauri::command]
@@ -181,7 +186,7 @@ pub(crate) async fn add_workspace_from_git_url(
     target_folder_name: Option<String>,
     state: State<'_, AppState>,
     app: AppHandle,
-) -> Result<WorkspaceInfo, String> {
+) -> CommandResult<WorkspaceInfo> {
     if remote_backend::is_remote_mode(&*state).await {
         let destination_path = remote_backend::normalize_path_for_remote(destination_path);
         let request = workspace_rpc::AddWorkspaceFromGitUrlRequest {
@@ -196,7 +201,7 @@ pub(crate) async fn add_workspace_from_git_url(
             workspace_remote_params(&request)?,
         )
         .await?;
-        return serde_json::from_value(response).map_err(|err| err.to_string());
+        return serde_json::from_value(response).map_err(|err| CommandError::from(err.to_string()));
     }
 
     workspaces_core::add_workspace_from_git_url_core(
@@ -212,6 +217,7 @@ pub(crate) async fn add_workspace_from_git_url(
         },
     )
     .await
+    .map_err(CommandError::from)
 }
 
 #[tauri::command]
@@ -221,7 +227,23 @@ pub(crate) async fn add_clone(
     copies_folder: String,
     state: State<'_, AppState>,
     app: AppHandle,
-) -> Result<WorkspaceInfo, String> {
+) -> CommandResult<WorkspaceInfo> {
+    if remote_backend::is_remote_mode(&*state).await {
+        let request = workspace_rpc::AddCloneRequest {
+            source_workspace_id,
+            copy_name,
+            copies_folder,
+        };
+        let response = remote_backend::call_remote(
+            &*state,
+            app,
+            "add_clone",
+            workspace_remote_params(&request)?,
+        )
+        .await?;
+        return serde_json::from_value(response).map_err(|err| CommandError::from(err.to_string()));
+    }
+
     workspaces_core::add_clone_core(
         source_workspace_id,
         copy_name,
@@ -235,6 +257,7 @@ pub(crate) async fn add_clone(
         },
     )
     .await
+    .map_err(CommandError::from)
 }
 
 #[tauri::command]
@@ -245,7 +268,7 @@ pub(crate) async fn add_worktree(
     copy_agents_md: Option<bool>,
     state: State<'_, AppState>,
     app: AppHandle,
-) -> Result<WorkspaceInfo, String> {
+) -> CommandResult<WorkspaceInfo> {
     let copy_agents_md = copy_agents_md.unwrap_or(true);
     if remote_backend::is_remote_mode(&*state).await {
         let request = workspace_rpc::AddWorktreeRequest {
@@ -261,7 +284,7 @@ pub(crate) async fn add_worktree(
             workspace_remote_params(&request)?,
         )
         .await?;
-        return serde_json::from_value(response).map_err(|err| err.to_string());
+        return serde_json::from_value(response).map_err(|err| CommandError::from(err.to_string()));
     }
 
     let data_dir = app
@@ -297,6 +320,7 @@ pub(crate) async fn add_worktree(
         },
     )
     .await
+    .map_err(CommandError::from)
 }
 
 #[tauri::command]
@@ -304,7 +328,7 @@ pub(crate) async fn worktree_setup_status(
     workspace_id: String,
     state: State<'_, AppState>,
     app: AppHandle,
-) -> Result<WorktreeSetupStatus, String> {
+) -> CommandResult<WorktreeSetupStatus> {
     if remote_backend::is_remote_mode(&*state).await {
         let request = workspace_rpc::WorkspaceIdRequest { workspace_id };
         let response = remote_backend::call_remote(
@@ -314,14 +338,16 @@ pub(crate) async fn worktree_setup_status(
             workspace_remote_params(&request)?,
         )
         .await?;
-        return serde_json::from_value(response).map_err(|err| err.to_string());
+        return serde_json::from_value(response).map_err(|err| CommandError::from(err.to_string()));
     }
 
     let data_dir = app
         .path()
         .app_data_dir()
         .map_err(|err| format!("Failed to resolve app data dir: {err}"))?;
-    workspaces_core::worktree_setup_status_core(&state.workspaces, &workspace_id, &data_dir).await
+    workspaces_core::worktree_setup_status_core(&state.workspaces, &workspace_id, &data_dir)
+        .await
+        .map_err(CommandError::from)
 }
 
 #[tauri::command]
@@ -329,7 +355,7 @@ pub(crate) async fn worktree_setup_mark_ran(
     workspace_id: String,
     state: State<'_, AppState>,
     app: AppHandle,
-) -> Result<(), String> {
+) -> CommandResult<()> {
     if remote_backend::is_remote_mode(&*state).await {
         let request = workspace_rpc::WorkspaceIdRequest { workspace_id };
         remote_backend::call_remote(
@@ -346,7 +372,9 @@ pub(crate) async fn worktree_setup_mark_ran(
         .path()
         .app_data_dir()
         .map_err(|err| format!("Failed to resolve app data dir: {err}"))?;
-    workspaces_core::worktree_setup_mark_ran_core(&state.workspaces, &workspace_id, &data_dir).await
+    workspaces_core::worktree_setup_mark_ran_core(&state.workspaces, &workspace_id, &data_dir)
+        .await
+        .map_err(CommandError::from)
 }
 
 #[tauri::command]
@@ -354,7 +382,7 @@ pub(crate) async fn remove_workspace(
     id: String,
     state: State<'_, AppState>,
     app: AppHandle,
-) -> Result<(), String> {
+) -> CommandResult<()> {
     if remote_backend::is_remote_mode(&*state).await {
         let request = workspace_rpc::IdRequest { id };
         remote_backend::call_remote(
@@ -386,6 +414,7 @@ pub(crate) async fn remove_workspace(
         true,
     )
     .await
+    .map_err(CommandError::from)
 }
 
 #[tauri::command]
@@ -393,7 +422,7 @@ pub(crate) async fn remove_worktree(
     id: String,
     state: State<'_, AppState>,
     app: AppHandle,
-) -> Result<(), String> {
+) -> CommandResult<()> {
     if remote_backend::is_remote_mode(&*state).await {
         let request = workspace_rpc::IdRequest { id };
         remote_backend::call_remote(
@@ -423,6 +452,7 @@ pub(crate) async fn remove_worktree(
         },
     )
     .await
+    .map_err(CommandError::from)
 }
 
 #[tauri::command]
@@ -431,7 +461,7 @@ pub(crate) async fn rename_worktree(
     branch: String,
     state: State<'_, AppState>,
     app: AppHandle,
-) -> Result<WorkspaceInfo, String> {
+) -> CommandResult<WorkspaceInfo> {
     if remote_backend::is_remote_mode(&*state).await {
         let request = workspace_rpc::RenameWorktreeRequest { id, branch };
         let response = remote_backend::call_remote(
@@ -441,7 +471,7 @@ pub(crate) async fn rename_worktree(
             workspace_remote_params(&request)?,
         )
         .await?;
-        return serde_json::from_value(response).map_err(|err| err.to_string());
+        return serde_json::from_value(response).map_err(|err| CommandError::from(err.to_string()));
     }
 
     let data_dir = app
@@ -479,6 +509,7 @@ pub(crate) async fn rename_worktree(
         },
     )
     .await
+    .map_err(CommandError::from)
 }
 
 #[tauri::command]
@@ -488,7 +519,7 @@ pub(crate) async fn rename_worktree_upstream(
     new_branch: String,
     state: State<'_, AppState>,
     app: AppHandle,
-) -> Result<(), String> {
+) -> CommandResult<()> {
     if remote_backend::is_remote_mode(&*state).await {
         let request = workspace_rpc::RenameWorktreeUpstreamRequest {
             id,
@@ -539,14 +570,30 @@ pub(crate) async fn rename_worktree_upstream(
         },
     )
     .await
+    .map_err(CommandError::from)
 }
 
 #[tauri::command]
 pub(crate) async fn apply_worktree_changes(
     workspace_id: String,
     state: State<'_, AppState>,
-) -> Result<(), String> {
-    workspaces_core::apply_worktree_changes_core(&state.workspaces, workspace_id).await
+    app: AppHandle,
+) -> CommandResult<()> {
+    if remote_backend::is_remote_mode(&*state).await {
+        let request = workspace_rpc::WorkspaceIdRequest { workspace_id };
+        remote_backend::call_remote(
+            &*state,
+            app,
+            "apply_worktree_changes",
+            workspace_remote_params(&request)?,
+        )
+        .await?;
+        return Ok(());
+    }
+
+    workspaces_core::apply_worktree_changes_core(&state.workspaces, workspace_id)
+        .await
+        .map_err(CommandError::from)
 }
 
 #[tauri::command]
@@ -555,7 +602,7 @@ pub(crate) async fn update_workspace_settings(
     settings: WorkspaceSettings,
     state: State<'_, AppState>,
     app: AppHandle,
-) -> Result<WorkspaceInfo, String> {
+) -> CommandResult<WorkspaceInfo> {
     if remote_backend::is_remote_mode(&*state).await {
         let request = workspace_rpc::UpdateWorkspaceSettingsRequest { id, settings };
         let response = remote_backend::call_remote(
@@ -565,7 +612,7 @@ pub(crate) async fn update_workspace_settings(
             workspace_remote_params(&request)?,
         )
         .await?;
-        return serde_json::from_value(response).map_err(|err| err.to_string());
+        return serde_json::from_value(response).map_err(|err| CommandError::from(err.to_string()));
     }
 
     workspaces_core::update_workspace_settings_core(
@@ -583,6 +630,7 @@ pub(crate) async fn update_workspace_settings(
         },
     )
     .await
+    .map_err(CommandError::from)
 }
 
 #[tauri::command]
@@ -590,7 +638,7 @@ pub(crate) async fn connect_workspace(
     id: String,
     state: State<'_, AppState>,
     app: AppHandle,
-) -> Result<(), String> {
+) -> CommandResult<()> {
     if remote_backend::is_remote_mode(&*state).await {
         let request = workspace_rpc::IdRequest { id };
         remote_backend::call_remote(
@@ -613,6 +661,7 @@ pub(crate) async fn connect_workspace(
         },
     )
     .await
+    .map_err(CommandError::from)
 }
 
 #[tauri::command]
@@ -620,7 +669,7 @@ pub(crate) async fn list_workspace_files(
     workspace_id: String,
     state: State<'_, AppState>,
     app: AppHandle,
-) -> Result<Vec<String>, String> {
+) -> CommandResult<Vec<String>> {
     if remote_backend::is_remote_mode(&*state).await {
         let request = workspace_rpc::WorkspaceIdRequest { workspace_id };
         let response = remote_backend::call_remote(
@@ -630,16 +679,18 @@ pub(crate) async fn list_workspace_files(
             workspace_remote_params(&request)?,
         )
         .await?;
-        return serde_json::from_value(response).map_err(|err| err.to_string());
+        return serde_json::from_value(response).map_err(|err| CommandError::from(err.to_string()));
     }
 
     workspaces_core::list_workspace_files_core(&state.workspaces, &workspace_id, |root| {
         list_workspace_files_inner(root, usize::MAX)
     })
     .await
+    .map_err(CommandError::from)
 }
 
 #[tauri::command]
+#[allow(clippy::too_many_arguments)]
 pub(crate) async fn open_workspace_in(
     path: String,
     app: Option<String>,
@@ -647,22 +698,66 @@ pub(crate) async fn open_workspace_in(
     command: Option<String>,
     line: Option<u32>,
     column: Option<u32>,
-) -> Result<(), String> {
-    workspaces_core::open_workspace_in_core(path, app, args, command, line, column).await
+    state: State<'_, AppState>,
+    handle: AppHandle,
+) -> CommandResult<()> {
+    // Desktop opens paths here even in remote mode; a mobile build has no
+    // apps to open them in, so the daemon's host does.
+    if platform::is_mobile() {
+        let request = workspace_rpc::OpenWorkspaceInRequest {
+            path,
+            app,
+            args,
+            command,
+            line,
+            column,
+        };
+        remote_backend::call_remote(
+            &*state,
+            handle,
+            "open_workspace_in",
+            workspace_remote_params(&request)?,
+        )
+        .await?;
+        return Ok(());
+    }
+
+    workspaces_core::open_workspace_in_core(path, app, args, command, line, column)
+        .await
+        .map_err(CommandError::from)
 }
 
 #[tauri::command]
-pub(crate) async fn get_open_app_icon(app_name: String) -> Result<Option<String>, String> {
+pub(crate) async fn get_open_app_icon(
+    app_name: String,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> CommandResult<Option<String>> {
+    if platform::is_mobile() {
+        let request = workspace_rpc::GetOpenAppIconRequest { app_name };
+        let response = remote_backend::call_remote(
+            &*state,
+            app,
+            "get_open_app_icon",
+            workspace_remote_params(&request)?,
+        )
+        .await?;
+        return serde_json::from_value(response).map_err(|err| CommandError::from(err.to_string()));
+    }
+
     #[cfg(target_os = "macos")]
     {
         return workspaces_core::get_open_app_icon_core(app_name, |name| {
             get_open_app_icon_inner(name)
         })
-        .await;
+        .await
+        .map_err(CommandError::from);
     }
 
     #[cfg(not(target_os = "macos"))]
     {
-        workspaces_core::get_open_app_icon_core(app_name, |_name| None).await
+        workspaces_core::get_open_app_icon_core(app_name, |_name| None)
+            .await
+            .map_err(CommandError::from)
     }
 }
@@ -18,7 +18,7 @@ use crate::types::{
     AppSettings, WorkspaceEntry, WorkspaceInfo, WorkspaceKind, WorkspaceSettings, WorktreeInfo,
 };
 use tokio::runtime::Runtime;
-use tokio::sync::Mutex;
+use tokio::sync::{Mutex, RwLock};
 use uuid::Uuid;
 
 fn workspace(name: &str, sort_order: Option<u32>) -> WorkspaceInfo {
@@ -59,6 +59,7 @@ fn workspace_with_id_and_kind(
             launch_scripts: None,
             worktree_setup_script: None,
             worktrees_folder: None,
+            ci_failure_notifications: false,
         },
     }
 }
@@ -301,7 +302,7 @@ fn rename_worktree_preserves_custom_name() {
             (worktree.id.clone(), worktree.clone()),
         ]));
         let sessions: Mutex<HashMap<String, Arc<WorkspaceSession>>> = Mutex::new(HashMap::new());
-        let app_settings = Mutex::new(AppSettings::default());
+        let app_settings = RwLock::new(AppSettings::default());
         let storage_path = temp_dir.join("workspaces.json");
 
         let updated = rename_worktree_core(
@@ -372,7 +373,7 @@ fn rename_worktree_updates_name_when_unmodified() {
             (worktree.id.clone(), worktree.clone()),
         ]));
         let sessions: Mutex<HashMap<String, Arc<WorkspaceSession>>> = Mutex::new(HashMap::new());
-        let app_settings = Mutex::new(AppSettings::default());
+        let app_settings = RwLock::new(AppSettings::default());
         let storage_path = temp_dir.join("workspaces.json");
 
         let updated = rename_worktree_core(
@@ -441,7 +442,7 @@ fn rename_worktree_validates_worktree_root_before_branch_rename() {
             (worktree.id.clone(), worktree.clone()),
         ]));
         let sessions: Mutex<HashMap<String, Arc<WorkspaceSession>>> = Mutex::new(HashMap::new());
-        let app_settings = Mutex::new(AppSettings::default());
+        let app_settings = RwLock::new(AppSettings::default());
         let storage_path = temp_dir.join("workspaces.json");
 
         let calls: Arc<StdMutex<Vec<Vec<String>>>> = Arc::new(StdMutex::new(Vec::new()));
@@ -511,7 +512,7 @@ fn update_workspace_settings_core_sanitizes_namespace_worktrees_folder() {
         };
         let workspaces = Mutex::new(HashMap::from([(id.clone(), entry)]));
         let sessions: Mutex<HashMap<String, Arc<WorkspaceSession>>> = Mutex::new(HashMap::new());
-        let app_settings = Mutex::new(AppSettings::default());
+        let app_settings = RwLock::new(AppSettings::default());
 
         let mut settings = WorkspaceSettings::default();
         settings.worktrees_folder = Some(r"\\?\I:\gpt-projects\worktrees".to_string());
@@ -578,7 +579,7 @@ fn rename_worktree_ignores_namespace_only_difference_in_worktree_root() {
             (worktree.id.clone(), worktree.clone()),
         ]));
         let sessions: Mutex<HashMap<String, Arc<WorkspaceSession>>> = Mutex::new(HashMap::new());
-        let app_settings = Mutex::new(AppSettings::default());
+        let app_settings = RwLock::new(AppSettings::default());
         let storage_path = temp_dir.join("workspaces.json");
         let calls: Arc<StdMutex<Vec<Vec<String>>>> = Arc::new(StdMutex::new(Vec::new()));
 
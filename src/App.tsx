@@ -28,6 +28,7 @@ import "./styles/debug.css";
 import "./styles/terminal.css";
 import "./styles/plan.css";
 import "./styles/about.css";
+import "./styles/activity-alert.css";
 import "./styles/tabbar.css";
 import "./styles/worktree-modal.css";
 import "./styles/clone-modal.css";
@@ -48,6 +49,14 @@ const AboutView = lazy(() =>
   })),
 );
 
+const ActivityAlertView = lazy(() =>
+  import("@/features/notifications/components/ActivityAlertView").then(
+    (module) => ({
+      default: module.ActivityAlertView,
+    }),
+  ),
+);
+
 export default function App() {
   const windowLabel = useWindowLabel();
 
@@ -59,5 +68,13 @@ export default function App() {
     );
   }
 
+  if (windowLabel === "activity") {
+    return (
+      <Suspense fallback={null}>
+        <ActivityAlertView />
+      </Suspense>
+    );
+  }
+
   return <MainApp />;
 }
@@ -62,6 +62,8 @@ import { useThreadListSortKey } from "@app/hooks/useThreadListSortKey";
 import { useThreadListActions } from "@app/hooks/useThreadListActions";
 import { useRemoteThreadLiveConnection } from "@app/hooks/useRemoteThreadLiveConnection";
 import { useTrayRecentThreads } from "@app/hooks/useTrayRecentThreads";
+import { useClientApprovalPrompts } from "@app/hooks/useClientApprovalPrompts";
+import { useRemoteActionConfirmationPrompts } from "@app/hooks/useRemoteActionConfirmationPrompts";
 import { useTraySessionUsage } from "@app/hooks/useTraySessionUsage";
 import { useTauriEvent } from "@app/hooks/useTauriEvent";
 import { useAppBootstrapOrchestration } from "@app/bootstrap/useAppBootstrapOrchestration";
@@ -82,8 +84,15 @@ import {
   resolveWorkspaceRuntimeCodexArgsBadgeLabel,
   resolveWorkspaceRuntimeCodexArgsOverride,
 } from "@threads/utils/threadCodexParamsSeed";
-import { subscribeTrayOpenThread } from "@services/events";
-import { setWorkspaceRuntimeCodexArgs } from "@services/tauri";
+import {
+  subscribeDeepLinkOpenProject,
+  subscribeTrayOpenThread,
+  type DeepLinkOpenProjectEvent,
+} from "@services/events";
+import {
+  setWorkspaceRuntimeCodexArgs,
+  takeDeepLinkProject,
+} from "@services/tauri";
 
 const SettingsView = lazy(() =>
   import("@settings/components/SettingsView").then((module) => ({
@@ -830,6 +839,9 @@ export default function MainApp() {
     isSubagentThread,
   });
 
+  useClientApprovalPrompts({ enabled: !isMobileRuntime });
+  useRemoteActionConfirmationPrompts({ enabled: !isMobileRuntime });
+
   useAutoExitEmptyDiff({
     centerMode,
     autoExitEnabled: diffSource === "local",
@@ -1342,7 +1354,7 @@ export default function MainApp() {
   });
   const { baseWorkspaceRef } = worktreeState;
 
-  useMainAppWorkspaceLifecycle({
+  const { remotePollIntervalMs } = useMainAppWorkspaceLifecycle({
     activeTab,
     isTablet,
     setActiveTab,
@@ -1489,6 +1501,28 @@ export default function MainApp() {
     },
   );
 
+  useTauriEvent(
+    subscribeDeepLinkOpenProject,
+    ({ workspaceId }: DeepLinkOpenProjectEvent) => {
+      selectWorkspace(workspaceId);
+    },
+  );
+
+  const deepLinkProjectTakenRef = useRef(false);
+  useEffect(() => {
+    if (!hasLoaded || deepLinkProjectTakenRef.current) {
+      return;
+    }
+    deepLinkProjectTakenRef.current = true;
+    void takeDeepLinkProject()
+      .then((workspaceId) => {
+        if (workspaceId) {
+          selectWorkspace(workspaceId);
+        }
+      })
+      .catch(() => {});
+  }, [hasLoaded, selectWorkspace]);
+
   useEffect(() => {
     recordPendingThreadLinkRef.current = recordPendingThreadLink;
     return () => {
@@ -1695,6 +1729,7 @@ export default function MainApp() {
   });
   const { workspaceHomeNode } = displayNodes;
   const layoutSurfaces = useMainAppLayoutSurfaces({
+    remotePollIntervalMs,
     appSettings: {
       usageShowRemaining: appSettings.usageShowRemaining,
       composerCodeBlockCopyUseModifier:
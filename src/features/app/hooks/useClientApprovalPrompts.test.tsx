@@ -0,0 +1,101 @@
+// @vitest-environment jsdom
+import { renderHook, waitFor } from "@testing-library/react";
+import { beforeEach, describe, expect, it, vi } from "vitest";
+import type { ClientApproval } from "../../../types";
+import { useClientApprovalPrompts } from "./useClientApprovalPrompts";
+
+const askMock = vi.hoisted(() => vi.fn());
+const listClientApprovalsMock = vi.hoisted(() => vi.fn());
+const decideClientApprovalMock = vi.hoisted(() => vi.fn());
+const subscribeMock = vi.hoisted(() => vi.fn());
+const subscribeReviewMock = vi.hoisted(() => vi.fn());
+
+vi.mock("@tauri-apps/plugin-dialog", () => ({
+  ask: askMock,
+}));
+
+vi.mock("@services/tauri", () => ({
+  listClientApprovals: listClientApprovalsMock,
+  decideClientApproval: decideClientApprovalMock,
+}));
+
+vi.mock("@services/events", () => ({
+  subscribeClientApprovalRequested: subscribeMock,
+  subscribeDeepLinkReviewClient: subscribeReviewMock,
+}));
+
+vi.mock("@services/toasts", () => ({
+  pushErrorToast: vi.fn(),
+}));
+
+function makeApproval(overrides: Partial<ClientApproval> = {}): ClientApproval {
+  return {
+    clientId: "phone-1",
+    clientName: "Phone (ios)",
+    status: "pending",
+    firstSeenAtMs: 1,
+    lastSeenAtMs: 1,
+    decidedAtMs: null,
+    ...overrides,
+  };
+}
+
+describe("useClientApprovalPrompts", () => {
+  beforeEach(() => {
+    askMock.mockReset();
+    listClientApprovalsMock.mockReset();
+    decideClientApprovalMock.mockReset().mockResolvedValue(undefined);
+    subscribeMock.mockReset().mockReturnValue(() => {});
+    subscribeReviewMock.mockReset().mockReturnValue(() => {});
+  });
+
+  it("prompts once for each pending client and records the answer", async () => {
+    listClientApprovalsMock.mockResolvedValue([
+      makeApproval(),
+      makeApproval({ clientId: "laptop-2", status: "approved" }),
+    ]);
+    askMock.mockResolvedValueOnce(true).mockResolvedValueOnce(false);
+
+    renderHook(() => useClientApprovalPrompts());
+
+    await waitFor(() =>
+      expect(decideClientApprovalMock).toHaveBeenCalledWith("phone-1", true),
+    );
+    expect(askMock).toHaveBeenCalledTimes(1);
+    expect(askMock.mock.calls[0][0]).toContain("Phone (ios)");
+
+    const onRequested = subscribeMock.mock.calls[0][0];
+    onRequested(makeApproval());
+    onRequested(makeApproval({ clientId: "tablet-3", clientName: null }));
+
+    await waitFor(() =>
+      expect(decideClientApprovalMock).toHaveBeenCalledWith("tablet-3", false),
+    );
+    expect(askMock).toHaveBeenCalledTimes(2);
+    expect(askMock.mock.calls[1][0]).toContain("An unknown device");
+  });
+
+  it("asks again about a client a link names, ahead of the queue", async () => {
+    listClientApprovalsMock.mockResolvedValue([makeApproval()]);
+    askMock.mockResolvedValue(false);
+
+    renderHook(() => useClientApprovalPrompts());
+
+    await waitFor(() =>
+      expect(decideClientApprovalMock).toHaveBeenCalledWith("phone-1", false),
+    );
+    const onReview = subscribeReviewMock.mock.calls[0][0];
+    onReview(makeApproval({ status: "denied" }));
+    onReview(makeApproval());
+
+    await waitFor(() => expect(decideClientApprovalMock).toHaveBeenCalledTimes(2));
+    expect(askMock).toHaveBeenCalledTimes(2);
+  });
+
+  it("does nothing while disabled", () => {
+    renderHook(() => useClientApprovalPrompts({ enabled: false }));
+
+    expect(listClientApprovalsMock).not.toHaveBeenCalled();
+    expect(subscribeMock).not.toHaveBeenCalled();
+  });
+});
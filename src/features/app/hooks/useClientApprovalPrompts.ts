@@ -0,0 +1,106 @@
+import { useCallback, useEffect, useRef } from "react";
+import { ask } from "@tauri-apps/plugin-dialog";
+import {
+  subscribeClientApprovalRequested,
+  subscribeDeepLinkReviewClient,
+} from "@services/events";
+import { decideClientApproval, listClientApprovals } from "@services/tauri";
+import { pushErrorToast } from "@services/toasts";
+import type { ClientApproval } from "../../../types";
+import { useTauriEvent } from "./useTauriEvent";
+
+type UseClientApprovalPromptsOptions = {
+  enabled?: boolean;
+};
+
+/**
+ * Asks the owner to allow or deny clients waiting on the daemon, one prompt
+ * at a time. The daemon repeats the request on every connection attempt, so
+ * each client is only asked about once per session, unless a
+ * `codexmonitor://approve` link asks for that client again.
+ */
+export function useClientApprovalPrompts({
+  enabled = true,
+}: UseClientApprovalPromptsOptions = {}) {
+  const queueRef = useRef<ClientApproval[]>([]);
+  const seenRef = useRef(new Set<string>());
+  const promptingRef = useRef(false);
+  const currentRef = useRef<string | null>(null);
+
+  const drainQueue = useCallback(async () => {
+    if (promptingRef.current) {
+      return;
+    }
+    promptingRef.current = true;
+    try {
+      let approval = queueRef.current.shift();
+      while (approval) {
+        currentRef.current = approval.clientId;
+        const name = approval.clientName ?? "An unknown device";
+        const allowed = await ask(
+          `${name} wants to connect to this CodexMonitor backend.\n\nClient ID: ${approval.clientId}\n\nDenied clients stay blocked even with a valid token.`,
+          {
+            title: "Allow New Client?",
+            kind: "warning",
+            okLabel: "Allow",
+            cancelLabel: "Deny",
+          },
+        );
+        try {
+          await decideClientApproval(approval.clientId, allowed);
+        } catch (error) {
+          pushErrorToast({
+            title: "Couldn't save client decision",
+            message: error instanceof Error ? error.message : String(error),
+          });
+        }
+        approval = queueRef.current.shift();
+      }
+    } finally {
+      currentRef.current = null;
+      promptingRef.current = false;
+    }
+  }, []);
+
+  const enqueue = useCallback(
+    (approval: ClientApproval) => {
+      if (approval.status !== "pending" || seenRef.current.has(approval.clientId)) {
+        return;
+      }
+      seenRef.current.add(approval.clientId);
+      queueRef.current.push(approval);
+      void drainQueue();
+    },
+    [drainQueue],
+  );
+
+  // A link names one client, which is asked about next.
+  const review = useCallback(
+    (approval: ClientApproval) => {
+      if (approval.status !== "pending" || currentRef.current === approval.clientId) {
+        return;
+      }
+      seenRef.current.add(approval.clientId);
+      queueRef.current = [
+        approval,
+        ...queueRef.current.filter((queued) => queued.clientId !== approval.clientId),
+      ];
+      void drainQueue();
+    },
+    [drainQueue],
+  );
+
+  useTauriEvent(subscribeClientApprovalRequested, enqueue, { enabled });
+  useTauriEvent(subscribeDeepLinkReviewClient, review, { enabled });
+
+  useEffect(() => {
+    if (!enabled) {
+      return;
+    }
+    listClientApprovals()
+      .then((approvals) => approvals.forEach(enqueue))
+      .catch(() => {
+        // Older daemons do not track clients.
+      });
+  }, [enabled, enqueue]);
+}
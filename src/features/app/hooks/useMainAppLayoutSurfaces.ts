@@ -2,7 +2,6 @@ import type { RefObject } from "react";
 import type { AppSettings, ComposerEditorSettings, WorkspaceInfo } from "@/types";
 import type { ThreadState } from "@/features/threads/hooks/useThreadsReducer";
 import type { WorkspaceLaunchScriptsState } from "@app/hooks/useWorkspaceLaunchScripts";
-import { REMOTE_THREAD_POLL_INTERVAL_MS } from "@app/hooks/useRemoteThreadRefreshOnFocus";
 import type { useMainAppComposerWorkspaceState } from "@app/hooks/useMainAppComposerWorkspaceState";
 import type { useMainAppDisplayNodes } from "@app/hooks/useMainAppDisplayNodes";
 import type { useMainAppGitState } from "@app/hooks/useMainAppGitState";
@@ -31,6 +30,7 @@ type UseMainAppLayoutSurfacesArgs = {
     | "splitChatDiffView"
     | "gitDiffIgnoreWhitespaceChanges"
   >;
+  remotePollIntervalMs: number;
   workspaces: WorkspaceInfo[];
   groupedWorkspaces: Array<{ id: string | null; name: string; workspaces: WorkspaceInfo[] }>;
   workspaceGroupsCount: number;
@@ -229,6 +229,7 @@ type UseMainAppLayoutSurfacesArgs = {
 
 export function useMainAppLayoutSurfaces({
   appSettings,
+  remotePollIntervalMs,
   workspaces,
   groupedWorkspaces,
   workspaceGroupsCount,
@@ -480,7 +481,7 @@ export function useMainAppLayoutSurfaces({
           ? threadStatusById[activeThreadId]?.lastDurationMs ?? null
           : null,
         showPollingFetchStatus: showMobilePollingFetchStatus,
-        pollingIntervalMs: REMOTE_THREAD_POLL_INTERVAL_MS,
+        pollingIntervalMs: remotePollIntervalMs,
       },
       composerProps: composerWorkspaceState.showComposer
         ? {
@@ -5,6 +5,7 @@ import {
 } from "@/features/workspaces/hooks/useWorkspaceRefreshOnFocus";
 import { useWorkspaceRestore } from "@/features/workspaces/hooks/useWorkspaceRestore";
 import { useTabActivationGuard } from "@app/hooks/useTabActivationGuard";
+import { useRemotePollInterval } from "@app/hooks/useRemotePollInterval";
 import {
   useRemoteThreadRefreshOnFocus,
 } from "@app/hooks/useRemoteThreadRefreshOnFocus";
@@ -58,12 +59,17 @@ export function useMainAppWorkspaceLifecycle({
     listThreadsForWorkspaces,
   });
 
+  const remotePollIntervalMs = useRemotePollInterval(backendMode);
+
   useWorkspaceRefreshOnFocus({
     workspaces,
     refreshWorkspaces,
     listThreadsForWorkspaces,
     backendMode,
-    pollIntervalMs: REMOTE_WORKSPACE_REFRESH_INTERVAL_MS,
+    pollIntervalMs: Math.max(
+      REMOTE_WORKSPACE_REFRESH_INTERVAL_MS,
+      remotePollIntervalMs,
+    ),
   });
 
   useRemoteThreadRefreshOnFocus({
@@ -75,7 +81,10 @@ export function useMainAppWorkspaceLifecycle({
     ),
     suspendPolling:
       backendMode === "remote" && remoteThreadConnectionState === "live",
+    pollIntervalMs: remotePollIntervalMs,
     reconnectWorkspace: connectWorkspace,
     refreshThread,
   });
+
+  return { remotePollIntervalMs };
 }
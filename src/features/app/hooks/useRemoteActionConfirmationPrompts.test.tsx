@@ -0,0 +1,92 @@
+// @vitest-environment jsdom
+import { renderHook, waitFor } from "@testing-library/react";
+import { beforeEach, describe, expect, it, vi } from "vitest";
+import type { RemoteActionConfirmation } from "../../../types";
+import { useRemoteActionConfirmationPrompts } from "./useRemoteActionConfirmationPrompts";
+
+const askMock = vi.hoisted(() => vi.fn());
+const listConfirmationsMock = vi.hoisted(() => vi.fn());
+const decideConfirmationMock = vi.hoisted(() => vi.fn());
+const subscribeMock = vi.hoisted(() => vi.fn());
+
+vi.mock("@tauri-apps/plugin-dialog", () => ({
+  ask: askMock,
+}));
+
+vi.mock("@services/tauri", () => ({
+  listRemoteActionConfirmations: listConfirmationsMock,
+  decideRemoteActionConfirmation: decideConfirmationMock,
+}));
+
+vi.mock("@services/events", () => ({
+  subscribeRemoteActionConfirmationRequested: subscribeMock,
+}));
+
+vi.mock("@services/toasts", () => ({
+  pushErrorToast: vi.fn(),
+}));
+
+function makeConfirmation(
+  overrides: Partial<RemoteActionConfirmation> = {},
+): RemoteActionConfirmation {
+  return {
+    id: "confirm-1",
+    method: "terminate_session",
+    summary: "Terminate session s-1",
+    clientId: "phone-1",
+    clientName: "Phone (ios)",
+    status: "pending",
+    requestedAtMs: 1,
+    expiresAtMs: Date.now() + 60_000,
+    decidedAtMs: null,
+    ...overrides,
+  };
+}
+
+describe("useRemoteActionConfirmationPrompts", () => {
+  beforeEach(() => {
+    askMock.mockReset();
+    listConfirmationsMock.mockReset();
+    decideConfirmationMock.mockReset().mockResolvedValue(undefined);
+    subscribeMock.mockReset().mockReturnValue(() => {});
+  });
+
+  it("prompts once for each pending request and records the answer", async () => {
+    listConfirmationsMock.mockResolvedValue([
+      makeConfirmation(),
+      makeConfirmation({ id: "confirm-0", status: "expired" }),
+    ]);
+    askMock.mockResolvedValueOnce(true).mockResolvedValueOnce(false);
+
+    renderHook(() => useRemoteActionConfirmationPrompts());
+
+    await waitFor(() =>
+      expect(decideConfirmationMock).toHaveBeenCalledWith("confirm-1", true),
+    );
+    expect(askMock).toHaveBeenCalledTimes(1);
+    expect(askMock.mock.calls[0][0]).toContain("Terminate session s-1");
+
+    const onRequested = subscribeMock.mock.calls[0][0];
+    onRequested(makeConfirmation());
+    onRequested(
+      makeConfirmation({
+        id: "confirm-2",
+        clientName: null,
+        summary: "Run `rm -rf build` in the workspace root",
+      }),
+    );
+
+    await waitFor(() =>
+      expect(decideConfirmationMock).toHaveBeenCalledWith("confirm-2", false),
+    );
+    expect(askMock).toHaveBeenCalledTimes(2);
+    expect(askMock.mock.calls[1][0]).toContain("A remote client");
+  });
+
+  it("does nothing while disabled", () => {
+    renderHook(() => useRemoteActionConfirmationPrompts({ enabled: false }));
+
+    expect(listConfirmationsMock).not.toHaveBeenCalled();
+    expect(subscribeMock).not.toHaveBeenCalled();
+  });
+});
@@ -0,0 +1,89 @@
+import { useCallback, useEffect, useRef } from "react";
+import { ask } from "@tauri-apps/plugin-dialog";
+import { subscribeRemoteActionConfirmationRequested } from "@services/events";
+import {
+  decideRemoteActionConfirmation,
+  listRemoteActionConfirmations,
+} from "@services/tauri";
+import { pushErrorToast } from "@services/toasts";
+import type { RemoteActionConfirmation } from "../../../types";
+import { useTauriEvent } from "./useTauriEvent";
+
+type UseRemoteActionConfirmationPromptsOptions = {
+  enabled?: boolean;
+};
+
+/**
+ * Asks the owner to confirm destructive requests a remote client sent, one
+ * prompt at a time. Requests that expired while earlier prompts were open
+ * are dropped, since the daemon has already rejected them.
+ */
+export function useRemoteActionConfirmationPrompts({
+  enabled = true,
+}: UseRemoteActionConfirmationPromptsOptions = {}) {
+  const queueRef = useRef<RemoteActionConfirmation[]>([]);
+  const seenRef = useRef(new Set<string>());
+  const promptingRef = useRef(false);
+
+  const drainQueue = useCallback(async () => {
+    if (promptingRef.current) {
+      return;
+    }
+    promptingRef.current = true;
+    try {
+      let confirmation = queueRef.current.shift();
+      while (confirmation) {
+        if (Date.now() >= confirmation.expiresAtMs) {
+          confirmation = queueRef.current.shift();
+          continue;
+        }
+        const name = confirmation.clientName ?? "A remote client";
+        const approved = await ask(
+          `${name} is asking to:\n\n${confirmation.summary}\n\nThe request is rejected if nobody answers in time.`,
+          {
+            title: "Confirm Remote Action?",
+            kind: "warning",
+            okLabel: "Allow",
+            cancelLabel: "Reject",
+          },
+        );
+        try {
+          await decideRemoteActionConfirmation(confirmation.id, approved);
+        } catch (error) {
+          pushErrorToast({
+            title: "Couldn't confirm remote action",
+            message: error instanceof Error ? error.message : String(error),
+          });
+        }
+        confirmation = queueRef.current.shift();
+      }
+    } finally {
+      promptingRef.current = false;
+    }
+  }, []);
+
+  const enqueue = useCallback(
+    (confirmation: RemoteActionConfirmation) => {
+      if (confirmation.status !== "pending" || seenRef.current.has(confirmation.id)) {
+        return;
+      }
+      seenRef.current.add(confirmation.id);
+      queueRef.current.push(confirmation);
+      void drainQueue();
+    },
+    [drainQueue],
+  );
+
+  useTauriEvent(subscribeRemoteActionConfirmationRequested, enqueue, { enabled });
+
+  useEffect(() => {
+    if (!enabled) {
+      return;
+    }
+    listRemoteActionConfirmations()
+      .then((confirmations) => confirmations.forEach(enqueue))
+      .catch(() => {
+        // Older daemons do not hold remote actions.
+      });
+  }, [enabled, enqueue]);
+}
@@ -0,0 +1,56 @@
+// @vitest-environment jsdom
+import { act, renderHook } from "@testing-library/react";
+import { afterEach, beforeEach, describe, expect, it, vi } from "vitest";
+import { useRemotePollInterval } from "./useRemotePollInterval";
+import { REMOTE_THREAD_POLL_INTERVAL_MS } from "./useRemoteThreadRefreshOnFocus";
+
+const getRemoteBackendStatusMock = vi.hoisted(() => vi.fn());
+
+vi.mock("@services/tauri", () => ({
+  getRemoteBackendStatus: getRemoteBackendStatusMock,
+}));
+
+async function flushPromises() {
+  for (let i = 0; i < 5; i += 1) {
+    await Promise.resolve();
+  }
+}
+
+describe("useRemotePollInterval", () => {
+  beforeEach(() => {
+    vi.useFakeTimers();
+    getRemoteBackendStatusMock.mockReset();
+  });
+
+  afterEach(() => {
+    vi.useRealTimers();
+  });
+
+  it("follows the backend's suggested interval in remote mode", async () => {
+    getRemoteBackendStatusMock
+      .mockResolvedValueOnce({ suggestedPollMs: 30000 })
+      .mockResolvedValueOnce({ suggestedPollMs: 5000 });
+
+    const { result } = renderHook(() => useRemotePollInterval("remote"));
+    expect(result.current).toBe(REMOTE_THREAD_POLL_INTERVAL_MS);
+
+    await act(async () => {
+      await flushPromises();
+    });
+    expect(result.current).toBe(30000);
+
+    await act(async () => {
+      await vi.advanceTimersByTimeAsync(30000);
+      await flushPromises();
+    });
+    expect(getRemoteBackendStatusMock).toHaveBeenCalledTimes(2);
+    expect(result.current).toBe(5000);
+  });
+
+  it("keeps the default in local mode", () => {
+    const { result } = renderHook(() => useRemotePollInterval("local"));
+
+    expect(result.current).toBe(REMOTE_THREAD_POLL_INTERVAL_MS);
+    expect(getRemoteBackendStatusMock).not.toHaveBeenCalled();
+  });
+});
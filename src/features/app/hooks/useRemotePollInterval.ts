@@ -0,0 +1,58 @@
+import { useEffect, useState } from "react";
+import { getRemoteBackendStatus } from "@services/tauri";
+import { REMOTE_THREAD_POLL_INTERVAL_MS } from "./useRemoteThreadRefreshOnFocus";
+
+/**
+ * The backend's suggested interval for polling the remote daemon, from link
+ * quality, recent activity and live subscriptions. Re-read every interval
+ * and on focus; the fixed default applies in local mode or until the first
+ * answer.
+ */
+export function useRemotePollInterval(backendMode: string) {
+  const [pollIntervalMs, setPollIntervalMs] = useState(
+    REMOTE_THREAD_POLL_INTERVAL_MS,
+  );
+
+  useEffect(() => {
+    if (backendMode !== "remote") {
+      setPollIntervalMs(REMOTE_THREAD_POLL_INTERVAL_MS);
+      return;
+    }
+    let cancelled = false;
+    let timer: ReturnType<typeof setTimeout> | null = null;
+
+    const refresh = () => {
+      if (timer) {
+        clearTimeout(timer);
+        timer = null;
+      }
+      void getRemoteBackendStatus()
+        .then((status) => status.suggestedPollMs)
+        .catch(() => null)
+        .then((suggestedPollMs) => {
+          if (cancelled) {
+            return;
+          }
+          if (suggestedPollMs) {
+            setPollIntervalMs(suggestedPollMs);
+          }
+          timer = setTimeout(
+            refresh,
+            suggestedPollMs ?? REMOTE_THREAD_POLL_INTERVAL_MS,
+          );
+        });
+    };
+
+    window.addEventListener("focus", refresh);
+    refresh();
+    return () => {
+      cancelled = true;
+      window.removeEventListener("focus", refresh);
+      if (timer) {
+        clearTimeout(timer);
+      }
+    };
+  }, [backendMode]);
+
+  return pollIntervalMs;
+}
@@ -10,6 +10,7 @@ type UseRemoteThreadRefreshOnFocusOptions = {
   activeThreadId: string | null;
   activeThreadIsProcessing?: boolean;
   suspendPolling?: boolean;
+  pollIntervalMs?: number;
   reconnectWorkspace?: (workspace: WorkspaceInfo) => Promise<unknown> | unknown;
   refreshThread: (workspaceId: string, threadId: string) => Promise<unknown> | unknown;
 };
@@ -20,6 +21,7 @@ export function useRemoteThreadRefreshOnFocus({
   activeThreadId,
   activeThreadIsProcessing = false,
   suspendPolling = false,
+  pollIntervalMs = REMOTE_THREAD_POLL_INTERVAL_MS,
   reconnectWorkspace,
   refreshThread,
 }: UseRemoteThreadRefreshOnFocusOptions) {
@@ -124,7 +126,6 @@ export function useRemoteThreadRefreshOnFocus({
       ) {
         return;
       }
-      const pollIntervalMs = REMOTE_THREAD_POLL_INTERVAL_MS;
       pollTimer = setInterval(() => {
         runRefresh();
       }, pollIntervalMs);
@@ -208,6 +209,7 @@ export function useRemoteThreadRefreshOnFocus({
     activeThreadId,
     activeThreadIsProcessing,
     backendMode,
+    pollIntervalMs,
     suspendPolling,
     workspaceId,
   ]);
@@ -5,6 +5,7 @@ import { ModalShell } from "../../design-system/components/modal/ModalShell";
 export type MobileServerSetupWizardProps = {
   remoteHostDraft: string;
   remoteTokenDraft: string;
+  remoteIdentityDraft: string;
   busy: boolean;
   checking: boolean;
   statusMessage: string | null;
@@ -12,12 +13,14 @@ export type MobileServerSetupWizardProps = {
   onClose: () => void;
   onRemoteHostChange: (value: string) => void;
   onRemoteTokenChange: (value: string) => void;
+  onRemoteIdentityChange: (value: string) => void;
   onConnectTest: () => void;
 };
 
 export function MobileServerSetupWizard({
   remoteHostDraft,
   remoteTokenDraft,
+  remoteIdentityDraft,
   busy,
   checking,
   statusMessage,
@@ -25,6 +28,7 @@ export function MobileServerSetupWizard({
   onClose,
   onRemoteHostChange,
   onRemoteTokenChange,
+  onRemoteIdentityChange,
   onConnectTest,
 }: MobileServerSetupWizardProps) {
   return (
@@ -77,6 +81,19 @@ export function MobileServerSetupWizard({
           disabled={busy || checking}
         />
 
+        <label className="mobile-setup-wizard-label" htmlFor="mobile-setup-identity">
+          Daemon identity
+        </label>
+        <input
+          id="mobile-setup-identity"
+          type="password"
+          className="mobile-setup-wizard-input"
+          value={remoteIdentityDraft}
+          placeholder="Daemon identity"
+          onChange={(event) => onRemoteIdentityChange(event.target.value)}
+          disabled={busy || checking}
+        />
+
         <button
           type="button"
           className="button primary mobile-setup-wizard-action"
@@ -99,7 +116,8 @@ export function MobileServerSetupWizard({
         ) : null}
 
         <div className="mobile-setup-wizard-hint">
-          Use the Tailscale host from desktop Server settings and keep the desktop daemon running.
+          Use the Tailscale host and daemon identity from desktop Server settings and keep the
+          desktop daemon running.
         </div>
       </div>
     </ModalShell>
@@ -19,11 +19,15 @@ type UseMobileServerSetupResult = {
 };
 
 function isRemoteServerConfigured(settings: AppSettings): boolean {
-  return Boolean(settings.remoteBackendToken?.trim()) && Boolean(settings.remoteBackendHost.trim());
+  return (
+    Boolean(settings.remoteBackendToken?.trim()) &&
+    Boolean(settings.remoteBackendIdentity?.trim()) &&
+    Boolean(settings.remoteBackendHost.trim())
+  );
 }
 
 function defaultMobileSetupMessage(): string {
-  return "Enter your desktop Tailscale host and token, then run Connect & test.";
+  return "Enter your desktop Tailscale host, token and daemon identity, then run Connect & test.";
 }
 
 function markActiveRemoteBackendConnected(settings: AppSettings, connectedAtMs: number): AppSettings {
@@ -37,6 +41,7 @@ function markActiveRemoteBackendConnected(settings: AppSettings, connectedAtMs:
             provider: "tcp" as const,
             host: settings.remoteBackendHost,
             token: settings.remoteBackendToken,
+            identity: settings.remoteBackendIdentity ?? null,
             lastConnectedAtMs: null,
           },
         ];
@@ -51,6 +56,7 @@ function markActiveRemoteBackendConnected(settings: AppSettings, connectedAtMs:
     provider: "tcp",
     host: settings.remoteBackendHost,
     token: settings.remoteBackendToken,
+    identity: settings.remoteBackendIdentity ?? null,
     lastConnectedAtMs: connectedAtMs,
   };
   return {
@@ -70,6 +76,9 @@ export function useMobileServerSetup({
 
   const [remoteHostDraft, setRemoteHostDraft] = useState(appSettings.remoteBackendHost);
   const [remoteTokenDraft, setRemoteTokenDraft] = useState(appSettings.remoteBackendToken ?? "");
+  const [remoteIdentityDraft, setRemoteIdentityDraft] = useState(
+    appSettings.remoteBackendIdentity ?? "",
+  );
   const [busy, setBusy] = useState(false);
   const [checking, setChecking] = useState(false);
   const [statusMessage, setStatusMessage] = useState<string | null>(null);
@@ -83,8 +92,10 @@ export function useMobileServerSetup({
     }
     setRemoteHostDraft(appSettings.remoteBackendHost);
     setRemoteTokenDraft(appSettings.remoteBackendToken ?? "");
+    setRemoteIdentityDraft(appSettings.remoteBackendIdentity ?? "");
   }, [
     appSettings.remoteBackendHost,
+    appSettings.remoteBackendIdentity,
     appSettings.remoteBackendToken,
     isMobileRuntime,
   ]);
@@ -131,8 +142,9 @@ export function useMobileServerSetup({
 
       const nextHost = remoteHostDraft.trim();
       const nextToken = remoteTokenDraft.trim() ? remoteTokenDraft.trim() : null;
+      const nextIdentity = remoteIdentityDraft.trim() ? remoteIdentityDraft.trim() : null;
 
-      if (!nextHost || !nextToken) {
+      if (!nextHost || !nextToken || !nextIdentity) {
         setMobileServerReady(false);
         setStatusError(true);
         setStatusMessage(defaultMobileSetupMessage());
@@ -150,6 +162,7 @@ export function useMobileServerSetup({
           remoteBackendProvider: "tcp",
           remoteBackendHost: nextHost,
           remoteBackendToken: nextToken,
+          remoteBackendIdentity: nextIdentity,
         });
         const connected = await runConnectivityCheck({ announceSuccess: true });
         if (connected) {
@@ -171,6 +184,7 @@ export function useMobileServerSetup({
     isMobileRuntime,
     queueSaveSettings,
     remoteHostDraft,
+    remoteIdentityDraft,
     remoteTokenDraft,
     runConnectivityCheck,
   ]);
@@ -233,6 +247,7 @@ export function useMobileServerSetup({
     mobileSetupWizardProps: {
       remoteHostDraft,
       remoteTokenDraft,
+      remoteIdentityDraft,
       busy,
       checking,
       statusMessage,
@@ -242,6 +257,7 @@ export function useMobileServerSetup({
       },
       onRemoteHostChange: setRemoteHostDraft,
       onRemoteTokenChange: setRemoteTokenDraft,
+      onRemoteIdentityChange: setRemoteIdentityDraft,
       onConnectTest,
     },
     handleMobileConnectSuccess,
@@ -0,0 +1,64 @@
+import { useEffect, useState } from "react";
+import type { ActivityAlert } from "@/types";
+import { subscribeActivityAlert } from "@services/events";
+import { dismissActivityAlert, getActivityAlert } from "@services/tauri";
+
+function alertTitle(alert: ActivityAlert | null) {
+  if (!alert) {
+    return "Codex Monitor";
+  }
+  return alert.trigger === "approval_requested"
+    ? "Approval needed"
+    : "Session failed";
+}
+
+export function ActivityAlertView() {
+  const [alert, setAlert] = useState<ActivityAlert | null>(null);
+
+  useEffect(() => {
+    let active = true;
+    getActivityAlert()
+      .then((value) => {
+        if (active) {
+          setAlert(value);
+        }
+      })
+      .catch(() => {
+        if (active) {
+          setAlert(null);
+        }
+      });
+    const unsubscribe = subscribeActivityAlert(setAlert);
+    return () => {
+      active = false;
+      unsubscribe();
+    };
+  }, []);
+
+  return (
+    <div className="activity-alert" data-tauri-drag-region>
+      <div className="activity-alert-text" data-tauri-drag-region>
+        <div className="activity-alert-title">{alertTitle(alert)}</div>
+        {alert?.detail ? (
+          <div className="activity-alert-detail">{alert.detail}</div>
+        ) : null}
+      </div>
+      <div className="activity-alert-actions">
+        <button
+          type="button"
+          className="ghost"
+          onClick={() => void dismissActivityAlert()}
+        >
+          Dismiss
+        </button>
+        <button
+          type="button"
+          className="primary"
+          onClick={() => void dismissActivityAlert(true)}
+        >
+          Open
+        </button>
+      </div>
+    </div>
+  );
+}
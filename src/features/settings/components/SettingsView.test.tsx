@@ -70,6 +70,14 @@ getAgentsSettingsMock.mockResolvedValue({
 const baseSettings: AppSettings = {
   codexBin: null,
   codexArgs: null,
+  codexEnv: {
+    vars: {},
+    pathPrepend: [],
+    httpProxy: null,
+    httpsProxy: null,
+    noProxy: null,
+    keychainVars: {},
+  },
   backendMode: "local",
   remoteBackendProvider: "tcp",
   remoteBackendHost: "127.0.0.1:4732",
@@ -84,7 +92,56 @@ const baseSettings: AppSettings = {
     },
   ],
   activeRemoteBackendId: "remote-default",
+  proxy: { url: null, bypass: [] },
+  rpcTimeouts: { connectMs: null, fastMs: null, normalMs: null, longMs: null },
+  remoteSlowCallMs: 2000,
   keepDaemonRunningAfterAppClose: false,
+  daemonEnv: {
+    vars: {},
+    pathPrepend: [],
+    httpProxy: null,
+    httpsProxy: null,
+    noProxy: null,
+    keychainVars: {},
+  },
+  daemonHttpBridgeEnabled: false,
+  daemonHttpBridgePort: 4733,
+  requireClientApproval: false,
+  confirmRemoteActions: false,
+  remoteActionConfirmTimeoutSecs: 60,
+  mockBackends: false,
+  daemonAuth: {
+    providers: ["token"],
+    mode: "any",
+    approvedTailnetPeers: [],
+    requireRequestSigning: false,
+  },
+  tailscaleStatusTtlSecs: 30,
+  dataDir: null,
+  daemonDataDir: null,
+  codexHomeOverride: null,
+  lowDiskSpaceThresholdMb: 1024,
+  eventCoalescingMs: {},
+  settingsSync: { enabled: false, sourceBackendId: null },
+  settingsUpdatedAtMs: 0,
+  webhooks: [],
+  notificationIntegrations: [],
+  hooks: [],
+  doNotDisturb: {
+    enabled: false,
+    windows: [],
+    suppressWhileScreenSharing: false,
+    allowUrgent: false,
+  },
+  focusFollowsActivity: { approvalRequested: "off", sessionFailed: "off" },
+  weeklyActivityReport: false,
+  redaction: { enabled: true, builtInDetectors: true, rules: [] },
+  remoteCommandAllowlist: [],
+  remoteCommandDenylist: [],
+  remoteCommandTimeoutSecs: 120,
+  sessionRetentionMaxAgeDays: null,
+  sessionRetentionMaxSizeMb: null,
+  maxConcurrentSessions: null,
   defaultAccessMode: "current",
   reviewDeliveryMode: "inline",
   composerModelShortcut: null,
@@ -105,6 +162,10 @@ const baseSettings: AppSettings = {
   cycleAgentPrevShortcut: null,
   cycleWorkspaceNextShortcut: null,
   cycleWorkspacePrevShortcut: null,
+  globalToggleWindowShortcut: null,
+  globalToggleDaemonShortcut: null,
+  globalStartSessionShortcut: null,
+  globalSessionTemplateId: null,
   lastComposerModelId: null,
   lastComposerReasoningEffort: null,
   uiScale: 1,
@@ -161,6 +222,9 @@ const baseSettings: AppSettings = {
   ],
   selectedOpenAppId: "vscode",
   globalWorktreesFolder: null,
+  editorLaunchers: [],
+  preferredEditor: null,
+  enabledPlugins: [],
 };
 
 const createDoctorResult = () => ({
@@ -1409,6 +1473,9 @@ describe("SettingsView Codex section", () => {
       fireEvent.change(screen.getByLabelText("New remote token"), {
         target: { value: "token-travel" },
       });
+      fireEvent.change(screen.getByLabelText("New remote daemon identity"), {
+        target: { value: "identity-travel" },
+      });
       fireEvent.click(screen.getByRole("button", { name: "Connect & add" }));
 
       await waitFor(() => {
@@ -1420,6 +1487,7 @@ describe("SettingsView Codex section", () => {
       expect(trialSettings.activeRemoteBackendId).toBeTruthy();
       expect(trialSettings.remoteBackendHost).toBe("travel-mac.tailnet.ts.net:4732");
       expect(trialSettings.remoteBackendToken).toBe("token-travel");
+      expect(trialSettings.remoteBackendIdentity).toBe("identity-travel");
       expect(connectedSettings.remoteBackends).toHaveLength(3);
       const connectedEntry = connectedSettings.remoteBackends.find(
         (entry) => entry.id === connectedSettings.activeRemoteBackendId,
@@ -1,8 +1,9 @@
-import { useMemo, useState } from "react";
+import { useEffect, useMemo, useState } from "react";
 import type { Dispatch, SetStateAction } from "react";
 import X from "lucide-react/dist/esm/icons/x";
 import type {
   AppSettings,
+  DaemonStopReason,
   TailscaleDaemonCommandPreview,
   TailscaleStatus,
   TcpDaemonStatus,
@@ -14,10 +15,20 @@ import {
   SettingsToggleSwitch,
 } from "@/features/design-system/components/settings/SettingsPrimitives";
 
+const DAEMON_STOP_REASON_LABELS: Record<DaemonStopReason, string> = {
+  user: "by request",
+  app_exit: "when the app quit",
+  data_dir_change: "to change the data directory",
+  update: "for an update",
+  crash: "after a crash",
+  exited: "on its own",
+};
+
 type AddRemoteBackendDraft = {
   name: string;
   host: string;
   token: string;
+  identity: string;
 };
 
 type SettingsServerSectionProps = {
@@ -36,6 +47,7 @@ type SettingsServerSectionProps = {
   remoteNameDraft: string;
   remoteHostDraft: string;
   remoteTokenDraft: string;
+  remoteIdentityDraft: string;
   nextRemoteNameSuggestion: string;
   tailscaleStatus: TailscaleStatus | null;
   tailscaleStatusBusy: boolean;
@@ -43,14 +55,18 @@ type SettingsServerSectionProps = {
   tailscaleCommandPreview: TailscaleDaemonCommandPreview | null;
   tailscaleCommandBusy: boolean;
   tailscaleCommandError: string | null;
+  daemonIdentity: string | null;
+  daemonIdentityError: string | null;
   tcpDaemonStatus: TcpDaemonStatus | null;
-  tcpDaemonBusyAction: "start" | "stop" | "status" | null;
+  tcpDaemonBusyAction: "start" | "stop" | "restart" | "update" | "status" | "firewall" | "service" | null;
   onSetRemoteNameDraft: Dispatch<SetStateAction<string>>;
   onSetRemoteHostDraft: Dispatch<SetStateAction<string>>;
   onSetRemoteTokenDraft: Dispatch<SetStateAction<string>>;
+  onSetRemoteIdentityDraft: Dispatch<SetStateAction<string>>;
   onCommitRemoteName: () => Promise<void>;
   onCommitRemoteHost: () => Promise<void>;
   onCommitRemoteToken: () => Promise<void>;
+  onCommitRemoteIdentity: () => Promise<void>;
   onSelectRemoteBackend: (id: string) => Promise<void>;
   onAddRemoteBackend: (draft: AddRemoteBackendDraft) => Promise<void>;
   onMoveRemoteBackend: (id: string, direction: "up" | "down") => Promise<void>;
@@ -60,7 +76,12 @@ type SettingsServerSectionProps = {
   onUseSuggestedTailscaleHost: () => Promise<void>;
   onTcpDaemonStart: () => Promise<void>;
   onTcpDaemonStop: () => Promise<void>;
+  onTcpDaemonRestart: () => Promise<void>;
+  onTcpDaemonApplyUpdate: () => Promise<void>;
   onTcpDaemonStatus: () => Promise<void>;
+  onTcpDaemonRegisterFirewall: () => Promise<void>;
+  onInstallDaemonService: () => Promise<void>;
+  onUninstallDaemonService: () => Promise<void>;
   onMobileConnectTest: () => void;
 };
 
@@ -80,6 +101,7 @@ export function SettingsServerSection({
   remoteNameDraft,
   remoteHostDraft,
   remoteTokenDraft,
+  remoteIdentityDraft,
   nextRemoteNameSuggestion,
   tailscaleStatus,
   tailscaleStatusBusy,
@@ -87,14 +109,18 @@ export function SettingsServerSection({
   tailscaleCommandPreview,
   tailscaleCommandBusy,
   tailscaleCommandError,
+  daemonIdentity,
+  daemonIdentityError,
   tcpDaemonStatus,
   tcpDaemonBusyAction,
   onSetRemoteNameDraft,
   onSetRemoteHostDraft,
   onSetRemoteTokenDraft,
+  onSetRemoteIdentityDraft,
   onCommitRemoteName,
   onCommitRemoteHost,
   onCommitRemoteToken,
+  onCommitRemoteIdentity,
   onSelectRemoteBackend,
   onAddRemoteBackend,
   onMoveRemoteBackend,
@@ -104,7 +130,12 @@ export function SettingsServerSection({
   onUseSuggestedTailscaleHost,
   onTcpDaemonStart,
   onTcpDaemonStop,
+  onTcpDaemonRestart,
+  onTcpDaemonApplyUpdate,
   onTcpDaemonStatus,
+  onTcpDaemonRegisterFirewall,
+  onInstallDaemonService,
+  onUninstallDaemonService,
   onMobileConnectTest,
 }: SettingsServerSectionProps) {
   const [pendingDeleteRemoteId, setPendingDeleteRemoteId] = useState<string | null>(
@@ -116,7 +147,65 @@ export function SettingsServerSection({
   const [addRemoteNameDraft, setAddRemoteNameDraft] = useState("");
   const [addRemoteHostDraft, setAddRemoteHostDraft] = useState("");
   const [addRemoteTokenDraft, setAddRemoteTokenDraft] = useState("");
+  const [addRemoteIdentityDraft, setAddRemoteIdentityDraft] = useState("");
+  const [httpBridgePortDraft, setHttpBridgePortDraft] = useState(
+    String(appSettings.daemonHttpBridgePort),
+  );
+  const [daemonDataDirDraft, setDaemonDataDirDraft] = useState(
+    appSettings.daemonDataDir ?? "",
+  );
+  const [commandAllowlistDraft, setCommandAllowlistDraft] = useState(
+    appSettings.remoteCommandAllowlist.join("\n"),
+  );
+  const [commandDenylistDraft, setCommandDenylistDraft] = useState(
+    appSettings.remoteCommandDenylist.join("\n"),
+  );
   const isMobileSimplified = isMobilePlatform;
+
+  useEffect(() => {
+    setHttpBridgePortDraft(String(appSettings.daemonHttpBridgePort));
+  }, [appSettings.daemonHttpBridgePort]);
+
+  useEffect(() => {
+    setDaemonDataDirDraft(appSettings.daemonDataDir ?? "");
+  }, [appSettings.daemonDataDir]);
+
+  useEffect(() => {
+    setCommandAllowlistDraft(appSettings.remoteCommandAllowlist.join("\n"));
+  }, [appSettings.remoteCommandAllowlist]);
+
+  useEffect(() => {
+    setCommandDenylistDraft(appSettings.remoteCommandDenylist.join("\n"));
+  }, [appSettings.remoteCommandDenylist]);
+
+  const commitHttpBridgePort = () => {
+    const port = Number(httpBridgePortDraft.trim());
+    if (!Number.isInteger(port) || port < 1 || port > 65535) {
+      setHttpBridgePortDraft(String(appSettings.daemonHttpBridgePort));
+      return;
+    }
+    if (port !== appSettings.daemonHttpBridgePort) {
+      void onUpdateAppSettings({ ...appSettings, daemonHttpBridgePort: port });
+    }
+  };
+  const commitDaemonDataDir = () => {
+    const next = daemonDataDirDraft.trim() || null;
+    if (next !== (appSettings.daemonDataDir ?? null)) {
+      void onUpdateAppSettings({ ...appSettings, daemonDataDir: next });
+    }
+  };
+  const commitCommandRules = (
+    key: "remoteCommandAllowlist" | "remoteCommandDenylist",
+    draft: string,
+  ) => {
+    const next = draft
+      .split("\n")
+      .map((rule) => rule.trim())
+      .filter(Boolean);
+    if (next.join("\n") !== appSettings[key].join("\n")) {
+      void onUpdateAppSettings({ ...appSettings, [key]: next });
+    }
+  };
   const pendingDeleteRemote = useMemo(
     () =>
       pendingDeleteRemoteId == null
@@ -128,6 +217,21 @@ export function SettingsServerSection({
     if (!tcpDaemonStatus) {
       return null;
     }
+    if (tcpDaemonStatus.operation === "starting") {
+      return "Mobile daemon is starting…";
+    }
+    if (tcpDaemonStatus.operation === "stopping") {
+      return "Mobile daemon is stopping…";
+    }
+    if (tcpDaemonStatus.operation === "restarting") {
+      return "Mobile daemon is restarting…";
+    }
+    if (tcpDaemonStatus.state === "starting") {
+      return "Mobile daemon is starting…";
+    }
+    if (tcpDaemonStatus.state === "stopping") {
+      return "Mobile daemon is stopping…";
+    }
     if (tcpDaemonStatus.state === "running") {
       return tcpDaemonStatus.pid
         ? `Mobile daemon is running (pid ${tcpDaemonStatus.pid}) on ${tcpDaemonStatus.listenAddr ?? "configured listen address"}.`
@@ -136,14 +240,42 @@ export function SettingsServerSection({
     if (tcpDaemonStatus.state === "error") {
       return tcpDaemonStatus.lastError ?? "Mobile daemon is in an error state.";
     }
-    return `Mobile daemon is stopped${tcpDaemonStatus.listenAddr ? ` (${tcpDaemonStatus.listenAddr})` : ""}.`;
+    const lastStop = tcpDaemonStatus.lastStop;
+    const stoppedBy = lastStop
+      ? ` ${DAEMON_STOP_REASON_LABELS[lastStop.reason]} at ${new Date(lastStop.atMs).toLocaleString()}`
+      : "";
+    return `Mobile daemon is stopped${stoppedBy}${tcpDaemonStatus.listenAddr ? ` (${tcpDaemonStatus.listenAddr})` : ""}.`;
   })();
+  const firewallTrust = tcpDaemonStatus?.firewallTrust ?? null;
+  const firewallTrustText = (() => {
+    switch (firewallTrust) {
+      case "allowed":
+        return "macOS firewall: daemon is allowed to accept connections.";
+      case "blocked":
+        return "macOS firewall: daemon is blocked from accepting connections.";
+      case "unregistered":
+        return "macOS firewall: daemon has no rule yet, so macOS will ask again after each update.";
+      case "block_all":
+        return "macOS firewall is blocking all incoming connections.";
+      case "unknown":
+        return "macOS firewall state could not be read.";
+      default:
+        return null;
+    }
+  })();
+  const serviceManager = tcpDaemonStatus?.serviceManager ?? null;
+  const serviceStatusText = serviceManager
+    ? `Installed as a ${serviceManager} service; the daemon keeps running after the app quits.`
+    : "Install as a background service to keep the daemon running after the app quits.";
+  const canRegisterFirewall =
+    firewallTrust === "blocked" || firewallTrust === "unregistered";
 
   const openAddRemoteModal = () => {
     setAddRemoteError(null);
     setAddRemoteNameDraft(nextRemoteNameSuggestion);
     setAddRemoteHostDraft(remoteHostDraft);
     setAddRemoteTokenDraft("");
+    setAddRemoteIdentityDraft("");
     setAddRemoteOpen(true);
   };
 
@@ -167,6 +299,7 @@ export function SettingsServerSection({
           name: addRemoteNameDraft,
           host: addRemoteHostDraft,
           token: addRemoteTokenDraft,
+          identity: addRemoteIdentityDraft,
         });
         setAddRemoteOpen(false);
       } catch (error) {
@@ -350,6 +483,173 @@ export function SettingsServerSection({
           </SettingsToggleRow>
         )}
 
+        {!isMobileSimplified && (
+          <SettingsToggleRow
+            title="Approve new clients"
+            subtitle="Asks here before a device connecting with the token for the first time gets access. Denied devices stay blocked."
+          >
+            <SettingsToggleSwitch
+              pressed={appSettings.requireClientApproval}
+              onClick={() =>
+                void onUpdateAppSettings({
+                  ...appSettings,
+                  requireClientApproval: !appSettings.requireClientApproval,
+                })
+              }
+            />
+          </SettingsToggleRow>
+        )}
+
+        {!isMobileSimplified && (
+          <SettingsToggleRow
+            title="Confirm destructive remote actions"
+            subtitle="Asks here before a remote client terminates a session, runs a command, or rewrites the global Codex config. Unanswered requests are rejected."
+          >
+            <SettingsToggleSwitch
+              pressed={appSettings.confirmRemoteActions}
+              onClick={() =>
+                void onUpdateAppSettings({
+                  ...appSettings,
+                  confirmRemoteActions: !appSettings.confirmRemoteActions,
+                })
+              }
+            />
+          </SettingsToggleRow>
+        )}
+
+        {!isMobileSimplified && (
+          <div className="settings-field">
+            <label className="settings-field-label" htmlFor="remote-command-allowlist">
+              Allowed remote commands
+            </label>
+            <div className="settings-help">
+              One command per line. A remote command runs only when it matches a line exactly,
+              arguments included: <code>git pull</code> allows <code>git pull</code> and nothing
+              else. End a line with <code>*</code> to allow any further arguments, as in{" "}
+              <code>npm test *</code>. Leave empty to turn remote commands off.
+            </div>
+            <textarea
+              id="remote-command-allowlist"
+              className="settings-agents-textarea"
+              value={commandAllowlistDraft}
+              placeholder={"git status\nnpm test *"}
+              onChange={(event) => setCommandAllowlistDraft(event.target.value)}
+              onBlur={() =>
+                commitCommandRules("remoteCommandAllowlist", commandAllowlistDraft)
+              }
+              spellCheck={false}
+            />
+          </div>
+        )}
+
+        {!isMobileSimplified && (
+          <div className="settings-field">
+            <label className="settings-field-label" htmlFor="remote-command-denylist">
+              Blocked remote commands
+            </label>
+            <div className="settings-help">
+              One command per line. A line blocks the command with any further arguments and
+              wins over the allowed commands.
+            </div>
+            <textarea
+              id="remote-command-denylist"
+              className="settings-agents-textarea"
+              value={commandDenylistDraft}
+              placeholder="git push --force"
+              onChange={(event) => setCommandDenylistDraft(event.target.value)}
+              onBlur={() =>
+                commitCommandRules("remoteCommandDenylist", commandDenylistDraft)
+              }
+              spellCheck={false}
+            />
+          </div>
+        )}
+
+        {!isMobileSimplified && (
+          <SettingsToggleRow
+            title="Local HTTP bridge"
+            subtitle="Lets scripts and shortcuts call daemon methods at http://127.0.0.1:<port>/v1/<method> with an Authorization: Bearer token. Restart the daemon to apply."
+          >
+            <SettingsToggleSwitch
+              pressed={appSettings.daemonHttpBridgeEnabled}
+              onClick={() =>
+                void onUpdateAppSettings({
+                  ...appSettings,
+                  daemonHttpBridgeEnabled: !appSettings.daemonHttpBridgeEnabled,
+                })
+              }
+            />
+          </SettingsToggleRow>
+        )}
+
+        {!isMobileSimplified && (
+          <SettingsToggleRow
+            title="Mock backends"
+            subtitle="Replaces Tailscale and the daemon with in-app fakes for UI development. Takes effect on the next launch."
+          >
+            <SettingsToggleSwitch
+              pressed={appSettings.mockBackends}
+              onClick={() =>
+                void onUpdateAppSettings({
+                  ...appSettings,
+                  mockBackends: !appSettings.mockBackends,
+                })
+              }
+            />
+          </SettingsToggleRow>
+        )}
+
+        {!isMobileSimplified && appSettings.daemonHttpBridgeEnabled && (
+          <div className="settings-field">
+            <label className="settings-field-label" htmlFor="daemon-http-bridge-port">
+              HTTP bridge port
+            </label>
+            <input
+              id="daemon-http-bridge-port"
+              type="number"
+              min={1}
+              max={65535}
+              className="settings-input settings-input--compact"
+              value={httpBridgePortDraft}
+              onChange={(event) => setHttpBridgePortDraft(event.target.value)}
+              onBlur={commitHttpBridgePort}
+              onKeyDown={(event) => {
+                if (event.key === "Enter") {
+                  event.preventDefault();
+                  commitHttpBridgePort();
+                }
+              }}
+            />
+          </div>
+        )}
+
+        {!isMobileSimplified && (
+          <div className="settings-field">
+            <label className="settings-field-label" htmlFor="daemon-data-dir">
+              Daemon data directory
+            </label>
+            <input
+              id="daemon-data-dir"
+              className="settings-input settings-input--compact"
+              value={daemonDataDirDraft}
+              placeholder="daemon"
+              onChange={(event) => setDaemonDataDirDraft(event.target.value)}
+              onBlur={commitDaemonDataDir}
+              onKeyDown={(event) => {
+                if (event.key === "Enter") {
+                  event.preventDefault();
+                  commitDaemonDataDir();
+                }
+              }}
+            />
+            <div className="settings-help">
+              Where the daemon keeps its identity and client approvals, relative to the app data
+              directory unless absolute. Workspaces and settings stay in the app data directory.
+              Restart the daemon to apply.
+            </div>
+          </div>
+        )}
+
         <div className="settings-field">
           <div className="settings-field-label">Remote backend</div>
           <div className="settings-field-row">
@@ -386,12 +686,29 @@ export function SettingsServerSection({
               }}
               aria-label="Remote backend token"
             />
+            <input
+              type="password"
+              className="settings-input settings-input--compact"
+              value={remoteIdentityDraft}
+              placeholder="Daemon identity (required)"
+              onChange={(event) => onSetRemoteIdentityDraft(event.target.value)}
+              onBlur={() => {
+                void onCommitRemoteIdentity();
+              }}
+              onKeyDown={(event) => {
+                if (event.key === "Enter") {
+                  event.preventDefault();
+                  void onCommitRemoteIdentity();
+                }
+              }}
+              aria-label="Remote daemon identity"
+            />
           </div>
           {remoteHostError && <div className="settings-help settings-help-error">{remoteHostError}</div>}
           <div className="settings-help">
             {isMobileSimplified
-              ? "Use the Tailscale host from your desktop CodexMonitor app (Server section), for example `macbook.your-tailnet.ts.net:4732`."
-              : "This host/token is used by mobile clients and desktop remote-mode testing."}
+              ? "Use the Tailscale host from your desktop CodexMonitor app (Server section), for example `macbook.your-tailnet.ts.net:4732`. Copy the daemon identity from there too; the token is only sent to a daemon that proves it holds it."
+              : "This host/token is used by mobile clients and desktop remote-mode testing. The token is only sent once the daemon proves it holds the identity."}
           </div>
         </div>
 
@@ -444,6 +761,16 @@ export function SettingsServerSection({
               >
                 {tcpDaemonBusyAction === "stop" ? "Stopping..." : "Stop daemon"}
               </button>
+              <button
+                type="button"
+                className="button settings-button-compact"
+                onClick={() => {
+                  void onTcpDaemonRestart();
+                }}
+                disabled={tcpDaemonBusyAction !== null}
+              >
+                {tcpDaemonBusyAction === "restart" ? "Restarting..." : "Restart daemon"}
+              </button>
               <button
                 type="button"
                 className="button settings-button-compact"
@@ -456,6 +783,64 @@ export function SettingsServerSection({
               </button>
             </div>
             {tcpRunnerStatusText && <div className="settings-help">{tcpRunnerStatusText}</div>}
+            {tcpDaemonStatus?.updatePending && (
+              <div className="settings-field-row">
+                <div className="settings-help">
+                  The daemon is running an older build than this app. Apply the update when no
+                  mobile client is busy; connected clients are drained first.
+                </div>
+                <button
+                  type="button"
+                  className="button settings-button-compact"
+                  onClick={() => {
+                    void onTcpDaemonApplyUpdate();
+                  }}
+                  disabled={tcpDaemonBusyAction !== null}
+                >
+                  {tcpDaemonBusyAction === "update" ? "Updating..." : "Apply update"}
+                </button>
+              </div>
+            )}
+            {firewallTrustText && (
+              <div className="settings-field-row">
+                <div className="settings-help">{firewallTrustText}</div>
+                {canRegisterFirewall && (
+                  <button
+                    type="button"
+                    className="button settings-button-compact"
+                    onClick={() => {
+                      void onTcpDaemonRegisterFirewall();
+                    }}
+                    disabled={tcpDaemonBusyAction !== null}
+                  >
+                    {tcpDaemonBusyAction === "firewall"
+                      ? "Allowing..."
+                      : "Allow through firewall"}
+                  </button>
+                )}
+              </div>
+            )}
+            <div className="settings-field-row">
+              <div className="settings-help">{serviceStatusText}</div>
+              <button
+                type="button"
+                className="button settings-button-compact"
+                onClick={() => {
+                  void (serviceManager
+                    ? onUninstallDaemonService()
+                    : onInstallDaemonService());
+                }}
+                disabled={tcpDaemonBusyAction !== null}
+              >
+                {tcpDaemonBusyAction === "service"
+                  ? serviceManager
+                    ? "Uninstalling..."
+                    : "Installing..."
+                  : serviceManager
+                    ? "Uninstall service"
+                    : "Install service"}
+              </button>
+            </div>
             {tcpDaemonStatus?.startedAtMs && (
               <div className="settings-help">
                 Started at: {new Date(tcpDaemonStatus.startedAtMs).toLocaleString()}
@@ -505,6 +890,15 @@ export function SettingsServerSection({
             {tailscaleStatus && (
               <>
                 <div className="settings-help">{tailscaleStatus.message}</div>
+                {tailscaleStatus.stale && (
+                  <div className="settings-help">
+                    Showing the last known Tailscale details
+                    {tailscaleStatus.lastSeenMs
+                      ? ` (seen ${new Date(tailscaleStatus.lastSeenMs).toLocaleString()})`
+                      : ""}
+                    .
+                  </div>
+                )}
                 <div className="settings-help">
                   {tailscaleStatus.installed
                     ? `Version: ${tailscaleStatus.version ?? "unknown"}`
@@ -520,6 +914,24 @@ export function SettingsServerSection({
                     Tailnet: <code>{tailscaleStatus.tailnetName}</code>
                   </div>
                 )}
+                {tailscaleStatus.tags.length > 0 && (
+                  <div className="settings-help">
+                    Tags: <code>{tailscaleStatus.tags.join(", ")}</code>
+                  </div>
+                )}
+                {tailscaleStatus.keyExpiryMs !== null && (
+                  <div
+                    className={`settings-help${
+                      tailscaleStatus.keyExpiringSoon ? " settings-help-error" : ""
+                    }`}
+                  >
+                    Node key {tailscaleStatus.keyExpiryMs <= Date.now() ? "expired" : "expires"}{" "}
+                    {new Date(tailscaleStatus.keyExpiryMs).toLocaleString()}
+                    {tailscaleStatus.keyExpiringSoon
+                      ? ". Re-authenticate this device or disable key expiry so remote access keeps working."
+                      : "."}
+                  </div>
+                )}
               </>
             )}
             {tailscaleCommandError && (
@@ -528,7 +940,8 @@ export function SettingsServerSection({
             {tailscaleCommandPreview && (
               <>
                 <div className="settings-help">
-                  Command template (manual fallback) for starting the daemon:
+                  Command template (manual fallback) for starting the daemon
+                  {tailscaleCommandPreview.shell === "powershell" ? " in PowerShell" : ""}:
                 </div>
                 <pre className="settings-command-preview">
                   <code>{tailscaleCommandPreview.command}</code>
@@ -540,6 +953,20 @@ export function SettingsServerSection({
                 )}
               </>
             )}
+            {daemonIdentityError && (
+              <div className="settings-help settings-help-error">{daemonIdentityError}</div>
+            )}
+            {daemonIdentity && (
+              <>
+                <div className="settings-help">
+                  Daemon identity. Paste it with the token on each device that connects to this
+                  daemon:
+                </div>
+                <pre className="settings-command-preview">
+                  <code>{daemonIdentity}</code>
+                </pre>
+              </>
+            )}
           </div>
         )}
       </>
@@ -604,6 +1031,20 @@ export function SettingsServerSection({
               value={addRemoteTokenDraft}
               placeholder="Token"
               onChange={(event) => setAddRemoteTokenDraft(event.target.value)}
+              disabled={addRemoteBusy}
+            />
+          </div>
+          <div className="settings-field">
+            <label className="settings-field-label" htmlFor="settings-add-remote-identity">
+              New remote daemon identity
+            </label>
+            <input
+              id="settings-add-remote-identity"
+              type="password"
+              className="settings-input settings-input--compact"
+              value={addRemoteIdentityDraft}
+              placeholder="From Settings > Server on the daemon's host"
+              onChange={(event) => setAddRemoteIdentityDraft(event.target.value)}
               onKeyDown={(event) => {
                 if (event.key === "Enter") {
                   event.preventDefault();
@@ -8,6 +8,7 @@ import {
   runCodexDoctor,
   updateAppSettings,
 } from "@services/tauri";
+import { subscribeSettingsSynced } from "@services/events";
 import { UI_SCALE_DEFAULT, UI_SCALE_MAX } from "@utils/uiScale";
 
 vi.mock("@services/tauri", () => ({
@@ -16,9 +17,14 @@ vi.mock("@services/tauri", () => ({
   runCodexDoctor: vi.fn(),
 }));
 
+vi.mock("@services/events", () => ({
+  subscribeSettingsSynced: vi.fn(() => () => {}),
+}));
+
 const getAppSettingsMock = vi.mocked(getAppSettings);
 const updateAppSettingsMock = vi.mocked(updateAppSettings);
 const runCodexDoctorMock = vi.mocked(runCodexDoctor);
+const subscribeSettingsSyncedMock = vi.mocked(subscribeSettingsSynced);
 
 describe("useAppSettings", () => {
   beforeEach(() => {
@@ -159,4 +165,20 @@ describe("useAppSettings", () => {
       response,
     );
   });
+
+  it("applies settings synced from another instance", async () => {
+    getAppSettingsMock.mockResolvedValue({ theme: "light" } as AppSettings);
+    const { result } = renderHook(() => useAppSettings());
+
+    await waitFor(() => expect(result.current.isLoading).toBe(false));
+    expect(result.current.settings.theme).toBe("light");
+
+    const onSynced = subscribeSettingsSyncedMock.mock.calls[0][0];
+    act(() => {
+      onSynced({ ...result.current.settings, theme: "dark", uiScale: 99 });
+    });
+
+    expect(result.current.settings.theme).toBe("dark");
+    expect(result.current.settings.uiScale).toBe(UI_SCALE_MAX);
+  });
 });
@@ -1,6 +1,7 @@
 import { useCallback, useEffect, useMemo, useState } from "react";
 import type { AppSettings } from "@/types";
 import { getAppSettings, runCodexDoctor, updateAppSettings } from "@services/tauri";
+import { subscribeSettingsSynced } from "@services/events";
 import { clampUiScale, UI_SCALE_DEFAULT } from "@utils/uiScale";
 import { CHAT_SCROLLBACK_DEFAULT, normalizeChatHistoryScrollbackItems } from "@utils/chatScrollback";
 import {
@@ -27,6 +28,14 @@ const DEFAULT_REMOTE_BACKEND_HOST = "127.0.0.1:4732";
 const DEFAULT_REMOTE_BACKEND_ID = "remote-default";
 const DEFAULT_REMOTE_BACKEND_NAME = "Primary remote";
 const DEFAULT_REMOTE_PROVIDER: AppSettings["remoteBackendProvider"] = "tcp";
+const DEFAULT_PROCESS_ENV: AppSettings["codexEnv"] = {
+  vars: {},
+  pathPrepend: [],
+  httpProxy: null,
+  httpsProxy: null,
+  noProxy: null,
+  keychainVars: {},
+};
 
 type RemoteBackendTarget = AppSettings["remoteBackends"][number];
 
@@ -53,10 +62,12 @@ function normalizeRemoteBackends(settings: AppSettings): {
   remoteBackendProvider: AppSettings["remoteBackendProvider"];
   remoteBackendHost: string;
   remoteBackendToken: string | null;
+  remoteBackendIdentity: string | null;
 } {
   const legacyProvider = normalizeRemoteProvider(settings.remoteBackendProvider);
   const legacyHost = normalizeRemoteHost(settings.remoteBackendHost);
   const legacyToken = normalizeRemoteToken(settings.remoteBackendToken);
+  const legacyIdentity = normalizeRemoteToken(settings.remoteBackendIdentity);
   const usedIds = new Set<string>();
 
   const normalized = (settings.remoteBackends ?? []).map((entry, index) => {
@@ -74,6 +85,7 @@ function normalizeRemoteBackends(settings: AppSettings): {
       provider: normalizeRemoteProvider(entry.provider),
       host: normalizeRemoteHost(entry.host),
       token: normalizeRemoteToken(entry.token),
+      identity: normalizeRemoteToken(entry.identity),
       lastConnectedAtMs:
         typeof entry.lastConnectedAtMs === "number" && Number.isFinite(entry.lastConnectedAtMs)
           ? entry.lastConnectedAtMs
@@ -88,6 +100,7 @@ function normalizeRemoteBackends(settings: AppSettings): {
       provider: legacyProvider,
       host: legacyHost,
       token: legacyToken,
+      identity: legacyIdentity,
       lastConnectedAtMs: null,
     };
     return {
@@ -96,6 +109,7 @@ function normalizeRemoteBackends(settings: AppSettings): {
       remoteBackendProvider: fallback.provider,
       remoteBackendHost: fallback.host,
       remoteBackendToken: fallback.token,
+      remoteBackendIdentity: fallback.identity ?? null,
     };
   }
 
@@ -110,6 +124,7 @@ function normalizeRemoteBackends(settings: AppSettings): {
     provider: legacyProvider,
     host: legacyHost,
     token: legacyToken,
+    identity: legacyIdentity,
   };
   const remoteBackends = [...normalized];
   remoteBackends[activeIndex] = syncedActive;
@@ -119,6 +134,7 @@ function normalizeRemoteBackends(settings: AppSettings): {
     remoteBackendProvider: syncedActive.provider,
     remoteBackendHost: syncedActive.host,
     remoteBackendToken: syncedActive.token,
+    remoteBackendIdentity: syncedActive.identity,
   };
 }
 
@@ -131,18 +147,68 @@ function buildDefaultSettings(): AppSettings {
     provider: DEFAULT_REMOTE_PROVIDER,
     host: DEFAULT_REMOTE_BACKEND_HOST,
     token: null,
+    identity: null,
     lastConnectedAtMs: null,
   };
   return {
     codexBin: null,
     codexArgs: null,
+    codexEnv: { ...DEFAULT_PROCESS_ENV },
     backendMode: isMobile ? "remote" : "local",
     remoteBackendProvider: defaultRemote.provider,
     remoteBackendHost: defaultRemote.host,
     remoteBackendToken: null,
+    remoteBackendIdentity: null,
     remoteBackends: [defaultRemote],
     activeRemoteBackendId: defaultRemote.id,
+    proxy: { url: null, bypass: [] },
+    rpcTimeouts: { connectMs: null, fastMs: null, normalMs: null, longMs: null },
+    remoteSlowCallMs: 2000,
     keepDaemonRunningAfterAppClose: false,
+    daemonEnv: { ...DEFAULT_PROCESS_ENV },
+    daemonHttpBridgeEnabled: false,
+    daemonHttpBridgePort: 4733,
+    requireClientApproval: false,
+    confirmRemoteActions: false,
+    remoteActionConfirmTimeoutSecs: 60,
+    mockBackends: false,
+    daemonAuth: {
+      providers: ["token"],
+      mode: "any",
+      approvedTailnetPeers: [],
+      requireRequestSigning: false,
+    },
+    tailscaleStatusTtlSecs: 30,
+    dataDir: null,
+    daemonDataDir: null,
+    codexHomeOverride: null,
+    lowDiskSpaceThresholdMb: 1024,
+    eventCoalescingMs: {
+      "tcp-daemon-status-changed": 250,
+      "tailscale-status-changed": 250,
+      "dictation-download": 250,
+      "codex-session-queue": 250,
+    },
+    settingsSync: { enabled: false, sourceBackendId: null },
+    settingsUpdatedAtMs: 0,
+    webhooks: [],
+    notificationIntegrations: [],
+    hooks: [],
+    doNotDisturb: {
+      enabled: false,
+      windows: [],
+      suppressWhileScreenSharing: false,
+      allowUrgent: false,
+    },
+    focusFollowsActivity: { approvalRequested: "off", sessionFailed: "off" },
+    weeklyActivityReport: false,
+    redaction: { enabled: true, builtInDetectors: true, rules: [] },
+    remoteCommandAllowlist: [],
+    remoteCommandDenylist: [],
+    remoteCommandTimeoutSecs: 120,
+    sessionRetentionMaxAgeDays: null,
+    sessionRetentionMaxSizeMb: null,
+    maxConcurrentSessions: null,
     defaultAccessMode: "current",
     reviewDeliveryMode: "inline",
     composerModelShortcut: isMac ? "cmd+shift+m" : "ctrl+shift+m",
@@ -163,6 +229,10 @@ function buildDefaultSettings(): AppSettings {
     cycleAgentPrevShortcut: isMac ? "cmd+ctrl+up" : "ctrl+alt+up",
     cycleWorkspaceNextShortcut: isMac ? "cmd+shift+down" : "ctrl+alt+shift+down",
     cycleWorkspacePrevShortcut: isMac ? "cmd+shift+up" : "ctrl+alt+shift+up",
+    globalToggleWindowShortcut: null,
+    globalToggleDaemonShortcut: null,
+    globalStartSessionShortcut: null,
+    globalSessionTemplateId: null,
     lastComposerModelId: null,
     lastComposerReasoningEffort: null,
     uiScale: UI_SCALE_DEFAULT,
@@ -208,6 +278,9 @@ function buildDefaultSettings(): AppSettings {
     openAppTargets: DEFAULT_OPEN_APP_TARGETS,
     selectedOpenAppId: DEFAULT_OPEN_APP_ID,
     globalWorktreesFolder: null,
+    editorLaunchers: [],
+    preferredEditor: null,
+    enabledPlugins: [],
   };
 }
 
@@ -310,6 +383,19 @@ export function useAppSettings() {
     };
   }, [defaultSettings]);
 
+  useEffect(
+    () =>
+      subscribeSettingsSynced((synced) => {
+        setSettings(
+          normalizeAppSettings({
+            ...defaultSettings,
+            ...synced,
+          }),
+        );
+      }),
+    [defaultSettings],
+  );
+
   const saveSettings = useCallback(async (next: AppSettings) => {
     const normalized = normalizeAppSettings(next);
     const saved = await updateAppSettings(normalized);
@@ -1,5 +1,6 @@
 import { useCallback, useEffect, useMemo, useRef, useState } from "react";
 import type { Dispatch, SetStateAction } from "react";
+import { ask } from "@tauri-apps/plugin-dialog";
 import type {
   AppSettings,
   TailscaleDaemonCommandPreview,
@@ -7,13 +8,21 @@ import type {
   TcpDaemonStatus,
 } from "@/types";
 import {
+  CommandError,
   listWorkspaces,
   tailscaleDaemonCommandPreview as fetchTailscaleDaemonCommandPreview,
+  tailscaleDaemonIdentity,
   tailscaleDaemonStart,
   tailscaleDaemonStatus,
+  tailscaleDaemonRegisterFirewall,
+  installDaemonService,
+  uninstallDaemonService,
   tailscaleDaemonStop,
+  tailscaleDaemonRestart,
+  tailscaleDaemonApplyUpdate,
   tailscaleStatus as fetchTailscaleStatus,
 } from "@services/tauri";
+import { subscribeTailscaleStatusChanged } from "@services/events";
 import { isMobilePlatform } from "@utils/platformPaths";
 import { DEFAULT_REMOTE_HOST } from "@settings/components/settingsViewConstants";
 
@@ -27,6 +36,7 @@ export type AddRemoteBackendDraft = {
   name: string;
   host: string;
   token: string;
+  identity: string;
 };
 
 export type SettingsServerSectionProps = {
@@ -45,6 +55,7 @@ export type SettingsServerSectionProps = {
   remoteNameDraft: string;
   remoteHostDraft: string;
   remoteTokenDraft: string;
+  remoteIdentityDraft: string;
   nextRemoteNameSuggestion: string;
   tailscaleStatus: TailscaleStatus | null;
   tailscaleStatusBusy: boolean;
@@ -52,14 +63,18 @@ export type SettingsServerSectionProps = {
   tailscaleCommandPreview: TailscaleDaemonCommandPreview | null;
   tailscaleCommandBusy: boolean;
   tailscaleCommandError: string | null;
+  daemonIdentity: string | null;
+  daemonIdentityError: string | null;
   tcpDaemonStatus: TcpDaemonStatus | null;
-  tcpDaemonBusyAction: "start" | "stop" | "status" | null;
+  tcpDaemonBusyAction: "start" | "stop" | "restart" | "update" | "status" | "firewall" | "service" | null;
   onSetRemoteNameDraft: Dispatch<SetStateAction<string>>;
   onSetRemoteHostDraft: Dispatch<SetStateAction<string>>;
   onSetRemoteTokenDraft: Dispatch<SetStateAction<string>>;
+  onSetRemoteIdentityDraft: Dispatch<SetStateAction<string>>;
   onCommitRemoteName: () => Promise<void>;
   onCommitRemoteHost: () => Promise<void>;
   onCommitRemoteToken: () => Promise<void>;
+  onCommitRemoteIdentity: () => Promise<void>;
   onSelectRemoteBackend: (id: string) => Promise<void>;
   onAddRemoteBackend: (draft: AddRemoteBackendDraft) => Promise<void>;
   onMoveRemoteBackend: (id: string, direction: "up" | "down") => Promise<void>;
@@ -69,7 +84,12 @@ export type SettingsServerSectionProps = {
   onUseSuggestedTailscaleHost: () => Promise<void>;
   onTcpDaemonStart: () => Promise<void>;
   onTcpDaemonStop: () => Promise<void>;
+  onTcpDaemonRestart: () => Promise<void>;
+  onTcpDaemonApplyUpdate: () => Promise<void>;
   onTcpDaemonStatus: () => Promise<void>;
+  onTcpDaemonRegisterFirewall: () => Promise<void>;
+  onInstallDaemonService: () => Promise<void>;
+  onUninstallDaemonService: () => Promise<void>;
   onMobileConnectTest: () => void;
 };
 
@@ -100,6 +120,7 @@ const buildFallbackRemoteBackend = (settings: AppSettings): RemoteBackendTarget
   provider: "tcp",
   host: settings.remoteBackendHost,
   token: settings.remoteBackendToken,
+  identity: settings.remoteBackendIdentity ?? null,
   lastConnectedAtMs: null,
 });
 
@@ -120,9 +141,17 @@ const validateRemoteHost = (value: string): string | null => {
   if (!trimmed) {
     return "Host is required.";
   }
+  if (/^wss?:\/\//i.test(trimmed)) {
+    try {
+      new URL(trimmed);
+      return null;
+    } catch {
+      return "Enter a valid WebSocket URL (for example `wss://macbook.tailnet.ts.net/`).";
+    }
+  }
   const match = trimmed.match(/^([^:\s]+|\[[^\]]+\]):([0-9]{1,5})$/);
   if (!match) {
-    return "Use host:port (for example `macbook.tailnet.ts.net:4732`).";
+    return "Use host:port or a ws:// / wss:// URL (for example `macbook.tailnet.ts.net:4732`).";
   }
   const port = Number(match[2]);
   if (!Number.isInteger(port) || port < 1 || port > 65535) {
@@ -151,6 +180,9 @@ export const useSettingsServerSection = ({
   const [remoteNameDraft, setRemoteNameDraft] = useState(initialActiveRemoteBackend.name);
   const [remoteHostDraft, setRemoteHostDraft] = useState(initialActiveRemoteBackend.host);
   const [remoteTokenDraft, setRemoteTokenDraft] = useState(initialActiveRemoteBackend.token ?? "");
+  const [remoteIdentityDraft, setRemoteIdentityDraft] = useState(
+    initialActiveRemoteBackend.identity ?? "",
+  );
   const [remoteStatusText, setRemoteStatusText] = useState<string | null>(null);
   const [remoteStatusError, setRemoteStatusError] = useState(false);
   const [remoteNameError, setRemoteNameError] = useState<string | null>(null);
@@ -162,9 +194,11 @@ export const useSettingsServerSection = ({
     useState<TailscaleDaemonCommandPreview | null>(null);
   const [tailscaleCommandBusy, setTailscaleCommandBusy] = useState(false);
   const [tailscaleCommandError, setTailscaleCommandError] = useState<string | null>(null);
+  const [daemonIdentity, setDaemonIdentity] = useState<string | null>(null);
+  const [daemonIdentityError, setDaemonIdentityError] = useState<string | null>(null);
   const [tcpDaemonStatus, setTcpDaemonStatus] = useState<TcpDaemonStatus | null>(null);
   const [tcpDaemonBusyAction, setTcpDaemonBusyAction] = useState<
-    "start" | "stop" | "status" | null
+    "start" | "stop" | "restart" | "update" | "status" | "firewall" | "service" | null
   >(null);
   const [mobileConnectBusy, setMobileConnectBusy] = useState(false);
   const [mobileConnectStatusText, setMobileConnectStatusText] = useState<string | null>(null);
@@ -187,6 +221,7 @@ export const useSettingsServerSection = ({
     setRemoteNameDraft(activeRemoteBackend.name);
     setRemoteHostDraft(activeRemoteBackend.host);
     setRemoteTokenDraft(activeRemoteBackend.token ?? "");
+    setRemoteIdentityDraft(activeRemoteBackend.identity ?? "");
     setRemoteNameError(null);
     setRemoteHostError(null);
   }, [activeRemoteBackend]);
@@ -200,6 +235,7 @@ export const useSettingsServerSection = ({
     provider: "tcp",
     host: entry.host?.trim() || DEFAULT_REMOTE_HOST,
     token: entry.token?.trim() ? entry.token.trim() : null,
+    identity: entry.identity?.trim() ? entry.identity.trim() : null,
     lastConnectedAtMs:
       typeof entry.lastConnectedAtMs === "number" && Number.isFinite(entry.lastConnectedAtMs)
         ? entry.lastConnectedAtMs
@@ -226,6 +262,7 @@ export const useSettingsServerSection = ({
         remoteBackendProvider: "tcp",
         remoteBackendHost: active.host,
         remoteBackendToken: active.token,
+        remoteBackendIdentity: active.identity ?? null,
         ...(mobilePlatform
           ? {
               backendMode: "remote",
@@ -247,6 +284,7 @@ export const useSettingsServerSection = ({
       const unchanged =
         nextSettings.remoteBackendHost === latestSettings.remoteBackendHost &&
         nextSettings.remoteBackendToken === latestSettings.remoteBackendToken &&
+        nextSettings.remoteBackendIdentity === latestSettings.remoteBackendIdentity &&
         nextSettings.backendMode === latestSettings.backendMode &&
         nextSettings.remoteBackendProvider === latestSettings.remoteBackendProvider &&
         nextSettings.activeRemoteBackendId === latestSettings.activeRemoteBackendId &&
@@ -329,6 +367,13 @@ export const useSettingsServerSection = ({
     setRemoteStatus("Remote token saved.");
   };
 
+  const handleCommitRemoteIdentity = async () => {
+    const nextIdentity = remoteIdentityDraft.trim() ? remoteIdentityDraft.trim() : null;
+    setRemoteIdentityDraft(nextIdentity ?? "");
+    await updateActiveRemoteBackend({ identity: nextIdentity });
+    setRemoteStatus("Daemon identity saved.");
+  };
+
   const handleSelectRemoteBackend = async (id: string) => {
     const latestSettings = latestSettingsRef.current;
     const candidates = getConfiguredRemoteBackends(latestSettings);
@@ -369,6 +414,12 @@ export const useSettingsServerSection = ({
       setRemoteStatus(message, true);
       throw new Error(message);
     }
+    const nextIdentity = draft.identity.trim() ? draft.identity.trim() : null;
+    if (!nextIdentity) {
+      const message = "Daemon identity is required.";
+      setRemoteStatus(message, true);
+      throw new Error(message);
+    }
 
     const nextId = createRemoteBackendId();
     const nextRemote: RemoteBackendTarget = {
@@ -377,6 +428,7 @@ export const useSettingsServerSection = ({
       provider: "tcp",
       host: nextHost,
       token: nextToken,
+      identity: nextIdentity,
       lastConnectedAtMs: null,
     };
 
@@ -481,12 +533,19 @@ export const useSettingsServerSection = ({
     void (async () => {
       const nextToken = remoteTokenDraft.trim() ? remoteTokenDraft.trim() : null;
       setRemoteTokenDraft(nextToken ?? "");
+      const nextIdentity = remoteIdentityDraft.trim() ? remoteIdentityDraft.trim() : null;
+      setRemoteIdentityDraft(nextIdentity ?? "");
 
       if (!nextToken) {
         setMobileConnectStatusError(true);
         setMobileConnectStatusText("Remote backend token is required.");
         return;
       }
+      if (!nextIdentity) {
+        setMobileConnectStatusError(true);
+        setMobileConnectStatusText("Daemon identity is required.");
+        return;
+      }
 
       const hostError = validateRemoteHost(remoteHostDraft);
       if (hostError) {
@@ -505,6 +564,7 @@ export const useSettingsServerSection = ({
         await updateActiveRemoteBackend({
           host: nextHost,
           token: nextToken,
+          identity: nextIdentity,
         });
 
         const workspaces = await listWorkspaces();
@@ -536,14 +596,14 @@ export const useSettingsServerSection = ({
     }
     setMobileConnectStatusText(null);
     setMobileConnectStatusError(false);
-  }, [mobilePlatform, remoteHostDraft, remoteTokenDraft]);
+  }, [mobilePlatform, remoteHostDraft, remoteTokenDraft, remoteIdentityDraft]);
 
-  const handleRefreshTailscaleStatus = useCallback(() => {
+  const loadTailscaleStatus = useCallback((forceRefresh: boolean) => {
     void (async () => {
       setTailscaleStatusBusy(true);
       setTailscaleStatusError(null);
       try {
-        const status = await fetchTailscaleStatus();
+        const status = await fetchTailscaleStatus(forceRefresh);
         setTailscaleStatus(status);
       } catch (error) {
         setTailscaleStatusError(
@@ -555,6 +615,17 @@ export const useSettingsServerSection = ({
     })();
   }, []);
 
+  const handleRefreshTailscaleStatus = useCallback(() => {
+    loadTailscaleStatus(true);
+  }, [loadTailscaleStatus]);
+
+  useEffect(() => {
+    return subscribeTailscaleStatusChanged((status) => {
+      setTailscaleStatus(status);
+      setTailscaleStatusError(null);
+    });
+  }, []);
+
   const handleRefreshTailscaleCommandPreview = useCallback(() => {
     void (async () => {
       setTailscaleCommandBusy(true);
@@ -572,6 +643,17 @@ export const useSettingsServerSection = ({
     })();
   }, []);
 
+  const loadDaemonIdentity = useCallback(() => {
+    void (async () => {
+      setDaemonIdentityError(null);
+      try {
+        setDaemonIdentity(await tailscaleDaemonIdentity());
+      } catch (error) {
+        setDaemonIdentityError(formatErrorMessage(error, "Unable to load the daemon identity."));
+      }
+    })();
+  }, []);
+
   const handleUseSuggestedTailscaleHost = async () => {
     const suggestedHost = tailscaleStatus?.suggestedRemoteHost ?? null;
     if (!suggestedHost) {
@@ -582,7 +664,7 @@ export const useSettingsServerSection = ({
 
   const runTcpDaemonAction = useCallback(
     async (
-      action: "start" | "stop" | "status",
+      action: "start" | "stop" | "restart" | "update" | "status" | "firewall" | "service",
       run: () => Promise<TcpDaemonStatus>,
     ) => {
       setTcpDaemonBusyAction(action);
@@ -602,6 +684,13 @@ export const useSettingsServerSection = ({
           startedAtMs: null,
           lastError: errorMessage,
           listenAddr: prev?.listenAddr ?? null,
+          firewallTrust: prev?.firewallTrust ?? null,
+          serviceManager: prev?.serviceManager ?? null,
+          operation: null,
+          launch: null,
+          managedExternally: prev?.managedExternally ?? false,
+          lastStop: prev?.lastStop ?? null,
+          updatePending: false,
         }));
       } finally {
         setTcpDaemonBusyAction(null);
@@ -611,30 +700,73 @@ export const useSettingsServerSection = ({
   );
 
   const handleTcpDaemonStart = useCallback(async () => {
-    await runTcpDaemonAction("start", tailscaleDaemonStart);
+    await runTcpDaemonAction("start", async () => {
+      try {
+        return await tailscaleDaemonStart();
+      } catch (error) {
+        if (!(error instanceof CommandError) || error.code !== "DataDirLocked") {
+          throw error;
+        }
+        const takeOver = await ask(
+          `${error.message}\n\nAnother daemon is using this data directory. Stop it and start the mobile access daemon here instead?`,
+          {
+            title: "Data Dir In Use",
+            kind: "warning",
+            okLabel: "Take Over",
+            cancelLabel: "Cancel",
+          },
+        );
+        if (!takeOver) {
+          throw error;
+        }
+        return tailscaleDaemonStart(true);
+      }
+    });
   }, [runTcpDaemonAction]);
 
   const handleTcpDaemonStop = useCallback(async () => {
     await runTcpDaemonAction("stop", tailscaleDaemonStop);
   }, [runTcpDaemonAction]);
 
+  const handleTcpDaemonRestart = useCallback(async () => {
+    await runTcpDaemonAction("restart", async () => (await tailscaleDaemonRestart()).status);
+  }, [runTcpDaemonAction]);
+
+  const handleTcpDaemonApplyUpdate = useCallback(async () => {
+    await runTcpDaemonAction("update", tailscaleDaemonApplyUpdate);
+  }, [runTcpDaemonAction]);
+
   const handleTcpDaemonStatus = useCallback(async () => {
     await runTcpDaemonAction("status", tailscaleDaemonStatus);
   }, [runTcpDaemonAction]);
 
+  const handleTcpDaemonRegisterFirewall = useCallback(async () => {
+    await runTcpDaemonAction("firewall", () => tailscaleDaemonRegisterFirewall());
+  }, [runTcpDaemonAction]);
+
+  const handleInstallDaemonService = useCallback(async () => {
+    await runTcpDaemonAction("service", installDaemonService);
+  }, [runTcpDaemonAction]);
+
+  const handleUninstallDaemonService = useCallback(async () => {
+    await runTcpDaemonAction("service", uninstallDaemonService);
+  }, [runTcpDaemonAction]);
+
   useEffect(() => {
     if (!mobilePlatform) {
       handleRefreshTailscaleCommandPreview();
+      loadDaemonIdentity();
       void handleTcpDaemonStatus();
     }
     if (tailscaleStatus === null && !tailscaleStatusBusy && !tailscaleStatusError) {
-      handleRefreshTailscaleStatus();
+      loadTailscaleStatus(false);
     }
   }, [
     appSettings.remoteBackendToken,
     handleRefreshTailscaleCommandPreview,
-    handleRefreshTailscaleStatus,
     handleTcpDaemonStatus,
+    loadDaemonIdentity,
+    loadTailscaleStatus,
     mobilePlatform,
     tailscaleStatus,
     tailscaleStatusBusy,
@@ -654,6 +786,7 @@ export const useSettingsServerSection = ({
     remoteNameDraft,
     remoteHostDraft,
     remoteTokenDraft,
+    remoteIdentityDraft,
     nextRemoteNameSuggestion: buildNextRemoteName(getConfiguredRemoteBackends(appSettings)),
     tailscaleStatus,
     tailscaleStatusBusy,
@@ -661,14 +794,18 @@ export const useSettingsServerSection = ({
     tailscaleCommandPreview,
     tailscaleCommandBusy,
     tailscaleCommandError,
+    daemonIdentity,
+    daemonIdentityError,
     tcpDaemonStatus,
     tcpDaemonBusyAction,
     onSetRemoteNameDraft: handleSetRemoteNameDraft,
     onSetRemoteHostDraft: handleSetRemoteHostDraft,
     onSetRemoteTokenDraft: setRemoteTokenDraft,
+    onSetRemoteIdentityDraft: setRemoteIdentityDraft,
     onCommitRemoteName: handleCommitRemoteName,
     onCommitRemoteHost: handleCommitRemoteHost,
     onCommitRemoteToken: handleCommitRemoteToken,
+    onCommitRemoteIdentity: handleCommitRemoteIdentity,
     onSelectRemoteBackend: handleSelectRemoteBackend,
     onAddRemoteBackend: handleAddRemoteBackend,
     onMoveRemoteBackend: handleMoveRemoteBackend,
@@ -678,7 +815,12 @@ export const useSettingsServerSection = ({
     onUseSuggestedTailscaleHost: handleUseSuggestedTailscaleHost,
     onTcpDaemonStart: handleTcpDaemonStart,
     onTcpDaemonStop: handleTcpDaemonStop,
+    onTcpDaemonRestart: handleTcpDaemonRestart,
+    onTcpDaemonApplyUpdate: handleTcpDaemonApplyUpdate,
     onTcpDaemonStatus: handleTcpDaemonStatus,
+    onTcpDaemonRegisterFirewall: handleTcpDaemonRegisterFirewall,
+    onInstallDaemonService: handleInstallDaemonService,
+    onUninstallDaemonService: handleUninstallDaemonService,
     isMobilePlatform: mobilePlatform,
     mobileConnectBusy,
     mobileConnectStatusText,
@@ -1,9 +1,18 @@
 import { listen } from "@tauri-apps/api/event";
 import type {
+  ActivityAlert,
   AppServerEvent,
+  AppSettings,
+  ClientApproval,
+  CodexLoginFlow,
   DictationEvent,
   DictationModelStatus,
+  RemoteActionConfirmation,
+  SessionSummary,
+  TailscaleStatus,
+  TcpDaemonStatus,
   TrayOpenThreadPayload,
+  TraySummary,
 } from "../types";
 
 export type Unsubscribe = () => void;
@@ -19,6 +28,37 @@ export type TerminalExitEvent = {
   terminalId: string;
 };
 
+export type RemoteCommandOutputEvent = {
+  workspaceId: string;
+  runId: string;
+  stream: "stdout" | "stderr";
+  data: string;
+};
+
+export type CodexSessionOutputEvent = {
+  sessionId: string;
+  stream: "stdout" | "stderr";
+  data: string;
+};
+
+export type CodexSessionExitEvent = {
+  sessionId: string;
+  exitCode: number | null;
+  /** Only for sessions the monitor started. */
+  summary: SessionSummary | null;
+};
+
+export type CodexSessionQueueEvent = {
+  sessionId: string;
+  status: "queued" | "started" | "cancelled" | "failed";
+  position: number | null;
+  error: string | null;
+};
+
+export type DeepLinkOpenProjectEvent = {
+  workspaceId: string;
+};
+
 type SubscriptionOptions = {
   onError?: (error: unknown) => void;
 };
@@ -91,8 +131,43 @@ const dictationDownloadHub = createEventHub<DictationModelStatus>("dictation-dow
 const dictationEventHub = createEventHub<DictationEvent>("dictation-event");
 const terminalOutputHub = createEventHub<TerminalOutputEvent>("terminal-output");
 const terminalExitHub = createEventHub<TerminalExitEvent>("terminal-exit");
+const remoteCommandOutputHub = createEventHub<RemoteCommandOutputEvent>(
+  "remote-command-output",
+);
+const codexSessionOutputHub = createEventHub<CodexSessionOutputEvent>(
+  "codex-session-output",
+);
+const codexSessionExitHub = createEventHub<CodexSessionExitEvent>(
+  "codex-session-exit",
+);
+const codexSessionQueueHub = createEventHub<CodexSessionQueueEvent>(
+  "codex-session-queue",
+);
+const codexLoginProgressHub = createEventHub<CodexLoginFlow>(
+  "codex-login-progress",
+);
+const tailscaleStatusChangedHub = createEventHub<TailscaleStatus>(
+  "tailscale-status-changed",
+);
+const tcpDaemonStatusChangedHub = createEventHub<TcpDaemonStatus>(
+  "tcp-daemon-status-changed",
+);
 const updaterCheckHub = createEventHub<void>("updater-check");
+const settingsSyncedHub = createEventHub<AppSettings>("settings-synced");
+const clientApprovalRequestedHub = createEventHub<ClientApproval>(
+  "client-approval-requested",
+);
+const remoteActionConfirmationRequestedHub =
+  createEventHub<RemoteActionConfirmation>("remote-action-confirmation-requested");
 const trayOpenThreadHub = createEventHub<TrayOpenThreadPayload>("tray-open-thread");
+const deepLinkOpenProjectHub = createEventHub<DeepLinkOpenProjectEvent>(
+  "deep-link-open-project",
+);
+const deepLinkReviewClientHub = createEventHub<ClientApproval>(
+  "deep-link-review-client",
+);
+const activityAlertHub = createEventHub<ActivityAlert>("activity-alert");
+const traySummaryChangedHub = createEventHub<TraySummary>("tray-summary-changed");
 const menuNewAgentHub = createEventHub<void>("menu-new-agent");
 const menuNewWorktreeAgentHub = createEventHub<void>("menu-new-worktree-agent");
 const menuNewCloneAgentHub = createEventHub<void>("menu-new-clone-agent");
@@ -153,6 +228,55 @@ export function subscribeTerminalExit(
   return terminalExitHub.subscribe(onEvent, options);
 }
 
+export function subscribeRemoteCommandOutput(
+  onEvent: (event: RemoteCommandOutputEvent) => void,
+  options?: SubscriptionOptions,
+): Unsubscribe {
+  return remoteCommandOutputHub.subscribe(onEvent, options);
+}
+
+export function subscribeCodexSessionOutput(
+  onEvent: (event: CodexSessionOutputEvent) => void,
+  options?: SubscriptionOptions,
+): Unsubscribe {
+  return codexSessionOutputHub.subscribe(onEvent, options);
+}
+
+export function subscribeCodexSessionExit(
+  onEvent: (event: CodexSessionExitEvent) => void,
+  options?: SubscriptionOptions,
+): Unsubscribe {
+  return codexSessionExitHub.subscribe(onEvent, options);
+}
+
+export function subscribeCodexSessionQueue(
+  onEvent: (event: CodexSessionQueueEvent) => void,
+  options?: SubscriptionOptions,
+): Unsubscribe {
+  return codexSessionQueueHub.subscribe(onEvent, options);
+}
+
+export function subscribeCodexLoginProgress(
+  onEvent: (flow: CodexLoginFlow) => void,
+  options?: SubscriptionOptions,
+): Unsubscribe {
+  return codexLoginProgressHub.subscribe(onEvent, options);
+}
+
+export function subscribeTailscaleStatusChanged(
+  onEvent: (status: TailscaleStatus) => void,
+  options?: SubscriptionOptions,
+): Unsubscribe {
+  return tailscaleStatusChangedHub.subscribe(onEvent, options);
+}
+
+export function subscribeTcpDaemonStatusChanged(
+  onEvent: (status: TcpDaemonStatus) => void,
+  options?: SubscriptionOptions,
+): Unsubscribe {
+  return tcpDaemonStatusChangedHub.subscribe(onEvent, options);
+}
+
 export function subscribeUpdaterCheck(
   onEvent: () => void,
   options?: SubscriptionOptions,
@@ -162,6 +286,27 @@ export function subscribeUpdaterCheck(
   }, options);
 }
 
+export function subscribeSettingsSynced(
+  onEvent: (settings: AppSettings) => void,
+  options?: SubscriptionOptions,
+): Unsubscribe {
+  return settingsSyncedHub.subscribe(onEvent, options);
+}
+
+export function subscribeClientApprovalRequested(
+  onEvent: (approval: ClientApproval) => void,
+  options?: SubscriptionOptions,
+): Unsubscribe {
+  return clientApprovalRequestedHub.subscribe(onEvent, options);
+}
+
+export function subscribeRemoteActionConfirmationRequested(
+  onEvent: (confirmation: RemoteActionConfirmation) => void,
+  options?: SubscriptionOptions,
+): Unsubscribe {
+  return remoteActionConfirmationRequestedHub.subscribe(onEvent, options);
+}
+
 export function subscribeTrayOpenThread(
   onEvent: (payload: TrayOpenThreadPayload) => void,
   options?: SubscriptionOptions,
@@ -171,6 +316,34 @@ export function subscribeTrayOpenThread(
   }, options);
 }
 
+export function subscribeDeepLinkOpenProject(
+  onEvent: (payload: DeepLinkOpenProjectEvent) => void,
+  options?: SubscriptionOptions,
+): Unsubscribe {
+  return deepLinkOpenProjectHub.subscribe(onEvent, options);
+}
+
+export function subscribeDeepLinkReviewClient(
+  onEvent: (approval: ClientApproval) => void,
+  options?: SubscriptionOptions,
+): Unsubscribe {
+  return deepLinkReviewClientHub.subscribe(onEvent, options);
+}
+
+export function subscribeActivityAlert(
+  onEvent: (alert: ActivityAlert) => void,
+  options?: SubscriptionOptions,
+): Unsubscribe {
+  return activityAlertHub.subscribe(onEvent, options);
+}
+
+export function subscribeTraySummaryChanged(
+  onEvent: (summary: TraySummary) => void,
+  options?: SubscriptionOptions,
+): Unsubscribe {
+  return traySummaryChangedHub.subscribe(onEvent, options);
+}
+
 export function subscribeMenuNewAgent(
   onEvent: () => void,
   options?: SubscriptionOptions,
@@ -3,6 +3,7 @@ import { invoke } from "@tauri-apps/api/core";
 import { open, save } from "@tauri-apps/plugin-dialog";
 import * as notification from "@tauri-apps/plugin-notification";
 import {
+  CommandError,
   exportMarkdownFile,
   addWorkspace,
   compactThread,
@@ -13,6 +14,7 @@ import {
   getAgentsSettings,
   getExperimentalFeatureList,
   getGitHubIssues,
+  getProjectCiStatus,
   getGitLog,
   getGitStatus,
   getOpenAppIcon,
@@ -23,10 +25,48 @@ import {
   readGlobalCodexConfigToml,
   listWorkspaces,
   openWorkspaceIn,
+  listEditors,
+  openInEditor,
+  openDiffInEditor,
+  listPlugins,
+  enablePlugin,
+  takeDeepLinkProject,
   readAgentMd,
   stageGitAll,
   respondToServerRequest,
   respondToUserInputRequest,
+  remoteListDir,
+  remoteReadFile,
+  searchSessions,
+  archiveSessions,
+  restoreArchive,
+  startCodexSession,
+  mergeSessionWorktree,
+  createPrFromSession,
+  discardSessionWorktree,
+  sendSessionInput,
+  interruptSession,
+  cancelQueuedSession,
+  terminateSession,
+  getCodexSession,
+  exportSession,
+  getProcessStats,
+  listSessionTemplates,
+  saveSessionTemplate,
+  deleteSessionTemplate,
+  listTags,
+  saveTag,
+  deleteTag,
+  setTags,
+  getTagAssignments,
+  listCodexSessions,
+  listSchedules,
+  upsertSchedule,
+  runScheduleNow,
+  runRemoteCommand,
+  listArchives,
+  listRemoteCommandAudit,
+  generateActivityReport,
   sendUserMessage,
   steerTurn,
   sendNotification,
@@ -34,12 +74,67 @@ import {
   setAgentsCoreSettings,
   setTrayRecentThreads,
   setTraySessionUsage,
+  getTraySummary,
+  getWindowState,
+  resetWindowState,
+  listRegisteredShortcuts,
+  getActivityAlert,
+  dismissActivityAlert,
   startReview,
   setThreadName,
   tailscaleDaemonStart,
   tailscaleDaemonCommandPreview,
+  tailscaleDaemonIdentity,
   tailscaleDaemonStatus,
+  getMonitorOverview,
+  tailscaleDaemonPreflight,
+  tailscaleDaemonRegisterFirewall,
+  adoptExternalDaemon,
+  installDaemonService,
+  uninstallDaemonService,
+  testProxy,
+  migrateDataDir,
+  getStorageHealth,
+  getHostCapabilities,
+  getPlatformCapabilities,
+  listWebhookDeliveries,
+  listHookRuns,
+  sendTestMessage,
+  notificationsSnooze,
+  testRedactionRules,
+  createBackup,
+  restoreBackup,
+  syncSettingsNow,
+  cleanStorage,
+  createGuestToken,
+  listGuestTokens,
+  revokeGuestToken,
+  listCapabilities,
+  getStartupReport,
+  probeRemoteQuality,
+  checkTailnetReachability,
+  tailscalePingPeer,
+  getTailscalePingHistory,
+  setMockBackendScript,
+  getRemoteBackendStatus,
+  getRemoteBackendMetrics,
+  getCodexHome,
+  setCodexHomeOverride,
+  snapshotCodexHome,
+  listCodexHomeSnapshots,
+  restoreCodexHomeSnapshot,
+  listConfigChanges,
+  revertConfigChange,
+  getCodexAuthStatus,
+  startCodexLogin,
+  cancelCodexLoginFlow,
+  listClientApprovals,
+  listRemoteActionConfirmations,
+  decideRemoteActionConfirmation,
+  decideClientApproval,
   tailscaleDaemonStop,
+  tailscaleDaemonRestart,
+  tailscaleDaemonApplyUpdate,
   tailscaleStatus,
   pickImageFiles,
   pickWorkspacePaths,
@@ -224,6 +319,17 @@ describe("tauri invoke wrappers", () => {
     });
   });
 
+  it("asks for a project's CI status by project id", async () => {
+    const invokeMock = vi.mocked(invoke);
+    invokeMock.mockResolvedValueOnce({ state: "success", checks: [] });
+
+    await getProjectCiStatus("ws-2");
+
+    expect(invokeMock).toHaveBeenCalledWith("get_project_ci_status", {
+      projectId: "ws-2",
+    });
+  });
+
   it("returns an empty list when the Tauri invoke bridge is missing", async () => {
     const invokeMock = vi.mocked(invoke);
     invokeMock.mockRejectedValueOnce(
@@ -363,6 +469,87 @@ describe("tauri invoke wrappers", () => {
     });
   });
 
+  it("reads the cached tray summary", async () => {
+    const invokeMock = vi.mocked(invoke);
+    invokeMock.mockResolvedValueOnce({
+      daemonState: "running",
+      runnerState: "local",
+      activeSessions: 2,
+      pendingApprovals: 1,
+      lastError: null,
+      updatedAtMs: 1,
+    });
+
+    const summary = await getTraySummary();
+
+    expect(invokeMock).toHaveBeenCalledWith("get_tray_summary");
+    expect(summary.pendingApprovals).toBe(1);
+  });
+
+  it("reads and resets the saved window state", async () => {
+    const invokeMock = vi.mocked(invoke);
+    invokeMock.mockResolvedValueOnce({
+      label: "main",
+      x: 120,
+      y: 80,
+      width: 1400,
+      height: 900,
+      maximized: false,
+      monitor: "Built-in Retina Display",
+      savedAtMs: 1,
+    });
+    invokeMock.mockResolvedValueOnce(undefined);
+
+    const state = await getWindowState();
+    await resetWindowState("main");
+
+    expect(invokeMock).toHaveBeenCalledWith("get_window_state", { label: null });
+    expect(invokeMock).toHaveBeenCalledWith("reset_window_state", {
+      label: "main",
+    });
+    expect(state?.width).toBe(1400);
+  });
+
+  it("lists registered global shortcuts", async () => {
+    const invokeMock = vi.mocked(invoke);
+    invokeMock.mockResolvedValueOnce([
+      {
+        action: "toggle_window",
+        shortcut: "cmd+shift+space",
+        registered: true,
+        error: null,
+        shadows: [],
+      },
+    ]);
+
+    const shortcuts = await listRegisteredShortcuts();
+
+    expect(invokeMock).toHaveBeenCalledWith("list_registered_shortcuts");
+    expect(shortcuts[0]?.registered).toBe(true);
+  });
+
+  it("reads and dismisses the activity alert", async () => {
+    const invokeMock = vi.mocked(invoke);
+    invokeMock.mockResolvedValueOnce({
+      trigger: "session_failed",
+      workspaceId: null,
+      threadId: null,
+      sessionId: "session-1",
+      detail: "Exited with code 2",
+      raisedAtMs: 1,
+    });
+    invokeMock.mockResolvedValueOnce(undefined);
+
+    const alert = await getActivityAlert();
+    await dismissActivityAlert(true);
+
+    expect(invokeMock).toHaveBeenCalledWith("get_activity_alert");
+    expect(invokeMock).toHaveBeenCalledWith("dismiss_activity_alert", {
+      open: true,
+    });
+    expect(alert?.sessionId).toBe("session-1");
+  });
+
   it("maps workspaceId/cursor/limit/threadId for apps_list", async () => {
     const invokeMock = vi.mocked(invoke);
     invokeMock.mockResolvedValueOnce({});
@@ -475,21 +662,944 @@ describe("tauri invoke wrappers", () => {
     });
   });
 
+  it("passes cwd and run id to run_remote_command", async () => {
+    const invokeMock = vi.mocked(invoke);
+    invokeMock.mockResolvedValueOnce({});
+
+    await runRemoteCommand("ws-1", "npm test", { cwd: "packages/app", runId: "run-1" });
+    await runRemoteCommand("ws-1", "git pull");
+
+    expect(invokeMock).toHaveBeenCalledWith("run_remote_command", {
+      workspaceId: "ws-1",
+      command: "npm test",
+      cwd: "packages/app",
+      runId: "run-1",
+    });
+    expect(invokeMock).toHaveBeenCalledWith("run_remote_command", {
+      workspaceId: "ws-1",
+      command: "git pull",
+      cwd: null,
+      runId: null,
+    });
+  });
+
+  it("maps remote file browser arguments", async () => {
+    const invokeMock = vi.mocked(invoke);
+    invokeMock.mockResolvedValue({});
+
+    await remoteListDir("ws-1");
+    await remoteReadFile("ws-1", "src/main.rs", { offset: 10, length: 20 });
+
+    expect(invokeMock).toHaveBeenCalledWith("remote_list_dir", {
+      workspaceId: "ws-1",
+      relPath: null,
+    });
+    expect(invokeMock).toHaveBeenCalledWith("remote_read_file", {
+      workspaceId: "ws-1",
+      relPath: "src/main.rs",
+      offset: 10,
+      length: 20,
+    });
+  });
+
+  it("passes session search filters through", async () => {
+    const invokeMock = vi.mocked(invoke);
+    invokeMock.mockResolvedValueOnce([]);
+
+    await searchSessions("flaky test", { workspaceId: "ws-1", role: "user" });
+
+    expect(invokeMock).toHaveBeenCalledWith("search_sessions", {
+      query: "flaky test",
+      filters: { workspaceId: "ws-1", role: "user" },
+    });
+  });
+
+  it("defaults session archiving to a real run without cutoff", async () => {
+    const invokeMock = vi.mocked(invoke);
+    invokeMock.mockResolvedValue({});
+
+    await archiveSessions();
+    await archiveSessions({ before: 1_700_000_000_000, dryRun: true });
+    await restoreArchive("sessions-20260101-120000.zip");
+
+    expect(invokeMock).toHaveBeenCalledWith("archive_sessions", {
+      before: null,
+      dryRun: false,
+    });
+    expect(invokeMock).toHaveBeenCalledWith("archive_sessions", {
+      before: 1_700_000_000_000,
+      dryRun: true,
+    });
+    expect(invokeMock).toHaveBeenCalledWith("restore_archive", {
+      name: "sessions-20260101-120000.zip",
+    });
+  });
+
+  it("maps start_codex_session options", async () => {
+    const invokeMock = vi.mocked(invoke);
+    invokeMock.mockResolvedValueOnce({ sessionId: "s-1" });
+
+    await startCodexSession("/tmp/project", "fix the tests", {
+      approvalMode: "never",
+    });
+
+    expect(invokeMock).toHaveBeenCalledWith("start_codex_session", {
+      projectPath: "/tmp/project",
+      prompt: "fix the tests",
+      model: null,
+      approvalMode: "never",
+      templateId: null,
+      isolateWorktree: false,
+    });
+  });
+
+  it("starts isolated sessions and merges, discards or publishes their worktrees", async () => {
+    const invokeMock = vi.mocked(invoke);
+    invokeMock.mockResolvedValue(undefined);
+
+    await startCodexSession("/tmp/project", "refactor", { isolateWorktree: true });
+    await mergeSessionWorktree("s-1");
+    await discardSessionWorktree("s-2");
+    await createPrFromSession("s-3", "Fix flaky test", { base: "main" });
+
+    expect(invokeMock).toHaveBeenCalledWith("start_codex_session", {
+      projectPath: "/tmp/project",
+      prompt: "refactor",
+      model: null,
+      approvalMode: null,
+      templateId: null,
+      isolateWorktree: true,
+    });
+    expect(invokeMock).toHaveBeenCalledWith("merge_session_worktree", {
+      sessionId: "s-1",
+    });
+    expect(invokeMock).toHaveBeenCalledWith("discard_session_worktree", {
+      sessionId: "s-2",
+    });
+    expect(invokeMock).toHaveBeenCalledWith("create_pr_from_session", {
+      sessionId: "s-3",
+      title: "Fix flaky test",
+      body: null,
+      base: "main",
+    });
+  });
+
+  it("takes the project a launch link opened", async () => {
+    const invokeMock = vi.mocked(invoke);
+    invokeMock.mockResolvedValueOnce("ws-1");
+
+    await expect(takeDeepLinkProject()).resolves.toBe("ws-1");
+    expect(invokeMock).toHaveBeenCalledWith("take_deep_link_project");
+  });
+
+  it("opens files and session diffs in the configured editor", async () => {
+    const invokeMock = vi.mocked(invoke);
+    invokeMock.mockResolvedValue(undefined);
+
+    await listEditors();
+    await openInEditor("/tmp/project/src/main.rs", 42);
+    await openInEditor("/tmp/project/README.md");
+    await openDiffInEditor("s-1", "src/main.rs");
+
+    expect(invokeMock).toHaveBeenCalledWith("list_editors");
+    expect(invokeMock).toHaveBeenCalledWith("open_in_editor", {
+      path: "/tmp/project/src/main.rs",
+      line: 42,
+    });
+    expect(invokeMock).toHaveBeenCalledWith("open_in_editor", {
+      path: "/tmp/project/README.md",
+      line: null,
+    });
+    expect(invokeMock).toHaveBeenCalledWith("open_diff_in_editor", {
+      sessionId: "s-1",
+      file: "src/main.rs",
+    });
+  });
+
+  it("lists and toggles daemon plugins", async () => {
+    const invokeMock = vi.mocked(invoke);
+    invokeMock.mockResolvedValue([]);
+
+    await listPlugins();
+    await enablePlugin("deploy", true);
+
+    expect(invokeMock).toHaveBeenCalledWith("list_plugins");
+    expect(invokeMock).toHaveBeenCalledWith("enable_plugin", {
+      id: "deploy",
+      enabled: true,
+    });
+  });
+
+  it("starts sessions from templates and manages saved templates", async () => {
+    const invokeMock = vi.mocked(invoke);
+    invokeMock.mockResolvedValue(undefined);
+
+    await startCodexSession(null, null, { templateId: "t-1" });
+    await listSessionTemplates();
+    await saveSessionTemplate({ name: "Update deps", prompt: "Update deps" });
+    await deleteSessionTemplate("t-1");
+
+    expect(invokeMock).toHaveBeenCalledWith("start_codex_session", {
+      projectPath: null,
+      prompt: null,
+      model: null,
+      approvalMode: null,
+      templateId: "t-1",
+      isolateWorktree: false,
+    });
+    expect(invokeMock).toHaveBeenCalledWith("list_session_templates");
+    expect(invokeMock).toHaveBeenCalledWith("save_session_template", {
+      template: { name: "Update deps", prompt: "Update deps" },
+    });
+    expect(invokeMock).toHaveBeenCalledWith("delete_session_template", {
+      id: "t-1",
+    });
+  });
+
+  it("manages tags and filters sessions by them", async () => {
+    const invokeMock = vi.mocked(invoke);
+    invokeMock.mockResolvedValue(undefined);
+
+    await listTags();
+    await saveTag({ name: "bug-fix" });
+    await setTags("project", "ws-1", ["tag-1"]);
+    await getTagAssignments();
+    await deleteTag("tag-1");
+    await listCodexSessions(["tag-1"]);
+    await listCodexSessions();
+
+    expect(invokeMock).toHaveBeenCalledWith("list_tags");
+    expect(invokeMock).toHaveBeenCalledWith("save_tag", {
+      tag: { name: "bug-fix" },
+    });
+    expect(invokeMock).toHaveBeenCalledWith("set_tags", {
+      target: "project",
+      id: "ws-1",
+      tagIds: ["tag-1"],
+    });
+    expect(invokeMock).toHaveBeenCalledWith("get_tag_assignments");
+    expect(invokeMock).toHaveBeenCalledWith("delete_tag", { id: "tag-1" });
+    expect(invokeMock).toHaveBeenCalledWith("list_codex_sessions", {
+      tags: ["tag-1"],
+      page: null,
+    });
+    expect(invokeMock).toHaveBeenCalledWith("list_codex_sessions", {
+      tags: null,
+      page: null,
+    });
+  });
+
+  it("generates activity reports", async () => {
+    const invokeMock = vi.mocked(invoke);
+    invokeMock.mockResolvedValue(undefined);
+
+    await generateActivityReport("week", "markdown");
+    await generateActivityReport("month", "json", { save: true });
+
+    expect(invokeMock).toHaveBeenCalledWith("generate_activity_report", {
+      range: "week",
+      format: "markdown",
+      save: false,
+    });
+    expect(invokeMock).toHaveBeenCalledWith("generate_activity_report", {
+      range: "month",
+      format: "json",
+      save: true,
+    });
+  });
+
+  it("requests session detail and process stats", async () => {
+    const invokeMock = vi.mocked(invoke);
+    invokeMock.mockResolvedValue(undefined);
+
+    await getCodexSession("s-1");
+    await getProcessStats();
+    await getProcessStats(4242);
+
+    expect(invokeMock).toHaveBeenCalledWith("get_codex_session", {
+      sessionId: "s-1",
+    });
+    expect(invokeMock).toHaveBeenCalledWith("get_process_stats", { pid: null });
+    expect(invokeMock).toHaveBeenCalledWith("get_process_stats", { pid: 4242 });
+  });
+
+  it("exports sessions with or without tool output", async () => {
+    const invokeMock = vi.mocked(invoke);
+    invokeMock.mockResolvedValue(undefined);
+
+    await exportSession("abc-123", "markdown");
+    await exportSession("abc-123", "html", { includeToolOutput: true });
+
+    expect(invokeMock).toHaveBeenCalledWith("export_session", {
+      sessionId: "abc-123",
+      format: "markdown",
+      includeToolOutput: false,
+    });
+    expect(invokeMock).toHaveBeenCalledWith("export_session", {
+      sessionId: "abc-123",
+      format: "html",
+      includeToolOutput: true,
+    });
+  });
+
+  it("invokes schedule wrappers", async () => {
+    const invokeMock = vi.mocked(invoke);
+    invokeMock.mockResolvedValue(undefined);
+
+    await listSchedules();
+    await upsertSchedule({ name: "Nightly", cron: "0 3 * * *", templateId: "t-1" });
+    await runScheduleNow("sched-1");
+
+    expect(invokeMock).toHaveBeenCalledWith("list_schedules");
+    expect(invokeMock).toHaveBeenCalledWith("upsert_schedule", {
+      schedule: { name: "Nightly", cron: "0 3 * * *", templateId: "t-1" },
+    });
+    expect(invokeMock).toHaveBeenCalledWith("run_schedule_now", {
+      id: "sched-1",
+    });
+  });
+
+  it("targets monitor sessions by id for input and interrupts", async () => {
+    const invokeMock = vi.mocked(invoke);
+    invokeMock.mockResolvedValue(undefined);
+
+    await sendSessionInput("s-1", "also update the docs");
+    await interruptSession("s-1");
+    await cancelQueuedSession("s-2");
+    await terminateSession("s-3", { force: true });
+    await terminateSession("external-1", { pid: 4242 });
+
+    expect(invokeMock).toHaveBeenCalledWith("send_session_input", {
+      sessionId: "s-1",
+      text: "also update the docs",
+    });
+    expect(invokeMock).toHaveBeenCalledWith("interrupt_session", {
+      sessionId: "s-1",
+    });
+    expect(invokeMock).toHaveBeenCalledWith("cancel_queued_session", {
+      sessionId: "s-2",
+    });
+    expect(invokeMock).toHaveBeenCalledWith("terminate_session", {
+      sessionId: "s-3",
+      pid: null,
+      force: true,
+    });
+    expect(invokeMock).toHaveBeenCalledWith("terminate_session", {
+      sessionId: "external-1",
+      pid: 4242,
+      force: false,
+    });
+  });
+
   it("invokes tailscale wrappers", async () => {
     const invokeMock = vi.mocked(invoke);
     invokeMock.mockResolvedValue(undefined);
 
     await tailscaleStatus();
     await tailscaleDaemonCommandPreview();
+    await tailscaleDaemonIdentity();
     await tailscaleDaemonStart();
+    await tailscaleDaemonStart(true);
     await tailscaleDaemonStop();
+    await tailscaleDaemonRestart();
+    await tailscaleDaemonApplyUpdate();
     await tailscaleDaemonStatus();
+    await tailscaleDaemonStatus(true);
+    await tailscaleDaemonPreflight();
+    await tailscaleDaemonRegisterFirewall(false);
+    await adoptExternalDaemon();
+    await installDaemonService();
+    await uninstallDaemonService();
+    await getMonitorOverview();
 
-    expect(invokeMock).toHaveBeenCalledWith("tailscale_status");
+    expect(invokeMock).toHaveBeenCalledWith("tailscale_status", {
+      forceRefresh: false,
+    });
     expect(invokeMock).toHaveBeenCalledWith("tailscale_daemon_command_preview");
-    expect(invokeMock).toHaveBeenCalledWith("tailscale_daemon_start");
+    expect(invokeMock).toHaveBeenCalledWith("tailscale_daemon_identity");
+    expect(invokeMock).toHaveBeenCalledWith("tailscale_daemon_start", {
+      takeover: false,
+    });
+    expect(invokeMock).toHaveBeenCalledWith("tailscale_daemon_start", {
+      takeover: true,
+    });
     expect(invokeMock).toHaveBeenCalledWith("tailscale_daemon_stop");
-    expect(invokeMock).toHaveBeenCalledWith("tailscale_daemon_status");
+    expect(invokeMock).toHaveBeenCalledWith("tailscale_daemon_restart");
+    expect(invokeMock).toHaveBeenCalledWith("tailscale_daemon_apply_update");
+    expect(invokeMock).toHaveBeenCalledWith("tailscale_daemon_status", {
+      verbose: false,
+    });
+    expect(invokeMock).toHaveBeenCalledWith("tailscale_daemon_status", {
+      verbose: true,
+    });
+    expect(invokeMock).toHaveBeenCalledWith("tailscale_daemon_preflight");
+    expect(invokeMock).toHaveBeenCalledWith("tailscale_daemon_register_firewall", {
+      verifySignature: false,
+    });
+    expect(invokeMock).toHaveBeenCalledWith("adopt_external_daemon");
+    expect(invokeMock).toHaveBeenCalledWith("install_daemon_service");
+    expect(invokeMock).toHaveBeenCalledWith("uninstall_daemon_service");
+    expect(invokeMock).toHaveBeenCalledWith("get_monitor_overview");
+  });
+
+  it("rethrows typed command errors and passes other failures through", async () => {
+    const invokeMock = vi.mocked(invoke);
+    invokeMock.mockRejectedValueOnce({
+      code: "DaemonPortInUse",
+      message: "Port 4732 is in use",
+      details: { listenAddr: "0.0.0.0:4732" },
+    });
+
+    const error = await tailscaleDaemonStart().catch((caught: unknown) => caught);
+    expect(error).toBeInstanceOf(CommandError);
+    expect((error as CommandError).code).toBe("DaemonPortInUse");
+    expect((error as CommandError).details).toEqual({ listenAddr: "0.0.0.0:4732" });
+    expect(String(error)).toBe("Port 4732 is in use");
+
+    invokeMock.mockRejectedValueOnce("plain failure");
+    await expect(tailscaleDaemonStart()).rejects.toBe("plain failure");
+  });
+
+  it("passes proxy settings and target to test_proxy", async () => {
+    const invokeMock = vi.mocked(invoke);
+    invokeMock.mockResolvedValue({ target: "a:1", proxied: true, latencyMs: 4 });
+    const proxy = { url: "socks5://proxy.corp:1080", bypass: [".corp"] };
+
+    await testProxy(proxy);
+    await testProxy(proxy, "wss://mac.example/rpc");
+
+    expect(invokeMock).toHaveBeenCalledWith("test_proxy", { proxy, target: null });
+    expect(invokeMock).toHaveBeenCalledWith("test_proxy", {
+      proxy,
+      target: "wss://mac.example/rpc",
+    });
+  });
+
+  it("passes the new path to migrate_data_dir", async () => {
+    const invokeMock = vi.mocked(invoke);
+    invokeMock.mockResolvedValue(undefined);
+
+    await migrateDataDir("/Volumes/External/CodexMonitor");
+
+    expect(invokeMock).toHaveBeenCalledWith("migrate_data_dir", {
+      newPath: "/Volumes/External/CodexMonitor",
+      overwrite: false,
+    });
+  });
+
+  it("passes backup paths and optional passphrases", async () => {
+    const invokeMock = vi.mocked(invoke);
+    invokeMock.mockResolvedValue({});
+
+    await createBackup("/backups/state.zip");
+    await createBackup("/backups/sealed.zip", "hunter2");
+    await restoreBackup("/backups/sealed.zip", "hunter2");
+
+    expect(invokeMock).toHaveBeenCalledWith("create_backup", {
+      path: "/backups/state.zip",
+      passphrase: null,
+    });
+    expect(invokeMock).toHaveBeenCalledWith("create_backup", {
+      path: "/backups/sealed.zip",
+      passphrase: "hunter2",
+    });
+    expect(invokeMock).toHaveBeenCalledWith("restore_backup", {
+      path: "/backups/sealed.zip",
+      passphrase: "hunter2",
+    });
+  });
+
+  it("invokes sync_settings_now without arguments", async () => {
+    const invokeMock = vi.mocked(invoke);
+    invokeMock.mockResolvedValue({
+      source: "Laptop",
+      settingsApplied: true,
+      templatesUpdated: 1,
+      projectsAdded: [],
+      syncedAtMs: 1,
+    });
+
+    const report = await syncSettingsNow();
+
+    expect(invokeMock).toHaveBeenCalledWith("sync_settings_now");
+    expect(report.settingsApplied).toBe(true);
+  });
+
+  it("lists webhook deliveries with optional filters", async () => {
+    const invokeMock = vi.mocked(invoke);
+    invokeMock.mockResolvedValue({ items: [], nextCursor: null });
+
+    await listWebhookDeliveries();
+    await listWebhookDeliveries("slack", { limit: 20 });
+
+    expect(invokeMock).toHaveBeenCalledWith("list_webhook_deliveries", {
+      endpointId: null,
+      page: null,
+    });
+    expect(invokeMock).toHaveBeenCalledWith("list_webhook_deliveries", {
+      endpointId: "slack",
+      page: { limit: 20 },
+    });
+  });
+
+  it("lists hook runs with optional filters", async () => {
+    const invokeMock = vi.mocked(invoke);
+    invokeMock.mockResolvedValue({ items: [], nextCursor: null });
+
+    await listHookRuns();
+    await listHookRuns("notify-on-finish", { limit: 5 });
+
+    expect(invokeMock).toHaveBeenCalledWith("list_hook_runs", {
+      hookId: null,
+      page: null,
+    });
+    expect(invokeMock).toHaveBeenCalledWith("list_hook_runs", {
+      hookId: "notify-on-finish",
+      page: { limit: 5 },
+    });
+  });
+
+  it("passes page cursors to paged list commands", async () => {
+    const invokeMock = vi.mocked(invoke);
+    invokeMock.mockResolvedValue({ items: [], nextCursor: null });
+
+    await listArchives({ cursor: "1700000000000:a.zip", limit: 10 });
+    await listRemoteCommandAudit();
+
+    expect(invokeMock).toHaveBeenCalledWith("list_archives", {
+      page: { cursor: "1700000000000:a.zip", limit: 10 },
+    });
+    expect(invokeMock).toHaveBeenCalledWith("list_remote_command_audit", {
+      page: null,
+    });
+  });
+
+  it("sends a test message through an integration", async () => {
+    const invokeMock = vi.mocked(invoke);
+    invokeMock.mockResolvedValue(undefined);
+
+    await sendTestMessage("team-slack");
+
+    expect(invokeMock).toHaveBeenCalledWith("send_test_message", {
+      integration: "team-slack",
+    });
+  });
+
+  it("snoozes notifications for the given minutes", async () => {
+    const invokeMock = vi.mocked(invoke);
+    invokeMock.mockResolvedValueOnce(1_700_000_000_000);
+
+    await expect(notificationsSnooze(60)).resolves.toBe(1_700_000_000_000);
+
+    expect(invokeMock).toHaveBeenCalledWith("notifications_snooze", {
+      minutes: 60,
+    });
+  });
+
+  it("tests redaction rules against a sample", async () => {
+    const invokeMock = vi.mocked(invoke);
+    invokeMock.mockResolvedValue(undefined);
+    const settings = {
+      enabled: true,
+      builtInDetectors: false,
+      rules: [{ name: "ticket", pattern: "SEC-\\d+" }],
+    };
+
+    await testRedactionRules("key sk-proj-123");
+    await testRedactionRules("SEC-42", settings);
+
+    expect(invokeMock).toHaveBeenCalledWith("test_redaction_rules", {
+      sample: "key sk-proj-123",
+      settings: null,
+    });
+    expect(invokeMock).toHaveBeenCalledWith("test_redaction_rules", {
+      sample: "SEC-42",
+      settings,
+    });
+  });
+
+  it("reads host capabilities", async () => {
+    const invokeMock = vi.mocked(invoke);
+    invokeMock.mockResolvedValueOnce({
+      os: "linux",
+      arch: "x86_64",
+      cpuCores: 8,
+      totalMemoryBytes: 16 * 1024 ** 3,
+      runtimes: [{ name: "node", version: "20.11.0" }],
+    });
+
+    await expect(getHostCapabilities()).resolves.toMatchObject({ cpuCores: 8 });
+    expect(invokeMock).toHaveBeenCalledWith("get_host_capabilities");
+  });
+
+  it("reads platform capabilities", async () => {
+    const invokeMock = vi.mocked(invoke);
+    invokeMock.mockResolvedValueOnce({
+      platform: "ios",
+      mobile: true,
+      remoteOnly: true,
+      subsystems: [
+        {
+          subsystem: "terminal",
+          available: false,
+          reason: "Terminal is not available on mobile builds.",
+        },
+      ],
+    });
+
+    await expect(getPlatformCapabilities()).resolves.toMatchObject({
+      remoteOnly: true,
+    });
+    expect(invokeMock).toHaveBeenCalledWith("get_platform_capabilities");
+  });
+
+  it("reads storage health and cleans the chosen targets", async () => {
+    const invokeMock = vi.mocked(invoke);
+    invokeMock.mockResolvedValueOnce({
+      dataDir: "/data",
+      freeBytes: 10,
+      lowSpace: true,
+      usage: { logsBytes: 1, archivesBytes: 2, cachesBytes: 3, totalBytes: 6 },
+    });
+    invokeMock.mockResolvedValueOnce({ freedBytes: 4, cleaned: ["daemon-service.log"] });
+
+    await expect(getStorageHealth()).resolves.toMatchObject({ lowSpace: true });
+    await expect(cleanStorage(["logs", "caches"])).resolves.toEqual({
+      freedBytes: 4,
+      cleaned: ["daemon-service.log"],
+    });
+
+    expect(invokeMock).toHaveBeenCalledWith("get_storage_health");
+    expect(invokeMock).toHaveBeenCalledWith("clean_storage", {
+      targets: ["logs", "caches"],
+    });
+  });
+
+  it("manages guest tokens", async () => {
+    const invokeMock = vi.mocked(invoke);
+    invokeMock.mockResolvedValue(undefined);
+
+    await createGuestToken(["read"], 3600);
+    await createGuestToken(["read", "write"], 600, "Pairing");
+    await listGuestTokens();
+    await revokeGuestToken("g-1");
+
+    expect(invokeMock).toHaveBeenCalledWith("create_guest_token", {
+      scopes: ["read"],
+      ttlSeconds: 3600,
+      label: null,
+    });
+    expect(invokeMock).toHaveBeenCalledWith("create_guest_token", {
+      scopes: ["read", "write"],
+      ttlSeconds: 600,
+      label: "Pairing",
+    });
+    expect(invokeMock).toHaveBeenCalledWith("list_guest_tokens");
+    expect(invokeMock).toHaveBeenCalledWith("revoke_guest_token", { id: "g-1" });
+  });
+
+  it("lists command capabilities", async () => {
+    const invokeMock = vi.mocked(invoke);
+    invokeMock.mockResolvedValueOnce([
+      {
+        name: "start_thread",
+        module: "codex",
+        params: [{ name: "workspaceId", rustType: "String", required: true }],
+        permission: "write",
+        remoteMethod: "start_thread",
+      },
+    ]);
+
+    const capabilities = await listCapabilities();
+
+    expect(invokeMock).toHaveBeenCalledWith("list_capabilities");
+    expect(capabilities[0].remoteMethod).toBe("start_thread");
+  });
+
+  it("reads the startup report", async () => {
+    const invokeMock = vi.mocked(invoke);
+    invokeMock.mockResolvedValueOnce({
+      readyAfterMs: 180,
+      phases: [
+        {
+          name: "settings_load",
+          kind: "setup",
+          startedAfterMs: 40,
+          durationMs: 3,
+        },
+      ],
+    });
+
+    const report = await getStartupReport();
+
+    expect(invokeMock).toHaveBeenCalledWith("get_startup_report");
+    expect(report.phases[0].name).toBe("settings_load");
+  });
+
+  it("probes remote link quality", async () => {
+    const invokeMock = vi.mocked(invoke);
+    invokeMock.mockResolvedValue({ path: "relay", relayRegion: "fra" });
+
+    await probeRemoteQuality();
+    const report = await probeRemoteQuality(10);
+
+    expect(invokeMock).toHaveBeenCalledWith("probe_remote_quality", {
+      pings: null,
+    });
+    expect(invokeMock).toHaveBeenCalledWith("probe_remote_quality", {
+      pings: 10,
+    });
+    expect(report.relayRegion).toBe("fra");
+  });
+
+  it("checks tailnet reachability from a peer", async () => {
+    const invokeMock = vi.mocked(invoke);
+    invokeMock.mockResolvedValue({ reachable: false, prober: "studio" });
+
+    await checkTailnetReachability();
+    const report = await checkTailnetReachability("studio");
+
+    expect(invokeMock).toHaveBeenCalledWith("check_tailnet_reachability", {
+      peer: null,
+    });
+    expect(invokeMock).toHaveBeenCalledWith("check_tailnet_reachability", {
+      peer: "studio",
+    });
+    expect(report.reachable).toBe(false);
+  });
+
+  it("pings a tailnet peer and reads the ping history", async () => {
+    const invokeMock = vi.mocked(invoke);
+    invokeMock.mockResolvedValueOnce({ target: "phone", path: "direct" });
+    invokeMock.mockResolvedValueOnce([{ target: "phone", latencyMs: 23.1 }]);
+
+    const result = await tailscalePingPeer("phone");
+    const history = await getTailscalePingHistory("phone");
+
+    expect(invokeMock).toHaveBeenCalledWith("tailscale_ping_peer", {
+      target: "phone",
+    });
+    expect(invokeMock).toHaveBeenCalledWith("tailscale_ping_history", {
+      target: "phone",
+    });
+    expect(result.path).toBe("direct");
+    expect(history[0].latencyMs).toBe(23.1);
+  });
+
+  it("rescripts the mock backends", async () => {
+    const invokeMock = vi.mocked(invoke);
+    invokeMock.mockResolvedValueOnce(undefined);
+    const script = {
+      tailscale: [
+        { afterMs: 0, state: "needs_login" as const },
+        { afterMs: 5_000, state: "running" as const },
+      ],
+      daemonStartMs: 500,
+      daemonStopMs: 100,
+      daemonStartError: null,
+    };
+
+    await setMockBackendScript(script);
+
+    expect(invokeMock).toHaveBeenCalledWith("set_mock_backend_script", { script });
+  });
+
+  it("reads the remote backend status", async () => {
+    const invokeMock = vi.mocked(invoke);
+    invokeMock.mockResolvedValueOnce({ connected: true, suggestedPollMs: 5000 });
+
+    const status = await getRemoteBackendStatus();
+
+    expect(invokeMock).toHaveBeenCalledWith("remote_backend_status");
+    expect(status.suggestedPollMs).toBe(5000);
+  });
+
+  it("reads and overrides the Codex home", async () => {
+    const invokeMock = vi.mocked(invoke);
+    invokeMock.mockResolvedValueOnce({
+      path: "/Users/me/.codex",
+      source: "default",
+      exists: true,
+    });
+    invokeMock.mockResolvedValueOnce({
+      path: "/srv/codex",
+      source: "override",
+      exists: false,
+    });
+
+    const current = await getCodexHome();
+    const updated = await setCodexHomeOverride("/srv/codex");
+
+    expect(invokeMock).toHaveBeenCalledWith("get_codex_home");
+    expect(invokeMock).toHaveBeenCalledWith("set_codex_home_override", {
+      path: "/srv/codex",
+    });
+    expect(current.source).toBe("default");
+    expect(updated.source).toBe("override");
+  });
+
+  it("snapshots and restores the Codex home", async () => {
+    const invokeMock = vi.mocked(invoke);
+    const snapshot = {
+      id: "codex-home-20261017-101500",
+      label: "Before MCP tweaks",
+      createdAtMs: 1,
+      codexHome: "/Users/me/.codex",
+      files: ["config.toml", "AGENTS.md"],
+      secretsIncluded: false,
+      sizeBytes: 512,
+    };
+    invokeMock.mockResolvedValueOnce(snapshot);
+    invokeMock.mockResolvedValueOnce([snapshot]);
+    invokeMock.mockResolvedValueOnce({
+      codexHome: "/Users/me/.codex",
+      restored: ["config.toml"],
+      removed: [],
+      previous: { ...snapshot, id: "codex-home-20261017-102000" },
+    });
+
+    await snapshotCodexHome("Before MCP tweaks", true);
+    const listed = await listCodexHomeSnapshots();
+    const report = await restoreCodexHomeSnapshot(snapshot.id);
+
+    expect(invokeMock).toHaveBeenCalledWith("snapshot_codex_home", {
+      label: "Before MCP tweaks",
+      excludeSecrets: true,
+    });
+    expect(invokeMock).toHaveBeenCalledWith("list_codex_home_snapshots");
+    expect(invokeMock).toHaveBeenCalledWith("restore_codex_home_snapshot", {
+      id: snapshot.id,
+    });
+    expect(listed).toHaveLength(1);
+    expect(report.restored).toEqual(["config.toml"]);
+  });
+
+  it("lists and reverts config changes", async () => {
+    const invokeMock = vi.mocked(invoke);
+    const change = {
+      id: "change-1",
+      timestampMs: 1,
+      source: "settings",
+      path: "/Users/me/.codex/config.toml",
+      changedKeys: ["features.unified_exec"],
+      diff: "@@ -0,0 +1,2 @@\n+[features]\n+unified_exec = true\n",
+      before: null,
+      after: "[features]\nunified_exec = true\n",
+      reverts: null,
+    };
+    invokeMock.mockResolvedValueOnce({ items: [change], nextCursor: null });
+    invokeMock.mockResolvedValueOnce({
+      ...change,
+      id: "change-2",
+      source: "revert",
+      reverts: "change-1",
+    });
+
+    const page = await listConfigChanges({ limit: 20 });
+    const reverted = await revertConfigChange(change.id);
+
+    expect(invokeMock).toHaveBeenCalledWith("list_config_changes", {
+      page: { limit: 20 },
+    });
+    expect(invokeMock).toHaveBeenCalledWith("revert_config_change", {
+      id: "change-1",
+    });
+    expect(page.items[0].changedKeys).toEqual(["features.unified_exec"]);
+    expect(reverted.reverts).toBe("change-1");
+  });
+
+  it("reads the Codex auth status", async () => {
+    const invokeMock = vi.mocked(invoke);
+    invokeMock.mockResolvedValueOnce({
+      loggedIn: true,
+      method: "chatgpt",
+      source: "auth_file",
+      codexHome: "/Users/me/.codex",
+      email: "me@example.com",
+      planType: "plus",
+      expiresAtMs: 2,
+      lastRefreshMs: 1,
+      refreshable: true,
+      expiringSoon: false,
+      expired: false,
+      message: "Signed in with ChatGPT as me@example.com.",
+    });
+
+    const status = await getCodexAuthStatus();
+
+    expect(invokeMock).toHaveBeenCalledWith("codex_auth_status");
+    expect(status.planType).toBe("plus");
+  });
+
+  it("starts and cancels a Codex device-code sign-in", async () => {
+    const invokeMock = vi.mocked(invoke);
+    const flow = {
+      id: "flow-1",
+      state: "awaiting_user",
+      verificationUrl: "https://auth.openai.com/codex/device",
+      userCode: "ABCD-1234",
+      startedAtMs: 1,
+      finishedAtMs: null,
+      message: "ABCD-1234",
+      auth: null,
+    };
+    invokeMock.mockResolvedValueOnce(flow);
+    invokeMock.mockResolvedValueOnce(flow);
+
+    const started = await startCodexLogin();
+    await cancelCodexLoginFlow();
+
+    expect(invokeMock).toHaveBeenCalledWith("codex_login_start");
+    expect(invokeMock).toHaveBeenCalledWith("codex_login_flow_cancel");
+    expect(started.userCode).toBe("ABCD-1234");
+  });
+
+  it("reads the remote backend metrics", async () => {
+    const invokeMock = vi.mocked(invoke);
+    invokeMock.mockResolvedValueOnce({
+      latencyBucketsMs: [25],
+      slowCallMs: 2000,
+      methods: [],
+      slowCalls: [{ method: "list_threads", traceId: "abc", durationMs: 3000 }],
+    });
+
+    const metrics = await getRemoteBackendMetrics();
+
+    expect(invokeMock).toHaveBeenCalledWith("get_remote_backend_metrics");
+    expect(metrics.slowCalls[0].traceId).toBe("abc");
+  });
+
+  it("lists and decides client approvals", async () => {
+    const invokeMock = vi.mocked(invoke);
+    invokeMock.mockResolvedValue(undefined);
+
+    await listClientApprovals();
+    await decideClientApproval("phone-1", true);
+    await decideClientApproval("tablet-2", false);
+
+    expect(invokeMock).toHaveBeenCalledWith("list_client_approvals");
+    expect(invokeMock).toHaveBeenCalledWith("decide_client_approval", {
+      clientId: "phone-1",
+      approved: true,
+    });
+    expect(invokeMock).toHaveBeenCalledWith("decide_client_approval", {
+      clientId: "tablet-2",
+      approved: false,
+    });
+  });
+
+  it("lists and decides remote action confirmations", async () => {
+    const invokeMock = vi.mocked(invoke);
+    invokeMock.mockResolvedValue(undefined);
+
+    await listRemoteActionConfirmations();
+    await decideRemoteActionConfirmation("confirm-1", false);
+
+    expect(invokeMock).toHaveBeenCalledWith("list_remote_action_confirmations");
+    expect(invokeMock).toHaveBeenCalledWith("decide_remote_action_confirmation", {
+      id: "confirm-1",
+      approved: false,
+    });
   });
 
   it("reads agent.md for a workspace", async () => {
@@ -1109,4 +2219,22 @@ describe("tauri invoke wrappers", () => {
     });
     expect(isPermissionGrantedMock).not.toHaveBeenCalled();
   });
+
+  it("skips notifications held back by Do Not Disturb", async () => {
+    const isPermissionGrantedMock = vi.mocked(notification.isPermissionGranted);
+    const sendNotificationMock = vi.mocked(notification.sendNotification);
+    const invokeMock = vi.mocked(invoke);
+
+    invokeMock.mockImplementation(async (command: string) =>
+      command === "should_show_notification" ? false : undefined,
+    );
+
+    await sendNotification("Quiet", "Hours", { urgent: true });
+
+    expect(invokeMock).toHaveBeenCalledWith("should_show_notification", {
+      urgent: true,
+    });
+    expect(isPermissionGrantedMock).not.toHaveBeenCalled();
+    expect(sendNotificationMock).not.toHaveBeenCalled();
+  });
 });
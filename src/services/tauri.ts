@@ -1,18 +1,94 @@
-import { invoke } from "@tauri-apps/api/core";
+import { invoke as tauriInvoke } from "@tauri-apps/api/core";
 import { open, save } from "@tauri-apps/plugin-dialog";
 import type { Options as NotificationOptions } from "@tauri-apps/plugin-notification";
 import type {
+  ActivityAlert,
+  ActivityReportFormat,
+  ActivityReportRange,
   AppSettings,
+  BackupReport,
+  BackupRestoreReport,
+  ClientApproval,
+  CodexApprovalMode,
+  CodexAuthStatus,
+  CodexHomeInfo,
+  CodexHomeRestoreReport,
+  CodexHomeSnapshotInfo,
+  CodexLoginFlow,
+  CodexSessionDetail,
+  CodexSessionInfo,
   CodexUpdateResult,
   CodexDoctorResult,
+  CommandCapability,
+  CommandErrorCode,
+  CommandErrorPayload,
+  ConfigChange,
+  DaemonPreflightReport,
   DictationModelStatus,
   DictationSessionState,
+  EditorInfo,
+  GeneratedActivityReport,
+  GuestToken,
+  GuestTokenScope,
+  HookRun,
+  HostCapabilities,
+  IssuedGuestToken,
   LocalUsageSnapshot,
+  MockBackendScript,
+  MonitorOverview,
+  Page,
+  PageResult,
+  PlatformCapabilities,
+  PluginInfo,
+  ProcessStats,
+  ProjectCiStatus,
+  ProxySettings,
+  ProxyTestResult,
+  RedactionSettings,
+  RedactionTestResult,
+  RegisteredShortcut,
+  RemoteActionConfirmation,
+  RemoteCommandAuditEntry,
+  RemoteCommandResult,
+  RemoteDirListing,
+  RemoteBackendMetrics,
+  RemoteBackendStatus,
+  RemoteFileChunk,
+  RemoteQualityReport,
+  Schedule,
+  ScheduleInput,
+  ScheduleRun,
+  SessionArchiveInfo,
+  SessionArchiveReport,
+  SessionExport,
+  SessionExportFormat,
+  SessionRestoreReport,
+  SessionSearchFilters,
+  SessionSearchHit,
+  SessionTemplate,
+  SettingsSyncReport,
+  SessionTemplateInput,
+  SessionPullRequest,
+  SessionWorktreeMergeResult,
+  StartupReport,
+  StorageCleanReport,
+  StorageHealth,
+  StorageTarget,
+  Tag,
+  TagAssignments,
+  TagInput,
+  TagTarget,
+  TailnetReachabilityReport,
+  TailscalePingResult,
+  TcpDaemonRestart,
   TcpDaemonStatus,
   TailscaleDaemonCommandPreview,
   TailscaleStatus,
   TrayRecentThreadEntry,
   TraySessionUsage,
+  TraySummary,
+  WebhookDelivery,
+  WindowState,
   WorkspaceInfo,
   AppMention,
   WorkspaceSettings,
@@ -29,6 +105,40 @@ import type {
   ReviewTarget,
 } from "../types";
 
+/** A failed backend command, carrying its error code for branching and localization. */
+export class CommandError extends Error {
+  readonly code: CommandErrorCode;
+  readonly details: unknown;
+
+  constructor(payload: CommandErrorPayload) {
+    super(payload.message);
+    this.name = "CommandError";
+    this.code = payload.code;
+    this.details = payload.details ?? null;
+  }
+
+  // Failures are often rendered with `String(error)`; keep that the plain message.
+  toString() {
+    return this.message;
+  }
+}
+
+function isCommandErrorPayload(error: unknown): error is CommandErrorPayload {
+  if (typeof error !== "object" || error === null || error instanceof Error) {
+    return false;
+  }
+  const candidate = error as Partial<CommandErrorPayload>;
+  return typeof candidate.code === "string" && typeof candidate.message === "string";
+}
+
+async function invoke<T>(...args: Parameters<typeof tauriInvoke>): Promise<T> {
+  try {
+    return await tauriInvoke<T>(...args);
+  } catch (error) {
+    throw isCommandErrorPayload(error) ? new CommandError(error) : error;
+  }
+}
+
 function isMissingTauriInvokeError(error: unknown) {
   return (
     error instanceof TypeError &&
@@ -119,6 +229,58 @@ export async function getCodexConfigPath(): Promise<string> {
   return invoke<string>("get_codex_config_path");
 }
 
+export async function getCodexHome(): Promise<CodexHomeInfo> {
+  return invoke<CodexHomeInfo>("get_codex_home");
+}
+
+/** Uses `path` as the Codex home; `null` goes back to `CODEX_HOME` or `~/.codex`. */
+export async function setCodexHomeOverride(path: string | null): Promise<CodexHomeInfo> {
+  return invoke<CodexHomeInfo>("set_codex_home_override", { path });
+}
+
+/** Saves config.toml, AGENTS.md and, unless `excludeSecrets`, auth.json. */
+export async function snapshotCodexHome(
+  label?: string | null,
+  excludeSecrets = false,
+): Promise<CodexHomeSnapshotInfo> {
+  return invoke<CodexHomeSnapshotInfo>("snapshot_codex_home", {
+    label: label ?? null,
+    excludeSecrets,
+  });
+}
+
+export async function listCodexHomeSnapshots(): Promise<CodexHomeSnapshotInfo[]> {
+  return invoke<CodexHomeSnapshotInfo[]>("list_codex_home_snapshots");
+}
+
+/** Restores a snapshot; the state it replaces is snapshotted first. */
+export async function restoreCodexHomeSnapshot(
+  id: string,
+): Promise<CodexHomeRestoreReport> {
+  return invoke<CodexHomeRestoreReport>("restore_codex_home_snapshot", { id });
+}
+
+/** Recorded config.toml writes, newest first. */
+export async function listConfigChanges(
+  page?: Partial<Page> | null,
+): Promise<PageResult<ConfigChange>> {
+  return invoke<PageResult<ConfigChange>>("list_config_changes", {
+    page: page ?? null,
+  });
+}
+
+/** Undoes one config.toml change; the revert is recorded as a change too. */
+export async function revertConfigChange(id: string): Promise<ConfigChange> {
+  return invoke<ConfigChange>("revert_config_change", { id });
+}
+
+export async function testProxy(
+  proxy: ProxySettings,
+  target?: string | null,
+): Promise<ProxyTestResult> {
+  return invoke<ProxyTestResult>("test_proxy", { proxy, target: target ?? null });
+}
+
 export type TextFileResponse = {
   exists: boolean;
   content: string;
@@ -369,6 +531,41 @@ export async function openWorkspaceIn(
   });
 }
 
+/** The project a `codexmonitor://open-project` link opened at launch. */
+export async function takeDeepLinkProject(): Promise<string | null> {
+  return invoke<string | null>("take_deep_link_project");
+}
+
+export async function listEditors(): Promise<EditorInfo[]> {
+  return invoke<EditorInfo[]>("list_editors");
+}
+
+export async function openInEditor(
+  path: string,
+  line?: number | null,
+): Promise<void> {
+  return invoke("open_in_editor", { path, line: line ?? null });
+}
+
+export async function openDiffInEditor(
+  sessionId: string,
+  file: string,
+): Promise<void> {
+  return invoke("open_diff_in_editor", { sessionId, file });
+}
+
+export async function listPlugins(): Promise<PluginInfo[]> {
+  return invoke<PluginInfo[]>("list_plugins");
+}
+
+/** Turns a plugin's `ext.*` methods on or off; returns every plugin. */
+export async function enablePlugin(
+  id: string,
+  enabled: boolean,
+): Promise<PluginInfo[]> {
+  return invoke<PluginInfo[]>("enable_plugin", { id, enabled });
+}
+
 export async function getOpenAppIcon(appName: string): Promise<string | null> {
   return invoke<string | null>("get_open_app_icon", { appName });
 }
@@ -683,6 +880,12 @@ export async function getGitHubIssues(
   return invoke("get_github_issues", { workspaceId: workspace_id });
 }
 
+export async function getProjectCiStatus(
+  projectId: string,
+): Promise<ProjectCiStatus> {
+  return invoke<ProjectCiStatus>("get_project_ci_status", { projectId });
+}
+
 export async function getGitHubPullRequests(
   workspace_id: string,
 ): Promise<GitHubPullRequestsResponse> {
@@ -730,6 +933,253 @@ export async function localUsageSnapshot(
   return invoke("local_usage_snapshot", payload);
 }
 
+export async function generateActivityReport(
+  range: ActivityReportRange,
+  format: ActivityReportFormat,
+  options?: { save?: boolean },
+): Promise<GeneratedActivityReport> {
+  return invoke<GeneratedActivityReport>("generate_activity_report", {
+    range,
+    format,
+    save: options?.save ?? false,
+  });
+}
+
+export async function runRemoteCommand(
+  workspaceId: string,
+  command: string,
+  options?: { cwd?: string | null; runId?: string | null },
+): Promise<RemoteCommandResult> {
+  return invoke<RemoteCommandResult>("run_remote_command", {
+    workspaceId,
+    command,
+    cwd: options?.cwd ?? null,
+    runId: options?.runId ?? null,
+  });
+}
+
+/** Newest first, from the daemon's log in remote mode. */
+export async function listRemoteCommandAudit(
+  page?: Partial<Page> | null,
+): Promise<PageResult<RemoteCommandAuditEntry>> {
+  return invoke<PageResult<RemoteCommandAuditEntry>>(
+    "list_remote_command_audit",
+    { page: page ?? null },
+  );
+}
+
+export async function remoteListDir(
+  workspaceId: string,
+  relPath?: string | null,
+): Promise<RemoteDirListing> {
+  return invoke<RemoteDirListing>("remote_list_dir", {
+    workspaceId,
+    relPath: relPath ?? null,
+  });
+}
+
+export async function remoteReadFile(
+  workspaceId: string,
+  relPath: string,
+  range?: { offset?: number; length?: number },
+): Promise<RemoteFileChunk> {
+  return invoke<RemoteFileChunk>("remote_read_file", {
+    workspaceId,
+    relPath,
+    offset: range?.offset ?? null,
+    length: range?.length ?? null,
+  });
+}
+
+export async function searchSessions(
+  query: string,
+  filters?: SessionSearchFilters | null,
+): Promise<SessionSearchHit[]> {
+  return invoke<SessionSearchHit[]>("search_sessions", {
+    query,
+    filters: filters ?? null,
+  });
+}
+
+export async function archiveSessions(options?: {
+  before?: number | null;
+  dryRun?: boolean;
+}): Promise<SessionArchiveReport> {
+  return invoke<SessionArchiveReport>("archive_sessions", {
+    before: options?.before ?? null,
+    dryRun: options?.dryRun ?? false,
+  });
+}
+
+export async function listArchives(
+  page?: Partial<Page> | null,
+): Promise<PageResult<SessionArchiveInfo>> {
+  return invoke<PageResult<SessionArchiveInfo>>("list_archives", {
+    page: page ?? null,
+  });
+}
+
+export async function startCodexSession(
+  projectPath: string | null,
+  prompt: string | null,
+  options?: {
+    model?: string | null;
+    approvalMode?: CodexApprovalMode | null;
+    templateId?: string | null;
+    isolateWorktree?: boolean;
+  },
+): Promise<CodexSessionInfo> {
+  return invoke<CodexSessionInfo>("start_codex_session", {
+    projectPath,
+    prompt,
+    model: options?.model ?? null,
+    approvalMode: options?.approvalMode ?? null,
+    templateId: options?.templateId ?? null,
+    isolateWorktree: options?.isolateWorktree ?? false,
+  });
+}
+
+export async function mergeSessionWorktree(
+  sessionId: string,
+): Promise<SessionWorktreeMergeResult> {
+  return invoke<SessionWorktreeMergeResult>("merge_session_worktree", {
+    sessionId,
+  });
+}
+
+export async function createPrFromSession(
+  sessionId: string,
+  title: string,
+  options?: { body?: string | null; base?: string | null },
+): Promise<SessionPullRequest> {
+  return invoke<SessionPullRequest>("create_pr_from_session", {
+    sessionId,
+    title,
+    body: options?.body ?? null,
+    base: options?.base ?? null,
+  });
+}
+
+export async function discardSessionWorktree(sessionId: string): Promise<void> {
+  return invoke("discard_session_worktree", { sessionId });
+}
+
+export async function listSessionTemplates(): Promise<SessionTemplate[]> {
+  return invoke<SessionTemplate[]>("list_session_templates");
+}
+
+export async function saveSessionTemplate(
+  template: SessionTemplateInput,
+): Promise<SessionTemplate> {
+  return invoke<SessionTemplate>("save_session_template", { template });
+}
+
+export async function deleteSessionTemplate(id: string): Promise<void> {
+  return invoke("delete_session_template", { id });
+}
+
+export async function listTags(): Promise<Tag[]> {
+  return invoke<Tag[]>("list_tags");
+}
+
+export async function saveTag(tag: TagInput): Promise<Tag> {
+  return invoke<Tag>("save_tag", { tag });
+}
+
+export async function deleteTag(id: string): Promise<void> {
+  return invoke("delete_tag", { id });
+}
+
+/** Replaces the tags of a session, or of a project by its workspace id. */
+export async function setTags(
+  target: TagTarget,
+  id: string,
+  tagIds: string[],
+): Promise<string[]> {
+  return invoke<string[]>("set_tags", { target, id, tagIds });
+}
+
+export async function getTagAssignments(): Promise<TagAssignments> {
+  return invoke<TagAssignments>("get_tag_assignments");
+}
+
+export async function listSchedules(): Promise<Schedule[]> {
+  return invoke<Schedule[]>("list_schedules");
+}
+
+export async function upsertSchedule(schedule: ScheduleInput): Promise<Schedule> {
+  return invoke<Schedule>("upsert_schedule", { schedule });
+}
+
+export async function runScheduleNow(id: string): Promise<ScheduleRun> {
+  return invoke<ScheduleRun>("run_schedule_now", { id });
+}
+
+export async function sendSessionInput(
+  sessionId: string,
+  text: string,
+): Promise<void> {
+  return invoke("send_session_input", { sessionId, text });
+}
+
+/** Monitor-started and queued sessions, narrowed to those with every tag. */
+export async function listCodexSessions(
+  tags?: string[] | null,
+  page?: Partial<Page> | null,
+): Promise<PageResult<CodexSessionInfo>> {
+  return invoke<PageResult<CodexSessionInfo>>("list_codex_sessions", {
+    tags: tags ?? null,
+    page: page ?? null,
+  });
+}
+
+export async function getCodexSession(
+  sessionId: string,
+): Promise<CodexSessionDetail> {
+  return invoke<CodexSessionDetail>("get_codex_session", { sessionId });
+}
+
+export async function exportSession(
+  sessionId: string,
+  format: SessionExportFormat,
+  options?: { includeToolOutput?: boolean },
+): Promise<SessionExport> {
+  return invoke<SessionExport>("export_session", {
+    sessionId,
+    format,
+    includeToolOutput: options?.includeToolOutput ?? false,
+  });
+}
+
+export async function getProcessStats(pid?: number | null): Promise<ProcessStats> {
+  return invoke<ProcessStats>("get_process_stats", { pid: pid ?? null });
+}
+
+export async function cancelQueuedSession(sessionId: string): Promise<void> {
+  return invoke("cancel_queued_session", { sessionId });
+}
+
+export async function interruptSession(sessionId: string): Promise<void> {
+  return invoke("interrupt_session", { sessionId });
+}
+
+export async function terminateSession(
+  sessionId: string,
+  options?: { pid?: number | null; force?: boolean },
+): Promise<void> {
+  return invoke("terminate_session", {
+    sessionId,
+    pid: options?.pid ?? null,
+    force: options?.force ?? false,
+  });
+}
+
+export async function restoreArchive(
+  name: string,
+): Promise<SessionRestoreReport> {
+  return invoke<SessionRestoreReport>("restore_archive", { name });
+}
+
 export async function getModelList(workspaceId: string) {
   return invoke<any>("model_list", { workspaceId });
 }
@@ -781,6 +1231,24 @@ export async function cancelCodexLogin(workspaceId: string) {
   );
 }
 
+/** Whether the Codex CLI is signed in, and until when. */
+export async function getCodexAuthStatus(): Promise<CodexAuthStatus> {
+  return invoke<CodexAuthStatus>("codex_auth_status");
+}
+
+/** Starts a device-code sign-in; enter `userCode` at `verificationUrl`. */
+export async function startCodexLogin(): Promise<CodexLoginFlow> {
+  return invoke<CodexLoginFlow>("codex_login_start");
+}
+
+export async function getCodexLoginFlowStatus(): Promise<CodexLoginFlow | null> {
+  return invoke<CodexLoginFlow | null>("codex_login_flow_status");
+}
+
+export async function cancelCodexLoginFlow(): Promise<CodexLoginFlow> {
+  return invoke<CodexLoginFlow>("codex_login_flow_cancel");
+}
+
 export async function getSkillsList(workspaceId: string) {
   return invoke<any>("skills_list", { workspaceId });
 }
@@ -869,28 +1337,296 @@ export async function isMobileRuntime(): Promise<boolean> {
   return invoke<boolean>("is_mobile_runtime");
 }
 
+/**
+ * Which device subsystems (terminal, dictation, Tailscale, ...) this build
+ * has. Mobile builds are remote-only and report them all unavailable.
+ */
+export async function getPlatformCapabilities(): Promise<PlatformCapabilities> {
+  return invoke<PlatformCapabilities>("get_platform_capabilities");
+}
+
 export async function updateAppSettings(settings: AppSettings): Promise<AppSettings> {
   return invoke<AppSettings>("update_app_settings", { settings });
 }
 
-export async function tailscaleStatus(): Promise<TailscaleStatus> {
-  return invoke<TailscaleStatus>("tailscale_status");
+/** Moves the app's state to `newPath`; the app restarts when it succeeds. */
+export async function migrateDataDir(
+  newPath: string,
+  overwrite = false,
+): Promise<void> {
+  return invoke("migrate_data_dir", { newPath, overwrite });
+}
+
+/**
+ * Saves settings, workspaces, templates, schedules and guest tokens to
+ * `path`. Tokens and proxy credentials are only included, encrypted, with a
+ * passphrase.
+ */
+export async function createBackup(
+  path: string,
+  passphrase?: string | null,
+): Promise<BackupReport> {
+  return invoke<BackupReport>("create_backup", { path, passphrase: passphrase ?? null });
+}
+
+/** Replaces the app's state with the backup at `path`; the app restarts when it succeeds. */
+export async function restoreBackup(
+  path: string,
+  passphrase?: string | null,
+): Promise<BackupRestoreReport> {
+  return invoke<BackupRestoreReport>("restore_backup", {
+    path,
+    passphrase: passphrase ?? null,
+  });
+}
+
+/**
+ * Pulls shared settings, session templates and projects from the remote
+ * backend chosen in `settingsSync`.
+ */
+export async function syncSettingsNow(): Promise<SettingsSyncReport> {
+  return invoke<SettingsSyncReport>("sync_settings_now");
+}
+
+/** Newest first; `endpointId` narrows the log to one webhook. */
+export async function listWebhookDeliveries(
+  endpointId?: string | null,
+  page?: Partial<Page> | null,
+): Promise<PageResult<WebhookDelivery>> {
+  return invoke<PageResult<WebhookDelivery>>("list_webhook_deliveries", {
+    endpointId: endpointId ?? null,
+    page: page ?? null,
+  });
+}
+
+/** Logged runs of automation hooks, newest first. */
+export async function listHookRuns(
+  hookId?: string | null,
+  page?: Partial<Page> | null,
+): Promise<PageResult<HookRun>> {
+  return invoke<PageResult<HookRun>>("list_hook_runs", {
+    hookId: hookId ?? null,
+    page: page ?? null,
+  });
+}
+
+/** Posts a sample message through a Slack or Discord integration, once. */
+export async function sendTestMessage(integration: string): Promise<void> {
+  return invoke("send_test_message", { integration });
+}
+
+/** Holds notifications back for `minutes`; `0` resumes them. */
+export async function notificationsSnooze(
+  minutes: number,
+): Promise<number | null> {
+  return invoke<number | null>("notifications_snooze", { minutes });
+}
+
+/**
+ * Runs `sample` through `settings`, or through the saved redaction rules
+ * (the daemon's in remote mode).
+ */
+export async function testRedactionRules(
+  sample: string,
+  settings?: RedactionSettings | null,
+): Promise<RedactionTestResult> {
+  return invoke<RedactionTestResult>("test_redaction_rules", {
+    sample,
+    settings: settings ?? null,
+  });
+}
+
+/** OS, CPU, memory and runtimes of the machine sessions run on. */
+export async function getHostCapabilities(): Promise<HostCapabilities> {
+  return invoke<HostCapabilities>("get_host_capabilities");
+}
+
+export async function getStorageHealth(): Promise<StorageHealth> {
+  return invoke<StorageHealth>("get_storage_health");
+}
+
+/** Empties logs and deletes caches; session archives are never touched. */
+export async function cleanStorage(targets: StorageTarget[]): Promise<StorageCleanReport> {
+  return invoke<StorageCleanReport>("clean_storage", { targets });
+}
+
+export async function tailscaleStatus(forceRefresh = false): Promise<TailscaleStatus> {
+  return invoke<TailscaleStatus>("tailscale_status", { forceRefresh });
 }
 
 export async function tailscaleDaemonCommandPreview(): Promise<TailscaleDaemonCommandPreview> {
   return invoke<TailscaleDaemonCommandPreview>("tailscale_daemon_command_preview");
 }
 
-export async function tailscaleDaemonStart(): Promise<TcpDaemonStatus> {
-  return invoke<TcpDaemonStatus>("tailscale_daemon_start");
+/**
+ * The managed daemon's identity secret, created on first use. Clients paste
+ * it next to the token so they only send the token to this daemon.
+ */
+export async function tailscaleDaemonIdentity(): Promise<string> {
+  return invoke<string>("tailscale_daemon_identity");
+}
+
+/**
+ * Starts the managed daemon. With `takeover`, a daemon holding its data dir
+ * is stopped first instead of failing with `DataDirLocked`.
+ */
+export async function tailscaleDaemonStart(takeover = false): Promise<TcpDaemonStatus> {
+  return invoke<TcpDaemonStatus>("tailscale_daemon_start", { takeover });
 }
 
 export async function tailscaleDaemonStop(): Promise<TcpDaemonStatus> {
   return invoke<TcpDaemonStatus>("tailscale_daemon_stop");
 }
 
-export async function tailscaleDaemonStatus(): Promise<TcpDaemonStatus> {
-  return invoke<TcpDaemonStatus>("tailscale_daemon_status");
+/** Stops and starts the daemon as one operation, resolving once it is ready. */
+export async function tailscaleDaemonRestart(): Promise<TcpDaemonRestart> {
+  return invoke<TcpDaemonRestart>("tailscale_daemon_restart");
+}
+
+/**
+ * Restarts a daemon whose status has `updatePending` onto the updated binary,
+ * draining its clients first. Resolves with the status unchanged otherwise.
+ */
+export async function tailscaleDaemonApplyUpdate(): Promise<TcpDaemonStatus> {
+  return invoke<TcpDaemonStatus>("tailscale_daemon_apply_update");
+}
+
+/** `verbose` adds the arguments and environment the daemon was spawned with. */
+export async function tailscaleDaemonStatus(verbose = false): Promise<TcpDaemonStatus> {
+  return invoke<TcpDaemonStatus>("tailscale_daemon_status", { verbose });
+}
+
+/** Tailscale, daemon and settings in one call; each section fails on its own. */
+export async function getMonitorOverview(): Promise<MonitorOverview> {
+  return invoke<MonitorOverview>("get_monitor_overview");
+}
+
+export async function createGuestToken(
+  scopes: GuestTokenScope[],
+  ttlSeconds: number,
+  label?: string | null,
+): Promise<IssuedGuestToken> {
+  return invoke<IssuedGuestToken>("create_guest_token", {
+    scopes,
+    ttlSeconds,
+    label: label ?? null,
+  });
+}
+
+export async function listGuestTokens(): Promise<GuestToken[]> {
+  return invoke<GuestToken[]>("list_guest_tokens");
+}
+
+export async function revokeGuestToken(id: string): Promise<void> {
+  return invoke("revoke_guest_token", { id });
+}
+
+/** Clients seen while approval was required, pending ones first. */
+export async function listCapabilities(): Promise<CommandCapability[]> {
+  return invoke<CommandCapability[]>("list_capabilities");
+}
+
+export async function getStartupReport(): Promise<StartupReport> {
+  return invoke<StartupReport>("get_startup_report");
+}
+
+export async function listClientApprovals(): Promise<ClientApproval[]> {
+  return invoke<ClientApproval[]>("list_client_approvals");
+}
+
+export async function decideClientApproval(
+  clientId: string,
+  approved: boolean,
+): Promise<ClientApproval> {
+  return invoke<ClientApproval>("decide_client_approval", { clientId, approved });
+}
+
+/** Destructive remote requests held for confirmation, pending ones first. */
+export async function listRemoteActionConfirmations(): Promise<
+  RemoteActionConfirmation[]
+> {
+  return invoke<RemoteActionConfirmation[]>("list_remote_action_confirmations");
+}
+
+export async function decideRemoteActionConfirmation(
+  id: string,
+  approved: boolean,
+): Promise<RemoteActionConfirmation> {
+  return invoke<RemoteActionConfirmation>("decide_remote_action_confirmation", {
+    id,
+    approved,
+  });
+}
+
+export async function tailscaleDaemonRegisterFirewall(
+  verifySignature = true,
+): Promise<TcpDaemonStatus> {
+  return invoke<TcpDaemonStatus>("tailscale_daemon_register_firewall", {
+    verifySignature,
+  });
+}
+
+/** Takes over a daemon started outside the app once it proves its identity. */
+export async function adoptExternalDaemon(): Promise<TcpDaemonStatus> {
+  return invoke<TcpDaemonStatus>("adopt_external_daemon");
+}
+
+export async function installDaemonService(): Promise<TcpDaemonStatus> {
+  return invoke<TcpDaemonStatus>("install_daemon_service");
+}
+
+export async function uninstallDaemonService(): Promise<TcpDaemonStatus> {
+  return invoke<TcpDaemonStatus>("uninstall_daemon_service");
+}
+
+export async function tailscaleDaemonPreflight(): Promise<DaemonPreflightReport> {
+  return invoke<DaemonPreflightReport>("tailscale_daemon_preflight");
+}
+
+export async function getRemoteBackendStatus(): Promise<RemoteBackendStatus> {
+  return invoke<RemoteBackendStatus>("remote_backend_status");
+}
+
+/** Per-method call counts and latency, and the recent slow calls. */
+export async function getRemoteBackendMetrics(): Promise<RemoteBackendMetrics> {
+  return invoke<RemoteBackendMetrics>("get_remote_backend_metrics");
+}
+
+export async function probeRemoteQuality(
+  pings?: number,
+): Promise<RemoteQualityReport> {
+  return invoke<RemoteQualityReport>("probe_remote_quality", {
+    pings: pings ?? null,
+  });
+}
+
+export async function checkTailnetReachability(
+  peer?: string,
+): Promise<TailnetReachabilityReport> {
+  return invoke<TailnetReachabilityReport>("check_tailnet_reachability", {
+    peer: peer ?? null,
+  });
+}
+
+export async function tailscalePingPeer(
+  target: string,
+): Promise<TailscalePingResult> {
+  return invoke<TailscalePingResult>("tailscale_ping_peer", { target });
+}
+
+export async function getTailscalePingHistory(
+  target?: string,
+): Promise<TailscalePingResult[]> {
+  return invoke<TailscalePingResult[]>("tailscale_ping_history", {
+    target: target ?? null,
+  });
+}
+
+/** Rescripts the mock backends; fails unless the app runs with them. */
+export async function setMockBackendScript(
+  script: MockBackendScript,
+): Promise<void> {
+  return invoke("set_mock_backend_script", { script });
 }
 
 type MenuAcceleratorUpdate = {
@@ -1096,6 +1832,38 @@ export async function setTraySessionUsage(usage: TraySessionUsage | null) {
   return invoke<void>("set_tray_session_usage", { usage });
 }
 
+/** The tray popover's summary, from state the background refresher caches. */
+export async function getTraySummary(): Promise<TraySummary> {
+  return invoke<TraySummary>("get_tray_summary");
+}
+
+/** The size and place saved for a window, `main` by default. */
+export async function getWindowState(
+  label?: string | null,
+): Promise<WindowState | null> {
+  return invoke<WindowState | null>("get_window_state", { label: label ?? null });
+}
+
+/** Forgets a window's saved size and place and recenters it. */
+export async function resetWindowState(label?: string | null): Promise<void> {
+  return invoke<void>("reset_window_state", { label: label ?? null });
+}
+
+/** The configured global shortcuts and whether each one is registered. */
+export async function listRegisteredShortcuts(): Promise<RegisteredShortcut[]> {
+  return invoke<RegisteredShortcut[]>("list_registered_shortcuts");
+}
+
+/** The activity the compact alert window shows. */
+export async function getActivityAlert(): Promise<ActivityAlert | null> {
+  return invoke<ActivityAlert | null>("get_activity_alert");
+}
+
+/** Closes the compact alert window, bringing up the main one when `open`. */
+export async function dismissActivityAlert(open = false): Promise<void> {
+  return invoke<void>("dismiss_activity_alert", { open });
+}
+
 export async function generateCommitMessage(
   workspaceId: string,
   commitMessageModelId: string | null,
@@ -1131,8 +1899,18 @@ export async function sendNotification(
     sound?: string;
     autoCancel?: boolean;
     extra?: Record<string, unknown>;
+    /** Lets the notification through Do Not Disturb when allowed. */
+    urgent?: boolean;
   },
 ): Promise<void> {
+  // Do Not Disturb is enforced by the backend; show the notification if it
+  // cannot be asked.
+  const allowed = await invoke<boolean>("should_show_notification", {
+    urgent: options?.urgent ?? false,
+  }).catch(() => true);
+  if (allowed === false) {
+    return;
+  }
   const macosDebugBuild = await invoke<boolean>("is_macos_debug_build").catch(
     () => false,
   );
@@ -0,0 +1,2124 @@
+// Generated from src-tauri/src/types.rs and the command registry.
+// Do not edit; run `npm run bindings` after changing the Rust types.
+
+export type JsonValue =
+  | null
+  | boolean
+  | number
+  | string
+  | JsonValue[]
+  | { [key: string]: JsonValue };
+
+export type GitFileStatus = {
+  path: string;
+  status: string;
+  additions: number;
+  deletions: number;
+};
+
+export type GitFileDiff = {
+  path: string;
+  diff: string;
+  oldLines: string[] | null;
+  newLines: string[] | null;
+  isBinary: boolean;
+  isImage: boolean;
+  oldImageData: string | null;
+  newImageData: string | null;
+  oldImageMime: string | null;
+  newImageMime: string | null;
+};
+
+export type GitCommitDiff = {
+  path: string;
+  status: string;
+  diff: string;
+  oldLines: string[] | null;
+  newLines: string[] | null;
+  isBinary: boolean;
+  isImage: boolean;
+  oldImageData: string | null;
+  newImageData: string | null;
+  oldImageMime: string | null;
+  newImageMime: string | null;
+};
+
+export type GitLogEntry = {
+  sha: string;
+  summary: string;
+  author: string;
+  timestamp: number;
+};
+
+export type GitLogResponse = {
+  total: number;
+  entries: GitLogEntry[];
+  ahead: number;
+  behind: number;
+  aheadEntries: GitLogEntry[];
+  behindEntries: GitLogEntry[];
+  upstream: string | null;
+};
+
+export type GitHubIssue = {
+  number: number;
+  title: string;
+  url: string;
+  updatedAt: string;
+};
+
+export type GitHubIssuesResponse = {
+  total: number;
+  issues: GitHubIssue[];
+};
+
+export type GitHubPullRequestAuthor = {
+  login: string;
+};
+
+export type GitHubPullRequest = {
+  number: number;
+  title: string;
+  url: string;
+  updatedAt: string;
+  createdAt: string;
+  body: string;
+  headRefName: string;
+  baseRefName: string;
+  isDraft: boolean;
+  author: GitHubPullRequestAuthor | null;
+};
+
+export type GitHubPullRequestsResponse = {
+  total: number;
+  pullRequests: GitHubPullRequest[];
+};
+
+export type GitHubPullRequestDiff = {
+  path: string;
+  status: string;
+  diff: string;
+};
+
+export type GitHubPullRequestComment = {
+  id: number;
+  body: string;
+  createdAt: string;
+  url: string;
+  author: GitHubPullRequestAuthor | null;
+};
+
+export type LocalUsageDay = {
+  day: string;
+  inputTokens: number;
+  cachedInputTokens: number;
+  outputTokens: number;
+  totalTokens: number;
+  agentTimeMs: number;
+  agentRuns: number;
+};
+
+export type LocalUsageTotals = {
+  last7DaysTokens: number;
+  last30DaysTokens: number;
+  averageDailyTokens: number;
+  cacheHitRatePercent: number;
+  peakDay: string | null;
+  peakDayTokens: number;
+};
+
+export type LocalUsageModel = {
+  model: string;
+  tokens: number;
+  sharePercent: number;
+};
+
+export type LocalUsageSnapshot = {
+  updatedAt: number;
+  days: LocalUsageDay[];
+  totals: LocalUsageTotals;
+  topModels: LocalUsageModel[];
+};
+
+export type ActivityReportRange = "week" | "month";
+
+export type ActivityReportFormat = "markdown" | "json";
+
+export type ActivityProject = {
+  name: string;
+  path: string;
+  sessions: number;
+};
+
+export type ActivityReport = {
+  range: ActivityReportRange;
+  fromDay: string;
+  toDay: string;
+  generatedAtMs: number;
+  sessions: number;
+  agentTimeMs: number;
+  agentRuns: number;
+  inputTokens: number;
+  cachedInputTokens: number;
+  outputTokens: number;
+  totalTokens: number;
+  projects: ActivityProject[];
+  scheduledRuns: number;
+  failedScheduledRuns: number;
+  days: LocalUsageDay[];
+};
+
+export type GeneratedActivityReport = {
+  format: ActivityReportFormat;
+  content: string;
+  path: string | null;
+};
+
+export type TcpDaemonState =
+  | "stopped"
+  | "starting"
+  | "running"
+  | "stopping"
+  | "error";
+
+export type ProcessOperation = "starting" | "stopping" | "restarting";
+
+export type ProcessLaunch = {
+  command: string;
+  shell: PreviewShell;
+  program: string;
+  args: string[];
+  env: Record<string, string>;
+  workingDir: string | null;
+};
+
+export type TcpDaemonStatus = {
+  state: TcpDaemonState;
+  pid: number | null;
+  startedAtMs: number | null;
+  lastError: string | null;
+  listenAddr: string | null;
+  firewallTrust: DaemonFirewallTrust | null;
+  serviceManager: DaemonServiceManager | null;
+  operation: ProcessOperation | null;
+  launch: ProcessLaunch | null;
+  managedExternally: boolean;
+  lastStop: DaemonStop | null;
+  updatePending: boolean;
+};
+
+export type DaemonStopReason =
+  | "user"
+  | "app_exit"
+  | "data_dir_change"
+  | "update"
+  | "crash"
+  | "exited";
+
+export type DaemonStop = {
+  reason: DaemonStopReason;
+  atMs: number;
+  detail: string | null;
+};
+
+export type TcpDaemonRestart = {
+  status: TcpDaemonStatus;
+  stopMs: number;
+  startMs: number;
+  readyMs: number;
+  totalMs: number;
+};
+
+export type DaemonServiceManager = "launchd" | "systemd";
+
+export type DaemonFirewallTrust =
+  | "off"
+  | "allowed"
+  | "blocked"
+  | "unregistered"
+  | "block_all"
+  | "unknown";
+
+export type TailscaleStatus = {
+  installed: boolean;
+  running: boolean;
+  version: string | null;
+  dnsName: string | null;
+  hostName: string | null;
+  tailnetName: string | null;
+  ipv4: string[];
+  ipv6: string[];
+  suggestedRemoteHost: string | null;
+  message: string;
+  tags: string[];
+  keyExpiryMs: number | null;
+  keyExpiringSoon: boolean;
+  stale: boolean;
+  lastSeenMs: number | null;
+};
+
+export type MockTailscaleState =
+  | "not_installed"
+  | "stopped"
+  | "needs_login"
+  | "needs_machine_auth"
+  | "starting"
+  | "running";
+
+export type MockTailscaleStep = {
+  afterMs: number;
+  state: MockTailscaleState;
+};
+
+export type MockBackendScript = {
+  tailscale: MockTailscaleStep[];
+  daemonStartMs: number;
+  daemonStopMs: number;
+  daemonStartError: string | null;
+};
+
+export type DaemonPreflightCheckStatus = "pass" | "warn" | "fail" | "skipped";
+
+export type DaemonPreflightCheck = {
+  id: string;
+  label: string;
+  status: DaemonPreflightCheckStatus;
+  detail: string;
+  fix: string | null;
+};
+
+export type DaemonPreflightReport = {
+  listenAddr: string;
+  ready: boolean;
+  checks: DaemonPreflightCheck[];
+};
+
+export type PreviewShell = "posix" | "powershell";
+
+export type TailscaleDaemonCommandPreview = {
+  command: string;
+  shell: PreviewShell;
+  daemonPath: string;
+  args: string[];
+  env: Record<string, string>;
+  workingDir: string | null;
+  tokenConfigured: boolean;
+};
+
+export type RemoteLinkPath = "local" | "direct" | "relay" | "unknown";
+
+export type RemoteQualityReport = {
+  host: string;
+  pingsSent: number;
+  pingsLost: number;
+  rttMinMs: number | null;
+  rttAvgMs: number | null;
+  rttMaxMs: number | null;
+  jitterMs: number | null;
+  throughputKbps: number | null;
+  path: RemoteLinkPath;
+  relayRegion: string | null;
+  suggestedPollIntervalMs: number;
+  suggestedMaxPayloadBytes: number;
+};
+
+export type RemoteBackendStatus = {
+  connected: boolean;
+  liveSubscriptions: number;
+  idleMs: number | null;
+  linkPath: RemoteLinkPath | null;
+  suggestedPollMs: number;
+};
+
+export type RemoteMethodMetrics = {
+  method: string;
+  calls: number;
+  errors: number;
+  totalMs: number;
+  maxMs: number;
+  histogram: number[];
+};
+
+export type RemoteSlowCall = {
+  method: string;
+  traceId: string;
+  durationMs: number;
+  finishedAtMs: number;
+  error: string | null;
+};
+
+export type RemoteBackendMetrics = {
+  latencyBucketsMs: number[];
+  slowCallMs: number;
+  methods: RemoteMethodMetrics[];
+  slowCalls: RemoteSlowCall[];
+};
+
+export type TailscalePong = {
+  latencyMs: number | null;
+  endpoint: string | null;
+  derpRegion: string | null;
+  error: string | null;
+};
+
+export type TailscalePingResult = {
+  target: string;
+  nodeName: string | null;
+  nodeIp: string | null;
+  pongs: TailscalePong[];
+  path: RemoteLinkPath;
+  latencyMs: number | null;
+  error: string | null;
+  pingedAtMs: number;
+};
+
+export type TcpProbeOutcome = "connected" | "refused" | "timed_out" | "failed";
+
+export type TcpProbeResult = {
+  outcome: TcpProbeOutcome;
+  latencyMs: number | null;
+  error: string | null;
+};
+
+export type TailnetReachabilityReport = {
+  prober: string;
+  targetHost: string;
+  targetPort: number;
+  reachable: boolean;
+  probe: TcpProbeResult;
+  tailnetName: string | null;
+  policyOwner: string | null;
+  guidance: string;
+};
+
+export type Page = {
+  cursor: string | null;
+  limit: number | null;
+};
+
+export type PageResult<T> = {
+  items: T[];
+  nextCursor: string | null;
+};
+
+export type RemoteCommandResult = {
+  runId: string;
+  exitCode: number | null;
+  timedOut: boolean;
+  durationMs: number;
+  stdout: string;
+  stderr: string;
+  truncated: boolean;
+};
+
+export type RemoteCommandAuditEntry = {
+  timestampMs: number;
+  runId: string;
+  workspaceId: string;
+  command: string;
+  cwd: string | null;
+  outcome: string;
+  error: string | null;
+  resolvedCwd: string | null;
+  exitCode: number | null;
+  durationMs: number | null;
+};
+
+export type RemoteDirEntry = {
+  name: string;
+  path: string;
+  kind: string;
+  size: number | null;
+  modifiedMs: number | null;
+};
+
+export type RemoteDirListing = {
+  path: string;
+  entries: RemoteDirEntry[];
+  truncated: boolean;
+};
+
+export type RemoteFileChunk = {
+  path: string;
+  size: number;
+  offset: number;
+  length: number;
+  binary: boolean;
+  truncated: boolean;
+  content: string | null;
+};
+
+export type SessionSearchFilters = {
+  workspaceId: string | null;
+  role: string | null;
+  sinceMs: number | null;
+  untilMs: number | null;
+  limit: number | null;
+  tags: string[];
+};
+
+export type SnippetSegment = {
+  text: string;
+  highlight: boolean;
+};
+
+export type SessionSearchHit = {
+  sessionId: string;
+  path: string;
+  cwd: string | null;
+  timestampMs: number | null;
+  role: string;
+  snippet: SnippetSegment[];
+};
+
+export type SessionExportFormat = "markdown" | "html";
+
+export type SessionExport = {
+  sessionId: string;
+  format: SessionExportFormat;
+  fileName: string;
+  path: string;
+  size: number;
+};
+
+export type SessionArchiveInfo = {
+  name: string;
+  path: string;
+  sizeBytes: number;
+  createdAtMs: number | null;
+  fileCount: number;
+  originalBytes: number;
+};
+
+export type SessionArchiveReport = {
+  dryRun: boolean;
+  fileCount: number;
+  reclaimedBytes: number;
+  archive: SessionArchiveInfo | null;
+};
+
+export type SessionRestoreReport = {
+  restored: number;
+  skipped: number;
+};
+
+export type BackupReport = {
+  path: string;
+  createdAtMs: number;
+  files: string[];
+  secretsIncluded: boolean;
+};
+
+export type BackupRestoreReport = {
+  appVersion: string;
+  createdAtMs: number;
+  files: string[];
+  secretsRestored: boolean;
+};
+
+export type StorageUsage = {
+  logsBytes: number;
+  archivesBytes: number;
+  cachesBytes: number;
+  totalBytes: number;
+};
+
+export type StorageHealth = {
+  dataDir: string;
+  freeBytes: number | null;
+  lowSpace: boolean;
+  usage: StorageUsage;
+};
+
+export type StorageTarget = "logs" | "caches";
+
+export type StorageCleanReport = {
+  freedBytes: number;
+  cleaned: string[];
+};
+
+export type HostRuntime = {
+  name: string;
+  version: string;
+};
+
+export type HostCapabilities = {
+  os: string;
+  arch: string;
+  cpuCores: number;
+  totalMemoryBytes: number | null;
+  runtimes: HostRuntime[];
+};
+
+export type PlatformSubsystem =
+  | "local_backend"
+  | "terminal"
+  | "dictation"
+  | "tailscale"
+  | "daemon_management"
+  | "tray"
+  | "menu"
+  | "global_shortcuts";
+
+export type PlatformSubsystemStatus = {
+  subsystem: PlatformSubsystem;
+  available: boolean;
+  reason: string | null;
+};
+
+export type PlatformCapabilities = {
+  platform: string;
+  mobile: boolean;
+  remoteOnly: boolean;
+  subsystems: PlatformSubsystemStatus[];
+};
+
+export type WebhookEvent =
+  | "session_completed"
+  | "approval_needed"
+  | "daemon_down"
+  | "ci_failed";
+
+export type WebhookEndpoint = {
+  id: string;
+  name: string;
+  url: string;
+  secret: string | null;
+  events: WebhookEvent[];
+  enabled: boolean;
+};
+
+export type NotificationIntegrationKind = "slack" | "discord";
+
+export type NotificationIntegration = {
+  id: string;
+  kind: NotificationIntegrationKind;
+  name: string;
+  keychainAccount: string;
+  events: WebhookEvent[];
+  messageTemplate: string | null;
+  enabled: boolean;
+};
+
+export type WebhookDelivery = {
+  id: string;
+  endpointId: string;
+  event: WebhookEvent;
+  attempts: number;
+  statusCode: number | null;
+  ok: boolean;
+  error: string | null;
+  createdAtMs: number;
+  finishedAtMs: number;
+};
+
+export type HookEvent =
+  | "session_started"
+  | "session_finished"
+  | "daemon_started"
+  | "daemon_stopped"
+  | "approval_requested";
+
+export type AutomationHook = {
+  id: string;
+  name: string;
+  command: string;
+  events: HookEvent[];
+  timeoutSecs: number;
+  enabled: boolean;
+};
+
+export type HookRun = {
+  id: string;
+  hookId: string;
+  event: HookEvent;
+  startedAtMs: number;
+  durationMs: number;
+  exitCode: number | null;
+  timedOut: boolean;
+  ok: boolean;
+  stdout: string;
+  stderr: string;
+  error: string | null;
+};
+
+export type CodexSessionInfo = {
+  sessionId: string;
+  projectPath: string;
+  model: string | null;
+  approvalMode: string | null;
+  pid: number | null;
+  startedAtMs: number;
+  status: string;
+  exitCode: number | null;
+  worktree: SessionWorktree | null;
+  summary: SessionSummary | null;
+};
+
+export type SessionCommandRun = {
+  command: string;
+  exitCode: number | null;
+};
+
+export type SessionTokenUsage = {
+  inputTokens: number;
+  cachedInputTokens: number;
+  outputTokens: number;
+};
+
+export type SessionSummary = {
+  filesTouched: string[];
+  commandsRun: SessionCommandRun[];
+  tokenUsage: SessionTokenUsage | null;
+  durationMs: number;
+  status: string;
+  exitCode: number | null;
+  lastMessage: string | null;
+  error: string | null;
+};
+
+export type SessionWorktree = {
+  sessionId: string;
+  sourcePath: string;
+  worktreePath: string;
+  branch: string;
+  baseCommit: string;
+  createdAtMs: number;
+};
+
+export type SessionWorktreeMergeResult = {
+  merged: boolean;
+  commit: string | null;
+};
+
+export type CiState = "success" | "failure" | "pending" | "none";
+
+export type CiCheckRun = {
+  name: string;
+  status: string;
+  conclusion: string | null;
+  url: string | null;
+};
+
+export type ProjectCiStatus = {
+  projectId: string;
+  repo: string;
+  branch: string;
+  commit: string | null;
+  state: CiState;
+  checks: CiCheckRun[];
+  fetchedAtMs: number;
+};
+
+export type SessionPullRequest = {
+  url: string;
+  branch: string;
+  base: string;
+  commit: string;
+};
+
+export type ProcessStats = {
+  pid: number;
+  cpuPercent: number;
+  rssBytes: number;
+  descendantCount: number;
+  sampledAtMs: number;
+};
+
+export type CodexSessionDetail = {
+  session: CodexSessionInfo;
+  resourceStats: ProcessStats[];
+};
+
+export type SessionTemplate = {
+  id: string;
+  name: string;
+  prompt: string;
+  model: string | null;
+  approvalMode: string | null;
+  workingDir: string | null;
+  env: Record<string, string>;
+  createdAtMs: number;
+  updatedAtMs: number;
+};
+
+export type GuestToken = {
+  id: string;
+  label: string | null;
+  scopes: string[];
+  createdAtMs: number;
+  expiresAtMs: number;
+};
+
+export type GuestTokenRecord = GuestToken & {
+  tokenHash: string;
+};
+
+export type IssuedGuestToken = {
+  guest: GuestToken;
+  token: string;
+};
+
+export type StartupPhaseKind = "setup" | "background" | "lazy";
+
+export type StartupPhase = {
+  name: string;
+  kind: StartupPhaseKind;
+  startedAfterMs: number;
+  durationMs: number;
+};
+
+export type CodexHomeSource = "override" | "env" | "default";
+
+export type CodexHomeInfo = {
+  path: string | null;
+  source: CodexHomeSource;
+  exists: boolean;
+};
+
+export type CodexHomeSnapshotInfo = {
+  id: string;
+  label: string | null;
+  createdAtMs: number;
+  codexHome: string;
+  files: string[];
+  secretsIncluded: boolean;
+  sizeBytes: number;
+};
+
+export type CodexHomeRestoreReport = {
+  codexHome: string;
+  restored: string[];
+  removed: string[];
+  previous: CodexHomeSnapshotInfo;
+};
+
+export type CodexAuthMethod = "chatgpt" | "api_key" | "none";
+
+export type CodexAuthSource = "auth_file" | "cli";
+
+export type CodexAuthStatus = {
+  loggedIn: boolean;
+  method: CodexAuthMethod;
+  source: CodexAuthSource;
+  codexHome: string | null;
+  email: string | null;
+  planType: string | null;
+  expiresAtMs: number | null;
+  lastRefreshMs: number | null;
+  refreshable: boolean;
+  expiringSoon: boolean;
+  expired: boolean;
+  message: string;
+};
+
+export type CodexLoginFlowState =
+  | "starting"
+  | "awaiting_user"
+  | "succeeded"
+  | "failed"
+  | "canceled";
+
+export type CodexLoginFlow = {
+  id: string;
+  state: CodexLoginFlowState;
+  verificationUrl: string | null;
+  userCode: string | null;
+  startedAtMs: number;
+  finishedAtMs: number | null;
+  message: string | null;
+  auth: CodexAuthStatus | null;
+};
+
+export type ConfigChangeSource =
+  | "settings"
+  | "editor"
+  | "snapshot_restore"
+  | "revert";
+
+export type ConfigChange = {
+  id: string;
+  timestampMs: number;
+  source: ConfigChangeSource;
+  path: string;
+  changedKeys: string[];
+  diff: string;
+  before: string | null;
+  after: string | null;
+  reverts: string | null;
+};
+
+export type WindowState = {
+  label: string;
+  x: number;
+  y: number;
+  width: number;
+  height: number;
+  maximized: boolean;
+  monitor: string | null;
+  savedAtMs: number;
+};
+
+export type GlobalShortcutAction =
+  | "toggle_window"
+  | "toggle_daemon"
+  | "start_session";
+
+export type RegisteredShortcut = {
+  action: GlobalShortcutAction;
+  shortcut: string;
+  registered: boolean;
+  error: string | null;
+  shadows: string[];
+};
+
+export type ActivityTrigger = "approval_requested" | "session_failed";
+
+export type ActivityAlert = {
+  trigger: ActivityTrigger;
+  workspaceId: string | null;
+  threadId: string | null;
+  sessionId: string | null;
+  detail: string | null;
+  raisedAtMs: number;
+};
+
+export type StartupReport = {
+  readyAfterMs: number | null;
+  phases: StartupPhase[];
+};
+
+export type CommandPermission = "read" | "write" | "owner" | "local";
+
+export type CommandParam = {
+  name: string;
+  rustType: string;
+  required: boolean;
+};
+
+export type CommandCapability = {
+  name: string;
+  module: string;
+  params: CommandParam[];
+  permission: CommandPermission;
+  remoteMethod: string | null;
+};
+
+export type ClientApprovalStatus = "pending" | "approved" | "denied";
+
+export type ClientApproval = {
+  clientId: string;
+  clientName: string | null;
+  status: ClientApprovalStatus;
+  firstSeenAtMs: number;
+  lastSeenAtMs: number;
+  decidedAtMs: number | null;
+};
+
+export type RemoteActionConfirmationStatus =
+  | "pending"
+  | "approved"
+  | "rejected"
+  | "expired";
+
+export type RemoteActionConfirmation = {
+  id: string;
+  method: string;
+  summary: string;
+  clientId: string | null;
+  clientName: string | null;
+  status: RemoteActionConfirmationStatus;
+  requestedAtMs: number;
+  expiresAtMs: number;
+  decidedAtMs: number | null;
+};
+
+export type SessionTemplateInput = {
+  id: string | null;
+  name: string;
+  prompt: string;
+  model: string | null;
+  approvalMode: string | null;
+  workingDir: string | null;
+  env: Record<string, string>;
+};
+
+export type Tag = {
+  id: string;
+  name: string;
+  color: string | null;
+  createdAtMs: number;
+};
+
+export type TagInput = {
+  id: string | null;
+  name: string;
+  color: string | null;
+};
+
+export type TagTarget = "session" | "project";
+
+export type TagAssignments = {
+  sessions: Record<string, string[]>;
+  projects: Record<string, string[]>;
+};
+
+export type TagStore = {
+  tags: Tag[];
+  assignments: TagAssignments;
+};
+
+export type ScheduleRun = {
+  atMs: number;
+  status: string;
+  message: string | null;
+  sessionId: string | null;
+};
+
+export type Schedule = {
+  id: string;
+  name: string;
+  cron: string;
+  templateId: string;
+  projectPath: string | null;
+  enabled: boolean;
+  nextRunAtMs: number | null;
+  runs: ScheduleRun[];
+  createdAtMs: number;
+  updatedAtMs: number;
+};
+
+export type ScheduleInput = {
+  id: string | null;
+  name: string;
+  cron: string;
+  templateId: string;
+  projectPath: string | null;
+  enabled: boolean;
+};
+
+export type BranchInfo = {
+  name: string;
+  last_commit: number;
+};
+
+export type WorkspaceEntry = {
+  id: string;
+  name: string;
+  path: string;
+  kind: WorkspaceKind;
+  parentId: string | null;
+  worktree: WorktreeInfo | null;
+  settings: WorkspaceSettings;
+};
+
+export type WorkspaceInfo = {
+  id: string;
+  name: string;
+  path: string;
+  connected: boolean;
+  kind: WorkspaceKind;
+  parentId: string | null;
+  worktree: WorktreeInfo | null;
+  settings: WorkspaceSettings;
+};
+
+export type WorkspaceKind = "main" | "worktree";
+
+export type WorktreeInfo = {
+  branch: string;
+};
+
+export type WorkspaceGroup = {
+  id: string;
+  name: string;
+  sortOrder: number | null;
+  copiesFolder: string | null;
+};
+
+export type WorkspaceSettings = {
+  sidebarCollapsed: boolean;
+  sortOrder: number | null;
+  groupId: string | null;
+  cloneSourceWorkspaceId: string | null;
+  gitRoot: string | null;
+  launchScript: string | null;
+  launchScripts: LaunchScriptEntry[] | null;
+  worktreeSetupScript: string | null;
+  worktreesFolder: string | null;
+  ciFailureNotifications: boolean;
+};
+
+export type LaunchScriptEntry = {
+  id: string;
+  script: string;
+  icon: string;
+  label: string | null;
+};
+
+export type WorktreeSetupStatus = {
+  shouldRun: boolean;
+  script: string | null;
+};
+
+export type OpenAppTarget = {
+  id: string;
+  label: string;
+  kind: string;
+  appName: string | null;
+  command: string | null;
+  args: string[];
+};
+
+export type EditorKind = "vscode" | "jetbrains" | "other";
+
+export type EditorLauncher = {
+  id: string;
+  label: string;
+  command: string;
+  kind: EditorKind;
+};
+
+export type EditorInfo = {
+  id: string;
+  label: string;
+  command: string;
+  kind: EditorKind;
+  available: boolean;
+};
+
+export type PluginScope = "read" | "write" | "owner";
+
+export type PluginMethod = {
+  name: string;
+  scope: PluginScope;
+  description: string;
+};
+
+export type PluginManifest = {
+  name: string;
+  command: string;
+  args: string[];
+  methods: PluginMethod[];
+  timeoutSecs: number;
+};
+
+export type PluginInfo = {
+  id: string;
+  name: string;
+  command: string;
+  methods: PluginMethod[];
+  enabled: boolean;
+  error: string | null;
+};
+
+export type RemoteBackendTarget = {
+  id: string;
+  name: string;
+  provider: RemoteBackendProvider;
+  host: string;
+  token: string | null;
+  identity: string | null;
+  lastConnectedAtMs: number | null;
+};
+
+export type ProcessEnvConfig = {
+  vars: Record<string, string>;
+  pathPrepend: string[];
+  httpProxy: string | null;
+  httpsProxy: string | null;
+  noProxy: string | null;
+  keychainVars: Record<string, string>;
+};
+
+export type ProxySettings = {
+  url: string | null;
+  bypass: string[];
+};
+
+export type RpcTimeoutClass = "fast" | "normal" | "long";
+
+export type RpcTimeouts = {
+  fastMs: number;
+  normalMs: number;
+  longMs: number;
+  fastMethods: string[];
+  longMethods: string[];
+};
+
+export type RpcTimeoutSettings = {
+  connectMs: number | null;
+  fastMs: number | null;
+  normalMs: number | null;
+  longMs: number | null;
+};
+
+export type ProxyTestResult = {
+  target: string;
+  proxied: boolean;
+  latencyMs: number;
+};
+
+export type QuietHoursWindow = {
+  days: number[];
+  start: string;
+  end: string;
+};
+
+export type DoNotDisturbSettings = {
+  enabled: boolean;
+  windows: QuietHoursWindow[];
+  suppressWhileScreenSharing: boolean;
+  allowUrgent: boolean;
+};
+
+export type RaiseWindowMode = "off" | "window" | "compact";
+
+export type FocusFollowsActivitySettings = {
+  approvalRequested: RaiseWindowMode;
+  sessionFailed: RaiseWindowMode;
+};
+
+export type RedactionRule = {
+  name: string;
+  pattern: string;
+  replacement: string | null;
+};
+
+export type DaemonAuthProvider = "token" | "tailnet_identity";
+
+export type DaemonAuthMode = "any" | "all";
+
+export type DaemonAuthSettings = {
+  providers: DaemonAuthProvider[];
+  mode: DaemonAuthMode;
+  approvedTailnetPeers: string[];
+  requireRequestSigning: boolean;
+};
+
+export type RedactionSettings = {
+  enabled: boolean;
+  builtInDetectors: boolean;
+  rules: RedactionRule[];
+};
+
+export type RedactionHit = {
+  rule: string;
+  count: number;
+};
+
+export type RedactionTestResult = {
+  redacted: string;
+  hits: RedactionHit[];
+};
+
+export type SettingsSyncConfig = {
+  enabled: boolean;
+  sourceBackendId: string | null;
+};
+
+export type SettingsSyncReport = {
+  source: string;
+  settingsApplied: boolean;
+  templatesUpdated: number;
+  projectsAdded: string[];
+  syncedAtMs: number;
+};
+
+export type AppSettings = {
+  codexBin: string | null;
+  codexArgs: string | null;
+  codexEnv: ProcessEnvConfig;
+  backendMode: BackendMode;
+  remoteBackendProvider: RemoteBackendProvider;
+  remoteBackendHost: string;
+  remoteBackendToken: string | null;
+  remoteBackendIdentity: string | null;
+  remoteBackends: RemoteBackendTarget[];
+  activeRemoteBackendId: string | null;
+  proxy: ProxySettings;
+  rpcTimeouts: RpcTimeoutSettings;
+  remoteSlowCallMs: number;
+  keepDaemonRunningAfterAppClose: boolean;
+  daemonEnv: ProcessEnvConfig;
+  daemonHttpBridgeEnabled: boolean;
+  daemonHttpBridgePort: number;
+  requireClientApproval: boolean;
+  confirmRemoteActions: boolean;
+  remoteActionConfirmTimeoutSecs: number;
+  mockBackends: boolean;
+  daemonAuth: DaemonAuthSettings;
+  tailscaleStatusTtlSecs: number;
+  dataDir: string | null;
+  daemonDataDir: string | null;
+  codexHomeOverride: string | null;
+  lowDiskSpaceThresholdMb: number;
+  eventCoalescingMs: Record<string, number>;
+  settingsSync: SettingsSyncConfig;
+  settingsUpdatedAtMs: number;
+  webhooks: WebhookEndpoint[];
+  notificationIntegrations: NotificationIntegration[];
+  hooks: AutomationHook[];
+  doNotDisturb: DoNotDisturbSettings;
+  focusFollowsActivity: FocusFollowsActivitySettings;
+  weeklyActivityReport: boolean;
+  redaction: RedactionSettings;
+  remoteCommandAllowlist: string[];
+  remoteCommandDenylist: string[];
+  remoteCommandTimeoutSecs: number;
+  sessionRetentionMaxAgeDays: number | null;
+  sessionRetentionMaxSizeMb: number | null;
+  maxConcurrentSessions: number | null;
+  defaultAccessMode: string;
+  reviewDeliveryMode: string;
+  composerModelShortcut: string | null;
+  composerAccessShortcut: string | null;
+  composerReasoningShortcut: string | null;
+  interruptShortcut: string | null;
+  composerCollaborationShortcut: string | null;
+  newAgentShortcut: string | null;
+  newWorktreeAgentShortcut: string | null;
+  newCloneAgentShortcut: string | null;
+  archiveThreadShortcut: string | null;
+  toggleProjectsSidebarShortcut: string | null;
+  toggleGitSidebarShortcut: string | null;
+  toggleDebugPanelShortcut: string | null;
+  toggleTerminalShortcut: string | null;
+  cycleAgentNextShortcut: string | null;
+  cycleAgentPrevShortcut: string | null;
+  cycleWorkspaceNextShortcut: string | null;
+  cycleWorkspacePrevShortcut: string | null;
+  globalToggleWindowShortcut: string | null;
+  globalToggleDaemonShortcut: string | null;
+  globalStartSessionShortcut: string | null;
+  globalSessionTemplateId: string | null;
+  lastComposerModelId: string | null;
+  lastComposerReasoningEffort: string | null;
+  uiScale: number;
+  theme: string;
+  usageShowRemaining: boolean;
+  showMessageFilePath: boolean;
+  chatHistoryScrollbackItems: number | null;
+  threadTitleAutogenerationEnabled: boolean;
+  automaticAppUpdateChecksEnabled: boolean;
+  uiFontFamily: string;
+  codeFontFamily: string;
+  codeFontSize: number;
+  notificationSoundsEnabled: boolean;
+  splitChatDiffView: boolean;
+  preloadGitDiffs: boolean;
+  gitDiffIgnoreWhitespaceChanges: boolean;
+  commitMessagePrompt: string;
+  commitMessageModelId: string | null;
+  systemNotificationsEnabled: boolean;
+  subagentSystemNotificationsEnabled: boolean;
+  collaborationModesEnabled: boolean;
+  steerEnabled: boolean;
+  followUpMessageBehavior: string;
+  composerFollowUpHintEnabled: boolean;
+  pauseQueuedMessagesWhenResponseRequired: boolean;
+  unifiedExecEnabled: boolean;
+  experimentalAppsEnabled: boolean;
+  personality: string;
+  dictationEnabled: boolean;
+  dictationModelId: string;
+  dictationPreferredLanguage: string | null;
+  dictationHoldKey: string;
+  composerEditorPreset: string;
+  composerFenceExpandOnSpace: boolean;
+  composerFenceExpandOnEnter: boolean;
+  composerFenceLanguageTags: boolean;
+  composerFenceWrapSelection: boolean;
+  composerFenceAutoWrapPasteMultiline: boolean;
+  composerFenceAutoWrapPasteCodeLike: boolean;
+  composerListContinuation: boolean;
+  composerCodeBlockCopyUseModifier: boolean;
+  workspaceGroups: WorkspaceGroup[];
+  globalWorktreesFolder: string | null;
+  openAppTargets: OpenAppTarget[];
+  selectedOpenAppId: string;
+  editorLaunchers: EditorLauncher[];
+  preferredEditor: string | null;
+  enabledPlugins: string[];
+};
+
+export type BackendMode = "local" | "remote";
+
+export type RemoteBackendProvider = "tcp";
+
+export type FileScope = "workspace" | "global";
+
+export type FileKind = "agents" | "config";
+
+export type TrayRecentThreadEntry = {
+  workspaceId: string;
+  workspaceLabel: string;
+  threadId: string;
+  threadLabel: string;
+  updatedAt: number;
+};
+
+export type TraySessionUsage = {
+  sessionLabel: string;
+  weeklyLabel: string | null;
+};
+
+export type MenuAcceleratorUpdate = {
+  id: string;
+  accelerator: string | null;
+};
+
+export type SetAgentsCoreInput = {
+  multiAgentEnabled: boolean;
+  maxThreads: number;
+  maxDepth: number;
+};
+
+export type CreateAgentInput = {
+  name: string;
+  description: string | null;
+  developerInstructions: string | null;
+  template: string | null;
+  model: string | null;
+  reasoningEffort: string | null;
+};
+
+export type UpdateAgentInput = {
+  originalName: string;
+  name: string;
+  description: string | null;
+  developerInstructions: string | null;
+  renameManagedFile: boolean | null;
+};
+
+export type DeleteAgentInput = {
+  name: string;
+  deleteManagedFile: boolean | null;
+};
+
+/** Arguments for each registered command, keyed by command name. */
+export type CommandArgs = {
+  get_app_settings: Record<string, never>;
+  update_app_settings: {
+    settings: AppSettings;
+  };
+  test_redaction_rules: {
+    sample: string;
+    settings?: RedactionSettings | null;
+  };
+  migrate_data_dir: {
+    newPath: string;
+  };
+  create_backup: {
+    path: string;
+    passphrase?: string | null;
+  };
+  restore_backup: {
+    path: string;
+    passphrase?: string | null;
+  };
+  sync_settings_now: Record<string, never>;
+  get_codex_config_path: Record<string, never>;
+  get_codex_home: Record<string, never>;
+  set_codex_home_override: {
+    path?: string | null;
+  };
+  snapshot_codex_home: {
+    label?: string | null;
+    excludeSecrets?: boolean | null;
+  };
+  list_codex_home_snapshots: Record<string, never>;
+  restore_codex_home_snapshot: {
+    id: string;
+  };
+  list_config_changes: {
+    page?: Page | null;
+  };
+  revert_config_change: {
+    id: string;
+  };
+  test_proxy: {
+    proxy: ProxySettings;
+    target?: string | null;
+  };
+  file_read: {
+    scope: FileScope;
+    kind: FileKind;
+    workspaceId?: string | null;
+  };
+  file_write: {
+    scope: FileScope;
+    kind: FileKind;
+    workspaceId?: string | null;
+    content: string;
+  };
+  read_image_as_data_url: {
+    path: string;
+  };
+  write_text_file: {
+    path: string;
+    content: string;
+  };
+  remote_list_dir: {
+    workspaceId: string;
+    relPath?: string | null;
+  };
+  remote_read_file: {
+    workspaceId: string;
+    relPath: string;
+    offset?: number | null;
+    length?: number | null;
+  };
+  get_config_model: {
+    workspaceId: string;
+  };
+  menu_set_accelerators: {
+    updates: MenuAcceleratorUpdate[];
+  };
+  set_tray_recent_threads: {
+    entries: TrayRecentThreadEntry[];
+  };
+  set_tray_session_usage: {
+    usage?: TraySessionUsage | null;
+  };
+  get_tray_summary: Record<string, never>;
+  get_window_state: {
+    label?: string | null;
+  };
+  reset_window_state: {
+    label?: string | null;
+  };
+  list_registered_shortcuts: Record<string, never>;
+  get_activity_alert: Record<string, never>;
+  dismiss_activity_alert: {
+    open?: boolean | null;
+  };
+  take_deep_link_project: Record<string, never>;
+  codex_doctor: {
+    codexBin?: string | null;
+    codexArgs?: string | null;
+  };
+  codex_auth_status: Record<string, never>;
+  codex_login_start: Record<string, never>;
+  codex_login_flow_status: Record<string, never>;
+  codex_login_flow_cancel: Record<string, never>;
+  codex_update: {
+    codexBin?: string | null;
+    codexArgs?: string | null;
+  };
+  list_workspaces: Record<string, never>;
+  is_workspace_path_dir: {
+    path: string;
+  };
+  add_workspace: {
+    path: string;
+  };
+  add_workspace_from_git_url: {
+    url: string;
+    destinationPath: string;
+    targetFolderName?: string | null;
+  };
+  add_clone: {
+    sourceWorkspaceId: string;
+    copyName: string;
+    copiesFolder: string;
+  };
+  add_worktree: {
+    parentId: string;
+    branch: string;
+    name?: string | null;
+    copyAgentsMd?: boolean | null;
+  };
+  worktree_setup_status: {
+    workspaceId: string;
+  };
+  worktree_setup_mark_ran: {
+    workspaceId: string;
+  };
+  remove_workspace: {
+    id: string;
+  };
+  remove_worktree: {
+    id: string;
+  };
+  rename_worktree: {
+    id: string;
+    branch: string;
+  };
+  rename_worktree_upstream: {
+    id: string;
+    oldBranch: string;
+    newBranch: string;
+  };
+  apply_worktree_changes: {
+    workspaceId: string;
+  };
+  update_workspace_settings: {
+    id: string;
+    settings: WorkspaceSettings;
+  };
+  set_workspace_runtime_codex_args: {
+    workspaceId: string;
+    codexArgs?: string | null;
+  };
+  start_thread: {
+    workspaceId: string;
+  };
+  send_user_message: {
+    workspaceId: string;
+    threadId: string;
+    text: string;
+    model?: string | null;
+    effort?: string | null;
+    serviceTier?: string | null;
+    accessMode?: string | null;
+    images?: string[] | null;
+    appMentions?: JsonValue[] | null;
+    collaborationMode?: JsonValue;
+  };
+  turn_steer: {
+    workspaceId: string;
+    threadId: string;
+    turnId: string;
+    text: string;
+    images?: string[] | null;
+    appMentions?: JsonValue[] | null;
+  };
+  turn_interrupt: {
+    workspaceId: string;
+    threadId: string;
+    turnId: string;
+  };
+  start_review: {
+    workspaceId: string;
+    threadId: string;
+    target: JsonValue;
+    delivery?: string | null;
+  };
+  respond_to_server_request: {
+    workspaceId: string;
+    requestId: JsonValue;
+    result: JsonValue;
+  };
+  remember_approval_rule: {
+    workspaceId: string;
+    command: string[];
+  };
+  generate_commit_message: {
+    workspaceId: string;
+    commitMessageModelId?: string | null;
+  };
+  generate_run_metadata: {
+    workspaceId: string;
+    prompt: string;
+  };
+  generate_agent_description: {
+    workspaceId: string;
+    description: string;
+  };
+  resume_thread: {
+    workspaceId: string;
+    threadId: string;
+  };
+  read_thread: {
+    workspaceId: string;
+    threadId: string;
+  };
+  thread_live_subscribe: {
+    workspaceId: string;
+    threadId: string;
+  };
+  thread_live_unsubscribe: {
+    workspaceId: string;
+    threadId: string;
+  };
+  fork_thread: {
+    workspaceId: string;
+    threadId: string;
+  };
+  list_threads: {
+    workspaceId: string;
+    cursor?: string | null;
+    limit?: number | null;
+    sortKey?: string | null;
+  };
+  list_mcp_server_status: {
+    workspaceId: string;
+    cursor?: string | null;
+    limit?: number | null;
+  };
+  archive_thread: {
+    workspaceId: string;
+    threadId: string;
+  };
+  compact_thread: {
+    workspaceId: string;
+    threadId: string;
+  };
+  set_thread_name: {
+    workspaceId: string;
+    threadId: string;
+    name: string;
+  };
+  collaboration_mode_list: {
+    workspaceId: string;
+  };
+  connect_workspace: {
+    id: string;
+  };
+  get_git_status: {
+    workspaceId: string;
+  };
+  init_git_repo: {
+    workspaceId: string;
+    branch: string;
+    force?: boolean | null;
+  };
+  create_github_repo: {
+    workspaceId: string;
+    repo: string;
+    visibility: string;
+    branch?: string | null;
+  };
+  list_git_roots: {
+    workspaceId: string;
+    depth?: number | null;
+  };
+  get_git_diffs: {
+    workspaceId: string;
+  };
+  get_git_log: {
+    workspaceId: string;
+    limit?: number | null;
+  };
+  get_git_commit_diff: {
+    workspaceId: string;
+    sha: string;
+  };
+  get_git_remote: {
+    workspaceId: string;
+  };
+  stage_git_file: {
+    workspaceId: string;
+    path: string;
+  };
+  stage_git_all: {
+    workspaceId: string;
+  };
+  unstage_git_file: {
+    workspaceId: string;
+    path: string;
+  };
+  revert_git_file: {
+    workspaceId: string;
+    path: string;
+  };
+  revert_git_all: {
+    workspaceId: string;
+  };
+  commit_git: {
+    workspaceId: string;
+    message: string;
+  };
+  push_git: {
+    workspaceId: string;
+  };
+  pull_git: {
+    workspaceId: string;
+  };
+  fetch_git: {
+    workspaceId: string;
+  };
+  sync_git: {
+    workspaceId: string;
+  };
+  get_github_issues: {
+    workspaceId: string;
+  };
+  get_github_pull_requests: {
+    workspaceId: string;
+  };
+  get_github_pull_request_diff: {
+    workspaceId: string;
+    prNumber: number;
+  };
+  get_github_pull_request_comments: {
+    workspaceId: string;
+    prNumber: number;
+  };
+  checkout_github_pull_request: {
+    workspaceId: string;
+    prNumber: number;
+  };
+  list_workspace_files: {
+    workspaceId: string;
+  };
+  read_workspace_file: {
+    workspaceId: string;
+    path: string;
+  };
+  open_workspace_in: {
+    path: string;
+    app?: string | null;
+    args: string[];
+    command?: string | null;
+    line?: number | null;
+    column?: number | null;
+  };
+  get_open_app_icon: {
+    appName: string;
+  };
+  list_git_branches: {
+    workspaceId: string;
+  };
+  checkout_git_branch: {
+    workspaceId: string;
+    name: string;
+  };
+  create_git_branch: {
+    workspaceId: string;
+    name: string;
+  };
+  model_list: {
+    workspaceId: string;
+  };
+  experimental_feature_list: {
+    workspaceId: string;
+    cursor?: string | null;
+    limit?: number | null;
+  };
+  set_codex_feature_flag: {
+    featureKey: string;
+    enabled: boolean;
+  };
+  get_agents_settings: Record<string, never>;
+  set_agents_core_settings: {
+    input: SetAgentsCoreInput;
+  };
+  create_agent: {
+    input: CreateAgentInput;
+  };
+  update_agent: {
+    input: UpdateAgentInput;
+  };
+  delete_agent: {
+    input: DeleteAgentInput;
+  };
+  read_agent_config_toml: {
+    agentName: string;
+  };
+  write_agent_config_toml: {
+    agentName: string;
+    content: string;
+  };
+  account_rate_limits: {
+    workspaceId: string;
+  };
+  account_read: {
+    workspaceId: string;
+  };
+  codex_login: {
+    workspaceId: string;
+  };
+  codex_login_cancel: {
+    workspaceId: string;
+  };
+  skills_list: {
+    workspaceId: string;
+  };
+  apps_list: {
+    workspaceId: string;
+    cursor?: string | null;
+    limit?: number | null;
+    threadId?: string | null;
+  };
+  prompts_list: {
+    workspaceId: string;
+  };
+  prompts_create: {
+    workspaceId: string;
+    scope: string;
+    name: string;
+    description?: string | null;
+    argumentHint?: string | null;
+    content: string;
+  };
+  prompts_update: {
+    workspaceId: string;
+    path: string;
+    name: string;
+    description?: string | null;
+    argumentHint?: string | null;
+    content: string;
+  };
+  prompts_delete: {
+    workspaceId: string;
+    path: string;
+  };
+  prompts_move: {
+    workspaceId: string;
+    path: string;
+    scope: string;
+  };
+  prompts_workspace_dir: {
+    workspaceId: string;
+  };
+  prompts_global_dir: {
+    workspaceId: string;
+  };
+  terminal_open: {
+    workspaceId: string;
+    terminalId: string;
+    cols: number;
+    rows: number;
+  };
+  terminal_write: {
+    workspaceId: string;
+    terminalId: string;
+    data: string;
+  };
+  terminal_resize: {
+    workspaceId: string;
+    terminalId: string;
+    cols: number;
+    rows: number;
+  };
+  terminal_close: {
+    workspaceId: string;
+    terminalId: string;
+  };
+  dictation_model_status: {
+    modelId?: string | null;
+  };
+  dictation_download_model: {
+    modelId?: string | null;
+  };
+  dictation_cancel_download: {
+    modelId?: string | null;
+  };
+  dictation_remove_model: {
+    modelId?: string | null;
+  };
+  dictation_start: {
+    preferredLanguage?: string | null;
+  };
+  dictation_request_permission: Record<string, never>;
+  dictation_stop: Record<string, never>;
+  dictation_cancel: Record<string, never>;
+  local_usage_snapshot: {
+    days?: number | null;
+    workspacePath?: string | null;
+  };
+  generate_activity_report: {
+    range: ActivityReportRange;
+    format: ActivityReportFormat;
+    save?: boolean | null;
+  };
+  get_host_capabilities: Record<string, never>;
+  run_remote_command: {
+    workspaceId: string;
+    command: string;
+    cwd?: string | null;
+    runId?: string | null;
+  };
+  list_remote_command_audit: {
+    page?: Page | null;
+  };
+  search_sessions: {
+    query: string;
+    filters?: SessionSearchFilters | null;
+  };
+  archive_sessions: {
+    before?: number | null;
+    dryRun?: boolean | null;
+  };
+  list_archives: {
+    page?: Page | null;
+  };
+  restore_archive: {
+    name: string;
+  };
+  start_codex_session: {
+    projectPath?: string | null;
+    prompt?: string | null;
+    model?: string | null;
+    approvalMode?: string | null;
+    templateId?: string | null;
+    isolateWorktree?: boolean | null;
+  };
+  merge_session_worktree: {
+    sessionId: string;
+  };
+  discard_session_worktree: {
+    sessionId: string;
+  };
+  get_project_ci_status: {
+    projectId: string;
+  };
+  create_pr_from_session: {
+    sessionId: string;
+    title: string;
+    body?: string | null;
+    base?: string | null;
+  };
+  list_editors: Record<string, never>;
+  open_in_editor: {
+    path: string;
+    line?: number | null;
+  };
+  open_diff_in_editor: {
+    sessionId: string;
+    file: string;
+  };
+  list_plugins: Record<string, never>;
+  enable_plugin: {
+    id: string;
+    enabled: boolean;
+  };
+  send_session_input: {
+    sessionId: string;
+    text: string;
+  };
+  list_codex_sessions: {
+    tags?: string[] | null;
+    page?: Page | null;
+  };
+  get_codex_session: {
+    sessionId: string;
+  };
+  export_session: {
+    sessionId: string;
+    format: SessionExportFormat;
+    includeToolOutput?: boolean | null;
+  };
+  get_process_stats: {
+    pid?: number | null;
+  };
+  cancel_queued_session: {
+    sessionId: string;
+  };
+  interrupt_session: {
+    sessionId: string;
+  };
+  terminate_session: {
+    sessionId: string;
+    pid?: number | null;
+    force?: boolean | null;
+  };
+  create_guest_token: {
+    scopes: string[];
+    ttlSeconds: number;
+    label?: string | null;
+  };
+  list_guest_tokens: Record<string, never>;
+  revoke_guest_token: {
+    id: string;
+  };
+  list_client_approvals: Record<string, never>;
+  decide_client_approval: {
+    clientId: string;
+    approved: boolean;
+  };
+  list_remote_action_confirmations: Record<string, never>;
+  decide_remote_action_confirmation: {
+    id: string;
+    approved: boolean;
+  };
+  list_session_templates: Record<string, never>;
+  save_session_template: {
+    template: SessionTemplateInput;
+  };
+  delete_session_template: {
+    id: string;
+  };
+  list_tags: Record<string, never>;
+  save_tag: {
+    tag: TagInput;
+  };
+  delete_tag: {
+    id: string;
+  };
+  set_tags: {
+    target: TagTarget;
+    id: string;
+    tagIds: string[];
+  };
+  get_tag_assignments: Record<string, never>;
+  list_schedules: Record<string, never>;
+  upsert_schedule: {
+    schedule: ScheduleInput;
+  };
+  run_schedule_now: {
+    id: string;
+  };
+  is_macos_debug_build: Record<string, never>;
+  app_build_type: Record<string, never>;
+  send_notification_fallback: {
+    title: string;
+    body: string;
+  };
+  should_show_notification: {
+    urgent?: boolean | null;
+  };
+  notifications_snooze: {
+    minutes: number;
+  };
+  tailscale_status: {
+    forceRefresh?: boolean | null;
+  };
+  tailscale_daemon_command_preview: Record<string, never>;
+  tailscale_daemon_identity: Record<string, never>;
+  tailscale_daemon_start: {
+    takeover?: boolean | null;
+  };
+  tailscale_daemon_stop: Record<string, never>;
+  tailscale_daemon_restart: Record<string, never>;
+  tailscale_daemon_apply_update: Record<string, never>;
+  tailscale_daemon_status: {
+    verbose?: boolean | null;
+  };
+  adopt_external_daemon: Record<string, never>;
+  tailscale_daemon_preflight: Record<string, never>;
+  tailscale_daemon_register_firewall: {
+    verifySignature?: boolean | null;
+  };
+  install_daemon_service: Record<string, never>;
+  uninstall_daemon_service: Record<string, never>;
+  probe_remote_quality: {
+    pings?: number | null;
+  };
+  check_tailnet_reachability: {
+    peer?: string | null;
+  };
+  tailscale_ping_peer: {
+    target: string;
+  };
+  tailscale_ping_history: {
+    target?: string | null;
+  };
+  set_mock_backend_script: {
+    script: MockBackendScript;
+  };
+  remote_backend_status: Record<string, never>;
+  get_remote_backend_metrics: Record<string, never>;
+  get_monitor_overview: Record<string, never>;
+  get_storage_health: Record<string, never>;
+  clean_storage: {
+    targets: StorageTarget[];
+  };
+  list_webhook_deliveries: {
+    endpointId?: string | null;
+    page?: Page | null;
+  };
+  send_test_message: {
+    integration: string;
+  };
+  list_hook_runs: {
+    hookId?: string | null;
+    page?: Page | null;
+  };
+  list_capabilities: Record<string, never>;
+  get_platform_capabilities: Record<string, never>;
+  get_startup_report: Record<string, never>;
+  is_mobile_runtime: Record<string, never>;
+};
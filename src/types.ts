@@ -1,3 +1,249 @@
+import type {
+  ActivityAlert,
+  ActivityProject,
+  ActivityReport,
+  ActivityReportFormat,
+  ActivityReportRange,
+  AutomationHook,
+  BackendMode,
+  BackupReport,
+  BackupRestoreReport,
+  CiCheckRun,
+  CiState,
+  ClientApproval,
+  ClientApprovalStatus,
+  CodexAuthMethod,
+  CodexAuthSource,
+  CodexAuthStatus,
+  CodexHomeInfo,
+  CodexHomeRestoreReport,
+  CodexHomeSnapshotInfo,
+  CodexHomeSource,
+  CodexLoginFlow,
+  CodexLoginFlowState,
+  CommandCapability,
+  CommandParam,
+  CommandPermission,
+  ConfigChange,
+  ConfigChangeSource,
+  DaemonAuthMode,
+  DaemonAuthProvider,
+  DaemonFirewallTrust,
+  DaemonPreflightCheck,
+  DaemonPreflightCheckStatus,
+  DaemonPreflightReport,
+  DaemonServiceManager,
+  DaemonStop,
+  DaemonStopReason,
+  EditorInfo,
+  EditorKind,
+  EditorLauncher,
+  GeneratedActivityReport,
+  GitFileStatus,
+  GitHubIssue,
+  GitHubIssuesResponse,
+  GitHubPullRequestDiff,
+  GitLogEntry,
+  GitLogResponse,
+  HookEvent,
+  HookRun,
+  HostCapabilities,
+  HostRuntime,
+  LocalUsageDay,
+  LocalUsageModel,
+  LocalUsageSnapshot,
+  LocalUsageTotals,
+  MockBackendScript,
+  MockTailscaleState,
+  MockTailscaleStep,
+  NotificationIntegrationKind,
+  Page,
+  PageResult,
+  PlatformCapabilities,
+  PlatformSubsystem,
+  PlatformSubsystemStatus,
+  PluginInfo,
+  PluginMethod,
+  PluginScope,
+  PreviewShell,
+  ProcessLaunch,
+  ProcessOperation,
+  ProcessStats,
+  ProjectCiStatus,
+  ProxyTestResult,
+  RedactionHit,
+  RedactionTestResult,
+  RegisteredShortcut,
+  RemoteActionConfirmation,
+  RemoteActionConfirmationStatus,
+  RemoteBackendProvider,
+  RemoteBackendStatus,
+  RemoteCommandAuditEntry,
+  RemoteCommandResult,
+  RemoteFileChunk,
+  RemoteLinkPath,
+  RemoteQualityReport,
+  RpcTimeoutClass,
+  RpcTimeoutSettings,
+  RpcTimeouts,
+  SessionArchiveInfo,
+  SessionArchiveReport,
+  SessionExport,
+  SessionExportFormat,
+  SessionPullRequest,
+  SessionRestoreReport,
+  SessionWorktree,
+  SessionWorktreeMergeResult,
+  SettingsSyncReport,
+  SnippetSegment,
+  StartupPhase,
+  StartupPhaseKind,
+  StartupReport,
+  StorageCleanReport,
+  StorageHealth,
+  StorageTarget,
+  StorageUsage,
+  Tag,
+  TagAssignments,
+  TagTarget,
+  TailnetReachabilityReport,
+  TailscaleDaemonCommandPreview,
+  TailscalePingResult,
+  TailscalePong,
+  TailscaleStatus,
+  TcpDaemonRestart,
+  TcpDaemonState,
+  TcpDaemonStatus,
+  TcpProbeOutcome,
+  TcpProbeResult,
+  WindowState,
+  WorkspaceKind,
+  WorktreeInfo,
+} from "./types.generated";
+
+export type {
+  ActivityAlert,
+  ActivityProject,
+  ActivityReport,
+  ActivityReportFormat,
+  ActivityReportRange,
+  AutomationHook,
+  BackendMode,
+  BackupReport,
+  BackupRestoreReport,
+  CiCheckRun,
+  CiState,
+  ClientApproval,
+  ClientApprovalStatus,
+  CodexAuthMethod,
+  CodexAuthSource,
+  CodexAuthStatus,
+  CodexHomeInfo,
+  CodexHomeRestoreReport,
+  CodexHomeSnapshotInfo,
+  CodexHomeSource,
+  CodexLoginFlow,
+  CodexLoginFlowState,
+  CommandCapability,
+  CommandParam,
+  CommandPermission,
+  ConfigChange,
+  ConfigChangeSource,
+  DaemonAuthMode,
+  DaemonAuthProvider,
+  DaemonFirewallTrust,
+  DaemonPreflightCheck,
+  DaemonPreflightCheckStatus,
+  DaemonPreflightReport,
+  DaemonServiceManager,
+  DaemonStop,
+  DaemonStopReason,
+  EditorInfo,
+  EditorKind,
+  EditorLauncher,
+  GeneratedActivityReport,
+  GitFileStatus,
+  GitHubIssue,
+  GitHubIssuesResponse,
+  GitHubPullRequestDiff,
+  GitLogEntry,
+  GitLogResponse,
+  HookEvent,
+  HookRun,
+  HostCapabilities,
+  HostRuntime,
+  LocalUsageDay,
+  LocalUsageModel,
+  LocalUsageSnapshot,
+  LocalUsageTotals,
+  MockBackendScript,
+  MockTailscaleState,
+  MockTailscaleStep,
+  NotificationIntegrationKind,
+  Page,
+  PageResult,
+  PlatformCapabilities,
+  PlatformSubsystem,
+  PlatformSubsystemStatus,
+  PluginInfo,
+  PluginMethod,
+  PluginScope,
+  PreviewShell,
+  ProcessLaunch,
+  ProcessOperation,
+  ProcessStats,
+  ProjectCiStatus,
+  ProxyTestResult,
+  RedactionHit,
+  RedactionTestResult,
+  RegisteredShortcut,
+  RemoteActionConfirmation,
+  RemoteActionConfirmationStatus,
+  RemoteBackendProvider,
+  RemoteBackendStatus,
+  RemoteCommandAuditEntry,
+  RemoteCommandResult,
+  RemoteFileChunk,
+  RemoteLinkPath,
+  RemoteQualityReport,
+  RpcTimeoutClass,
+  RpcTimeoutSettings,
+  RpcTimeouts,
+  SessionArchiveInfo,
+  SessionArchiveReport,
+  SessionExport,
+  SessionExportFormat,
+  SessionPullRequest,
+  SessionRestoreReport,
+  SessionWorktree,
+  SessionWorktreeMergeResult,
+  SettingsSyncReport,
+  SnippetSegment,
+  StartupPhase,
+  StartupPhaseKind,
+  StartupReport,
+  StorageCleanReport,
+  StorageHealth,
+  StorageTarget,
+  StorageUsage,
+  Tag,
+  TagAssignments,
+  TagTarget,
+  TailnetReachabilityReport,
+  TailscaleDaemonCommandPreview,
+  TailscalePingResult,
+  TailscalePong,
+  TailscaleStatus,
+  TcpDaemonRestart,
+  TcpDaemonState,
+  TcpDaemonStatus,
+  TcpProbeOutcome,
+  TcpProbeResult,
+  WindowState,
+  WorkspaceKind,
+  WorktreeInfo,
+};
+
 export type WorkspaceSettings = {
   sidebarCollapsed: boolean;
   sortOrder?: number | null;
@@ -8,6 +254,7 @@ export type WorkspaceSettings = {
   launchScripts?: LaunchScriptEntry[] | null;
   worktreeSetupScript?: string | null;
   worktreesFolder?: string | null;
+  ciFailureNotifications?: boolean;
 };
 
 export type LaunchScriptIconId =
@@ -41,12 +288,6 @@ export type WorkspaceGroup = {
   copiesFolder?: string | null;
 };
 
-export type WorkspaceKind = "main" | "worktree";
-
-export type WorktreeInfo = {
-  branch: string;
-};
-
 export type WorkspaceInfo = {
   id: string;
   name: string;
@@ -81,6 +322,19 @@ export type TrayOpenThreadPayload = {
   threadId: string;
 };
 
+/** Where Codex runs: here, or on a remote backend that may be down. */
+export type TrayRunnerState = "local" | "connected" | "disconnected";
+
+export type TraySummary = {
+  daemonState: TcpDaemonState;
+  runnerState: TrayRunnerState;
+  /** `null` while sessions run on a remote backend. */
+  activeSessions: number | null;
+  pendingApprovals: number;
+  lastError: string | null;
+  updatedAtMs: number;
+};
+
 export type Message = {
   id: string;
   role: "user" | "assistant";
@@ -195,14 +449,13 @@ export type PullRequestSelectionRange = {
 
 export type AccessMode = "read-only" | "current" | "full-access";
 export type ServiceTier = "fast" | "flex";
-export type BackendMode = "local" | "remote";
-export type RemoteBackendProvider = "tcp";
 export type RemoteBackendTarget = {
   id: string;
   name: string;
   provider: RemoteBackendProvider;
   host: string;
   token: string | null;
+  identity?: string | null;
   lastConnectedAtMs?: number | null;
 };
 export type ThemePreference = "system" | "light" | "dark" | "dim";
@@ -235,16 +488,103 @@ export type OpenAppTarget = {
   args: string[];
 };
 
+export type ProcessEnvConfig = {
+  vars: Record<string, string>;
+  pathPrepend: string[];
+  httpProxy: string | null;
+  httpsProxy: string | null;
+  noProxy: string | null;
+  /** Variable name to keychain account; resolved when the process starts. */
+  keychainVars: Record<string, string>;
+};
+
+/** Failure kinds a backend command can report; see `CommandError`. */
+export type CommandErrorCode =
+  | "DaemonPortInUse"
+  | "TokenMissing"
+  | "RemoteUnreachable"
+  | "RemoteDisconnected"
+  | "AuthFailed"
+  | "ConnectTimeout"
+  | "TailscaleNotConnected"
+  | "ProxyRequired"
+  | "Timeout"
+  | "WorkspaceNotFound"
+  | "ThreadNotFound"
+  | "OperationInProgress"
+  | "DataDirLocked"
+  | "DataDirNotEmpty"
+  | "Unsupported"
+  | "Unknown";
+
+export type CommandErrorPayload = {
+  code: CommandErrorCode;
+  message: string;
+  details: unknown;
+};
+
+export type ProxySettings = {
+  /** `http://` or `socks5://` URL, optionally with `user:password@`. */
+  url: string | null;
+  /** Exact hosts, `.domain` suffixes, or `*`; loopback and tailnet always bypass. */
+  bypass: string[];
+};
+
+export type SettingsSyncConfig = {
+  enabled: boolean;
+  /** Saved remote backend to pull from; the active one when `null`. */
+  sourceBackendId: string | null;
+};
+
 export type AppSettings = {
   codexBin: string | null;
   codexArgs: string | null;
+  codexEnv: ProcessEnvConfig;
   backendMode: BackendMode;
   remoteBackendProvider: RemoteBackendProvider;
   remoteBackendHost: string;
   remoteBackendToken: string | null;
+  remoteBackendIdentity?: string | null;
   remoteBackends: RemoteBackendTarget[];
   activeRemoteBackendId: string | null;
+  proxy: ProxySettings;
+  /** Per-class remote backend timeouts; unset values use the daemon's. */
+  rpcTimeouts: RpcTimeoutSettings;
+  /** Remote calls slower than this are kept in the slow-call log. */
+  remoteSlowCallMs: number;
   keepDaemonRunningAfterAppClose: boolean;
+  daemonEnv: ProcessEnvConfig;
+  daemonHttpBridgeEnabled: boolean;
+  daemonHttpBridgePort: number;
+  requireClientApproval: boolean;
+  /** Hold destructive requests from remote clients until confirmed here. */
+  confirmRemoteActions: boolean;
+  remoteActionConfirmTimeoutSecs: number;
+  /** Use in-process fakes for Tailscale and the daemon from the next launch. */
+  mockBackends: boolean;
+  daemonAuth: DaemonAuthSettings;
+  tailscaleStatusTtlSecs: number;
+  dataDir: string | null;
+  daemonDataDir: string | null;
+  /** Changed only through `setCodexHomeOverride`, which validates it. */
+  codexHomeOverride: string | null;
+  lowDiskSpaceThresholdMb: number;
+  eventCoalescingMs: Record<string, number>;
+  settingsSync: SettingsSyncConfig;
+  settingsUpdatedAtMs: number;
+  webhooks: WebhookEndpoint[];
+  notificationIntegrations: NotificationIntegration[];
+  hooks: AutomationHook[];
+  doNotDisturb: DoNotDisturbSettings;
+  focusFollowsActivity: FocusFollowsActivitySettings;
+  weeklyActivityReport: boolean;
+  redaction: RedactionSettings;
+  remoteCommandAllowlist: string[];
+  remoteCommandDenylist: string[];
+  remoteCommandTimeoutSecs: number;
+  sessionRetentionMaxAgeDays: number | null;
+  sessionRetentionMaxSizeMb: number | null;
+  maxConcurrentSessions: number | null;
   defaultAccessMode: AccessMode;
   reviewDeliveryMode: "inline" | "detached";
   composerModelShortcut: string | null;
@@ -265,6 +605,12 @@ export type AppSettings = {
   cycleAgentPrevShortcut: string | null;
   cycleWorkspaceNextShortcut: string | null;
   cycleWorkspacePrevShortcut: string | null;
+  /** System-wide hotkeys; see `listRegisteredShortcuts`. */
+  globalToggleWindowShortcut: string | null;
+  globalToggleDaemonShortcut: string | null;
+  globalStartSessionShortcut: string | null;
+  /** The session template `globalStartSessionShortcut` starts. */
+  globalSessionTemplateId: string | null;
   lastComposerModelId: string | null;
   lastComposerReasoningEffort: string | null;
   uiScale: number;
@@ -310,6 +656,9 @@ export type AppSettings = {
   globalWorktreesFolder: string | null;
   openAppTargets: OpenAppTarget[];
   selectedOpenAppId: string;
+  editorLaunchers: EditorLauncher[];
+  preferredEditor: string | null;
+  enabledPlugins: string[];
 };
 
 export type CodexFeatureStage =
@@ -329,34 +678,253 @@ export type CodexFeature = {
   announcement: string | null;
 };
 
-export type TcpDaemonState = "stopped" | "running" | "error";
+export type GuestTokenScope = "read" | "write";
+
+export type GuestToken = {
+  id: string;
+  label: string | null;
+  scopes: GuestTokenScope[];
+  createdAtMs: number;
+  expiresAtMs: number;
+};
+
+export type IssuedGuestToken = {
+  guest: GuestToken;
+  token: string;
+};
+
+export type RemoteDirEntry = {
+  name: string;
+  path: string;
+  kind: "file" | "dir" | "symlink";
+  size: number | null;
+  modifiedMs: number | null;
+};
+
+export type RemoteDirListing = {
+  path: string;
+  entries: RemoteDirEntry[];
+  truncated: boolean;
+};
+
+export type SessionSearchFilters = {
+  workspaceId?: string | null;
+  role?: "user" | "assistant" | null;
+  sinceMs?: number | null;
+  untilMs?: number | null;
+  limit?: number | null;
+  /** Tag ids a session must all carry, itself or through its project. */
+  tags?: string[];
+};
+
+export type SessionSearchHit = {
+  sessionId: string;
+  path: string;
+  cwd: string | null;
+  timestampMs: number | null;
+  role: "user" | "assistant";
+  snippet: SnippetSegment[];
+};
+
+export type WebhookEvent =
+  | "session_completed"
+  | "approval_needed"
+  | "daemon_down"
+  | "ci_failed";
+
+export type WebhookEndpoint = {
+  id: string;
+  name: string;
+  url: string;
+  /** Signs each body with HMAC-SHA256 (`X-CodexMonitor-Signature`) when set. */
+  secret: string | null;
+  events: WebhookEvent[];
+  enabled: boolean;
+};
+
+export type QuietHoursWindow = {
+  /** 0 is Sunday; every day when empty. */
+  days: number[];
+  /** Local `HH:MM`; a window ending before it starts runs past midnight. */
+  start: string;
+  end: string;
+};
+
+export type DoNotDisturbSettings = {
+  enabled: boolean;
+  windows: QuietHoursWindow[];
+  suppressWhileScreenSharing: boolean;
+  allowUrgent: boolean;
+};
+
+/** How the app comes forward for activity while none of its windows has focus. */
+export type RaiseWindowMode = "off" | "window" | "compact";
+
+export type FocusFollowsActivitySettings = {
+  approvalRequested: RaiseWindowMode;
+  /** Sessions that exit with a non-zero code. */
+  sessionFailed: RaiseWindowMode;
+};
+
+export type DaemonAuthSettings = {
+  providers: DaemonAuthProvider[];
+  /** `any`: one enabled provider admits a client; `all`: every one must. */
+  mode: DaemonAuthMode;
+  /** Tailnet IPs or MagicDNS/host names trusted as owner. */
+  approvedTailnetPeers: string[];
+  /** Refuse unsigned mutating requests from remote token clients. */
+  requireRequestSigning: boolean;
+};
+
+export type RedactionRule = {
+  name: string;
+  pattern: string;
+  /** Defaults to `[REDACTED:<name>]`; may use capture groups as `$1`. */
+  replacement?: string | null;
+};
+
+export type RedactionSettings = {
+  enabled: boolean;
+  builtInDetectors: boolean;
+  rules: RedactionRule[];
+};
+
+export type NotificationIntegration = {
+  id: string;
+  kind: NotificationIntegrationKind;
+  name: string;
+  /** Keychain account (service `CodexMonitor`) holding the incoming webhook URL. */
+  keychainAccount: string;
+  events: WebhookEvent[];
+  /**
+   * Placeholders: `{summary}`, `{event}`, `{session}`, `{project}`,
+   * `{duration}`, `{result}`, `{workspace}`, `{outcome}`. The event summary
+   * when `null`.
+   */
+  messageTemplate: string | null;
+  enabled: boolean;
+};
 
-export type TcpDaemonStatus = {
-  state: TcpDaemonState;
+export type WebhookDelivery = {
+  id: string;
+  endpointId: string;
+  event: WebhookEvent;
+  attempts: number;
+  statusCode: number | null;
+  ok: boolean;
+  error: string | null;
+  createdAtMs: number;
+  finishedAtMs: number;
+};
+
+export type CodexApprovalMode =
+  | "untrusted"
+  | "on-failure"
+  | "on-request"
+  | "never";
+
+export type CodexSessionInfo = {
+  sessionId: string;
+  projectPath: string;
+  model: string | null;
+  approvalMode: CodexApprovalMode | null;
   pid: number | null;
-  startedAtMs: number | null;
-  lastError: string | null;
-  listenAddr: string | null;
+  startedAtMs: number;
+  status: "queued" | "running" | "exited" | "terminated";
+  exitCode: number | null;
+  worktree: SessionWorktree | null;
+  /** Set once the session has finished. */
+  summary: SessionSummary | null;
+};
+
+/** What a finished session did, read from its `codex exec --json` output. */
+export type SessionSummary = {
+  filesTouched: string[];
+  commandsRun: { command: string; exitCode: number | null }[];
+  tokenUsage: {
+    inputTokens: number;
+    cachedInputTokens: number;
+    outputTokens: number;
+  } | null;
+  durationMs: number;
+  status: "exited" | "terminated";
+  exitCode: number | null;
+  lastMessage: string | null;
+  error: string | null;
+};
+
+export type CodexSessionDetail = {
+  session: CodexSessionInfo;
+  resourceStats: ProcessStats[];
+};
+
+export type SessionTemplate = {
+  id: string;
+  name: string;
+  prompt: string;
+  model: string | null;
+  approvalMode: CodexApprovalMode | null;
+  workingDir: string | null;
+  env: Record<string, string>;
+  createdAtMs: number;
+  updatedAtMs: number;
 };
 
-export type TailscaleStatus = {
-  installed: boolean;
-  running: boolean;
-  version: string | null;
-  dnsName: string | null;
-  hostName: string | null;
-  tailnetName: string | null;
-  ipv4: string[];
-  ipv6: string[];
-  suggestedRemoteHost: string | null;
-  message: string;
+export type SessionTemplateInput = {
+  id?: string | null;
+  name: string;
+  prompt: string;
+  model?: string | null;
+  approvalMode?: CodexApprovalMode | null;
+  workingDir?: string | null;
+  env?: Record<string, string>;
 };
 
-export type TailscaleDaemonCommandPreview = {
-  command: string;
-  daemonPath: string;
-  args: string[];
-  tokenConfigured: boolean;
+export type TagInput = {
+  id?: string | null;
+  name: string;
+  color?: string | null;
+};
+
+export type ScheduleRun = {
+  atMs: number;
+  status: "started" | "queued" | "skipped" | "failed";
+  message: string | null;
+  sessionId: string | null;
+};
+
+export type Schedule = {
+  id: string;
+  name: string;
+  cron: string;
+  templateId: string;
+  projectPath: string | null;
+  enabled: boolean;
+  nextRunAtMs: number | null;
+  runs: ScheduleRun[];
+  createdAtMs: number;
+  updatedAtMs: number;
+};
+
+export type ScheduleInput = {
+  id?: string | null;
+  name: string;
+  cron: string;
+  templateId: string;
+  projectPath?: string | null;
+  enabled?: boolean;
+};
+
+export type OverviewSection<T> = {
+  value: T | null;
+  error: CommandErrorPayload | null;
+  fetchedAtMs: number;
+};
+
+export type MonitorOverview = {
+  tailscale: OverviewSection<TailscaleStatus>;
+  daemon: OverviewSection<TcpDaemonStatus>;
+  settings: OverviewSection<AppSettings>;
 };
 
 export type CodexDoctorResult = {
@@ -425,13 +993,6 @@ export type RequestUserInputResponse = {
   answers: Record<string, RequestUserInputAnswer>;
 };
 
-export type GitFileStatus = {
-  path: string;
-  status: string;
-  additions: number;
-  deletions: number;
-};
-
 export type GitFileDiff = {
   path: string;
   diff: string;
@@ -459,35 +1020,6 @@ export type GitCommitDiff = {
   newImageMime?: string | null;
 };
 
-export type GitLogEntry = {
-  sha: string;
-  summary: string;
-  author: string;
-  timestamp: number;
-};
-
-export type GitLogResponse = {
-  total: number;
-  entries: GitLogEntry[];
-  ahead: number;
-  behind: number;
-  aheadEntries: GitLogEntry[];
-  behindEntries: GitLogEntry[];
-  upstream: string | null;
-};
-
-export type GitHubIssue = {
-  number: number;
-  title: string;
-  url: string;
-  updatedAt: string;
-};
-
-export type GitHubIssuesResponse = {
-  total: number;
-  issues: GitHubIssue[];
-};
-
 export type GitHubUser = {
   login: string;
 };
@@ -510,12 +1042,6 @@ export type GitHubPullRequestsResponse = {
   pullRequests: GitHubPullRequest[];
 };
 
-export type GitHubPullRequestDiff = {
-  path: string;
-  status: string;
-  diff: string;
-};
-
 export type GitHubPullRequestComment = {
   id: number;
   body: string;
@@ -538,38 +1064,6 @@ export type ThreadTokenUsage = {
   modelContextWindow: number | null;
 };
 
-export type LocalUsageDay = {
-  day: string;
-  inputTokens: number;
-  cachedInputTokens: number;
-  outputTokens: number;
-  totalTokens: number;
-  agentTimeMs: number;
-  agentRuns: number;
-};
-
-export type LocalUsageTotals = {
-  last7DaysTokens: number;
-  last30DaysTokens: number;
-  averageDailyTokens: number;
-  cacheHitRatePercent: number;
-  peakDay: string | null;
-  peakDayTokens: number;
-};
-
-export type LocalUsageModel = {
-  model: string;
-  tokens: number;
-  sharePercent: number;
-};
-
-export type LocalUsageSnapshot = {
-  updatedAt: number;
-  days: LocalUsageDay[];
-  totals: LocalUsageTotals;
-  topModels: LocalUsageModel[];
-};
-
 export type TurnPlanStepStatus = "pending" | "inProgress" | "completed";
 
 export type TurnPlanStep = {